@@ -7,12 +7,19 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
-use super::{MetricsSnapshot, Decision};
+use super::{MetricsSnapshot, Decision, LearningSummary};
 use super::analysis::{Analysis, Recommendation};
+use crate::util::privacy;
 
 const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01"; // Latest stable API version (new features use beta headers)
 const MAX_TOKENS: u32 = 2048;
+const NARRATOR_MAX_TOKENS: u32 = 120;
+
+const NARRATOR_SYSTEM_PROMPT: &str = "You are a lively esports caster for Orbit Royale, \
+a space battle royale where players orbit gravity wells and absorb smaller players. \
+Given a digest of the last minute of a match, reply with ONE short sentence (max 25 words) \
+of commentary for spectators. Only mention facts present in the digest. No markdown, no quotes.";
 
 /// Claude API Client for simulation analysis
 pub struct ClaudeClient {
@@ -36,26 +43,45 @@ impl ClaudeClient {
         &self,
         snapshot: &MetricsSnapshot,
         recent_decisions: &[&Decision],
+        learning: &LearningSummary,
     ) -> Result<Analysis, String> {
         if self.api_key.is_empty() {
             return Err("API key not configured".to_string());
         }
 
         let system_prompt = self.build_system_prompt();
-        let user_message = self.build_user_message(snapshot, recent_decisions)?;
+        let user_message = self.build_user_message(snapshot, recent_decisions, learning)?;
+
+        debug!("Sending analysis request to Claude API");
+        let text = self.send(system_prompt, user_message, MAX_TOKENS).await?;
+
+        // Parse the JSON response from Claude
+        self.parse_analysis_response(&text)
+    }
+
+    /// Produce one line of spectator commentary from a match digest
+    pub async fn narrate(&self, digest: &str) -> Result<String, String> {
+        if self.api_key.is_empty() {
+            return Err("API key not configured".to_string());
+        }
 
+        debug!("Sending narrator request to Claude API");
+        self.send(NARRATOR_SYSTEM_PROMPT.to_string(), digest.to_string(), NARRATOR_MAX_TOKENS)
+            .await
+    }
+
+    /// Send a single-turn request and return the first text block
+    async fn send(&self, system: String, user_message: String, max_tokens: u32) -> Result<String, String> {
         let request = ClaudeRequest {
             model: self.model.clone(),
-            max_tokens: MAX_TOKENS,
-            system: system_prompt,
+            max_tokens,
+            system,
             messages: vec![Message {
                 role: "user".to_string(),
                 content: user_message,
             }],
         };
 
-        debug!("Sending analysis request to Claude API");
-
         let response = self.client
             .post(CLAUDE_API_URL)
             .header("x-api-key", &self.api_key)
@@ -79,15 +105,13 @@ impl ClaudeClient {
             .map_err(|e| format!("Failed to parse response: {}", e))?;
 
         // Extract the text content
-        let text = claude_response.content
-            .first()
-            .and_then(|c| match c {
-                ContentBlock::Text { text } => Some(text.as_str()),
+        claude_response.content
+            .into_iter()
+            .next()
+            .map(|c| match c {
+                ContentBlock::Text { text } => text,
             })
-            .ok_or_else(|| "No text content in response".to_string())?;
-
-        // Parse the JSON response from Claude
-        self.parse_analysis_response(text)
+            .ok_or_else(|| "No text content in response".to_string())
     }
 
     /// Build the system prompt for Claude
@@ -105,6 +129,14 @@ Your job is to analyze game metrics and recommend parameter adjustments to maint
 | arena.max_wells | 5-50 | Maximum gravity wells |
 | arena.base_player_count | 1-100 | Base player count for density calculation |
 | arena.area_per_player | 50000-500000 | Target square units per player |
+| projectile.tap_cost | 0.25-4 | Mass a quick-tap shot costs per unit of projectile mass |
+| projectile.full_charge_cost | 0.25-4 | Mass a fully charged shot costs per unit of projectile mass |
+| projectile.miss_refund | 0-1 | Share of a projectile's mass returned when it expires without a hit |
+| modifier.solar_flare | 1 | Trigger a solar flare (projectiles 30% faster for 60s) |
+| modifier.dense_nebula | 1 | Trigger a dense nebula (vision radius halved for 45s) |
+| modifier.debris_surge | 1 | Trigger a debris surge (debris spawns twice as fast for 90s) |
+
+Modifiers are one-off events rather than tunables: use them sparingly to liven up stale matches.
 
 ## Performance Guidelines
 
@@ -125,8 +157,10 @@ Your job is to analyze game metrics and recommend parameter adjustments to maint
 
 1. Make small, incremental changes (max 20% per adjustment)
 2. Only recommend changes when confident (>0.7)
-3. Consider past decision outcomes when available
-4. Prioritize performance over aesthetics
+3. Consider past decision outcomes when available: the learned outcomes count how often each kind of change
+   helped over the server's whole history, and outweigh a single recent decision
+4. Outcomes are scored on tick time p95, human retention, kill rate variance and bytes sent per second together:
+   a faster tick that drives players away or makes the action bursty counts as a failure
 
 ## Response Format
 
@@ -151,11 +185,12 @@ If no changes are needed, return empty recommendations with confidence < 0.5.
 "#.to_string()
     }
 
-    /// Build the user message with current metrics and history
+    /// Build the user message with current metrics, history and learned outcomes
     fn build_user_message(
         &self,
         snapshot: &MetricsSnapshot,
         recent_decisions: &[&Decision],
+        learning: &LearningSummary,
     ) -> Result<String, String> {
         let metrics_json = serde_json::to_string_pretty(snapshot)
             .map_err(|e| format!("Failed to serialize metrics: {}", e))?;
@@ -166,8 +201,13 @@ If no changes are needed, return empty recommendations with confidence < 0.5.
             let mut summary = String::new();
             for decision in recent_decisions {
                 let outcome_str = match &decision.outcome {
-                    Some(o) if o.success => format!("SUCCESS ({}us)", o.performance_delta_us),
-                    Some(o) => format!("FAILED ({}us)", o.performance_delta_us),
+                    Some(o) => {
+                        let verdict = if o.success { "SUCCESS" } else { "FAILED" };
+                        match &o.score {
+                            Some(s) => format!("{} (score {:+.2}, {}us)", verdict, s.total, o.performance_delta_us),
+                            None => format!("{} ({}us)", verdict, o.performance_delta_us),
+                        }
+                    }
                     None => "PENDING".to_string(),
                 };
 
@@ -182,10 +222,18 @@ If no changes are needed, return empty recommendations with confidence < 0.5.
             summary
         };
 
+        let learned = if learning.is_empty() {
+            "No evaluated decisions yet".to_string()
+        } else {
+            learning.render()
+        };
+
+        // Decision text originates from earlier model output; scrub before re-sending
         Ok(format!(
-            "## Current Metrics\n\n```json\n{}\n```\n\n## Recent Decisions\n\n{}",
+            "## Current Metrics\n\n```json\n{}\n```\n\n## Learned Outcomes\n\n{}\n\n## Recent Decisions\n\n{}",
             metrics_json,
-            history_summary
+            learned.trim_end(),
+            privacy::scrub_ai_text(&history_summary)
         ))
     }
 
@@ -308,4 +356,32 @@ Let me know if you need more details."#;
         let analysis = result.unwrap();
         assert_eq!(analysis.recommendations.len(), 1);
     }
+
+    #[test]
+    fn test_user_message_includes_learned_outcomes() {
+        let client = ClaudeClient::new("test".to_string(), "test".to_string());
+        let snapshot = MetricsSnapshot {
+            timestamp: chrono::Utc::now(),
+            tick_time_p95_us: 22000,
+            tick_time_max_us: 30000,
+            total_players: 80,
+            human_players: 10,
+            bot_players: 70,
+            alive_players: 60,
+            projectile_count: 40,
+            debris_count: 150,
+            gravity_well_count: 12,
+            arena_scale: 1.5,
+            arena_radius: 1800.0,
+            performance_status: "warning".to_string(),
+            budget_percent: 85,
+            kills_per_min: 12.0,
+            kill_rate_variance: 0.4,
+            send_bytes_per_sec: 250_000,
+        };
+
+        let message = client.build_user_message(&snapshot, &[], &LearningSummary::default()).unwrap();
+        assert!(message.contains("## Learned Outcomes\n\nNo evaluated decisions yet"));
+        assert!(message.contains("No recent decisions"));
+    }
 }
@@ -1,7 +1,8 @@
 //! Decision History Storage
 //!
 //! Stores and persists AI decision history for learning and auditing.
-//! Decisions are stored in a JSON file for persistence across restarts.
+//! Decisions are stored in a JSON file for persistence across restarts,
+//! together with the learned outcome summary (see `learning`).
 
 use std::fs;
 use std::path::Path;
@@ -9,7 +10,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+use super::learning::LearningSummary;
+use super::scoring::OutcomeScore;
 use super::MetricsSnapshot;
+use crate::util::privacy;
 
 /// A recorded AI decision with full context
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,8 +58,22 @@ pub struct Outcome {
     pub performance_delta_us: i64,
     /// Change in player count
     pub player_delta: i32,
-    /// Whether the decision was successful
+    /// Whether the decision was successful (score of at least 0)
     pub success: bool,
+    /// Per-objective score (absent in history saved before scoring)
+    #[serde(default)]
+    pub score: Option<OutcomeScore>,
+}
+
+impl Decision {
+    /// Scrub IPs/emails from free-form text fields (respects PRIVACY_SCRUB_AI)
+    pub fn scrub_pii(&mut self) {
+        self.analysis = privacy::scrub_ai_text(&self.analysis);
+        self.reasoning = privacy::scrub_ai_text(&self.reasoning);
+        for action in &mut self.actions {
+            action.reason = privacy::scrub_ai_text(&action.reason);
+        }
+    }
 }
 
 /// Container for decision history with persistence
@@ -67,6 +85,9 @@ pub struct DecisionHistory {
     decisions: Vec<Decision>,
     /// Aggregate statistics
     statistics: Statistics,
+    /// Outcomes per parameter, kept when old decisions are trimmed
+    #[serde(default)]
+    learning: LearningSummary,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -83,6 +104,7 @@ impl DecisionHistory {
             version: 1,
             decisions: Vec::new(),
             statistics: Statistics::default(),
+            learning: LearningSummary::default(),
         }
     }
 
@@ -133,7 +155,13 @@ impl DecisionHistory {
             }
         }
 
-        let contents = serde_json::to_string_pretty(self)
+        // Free-form text is scrubbed of PII before it touches disk
+        let mut persisted = self.clone();
+        for decision in &mut persisted.decisions {
+            decision.scrub_pii();
+        }
+
+        let contents = serde_json::to_string_pretty(&persisted)
             .map_err(|e| format!("Failed to serialize history: {}", e))?;
 
         fs::write(path, contents)
@@ -154,11 +182,6 @@ impl DecisionHistory {
         self.decisions.get(index)
     }
 
-    /// Get a mutable decision by index
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut Decision> {
-        self.decisions.get_mut(index)
-    }
-
     /// Get the last decision
     pub fn last(&self) -> Option<&Decision> {
         self.decisions.last()
@@ -186,7 +209,7 @@ impl DecisionHistory {
         }
     }
 
-    /// Update an outcome and recalculate statistics
+    /// Update an outcome and recalculate statistics and the learned summary
     pub fn update_outcome(&mut self, index: usize, outcome: Outcome) {
         if let Some(decision) = self.decisions.get_mut(index) {
             if outcome.success {
@@ -195,9 +218,15 @@ impl DecisionHistory {
                 self.statistics.failed += 1;
             }
             decision.outcome = Some(outcome);
+            self.learning.record(decision);
         }
     }
 
+    /// What past outcomes taught, over every decision ever evaluated
+    pub fn learning(&self) -> &LearningSummary {
+        &self.learning
+    }
+
     /// Get success rate (successful, total with outcomes)
     pub fn success_rate(&self) -> (usize, usize) {
         let with_outcomes: Vec<_> = self.decisions.iter()
@@ -248,6 +277,9 @@ mod tests {
                 arena_radius: 2000.0,
                 performance_status: "good".to_string(),
                 budget_percent: 50,
+                kills_per_min: 0.0,
+                kill_rate_variance: 0.0,
+                send_bytes_per_sec: 0,
             },
             analysis: "Test analysis".to_string(),
             reasoning: "Test reasoning".to_string(),
@@ -304,6 +336,7 @@ mod tests {
             performance_delta_us: -1000,
             player_delta: 0,
             success: true,
+            score: None,
         });
 
         let mut d2 = create_test_decision("test_2");
@@ -312,6 +345,7 @@ mod tests {
             performance_delta_us: 5000,
             player_delta: -10,
             success: false,
+            score: None,
         });
 
         history.add(d1);
@@ -323,6 +357,33 @@ mod tests {
         assert_eq!(total, 2); // Only counts those with outcomes
     }
 
+    #[test]
+    fn test_learning_outlives_trimmed_decisions() {
+        let mut history = DecisionHistory::new();
+        history.add(create_test_decision("test_1"));
+        history.update_outcome(
+            0,
+            Outcome {
+                evaluated_at: Utc::now(),
+                performance_delta_us: -500,
+                player_delta: 0,
+                success: true,
+                score: None,
+            },
+        );
+        history.remove_oldest();
+
+        assert!(history.is_empty());
+        let effects = history.learning().effects();
+        assert_eq!((effects[0].parameter.as_str(), effects[0].successes), ("arena.max_wells", 1));
+
+        // Files written before the summary existed still load
+        let mut old_format = serde_json::to_value(&history).unwrap();
+        old_format.as_object_mut().unwrap().remove("learning");
+        let loaded: DecisionHistory = serde_json::from_value(old_format).unwrap();
+        assert!(loaded.learning().is_empty());
+    }
+
     #[test]
     fn test_remove_oldest() {
         let mut history = DecisionHistory::new();
@@ -336,4 +397,25 @@ mod tests {
         assert_eq!(history.len(), 2);
         assert_eq!(history.get(0).unwrap().id, "test_1");
     }
+
+    #[test]
+    fn test_save_scrubs_pii() {
+        let mut history = DecisionHistory::new();
+        let mut decision = create_test_decision("test_pii");
+        decision.reasoning = "Spike from 198.51.100.7 reported by ops@example.com".to_string();
+        history.add(decision);
+
+        let path = std::env::temp_dir().join(format!("orbit_history_pii_{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        history.save(path_str).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(!contents.contains("198.51.100.7"));
+        assert!(!contents.contains("ops@example.com"));
+        assert!(contents.contains(privacy::REDACTED));
+
+        // In-memory history is left intact
+        assert!(history.last().unwrap().reasoning.contains("198.51.100.7"));
+    }
 }
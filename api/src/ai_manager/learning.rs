@@ -0,0 +1,205 @@
+//! Learned Outcome Summary
+//!
+//! Aggregates evaluated decisions per parameter, direction of the change and
+//! the server's performance status when it was made, so the tuner can see
+//! what tended to work ("raising arena.area_per_player helped 7/9 times under
+//! warning performance") rather than only the last few raw decisions. The
+//! summary is stored with the decision history and keeps counting after old
+//! decisions are trimmed, so it covers weeks of tuning.
+
+use serde::{Deserialize, Serialize};
+
+use super::history::Decision;
+
+/// Most lines the rendered summary holds (the most tried effects first)
+const MAX_LINES: usize = 20;
+
+/// Which way a change went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Raised,
+    Lowered,
+    /// A modifier was started
+    Triggered,
+}
+
+impl Direction {
+    fn of(parameter: &str, old_value: f32, new_value: f32) -> Option<Self> {
+        if parameter.starts_with("modifier.") {
+            Some(Direction::Triggered)
+        } else if new_value > old_value {
+            Some(Direction::Raised)
+        } else if new_value < old_value {
+            Some(Direction::Lowered)
+        } else {
+            None
+        }
+    }
+
+    fn verb(&self) -> &'static str {
+        match self {
+            Direction::Raised => "raising",
+            Direction::Lowered => "lowering",
+            Direction::Triggered => "triggering",
+        }
+    }
+}
+
+/// Outcomes of one kind of change under one performance status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedEffect {
+    pub parameter: String,
+    pub direction: Direction,
+    /// Performance status when the change was made
+    pub load: String,
+    pub attempts: u32,
+    pub successes: u32,
+    /// Sum of the tick time p95 changes that followed (negative = faster)
+    pub total_performance_delta_us: i64,
+}
+
+/// Per-parameter outcome counts over every evaluated decision
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LearningSummary {
+    effects: Vec<LearnedEffect>,
+}
+
+impl LearningSummary {
+    /// Count an evaluated decision's actions (decisions without an outcome are skipped)
+    pub fn record(&mut self, decision: &Decision) {
+        let Some(outcome) = &decision.outcome else {
+            return;
+        };
+        let load = &decision.metrics_before.performance_status;
+        for action in &decision.actions {
+            let Some(direction) = Direction::of(&action.parameter, action.old_value, action.new_value) else {
+                continue;
+            };
+            let position = self
+                .effects
+                .iter()
+                .position(|e| e.parameter == action.parameter && e.direction == direction && &e.load == load);
+            let effect = match position {
+                Some(index) => &mut self.effects[index],
+                None => {
+                    self.effects.push(LearnedEffect {
+                        parameter: action.parameter.clone(),
+                        direction,
+                        load: load.clone(),
+                        attempts: 0,
+                        successes: 0,
+                        total_performance_delta_us: 0,
+                    });
+                    self.effects.last_mut().expect("just pushed")
+                }
+            };
+            effect.attempts += 1;
+            effect.successes += u32::from(outcome.success);
+            effect.total_performance_delta_us += outcome.performance_delta_us;
+        }
+    }
+
+    #[cfg(test)]
+    pub fn effects(&self) -> &[LearnedEffect] {
+        &self.effects
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// One line per effect for the prompt, the most tried first
+    pub fn render(&self) -> String {
+        let mut effects: Vec<&LearnedEffect> = self.effects.iter().collect();
+        effects.sort_by(|a, b| b.attempts.cmp(&a.attempts).then(a.parameter.cmp(&b.parameter)));
+        effects
+            .iter()
+            .take(MAX_LINES)
+            .map(|e| {
+                format!(
+                    "- {} {} helped {}/{} times under {} performance (avg tick p95 {:+}us)\n",
+                    e.direction.verb(),
+                    e.parameter,
+                    e.successes,
+                    e.attempts,
+                    e.load,
+                    e.total_performance_delta_us / i64::from(e.attempts.max(1))
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_manager::{Action, MetricsSnapshot, Outcome};
+    use chrono::Utc;
+
+    fn decision(status: &str, parameter: &str, old_value: f32, new_value: f32, delta_us: i64) -> Decision {
+        Decision {
+            id: "test".to_string(),
+            timestamp: Utc::now(),
+            metrics_before: MetricsSnapshot {
+                timestamp: Utc::now(),
+                tick_time_p95_us: 20000,
+                tick_time_max_us: 25000,
+                total_players: 50,
+                human_players: 5,
+                bot_players: 45,
+                alive_players: 40,
+                projectile_count: 10,
+                debris_count: 100,
+                gravity_well_count: 8,
+                arena_scale: 1.0,
+                arena_radius: 1500.0,
+                performance_status: status.to_string(),
+                budget_percent: 70,
+                kills_per_min: 0.0,
+                kill_rate_variance: 0.0,
+                send_bytes_per_sec: 0,
+            },
+            analysis: String::new(),
+            reasoning: String::new(),
+            actions: vec![Action { parameter: parameter.to_string(), old_value, new_value, reason: String::new() }],
+            confidence: 0.8,
+            outcome: Some(Outcome {
+                evaluated_at: Utc::now(),
+                performance_delta_us: delta_us,
+                player_delta: 0,
+                success: delta_us <= 0,
+                score: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_outcomes_are_grouped_by_parameter_direction_and_load() {
+        let mut summary = LearningSummary::default();
+        summary.record(&decision("warning", "arena.area_per_player", 100000.0, 120000.0, -1000));
+        summary.record(&decision("warning", "arena.area_per_player", 120000.0, 140000.0, -3000));
+        summary.record(&decision("warning", "arena.area_per_player", 140000.0, 160000.0, 500));
+        summary.record(&decision("good", "arena.area_per_player", 160000.0, 140000.0, 0));
+        summary.record(&decision("good", "modifier.solar_flare", 0.0, 1.0, 200));
+        // Unchanged values and pending decisions teach nothing
+        summary.record(&decision("good", "arena.max_wells", 20.0, 20.0, 0));
+        let mut pending = decision("good", "arena.max_wells", 20.0, 16.0, 0);
+        pending.outcome = None;
+        summary.record(&pending);
+
+        assert_eq!(summary.effects().len(), 3);
+        let rendered = summary.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(
+            lines[0],
+            "- raising arena.area_per_player helped 2/3 times under warning performance (avg tick p95 -1166us)"
+        );
+        assert!(lines.contains(
+            &"- triggering modifier.solar_flare helped 0/1 times under good performance (avg tick p95 +200us)"
+        ));
+        assert!(lines.contains(
+            &"- lowering arena.area_per_player helped 1/1 times under good performance (avg tick p95 +0us)"
+        ));
+    }
+}
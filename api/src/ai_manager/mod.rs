@@ -8,8 +8,11 @@
 //! - Real-time metrics monitoring and analysis
 //! - Claude API integration for intelligent decision making
 //! - Decision history with outcome tracking
+//! - Learned outcome summary per parameter, fed back into every prompt
+//! - Outcomes scored on tick time, human retention, kill rate variance and bandwidth
 //! - Configurable evaluation intervals and confidence thresholds
 //! - Full decision logging with explanations
+//! - Optional spectator narrator (throttled, cached match commentary)
 //!
 //! # Architecture
 //!
@@ -33,9 +36,15 @@
 mod client;
 mod history;
 mod analysis;
+mod learning;
+mod narrator;
+mod scoring;
 
 pub use client::ClaudeClient;
+pub use narrator::{EventAggregator, Narrator};
 pub use history::{Decision, DecisionHistory, Action, Outcome};
+pub use learning::LearningSummary;
+pub use scoring::OutcomeScore;
 pub use analysis::{Analysis, Recommendation};
 
 use std::sync::Arc;
@@ -44,8 +53,10 @@ use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use tracing::{info, warn, error, debug};
 
-use crate::config::{AIManagerConfig, ArenaScalingConfig};
+use crate::config::{AIManagerConfig, ArenaScalingConfig, ProjectileEconomyConfig};
+use crate::game::modifiers::{GlobalModifier, ModifierRequests};
 use crate::metrics::{Metrics, AIManagerMetrics, AIDecisionSummary, AIActionSummary, AIOutcomeSummary};
+use crate::server::ShutdownToken;
 
 /// Snapshot of game metrics for AI analysis
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -64,6 +75,15 @@ pub struct MetricsSnapshot {
     pub arena_radius: f32,
     pub performance_status: String,
     pub budget_percent: u64,
+    /// Kills in the last minute
+    #[serde(default)]
+    pub kills_per_min: f32,
+    /// Variance of kills per second over the last minute
+    #[serde(default)]
+    pub kill_rate_variance: f32,
+    /// Bytes sent per second over the last minute
+    #[serde(default)]
+    pub send_bytes_per_sec: u64,
 }
 
 impl MetricsSnapshot {
@@ -92,6 +112,9 @@ impl MetricsSnapshot {
                 _ => "catastrophic".to_string(),
             },
             budget_percent: metrics.budget_usage_percent.load(Ordering::Relaxed),
+            kills_per_min: metrics.kill_rate.read().mean() * 60.0,
+            kill_rate_variance: metrics.kill_rate.read().variance(),
+            send_bytes_per_sec: metrics.send_rate.read().mean() as u64,
         }
     }
 }
@@ -164,11 +187,14 @@ impl AIManager {
         error_lower.contains("billing")
     }
 
-    /// Run the AI manager main loop
+    /// Run the AI manager main loop until `shutdown` is triggered, then save the history
     pub async fn run(
         mut self,
         metrics: Arc<Metrics>,
         arena_config: Arc<RwLock<ArenaScalingConfig>>,
+        modifier_requests: ModifierRequests,
+        projectile_economy: Arc<RwLock<ProjectileEconomyConfig>>,
+        shutdown: ShutdownToken,
     ) {
         let interval = Duration::from_secs(self.config.eval_interval_minutes as u64 * 60);
         let mut interval_timer = tokio::time::interval(interval);
@@ -179,9 +205,14 @@ impl AIManager {
         metrics.ai_enabled.store(1, std::sync::atomic::Ordering::Relaxed);
 
         const MAX_CONSECUTIVE_ERRORS: u32 = 5;
+        let stopped = shutdown.triggered();
+        tokio::pin!(stopped);
 
         loop {
-            interval_timer.tick().await;
+            tokio::select! {
+                _ = interval_timer.tick() => {}
+                _ = &mut stopped => break,
+            }
 
             // Skip if disabled due to fatal error
             if self.disabled_due_to_error {
@@ -208,6 +239,12 @@ impl AIManager {
             // 2. Evaluate any pending decisions (made >60s ago)
             self.evaluate_pending_decisions(&snapshot, &metrics);
 
+            // Parameters are frozen after an incident: changes would be undone
+            if metrics.safe_mode.load(std::sync::atomic::Ordering::Relaxed) {
+                info!("AI Manager: parameters frozen after an incident, skipping analysis");
+                continue;
+            }
+
             // 3. Ask Claude for analysis
             match self.analyze_simulation(&snapshot).await {
                 Ok(analysis) => {
@@ -223,6 +260,8 @@ impl AIManager {
                         let actions = self.apply_recommendations(
                             &analysis,
                             &arena_config,
+                            &modifier_requests,
+                            &projectile_economy,
                         );
 
                         if !actions.is_empty() {
@@ -321,11 +360,16 @@ impl AIManager {
                 });
             }
         }
+
+        if let Err(e) = self.history.save(&self.config.history_file) {
+            error!("Failed to save AI decision history on shutdown: {}", e);
+        }
+        info!("AI Manager stopped");
     }
 
     /// Analyze current simulation state using Claude API
     async fn analyze_simulation(&self, snapshot: &MetricsSnapshot) -> Result<Analysis, String> {
-        self.client.analyze(snapshot, &self.history.recent(5)).await
+        self.client.analyze(snapshot, &self.history.recent(5), self.history.learning()).await
     }
 
     /// Apply recommended parameter changes
@@ -333,10 +377,57 @@ impl AIManager {
         &self,
         analysis: &Analysis,
         arena_config: &Arc<RwLock<ArenaScalingConfig>>,
+        modifier_requests: &ModifierRequests,
+        projectile_economy: &Arc<RwLock<ProjectileEconomyConfig>>,
     ) -> Vec<Action> {
         let mut actions = Vec::new();
 
         for rec in &analysis.recommendations {
+            // Global modifiers are triggered rather than tuned: a positive value starts one
+            if let Some(name) = rec.parameter.strip_prefix("modifier.") {
+                match GlobalModifier::from_name(name) {
+                    Some(modifier) if rec.value > 0.0 => {
+                        info!("AI: Triggering modifier {} (reason: {})", name, rec.reason);
+                        modifier_requests.lock().push(modifier);
+                        actions.push(Action {
+                            parameter: rec.parameter.clone(),
+                            old_value: 0.0,
+                            new_value: 1.0,
+                            reason: rec.reason.clone(),
+                        });
+                    }
+                    Some(_) => {}
+                    None => warn!("AI: Unknown modifier '{}', skipping", name),
+                }
+                continue;
+            }
+
+            // The projectile economy is tuned in its own shared config
+            if rec.parameter.starts_with("projectile.") {
+                let mut economy = projectile_economy.write();
+                let Some(old) = self.get_projectile_value(&economy, &rec.parameter) else {
+                    warn!("AI: Unknown parameter '{}', skipping", rec.parameter);
+                    continue;
+                };
+                // Safety: same 20% limit as arena tuning, but a refund can start from zero
+                let max_change = (old.abs() * 0.2).max(0.05);
+                let clamped_new = old + (rec.value - old).clamp(-max_change, max_change);
+
+                if self.set_projectile_value(&mut economy, &rec.parameter, clamped_new) {
+                    info!(
+                        "AI: Applied {} = {} -> {} (requested: {}, reason: {})",
+                        rec.parameter, old, clamped_new, rec.value, rec.reason
+                    );
+                    actions.push(Action {
+                        parameter: rec.parameter.clone(),
+                        old_value: old,
+                        new_value: clamped_new,
+                        reason: rec.reason.clone(),
+                    });
+                }
+                continue;
+            }
+
             // Validate parameter is known and value is in range
             if !self.is_valid_parameter(&rec.parameter) {
                 warn!("AI: Unknown parameter '{}', skipping", rec.parameter);
@@ -398,17 +489,21 @@ impl AIManager {
                     let player_before = decision.metrics_before.total_players as i32;
                     let player_after = current.total_players as i32;
 
+                    let score = OutcomeScore::compute(&decision.metrics_before, current, &self.config.outcome_weights);
+
                     let outcome = Outcome {
                         evaluated_at: now,
                         performance_delta_us: perf_after - perf_before,
                         player_delta: player_after - player_before,
-                        success: perf_after <= perf_before, // Lower tick time = better
+                        success: score.is_success(),
+                        score: Some(score),
                     };
 
                     info!(
-                        "AI Outcome: {} - {} (perf: {}us, players: {})",
+                        "AI Outcome: {} - {} (score: {:+.2}, perf: {}us, players: {})",
                         decision.id,
                         if outcome.success { "SUCCESS" } else { "FAILED" },
+                        score.total,
                         outcome.performance_delta_us,
                         outcome.player_delta
                     );
@@ -417,9 +512,7 @@ impl AIManager {
                         successful += 1;
                     }
 
-                    if let Some(d) = self.history.get_mut(idx) {
-                        d.outcome = Some(outcome);
-                    }
+                    self.history.update_outcome(idx, outcome);
 
                     evaluated.push(idx);
                 }
@@ -504,6 +597,35 @@ impl AIManager {
         }
     }
 
+    /// Get current value of a projectile economy parameter
+    fn get_projectile_value(&self, economy: &ProjectileEconomyConfig, param: &str) -> Option<f32> {
+        match param {
+            "projectile.tap_cost" => Some(economy.tap_cost),
+            "projectile.full_charge_cost" => Some(economy.full_charge_cost),
+            "projectile.miss_refund" => Some(economy.miss_refund),
+            _ => None,
+        }
+    }
+
+    /// Set a projectile economy parameter
+    fn set_projectile_value(&self, economy: &mut ProjectileEconomyConfig, param: &str, value: f32) -> bool {
+        match param {
+            "projectile.tap_cost" => {
+                economy.tap_cost = value.clamp(0.25, 4.0);
+                true
+            }
+            "projectile.full_charge_cost" => {
+                economy.full_charge_cost = value.clamp(0.25, 4.0);
+                true
+            }
+            "projectile.miss_refund" => {
+                economy.miss_refund = value.clamp(0.0, 1.0);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Generate a unique decision ID
     fn generate_decision_id(&self) -> String {
         format!("dec_{}_{:03}",
@@ -546,6 +668,7 @@ impl AIManager {
                         success: o.success,
                         performance_delta_us: o.performance_delta_us,
                         player_delta: o.player_delta,
+                        score: o.score.map(|s| s.total),
                     }),
                 }
             }).collect(),
@@ -575,4 +698,32 @@ mod tests {
         let id = manager.generate_decision_id();
         assert!(id.starts_with("dec_"));
     }
+
+    #[test]
+    fn test_projectile_economy_recommendations_are_limited() {
+        let manager = AIManager::new(AIManagerConfig::default());
+        let arena_config = Arc::new(RwLock::new(ArenaScalingConfig::default()));
+        let economy = Arc::new(RwLock::new(ProjectileEconomyConfig::default()));
+        let recommend = |parameter: &str, value: f32| Recommendation {
+            parameter: parameter.to_string(),
+            value,
+            reason: "test".to_string(),
+        };
+        let analysis = Analysis {
+            summary: "Too passive".to_string(),
+            reasoning: "Few shots fired".to_string(),
+            recommendations: vec![
+                recommend("projectile.tap_cost", 0.1),
+                recommend("projectile.miss_refund", 0.5),
+                recommend("projectile.speed", 2.0),
+            ],
+            confidence: 0.9,
+        };
+
+        let actions = manager.apply_recommendations(&analysis, &arena_config, &ModifierRequests::default(), &economy);
+        assert_eq!(actions.len(), 2);
+        let economy = economy.read();
+        assert!((economy.tap_cost - 0.8).abs() < 0.001);
+        assert!((economy.miss_refund - 0.05).abs() < 0.001);
+    }
 }
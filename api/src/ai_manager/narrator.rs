@@ -0,0 +1,358 @@
+//! Spectator Narrator
+//!
+//! Aggregates game loop events into a compact per-window digest and turns it
+//! into one line of commentary via Claude. Cost is bounded by an hourly call
+//! budget and by caching: windows whose coarse facts match a previous window
+//! reuse that line instead of calling the API again.
+
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use hashbrown::HashMap;
+use tracing::{debug, warn};
+
+use super::ClaudeClient;
+use crate::game::game_loop::GameLoopEvent;
+use crate::game::state::{GameState, PlayerId};
+use crate::util::privacy;
+
+/// Longest commentary line sent to clients (characters)
+const MAX_COMMENTARY_CHARS: usize = 200;
+
+/// Number of top killers included in a digest
+const TOP_KILLERS: usize = 3;
+
+/// Cached lines kept for reuse
+const CACHE_CAPACITY: usize = 32;
+
+/// Leader within this fraction of the arena radius is "in the center"
+const CENTER_FRACTION: f32 = 0.25;
+
+/// Current mass leader
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderInfo {
+    pub name: String,
+    pub mass: f32,
+    pub region: &'static str,
+    pub is_bot: bool,
+}
+
+/// Facts about one commentary window
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchDigest {
+    pub window_secs: u32,
+    /// (name, kills in window), most kills first
+    pub top_killers: Vec<(String, u32)>,
+    pub total_kills: u32,
+    pub leader: Option<LeaderInfo>,
+    pub humans_alive: u32,
+    pub bots_alive: u32,
+    pub well_explosions: u32,
+    pub zone_collapses: u32,
+}
+
+impl MatchDigest {
+    /// Nothing worth commenting on
+    pub fn is_quiet(&self) -> bool {
+        self.total_kills == 0 && self.well_explosions == 0 && self.zone_collapses == 0
+    }
+
+    /// Prompt text for the narrator
+    pub fn to_prompt(&self) -> String {
+        let mut out = format!(
+            "Window: last {}s\nAlive: {} humans, {} bots\nKills: {}\n",
+            self.window_secs, self.humans_alive, self.bots_alive, self.total_kills
+        );
+        for (name, kills) in &self.top_killers {
+            out.push_str(&format!("- {} scored {} kill(s)\n", name, kills));
+        }
+        if let Some(leader) = &self.leader {
+            out.push_str(&format!(
+                "Mass leader: {} ({}, mass {:.0}) in the {}\n",
+                leader.name,
+                if leader.is_bot { "bot" } else { "human" },
+                leader.mass,
+                leader.region
+            ));
+        }
+        if self.well_explosions > 0 {
+            out.push_str(&format!("Gravity well explosions: {}\n", self.well_explosions));
+        }
+        if self.zone_collapses > 0 {
+            out.push_str(&format!("Arena collapses: {}\n", self.zone_collapses));
+        }
+        out
+    }
+
+    /// Coarse fingerprint; windows with the same key reuse cached commentary
+    pub fn cache_key(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.top_killers.first().map(|(name, _)| name).hash(&mut hasher);
+        (self.total_kills / 3).hash(&mut hasher);
+        self.leader.as_ref().map(|l| (&l.name, l.region)).hash(&mut hasher);
+        (self.well_explosions > 0).hash(&mut hasher);
+        (self.zone_collapses > 0).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Accumulates events between commentary windows
+#[derive(Debug, Default)]
+pub struct EventAggregator {
+    kills: HashMap<PlayerId, u32>,
+    total_kills: u32,
+    well_explosions: u32,
+    zone_collapses: u32,
+}
+
+impl EventAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, event: &GameLoopEvent) {
+        match event {
+            GameLoopEvent::PlayerKilled { killer_id, .. } => {
+                *self.kills.entry(*killer_id).or_insert(0) += 1;
+                self.total_kills += 1;
+            }
+            GameLoopEvent::GravityWaveExplosion { .. } => self.well_explosions += 1,
+            GameLoopEvent::ZoneCollapse { .. } => self.zone_collapses += 1,
+            _ => {}
+        }
+    }
+
+    /// Build a digest for the window and reset the counters
+    pub fn take_digest(&mut self, state: &GameState, window_secs: u32) -> MatchDigest {
+        let mut killers: Vec<(PlayerId, u32)> = self.kills.drain().collect();
+        killers.sort_by_key(|&(_, kills)| std::cmp::Reverse(kills));
+        let top_killers = killers
+            .into_iter()
+            .filter_map(|(id, kills)| state.get_player(id).map(|p| (privacy::name(&p.name), kills)))
+            .take(TOP_KILLERS)
+            .collect();
+
+        let alive = state.players.values().filter(|p| p.alive);
+        let (mut humans_alive, mut bots_alive) = (0, 0);
+        let mut leader: Option<&crate::game::state::Player> = None;
+        for p in alive {
+            if p.is_bot {
+                bots_alive += 1;
+            } else {
+                humans_alive += 1;
+            }
+            if leader.map_or(true, |l| p.mass > l.mass) {
+                leader = Some(p);
+            }
+        }
+        let arena_radius = state.arena.escape_radius.max(1.0);
+
+        let digest = MatchDigest {
+            window_secs,
+            top_killers,
+            total_kills: self.total_kills,
+            leader: leader.map(|p| LeaderInfo {
+                name: privacy::name(&p.name),
+                mass: p.mass,
+                region: region_name(p.position.x, p.position.y, arena_radius),
+                is_bot: p.is_bot,
+            }),
+            humans_alive,
+            bots_alive,
+            well_explosions: self.well_explosions,
+            zone_collapses: self.zone_collapses,
+        };
+
+        self.total_kills = 0;
+        self.well_explosions = 0;
+        self.zone_collapses = 0;
+        digest
+    }
+}
+
+/// Compass region of a position (screen space: negative y is north)
+fn region_name(x: f32, y: f32, arena_radius: f32) -> &'static str {
+    if (x * x + y * y).sqrt() < arena_radius * CENTER_FRACTION {
+        return "center";
+    }
+    if y.abs() >= x.abs() {
+        if y < 0.0 { "north" } else { "south" }
+    } else if x > 0.0 {
+        "east"
+    } else {
+        "west"
+    }
+}
+
+/// Single-line, length-capped, PII-scrubbed commentary
+pub fn sanitize_commentary(text: &str) -> Option<String> {
+    let line = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string();
+    if line.is_empty() {
+        return None;
+    }
+    let scrubbed = privacy::scrub_text(&line);
+    Some(scrubbed.chars().take(MAX_COMMENTARY_CHARS).collect())
+}
+
+/// Throttled, cached commentary generator
+pub struct Narrator {
+    client: ClaudeClient,
+    max_calls_per_hour: u32,
+    calls: VecDeque<Instant>,
+    cache: VecDeque<(u64, String)>,
+}
+
+impl Narrator {
+    pub fn new(client: ClaudeClient, max_calls_per_hour: u32) -> Self {
+        Self {
+            client,
+            max_calls_per_hour,
+            calls: VecDeque::new(),
+            cache: VecDeque::new(),
+        }
+    }
+
+    fn cached(&self, key: u64) -> Option<String> {
+        self.cache.iter().find(|(k, _)| *k == key).map(|(_, text)| text.clone())
+    }
+
+    fn remember(&mut self, key: u64, text: String) {
+        if self.cache.len() >= CACHE_CAPACITY {
+            self.cache.pop_front();
+        }
+        self.cache.push_back((key, text));
+    }
+
+    /// Reserve an API call against the hourly budget
+    fn try_reserve_call(&mut self, now: Instant) -> bool {
+        let hour = Duration::from_secs(3600);
+        while self.calls.front().is_some_and(|&t| now.duration_since(t) >= hour) {
+            self.calls.pop_front();
+        }
+        if self.calls.len() >= self.max_calls_per_hour as usize {
+            return false;
+        }
+        self.calls.push_back(now);
+        true
+    }
+
+    /// Commentary for a digest (cache hit, fresh API call, or None)
+    pub async fn commentary(&mut self, digest: &MatchDigest) -> Option<String> {
+        if digest.is_quiet() {
+            return None;
+        }
+        let key = digest.cache_key();
+        if let Some(text) = self.cached(key) {
+            debug!("Narrator: reusing cached commentary");
+            return Some(text);
+        }
+        if !self.try_reserve_call(Instant::now()) {
+            debug!("Narrator: hourly call budget exhausted");
+            return None;
+        }
+
+        match self.client.narrate(&digest.to_prompt()).await {
+            Ok(text) => {
+                let line = sanitize_commentary(&text)?;
+                self.remember(key, line.clone());
+                Some(line)
+            }
+            Err(e) => {
+                warn!("Narrator request failed: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Player;
+    use crate::util::vec2::Vec2;
+
+    fn add_player(state: &mut GameState, name: &str, mass: f32, position: Vec2) -> PlayerId {
+        let player = Player {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            mass,
+            position,
+            alive: true,
+            ..Default::default()
+        };
+        let id = player.id;
+        state.add_player(player);
+        id
+    }
+
+    #[test]
+    fn test_aggregator_builds_digest_and_resets() {
+        let mut state = GameState::new();
+        let alice = add_player(&mut state, "Alice", 400.0, Vec2::new(0.0, -2000.0));
+        let bob = add_player(&mut state, "Bob", 100.0, Vec2::ZERO);
+
+        let mut agg = EventAggregator::new();
+        for _ in 0..2 {
+            agg.record(&GameLoopEvent::PlayerKilled { killer_id: alice, victim_id: bob });
+        }
+        agg.record(&GameLoopEvent::PlayerKilled { killer_id: bob, victim_id: alice });
+        agg.record(&GameLoopEvent::Tick { tick: 1 });
+
+        let digest = agg.take_digest(&state, 60);
+        assert_eq!(digest.total_kills, 3);
+        assert_eq!(digest.top_killers[0], ("Alice".to_string(), 2));
+        let leader = digest.leader.as_ref().unwrap();
+        assert_eq!(leader.name, "Alice");
+        assert_eq!(leader.region, "north");
+        assert!(digest.to_prompt().contains("Alice scored 2 kill(s)"));
+
+        let next = agg.take_digest(&state, 60);
+        assert!(next.is_quiet());
+    }
+
+    #[test]
+    fn test_cache_key_ignores_small_changes() {
+        let base = MatchDigest {
+            total_kills: 4,
+            top_killers: vec![("Alice".to_string(), 3)],
+            ..Default::default()
+        };
+        let similar = MatchDigest { total_kills: 5, bots_alive: 12, ..base.clone() };
+        let different = MatchDigest { top_killers: vec![("Bob".to_string(), 3)], ..base.clone() };
+
+        assert_eq!(base.cache_key(), similar.cache_key());
+        assert_ne!(base.cache_key(), different.cache_key());
+    }
+
+    #[test]
+    fn test_sanitize_commentary() {
+        assert_eq!(
+            sanitize_commentary("\"Alice is\n  unstoppable!\"").as_deref(),
+            Some("Alice is unstoppable!")
+        );
+        assert_eq!(sanitize_commentary("   "), None);
+        let long = "word ".repeat(100);
+        assert_eq!(sanitize_commentary(&long).unwrap().chars().count(), MAX_COMMENTARY_CHARS);
+    }
+
+    #[tokio::test]
+    async fn test_cached_commentary_skips_api_and_budget() {
+        let mut narrator = Narrator::new(ClaudeClient::new(String::new(), "test".to_string()), 1);
+        let digest = MatchDigest { total_kills: 1, ..Default::default() };
+        narrator.remember(digest.cache_key(), "What a play!".to_string());
+
+        assert_eq!(narrator.commentary(&digest).await.as_deref(), Some("What a play!"));
+        assert!(narrator.calls.is_empty());
+
+        // Budget of 1: second reservation in the same hour is refused
+        let now = Instant::now();
+        assert!(narrator.try_reserve_call(now));
+        assert!(!narrator.try_reserve_call(now));
+    }
+}
@@ -0,0 +1,118 @@
+//! Outcome Scoring
+//!
+//! A decision used to succeed whenever the tick time didn't get worse, so the
+//! tuner happily traded fun for speed. Now every objective is compared between
+//! the snapshot taken when the decision was made and the one taken when it is
+//! evaluated, each scaled to -1 (much worse) to 1 (much better):
+//!
+//! - tick time p95 and bytes sent per second: lower is better
+//! - human retention: humans still on the server, relative to before
+//! - kill rate variance: steady action beats bursts and droughts
+//!
+//! The weighted mean (weights from `AI_WEIGHT_*`) is the decision's score, and
+//! a score of at least 0 counts as a success.
+
+use serde::{Deserialize, Serialize};
+
+use super::MetricsSnapshot;
+use crate::config::OutcomeWeights;
+
+/// How a decision moved each objective, from -1 (much worse) to 1 (much better)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OutcomeScore {
+    pub tick_p95: f32,
+    pub retention: f32,
+    pub kill_variance: f32,
+    pub bandwidth: f32,
+    /// Weighted mean of the objectives
+    pub total: f32,
+}
+
+impl OutcomeScore {
+    /// Score the change from `before` to `after`
+    pub fn compute(before: &MetricsSnapshot, after: &MetricsSnapshot, weights: &OutcomeWeights) -> Self {
+        let tick_p95 = lower_is_better(before.tick_time_p95_us as f32, after.tick_time_p95_us as f32);
+        let (humans_before, humans_after) = (before.human_players as f32, after.human_players as f32);
+        let retention =
+            if humans_before > 0.0 { ((humans_after - humans_before) / humans_before).clamp(-1.0, 1.0) } else { 0.0 };
+        let kill_variance = lower_is_better(before.kill_rate_variance, after.kill_rate_variance);
+        let bandwidth = lower_is_better(before.send_bytes_per_sec as f32, after.send_bytes_per_sec as f32);
+
+        let weighted = tick_p95 * weights.tick_p95
+            + retention * weights.retention
+            + kill_variance * weights.kill_variance
+            + bandwidth * weights.bandwidth;
+        let total = if weights.total() > 0.0 { weighted / weights.total() } else { tick_p95 };
+
+        Self { tick_p95, retention, kill_variance, bandwidth, total }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.total >= 0.0
+    }
+}
+
+/// Relative change of a value where lower is better, in -1..=1
+fn lower_is_better(before: f32, after: f32) -> f32 {
+    let scale = before.max(after);
+    if scale <= 0.0 {
+        0.0
+    } else {
+        (before - after) / scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn snapshot(
+        tick_time_p95_us: u64,
+        human_players: u64,
+        kill_rate_variance: f32,
+        send_bytes_per_sec: u64,
+    ) -> MetricsSnapshot {
+        MetricsSnapshot {
+            timestamp: Utc::now(),
+            tick_time_p95_us,
+            tick_time_max_us: tick_time_p95_us,
+            total_players: 50,
+            human_players,
+            bot_players: 50 - human_players,
+            alive_players: 40,
+            projectile_count: 10,
+            debris_count: 100,
+            gravity_well_count: 8,
+            arena_scale: 1.0,
+            arena_radius: 1500.0,
+            performance_status: "good".to_string(),
+            budget_percent: 60,
+            kills_per_min: 6.0,
+            kill_rate_variance,
+            send_bytes_per_sec,
+        }
+    }
+
+    #[test]
+    fn test_faster_ticks_do_not_make_up_for_lost_players() {
+        let weights = OutcomeWeights::default();
+        let before = snapshot(20000, 10, 0.2, 100_000);
+
+        // 25% faster ticks, but half the humans left
+        let score = OutcomeScore::compute(&before, &snapshot(15000, 5, 0.2, 100_000), &weights);
+        assert_eq!(score.tick_p95, 0.25);
+        assert_eq!(score.retention, -0.5);
+        assert!(!score.is_success());
+
+        // Nothing changed: still a success, as before scoring
+        assert!(OutcomeScore::compute(&before, &before, &weights).is_success());
+
+        // Weighting only tick time brings back the old behavior
+        let tick_only = OutcomeWeights { tick_p95: 1.0, retention: 0.0, kill_variance: 0.0, bandwidth: 0.0 };
+        let score = OutcomeScore::compute(&before, &snapshot(15000, 5, 0.8, 200_000), &tick_only);
+        assert_eq!(score.total, 0.25);
+        assert_eq!(score.bandwidth, -0.5);
+        assert!((score.kill_variance + 0.75).abs() < 1e-6);
+    }
+}
@@ -1,17 +1,26 @@
 //! Ban and sanction system for anti-cheat
 //!
-//! Manages player and IP bans with escalation and expiration.
+//! Manages player and IP bans with escalation and expiration. Active bans
+//! can be saved to a JSON file on shutdown and loaded back at startup, each
+//! keeping the time it had left.
 
 #![allow(dead_code)] // Sanction fields for future admin integration
 
 use std::collections::HashMap;
+use std::fs;
 use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
+
 use crate::game::state::PlayerId;
+use crate::metrics::Metrics;
 
 /// Types of sanctions that can be applied
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SanctionType {
     /// Temporary kick from current game
     Kick,
@@ -36,10 +45,22 @@ impl SanctionType {
             SanctionType::PermanentBan => None,                                 // Permanent
         }
     }
+
+    /// Tier label for metrics
+    pub fn tier(&self) -> &'static str {
+        match self {
+            SanctionType::Kick => "kick",
+            SanctionType::ShortBan => "short_ban",
+            SanctionType::MediumBan => "medium_ban",
+            SanctionType::LongBan => "long_ban",
+            SanctionType::PermanentBan => "permanent_ban",
+        }
+    }
 }
 
 /// Reason for a sanction
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SanctionReason {
     /// Cheat detected (specify which)
     CheatDetected(String),
@@ -127,6 +148,43 @@ impl BanRecord {
     }
 }
 
+/// An active ban as kept in the sanctions file
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedBan {
+    player_id: Option<PlayerId>,
+    ip_address: Option<IpAddr>,
+    sanction_type: SanctionType,
+    reason: SanctionReason,
+    /// Seconds left when saved (None = permanent)
+    remaining_secs: Option<u64>,
+    violation_count: u32,
+}
+
+impl SavedBan {
+    fn from_record(record: &BanRecord) -> Self {
+        Self {
+            player_id: record.player_id,
+            ip_address: record.ip_address,
+            sanction_type: record.sanction_type,
+            reason: record.reason.clone(),
+            remaining_secs: record.remaining().map(|d| d.as_secs().max(1)),
+            violation_count: record.violation_count,
+        }
+    }
+
+    fn into_record(self, now: Instant) -> BanRecord {
+        BanRecord {
+            player_id: self.player_id,
+            ip_address: self.ip_address,
+            sanction_type: self.sanction_type,
+            reason: self.reason,
+            created_at: now,
+            expires_at: self.remaining_secs.map(|secs| now + Duration::from_secs(secs)),
+            violation_count: self.violation_count,
+        }
+    }
+}
+
 /// Ban list managing all bans
 pub struct BanList {
     /// Bans by player ID
@@ -138,6 +196,8 @@ pub struct BanList {
     /// Configuration
     escalation_window: Duration,
     violations_for_escalation: u32,
+    /// Counts issued sanctions by tier when set
+    metrics: Option<Arc<Metrics>>,
 }
 
 impl BanList {
@@ -148,9 +208,16 @@ impl BanList {
             violation_history: HashMap::new(),
             escalation_window: Duration::from_secs(24 * 60 * 60), // 24 hours
             violations_for_escalation: 3,
+            metrics: None,
         }
     }
 
+    /// Count every sanction this list issues in the metrics registry
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Add a ban
     pub fn add_ban(&mut self, record: BanRecord) {
         if let Some(player_id) = record.player_id {
@@ -240,6 +307,9 @@ impl BanList {
 
         let record = BanRecord::new(Some(player_id), ip, sanction_type, reason);
         self.add_ban(record);
+        if let Some(metrics) = &self.metrics {
+            metrics.anticheat_sanctions.increment(sanction_type.tier());
+        }
 
         sanction_type
     }
@@ -273,6 +343,42 @@ impl BanList {
         let ip_active = self.ip_bans.values().filter(|b| !b.is_expired()).count();
         player_active + ip_active
     }
+
+    /// Write the active bans to a JSON file, returning how many were saved.
+    /// A ban on both a player and an IP is saved once.
+    pub fn save(&self, path: &str) -> Result<usize, String> {
+        let ip_only = self
+            .ip_bans
+            .values()
+            .filter(|ban| ban.player_id.map_or(true, |id| !self.player_bans.contains_key(&id)));
+        let saved: Vec<SavedBan> = self
+            .player_bans
+            .values()
+            .chain(ip_only)
+            .filter(|ban| !ban.is_expired())
+            .map(SavedBan::from_record)
+            .collect();
+        let json = serde_json::to_string_pretty(&saved).map_err(|e| format!("Failed to encode sanctions: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write sanctions: {}", e))?;
+        Ok(saved.len())
+    }
+
+    /// Add the bans saved at `path` (a missing file holds none), returning
+    /// how many were loaded. Each one runs for the time it had left.
+    pub fn load(&mut self, path: &str) -> Result<usize, String> {
+        if !Path::new(path).exists() {
+            return Ok(0);
+        }
+        let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read sanctions: {}", e))?;
+        let saved: Vec<SavedBan> =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse sanctions: {}", e))?;
+        let now = Instant::now();
+        let count = saved.len();
+        for ban in saved {
+            self.add_ban(ban.into_record(now));
+        }
+        Ok(count)
+    }
 }
 
 impl Default for BanList {
@@ -423,6 +529,19 @@ mod tests {
         assert_eq!(s3, SanctionType::MediumBan);
     }
 
+    #[test]
+    fn test_sanctions_are_counted_by_tier() {
+        let metrics = Arc::new(Metrics::new());
+        let mut list = BanList::new().with_metrics(metrics.clone());
+        let player_id = test_player_id();
+
+        list.apply_sanction(player_id, None, SanctionReason::InvalidInputSpam);
+        list.apply_sanction(test_player_id(), None, SanctionReason::InvalidInputSpam);
+        list.apply_sanction(player_id, None, SanctionReason::InvalidInputSpam);
+        assert_eq!(metrics.anticheat_sanctions.get("kick"), 2);
+        assert_eq!(metrics.anticheat_sanctions.get("short_ban"), 1);
+    }
+
     #[test]
     fn test_cleanup_expired() {
         let mut list = BanList::new();
@@ -475,4 +594,39 @@ mod tests {
         let reason = SanctionReason::DoSAttempt;
         assert!(reason.to_string().contains("DoS"));
     }
+
+    #[test]
+    fn test_active_bans_survive_save_and_load() {
+        let path = std::env::temp_dir().join(format!("orbit_sanctions_{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let _ = fs::remove_file(&path);
+
+        let mut list = BanList::new();
+        let player = test_player_id();
+        list.add_ban(BanRecord::new(
+            Some(player),
+            Some(test_ip()),
+            SanctionType::LongBan,
+            SanctionReason::CheatDetected("Speedhack".to_string()),
+        ));
+        let attacker = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 9));
+        list.add_ban(BanRecord::new(None, Some(attacker), SanctionType::PermanentBan, SanctionReason::DoSAttempt));
+        // Kicks expire at once and aren't kept
+        let kicked = test_player_id();
+        list.add_ban(BanRecord::new(Some(kicked), None, SanctionType::Kick, SanctionReason::InvalidInputSpam));
+        assert_eq!(list.save(path_str), Ok(2));
+
+        let mut reloaded = BanList::new();
+        assert_eq!(reloaded.load(path_str), Ok(2));
+        let _ = fs::remove_file(&path);
+        let ban = reloaded.is_banned(None, Some(test_ip())).unwrap();
+        assert_eq!(ban.player_id, Some(player));
+        assert_eq!(ban.reason, SanctionReason::CheatDetected("Speedhack".to_string()));
+        assert!(ban.remaining().unwrap() > Duration::from_secs(23 * 60 * 60));
+        assert!(reloaded.is_player_banned(player).is_some());
+        assert!(reloaded.is_ip_banned(attacker).unwrap().remaining().is_none());
+
+        // Nothing saved yet: nothing to load
+        assert_eq!(BanList::new().load(path_str), Ok(0));
+    }
 }
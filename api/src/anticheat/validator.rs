@@ -20,6 +20,21 @@ pub enum CheatViolation {
     SequenceJump(u64, u64),
 }
 
+impl CheatViolation {
+    /// Violation type label for metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CheatViolation::InvalidThrust(_) => "invalid_thrust",
+            CheatViolation::InvalidAim(_) => "invalid_aim",
+            CheatViolation::FutureInput(_, _) => "future_input",
+            CheatViolation::StaleInput(_, _, _) => "stale_input",
+            CheatViolation::InvalidFloats => "invalid_floats",
+            CheatViolation::SequenceRegression(_, _) => "sequence_regression",
+            CheatViolation::SequenceJump(_, _) => "sequence_jump",
+        }
+    }
+}
+
 /// Configuration for input validation
 #[derive(Debug, Clone)]
 pub struct ValidationConfig {
@@ -145,7 +160,9 @@ impl Default for InputValidator {
 }
 
 /// Sanitize input by clamping values to valid ranges
-/// Use this after validation to ensure safe processing
+/// Use this after validation to ensure safe processing.
+/// Fire flags are left alone: a malformed input still charges visibly, so
+/// sending garbage vectors cannot be used to hide a windup from opponents.
 pub fn sanitize_input(input: &mut PlayerInput) {
     // Clamp thrust to unit length
     if input.thrust.magnitude() > 1.0 {
@@ -197,6 +214,7 @@ mod tests {
             boost: false,
             fire: false,
             fire_released: false,
+            state_hash: None,
         }
     }
 
@@ -360,6 +378,19 @@ mod tests {
         assert_eq!(input.thrust, Vec2::ZERO);
     }
 
+    #[test]
+    fn test_sanitize_keeps_fire_flags() {
+        let mut input = valid_input();
+        input.aim = Vec2::new(f32::INFINITY, 0.0);
+        input.fire = true;
+
+        sanitize_input(&mut input);
+
+        assert!(input.fire);
+        assert!(!input.fire_released);
+        assert_eq!(input.aim, Vec2::ZERO);
+    }
+
     #[test]
     fn test_sanitize_preserves_valid() {
         let mut input = valid_input();
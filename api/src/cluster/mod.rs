@@ -0,0 +1,283 @@
+//! Cluster mode: occupancy sharing and cross-instance party placement
+//!
+//! Every instance publishes an occupancy heartbeat on a shared pub/sub
+//! channel and keeps a view of all instances from the heartbeats it
+//! receives. Matchmaking asks the view for the least-loaded live instance;
+//! the placement is announced as a seat reservation so other instances
+//! count those seats before the target's next heartbeat arrives. The view
+//! also gives the cluster-wide player count for metrics.
+//!
+//! The state here is transport-agnostic; the Redis bus that feeds it lives
+//! in [`redis`] behind the `cluster` feature. Without it (or without
+//! CLUSTER_REDIS_URL) the view stays empty and placement is unavailable.
+
+#[cfg(feature = "cluster")]
+pub mod redis;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::config::ClusterConfig;
+
+/// One instance's advertised load
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceOccupancy {
+    pub instance_id: String,
+    /// Address clients connect to
+    pub address: String,
+    /// Connected human players (bots are filler and don't count against capacity)
+    pub players: usize,
+    pub capacity: usize,
+    /// Draining instances finish their games but take no placements
+    pub draining: bool,
+}
+
+/// Messages exchanged between instances
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClusterMessage {
+    /// Periodic occupancy heartbeat
+    Occupancy(InstanceOccupancy),
+    /// Seats claimed on an instance by a placement, until its next heartbeat
+    Reserve { instance_id: String, seats: usize },
+}
+
+/// Where a party should connect
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Placement {
+    pub instance_id: String,
+    pub address: String,
+}
+
+/// Why a party could not be placed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ClusterError {
+    #[error("cluster mode is not active (set CLUSTER_REDIS_URL and build with the cluster feature)")]
+    Inactive,
+    #[error("no live instance has room for a party of {0}")]
+    NoCapacity(usize),
+}
+
+#[derive(Debug)]
+struct InstanceEntry {
+    occupancy: InstanceOccupancy,
+    last_seen: Instant,
+    /// Seats reserved since the last heartbeat
+    reserved: usize,
+}
+
+impl InstanceEntry {
+    fn load(&self) -> usize {
+        self.occupancy.players + self.reserved
+    }
+
+    fn free_seats(&self) -> usize {
+        self.occupancy.capacity.saturating_sub(self.load())
+    }
+}
+
+/// Instances known from heartbeats
+#[derive(Debug, Default)]
+pub struct ClusterView {
+    instances: HashMap<String, InstanceEntry>,
+}
+
+impl ClusterView {
+    /// Fold a received message into the view
+    pub fn apply(&mut self, message: ClusterMessage, now: Instant) {
+        match message {
+            ClusterMessage::Occupancy(occupancy) => {
+                self.instances.insert(
+                    occupancy.instance_id.clone(),
+                    InstanceEntry { occupancy, last_seen: now, reserved: 0 },
+                );
+            }
+            ClusterMessage::Reserve { instance_id, seats } => {
+                if let Some(entry) = self.instances.get_mut(&instance_id) {
+                    entry.reserved += seats;
+                }
+            }
+        }
+    }
+
+    /// Forget instances whose heartbeat is older than `stale`
+    pub fn prune(&mut self, now: Instant, stale: Duration) {
+        self.instances.retain(|_, entry| now.duration_since(entry.last_seen) <= stale);
+    }
+
+    /// Live, non-draining instance with the lowest load ratio that fits the party
+    /// (ties go to the lowest instance id, so every instance picks alike)
+    pub fn least_loaded(&self, party_size: usize) -> Option<&InstanceOccupancy> {
+        self.instances
+            .values()
+            .filter(|entry| !entry.occupancy.draining && entry.free_seats() >= party_size)
+            .min_by(|a, b| {
+                let ratio = |e: &InstanceEntry| e.load() as f64 / e.occupancy.capacity.max(1) as f64;
+                ratio(a)
+                    .total_cmp(&ratio(b))
+                    .then_with(|| a.occupancy.instance_id.cmp(&b.occupancy.instance_id))
+            })
+            .map(|entry| &entry.occupancy)
+    }
+
+    /// Players across every known instance
+    pub fn total_players(&self) -> usize {
+        self.instances.values().map(|entry| entry.occupancy.players).sum()
+    }
+
+    /// Known instances, sorted by id
+    pub fn instances(&self) -> Vec<InstanceOccupancy> {
+        let mut instances: Vec<_> = self.instances.values().map(|entry| entry.occupancy.clone()).collect();
+        instances.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+        instances
+    }
+}
+
+/// Shared cluster state: the view plus the outbound bus once connected
+#[derive(Debug)]
+pub struct ClusterState {
+    stale: Duration,
+    view: RwLock<ClusterView>,
+    outbox: Mutex<Option<mpsc::UnboundedSender<ClusterMessage>>>,
+}
+
+impl ClusterState {
+    pub fn new(config: &ClusterConfig) -> Self {
+        Self {
+            stale: Duration::from_secs(config.stale_secs),
+            view: RwLock::new(ClusterView::default()),
+            outbox: Mutex::new(None),
+        }
+    }
+
+    /// Whether a bus is connected
+    pub fn is_active(&self) -> bool {
+        self.outbox.lock().as_ref().is_some_and(|sender| !sender.is_closed())
+    }
+
+    /// Connect the outbound bus (called by the transport once subscribed)
+    #[cfg_attr(not(feature = "cluster"), allow(dead_code))]
+    pub fn attach(&self, sender: mpsc::UnboundedSender<ClusterMessage>) {
+        *self.outbox.lock() = Some(sender);
+    }
+
+    /// Fold a message received from the bus into the view
+    #[cfg_attr(not(feature = "cluster"), allow(dead_code))]
+    pub fn receive(&self, message: ClusterMessage) {
+        let now = Instant::now();
+        let mut view = self.view.write();
+        view.apply(message, now);
+        view.prune(now, self.stale);
+    }
+
+    /// Pick the least-loaded instance for a party and announce the reservation
+    pub fn place_party(&self, party_size: usize) -> Result<Placement, ClusterError> {
+        let outbox = self.outbox.lock();
+        let Some(sender) = outbox.as_ref().filter(|sender| !sender.is_closed()) else {
+            return Err(ClusterError::Inactive);
+        };
+
+        let now = Instant::now();
+        let mut view = self.view.write();
+        view.prune(now, self.stale);
+        let target = view.least_loaded(party_size).ok_or(ClusterError::NoCapacity(party_size))?;
+        let placement = Placement { instance_id: target.instance_id.clone(), address: target.address.clone() };
+
+        // Count the seats locally right away; other instances learn of them from the bus
+        let reserve = ClusterMessage::Reserve { instance_id: placement.instance_id.clone(), seats: party_size };
+        view.apply(reserve.clone(), now);
+        let _ = sender.send(reserve);
+        Ok(placement)
+    }
+
+    /// Players across the cluster
+    pub fn total_players(&self) -> usize {
+        self.view.read().total_players()
+    }
+
+    /// Known instances
+    pub fn instances(&self) -> Vec<InstanceOccupancy> {
+        self.view.read().instances()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occupancy(id: &str, players: usize, capacity: usize) -> ClusterMessage {
+        ClusterMessage::Occupancy(InstanceOccupancy {
+            instance_id: id.to_string(),
+            address: format!("https://{}:4433", id),
+            players,
+            capacity,
+            draining: false,
+        })
+    }
+
+    #[test]
+    fn test_least_loaded_by_ratio_with_room_for_party() {
+        let now = Instant::now();
+        let mut view = ClusterView::default();
+        view.apply(occupancy("a", 50, 100), now);
+        view.apply(occupancy("b", 20, 25), now);
+        view.apply(occupancy("c", 10, 40), now);
+
+        assert_eq!(view.least_loaded(4).unwrap().instance_id, "c");
+        assert_eq!(view.least_loaded(40).unwrap().instance_id, "a");
+        assert!(view.least_loaded(60).is_none());
+        assert_eq!(view.total_players(), 80);
+    }
+
+    #[test]
+    fn test_reservations_and_heartbeats() {
+        let now = Instant::now();
+        let mut view = ClusterView::default();
+        view.apply(occupancy("a", 10, 100), now);
+        view.apply(occupancy("b", 20, 100), now);
+
+        // Reserved seats count against "a" until its next heartbeat
+        view.apply(ClusterMessage::Reserve { instance_id: "a".to_string(), seats: 15 }, now);
+        assert_eq!(view.least_loaded(1).unwrap().instance_id, "b");
+        view.apply(occupancy("a", 12, 100), now);
+        assert_eq!(view.least_loaded(1).unwrap().instance_id, "a");
+
+        // Draining and stale instances take no placements
+        if let ClusterMessage::Occupancy(mut a) = occupancy("a", 12, 100) {
+            a.draining = true;
+            view.apply(ClusterMessage::Occupancy(a), now);
+        }
+        assert_eq!(view.least_loaded(1).unwrap().instance_id, "b");
+        view.apply(occupancy("c", 0, 100), now + Duration::from_secs(20));
+        view.prune(now + Duration::from_secs(20), Duration::from_secs(10));
+        assert_eq!(view.instances().len(), 1);
+    }
+
+    #[test]
+    fn test_place_party_requires_bus_and_announces_reservation() {
+        let state = ClusterState::new(&ClusterConfig::default());
+        assert_eq!(state.place_party(2), Err(ClusterError::Inactive));
+
+        let (sender, mut outbox) = mpsc::unbounded_channel();
+        state.attach(sender);
+        assert_eq!(state.place_party(2), Err(ClusterError::NoCapacity(2)));
+
+        state.receive(occupancy("a", 0, 3));
+        let placement = state.place_party(2).unwrap();
+        assert_eq!(placement.address, "https://a:4433");
+        assert_eq!(outbox.try_recv().unwrap(), ClusterMessage::Reserve { instance_id: "a".to_string(), seats: 2 });
+        assert_eq!(state.place_party(2), Err(ClusterError::NoCapacity(2)));
+    }
+
+    #[test]
+    fn test_message_json() {
+        let json = serde_json::to_string(&ClusterMessage::Reserve { instance_id: "a".to_string(), seats: 3 }).unwrap();
+        assert_eq!(json, r#"{"type":"reserve","instance_id":"a","seats":3}"#);
+        let parsed: ClusterMessage = serde_json::from_str(&serde_json::to_string(&occupancy("b", 1, 2)).unwrap()).unwrap();
+        assert_eq!(parsed, occupancy("b", 1, 2));
+    }
+}
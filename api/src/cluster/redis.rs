@@ -0,0 +1,94 @@
+//! Redis pub/sub bus for cluster mode
+//!
+//! One subscription delivers every instance's messages (including our own
+//! heartbeats, which is how this instance appears in its own view). A
+//! multiplexed connection publishes heartbeats and reservations. On any
+//! connection error the bus is torn down and reconnected after a delay.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use redis::AsyncCommands;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::cluster::{ClusterMessage, InstanceOccupancy};
+use crate::config::ClusterConfig;
+use crate::metrics::Metrics;
+use crate::server::ShutdownToken;
+
+/// Wait before reconnecting after the bus fails
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Start cluster mode if CLUSTER_REDIS_URL is set (runs until `shutdown` is triggered)
+pub fn start_cluster(config: ClusterConfig, metrics: Arc<Metrics>, shutdown: ShutdownToken) {
+    let Some(url) = config.redis_url.clone() else {
+        return;
+    };
+    info!("Cluster mode: instance {} on channel {}", config.instance_id, config.channel);
+
+    tokio::spawn(async move {
+        let bus = async {
+            loop {
+                if let Err(e) = run_bus(&url, &config, &metrics).await {
+                    warn!("Cluster bus error: {} (reconnecting in {:?})", e, RECONNECT_DELAY);
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        };
+        tokio::select! {
+            _ = bus => {}
+            _ = shutdown.triggered() => info!("Cluster bus stopped"),
+        }
+    });
+}
+
+/// This instance's current occupancy from the session metrics
+fn local_occupancy(config: &ClusterConfig, metrics: &Metrics) -> InstanceOccupancy {
+    InstanceOccupancy {
+        instance_id: config.instance_id.clone(),
+        address: config.public_address.clone(),
+        players: metrics.human_players.load(Ordering::Relaxed) as usize,
+        capacity: config.capacity,
+        draining: metrics.draining.load(Ordering::Relaxed),
+    }
+}
+
+async fn run_bus(url: &str, config: &ClusterConfig, metrics: &Metrics) -> redis::RedisResult<()> {
+    let client = redis::Client::open(url)?;
+    let mut publisher = client.get_multiplexed_async_connection().await?;
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(&config.channel).await?;
+    let mut messages = pubsub.into_on_message();
+
+    let (sender, mut outbox) = mpsc::unbounded_channel::<ClusterMessage>();
+    metrics.cluster.attach(sender);
+    info!("Cluster bus connected");
+
+    let mut heartbeat = tokio::time::interval(Duration::from_secs(config.heartbeat_secs));
+    loop {
+        let outgoing = tokio::select! {
+            _ = heartbeat.tick() => ClusterMessage::Occupancy(local_occupancy(config, metrics)),
+            Some(message) = outbox.recv() => message,
+            message = messages.next() => {
+                let Some(message) = message else {
+                    return Err((redis::ErrorKind::IoError, "subscription closed").into());
+                };
+                match message.get_payload::<String>().map(|p| serde_json::from_str::<ClusterMessage>(&p)) {
+                    Ok(Ok(message)) => {
+                        metrics.cluster.receive(message);
+                        metrics.cluster_players.store(metrics.cluster.total_players() as u64, Ordering::Relaxed);
+                        metrics.cluster_instances.store(metrics.cluster.instances().len() as u64, Ordering::Relaxed);
+                    }
+                    _ => debug!("Ignoring malformed cluster message"),
+                }
+                continue;
+            }
+        };
+
+        let payload = serde_json::to_string(&outgoing).unwrap_or_default();
+        publisher.publish::<_, _, i64>(&config.channel, payload).await?;
+    }
+}
@@ -1,6 +1,11 @@
 use std::net::{IpAddr, Ipv4Addr};
 
 use crate::game::constants::{debris_spawning, gravity_waves};
+use crate::game::schedule::ScheduledEvent;
+use crate::game::state::DebrisSize;
+use crate::game::systems::collision::{CollisionLayer, CollisionMatrix, CollisionResponse};
+use crate::game::systems::physics::Integrator;
+use crate::util::vec2::Vec2;
 
 // ============================================================================
 // Configuration Validation Constants
@@ -67,6 +72,8 @@ pub struct GravityConfig {
     /// Influence radius for limited mode (units)
     /// Wells beyond this distance don't affect entities
     pub influence_radius: f32,
+    /// Multiplier on well gravity (1.0 = normal; practice rooms tune it)
+    pub strength: f32,
 }
 
 impl Default for GravityConfig {
@@ -74,6 +81,7 @@ impl Default for GravityConfig {
         Self {
             range_mode: GravityRangeMode::Limited,
             influence_radius: 5000.0,
+            strength: 1.0,
         }
     }
 }
@@ -145,6 +153,8 @@ pub struct ServerConfig {
     pub tls_cert_path: Option<String>,
     /// Path to TLS key file (if not using self-signed)
     pub tls_key_path: Option<String>,
+    /// Thread pools and game loop pinning
+    pub runtime: RuntimeConfig,
 }
 
 impl Default for ServerConfig {
@@ -159,6 +169,7 @@ impl Default for ServerConfig {
             tls_enabled: true,
             tls_cert_path: None,
             tls_key_path: None,
+            runtime: RuntimeConfig::default(),
         }
     }
 }
@@ -208,6 +219,8 @@ impl ServerConfig {
             config.tls_key_path = Some(key_path);
         }
 
+        config.runtime = RuntimeConfig::from_env();
+
         config
     }
 
@@ -436,6 +449,8 @@ pub struct DebrisSpawnConfig {
     pub well_debris_count: usize,
     /// Spawn rate per second for debris around gravity wells
     pub well_spawn_rate: f32,
+    /// Most fragments one broken asteroid may spawn (fragments also count toward `max_count`)
+    pub max_fragments: usize,
 }
 
 impl Default for DebrisSpawnConfig {
@@ -460,6 +475,7 @@ impl Default for DebrisSpawnConfig {
             lifetime: debris_spawning::LIFETIME,
             well_debris_count: 8,   // 8 debris per well initially
             well_spawn_rate: 2.0,   // 2 debris per second around wells
+            max_fragments: 12,
         }
     }
 }
@@ -587,6 +603,16 @@ impl DebrisSpawnConfig {
             }
         }
 
+        if let Ok(val) = std::env::var("DEBRIS_MAX_FRAGMENTS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if parsed <= 100 {
+                    config.max_fragments = parsed;
+                } else {
+                    tracing::warn!("DEBRIS_MAX_FRAGMENTS must be 0-100, using default");
+                }
+            }
+        }
+
         // Lifetime
         if let Ok(val) = std::env::var("DEBRIS_LIFETIME") {
             if let Ok(parsed) = val.parse::<f32>() {
@@ -640,7 +666,7 @@ impl DebrisSpawnConfig {
 /// Arena scaling configuration
 /// Controls dynamic arena sizing based on player count and simulation mode
 /// All values can be overridden via ARENA_* environment variables
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ArenaScalingConfig {
     // Growth/Shrink behavior
     /// How fast arena grows towards target (0.01-0.1)
@@ -944,170 +970,4396 @@ impl ArenaScalingConfig {
     }
 }
 
-/// AI Simulation Manager configuration
-/// Controls the autonomous AI that monitors and adjusts simulation parameters
-/// All values can be overridden via AI_* environment variables
+/// Hand-made arena layout (see `game::arena_layout`)
+/// All values can be overridden via ARENA_LAYOUT_* environment variables
+#[derive(Debug, Clone, Default)]
+pub struct ArenaLayoutConfig {
+    /// JSON layout file used instead of procedural wells (None = procedural)
+    pub path: Option<String>,
+}
+
+impl ArenaLayoutConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("ARENA_LAYOUT_FILE") {
+            if !val.is_empty() {
+                config.path = Some(val);
+            }
+        }
+
+        config
+    }
+}
+
+/// Named arena presets switched from the admin API (see `game::arena_preset`)
+/// All values can be overridden via ARENA_PRESET_* environment variables
+#[derive(Debug, Clone)]
+pub struct ArenaPresetConfig {
+    /// Directory presets are saved to and loaded from (None = memory only)
+    pub dir: Option<String>,
+    /// Seconds the arena takes to change to a preset (0.5-30)
+    pub transition_secs: f32,
+}
+
+impl Default for ArenaPresetConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            transition_secs: 3.0,
+        }
+    }
+}
+
+impl ArenaPresetConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("ARENA_PRESET_DIR") {
+            if !val.is_empty() {
+                config.dir = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("ARENA_PRESET_TRANSITION_SECS") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.5..=30.0).contains(&parsed) {
+                    config.transition_secs = parsed;
+                } else {
+                    tracing::warn!("ARENA_PRESET_TRANSITION_SECS must be 0.5-30, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// How a piece of personal data is rendered in logs and persisted files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedactionPolicy {
+    /// Log the value unchanged
+    Full,
+    /// Keep a coarse prefix (IP network / first characters of a name)
+    #[default]
+    Truncate,
+    /// Replace with a salted, non-reversible hash (stable per value)
+    Hash,
+    /// Drop the value entirely
+    Omit,
+}
+
+/// Unrecognized PRIVACY_*_POLICY value
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("must be full/truncate/hash/omit")]
+pub struct InvalidRedactionPolicy;
+
+impl std::str::FromStr for RedactionPolicy {
+    type Err = InvalidRedactionPolicy;
+
+    /// Parse from string (case-insensitive)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "full" | "none" => Ok(Self::Full),
+            "truncate" => Ok(Self::Truncate),
+            "hash" => Ok(Self::Hash),
+            "omit" => Ok(Self::Omit),
+            _ => Err(InvalidRedactionPolicy),
+        }
+    }
+}
+
+/// Global cached privacy configuration
+static PRIVACY_CONFIG: OnceLock<PrivacyConfig> = OnceLock::new();
+
+/// Privacy configuration for logs, metrics and AI manager persistence
+/// All values can be overridden via PRIVACY_* environment variables
+#[derive(Debug, Clone)]
+pub struct PrivacyConfig {
+    /// Policy applied to client IP addresses
+    pub ip_policy: RedactionPolicy,
+    /// Policy applied to player display names
+    pub name_policy: RedactionPolicy,
+    /// Salt mixed into hashed values so hashes can't be reversed by trying every
+    /// IP or name; random per process unless PRIVACY_HASH_SALT is set
+    pub hash_salt: String,
+    /// Scrub IPs/emails from AI manager prompts and decision history
+    pub scrub_ai_text: bool,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self {
+            ip_policy: RedactionPolicy::Truncate,
+            name_policy: RedactionPolicy::Full,
+            hash_salt: random_salt(),
+            scrub_ai_text: true,
+        }
+    }
+}
+
+/// Random hex salt for hashing personal data
+fn random_salt() -> String {
+    use rand::RngCore;
+
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl PrivacyConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("PRIVACY_IP_POLICY") {
+            match val.parse::<RedactionPolicy>() {
+                Ok(policy) => config.ip_policy = policy,
+                Err(e) => tracing::warn!("Invalid PRIVACY_IP_POLICY '{}', {}, using default", val, e),
+            }
+        }
+
+        if let Ok(val) = std::env::var("PRIVACY_NAME_POLICY") {
+            match val.parse::<RedactionPolicy>() {
+                Ok(policy) => config.name_policy = policy,
+                Err(e) => tracing::warn!("Invalid PRIVACY_NAME_POLICY '{}', {}, using default", val, e),
+            }
+        }
+
+        match std::env::var("PRIVACY_HASH_SALT") {
+            Ok(val) if !val.is_empty() => config.hash_salt = val,
+            _ if config.ip_policy == RedactionPolicy::Hash || config.name_policy == RedactionPolicy::Hash => {
+                tracing::warn!("PRIVACY_HASH_SALT not set: hashed IPs and names won't match across restarts");
+            }
+            _ => {}
+        }
+
+        if let Ok(val) = std::env::var("PRIVACY_SCRUB_AI") {
+            config.scrub_ai_text = val.to_lowercase() == "true" || val == "1";
+        }
+
+        tracing::info!(
+            "Privacy config: ip={:?}, name={:?}, scrub_ai={}",
+            config.ip_policy,
+            config.name_policy,
+            config.scrub_ai_text
+        );
+
+        config
+    }
+
+    /// Get the global cached configuration (loads from env on first call)
+    pub fn global() -> &'static Self {
+        PRIVACY_CONFIG.get_or_init(Self::from_env)
+    }
+}
+
+/// Server-side scripting configuration (requires the `scripting` feature)
+/// Operator-provided WASM scripts run on match start, kills and interval timers
+/// All values can be overridden via SCRIPTING_* environment variables
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
-pub struct AIManagerConfig {
-    /// Master switch - when false, AI manager is disabled
+pub struct ScriptingConfig {
+    /// Master switch - when false, no scripts are loaded
     pub enabled: bool,
-    /// Claude API key (required if enabled)
-    pub api_key: Option<String>,
-    /// Minutes between AI evaluations (1-60)
-    pub eval_interval_minutes: u32,
-    /// Maximum decisions to keep in history (10-1000)
-    pub max_history: usize,
-    /// Minimum confidence to act on recommendations (0.0-1.0)
-    pub confidence_threshold: f32,
-    /// Claude model to use
-    pub model: String,
-    /// Path to decision history file
-    pub history_file: String,
+    /// Directory scanned for `*.wasm` scripts
+    pub script_dir: String,
+    /// Fuel (instruction budget) granted to each hook invocation
+    pub fuel_per_call: u64,
+    /// Ticks between `on_interval` hook invocations (0 = disabled)
+    pub interval_ticks: u32,
+    /// Maximum debris a single hook invocation may spawn
+    pub max_spawns_per_call: usize,
 }
 
-impl Default for AIManagerConfig {
+impl Default for ScriptingConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            api_key: None,
-            eval_interval_minutes: 2,
-            max_history: 100,
-            confidence_threshold: 0.7,
-            model: "claude-sonnet-4-5".to_string(),
-            history_file: "data/ai_decisions.json".to_string(),
+            script_dir: "scripts".to_string(),
+            fuel_per_call: 1_000_000,
+            interval_ticks: 30,
+            max_spawns_per_call: 32,
         }
     }
 }
 
-impl AIManagerConfig {
+impl ScriptingConfig {
     /// Load config from environment variables, falling back to defaults
     #[allow(dead_code)]
     pub fn from_env() -> Self {
         let mut config = Self::default();
 
-        // Feature flag
-        if let Ok(val) = std::env::var("AI_ENABLED") {
+        if let Ok(val) = std::env::var("SCRIPTING_ENABLED") {
             config.enabled = val.to_lowercase() == "true" || val == "1";
         }
 
-        // API key (required if enabled) - use ORBIT_API_KEY for the Anthropic API key
-        if let Ok(val) = std::env::var("ORBIT_API_KEY") {
+        if let Ok(val) = std::env::var("SCRIPTING_DIR") {
             if !val.is_empty() {
-                config.api_key = Some(val);
+                config.script_dir = val;
             }
         }
 
-        // Evaluation interval
-        if let Ok(val) = std::env::var("AI_EVAL_INTERVAL_MINUTES") {
+        if let Ok(val) = std::env::var("SCRIPTING_FUEL_PER_CALL") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1_000..=100_000_000).contains(&parsed) {
+                    config.fuel_per_call = parsed;
+                } else {
+                    tracing::warn!("SCRIPTING_FUEL_PER_CALL must be 1000-100000000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SCRIPTING_INTERVAL_TICKS") {
             if let Ok(parsed) = val.parse::<u32>() {
-                if parsed >= 1 && parsed <= 60 {
-                    config.eval_interval_minutes = parsed;
+                if parsed <= 36_000 {
+                    config.interval_ticks = parsed;
                 } else {
-                    tracing::warn!("AI_EVAL_INTERVAL_MINUTES must be 1-60, using default");
+                    tracing::warn!("SCRIPTING_INTERVAL_TICKS must be 0-36000, using default");
                 }
             }
         }
 
-        // Max history
-        if let Ok(val) = std::env::var("AI_MAX_HISTORY") {
+        if let Ok(val) = std::env::var("SCRIPTING_MAX_SPAWNS_PER_CALL") {
             if let Ok(parsed) = val.parse::<usize>() {
-                if parsed >= 10 && parsed <= 1000 {
-                    config.max_history = parsed;
+                if parsed <= 1000 {
+                    config.max_spawns_per_call = parsed;
                 } else {
-                    tracing::warn!("AI_MAX_HISTORY must be 10-1000, using default");
+                    tracing::warn!("SCRIPTING_MAX_SPAWNS_PER_CALL must be 0-1000, using default");
                 }
             }
         }
 
-        // Confidence threshold
-        if let Ok(val) = std::env::var("AI_CONFIDENCE_THRESHOLD") {
+        if config.enabled {
+            tracing::info!(
+                "Scripting enabled: dir={}, fuel={}, interval={} ticks",
+                config.script_dir,
+                config.fuel_per_call,
+                config.interval_ticks
+            );
+        }
+
+        config
+    }
+}
+
+/// Bot chatter configuration
+/// Rare contextual bot messages (taunts after kills, distress while fleeing)
+/// All values can be overridden via CHATTER_* environment variables
+#[derive(Debug, Clone)]
+pub struct ChatterConfig {
+    /// Master switch - when false, bots never chat
+    pub enabled: bool,
+    /// Optional JSON phrase table (`{"taunt": [...], "distress": [...]}`)
+    pub phrases_path: Option<String>,
+    /// Chance a bot taunts after scoring a kill (0.0-1.0)
+    pub taunt_chance: f32,
+    /// Chance per check (once a second) that a threatened bot calls for help (0.0-1.0)
+    pub distress_chance: f32,
+    /// Minimum seconds between messages from the same bot
+    pub bot_cooldown_secs: f32,
+    /// Maximum chatter messages across the whole arena per minute
+    pub max_per_minute: u32,
+}
+
+impl Default for ChatterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            phrases_path: None,
+            taunt_chance: 0.3,
+            distress_chance: 0.05,
+            bot_cooldown_secs: 30.0,
+            max_per_minute: 6,
+        }
+    }
+}
+
+impl ChatterConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("CHATTER_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("CHATTER_PHRASES_PATH") {
+            if !val.is_empty() {
+                config.phrases_path = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("CHATTER_TAUNT_CHANCE") {
             if let Ok(parsed) = val.parse::<f32>() {
-                if parsed >= 0.0 && parsed <= 1.0 {
-                    config.confidence_threshold = parsed;
+                if (0.0..=1.0).contains(&parsed) {
+                    config.taunt_chance = parsed;
                 } else {
-                    tracing::warn!("AI_CONFIDENCE_THRESHOLD must be 0.0-1.0, using default");
+                    tracing::warn!("CHATTER_TAUNT_CHANCE must be 0.0-1.0, using default");
                 }
             }
         }
 
-        // Model
-        if let Ok(val) = std::env::var("AI_MODEL") {
-            if !val.is_empty() {
-                config.model = val;
+        if let Ok(val) = std::env::var("CHATTER_DISTRESS_CHANCE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.distress_chance = parsed;
+                } else {
+                    tracing::warn!("CHATTER_DISTRESS_CHANCE must be 0.0-1.0, using default");
+                }
             }
         }
 
-        // History file path
-        if let Ok(val) = std::env::var("AI_HISTORY_FILE") {
-            if !val.is_empty() {
-                config.history_file = val;
+        if let Ok(val) = std::env::var("CHATTER_BOT_COOLDOWN_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=3600.0).contains(&parsed) {
+                    config.bot_cooldown_secs = parsed;
+                } else {
+                    tracing::warn!("CHATTER_BOT_COOLDOWN_SECS must be 1-3600, using default");
+                }
             }
         }
 
-        // Validate configuration
-        if config.enabled {
-            if config.api_key.is_none() {
-                tracing::error!("AI_ENABLED=true but ORBIT_API_KEY not set, disabling AI manager");
-                config.enabled = false;
-            } else {
-                tracing::info!(
-                    "AI manager enabled: interval={}min, model={}, threshold={}",
-                    config.eval_interval_minutes,
-                    config.model,
-                    config.confidence_threshold
-                );
+        if let Ok(val) = std::env::var("CHATTER_MAX_PER_MINUTE") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if parsed <= 120 {
+                    config.max_per_minute = parsed;
+                } else {
+                    tracing::warn!("CHATTER_MAX_PER_MINUTE must be 0-120, using default");
+                }
             }
-        } else {
-            tracing::info!("AI manager disabled");
         }
 
         config
     }
+}
 
-    /// Check if AI manager should be active
-    #[allow(dead_code)]
-    pub fn is_active(&self) -> bool {
-        self.enabled && self.api_key.is_some()
-    }
+/// Role assignments for auth tokens (admin, moderator, vip)
+/// All values can be overridden via ROLES_* environment variables
+#[derive(Debug, Clone, Default)]
+pub struct RolesConfig {
+    /// Optional JSON file mapping tokens to roles (`{"<token>": "admin", ...}`)
+    pub roles_file: Option<String>,
+    /// Tokens granted the admin role
+    pub admin_tokens: Vec<String>,
+    /// Tokens granted the moderator role
+    pub moderator_tokens: Vec<String>,
+    /// Tokens granted the vip role
+    pub vip_tokens: Vec<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl RolesConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
 
-    #[test]
-    fn test_default_config() {
-        let config = ServerConfig::default();
-        assert_eq!(config.port, 4433);
-        assert_eq!(config.max_rooms, 100);
-        assert_eq!(config.max_players_per_room, 10);
-        assert!(config.tls_enabled);
-    }
+        if let Ok(val) = std::env::var("ROLES_FILE") {
+            if !val.is_empty() {
+                config.roles_file = Some(val);
+            }
+        }
 
-    #[test]
-    fn test_load_or_default() {
-        let config = ServerConfig::load_or_default();
-        assert!(config.port > 0);
-    }
+        let token_list = |var: &str| -> Vec<String> {
+            std::env::var(var)
+                .map(|val| {
+                    val.split(',')
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        config.admin_tokens = token_list("ROLES_ADMIN_TOKENS");
+        config.moderator_tokens = token_list("ROLES_MODERATOR_TOKENS");
+        config.vip_tokens = token_list("ROLES_VIP_TOKENS");
 
-    #[test]
-    fn test_gravity_wave_config_defaults() {
-        let config = GravityWaveConfig::default();
-        assert!(config.enabled);
-        assert_eq!(config.wave_speed, 300.0);
-        assert_eq!(config.wave_base_impulse, 180.0);
-        assert_eq!(config.min_explosion_delay, 30.0);
-        assert_eq!(config.max_explosion_delay, 90.0);
+        config
     }
+}
 
-    #[test]
-    fn test_gravity_wave_random_delay() {
-        let config = GravityWaveConfig::default();
-        let delay = config.random_explosion_delay();
-        assert!(delay >= config.min_explosion_delay);
-        assert!(delay <= config.max_explosion_delay);
-    }
+/// Player account token signing (see `net::account`)
+/// All values can be overridden via ACCOUNT_* environment variables
+#[derive(Debug, Clone, Default)]
+pub struct AccountConfig {
+    /// Secret account tokens are signed with (unset = a random key per process)
+    pub secret: Option<String>,
+}
+
+impl AccountConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("ACCOUNT_SECRET") {
+            if !val.is_empty() {
+                config.secret = Some(val);
+            }
+        }
+
+        config
+    }
+}
+
+/// Whether player snapshots are encrypted per connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapshotEncryptionMode {
+    /// Snapshots are sent in the clear
+    #[default]
+    Off,
+    /// Encrypted for clients that offer a key, in the clear for the rest
+    Optional,
+    /// Players whose client offers no key are rejected (tournaments)
+    Required,
+}
+
+/// Unrecognized SNAPSHOT_ENCRYPTION value
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("must be 'off', 'optional' or 'required'")]
+pub struct InvalidEncryptionMode;
+
+impl std::str::FromStr for SnapshotEncryptionMode {
+    type Err = InvalidEncryptionMode;
+
+    /// Parse from string (case-insensitive)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "optional" => Ok(Self::Optional),
+            "required" => Ok(Self::Required),
+            _ => Err(InvalidEncryptionMode),
+        }
+    }
+}
+
+/// Streaming the world to joining clients in chunks (see `net::world_stream`)
+/// All values can be overridden via JOIN_STREAM_* environment variables
+#[derive(Debug, Clone)]
+pub struct JoinStreamConfig {
+    /// Stream to clients that announce progressive join support
+    pub enabled: bool,
+    /// Players, projectiles and debris per chunk
+    pub chunk_entities: usize,
+    /// Chunks sent per tick
+    pub chunks_per_tick: usize,
+}
+
+impl Default for JoinStreamConfig {
+    fn default() -> Self {
+        Self { enabled: true, chunk_entities: 128, chunks_per_tick: 1 }
+    }
+}
+
+impl JoinStreamConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("JOIN_STREAM_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("JOIN_STREAM_CHUNK_ENTITIES") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (16..=4096).contains(&parsed) {
+                    config.chunk_entities = parsed;
+                } else {
+                    tracing::warn!("JOIN_STREAM_CHUNK_ENTITIES must be 16-4096, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("JOIN_STREAM_CHUNKS_PER_TICK") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=16).contains(&parsed) {
+                    config.chunks_per_tick = parsed;
+                } else {
+                    tracing::warn!("JOIN_STREAM_CHUNKS_PER_TICK must be 1-16, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Hints of the arena cells a player is about to see (see `net::region_hint`)
+/// All values can be overridden via REGION_HINT_* environment variables
+#[derive(Debug, Clone)]
+pub struct RegionHintConfig {
+    /// Send hints to players
+    pub enabled: bool,
+    /// Ticks between a player's hints
+    pub interval_ticks: u64,
+    /// How far ahead the player's velocity is projected (milliseconds)
+    pub lookahead_ms: u64,
+    /// Cells per hint, nearest first
+    pub max_cells: usize,
+}
+
+impl Default for RegionHintConfig {
+    fn default() -> Self {
+        Self { enabled: true, interval_ticks: 30, lookahead_ms: 1500, max_cells: 24 }
+    }
+}
+
+impl RegionHintConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("REGION_HINT_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("REGION_HINT_INTERVAL_TICKS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (5..=600).contains(&parsed) {
+                    config.interval_ticks = parsed;
+                } else {
+                    tracing::warn!("REGION_HINT_INTERVAL_TICKS must be 5-600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REGION_HINT_LOOKAHEAD_MS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (100..=10_000).contains(&parsed) {
+                    config.lookahead_ms = parsed;
+                } else {
+                    tracing::warn!("REGION_HINT_LOOKAHEAD_MS must be 100-10000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REGION_HINT_MAX_CELLS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=256).contains(&parsed) {
+                    config.max_cells = parsed;
+                } else {
+                    tracing::warn!("REGION_HINT_MAX_CELLS must be 1-256, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Snapshot broadcast worker pool (see `net::broadcast`)
+/// All values can be overridden via BROADCAST_* environment variables
+#[derive(Debug, Clone)]
+pub struct BroadcastConfig {
+    /// Tasks each broadcast's player snapshots are split across
+    pub workers: usize,
+    /// Frames waiting for the pool before new ones are dropped
+    pub queue_depth: usize,
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self { workers: 2, queue_depth: 2 }
+    }
+}
+
+impl BroadcastConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("BROADCAST_WORKERS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=64).contains(&parsed) {
+                    config.workers = parsed;
+                } else {
+                    tracing::warn!("BROADCAST_WORKERS must be 1-64, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("BROADCAST_QUEUE_DEPTH") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=16).contains(&parsed) {
+                    config.queue_depth = parsed;
+                } else {
+                    tracing::warn!("BROADCAST_QUEUE_DEPTH must be 1-16, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Tick-time spike watchdog (see `game::tick_watchdog`)
+/// All values can be overridden via WATCHDOG_* environment variables
+#[derive(Debug, Clone)]
+pub struct TickWatchdogConfig {
+    /// Watch tick times for spikes
+    pub enabled: bool,
+    /// Tick time that counts as a spike (milliseconds; the budget is ~33ms)
+    pub spike_ms: u64,
+    /// How long an armed profiler waits for the next spike (seconds)
+    pub capture_window_secs: u64,
+    /// Pause after a capture before the watchdog arms again (seconds)
+    pub cooldown_secs: u64,
+    /// Profiles captured per process lifetime
+    pub max_captures: u32,
+    /// Profiler sampling frequency (Hz)
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    pub sample_hz: i32,
+    /// Directory flamegraph SVGs are written to
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    pub diagnostics_dir: String,
+}
+
+impl Default for TickWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            spike_ms: 100,
+            capture_window_secs: 120,
+            cooldown_secs: 900,
+            max_captures: 10,
+            sample_hz: 99,
+            diagnostics_dir: "diagnostics".to_string(),
+        }
+    }
+}
+
+impl TickWatchdogConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("WATCHDOG_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("WATCHDOG_SPIKE_MS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (10..=10_000).contains(&parsed) {
+                    config.spike_ms = parsed;
+                } else {
+                    tracing::warn!("WATCHDOG_SPIKE_MS must be 10-10000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WATCHDOG_CAPTURE_WINDOW_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=3600).contains(&parsed) {
+                    config.capture_window_secs = parsed;
+                } else {
+                    tracing::warn!("WATCHDOG_CAPTURE_WINDOW_SECS must be 1-3600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WATCHDOG_COOLDOWN_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed <= 86_400 {
+                    config.cooldown_secs = parsed;
+                } else {
+                    tracing::warn!("WATCHDOG_COOLDOWN_SECS must be 0-86400, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WATCHDOG_MAX_CAPTURES") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if parsed <= 1000 {
+                    config.max_captures = parsed;
+                } else {
+                    tracing::warn!("WATCHDOG_MAX_CAPTURES must be 0-1000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WATCHDOG_SAMPLE_HZ") {
+            if let Ok(parsed) = val.parse::<i32>() {
+                if (1..=1000).contains(&parsed) {
+                    config.sample_hz = parsed;
+                } else {
+                    tracing::warn!("WATCHDOG_SAMPLE_HZ must be 1-1000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WATCHDOG_DIAGNOSTICS_DIR") {
+            if !val.is_empty() {
+                config.diagnostics_dir = val;
+            }
+        }
+
+        config
+    }
+}
+
+/// Parameter freeze after incidents (see `game::safe_mode`)
+/// All values can be overridden via SAFE_MODE_* environment variables
+#[derive(Debug, Clone)]
+pub struct SafeModeConfig {
+    /// Freeze parameters after Catastrophic performance or a crash restart
+    pub enabled: bool,
+    /// How long parameters stay frozen after an incident (seconds)
+    pub freeze_secs: u64,
+    /// How long parameters must keep performance Good or better to become
+    /// the last-known-good set (seconds)
+    pub good_after_secs: u64,
+    /// JSON file the last-known-good set is stored in; without one a crash
+    /// restart can't be detected
+    pub path: Option<String>,
+}
+
+impl Default for SafeModeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            freeze_secs: 900,
+            good_after_secs: 300,
+            path: None,
+        }
+    }
+}
+
+impl SafeModeConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SAFE_MODE_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("SAFE_MODE_FREEZE_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (10..=86_400).contains(&parsed) {
+                    config.freeze_secs = parsed;
+                } else {
+                    tracing::warn!("SAFE_MODE_FREEZE_SECS must be 10-86400, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SAFE_MODE_GOOD_AFTER_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (10..=86_400).contains(&parsed) {
+                    config.good_after_secs = parsed;
+                } else {
+                    tracing::warn!("SAFE_MODE_GOOD_AFTER_SECS must be 10-86400, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SAFE_MODE_PATH") {
+            if !val.is_empty() {
+                config.path = Some(val);
+            }
+        }
+
+        config
+    }
+}
+
+/// Thread pools and core pinning (see `runtime`)
+/// Unset values keep the library defaults (one tokio worker and one Rayon
+/// thread per core, game loop on a tokio worker). All values can be
+/// overridden via RUNTIME_* environment variables
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeConfig {
+    /// Tokio worker threads (networking, broadcasts, background tasks)
+    pub tokio_workers: Option<usize>,
+    /// Rayon pool size (parallel physics, gravity and AI within a tick)
+    pub rayon_threads: Option<usize>,
+    /// Run the game loop on a dedicated thread pinned to this core
+    pub game_loop_core: Option<usize>,
+}
+
+impl RuntimeConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("RUNTIME_TOKIO_WORKERS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=1024).contains(&parsed) {
+                    config.tokio_workers = Some(parsed);
+                } else {
+                    tracing::warn!("RUNTIME_TOKIO_WORKERS must be 1-1024, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("RUNTIME_RAYON_THREADS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=1024).contains(&parsed) {
+                    config.rayon_threads = Some(parsed);
+                } else {
+                    tracing::warn!("RUNTIME_RAYON_THREADS must be 1-1024, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("RUNTIME_GAME_LOOP_CORE") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if parsed < 1024 {
+                    config.game_loop_core = Some(parsed);
+                } else {
+                    tracing::warn!("RUNTIME_GAME_LOOP_CORE must be 0-1023, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Idle-server hibernation (see `net::hibernation`)
+/// All values can be overridden via HIBERNATION_* environment variables
+#[derive(Debug, Clone)]
+pub struct HibernationConfig {
+    /// Hibernate when no one is connected (never in simulation mode)
+    pub enabled: bool,
+    /// Minutes without any connected client before hibernating
+    pub idle_minutes: u64,
+    /// Tick rate while hibernating (Hz)
+    pub tick_hz: u32,
+    /// Bots kept alive while hibernating
+    pub bots: usize,
+}
+
+impl Default for HibernationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_minutes: 10,
+            tick_hz: 2,
+            bots: 4,
+        }
+    }
+}
+
+impl HibernationConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("HIBERNATION_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("HIBERNATION_IDLE_MINUTES") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=1440).contains(&parsed) {
+                    config.idle_minutes = parsed;
+                } else {
+                    tracing::warn!("HIBERNATION_IDLE_MINUTES must be 1-1440, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIBERNATION_TICK_HZ") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=5).contains(&parsed) {
+                    config.tick_hz = parsed;
+                } else {
+                    tracing::warn!("HIBERNATION_TICK_HZ must be 1-5, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIBERNATION_BOTS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if parsed <= 100 {
+                    config.bots = parsed;
+                } else {
+                    tracing::warn!("HIBERNATION_BOTS must be 0-100, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Player reports and karma (see `net::reports`)
+/// All values can be overridden via REPORT_* environment variables
+#[derive(Debug, Clone)]
+pub struct ReportConfig {
+    /// Reports one player may file per window
+    pub max_per_window: u32,
+    /// Rate limit window, also how long repeat reports of the same player are ignored (seconds)
+    pub window_secs: u64,
+    /// Karma lost per report from a fully credible reporter (karma ranges 0-100)
+    pub karma_penalty: f32,
+    /// Karma regained per hour without reports
+    pub karma_recovery_per_hour: f32,
+    /// Weighted report score that puts a player in the moderation review queue
+    pub review_threshold: f32,
+    /// Players below this karma are matched with each other
+    #[cfg_attr(not(feature = "lobby"), allow(dead_code))]
+    pub low_karma_threshold: f32,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            max_per_window: 3,
+            window_secs: 600,
+            karma_penalty: 10.0,
+            karma_recovery_per_hour: 5.0,
+            review_threshold: 3.0,
+            low_karma_threshold: 40.0,
+        }
+    }
+}
+
+impl ReportConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("REPORT_MAX_PER_WINDOW") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=100).contains(&parsed) {
+                    config.max_per_window = parsed;
+                } else {
+                    tracing::warn!("REPORT_MAX_PER_WINDOW must be 1-100, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REPORT_WINDOW_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (10..=86_400).contains(&parsed) {
+                    config.window_secs = parsed;
+                } else {
+                    tracing::warn!("REPORT_WINDOW_SECS must be 10-86400, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REPORT_KARMA_PENALTY") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.0..=100.0).contains(&parsed) {
+                    config.karma_penalty = parsed;
+                } else {
+                    tracing::warn!("REPORT_KARMA_PENALTY must be 0-100, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REPORT_KARMA_RECOVERY_PER_HOUR") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.0..=100.0).contains(&parsed) {
+                    config.karma_recovery_per_hour = parsed;
+                } else {
+                    tracing::warn!("REPORT_KARMA_RECOVERY_PER_HOUR must be 0-100, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REPORT_REVIEW_THRESHOLD") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.5..=100.0).contains(&parsed) {
+                    config.review_threshold = parsed;
+                } else {
+                    tracing::warn!("REPORT_REVIEW_THRESHOLD must be 0.5-100, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REPORT_LOW_KARMA_THRESHOLD") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.0..=100.0).contains(&parsed) {
+                    config.low_karma_threshold = parsed;
+                } else {
+                    tracing::warn!("REPORT_LOW_KARMA_THRESHOLD must be 0-100, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Guided tutorial for new players in lobby rooms (see `game::tutorial`)
+/// All values can be overridden via TUTORIAL_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Read by the lobby
+pub struct TutorialConfig {
+    /// Run the tutorial for players the server hasn't seen finish it
+    pub enabled: bool,
+    /// JSON file remembering who finished the tutorial (None = in memory only)
+    pub profiles_path: Option<String>,
+    /// Seconds of steady orbit needed for the first objective
+    pub orbit_secs: f32,
+    /// Debris pickups needed for the second objective
+    pub debris_goal: u32,
+}
+
+impl Default for TutorialConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            profiles_path: None,
+            orbit_secs: 5.0,
+            debris_goal: 5,
+        }
+    }
+}
+
+impl TutorialConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("TUTORIAL_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("TUTORIAL_PROFILES_PATH") {
+            if !val.is_empty() {
+                config.profiles_path = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("TUTORIAL_ORBIT_SECS") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (1.0..=60.0).contains(&parsed) {
+                    config.orbit_secs = parsed;
+                } else {
+                    tracing::warn!("TUTORIAL_ORBIT_SECS must be 1-60, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("TUTORIAL_DEBRIS_GOAL") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=50).contains(&parsed) {
+                    config.debris_goal = parsed;
+                } else {
+                    tracing::warn!("TUTORIAL_DEBRIS_GOAL must be 1-50, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Smurf detection for matchmaking (see `lobby::smurf`)
+/// New accounts that play far above their rating are matched at a higher
+/// effective MMR; nothing here sanctions anyone.
+/// All values can be overridden via SMURF_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Read by the lobby
+pub struct SmurfConfig {
+    /// Boost the effective MMR of likely smurfs
+    pub enabled: bool,
+    /// Accounts younger than this many days can be flagged
+    pub new_account_days: f32,
+    /// Matches whose accuracy and aim snaps count as early stats
+    pub early_matches: u32,
+    /// Early shots needed before accuracy counts
+    pub min_shots: u32,
+    /// Early accuracy (0-1) at or above which it counts toward the score
+    pub accuracy_threshold: f32,
+    /// Early share of aim changes that snap past 90 degrees, at or above which it counts
+    pub aim_snap_threshold: f32,
+    /// Consecutive wins at or above which the streak counts
+    pub win_streak: u32,
+    /// Score (0-1) at which an account is flagged
+    pub flag_score: f32,
+    /// Effective MMR added to a flagged account at a full score
+    pub max_mmr_boost: u32,
+}
+
+impl Default for SmurfConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            new_account_days: 14.0,
+            early_matches: 10,
+            min_shots: 20,
+            accuracy_threshold: 0.45,
+            aim_snap_threshold: 0.2,
+            win_streak: 3,
+            flag_score: 0.6,
+            max_mmr_boost: 600,
+        }
+    }
+}
+
+impl SmurfConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SMURF_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("SMURF_NEW_ACCOUNT_DAYS") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (1.0..=365.0).contains(&parsed) {
+                    config.new_account_days = parsed;
+                } else {
+                    tracing::warn!("SMURF_NEW_ACCOUNT_DAYS must be 1-365, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SMURF_EARLY_MATCHES") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=100).contains(&parsed) {
+                    config.early_matches = parsed;
+                } else {
+                    tracing::warn!("SMURF_EARLY_MATCHES must be 1-100, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SMURF_MIN_SHOTS") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=1000).contains(&parsed) {
+                    config.min_shots = parsed;
+                } else {
+                    tracing::warn!("SMURF_MIN_SHOTS must be 1-1000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SMURF_ACCURACY_THRESHOLD") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.accuracy_threshold = parsed;
+                } else {
+                    tracing::warn!("SMURF_ACCURACY_THRESHOLD must be 0-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SMURF_AIM_SNAP_THRESHOLD") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.aim_snap_threshold = parsed;
+                } else {
+                    tracing::warn!("SMURF_AIM_SNAP_THRESHOLD must be 0-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SMURF_WIN_STREAK") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=20).contains(&parsed) {
+                    config.win_streak = parsed;
+                } else {
+                    tracing::warn!("SMURF_WIN_STREAK must be 1-20, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SMURF_FLAG_SCORE") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.1..=1.0).contains(&parsed) {
+                    config.flag_score = parsed;
+                } else {
+                    tracing::warn!("SMURF_FLAG_SCORE must be 0.1-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SMURF_MAX_MMR_BOOST") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if parsed <= 5000 {
+                    config.max_mmr_boost = parsed;
+                } else {
+                    tracing::warn!("SMURF_MAX_MMR_BOOST must be 0-5000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Finished lobby matches kept for the `/matches` endpoints (see `lobby::match_history`)
+/// All values can be overridden via MATCH_HISTORY_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Read by the lobby
+pub struct MatchHistoryConfig {
+    /// JSON lines file each finished match is appended to (None = in memory only)
+    pub path: Option<String>,
+    /// Newest matches kept in memory and served
+    pub capacity: usize,
+}
+
+impl Default for MatchHistoryConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            capacity: 500,
+        }
+    }
+}
+
+impl MatchHistoryConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("MATCH_HISTORY_PATH") {
+            if !val.is_empty() {
+                config.path = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("MATCH_HISTORY_CAPACITY") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=100_000).contains(&parsed) {
+                    config.capacity = parsed;
+                } else {
+                    tracing::warn!("MATCH_HISTORY_CAPACITY must be 1-100000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Lobby queue analytics and bot backfill (see `lobby::queue_stats`)
+/// All values can be overridden via LOBBY_QUEUE_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Read by the lobby
+pub struct LobbyQueueConfig {
+    /// Width of an MMR bucket waits are averaged over
+    pub mmr_bucket_size: u32,
+    /// Recent waits kept per mode and MMR bucket
+    pub window: usize,
+    /// Seconds between estimates sent to waiting players
+    pub estimate_interval_secs: f32,
+    /// Start a waiting room with bots once someone waited this long (0 = never)
+    pub backfill_after_secs: f32,
+}
+
+impl Default for LobbyQueueConfig {
+    fn default() -> Self {
+        Self {
+            mmr_bucket_size: 250,
+            window: 50,
+            estimate_interval_secs: 5.0,
+            backfill_after_secs: 45.0,
+        }
+    }
+}
+
+impl LobbyQueueConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("LOBBY_QUEUE_MMR_BUCKET_SIZE") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (50..=5000).contains(&parsed) {
+                    config.mmr_bucket_size = parsed;
+                } else {
+                    tracing::warn!("LOBBY_QUEUE_MMR_BUCKET_SIZE must be 50-5000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("LOBBY_QUEUE_WINDOW") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=1000).contains(&parsed) {
+                    config.window = parsed;
+                } else {
+                    tracing::warn!("LOBBY_QUEUE_WINDOW must be 1-1000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("LOBBY_QUEUE_ESTIMATE_INTERVAL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=60.0).contains(&parsed) {
+                    config.estimate_interval_secs = parsed;
+                } else {
+                    tracing::warn!("LOBBY_QUEUE_ESTIMATE_INTERVAL_SECS must be 1-60, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("LOBBY_QUEUE_BACKFILL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=600.0).contains(&parsed) {
+                    config.backfill_after_secs = parsed;
+                } else {
+                    tracing::warn!("LOBBY_QUEUE_BACKFILL_SECS must be 0-600, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Room browser served by the lobby (`ListRooms`)
+/// All values can be overridden via ROOM_BROWSER_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Read by the lobby
+pub struct RoomBrowserConfig {
+    /// Region this server's rooms are listed under
+    pub region: String,
+    /// Rooms per page
+    pub page_size: usize,
+    /// Minimum seconds between two room lists for one connection
+    pub min_interval_secs: f32,
+}
+
+impl Default for RoomBrowserConfig {
+    fn default() -> Self {
+        Self {
+            region: "default".to_string(),
+            page_size: 20,
+            min_interval_secs: 1.0,
+        }
+    }
+}
+
+impl RoomBrowserConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("ROOM_BROWSER_REGION") {
+            if !val.is_empty() {
+                config.region = val;
+            }
+        }
+
+        if let Ok(val) = std::env::var("ROOM_BROWSER_PAGE_SIZE") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=100).contains(&parsed) {
+                    config.page_size = parsed;
+                } else {
+                    tracing::warn!("ROOM_BROWSER_PAGE_SIZE must be 1-100, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("ROOM_BROWSER_MIN_INTERVAL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=60.0).contains(&parsed) {
+                    config.min_interval_secs = parsed;
+                } else {
+                    tracing::warn!("ROOM_BROWSER_MIN_INTERVAL_SECS must be 0-60, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Galaxy of lobby arenas linked by wormholes (see `lobby::galaxy`)
+/// All values can be overridden via GALAXY_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Read by the lobby
+pub struct GalaxyConfig {
+    /// JSON file with the galaxy's arenas and wormholes (None = no galaxy)
+    pub topology_path: Option<String>,
+    /// Seconds after a transit before the player can take another wormhole
+    pub transit_cooldown_secs: f32,
+}
+
+impl Default for GalaxyConfig {
+    fn default() -> Self {
+        Self {
+            topology_path: None,
+            transit_cooldown_secs: 3.0,
+        }
+    }
+}
+
+impl GalaxyConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("GALAXY_FILE") {
+            if !val.is_empty() {
+                config.topology_path = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("GALAXY_TRANSIT_COOLDOWN_SECS") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.0..=60.0).contains(&parsed) {
+                    config.transit_cooldown_secs = parsed;
+                } else {
+                    tracing::warn!("GALAXY_TRANSIT_COOLDOWN_SECS must be 0-60, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Practice room defaults (see `game::scenario`)
+/// All values can be overridden via PRACTICE_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)] // Read by lobby practice rooms
+pub struct PracticeConfig {
+    /// Well gravity multiplier for new practice rooms
+    pub gravity_strength: f32,
+    /// Targets placed around the first player when practice starts
+    pub initial_targets: u32,
+    /// Distance of spawned targets from the requesting player (units)
+    pub target_distance: f32,
+    /// Seconds between DPS/accuracy readouts
+    pub readout_secs: f32,
+}
+
+impl Default for PracticeConfig {
+    fn default() -> Self {
+        Self {
+            gravity_strength: 1.0,
+            initial_targets: 3,
+            target_distance: 300.0,
+            readout_secs: 2.0,
+        }
+    }
+}
+
+impl PracticeConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("PRACTICE_GRAVITY_STRENGTH") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.0..=crate::game::scenario::MAX_GRAVITY_STRENGTH).contains(&parsed) {
+                    config.gravity_strength = parsed;
+                } else {
+                    tracing::warn!(
+                        "PRACTICE_GRAVITY_STRENGTH must be 0-{}, using default",
+                        crate::game::scenario::MAX_GRAVITY_STRENGTH
+                    );
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("PRACTICE_INITIAL_TARGETS") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if parsed <= crate::game::scenario::MAX_TARGETS {
+                    config.initial_targets = parsed;
+                } else {
+                    tracing::warn!(
+                        "PRACTICE_INITIAL_TARGETS must be 0-{}, using default",
+                        crate::game::scenario::MAX_TARGETS
+                    );
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("PRACTICE_TARGET_DISTANCE") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (50.0..=2000.0).contains(&parsed) {
+                    config.target_distance = parsed;
+                } else {
+                    tracing::warn!("PRACTICE_TARGET_DISTANCE must be 50-2000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("PRACTICE_READOUT_SECS") {
+            if let Some(parsed) = parse_safe_f32(&val) {
+                if (0.5..=30.0).contains(&parsed) {
+                    config.readout_secs = parsed;
+                } else {
+                    tracing::warn!("PRACTICE_READOUT_SECS must be 0.5-30, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Spectator stream delay for competitive matches (see `net::spectator_delay`)
+/// All values can be overridden via SPECTATOR_DELAY_* environment variables
+#[derive(Debug, Clone)]
+pub struct SpectatorDelayConfig {
+    /// Seconds spectators lag behind the live match (0 = live)
+    pub delay_secs: u64,
+    /// Spectators with caster tools (moderators and above) watch live
+    pub exempt_casters: bool,
+}
+
+impl Default for SpectatorDelayConfig {
+    fn default() -> Self {
+        Self {
+            delay_secs: 0,
+            exempt_casters: true,
+        }
+    }
+}
+
+impl SpectatorDelayConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SPECTATOR_DELAY_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed <= 300 {
+                    config.delay_secs = parsed;
+                } else {
+                    tracing::warn!("SPECTATOR_DELAY_SECS must be 0-300, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SPECTATOR_DELAY_EXEMPT_CASTERS") {
+            config.exempt_casters = val.to_lowercase() == "true" || val == "1";
+        }
+
+        config
+    }
+
+    /// Spectator streams are never sealed, so with encryption required every
+    /// spectator (casters included) watches at least
+    /// `REQUIRED_ENCRYPTION_SPECTATOR_DELAY_SECS` behind
+    pub fn for_encryption(mut self, mode: SnapshotEncryptionMode) -> Self {
+        if mode == SnapshotEncryptionMode::Required {
+            self.delay_secs = self.delay_secs.max(REQUIRED_ENCRYPTION_SPECTATOR_DELAY_SECS);
+            self.exempt_casters = false;
+        }
+        self
+    }
+}
+
+/// Minimum spectator delay while snapshot encryption is required
+pub const REQUIRED_ENCRYPTION_SPECTATOR_DELAY_SECS: u64 = 30;
+
+/// Snapshot encryption above the transport (see `net::snapshot_crypto`)
+/// Overridden via the SNAPSHOT_ENCRYPTION environment variable
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotEncryptionConfig {
+    pub mode: SnapshotEncryptionMode,
+}
+
+impl SnapshotEncryptionConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SNAPSHOT_ENCRYPTION") {
+            match val.parse::<SnapshotEncryptionMode>() {
+                Ok(mode) => config.mode = mode,
+                Err(e) => tracing::warn!("Invalid SNAPSHOT_ENCRYPTION '{}', {}, using default", val, e),
+            }
+        }
+
+        config
+    }
+}
+
+/// Cluster mode: occupancy and matchmaking shared through Redis pub/sub
+/// (requires the `cluster` feature; off unless CLUSTER_REDIS_URL is set)
+/// All values can be overridden via CLUSTER_* environment variables
+#[derive(Debug, Clone)]
+pub struct ClusterConfig {
+    /// Redis URL (e.g. `redis://redis:6379`); None = single-instance mode
+    pub redis_url: Option<String>,
+    /// Pub/sub channel shared by every instance
+    pub channel: String,
+    /// Unique id of this instance (defaults to HOSTNAME, then a random id)
+    pub instance_id: String,
+    /// Address clients should connect to for this instance (e.g. `https://eu-1.example.com:4433`)
+    pub public_address: String,
+    /// Players this instance advertises room for
+    pub capacity: usize,
+    /// Seconds between occupancy heartbeats
+    pub heartbeat_secs: u64,
+    /// Seconds without a heartbeat before an instance is dropped from placement
+    pub stale_secs: u64,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        let instance_id = std::env::var("HOSTNAME")
+            .ok()
+            .filter(|h| !h.is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        Self {
+            redis_url: None,
+            channel: "orbit:cluster".to_string(),
+            instance_id,
+            public_address: String::new(),
+            capacity: 100,
+            heartbeat_secs: 2,
+            stale_secs: 10,
+        }
+    }
+}
+
+impl ClusterConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("CLUSTER_REDIS_URL") {
+            if !val.is_empty() {
+                config.redis_url = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("CLUSTER_CHANNEL") {
+            if !val.is_empty() {
+                config.channel = val;
+            }
+        }
+
+        if let Ok(val) = std::env::var("CLUSTER_INSTANCE_ID") {
+            if !val.is_empty() {
+                config.instance_id = val;
+            }
+        }
+
+        if let Ok(val) = std::env::var("CLUSTER_PUBLIC_ADDRESS") {
+            config.public_address = val;
+        }
+
+        if let Ok(val) = std::env::var("CLUSTER_CAPACITY") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=100_000).contains(&parsed) {
+                    config.capacity = parsed;
+                } else {
+                    tracing::warn!("CLUSTER_CAPACITY must be 1-100000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("CLUSTER_HEARTBEAT_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=60).contains(&parsed) {
+                    config.heartbeat_secs = parsed;
+                } else {
+                    tracing::warn!("CLUSTER_HEARTBEAT_SECS must be 1-60, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("CLUSTER_STALE_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed > config.heartbeat_secs && parsed <= 600 {
+                    config.stale_secs = parsed;
+                } else {
+                    tracing::warn!("CLUSTER_STALE_SECS must exceed the heartbeat interval (max 600), using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Health and readiness probes (`/healthz`, `/readyz`)
+/// All values can be overridden via PROBE_* environment variables
+#[derive(Debug, Clone)]
+pub struct ProbeConfig {
+    /// Longest gap since the last game tick before the process counts as unhealthy (ms)
+    pub max_tick_age_ms: u64,
+    /// Also fail health on a catastrophic tick budget, and readiness on a critical one
+    pub strict: bool,
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            max_tick_age_ms: 5000,
+            strict: false,
+        }
+    }
+}
+
+impl ProbeConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("PROBE_MAX_TICK_AGE_MS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (100..=60_000).contains(&parsed) {
+                    config.max_tick_age_ms = parsed;
+                } else {
+                    tracing::warn!("PROBE_MAX_TICK_AGE_MS must be 100-60000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("PROBE_STRICT") {
+            config.strict = val.to_lowercase() == "true" || val == "1";
+        }
+
+        config
+    }
+}
+
+/// Orderly shutdown once the shutdown token fires (see `server`)
+/// All values can be overridden via SHUTDOWN_* environment variables
+#[derive(Debug, Clone)]
+pub struct ShutdownConfig {
+    /// Longest the flush may take (clients notified, history and sanctions saved) before the process stops anyway
+    pub timeout_secs: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self { timeout_secs: 10 }
+    }
+}
+
+impl ShutdownConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SHUTDOWN_TIMEOUT_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=300).contains(&parsed) {
+                    config.timeout_secs = parsed;
+                } else {
+                    tracing::warn!("SHUTDOWN_TIMEOUT_SECS must be 1-300, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Anti-cheat ban persistence (see `anticheat::sanctions`)
+/// All values can be overridden via SANCTIONS_* environment variables
+#[derive(Debug, Clone, Default)]
+pub struct SanctionsConfig {
+    /// JSON file active bans are loaded from at startup and saved to on shutdown (None = in memory only)
+    pub path: Option<String>,
+}
+
+impl SanctionsConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SANCTIONS_PATH") {
+            if !val.is_empty() {
+                config.path = Some(val);
+            }
+        }
+
+        config
+    }
+}
+
+/// How long per-player data is kept on disk (match history, tutorial
+/// profiles, the moderation audit log). Bans are kept until they expire.
+/// All values can be overridden via RETENTION_* environment variables
+#[derive(Debug, Clone)]
+pub struct RetentionConfig {
+    /// Days per-player data is kept (0 = forever)
+    pub days: u64,
+    /// Minutes between purges
+    pub interval_minutes: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            days: 0,
+            interval_minutes: 60,
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("RETENTION_DAYS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed <= 3650 {
+                    config.days = parsed;
+                } else {
+                    tracing::warn!("RETENTION_DAYS must be 0-3650, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("RETENTION_INTERVAL_MINUTES") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=1440).contains(&parsed) {
+                    config.interval_minutes = parsed;
+                } else {
+                    tracing::warn!("RETENTION_INTERVAL_MINUTES must be 1-1440, using default");
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Unix seconds before which data is purged, if anything is
+    pub fn cutoff(&self, now_secs: u64) -> Option<u64> {
+        (self.days > 0).then(|| now_secs.saturating_sub(self.days * 86_400))
+    }
+}
+
+/// Hosted-room tenants and their API keys (see `tenants`)
+/// All values can be overridden via TENANTS_* environment variables
+#[derive(Debug, Clone, Default)]
+pub struct TenantsConfig {
+    /// JSON file mapping API keys to tenants (`{"<key>": {"id": "...", "max_rooms": N, "max_players": N}}`)
+    pub tenants_file: Option<String>,
+}
+
+impl TenantsConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("TENANTS_FILE") {
+            if !val.is_empty() {
+                config.tenants_file = Some(val);
+            }
+        }
+
+        config
+    }
+}
+
+/// Rich presence relay access (see `net::presence`)
+/// All values can be overridden via PRESENCE_* environment variables
+#[derive(Debug, Clone, Default)]
+pub struct PresenceConfig {
+    /// Bearer tokens of relays allowed to poll `/presence` (none = route disabled)
+    pub relay_tokens: Vec<String>,
+}
+
+impl PresenceConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("PRESENCE_RELAY_TOKENS") {
+            config.relay_tokens = val.split(',').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect();
+        }
+
+        config
+    }
+
+    /// Whether a request's bearer token belongs to a relay
+    pub fn allows(&self, token: Option<&str>) -> bool {
+        token.is_some_and(|token| self.relay_tokens.iter().any(|t| t == token))
+    }
+}
+
+/// Public stats API keys, rate limits and caching (see `public_api`)
+/// All values can be overridden via PUBLIC_API_* environment variables
+#[derive(Debug, Clone)]
+pub struct PublicApiConfig {
+    /// JSON file mapping API keys to sites (`{"<key>": {"id": "...", "requests_per_minute": N}}`)
+    pub keys_file: Option<String>,
+    /// Requests per minute for keys that don't set their own limit
+    pub requests_per_minute: u32,
+    /// Seconds a response is served from cache (0 = no caching)
+    pub cache_secs: u64,
+}
+
+impl Default for PublicApiConfig {
+    fn default() -> Self {
+        Self {
+            keys_file: None,
+            requests_per_minute: 60,
+            cache_secs: 5,
+        }
+    }
+}
+
+impl PublicApiConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("PUBLIC_API_KEYS_FILE") {
+            if !val.is_empty() {
+                config.keys_file = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("PUBLIC_API_REQUESTS_PER_MINUTE") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=10_000).contains(&parsed) {
+                    config.requests_per_minute = parsed;
+                } else {
+                    tracing::warn!("PUBLIC_API_REQUESTS_PER_MINUTE must be 1-10000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("PUBLIC_API_CACHE_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed <= 300 {
+                    config.cache_secs = parsed;
+                } else {
+                    tracing::warn!("PUBLIC_API_CACHE_SECS must be 0-300, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Background export of replays, highlights, heatmaps and match logs to
+/// S3-compatible storage (see `export`)
+/// All values can be overridden via EXPORT_* environment variables
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "s3_export"), allow(dead_code))] // Read by the uploader
+pub struct ExportConfig {
+    /// Bucket uploads go to (None = export off)
+    pub bucket: Option<String>,
+    /// Store endpoint, path-style (`https://s3.us-east-1.amazonaws.com`, a MinIO URL, ...)
+    pub endpoint: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Prefix of every object key (e.g. the host name), empty for none
+    pub key_prefix: String,
+    /// Seconds between export passes
+    pub interval_secs: u64,
+    /// Attempts per upload within a pass before it waits for the next pass
+    pub max_attempts: u32,
+    /// Object tags for bucket lifecycle rules, added to the `kind` tag
+    pub tags: Vec<(String, String)>,
+    /// JSON file recording what has been uploaded (None = in memory, so a restart re-uploads)
+    pub state_path: Option<String>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            bucket: None,
+            endpoint: "https://s3.us-east-1.amazonaws.com".to_string(),
+            region: "us-east-1".to_string(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            key_prefix: String::new(),
+            interval_secs: 300,
+            max_attempts: 5,
+            tags: Vec::new(),
+            state_path: None,
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Load config from environment variables, falling back to defaults
+    /// (credentials fall back to AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY)
+    #[cfg_attr(not(feature = "s3_export"), allow(dead_code))]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        config.bucket = var("EXPORT_S3_BUCKET");
+        if let Some(val) = var("EXPORT_S3_ENDPOINT") {
+            config.endpoint = val.trim_end_matches('/').to_string();
+        }
+        if let Some(val) = var("EXPORT_S3_REGION") {
+            config.region = val;
+        }
+        if let Some(val) = var("EXPORT_S3_ACCESS_KEY_ID").or_else(|| var("AWS_ACCESS_KEY_ID")) {
+            config.access_key_id = val;
+        }
+        if let Some(val) = var("EXPORT_S3_SECRET_ACCESS_KEY").or_else(|| var("AWS_SECRET_ACCESS_KEY")) {
+            config.secret_access_key = val;
+        }
+        if let Some(val) = var("EXPORT_KEY_PREFIX") {
+            config.key_prefix = val.trim_matches('/').to_string();
+        }
+
+        if let Ok(val) = std::env::var("EXPORT_INTERVAL_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (10..=86_400).contains(&parsed) {
+                    config.interval_secs = parsed;
+                } else {
+                    tracing::warn!("EXPORT_INTERVAL_SECS must be 10-86400, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("EXPORT_MAX_ATTEMPTS") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=20).contains(&parsed) {
+                    config.max_attempts = parsed;
+                } else {
+                    tracing::warn!("EXPORT_MAX_ATTEMPTS must be 1-20, using default");
+                }
+            }
+        }
+
+        if let Some(val) = var("EXPORT_TAGS") {
+            for pair in val.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                match pair.split_once('=') {
+                    Some((key, value)) if !key.trim().is_empty() => {
+                        config.tags.push((key.trim().to_string(), value.trim().to_string()))
+                    }
+                    _ => tracing::warn!("Ignoring EXPORT_TAGS entry {:?} (use key=value)", pair),
+                }
+            }
+        }
+
+        config.state_path = var("EXPORT_STATE_PATH");
+
+        config
+    }
+}
+
+/// Which connections session capture records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptureMode {
+    /// Nothing is captured
+    #[default]
+    Off,
+    /// Every connection keeps a ring buffer of its last window
+    Ring,
+    /// Only players an admin starts a capture for
+    OptIn,
+}
+
+/// Unrecognized CAPTURE_MODE value
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("must be 'off', 'ring' or 'opt_in'")]
+pub struct InvalidCaptureMode;
+
+impl std::str::FromStr for CaptureMode {
+    type Err = InvalidCaptureMode;
+
+    /// Parse from string (case-insensitive)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "ring" => Ok(Self::Ring),
+            "opt_in" | "optin" => Ok(Self::OptIn),
+            _ => Err(InvalidCaptureMode),
+        }
+    }
+}
+
+/// Per-connection session capture for bug reports (see `net::capture`)
+/// All values can be overridden via CAPTURE_* environment variables
+#[derive(Debug, Clone)]
+pub struct CaptureConfig {
+    /// Which connections are captured
+    pub mode: CaptureMode,
+    /// Seconds of history kept per connection
+    pub window_secs: u64,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            mode: CaptureMode::Off,
+            window_secs: 60,
+        }
+    }
+}
+
+impl CaptureConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("CAPTURE_MODE") {
+            match val.parse::<CaptureMode>() {
+                Ok(mode) => config.mode = mode,
+                Err(e) => tracing::warn!("Invalid CAPTURE_MODE '{}', {}, using default", val, e),
+            }
+        }
+
+        if let Ok(val) = std::env::var("CAPTURE_WINDOW_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (5..=600).contains(&parsed) {
+                    config.window_secs = parsed;
+                } else {
+                    tracing::warn!("CAPTURE_WINDOW_SECS must be 5-600, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Dev-mode network condition simulator (see `net::netsim`)
+/// Conditions themselves are set per connection through the admin API
+#[derive(Debug, Clone, Default)]
+pub struct NetSimConfig {
+    /// Whether simulated conditions may be applied (never enable in production)
+    pub enabled: bool,
+}
+
+impl NetSimConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("NETSIM_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if config.enabled {
+            tracing::warn!("Network simulator enabled (NETSIM_ENABLED): admins can degrade any connection");
+        }
+
+        config
+    }
+}
+
+/// Analytics heatmaps of occupancy, kills and well deaths
+/// All values can be overridden via HEATMAP_* environment variables
+#[derive(Debug, Clone)]
+pub struct HeatmapConfig {
+    /// Whether heatmaps are recorded
+    pub enabled: bool,
+    /// Cell size for kill and well-death locations (units); occupancy uses the AI zone size
+    pub cell_size: f32,
+    /// Seconds between occupancy samples
+    pub sample_secs: f32,
+    /// Optional JSON file the totals are saved to and reloaded from
+    pub path: Option<String>,
+    /// Seconds between saves to `path`
+    pub save_secs: f32,
+}
+
+impl Default for HeatmapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cell_size: 250.0,
+            sample_secs: 5.0,
+            path: None,
+            save_secs: 300.0,
+        }
+    }
+}
+
+impl HeatmapConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("HEATMAP_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("HEATMAP_CELL_SIZE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (50.0..=5000.0).contains(&parsed) {
+                    config.cell_size = parsed;
+                } else {
+                    tracing::warn!("HEATMAP_CELL_SIZE must be 50-5000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HEATMAP_SAMPLE_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=600.0).contains(&parsed) {
+                    config.sample_secs = parsed;
+                } else {
+                    tracing::warn!("HEATMAP_SAMPLE_SECS must be 1-600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HEATMAP_PATH") {
+            if !val.is_empty() {
+                config.path = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("HEATMAP_SAVE_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (10.0..=86400.0).contains(&parsed) {
+                    config.save_secs = parsed;
+                } else {
+                    tracing::warn!("HEATMAP_SAVE_SECS must be 10-86400, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Match replay files (see `net::replay`)
+/// All values can be overridden via REPLAY_* environment variables
+#[derive(Debug, Clone)]
+pub struct ReplayConfig {
+    /// Directory replays are written to (unset = no replays)
+    pub dir: Option<String>,
+    /// Seconds between keyframes (full snapshots playback can start from)
+    pub keyframe_secs: u64,
+    /// Minutes after which a replay is closed and a new one started
+    pub max_minutes: u64,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            keyframe_secs: 10,
+            max_minutes: 60,
+        }
+    }
+}
+
+impl ReplayConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("REPLAY_DIR") {
+            if !val.is_empty() {
+                config.dir = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("REPLAY_KEYFRAME_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=300).contains(&parsed) {
+                    config.keyframe_secs = parsed;
+                } else {
+                    tracing::warn!("REPLAY_KEYFRAME_SECS must be 1-300, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("REPLAY_MAX_MINUTES") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=1440).contains(&parsed) {
+                    config.max_minutes = parsed;
+                } else {
+                    tracing::warn!("REPLAY_MAX_MINUTES must be 1-1440, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Automatic highlight clips (see `net::highlights`)
+/// All values can be overridden via HIGHLIGHT_* environment variables
+#[derive(Debug, Clone)]
+pub struct HighlightConfig {
+    /// Directory clips are written to (unset = no highlights)
+    pub dir: Option<String>,
+    /// Kills by one player within `multi_kill_secs` that make a multi-kill
+    pub multi_kill: usize,
+    pub multi_kill_secs: f32,
+    /// Factor a player's mass must grow or shrink by within 5 seconds
+    pub mass_ratio: f32,
+    /// Distance from a well's core (units) that counts as a narrow escape
+    pub well_margin: f32,
+    /// Seconds of play kept before and after the moment
+    pub pre_roll_secs: f32,
+    pub post_roll_secs: f32,
+    /// Whether bots get highlights too
+    pub include_bots: bool,
+}
+
+impl Default for HighlightConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            multi_kill: 3,
+            multi_kill_secs: 10.0,
+            mass_ratio: 2.0,
+            well_margin: 30.0,
+            pre_roll_secs: 5.0,
+            post_roll_secs: 3.0,
+            include_bots: false,
+        }
+    }
+}
+
+impl HighlightConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_DIR") {
+            if !val.is_empty() {
+                config.dir = Some(val);
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_MULTI_KILL") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (2..=20).contains(&parsed) {
+                    config.multi_kill = parsed;
+                } else {
+                    tracing::warn!("HIGHLIGHT_MULTI_KILL must be 2-20, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_MULTI_KILL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=60.0).contains(&parsed) {
+                    config.multi_kill_secs = parsed;
+                } else {
+                    tracing::warn!("HIGHLIGHT_MULTI_KILL_SECS must be 1-60, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_MASS_RATIO") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.2..=10.0).contains(&parsed) {
+                    config.mass_ratio = parsed;
+                } else {
+                    tracing::warn!("HIGHLIGHT_MASS_RATIO must be 1.2-10, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_WELL_MARGIN") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=500.0).contains(&parsed) {
+                    config.well_margin = parsed;
+                } else {
+                    tracing::warn!("HIGHLIGHT_WELL_MARGIN must be 1-500, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_PRE_ROLL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=30.0).contains(&parsed) {
+                    config.pre_roll_secs = parsed;
+                } else {
+                    tracing::warn!("HIGHLIGHT_PRE_ROLL_SECS must be 0-30, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_POST_ROLL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=30.0).contains(&parsed) {
+                    config.post_roll_secs = parsed;
+                } else {
+                    tracing::warn!("HIGHLIGHT_POST_ROLL_SECS must be 0-30, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HIGHLIGHT_INCLUDE_BOTS") {
+            config.include_bots = val.to_lowercase() == "true" || val == "1";
+        }
+
+        config
+    }
+}
+
+/// Where bots spawn relative to human players
+/// All values can be overridden via BOT_PLACEMENT_* environment variables
+#[derive(Debug, Clone)]
+pub struct BotPlacementConfig {
+    /// Share of bot spawns placed within view of a human (0-1); the rest spread across all wells
+    pub near_human_share: f32,
+    /// Wells within this distance of a human count as within their view (units)
+    pub near_human_radius: f32,
+}
+
+impl Default for BotPlacementConfig {
+    fn default() -> Self {
+        Self {
+            near_human_share: 0.5,
+            near_human_radius: 800.0,
+        }
+    }
+}
+
+impl BotPlacementConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("BOT_PLACEMENT_NEAR_HUMAN_SHARE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.near_human_share = parsed;
+                } else {
+                    tracing::warn!("BOT_PLACEMENT_NEAR_HUMAN_SHARE must be 0-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("BOT_PLACEMENT_NEAR_HUMAN_RADIUS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (100.0..=5000.0).contains(&parsed) {
+                    config.near_human_radius = parsed;
+                } else {
+                    tracing::warn!("BOT_PLACEMENT_NEAR_HUMAN_RADIUS must be 100-5000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Arena boundary behaviour beyond the safe (escape) radius
+/// All values can be overridden via BOUNDARY_* environment variables
+#[derive(Debug, Clone)]
+pub struct BoundaryConfig {
+    /// Inward acceleration per unit beyond the safe radius (units/s² per unit)
+    pub stiffness: f32,
+    /// Damping of outward radial speed beyond the safe radius (per second)
+    pub damping: f32,
+    /// Cap on the restoring acceleration (units/s²)
+    pub max_accel: f32,
+    /// Distance beyond the safe radius at which mass drain reaches the base rate (units)
+    pub drain_ramp: f32,
+}
+
+impl Default for BoundaryConfig {
+    fn default() -> Self {
+        Self {
+            stiffness: 0.5,
+            damping: 1.0,
+            max_accel: 150.0,
+            drain_ramp: 100.0,
+        }
+    }
+}
+
+impl BoundaryConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("BOUNDARY_STIFFNESS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    config.stiffness = parsed;
+                } else {
+                    tracing::warn!("BOUNDARY_STIFFNESS must be 0-10, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("BOUNDARY_DAMPING") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    config.damping = parsed;
+                } else {
+                    tracing::warn!("BOUNDARY_DAMPING must be 0-10, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("BOUNDARY_MAX_ACCEL") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1000.0).contains(&parsed) {
+                    config.max_accel = parsed;
+                } else {
+                    tracing::warn!("BOUNDARY_MAX_ACCEL must be 0-1000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("BOUNDARY_DRAIN_RAMP") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=1000.0).contains(&parsed) {
+                    config.drain_ramp = parsed;
+                } else {
+                    tracing::warn!("BOUNDARY_DRAIN_RAMP must be 1-1000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Physics integration settings
+/// Integrator selected via PHYSICS_INTEGRATOR: legacy, semi_implicit_euler or velocity_verlet
+#[derive(Debug, Clone, Default)]
+pub struct PhysicsConfig {
+    pub integrator: Integrator,
+}
+
+impl PhysicsConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("PHYSICS_INTEGRATOR") {
+            if let Some(integrator) = Integrator::from_name(&val) {
+                config.integrator = integrator;
+            } else {
+                tracing::warn!(
+                    "Invalid PHYSICS_INTEGRATOR '{}', must be legacy, semi_implicit_euler or velocity_verlet, using default",
+                    val
+                );
+            }
+        }
+
+        config
+    }
+}
+
+/// Gravity well capture rules
+/// All values can be overridden via WELL_CAPTURE_* environment variables
+#[derive(Debug, Clone)]
+pub struct WellCaptureConfig {
+    /// Allow wells to be claimed
+    pub enabled: bool,
+    /// Seconds a player must orbit uncontested to claim a well
+    pub capture_secs: f32,
+    /// Outer edge of the capture ring, as a multiple of the well's core radius
+    pub ring_multiplier: f32,
+    /// Passive debris spawned around each owned well per second
+    pub debris_per_sec: f32,
+}
+
+impl Default for WellCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            capture_secs: 10.0,
+            ring_multiplier: 8.0,
+            debris_per_sec: 0.5,
+        }
+    }
+}
+
+impl WellCaptureConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("WELL_CAPTURE_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("WELL_CAPTURE_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=120.0).contains(&parsed) {
+                    config.capture_secs = parsed;
+                } else {
+                    tracing::warn!("WELL_CAPTURE_SECS must be 1-120, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WELL_CAPTURE_RING_MULTIPLIER") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (3.0..=20.0).contains(&parsed) {
+                    config.ring_multiplier = parsed;
+                } else {
+                    tracing::warn!("WELL_CAPTURE_RING_MULTIPLIER must be 3-20, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WELL_CAPTURE_DEBRIS_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    config.debris_per_sec = parsed;
+                } else {
+                    tracing::warn!("WELL_CAPTURE_DEBRIS_PER_SEC must be 0-10, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Statistical resolution of bot-vs-bot fights no human can see
+/// All values can be overridden via COMBAT_RESOLVER_* environment variables
+#[derive(Debug, Clone)]
+pub struct CombatResolverConfig {
+    /// Resolve unseen bot fights statistically instead of simulating projectiles
+    pub enabled: bool,
+    /// Both bots must be at least this far from every alive human (beyond a
+    /// zoomed-out AOI plus its buffers)
+    pub human_clearance: f32,
+    /// A chasing bot engages its target within this distance
+    pub engagement_range: f32,
+    /// Ticks between resolution rolls
+    pub interval_ticks: u32,
+    /// Chance per second of a fight ending in a kill, at full aggression
+    pub kill_rate_per_sec: f32,
+    /// Mass a fully aggressive attacker spends on shots per second
+    pub mass_exchange_per_sec: f32,
+}
+
+impl Default for CombatResolverConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            human_clearance: 4500.0,
+            engagement_range: 1200.0,
+            interval_ticks: 15,
+            kill_rate_per_sec: 0.2,
+            mass_exchange_per_sec: 8.0,
+        }
+    }
+}
+
+impl CombatResolverConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("COMBAT_RESOLVER_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("COMBAT_RESOLVER_HUMAN_CLEARANCE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1000.0..=20000.0).contains(&parsed) {
+                    config.human_clearance = parsed;
+                } else {
+                    tracing::warn!("COMBAT_RESOLVER_HUMAN_CLEARANCE must be 1000-20000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("COMBAT_RESOLVER_ENGAGEMENT_RANGE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (100.0..=5000.0).contains(&parsed) {
+                    config.engagement_range = parsed;
+                } else {
+                    tracing::warn!("COMBAT_RESOLVER_ENGAGEMENT_RANGE must be 100-5000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("COMBAT_RESOLVER_INTERVAL_TICKS") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=300).contains(&parsed) {
+                    config.interval_ticks = parsed;
+                } else {
+                    tracing::warn!("COMBAT_RESOLVER_INTERVAL_TICKS must be 1-300, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("COMBAT_RESOLVER_KILL_RATE_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=5.0).contains(&parsed) {
+                    config.kill_rate_per_sec = parsed;
+                } else {
+                    tracing::warn!("COMBAT_RESOLVER_KILL_RATE_PER_SEC must be 0-5, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("COMBAT_RESOLVER_MASS_EXCHANGE_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=100.0).contains(&parsed) {
+                    config.mass_exchange_per_sec = parsed;
+                } else {
+                    tracing::warn!("COMBAT_RESOLVER_MASS_EXCHANGE_PER_SEC must be 0-100, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Bot factions with home wells and timed raids
+/// All values can be overridden via FACTION_* environment variables
+#[derive(Debug, Clone)]
+pub struct FactionConfig {
+    /// Split bots into factions (disabled by default)
+    pub enabled: bool,
+    /// Number of factions
+    pub count: u8,
+    /// Bots fight intruders within this distance of the well they hold
+    pub home_radius: f32,
+    /// Seconds between raids
+    pub raid_interval_secs: f32,
+    /// Seconds a raid lasts before the raiders head home
+    pub raid_duration_secs: f32,
+}
+
+impl Default for FactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            count: 4,
+            home_radius: 1500.0,
+            raid_interval_secs: 90.0,
+            raid_duration_secs: 30.0,
+        }
+    }
+}
+
+impl FactionConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("FACTIONS_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("FACTION_COUNT") {
+            if let Ok(parsed) = val.parse::<u8>() {
+                if (2..=8).contains(&parsed) {
+                    config.count = parsed;
+                } else {
+                    tracing::warn!("FACTION_COUNT must be 2-8, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("FACTION_HOME_RADIUS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (200.0..=10000.0).contains(&parsed) {
+                    config.home_radius = parsed;
+                } else {
+                    tracing::warn!("FACTION_HOME_RADIUS must be 200-10000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("FACTION_RAID_INTERVAL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (10.0..=3600.0).contains(&parsed) {
+                    config.raid_interval_secs = parsed;
+                } else {
+                    tracing::warn!("FACTION_RAID_INTERVAL_SECS must be 10-3600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("FACTION_RAID_DURATION_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (5.0..=600.0).contains(&parsed) {
+                    config.raid_duration_secs = parsed;
+                } else {
+                    tracing::warn!("FACTION_RAID_DURATION_SECS must be 5-600, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Dynamic difficulty while only a few humans are online
+/// All values can be overridden via DIFFICULTY_* environment variables
+#[derive(Debug, Clone)]
+pub struct DifficultyConfig {
+    /// Ease bots off humans who keep dying
+    pub enabled: bool,
+    /// Only adjust with at most this many humans in the match
+    pub max_humans: usize,
+    /// Deaths older than this many seconds are forgotten
+    pub window_secs: f32,
+    /// Deaths per minute at or below which bots play at full strength
+    pub target_deaths_per_min: f32,
+    /// Deaths per minute at which bots are eased off the most
+    pub max_deaths_per_min: f32,
+    /// Lowest aggression/accuracy multiplier for bots near a struggling human
+    pub min_multiplier: f32,
+    /// Bots within this distance of a human are adjusted for them
+    pub radius: f32,
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_humans: 2,
+            window_secs: 120.0,
+            target_deaths_per_min: 1.0,
+            max_deaths_per_min: 4.0,
+            min_multiplier: 0.4,
+            radius: 2500.0,
+        }
+    }
+}
+
+impl DifficultyConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("DIFFICULTY_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("DIFFICULTY_MAX_HUMANS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=8).contains(&parsed) {
+                    config.max_humans = parsed;
+                } else {
+                    tracing::warn!("DIFFICULTY_MAX_HUMANS must be 1-8, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("DIFFICULTY_WINDOW_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (30.0..=600.0).contains(&parsed) {
+                    config.window_secs = parsed;
+                } else {
+                    tracing::warn!("DIFFICULTY_WINDOW_SECS must be 30-600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("DIFFICULTY_TARGET_DEATHS_PER_MIN") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    config.target_deaths_per_min = parsed;
+                } else {
+                    tracing::warn!("DIFFICULTY_TARGET_DEATHS_PER_MIN must be 0-10, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("DIFFICULTY_MAX_DEATHS_PER_MIN") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if parsed > config.target_deaths_per_min && parsed <= 30.0 {
+                    config.max_deaths_per_min = parsed;
+                } else {
+                    tracing::warn!("DIFFICULTY_MAX_DEATHS_PER_MIN must exceed the target (max 30), using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("DIFFICULTY_MIN_MULTIPLIER") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.1..=1.0).contains(&parsed) {
+                    config.min_multiplier = parsed;
+                } else {
+                    tracing::warn!("DIFFICULTY_MIN_MULTIPLIER must be 0.1-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("DIFFICULTY_RADIUS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (500.0..=10000.0).contains(&parsed) {
+                    config.radius = parsed;
+                } else {
+                    tracing::warn!("DIFFICULTY_RADIUS must be 500-10000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Projectile mass economy: what a shot costs its owner and what a miss returns
+/// All values can be overridden via PROJECTILE_* environment variables
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProjectileEconomyConfig {
+    /// Mass charged per unit of projectile mass for a quick tap
+    pub tap_cost: f32,
+    /// Mass charged per unit of projectile mass for a full charge (interpolated in between)
+    pub full_charge_cost: f32,
+    /// Share of a projectile's mass returned to its owner when it expires without a hit
+    pub miss_refund: f32,
+}
+
+impl Default for ProjectileEconomyConfig {
+    fn default() -> Self {
+        Self {
+            tap_cost: 1.0,
+            full_charge_cost: 1.0,
+            miss_refund: 0.0,
+        }
+    }
+}
+
+impl ProjectileEconomyConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("PROJECTILE_TAP_COST") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.25..=4.0).contains(&parsed) {
+                    config.tap_cost = parsed;
+                } else {
+                    tracing::warn!("PROJECTILE_TAP_COST must be 0.25-4, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("PROJECTILE_FULL_CHARGE_COST") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.25..=4.0).contains(&parsed) {
+                    config.full_charge_cost = parsed;
+                } else {
+                    tracing::warn!("PROJECTILE_FULL_CHARGE_COST must be 0.25-4, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("PROJECTILE_MISS_REFUND") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.miss_refund = parsed;
+                } else {
+                    tracing::warn!("PROJECTILE_MISS_REFUND must be 0-1, using default");
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Mass charged per unit of projectile mass at `progress` (0 = tap, 1 = full charge)
+    pub fn cost_multiplier(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        self.tap_cost + (self.full_charge_cost - self.tap_cost) * progress
+    }
+}
+
+/// Heat: an optional secondary resource that replaces the mass cost of firing
+/// Heat is a 0-1 gauge on each player; a full gauge blocks shots and boosting.
+/// All values can be overridden via HEAT_* environment variables
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HeatConfig {
+    /// Shots cost heat instead of mass (off = classic mass-cost firing)
+    pub enabled: bool,
+    /// Heat gained per second of boosting
+    pub boost_per_sec: f32,
+    /// Heat gained by a quick-tap shot
+    pub fire_tap: f32,
+    /// Heat gained by a fully charged shot (interpolated in between)
+    pub fire_full: f32,
+    /// Heat lost per second
+    pub dissipation_per_sec: f32,
+    /// Extra heat lost per second while in a stable orbit
+    pub orbit_dissipation_per_sec: f32,
+}
+
+impl Default for HeatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            boost_per_sec: 0.25,
+            fire_tap: 0.1,
+            fire_full: 0.4,
+            dissipation_per_sec: 0.1,
+            orbit_dissipation_per_sec: 0.3,
+        }
+    }
+}
+
+impl HeatConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("HEAT_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("HEAT_BOOST_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=2.0).contains(&parsed) {
+                    config.boost_per_sec = parsed;
+                } else {
+                    tracing::warn!("HEAT_BOOST_PER_SEC must be 0-2, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HEAT_FIRE_TAP") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.fire_tap = parsed;
+                } else {
+                    tracing::warn!("HEAT_FIRE_TAP must be 0-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HEAT_FIRE_FULL") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.fire_full = parsed;
+                } else {
+                    tracing::warn!("HEAT_FIRE_FULL must be 0-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HEAT_DISSIPATION_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=2.0).contains(&parsed) {
+                    config.dissipation_per_sec = parsed;
+                } else {
+                    tracing::warn!("HEAT_DISSIPATION_PER_SEC must be 0-2, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("HEAT_ORBIT_DISSIPATION_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=2.0).contains(&parsed) {
+                    config.orbit_dissipation_per_sec = parsed;
+                } else {
+                    tracing::warn!("HEAT_ORBIT_DISSIPATION_PER_SEC must be 0-2, using default");
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Heat gained by a shot at `progress` (0 = tap, 1 = full charge)
+    pub fn fire_heat(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        self.fire_tap + (self.fire_full - self.fire_tap) * progress
+    }
+}
+
+/// Boost fuel configuration
+/// Fuel is a 0-1 tank on each player that boosting burns. It only refills
+/// inside a well's orbit band, so players who keep orbiting keep boosting.
+/// All values can be overridden via FUEL_* environment variables
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FuelConfig {
+    /// Boosting burns fuel (off = unlimited boost)
+    pub enabled: bool,
+    /// Fuel burned per second of boosting
+    pub burn_per_sec: f32,
+    /// Fuel regained per second inside an orbit band
+    pub regen_per_sec: f32,
+    /// Inner edge of a well's orbit band, in core radii from its center
+    pub band_inner_core_radii: f32,
+    /// Outer edge of a well's orbit band, in units from its center
+    pub band_outer_distance: f32,
+    /// Fuel bots keep back for escapes; only fleeing bots dip below it
+    pub bot_reserve: f32,
+}
+
+impl Default for FuelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            burn_per_sec: 0.2,
+            regen_per_sec: 0.25,
+            band_inner_core_radii: 3.0,
+            band_outer_distance: 900.0,
+            bot_reserve: 0.3,
+        }
+    }
+}
+
+impl FuelConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("FUEL_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("FUEL_BURN_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=2.0).contains(&parsed) {
+                    config.burn_per_sec = parsed;
+                } else {
+                    tracing::warn!("FUEL_BURN_PER_SEC must be 0-2, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("FUEL_REGEN_PER_SEC") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=2.0).contains(&parsed) {
+                    config.regen_per_sec = parsed;
+                } else {
+                    tracing::warn!("FUEL_REGEN_PER_SEC must be 0-2, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("FUEL_BAND_INNER_CORE_RADII") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=20.0).contains(&parsed) {
+                    config.band_inner_core_radii = parsed;
+                } else {
+                    tracing::warn!("FUEL_BAND_INNER_CORE_RADII must be 1-20, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("FUEL_BAND_OUTER_DISTANCE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (100.0..=5000.0).contains(&parsed) {
+                    config.band_outer_distance = parsed;
+                } else {
+                    tracing::warn!("FUEL_BAND_OUTER_DISTANCE must be 100-5000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("FUEL_BOT_RESERVE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.bot_reserve = parsed;
+                } else {
+                    tracing::warn!("FUEL_BOT_RESERVE must be 0-1, using default");
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Whether `position` is inside the orbit band of a well at `well` with `core_radius`
+    pub fn in_orbit_band(&self, position: Vec2, well: Vec2, core_radius: f32) -> bool {
+        let distance = position.distance_to(well);
+        distance >= core_radius * self.band_inner_core_radii && distance <= self.band_outer_distance
+    }
+
+    /// Whether a bot with `fuel` left should boost; `urgent` boosts may spend the reserve
+    pub fn bot_may_boost(&self, fuel: f32, urgent: bool) -> bool {
+        !self.enabled || (fuel > 0.0 && (urgent || fuel >= self.bot_reserve))
+    }
+}
+
+/// Debris an asteroid drops when it breaks: between `min` and `max` fragments of `size`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AsteroidLoot {
+    pub size: DebrisSize,
+    pub min: u32,
+    pub max: u32,
+}
+
+/// Destructible asteroids: large obstacles that block projectiles and break into debris
+/// The loot table is set via ASTEROID_LOOT, a comma-separated list of
+/// `size:min-max` entries (small, medium, large), e.g. `small:4-8,large:0-1`.
+/// All values can be overridden via ASTEROID_* environment variables
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsteroidConfig {
+    /// Spawn asteroids at match start
+    pub enabled: bool,
+    /// Asteroids kept in the arena
+    pub count: usize,
+    pub radius: f32,
+    /// Projectile mass an asteroid absorbs before breaking
+    pub health: f32,
+    /// Seconds before a broken asteroid is replaced
+    pub respawn_secs: f32,
+    /// Fragments dropped on breaking, rolled per entry
+    pub loot: Vec<AsteroidLoot>,
+}
+
+impl Default for AsteroidConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            count: 6,
+            radius: 60.0,
+            health: 150.0,
+            respawn_secs: 45.0,
+            loot: vec![
+                AsteroidLoot { size: DebrisSize::Small, min: 4, max: 8 },
+                AsteroidLoot { size: DebrisSize::Medium, min: 1, max: 3 },
+                AsteroidLoot { size: DebrisSize::Large, min: 0, max: 1 },
+            ],
+        }
+    }
+}
+
+impl AsteroidConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("ASTEROIDS_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("ASTEROID_COUNT") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=64).contains(&parsed) {
+                    config.count = parsed;
+                } else {
+                    tracing::warn!("ASTEROID_COUNT must be 1-64, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("ASTEROID_RADIUS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (10.0..=300.0).contains(&parsed) {
+                    config.radius = parsed;
+                } else {
+                    tracing::warn!("ASTEROID_RADIUS must be 10-300, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("ASTEROID_HEALTH") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=5000.0).contains(&parsed) {
+                    config.health = parsed;
+                } else {
+                    tracing::warn!("ASTEROID_HEALTH must be 1-5000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("ASTEROID_RESPAWN_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=600.0).contains(&parsed) {
+                    config.respawn_secs = parsed;
+                } else {
+                    tracing::warn!("ASTEROID_RESPAWN_SECS must be 1-600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("ASTEROID_LOOT") {
+            match Self::parse_loot(&val) {
+                Some(loot) => config.loot = loot,
+                None => tracing::warn!("ASTEROID_LOOT must be a list of size:min-max entries, using default"),
+            }
+        }
+
+        config
+    }
+
+    /// Parse a comma-separated list of `size:min-max` entries (None if any is invalid)
+    fn parse_loot(list: &str) -> Option<Vec<AsteroidLoot>> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .map(|entry| {
+                let (size, range) = entry.split_once(':')?;
+                let size = match size.trim().to_lowercase().as_str() {
+                    "small" => DebrisSize::Small,
+                    "medium" => DebrisSize::Medium,
+                    "large" => DebrisSize::Large,
+                    _ => return None,
+                };
+                let (min, max) = range.split_once('-')?;
+                let (min, max) = (min.trim().parse::<u32>().ok()?, max.trim().parse::<u32>().ok()?);
+                (min <= max && max <= 64).then_some(AsteroidLoot { size, min, max })
+            })
+            .collect()
+    }
+}
+
+/// Periodic global modifiers ("weather", e.g. solar flares)
+/// All values can be overridden via WEATHER_* environment variables
+#[derive(Debug, Clone)]
+pub struct WeatherConfig {
+    /// Roll for random modifiers (requested modifiers start either way)
+    pub enabled: bool,
+    /// Seconds between rolls
+    pub roll_interval_secs: f32,
+    /// Chance a roll starts a modifier (0-1), only when none is active
+    pub chance: f32,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            roll_interval_secs: 120.0,
+            chance: 0.5,
+        }
+    }
+}
+
+impl WeatherConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("WEATHER_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("WEATHER_ROLL_INTERVAL_SECS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (10.0..=3600.0).contains(&parsed) {
+                    config.roll_interval_secs = parsed;
+                } else {
+                    tracing::warn!("WEATHER_ROLL_INTERVAL_SECS must be 10-3600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("WEATHER_CHANCE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.chance = parsed;
+                } else {
+                    tracing::warn!("WEATHER_CHANCE must be 0-1, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Timed server events (see [`crate::game::schedule`])
+/// Events are set via SCHEDULED_EVENTS, a semicolon-separated list of
+/// `cron|action` entries, e.g. `0 * * * *|boss;0 0 * * 6|modifier:debris_surge:172800`.
+/// Invalid entries are skipped with a warning
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleConfig {
+    pub events: Vec<ScheduledEvent>,
+    /// Seconds before an event that players are told it's coming (0 = no warning)
+    pub announce_lead_secs: u64,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            events: Vec::new(),
+            announce_lead_secs: 300,
+        }
+    }
+}
+
+impl ScheduleConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SCHEDULED_EVENTS") {
+            config.events = Self::parse_events(&val);
+        }
+
+        if let Ok(val) = std::env::var("SCHEDULE_ANNOUNCE_LEAD_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed <= 86_400 {
+                    config.announce_lead_secs = parsed;
+                } else {
+                    tracing::warn!("SCHEDULE_ANNOUNCE_LEAD_SECS must be 0-86400, using default");
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Parse a semicolon-separated list of `cron|action` entries, skipping invalid ones
+    fn parse_events(list: &str) -> Vec<ScheduledEvent> {
+        list.split(';')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .filter_map(|entry| match ScheduledEvent::parse(entry) {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    tracing::warn!("Ignoring scheduled event '{}': {}", entry, e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Server-side orbit assist for players who opt in
+/// All values can be overridden via ORBIT_ASSIST_* environment variables
+#[derive(Debug, Clone)]
+pub struct OrbitAssistConfig {
+    /// Allow players to opt in (false ignores the join request setting)
+    pub enabled: bool,
+    /// Maximum corrective acceleration in units/s^2 (boost thrust is 200)
+    pub max_correction: f32,
+    /// Only assist within this distance of a well's center
+    pub max_range: f32,
+}
+
+impl Default for OrbitAssistConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_correction: 30.0,
+            max_range: 800.0,
+        }
+    }
+}
+
+impl OrbitAssistConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("ORBIT_ASSIST_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("ORBIT_ASSIST_MAX_CORRECTION") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=200.0).contains(&parsed) {
+                    config.max_correction = parsed;
+                } else {
+                    tracing::warn!("ORBIT_ASSIST_MAX_CORRECTION must be 0-200, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("ORBIT_ASSIST_MAX_RANGE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (50.0..=5000.0).contains(&parsed) {
+                    config.max_range = parsed;
+                } else {
+                    tracing::warn!("ORBIT_ASSIST_MAX_RANGE must be 50-5000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Collision layer interaction rules and impulse response
+/// Layer pairs are toggled via COLLISION_ENABLE / COLLISION_DISABLE, each a
+/// comma-separated list of `layer:layer` pairs (player, projectile, debris,
+/// hazard, ghost, asteroid), e.g. `COLLISION_DISABLE=ghost:projectile,ghost:debris`.
+/// Response tuning via COLLISION_RESTITUTION, COLLISION_KNOCKBACK_SCALE and
+/// COLLISION_MAX_KNOCKBACK
+#[derive(Debug, Clone, Default)]
+pub struct CollisionConfig {
+    pub matrix: CollisionMatrix,
+    pub response: CollisionResponse,
+}
+
+impl CollisionConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("COLLISION_ENABLE") {
+            config.apply_pairs("COLLISION_ENABLE", &val, true);
+        }
+        if let Ok(val) = std::env::var("COLLISION_DISABLE") {
+            config.apply_pairs("COLLISION_DISABLE", &val, false);
+        }
+
+        if let Ok(val) = std::env::var("COLLISION_RESTITUTION") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.response.restitution = parsed;
+                } else {
+                    tracing::warn!("COLLISION_RESTITUTION must be 0-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("COLLISION_KNOCKBACK_SCALE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=5.0).contains(&parsed) {
+                    config.response.knockback_scale = parsed;
+                } else {
+                    tracing::warn!("COLLISION_KNOCKBACK_SCALE must be 0-5, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("COLLISION_MAX_KNOCKBACK") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=2000.0).contains(&parsed) {
+                    config.response.max_knockback = parsed;
+                } else {
+                    tracing::warn!("COLLISION_MAX_KNOCKBACK must be 0-2000, using default");
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Apply a comma-separated list of `layer:layer` pairs, skipping invalid entries
+    fn apply_pairs(&mut self, var: &str, list: &str, enabled: bool) {
+        for entry in list.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let pair = entry
+                .split_once(':')
+                .and_then(|(a, b)| CollisionLayer::from_name(a).zip(CollisionLayer::from_name(b)));
+            match pair {
+                Some((a, b)) => self.matrix.set(a, b, enabled),
+                None => tracing::warn!(
+                    "{} entry '{}' is not a layer:layer pair, ignoring",
+                    var,
+                    entry
+                ),
+            }
+        }
+    }
+}
+
+/// Apparent-size culling of snapshot entities
+/// All values can be overridden via AOI_* environment variables
+#[derive(Debug, Clone)]
+pub struct AoiCullingConfig {
+    /// Minimum on-screen radius in pixels (world radius x viewport zoom);
+    /// smaller entities are left out of snapshots. 0 disables culling.
+    pub min_apparent_radius_px: f32,
+}
+
+impl Default for AoiCullingConfig {
+    fn default() -> Self {
+        Self {
+            min_apparent_radius_px: 2.0,
+        }
+    }
+}
+
+impl AoiCullingConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("AOI_MIN_APPARENT_RADIUS_PX") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=20.0).contains(&parsed) {
+                    config.min_apparent_radius_px = parsed;
+                } else {
+                    tracing::warn!("AOI_MIN_APPARENT_RADIUS_PX must be 0-20, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Adaptive per-client snapshot rate
+/// All values can be overridden via SNAPSHOT_RATE_* environment variables
+#[derive(Debug, Clone)]
+pub struct SnapshotRateConfig {
+    /// Adapt each client's rate to measured loss and RTT (false = negotiated rate only)
+    pub adaptive: bool,
+    /// Let clients opt into 20Hz snapshots (off by default: it costs a frame every tick)
+    pub allow_high: bool,
+    /// Input loss fraction that drops a client to 5Hz
+    pub loss_degrade: f32,
+    /// Input loss fraction below which a degraded client may recover
+    pub loss_recover: f32,
+    /// RTT that drops a client to 5Hz (ms)
+    pub rtt_degrade_ms: f32,
+    /// Highest RTT at which opted-in clients get 20Hz (ms)
+    pub high_max_rtt_ms: f32,
+    /// Minimum time between rate increases (seconds)
+    pub min_switch_secs: u64,
+}
+
+impl Default for SnapshotRateConfig {
+    fn default() -> Self {
+        Self {
+            adaptive: true,
+            allow_high: false,
+            loss_degrade: 0.05,
+            loss_recover: 0.01,
+            rtt_degrade_ms: 250.0,
+            high_max_rtt_ms: 80.0,
+            min_switch_secs: 5,
+        }
+    }
+}
+
+impl SnapshotRateConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SNAPSHOT_RATE_ADAPTIVE") {
+            config.adaptive = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("SNAPSHOT_RATE_ALLOW_HIGH") {
+            config.allow_high = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("SNAPSHOT_RATE_LOSS_DEGRADE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.loss_degrade = parsed;
+                } else {
+                    tracing::warn!("SNAPSHOT_RATE_LOSS_DEGRADE must be 0-1, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SNAPSHOT_RATE_LOSS_RECOVER") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=config.loss_degrade).contains(&parsed) {
+                    config.loss_recover = parsed;
+                } else {
+                    tracing::warn!("SNAPSHOT_RATE_LOSS_RECOVER must be 0-SNAPSHOT_RATE_LOSS_DEGRADE, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SNAPSHOT_RATE_RTT_DEGRADE_MS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (10.0..=5000.0).contains(&parsed) {
+                    config.rtt_degrade_ms = parsed;
+                } else {
+                    tracing::warn!("SNAPSHOT_RATE_RTT_DEGRADE_MS must be 10-5000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SNAPSHOT_RATE_HIGH_MAX_RTT_MS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=1000.0).contains(&parsed) {
+                    config.high_max_rtt_ms = parsed;
+                } else {
+                    tracing::warn!("SNAPSHOT_RATE_HIGH_MAX_RTT_MS must be 1-1000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SNAPSHOT_RATE_MIN_SWITCH_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed <= 300 {
+                    config.min_switch_secs = parsed;
+                } else {
+                    tracing::warn!("SNAPSHOT_RATE_MIN_SWITCH_SECS must be 0-300, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Per-client send pacing across the snapshot interval
+/// All values can be overridden via SEND_PACING_* environment variables
+#[derive(Debug, Clone)]
+pub struct SendPacingConfig {
+    /// Spread client writes across the interval instead of one burst
+    pub enabled: bool,
+    /// Round-robin slots per snapshot interval
+    pub slots: usize,
+}
+
+impl Default for SendPacingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            slots: 4,
+        }
+    }
+}
+
+impl SendPacingConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SEND_PACING_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("SEND_PACING_SLOTS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=32).contains(&parsed) {
+                    config.slots = parsed;
+                } else {
+                    tracing::warn!("SEND_PACING_SLOTS must be 1-32, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Server-recommended client interpolation delay
+/// All values can be overridden via INTERP_DELAY_* environment variables
+#[derive(Debug, Clone)]
+pub struct InterpDelayConfig {
+    /// Send per-client recommendations in snapshots (0 = client decides alone)
+    pub enabled: bool,
+    /// Snapshot intervals to keep buffered
+    pub buffer_snapshots: f32,
+    /// Extra delay per ms of measured jitter
+    pub jitter_multiplier: f32,
+    /// Lower bound of the recommendation (ms)
+    pub min_ms: u16,
+    /// Upper bound of the recommendation (ms)
+    pub max_ms: u16,
+    /// Minimum change before a client's recommendation is updated (ms)
+    pub hysteresis_ms: u16,
+}
+
+impl Default for InterpDelayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            buffer_snapshots: 2.0,
+            jitter_multiplier: 2.0,
+            min_ms: 100,
+            max_ms: 500,
+            hysteresis_ms: 10,
+        }
+    }
+}
+
+impl InterpDelayConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("INTERP_DELAY_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("INTERP_DELAY_BUFFER_SNAPSHOTS") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (1.0..=10.0).contains(&parsed) {
+                    config.buffer_snapshots = parsed;
+                } else {
+                    tracing::warn!("INTERP_DELAY_BUFFER_SNAPSHOTS must be 1-10, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("INTERP_DELAY_JITTER_MULTIPLIER") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    config.jitter_multiplier = parsed;
+                } else {
+                    tracing::warn!("INTERP_DELAY_JITTER_MULTIPLIER must be 0-10, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("INTERP_DELAY_MIN_MS") {
+            if let Ok(parsed) = val.parse::<u16>() {
+                if (10..=2000).contains(&parsed) {
+                    config.min_ms = parsed;
+                } else {
+                    tracing::warn!("INTERP_DELAY_MIN_MS must be 10-2000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("INTERP_DELAY_MAX_MS") {
+            if let Ok(parsed) = val.parse::<u16>() {
+                if (10..=2000).contains(&parsed) {
+                    config.max_ms = parsed;
+                } else {
+                    tracing::warn!("INTERP_DELAY_MAX_MS must be 10-2000, using default");
+                }
+            }
+        }
+
+        if config.max_ms < config.min_ms {
+            tracing::warn!("INTERP_DELAY_MAX_MS below INTERP_DELAY_MIN_MS, using min for both");
+            config.max_ms = config.min_ms;
+        }
+
+        if let Ok(val) = std::env::var("INTERP_DELAY_HYSTERESIS_MS") {
+            if let Ok(parsed) = val.parse::<u16>() {
+                if parsed <= 200 {
+                    config.hysteresis_ms = parsed;
+                } else {
+                    tracing::warn!("INTERP_DELAY_HYSTERESIS_MS must be 0-200, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Client prediction desync detection (client-reported state hashes)
+/// All values can be overridden via DESYNC_* environment variables
+#[derive(Debug, Clone)]
+pub struct DesyncConfig {
+    /// Compare client state hashes against authoritative state
+    pub enabled: bool,
+    /// Authoritative hashes retained per player (ticks)
+    pub history_ticks: usize,
+    /// Consecutive mismatches before a player is reported as desynced
+    pub warn_streak: u32,
+}
+
+impl Default for DesyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            history_ticks: 64,
+            warn_streak: 30,
+        }
+    }
+}
+
+impl DesyncConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("DESYNC_DETECTION_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("DESYNC_HISTORY_TICKS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=600).contains(&parsed) {
+                    config.history_ticks = parsed;
+                } else {
+                    tracing::warn!("DESYNC_HISTORY_TICKS must be 1-600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("DESYNC_WARN_STREAK") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=10000).contains(&parsed) {
+                    config.warn_streak = parsed;
+                } else {
+                    tracing::warn!("DESYNC_WARN_STREAK must be 1-10000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Retained full snapshots for the snapshot diff debug endpoint
+/// All values can be overridden via SNAPSHOT_* environment variables
+#[derive(Debug, Clone)]
+pub struct SnapshotHistoryConfig {
+    /// Snapshots retained (0 = disabled). Snapshots are taken at 10 Hz.
+    pub capacity: usize,
+    /// Default movement threshold for diffs (world units)
+    pub default_epsilon: f32,
+}
+
+impl Default for SnapshotHistoryConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            default_epsilon: 0.5,
+        }
+    }
+}
+
+impl SnapshotHistoryConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("SNAPSHOT_HISTORY_CAPACITY") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if parsed <= 600 {
+                    config.capacity = parsed;
+                } else {
+                    tracing::warn!("SNAPSHOT_HISTORY_CAPACITY must be 0-600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("SNAPSHOT_DIFF_EPSILON") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1000.0).contains(&parsed) {
+                    config.default_epsilon = parsed;
+                } else {
+                    tracing::warn!("SNAPSHOT_DIFF_EPSILON must be 0-1000, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// Player chat and moderator chat commands
+/// All values can be overridden via CHAT_* / MODERATION_* environment variables
+#[derive(Debug, Clone)]
+pub struct ModerationConfig {
+    /// Master switch - when false, chat messages are dropped (commands still work)
+    pub chat_enabled: bool,
+    /// Longest chat message relayed (characters)
+    pub chat_max_len: usize,
+    /// Mute duration when `/mute` is given no duration
+    pub default_mute_secs: u64,
+    /// Optional JSON-lines file receiving the moderation audit log
+    pub audit_log_path: Option<String>,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            chat_enabled: true,
+            chat_max_len: 200,
+            default_mute_secs: 600,
+            audit_log_path: None,
+        }
+    }
+}
+
+impl ModerationConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("CHAT_ENABLED") {
+            config.chat_enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("CHAT_MAX_LEN") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=1000).contains(&parsed) {
+                    config.chat_max_len = parsed;
+                } else {
+                    tracing::warn!("CHAT_MAX_LEN must be 1-1000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("MODERATION_DEFAULT_MUTE_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if (1..=86_400).contains(&parsed) {
+                    config.default_mute_secs = parsed;
+                } else {
+                    tracing::warn!("MODERATION_DEFAULT_MUTE_SECS must be 1-86400, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("MODERATION_AUDIT_LOG") {
+            if !val.is_empty() {
+                config.audit_log_path = Some(val);
+            }
+        }
+
+        config
+    }
+}
+
+/// Join queue used when the server cannot accept a player immediately
+/// All values can be overridden via JOIN_QUEUE_* environment variables
+#[derive(Debug, Clone)]
+pub struct JoinQueueConfig {
+    /// Master switch - when false, joins at capacity are rejected outright
+    pub enabled: bool,
+    /// Maximum queued connections; joins beyond this are rejected
+    pub max_len: usize,
+    /// Hard cap on human players (0 = only performance-based admission)
+    pub max_humans: usize,
+    /// Slots under `max_humans` held back for party members and reconnects
+    pub reserved_slots: usize,
+    /// Seconds after leaving during which a session token grants reconnect priority
+    pub reconnect_grace_secs: u64,
+    /// Assumed seconds per admission when estimating ETA without history
+    pub default_secs_per_slot: u32,
+}
+
+impl Default for JoinQueueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_len: 100,
+            max_humans: 0,
+            reserved_slots: 2,
+            reconnect_grace_secs: 120,
+            default_secs_per_slot: 15,
+        }
+    }
+}
+
+impl JoinQueueConfig {
+    /// Load config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(val) = std::env::var("JOIN_QUEUE_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        if let Ok(val) = std::env::var("JOIN_QUEUE_MAX_LEN") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if (1..=10_000).contains(&parsed) {
+                    config.max_len = parsed;
+                } else {
+                    tracing::warn!("JOIN_QUEUE_MAX_LEN must be 1-10000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("JOIN_QUEUE_MAX_HUMANS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if parsed <= 10_000 {
+                    config.max_humans = parsed;
+                } else {
+                    tracing::warn!("JOIN_QUEUE_MAX_HUMANS must be 0-10000, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("JOIN_QUEUE_RESERVED_SLOTS") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if parsed <= 100 {
+                    config.reserved_slots = parsed;
+                } else {
+                    tracing::warn!("JOIN_QUEUE_RESERVED_SLOTS must be 0-100, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("JOIN_QUEUE_RECONNECT_GRACE_SECS") {
+            if let Ok(parsed) = val.parse::<u64>() {
+                if parsed <= 3600 {
+                    config.reconnect_grace_secs = parsed;
+                } else {
+                    tracing::warn!("JOIN_QUEUE_RECONNECT_GRACE_SECS must be 0-3600, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("JOIN_QUEUE_DEFAULT_SECS_PER_SLOT") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (1..=600).contains(&parsed) {
+                    config.default_secs_per_slot = parsed;
+                } else {
+                    tracing::warn!("JOIN_QUEUE_DEFAULT_SECS_PER_SLOT must be 1-600, using default");
+                }
+            }
+        }
+
+        config
+    }
+}
+
+/// AI Simulation Manager configuration
+/// Controls the autonomous AI that monitors and adjusts simulation parameters
+/// All values can be overridden via AI_* environment variables
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct AIManagerConfig {
+    /// Master switch - when false, AI manager is disabled
+    pub enabled: bool,
+    /// Claude API key (required if enabled)
+    pub api_key: Option<String>,
+    /// Minutes between AI evaluations (1-60)
+    pub eval_interval_minutes: u32,
+    /// Maximum decisions to keep in history (10-1000)
+    pub max_history: usize,
+    /// Minimum confidence to act on recommendations (0.0-1.0)
+    pub confidence_threshold: f32,
+    /// Claude model to use
+    pub model: String,
+    /// Path to decision history file
+    pub history_file: String,
+    /// Spectator commentary feed (independent of parameter tuning)
+    pub narrator_enabled: bool,
+    /// Seconds between commentary lines (15-600)
+    pub narrator_interval_secs: u32,
+    /// Maximum narrator API calls per hour (cached lines don't count)
+    pub narrator_max_calls_per_hour: u32,
+    /// How much each objective counts when a decision's outcome is scored
+    pub outcome_weights: OutcomeWeights,
+}
+
+/// Weights of the objectives an AI decision's outcome is scored on (see `ai_manager::scoring`)
+/// All values can be overridden via AI_WEIGHT_* environment variables
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutcomeWeights {
+    /// Tick time p95 (lower is better)
+    pub tick_p95: f32,
+    /// Human players staying on the server
+    pub retention: f32,
+    /// Steady action: variance of kills per second (lower is better)
+    pub kill_variance: f32,
+    /// Bytes sent per second (lower is better)
+    pub bandwidth: f32,
+}
+
+impl Default for OutcomeWeights {
+    fn default() -> Self {
+        Self {
+            tick_p95: 1.0,
+            retention: 1.0,
+            kill_variance: 0.5,
+            bandwidth: 0.25,
+        }
+    }
+}
+
+impl OutcomeWeights {
+    /// Sum of the weights
+    pub fn total(&self) -> f32 {
+        self.tick_p95 + self.retention + self.kill_variance + self.bandwidth
+    }
+
+    /// Load weights from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let mut weights = Self::default();
+
+        if let Ok(val) = std::env::var("AI_WEIGHT_TICK_P95") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    weights.tick_p95 = parsed;
+                } else {
+                    tracing::warn!("AI_WEIGHT_TICK_P95 must be 0.0-10.0, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("AI_WEIGHT_RETENTION") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    weights.retention = parsed;
+                } else {
+                    tracing::warn!("AI_WEIGHT_RETENTION must be 0.0-10.0, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("AI_WEIGHT_KILL_VARIANCE") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    weights.kill_variance = parsed;
+                } else {
+                    tracing::warn!("AI_WEIGHT_KILL_VARIANCE must be 0.0-10.0, using default");
+                }
+            }
+        }
+
+        if let Ok(val) = std::env::var("AI_WEIGHT_BANDWIDTH") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=10.0).contains(&parsed) {
+                    weights.bandwidth = parsed;
+                } else {
+                    tracing::warn!("AI_WEIGHT_BANDWIDTH must be 0.0-10.0, using default");
+                }
+            }
+        }
+
+        if weights.total() <= 0.0 {
+            tracing::warn!("AI_WEIGHT_* are all 0, using defaults");
+            weights = Self::default();
+        }
+
+        weights
+    }
+}
+
+impl Default for AIManagerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_key: None,
+            eval_interval_minutes: 2,
+            max_history: 100,
+            confidence_threshold: 0.7,
+            model: "claude-sonnet-4-5".to_string(),
+            history_file: "data/ai_decisions.json".to_string(),
+            narrator_enabled: false,
+            narrator_interval_secs: 60,
+            narrator_max_calls_per_hour: 20,
+            outcome_weights: OutcomeWeights::default(),
+        }
+    }
+}
+
+impl AIManagerConfig {
+    /// Load config from environment variables, falling back to defaults
+    #[allow(dead_code)]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        // Feature flag
+        if let Ok(val) = std::env::var("AI_ENABLED") {
+            config.enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        // API key (required if enabled) - use ORBIT_API_KEY for the Anthropic API key
+        if let Ok(val) = std::env::var("ORBIT_API_KEY") {
+            if !val.is_empty() {
+                config.api_key = Some(val);
+            }
+        }
+
+        // Evaluation interval
+        if let Ok(val) = std::env::var("AI_EVAL_INTERVAL_MINUTES") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if parsed >= 1 && parsed <= 60 {
+                    config.eval_interval_minutes = parsed;
+                } else {
+                    tracing::warn!("AI_EVAL_INTERVAL_MINUTES must be 1-60, using default");
+                }
+            }
+        }
+
+        // Max history
+        if let Ok(val) = std::env::var("AI_MAX_HISTORY") {
+            if let Ok(parsed) = val.parse::<usize>() {
+                if parsed >= 10 && parsed <= 1000 {
+                    config.max_history = parsed;
+                } else {
+                    tracing::warn!("AI_MAX_HISTORY must be 10-1000, using default");
+                }
+            }
+        }
+
+        // Confidence threshold
+        if let Ok(val) = std::env::var("AI_CONFIDENCE_THRESHOLD") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if parsed >= 0.0 && parsed <= 1.0 {
+                    config.confidence_threshold = parsed;
+                } else {
+                    tracing::warn!("AI_CONFIDENCE_THRESHOLD must be 0.0-1.0, using default");
+                }
+            }
+        }
+
+        // Model
+        if let Ok(val) = std::env::var("AI_MODEL") {
+            if !val.is_empty() {
+                config.model = val;
+            }
+        }
+
+        // History file path
+        if let Ok(val) = std::env::var("AI_HISTORY_FILE") {
+            if !val.is_empty() {
+                config.history_file = val;
+            }
+        }
+
+        // Narrator
+        if let Ok(val) = std::env::var("AI_NARRATOR_ENABLED") {
+            config.narrator_enabled = val.to_lowercase() == "true" || val == "1";
+        }
+        if let Ok(val) = std::env::var("AI_NARRATOR_INTERVAL_SECS") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if (15..=600).contains(&parsed) {
+                    config.narrator_interval_secs = parsed;
+                } else {
+                    tracing::warn!("AI_NARRATOR_INTERVAL_SECS must be 15-600, using default");
+                }
+            }
+        }
+        if let Ok(val) = std::env::var("AI_NARRATOR_MAX_CALLS_PER_HOUR") {
+            if let Ok(parsed) = val.parse::<u32>() {
+                if parsed <= 360 {
+                    config.narrator_max_calls_per_hour = parsed;
+                } else {
+                    tracing::warn!("AI_NARRATOR_MAX_CALLS_PER_HOUR must be 0-360, using default");
+                }
+            }
+        }
+
+        config.outcome_weights = OutcomeWeights::from_env();
+
+        // Validate configuration
+        if config.enabled {
+            if config.api_key.is_none() {
+                tracing::error!("AI_ENABLED=true but ORBIT_API_KEY not set, disabling AI manager");
+                config.enabled = false;
+            } else {
+                tracing::info!(
+                    "AI manager enabled: interval={}min, model={}, threshold={}",
+                    config.eval_interval_minutes,
+                    config.model,
+                    config.confidence_threshold
+                );
+            }
+        } else {
+            tracing::info!("AI manager disabled");
+        }
+
+        config
+    }
+
+    /// Check if AI manager should be active
+    #[allow(dead_code)]
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.api_key.is_some()
+    }
+
+    /// Check if the spectator narrator should run
+    #[allow(dead_code)]
+    pub fn narrator_active(&self) -> bool {
+        self.narrator_enabled && self.api_key.is_some() && self.narrator_max_calls_per_hour > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = ServerConfig::default();
+        assert_eq!(config.port, 4433);
+        assert_eq!(config.max_rooms, 100);
+        assert_eq!(config.max_players_per_room, 10);
+        assert!(config.tls_enabled);
+    }
+
+    #[test]
+    fn test_load_or_default() {
+        let config = ServerConfig::load_or_default();
+        assert!(config.port > 0);
+    }
+
+    #[test]
+    fn test_gravity_wave_config_defaults() {
+        let config = GravityWaveConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.wave_speed, 300.0);
+        assert_eq!(config.wave_base_impulse, 180.0);
+        assert_eq!(config.min_explosion_delay, 30.0);
+        assert_eq!(config.max_explosion_delay, 90.0);
+    }
+
+    #[test]
+    fn test_gravity_wave_random_delay() {
+        let config = GravityWaveConfig::default();
+        let delay = config.random_explosion_delay();
+        assert!(delay >= config.min_explosion_delay);
+        assert!(delay <= config.max_explosion_delay);
+    }
 
     #[test]
     fn test_debris_spawn_config_defaults() {
@@ -1177,6 +5429,7 @@ mod tests {
         let config = GravityConfig::default();
         assert_eq!(config.range_mode, GravityRangeMode::Limited);
         assert_eq!(config.influence_radius, 5000.0);
+        assert_eq!(config.strength, 1.0);
     }
 
     #[test]
@@ -1202,4 +5455,369 @@ mod tests {
         config.api_key = Some("test-key".to_string());
         assert!(config.is_active()); // Now active
     }
+
+    #[test]
+    fn test_narrator_active_is_independent_of_tuning() {
+        let mut config = AIManagerConfig {
+            api_key: Some("test-key".to_string()),
+            ..Default::default()
+        };
+        assert!(!config.narrator_active());
+
+        config.narrator_enabled = true;
+        assert!(!config.is_active());
+        assert!(config.narrator_active());
+
+        config.narrator_max_calls_per_hour = 0;
+        assert!(!config.narrator_active());
+    }
+
+    #[test]
+    fn test_redaction_policy_from_str() {
+        assert_eq!("full".parse(), Ok(RedactionPolicy::Full));
+        assert_eq!("TRUNCATE".parse(), Ok(RedactionPolicy::Truncate));
+        assert_eq!("hash".parse(), Ok(RedactionPolicy::Hash));
+        assert_eq!("omit".parse(), Ok(RedactionPolicy::Omit));
+        assert_eq!("bogus".parse::<RedactionPolicy>(), Err(InvalidRedactionPolicy));
+    }
+
+    #[test]
+    fn test_privacy_config_defaults() {
+        let config = PrivacyConfig::default();
+        assert_eq!(config.ip_policy, RedactionPolicy::Truncate);
+        assert_eq!(config.name_policy, RedactionPolicy::Full);
+        assert!(config.scrub_ai_text);
+        // Hashes are never unsalted
+        assert_eq!(config.hash_salt.len(), 32);
+        assert_ne!(config.hash_salt, PrivacyConfig::default().hash_salt);
+    }
+
+    #[test]
+    fn test_scripting_config_defaults() {
+        let config = ScriptingConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.script_dir, "scripts");
+        assert_eq!(config.interval_ticks, 30);
+        assert!(config.fuel_per_call > 0);
+    }
+
+    #[test]
+    fn test_chatter_config_defaults() {
+        let config = ChatterConfig::default();
+        assert!(config.enabled);
+        assert!(config.phrases_path.is_none());
+        assert!(config.taunt_chance > 0.0 && config.taunt_chance <= 1.0);
+        assert!(config.max_per_minute > 0);
+    }
+
+    #[test]
+    fn test_join_queue_config_defaults() {
+        let config = JoinQueueConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.max_humans, 0);
+        assert!(config.reserved_slots > 0);
+        assert!(config.default_secs_per_slot > 0);
+    }
+
+    #[test]
+    fn test_snapshot_history_config_defaults() {
+        let config = SnapshotHistoryConfig::default();
+        assert_eq!(config.capacity, 0);
+        assert!(config.default_epsilon > 0.0);
+    }
+
+    #[test]
+    fn test_desync_config_defaults() {
+        let config = DesyncConfig::default();
+        assert!(config.enabled);
+        assert!(config.history_ticks > 0);
+        assert!(config.warn_streak > 1);
+    }
+
+    #[test]
+    fn test_interp_delay_config_defaults() {
+        let config = InterpDelayConfig::default();
+        assert!(config.enabled);
+        assert!(config.min_ms <= config.max_ms);
+        assert!(config.buffer_snapshots >= 1.0);
+    }
+
+    #[test]
+    fn test_send_pacing_config_defaults() {
+        let config = SendPacingConfig::default();
+        assert!(!config.enabled);
+        assert!(config.slots > 1);
+    }
+
+    #[test]
+    fn test_snapshot_rate_config_defaults() {
+        let config = SnapshotRateConfig::default();
+        assert!(config.adaptive);
+        assert!(!config.allow_high);
+        assert!(config.loss_recover < config.loss_degrade);
+        assert!(config.high_max_rtt_ms < config.rtt_degrade_ms);
+    }
+
+    #[test]
+    fn test_aoi_culling_config_defaults() {
+        let config = AoiCullingConfig::default();
+        assert!(config.min_apparent_radius_px > 0.0);
+    }
+
+    #[test]
+    fn test_collision_config_pairs() {
+        let mut config = CollisionConfig::default();
+        assert_eq!(config.matrix, CollisionMatrix::default());
+        assert!((0.0..=1.0).contains(&config.response.restitution));
+        assert_eq!(config.response.knockback_scale, 1.0);
+
+        config.apply_pairs(
+            "COLLISION_ENABLE",
+            "projectile:debris, bogus, ghost:wall",
+            true,
+        );
+        config.apply_pairs("COLLISION_DISABLE", "ghost:hazard", false);
+        assert!(config
+            .matrix
+            .interacts(CollisionLayer::Debris, CollisionLayer::Projectile));
+        assert!(!config
+            .matrix
+            .interacts(CollisionLayer::Ghost, CollisionLayer::Hazard));
+        assert!(config
+            .matrix
+            .interacts(CollisionLayer::Player, CollisionLayer::Hazard));
+    }
+
+    #[test]
+    fn test_orbit_assist_config_defaults() {
+        let config = OrbitAssistConfig::default();
+        assert!(config.enabled);
+        assert!(config.max_correction > 0.0 && config.max_correction < crate::game::constants::boost::BASE_THRUST);
+    }
+
+    #[test]
+    fn test_join_stream_config_defaults() {
+        let config = JoinStreamConfig::default();
+        assert!(config.enabled);
+        assert!((16..=4096).contains(&config.chunk_entities));
+        assert!(config.chunks_per_tick >= 1);
+    }
+
+    #[test]
+    fn test_region_hint_config_defaults() {
+        let config = RegionHintConfig::default();
+        assert!(config.enabled);
+        assert!((5..=600).contains(&config.interval_ticks));
+        assert!(config.max_cells >= 1);
+    }
+
+    #[test]
+    fn test_broadcast_config_defaults() {
+        let config = BroadcastConfig::default();
+        assert!(config.workers >= 1);
+        assert!(config.queue_depth >= 1);
+    }
+
+    #[test]
+    fn test_tick_watchdog_config_defaults() {
+        let config = TickWatchdogConfig::default();
+        assert!(config.enabled);
+        assert!(config.spike_ms > crate::game::constants::physics::TICK_DURATION_MS);
+        assert!(config.max_captures > 0);
+    }
+
+    #[test]
+    fn test_runtime_config_defaults() {
+        let config = RuntimeConfig::default();
+        assert!(config.tokio_workers.is_none());
+        assert!(config.rayon_threads.is_none());
+        assert!(config.game_loop_core.is_none());
+    }
+
+    #[test]
+    fn test_hibernation_config_defaults() {
+        let config = HibernationConfig::default();
+        assert!(config.enabled);
+        assert!(config.idle_minutes > 0);
+        assert!((1..=5).contains(&config.tick_hz));
+    }
+
+    #[test]
+    fn test_report_config_defaults() {
+        let config = ReportConfig::default();
+        assert!(config.max_per_window > 0);
+        assert!(config.review_threshold > 0.0);
+        assert!(config.low_karma_threshold < 100.0);
+    }
+
+    #[test]
+    fn test_tutorial_config_defaults() {
+        let config = TutorialConfig::default();
+        assert!(config.enabled);
+        assert!(config.profiles_path.is_none());
+        assert!(config.orbit_secs > 0.0);
+        assert!(config.debris_goal > 0);
+    }
+
+    #[test]
+    fn test_match_history_config_defaults() {
+        let config = MatchHistoryConfig::default();
+        assert!(config.path.is_none());
+        assert!(config.capacity > 0);
+    }
+
+    #[test]
+    fn test_lobby_queue_config_defaults() {
+        let config = LobbyQueueConfig::default();
+        assert!(config.mmr_bucket_size > 0 && config.window > 0);
+        assert!(config.backfill_after_secs > config.estimate_interval_secs);
+    }
+
+    #[test]
+    fn test_room_browser_config_defaults() {
+        let config = RoomBrowserConfig::default();
+        assert!(!config.region.is_empty());
+        assert!((1..=100).contains(&config.page_size));
+    }
+
+    #[test]
+    fn test_practice_config_defaults() {
+        let config = PracticeConfig::default();
+        assert_eq!(config.gravity_strength, 1.0);
+        assert!(config.initial_targets <= crate::game::scenario::MAX_TARGETS);
+        assert!(config.readout_secs > 0.0);
+    }
+
+    #[test]
+    fn test_spectator_delay_config_defaults() {
+        let config = SpectatorDelayConfig::default();
+        assert_eq!(config.delay_secs, 0);
+        assert!(config.exempt_casters);
+    }
+
+    #[test]
+    fn test_required_encryption_forces_spectator_delay() {
+        let config = SpectatorDelayConfig::default().for_encryption(SnapshotEncryptionMode::Required);
+        assert_eq!(config.delay_secs, REQUIRED_ENCRYPTION_SPECTATOR_DELAY_SECS);
+        assert!(!config.exempt_casters);
+
+        let longer = SpectatorDelayConfig { delay_secs: 120, exempt_casters: true };
+        assert_eq!(longer.clone().for_encryption(SnapshotEncryptionMode::Required).delay_secs, 120);
+        assert!(longer.for_encryption(SnapshotEncryptionMode::Optional).exempt_casters);
+    }
+
+    #[test]
+    fn test_heatmap_config_defaults() {
+        let config = HeatmapConfig::default();
+        assert!(config.cell_size > 0.0);
+        assert!(config.sample_secs > 0.0);
+        assert!(config.path.is_none());
+    }
+
+    #[test]
+    fn test_bot_placement_config_defaults() {
+        let config = BotPlacementConfig::default();
+        assert!((0.0..=1.0).contains(&config.near_human_share));
+        assert!(config.near_human_radius > 0.0);
+    }
+
+    #[test]
+    fn test_boundary_config_defaults() {
+        let config = BoundaryConfig::default();
+        assert!(config.stiffness > 0.0 && config.damping > 0.0);
+        assert!(config.max_accel < crate::game::constants::boost::BASE_THRUST);
+        assert!(config.drain_ramp >= 1.0);
+    }
+
+    #[test]
+    fn test_physics_config_defaults() {
+        assert_eq!(PhysicsConfig::default().integrator, Integrator::Legacy);
+    }
+
+    #[test]
+    fn test_well_capture_config_defaults() {
+        let config = WellCaptureConfig::default();
+        assert!(config.enabled);
+        assert!(config.capture_secs > 0.0);
+        assert!(config.ring_multiplier > 3.0);
+    }
+
+    #[test]
+    fn test_combat_resolver_config_defaults() {
+        let config = CombatResolverConfig::default();
+        assert!(config.enabled);
+        assert!(config.human_clearance > config.engagement_range);
+        assert!(config.interval_ticks > 0);
+    }
+
+    #[test]
+    fn test_faction_config_defaults() {
+        let config = FactionConfig::default();
+        assert!(!config.enabled);
+        assert!((2..=8).contains(&config.count));
+        assert!(config.raid_interval_secs > config.raid_duration_secs);
+    }
+
+    #[test]
+    fn test_difficulty_config_defaults() {
+        let config = DifficultyConfig::default();
+        assert!(config.enabled);
+        assert!(config.max_deaths_per_min > config.target_deaths_per_min);
+        assert!((0.1..=1.0).contains(&config.min_multiplier));
+    }
+
+    #[test]
+    fn test_projectile_economy_config_defaults() {
+        // Defaults keep the classic economy: pay the projectile's mass, no refund
+        let config = ProjectileEconomyConfig::default();
+        assert_eq!(config.cost_multiplier(0.0), 1.0);
+        assert_eq!(config.cost_multiplier(1.0), 1.0);
+        assert_eq!(config.miss_refund, 0.0);
+
+        let tuned = ProjectileEconomyConfig { tap_cost: 0.5, full_charge_cost: 2.0, ..config };
+        assert_eq!(tuned.cost_multiplier(0.5), 1.25);
+        assert_eq!(tuned.cost_multiplier(3.0), 2.0);
+    }
+
+    #[test]
+    fn test_heat_config_defaults() {
+        let config = HeatConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.fire_heat(0.0), config.fire_tap);
+        assert_eq!(config.fire_heat(1.0), config.fire_full);
+        assert!(config.fire_full <= 1.0);
+    }
+
+    #[test]
+    fn test_asteroid_loot_parsing() {
+        let loot = AsteroidConfig::parse_loot("small:2-4, large:0-1").unwrap();
+        assert_eq!(
+            loot,
+            vec![
+                AsteroidLoot { size: DebrisSize::Small, min: 2, max: 4 },
+                AsteroidLoot { size: DebrisSize::Large, min: 0, max: 1 },
+            ]
+        );
+        assert!(AsteroidConfig::parse_loot("small:4-2").is_none());
+        assert!(AsteroidConfig::parse_loot("huge:1-2").is_none());
+        assert!(!AsteroidConfig::default().enabled);
+    }
+
+    #[test]
+    fn test_weather_config_defaults() {
+        let config = WeatherConfig::default();
+        assert!(!config.enabled);
+        assert!((0.0..=1.0).contains(&config.chance));
+    }
+
+    #[test]
+    fn test_scheduled_events_skip_invalid_entries() {
+        use crate::game::schedule::ScheduledAction;
+
+        let list = " 0 * * * *|boss ; 61 * * * *|boss; 0 4 * * *|teleport;0 4 * * *|arena_reset;";
+        let events = ScheduleConfig::parse_events(list);
+        let actions: Vec<_> = events.iter().map(|e| e.action).collect();
+        assert_eq!(actions, vec![ScheduledAction::BossSpawn, ScheduledAction::ArenaReset]);
+        assert!(ScheduleConfig::default().events.is_empty());
+    }
 }
@@ -0,0 +1,407 @@
+//! Export of match data to S3-compatible object storage
+//!
+//! A background job uploads what the server writes to disk, so hosts don't
+//! need sidecar scripts to ship it: finished match replays and highlight
+//! clips (once each, after their index is written), the heatmap totals file
+//! (a new object each time it changes) and the match history log, which is
+//! append-only and is rotated here by uploading the lines added since the
+//! last pass as their own object. Objects are keyed
+//! `<prefix>/<kind>/<name>` and tagged with `kind` plus the configured tags,
+//! so bucket lifecycle rules can expire or archive each kind on its own.
+//!
+//! An upload that fails is retried with backoff a few times, then left for
+//! the next pass; nothing is marked uploaded until the store accepts it. What
+//! has been uploaded is kept in a small state file so restarts don't upload
+//! everything again.
+//!
+//! Scanning, keys and signing live here; the HTTP uploader is in [`s3`]
+//! behind the `s3_export` feature.
+
+// Only the uploader drives the exporter
+#![cfg_attr(not(feature = "s3_export"), allow(dead_code))]
+
+pub mod sigv4;
+#[cfg(feature = "s3_export")]
+pub mod s3;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::ExportConfig;
+use crate::net::replay::{ReplayReader, REPLAY_EXTENSION};
+
+/// First retry delay; doubles per attempt
+const RETRY_BASE: Duration = Duration::from_secs(1);
+
+/// Longest retry delay
+const RETRY_MAX: Duration = Duration::from_secs(60);
+
+/// What a source holds; also the key segment and `kind` tag of its objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    Replays,
+    Highlights,
+    Heatmaps,
+    Matches,
+}
+
+impl ExportKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportKind::Replays => "replays",
+            ExportKind::Highlights => "highlights",
+            ExportKind::Heatmaps => "heatmaps",
+            ExportKind::Matches => "matches",
+        }
+    }
+}
+
+/// How a source's files are written, which decides when they are uploaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceLayout {
+    /// Directory of replay files, each uploaded once it is finished
+    Replays { dir: String },
+    /// One file rewritten in place, uploaded whenever it changes
+    Snapshot { path: String },
+    /// Append-only JSON lines, uploaded in chunks of new complete lines
+    AppendLog { path: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportSource {
+    pub kind: ExportKind,
+    pub layout: SourceLayout,
+}
+
+/// What has been uploaded, saved to the state file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct ExportState {
+    /// Object keys of uploaded replay files
+    #[serde(default)]
+    uploaded: BTreeSet<String>,
+    /// Modification time (Unix seconds) of each snapshot file when last uploaded
+    #[serde(default)]
+    snapshots: BTreeMap<String, u64>,
+    /// Bytes of each log already uploaded
+    #[serde(default)]
+    log_offsets: BTreeMap<String, u64>,
+}
+
+/// Where an upload's body comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadBody {
+    File(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+/// Progress recorded once an upload succeeds
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Progress {
+    File,
+    Snapshot { path: String, modified: u64 },
+    Log { path: String, offset: u64 },
+}
+
+/// One object to upload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upload {
+    pub key: String,
+    pub kind: ExportKind,
+    pub body: UploadBody,
+    progress: Progress,
+}
+
+impl Upload {
+    /// Read the body (files are read only when their upload starts)
+    pub fn read_body(&self) -> std::io::Result<Vec<u8>> {
+        match &self.body {
+            UploadBody::File(path) => fs::read(path),
+            UploadBody::Bytes(bytes) => Ok(bytes.clone()),
+        }
+    }
+}
+
+/// Finds what to upload and remembers what was
+#[derive(Debug)]
+pub struct Exporter {
+    key_prefix: String,
+    tags: Vec<(String, String)>,
+    sources: Vec<ExportSource>,
+    state: ExportState,
+    state_path: Option<String>,
+}
+
+impl Exporter {
+    pub fn new(config: &ExportConfig, sources: Vec<ExportSource>) -> Self {
+        let state = config
+            .state_path
+            .as_deref()
+            .filter(|path| Path::new(path).exists())
+            .and_then(|path| match fs::read_to_string(path).map(|s| serde_json::from_str(&s)) {
+                Ok(Ok(state)) => Some(state),
+                Ok(Err(e)) => {
+                    warn!("Ignoring unreadable export state {}: {}", path, e);
+                    None
+                }
+                Err(e) => {
+                    warn!("Failed to read export state {}: {}", path, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self {
+            key_prefix: config.key_prefix.clone(),
+            tags: config.tags.clone(),
+            sources,
+            state,
+            state_path: config.state_path.clone(),
+        }
+    }
+
+    /// Object key of a file name under a kind
+    pub fn object_key(&self, kind: ExportKind, name: &str) -> String {
+        if self.key_prefix.is_empty() {
+            format!("{}/{}", kind.name(), name)
+        } else {
+            format!("{}/{}/{}", self.key_prefix, kind.name(), name)
+        }
+    }
+
+    /// `x-amz-tagging` value for objects of a kind
+    pub fn tagging(&self, kind: ExportKind) -> String {
+        std::iter::once(("kind", kind.name()))
+            .chain(self.tags.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .map(|(k, v)| format!("{}={}", sigv4::uri_encode(k, false), sigv4::uri_encode(v, false)))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// Everything due for upload, at Unix time `now`
+    pub fn pending(&self, now: u64) -> Vec<Upload> {
+        let mut uploads = Vec::new();
+        for source in &self.sources {
+            let result = match &source.layout {
+                SourceLayout::Replays { dir } => self.pending_replays(source.kind, dir, &mut uploads),
+                SourceLayout::Snapshot { path } => self.pending_snapshot(source.kind, path, now, &mut uploads),
+                SourceLayout::AppendLog { path } => self.pending_log(source.kind, path, now, &mut uploads),
+            };
+            if let Err(e) = result {
+                warn!("Export: skipping {} this pass: {}", source.kind.name(), e);
+            }
+        }
+        uploads
+    }
+
+    fn pending_replays(&self, kind: ExportKind, dir: &str, uploads: &mut Vec<Upload>) -> std::io::Result<()> {
+        if !Path::new(dir).exists() {
+            return Ok(());
+        }
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(REPLAY_EXTENSION))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let key = self.object_key(kind, name);
+            // A replay still being recorded has no index yet
+            let finished = fs::File::open(&path).is_ok_and(|file| ReplayReader::open(file).is_ok());
+            if finished && !self.state.uploaded.contains(&key) {
+                uploads.push(Upload { key, kind, body: UploadBody::File(path), progress: Progress::File });
+            }
+        }
+        Ok(())
+    }
+
+    fn pending_snapshot(
+        &self,
+        kind: ExportKind,
+        path: &str,
+        now: u64,
+        uploads: &mut Vec<Upload>,
+    ) -> std::io::Result<()> {
+        let Ok(metadata) = fs::metadata(path) else {
+            return Ok(());
+        };
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        if self.state.snapshots.get(path) == Some(&modified) {
+            return Ok(());
+        }
+        let body = fs::read(path)?;
+        // Skip a file caught mid-write; the next pass sees the whole one
+        if serde_json::from_slice::<serde_json::Value>(&body).is_err() {
+            return Ok(());
+        }
+        let file = Path::new(path);
+        let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or(kind.name());
+        let name = match file.extension().and_then(|e| e.to_str()) {
+            Some(extension) => format!("{}-{}.{}", stem, now, extension),
+            None => format!("{}-{}", stem, now),
+        };
+        uploads.push(Upload {
+            key: self.object_key(kind, &name),
+            kind,
+            body: UploadBody::Bytes(body),
+            progress: Progress::Snapshot { path: path.to_string(), modified },
+        });
+        Ok(())
+    }
+
+    fn pending_log(&self, kind: ExportKind, path: &str, now: u64, uploads: &mut Vec<Upload>) -> std::io::Result<()> {
+        let Ok(mut file) = fs::File::open(path) else {
+            return Ok(());
+        };
+        let len = file.metadata()?.len();
+        // A log shorter than what was uploaded was replaced; start it over
+        let offset = self.state.log_offsets.get(path).copied().filter(|&offset| offset <= len).unwrap_or(0);
+        file.seek(SeekFrom::Start(offset))?;
+        let mut tail = Vec::new();
+        file.read_to_end(&mut tail)?;
+        // Only complete lines; a partly written one waits for the next pass
+        let Some(end) = tail.iter().rposition(|&b| b == b'\n').map(|i| i + 1) else {
+            return Ok(());
+        };
+        tail.truncate(end);
+        let stem = Path::new(path).file_stem().and_then(|s| s.to_str()).unwrap_or(kind.name());
+        uploads.push(Upload {
+            key: self.object_key(kind, &format!("{}-{}-{}.jsonl", stem, now, offset)),
+            kind,
+            body: UploadBody::Bytes(tail),
+            progress: Progress::Log { path: path.to_string(), offset: offset + end as u64 },
+        });
+        Ok(())
+    }
+
+    /// Record a successful upload and save the state file
+    pub fn complete(&mut self, upload: &Upload) {
+        match &upload.progress {
+            Progress::File => {
+                self.state.uploaded.insert(upload.key.clone());
+            }
+            Progress::Snapshot { path, modified } => {
+                self.state.snapshots.insert(path.clone(), *modified);
+            }
+            Progress::Log { path, offset } => {
+                self.state.log_offsets.insert(path.clone(), *offset);
+            }
+        }
+        if let Err(e) = self.save() {
+            warn!("Failed to save export state: {}", e);
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.state_path else {
+            return Ok(());
+        };
+        let contents =
+            serde_json::to_string(&self.state).map_err(|e| format!("Failed to serialize export state: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write export state file: {}", e))
+    }
+}
+
+/// Wait before retry number `attempt` (1-based) of a failed upload
+pub fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE.saturating_mul(1 << attempt.saturating_sub(1).min(16)).min(RETRY_MAX)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::config::ExportConfig;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("orbit_export_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_log_is_rotated_in_chunks_of_complete_lines() {
+        let dir = temp_dir("log");
+        let log = dir.join("matches.jsonl");
+        let path = log.to_str().unwrap().to_string();
+        let config = ExportConfig {
+            key_prefix: "host-1".to_string(),
+            state_path: Some(dir.join("state.json").to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        let source = ExportSource { kind: ExportKind::Matches, layout: SourceLayout::AppendLog { path: path.clone() } };
+        let mut exporter = Exporter::new(&config, vec![source.clone()]);
+
+        fs::write(&log, "{\"id\":1}\n{\"id\":2}\n{\"id\"").unwrap();
+        let uploads = exporter.pending(100);
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].key, "host-1/matches/matches-100-0.jsonl");
+        assert_eq!(uploads[0].read_body().unwrap(), b"{\"id\":1}\n{\"id\":2}\n");
+
+        // Nothing moves until the store accepts the upload
+        assert_eq!(exporter.pending(101).len(), 1);
+        exporter.complete(&uploads[0]);
+        assert!(exporter.pending(102).is_empty());
+
+        // The rest of the line arrives; a restarted exporter carries on from the state file
+        fs::write(&log, "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n").unwrap();
+        let restarted = Exporter::new(&config, vec![source]);
+        let uploads = restarted.pending(200);
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(uploads[0].key, "host-1/matches/matches-200-18.jsonl");
+        assert_eq!(uploads[0].read_body().unwrap(), b"{\"id\":3}\n");
+    }
+
+    #[test]
+    fn test_snapshots_upload_when_changed_and_replays_once_finished() {
+        let dir = temp_dir("files");
+        let heatmap = dir.join("heatmaps.json");
+        let replays = dir.join("replays");
+        fs::create_dir_all(&replays).unwrap();
+        fs::write(replays.join("match-1-0.orbr"), b"ORBR unfinished").unwrap();
+        fs::write(&heatmap, "{\"layers\":{}}").unwrap();
+
+        let config = ExportConfig { tags: vec![("retention".to_string(), "90 days".to_string())], ..Default::default() };
+        let mut exporter = Exporter::new(
+            &config,
+            vec![
+                ExportSource {
+                    kind: ExportKind::Replays,
+                    layout: SourceLayout::Replays { dir: replays.to_str().unwrap().to_string() },
+                },
+                ExportSource {
+                    kind: ExportKind::Heatmaps,
+                    layout: SourceLayout::Snapshot { path: heatmap.to_str().unwrap().to_string() },
+                },
+            ],
+        );
+
+        let uploads = exporter.pending(50);
+        // The unfinished replay is left alone
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].key, "heatmaps/heatmaps-50.json");
+        assert_eq!(exporter.tagging(ExportKind::Heatmaps), "kind=heatmaps&retention=90%20days");
+        exporter.complete(&uploads[0]);
+        let unchanged = exporter.pending(60);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(unchanged.is_empty());
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_to_a_cap() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(3), Duration::from_secs(4));
+        assert_eq!(retry_delay(30), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,151 @@
+//! S3 uploader for the exporter
+//!
+//! Each pass asks the [`Exporter`] what is due and PUTs it, path-style
+//! (`<endpoint>/<bucket>/<key>`), signed with SigV4 and tagged for lifecycle
+//! rules. A failed upload is retried with backoff up to the configured
+//! attempts; after that the pass ends and the next one starts over with
+//! whatever is still pending.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, info, warn};
+
+use crate::config::ExportConfig;
+use crate::export::sigv4::{self, Credentials, SigningRequest};
+use crate::export::{retry_delay, ExportSource, Exporter, Upload};
+use crate::server::ShutdownToken;
+
+/// Longest a single upload may take
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Start exporting if EXPORT_S3_BUCKET is set (runs until `shutdown` is triggered)
+pub fn start_exporter(config: ExportConfig, sources: Vec<ExportSource>, shutdown: ShutdownToken) {
+    let Some(bucket) = config.bucket.clone() else {
+        return;
+    };
+    if sources.is_empty() {
+        warn!("Export: EXPORT_S3_BUCKET is set, but nothing is written to disk to export");
+        return;
+    }
+    let client = match reqwest::Client::builder().timeout(UPLOAD_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("Export: failed to create HTTP client: {}", e);
+            return;
+        }
+    };
+    let kinds: Vec<_> = sources.iter().map(|s| s.kind.name()).collect();
+    info!("Export: uploading {} to {}/{} every {}s", kinds.join(", "), config.endpoint, bucket, config.interval_secs);
+
+    tokio::spawn(async move {
+        let mut exporter = Exporter::new(&config, sources);
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+        let stopped = shutdown.triggered();
+        tokio::pin!(stopped);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = &mut stopped => break,
+            }
+            let pass = run_pass(&client, &config, &bucket, &mut exporter);
+            tokio::select! {
+                _ = pass => {}
+                _ = &mut stopped => break,
+            }
+        }
+        info!("Export stopped");
+    });
+}
+
+/// Upload everything pending, stopping at the first upload that keeps failing
+async fn run_pass(client: &reqwest::Client, config: &ExportConfig, bucket: &str, exporter: &mut Exporter) {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    for upload in exporter.pending(now) {
+        let body = match upload.read_body() {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Export: failed to read {}: {}", upload.key, e);
+                continue;
+            }
+        };
+        let tagging = exporter.tagging(upload.kind);
+        let mut attempt = 1;
+        loop {
+            match put(client, config, bucket, &upload, &body, &tagging).await {
+                Ok(()) => {
+                    debug!("Export: uploaded {} ({} bytes)", upload.key, body.len());
+                    exporter.complete(&upload);
+                    break;
+                }
+                Err(e) if attempt < config.max_attempts => {
+                    let delay = retry_delay(attempt);
+                    warn!("Export: upload of {} failed: {} (retrying in {:?})", upload.key, e, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    warn!("Export: giving up on {} until the next pass: {}", upload.key, e);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// PUT one object
+async fn put(
+    client: &reqwest::Client,
+    config: &ExportConfig,
+    bucket: &str,
+    upload: &Upload,
+    body: &[u8],
+    tagging: &str,
+) -> Result<(), String> {
+    let host = config.endpoint.split_once("://").map_or(config.endpoint.as_str(), |(_, rest)| rest);
+    let host = host.split('/').next().unwrap_or(host);
+    let path = format!("/{}/{}", sigv4::uri_encode(bucket, false), sigv4::uri_encode(&upload.key, true));
+    let payload_hash = sigv4::sha256_hex(body);
+    let amz_date = sigv4::amz_date(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()));
+
+    let headers = [
+        ("host", host),
+        ("x-amz-content-sha256", payload_hash.as_str()),
+        ("x-amz-date", amz_date.as_str()),
+        ("x-amz-tagging", tagging),
+    ];
+    let credentials = Credentials {
+        access_key_id: config.access_key_id.clone(),
+        secret_access_key: config.secret_access_key.clone(),
+    };
+    let authorization = sigv4::authorization(
+        &SigningRequest {
+            method: "PUT",
+            path: &path,
+            query: "",
+            headers: &headers,
+            payload_hash: &payload_hash,
+            amz_date: &amz_date,
+            region: &config.region,
+            service: "s3",
+        },
+        &credentials,
+    );
+
+    let response = client
+        .put(format!("{}{}", config.endpoint, path))
+        .header("x-amz-content-sha256", &payload_hash)
+        .header("x-amz-date", &amz_date)
+        .header("x-amz-tagging", tagging)
+        .header("authorization", authorization)
+        .body(body.to_vec())
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        let text = response.text().await.unwrap_or_default();
+        Err(format!("{}: {}", status, text.chars().take(200).collect::<String>()))
+    }
+}
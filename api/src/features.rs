@@ -0,0 +1,201 @@
+//! Compiled features and the startup self-check
+//!
+//! Optional subsystems are cargo features. A build without one swaps its
+//! shared state for a `()` placeholder and keeps running, so settings meant
+//! for the missing subsystem would be ignored without a word. [`FeatureSet`]
+//! records what this binary was built with; it is logged at startup and
+//! served on `/info`. Before anything starts, [`FeatureSet::self_check`]
+//! compares it with the environment: settings for a subsystem that wasn't
+//! built are errors that stop startup, combinations that run with reduced
+//! behavior are warnings.
+
+use serde::Serialize;
+
+/// Cargo features this binary was built with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FeatureSet {
+    pub anticheat: bool,
+    pub lobby: bool,
+    pub dos_ratelimit: bool,
+    pub metrics_extended: bool,
+    pub advanced_physics: bool,
+    pub ai_manager: bool,
+    pub scripting: bool,
+    pub cluster: bool,
+    pub profiling: bool,
+    pub s3_export: bool,
+    pub minimal: bool,
+}
+
+/// Environment settings that only take effect with a feature
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeatureSettings {
+    /// `TENANTS_FILE` is set (hosted rooms are lobby rooms)
+    pub tenants_file: bool,
+    /// `MATCH_HISTORY_PATH` is set (the lobby records matches)
+    pub match_history_path: bool,
+    /// `SCRIPTING_ENABLED` is on
+    pub scripting_enabled: bool,
+    /// `CLUSTER_REDIS_URL` is set
+    pub cluster_redis_url: bool,
+    /// `AI_ENABLED` is on
+    pub ai_enabled: bool,
+    /// `EXPORT_S3_BUCKET` is set
+    pub export_s3_bucket: bool,
+}
+
+impl FeatureSettings {
+    pub fn from_env() -> Self {
+        let flag = |name: &str| std::env::var(name).is_ok_and(|v| v.to_lowercase() == "true" || v == "1");
+        let set = |name: &str| std::env::var(name).is_ok_and(|v| !v.is_empty());
+        Self {
+            tenants_file: set("TENANTS_FILE"),
+            match_history_path: set("MATCH_HISTORY_PATH"),
+            scripting_enabled: flag("SCRIPTING_ENABLED"),
+            cluster_redis_url: set("CLUSTER_REDIS_URL"),
+            ai_enabled: flag("AI_ENABLED"),
+            export_s3_bucket: set("EXPORT_S3_BUCKET"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Runs, but something configured has no effect
+    Warning,
+    /// Refuses to start
+    Error,
+}
+
+/// One inconsistency found by the self-check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeatureIssue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl FeatureIssue {
+    fn warning(message: &str) -> Self {
+        Self { severity: Severity::Warning, message: message.to_string() }
+    }
+
+    fn error(message: &str) -> Self {
+        Self { severity: Severity::Error, message: message.to_string() }
+    }
+}
+
+impl FeatureSet {
+    /// Features of the running binary
+    pub const fn compiled() -> Self {
+        Self {
+            anticheat: cfg!(feature = "anticheat"),
+            lobby: cfg!(feature = "lobby"),
+            dos_ratelimit: cfg!(feature = "dos_ratelimit"),
+            metrics_extended: cfg!(feature = "metrics_extended"),
+            advanced_physics: cfg!(feature = "advanced_physics"),
+            ai_manager: cfg!(feature = "ai_manager"),
+            scripting: cfg!(feature = "scripting"),
+            cluster: cfg!(feature = "cluster"),
+            profiling: cfg!(feature = "profiling"),
+            s3_export: cfg!(feature = "s3_export"),
+            minimal: cfg!(feature = "minimal"),
+        }
+    }
+
+    /// Names of the enabled features, in Cargo.toml order
+    pub fn enabled(&self) -> Vec<&'static str> {
+        [
+            ("anticheat", self.anticheat),
+            ("lobby", self.lobby),
+            ("dos_ratelimit", self.dos_ratelimit),
+            ("metrics_extended", self.metrics_extended),
+            ("advanced_physics", self.advanced_physics),
+            ("ai_manager", self.ai_manager),
+            ("scripting", self.scripting),
+            ("cluster", self.cluster),
+            ("profiling", self.profiling),
+            ("s3_export", self.s3_export),
+            ("minimal", self.minimal),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+    }
+
+    /// Check that the features wire together and cover what the environment
+    /// asks for. Errors first.
+    pub fn self_check(&self, settings: &FeatureSettings) -> Vec<FeatureIssue> {
+        let mut issues = Vec::new();
+        if settings.tenants_file && !self.lobby {
+            issues.push(FeatureIssue::error("TENANTS_FILE is set, but hosted rooms need the lobby feature"));
+        }
+        if settings.scripting_enabled && !self.scripting {
+            issues.push(FeatureIssue::error("SCRIPTING_ENABLED is on, but the scripting feature is not built"));
+        }
+        if settings.cluster_redis_url && !self.cluster {
+            issues.push(FeatureIssue::error("CLUSTER_REDIS_URL is set, but the cluster feature is not built"));
+        }
+        if settings.export_s3_bucket && !self.s3_export {
+            issues.push(FeatureIssue::error("EXPORT_S3_BUCKET is set, but the s3_export feature is not built"));
+        }
+        if settings.match_history_path && !self.lobby {
+            issues.push(FeatureIssue::warning("MATCH_HISTORY_PATH has no effect without the lobby feature"));
+        }
+        if settings.ai_enabled && !self.ai_manager {
+            issues.push(FeatureIssue::warning("AI_ENABLED has no effect without the ai_manager feature"));
+        }
+        if self.anticheat && !self.lobby {
+            issues.push(FeatureIssue::warning(
+                "anticheat without lobby: bans apply at connect, but low-karma matchmaking is off",
+            ));
+        }
+        let optional = self.enabled().len() - usize::from(self.minimal);
+        if self.minimal && optional > 0 {
+            issues.push(FeatureIssue::warning(
+                "minimal is built with other features; build it with --no-default-features",
+            ));
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn none() -> FeatureSet {
+        FeatureSet {
+            anticheat: false,
+            lobby: false,
+            dos_ratelimit: false,
+            metrics_extended: false,
+            advanced_physics: false,
+            ai_manager: false,
+            scripting: false,
+            cluster: false,
+            profiling: false,
+            s3_export: false,
+            minimal: false,
+        }
+    }
+
+    #[test]
+    fn test_compiled_matches_cfg() {
+        let features = FeatureSet::compiled();
+        assert_eq!(features.lobby, cfg!(feature = "lobby"));
+        assert_eq!(features.enabled().contains(&"anticheat"), cfg!(feature = "anticheat"));
+    }
+
+    #[test]
+    fn test_self_check_flags_settings_for_missing_features() {
+        let settings = FeatureSettings { tenants_file: true, ai_enabled: true, ..Default::default() };
+        let features = FeatureSet { anticheat: true, minimal: true, ..none() };
+        let severities: Vec<_> = features.self_check(&settings).iter().map(|issue| issue.severity).collect();
+        assert_eq!(severities, [Severity::Error, Severity::Warning, Severity::Warning, Severity::Warning]);
+
+        let full = FeatureSet { anticheat: true, lobby: true, ai_manager: true, ..none() };
+        assert!(full.self_check(&settings).is_empty());
+        assert!(FeatureSet { minimal: true, ..none() }.self_check(&FeatureSettings::default()).is_empty());
+    }
+}
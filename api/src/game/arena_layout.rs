@@ -0,0 +1,324 @@
+//! Hand-made arena layouts
+//!
+//! Operators can replace procedural well placement with a layout file made in
+//! an arena editor: the arena's size, its orbital wells (position, mass, core),
+//! zones humans spawn in and hazards (fixed asteroids). A layout arena keeps
+//! its size and wells: scaling leaves it alone, and exploding wells pulse a
+//! wave but stay where they are. Layouts are validated against the same
+//! density rules procedural arenas follow, and one only holds as many players
+//! as procedural scaling would give its area; past that the arena falls back
+//! to procedural scaling.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::{ArenaLayoutConfig, ArenaScalingConfig};
+use crate::game::constants::{arena, physics::CENTRAL_MASS};
+use crate::game::state::{Arena, Asteroid, GameState, GravityWell, CENTRAL_WELL_ID};
+use crate::util::vec2::Vec2;
+
+/// Layouts may pack wells at most this much denser than procedural arenas
+const MAX_WELL_DENSITY_FACTOR: f32 = 2.0;
+
+fn default_well_mass() -> f32 {
+    CENTRAL_MASS
+}
+
+fn default_core_radius() -> f32 {
+    arena::CORE_RADIUS
+}
+
+/// An orbital well in a layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WellLayout {
+    pub position: Vec2,
+    #[serde(default = "default_well_mass")]
+    pub mass: f32,
+    #[serde(default = "default_core_radius")]
+    pub core_radius: f32,
+}
+
+/// A circle humans spawn in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnZone {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+/// An asteroid placed at the start of every match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HazardLayout {
+    pub position: Vec2,
+    pub radius: f32,
+    /// Projectile mass it absorbs before breaking
+    pub health: f32,
+}
+
+/// Why a layout was refused
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LayoutError {
+    #[error("escape radius must be {min}-{max}")]
+    EscapeRadius { min: f32, max: f32 },
+    #[error("layout needs {min}-{max} wells for its size")]
+    WellCount { min: usize, max: usize },
+    #[error("well {0} is too close to the center or the edge")]
+    WellPlacement(usize),
+    #[error("well {0} needs a positive mass and core radius")]
+    WellSize(usize),
+    #[error("wells {0} and {1} are closer than {2:.0} units")]
+    WellSpacing(usize, usize, f32),
+    #[error("spawn zone {0} is outside the arena or over a well core")]
+    SpawnZone(usize),
+    #[error("hazard {0} is outside the arena or has no size or health")]
+    Hazard(usize),
+}
+
+/// A hand-made arena
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArenaLayout {
+    #[serde(default)]
+    pub name: String,
+    pub escape_radius: f32,
+    /// Orbital wells (the central well is always there)
+    pub wells: Vec<WellLayout>,
+    /// Zones humans spawn in (empty = near wells, as usual)
+    #[serde(default)]
+    pub spawn_zones: Vec<SpawnZone>,
+    #[serde(default)]
+    pub hazards: Vec<HazardLayout>,
+}
+
+impl ArenaLayout {
+    /// The `ARENA_LAYOUT_FILE` layout for an arena of up to `players`, or None
+    /// for procedural wells (no layout, an invalid one, or one too small)
+    pub fn from_env(players: usize, config: &ArenaScalingConfig) -> Option<Arc<Self>> {
+        let path = ArenaLayoutConfig::from_env().path?;
+        let layout = match Self::load(&path, config) {
+            Ok(layout) => layout,
+            Err(e) => {
+                warn!("Arena layout {}: {}, using procedural wells", path, e);
+                return None;
+            }
+        };
+        if !layout.fits(players, config) {
+            info!(
+                "Arena layout {} holds {} players, not {}; using procedural wells",
+                path,
+                layout.capacity(config),
+                players
+            );
+            return None;
+        }
+        Some(Arc::new(layout))
+    }
+
+    /// Load and validate a layout file
+    pub fn load(path: &str, config: &ArenaScalingConfig) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read layout file: {}", e))?;
+        let layout: Self =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse layout file: {}", e))?;
+        layout.validate(config).map_err(|e| format!("Invalid layout: {}", e))?;
+        Ok(layout)
+    }
+
+    /// Check the layout against procedural density rules: size limits, well
+    /// count per area, the ring wells may sit in and well spacing
+    pub fn validate(&self, config: &ArenaScalingConfig) -> Result<(), LayoutError> {
+        let escape = self.escape_radius;
+        let (min_escape, max_escape) =
+            (config.min_escape_radius, config.min_escape_radius * config.max_escape_multiplier);
+        if !(min_escape..=max_escape).contains(&escape) {
+            return Err(LayoutError::EscapeRadius { min: min_escape, max: max_escape });
+        }
+
+        let procedural_wells = (self.area() / config.wells_per_area).ceil();
+        let max_wells =
+            ((procedural_wells * MAX_WELL_DENSITY_FACTOR) as usize).clamp(config.min_wells, config.max_wells);
+        if !(config.min_wells..=max_wells).contains(&self.wells.len()) {
+            return Err(LayoutError::WellCount { min: config.min_wells, max: max_wells });
+        }
+
+        let (min_radius, max_radius) = (escape * config.center_exclusion_ratio, escape * config.well_max_ratio);
+        let spacing = Arena::min_well_spacing(escape, self.wells.len());
+        for (i, well) in self.wells.iter().enumerate() {
+            if !(min_radius..=max_radius).contains(&well.position.length()) {
+                return Err(LayoutError::WellPlacement(i));
+            }
+            if well.mass <= 0.0 || well.core_radius <= 0.0 {
+                return Err(LayoutError::WellSize(i));
+            }
+            if let Some(j) = self.wells[..i].iter().position(|o| o.position.distance_to(well.position) < spacing) {
+                return Err(LayoutError::WellSpacing(j, i, spacing));
+            }
+        }
+
+        let central_core = arena::CORE_RADIUS * config.supermassive_core_mult;
+        for (i, zone) in self.spawn_zones.iter().enumerate() {
+            let inside = zone.radius > 0.0 && zone.center.length() + zone.radius <= escape;
+            let clear_of_cores = zone.center.length() > central_core + zone.radius
+                && self.wells.iter().all(|w| w.position.distance_to(zone.center) > w.core_radius + zone.radius);
+            if !inside || !clear_of_cores {
+                return Err(LayoutError::SpawnZone(i));
+            }
+        }
+
+        for (i, hazard) in self.hazards.iter().enumerate() {
+            let inside = hazard.position.length() + hazard.radius <= escape;
+            if !inside || hazard.radius <= 0.0 || hazard.health <= 0.0 {
+                return Err(LayoutError::Hazard(i));
+            }
+        }
+        Ok(())
+    }
+
+    fn area(&self) -> f32 {
+        std::f32::consts::PI * self.escape_radius * self.escape_radius
+    }
+
+    /// Most players the layout holds at procedural density
+    pub fn capacity(&self, config: &ArenaScalingConfig) -> usize {
+        (self.area() / config.area_per_player) as usize
+    }
+
+    /// Whether `players` fit without crowding them more than procedural scaling would
+    pub fn fits(&self, players: usize, config: &ArenaScalingConfig) -> bool {
+        players <= self.capacity(config)
+    }
+
+    /// Replace a fresh arena's size and orbital wells with the layout's, pinning them
+    pub fn apply(&self, state: &mut GameState) {
+        let arena = &mut state.arena;
+        let orbital: Vec<_> = arena.gravity_wells.keys().copied().filter(|&id| id != CENTRAL_WELL_ID).collect();
+        for id in orbital {
+            arena.remove_well(id);
+        }
+        arena.escape_radius = self.escape_radius;
+        arena.outer_radius = self.escape_radius - 200.0;
+        arena.scale = self.escape_radius / arena::ESCAPE_RADIUS;
+        for well in &self.wells {
+            let id = arena.alloc_well_id();
+            arena.insert_well(GravityWell::new(id, well.position, well.mass, well.core_radius));
+        }
+        arena.fixed_layout = true;
+        state.rebuild_well_grid();
+    }
+
+    /// Place the layout's hazards (a match is starting)
+    pub fn place_hazards(&self, state: &mut GameState) {
+        for hazard in &self.hazards {
+            let id = state.next_entity_id();
+            state.asteroids.push(Asteroid::new(id, hazard.position, hazard.radius, hazard.health));
+        }
+    }
+
+    /// A spawn point in a random zone away from `existing` players, if the layout has zones
+    pub fn spawn_position(&self, existing: &[Vec2]) -> Option<Vec2> {
+        use rand::Rng;
+
+        /// Distance kept from other players when possible
+        const SPAWN_GAP: f32 = 80.0;
+        const ATTEMPTS: usize = 16;
+
+        if self.spawn_zones.is_empty() {
+            return None;
+        }
+        let mut rng = rand::thread_rng();
+        let mut candidate = Vec2::ZERO;
+        for _ in 0..ATTEMPTS {
+            let zone = &self.spawn_zones[rng.gen_range(0..self.spawn_zones.len())];
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            candidate = zone.center + Vec2::from_angle(angle) * zone.radius * rng.gen::<f32>().sqrt();
+            if existing.iter().all(|p| p.distance_to(candidate) >= SPAWN_GAP) {
+                break;
+            }
+        }
+        Some(candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> ArenaLayout {
+        serde_json::from_str(
+            r#"{
+                "name": "Twin Suns",
+                "escape_radius": 2400,
+                "wells": [
+                    {"position": {"x": 1200, "y": 0}, "mass": 8000},
+                    {"position": {"x": -1200, "y": 0}, "core_radius": 70}
+                ],
+                "spawn_zones": [{"center": {"x": 0, "y": 1500}, "radius": 300}],
+                "hazards": [{"position": {"x": 0, "y": -1600}, "radius": 60, "health": 100}]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_layout_validation_follows_density_rules() {
+        let config = ArenaScalingConfig::default();
+        let valid = layout();
+        valid.validate(&config).unwrap();
+        assert_eq!(valid.wells[1].mass, CENTRAL_MASS);
+
+        let mut small = valid.clone();
+        small.escape_radius = 100.0;
+        assert!(matches!(small.validate(&config), Err(LayoutError::EscapeRadius { .. })));
+
+        let mut crowded = valid.clone();
+        crowded.wells[1].position = Vec2::new(1150.0, 300.0);
+        assert!(matches!(crowded.validate(&config), Err(LayoutError::WellSpacing(0, 1, _))));
+
+        let mut central = valid.clone();
+        central.wells[0].position = Vec2::new(100.0, 0.0);
+        assert_eq!(central.validate(&config), Err(LayoutError::WellPlacement(0)));
+
+        let mut dense = valid.clone();
+        dense.wells = (0..40)
+            .map(|i| WellLayout {
+                position: Vec2::from_angle(i as f32) * 1500.0,
+                mass: CENTRAL_MASS,
+                core_radius: arena::CORE_RADIUS,
+            })
+            .collect();
+        assert!(matches!(dense.validate(&config), Err(LayoutError::WellCount { .. })));
+
+        let mut zone_on_well = valid.clone();
+        zone_on_well.spawn_zones[0].center = Vec2::new(1200.0, 100.0);
+        assert_eq!(zone_on_well.validate(&config), Err(LayoutError::SpawnZone(0)));
+    }
+
+    #[test]
+    fn test_applied_layout_is_pinned_until_players_outgrow_it() {
+        let config = ArenaScalingConfig::default();
+        let layout = layout();
+        let mut state = GameState::new();
+        state.arena.scale_for_simulation(10, &config, true);
+        layout.apply(&mut state);
+
+        assert_eq!(state.arena.escape_radius, 2400.0);
+        assert_eq!(state.arena.orbital_well_count(), 2);
+        let mut wells = state.arena.gravity_wells.values();
+        assert!(wells.any(|w| w.position == Vec2::new(-1200.0, 0.0) && w.core_radius == 70.0));
+
+        // Scaling leaves the layout alone while the players fit...
+        let capacity = layout.capacity(&config);
+        assert!(layout.fits(capacity, &config) && !layout.fits(capacity + 1, &config));
+        state.arena.scale_for_simulation(capacity, &config, true);
+        assert_eq!((state.arena.escape_radius, state.arena.orbital_well_count()), (2400.0, 2));
+
+        // ...and takes over once they don't
+        state.arena.scale_for_simulation(capacity * 4, &config, true);
+        assert!(!state.arena.fixed_layout);
+        assert!(state.arena.escape_radius > 2400.0);
+
+        layout.place_hazards(&mut state);
+        assert_eq!(state.asteroids.len(), 1);
+        let spawn = layout.spawn_position(&[]).unwrap();
+        assert!(spawn.distance_to(Vec2::new(0.0, 1500.0)) <= 300.0);
+    }
+}
@@ -0,0 +1,352 @@
+//! Named arena presets
+//!
+//! Organizers save the running arena (its size, orbital wells and active
+//! modifiers) under a name from the admin API and switch back to it later
+//! without a restart. A preset is an arena layout (see `arena_layout`) plus
+//! modifiers, so layout files load as presets too. Switching runs a
+//! transition: over a few seconds wells glide to their new positions, new
+//! wells grow from nothing, surplus ones fade out and the arena resizes, so
+//! players see the layout change rather than a jump. With `ARENA_PRESET_DIR`
+//! set presets are saved as JSON files and reloaded on startup.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::ArenaPresetConfig;
+use crate::game::arena_layout::{ArenaLayout, WellLayout};
+use crate::game::constants::arena;
+use crate::game::modifiers::ActiveModifier;
+use crate::game::state::{GameState, GravityWell, WellId, CENTRAL_WELL_ID};
+use crate::net::state_view::GameStateView;
+use crate::util::vec2::Vec2;
+
+/// Longest preset name accepted
+pub const MAX_PRESET_NAME_LEN: usize = 64;
+
+/// Core radius new wells grow from (a zero core would divide by zero)
+const MIN_TRANSITION_CORE: f32 = 1.0;
+
+/// A saved arena
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArenaPreset {
+    #[serde(flatten)]
+    pub layout: ArenaLayout,
+    /// Modifiers running when the preset was saved (restarted with the time they had left)
+    #[serde(default)]
+    pub modifiers: Vec<ActiveModifier>,
+}
+
+impl ArenaPreset {
+    /// The arena in a published state view, saved as `name`
+    pub fn capture(name: &str, view: &GameStateView) -> Self {
+        let wells = view
+            .snapshot
+            .gravity_wells
+            .iter()
+            .filter(|w| w.id != CENTRAL_WELL_ID)
+            .map(|w| WellLayout { position: w.position, mass: w.mass, core_radius: w.core_radius })
+            .collect();
+        Self {
+            layout: ArenaLayout {
+                name: name.to_string(),
+                escape_radius: view.escape_radius,
+                wells,
+                spawn_zones: Vec::new(),
+                hazards: Vec::new(),
+            },
+            modifiers: view.modifiers.clone(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.layout.name
+    }
+}
+
+/// Why a preset could not be saved or applied
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PresetError {
+    #[error("preset names are 1-{MAX_PRESET_NAME_LEN} letters, digits, '-' or '_'")]
+    InvalidName,
+    #[error("no preset named {0}")]
+    NotFound(String),
+    #[error("arena has no size to save yet")]
+    EmptyArena,
+    #[error("failed to write preset file: {0}")]
+    Io(String),
+}
+
+/// Whether a name is safe as a preset name (and file name)
+pub fn valid_name(name: &str) -> bool {
+    (1..=MAX_PRESET_NAME_LEN).contains(&name.len())
+        && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// Saved presets and the switch the admin API asked for
+#[derive(Debug, Default)]
+pub struct ArenaPresets {
+    presets: RwLock<BTreeMap<String, ArenaPreset>>,
+    /// Directory presets are saved to (None = memory only)
+    dir: Option<PathBuf>,
+    /// Seconds a switch takes unless the request says otherwise
+    pub transition_secs: f32,
+    /// Preset to switch to and the transition length, taken by the game session
+    pending: Mutex<Option<(ArenaPreset, f32)>>,
+}
+
+impl ArenaPresets {
+    /// Presets from `ARENA_PRESET_DIR`, if set (invalid files are skipped)
+    pub fn new(config: &ArenaPresetConfig) -> Self {
+        let dir = config.dir.as_ref().map(PathBuf::from);
+        let mut presets = BTreeMap::new();
+        if let Some(entries) = dir.as_ref().and_then(|d| std::fs::read_dir(d).ok()) {
+            for path in entries.flatten().map(|e| e.path()) {
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                let parsed = std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|s| serde_json::from_str::<ArenaPreset>(&s).map_err(|e| e.to_string()));
+                match parsed {
+                    Ok(mut preset) if valid_name(&name) => {
+                        preset.layout.name = name.clone();
+                        presets.insert(name, preset);
+                    }
+                    Ok(_) => warn!("Arena preset {:?} skipped: invalid name", path),
+                    Err(e) => warn!("Arena preset {:?} skipped: {}", path, e),
+                }
+            }
+            info!("Loaded {} arena presets", presets.len());
+        }
+        Self {
+            presets: RwLock::new(presets),
+            dir,
+            transition_secs: config.transition_secs,
+            pending: Mutex::new(None),
+        }
+    }
+
+    /// Preset names, sorted
+    pub fn names(&self) -> Vec<String> {
+        self.presets.read().keys().cloned().collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<ArenaPreset> {
+        self.presets.read().get(name).cloned()
+    }
+
+    /// Save (or overwrite) a preset, writing it to the preset directory if set
+    pub fn save(&self, preset: ArenaPreset) -> Result<(), PresetError> {
+        if !valid_name(preset.name()) {
+            return Err(PresetError::InvalidName);
+        }
+        if preset.layout.escape_radius <= 0.0 {
+            return Err(PresetError::EmptyArena);
+        }
+        if let Some(dir) = &self.dir {
+            let json = serde_json::to_vec_pretty(&preset).map_err(|e| PresetError::Io(e.to_string()))?;
+            std::fs::create_dir_all(dir)
+                .and_then(|_| std::fs::write(dir.join(format!("{}.json", preset.name())), json))
+                .map_err(|e| PresetError::Io(e.to_string()))?;
+        }
+        self.presets.write().insert(preset.name().to_string(), preset);
+        Ok(())
+    }
+
+    /// Ask the game session to switch to a preset over `secs` seconds
+    pub fn request_apply(&self, name: &str, secs: f32) -> Result<ArenaPreset, PresetError> {
+        let preset = self.get(name).ok_or_else(|| PresetError::NotFound(name.to_string()))?;
+        *self.pending.lock() = Some((preset.clone(), secs));
+        Ok(preset)
+    }
+
+    /// The requested switch, if any (called by the game session)
+    pub fn take_request(&self) -> Option<(ArenaPreset, f32)> {
+        self.pending.lock().take()
+    }
+}
+
+/// One well moving between layouts
+#[derive(Debug, Clone)]
+struct WellPath {
+    id: WellId,
+    from: (Vec2, f32, f32),
+    to: (Vec2, f32, f32),
+    /// Removed once the transition ends (faded to nothing)
+    fading: bool,
+}
+
+/// An arena changing to a preset
+#[derive(Debug, Clone)]
+pub struct ArenaTransition {
+    duration: f32,
+    elapsed: f32,
+    from_escape: f32,
+    to_escape: f32,
+    wells: Vec<WellPath>,
+}
+
+impl ArenaTransition {
+    /// Start moving the arena in `state` toward `layout`. Orbital wells are
+    /// paired with the layout's in id order; new wells start massless at their
+    /// target position and surplus ones fade out. The arena is pinned to the
+    /// layout from the start, so scaling doesn't fight the transition.
+    pub fn start(state: &mut GameState, layout: &ArenaLayout, duration: f32) -> Self {
+        let arena = &mut state.arena;
+        let mut wells: Vec<WellPath> = arena
+            .gravity_wells
+            .values()
+            .filter(|w| w.id != CENTRAL_WELL_ID)
+            .map(|w| {
+                let from = (w.position, w.mass, w.core_radius);
+                WellPath { id: w.id, from, to: (w.position, 0.0, MIN_TRANSITION_CORE), fading: true }
+            })
+            .collect();
+        wells.sort_by_key(|p| p.id);
+        for (path, target) in wells.iter_mut().zip(&layout.wells) {
+            path.to = (target.position, target.mass, target.core_radius);
+            path.fading = false;
+        }
+        let kept = wells.len();
+        for target in layout.wells.iter().skip(kept) {
+            let id = arena.alloc_well_id();
+            arena.insert_well(GravityWell::new(id, target.position, 0.0, MIN_TRANSITION_CORE));
+            wells.push(WellPath {
+                id,
+                from: (target.position, 0.0, MIN_TRANSITION_CORE),
+                to: (target.position, target.mass, target.core_radius),
+                fading: false,
+            });
+        }
+        for path in &wells {
+            if let Some(well) = arena.gravity_wells.get_mut(&path.id) {
+                well.target_position = path.to.0;
+            }
+        }
+        arena.fixed_layout = true;
+
+        Self {
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            from_escape: arena.escape_radius,
+            to_escape: layout.escape_radius,
+            wells,
+        }
+    }
+
+    /// Advance by `dt` seconds. Returns true once the arena matches the preset.
+    pub fn advance(&mut self, state: &mut GameState, dt: f32) -> bool {
+        self.elapsed += dt;
+        let t = if self.duration > 0.0 { (self.elapsed / self.duration).min(1.0) } else { 1.0 };
+        let eased = t * t * (3.0 - 2.0 * t);
+        let lerp = |a: f32, b: f32| a + (b - a) * eased;
+
+        let arena = &mut state.arena;
+        arena.escape_radius = lerp(self.from_escape, self.to_escape);
+        arena.outer_radius = arena.escape_radius - 200.0;
+        arena.scale = arena.escape_radius / arena::ESCAPE_RADIUS;
+        for path in &self.wells {
+            // Scaling may have taken over (players outgrew the preset) and removed wells
+            let Some(well) = arena.gravity_wells.get_mut(&path.id) else {
+                continue;
+            };
+            well.position = path.from.0 + (path.to.0 - path.from.0) * eased;
+            well.mass = lerp(path.from.1, path.to.1);
+            well.core_radius = lerp(path.from.2, path.to.2);
+        }
+
+        let done = t >= 1.0;
+        if done {
+            for path in self.wells.iter().filter(|p| p.fading) {
+                arena.remove_well(path.id);
+            }
+        }
+        state.rebuild_well_grid();
+        done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::modifiers::GlobalModifier;
+
+    fn preset(name: &str, wells: &[(f32, f32)]) -> ArenaPreset {
+        ArenaPreset {
+            layout: ArenaLayout {
+                name: name.to_string(),
+                escape_radius: 2000.0,
+                wells: wells
+                    .iter()
+                    .map(|&(x, y)| WellLayout { position: Vec2::new(x, y), mass: 9000.0, core_radius: 60.0 })
+                    .collect(),
+                spawn_zones: Vec::new(),
+                hazards: Vec::new(),
+            },
+            modifiers: vec![ActiveModifier { modifier: GlobalModifier::SolarFlare, remaining: 30.0 }],
+        }
+    }
+
+    #[test]
+    fn test_presets_save_to_and_load_from_the_preset_dir() {
+        let dir = std::env::temp_dir().join(format!("orbit-presets-{}", uuid::Uuid::new_v4()));
+        let config = ArenaPresetConfig { dir: Some(dir.to_string_lossy().into_owned()), transition_secs: 3.0 };
+        let presets = ArenaPresets::new(&config);
+
+        assert_eq!(presets.save(preset("../escape", &[])), Err(PresetError::InvalidName));
+        presets.save(preset("finals", &[(1000.0, 0.0)])).unwrap();
+        assert_eq!(presets.names(), vec!["finals".to_string()]);
+
+        let reloaded = ArenaPresets::new(&config);
+        assert_eq!(reloaded.get("finals"), Some(preset("finals", &[(1000.0, 0.0)])));
+
+        assert_eq!(reloaded.request_apply("warmup", 2.0), Err(PresetError::NotFound("warmup".to_string())));
+        reloaded.request_apply("finals", 2.0).unwrap();
+        assert_eq!(reloaded.take_request().map(|(p, secs)| (p.layout.name, secs)), Some(("finals".to_string(), 2.0)));
+        assert!(reloaded.take_request().is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_transition_moves_grows_and_fades_wells() {
+        let mut state = GameState::new();
+        for (i, x) in [800.0, -800.0, 0.0].into_iter().enumerate() {
+            let id = state.arena.alloc_well_id();
+            state.arena.insert_well(GravityWell::new(id, Vec2::new(x, 600.0 * i as f32), 10000.0, 50.0));
+        }
+        let from_escape = state.arena.escape_radius;
+
+        // The three wells move and a fourth grows in
+        let target = preset("finals", &[(1200.0, 0.0), (-1200.0, 0.0), (0.0, 1200.0), (0.0, -1200.0)]);
+        let mut transition = ArenaTransition::start(&mut state, &target.layout, 2.0);
+        assert_eq!(state.arena.orbital_well_count(), 4);
+        assert!(state.arena.fixed_layout);
+
+        assert!(!transition.advance(&mut state, 1.0));
+        let halfway = state.arena.escape_radius;
+        assert!(halfway > from_escape && halfway < 2000.0);
+        assert!(state.arena.gravity_wells.values().any(|w| w.mass > 0.0 && w.mass < 9000.0));
+
+        assert!(transition.advance(&mut state, 1.0));
+        assert_eq!(state.arena.escape_radius, 2000.0);
+        assert_eq!(state.arena.orbital_well_count(), 4);
+        let mut positions: Vec<_> =
+            state.arena.gravity_wells.values().filter(|w| w.id != CENTRAL_WELL_ID).map(|w| w.position).collect();
+        positions.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        let expected = [(-1200.0, 0.0), (0.0, -1200.0), (0.0, 1200.0), (1200.0, 0.0)].map(|(x, y)| Vec2::new(x, y));
+        assert_eq!(positions, expected);
+        assert!(state.arena.gravity_wells.values().filter(|w| w.id != CENTRAL_WELL_ID).all(|w| w.mass == 9000.0));
+
+        // Surplus wells fade out and are removed at the end
+        let mut transition = ArenaTransition::start(&mut state, &preset("duel", &[(1000.0, 0.0)]).layout, 1.0);
+        assert!(!transition.advance(&mut state, 0.5));
+        assert_eq!(state.arena.orbital_well_count(), 4);
+        assert!(transition.advance(&mut state, 0.5));
+        assert_eq!(state.arena.orbital_well_count(), 1);
+    }
+}
@@ -0,0 +1,138 @@
+//! Arena generation seeds and their shareable codes
+//!
+//! Every match draws a fresh seed unless one is supplied (e.g. when a room is
+//! created from a code), and the arena's well layout - the golden-angle base
+//! offset and each well's size - is derived from it alone. Two matches with
+//! the same seed therefore place identical wells as the arena grows, so a
+//! community can race on the same layout by sharing its code.
+//!
+//! Codes are the seed in Crockford base32: seven characters, case-insensitive,
+//! with `I`/`L` read as `1`, `O` as `0` and dashes ignored.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Characters in a code (7 x 5 bits covers a u32)
+const CODE_LEN: usize = 7;
+
+/// Why a shared code could not be read
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SeedCodeError {
+    #[error("arena code must be 1-{CODE_LEN} characters")]
+    Length,
+    #[error("invalid character '{0}' in arena code")]
+    Character(char),
+    #[error("arena code is out of range")]
+    Range,
+}
+
+/// Seed for one arena's layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ArenaSeed(pub u32);
+
+impl ArenaSeed {
+    /// A fresh seed for a new match
+    pub fn random() -> Self {
+        Self(rand::thread_rng().gen())
+    }
+
+    /// Shareable code for this seed
+    pub fn code(self) -> String {
+        (0..CODE_LEN)
+            .rev()
+            .map(|i| CODE_ALPHABET[((u64::from(self.0) >> (i * 5)) & 0x1f) as usize] as char)
+            .collect()
+    }
+
+    /// Base angular offset of the golden-angle well distribution
+    pub fn well_base_offset(self) -> f32 {
+        self.rng(0).gen_range(0.0..std::f32::consts::TAU)
+    }
+
+    /// Size choice (index below `choices`) for the well at a golden-angle index
+    pub fn well_size_index(self, angle_index: u32, choices: usize) -> usize {
+        self.rng(u64::from(angle_index) + 1).gen_range(0..choices)
+    }
+
+    /// Independent stream per draw, so a well's size doesn't depend on how
+    /// many wells were added before it or in which batches
+    fn rng(self, stream: u64) -> StdRng {
+        StdRng::seed_from_u64((u64::from(self.0) << 32) ^ stream)
+    }
+}
+
+impl fmt::Display for ArenaSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code())
+    }
+}
+
+impl FromStr for ArenaSeed {
+    type Err = SeedCodeError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let digits: Vec<char> = code.chars().filter(|&c| c != '-').collect();
+        if digits.is_empty() || digits.len() > CODE_LEN {
+            return Err(SeedCodeError::Length);
+        }
+
+        let mut value: u64 = 0;
+        for c in digits {
+            let normalized = match c.to_ascii_uppercase() {
+                'I' | 'L' => '1',
+                'O' => '0',
+                other => other,
+            };
+            let digit = CODE_ALPHABET
+                .iter()
+                .position(|&a| a as char == normalized)
+                .ok_or(SeedCodeError::Character(c))?;
+            value = (value << 5) | digit as u64;
+        }
+        u32::try_from(value).map(Self).map_err(|_| SeedCodeError::Range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_round_trip() {
+        for seed in [0, 1, 0xdead_beef, u32::MAX] {
+            let code = ArenaSeed(seed).code();
+            assert_eq!(code.len(), CODE_LEN);
+            assert_eq!(code.parse::<ArenaSeed>(), Ok(ArenaSeed(seed)));
+        }
+
+        // Lenient reading of hand-typed codes
+        let code = ArenaSeed(0x0123_4567).code();
+        let typed = format!("{}-{}", &code[..3], &code[3..]).to_lowercase();
+        assert_eq!(typed.parse::<ArenaSeed>(), Ok(ArenaSeed(0x0123_4567)));
+        assert_eq!("0O1IL".parse::<ArenaSeed>(), "00111".parse::<ArenaSeed>());
+    }
+
+    #[test]
+    fn test_invalid_codes() {
+        assert_eq!("".parse::<ArenaSeed>(), Err(SeedCodeError::Length));
+        assert_eq!("12345678".parse::<ArenaSeed>(), Err(SeedCodeError::Length));
+        assert_eq!("AB#".parse::<ArenaSeed>(), Err(SeedCodeError::Character('#')));
+        assert_eq!("ZZZZZZZ".parse::<ArenaSeed>(), Err(SeedCodeError::Range));
+    }
+
+    #[test]
+    fn test_layout_draws_are_deterministic() {
+        let seed = ArenaSeed(42);
+        assert_eq!(seed.well_base_offset(), ArenaSeed(42).well_base_offset());
+        let sizes: Vec<_> = (0..16).map(|i| seed.well_size_index(i, 5)).collect();
+        assert_eq!(sizes, (0..16).map(|i| ArenaSeed(42).well_size_index(i, 5)).collect::<Vec<_>>());
+        assert!(sizes.iter().all(|&s| s < 5));
+        assert_ne!(seed.well_base_offset(), ArenaSeed(43).well_base_offset());
+    }
+}
@@ -0,0 +1,166 @@
+//! Balance analytics
+//!
+//! Counts deaths by cause over the last hour in one-minute buckets and flags
+//! causes whose share points to a degenerate strategy: ramming that decides
+//! most fights, wells that kill more players than fights do, a zone that
+//! outruns players, or players leaving instead of playing on. Flags need
+//! `MIN_DEATHS` in the window so a quiet server doesn't raise them. Served
+//! at `/analytics/balance`.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::net::protocol::DeathCause;
+
+/// Minutes of deaths kept
+pub const WINDOW_MINUTES: u64 = 60;
+/// Deaths the window needs before any share is flagged
+const MIN_DEATHS: u32 = 50;
+
+/// Shares of all deaths above which a cause is flagged, and why
+const RULES: [(DeathCause, f32, &str); 4] = [
+    (DeathCause::Crush, 0.6, "ramming decides most fights; shooting may be too weak to matter"),
+    (DeathCause::Well, 0.4, "wells kill more players than fights do; their pull may be too strong"),
+    (DeathCause::Boundary, 0.25, "the zone outruns players; collapses may be too fast"),
+    (DeathCause::Disconnect, 0.2, "players leave mid-match rather than play on"),
+];
+
+/// Deaths and share of one cause
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CauseShare {
+    pub cause: &'static str,
+    pub deaths: u32,
+    pub share: f32,
+}
+
+/// A cause whose share looks degenerate
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BalanceFlag {
+    pub cause: &'static str,
+    pub share: f32,
+    pub threshold: f32,
+    pub note: &'static str,
+}
+
+/// Deaths by cause over the window, and what stands out
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BalanceReport {
+    pub window_minutes: u64,
+    pub total_deaths: u32,
+    pub causes: Vec<CauseShare>,
+    pub flags: Vec<BalanceFlag>,
+}
+
+/// Deaths by cause per minute since start, oldest first
+#[derive(Debug)]
+struct Buckets {
+    started: Instant,
+    minutes: VecDeque<(u64, [u32; DeathCause::ALL.len()])>,
+}
+
+impl Buckets {
+    fn trim(&mut self, minute: u64) {
+        while self.minutes.front().is_some_and(|&(m, _)| m + WINDOW_MINUTES <= minute) {
+            self.minutes.pop_front();
+        }
+    }
+}
+
+/// Rolling death counts by cause, shared between the game loop and the metrics server
+#[derive(Debug)]
+pub struct BalanceAnalytics {
+    buckets: Mutex<Buckets>,
+}
+
+impl BalanceAnalytics {
+    pub fn new(now: Instant) -> Self {
+        Self { buckets: Mutex::new(Buckets { started: now, minutes: VecDeque::new() }) }
+    }
+
+    pub fn record(&self, cause: DeathCause, now: Instant) {
+        let index = DeathCause::ALL.iter().position(|&c| c == cause).expect("every cause is listed");
+        let mut buckets = self.buckets.lock();
+        let minute = now.saturating_duration_since(buckets.started).as_secs() / 60;
+        buckets.trim(minute);
+        if buckets.minutes.back().map_or(true, |&(m, _)| m != minute) {
+            buckets.minutes.push_back((minute, [0; DeathCause::ALL.len()]));
+        }
+        if let Some((_, counts)) = buckets.minutes.back_mut() {
+            counts[index] += 1;
+        }
+    }
+
+    pub fn report(&self, now: Instant) -> BalanceReport {
+        let mut buckets = self.buckets.lock();
+        let minute = now.saturating_duration_since(buckets.started).as_secs() / 60;
+        buckets.trim(minute);
+        let mut totals = [0u32; DeathCause::ALL.len()];
+        for (_, counts) in &buckets.minutes {
+            for (total, count) in totals.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+        drop(buckets);
+
+        let total_deaths: u32 = totals.iter().sum();
+        let share = |deaths: u32| if total_deaths == 0 { 0.0 } else { deaths as f32 / total_deaths as f32 };
+        let causes = DeathCause::ALL
+            .iter()
+            .zip(totals)
+            .map(|(cause, deaths)| CauseShare { cause: cause.name(), deaths, share: share(deaths) })
+            .collect::<Vec<_>>();
+        let flags = RULES
+            .iter()
+            .filter(|_| total_deaths >= MIN_DEATHS)
+            .filter_map(|&(cause, threshold, note)| {
+                let share = causes.iter().find(|c| c.cause == cause.name())?.share;
+                (share > threshold).then_some(BalanceFlag { cause: cause.name(), share, threshold, note })
+            })
+            .collect();
+        BalanceReport { window_minutes: WINDOW_MINUTES, total_deaths, causes, flags }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_dominant_cause_is_flagged_within_the_window() {
+        let start = Instant::now();
+        let at = |minutes: u64| start + Duration::from_secs(minutes * 60);
+        let analytics = BalanceAnalytics::new(start);
+
+        // Too few deaths to judge
+        for _ in 0..10 {
+            analytics.record(DeathCause::Well, at(0));
+        }
+        assert!(analytics.report(at(0)).flags.is_empty());
+
+        for _ in 0..30 {
+            analytics.record(DeathCause::Crush, at(30));
+        }
+        for _ in 0..20 {
+            analytics.record(DeathCause::Projectile, at(31));
+        }
+        let report = analytics.report(at(31));
+        assert_eq!(report.total_deaths, 60);
+        assert_eq!(report.causes[0], CauseShare { cause: "crush", deaths: 30, share: 0.5 });
+        // Wells took 1 in 6 deaths and ramming half: neither crosses its threshold
+        assert!(report.flags.is_empty());
+
+        // An hour on, the early well deaths are gone and ramming dominates
+        for _ in 0..10 {
+            analytics.record(DeathCause::Crush, at(60));
+        }
+        let report = analytics.report(at(60));
+        assert_eq!(report.total_deaths, 60);
+        let flagged: Vec<_> = report.flags.iter().map(|f| f.cause).collect();
+        assert_eq!(flagged, ["crush"]);
+        assert_eq!(analytics.report(at(200)).total_deaths, 0);
+    }
+}
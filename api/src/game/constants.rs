@@ -98,7 +98,7 @@ pub mod arena {
     pub const COLLAPSE_INTERVAL: f32 = 30.0;
     /// Number of collapse phases
     pub const COLLAPSE_PHASES: u8 = 8;
-    /// Mass drain rate when outside escape radius
+    /// Mass drain rate outside the escape radius, reached `BoundaryConfig::drain_ramp` units out
     pub const ESCAPE_MASS_DRAIN: f32 = 10.0;
 }
 
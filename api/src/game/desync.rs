@@ -0,0 +1,192 @@
+//! Client prediction desync detection
+//!
+//! Clients may attach a hash of their predicted local state at a given tick to
+//! their input. The server keeps a short history of authoritative hashes per
+//! player and counts mismatches, so systematic prediction bugs (e.g. physics
+//! constants drifting between client and server) show up in metrics and logs
+//! instead of only as rubber-banding reports.
+//!
+//! The hash covers position and velocity floored to whole world units, FNV-1a
+//! over the four values as little-endian i32s. The client must hash the same
+//! way (see `hashPredictedState` in the client's StateSync).
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::config::DesyncConfig;
+use crate::game::state::PlayerId;
+use crate::util::vec2::Vec2;
+
+const FNV_OFFSET: u32 = 0x811c_9dc5;
+const FNV_PRIME: u32 = 0x0100_0193;
+
+/// Hash of a player's physics state as seen by prediction
+pub fn state_hash(position: Vec2, velocity: Vec2) -> u32 {
+    [position.x, position.y, velocity.x, velocity.y]
+        .iter()
+        .flat_map(|v| (v.floor() as i32).to_le_bytes())
+        .fold(FNV_OFFSET, |hash, byte| (hash ^ byte as u32).wrapping_mul(FNV_PRIME))
+}
+
+/// Outcome of comparing a client hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DesyncCheck {
+    Match,
+    Mismatch,
+    /// Tick not in the retained history (too old, or not simulated yet)
+    Unknown,
+}
+
+/// Per-player comparison counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DesyncStats {
+    pub checks: u64,
+    pub mismatches: u64,
+    /// Mismatches since the last match
+    pub streak: u32,
+}
+
+#[derive(Debug, Default)]
+struct PlayerHistory {
+    /// (tick, authoritative hash), oldest first
+    hashes: VecDeque<(u64, u32)>,
+    stats: DesyncStats,
+}
+
+/// Tracks authoritative hashes and client mismatches
+#[derive(Debug)]
+pub struct DesyncTracker {
+    config: DesyncConfig,
+    players: HashMap<PlayerId, PlayerHistory>,
+}
+
+impl DesyncTracker {
+    pub fn new(config: DesyncConfig) -> Self {
+        Self {
+            config,
+            players: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Streak length at which a desync is reported
+    pub fn warn_streak(&self) -> u32 {
+        self.config.warn_streak
+    }
+
+    /// Record a player's authoritative state after simulating `tick`
+    pub fn record(&mut self, player_id: PlayerId, tick: u64, position: Vec2, velocity: Vec2) {
+        let history = self.players.entry(player_id).or_default();
+        if history.hashes.len() >= self.config.history_ticks {
+            history.hashes.pop_front();
+        }
+        history.hashes.push_back((tick, state_hash(position, velocity)));
+    }
+
+    /// Compare a client-reported hash against the authoritative one
+    pub fn check(&mut self, player_id: PlayerId, tick: u64, client_hash: u32) -> DesyncCheck {
+        let Some(history) = self.players.get_mut(&player_id) else {
+            return DesyncCheck::Unknown;
+        };
+        let Some(&(_, hash)) = history.hashes.iter().find(|(t, _)| *t == tick) else {
+            return DesyncCheck::Unknown;
+        };
+
+        history.stats.checks += 1;
+        if hash == client_hash {
+            history.stats.streak = 0;
+            DesyncCheck::Match
+        } else {
+            history.stats.mismatches += 1;
+            history.stats.streak += 1;
+            DesyncCheck::Mismatch
+        }
+    }
+
+    pub fn stats(&self, player_id: PlayerId) -> Option<DesyncStats> {
+        self.players.get(&player_id).map(|h| h.stats)
+    }
+
+    /// Players whose current mismatch streak has reached the warning threshold
+    pub fn desynced_count(&self) -> usize {
+        self.players
+            .values()
+            .filter(|h| h.stats.streak >= self.config.warn_streak)
+            .count()
+    }
+
+    pub fn remove(&mut self, player_id: PlayerId) {
+        self.players.remove(&player_id);
+    }
+}
+
+impl Default for DesyncTracker {
+    fn default() -> Self {
+        Self::new(DesyncConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_hash_quantizes_to_whole_units() {
+        let a = state_hash(Vec2::new(10.2, -3.7), Vec2::new(1.9, 0.0));
+        let b = state_hash(Vec2::new(10.9, -3.1), Vec2::new(1.1, 0.5));
+        let c = state_hash(Vec2::new(11.0, -3.1), Vec2::new(1.1, 0.5));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        // Reference value shared with the client implementation
+        assert_eq!(state_hash(Vec2::ZERO, Vec2::ZERO), 0x6969_1905);
+    }
+
+    #[test]
+    fn test_check_counts_mismatch_streaks() {
+        let mut tracker = DesyncTracker::default();
+        let player = uuid::Uuid::new_v4();
+        let (pos, vel) = (Vec2::new(5.0, 5.0), Vec2::new(1.0, 0.0));
+        for tick in 1..=3 {
+            tracker.record(player, tick, pos, vel);
+        }
+        let good = state_hash(pos, vel);
+
+        assert_eq!(tracker.check(player, 1, good), DesyncCheck::Match);
+        assert_eq!(tracker.check(player, 2, good ^ 1), DesyncCheck::Mismatch);
+        assert_eq!(tracker.check(player, 3, good ^ 1), DesyncCheck::Mismatch);
+        assert_eq!(tracker.check(player, 99, good), DesyncCheck::Unknown);
+        assert_eq!(
+            tracker.stats(player),
+            Some(DesyncStats { checks: 3, mismatches: 2, streak: 2 })
+        );
+
+        assert_eq!(tracker.check(player, 3, good), DesyncCheck::Match);
+        assert_eq!(tracker.stats(player).unwrap().streak, 0);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut tracker = DesyncTracker::new(DesyncConfig { history_ticks: 2, ..Default::default() });
+        let player = uuid::Uuid::new_v4();
+        for tick in 1..=3 {
+            tracker.record(player, tick, Vec2::ZERO, Vec2::ZERO);
+        }
+        assert_eq!(tracker.check(player, 1, 0), DesyncCheck::Unknown);
+        assert_eq!(tracker.check(player, 2, state_hash(Vec2::ZERO, Vec2::ZERO)), DesyncCheck::Match);
+    }
+
+    #[test]
+    fn test_desynced_count_uses_warn_streak() {
+        let mut tracker = DesyncTracker::new(DesyncConfig { warn_streak: 2, ..Default::default() });
+        let player = uuid::Uuid::new_v4();
+        tracker.record(player, 1, Vec2::ZERO, Vec2::ZERO);
+        tracker.check(player, 1, 1);
+        assert_eq!(tracker.desynced_count(), 0);
+        tracker.check(player, 1, 1);
+        assert_eq!(tracker.desynced_count(), 1);
+        tracker.remove(player);
+        assert_eq!(tracker.desynced_count(), 0);
+    }
+}
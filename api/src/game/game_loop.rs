@@ -4,18 +4,34 @@
 
 #![allow(dead_code)] // Config fields and event data
 
+use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 
-use crate::config::{ArenaScalingConfig, DebrisSpawnConfig, GravityConfig, GravityWaveConfig};
+use crate::config::{
+    ArenaScalingConfig, AsteroidConfig, BotPlacementConfig, BoundaryConfig, CombatResolverConfig, DebrisSpawnConfig,
+    DifficultyConfig, FactionConfig, FuelConfig, GravityConfig, GravityWaveConfig, HeatConfig, OrbitAssistConfig,
+    ProjectileEconomyConfig, ScheduleConfig, WeatherConfig, WellCaptureConfig,
+};
+use crate::game::arena_layout::ArenaLayout;
+use crate::game::arena_preset::{ArenaPreset, ArenaTransition};
+use crate::game::arena_seed::ArenaSeed;
 use crate::game::constants::physics::{DT, TICK_RATE};
-use crate::game::match_result::{check_match_end, determine_result, MatchEndReason, MatchResult};
+use crate::game::match_result::{check_match_end, determine_result, MatchEndReason, MatchResult, MatchTally};
+use crate::game::modifiers::{GlobalModifier, WeatherRoller};
+use crate::game::schedule::{self, EventScheduler, ScheduleSignal, ScheduledAction};
 use crate::game::state::{GameState, MatchPhase, PlayerId, WellId};
-use crate::game::systems::{ai, ai_soa, arena, collision, debris, gravity, physics, projectile};
-use crate::net::protocol::PlayerInput;
+use crate::game::systems::custom::{GameSystem, SystemPhase, SystemRegistry};
+use crate::game::systems::{
+    ai, ai_soa, arena, asteroids, bot_placement, collision, combat_resolver, debris, difficulty, factions, gravity,
+    orbit_assist, physics, projectile, well_capture,
+};
+use crate::game::tutorial::TutorialStep;
+use crate::net::protocol::{ChatterKind, DeathCause, PlayerInput};
 use crate::util::vec2::Vec2;
 
 /// Maximum inputs per tick to buffer inline (avoids heap allocation for typical loads)
@@ -26,8 +42,18 @@ const INLINE_INPUTS_CAPACITY: usize = 4;
 /// OPTIMIZATION: Stores up to 4 inputs inline on the stack, spills to heap only when exceeded
 type InputBuffer = SmallVec<[PlayerInput; INLINE_INPUTS_CAPACITY]>;
 
+/// Max inputs to process per player per tick (prevents flooding)
+const MAX_INPUTS_PER_TICK: usize = 10;
+
+/// Countdown between a referee resuming a paused match and play continuing
+pub const RESUME_COUNTDOWN_SECS: f32 = 3.0;
+
+/// Capacity of the event subscription channel (in events, not ticks)
+/// Subscribers that fall further behind receive `RecvError::Lagged` and skip ahead
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
 /// Events generated by the game loop
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum GameLoopEvent {
     /// Game state was updated
     Tick { tick: u64 },
@@ -38,6 +64,13 @@ pub enum GameLoopEvent {
         killer_id: PlayerId,
         victim_id: PlayerId,
     },
+    /// Player died, whatever the cause (kills also report `PlayerKilled`)
+    PlayerDied {
+        victim_id: PlayerId,
+        /// Player credited with the death, if any
+        killer_id: Option<PlayerId>,
+        cause: DeathCause,
+    },
     /// Match ended
     MatchEnded { result: MatchResult },
     /// Zone collapsed
@@ -65,6 +98,117 @@ pub enum GameLoopEvent {
         well_id: WellId,
         position: Vec2,
     },
+    /// Custom event emitted by a server-side script or custom system
+    ScriptEvent {
+        source: String,
+        message: String,
+    },
+    /// A bot emitted a chatter line (taunt/distress)
+    BotChatter {
+        bot_id: PlayerId,
+        kind: ChatterKind,
+        text: String,
+        position: Vec2,
+    },
+    /// A paused match finished its resume countdown and is running again
+    MatchResumed,
+    /// A player claimed a gravity well
+    WellCaptured {
+        well_id: WellId,
+        owner_id: PlayerId,
+        previous_owner: Option<PlayerId>,
+    },
+    /// A well's capture ring holds more than one player
+    WellContested { well_id: WellId },
+    /// A global modifier started (lasting `duration` seconds)
+    ModifierStarted { modifier: GlobalModifier, duration: f32 },
+    /// A global modifier expired
+    ModifierEnded { modifier: GlobalModifier },
+    /// A scheduled event runs in `starts_in` seconds
+    ScheduledEventUpcoming { action: ScheduledAction, starts_in: u64 },
+    /// A scheduled boss bot joined the arena
+    BossSpawned { player_id: PlayerId },
+    /// A scheduled arena reset cleared the arena and respawned everyone
+    ArenaReset,
+    /// The arena started changing to a preset, taking `duration` seconds
+    ArenaTransition { name: String, duration: f32 },
+    /// A player's projectile struck another player
+    PlayerHit {
+        shooter_id: PlayerId,
+        victim_id: PlayerId,
+        projectile_id: u64,
+        /// Projectile mass transferred to the victim
+        amount: f32,
+        position: Vec2,
+    },
+    /// A player fell into a gravity well's core and died
+    PlayerFellIntoWell {
+        player_id: PlayerId,
+        well_id: WellId,
+        position: Vec2,
+    },
+    /// Periodic practice-room readout for a player who has fired
+    PracticeReadout {
+        player_id: PlayerId,
+        /// Mass delivered per second over the last readout window
+        damage_per_sec: f32,
+        /// Share of shots that hit since practice started (0-1)
+        accuracy: f32,
+        shots: u32,
+        hits: u32,
+    },
+    /// A player picked up debris
+    DebrisCollected { player_id: PlayerId, debris_id: u64 },
+    /// An asteroid ran out of health and broke into debris
+    AsteroidDestroyed {
+        asteroid_id: u64,
+        position: Vec2,
+        /// Debris fragments it left behind
+        fragments: usize,
+    },
+    /// A tutorial player was given a new objective (`target` marks where to go, if anywhere)
+    TutorialObjective {
+        player_id: PlayerId,
+        step: TutorialStep,
+        target: Option<Vec2>,
+    },
+    /// A tutorial player advanced through the current objective
+    TutorialProgress {
+        player_id: PlayerId,
+        step: TutorialStep,
+        /// Share of the objective done (0-1)
+        progress: f32,
+    },
+    /// A tutorial player finished every objective
+    TutorialCompleted { player_id: PlayerId },
+}
+
+/// Referee pause state of the simulation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum PauseState {
+    #[default]
+    Running,
+    /// Frozen until a referee resumes
+    Paused { reason: String },
+    /// Still frozen, counting down (real time) to resuming play
+    Resuming { reason: String, remaining: f32 },
+}
+
+impl PauseState {
+    /// Whether the simulation is frozen
+    pub fn is_frozen(&self) -> bool {
+        !matches!(self, PauseState::Running)
+    }
+}
+
+/// What happens to player inputs that arrive while a match is paused
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PauseInputPolicy {
+    /// Discard them (players resume from a neutral input)
+    #[default]
+    Drop,
+    /// Keep the most recent ones and apply them on the first tick after resuming
+    Buffer,
 }
 
 /// Configuration for the game loop
@@ -73,9 +217,54 @@ pub struct GameLoopConfig {
     pub tick_rate: u32,
     pub enable_inter_entity_gravity: bool,
     pub gravity_config: GravityConfig,
+    /// Time integration scheme for gravity and motion
+    pub integrator: physics::Integrator,
     pub gravity_wave_config: GravityWaveConfig,
     pub debris_spawn_config: DebrisSpawnConfig,
     pub arena_scaling_config: ArenaScalingConfig,
+    /// Restoring force and mass drain beyond the arena's safe radius
+    pub boundary_config: BoundaryConfig,
+    /// Share of bots spawned within view of humans
+    pub bot_placement_config: BotPlacementConfig,
+    /// Which collision layers interact (players, projectiles, debris, hazards, ghosts)
+    pub collision_matrix: collision::CollisionMatrix,
+    /// Impulse response tuning for player-player collisions
+    pub collision_response: collision::CollisionResponse,
+    pub orbit_assist_config: OrbitAssistConfig,
+    /// Periodic global modifier rolls
+    pub weather_config: WeatherConfig,
+    /// Gravity well claiming rules
+    pub well_capture_config: WellCaptureConfig,
+    /// Statistical bot-vs-bot fights outside human view
+    pub combat_resolver_config: CombatResolverConfig,
+    /// Easing bots off solo humans who keep dying
+    pub difficulty_config: DifficultyConfig,
+    /// Bot factions holding home wells and raiding each other
+    pub faction_config: FactionConfig,
+    /// Projectile cost per charge level and refund on a miss
+    pub projectile_economy: ProjectileEconomyConfig,
+    /// Heat as the cost of firing instead of mass (disabled by default)
+    pub heat_config: HeatConfig,
+    /// Boost fuel that refills only in orbit bands (disabled by default)
+    pub fuel_config: FuelConfig,
+    /// Destructible asteroids and their loot (disabled by default)
+    pub asteroid_config: AsteroidConfig,
+    /// Timed events from cron expressions (none by default)
+    pub schedule_config: ScheduleConfig,
+    /// Simulation speed multiplier (1.0 = normal). Scales the timestep every
+    /// system sees, so velocities, forces and timers slow down together; the
+    /// match clock and countdown stay in real time.
+    pub sim_speed: f32,
+    /// Handling of inputs received while a referee has paused the match
+    pub pause_input_policy: PauseInputPolicy,
+    /// Embedder-provided systems, run at their registered phase each playing tick
+    pub custom_systems: SystemRegistry,
+    /// Arena seed for every match (None = a fresh seed per match)
+    pub arena_seed: Option<ArenaSeed>,
+    /// Hand-made arena for every match (None = procedural wells)
+    pub arena_layout: Option<Arc<ArenaLayout>>,
+    /// Never end the match (practice rooms run until closed)
+    pub endless: bool,
 }
 
 impl Default for GameLoopConfig {
@@ -84,13 +273,52 @@ impl Default for GameLoopConfig {
             tick_rate: TICK_RATE,
             enable_inter_entity_gravity: false,
             gravity_config: GravityConfig::default(),
+            integrator: physics::Integrator::default(),
             gravity_wave_config: GravityWaveConfig::default(),
             debris_spawn_config: DebrisSpawnConfig::default(),
             arena_scaling_config: ArenaScalingConfig::default(),
+            boundary_config: BoundaryConfig::default(),
+            bot_placement_config: BotPlacementConfig::default(),
+            collision_matrix: collision::CollisionMatrix::default(),
+            collision_response: collision::CollisionResponse::default(),
+            orbit_assist_config: OrbitAssistConfig::default(),
+            weather_config: WeatherConfig::default(),
+            well_capture_config: WellCaptureConfig::default(),
+            combat_resolver_config: CombatResolverConfig::default(),
+            difficulty_config: DifficultyConfig::default(),
+            faction_config: FactionConfig::default(),
+            projectile_economy: ProjectileEconomyConfig::default(),
+            heat_config: HeatConfig::default(),
+            fuel_config: FuelConfig::default(),
+            asteroid_config: AsteroidConfig::default(),
+            schedule_config: ScheduleConfig::default(),
+            sim_speed: 1.0,
+            pause_input_policy: PauseInputPolicy::default(),
+            custom_systems: SystemRegistry::new(),
+            arena_seed: None,
+            arena_layout: None,
+            endless: false,
         }
     }
 }
 
+impl GameLoopConfig {
+    /// Register a custom system to run at `phase` (builder style)
+    pub fn with_system<S: GameSystem + 'static>(mut self, phase: SystemPhase, system: S) -> Self {
+        self.custom_systems.register(phase, system);
+        self
+    }
+
+    /// Register a custom system to run at `phase`, returning a handle to it
+    pub fn register_system<S: GameSystem + 'static>(
+        &mut self,
+        phase: SystemPhase,
+        system: S,
+    ) -> std::sync::Arc<parking_lot::Mutex<S>> {
+        self.custom_systems.register(phase, system)
+    }
+}
+
 /// Game loop manager
 pub struct GameLoop {
     config: GameLoopConfig,
@@ -101,32 +329,107 @@ pub struct GameLoop {
     /// Million-scale SoA AI manager with adaptive dormancy
     ai_manager_soa: ai_soa::AiManagerSoA,
     charge_manager: projectile::ChargeManager,
+    bot_placer: bot_placement::BotPlacer,
     debris_spawn_state: debris::DebrisSpawnState,
+    asteroid_field: asteroids::AsteroidField,
     /// Pending inputs per player, buffered until next tick
     /// OPTIMIZATION: Uses FxHashMap + SmallVec to minimize allocations
     pending_inputs: FxHashMap<PlayerId, InputBuffer>,
+    pause: PauseState,
+    weather: WeatherRoller,
+    /// Modifiers requested outside the tick, started on the next playing tick
+    queued_modifiers: Vec<GlobalModifier>,
+    /// Preset to switch the arena to on the next playing tick, and the transition length
+    queued_preset: Option<(ArenaPreset, f32)>,
+    /// Arena change to a preset in progress
+    arena_transition: Option<ArenaTransition>,
+    scheduler: EventScheduler,
+    /// Players who opted into orbit assist
+    orbit_assisted: rustc_hash::FxHashSet<PlayerId>,
+    /// Bots holding fire in an unseen fight, with their target
+    unseen_fights: FxHashMap<PlayerId, PlayerId>,
+    difficulty: difficulty::DifficultyController,
+    factions: factions::FactionController,
+    /// Per-player stats over the current match, for the end-of-match awards
+    tally: MatchTally,
+    /// Deaths of humans who left mid-match, reported by the next tick
+    departures: Vec<GameLoopEvent>,
     last_tick_time: Instant,
     accumulator: Duration,
     /// Last tick duration in microseconds (for adaptive AI)
     last_tick_us: u64,
     /// Last performance status (0=Excellent, 4=Catastrophic)
     last_performance_status: u64,
+    /// Broadcast channel for external event subscribers (analytics, bots, etc.)
+    event_tx: broadcast::Sender<GameLoopEvent>,
 }
 
 impl GameLoop {
     pub fn new(config: GameLoopConfig) -> Self {
         Self {
+            state: Self::fresh_state(&config),
             config,
-            state: GameState::new(),
             legacy_ai_manager: ai::AiManager::new(),
             ai_manager_soa: ai_soa::AiManagerSoA::new(),
             charge_manager: projectile::ChargeManager::new(),
+            bot_placer: bot_placement::BotPlacer::default(),
             debris_spawn_state: debris::DebrisSpawnState::new(),
+            asteroid_field: asteroids::AsteroidField::default(),
             pending_inputs: FxHashMap::default(),
+            pause: PauseState::Running,
+            weather: WeatherRoller::default(),
+            queued_modifiers: Vec::new(),
+            queued_preset: None,
+            arena_transition: None,
+            scheduler: EventScheduler::default(),
+            orbit_assisted: rustc_hash::FxHashSet::default(),
+            unseen_fights: FxHashMap::default(),
+            difficulty: difficulty::DifficultyController::default(),
+            factions: factions::FactionController::default(),
+            tally: MatchTally::default(),
+            departures: Vec::new(),
             last_tick_time: Instant::now(),
             accumulator: Duration::ZERO,
             last_tick_us: 0,
             last_performance_status: 0,
+            event_tx: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// State for a new match: the seeded arena (or the layout) and modifiers from config
+    fn fresh_state(config: &GameLoopConfig) -> GameState {
+        let mut state = GameState::with_arena_seed(config.arena_seed.unwrap_or_else(ArenaSeed::random));
+        if let Some(layout) = &config.arena_layout {
+            layout.apply(&mut state);
+        }
+        state.modifiers.set_projectile_economy(config.projectile_economy);
+        state.modifiers.set_heat(config.heat_config);
+        state.modifiers.set_fuel(config.fuel_config);
+        state
+    }
+
+    /// Subscribe to events produced by every tick
+    ///
+    /// Events are delivered in the same order `tick()` returns them. Slow
+    /// subscribers don't block the simulation; they observe `Lagged` instead.
+    pub fn subscribe(&self) -> broadcast::Receiver<GameLoopEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Number of active event subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.event_tx.receiver_count()
+    }
+
+    /// Publish tick events to subscribers
+    /// OPTIMIZATION: Skips cloning entirely when nobody is subscribed
+    fn publish_events(&self, events: &[GameLoopEvent]) {
+        if self.event_tx.receiver_count() == 0 {
+            return;
+        }
+        for event in events {
+            // Only fails when all receivers dropped between the check and send
+            let _ = self.event_tx.send(event.clone());
         }
     }
 
@@ -145,6 +448,31 @@ impl GameLoop {
         &mut self.state
     }
 
+    /// Change the projectile economy, now and for later matches
+    pub fn set_projectile_economy(&mut self, economy: ProjectileEconomyConfig) {
+        self.config.projectile_economy = economy;
+        self.state.modifiers.set_projectile_economy(economy);
+    }
+
+    /// Keep the match running forever (or let it end again)
+    pub fn set_endless(&mut self, endless: bool) {
+        self.config.endless = endless;
+    }
+
+    /// Fill the arena with asteroids (if enabled), replacing any there are,
+    /// and place the layout's hazards
+    pub fn spawn_asteroids(&mut self) {
+        self.asteroid_field.spawn_initial(&mut self.state, &self.config.asteroid_config);
+        if let Some(layout) = self.fixed_layout() {
+            layout.place_hazards(&mut self.state);
+        }
+    }
+
+    /// The arena layout, while the arena still follows it
+    fn fixed_layout(&self) -> Option<Arc<ArenaLayout>> {
+        self.config.arena_layout.clone().filter(|_| self.state.arena.fixed_layout)
+    }
+
     /// Queue player input for processing
     /// OPTIMIZATION: Uses SmallVec to avoid heap allocation for typical input counts
     pub fn queue_input(&mut self, player_id: PlayerId, input: PlayerInput) {
@@ -154,6 +482,225 @@ impl GameLoop {
             .push(input);
     }
 
+    /// Start a global modifier on the next playing tick (ignored if already running)
+    pub fn queue_modifier(&mut self, modifier: GlobalModifier) {
+        self.queued_modifiers.push(modifier);
+    }
+
+    /// Switch the arena to a preset over `duration` seconds on the next playing
+    /// tick. Later matches keep the preset's layout.
+    pub fn queue_preset(&mut self, preset: ArenaPreset, duration: f32) {
+        self.queued_preset = Some((preset, duration));
+    }
+
+    /// Start a queued preset and move the arena along its transition
+    fn update_arena_transition(&mut self, events: &mut Vec<GameLoopEvent>) {
+        if let Some((preset, duration)) = self.queued_preset.take() {
+            let running: Vec<_> = self.state.modifiers.active().iter().map(|a| a.modifier).collect();
+            for modifier in running {
+                let kept = preset.modifiers.iter().any(|a| a.modifier == modifier);
+                if !kept && self.state.modifiers.deactivate(modifier) {
+                    events.push(GameLoopEvent::ModifierEnded { modifier });
+                }
+            }
+            for active in &preset.modifiers {
+                Self::start_modifier(&mut self.state, active.modifier, active.remaining, events);
+            }
+
+            tracing::info!("Arena changing to preset {} over {}s", preset.name(), duration);
+            self.arena_transition = Some(ArenaTransition::start(&mut self.state, &preset.layout, duration));
+            events.push(GameLoopEvent::ArenaTransition { name: preset.name().to_string(), duration });
+            self.config.arena_layout = Some(Arc::new(preset.layout));
+        }
+
+        if let Some(transition) = &mut self.arena_transition {
+            if transition.advance(&mut self.state, DT) {
+                self.arena_transition = None;
+            }
+        }
+    }
+
+    /// Expire finished modifiers and start rolled or queued ones.
+    /// Durations run in real time, like the match clock.
+    fn update_modifiers(&mut self, events: &mut Vec<GameLoopEvent>) {
+        for modifier in self.state.modifiers.update(DT) {
+            events.push(GameLoopEvent::ModifierEnded { modifier });
+        }
+
+        let idle = self.state.modifiers.is_empty() && self.queued_modifiers.is_empty();
+        let rolled = self.weather.roll(&self.config.weather_config, idle, DT, &mut rand::thread_rng());
+        for modifier in self.queued_modifiers.drain(..).chain(rolled) {
+            Self::start_modifier(&mut self.state, modifier, modifier.duration_secs(), events);
+        }
+    }
+
+    /// Start `modifier` for `duration` seconds unless it's already running
+    fn start_modifier(state: &mut GameState, modifier: GlobalModifier, duration: f32, events: &mut Vec<GameLoopEvent>) {
+        if state.modifiers.activate(modifier, duration) {
+            let (parameter, factor) = modifier.effect();
+            tracing::info!("Modifier {} started: {} x{} for {}s", modifier.name(), parameter.key(), factor, duration);
+            events.push(GameLoopEvent::ModifierStarted { modifier, duration });
+        }
+    }
+
+    /// Announce upcoming scheduled events and run due ones. `now` is wall-clock
+    /// unix seconds: schedules follow the calendar, not the match clock.
+    fn run_schedule(&mut self, now: u64, events: &mut Vec<GameLoopEvent>) {
+        let config = &self.config.schedule_config;
+        if config.events.is_empty() {
+            return;
+        }
+        for signal in self.scheduler.update(&config.events, config.announce_lead_secs, now) {
+            match signal {
+                ScheduleSignal::Upcoming { action, starts_in } => {
+                    events.push(GameLoopEvent::ScheduledEventUpcoming { action, starts_in });
+                }
+                ScheduleSignal::Due { action } => {
+                    tracing::info!("Scheduled event: {}", action.description());
+                    match action {
+                        ScheduledAction::Modifier { modifier, duration } => {
+                            Self::start_modifier(&mut self.state, modifier, duration, events);
+                        }
+                        ScheduledAction::BossSpawn => {
+                            let player_id = self.spawn_boss();
+                            events.push(GameLoopEvent::BossSpawned { player_id });
+                        }
+                        ScheduledAction::ArenaReset => {
+                            self.reset_arena();
+                            events.push(GameLoopEvent::ArenaReset);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drop a heavy boss bot into the arena
+    pub fn spawn_boss(&mut self) -> PlayerId {
+        let name = format!("Boss {}", ai::generate_bot_name());
+        let mut boss = crate::game::state::Player::new(Uuid::new_v4(), name, true, self.state.players.len() as u8);
+        boss.mass = schedule::BOSS_MASS;
+        self.add_player(boss)
+    }
+
+    /// Clear projectiles and debris, restock the arena and respawn every alive
+    /// player at starting mass. Scores and the well layout are kept.
+    pub fn reset_arena(&mut self) {
+        use crate::game::constants::{arena::COLLAPSE_INTERVAL, mass, spawn::PROTECTION_DURATION};
+
+        self.state.projectiles.clear();
+        self.state.debris.clear();
+        self.state.gravity_waves.clear();
+        self.charge_manager = projectile::ChargeManager::new();
+        let arena = &mut self.state.arena;
+        arena.collapse_phase = 0;
+        arena.is_collapsing = false;
+        arena.collapse_progress = 0.0;
+        arena.time_until_collapse = COLLAPSE_INTERVAL;
+
+        debris::spawn_initial(&mut self.state, &self.config.debris_spawn_config);
+        debris::spawn_around_wells(&mut self.state, &self.config.debris_spawn_config);
+        self.spawn_asteroids();
+
+        let alive: Vec<(PlayerId, bool)> = self.state.alive_players().map(|p| (p.id, p.is_bot)).collect();
+        let wells: Vec<_> = self.state.arena.gravity_wells.values().cloned().collect();
+        let tick = self.state.tick;
+        for (player_id, is_bot) in alive {
+            let position = self.spawn_position(player_id, is_bot);
+            if let Some(player) = self.state.get_player_mut(player_id) {
+                player.position = position;
+                player.velocity = arena::spawn_velocity_for_well(position, &wells);
+                player.mass = mass::STARTING;
+                player.spawn_protection = PROTECTION_DURATION;
+                player.spawn_tick = tick;
+                player.heat = 0.0;
+                player.fuel = 1.0;
+                player.effects.clear();
+            }
+        }
+    }
+
+    /// Freeze the simulation (networking keeps running). Pausing again while
+    /// resuming cancels the countdown. Returns false if already paused.
+    pub fn pause(&mut self, reason: String) -> bool {
+        if matches!(self.pause, PauseState::Paused { .. }) {
+            return false;
+        }
+        self.pause = PauseState::Paused { reason };
+        true
+    }
+
+    /// Start the resume countdown. Returns the countdown length, or None if
+    /// the match isn't paused (or is already resuming).
+    pub fn resume(&mut self) -> Option<f32> {
+        let PauseState::Paused { reason } = &self.pause else {
+            return None;
+        };
+        self.pause = PauseState::Resuming {
+            reason: reason.clone(),
+            remaining: RESUME_COUNTDOWN_SECS,
+        };
+        Some(RESUME_COUNTDOWN_SECS)
+    }
+
+    pub fn pause_state(&self) -> &PauseState {
+        &self.pause
+    }
+
+    /// Advance the pause state by one tick. Returns true while the simulation
+    /// stays frozen; emits `MatchResumed` when the countdown completes.
+    fn advance_pause(&mut self, events: &mut Vec<GameLoopEvent>) -> bool {
+        match &mut self.pause {
+            PauseState::Running => return false,
+            PauseState::Paused { .. } => {}
+            PauseState::Resuming { remaining, .. } => {
+                *remaining -= DT;
+                // Half-tick slack so float drift can't add an extra tick
+                if *remaining < DT * 0.5 {
+                    self.pause = PauseState::Running;
+                    events.push(GameLoopEvent::MatchResumed);
+                    return false;
+                }
+            }
+        }
+
+        match self.config.pause_input_policy {
+            PauseInputPolicy::Drop => self.pending_inputs.clear(),
+            PauseInputPolicy::Buffer => {
+                for buffer in self.pending_inputs.values_mut() {
+                    if buffer.len() > MAX_INPUTS_PER_TICK {
+                        buffer.drain(..buffer.len() - MAX_INPUTS_PER_TICK);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Simulated seconds per tick
+    fn sim_dt(&self) -> f32 {
+        DT * self.config.sim_speed
+    }
+
+    /// Tone down every bot (low aggression and accuracy), e.g. for matches with tutorial players
+    pub fn soften_bots(&mut self) {
+        self.ai_manager_soa.soften_all();
+    }
+
+    /// Change the well gravity multiplier (takes effect next tick)
+    pub fn set_gravity_strength(&mut self, strength: f32) {
+        self.config.gravity_config.strength = strength;
+    }
+
+    /// Enable or disable orbit assist for a player
+    pub fn set_orbit_assist(&mut self, player_id: PlayerId, enabled: bool) {
+        if enabled {
+            self.orbit_assisted.insert(player_id);
+        } else {
+            self.orbit_assisted.remove(&player_id);
+        }
+    }
+
     /// Process accumulated time and run ticks
     pub fn update(&mut self) -> Vec<GameLoopEvent> {
         let now = Instant::now();
@@ -174,7 +721,14 @@ impl GameLoop {
 
     /// Run a single game tick
     pub fn tick(&mut self) -> Vec<GameLoopEvent> {
-        let mut events = Vec::new();
+        let mut events = std::mem::take(&mut self.departures);
+
+        // Referee pause freezes everything, including the match phase
+        if self.advance_pause(&mut events) {
+            self.state.tick += 1;
+            self.publish_events(&events);
+            return events;
+        }
 
         // Update match phase
         if let Some(phase_event) = self.update_match_phase() {
@@ -184,27 +738,54 @@ impl GameLoop {
         // Only process game logic during playing phase
         if self.state.match_state.phase != MatchPhase::Playing {
             self.state.tick += 1;
+            self.publish_events(&events);
             return events;
         }
 
+        // Simulated seconds this tick (shorter than wall time in slow-mode rooms)
+        let dt = self.sim_dt();
+
         // Process player inputs
-        self.process_inputs();
+        let thrusting = self.process_inputs();
+
+        // Steady assisted players who aren't thrusting this tick
+        if !self.orbit_assisted.is_empty() {
+            let idle = self.orbit_assisted.iter().filter(|id| !thrusting.contains(id));
+            orbit_assist::update(&mut self.state, idle, &self.config.orbit_assist_config, dt);
+        }
+
+        // Ease bots off solo humans who keep dying
+        if self.state.tick % difficulty::UPDATE_INTERVAL_TICKS == 0 {
+            self.difficulty.update(&self.state, &mut self.ai_manager_soa, &self.config.difficulty_config);
+        }
+
+        // Keep bot factions homed and run their raids
+        if self.state.tick % factions::UPDATE_INTERVAL_TICKS == 0 {
+            let elapsed = dt * factions::UPDATE_INTERVAL_TICKS as f32;
+            let config = &self.config.faction_config;
+            self.factions.update(&mut self.state, &mut self.ai_manager_soa, config, elapsed);
+        }
 
         // Update AI (SoA with adaptive dormancy)
         self.ai_manager_soa.update_with_metrics(
             &self.state,
-            DT,
+            dt,
             self.last_tick_us,
             self.last_performance_status,
         );
+        // Bot fights no human can see are rolled instead of simulated
+        self.resolve_unseen_fights(&mut events);
         self.process_ai_inputs();
+        self.config.custom_systems.run_phase(SystemPhase::Input, &mut self.state, dt, &mut events);
 
         // Run physics systems
-        gravity::update_central_with_config(&mut self.state, &self.config.gravity_config, DT);
-        if self.config.enable_inter_entity_gravity {
-            gravity::update_inter_entity(&mut self.state, DT);
-        }
-        physics::update(&mut self.state, DT);
+        physics::step(
+            &mut self.state,
+            self.config.integrator,
+            &self.config.gravity_config,
+            self.config.enable_inter_entity_gravity,
+            dt,
+        );
 
         // Update gravity wave explosions (occasional random events)
         // Only if feature is enabled via config
@@ -220,7 +801,7 @@ impl GameLoop {
             let wave_events = gravity::update_explosions(
                 &mut self.state,
                 &self.config.gravity_wave_config,
-                DT,
+                dt,
                 target_wells,
                 escape_radius,
             );
@@ -239,15 +820,25 @@ impl GameLoop {
                 }
             }
             // Update active gravity waves (expanding and pushing players)
-            gravity::update_waves(&mut self.state, &self.config.gravity_wave_config, DT);
+            gravity::update_waves(&mut self.state, &self.config.gravity_wave_config, dt);
         }
+        self.config.custom_systems.run_phase(SystemPhase::Physics, &mut self.state, dt, &mut events);
 
         // Run collision system
-        let collision_events = collision::update(&mut self.state);
+        let collision_events = collision::update_with_rules(
+            &mut self.state,
+            &self.config.collision_matrix,
+            &self.config.collision_response,
+        );
         for event in collision_events {
             match event {
                 collision::CollisionEvent::Kill { killer_id, victim_id } => {
                     events.push(GameLoopEvent::PlayerKilled { killer_id, victim_id });
+                    events.push(GameLoopEvent::PlayerDied {
+                        victim_id,
+                        killer_id: Some(killer_id),
+                        cause: DeathCause::Crush,
+                    });
                 }
                 collision::CollisionEvent::Deflection { player_a, player_b, position, intensity } => {
                     events.push(GameLoopEvent::PlayerDeflection {
@@ -256,28 +847,118 @@ impl GameLoop {
                         position,
                         intensity,
                     });
+                    // A close fight can still leave either side below minimum mass
+                    for (victim_id, other) in [(player_a, player_b), (player_b, player_a)] {
+                        if self.state.get_player(victim_id).is_some_and(|p| !p.alive) {
+                            events.push(GameLoopEvent::PlayerDied {
+                                victim_id,
+                                killer_id: Some(other),
+                                cause: DeathCause::Crush,
+                            });
+                        }
+                    }
                 }
-                _ => {} // ProjectileAbsorbed, DebrisCollected - no visual event needed
+                collision::CollisionEvent::ProjectileAbsorbed {
+                    player_id,
+                    projectile_id,
+                    owner_id,
+                    position,
+                    mass_gained,
+                } if owner_id != player_id => {
+                    events.push(GameLoopEvent::PlayerHit {
+                        shooter_id: owner_id,
+                        victim_id: player_id,
+                        projectile_id,
+                        amount: mass_gained,
+                        position,
+                    });
+                }
+                collision::CollisionEvent::DebrisCollected { player_id, debris_id, .. } => {
+                    events.push(GameLoopEvent::DebrisCollected { player_id, debris_id });
+                }
+                _ => {} // Own projectiles, DebrisSwept, AsteroidHit - no visual event needed
             }
         }
 
+        // Break asteroids shot down to nothing and replace them over time
+        let broken = self.asteroid_field.update(
+            &mut self.state,
+            &self.config.asteroid_config,
+            &self.config.debris_spawn_config,
+            dt,
+        );
+        events.extend(broken.into_iter().map(|b| GameLoopEvent::AsteroidDestroyed {
+            asteroid_id: b.asteroid_id,
+            position: b.position,
+            fragments: b.fragments,
+        }));
+        self.config.custom_systems.run_phase(SystemPhase::Collision, &mut self.state, dt, &mut events);
+
         // Run arena system
-        let arena_events = arena::update(&mut self.state, DT);
+        let arena_events = arena::update_with_config(
+            &mut self.state,
+            dt,
+            &self.config.collision_matrix,
+            &self.config.boundary_config,
+        );
         for event in arena_events {
-            if let arena::ArenaEvent::CollapseStarted { phase, new_safe_radius } = event {
-                events.push(GameLoopEvent::ZoneCollapse {
-                    phase,
-                    new_radius: new_safe_radius,
-                });
+            match event {
+                arena::ArenaEvent::CollapseStarted { phase, new_safe_radius } => {
+                    events.push(GameLoopEvent::ZoneCollapse {
+                        phase,
+                        new_radius: new_safe_radius,
+                    });
+                }
+                arena::ArenaEvent::PlayerEnteredCore { player_id, well_id, position } => {
+                    events.push(GameLoopEvent::PlayerFellIntoWell { player_id, well_id, position });
+                    events.push(GameLoopEvent::PlayerDied {
+                        victim_id: player_id,
+                        killer_id: None,
+                        cause: DeathCause::Well,
+                    });
+                }
+                arena::ArenaEvent::PlayerDrained { player_id } => {
+                    events.push(GameLoopEvent::PlayerDied {
+                        victim_id: player_id,
+                        killer_id: None,
+                        cause: DeathCause::Boundary,
+                    });
+                }
+                arena::ArenaEvent::PlayerOutsideArena { .. } => {}
             }
         }
 
+        // Well claiming (and passive debris around owned wells)
+        let capture_events = well_capture::update(
+            &mut self.state,
+            &self.config.well_capture_config,
+            &self.config.debris_spawn_config,
+            dt,
+        );
+        for event in capture_events {
+            events.push(match event {
+                well_capture::CaptureEvent::Captured { well_id, owner_id, previous_owner } => {
+                    GameLoopEvent::WellCaptured { well_id, owner_id, previous_owner }
+                }
+                well_capture::CaptureEvent::Contested { well_id } => GameLoopEvent::WellContested { well_id },
+            });
+        }
+
+        self.update_modifiers(&mut events);
+        self.update_arena_transition(&mut events);
+
+        // Scheduled events are checked once a second
+        if self.state.tick % TICK_RATE as u64 == 0 {
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            self.run_schedule(now.as_secs(), &mut events);
+        }
+
         // Spawn new debris over time (if enabled)
         debris::update(
             &mut self.state,
             &self.config.debris_spawn_config,
             &mut self.debris_spawn_state,
-            DT,
+            dt,
         );
 
         // Spawn debris around gravity wells (feeding zones)
@@ -285,16 +966,21 @@ impl GameLoop {
             &mut self.state,
             &self.config.debris_spawn_config,
             &mut self.debris_spawn_state.well_accumulator,
-            DT,
+            dt,
         );
 
+        self.config.custom_systems.run_phase(SystemPhase::Late, &mut self.state, dt, &mut events);
+
         // Update match time
         self.state.match_state.match_time += DT;
 
+        if self.state.match_state.phase == MatchPhase::Playing {
+            self.tally.observe(&self.state, &events, DT);
+        }
+
         // Check for match end
-        if let Some(reason) = check_match_end(&self.state) {
-            self.end_match(reason);
-            let result = determine_result(&self.state);
+        if let Some(reason) = check_match_end(&self.state).filter(|_| !self.config.endless) {
+            let result = self.end_match(reason);
             events.push(GameLoopEvent::MatchEnded { result });
         }
 
@@ -303,17 +989,18 @@ impl GameLoop {
             tick: self.state.tick,
         });
 
+        self.publish_events(&events);
+
         events
     }
 
-    /// Process pending player inputs
+    /// Process pending player inputs, returning the players who thrusted
     /// OPTIMIZATION: Uses SmallVec + FxHashMap to minimize allocation overhead
-    fn process_inputs(&mut self) {
-        // Max inputs to process per player per tick (prevents flooding)
-        const MAX_INPUTS_PER_TICK: usize = 10;
-
+    fn process_inputs(&mut self) -> Vec<PlayerId> {
+        let dt = self.sim_dt();
         let inputs: Vec<(PlayerId, InputBuffer)> =
             self.pending_inputs.drain().collect();
+        let mut thrusting = Vec::new();
 
         for (player_id, player_inputs) in inputs {
             if player_inputs.is_empty() {
@@ -322,15 +1009,19 @@ impl GameLoop {
 
             let coalesced = Self::coalesce_inputs(&player_inputs, MAX_INPUTS_PER_TICK);
 
-            physics::apply_thrust(&mut self.state, player_id, &coalesced, DT);
+            if physics::apply_thrust(&mut self.state, player_id, &coalesced, dt) {
+                thrusting.push(player_id);
+            }
             projectile::process_input(
                 &mut self.state,
                 player_id,
                 &coalesced,
                 &mut self.charge_manager,
-                DT,
+                dt,
             );
         }
+
+        thrusting
     }
 
     /// Coalesce multiple inputs into one, preserving transient event flags.
@@ -368,6 +1059,7 @@ impl GameLoop {
     /// Process AI inputs
     fn process_ai_inputs(&mut self) {
         let tick = self.state.tick;
+        let dt = self.sim_dt();
 
         // Collect bot player IDs first to avoid borrow issues
         let bot_ids: Vec<PlayerId> = self
@@ -379,19 +1071,67 @@ impl GameLoop {
             .collect();
 
         for player_id in bot_ids {
-            if let Some(input) = self.ai_manager_soa.get_input(player_id, tick) {
-                physics::apply_thrust(&mut self.state, player_id, &input, DT);
+            if let Some(mut input) = self.ai_manager_soa.get_input(player_id, tick) {
+                // Shots in an unseen fight are accounted for by the resolver
+                if self.unseen_fights.contains_key(&player_id) {
+                    input.fire = false;
+                    input.fire_released = false;
+                }
+                physics::apply_thrust(&mut self.state, player_id, &input, dt);
                 projectile::process_input(
                     &mut self.state,
                     player_id,
                     &input,
                     &mut self.charge_manager,
-                    DT,
+                    dt,
                 );
             }
         }
     }
 
+    /// Every `interval_ticks`, resolve the bot-vs-bot fights that were held out
+    /// of the simulation since the last roll, then pick the ones to hold next
+    fn resolve_unseen_fights(&mut self, events: &mut Vec<GameLoopEvent>) {
+        let config = &self.config.combat_resolver_config;
+        if !config.enabled {
+            self.unseen_fights.clear();
+            return;
+        }
+        if self.state.tick % config.interval_ticks.max(1) as u64 != 0 {
+            return;
+        }
+
+        let ai = &self.ai_manager_soa;
+        let candidates = (0..ai.count).filter(|&i| ai.behaviors[i] == ai_soa::AiBehavior::Chase).filter_map(|i| {
+            let target = ai.target_ids[i]?;
+            // Only bots: a human target sees the fight
+            let target_index = ai.get_index(target)? as usize;
+            Some(combat_resolver::Engagement {
+                attacker: ai.bot_ids[i],
+                target,
+                aggression: ai.effective_aggression(i),
+                attacker_accuracy: ai.effective_accuracy(i),
+                target_accuracy: ai.effective_accuracy(target_index),
+            })
+        });
+        let fights = combat_resolver::unseen(&self.state, candidates, config);
+
+        // Fights that just started were simulated until now; they're rolled next time
+        let held: Vec<_> =
+            fights.iter().filter(|f| self.unseen_fights.get(&f.attacker) == Some(&f.target)).copied().collect();
+        let window = config.interval_ticks as f32 * self.sim_dt();
+        let kills = combat_resolver::resolve(&mut self.state, &held, config, window, &mut rand::thread_rng());
+        for kill in kills {
+            events.push(GameLoopEvent::PlayerKilled { killer_id: kill.killer_id, victim_id: kill.victim_id });
+            events.push(GameLoopEvent::PlayerDied {
+                victim_id: kill.victim_id,
+                killer_id: Some(kill.killer_id),
+                cause: DeathCause::Projectile,
+            });
+        }
+        self.unseen_fights = fights.iter().map(|f| (f.attacker, f.target)).collect();
+    }
+
     /// Update match phase (countdown, etc.)
     fn update_match_phase(&mut self) -> Option<GameLoopEvent> {
         match self.state.match_state.phase {
@@ -411,11 +1151,13 @@ impl GameLoop {
                 if self.state.match_state.countdown_time <= 0.0 {
                     self.state.match_state.phase = MatchPhase::Playing;
                     self.state.match_state.match_time = 0.0;
+                    self.tally.clear();
 
                     // Spawn initial debris when match starts
                     debris::spawn_initial(&mut self.state, &self.config.debris_spawn_config);
                     // Spawn debris around gravity wells (feeding zones)
                     debris::spawn_around_wells(&mut self.state, &self.config.debris_spawn_config);
+                    self.spawn_asteroids();
 
                     return Some(GameLoopEvent::PhaseChange {
                         phase: MatchPhase::Playing,
@@ -430,30 +1172,48 @@ impl GameLoop {
     }
 
     /// End the match
-    fn end_match(&mut self, _reason: MatchEndReason) {
+    fn end_match(&mut self, _reason: MatchEndReason) -> MatchResult {
         self.state.match_state.phase = MatchPhase::Ended;
-        let result = determine_result(&self.state);
+        let result = determine_result(&self.state, &self.tally);
         self.state.match_state.winner_id = result.winner_id;
+        result
     }
 
-    /// Add a player to the game
-    pub fn add_player(&mut self, mut player: crate::game::state::Player) -> PlayerId {
-        let id = player.id;
-
-        // Collect existing player positions for safe spawn
+    /// Pick a spawn position near a gravity well, away from other alive players.
+    /// Bots follow the placement policy (a share spawn within view of humans).
+    pub fn spawn_position(&mut self, player_id: PlayerId, is_bot: bool) -> Vec2 {
         let existing_positions: Vec<_> = self
             .state
             .players
             .values()
-            .filter(|p| p.alive)
+            .filter(|p| p.alive && p.id != player_id)
             .map(|p| p.position)
             .collect();
 
-        // Assign safe spawn position near a gravity well, away from other players
+        if is_bot {
+            return self.bot_placer.place(
+                &self.state,
+                &self.ai_manager_soa.zone_grid,
+                &self.config.bot_placement_config,
+                &existing_positions,
+            );
+        }
+        if let Some(position) = self.fixed_layout().and_then(|l| l.spawn_position(&existing_positions)) {
+            return position;
+        }
         let wells: Vec<_> = self.state.arena.gravity_wells.values().cloned().collect();
-        player.position = arena::safe_spawn_near_well(&wells, &existing_positions);
+        arena::safe_spawn_near_well(&wells, &existing_positions)
+    }
+
+    /// Add a player to the game
+    pub fn add_player(&mut self, mut player: crate::game::state::Player) -> PlayerId {
+        let id = player.id;
+
+        // Assign safe spawn position near a gravity well, away from other players
+        player.position = self.spawn_position(id, player.is_bot);
 
         // Use orbital velocity relative to nearest well
+        let wells: Vec<_> = self.state.arena.gravity_wells.values().cloned().collect();
         player.velocity = arena::spawn_velocity_for_well(player.position, &wells);
 
         // Record spawn tick for birth animation detection
@@ -468,19 +1228,49 @@ impl GameLoop {
         id
     }
 
-    /// Remove a player from the game
-    pub fn remove_player(&mut self, player_id: PlayerId) -> Option<crate::game::state::Player> {
+    /// Place a player handed off by another arena's loop (see `hand_off_player`).
+    /// They keep their mass and velocity; without a `position` they spawn as usual.
+    pub fn receive_player(&mut self, mut player: crate::game::state::Player, position: Option<Vec2>) -> PlayerId {
+        let id = player.id;
+        player.position = position.unwrap_or_else(|| self.spawn_position(id, player.is_bot));
+        player.spawn_tick = self.state.tick;
+        self.state.add_player(player);
+        id
+    }
+
+    /// Take a player out of this loop to hand them to another arena. Unlike
+    /// `remove_player` this is not a departure: nobody dies.
+    pub fn hand_off_player(&mut self, player_id: PlayerId) -> Option<crate::game::state::Player> {
         self.ai_manager_soa.unregister_bot(player_id);
         self.charge_manager.remove(player_id);
         self.pending_inputs.remove(&player_id);
+        self.orbit_assisted.remove(&player_id);
         self.state.remove_player(player_id)
     }
 
+    /// Remove a player from the game. A human leaving alive mid-match dies of `Disconnect`.
+    pub fn remove_player(&mut self, player_id: PlayerId) -> Option<crate::game::state::Player> {
+        let player = self.hand_off_player(player_id)?;
+        if player.alive && !player.is_bot && self.state.match_state.phase == MatchPhase::Playing {
+            self.departures.push(GameLoopEvent::PlayerDied {
+                victim_id: player_id,
+                killer_id: None,
+                cause: DeathCause::Disconnect,
+            });
+        }
+        Some(player)
+    }
+
     /// Reset charge state for a player (e.g., on respawn)
     pub fn reset_charge(&mut self, player_id: PlayerId) {
         self.charge_manager.reset(player_id);
     }
 
+    /// Current charge state of every player
+    pub fn charges(&self) -> &projectile::ChargeManager {
+        &self.charge_manager
+    }
+
     /// Add AI bots to fill the game
     /// Uses same spawn mechanics as human players (add_player handles positioning)
     pub fn fill_with_bots(&mut self, total_players: usize) {
@@ -505,12 +1295,22 @@ impl GameLoop {
 
     /// Reset the game for a new match
     pub fn reset(&mut self) {
-        self.state = GameState::new();
+        self.state = Self::fresh_state(&self.config);
         self.legacy_ai_manager = ai::AiManager::new();
         self.ai_manager_soa = ai_soa::AiManagerSoA::new();
         self.charge_manager = projectile::ChargeManager::new();
         self.debris_spawn_state = debris::DebrisSpawnState::new();
+        self.asteroid_field = asteroids::AsteroidField::default();
         self.pending_inputs.clear();
+        self.pause = PauseState::Running;
+        self.weather = WeatherRoller::default();
+        self.queued_modifiers.clear();
+        self.arena_transition = None;
+        self.unseen_fights.clear();
+        self.difficulty = difficulty::DifficultyController::default();
+        self.factions = factions::FactionController::default();
+        self.tally.clear();
+        self.departures.clear();
         self.last_tick_us = 0;
         self.last_performance_status = 0;
     }
@@ -526,10 +1326,39 @@ impl GameLoop {
         self.last_performance_status = performance_status;
     }
 
+    /// Viewports humans are looking through this tick (center, view radius).
+    /// Bots in zones they cover keep full detail.
+    pub fn set_human_views(&mut self, views: Vec<(Vec2, f32)>) {
+        self.ai_manager_soa.set_human_views(views);
+    }
+
     /// Get AI manager statistics for monitoring/debugging
     pub fn ai_stats(&self) -> ai_soa::AiManagerStats {
         self.ai_manager_soa.stats()
     }
+
+    /// Bot AI decision state, to be saved alongside `state()`
+    #[allow(dead_code)] // Library API for state save/restore and server handoff
+    pub fn ai_snapshot(&self) -> ai_soa::AiSnapshot {
+        self.ai_manager_soa.snapshot()
+    }
+
+    /// Restore bot AI decision state saved by `ai_snapshot`, after the game
+    /// state it belongs to. Brains of players that are no longer bots in the
+    /// current state are skipped. Returns the number of bots restored.
+    #[allow(dead_code)] // Library API for state save/restore and server handoff
+    pub fn restore_ai(&mut self, snapshot: &ai_soa::AiSnapshot) -> Result<usize, ai_soa::AiRestoreError> {
+        let mut snapshot = snapshot.clone();
+        snapshot
+            .bots
+            .retain(|brain| self.state.get_player(brain.player_id).is_some_and(|p| p.is_bot));
+        self.ai_manager_soa.restore(&snapshot)
+    }
+
+    /// Bot AI zone grid as of the last AI update
+    pub fn zone_grid(&self) -> &ai_soa::ZoneGrid {
+        &self.ai_manager_soa.zone_grid
+    }
 }
 
 #[cfg(test)]
@@ -554,6 +1383,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         }
     }
 
@@ -588,6 +1421,26 @@ mod tests {
         assert!(game_loop.state().get_player(id).is_none());
     }
 
+    #[test]
+    fn test_leaving_mid_match_is_a_disconnect_death() {
+        let mut game_loop = GameLoop::new(GameLoopConfig::default());
+        let waiting = game_loop.add_player(create_player("Early", false));
+        game_loop.remove_player(waiting);
+        game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+        let id = game_loop.add_player(create_player("Quitter", false));
+        game_loop.remove_player(id);
+
+        let deaths: Vec<_> = game_loop
+            .tick()
+            .into_iter()
+            .filter_map(|e| match e {
+                GameLoopEvent::PlayerDied { victim_id, cause, .. } => Some((victim_id, cause)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(deaths, [(id, DeathCause::Disconnect)]);
+    }
+
     #[test]
     fn test_fill_with_bots() {
         let mut game_loop = GameLoop::new(GameLoopConfig::default());
@@ -729,6 +1582,65 @@ mod tests {
         assert_eq!(game_loop.state().projectiles.len(), 0);
     }
 
+    #[test]
+    fn test_enemy_windup_visible_in_snapshot() {
+        use crate::net::protocol::GameSnapshot;
+
+        let mut game_loop = GameLoop::new(GameLoopConfig::default());
+        let player = create_player("Charger", false);
+        let player_id = player.id;
+        game_loop.add_player(player);
+        game_loop.add_player(create_player("Opponent", false));
+        game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+
+        let charge_of = |game_loop: &GameLoop| {
+            let mut snapshot = GameSnapshot::from_game_state(game_loop.state());
+            snapshot.apply_charges(game_loop.charges());
+            snapshot.players.iter().find(|p| p.id == player_id).unwrap().clone()
+        };
+        assert!(!charge_of(&game_loop).is_charging());
+
+        for i in 1..=15 {
+            game_loop.queue_input(
+                player_id,
+                PlayerInput { sequence: i, tick: i, fire: true, aim: Vec2::new(1.0, 0.0), ..Default::default() },
+            );
+            game_loop.tick();
+        }
+        let charging = charge_of(&game_loop);
+        assert!(charging.is_charging());
+        // Half a second held: halfway to a full charge
+        assert!((charging.charge_ratio() - 0.5).abs() < 0.01, "ratio {}", charging.charge_ratio());
+
+        // A dead player never shows a windup
+        game_loop.state_mut().get_player_mut(player_id).unwrap().alive = false;
+        assert!(!charge_of(&game_loop).is_charging());
+    }
+
+    #[test]
+    fn test_projectile_hit_reported_for_other_players_only() {
+        let mut game_loop = GameLoop::new(GameLoopConfig::default());
+        let shooter = create_player("Shooter", false);
+        let victim = create_player("Victim", false);
+        let (shooter_id, victim_id) = (shooter.id, victim.id);
+        game_loop.add_player(shooter);
+        game_loop.add_player(victim);
+        game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+        let target = game_loop.state().get_player(victim_id).unwrap().position;
+        game_loop.state_mut().get_player_mut(victim_id).unwrap().spawn_protection = 0.0;
+        game_loop.state_mut().add_projectile(shooter_id, target, Vec2::ZERO, 15.0);
+
+        let hits: Vec<_> = game_loop
+            .tick()
+            .into_iter()
+            .filter_map(|e| match e {
+                GameLoopEvent::PlayerHit { shooter_id, victim_id, amount, .. } => Some((shooter_id, victim_id, amount)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(hits, vec![(shooter_id, victim_id, 15.0)]);
+    }
+
     #[test]
     fn test_fire_released_preserved_when_multiple_inputs_coalesce() {
         // Regression test: When multiple inputs arrive in one tick,
@@ -878,4 +1790,262 @@ mod tests {
         assert!(coalesced.fire, "fire should be true when any input had fire=true and fire_released=true");
         assert!(coalesced.fire_released, "fire_released should be true");
     }
+
+    #[test]
+    fn test_subscribe_receives_tick_events() {
+        let mut game_loop = GameLoop::new(GameLoopConfig::default());
+        game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+        let mut rx = game_loop.subscribe();
+        assert_eq!(game_loop.subscriber_count(), 1);
+
+        let events = game_loop.tick();
+        let mut received = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            received.push(event);
+        }
+
+        assert_eq!(received.len(), events.len());
+        assert!(matches!(received.last(), Some(GameLoopEvent::Tick { tick: 1 })));
+    }
+
+    #[test]
+    fn test_tick_without_subscribers() {
+        let mut game_loop = GameLoop::new(GameLoopConfig::default());
+        assert_eq!(game_loop.subscriber_count(), 0);
+        // Must not panic or block when nobody listens
+        game_loop.tick();
+
+        let rx = game_loop.subscribe();
+        drop(rx);
+        game_loop.tick();
+        assert_eq!(game_loop.state().tick, 2);
+    }
+
+    struct TickRecorder {
+        seen_ticks: Vec<u64>,
+    }
+
+    impl GameSystem for TickRecorder {
+        fn run(&mut self, state: &mut GameState, _dt: f32, events: &mut Vec<GameLoopEvent>) {
+            self.seen_ticks.push(state.tick);
+            events.push(GameLoopEvent::ZoneCollapse { phase: 99, new_radius: 0.0 });
+        }
+    }
+
+    #[test]
+    fn test_custom_system_runs_each_playing_tick() {
+        let mut config = GameLoopConfig::default();
+        let recorder = config.register_system(SystemPhase::Late, TickRecorder { seen_ticks: Vec::new() });
+        let mut game_loop = GameLoop::new(config);
+
+        // Not playing yet: custom systems are skipped like built-in ones
+        game_loop.tick();
+        assert!(recorder.lock().seen_ticks.is_empty());
+
+        game_loop.add_player(create_player("A", false));
+        game_loop.add_player(create_player("B", false));
+        game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+        let events = game_loop.tick();
+        game_loop.tick();
+
+        assert_eq!(recorder.lock().seen_ticks, vec![1, 2]);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GameLoopEvent::ZoneCollapse { phase: 99, .. })));
+    }
+
+    fn playing_loop(config: GameLoopConfig) -> (GameLoop, PlayerId) {
+        let mut game_loop = GameLoop::new(config);
+        let player = create_player("A", false);
+        let player_id = player.id;
+        game_loop.add_player(player);
+        game_loop.add_player(create_player("B", false));
+        game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+        (game_loop, player_id)
+    }
+
+    #[test]
+    fn test_pause_freezes_simulation_and_resumes_after_countdown() {
+        let (mut game_loop, player_id) = playing_loop(GameLoopConfig::default());
+        game_loop.state_mut().get_player_mut(player_id).unwrap().velocity = Vec2::new(100.0, 0.0);
+
+        assert!(game_loop.pause("Technical issue".to_string()));
+        assert!(!game_loop.pause("Again".to_string()));
+        let frozen_at = game_loop.state().get_player(player_id).unwrap().position;
+        let match_time = game_loop.state().match_state.match_time;
+        for _ in 0..10 {
+            game_loop.tick();
+        }
+        assert_eq!(game_loop.state().get_player(player_id).unwrap().position, frozen_at);
+        assert_eq!(game_loop.state().match_state.match_time, match_time);
+
+        assert_eq!(game_loop.resume(), Some(RESUME_COUNTDOWN_SECS));
+        assert_eq!(game_loop.resume(), None);
+        let countdown_ticks = (RESUME_COUNTDOWN_SECS / DT).round() as usize;
+        for _ in 0..countdown_ticks - 1 {
+            assert!(game_loop.tick().is_empty());
+        }
+        assert_eq!(game_loop.state().get_player(player_id).unwrap().position, frozen_at);
+
+        let events = game_loop.tick();
+        assert!(events.iter().any(|e| matches!(e, GameLoopEvent::MatchResumed)));
+        assert_eq!(game_loop.pause_state(), &PauseState::Running);
+        assert_ne!(game_loop.state().get_player(player_id).unwrap().position, frozen_at);
+    }
+
+    #[test]
+    fn test_pause_input_policy() {
+        let input = PlayerInput {
+            sequence: 1,
+            tick: 1,
+            thrust: Vec2::new(1.0, 0.0),
+            ..Default::default()
+        };
+
+        let (mut dropping, player_id) = playing_loop(GameLoopConfig::default());
+        dropping.pause(String::new());
+        dropping.queue_input(player_id, input.clone());
+        dropping.tick();
+        assert!(!dropping.pending_inputs.contains_key(&player_id));
+
+        let config = GameLoopConfig {
+            pause_input_policy: PauseInputPolicy::Buffer,
+            ..Default::default()
+        };
+        let (mut buffering, player_id) = playing_loop(config);
+        buffering.pause(String::new());
+        for sequence in 0..(MAX_INPUTS_PER_TICK as u64 + 5) {
+            buffering.queue_input(player_id, PlayerInput { sequence, ..input.clone() });
+            buffering.tick();
+        }
+        let buffered = &buffering.pending_inputs[&player_id];
+        assert_eq!(buffered.len(), MAX_INPUTS_PER_TICK);
+        assert_eq!(buffered.last().unwrap().sequence, MAX_INPUTS_PER_TICK as u64 + 4);
+    }
+
+    #[test]
+    fn test_queued_modifier_starts_and_expires() {
+        use crate::game::modifiers::Parameter;
+
+        let (mut game_loop, _) = playing_loop(GameLoopConfig::default());
+        game_loop.queue_modifier(GlobalModifier::SolarFlare);
+        let events = game_loop.tick();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            GameLoopEvent::ModifierStarted { modifier: GlobalModifier::SolarFlare, .. }
+        )));
+        assert!(game_loop.state().modifiers.multiplier(Parameter::ProjectileSpeed) > 1.0);
+
+        let ticks = (GlobalModifier::SolarFlare.duration_secs() / DT).ceil() as usize + 1;
+        let mut ended = false;
+        for _ in 0..ticks {
+            // Keep the match running even if the test players die
+            game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+            ended |= game_loop.tick().iter().any(|e| {
+                matches!(e, GameLoopEvent::ModifierEnded { modifier: GlobalModifier::SolarFlare })
+            });
+        }
+        assert!(ended);
+        assert!(game_loop.state().modifiers.is_empty());
+    }
+
+    #[test]
+    fn test_queued_preset_transitions_the_arena_and_swaps_modifiers() {
+        use crate::game::arena_preset::ArenaPreset;
+
+        let (mut game_loop, _) = playing_loop(GameLoopConfig::default());
+        game_loop.queue_modifier(GlobalModifier::SolarFlare);
+        game_loop.tick();
+
+        let preset: ArenaPreset = serde_json::from_str(
+            r#"{ "name": "finals", "escape_radius": 1500, "wells": [{"position": {"x": 700, "y": 0}}],
+                 "modifiers": [{"modifier": "DenseNebula", "remaining": 60}] }"#,
+        )
+        .unwrap();
+        game_loop.queue_preset(preset, 1.0);
+        let events = game_loop.tick();
+        assert!(events.iter().any(|e| matches!(e, GameLoopEvent::ArenaTransition { name, .. } if name == "finals")));
+        let ended = GlobalModifier::SolarFlare;
+        assert!(events.iter().any(|e| matches!(e, GameLoopEvent::ModifierEnded { modifier } if *modifier == ended)));
+        assert!(game_loop.state().modifiers.is_active(GlobalModifier::DenseNebula));
+
+        for _ in 0..TICK_RATE {
+            game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+            game_loop.tick();
+        }
+        let arena = &game_loop.state().arena;
+        assert_eq!((arena.escape_radius, arena.orbital_well_count()), (1500.0, 1));
+        assert!(arena.gravity_wells.values().any(|w| w.position == Vec2::new(700.0, 0.0)));
+
+        // The next match keeps the preset
+        game_loop.reset();
+        assert_eq!(game_loop.state().arena.escape_radius, 1500.0);
+    }
+
+    #[test]
+    fn test_scheduled_events_run_their_actions() {
+        use crate::game::schedule::ScheduledEvent;
+
+        let events = ["0 * * * *|boss", "0 * * * *|modifier:debris_surge:600", "30 * * * *|arena_reset"];
+        let schedule_config = ScheduleConfig {
+            events: events.iter().map(|e| ScheduledEvent::parse(e).unwrap()).collect(),
+            announce_lead_secs: 60,
+        };
+        let (mut game_loop, player_id) = playing_loop(GameLoopConfig { schedule_config, ..Default::default() });
+        game_loop.state_mut().get_player_mut(player_id).unwrap().mass = 300.0;
+        game_loop.state_mut().debris.clear();
+
+        // 10 minutes to the hour: nothing yet; 1 minute to go: the hourly events are announced
+        let mut out = Vec::new();
+        game_loop.run_schedule(3000, &mut out);
+        assert!(out.is_empty());
+        game_loop.run_schedule(3540, &mut out);
+        assert_eq!(out.len(), 2);
+        let boss_upcoming = GameLoopEvent::ScheduledEventUpcoming { action: ScheduledAction::BossSpawn, starts_in: 60 };
+        assert_eq!(format!("{:?}", out[0]), format!("{:?}", boss_upcoming));
+
+        out.clear();
+        game_loop.run_schedule(3600, &mut out);
+        let GameLoopEvent::BossSpawned { player_id: boss_id } = out[0] else {
+            panic!("expected a boss, got {:?}", out[0]);
+        };
+        let boss = game_loop.state().get_player(boss_id).unwrap();
+        assert!(boss.is_bot && boss.mass == schedule::BOSS_MASS);
+        assert!(matches!(
+            out[1],
+            GameLoopEvent::ModifierStarted { modifier: GlobalModifier::DebrisSurge, duration } if duration == 600.0
+        ));
+
+        out.clear();
+        game_loop.run_schedule(5400, &mut out);
+        assert!(out.iter().any(|e| matches!(e, GameLoopEvent::ArenaReset)));
+        let state = game_loop.state();
+        assert_eq!(state.get_player(player_id).unwrap().mass, crate::game::constants::mass::STARTING);
+        assert!(!state.debris.is_empty());
+    }
+
+    #[test]
+    fn test_bot_brains_survive_state_restore() {
+        let (mut game_loop, human_id) = playing_loop(GameLoopConfig::default());
+        let bots: Vec<_> = (0..4).map(|i| game_loop.add_player(create_player(&format!("Bot{i}"), true))).collect();
+        for _ in 0..30 {
+            game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+            game_loop.tick();
+        }
+        let saved_state = serde_json::to_string(game_loop.state()).unwrap();
+        let saved_ai = serde_json::to_string(&game_loop.ai_snapshot()).unwrap();
+
+        // A fresh server restores the state, then the bot brains
+        let mut restored = GameLoop::new(GameLoopConfig::default());
+        *restored.state_mut() = serde_json::from_str(&saved_state).unwrap();
+        let mut snapshot: ai_soa::AiSnapshot = serde_json::from_str(&saved_ai).unwrap();
+        // A brain for a player that is not a bot here is ignored
+        let mut stray = snapshot.bots[0].clone();
+        stray.player_id = human_id;
+        snapshot.bots.push(stray);
+        assert_eq!(restored.restore_ai(&snapshot).unwrap(), bots.len());
+
+        assert_eq!(serde_json::to_string(&restored.ai_snapshot()).unwrap(), saved_ai);
+        assert!(restored.ai_snapshot().bots.iter().all(|b| bots.contains(&b.player_id)));
+    }
 }
@@ -0,0 +1,172 @@
+//! Ghost runs for practice rooms
+//!
+//! A `GhostTrack` is a recorded player's positions over time, usually built
+//! from a session capture (`CaptureDump::ghost_track`). Practice rooms replay
+//! tracks as ghosts: they are never part of the simulated `GameState`, so
+//! nothing collides with, attracts or targets them. They only exist in the
+//! room's snapshots, flagged so clients render them translucently. A ghost
+//! loops back to the start of its run when it reaches the end.
+
+#![allow(dead_code)] // Driven by lobby practice rooms
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::state::PlayerId;
+use crate::util::vec2::Vec2;
+
+/// Most ghosts a room replays at once
+pub const MAX_GHOSTS: usize = 4;
+
+/// One sample of a recorded run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GhostFrame {
+    /// Seconds since the start of the run
+    pub at: f32,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub mass: f32,
+    pub alive: bool,
+}
+
+/// A recorded run, frames in time order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GhostTrack {
+    /// Name shown above the ghost
+    pub name: String,
+    pub frames: Vec<GhostFrame>,
+}
+
+impl GhostTrack {
+    /// Length of the run in seconds
+    pub fn duration(&self) -> f32 {
+        self.frames.last().map_or(0.0, |f| f.at)
+    }
+
+    /// State `t` seconds into the run, interpolated between frames
+    /// (None for a track without frames)
+    pub fn sample(&self, t: f32) -> Option<GhostFrame> {
+        let next = self.frames.partition_point(|f| f.at <= t);
+        let (before, after) = match (next.checked_sub(1).map(|i| self.frames[i]), self.frames.get(next)) {
+            (Some(before), Some(after)) => (before, *after),
+            (Some(only), None) | (None, Some(&only)) => return Some(only),
+            (None, None) => return None,
+        };
+        // Deaths and respawns are cuts, not glides across the arena
+        if !before.alive || !after.alive {
+            return Some(before);
+        }
+
+        let span = after.at - before.at;
+        let k = if span > 0.0 { (t - before.at) / span } else { 0.0 };
+        Some(GhostFrame {
+            at: t,
+            position: before.position.lerp(after.position, k),
+            velocity: before.velocity.lerp(after.velocity, k),
+            mass: before.mass + (after.mass - before.mass) * k,
+            alive: true,
+        })
+    }
+}
+
+/// A track being replayed in a room
+#[derive(Debug, Clone)]
+pub struct Ghost {
+    /// Snapshot id (never a real player's)
+    pub id: PlayerId,
+    pub track: GhostTrack,
+    /// Match time the replay started at
+    started_at: f32,
+}
+
+impl Ghost {
+    /// The ghost's state at `match_time`, looping over the run
+    pub fn frame_at(&self, match_time: f32) -> Option<GhostFrame> {
+        let duration = self.track.duration();
+        let elapsed = (match_time - self.started_at).max(0.0);
+        let t = if duration > 0.0 { elapsed % duration } else { 0.0 };
+        self.track.sample(t)
+    }
+}
+
+/// Ghosts replayed in one room
+#[derive(Debug, Clone, Default)]
+pub struct GhostPlayback {
+    ghosts: Vec<Ghost>,
+}
+
+impl GhostPlayback {
+    /// Start replaying `track` from `match_time` (None when the room already
+    /// replays `MAX_GHOSTS` or the track is empty)
+    pub fn add(&mut self, track: GhostTrack, match_time: f32) -> Option<PlayerId> {
+        if self.ghosts.len() >= MAX_GHOSTS || track.frames.is_empty() {
+            return None;
+        }
+        let id = uuid::Uuid::new_v4();
+        self.ghosts.push(Ghost { id, track, started_at: match_time });
+        Some(id)
+    }
+
+    /// Restart every ghost from the beginning of its run (for racing)
+    pub fn restart(&mut self, match_time: f32) {
+        for ghost in &mut self.ghosts {
+            ghost.started_at = match_time;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.ghosts.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ghosts.is_empty()
+    }
+
+    /// Every ghost with its current state
+    pub fn frames(&self, match_time: f32) -> impl Iterator<Item = (&Ghost, GhostFrame)> {
+        self.ghosts.iter().filter_map(move |ghost| Some((ghost, ghost.frame_at(match_time)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(at: f32, x: f32, alive: bool) -> GhostFrame {
+        GhostFrame { at, position: Vec2::new(x, 0.0), velocity: Vec2::ZERO, mass: 100.0, alive }
+    }
+
+    fn track(frames: Vec<GhostFrame>) -> GhostTrack {
+        GhostTrack { name: "Run".to_string(), frames }
+    }
+
+    #[test]
+    fn test_sample_interpolates_and_cuts_on_death() {
+        let track = track(vec![frame(0.0, 0.0, true), frame(1.0, 100.0, true), frame(2.0, 500.0, false)]);
+        assert_eq!(track.sample(0.5).unwrap().position, Vec2::new(50.0, 0.0));
+        assert_eq!(track.sample(1.5).unwrap().position, Vec2::new(100.0, 0.0));
+        assert!(!track.sample(3.0).unwrap().alive);
+        assert_eq!(GhostTrack { name: String::new(), frames: Vec::new() }.sample(0.0), None);
+    }
+
+    #[test]
+    fn test_playback_loops_and_restarts() {
+        let mut playback = GhostPlayback::default();
+        let id = playback.add(track(vec![frame(0.0, 0.0, true), frame(2.0, 200.0, true)]), 10.0).unwrap();
+
+        let position = |playback: &GhostPlayback, now: f32| {
+            let (ghost, frame) = playback.frames(now).next().unwrap();
+            assert_eq!(ghost.id, id);
+            frame.position.x
+        };
+        assert_eq!(position(&playback, 11.0), 100.0);
+        assert_eq!(position(&playback, 13.0), 100.0);
+
+        playback.restart(13.0);
+        assert_eq!(position(&playback, 13.0), 0.0);
+
+        for _ in 1..MAX_GHOSTS {
+            playback.add(track(vec![frame(0.0, 0.0, true)]), 0.0).unwrap();
+        }
+        assert_eq!(playback.add(track(vec![frame(0.0, 0.0, true)]), 0.0), None);
+    }
+}
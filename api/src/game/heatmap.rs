@@ -0,0 +1,441 @@
+//! Gameplay heatmaps for level design
+//!
+//! Accumulates where play concentrates over time: occupancy sampled from the
+//! AI zone grid, kill locations, and deaths by falling into a gravity well.
+//! Each layer is a sparse grid of counts; exports downsample a layer to a
+//! fixed-size image covering the largest arena seen, served as JSON or a
+//! grayscale PNG by the metrics endpoint. With `HEATMAP_PATH` set the totals
+//! are saved periodically and reloaded on startup, so well layouts can be
+//! compared over many sessions rather than a single match.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::HeatmapConfig;
+use crate::game::constants::physics::TICK_RATE;
+use crate::game::systems::ai_soa::ZoneGrid;
+use crate::util::vec2::Vec2;
+
+/// What a heatmap counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeatmapLayer {
+    /// Bots (and human presence) per AI zone, sampled periodically
+    Occupancy,
+    /// Where players were killed by other players
+    Kills,
+    /// Where players fell into a gravity well core
+    WellDeaths,
+}
+
+impl HeatmapLayer {
+    pub const ALL: [HeatmapLayer; 3] = [HeatmapLayer::Occupancy, HeatmapLayer::Kills, HeatmapLayer::WellDeaths];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HeatmapLayer::Occupancy => "occupancy",
+            HeatmapLayer::Kills => "kills",
+            HeatmapLayer::WellDeaths => "well_deaths",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.name() == name)
+    }
+}
+
+/// Accumulated counts for one layer
+#[derive(Debug, Clone)]
+struct Grid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), f32>,
+}
+
+impl Grid {
+    fn new(cell_size: f32) -> Self {
+        Self { cell_size, cells: HashMap::new() }
+    }
+
+    fn cell_of(&self, position: Vec2) -> (i32, i32) {
+        ((position.x / self.cell_size).floor() as i32, (position.y / self.cell_size).floor() as i32)
+    }
+
+    fn cell_center(&self, cell: (i32, i32)) -> Vec2 {
+        Vec2::new((cell.0 as f32 + 0.5) * self.cell_size, (cell.1 as f32 + 0.5) * self.cell_size)
+    }
+
+    fn add(&mut self, position: Vec2, weight: f32) {
+        *self.cells.entry(self.cell_of(position)).or_insert(0.0) += weight;
+    }
+}
+
+/// A layer downsampled to a square image over the arena
+#[derive(Debug, Clone, Serialize)]
+pub struct HeatmapImage {
+    pub layer: HeatmapLayer,
+    /// World position of the minimum corner of the image
+    pub origin: Vec2,
+    /// World units covered by one pixel
+    pub pixel_size: f32,
+    pub width: usize,
+    pub height: usize,
+    /// Largest pixel value (0 when nothing was recorded)
+    pub max: f32,
+    /// Row-major pixel values; row 0 is the minimum y
+    pub values: Vec<f32>,
+}
+
+impl HeatmapImage {
+    /// Encode as an 8-bit grayscale PNG, scaled so the hottest pixel is white
+    pub fn to_png(&self) -> Vec<u8> {
+        let scale = if self.max > 0.0 { 255.0 / self.max } else { 0.0 };
+        let mut raw = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.values.chunks(self.width.max(1)) {
+            raw.push(0); // Filter: none
+            raw.extend(row.iter().map(|v| (v * scale).round().clamp(0.0, 255.0) as u8));
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 0, 0, 0, 0]); // 8-bit grayscale, no interlace
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Zlib stream of uncompressed (stored) deflate blocks; images are small
+/// enough that skipping compression beats pulling in a deflate dependency
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// On-disk form of the accumulated layers
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedHeatmaps {
+    extent: f32,
+    layers: Vec<SavedLayer>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedLayer {
+    layer: HeatmapLayer,
+    cell_size: f32,
+    cells: Vec<(i32, i32, f32)>,
+}
+
+#[derive(Debug)]
+struct Layers {
+    /// Largest arena escape radius seen; exports cover [-extent, extent]
+    extent: f32,
+    grids: HashMap<HeatmapLayer, Grid>,
+}
+
+/// Heatmap store shared by the game session (recording) and the metrics endpoint (export)
+#[derive(Debug)]
+pub struct Heatmaps {
+    enabled: bool,
+    cell_size: f32,
+    sample_interval_ticks: u64,
+    save_interval_ticks: u64,
+    path: Option<String>,
+    layers: Mutex<Layers>,
+}
+
+impl Heatmaps {
+    /// Create the store, loading saved totals from the configured path if present
+    pub fn new(config: &HeatmapConfig) -> Self {
+        let heatmaps = Self {
+            enabled: config.enabled,
+            cell_size: config.cell_size,
+            sample_interval_ticks: ((config.sample_secs * TICK_RATE as f32) as u64).max(1),
+            save_interval_ticks: ((config.save_secs * TICK_RATE as f32) as u64).max(1),
+            path: config.path.clone(),
+            layers: Mutex::new(Layers { extent: 0.0, grids: HashMap::new() }),
+        };
+        if let Some(path) = heatmaps.path.as_deref().filter(|_| heatmaps.enabled) {
+            match std::fs::read_to_string(path) {
+                Ok(json) => match serde_json::from_str::<SavedHeatmaps>(&json) {
+                    Ok(saved) => {
+                        heatmaps.restore(saved);
+                        info!("Loaded heatmaps from {}", path);
+                    }
+                    Err(e) => warn!("Ignoring unreadable heatmap file {}: {}", path, e),
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => warn!("Failed to read heatmap file {}: {}", path, e),
+            }
+        }
+        heatmaps
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether occupancy should be sampled on this tick
+    pub fn sample_due(&self, tick: u64) -> bool {
+        self.enabled && tick % self.sample_interval_ticks == 0
+    }
+
+    /// Whether the totals should be saved on this tick
+    pub fn save_due(&self, tick: u64) -> bool {
+        self.enabled && self.path.is_some() && tick % self.save_interval_ticks == 0
+    }
+
+    /// Count an event (kill or well death) at a world position
+    pub fn record(&self, layer: HeatmapLayer, position: Vec2, weight: f32) {
+        if !self.enabled {
+            return;
+        }
+        self.layers
+            .lock()
+            .grids
+            .entry(layer)
+            .or_insert_with(|| Grid::new(self.cell_size))
+            .add(position, weight);
+    }
+
+    /// Add one occupancy sample from the AI zone grid: each zone counts its
+    /// bots, plus one when a human is present. The occupancy layer keeps the
+    /// zone grid's resolution.
+    pub fn sample_occupancy(&self, zones: &ZoneGrid, arena_extent: f32) {
+        if !self.enabled {
+            return;
+        }
+        let mut layers = self.layers.lock();
+        layers.extent = layers.extent.max(arena_extent);
+        let grid = layers
+            .grids
+            .entry(HeatmapLayer::Occupancy)
+            .or_insert_with(|| Grid::new(zones.cell_size()));
+        if grid.cell_size != zones.cell_size() {
+            warn!("AI zone size changed, restarting the occupancy heatmap");
+            *grid = Grid::new(zones.cell_size());
+        }
+        for zone in zones.zones() {
+            let weight = zone.bot_count as f32 + f32::from(u8::from(zone.has_human));
+            if weight > 0.0 {
+                grid.add(zone.center, weight);
+            }
+        }
+    }
+
+    /// Downsample a layer to a `size`×`size` image over the largest arena seen.
+    /// Layers finer than a pixel are summed into it; coarser ones are spread
+    /// evenly over the pixels they cover, so totals are preserved either way.
+    pub fn export(&self, layer: HeatmapLayer, size: usize) -> HeatmapImage {
+        let layers = self.layers.lock();
+        let extent = layers.extent.max(1.0);
+        let pixel_size = 2.0 * extent / size as f32;
+        let origin = Vec2::new(-extent, -extent);
+        let mut values = vec![0.0; size * size];
+        let pixel_of = |position: Vec2| {
+            let x = ((position.x - origin.x) / pixel_size).floor();
+            let y = ((position.y - origin.y) / pixel_size).floor();
+            let in_bounds = (0.0..size as f32).contains(&x) && (0.0..size as f32).contains(&y);
+            in_bounds.then(|| y as usize * size + x as usize)
+        };
+
+        if let Some(grid) = layers.grids.get(&layer) {
+            if grid.cell_size <= pixel_size {
+                for (&cell, &count) in &grid.cells {
+                    if let Some(i) = pixel_of(grid.cell_center(cell)) {
+                        values[i] += count;
+                    }
+                }
+            } else {
+                let share = (pixel_size / grid.cell_size).powi(2);
+                for (i, value) in values.iter_mut().enumerate() {
+                    let center = origin + Vec2::new((i % size) as f32 + 0.5, (i / size) as f32 + 0.5) * pixel_size;
+                    if let Some(count) = grid.cells.get(&grid.cell_of(center)) {
+                        *value = count * share;
+                    }
+                }
+            }
+        }
+
+        let max = values.iter().copied().fold(0.0, f32::max);
+        HeatmapImage { layer, origin, pixel_size, width: size, height: size, max, values }
+    }
+
+    /// Write the totals to the configured path (no-op without one)
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let saved = {
+            let layers = self.layers.lock();
+            SavedHeatmaps {
+                extent: layers.extent,
+                layers: layers
+                    .grids
+                    .iter()
+                    .map(|(&layer, grid)| SavedLayer {
+                        layer,
+                        cell_size: grid.cell_size,
+                        cells: grid.cells.iter().map(|(&(x, y), &count)| (x, y, count)).collect(),
+                    })
+                    .collect(),
+            }
+        };
+        std::fs::write(path, serde_json::to_vec(&saved)?)
+    }
+
+    fn restore(&self, saved: SavedHeatmaps) {
+        let mut layers = self.layers.lock();
+        layers.extent = saved.extent;
+        for saved_layer in saved.layers {
+            let mut grid = Grid::new(saved_layer.cell_size);
+            grid.cells = saved_layer.cells.into_iter().map(|(x, y, count)| ((x, y), count)).collect();
+            layers.grids.insert(saved_layer.layer, grid);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> HeatmapConfig {
+        HeatmapConfig { enabled: true, path: None, ..Default::default() }
+    }
+
+    #[test]
+    fn test_events_are_binned_and_totals_preserved() {
+        let heatmaps = Heatmaps::new(&HeatmapConfig { cell_size: 100.0, ..enabled() });
+        heatmaps.sample_occupancy(&ZoneGrid::new(1000.0), 3200.0);
+        heatmaps.record(HeatmapLayer::Kills, Vec2::new(1010.0, -20.0), 1.0);
+        heatmaps.record(HeatmapLayer::Kills, Vec2::new(1020.0, -30.0), 1.0);
+        heatmaps.record(HeatmapLayer::Kills, Vec2::new(-3000.0, 3000.0), 1.0);
+
+        let image = heatmaps.export(HeatmapLayer::Kills, 64);
+        assert_eq!(image.values.len(), 64 * 64);
+        assert_eq!(image.max, 2.0);
+        assert_eq!(image.values.iter().sum::<f32>(), 3.0);
+        let hottest = image.values.iter().position(|&v| v == 2.0).unwrap();
+        let center = image.origin
+            + Vec2::new((hottest % 64) as f32 + 0.5, (hottest / 64) as f32 + 0.5) * image.pixel_size;
+        assert!(center.distance_to(Vec2::new(1050.0, -50.0)) < image.pixel_size);
+
+        assert_eq!(heatmaps.export(HeatmapLayer::WellDeaths, 16).max, 0.0);
+    }
+
+    #[test]
+    fn test_occupancy_samples_zone_grid() {
+        let heatmaps = Heatmaps::new(&enabled());
+        let mut zones = ZoneGrid::new(1000.0);
+        zones.get_or_create_zone((0, 0)).bot_count = 3;
+        zones.get_or_create_zone((-2, 1)).has_human = true;
+        zones.get_or_create_zone((1, 1));
+        heatmaps.sample_occupancy(&zones, 4000.0);
+        heatmaps.sample_occupancy(&zones, 4000.0);
+
+        // Zones are coarser than the 8 pixels: each spreads over the pixels it covers
+        let image = heatmaps.export(HeatmapLayer::Occupancy, 32);
+        assert!((image.values.iter().sum::<f32>() - 8.0).abs() < 1e-3);
+        assert!((image.max - 6.0 / 16.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_disabled_store_records_nothing() {
+        let heatmaps = Heatmaps::new(&HeatmapConfig { enabled: false, ..Default::default() });
+        heatmaps.record(HeatmapLayer::Kills, Vec2::ZERO, 1.0);
+        assert!(!heatmaps.sample_due(0));
+        assert_eq!(heatmaps.export(HeatmapLayer::Kills, 8).max, 0.0);
+    }
+
+    #[test]
+    fn test_png_encoding() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+
+        let heatmaps = Heatmaps::new(&enabled());
+        heatmaps.sample_occupancy(&ZoneGrid::new(1000.0), 1000.0);
+        heatmaps.record(HeatmapLayer::Kills, Vec2::new(100.0, 100.0), 1.0);
+        let png = heatmaps.export(HeatmapLayer::Kills, 300).to_png();
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 300);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xAE\x42\x60\x82");
+        // 300 rows of 301 bytes need two stored blocks
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(idat_len, 2 + 2 * 5 + 301 * 300 + 4);
+    }
+
+    #[test]
+    fn test_save_and_reload() {
+        let path = std::env::temp_dir().join(format!("heatmap-{}.json", uuid::Uuid::new_v4()));
+        let config = HeatmapConfig { path: Some(path.to_string_lossy().into_owned()), ..enabled() };
+
+        let heatmaps = Heatmaps::new(&config);
+        heatmaps.sample_occupancy(&ZoneGrid::new(1000.0), 2000.0);
+        heatmaps.record(HeatmapLayer::WellDeaths, Vec2::new(500.0, 500.0), 1.0);
+        heatmaps.save().unwrap();
+
+        let reloaded = Heatmaps::new(&config);
+        let _ = std::fs::remove_file(&path);
+        let (original, reloaded) =
+            (heatmaps.export(HeatmapLayer::WellDeaths, 16), reloaded.export(HeatmapLayer::WellDeaths, 16));
+        assert_eq!(reloaded.values, original.values);
+        assert_eq!(reloaded.max, 1.0);
+    }
+
+    #[test]
+    fn test_layer_names_round_trip() {
+        for layer in HeatmapLayer::ALL {
+            assert_eq!(HeatmapLayer::from_name(layer.name()), Some(layer));
+        }
+        assert_eq!(HeatmapLayer::from_name("deaths"), None);
+    }
+}
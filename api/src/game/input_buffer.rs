@@ -143,6 +143,7 @@ mod tests {
             boost: false,
             fire: false,
             fire_released: false,
+            state_hash: None,
         }
     }
 
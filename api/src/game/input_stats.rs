@@ -0,0 +1,244 @@
+//! Per-player input statistics
+//!
+//! Derived, privacy-neutral stats (APM, thrust magnitude, boost/fire usage,
+//! aim snaps) computed from the raw input stream. The game session records
+//! every accepted input once and publishes the result on the opt-in
+//! `/players/input-stats` metrics endpoint; the lobby's smurf detection uses
+//! the same tracker, so all share one computation path.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::game::state::PlayerId;
+use crate::net::protocol::PlayerInput;
+use crate::util::vec2::Vec2;
+
+/// Window over which actions are counted for APM
+const APM_WINDOW: Duration = Duration::from_secs(60);
+
+/// Thrust below this magnitude counts as "not thrusting"
+const THRUST_DEADZONE: f32 = 0.1;
+
+/// Thrust direction change (cosine) below which a new action is counted (~30°)
+const DIRECTION_CHANGE_COS: f32 = 0.866;
+
+/// Aim direction change (cosine) below which one input counts as a snap (past 90°)
+const AIM_SNAP_COS: f32 = 0.0;
+
+/// Aim below this magnitude counts as "not aiming"
+const AIM_DEADZONE: f32 = 0.1;
+
+/// Snapshot of a player's input stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct InputStats {
+    /// Discrete actions (thrust start/turn, boost press, fire press) per minute,
+    /// extrapolated when fewer than 60 seconds have been observed
+    pub apm: f32,
+    /// Mean thrust magnitude across all inputs (0-1)
+    pub avg_thrust: f32,
+    /// Fraction of inputs with boost held (0-1)
+    pub boost_ratio: f32,
+    /// Fraction of inputs with fire held (0-1)
+    pub fire_ratio: f32,
+    /// Fraction of aim changes that turn past 90° from one input to the next (0-1)
+    pub aim_snap_ratio: f32,
+    /// Total inputs observed
+    pub samples: u64,
+}
+
+/// Input stats tagged with the player they belong to (endpoint payload)
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerInputStats {
+    pub player_id: PlayerId,
+    /// Display name after the configured privacy redaction
+    pub name: String,
+    #[serde(flatten)]
+    pub stats: InputStats,
+}
+
+/// Accumulates inputs for one player
+#[derive(Debug, Clone)]
+pub struct InputStatsTracker {
+    started: Instant,
+    actions: VecDeque<Instant>,
+    last_thrust: Vec2,
+    last_boost: bool,
+    last_fire: bool,
+    thrust_sum: f64,
+    boost_samples: u64,
+    fire_samples: u64,
+    samples: u64,
+    last_aim: Vec2,
+    aim_changes: u64,
+    aim_snaps: u64,
+}
+
+impl InputStatsTracker {
+    pub fn new() -> Self {
+        Self::started_at(Instant::now())
+    }
+
+    fn started_at(now: Instant) -> Self {
+        Self {
+            started: now,
+            actions: VecDeque::new(),
+            last_thrust: Vec2::ZERO,
+            last_boost: false,
+            last_fire: false,
+            thrust_sum: 0.0,
+            boost_samples: 0,
+            fire_samples: 0,
+            samples: 0,
+            last_aim: Vec2::ZERO,
+            aim_changes: 0,
+            aim_snaps: 0,
+        }
+    }
+
+    /// Record a client input
+    pub fn record(&mut self, input: &PlayerInput) {
+        self.record_at(Instant::now(), input.thrust, input.boost, input.fire);
+        self.record_aim(input.aim);
+    }
+
+    /// Record an aim direction, counting snaps between consecutive aims
+    pub fn record_aim(&mut self, aim: Vec2) {
+        if aim.length() < AIM_DEADZONE {
+            return;
+        }
+        let aim = aim.normalize();
+        if self.last_aim != Vec2::ZERO && self.last_aim != aim {
+            self.aim_changes += 1;
+            if self.last_aim.dot(aim) < AIM_SNAP_COS {
+                self.aim_snaps += 1;
+            }
+        }
+        self.last_aim = aim;
+    }
+
+    /// Record raw input values at a given time
+    pub fn record_at(&mut self, now: Instant, thrust: Vec2, boost: bool, fire: bool) {
+        let magnitude = thrust.length().min(1.0);
+        self.samples += 1;
+        self.thrust_sum += magnitude as f64;
+        self.boost_samples += boost as u64;
+        self.fire_samples += fire as u64;
+
+        let mut actions = 0;
+        if magnitude >= THRUST_DEADZONE {
+            let was_thrusting = self.last_thrust.length() >= THRUST_DEADZONE;
+            let turned = was_thrusting
+                && self.last_thrust.normalize().dot(thrust.normalize()) < DIRECTION_CHANGE_COS;
+            if !was_thrusting || turned {
+                actions += 1;
+            }
+            self.last_thrust = thrust;
+        } else {
+            self.last_thrust = Vec2::ZERO;
+        }
+        if boost && !self.last_boost {
+            actions += 1;
+        }
+        if fire && !self.last_fire {
+            actions += 1;
+        }
+        self.last_boost = boost;
+        self.last_fire = fire;
+
+        for _ in 0..actions {
+            self.actions.push_back(now);
+        }
+        while self.actions.front().is_some_and(|&t| now.duration_since(t) > APM_WINDOW) {
+            self.actions.pop_front();
+        }
+    }
+
+    /// Current stats
+    pub fn stats(&self) -> InputStats {
+        self.stats_at(Instant::now())
+    }
+
+    /// Stats as of `now`
+    pub fn stats_at(&self, now: Instant) -> InputStats {
+        if self.samples == 0 {
+            return InputStats::default();
+        }
+        let recent = self
+            .actions
+            .iter()
+            .filter(|&&t| now.duration_since(t) <= APM_WINDOW)
+            .count();
+        // Extrapolate over the observed time (at least 1s) until a full window exists
+        let observed = now.duration_since(self.started).min(APM_WINDOW).as_secs_f32().max(1.0);
+        let samples = self.samples as f32;
+
+        InputStats {
+            apm: recent as f32 * 60.0 / observed,
+            avg_thrust: (self.thrust_sum / self.samples as f64) as f32,
+            boost_ratio: self.boost_samples as f32 / samples,
+            fire_ratio: self.fire_samples as f32 / samples,
+            aim_snap_ratio: if self.aim_changes > 0 { self.aim_snaps as f32 / self.aim_changes as f32 } else { 0.0 },
+            samples: self.samples,
+        }
+    }
+}
+
+impl Default for InputStatsTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_tracker_reports_zero() {
+        assert_eq!(InputStatsTracker::new().stats(), InputStats::default());
+    }
+
+    #[test]
+    fn test_counts_actions_and_ratios() {
+        let start = Instant::now();
+        let mut tracker = InputStatsTracker::started_at(start);
+        let t = |ms: u64| start + Duration::from_millis(ms);
+
+        tracker.record_at(t(0), Vec2::new(1.0, 0.0), false, false); // thrust start
+        tracker.record_at(t(100), Vec2::new(1.0, 0.05), false, false); // small turn: no action
+        tracker.record_at(t(200), Vec2::new(0.0, 1.0), true, false); // turn + boost press
+        tracker.record_at(t(300), Vec2::new(0.0, 1.0), true, true); // fire press
+
+        let stats = tracker.stats_at(t(30_000));
+        assert_eq!(stats.samples, 4);
+        assert!((stats.apm - 8.0).abs() < 1e-3, "4 actions over 30s = 8 APM, got {}", stats.apm);
+        assert!((stats.avg_thrust - 1.0).abs() < 0.01);
+        assert_eq!(stats.boost_ratio, 0.5);
+        assert_eq!(stats.fire_ratio, 0.25);
+    }
+
+    #[test]
+    fn test_aim_snaps_count_sharp_turns_between_inputs() {
+        let mut tracker = InputStatsTracker::new();
+        tracker.record_at(Instant::now(), Vec2::ZERO, false, false);
+        for aim in [Vec2::new(1.0, 0.0), Vec2::new(1.0, 0.1), Vec2::new(-1.0, 0.0), Vec2::ZERO, Vec2::new(-1.0, 0.0)] {
+            tracker.record_aim(aim);
+        }
+        // Two changes (the unchanged and idle aims don't count), one of them a snap
+        assert_eq!(tracker.stats().aim_snap_ratio, 0.5);
+    }
+
+    #[test]
+    fn test_old_actions_leave_window() {
+        let start = Instant::now();
+        let mut tracker = InputStatsTracker::started_at(start);
+        tracker.record_at(start, Vec2::ZERO, true, false);
+        tracker.record_at(start + Duration::from_secs(61), Vec2::ZERO, false, false);
+
+        let stats = tracker.stats_at(start + Duration::from_secs(61));
+        assert_eq!(stats.apm, 0.0);
+        assert_eq!(tracker.actions.len(), 0);
+    }
+}
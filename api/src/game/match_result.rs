@@ -1,10 +1,21 @@
 //! Match result and ranking system
 //!
-//! Computes final match results and player rankings.
+//! Computes final match results, player rankings and the end-of-match
+//! awards. Awards come from the rankings plus a `MatchTally` of per-player
+//! numbers the game loop gathers while the match plays.
 
 #![allow(dead_code)] // Match result fields for UI/API consumption
 
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::game_loop::GameLoopEvent;
 use crate::game::state::{GameState, MatchPhase, PlayerId};
+use crate::net::protocol::DeathCause;
+
+/// Name shown for players who opted out of per-player analytics
+pub const ANONYMOUS_NAME: &str = "Anonymous";
 
 /// Match result information
 #[derive(Debug, Clone)]
@@ -14,6 +25,24 @@ pub struct MatchResult {
     pub rankings: Vec<PlayerRanking>,
     pub match_duration: f32,
     pub total_kills: u32,
+    pub awards: Vec<Award>,
+    /// Deaths over the match per cause, in `DeathCause::ALL` order (causes nobody died of are left out)
+    pub deaths_by_cause: Vec<(DeathCause, u32)>,
+}
+
+impl MatchResult {
+    /// Show a player as [`ANONYMOUS_NAME`]; their ranking still counts toward the totals
+    pub fn anonymize(&mut self, player_id: PlayerId) {
+        for ranking in self.rankings.iter_mut().filter(|r| r.player_id == player_id) {
+            ranking.name = ANONYMOUS_NAME.to_string();
+        }
+        if self.winner_id == Some(player_id) {
+            self.winner_name = Some(ANONYMOUS_NAME.to_string());
+        }
+        for award in self.awards.iter_mut().filter(|a| a.player_id == player_id) {
+            award.name = ANONYMOUS_NAME.to_string();
+        }
+    }
 }
 
 /// Player ranking in match results
@@ -29,8 +58,143 @@ pub struct PlayerRanking {
     pub is_bot: bool,
 }
 
-/// Determine match result from game state
-pub fn determine_result(state: &GameState) -> MatchResult {
+/// Mass a player must lose from their peak for the low point to count toward a comeback
+const COMEBACK_DROP: f32 = 0.5;
+/// Final mass over the low point needed for the comeback award
+const COMEBACK_MIN_RATIO: f32 = 2.0;
+
+/// End-of-match award categories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AwardKind {
+    /// Holds the most of the other awards (ties go to the better rank)
+    Mvp,
+    MostKills,
+    /// Alive for the most match time
+    Survivor,
+    /// Collected the most debris
+    DebrisHoarder,
+    /// Best-ranked player who finished alive without a kill
+    Pacifist,
+    /// Biggest climb back from a heavy mass loss
+    Comeback,
+}
+
+impl AwardKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            AwardKind::Mvp => "MVP",
+            AwardKind::MostKills => "Most Kills",
+            AwardKind::Survivor => "Survivor",
+            AwardKind::DebrisHoarder => "Debris Hoarder",
+            AwardKind::Pacifist => "Pacifist",
+            AwardKind::Comeback => "Comeback",
+        }
+    }
+}
+
+/// An award and who earned it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Award {
+    pub kind: AwardKind,
+    pub player_id: PlayerId,
+    pub name: String,
+    /// What earned it: awards held (MVP), kills, seconds alive (Survivor),
+    /// debris collected, final mass (Pacifist) or final over lowest mass (Comeback)
+    pub value: f32,
+}
+
+/// One player's numbers over the match
+#[derive(Debug, Clone, Default)]
+struct PlayerTally {
+    debris_collected: u32,
+    alive_secs: f32,
+    peak_mass: f32,
+    /// Lowest mass reached after losing `COMEBACK_DROP` of the peak
+    low_mass: Option<f32>,
+}
+
+/// Per-player stats and deaths by cause gathered while a match plays
+#[derive(Debug, Default)]
+pub struct MatchTally {
+    players: HashMap<PlayerId, PlayerTally>,
+    deaths: HashMap<DeathCause, u32>,
+}
+
+impl MatchTally {
+    pub fn clear(&mut self) {
+        self.players.clear();
+        self.deaths.clear();
+    }
+
+    /// Count one playing tick: debris pickups and deaths from `events`, time alive and mass swings
+    pub fn observe(&mut self, state: &GameState, events: &[GameLoopEvent], dt: f32) {
+        for event in events {
+            match event {
+                GameLoopEvent::DebrisCollected { player_id, .. } => {
+                    self.players.entry(*player_id).or_default().debris_collected += 1;
+                }
+                GameLoopEvent::PlayerDied { cause, .. } => *self.deaths.entry(*cause).or_insert(0) += 1,
+                _ => {}
+            }
+        }
+        for player in state.players.values().filter(|p| p.alive) {
+            let tally = self.players.entry(player.id).or_default();
+            tally.alive_secs += dt;
+            tally.peak_mass = tally.peak_mass.max(player.mass);
+            if player.mass < tally.peak_mass * COMEBACK_DROP {
+                tally.low_mass = Some(tally.low_mass.map_or(player.mass, |low| low.min(player.mass)));
+            }
+        }
+    }
+}
+
+/// Pick the awards: each goes to the best-scoring player, ties to the better rank
+fn compute_awards(rankings: &[PlayerRanking], tally: &MatchTally) -> Vec<Award> {
+    let stats = |ranking: &PlayerRanking| tally.players.get(&ranking.player_id).cloned().unwrap_or_default();
+    let best = |kind: AwardKind, score: &dyn Fn(&PlayerRanking) -> Option<f32>| {
+        let mut best: Option<(&PlayerRanking, f32)> = None;
+        for ranking in rankings {
+            if let Some(value) = score(ranking) {
+                if best.map_or(true, |(_, top)| value > top) {
+                    best = Some((ranking, value));
+                }
+            }
+        }
+        best.map(|(r, value)| Award { kind, player_id: r.player_id, name: r.name.clone(), value })
+    };
+
+    let mut awards: Vec<Award> = [
+        best(AwardKind::MostKills, &|r| (r.kills > 0).then_some(r.kills as f32)),
+        best(AwardKind::Survivor, &|r| Some(stats(r).alive_secs).filter(|&secs| secs > 0.0)),
+        best(AwardKind::DebrisHoarder, &|r| {
+            let collected = stats(r).debris_collected;
+            (collected > 0).then_some(collected as f32)
+        }),
+        rankings.iter().find(|r| r.survived && r.kills == 0).map(|r| Award {
+            kind: AwardKind::Pacifist,
+            player_id: r.player_id,
+            name: r.name.clone(),
+            value: r.final_mass,
+        }),
+        best(AwardKind::Comeback, &|r| {
+            let low = stats(r).low_mass.filter(|&low| low > 0.0 && r.survived)?;
+            Some(r.final_mass / low).filter(|&ratio| ratio >= COMEBACK_MIN_RATIO)
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let held = |ranking: &PlayerRanking| awards.iter().filter(|a| a.player_id == ranking.player_id).count();
+    if let Some(mvp) = best(AwardKind::Mvp, &|r| Some(held(r) as f32).filter(|&count| count > 0.0)) {
+        awards.insert(0, mvp);
+    }
+    awards
+}
+
+/// Determine match result from game state and the stats tallied over the match
+pub fn determine_result(state: &GameState, tally: &MatchTally) -> MatchResult {
     let mut rankings: Vec<PlayerRanking> = state
         .players
         .values()
@@ -74,6 +238,11 @@ pub fn determine_result(state: &GameState) -> MatchResult {
     MatchResult {
         winner_id,
         winner_name,
+        awards: compute_awards(&rankings, tally),
+        deaths_by_cause: DeathCause::ALL
+            .into_iter()
+            .filter_map(|cause| tally.deaths.get(&cause).map(|&count| (cause, count)))
+            .collect(),
         rankings,
         match_duration: state.match_state.match_time,
         total_kills,
@@ -144,6 +313,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         }
     }
 
@@ -154,7 +327,7 @@ mod tests {
         state.add_player(create_player("Loser1", false, 2, 50.0, false));
         state.add_player(create_player("Loser2", false, 1, 30.0, false));
 
-        let result = determine_result(&state);
+        let result = determine_result(&state, &MatchTally::default());
 
         assert!(result.winner_id.is_some());
         assert_eq!(result.winner_name.as_deref(), Some("Winner"));
@@ -168,13 +341,74 @@ mod tests {
         state.add_player(create_player("Dead1", false, 3, 0.0, false));
         state.add_player(create_player("Dead2", false, 2, 0.0, false));
 
-        let result = determine_result(&state);
+        let result = determine_result(&state, &MatchTally::default());
 
         assert!(result.winner_id.is_none());
         // Still ranked by kills
         assert_eq!(result.rankings[0].name, "Dead1");
     }
 
+    #[test]
+    fn test_anonymize_keeps_the_ranking() {
+        let mut state = GameState::new();
+        state.add_player(create_player("Winner", true, 5, 200.0, false));
+        state.add_player(create_player("Loser", false, 2, 50.0, false));
+
+        let mut result = determine_result(&state, &MatchTally::default());
+        let winner = result.winner_id.unwrap();
+        result.anonymize(winner);
+
+        assert_eq!(result.winner_name.as_deref(), Some(ANONYMOUS_NAME));
+        assert_eq!((result.rankings[0].name.as_str(), result.rankings[0].kills), (ANONYMOUS_NAME, 5));
+        assert_eq!(result.rankings[1].name, "Loser");
+        assert_eq!(result.total_kills, 7);
+    }
+
+    #[test]
+    fn test_awards_from_the_match_tally() {
+        let mut state = GameState::new();
+        let winner = create_player("Winner", true, 4, 200.0, false);
+        let hoarder = create_player("Hoarder", true, 0, 80.0, false);
+        let early = create_player("Early", false, 1, 0.0, true);
+        let (winner_id, hoarder_id, early_id) = (winner.id, hoarder.id, early.id);
+        state.add_player(winner);
+        state.add_player(hoarder);
+        state.add_player(early);
+
+        let mut tally = MatchTally::default();
+        let debris = |player_id| GameLoopEvent::DebrisCollected { player_id, debris_id: 0 };
+        tally.observe(&state, &[debris(hoarder_id), debris(hoarder_id), debris(winner_id)], 1.0);
+        // The winner drops to a quarter of their peak and climbs back
+        state.players.get_mut(&winner_id).unwrap().mass = 50.0;
+        tally.observe(&state, &[], 1.0);
+        state.players.get_mut(&winner_id).unwrap().mass = 300.0;
+        state.players.get_mut(&hoarder_id).unwrap().alive = false;
+        let died = GameLoopEvent::PlayerDied { victim_id: hoarder_id, killer_id: None, cause: DeathCause::Well };
+        tally.observe(&state, &[died], 1.0);
+
+        let result = determine_result(&state, &tally);
+        assert_eq!(result.deaths_by_cause, [(DeathCause::Well, 1)]);
+        let holder = |kind| result.awards.iter().find(|a| a.kind == kind).map(|a| (a.player_id, a.value));
+        assert_eq!(holder(AwardKind::MostKills), Some((winner_id, 4.0)));
+        assert_eq!(holder(AwardKind::Survivor), Some((winner_id, 3.0)));
+        assert_eq!(holder(AwardKind::DebrisHoarder), Some((hoarder_id, 2.0)));
+        assert_eq!(holder(AwardKind::Comeback), Some((winner_id, 6.0)));
+        // Nobody finished alive without a kill
+        assert_eq!(holder(AwardKind::Pacifist), None);
+        assert_eq!(result.awards[0].kind, AwardKind::Mvp);
+        assert_eq!((result.awards[0].player_id, result.awards[0].value), (winner_id, 3.0));
+        assert!(result.awards.iter().all(|a| a.player_id != early_id));
+
+        let mut result = result;
+        result.anonymize(hoarder_id);
+        assert_eq!(holder_name(&result, AwardKind::DebrisHoarder), ANONYMOUS_NAME);
+        assert_eq!(holder_name(&result, AwardKind::Mvp), "Winner");
+    }
+
+    fn holder_name(result: &MatchResult, kind: AwardKind) -> &str {
+        &result.awards.iter().find(|a| a.kind == kind).unwrap().name
+    }
+
     #[test]
     fn test_ranking_order() {
         let mut state = GameState::new();
@@ -182,7 +416,7 @@ mod tests {
         state.add_player(create_player("HighMass", true, 5, 300.0, false));
         state.add_player(create_player("Dead", false, 15, 0.0, false));
 
-        let result = determine_result(&state);
+        let result = determine_result(&state, &MatchTally::default());
 
         // Alive beats dead
         assert!(result.rankings[0].survived);
@@ -257,7 +491,7 @@ mod tests {
         state.add_player(create_player("P2", false, 3, 0.0, false));
         state.add_player(create_player("P3", false, 2, 0.0, false));
 
-        let result = determine_result(&state);
+        let result = determine_result(&state, &MatchTally::default());
         assert_eq!(result.total_kills, 10);
     }
 }
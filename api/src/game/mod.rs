@@ -6,3 +6,17 @@ pub mod match_result;
 pub mod performance;
 pub mod spatial;
 pub mod input_buffer;
+pub mod input_stats;
+pub mod desync;
+pub mod modifiers;
+pub mod heatmap;
+pub mod arena_seed;
+pub mod scenario;
+pub mod ghost;
+pub mod tutorial;
+pub mod tick_watchdog;
+pub mod safe_mode;
+pub mod schedule;
+pub mod balance;
+pub mod arena_layout;
+pub mod arena_preset;
@@ -0,0 +1,275 @@
+//! Global modifiers ("weather")
+//!
+//! Timed, arena-wide effects such as a solar flare (faster projectiles) or a
+//! dense nebula (shorter vision). Modifiers scale named parameters in the
+//! [`ParameterRegistry`] held on the game state; systems read the current
+//! multiplier instead of their base constant, so an expired modifier needs no
+//! cleanup beyond dropping it from the registry.
+//!
+//! Modifiers start from the periodic [`WeatherRoller`], on request from
+//! outside the game loop (the AI manager) via [`ModifierRequests`], or from
+//! the event schedule, which may set its own duration.
+//!
+//! The registry also carries the projectile economy (shot cost per charge
+//! level, refund on a miss), the heat rules and boost fuel. Those are tuned rather than
+//! timed: the game loop sets them from the room's ruleset, and the AI manager
+//! may adjust the economy live.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{FuelConfig, HeatConfig, ProjectileEconomyConfig, WeatherConfig};
+use crate::net::i18n::{keys, LocalizedText};
+
+/// Queue of modifiers requested outside the game loop, started on the next playing tick
+pub type ModifierRequests = Arc<Mutex<Vec<GlobalModifier>>>;
+
+/// A tunable gameplay parameter that modifiers can scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Parameter {
+    /// Projectile launch speed
+    ProjectileSpeed,
+    /// Area-of-interest radius for players (what they can see)
+    VisionRadius,
+    /// Debris spawning, in the arena and around wells
+    DebrisSpawnRate,
+}
+
+impl Parameter {
+    /// Registry key (as used in logs and AI recommendations)
+    pub fn key(self) -> &'static str {
+        match self {
+            Parameter::ProjectileSpeed => "projectile.speed",
+            Parameter::VisionRadius => "vision.radius",
+            Parameter::DebrisSpawnRate => "debris.spawn_rate",
+        }
+    }
+}
+
+/// A timed arena-wide effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GlobalModifier {
+    /// Projectiles travel 30% faster
+    SolarFlare,
+    /// Vision radius halved
+    DenseNebula,
+    /// Debris spawns twice as fast
+    DebrisSurge,
+}
+
+impl GlobalModifier {
+    pub const ALL: [GlobalModifier; 3] =
+        [GlobalModifier::SolarFlare, GlobalModifier::DenseNebula, GlobalModifier::DebrisSurge];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GlobalModifier::SolarFlare => "solar_flare",
+            GlobalModifier::DenseNebula => "dense_nebula",
+            GlobalModifier::DebrisSurge => "debris_surge",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.name() == name)
+    }
+
+    /// Player-facing announcement text
+    pub fn description(self) -> LocalizedText {
+        LocalizedText::new(match self {
+            GlobalModifier::SolarFlare => keys::MODIFIER_SOLAR_FLARE,
+            GlobalModifier::DenseNebula => keys::MODIFIER_DENSE_NEBULA,
+            GlobalModifier::DebrisSurge => keys::MODIFIER_DEBRIS_SURGE,
+        })
+    }
+
+    /// How long the modifier lasts, in seconds
+    pub fn duration_secs(self) -> f32 {
+        match self {
+            GlobalModifier::SolarFlare => 60.0,
+            GlobalModifier::DenseNebula => 45.0,
+            GlobalModifier::DebrisSurge => 90.0,
+        }
+    }
+
+    /// Parameter this modifier scales, and by how much
+    pub fn effect(self) -> (Parameter, f32) {
+        match self {
+            GlobalModifier::SolarFlare => (Parameter::ProjectileSpeed, 1.3),
+            GlobalModifier::DenseNebula => (Parameter::VisionRadius, 0.5),
+            GlobalModifier::DebrisSurge => (Parameter::DebrisSpawnRate, 2.0),
+        }
+    }
+}
+
+/// A running modifier and the seconds it has left
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActiveModifier {
+    pub modifier: GlobalModifier,
+    pub remaining: f32,
+}
+
+/// Active modifiers and the parameter multipliers they produce, plus the
+/// tuned projectile economy, heat rules and boost fuel
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParameterRegistry {
+    active: Vec<ActiveModifier>,
+    projectile_economy: ProjectileEconomyConfig,
+    heat: HeatConfig,
+    #[serde(default)]
+    fuel: FuelConfig,
+}
+
+impl ParameterRegistry {
+    /// Combined multiplier for a parameter (1.0 when unmodified)
+    pub fn multiplier(&self, parameter: Parameter) -> f32 {
+        self.active
+            .iter()
+            .map(|a| a.modifier.effect())
+            .filter(|&(p, _)| p == parameter)
+            .map(|(_, factor)| factor)
+            .product()
+    }
+
+    /// Start a modifier for `duration` seconds. Returns false if it was already running.
+    pub fn activate(&mut self, modifier: GlobalModifier, duration: f32) -> bool {
+        if self.is_active(modifier) {
+            return false;
+        }
+        self.active.push(ActiveModifier { modifier, remaining: duration });
+        true
+    }
+
+    /// Current projectile cost and refund rules
+    pub fn projectile_economy(&self) -> &ProjectileEconomyConfig {
+        &self.projectile_economy
+    }
+
+    pub fn set_projectile_economy(&mut self, economy: ProjectileEconomyConfig) {
+        self.projectile_economy = economy;
+    }
+
+    /// Current heat rules (disabled = classic mass-cost firing)
+    pub fn heat(&self) -> &HeatConfig {
+        &self.heat
+    }
+
+    pub fn set_heat(&mut self, heat: HeatConfig) {
+        self.heat = heat;
+    }
+
+    /// Current boost fuel rules (disabled = unlimited boost)
+    pub fn fuel(&self) -> &FuelConfig {
+        &self.fuel
+    }
+
+    pub fn set_fuel(&mut self, fuel: FuelConfig) {
+        self.fuel = fuel;
+    }
+
+    /// Stop a modifier early. Returns false if it wasn't running.
+    pub fn deactivate(&mut self, modifier: GlobalModifier) -> bool {
+        let before = self.active.len();
+        self.active.retain(|a| a.modifier != modifier);
+        self.active.len() != before
+    }
+
+    pub fn is_active(&self, modifier: GlobalModifier) -> bool {
+        self.active.iter().any(|a| a.modifier == modifier)
+    }
+
+    pub fn active(&self) -> &[ActiveModifier] {
+        &self.active
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Count down active modifiers, returning the ones that expired
+    pub fn update(&mut self, dt: f32) -> Vec<GlobalModifier> {
+        let mut expired = Vec::new();
+        self.active.retain_mut(|a| {
+            a.remaining -= dt;
+            if a.remaining <= 0.0 {
+                expired.push(a.modifier);
+                false
+            } else {
+                true
+            }
+        });
+        expired
+    }
+}
+
+/// Periodically rolls for a random modifier while none is active
+#[derive(Debug, Clone, Default)]
+pub struct WeatherRoller {
+    since_roll: f32,
+}
+
+impl WeatherRoller {
+    /// Advance the roll timer; returns a modifier to start when a roll succeeds
+    pub fn roll(&mut self, config: &WeatherConfig, idle: bool, dt: f32, rng: &mut impl Rng) -> Option<GlobalModifier> {
+        if !config.enabled {
+            return None;
+        }
+        self.since_roll += dt;
+        if self.since_roll < config.roll_interval_secs {
+            return None;
+        }
+        self.since_roll = 0.0;
+
+        if !idle || !rng.gen_bool(config.chance as f64) {
+            return None;
+        }
+        Some(GlobalModifier::ALL[rng.gen_range(0..GlobalModifier::ALL.len())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_multipliers_and_expiry() {
+        let mut registry = ParameterRegistry::default();
+        assert_eq!(registry.multiplier(Parameter::ProjectileSpeed), 1.0);
+
+        assert!(registry.activate(GlobalModifier::SolarFlare, 2.0));
+        assert!(!registry.activate(GlobalModifier::SolarFlare, 2.0));
+        assert!(registry.activate(GlobalModifier::DenseNebula, 1.0));
+        assert_eq!(registry.multiplier(Parameter::ProjectileSpeed), 1.3);
+        assert_eq!(registry.multiplier(Parameter::VisionRadius), 0.5);
+
+        assert_eq!(registry.update(1.5), vec![GlobalModifier::DenseNebula]);
+        assert_eq!(registry.multiplier(Parameter::VisionRadius), 1.0);
+        assert_eq!(registry.update(1.0), vec![GlobalModifier::SolarFlare]);
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_roller_waits_for_interval_and_idle_arena() {
+        let config = WeatherConfig { enabled: true, roll_interval_secs: 10.0, chance: 1.0 };
+        let mut rng = rand::thread_rng();
+        let mut roller = WeatherRoller::default();
+
+        assert_eq!(roller.roll(&config, true, 9.0, &mut rng), None);
+        assert!(roller.roll(&config, true, 1.0, &mut rng).is_some());
+        // A modifier is already running: the roll is spent without starting another
+        assert_eq!(roller.roll(&config, false, 10.0, &mut rng), None);
+
+        let disabled = WeatherConfig { enabled: false, ..config };
+        assert_eq!(roller.roll(&disabled, true, 100.0, &mut rng), None);
+    }
+
+    #[test]
+    fn test_modifier_names_round_trip() {
+        for modifier in GlobalModifier::ALL {
+            assert_eq!(GlobalModifier::from_name(modifier.name()), Some(modifier));
+        }
+        assert_eq!(GlobalModifier::from_name("blizzard"), None);
+    }
+}
@@ -0,0 +1,246 @@
+//! Parameter freeze after incidents
+//!
+//! Runtime tuning can push a server over the edge: one well too many and
+//! ticks blow through the budget. When performance reaches Catastrophic, or
+//! the previous run crashed, the server enters safe mode for `freeze_secs`.
+//! Parameters go back to the last set that kept performance Good or better
+//! for `good_after_secs`, and any change to them is undone until the window
+//! passes. The AI manager skips its evaluations meanwhile.
+//!
+//! The last-known-good set is stored at `path` together with a `running`
+//! flag that is set at startup and cleared by a clean shutdown. Finding the
+//! flag still set means the previous process died mid-run. After such a
+//! crash restart the saved set replaces the one from the environment.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::{ArenaScalingConfig, ProjectileEconomyConfig, SafeModeConfig};
+use crate::game::performance::PerformanceStatus;
+
+/// The parameters that change at runtime, as one snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterSet {
+    pub arena: ArenaScalingConfig,
+    pub projectile_economy: ProjectileEconomyConfig,
+}
+
+/// What put the server in safe mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Incident {
+    /// Performance reached Catastrophic
+    Catastrophic,
+    /// The previous run didn't shut down cleanly
+    CrashRestart,
+}
+
+impl Incident {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Incident::Catastrophic => "catastrophic performance",
+            Incident::CrashRestart => "restart after a crash",
+        }
+    }
+}
+
+/// What safe mode did on one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeModeEvent {
+    /// Nothing changed
+    Quiet,
+    /// An incident started a freeze; parameters go back to the last-known-good set
+    Entered(Incident),
+    /// The freeze window passed
+    Released,
+    /// The current parameters became the last-known-good set
+    Snapshot,
+}
+
+/// Contents of the state file
+#[derive(Serialize, Deserialize)]
+struct SavedState {
+    /// Set while a server runs, cleared by a clean shutdown
+    running: bool,
+    last_good: ParameterSet,
+}
+
+/// Tracks the last-known-good parameters and freezes them after incidents
+pub struct SafeMode {
+    config: SafeModeConfig,
+    last_good: ParameterSet,
+    frozen_until: Option<Instant>,
+    /// Parameters currently proving themselves, and since when
+    candidate: Option<(ParameterSet, Instant)>,
+    /// A crash restart found at startup, reported by the first check
+    pending: Option<Incident>,
+}
+
+impl SafeMode {
+    /// Start with `current` as the last-known-good set. With a state file, a
+    /// crash restart takes the saved set instead and freezes on the first check.
+    pub fn new(config: SafeModeConfig, current: ParameterSet) -> Self {
+        let mut safe_mode = Self { config, last_good: current, frozen_until: None, candidate: None, pending: None };
+        if !safe_mode.config.enabled {
+            return safe_mode;
+        }
+        if let Some(path) = safe_mode.config.path.clone() {
+            match load(&path) {
+                Ok(Some(saved)) if saved.running => {
+                    safe_mode.last_good = saved.last_good;
+                    safe_mode.pending = Some(Incident::CrashRestart);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("{}", e),
+            }
+            if let Err(e) = safe_mode.save(true) {
+                warn!("{}", e);
+            }
+        }
+        safe_mode
+    }
+
+    pub fn last_good(&self) -> &ParameterSet {
+        &self.last_good
+    }
+
+    pub fn is_frozen(&self, now: Instant) -> bool {
+        self.frozen_until.is_some_and(|until| now < until)
+    }
+
+    /// Check the current performance and parameters, starting or ending a freeze
+    /// and promoting parameters that held up to the last-known-good set
+    pub fn observe(&mut self, status: PerformanceStatus, current: &ParameterSet, now: Instant) -> SafeModeEvent {
+        if !self.config.enabled {
+            return SafeModeEvent::Quiet;
+        }
+        if let Some(incident) = self.pending.take() {
+            return self.freeze(incident, now);
+        }
+        if let Some(until) = self.frozen_until {
+            if now < until {
+                return SafeModeEvent::Quiet;
+            }
+            self.frozen_until = None;
+            return SafeModeEvent::Released;
+        }
+        if status == PerformanceStatus::Catastrophic {
+            return self.freeze(Incident::Catastrophic, now);
+        }
+        if !matches!(status, PerformanceStatus::Excellent | PerformanceStatus::Good) {
+            self.candidate = None;
+            return SafeModeEvent::Quiet;
+        }
+
+        let since = match &self.candidate {
+            Some((params, since)) if params == current => *since,
+            _ => {
+                self.candidate = Some((current.clone(), now));
+                now
+            }
+        };
+        if now.duration_since(since) >= Duration::from_secs(self.config.good_after_secs) && *current != self.last_good {
+            self.last_good = current.clone();
+            return SafeModeEvent::Snapshot;
+        }
+        SafeModeEvent::Quiet
+    }
+
+    fn freeze(&mut self, incident: Incident, now: Instant) -> SafeModeEvent {
+        self.frozen_until = Some(now + Duration::from_secs(self.config.freeze_secs));
+        self.candidate = None;
+        SafeModeEvent::Entered(incident)
+    }
+
+    /// Write the last-known-good set to the state file, if there is one.
+    /// `running` is false only for a clean shutdown.
+    pub fn save(&self, running: bool) -> Result<(), String> {
+        let Some(path) = self.config.path.as_deref().filter(|_| self.config.enabled) else {
+            return Ok(());
+        };
+        let state = SavedState { running, last_good: self.last_good.clone() };
+        let json =
+            serde_json::to_string_pretty(&state).map_err(|e| format!("Failed to encode safe mode state: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write safe mode state: {}", e))
+    }
+}
+
+/// Read the state file (a missing file holds nothing)
+fn load(path: &str) -> Result<Option<SavedState>, String> {
+    if !Path::new(path).exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read safe mode state: {}", e))?;
+    serde_json::from_str(&contents).map(Some).map_err(|e| format!("Failed to parse safe mode state: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(max_wells: usize) -> ParameterSet {
+        ParameterSet {
+            arena: ArenaScalingConfig { max_wells, ..ArenaScalingConfig::default() },
+            projectile_economy: ProjectileEconomyConfig::default(),
+        }
+    }
+
+    fn config(path: Option<String>) -> SafeModeConfig {
+        SafeModeConfig { freeze_secs: 600, good_after_secs: 300, path, ..SafeModeConfig::default() }
+    }
+
+    #[test]
+    fn test_catastrophic_performance_freezes_on_the_last_good_set() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut safe_mode = SafeMode::new(config(None), params(20));
+
+        // A change only becomes known-good after holding up for good_after_secs
+        assert_eq!(safe_mode.observe(PerformanceStatus::Good, &params(30), at(0)), SafeModeEvent::Quiet);
+        assert_eq!(safe_mode.observe(PerformanceStatus::Good, &params(30), at(299)), SafeModeEvent::Quiet);
+        assert_eq!(safe_mode.observe(PerformanceStatus::Excellent, &params(30), at(300)), SafeModeEvent::Snapshot);
+
+        // A change that runs into trouble never does
+        assert_eq!(safe_mode.observe(PerformanceStatus::Good, &params(40), at(400)), SafeModeEvent::Quiet);
+        assert_eq!(safe_mode.observe(PerformanceStatus::Warning, &params(40), at(500)), SafeModeEvent::Quiet);
+        assert_eq!(
+            safe_mode.observe(PerformanceStatus::Catastrophic, &params(40), at(710)),
+            SafeModeEvent::Entered(Incident::Catastrophic)
+        );
+        assert_eq!(safe_mode.last_good(), &params(30));
+        assert!(safe_mode.is_frozen(at(1000)));
+
+        assert_eq!(safe_mode.observe(PerformanceStatus::Catastrophic, &params(30), at(1000)), SafeModeEvent::Quiet);
+        assert_eq!(safe_mode.observe(PerformanceStatus::Good, &params(30), at(1310)), SafeModeEvent::Released);
+        assert!(!safe_mode.is_frozen(at(1310)));
+    }
+
+    #[test]
+    fn test_crash_restart_restores_the_saved_set() {
+        let path = std::env::temp_dir().join(format!("orbit_safe_mode_{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap().to_string();
+        let _ = fs::remove_file(&path);
+
+        // First run: no state file yet, so no incident
+        let mut first = SafeMode::new(config(Some(path_str.clone())), params(20));
+        assert_eq!(first.observe(PerformanceStatus::Good, &params(20), Instant::now()), SafeModeEvent::Quiet);
+
+        // A clean shutdown clears the running flag
+        first.save(false).unwrap();
+        let mut clean = SafeMode::new(config(Some(path_str.clone())), params(25));
+        assert_eq!(clean.observe(PerformanceStatus::Good, &params(25), Instant::now()), SafeModeEvent::Quiet);
+        assert_eq!(clean.last_good(), &params(25));
+
+        // The second run dies without one: the next start freezes on its set
+        let mut crashed = SafeMode::new(config(Some(path_str)), params(50));
+        assert_eq!(crashed.last_good(), &params(25));
+        assert_eq!(
+            crashed.observe(PerformanceStatus::Excellent, &params(50), Instant::now()),
+            SafeModeEvent::Entered(Incident::CrashRestart)
+        );
+        let _ = fs::remove_file(&path);
+    }
+}
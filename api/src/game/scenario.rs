@@ -0,0 +1,331 @@
+//! Practice room scenarios
+//!
+//! `PracticeScenario` is the custom system behind `RoomKind::Practice`. It
+//! keeps stationary targets pinned to their anchors (respawning them in place
+//! when destroyed), respawns everyone else after the usual delay however many
+//! times they die, and tracks each shooter's shots, hits and delivered mass,
+//! emitting a `GameLoopEvent::PracticeReadout` per shooter every readout period.
+//!
+//! Targets are ordinary non-bot players, so the AI never moves them and
+//! projectiles, collisions and snapshots treat them like anyone else.
+
+#![allow(dead_code)] // Driven by lobby practice rooms
+
+use hashbrown::HashMap;
+
+use crate::config::PracticeConfig;
+use crate::game::constants::{mass, spawn};
+use crate::game::game_loop::GameLoopEvent;
+use crate::game::state::{EntityId, GameState, Player, PlayerId};
+use crate::game::systems::arena;
+use crate::game::systems::custom::GameSystem;
+use crate::util::vec2::Vec2;
+
+/// Upper bound of the practice gravity multiplier
+pub const MAX_GRAVITY_STRENGTH: f32 = 3.0;
+
+/// Most targets a practice room holds at once
+pub const MAX_TARGETS: u32 = 16;
+
+/// Color palette index of targets
+const TARGET_COLOR: u8 = 0;
+
+/// A command from a player in a practice room
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PracticeCommand {
+    /// Place targets evenly on a ring around the player
+    SpawnTargets { count: u32 },
+    /// Remove every target
+    ClearTargets,
+    /// Change the well gravity multiplier (0 - `MAX_GRAVITY_STRENGTH`)
+    SetGravity(f32),
+    /// Send every ghost back to the start of its run
+    RestartGhosts,
+    /// Stop replaying ghosts
+    ClearGhosts,
+}
+
+/// Running totals for one shooter
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ShooterStats {
+    shots: u32,
+    hits: u32,
+    /// Mass delivered since the last readout
+    window_damage: f32,
+}
+
+/// Drives target spawns, respawns and readouts in a practice room
+#[derive(Debug)]
+pub struct PracticeScenario {
+    config: PracticeConfig,
+    /// Targets and the positions they are pinned to
+    targets: HashMap<PlayerId, Vec2>,
+    stats: HashMap<PlayerId, ShooterStats>,
+    /// Projectiles with lower ids were already counted as shots
+    next_projectile_id: EntityId,
+    /// Seconds since the last readout
+    since_readout: f32,
+    /// Targets ever spawned (for naming)
+    spawned: u32,
+}
+
+impl PracticeScenario {
+    pub fn new(config: PracticeConfig) -> Self {
+        Self {
+            config,
+            targets: HashMap::new(),
+            stats: HashMap::new(),
+            next_projectile_id: 0,
+            since_readout: 0.0,
+            spawned: 0,
+        }
+    }
+
+    /// Number of targets placed at the start of a practice match
+    pub fn initial_targets(&self) -> u32 {
+        self.config.initial_targets
+    }
+
+    /// Whether a player is one of this scenario's targets
+    pub fn is_target(&self, player_id: PlayerId) -> bool {
+        self.targets.contains_key(&player_id)
+    }
+
+    /// Place up to `count` targets on a ring around `center`, returning how
+    /// many were added (the room never holds more than `MAX_TARGETS`)
+    pub fn spawn_targets(&mut self, state: &mut GameState, center: Vec2, count: u32) -> u32 {
+        let count = count.min(MAX_TARGETS.saturating_sub(self.targets.len() as u32));
+        for i in 0..count {
+            let angle = std::f32::consts::TAU * i as f32 / count as f32;
+            let anchor = center + Vec2::from_angle(angle) * self.config.target_distance;
+
+            self.spawned += 1;
+            let mut target =
+                Player::new(uuid::Uuid::new_v4(), format!("Target {}", self.spawned), false, TARGET_COLOR);
+            target.position = anchor;
+            target.spawn_protection = 0.0;
+            target.spawn_tick = state.tick;
+            self.targets.insert(target.id, anchor);
+            state.add_player(target);
+        }
+        count
+    }
+
+    /// Remove every target from the arena
+    pub fn clear_targets(&mut self, state: &mut GameState) {
+        for (id, _) in self.targets.drain() {
+            state.remove_player(id);
+        }
+    }
+
+    /// Forget targets and stats (the room's match was reset)
+    pub fn reset(&mut self) {
+        self.targets.clear();
+        self.stats.clear();
+        self.next_projectile_id = 0;
+        self.since_readout = 0.0;
+    }
+
+    /// Hold targets at their anchors, bringing destroyed ones straight back
+    fn pin_targets(&mut self, state: &mut GameState) {
+        self.targets.retain(|id, anchor| {
+            let Some(target) = state.players.get_mut(id) else {
+                return false;
+            };
+            if !target.alive {
+                target.alive = true;
+                target.respawn_timer = 0.0;
+                target.spawn_tick = state.tick;
+            }
+            target.position = *anchor;
+            target.velocity = Vec2::ZERO;
+            target.mass = mass::STARTING;
+            target.spawn_protection = 0.0;
+            true
+        });
+    }
+
+    /// Respawn dead players (other than targets) once their delay has passed
+    fn respawn_players(&self, state: &mut GameState, dt: f32) {
+        let mut ready = Vec::new();
+        for player in state.players.values_mut() {
+            if player.alive || self.targets.contains_key(&player.id) {
+                continue;
+            }
+            player.respawn_timer -= dt;
+            if player.respawn_timer <= 0.0 {
+                ready.push(player.id);
+            }
+        }
+        if ready.is_empty() {
+            return;
+        }
+
+        let wells: Vec<_> = state.arena.gravity_wells.values().cloned().collect();
+        for id in ready {
+            let occupied: Vec<Vec2> = state.players.values().filter(|p| p.alive).map(|p| p.position).collect();
+            let position = arena::safe_spawn_near_well(&wells, &occupied);
+            let tick = state.tick;
+            if let Some(player) = state.get_player_mut(id) {
+                player.position = position;
+                player.velocity = arena::spawn_velocity_for_well(position, &wells);
+                player.alive = true;
+                player.mass = mass::STARTING;
+                player.spawn_protection = spawn::PROTECTION_DURATION;
+                player.respawn_timer = 0.0;
+                player.spawn_tick = tick;
+                player.heat = 0.0;
+                player.fuel = 1.0;
+                player.effects.clear();
+            }
+        }
+    }
+
+    /// Count new projectiles as shots and this tick's hits against their shooters
+    fn record_shots(&mut self, state: &GameState, events: &[GameLoopEvent]) {
+        let mut next = self.next_projectile_id;
+        for projectile in &state.projectiles {
+            if projectile.id < self.next_projectile_id {
+                continue;
+            }
+            next = next.max(projectile.id + 1);
+            if !self.is_target(projectile.owner_id) {
+                self.stats.entry(projectile.owner_id).or_default().shots += 1;
+            }
+        }
+        self.next_projectile_id = next;
+
+        for event in events {
+            if let GameLoopEvent::PlayerHit { shooter_id, amount, .. } = *event {
+                if let Some(stats) = self.stats.get_mut(&shooter_id) {
+                    stats.hits += 1;
+                    stats.window_damage += amount;
+                }
+            }
+        }
+    }
+
+    /// Emit a readout per shooter and start a new damage window
+    fn emit_readouts(&mut self, state: &GameState, events: &mut Vec<GameLoopEvent>) {
+        let window = self.since_readout;
+        self.since_readout = 0.0;
+        self.stats.retain(|id, _| state.players.contains_key(id));
+
+        for (&player_id, stats) in self.stats.iter_mut() {
+            events.push(GameLoopEvent::PracticeReadout {
+                player_id,
+                damage_per_sec: stats.window_damage / window,
+                accuracy: if stats.shots > 0 { (stats.hits as f32 / stats.shots as f32).min(1.0) } else { 0.0 },
+                shots: stats.shots,
+                hits: stats.hits,
+            });
+            stats.window_damage = 0.0;
+        }
+    }
+}
+
+impl GameSystem for PracticeScenario {
+    fn run(&mut self, state: &mut GameState, dt: f32, events: &mut Vec<GameLoopEvent>) {
+        self.pin_targets(state);
+        self.respawn_players(state, dt);
+        self.record_shots(state, events);
+
+        self.since_readout += dt;
+        if self.since_readout >= self.config.readout_secs {
+            self.emit_readouts(state, events);
+        }
+    }
+
+    fn name(&self) -> &str {
+        "practice_scenario"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario() -> PracticeScenario {
+        PracticeScenario::new(PracticeConfig { readout_secs: 1.0, ..PracticeConfig::default() })
+    }
+
+    fn add_shooter(state: &mut GameState) -> PlayerId {
+        let player = Player::new(uuid::Uuid::new_v4(), "Shooter".into(), false, 1);
+        let id = player.id;
+        state.add_player(player);
+        id
+    }
+
+    #[test]
+    fn test_targets_stay_pinned_and_come_back() {
+        let mut state = GameState::new();
+        let mut scenario = scenario();
+        assert_eq!(scenario.spawn_targets(&mut state, Vec2::ZERO, 4), 4);
+        assert_eq!(scenario.spawn_targets(&mut state, Vec2::ZERO, MAX_TARGETS), MAX_TARGETS - 4);
+
+        let id = *scenario.targets.keys().next().unwrap();
+        let anchor = scenario.targets[&id];
+        let target = state.get_player_mut(id).unwrap();
+        target.position += Vec2::new(40.0, 0.0);
+        target.velocity = Vec2::new(10.0, 0.0);
+        target.alive = false;
+
+        scenario.run(&mut state, 0.1, &mut Vec::new());
+        let target = state.get_player(id).unwrap();
+        assert!(target.alive);
+        assert_eq!(target.position, anchor);
+        assert_eq!(target.velocity, Vec2::ZERO);
+
+        scenario.clear_targets(&mut state);
+        assert!(state.players.is_empty());
+    }
+
+    #[test]
+    fn test_players_respawn_after_delay() {
+        let mut state = GameState::new();
+        let mut scenario = scenario();
+        let id = add_shooter(&mut state);
+        let player = state.get_player_mut(id).unwrap();
+        player.alive = false;
+        player.respawn_timer = spawn::RESPAWN_DELAY;
+
+        scenario.run(&mut state, spawn::RESPAWN_DELAY / 2.0, &mut Vec::new());
+        assert!(!state.get_player(id).unwrap().alive);
+        scenario.run(&mut state, spawn::RESPAWN_DELAY, &mut Vec::new());
+        assert!(state.get_player(id).unwrap().alive);
+        assert_eq!(state.get_player(id).unwrap().mass, mass::STARTING);
+    }
+
+    #[test]
+    fn test_readout_reports_accuracy_and_dps() {
+        let mut state = GameState::new();
+        let mut scenario = scenario();
+        let shooter = add_shooter(&mut state);
+        scenario.spawn_targets(&mut state, Vec2::ZERO, 1);
+        let target = *scenario.targets.keys().next().unwrap();
+
+        for _ in 0..4 {
+            state.add_projectile(shooter, Vec2::ZERO, Vec2::ZERO, 5.0);
+        }
+        let hit = GameLoopEvent::PlayerHit {
+            shooter_id: shooter,
+            victim_id: target,
+            projectile_id: 1,
+            amount: 5.0,
+            position: Vec2::ZERO,
+        };
+        let mut events = vec![hit];
+        scenario.run(&mut state, 0.5, &mut events);
+        assert_eq!(events.len(), 1, "no readout before the period elapses");
+
+        let mut events = Vec::new();
+        scenario.run(&mut state, 0.5, &mut events);
+        let readout = events.iter().find_map(|e| match *e {
+            GameLoopEvent::PracticeReadout { player_id, damage_per_sec, accuracy, shots, hits } => {
+                Some((player_id, damage_per_sec, accuracy, shots, hits))
+            }
+            _ => None,
+        });
+        assert_eq!(readout, Some((shooter, 5.0, 0.25, 4, 1)));
+    }
+}
@@ -0,0 +1,348 @@
+//! Timed server events
+//!
+//! Operators schedule happenings with cron expressions: a debris surge every
+//! weekend, a boss bot every hour, a fresh arena every night. Each entry pairs
+//! a standard five-field expression (minute, hour, day of month, month, day of
+//! week, in UTC) with an action, e.g. `0 * * * *|boss`. The scheduler tracks
+//! every entry's next run, reports it `announce_lead_secs` ahead so players
+//! can be warned, and reports it again once it's due. The game loop carries
+//! the actions out; timed effects go through the modifiers system.
+//!
+//! Field syntax is the usual subset: `*`, numbers, ranges (`1-5`), steps
+//! (`*/15`, `0-30/10`) and comma lists. When both day fields are restricted,
+//! either one matching is enough, as in cron.
+
+use crate::game::modifiers::GlobalModifier;
+use crate::net::i18n::{keys, LocalizedText};
+
+/// Starting mass of a scheduled boss bot
+pub const BOSS_MASS: f32 = 800.0;
+
+/// How far ahead `next_after` looks before deciding an expression never runs
+const SEARCH_LIMIT_SECS: u64 = 5 * 366 * SECS_PER_DAY;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Why a scheduled event entry was rejected
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ScheduleError {
+    #[error("expected `cron|action`")]
+    MissingAction,
+    #[error("expected 5 cron fields, got {0}")]
+    FieldCount(usize),
+    #[error("invalid cron field `{0}`")]
+    InvalidField(String),
+    #[error("unknown action `{0}`")]
+    UnknownAction(String),
+}
+
+/// A parsed cron expression; each field is a bitmask of the values it allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    weekdays: u8,
+    /// Day of month or day of week was `*` (cron matches both day fields then)
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, ScheduleError> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let &[minute, hour, day, month, weekday] = fields.as_slice() else {
+            return Err(ScheduleError::FieldCount(fields.len()));
+        };
+        // Sunday may be written as 0 or 7
+        let weekdays = parse_field(weekday, 0, 7)?;
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)? as u32,
+            days: parse_field(day, 1, 31)? as u32,
+            months: parse_field(month, 1, 12)? as u16,
+            weekdays: ((weekdays | weekdays >> 7) & 0x7f) as u8,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    /// Start of the first matching minute after `unix_secs`. None if nothing
+    /// matches within a few years (e.g. `0 0 31 2 *`).
+    pub fn next_after(&self, unix_secs: u64) -> Option<u64> {
+        let mut t = (unix_secs / 60 + 1) * 60;
+        let limit = unix_secs + SEARCH_LIMIT_SECS;
+        while t <= limit {
+            let time = CivilTime::from_unix(t);
+            if !self.day_matches(&time) {
+                t = (t / SECS_PER_DAY + 1) * SECS_PER_DAY;
+            } else if !bit(self.hours as u64, time.hour) {
+                t = (t / 3600 + 1) * 3600;
+            } else if !bit(self.minutes, time.minute) {
+                t += 60;
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, time: &CivilTime) -> bool {
+        if !bit(self.months as u64, time.month) {
+            return false;
+        }
+        let day = bit(self.days as u64, time.day);
+        let weekday = bit(self.weekdays as u64, time.weekday);
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parse one comma-separated cron field into a bitmask of allowed values
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, ScheduleError> {
+    let invalid = || ScheduleError::InvalidField(field.to_string());
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => {
+                    (start.parse::<u32>().map_err(|_| invalid())?, end.parse::<u32>().map_err(|_| invalid())?)
+                }
+                None => {
+                    let value = range.parse::<u32>().map_err(|_| invalid())?;
+                    // `5/10` means from 5 to the end in steps of 10
+                    (value, if part.contains('/') { max } else { value })
+                }
+            },
+        };
+        if step == 0 || start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// UTC calendar fields of a unix timestamp
+struct CivilTime {
+    month: u32,
+    day: u32,
+    /// 0 = Sunday
+    weekday: u32,
+    hour: u32,
+    minute: u32,
+}
+
+impl CivilTime {
+    fn from_unix(unix_secs: u64) -> Self {
+        let days = unix_secs / SECS_PER_DAY;
+        let secs = unix_secs % SECS_PER_DAY;
+        // Days to civil date (Howard Hinnant's algorithm, shifted to start years in March)
+        let z = days + 719_468;
+        let day_of_era = z % 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        Self {
+            month: (if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 }) as u32,
+            day: (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32,
+            // 1970-01-01 was a Thursday
+            weekday: ((days + 4) % 7) as u32,
+            hour: (secs / 3600) as u32,
+            minute: (secs % 3600 / 60) as u32,
+        }
+    }
+}
+
+/// What a scheduled event does when it comes due
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScheduledAction {
+    /// Start a global modifier for `duration` seconds
+    Modifier { modifier: GlobalModifier, duration: f32 },
+    /// Drop a heavy boss bot into the arena
+    BossSpawn,
+    /// Clear the arena and respawn every player, keeping scores
+    ArenaReset,
+}
+
+impl ScheduledAction {
+    /// Parse `modifier:<name>[:<secs>]`, `boss` or `arena_reset`
+    pub fn parse(action: &str) -> Result<Self, ScheduleError> {
+        let unknown = || ScheduleError::UnknownAction(action.to_string());
+        let mut parts = action.split(':').map(str::trim);
+        match parts.next().unwrap_or_default() {
+            "boss" => Ok(ScheduledAction::BossSpawn),
+            "arena_reset" => Ok(ScheduledAction::ArenaReset),
+            "modifier" => {
+                let modifier = parts.next().and_then(GlobalModifier::from_name).ok_or_else(unknown)?;
+                let duration = match parts.next() {
+                    Some(secs) => secs.parse::<f32>().ok().filter(|d| *d > 0.0).ok_or_else(unknown)?,
+                    None => modifier.duration_secs(),
+                };
+                Ok(ScheduledAction::Modifier { modifier, duration })
+            }
+            _ => Err(unknown()),
+        }
+    }
+
+    /// Player-facing summary, for announcements
+    pub fn description(&self) -> LocalizedText {
+        match self {
+            ScheduledAction::Modifier { modifier, .. } => modifier.description(),
+            ScheduledAction::BossSpawn => LocalizedText::new(keys::SCHEDULE_BOSS_SPAWN),
+            ScheduledAction::ArenaReset => LocalizedText::new(keys::SCHEDULE_ARENA_RESET),
+        }
+    }
+}
+
+/// One configured `cron|action` entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledEvent {
+    pub schedule: CronSchedule,
+    pub action: ScheduledAction,
+}
+
+impl ScheduledEvent {
+    pub fn parse(entry: &str) -> Result<Self, ScheduleError> {
+        let (cron, action) = entry.split_once('|').ok_or(ScheduleError::MissingAction)?;
+        Ok(Self { schedule: CronSchedule::parse(cron)?, action: ScheduledAction::parse(action.trim())? })
+    }
+}
+
+/// What the scheduler has to report about an event this update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScheduleSignal {
+    /// The event runs in `starts_in` seconds (sent once per run)
+    Upcoming { action: ScheduledAction, starts_in: u64 },
+    /// The event is due now
+    Due { action: ScheduledAction },
+}
+
+/// Next run of each configured event
+#[derive(Debug, Default)]
+pub struct EventScheduler {
+    /// Unix time of each event's next run, and whether it has been announced
+    next: Vec<Option<(u64, bool)>>,
+}
+
+impl EventScheduler {
+    /// Announce and fire `events` against wall-clock `now` (unix seconds).
+    /// Runs missed while the server wasn't ticking fire once, late.
+    pub fn update(&mut self, events: &[ScheduledEvent], announce_lead_secs: u64, now: u64) -> Vec<ScheduleSignal> {
+        if self.next.len() != events.len() {
+            self.next = events.iter().map(|e| e.schedule.next_after(now).map(|at| (at, false))).collect();
+        }
+
+        let mut signals = Vec::new();
+        for (event, next) in events.iter().zip(&mut self.next) {
+            let Some((at, announced)) = next else {
+                continue;
+            };
+            if now >= *at {
+                signals.push(ScheduleSignal::Due { action: event.action });
+                *next = event.schedule.next_after(now).map(|at| (at, false));
+            } else if !*announced && announce_lead_secs > 0 && *at - now <= announce_lead_secs {
+                signals.push(ScheduleSignal::Upcoming { action: event.action, starts_in: *at - now });
+                *announced = true;
+            }
+        }
+        signals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-03-09 (a Saturday) 12:34:56 UTC
+    const SATURDAY_NOON: u64 = 1_709_987_696;
+
+    /// Whether `expression` fires in the minute containing `unix_secs`
+    fn fires(expression: &str, unix_secs: u64) -> bool {
+        let minute = unix_secs - unix_secs % 60;
+        CronSchedule::parse(expression).unwrap().next_after(minute - 1) == Some(minute)
+    }
+
+    #[test]
+    fn test_civil_time_and_matching() {
+        let time = CivilTime::from_unix(SATURDAY_NOON);
+        assert_eq!((time.month, time.day, time.weekday, time.hour, time.minute), (3, 9, 6, 12, 34));
+        // Leap day
+        let leap = CivilTime::from_unix(1_709_164_800);
+        assert_eq!((leap.month, leap.day), (2, 29));
+
+        assert!(fires("* * * * *", SATURDAY_NOON));
+        assert!(fires("30-40/2 12 * * 6", SATURDAY_NOON));
+        assert!(fires("34 12 9 3 *", SATURDAY_NOON));
+        assert!(!fires("*/5 * * * *", SATURDAY_NOON));
+        // Either restricted day field may match
+        assert!(fires("34 12 1 * 6", SATURDAY_NOON));
+        assert!(!fires("34 12 9 * 1", SATURDAY_NOON + SECS_PER_DAY));
+
+        assert_eq!(CronSchedule::parse("* * *"), Err(ScheduleError::FieldCount(3)));
+        assert_eq!(CronSchedule::parse("60 * * * *"), Err(ScheduleError::InvalidField("60".to_string())));
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_next_run() {
+        let hourly = CronSchedule::parse("0 * * * *").unwrap();
+        assert_eq!(hourly.next_after(SATURDAY_NOON), Some(SATURDAY_NOON - 34 * 60 - 56 + 3600));
+
+        // Next Saturday midnight; Sunday may also be written as 7
+        let weekend = CronSchedule::parse("0 0 * * 6").unwrap();
+        let next = weekend.next_after(SATURDAY_NOON).unwrap();
+        assert_eq!(next, SATURDAY_NOON - (12 * 3600 + 34 * 60 + 56) + 7 * SECS_PER_DAY);
+        assert!(fires("0 0 * * 7", next + SECS_PER_DAY));
+
+        assert_eq!(CronSchedule::parse("0 0 31 2 *").unwrap().next_after(SATURDAY_NOON), None);
+    }
+
+    #[test]
+    fn test_action_parsing() {
+        let surge = ScheduledEvent::parse("0 0 * * 6|modifier:debris_surge:172800").unwrap();
+        let weekend_surge = ScheduledAction::Modifier { modifier: GlobalModifier::DebrisSurge, duration: 172_800.0 };
+        assert_eq!(surge.action, weekend_surge);
+        assert_eq!(
+            ScheduledAction::parse("modifier:solar_flare"),
+            Ok(ScheduledAction::Modifier { modifier: GlobalModifier::SolarFlare, duration: 60.0 })
+        );
+        assert_eq!(ScheduledAction::parse("arena_reset"), Ok(ScheduledAction::ArenaReset));
+        assert!(ScheduledAction::parse("modifier:blizzard").is_err());
+        assert!(ScheduledAction::parse("modifier:solar_flare:-1").is_err());
+        assert_eq!(ScheduledEvent::parse("0 * * * *"), Err(ScheduleError::MissingAction));
+    }
+
+    #[test]
+    fn test_scheduler_announces_then_fires() {
+        let events = [ScheduledEvent::parse("0 * * * *|boss").unwrap()];
+        let top_of_hour = SATURDAY_NOON - 34 * 60 - 56 + 3600;
+        let mut scheduler = EventScheduler::default();
+
+        assert!(scheduler.update(&events, 300, SATURDAY_NOON).is_empty());
+        let upcoming = ScheduleSignal::Upcoming { action: ScheduledAction::BossSpawn, starts_in: 300 };
+        assert_eq!(scheduler.update(&events, 300, top_of_hour - 300), vec![upcoming]);
+        assert!(scheduler.update(&events, 300, top_of_hour - 200).is_empty());
+        assert_eq!(
+            scheduler.update(&events, 300, top_of_hour + 1),
+            vec![ScheduleSignal::Due { action: ScheduledAction::BossSpawn }]
+        );
+        // Rescheduled for the next hour
+        assert!(scheduler.update(&events, 300, top_of_hour + 2).is_empty());
+        assert_eq!(scheduler.update(&events, 300, top_of_hour + 3300).len(), 1);
+    }
+}
@@ -11,8 +11,10 @@ use smallvec::SmallVec;
 use uuid::Uuid;
 
 use crate::config::ArenaScalingConfig;
+use crate::game::arena_seed::ArenaSeed;
 use crate::game::constants::{arena, mass, spawn};
 use crate::game::spatial::WellSpatialGrid;
+use crate::game::systems::status_effects::StatusEffects;
 use crate::util::vec2::Vec2;
 
 /// Unique player identifier
@@ -58,12 +60,28 @@ pub struct Player {
     /// Tick when player spawned/respawned (for birth animation detection)
     #[serde(default)]
     pub spawn_tick: u64,
+    /// Heat gauge (0-1), used when the ruleset fires on heat (see `HeatConfig`)
+    #[serde(default)]
+    pub heat: f32,
+    /// Boost fuel (0-1), used when the ruleset enables it (see `FuelConfig`)
+    #[serde(default = "full_tank")]
+    pub fuel: f32,
+    /// Bot faction, from 1 (0 = none, see `systems::factions`)
+    #[serde(default)]
+    pub faction: u8,
 
     // === COLD FIELDS (Cache Line 3 - rarely accessed in hot path) ===
     /// Unique player identifier
     pub id: PlayerId,
     /// Player display name
     pub name: String,
+    /// Active burn, slow and shield (see `systems::status_effects`)
+    #[serde(default)]
+    pub effects: StatusEffects,
+}
+
+fn full_tank() -> f32 {
+    1.0
 }
 
 impl Player {
@@ -83,9 +101,13 @@ impl Player {
             is_bot,
             color_index,
             spawn_tick: 0, // Set properly when added to game via add_player
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
             // COLD fields
             id,
             name,
+            effects: StatusEffects::default(),
         }
     }
 
@@ -201,6 +223,27 @@ impl Debris {
     }
 }
 
+/// Destructible asteroid (a fixed obstacle that breaks into debris)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Asteroid {
+    pub id: EntityId,
+    pub position: Vec2,
+    pub radius: f32,
+    /// Projectile mass it can still absorb before breaking
+    pub health: f32,
+    pub max_health: f32,
+}
+
+impl Asteroid {
+    pub fn new(id: EntityId, position: Vec2, radius: f32, health: f32) -> Self {
+        Self { id, position, radius, health, max_health: health }
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.health <= 0.0
+    }
+}
+
 /// Unique identifier for gravity wells (stable across removals)
 pub type WellId = u32;
 
@@ -225,6 +268,25 @@ pub struct GravityWell {
     /// Whether the well is currently charging (pre-explosion warning)
     #[serde(default)]
     pub is_charging: bool,
+    /// Capture state (owner, progress, contested)
+    #[serde(default)]
+    pub ownership: WellOwnership,
+}
+
+/// Who owns a gravity well and how far a capture has progressed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WellOwnership {
+    /// Player who claimed the well
+    pub owner: Option<PlayerId>,
+    /// Player currently capturing (alone in the capture ring)
+    pub capturer: Option<PlayerId>,
+    /// Capture progress for `capturer` (0-1)
+    pub progress: f32,
+    /// More than one player is in the capture ring (progress frozen)
+    pub contested: bool,
+    /// Fractional passive debris owed to the owner's well
+    #[serde(skip)]
+    pub debris_accumulator: f32,
 }
 
 impl GravityWell {
@@ -237,6 +299,7 @@ impl GravityWell {
             core_radius,
             explosion_timer: crate::config::GravityWaveConfig::global().random_explosion_delay(),
             is_charging: false,
+            ownership: WellOwnership::default(),
         }
     }
 
@@ -318,8 +381,11 @@ pub struct Arena {
     /// Next well ID to assign (monotonically increasing)
     #[serde(default = "default_next_well_id")]
     pub next_well_id: WellId,
+    /// Seed the well layout is derived from (shared as an arena code)
+    #[serde(default = "ArenaSeed::random")]
+    pub seed: ArenaSeed,
     /// Base angular offset for golden angle well distribution.
-    /// Initialized once at arena creation, reused for all well additions
+    /// Derived from the seed at arena creation, reused for all well additions
     /// to maintain consistent golden angle spacing across batches.
     #[serde(default = "default_well_base_offset")]
     pub well_base_offset: f32,
@@ -328,6 +394,10 @@ pub struct Arena {
     /// Unlike well count, this counter only goes up - destroyed wells don't free their angle slots.
     #[serde(default)]
     pub next_well_angle_index: u32,
+    /// Size and wells come from a hand-made layout (see `arena_layout`);
+    /// scaling leaves them alone until the players outgrow the layout
+    #[serde(default)]
+    pub fixed_layout: bool,
 }
 
 fn default_next_well_id() -> WellId { 1 }
@@ -341,6 +411,13 @@ fn default_scale() -> f32 { 1.0 }
 
 impl Default for Arena {
     fn default() -> Self {
+        Self::with_seed(ArenaSeed::random())
+    }
+}
+
+impl Arena {
+    /// Create an arena whose well layout follows `seed`
+    pub fn with_seed(seed: ArenaSeed) -> Self {
         use crate::game::constants::physics::CENTRAL_MASS;
         let central_well = GravityWell::new(CENTRAL_WELL_ID, Vec2::ZERO, CENTRAL_MASS, arena::CORE_RADIUS);
         let mut wells = HashMap::with_capacity(32);
@@ -365,13 +442,13 @@ impl Default for Arena {
             well_grid,
             shrink_delay_ticks: 0,
             next_well_id: 1, // Central well uses ID 0
-            well_base_offset: default_well_base_offset(),
+            seed,
+            well_base_offset: seed.well_base_offset(),
             next_well_angle_index: 0, // Start at 0, increment for each well added
+            fixed_layout: false,
         }
     }
-}
 
-impl Arena {
     /// Get the current safe radius based on collapse progress
     /// Uses escape_radius directly since it's already lerped to the correct size
     /// (scale is derived from escape_radius, so multiplying them would be quadratic)
@@ -456,7 +533,7 @@ impl Arena {
     /// Calculate minimum well spacing dynamically based on arena size and well count.
     /// Uses formula: sqrt(arena_area / total_wells) * factor
     /// This ensures spacing scales naturally as arena grows/shrinks.
-    fn calculate_min_well_spacing(&self, escape_radius: f32, total_wells: usize) -> f32 {
+    pub fn min_well_spacing(escape_radius: f32, total_wells: usize) -> f32 {
         let arena_area = std::f32::consts::PI * escape_radius * escape_radius;
         let area_per_well = arena_area / (total_wells.max(1) as f32);
         let ideal_spacing = area_per_well.sqrt();
@@ -681,6 +758,15 @@ impl Arena {
     /// * `config` - Arena scaling configuration
     /// * `can_grow` - If false, arena will not grow (used for health-based limiting)
     pub fn scale_for_simulation(&mut self, target_player_count: usize, config: &ArenaScalingConfig, can_grow: bool) {
+        if self.fixed_layout {
+            let area = std::f32::consts::PI * self.escape_radius * self.escape_radius;
+            if target_player_count <= (area / config.area_per_player) as usize {
+                return;
+            }
+            // Outgrown: procedural scaling takes over from here
+            self.fixed_layout = false;
+        }
+
         let min_escape = config.min_escape_radius;
         // Safety cap as emergency brake (default 50x = 40,000 units)
         let max_escape = config.min_escape_radius * config.max_escape_multiplier;
@@ -833,13 +919,11 @@ impl Arena {
     /// - Dynamic minimum spacing based on arena size and well count
     pub fn add_orbital_wells(&mut self, count: usize, escape_radius: f32, config: &ArenaScalingConfig) {
         use crate::game::constants::physics::CENTRAL_MASS;
-        use rand::Rng;
 
         // Golden angle in radians: 360°/φ² ≈ 137.5077° = 2.399963 radians
         // This is nature's optimal spacing angle (sunflower seeds, pinecones, etc.)
         const GOLDEN_ANGLE: f32 = 2.399963;
 
-        let mut rng = rand::thread_rng();
         let size_multipliers = [0.6, 0.8, 1.0, 1.2, 1.4];
 
         // Count existing orbital wells (exclude central supermassive at ID 0)
@@ -863,7 +947,7 @@ impl Arena {
         let total_wells = existing_orbital + actual_count;
 
         // Dynamic minimum spacing based on arena size and well count
-        let min_spacing = self.calculate_min_well_spacing(escape_radius, total_wells);
+        let min_spacing = Self::min_well_spacing(escape_radius, total_wells);
 
         // Find optimal radii that fill gaps in existing distribution
         // (FIX: Previously used Fermat spiral that didn't account for existing wells)
@@ -896,8 +980,8 @@ impl Arena {
                 }
             };
 
-            // Random well size for variety
-            let size_mult = size_multipliers[rng.gen_range(0..size_multipliers.len())];
+            // Well size for variety, drawn from the arena seed
+            let size_mult = size_multipliers[self.seed.well_size_index(angle_index, size_multipliers.len())];
             let well_mass = CENTRAL_MASS * size_mult;
             let well_core = arena::CORE_RADIUS * size_mult;
 
@@ -962,12 +1046,17 @@ pub struct GameState {
     pub players: HashMap<PlayerId, Player>,
     pub projectiles: Vec<Projectile>,
     pub debris: Vec<Debris>,
+    #[serde(default)]
+    pub asteroids: Vec<Asteroid>,
     /// Active gravity waves from well explosions (not serialized - visual only)
     #[serde(skip)]
     pub gravity_waves: Vec<GravityWave>,
     /// Spatial grid for efficient gravity well lookups (not serialized - rebuilt as needed)
     #[serde(skip)]
     pub well_grid: crate::game::spatial::WellSpatialGrid,
+    /// Active global modifiers and the parameter multipliers they apply
+    #[serde(default)]
+    pub modifiers: crate::game::modifiers::ParameterRegistry,
     next_entity_id: EntityId,
 }
 
@@ -976,6 +1065,11 @@ impl GameState {
         Self::default()
     }
 
+    /// Fresh state whose arena layout follows `seed`
+    pub fn with_arena_seed(seed: ArenaSeed) -> Self {
+        Self { arena: Arena::with_seed(seed), ..Self::default() }
+    }
+
     /// Rebuild the spatial grid for gravity wells
     /// Call this after wells are added, removed, or moved
     pub fn rebuild_well_grid(&mut self) {
@@ -1048,6 +1142,13 @@ impl GameState {
         self.debris.push(debris);
         id
     }
+
+    /// Add an asteroid at full health
+    pub fn add_asteroid(&mut self, position: Vec2, radius: f32, health: f32) -> EntityId {
+        let id = self.next_entity_id();
+        self.asteroids.push(Asteroid::new(id, position, radius, health));
+        id
+    }
 }
 
 #[cfg(test)]
@@ -1843,6 +1944,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_same_seed_same_well_layout() {
+        use crate::config::ArenaScalingConfig;
+        let config = ArenaScalingConfig::default();
+        let layout = |batches: &[usize]| {
+            let mut arena = Arena::with_seed(ArenaSeed(7));
+            for (i, &count) in batches.iter().enumerate() {
+                arena.add_orbital_wells(count, 1000.0 + 500.0 * i as f32, &config);
+            }
+            let mut wells: Vec<_> = arena.wells_iter().map(|w| (w.id, w.position, w.mass)).collect();
+            wells.sort_by_key(|&(id, _, _)| id);
+            wells
+        };
+
+        assert_eq!(layout(&[2, 3, 4]), layout(&[2, 3, 4]));
+        assert_ne!(Arena::with_seed(ArenaSeed(7)).well_base_offset, Arena::with_seed(ArenaSeed(8)).well_base_offset);
+    }
+
     #[test]
     fn test_incremental_well_distribution_quality() {
         // Add wells in multiple batches and verify ALL wells maintain minimum spacing
@@ -1928,14 +2047,12 @@ mod tests {
     #[test]
     fn test_dynamic_well_spacing() {
         // Test that spacing scales dynamically with arena size and well count
-        let arena = Arena::default();
-
         // Small arena with few wells
-        let small_spacing = arena.calculate_min_well_spacing(800.0, 3);
+        let small_spacing = Arena::min_well_spacing(800.0, 3);
         // sqrt(PI * 800^2 / 3) * 0.4 ≈ 327
 
         // Larger arena with more wells
-        let large_spacing = arena.calculate_min_well_spacing(3000.0, 10);
+        let large_spacing = Arena::min_well_spacing(3000.0, 10);
         // sqrt(PI * 3000^2 / 10) * 0.4 ≈ 672
 
         // Spacing should scale with arena size
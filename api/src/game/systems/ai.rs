@@ -3,7 +3,7 @@ use rayon::prelude::*;
 
 use crate::game::constants::ai::*;
 use crate::game::state::{GameState, Player, PlayerId};
-use crate::game::systems::arena::{get_zone, Zone};
+use crate::game::systems::arena::boundary_weight;
 use crate::net::protocol::PlayerInput;
 use crate::util::vec2::Vec2;
 
@@ -257,6 +257,7 @@ impl AiManager {
             boost: ai.wants_boost,
             fire: ai.wants_fire,
             fire_released: !ai.wants_fire && ai.charge_time > 0.0,
+            state_hash: None,
         })
     }
 }
@@ -432,7 +433,8 @@ fn execute_orbit(ai: &mut AiState, bot: &Player, state: &GameState) {
 
     // Light boost to maintain orbit - only if significantly below orbital velocity
     let orbital_vel = crate::game::systems::gravity::orbital_velocity(current_radius);
-    ai.wants_boost = bot.velocity.length() < orbital_vel * ORBIT_VELOCITY_BOOST_THRESHOLD;
+    ai.wants_boost = bot.velocity.length() < orbital_vel * ORBIT_VELOCITY_BOOST_THRESHOLD
+        && state.modifiers.fuel().bot_may_boost(bot.fuel, false);
 }
 
 fn execute_chase(ai: &mut AiState, bot: &Player, state: &GameState) {
@@ -453,7 +455,7 @@ fn execute_chase(ai: &mut AiState, bot: &Player, state: &GameState) {
     let chase_dir = (predicted_pos - bot.position).normalize();
 
     ai.thrust_direction = chase_dir;
-    ai.wants_boost = distance > CHASE_BOOST_DISTANCE_THRESHOLD;
+    ai.wants_boost = distance > CHASE_BOOST_DISTANCE_THRESHOLD && state.modifiers.fuel().bot_may_boost(bot.fuel, false);
     ai.aim_direction = chase_dir;
 }
 
@@ -469,18 +471,18 @@ fn execute_flee(ai: &mut AiState, bot: &Player, state: &GameState) {
     // Flee direction is away from threat
     let flee_dir = (bot.position - threat.position).normalize();
 
-    // Also try to stay in arena
-    let zone = get_zone(bot.position, &state.arena);
-    let adjusted_dir = if zone == Zone::Escape || zone == Zone::Outside {
-        // Blend flee direction with direction toward center
+    // Also try to stay in arena: blend in the direction toward center, more strongly near the edge
+    let edge = boundary_weight(bot.position, &state.arena);
+    let adjusted_dir = if edge > 0.0 {
         let to_center = -bot.position.normalize();
-        (flee_dir + to_center).normalize()
+        (flee_dir + to_center * edge).normalize()
     } else {
         flee_dir
     };
 
     ai.thrust_direction = adjusted_dir;
-    ai.wants_boost = true;
+    // Fleeing may spend the fuel reserve
+    ai.wants_boost = state.modifiers.fuel().bot_may_boost(bot.fuel, true);
     ai.aim_direction = -flee_dir; // Aim at threat while fleeing
 }
 
@@ -656,6 +658,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         }
     }
 
@@ -757,6 +763,33 @@ mod tests {
         assert!(ai.wants_boost);
     }
 
+    #[test]
+    fn test_bots_keep_a_fuel_reserve_except_when_fleeing() {
+        let mut state = create_test_state();
+        let fuel = crate::config::FuelConfig { enabled: true, ..Default::default() };
+        state.modifiers.set_fuel(fuel);
+
+        let mut bot = create_bot(Vec2::new(100.0, 0.0), 50.0);
+        bot.fuel = fuel.bot_reserve * 0.5;
+        let bot_id = bot.id;
+        let target = create_bot(Vec2::new(1000.0, 0.0), 200.0);
+        let target_id = target.id;
+        state.add_player(bot);
+        state.add_player(target);
+
+        let mut ai = AiState { target_id: Some(target_id), ..Default::default() };
+        let bot_ref = state.get_player(bot_id).unwrap();
+        execute_chase(&mut ai, bot_ref, &state);
+        assert!(!ai.wants_boost, "A chase shouldn't spend the reserve");
+
+        execute_flee(&mut ai, bot_ref, &state);
+        assert!(ai.wants_boost);
+
+        state.get_player_mut(bot_id).unwrap().fuel = 0.0;
+        execute_flee(&mut ai, state.get_player(bot_id).unwrap(), &state);
+        assert!(!ai.wants_boost);
+    }
+
     #[test]
     fn test_decision_timer() {
         let mut state = create_test_state();
@@ -6,6 +6,7 @@
 //! - Behavior batching for branch-free processing
 //! - Dormancy system for distant bot optimization
 //! - Zone-based approximate queries
+//! - Cold storage that takes far-off dormant bots out of the hot arrays
 //! - **Adaptive dormancy** that adjusts based on game health metrics
 //!
 //! # Environment Variables
@@ -18,11 +19,18 @@
 //! - `AI_SOA_ZONE_QUERIES_ENABLED` - Enable/disable zone-based queries (default: true)
 //! - `AI_SOA_BEHAVIOR_BATCHING_ENABLED` - Enable/disable behavior batching (default: true)
 //! - `AI_SOA_PARALLEL_ENABLED` - Enable/disable parallel processing (default: true)
+//! - `AI_SOA_FLOW_FIELD_ENABLED` - Steer Reduced/Dormant Collect and Flee bots from a shared
+//!   per-zone flow field (default: true, requires zone queries)
 //!
 //! ## LOD Distance Thresholds (base values, adjusted dynamically if adaptive)
 //! - `AI_SOA_LOD_FULL_RADIUS` - Distance for full AI updates (default: 500.0)
 //! - `AI_SOA_LOD_REDUCED_RADIUS` - Distance for reduced updates (default: 2000.0)
 //! - `AI_SOA_LOD_DORMANT_RADIUS` - Distance for dormant mode (default: 5000.0)
+//! - `AI_SOA_OFFSCREEN_LOD_SCALE` - Radius multiplier for bots in zones no human viewport
+//!   covers, 0.1-1.0 (default: 0.5, 1.0 = off). Bots in watched zones always run Full.
+//!   Only applies once viewports are supplied and zone queries are enabled.
+//! - `AI_SOA_COLD_RADIUS` - Zones further than this from every human move their Dormant bots
+//!   into cold storage (default: 15000.0, 0 = off). Needs dormancy, zone queries and a human.
 //!
 //! ## Adaptive Dormancy Configuration
 //! - `AI_SOA_TARGET_TICK_MS` - Target tick duration in ms (default: 30.0 for 30Hz)
@@ -31,6 +39,10 @@
 //! - `AI_SOA_MIN_LOD_SCALE` - Minimum LOD scale factor (default: 0.25)
 //! - `AI_SOA_MAX_LOD_SCALE` - Maximum LOD scale factor (default: 2.0)
 //!
+//! ## Flocking
+//! - `AI_SOA_FLOCKING_WEIGHT` - Blend of zone flocking (separation/alignment/cohesion) into
+//!   bot thrust, 0.0-1.0 (default: 0.0 = disabled). Full-LOD bots in combat never flock.
+//!
 //! ## Update Intervals
 //! - `AI_SOA_REDUCED_UPDATE_INTERVAL` - Ticks between reduced mode updates (default: 4)
 //! - `AI_SOA_DORMANT_UPDATE_INTERVAL` - Ticks between dormant mode updates (default: 8)
@@ -40,20 +52,33 @@
 //!
 //! ## Decision Making
 //! - `AI_SOA_DECISION_INTERVAL` - Seconds between AI decisions (default: 0.5)
+//! - `AI_SOA_BEHAVIOR_TREE` - Path to a JSON behavior tree for Full-LOD bots (default: unset = hardcoded)
+//!
+//! ## Learned Policy (elite bots)
+//! - `AI_SOA_POLICY_PATH` - Path to a JSON MLP policy (default: unset = no elite bots)
+//! - `AI_SOA_ELITE_FRACTION` - Fraction of bots driven by the policy (default: 0.05)
 //!
 //! ## Wake-up Rate Limiting (prevents CPU spikes when humans join)
 //! - `AI_SOA_BASE_WAKEUPS_PER_TICK` - Base wake-ups per tick at reference bot count (default: 30)
 //! - `AI_SOA_WAKEUP_SCALE_REFERENCE` - Reference bot count for scaling formula (default: 500)
 
 use bitvec::prelude::*;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use rand::Rng;
 use rayon::prelude::*;
-use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 
 use crate::game::constants::ai::*;
-use crate::game::state::{GameState, PlayerId, WellId};
+use crate::game::state::{GameState, Player, PlayerId, WellId};
+use crate::game::systems::behavior_tree::{BehaviorTree, BtContext};
+use crate::game::systems::bot_policy::{self, MlpPolicy, ACT_DIM, OBS_DIM};
+use crate::game::systems::cold_storage::{BotPersonality, ColdBot, ColdStorage};
+use crate::game::systems::factions::{faction_order, FactionOrder, MAX_FACTIONS};
+use crate::game::systems::flow_field::FlowField;
 use crate::net::protocol::PlayerInput;
+use crate::util::arena::{ArenaVec, TickArena};
 use crate::util::vec2::Vec2;
 
 // ============================================================================
@@ -68,6 +93,18 @@ pub const DEFAULT_LOD_FULL_RADIUS: f32 = 500.0;
 pub const DEFAULT_LOD_REDUCED_RADIUS: f32 = 2000.0;
 pub const DEFAULT_LOD_DORMANT_RADIUS: f32 = 5000.0;
 
+/// LOD radius multiplier for zones off every human's screen
+pub const DEFAULT_OFFSCREEN_LOD_SCALE: f32 = 0.5;
+
+/// Zone distance from every human beyond which dormant bots are frozen
+pub const DEFAULT_COLD_RADIUS: f32 = 15000.0;
+
+/// Ticks between freeze/thaw passes over the zones
+const COLD_STORAGE_INTERVAL_TICKS: u32 = 30;
+
+/// Ticks between re-bucketing frozen bots by their drifted positions
+const COLD_REBUCKET_INTERVAL_TICKS: u32 = 90;
+
 /// Update frequency for reduced mode (every N ticks)
 pub const DEFAULT_REDUCED_UPDATE_INTERVAL: u32 = 4;
 
@@ -102,6 +139,45 @@ pub const DEFAULT_MAX_LOD_SCALE: f32 = 2.0;
 /// Health metric history size for averaging
 pub const HEALTH_HISTORY_SIZE: usize = 30;
 
+// ============================================================================
+// Flocking Constants
+// ============================================================================
+
+/// Pull toward the zone's average heading
+const FLOCK_ALIGNMENT: f32 = 1.0;
+
+/// Pull toward the zone's bot centroid (full strength at `FLOCK_COHESION_RADIUS`)
+const FLOCK_COHESION: f32 = 0.6;
+
+/// Push away from the centroid inside `FLOCK_SEPARATION_RADIUS`
+const FLOCK_SEPARATION: f32 = 1.0;
+
+/// Distance from the centroid at which cohesion reaches full strength
+const FLOCK_COHESION_RADIUS: f32 = 1000.0;
+
+/// Bots closer than this to the centroid are pushed outward, so swarms don't collapse
+const FLOCK_SEPARATION_RADIUS: f32 = 150.0;
+
+// ============================================================================
+// Asteroid Avoidance
+// ============================================================================
+
+/// Distance beyond an asteroid's surface (and the bot's own radius) at which bots start steering away
+const ASTEROID_AVOID_MARGIN: f32 = 120.0;
+
+/// Push away from an asteroid at contact (fades to 0 at the margin)
+const ASTEROID_AVOID_STRENGTH: f32 = 1.5;
+
+// ============================================================================
+// Gentle Personality (matches with tutorial players)
+// ============================================================================
+
+/// Aggression of softened bots (random personalities draw 0.2-0.8)
+const GENTLE_AGGRESSION: f32 = 0.05;
+
+/// Aim accuracy of softened bots (random personalities draw 0.5-0.9)
+const GENTLE_ACCURACY: f32 = 0.2;
+
 // ============================================================================
 // Runtime Configuration (loaded from ENV vars)
 // ============================================================================
@@ -123,6 +199,10 @@ pub struct AiSoaConfig {
     pub behavior_batching_enabled: bool,
     /// Enable parallel processing via Rayon
     pub parallel_enabled: bool,
+    /// Steer low-LOD Collect/Flee bots from the shared per-zone flow field
+    pub flow_field_enabled: bool,
+    /// Blend of zone flocking into bot thrust (0.0 = disabled)
+    pub flocking_weight: f32,
 
     // LOD distance thresholds (base values, scaled by adaptive system)
     /// Base distance from human for full AI updates (every tick)
@@ -131,6 +211,10 @@ pub struct AiSoaConfig {
     pub lod_reduced_radius: f32,
     /// Base distance from human for dormant mode
     pub lod_dormant_radius: f32,
+    /// Radius multiplier for bots in zones no human is looking at (1.0 = off)
+    pub offscreen_lod_scale: f32,
+    /// Zone distance from every human beyond which dormant bots are frozen (0 = off)
+    pub cold_radius: f32,
 
     // Adaptive dormancy settings
     /// Target tick duration in milliseconds
@@ -159,6 +243,14 @@ pub struct AiSoaConfig {
     pub decision_interval: f32,
     /// Cache refresh interval for nearest well (seconds)
     pub well_cache_refresh_interval: f32,
+    /// Optional JSON behavior tree for Full-LOD bot decisions
+    pub behavior_tree_path: Option<String>,
+
+    // Learned policy
+    /// Optional JSON MLP policy for elite bots
+    pub policy_path: Option<String>,
+    /// Fraction of bots marked elite at registration (0.0-1.0)
+    pub elite_fraction: f32,
 
     // Wake-up rate limiting (prevents CPU spikes when humans join)
     /// Base wake-ups per tick at reference bot count (scales linearly with bot count)
@@ -176,11 +268,15 @@ impl Default for AiSoaConfig {
             zone_queries_enabled: true,
             behavior_batching_enabled: true,
             parallel_enabled: true,
+            flow_field_enabled: true,
+            flocking_weight: 0.0,
 
             // LOD thresholds (base values)
             lod_full_radius: DEFAULT_LOD_FULL_RADIUS,
             lod_reduced_radius: DEFAULT_LOD_REDUCED_RADIUS,
             lod_dormant_radius: DEFAULT_LOD_DORMANT_RADIUS,
+            offscreen_lod_scale: DEFAULT_OFFSCREEN_LOD_SCALE,
+            cold_radius: DEFAULT_COLD_RADIUS,
 
             // Adaptive dormancy
             target_tick_ms: DEFAULT_TARGET_TICK_MS,
@@ -199,6 +295,11 @@ impl Default for AiSoaConfig {
             // Decision making
             decision_interval: DEFAULT_DECISION_INTERVAL_SOA,
             well_cache_refresh_interval: DEFAULT_WELL_CACHE_REFRESH_INTERVAL,
+            behavior_tree_path: None,
+
+            // Learned policy
+            policy_path: None,
+            elite_fraction: 0.05,
 
             // Wake-up rate limiting
             base_wakeups_per_tick: 30,    // Base wake-ups at 500 bots
@@ -228,6 +329,12 @@ impl AiSoaConfig {
         if let Ok(val) = std::env::var("AI_SOA_PARALLEL_ENABLED") {
             config.parallel_enabled = val.parse().unwrap_or(true);
         }
+        if let Ok(val) = std::env::var("AI_SOA_FLOW_FIELD_ENABLED") {
+            config.flow_field_enabled = val.parse().unwrap_or(true);
+        }
+        if let Ok(val) = std::env::var("AI_SOA_FLOCKING_WEIGHT") {
+            config.flocking_weight = val.parse().unwrap_or(0.0_f32).clamp(0.0, 1.0);
+        }
 
         // LOD thresholds (base values)
         if let Ok(val) = std::env::var("AI_SOA_LOD_FULL_RADIUS") {
@@ -239,6 +346,12 @@ impl AiSoaConfig {
         if let Ok(val) = std::env::var("AI_SOA_LOD_DORMANT_RADIUS") {
             config.lod_dormant_radius = val.parse().unwrap_or(DEFAULT_LOD_DORMANT_RADIUS);
         }
+        if let Ok(val) = std::env::var("AI_SOA_OFFSCREEN_LOD_SCALE") {
+            config.offscreen_lod_scale = val.parse().unwrap_or(DEFAULT_OFFSCREEN_LOD_SCALE).clamp(0.1, 1.0);
+        }
+        if let Ok(val) = std::env::var("AI_SOA_COLD_RADIUS") {
+            config.cold_radius = val.parse().unwrap_or(DEFAULT_COLD_RADIUS).max(0.0);
+        }
 
         // Adaptive dormancy settings
         if let Ok(val) = std::env::var("AI_SOA_TARGET_TICK_MS") {
@@ -277,6 +390,25 @@ impl AiSoaConfig {
         if let Ok(val) = std::env::var("AI_SOA_WELL_CACHE_REFRESH_INTERVAL") {
             config.well_cache_refresh_interval = val.parse().unwrap_or(DEFAULT_WELL_CACHE_REFRESH_INTERVAL);
         }
+        if let Ok(val) = std::env::var("AI_SOA_BEHAVIOR_TREE") {
+            if !val.is_empty() {
+                config.behavior_tree_path = Some(val);
+            }
+        }
+
+        // Learned policy
+        if let Ok(val) = std::env::var("AI_SOA_POLICY_PATH") {
+            if !val.is_empty() {
+                config.policy_path = Some(val);
+            }
+        }
+        if let Ok(val) = std::env::var("AI_SOA_ELITE_FRACTION") {
+            if let Ok(parsed) = val.parse::<f32>() {
+                if (0.0..=1.0).contains(&parsed) {
+                    config.elite_fraction = parsed;
+                }
+            }
+        }
 
         // Wake-up rate limiting
         if let Ok(val) = std::env::var("AI_SOA_BASE_WAKEUPS_PER_TICK") {
@@ -304,6 +436,8 @@ impl AiSoaConfig {
             lod_full = config.lod_full_radius,
             lod_reduced = config.lod_reduced_radius,
             lod_dormant = config.lod_dormant_radius,
+            offscreen_lod_scale = config.offscreen_lod_scale,
+            cold_radius = config.cold_radius,
             target_tick_ms = config.target_tick_ms,
             base_wakeups = config.base_wakeups_per_tick,
             wakeup_ref = config.wakeup_scale_reference,
@@ -615,7 +749,7 @@ pub struct AdaptiveDormancyStats {
 // ============================================================================
 
 /// AI behavior mode (1 byte for SoA efficiency)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum AiBehavior {
     #[default]
@@ -647,8 +781,11 @@ pub struct ZoneData {
     pub bot_count: u32,
     pub total_mass: f32,
     pub velocity_sum: Vec2,
+    pub position_sum: Vec2,
     pub threat_mass: f32, // Mass of threatening entities in zone
     pub has_human: bool,
+    /// Hot bots per faction (index = faction id - 1)
+    pub faction_bots: [u32; MAX_FACTIONS],
 }
 
 impl ZoneData {
@@ -661,6 +798,21 @@ impl ZoneData {
         }
     }
 
+    /// Faction with the most bots here, the lowest id on a tie
+    pub fn dominant_faction(&self) -> Option<u8> {
+        let (index, &count) = self.faction_bots.iter().enumerate().rev().max_by_key(|&(_, &count)| count)?;
+        (count > 0).then_some(index as u8 + 1)
+    }
+
+    #[inline]
+    pub fn centroid(&self) -> Vec2 {
+        if self.bot_count > 0 {
+            self.position_sum * (1.0 / self.bot_count as f32)
+        } else {
+            self.center
+        }
+    }
+
     #[inline]
     pub fn threat_level(&self) -> f32 {
         if self.total_mass > 0.0 {
@@ -677,6 +829,8 @@ pub struct ZoneGrid {
     cell_size: f32,
     inv_cell_size: f32,
     zones: HashMap<(i32, i32), ZoneData>,
+    /// Cells overlapping some human's viewport (rebuilt every update)
+    watched: HashSet<(i32, i32)>,
 }
 
 impl ZoneGrid {
@@ -685,6 +839,7 @@ impl ZoneGrid {
             cell_size,
             inv_cell_size: 1.0 / cell_size,
             zones: HashMap::with_capacity(256),
+            watched: HashSet::new(),
         }
     }
 
@@ -704,6 +859,15 @@ impl ZoneGrid {
         )
     }
 
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// All zones seen so far (counts are from the last update)
+    pub fn zones(&self) -> impl Iterator<Item = &ZoneData> {
+        self.zones.values()
+    }
+
     pub fn get_zone(&self, cell: (i32, i32)) -> Option<&ZoneData> {
         self.zones.get(&cell)
     }
@@ -721,11 +885,35 @@ impl ZoneGrid {
             zone.bot_count = 0;
             zone.total_mass = 0.0;
             zone.velocity_sum = Vec2::ZERO;
+            zone.position_sum = Vec2::ZERO;
             zone.threat_mass = 0.0;
             zone.has_human = false;
+            zone.faction_bots = [0; MAX_FACTIONS];
+        }
+        self.watched.clear();
+    }
+
+    /// Mark every cell overlapping the square around a viewport as watched
+    pub fn mark_watched(&mut self, center: Vec2, radius: f32) {
+        let (min_x, min_y) = self.position_to_cell(center - Vec2::new(radius, radius));
+        let (max_x, max_y) = self.position_to_cell(center + Vec2::new(radius, radius));
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.watched.insert((x, y));
+            }
         }
     }
 
+    /// Whether a cell is on some human's screen
+    #[inline]
+    pub fn is_watched(&self, cell: (i32, i32)) -> bool {
+        self.watched.contains(&cell)
+    }
+
+    pub fn watched_count(&self) -> usize {
+        self.watched.len()
+    }
+
     /// Get adjacent zone cells (3x3 neighborhood)
     pub fn adjacent_cells(&self, cell: (i32, i32)) -> impl Iterator<Item = (i32, i32)> {
         let (cx, cy) = cell;
@@ -822,6 +1010,12 @@ pub struct AiManagerSoA {
     pub accuracy: Vec<f32>,
     pub reaction_variance: Vec<f32>,
 
+    // === Difficulty (1.0 = personality as drawn) ===
+    /// Aggression multiplier, set by the difficulty controller
+    pub aggression_scale: Vec<f32>,
+    /// Accuracy multiplier, set by the difficulty controller
+    pub accuracy_scale: Vec<f32>,
+
     // === Caching ===
     /// Cached nearest well ID for orbit behavior
     pub cached_well_ids: Vec<Option<WellId>>,
@@ -833,16 +1027,41 @@ pub struct AiManagerSoA {
     pub update_modes: Vec<UpdateMode>,
     /// Active mask (1 = should update this tick)
     pub active_mask: BitVec,
+    /// Elite mask (1 = actions come from the learned policy when loaded)
+    pub is_elite: BitVec,
     /// Adaptive dormancy controller (adjusts thresholds based on health)
     pub adaptive: AdaptiveDormancy,
 
     // === Hierarchical Spatial ===
     /// Zone grid for aggregate queries
     pub zone_grid: ZoneGrid,
+    /// Per-zone steering targets shared by low-LOD bots
+    pub flow_field: FlowField,
+    /// Center and radius of each human viewport, set before every update
+    human_views: Vec<(Vec2, f32)>,
+    /// Far-off dormant bots taken out of the hot arrays
+    pub cold_storage: ColdStorage,
+    /// Orders per faction (index = faction id - 1), set by the faction controller
+    pub faction_orders: Vec<Option<FactionOrder>>,
+    /// Alive faction bots with their position and faction, refreshed before decisions
+    faction_bots: Vec<(PlayerId, Vec2, u8)>,
 
     // === Behavior Batches ===
     pub batches: BehaviorBatches,
 
+    // === Data-Driven Decisions ===
+    /// Optional behavior tree for Full-LOD bots (None = hardcoded switch)
+    pub behavior_tree: Option<Arc<BehaviorTree>>,
+    /// Optional learned policy for elite bots (None = all bots scripted)
+    pub policy: Option<Arc<MlpPolicy>>,
+    /// Reused observation/action buffers for batched inference
+    policy_obs: Vec<f32>,
+    policy_actions: Vec<f32>,
+    /// Scratch space for per-batch results, reset every update
+    arena: TickArena,
+    /// Cost of each behavior batch in the last update
+    batch_timings: BatchTimings,
+
     // === Tick Counter ===
     pub tick_counter: u32,
 }
@@ -878,15 +1097,30 @@ impl AiManagerSoA {
             accuracy: Vec::with_capacity(capacity),
             reaction_variance: Vec::with_capacity(capacity),
 
+            aggression_scale: Vec::with_capacity(capacity),
+            accuracy_scale: Vec::with_capacity(capacity),
+
             cached_well_ids: Vec::with_capacity(capacity),
             well_cache_timers: Vec::with_capacity(capacity),
 
             update_modes: Vec::with_capacity(capacity),
             active_mask: BitVec::with_capacity(capacity),
+            is_elite: BitVec::with_capacity(capacity),
             adaptive: AdaptiveDormancy::new(),
 
             zone_grid: ZoneGrid::default(),
+            flow_field: FlowField::default(),
+            human_views: Vec::new(),
+            cold_storage: ColdStorage::default(),
+            faction_orders: Vec::new(),
+            faction_bots: Vec::new(),
             batches: BehaviorBatches::default(),
+            behavior_tree: BehaviorTree::global(),
+            policy: MlpPolicy::global(),
+            policy_obs: Vec::new(),
+            policy_actions: Vec::new(),
+            arena: TickArena::for_ai(),
+            batch_timings: BatchTimings::default(),
             tick_counter: 0,
         }
     }
@@ -916,19 +1150,37 @@ impl AiManagerSoA {
         ((base_max as f32) * health_factor).max(5.0) as usize
     }
 
-    /// Register a new bot
+    /// Register a new bot (a frozen one is materialized instead)
     pub fn register_bot(&mut self, player_id: PlayerId) {
         if self.id_to_index.contains_key(&player_id) {
             return;
         }
+        if let Some(bot) = self.cold_storage.remove(player_id) {
+            self.push_bot(player_id, bot.unpack(), UpdateMode::Dormant);
+            return;
+        }
+
+        // Initialize with random personality
+        let mut rng = rand::thread_rng();
+        let personality = BotPersonality {
+            aggression: rng.gen_range(0.2..0.8),
+            preferred_radius: rng.gen_range(250.0..400.0),
+            accuracy: rng.gen_range(0.5..0.9),
+            reaction_variance: rng.gen_range(0.1..0.5),
+            aggression_scale: 1.0,
+            accuracy_scale: 1.0,
+            is_elite: rng.gen::<f32>() < AiSoaConfig::global().elite_fraction,
+        };
+        self.push_bot(player_id, personality, UpdateMode::Full);
+    }
 
+    /// Append a bot to the hot arrays with fresh decision state
+    fn push_bot(&mut self, player_id: PlayerId, personality: BotPersonality, mode: UpdateMode) {
         let index = self.count as u32;
         self.id_to_index.insert(player_id, index);
         self.bot_ids.push(player_id);
         self.count += 1;
 
-        // Initialize with random personality
-        let mut rng = rand::thread_rng();
         let config = AiSoaConfig::global();
 
         self.behaviors.push(AiBehavior::Idle);
@@ -947,21 +1199,58 @@ impl AiManagerSoA {
 
         self.target_ids.push(None);
 
-        self.aggression.push(rng.gen_range(0.2..0.8));
-        self.preferred_radius.push(rng.gen_range(250.0..400.0));
-        self.accuracy.push(rng.gen_range(0.5..0.9));
-        self.reaction_variance.push(rng.gen_range(0.1..0.5));
+        self.aggression.push(personality.aggression);
+        self.preferred_radius.push(personality.preferred_radius);
+        self.accuracy.push(personality.accuracy);
+        self.reaction_variance.push(personality.reaction_variance);
+
+        self.aggression_scale.push(personality.aggression_scale);
+        self.accuracy_scale.push(personality.accuracy_scale);
 
         self.cached_well_ids.push(None);
         self.well_cache_timers.push(0.0);
 
-        self.update_modes.push(UpdateMode::Full);
-        self.active_mask.push(true);
+        self.update_modes.push(mode);
+        self.active_mask.push(mode == UpdateMode::Full);
+        self.is_elite.push(personality.is_elite);
+    }
+
+    /// Personality and difficulty of the bot at `idx`
+    fn personality(&self, idx: usize) -> BotPersonality {
+        BotPersonality {
+            aggression: self.aggression[idx],
+            preferred_radius: self.preferred_radius[idx],
+            accuracy: self.accuracy[idx],
+            reaction_variance: self.reaction_variance[idx],
+            aggression_scale: self.aggression_scale[idx],
+            accuracy_scale: self.accuracy_scale[idx],
+            is_elite: self.is_elite[idx],
+        }
+    }
+
+    /// Give every registered bot a gentle personality: rarely hunts, aims
+    /// loosely and is never elite
+    pub fn soften_all(&mut self) {
+        for i in 0..self.count {
+            self.aggression[i] = GENTLE_AGGRESSION;
+            self.accuracy[i] = GENTLE_ACCURACY;
+            self.is_elite.set(i, false);
+        }
+        for bot in self.cold_storage.bots_mut() {
+            let personality = BotPersonality {
+                aggression: GENTLE_AGGRESSION,
+                accuracy: GENTLE_ACCURACY,
+                is_elite: false,
+                ..bot.unpack()
+            };
+            bot.repack(&personality);
+        }
     }
 
     /// Unregister a bot (swap-remove for O(1))
     pub fn unregister_bot(&mut self, player_id: PlayerId) {
         let Some(&index) = self.id_to_index.get(&player_id) else {
+            self.cold_storage.remove(player_id);
             return;
         };
         let idx = index as usize;
@@ -985,6 +1274,8 @@ impl AiManagerSoA {
             self.preferred_radius.swap(idx, last_idx);
             self.accuracy.swap(idx, last_idx);
             self.reaction_variance.swap(idx, last_idx);
+            self.aggression_scale.swap(idx, last_idx);
+            self.accuracy_scale.swap(idx, last_idx);
             self.cached_well_ids.swap(idx, last_idx);
             self.well_cache_timers.swap(idx, last_idx);
             self.update_modes.swap(idx, last_idx);
@@ -1004,6 +1295,11 @@ impl AiManagerSoA {
             let curr_active = self.active_mask.get(idx).map(|b| *b).unwrap_or(false);
             self.active_mask.set(idx, last_active);
             self.active_mask.set(last_idx, curr_active);
+
+            let last_elite = self.is_elite.get(last_idx).map(|b| *b).unwrap_or(false);
+            let curr_elite = self.is_elite.get(idx).map(|b| *b).unwrap_or(false);
+            self.is_elite.set(idx, last_elite);
+            self.is_elite.set(last_idx, curr_elite);
         }
 
         // Remove last element
@@ -1023,14 +1319,29 @@ impl AiManagerSoA {
         self.preferred_radius.pop();
         self.accuracy.pop();
         self.reaction_variance.pop();
+        self.aggression_scale.pop();
+        self.accuracy_scale.pop();
         self.cached_well_ids.pop();
         self.well_cache_timers.pop();
         self.update_modes.pop();
         self.active_mask.pop();
+        self.is_elite.pop();
 
         self.count -= 1;
     }
 
+    /// Aggression of the bot at `idx` after its difficulty multiplier
+    #[inline]
+    pub fn effective_aggression(&self, idx: usize) -> f32 {
+        (self.aggression[idx] * self.aggression_scale[idx]).clamp(0.0, 1.0)
+    }
+
+    /// Accuracy of the bot at `idx` after its difficulty multiplier
+    #[inline]
+    pub fn effective_accuracy(&self, idx: usize) -> f32 {
+        (self.accuracy[idx] * self.accuracy_scale[idx]).clamp(0.0, 1.0)
+    }
+
     /// Get dense index for a player ID
     #[inline]
     pub fn get_index(&self, player_id: PlayerId) -> Option<u32> {
@@ -1056,6 +1367,10 @@ impl AiManagerSoA {
             zone.bot_count += 1;
             zone.total_mass += player.mass;
             zone.velocity_sum = zone.velocity_sum + player.velocity;
+            zone.position_sum += player.position;
+            if let Some(count) = zone.faction_bots.get_mut((player.faction as usize).wrapping_sub(1)) {
+                *count += 1;
+            }
         }
 
         // Mark zones with human players
@@ -1067,9 +1382,92 @@ impl AiManagerSoA {
                 zone.threat_mass += player.mass;
             }
         }
+
+        // Frozen bots count through their zone aggregates
+        for (&cell, cold) in self.cold_storage.zones() {
+            let zone = self.zone_grid.get_or_create_zone(cell);
+            zone.bot_count += cold.bots.len() as u32;
+            zone.total_mass += cold.total_mass;
+            zone.position_sum += cold.position_sum;
+        }
+
+        for &(center, radius) in &self.human_views {
+            self.zone_grid.mark_watched(center, radius);
+        }
+    }
+
+    /// Freeze Dormant bots in zones beyond the cold radius from every human and
+    /// materialize frozen zones that a human came within the radius of (or is
+    /// looking at). Freezing waits for an extra zone width of distance, so a
+    /// human moving along the edge doesn't churn bots in and out. Nothing is
+    /// frozen while no human is playing.
+    pub fn update_cold_storage(&mut self, state: &GameState) {
+        let config = AiSoaConfig::global();
+        if self.tick_counter % COLD_REBUCKET_INTERVAL_TICKS == 0 && !self.cold_storage.is_empty() {
+            self.cold_storage.rebucket(&self.zone_grid, state);
+        }
+
+        let humans: Vec<Vec2> = state.players.values().filter(|p| !p.is_bot && p.alive).map(|p| p.position).collect();
+        let nearest_sq = |grid: &ZoneGrid, cell: (i32, i32)| {
+            let center = grid.cell_center(cell);
+            humans.iter().map(|h| h.distance_sq_to(center)).fold(f32::MAX, f32::min)
+        };
+
+        let thaw_sq = config.cold_radius * config.cold_radius;
+        let grid = &self.zone_grid;
+        let thawing: Vec<(i32, i32)> = self
+            .cold_storage
+            .zones()
+            .map(|(&cell, _)| cell)
+            .filter(|&cell| grid.is_watched(cell) || nearest_sq(grid, cell) <= thaw_sq)
+            .collect();
+        for cell in thawing {
+            for bot in self.cold_storage.take_zone(cell) {
+                self.push_bot(bot.player_id, bot.unpack(), UpdateMode::Dormant);
+            }
+        }
+
+        if config.cold_radius <= 0.0 || humans.is_empty() {
+            return;
+        }
+        let grid = &self.zone_grid;
+        let freeze_radius = config.cold_radius + grid.cell_size();
+        let freeze_sq = freeze_radius * freeze_radius;
+        let mut far_zones: HashMap<(i32, i32), bool> = HashMap::new();
+        let freezing: Vec<(usize, (i32, i32))> = (0..self.count)
+            .filter(|&i| self.update_modes[i] == UpdateMode::Dormant)
+            .filter_map(|i| {
+                let player = state.get_player(self.bot_ids[i])?;
+                let cell = grid.position_to_cell(player.position);
+                let far = *far_zones
+                    .entry(cell)
+                    .or_insert_with(|| !grid.is_watched(cell) && nearest_sq(grid, cell) > freeze_sq);
+                far.then_some((i, cell))
+            })
+            .collect();
+
+        // Highest index first, so swap-removes don't move bots still to freeze
+        for &(i, cell) in freezing.iter().rev() {
+            let player_id = self.bot_ids[i];
+            let Some(player) = state.get_player(player_id) else {
+                continue;
+            };
+            let bot = ColdBot::pack(player_id, &self.personality(i));
+            let mass = if player.alive { player.mass } else { 0.0 };
+            let position = player.position;
+            self.unregister_bot(player_id);
+            self.cold_storage.insert(cell, bot, mass, position);
+        }
+    }
+
+    /// Set the viewports humans are looking through (center and view radius),
+    /// used to keep the zones they cover at full detail. Empty = scale by
+    /// distance alone.
+    pub fn set_human_views(&mut self, views: Vec<(Vec2, f32)>) {
+        self.human_views = views;
     }
 
-    /// Update dormancy based on distance to human players
+    /// Update dormancy based on distance to human players and which zones they're watching
     /// Respects AI_SOA_DORMANCY_ENABLED env var - when disabled, all bots update every tick
     /// Uses adaptive thresholds when AI_SOA_ADAPTIVE_DORMANCY is enabled
     /// OPTIMIZED: Uses parallel processing for bot distance calculations
@@ -1121,6 +1519,38 @@ impl AiManagerSoA {
         let reduced_interval = config.reduced_update_interval;
         let dormant_interval = config.dormant_update_interval;
 
+        // Zones on some human's screen keep full detail; off-screen zones shrink the radii
+        let attention = config.zone_queries_enabled && !self.human_views.is_empty();
+        let offscreen_sq = config.offscreen_lod_scale * config.offscreen_lod_scale;
+        let zone_grid = &self.zone_grid;
+        let lod_mode = |position: Vec2| {
+            let (full_sq, reduced_sq) = if !attention {
+                (full_radius_sq, reduced_radius_sq)
+            } else if zone_grid.is_watched(zone_grid.position_to_cell(position)) {
+                return UpdateMode::Full;
+            } else {
+                (full_radius_sq * offscreen_sq, reduced_radius_sq * offscreen_sq)
+            };
+
+            // Find minimum squared distance to any human (avoid sqrt)
+            let min_dist_sq = human_positions
+                .iter()
+                .map(|&h| {
+                    let dx = position.x - h.x;
+                    let dy = position.y - h.y;
+                    dx * dx + dy * dy
+                })
+                .fold(f32::MAX, |a, b| a.min(b));
+
+            if min_dist_sq < full_sq {
+                UpdateMode::Full
+            } else if min_dist_sq < reduced_sq {
+                UpdateMode::Reduced
+            } else {
+                UpdateMode::Dormant
+            }
+        };
+
         // Snapshot current modes to detect wake-ups (Dormant → Full/Reduced)
         let current_modes: Vec<UpdateMode> = self.update_modes.clone();
 
@@ -1136,24 +1566,7 @@ impl AiManagerSoA {
                         return Some((i, UpdateMode::Dormant, false));
                     }
 
-                    // Find minimum squared distance to any human (avoid sqrt)
-                    let min_dist_sq = human_positions
-                        .iter()
-                        .map(|&h| {
-                            let dx = player.position.x - h.x;
-                            let dy = player.position.y - h.y;
-                            dx * dx + dy * dy
-                        })
-                        .fold(f32::MAX, |a, b| a.min(b));
-
-                    // Determine update mode based on squared distance
-                    let mode = if min_dist_sq < full_radius_sq {
-                        UpdateMode::Full
-                    } else if min_dist_sq < reduced_radius_sq {
-                        UpdateMode::Reduced
-                    } else {
-                        UpdateMode::Dormant
-                    };
+                    let mode = lod_mode(player.position);
 
                     // Stagger updates by bot index to distribute load evenly across ticks
                     let should_update = match mode {
@@ -1204,23 +1617,7 @@ impl AiManagerSoA {
                     continue;
                 }
 
-                // Find minimum squared distance (avoid sqrt)
-                let min_dist_sq = human_positions
-                    .iter()
-                    .map(|&h| {
-                        let dx = player.position.x - h.x;
-                        let dy = player.position.y - h.y;
-                        dx * dx + dy * dy
-                    })
-                    .fold(f32::MAX, |a, b| a.min(b));
-
-                let new_mode = if min_dist_sq < full_radius_sq {
-                    UpdateMode::Full
-                } else if min_dist_sq < reduced_radius_sq {
-                    UpdateMode::Reduced
-                } else {
-                    UpdateMode::Dormant
-                };
+                let new_mode = lod_mode(player.position);
 
                 // Rate-limit wake-ups (Dormant → Full/Reduced)
                 let is_wakeup =
@@ -1272,35 +1669,63 @@ impl AiManagerSoA {
     pub fn update(&mut self, state: &GameState, dt: f32, performance_status: u64) {
         let config = AiSoaConfig::global();
         self.tick_counter = self.tick_counter.wrapping_add(1);
+        self.arena.reset();
 
         // Update zones (for aggregate queries) - skip if zone queries disabled
         if config.zone_queries_enabled {
             self.update_zones(state);
+            if config.flow_field_enabled {
+                self.flow_field.rebuild(&self.zone_grid, state.debris.iter().map(|d| d.position));
+            }
         }
 
         // Update dormancy (skip if dormancy disabled - handled in update_dormancy)
         self.update_dormancy(state, performance_status);
+        if config.dormancy_enabled
+            && config.zone_queries_enabled
+            && self.tick_counter % COLD_STORAGE_INTERVAL_TICKS == 0
+        {
+            self.update_cold_storage(state);
+        }
 
         // Rebuild behavior batches (skip if batching disabled)
         if config.behavior_batching_enabled {
             self.batches.rebuild(&self.behaviors, &self.active_mask);
 
-            // Process each behavior batch
-            self.update_orbit_batch(state, dt);
-            self.update_chase_batch(state, dt);
-            self.update_flee_batch(state, dt);
-            self.update_collect_batch(state, dt);
+            // Process each behavior batch, timing the ones that do real work
+            self.batch_timings = BatchTimings {
+                orbit: self.timed_batch(self.batches.orbit.len(), |ai| ai.update_orbit_batch(state, dt)),
+                chase: self.timed_batch(self.batches.chase.len(), |ai| ai.update_chase_batch(state, dt)),
+                flee: self.timed_batch(self.batches.flee.len(), |ai| ai.update_flee_batch(state, dt)),
+                collect: self.timed_batch(self.batches.collect.len(), |ai| ai.update_collect_batch(state, dt)),
+            };
             self.update_idle_batch(state, dt);
         } else {
             // Fallback: update all bots sequentially (for debugging/comparison)
+            self.batch_timings = BatchTimings::default();
             self.update_all_sequential(state, dt);
         }
 
+        // Swarm non-combat bots within their zones
+        if config.zone_queries_enabled && config.flocking_weight > 0.0 {
+            self.apply_flocking(state, config.flocking_weight);
+        }
+
+        // Asteroids are soft obstacles: steer around them rather than into them
+        if !state.asteroids.is_empty() {
+            self.avoid_asteroids(state);
+        }
+
         // Update decision timers and make new decisions
         self.update_decisions(state, dt);
 
         // Update firing for combat behaviors
         self.update_firing(state, dt);
+
+        // Elite bots: learned policy overrides scripted movement/aim/fire
+        if self.policy.is_some() {
+            self.update_elite_policy(state, dt);
+        }
     }
 
     /// Combined update with metrics (convenience method).
@@ -1384,6 +1809,33 @@ impl AiManagerSoA {
     /// Minimum batch size for parallel processing (avoids thread overhead for small batches)
     const MIN_PARALLEL_BATCH_SIZE: usize = 64;
 
+    /// Run `compute` for every bot in a batch, collecting into an arena slice
+    /// (one slot per index, `None` for bots that produced no result)
+    fn batch_results<'a, T, F>(arena: &'a bumpalo::Bump, indices: &[u32], parallel: bool, compute: F) -> &'a [Option<T>]
+    where
+        T: Copy + Send,
+        F: Fn(u32) -> Option<T> + Sync,
+    {
+        let results = arena.alloc_slice_fill_copy(indices.len(), None);
+        if parallel {
+            results.par_iter_mut().zip(indices.par_iter()).for_each(|(slot, &idx)| *slot = compute(idx));
+        } else {
+            results.iter_mut().zip(indices).for_each(|(slot, &idx)| *slot = compute(idx));
+        }
+        results
+    }
+
+    /// Run one behavior batch of `size` bots and measure it
+    fn timed_batch(&mut self, size: usize, run: impl FnOnce(&mut Self)) -> BatchTiming {
+        let start = Instant::now();
+        run(self);
+        BatchTiming {
+            micros: start.elapsed().as_micros() as u64,
+            size,
+            parallel: AiSoaConfig::global().parallel_enabled && size >= Self::MIN_PARALLEL_BATCH_SIZE,
+        }
+    }
+
     /// Update all bots in orbit behavior
     /// OPTIMIZED: Pre-collects well data, uses batch threshold for parallelism
     fn update_orbit_batch(&mut self, state: &GameState, _dt: f32) {
@@ -1394,13 +1846,17 @@ impl AiManagerSoA {
 
         // OPTIMIZATION: Pre-collect well data once (avoid HashMap access in hot loop)
         // Filter out central well - bots should orbit orbital wells only
-        let wells: Vec<(Vec2, f32)> = state
-            .arena
-            .gravity_wells
-            .values()
-            .filter(|w| w.id != crate::game::state::CENTRAL_WELL_ID)
-            .map(|w| (w.position, w.core_radius))
-            .collect();
+        let arena = self.arena.bump();
+        let wells = ArenaVec::from_iter_in(
+            state
+                .arena
+                .gravity_wells
+                .values()
+                .filter(|w| w.id != crate::game::state::CENTRAL_WELL_ID)
+                .map(|w| (w.position, w.core_radius)),
+            arena,
+        )
+        .into_bump_slice();
 
         if wells.is_empty() {
             return;
@@ -1409,6 +1865,8 @@ impl AiManagerSoA {
         let config = AiSoaConfig::global();
         let use_parallel = config.parallel_enabled && indices.len() >= Self::MIN_PARALLEL_BATCH_SIZE;
 
+        let fuel = *state.modifiers.fuel();
+
         // Closure to compute orbit for a single bot
         let compute_orbit = |idx: u32| -> Option<(u32, f32, f32, bool)> {
             let i = idx as usize;
@@ -1418,17 +1876,23 @@ impl AiManagerSoA {
                 return None;
             }
 
-            // Find nearest well from pre-collected data (faster than HashMap iteration)
-            let (well_pos, core_radius) = wells
-                .iter()
-                .map(|&(pos, radius)| {
-                    let dx = pos.x - player.position.x;
-                    let dy = pos.y - player.position.y;
-                    (pos, radius, dx * dx + dy * dy)
-                })
-                .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
-                .map(|(pos, radius, _)| (pos, radius))
-                .unwrap_or((Vec2::ZERO, 50.0));
+            // Faction bots orbit their anchor well, the rest the nearest one
+            // (pre-collected, faster than HashMap iteration)
+            let anchor = faction_order(&self.faction_orders, player.faction)
+                .and_then(|order| state.arena.gravity_wells.get(&order.well))
+                .map(|w| (w.position, w.core_radius));
+            let (well_pos, core_radius) = anchor.unwrap_or_else(|| {
+                wells
+                    .iter()
+                    .map(|&(pos, radius)| {
+                        let dx = pos.x - player.position.x;
+                        let dy = pos.y - player.position.y;
+                        (pos, radius, dx * dx + dy * dy)
+                    })
+                    .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+                    .map(|(pos, radius, _)| (pos, radius))
+                    .unwrap_or((Vec2::ZERO, 50.0))
+            });
 
             let to_well = well_pos - player.position;
             let current_radius = to_well.length();
@@ -1457,20 +1921,17 @@ impl AiManagerSoA {
 
             let thrust = (tangent + radial).normalize();
             let orbital_vel = crate::game::systems::gravity::orbital_velocity(current_radius);
-            let boost = player.velocity.length() < orbital_vel * 0.6;
+            // Topping up orbital speed isn't worth the fuel reserve
+            let boost = player.velocity.length() < orbital_vel * 0.6 && fuel.bot_may_boost(player.fuel, false);
 
             Some((idx, thrust.x, thrust.y, boost))
         };
 
         // OPTIMIZATION: Use parallel only for large batches
-        let results: Vec<(u32, f32, f32, bool)> = if use_parallel {
-            indices.par_iter().filter_map(|&idx| compute_orbit(idx)).collect()
-        } else {
-            indices.iter().filter_map(|&idx| compute_orbit(idx)).collect()
-        };
+        let results = Self::batch_results(arena, indices, use_parallel, compute_orbit);
 
         // Apply results
-        for (idx, tx, ty, boost) in results {
+        for &(idx, tx, ty, boost) in results.iter().flatten() {
             let i = idx as usize;
             self.thrust_x[i] = tx;
             self.thrust_y[i] = ty;
@@ -1489,6 +1950,7 @@ impl AiManagerSoA {
         let config = AiSoaConfig::global();
         let use_parallel = config.parallel_enabled && indices.len() >= Self::MIN_PARALLEL_BATCH_SIZE;
 
+        let fuel = *state.modifiers.fuel();
         let compute_chase = |idx: u32| -> Option<(u32, f32, f32, f32, f32, bool, bool)> {
             let i = idx as usize;
             let player_id = self.bot_ids[i];
@@ -1510,18 +1972,14 @@ impl AiManagerSoA {
             let predicted_pos = target.position + target.velocity * time_to_reach * 0.5;
 
             let chase_dir = (predicted_pos - player.position).normalize();
-            let boost = distance > 100.0;
+            let boost = distance > 100.0 && fuel.bot_may_boost(player.fuel, false);
 
             Some((idx, chase_dir.x, chase_dir.y, chase_dir.x, chase_dir.y, boost, false))
         };
 
-        let results: Vec<_> = if use_parallel {
-            indices.par_iter().filter_map(|&idx| compute_chase(idx)).collect()
-        } else {
-            indices.iter().filter_map(|&idx| compute_chase(idx)).collect()
-        };
+        let results = Self::batch_results(self.arena.bump(), indices, use_parallel, compute_chase);
 
-        for (idx, tx, ty, ax, ay, boost, to_idle) in results {
+        for &(idx, tx, ty, ax, ay, boost, to_idle) in results.iter().flatten() {
             let i = idx as usize;
             self.thrust_x[i] = tx;
             self.thrust_y[i] = ty;
@@ -1545,6 +2003,7 @@ impl AiManagerSoA {
 
         let config = AiSoaConfig::global();
         let use_parallel = config.parallel_enabled && indices.len() >= Self::MIN_PARALLEL_BATCH_SIZE;
+        let flow = config.flow_field_enabled.then_some(&self.flow_field);
 
         let compute_flee = |idx: u32| -> Option<(u32, f32, f32, f32, f32, bool)> {
             let i = idx as usize;
@@ -1560,15 +2019,18 @@ impl AiManagerSoA {
                 return Some((idx, 0.0, 0.0, 1.0, 0.0, true));
             }
 
-            let flee_dir = (player.position - threat.position).normalize();
+            // Low-LOD bots flee the dominant threat around their zone
+            let flee_dir = flow
+                .filter(|_| self.update_modes[i] != UpdateMode::Full)
+                .and_then(|f| f.sample(&self.zone_grid, player.position))
+                .and_then(|cell| cell.away_from_threat(player.position))
+                .unwrap_or_else(|| (player.position - threat.position).normalize());
 
-            // Stay in arena
-            let zone = crate::game::systems::arena::get_zone(player.position, &state.arena);
-            let adjusted = if zone == crate::game::systems::arena::Zone::Escape
-                || zone == crate::game::systems::arena::Zone::Outside
-            {
+            // Stay in arena (eased in toward the edge)
+            let edge = crate::game::systems::arena::boundary_weight(player.position, &state.arena);
+            let adjusted = if edge > 0.0 {
                 let to_center = -player.position.normalize();
-                (flee_dir + to_center).normalize()
+                (flee_dir + to_center * edge).normalize()
             } else {
                 flee_dir
             };
@@ -1576,13 +2038,9 @@ impl AiManagerSoA {
             Some((idx, adjusted.x, adjusted.y, -flee_dir.x, -flee_dir.y, true))
         };
 
-        let results: Vec<_> = if use_parallel {
-            indices.par_iter().filter_map(|&idx| compute_flee(idx)).collect()
-        } else {
-            indices.iter().filter_map(|&idx| compute_flee(idx)).collect()
-        };
+        let results = Self::batch_results(self.arena.bump(), indices, use_parallel, compute_flee);
 
-        for (idx, tx, ty, ax, ay, to_idle) in results {
+        for &(idx, tx, ty, ax, ay, to_idle) in results.iter().flatten() {
             let i = idx as usize;
             self.thrust_x[i] = tx;
             self.thrust_y[i] = ty;
@@ -1605,10 +2063,13 @@ impl AiManagerSoA {
         }
 
         // OPTIMIZATION: Pre-collect debris positions once
-        let debris_positions: Vec<Vec2> = state.debris.iter().map(|d| d.position).collect();
+        let arena = self.arena.bump();
+        let debris_positions =
+            ArenaVec::from_iter_in(state.debris.iter().map(|d| d.position), arena).into_bump_slice();
 
         let config = AiSoaConfig::global();
         let use_parallel = config.parallel_enabled && indices.len() >= Self::MIN_PARALLEL_BATCH_SIZE;
+        let flow = config.flow_field_enabled.then_some(&self.flow_field);
 
         let compute_collect = |idx: u32| -> Option<(u32, f32, f32, bool)> {
             let i = idx as usize;
@@ -1618,6 +2079,15 @@ impl AiManagerSoA {
                 return None;
             }
 
+            // Low-LOD bots head for their zone's nearest debris cluster instead of scanning all debris
+            let shared = flow
+                .filter(|_| self.update_modes[i] != UpdateMode::Full)
+                .and_then(|f| f.sample(&self.zone_grid, player.position))
+                .and_then(|cell| cell.toward_debris(player.position));
+            if let Some(dir) = shared {
+                return Some((idx, dir.x, dir.y, false));
+            }
+
             // Find nearest debris using pre-collected positions (avoid Vec access in loop)
             let nearest_pos = debris_positions
                 .iter()
@@ -1637,13 +2107,9 @@ impl AiManagerSoA {
             }
         };
 
-        let results: Vec<_> = if use_parallel {
-            indices.par_iter().filter_map(|&idx| compute_collect(idx)).collect()
-        } else {
-            indices.iter().filter_map(|&idx| compute_collect(idx)).collect()
-        };
+        let results = Self::batch_results(arena, indices, use_parallel, compute_collect);
 
-        for (idx, tx, ty, to_orbit) in results {
+        for &(idx, tx, ty, to_orbit) in results.iter().flatten() {
             let i = idx as usize;
             self.thrust_x[i] = tx;
             self.thrust_y[i] = ty;
@@ -1654,6 +2120,69 @@ impl AiManagerSoA {
         }
     }
 
+    /// Blend flocking into the thrust of active bots: align with the zone's
+    /// average heading, drift toward the zone's centroid and keep clear of it
+    /// up close. O(1) per bot from zone aggregates; no neighbour queries.
+    /// Full-LOD bots chasing or fleeing are left alone so combat stays sharp.
+    fn apply_flocking(&mut self, state: &GameState, weight: f32) {
+        for i in 0..self.count {
+            if !self.active_mask[i] {
+                continue;
+            }
+            let in_combat = matches!(self.behaviors[i], AiBehavior::Chase | AiBehavior::Flee);
+            if in_combat && self.update_modes[i] == UpdateMode::Full {
+                continue;
+            }
+            let Some(player) = state.get_player(self.bot_ids[i]).filter(|p| p.alive) else {
+                continue;
+            };
+            let Some(zone) = self.zone_grid.get_zone(self.zone_grid.position_to_cell(player.position)) else {
+                continue;
+            };
+            if zone.bot_count < 2 {
+                continue;
+            }
+
+            let (to_centroid, distance) = (zone.centroid() - player.position).normalize_with_length();
+            let alignment = zone.average_velocity().normalize() * FLOCK_ALIGNMENT;
+            let cohesion = to_centroid * (FLOCK_COHESION * (distance / FLOCK_COHESION_RADIUS).min(1.0));
+            let separation = -to_centroid * (FLOCK_SEPARATION * (1.0 - distance / FLOCK_SEPARATION_RADIUS).max(0.0));
+            let flock = (alignment + cohesion + separation).clamp_length(1.0);
+
+            let thrust = (Vec2::new(self.thrust_x[i], self.thrust_y[i]) + flock * weight).clamp_length(1.0);
+            self.thrust_x[i] = thrust.x;
+            self.thrust_y[i] = thrust.y;
+        }
+    }
+
+    /// Bend thrust away from nearby asteroids, harder the closer a bot is.
+    /// Bots keep their goal, so they slide around an asteroid rather than stop.
+    fn avoid_asteroids(&mut self, state: &GameState) {
+        for i in 0..self.count {
+            if !self.active_mask[i] {
+                continue;
+            }
+            let Some(player) = state.get_player(self.bot_ids[i]).filter(|p| p.alive) else {
+                continue;
+            };
+
+            let mut push = Vec2::ZERO;
+            for asteroid in &state.asteroids {
+                let (away, distance) = (player.position - asteroid.position).normalize_with_length();
+                let gap = distance - asteroid.radius - player.radius();
+                if gap < ASTEROID_AVOID_MARGIN {
+                    push += away * (ASTEROID_AVOID_STRENGTH * (1.0 - gap.max(0.0) / ASTEROID_AVOID_MARGIN));
+                }
+            }
+            if push == Vec2::ZERO {
+                continue;
+            }
+            let thrust = (Vec2::new(self.thrust_x[i], self.thrust_y[i]) + push).clamp_length(1.0);
+            self.thrust_x[i] = thrust.x;
+            self.thrust_y[i] = thrust.y;
+        }
+    }
+
     /// Update all bots in idle behavior
     fn update_idle_batch(&mut self, state: &GameState, _dt: f32) {
         let indices = &self.batches.idle;
@@ -1691,6 +2220,7 @@ impl AiManagerSoA {
             .filter(|p| !p.is_bot && p.alive)
             .map(|p| (p.id, p.position, p.mass))
             .collect();
+        self.collect_faction_bots(state);
 
         let has_debris = !state.debris.is_empty();
         let aggression_radius_sq = (AGGRESSION_RADIUS * 2.0) * (AGGRESSION_RADIUS * 2.0);
@@ -1714,6 +2244,21 @@ impl AiManagerSoA {
         }
     }
 
+    /// Refresh the alive faction bots (none while no faction has orders)
+    fn collect_faction_bots(&mut self, state: &GameState) {
+        self.faction_bots.clear();
+        if self.faction_orders.is_empty() {
+            return;
+        }
+        self.faction_bots.extend(
+            state
+                .players
+                .values()
+                .filter(|p| p.is_bot && p.alive && p.faction > 0)
+                .map(|p| (p.id, p.position, p.faction)),
+        );
+    }
+
     /// Decide behavior for a single bot
     /// OPTIMIZED: Uses pre-collected human data and squared distance
     fn decide_behavior_optimized(
@@ -1759,8 +2304,19 @@ impl AiManagerSoA {
             }
         }
 
+        // Data-driven behavior tree for Full-LOD bots (Reduced/Dormant keep the fast path)
+        if self.update_modes[idx] == UpdateMode::Full {
+            if let Some(tree) = self.behavior_tree.as_deref() {
+                let ctx = self.tree_context(idx, bot, humans, has_debris);
+                let decision = tree.evaluate(&ctx, rng).unwrap_or(AiBehavior::Orbit);
+                self.apply_tree_decision(idx, decision, &ctx);
+                return;
+            }
+        }
+
         // Behavior selection
-        if has_threat && rng.gen::<f32>() > self.aggression[idx] {
+        let aggression = self.effective_aggression(idx);
+        if has_threat && rng.gen::<f32>() > aggression {
             // Flee from threat
             self.behaviors[idx] = AiBehavior::Flee;
             self.thrust_x[idx] = threat_direction.x;
@@ -1769,7 +2325,7 @@ impl AiManagerSoA {
         }
 
         // Check for chase opportunity using pre-collected human data
-        if rng.gen::<f32>() < self.aggression[idx] {
+        if rng.gen::<f32>() < aggression {
             for &(human_id, human_pos, human_mass) in humans {
                 let dx = bot.position.x - human_pos.x;
                 let dy = bot.position.y - human_pos.y;
@@ -1782,6 +2338,24 @@ impl AiManagerSoA {
             }
         }
 
+        // Fight the nearest bot of another faction inside this faction's region
+        if let Some(order) = faction_order(&self.faction_orders, bot.faction).filter(|o| o.contains(bot.position)) {
+            if rng.gen::<f32>() < aggression {
+                let rival = self
+                    .faction_bots
+                    .iter()
+                    .filter(|&&(_, pos, faction)| faction != bot.faction && order.contains(pos))
+                    .map(|&(id, pos, _)| (id, bot.position.distance_sq_to(pos)))
+                    .filter(|&(_, dist_sq)| dist_sq < aggression_radius_sq)
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+                if let Some((rival_id, _)) = rival {
+                    self.behaviors[idx] = AiBehavior::Chase;
+                    self.target_ids[idx] = Some(rival_id);
+                    return;
+                }
+            }
+        }
+
         // Check for collect opportunity
         if has_debris && rng.gen::<f32>() < 0.3 {
             self.behaviors[idx] = AiBehavior::Collect;
@@ -1793,6 +2367,90 @@ impl AiManagerSoA {
         self.target_ids[idx] = None;
     }
 
+    /// Gather behavior tree inputs for a bot
+    fn tree_context(
+        &self,
+        idx: usize,
+        bot: &Player,
+        humans: &[(PlayerId, Vec2, f32)],
+        has_debris: bool,
+    ) -> BtContext {
+        // Nearest alive human
+        let mut nearest: Option<(PlayerId, Vec2, f32, f32)> = None;
+        for &(human_id, human_pos, human_mass) in humans {
+            let dist_sq = bot.position.distance_sq_to(human_pos);
+            if nearest.map_or(true, |(_, _, _, best)| dist_sq < best) {
+                nearest = Some((human_id, human_pos, human_mass, dist_sq));
+            }
+        }
+
+        // Strongest threatening zone within aggression radius
+        let bot_cell = self.zone_grid.position_to_cell(bot.position);
+        let aggr_radius_sq = AGGRESSION_RADIUS * AGGRESSION_RADIUS;
+        let mut threat_level = 0.0;
+        let mut flee_direction = Vec2::ZERO;
+        for cell in self.zone_grid.adjacent_cells(bot_cell) {
+            if let Some(zone) = self.zone_grid.get_zone(cell) {
+                if !zone.has_human || bot.mass <= 0.0 {
+                    continue;
+                }
+                let ratio = zone.threat_mass / bot.mass;
+                if ratio > threat_level && bot.position.distance_sq_to(zone.center) < aggr_radius_sq {
+                    threat_level = ratio;
+                    flee_direction = (bot.position - zone.center).normalize();
+                }
+            }
+        }
+        if flee_direction == Vec2::ZERO {
+            if let Some((_, human_pos, _, _)) = nearest {
+                flee_direction = (bot.position - human_pos).normalize();
+            }
+        }
+
+        BtContext {
+            mass_ratio: nearest
+                .map(|(_, _, mass, _)| if mass > 0.0 { bot.mass / mass } else { 0.0 })
+                .unwrap_or(0.0),
+            human_distance: nearest
+                .map(|(_, _, _, dist_sq)| dist_sq.sqrt())
+                .unwrap_or(f32::INFINITY),
+            threat_level,
+            has_debris,
+            aggression: self.effective_aggression(idx),
+            nearest_human: nearest.map(|(id, _, _, _)| id),
+            flee_direction,
+        }
+    }
+
+    /// Apply a behavior chosen by the behavior tree
+    fn apply_tree_decision(&mut self, idx: usize, behavior: AiBehavior, ctx: &BtContext) {
+        match behavior {
+            AiBehavior::Flee => {
+                self.behaviors[idx] = AiBehavior::Flee;
+                self.thrust_x[idx] = ctx.flee_direction.x;
+                self.thrust_y[idx] = ctx.flee_direction.y;
+            }
+            AiBehavior::Chase => match ctx.nearest_human {
+                Some(target) => {
+                    self.behaviors[idx] = AiBehavior::Chase;
+                    self.target_ids[idx] = Some(target);
+                }
+                // Nothing to chase: behave like the hardcoded default
+                None => {
+                    self.behaviors[idx] = AiBehavior::Orbit;
+                    self.target_ids[idx] = None;
+                }
+            },
+            AiBehavior::Orbit | AiBehavior::Idle => {
+                self.behaviors[idx] = behavior;
+                self.target_ids[idx] = None;
+            }
+            AiBehavior::Collect => {
+                self.behaviors[idx] = AiBehavior::Collect;
+            }
+        }
+    }
+
     /// Test helper: Backwards-compatible decision making for a single bot
     /// Pre-computes required data and calls the optimized version
     #[cfg(test)]
@@ -1803,6 +2461,7 @@ impl AiManagerSoA {
             .filter(|p| !p.is_bot && p.alive)
             .map(|p| (p.id, p.position, p.mass))
             .collect();
+        self.collect_faction_bots(state);
         let has_debris = !state.debris.is_empty();
         let aggression_radius_sq = (AGGRESSION_RADIUS * 2.0) * (AGGRESSION_RADIUS * 2.0);
         self.decide_behavior_optimized(idx, state, &humans, has_debris, aggression_radius_sq, rng);
@@ -1818,6 +2477,10 @@ impl AiManagerSoA {
             if !self.active_mask.get(i).map(|b| *b).unwrap_or(false) {
                 continue;
             }
+            // Elite bots manage their own charge/fire state
+            if self.is_policy_driven(i) {
+                continue;
+            }
 
             let behavior = self.behaviors[i];
             if behavior != AiBehavior::Chase && behavior != AiBehavior::Flee {
@@ -1853,7 +2516,7 @@ impl AiManagerSoA {
             }
 
             // Aim with accuracy offset - only compute when in range
-            let accuracy_offset = (1.0 - self.accuracy[i]) * rng.gen_range(-0.3..0.3);
+            let accuracy_offset = (1.0 - self.effective_accuracy(i)) * rng.gen_range(-0.3..0.3);
             let inv_dist = 1.0 / distance_sq.sqrt();
             let aim_x = dx * inv_dist;
             let aim_y = dy * inv_dist;
@@ -1879,6 +2542,83 @@ impl AiManagerSoA {
         }
     }
 
+    /// Check if a bot's actions come from the learned policy this tick
+    #[inline]
+    fn is_policy_driven(&self, idx: usize) -> bool {
+        self.policy.is_some()
+            && self.update_modes[idx] == UpdateMode::Full
+            && self.is_elite.get(idx).map(|b| *b).unwrap_or(false)
+    }
+
+    /// Run the learned policy for active, Full-LOD elite bots
+    /// OPTIMIZATION: Observations are gathered into one buffer and inferred in a
+    /// single batched forward pass per tick.
+    fn update_elite_policy(&mut self, state: &GameState, dt: f32) {
+        let Some(policy) = self.policy.clone() else {
+            return;
+        };
+
+        let indices: Vec<usize> = self
+            .is_elite
+            .iter_ones()
+            .filter(|&i| {
+                i < self.count
+                    && self.is_policy_driven(i)
+                    && self.active_mask.get(i).map(|b| *b).unwrap_or(false)
+                    && state.get_player(self.bot_ids[i]).is_some_and(|p| p.alive)
+            })
+            .collect();
+        if indices.is_empty() {
+            return;
+        }
+
+        let humans: Vec<(PlayerId, Vec2, f32)> = state
+            .players
+            .values()
+            .filter(|p| !p.is_bot && p.alive)
+            .map(|p| (p.id, p.position, p.mass))
+            .collect();
+
+        self.policy_obs.clear();
+        for &i in &indices {
+            if let Some(bot) = state.get_player(self.bot_ids[i]) {
+                bot_policy::observe(bot, state, &humans, &mut self.policy_obs);
+            }
+        }
+        debug_assert_eq!(self.policy_obs.len(), indices.len() * OBS_DIM);
+        policy.forward_batch(&self.policy_obs, &mut self.policy_actions);
+
+        for (k, &i) in indices.iter().enumerate() {
+            let a = &self.policy_actions[k * ACT_DIM..(k + 1) * ACT_DIM];
+
+            // Thrust is clamped to unit length; aim is normalized (kept if degenerate)
+            let thrust = Vec2::new(a[0], a[1]);
+            let thrust_len = thrust.length();
+            let thrust = if thrust_len > 1.0 { thrust * (1.0 / thrust_len) } else { thrust };
+            self.thrust_x[i] = thrust.x;
+            self.thrust_y[i] = thrust.y;
+
+            let aim_len = (a[2] * a[2] + a[3] * a[3]).sqrt();
+            if aim_len > 1e-4 {
+                self.aim_x[i] = a[2] / aim_len;
+                self.aim_y[i] = a[3] / aim_len;
+            }
+
+            self.wants_boost.set(i, a[4] > 0.0);
+
+            // Hold to charge; keep the charge for one tick after release so
+            // get_input() reports fire_released
+            let fire = a[5] > 0.0;
+            let was_firing = self.wants_fire.get(i).map(|b| *b).unwrap_or(false);
+            if fire {
+                self.charge_times[i] += dt;
+            } else if !was_firing {
+                self.charge_times[i] = 0.0;
+            }
+            self.wants_fire.set(i, fire);
+        }
+    }
+
     /// Generate input for a bot
     pub fn get_input(&self, player_id: PlayerId, tick: u64) -> Option<PlayerInput> {
         let idx = *self.id_to_index.get(&player_id)? as usize;
@@ -1893,6 +2633,7 @@ impl AiManagerSoA {
             fire: self.wants_fire.get(idx).map(|b| *b).unwrap_or(false),
             fire_released: !self.wants_fire.get(idx).map(|b| *b).unwrap_or(false)
                 && self.charge_times[idx] > 0.0,
+            state_hash: None,
         })
     }
 
@@ -1916,12 +2657,15 @@ impl AiManagerSoA {
             .count();
 
         AiManagerStats {
-            total_bots: self.count,
+            total_bots: self.count + self.cold_storage.len(),
+            cold_bots: self.cold_storage.len(),
             active_this_tick: active_count,
             full_mode: full_count,
             reduced_mode: reduced_count,
             dormant_mode: dormant_count,
             zone_count: self.zone_grid.zones.len(),
+            watched_zones: self.zone_grid.watched_count(),
+            batch_timings: self.batch_timings,
             adaptive: if self.adaptive.enabled {
                 Some(self.adaptive.stats())
             } else {
@@ -1942,6 +2686,137 @@ impl Default for AiManagerSoA {
     }
 }
 
+// ============================================================================
+// Snapshot / Restore
+// ============================================================================
+
+/// Format version of [`AiSnapshot`]; bump when [`BotBrain`] changes incompatibly
+pub const AI_SNAPSHOT_VERSION: u32 = 1;
+
+/// One bot's decision state, keyed by player ID so it survives index reshuffles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BotBrain {
+    pub player_id: PlayerId,
+    pub behavior: AiBehavior,
+    pub target_id: Option<PlayerId>,
+    pub decision_timer: f32,
+    pub charge_time: f32,
+    pub wants_boost: bool,
+    pub wants_fire: bool,
+    pub thrust: Vec2,
+    pub aim: Vec2,
+    pub aggression: f32,
+    pub preferred_radius: f32,
+    pub accuracy: f32,
+    pub reaction_variance: f32,
+    pub cached_well_id: Option<WellId>,
+    pub well_cache_timer: f32,
+    pub is_elite: bool,
+}
+
+/// Serializable bot AI state, saved alongside the game state so bots keep
+/// their behaviors, targets and personalities across a save/restore,
+/// cross-server handoff or crash-dump restart. LOD modes, zones and batches
+/// are derived every tick and are not included.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiSnapshot {
+    pub version: u32,
+    pub tick_counter: u32,
+    pub bots: Vec<BotBrain>,
+}
+
+/// Why an AI snapshot could not be restored
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AiRestoreError {
+    #[error("AI snapshot version {found} is not supported (expected {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+}
+
+impl AiManagerSoA {
+    /// Capture every registered bot's decision state (frozen bots have none
+    /// beyond their personality)
+    pub fn snapshot(&self) -> AiSnapshot {
+        let mut bots: Vec<BotBrain> = (0..self.count)
+            .map(|i| BotBrain {
+                player_id: self.bot_ids[i],
+                behavior: self.behaviors[i],
+                target_id: self.target_ids[i],
+                decision_timer: self.decision_timers[i],
+                charge_time: self.charge_times[i],
+                wants_boost: self.wants_boost[i],
+                wants_fire: self.wants_fire[i],
+                thrust: Vec2::new(self.thrust_x[i], self.thrust_y[i]),
+                aim: Vec2::new(self.aim_x[i], self.aim_y[i]),
+                aggression: self.aggression[i],
+                preferred_radius: self.preferred_radius[i],
+                accuracy: self.accuracy[i],
+                reaction_variance: self.reaction_variance[i],
+                cached_well_id: self.cached_well_ids[i],
+                well_cache_timer: self.well_cache_timers[i],
+                is_elite: self.is_elite[i],
+            })
+            .collect();
+        bots.extend(self.cold_storage.bots().map(|bot| {
+            let personality = bot.unpack();
+            BotBrain {
+                player_id: bot.player_id,
+                behavior: AiBehavior::Idle,
+                target_id: None,
+                decision_timer: 0.0,
+                charge_time: 0.0,
+                wants_boost: false,
+                wants_fire: false,
+                thrust: Vec2::ZERO,
+                aim: Vec2::new(1.0, 0.0),
+                aggression: personality.aggression,
+                preferred_radius: personality.preferred_radius,
+                accuracy: personality.accuracy,
+                reaction_variance: personality.reaction_variance,
+                cached_well_id: None,
+                well_cache_timer: 0.0,
+                is_elite: personality.is_elite,
+            }
+        }));
+        AiSnapshot { version: AI_SNAPSHOT_VERSION, tick_counter: self.tick_counter, bots }
+    }
+
+    /// Restore bot decision state from a snapshot. Bots in the snapshot are
+    /// registered if needed; registered bots missing from it keep their
+    /// current state. Returns the number of bots restored.
+    pub fn restore(&mut self, snapshot: &AiSnapshot) -> Result<usize, AiRestoreError> {
+        if snapshot.version != AI_SNAPSHOT_VERSION {
+            return Err(AiRestoreError::UnsupportedVersion {
+                found: snapshot.version,
+                expected: AI_SNAPSHOT_VERSION,
+            });
+        }
+
+        for brain in &snapshot.bots {
+            self.register_bot(brain.player_id);
+            let i = self.id_to_index[&brain.player_id] as usize;
+            self.behaviors[i] = brain.behavior;
+            self.target_ids[i] = brain.target_id;
+            self.decision_timers[i] = brain.decision_timer;
+            self.charge_times[i] = brain.charge_time;
+            self.wants_boost.set(i, brain.wants_boost);
+            self.wants_fire.set(i, brain.wants_fire);
+            self.thrust_x[i] = brain.thrust.x;
+            self.thrust_y[i] = brain.thrust.y;
+            self.aim_x[i] = brain.aim.x;
+            self.aim_y[i] = brain.aim.y;
+            self.aggression[i] = brain.aggression;
+            self.preferred_radius[i] = brain.preferred_radius;
+            self.accuracy[i] = brain.accuracy;
+            self.reaction_variance[i] = brain.reaction_variance;
+            self.cached_well_ids[i] = brain.cached_well_id;
+            self.well_cache_timers[i] = brain.well_cache_timer;
+            self.is_elite.set(i, brain.is_elite);
+        }
+        self.tick_counter = snapshot.tick_counter;
+        Ok(snapshot.bots.len())
+    }
+}
+
 /// Statistics about the AI manager state
 #[derive(Debug, Clone)]
 pub struct AiManagerStats {
@@ -1950,11 +2825,54 @@ pub struct AiManagerStats {
     pub full_mode: usize,
     pub reduced_mode: usize,
     pub dormant_mode: usize,
+    /// Bots frozen in cold storage (counted in `total_bots`, not in the modes)
+    pub cold_bots: usize,
     pub zone_count: usize,
+    /// Zones on some human's screen
+    pub watched_zones: usize,
+    /// Cost of each behavior batch in the last update
+    pub batch_timings: BatchTimings,
     /// Adaptive dormancy stats (if enabled)
     pub adaptive: Option<AdaptiveDormancyStats>,
 }
 
+/// Cost of one behavior batch in an update
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchTiming {
+    /// Wall time spent on the batch
+    pub micros: u64,
+    /// Bots in the batch
+    pub size: usize,
+    /// Whether the batch was large enough to run on the Rayon pool
+    pub parallel: bool,
+}
+
+/// Per-behavior batch costs of the last update (all zero with batching disabled).
+/// Idle bots are only zeroed out and aren't timed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchTimings {
+    pub orbit: BatchTiming,
+    pub chase: BatchTiming,
+    pub flee: BatchTiming,
+    pub collect: BatchTiming,
+}
+
+impl BatchTimings {
+    /// Each timed behavior with its label
+    pub fn by_behavior(&self) -> [(&'static str, BatchTiming); 4] {
+        [("orbit", self.orbit), ("chase", self.chase), ("flee", self.flee), ("collect", self.collect)]
+    }
+
+    /// The behavior that took longest, if any took measurable time
+    pub fn dominant(&self) -> Option<&'static str> {
+        self.by_behavior()
+            .into_iter()
+            .filter(|(_, timing)| timing.micros > 0)
+            .max_by_key(|(_, timing)| timing.micros)
+            .map(|(name, _)| name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1987,6 +2905,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         }
     }
 
@@ -2032,6 +2954,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_soften_all() {
+        let mut manager = AiManagerSoA::default();
+        for _ in 0..20 {
+            manager.register_bot(Uuid::new_v4());
+        }
+
+        manager.soften_all();
+
+        assert!(manager.aggression.iter().all(|&a| a == GENTLE_AGGRESSION));
+        assert!(manager.accuracy.iter().all(|&a| a == GENTLE_ACCURACY));
+        assert!(manager.is_elite.iter().all(|e| !*e));
+    }
+
     #[test]
     fn test_register_duplicate_bot() {
         let mut manager = AiManagerSoA::default();
@@ -2660,6 +3596,134 @@ mod tests {
         assert!(manager.thrust_x[idx] > 0.5);
     }
 
+    #[test]
+    fn test_low_lod_collect_follows_flow_field() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+        let bot = create_bot_player(Vec2::new(1000.0, 1000.0), 100.0);
+        let bot_id = bot.id;
+        state.add_player(bot);
+        manager.register_bot(bot_id);
+
+        // One piece just ahead, the rest of the cluster far up-right
+        let debris = [(1, Vec2::new(1100.0, 1000.0)), (2, Vec2::new(3000.0, 3000.0)), (3, Vec2::new(3100.0, 3100.0))];
+        for (id, position) in debris {
+            state.debris.push(crate::game::state::Debris::new(
+                id,
+                position,
+                Vec2::ZERO,
+                crate::game::state::DebrisSize::Medium,
+            ));
+        }
+        manager.update_zones(&state);
+        manager.flow_field.rebuild(&manager.zone_grid, state.debris.iter().map(|d| d.position));
+
+        let idx = manager.get_index(bot_id).unwrap() as usize;
+        manager.behaviors[idx] = AiBehavior::Collect;
+        manager.batches.rebuild(&manager.behaviors, &manager.active_mask);
+
+        // Full LOD: exact nearest piece
+        manager.update_collect_batch(&state, 0.033);
+        assert!(manager.thrust_x[idx] > 0.99);
+
+        // Reduced LOD: toward the zone's cluster centroid
+        manager.update_modes[idx] = UpdateMode::Reduced;
+        manager.update_collect_batch(&state, 0.033);
+        let expected = (Vec2::new(2400.0, 2366.667) - Vec2::new(1000.0, 1000.0)).normalize();
+        assert!((Vec2::new(manager.thrust_x[idx], manager.thrust_y[idx]) - expected).length() < 1e-3);
+    }
+
+    #[test]
+    fn test_flocking_swarms_non_combat_bots() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+        let positions = [Vec2::new(1000.0, 1000.0), Vec2::new(1100.0, 1000.0), Vec2::new(2000.0, 1000.0)];
+        let ids: Vec<_> = positions
+            .iter()
+            .map(|&position| {
+                let mut bot = create_bot_player(position, 100.0);
+                bot.velocity = Vec2::new(0.0, 50.0);
+                let id = bot.id;
+                state.add_player(bot);
+                manager.register_bot(id);
+                id
+            })
+            .collect();
+        manager.update_zones(&state);
+        let zone = manager.zone_grid.get_zone((0, 0)).unwrap();
+        assert!((zone.centroid() - Vec2::new(1366.667, 1000.0)).length() < 1e-2);
+
+        // The far bot idles; the near bot is in a full-LOD chase
+        let (near, far) = (manager.get_index(ids[0]).unwrap() as usize, manager.get_index(ids[2]).unwrap() as usize);
+        manager.behaviors[near] = AiBehavior::Chase;
+        manager.thrust_x[near] = 1.0;
+        manager.apply_flocking(&state, 0.5);
+
+        assert_eq!((manager.thrust_x[near], manager.thrust_y[near]), (1.0, 0.0));
+        // Aligned with the swarm's heading and pulled back toward its centroid
+        assert!(manager.thrust_y[far] > 0.3);
+        assert!(manager.thrust_x[far] < 0.0);
+        assert!(Vec2::new(manager.thrust_x[far], manager.thrust_y[far]).length() <= 0.5 + 1e-5);
+
+        // Once the chase is only simulated at reduced LOD, it flocks too
+        manager.update_modes[near] = UpdateMode::Reduced;
+        manager.apply_flocking(&state, 0.5);
+        assert!(manager.thrust_y[near] > 0.0);
+    }
+
+    #[test]
+    fn test_bots_steer_around_asteroids() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+        state.add_asteroid(Vec2::new(1000.0, 0.0), 60.0, 100.0);
+        let near = create_bot_player(Vec2::new(900.0, 0.0), 100.0);
+        let far = create_bot_player(Vec2::ZERO, 100.0);
+        let (near_id, far_id) = (near.id, far.id);
+        state.add_player(near);
+        state.add_player(far);
+        manager.register_bot(near_id);
+        manager.register_bot(far_id);
+        let (near, far) = (manager.get_index(near_id).unwrap() as usize, manager.get_index(far_id).unwrap() as usize);
+        // Both heading straight for the asteroid
+        manager.thrust_x[near] = 1.0;
+        manager.thrust_x[far] = 1.0;
+
+        manager.avoid_asteroids(&state);
+
+        assert!(manager.thrust_x[near] < 0.0, "pushed back off the rock");
+        assert_eq!(manager.thrust_x[far], 1.0);
+    }
+
+    #[test]
+    fn test_low_lod_flee_uses_dominant_zone_threat() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+        let bot = create_bot_player(Vec2::new(100.0, 100.0), 50.0);
+        let bot_id = bot.id;
+        state.add_player(bot);
+        manager.register_bot(bot_id);
+        // The bot's own threat is to its right, but a heavy human sits in the zone below
+        let threat = create_bot_player(Vec2::new(300.0, 100.0), 200.0);
+        let threat_id = threat.id;
+        state.add_player(threat);
+        state.add_player(create_human_player(Vec2::new(100.0, -3000.0), 500.0));
+        manager.update_zones(&state);
+        manager.flow_field.rebuild(&manager.zone_grid, std::iter::empty());
+
+        let idx = manager.get_index(bot_id).unwrap() as usize;
+        manager.behaviors[idx] = AiBehavior::Flee;
+        manager.target_ids[idx] = Some(threat_id);
+        manager.batches.rebuild(&manager.behaviors, &manager.active_mask);
+
+        manager.update_flee_batch(&state, 0.033);
+        assert!(manager.thrust_x[idx] < -0.99, "full LOD flees its own threat");
+
+        manager.update_modes[idx] = UpdateMode::Dormant;
+        manager.target_ids[idx] = Some(threat_id);
+        manager.update_flee_batch(&state, 0.033);
+        assert!(manager.thrust_y[idx] > 0.5, "dormant bot flees the zone threat");
+    }
+
     #[test]
     fn test_idle_behavior() {
         let mut manager = AiManagerSoA::default();
@@ -2698,6 +3762,8 @@ mod tests {
         assert!(config.zone_queries_enabled);
         assert!(config.behavior_batching_enabled);
         assert!(config.parallel_enabled);
+        assert!(config.flow_field_enabled);
+        assert_eq!(config.flocking_weight, 0.0);
 
         // LOD thresholds
         assert!((config.lod_full_radius - 500.0).abs() < 0.01);
@@ -2710,6 +3776,8 @@ mod tests {
 
         // Spatial
         assert!((config.zone_cell_size - 4096.0).abs() < 0.01);
+        assert!((config.offscreen_lod_scale - 0.5).abs() < 0.01);
+        assert!((config.cold_radius - 15000.0).abs() < 0.01);
 
         // Decision making
         assert!((config.decision_interval - 0.5).abs() < 0.01);
@@ -2723,9 +3791,13 @@ mod tests {
             zone_queries_enabled: false,
             behavior_batching_enabled: false,
             parallel_enabled: false,
+            flow_field_enabled: false,
+            flocking_weight: 0.5,
             lod_full_radius: 100.0,
             lod_reduced_radius: 500.0,
             lod_dormant_radius: 1000.0,
+            offscreen_lod_scale: 0.25,
+            cold_radius: 0.0,
             target_tick_ms: 20.0,
             critical_tick_ms: 40.0,
             adaptation_rate: 0.2,
@@ -2736,6 +3808,9 @@ mod tests {
             zone_cell_size: 2048.0,
             decision_interval: 0.25,
             well_cache_refresh_interval: 0.25,
+            behavior_tree_path: None,
+            policy_path: None,
+            elite_fraction: 0.05,
             base_wakeups_per_tick: 50,
             wakeup_scale_reference: 1000,
         };
@@ -2998,6 +4073,82 @@ mod tests {
         assert_eq!(manager.update_modes[idx], UpdateMode::Reduced);
     }
 
+    #[test]
+    fn test_far_dormant_bots_are_frozen_and_thawed_by_zone() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+        let human = create_human_player(Vec2::new(0.0, 0.0), 100.0);
+        let human_id = human.id;
+        state.add_player(human);
+        let near = create_bot_player(Vec2::new(1000.0, 0.0), 100.0);
+        let far = create_bot_player(Vec2::new(40000.0, 0.0), 80.0);
+        let (near_id, far_id) = (near.id, far.id);
+        for bot in [near, far] {
+            manager.register_bot(bot.id);
+            state.add_player(bot);
+        }
+        let aggression = manager.aggression[manager.get_index(far_id).unwrap() as usize];
+
+        manager.update_dormancy(&state, 1);
+        manager.update_cold_storage(&state);
+        assert!(manager.get_index(far_id).is_none());
+        assert_eq!(manager.cold_storage.bots().map(|b| b.player_id).collect::<Vec<_>>(), [far_id]);
+        assert!(manager.get_index(near_id).is_some());
+        let stats = manager.stats();
+        assert_eq!((stats.total_bots, stats.cold_bots, stats.dormant_mode), (2, 1, 0));
+        assert_eq!(manager.snapshot().bots.len(), 2);
+
+        // Zone queries still see the frozen bot
+        manager.update_zones(&state);
+        let zone = manager.zone_grid.get_zone(manager.zone_grid.position_to_cell(Vec2::new(40000.0, 0.0))).unwrap();
+        assert_eq!((zone.bot_count, zone.total_mass), (1, 80.0));
+
+        // A human closing in materializes the zone as Dormant bots
+        state.get_player_mut(human_id).unwrap().position = Vec2::new(30000.0, 0.0);
+        manager.update_cold_storage(&state);
+        let idx = manager.get_index(far_id).unwrap() as usize;
+        assert!(manager.cold_storage.is_empty());
+        assert_eq!(manager.update_modes[idx], UpdateMode::Dormant);
+        assert!((manager.aggression[idx] - aggression).abs() < 0.005);
+
+        // Leaving the game while frozen drops the bot
+        state.get_player_mut(human_id).unwrap().position = Vec2::ZERO;
+        manager.update_cold_storage(&state);
+        manager.unregister_bot(far_id);
+        assert_eq!(manager.stats().total_bots, 1);
+    }
+
+    #[test]
+    fn test_dormancy_follows_watched_zones() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+
+        // Human near the right edge of zone (0, 0), seeing only that zone
+        let human = create_human_player(Vec2::new(3900.0, 0.0), 100.0);
+        state.add_player(human);
+        let on_screen = create_bot_player(Vec2::new(2900.0, 0.0), 100.0);
+        let off_screen = create_bot_player(Vec2::new(5400.0, 0.0), 100.0);
+        let (on_id, off_id) = (on_screen.id, off_screen.id);
+        for bot in [on_screen, off_screen] {
+            manager.register_bot(bot.id);
+            state.add_player(bot);
+        }
+        let mode = |manager: &AiManagerSoA, id| manager.update_modes[manager.get_index(id).unwrap() as usize];
+
+        // Without viewports both bots are within the reduced radius
+        manager.update_zones(&state);
+        manager.update_dormancy(&state, 1);
+        assert_eq!((mode(&manager, on_id), mode(&manager, off_id)), (UpdateMode::Reduced, UpdateMode::Reduced));
+
+        // The watched zone runs at full detail, the zone next door at half the radius
+        manager.set_human_views(vec![(Vec2::new(3900.0, 0.0), 100.0)]);
+        manager.update_zones(&state);
+        manager.update_dormancy(&state, 1);
+        assert!(manager.zone_grid.is_watched((0, 0)) && !manager.zone_grid.is_watched((1, 0)));
+        assert_eq!((mode(&manager, on_id), mode(&manager, off_id)), (UpdateMode::Full, UpdateMode::Dormant));
+        assert_eq!(manager.stats().watched_zones, 2);
+    }
+
     #[test]
     fn test_tick_counter_reduced_interval() {
         let mut manager = AiManagerSoA::default();
@@ -3190,6 +4341,38 @@ mod tests {
         assert!(manager.target_ids[idx].is_none());
     }
 
+    #[test]
+    fn test_faction_bots_fight_rivals_in_their_region() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+        state.debris.clear();
+
+        let mut bots = Vec::new();
+        for (x, faction) in [(300.0, 1), (320.0, 1), (450.0, 2), (2500.0, 2)] {
+            let mut bot = create_bot_player(Vec2::new(x, 0.0), 100.0);
+            bot.faction = faction;
+            bots.push(bot.id);
+            manager.register_bot(bot.id);
+            state.add_player(bot);
+        }
+        let order = FactionOrder { well: 1, center: Vec2::ZERO, radius: 1000.0 };
+        manager.faction_orders = vec![Some(order), None];
+
+        let idx = manager.get_index(bots[0]).unwrap() as usize;
+        manager.aggression[idx] = 1.0;
+        let mut rng = rand::thread_rng();
+        manager.decide_behavior(idx, &state, &mut rng);
+        // The nearest rival, never a faction mate
+        assert_eq!(manager.behaviors[idx], AiBehavior::Chase);
+        assert_eq!(manager.target_ids[idx], Some(bots[2]));
+
+        // Faction 2 has no orders, so it doesn't defend anything
+        let idx = manager.get_index(bots[2]).unwrap() as usize;
+        manager.aggression[idx] = 1.0;
+        manager.decide_behavior(idx, &state, &mut rng);
+        assert_eq!(manager.behaviors[idx], AiBehavior::Orbit);
+    }
+
     // ========================================================================
     // Firing Logic Tests
     // ========================================================================
@@ -3622,6 +4805,28 @@ mod tests {
         assert_eq!(manager.tick_counter, 2);
     }
 
+    #[test]
+    fn test_update_records_batch_timings() {
+        let mut manager = AiManagerSoA::default();
+        let mut state = create_test_state();
+        state.arena.gravity_wells.insert(1, create_gravity_well(1, Vec2::new(2000.0, 0.0), 10000.0, 50.0));
+        for i in 0..5 {
+            let bot = create_bot_player(Vec2::new(2300.0 + i as f32 * 10.0, 0.0), 100.0);
+            manager.register_bot(bot.id);
+            state.add_player(bot);
+        }
+        state.add_player(create_human_player(Vec2::new(2400.0, 0.0), 150.0));
+        manager.behaviors[..3].fill(AiBehavior::Orbit);
+        manager.behaviors[3..].fill(AiBehavior::Chase);
+
+        manager.update(&state, 0.033, 1);
+        let timings = manager.stats().batch_timings;
+        assert_eq!((timings.orbit.size, timings.chase.size, timings.flee.size), (3, 2, 0));
+        assert!(matches!(timings.dominant(), Some("orbit" | "chase") | None));
+        // Far below the parallel threshold
+        assert!(timings.by_behavior().iter().all(|(_, t)| !t.parallel));
+    }
+
     // ========================================================================
     // Adaptive Dormancy Tests
     // ========================================================================
@@ -4049,6 +5254,111 @@ mod tests {
         assert_eq!(manager.behaviors[idx], AiBehavior::Chase);
     }
 
+    #[test]
+    fn test_behavior_tree_overrides_full_lod_decisions() {
+        let mut manager = AiManagerSoA::default();
+        manager.behavior_tree = Some(Arc::new(
+            BehaviorTree::from_json(r#"{ "type": "action", "behavior": "idle" }"#).unwrap(),
+        ));
+        let mut state = create_test_state();
+
+        let bot = create_bot_player(Vec2::new(0.0, 0.0), 200.0);
+        let bot_id = bot.id;
+        state.add_player(bot);
+        manager.register_bot(bot_id);
+        state.add_player(create_human_player(Vec2::new(100.0, 0.0), 100.0));
+
+        let idx = manager.get_index(bot_id).unwrap() as usize;
+        manager.aggression[idx] = 1.0;
+        manager.active_mask.set(idx, true);
+
+        // Full LOD: tree decides (would chase on the hardcoded path)
+        manager.update_modes[idx] = UpdateMode::Full;
+        manager.update_decisions(&state, 1.0);
+        assert_eq!(manager.behaviors[idx], AiBehavior::Idle);
+
+        // Reduced LOD: hardcoded fast path
+        manager.update_modes[idx] = UpdateMode::Reduced;
+        manager.decision_timers[idx] = 0.0;
+        manager.update_decisions(&state, 1.0);
+        assert_eq!(manager.behaviors[idx], AiBehavior::Chase);
+    }
+
+    #[test]
+    fn test_behavior_tree_chase_targets_nearest_human() {
+        let mut manager = AiManagerSoA::default();
+        manager.behavior_tree = Some(Arc::new(
+            BehaviorTree::from_json(r#"{ "type": "action", "behavior": "chase" }"#).unwrap(),
+        ));
+        let mut state = create_test_state();
+
+        let bot = create_bot_player(Vec2::new(0.0, 0.0), 100.0);
+        let bot_id = bot.id;
+        state.add_player(bot);
+        manager.register_bot(bot_id);
+        let near = create_human_player(Vec2::new(50.0, 0.0), 100.0);
+        let near_id = near.id;
+        state.add_player(near);
+        state.add_player(create_human_player(Vec2::new(900.0, 0.0), 100.0));
+
+        let idx = manager.get_index(bot_id).unwrap() as usize;
+        manager.active_mask.set(idx, true);
+        manager.update_modes[idx] = UpdateMode::Full;
+        manager.update_decisions(&state, 1.0);
+
+        assert_eq!(manager.behaviors[idx], AiBehavior::Chase);
+        assert_eq!(manager.target_ids[idx], Some(near_id));
+    }
+
+    #[test]
+    fn test_policy_drives_elite_bots_only() {
+        // Linear policy: thrust/aim toward nearest human, fire when one exists
+        let mut rows = vec![vec![0.0f32; OBS_DIM]; ACT_DIM];
+        rows[0][2] = 10.0;
+        rows[2][2] = 1.0;
+        rows[5][9] = 1.0;
+        let json = serde_json::json!({
+            "layers": [{ "weights": rows, "biases": vec![0.0f32; ACT_DIM], "activation": "linear" }]
+        });
+        let mut manager = AiManagerSoA::default();
+        manager.policy = Some(Arc::new(MlpPolicy::from_json(&json.to_string()).unwrap()));
+        let mut state = create_test_state();
+
+        let elite = create_bot_player(Vec2::new(0.0, 0.0), 100.0);
+        let scripted = create_bot_player(Vec2::new(0.0, 50.0), 100.0);
+        let (elite_id, scripted_id) = (elite.id, scripted.id);
+        state.add_player(elite);
+        state.add_player(scripted);
+        state.add_player(create_human_player(Vec2::new(500.0, 0.0), 100.0));
+        manager.register_bot(elite_id);
+        manager.register_bot(scripted_id);
+
+        let e = manager.get_index(elite_id).unwrap() as usize;
+        let s = manager.get_index(scripted_id).unwrap() as usize;
+        manager.is_elite.set(e, true);
+        manager.is_elite.set(s, false);
+        for i in [e, s] {
+            manager.active_mask.set(i, true);
+            manager.update_modes[i] = UpdateMode::Full;
+            manager.behaviors[i] = AiBehavior::Idle;
+            manager.thrust_x[i] = 0.0;
+        }
+
+        manager.update_elite_policy(&state, 0.05);
+
+        assert!((manager.thrust_x[e] - 1.0).abs() < 1e-5, "thrust clamped to unit length");
+        assert!((manager.aim_x[e] - 1.0).abs() < 1e-5);
+        assert!(manager.wants_fire[e]);
+        assert!(manager.charge_times[e] > 0.0);
+        assert_eq!(manager.thrust_x[s], 0.0);
+
+        // Removing the elite bot keeps the mask aligned with the swapped-in bot
+        manager.unregister_bot(elite_id);
+        let s = manager.get_index(scripted_id).unwrap() as usize;
+        assert!(!manager.is_elite[s]);
+        assert_eq!(manager.is_elite.len(), manager.count);
+    }
+
     #[test]
     fn test_firing_squared_distance_range_check() {
         // Tests that firing correctly uses squared distance for range check
@@ -4813,4 +6123,73 @@ mod tests {
             dormant_count
         );
     }
+
+    // ========================================================================
+    // Snapshot / Restore Tests
+    // ========================================================================
+
+    /// A manager whose bots have run long enough to hold non-default brains
+    fn busy_manager() -> (AiManagerSoA, GameState) {
+        let mut state = create_test_state();
+        let mut manager = AiManagerSoA::default();
+        state.add_player(create_human_player(Vec2::new(0.0, 0.0), 100.0));
+        for i in 0..8 {
+            let bot = create_bot_player(Vec2::new(150.0 + i as f32 * 40.0, 80.0), 50.0 + i as f32 * 20.0);
+            manager.register_bot(bot.id);
+            state.add_player(bot);
+        }
+        for _ in 0..30 {
+            manager.update(&state, 1.0 / 30.0, 0);
+        }
+        (manager, state)
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_restores_brains() {
+        let (manager, state) = busy_manager();
+        let snapshot = manager.snapshot();
+        assert_eq!(snapshot.bots.len(), 8);
+        assert!(snapshot.bots.iter().any(|b| b.behavior != AiBehavior::Idle));
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let mut restored = AiManagerSoA::default();
+        assert_eq!(restored.restore(&serde_json::from_str(&json).unwrap()), Ok(8));
+
+        assert_eq!(restored.snapshot(), snapshot);
+        for &id in &manager.bot_ids {
+            let input = |m: &AiManagerSoA| format!("{:?}", m.get_input(id, state.tick));
+            assert_eq!(input(&restored), input(&manager));
+        }
+    }
+
+    #[test]
+    fn test_restore_matches_bots_by_id_not_index() {
+        let (manager, _) = busy_manager();
+        let snapshot = manager.snapshot();
+
+        // The receiving server registered the same bots in a different order
+        let mut restored = AiManagerSoA::default();
+        for &id in manager.bot_ids.iter().rev() {
+            restored.register_bot(id);
+        }
+        restored.restore(&snapshot).unwrap();
+
+        let by_id = |s: AiSnapshot| {
+            let mut bots = s.bots;
+            bots.sort_by_key(|b| b.player_id);
+            bots
+        };
+        assert_eq!(by_id(restored.snapshot()), by_id(snapshot));
+    }
+
+    #[test]
+    fn test_restore_rejects_unknown_version() {
+        let (manager, _) = busy_manager();
+        let mut snapshot = manager.snapshot();
+        snapshot.version = AI_SNAPSHOT_VERSION + 1;
+
+        let mut restored = AiManagerSoA::default();
+        assert!(matches!(restored.restore(&snapshot), Err(AiRestoreError::UnsupportedVersion { .. })));
+        assert_eq!(restored.count, 0);
+    }
 }
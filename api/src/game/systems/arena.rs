@@ -1,12 +1,19 @@
 //! Arena management system
 //!
 //! Handles zone collapse, player boundary checks, and arena events.
+//!
+//! The arena edge is soft: beyond the safe radius players feel a damped
+//! restoring force and lose mass at a rate that grows from zero with the
+//! distance out, so nothing changes abruptly when crossing the line.
 
 #![allow(dead_code)] // Arena utilities and event variants
 
+use crate::config::BoundaryConfig;
 use crate::game::constants::arena::*;
 use crate::game::constants::spawn::RESPAWN_DELAY;
-use crate::game::state::{GameState, MatchPhase, CENTRAL_WELL_ID};
+use crate::game::state::{GameState, MatchPhase, WellId, CENTRAL_WELL_ID};
+use crate::game::systems::collision::{player_layer, CollisionLayer, CollisionMatrix};
+use crate::game::systems::status_effects::EffectKind;
 
 // ============================================================================
 // Arena System Constants
@@ -18,10 +25,6 @@ const COLLAPSE_SHRINK_FACTOR: f32 = 0.8;
 /// Collapse progress shrink factor for inner radius (60% shrink at max collapse)
 const INNER_COLLAPSE_SHRINK_FACTOR: f32 = 0.6;
 
-/// Distance divisor for progressive drain rate increase outside safe zone
-/// Mass drain increases by 1x for each 100 units beyond safe radius
-const DRAIN_RATE_DISTANCE_DIVISOR: f32 = 100.0;
-
 /// Supermassive black hole safe spawn multiplier (3x core radius for safety margin)
 const SUPERMASSIVE_SAFE_SPAWN_MULTIPLIER: f32 = 3.0;
 
@@ -66,13 +69,34 @@ pub enum ArenaEvent {
     /// Zone collapse started
     CollapseStarted { phase: u8, new_safe_radius: f32 },
     /// Player entered danger zone (core)
-    PlayerEnteredCore { player_id: uuid::Uuid },
+    PlayerEnteredCore {
+        player_id: uuid::Uuid,
+        well_id: WellId,
+        position: crate::util::vec2::Vec2,
+    },
     /// Player is outside arena bounds
     PlayerOutsideArena { player_id: uuid::Uuid, mass_lost: f32 },
+    /// Player outside the arena was drained below minimum mass and died
+    PlayerDrained { player_id: uuid::Uuid },
 }
 
 /// Update arena state (zone collapse)
 pub fn update(state: &mut GameState, dt: f32) -> Vec<ArenaEvent> {
+    update_with_matrix(state, dt, &CollisionMatrix::default())
+}
+
+/// Update arena state, deciding which players well cores affect by collision layer
+pub fn update_with_matrix(state: &mut GameState, dt: f32, matrix: &CollisionMatrix) -> Vec<ArenaEvent> {
+    update_with_config(state, dt, matrix, &BoundaryConfig::default())
+}
+
+/// Update arena state with explicit collision layers and boundary rules
+pub fn update_with_config(
+    state: &mut GameState,
+    dt: f32,
+    matrix: &CollisionMatrix,
+    boundary: &BoundaryConfig,
+) -> Vec<ArenaEvent> {
     let mut events = Vec::new();
 
     // Only update arena during playing phase
@@ -84,7 +108,7 @@ pub fn update(state: &mut GameState, dt: f32) -> Vec<ArenaEvent> {
     // The arena stays at fixed size forever
 
     // Check players against arena boundaries
-    events.extend(check_player_boundaries(state, dt));
+    events.extend(check_player_boundaries(state, dt, matrix, boundary));
 
     events
 }
@@ -105,7 +129,12 @@ fn update_arena_radii(state: &mut GameState) {
 }
 
 /// Check player positions against arena boundaries
-fn check_player_boundaries(state: &mut GameState, dt: f32) -> Vec<ArenaEvent> {
+fn check_player_boundaries(
+    state: &mut GameState,
+    dt: f32,
+    matrix: &CollisionMatrix,
+    boundary: &BoundaryConfig,
+) -> Vec<ArenaEvent> {
     let mut events = Vec::new();
     let safe_radius = state.arena.current_safe_radius();
     let wells: Vec<_> = state.arena.gravity_wells.values().cloned().collect();
@@ -117,55 +146,84 @@ fn check_player_boundaries(state: &mut GameState, dt: f32) -> Vec<ArenaEvent> {
 
         // Check against all gravity well cores (instant death zones)
         // Use squared distance to avoid sqrt()
-        let mut in_core = false;
-        for well in &wells {
+        let mut core_well = None;
+        let hits_hazards = matrix.interacts(player_layer(player), CollisionLayer::Hazard);
+        for well in wells.iter().filter(|_| hits_hazards) {
             let dist_sq = player.position.distance_sq_to(well.position);
             let core_radius_sq = well.core_radius * well.core_radius;
             if dist_sq < core_radius_sq {
-                in_core = true;
+                core_well = Some(well.id);
                 break;
             }
         }
 
-        if in_core {
+        if let Some(well_id) = core_well {
             player.alive = false;
             player.deaths += 1;
             player.respawn_timer = RESPAWN_DELAY;
-            events.push(ArenaEvent::PlayerEnteredCore { player_id: player.id });
+            events.push(ArenaEvent::PlayerEnteredCore { player_id: player.id, well_id, position: player.position });
             continue;
         }
 
-        // Check outside safe zone (mass drain) - distance from arena center
-        // Skip mass drain for spawn-protected players (they're invulnerable)
-        if player.spawn_protection > 0.0 {
+        // Outside the safe zone - distance from arena center
+        let distance_from_center = player.position.length();
+        let excess = distance_from_center - safe_radius;
+        if excess <= 0.0 {
             continue;
         }
+        player.velocity += boundary_acceleration(player.position, player.velocity, excess, boundary) * dt;
 
-        let distance_from_center = player.position.length();
-        if distance_from_center > safe_radius {
-            let excess = distance_from_center - safe_radius;
-            let drain_rate = ESCAPE_MASS_DRAIN * (1.0 + excess / DRAIN_RATE_DISTANCE_DIVISOR); // Faster drain farther out
-            let mass_lost = drain_rate * dt;
-
-            player.mass = (player.mass - mass_lost).max(0.0);
-
-            events.push(ArenaEvent::PlayerOutsideArena {
-                player_id: player.id,
-                mass_lost,
-            });
-
-            // Check for death from mass loss
-            if player.mass < crate::game::constants::mass::MINIMUM {
-                player.alive = false;
-                player.deaths += 1;
-                player.respawn_timer = RESPAWN_DELAY;
-            }
+        // Skip mass drain for spawn-protected or shielded players
+        if player.spawn_protection > 0.0 || player.effects.has(EffectKind::Shield) {
+            continue;
+        }
+
+        // Drain grows from zero at the edge: faster farther out
+        let drain_rate = ESCAPE_MASS_DRAIN * excess / boundary.drain_ramp;
+        let mass_lost = drain_rate * dt;
+
+        player.mass = (player.mass - mass_lost).max(0.0);
+
+        events.push(ArenaEvent::PlayerOutsideArena {
+            player_id: player.id,
+            mass_lost,
+        });
+
+        // Check for death from mass loss
+        if player.mass < crate::game::constants::mass::MINIMUM {
+            player.alive = false;
+            player.deaths += 1;
+            player.respawn_timer = RESPAWN_DELAY;
+            events.push(ArenaEvent::PlayerDrained { player_id: player.id });
         }
     }
 
     events
 }
 
+/// Restoring acceleration for a body `excess` units beyond the safe radius:
+/// a spring pulling toward the center plus damping of outward radial speed
+pub fn boundary_acceleration(
+    position: crate::util::vec2::Vec2,
+    velocity: crate::util::vec2::Vec2,
+    excess: f32,
+    boundary: &BoundaryConfig,
+) -> crate::util::vec2::Vec2 {
+    let inward = -position.normalize();
+    let outward_speed = (-velocity.dot(inward)).max(0.0);
+    let accel = (boundary.stiffness * excess + boundary.damping * outward_speed).min(boundary.max_accel);
+    inward * accel
+}
+
+/// How strongly bots should steer back toward the center (0 inside the outer
+/// radius, easing to 1 at the escape radius). Continuous, so bots near the
+/// edge don't flip between behaviours from one tick to the next.
+pub fn boundary_weight(position: crate::util::vec2::Vec2, arena: &crate::game::state::Arena) -> f32 {
+    let band = (arena.escape_radius - arena.outer_radius).max(1.0);
+    let t = ((position.length() - arena.outer_radius) / band).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
 /// Get the zone a position is in
 pub fn get_zone(position: crate::util::vec2::Vec2, arena: &crate::game::state::Arena) -> Zone {
     let distance = position.length();
@@ -457,6 +515,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         };
         state.add_player(player);
         (state, player_id)
@@ -512,7 +574,22 @@ mod tests {
         assert!(!state.get_player(player_id).unwrap().alive);
         assert!(events
             .iter()
-            .any(|e| matches!(e, ArenaEvent::PlayerEnteredCore { .. })));
+            .any(|e| matches!(e, ArenaEvent::PlayerEnteredCore { well_id: CENTRAL_WELL_ID, .. })));
+    }
+
+    #[test]
+    fn test_core_ignores_layers_without_hazard() {
+        let (mut state, player_id) = create_test_state();
+        let player = state.get_player_mut(player_id).unwrap();
+        player.position = Vec2::new(25.0, 0.0);
+        player.spawn_protection = 3.0;
+
+        let matrix =
+            CollisionMatrix::default().with(CollisionLayer::Ghost, CollisionLayer::Hazard, false);
+        let events = update_with_matrix(&mut state, 0.1, &matrix);
+
+        assert!(state.get_player(player_id).unwrap().alive);
+        assert!(events.is_empty());
     }
 
     #[test]
@@ -529,6 +606,74 @@ mod tests {
             .any(|e| matches!(e, ArenaEvent::PlayerOutsideArena { .. })));
     }
 
+    #[test]
+    fn test_drain_ramps_from_zero_at_the_edge() {
+        let boundary = BoundaryConfig::default();
+        let drain_at = |distance: f32| {
+            let (mut state, player_id) = create_test_state();
+            state.get_player_mut(player_id).unwrap().position = Vec2::new(ESCAPE_RADIUS + distance, 0.0);
+            update(&mut state, 1.0);
+            100.0 - state.get_player(player_id).unwrap().mass
+        };
+
+        assert!(drain_at(1.0) < ESCAPE_MASS_DRAIN * 0.05);
+        assert!((drain_at(boundary.drain_ramp) - ESCAPE_MASS_DRAIN).abs() < 1e-3);
+        assert!(drain_at(2.0 * boundary.drain_ramp) > drain_at(boundary.drain_ramp));
+    }
+
+    #[test]
+    fn test_restoring_force_pulls_inward_and_damps() {
+        let boundary = BoundaryConfig::default();
+        let position = Vec2::new(ESCAPE_RADIUS + 50.0, 0.0);
+
+        let at_rest = boundary_acceleration(position, Vec2::ZERO, 50.0, &boundary);
+        assert!(at_rest.x < 0.0 && at_rest.y.abs() < 1e-6);
+        assert!((at_rest.x + boundary.stiffness * 50.0).abs() < 1e-3);
+
+        // Moving outward is damped harder; moving back in is not resisted
+        let outward = boundary_acceleration(position, Vec2::new(40.0, 0.0), 50.0, &boundary);
+        let inward = boundary_acceleration(position, Vec2::new(-40.0, 0.0), 50.0, &boundary);
+        assert!(outward.x < at_rest.x);
+        assert_eq!(inward, at_rest);
+
+        let far = boundary_acceleration(position * 10.0, Vec2::new(500.0, 0.0), 10_000.0, &boundary);
+        assert!((far.length() - boundary.max_accel).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_boundary_turns_escaping_player_around() {
+        let (mut state, player_id) = create_test_state();
+        let player = state.get_player_mut(player_id).unwrap();
+        player.position = Vec2::new(ESCAPE_RADIUS, 0.0);
+        player.velocity = Vec2::new(60.0, 0.0);
+
+        let mut max_distance: f32 = 0.0;
+        for _ in 0..600 {
+            crate::game::systems::physics::update(&mut state, 1.0 / 30.0);
+            update(&mut state, 1.0 / 30.0);
+            max_distance = max_distance.max(state.get_player(player_id).unwrap().position.length());
+        }
+        let player = state.get_player(player_id).unwrap();
+        assert!(max_distance < ESCAPE_RADIUS + 100.0, "drifted to {max_distance}");
+        assert!(player.position.length() < ESCAPE_RADIUS + 5.0);
+    }
+
+    #[test]
+    fn test_boundary_weight_is_continuous() {
+        let arena = Arena::default();
+        assert_eq!(boundary_weight(Vec2::new(arena.outer_radius - 1.0, 0.0), &arena), 0.0);
+        assert_eq!(boundary_weight(Vec2::new(arena.escape_radius + 50.0, 0.0), &arena), 1.0);
+
+        // No jumps between neighbouring positions across the whole band
+        let mut previous = 0.0;
+        for step in 0..=400 {
+            let r = arena.outer_radius - 10.0 + step as f32;
+            let weight = boundary_weight(Vec2::new(r, 0.0), &arena);
+            assert!(weight >= previous && weight - previous < 0.01, "jump at r={r}");
+            previous = weight;
+        }
+    }
+
     #[test]
     fn test_collapse_disabled_for_eternal_mode() {
         let (mut state, _) = create_test_state();
@@ -675,6 +820,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         };
         state.add_player(player);
 
@@ -726,6 +875,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         };
         state.add_player(player);
 
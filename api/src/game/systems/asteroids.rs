@@ -0,0 +1,198 @@
+//! Destructible asteroids
+//!
+//! Asteroids are fixed rocks scattered through the outer part of the arena.
+//! The collision system stops every projectile that touches one, taking the
+//! projectile's mass off its health, and bounces players off it. At zero
+//! health an asteroid breaks: each loot table entry is rolled and the
+//! fragments scatter where it stood (capped by the debris config, see
+//! [`debris::spawn_fragments`]). A replacement appears elsewhere
+//! `respawn_secs` later, so the field stays at `count` asteroids.
+
+use rand::Rng;
+
+use crate::config::{AsteroidConfig, DebrisSpawnConfig};
+use crate::game::state::{DebrisSize, EntityId, GameState};
+use crate::game::systems::debris;
+use crate::util::vec2::Vec2;
+
+/// Asteroids are placed between these shares of the escape radius
+const PLACEMENT_MIN_RADIUS_SHARE: f32 = 0.45;
+const PLACEMENT_MAX_RADIUS_SHARE: f32 = 0.9;
+
+/// Clearance around gravity wells, in core radii (past their debris rings)
+const WELL_CLEARANCE_MULTIPLIER: f32 = 6.0;
+
+/// Gap kept between asteroids and from alive players when placing one
+const PLACEMENT_GAP: f32 = 40.0;
+
+/// Random positions tried before giving up on a placement until the next tick
+const PLACEMENT_ATTEMPTS: usize = 16;
+
+/// An asteroid that broke this tick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrokenAsteroid {
+    pub asteroid_id: EntityId,
+    pub position: Vec2,
+    /// Debris fragments it left behind
+    pub fragments: usize,
+}
+
+/// Breaks asteroids and replaces them over time
+#[derive(Debug, Default)]
+pub struct AsteroidField {
+    /// Seconds until each pending replacement appears
+    respawns: Vec<f32>,
+}
+
+impl AsteroidField {
+    /// Fill the arena with `config.count` fresh asteroids (a match is starting)
+    pub fn spawn_initial(&mut self, state: &mut GameState, config: &AsteroidConfig) {
+        self.respawns.clear();
+        state.asteroids.clear();
+        if !config.enabled {
+            return;
+        }
+        for _ in 0..config.count {
+            if !place(state, config) {
+                // Try again later rather than overlapping
+                self.respawns.push(0.0);
+            }
+        }
+    }
+
+    /// Break asteroids out of health and place due replacements
+    pub fn update(
+        &mut self,
+        state: &mut GameState,
+        config: &AsteroidConfig,
+        debris_config: &DebrisSpawnConfig,
+        dt: f32,
+    ) -> Vec<BrokenAsteroid> {
+        let mut broken = Vec::new();
+        let mut i = 0;
+        while i < state.asteroids.len() {
+            if !state.asteroids[i].is_broken() {
+                i += 1;
+                continue;
+            }
+            let asteroid = state.asteroids.swap_remove(i);
+            let loot = roll_loot(config);
+            let fragments = debris::spawn_fragments(state, debris_config, asteroid.position, asteroid.radius, &loot);
+            broken.push(BrokenAsteroid { asteroid_id: asteroid.id, position: asteroid.position, fragments });
+            self.respawns.push(config.respawn_secs);
+        }
+
+        if !config.enabled {
+            self.respawns.clear();
+            return broken;
+        }
+        for timer in &mut self.respawns {
+            *timer -= dt;
+        }
+        let mut pending = std::mem::take(&mut self.respawns);
+        pending.retain(|&timer| timer > 0.0 || !place(state, config));
+        self.respawns = pending;
+
+        broken
+    }
+}
+
+/// Fragment sizes dropped by one broken asteroid
+fn roll_loot(config: &AsteroidConfig) -> Vec<DebrisSize> {
+    let mut rng = rand::thread_rng();
+    let mut sizes = Vec::new();
+    for entry in &config.loot {
+        let count = rng.gen_range(entry.min..=entry.max);
+        sizes.extend(std::iter::repeat(entry.size).take(count as usize));
+    }
+    sizes
+}
+
+/// Add one asteroid clear of wells, other asteroids and players. False when
+/// no free spot turned up.
+fn place(state: &mut GameState, config: &AsteroidConfig) -> bool {
+    let mut rng = rand::thread_rng();
+    let escape_radius = state.arena.escape_radius;
+    let clear = |position: Vec2| {
+        let wells_clear = state.arena.gravity_wells.values().all(|w| {
+            w.position.distance_to(position) > w.core_radius * WELL_CLEARANCE_MULTIPLIER + config.radius
+        });
+        let asteroids_clear = state
+            .asteroids
+            .iter()
+            .all(|a| a.position.distance_to(position) > a.radius + config.radius + PLACEMENT_GAP);
+        let players_clear = state
+            .alive_players()
+            .all(|p| p.position.distance_to(position) > p.radius() + config.radius + PLACEMENT_GAP);
+        wells_clear && asteroids_clear && players_clear
+    };
+
+    for _ in 0..PLACEMENT_ATTEMPTS {
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let distance =
+            rng.gen_range(PLACEMENT_MIN_RADIUS_SHARE..PLACEMENT_MAX_RADIUS_SHARE) * escape_radius;
+        let position = Vec2::from_angle(angle) * distance;
+        if clear(position) {
+            state.add_asteroid(position, config.radius, config.health);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AsteroidLoot;
+
+    fn config() -> AsteroidConfig {
+        AsteroidConfig { enabled: true, count: 4, ..Default::default() }
+    }
+
+    #[test]
+    fn test_initial_field_is_spread_out() {
+        let mut state = GameState::new();
+        let config = config();
+        AsteroidField::default().spawn_initial(&mut state, &config);
+
+        assert_eq!(state.asteroids.len(), config.count);
+        for (i, a) in state.asteroids.iter().enumerate() {
+            assert!(a.position.length() >= state.arena.escape_radius * PLACEMENT_MIN_RADIUS_SHARE);
+            for b in &state.asteroids[i + 1..] {
+                assert!(a.position.distance_to(b.position) > a.radius + b.radius);
+            }
+        }
+
+        let disabled = AsteroidConfig::default();
+        AsteroidField::default().spawn_initial(&mut state, &disabled);
+        assert!(state.asteroids.is_empty());
+    }
+
+    #[test]
+    fn test_broken_asteroid_drops_loot_and_respawns() {
+        let mut state = GameState::new();
+        let config = AsteroidConfig {
+            loot: vec![AsteroidLoot { size: DebrisSize::Medium, min: 3, max: 3 }],
+            respawn_secs: 1.0,
+            ..config()
+        };
+        let debris_config = DebrisSpawnConfig::default();
+        let mut field = AsteroidField::default();
+        field.spawn_initial(&mut state, &config);
+        let target = state.asteroids[0].clone();
+        state.asteroids[0].health = 0.0;
+
+        let broken = field.update(&mut state, &config, &debris_config, 0.5);
+        assert_eq!(broken, vec![BrokenAsteroid { asteroid_id: target.id, position: target.position, fragments: 3 }]);
+        assert_eq!(state.debris.len(), 3);
+        assert!(state.debris.iter().all(|d| d.size == DebrisSize::Medium));
+        assert_eq!(state.asteroids.len(), config.count - 1);
+
+        // Replaced once the respawn timer runs out
+        assert!(field.update(&mut state, &config, &debris_config, 0.4).is_empty());
+        assert_eq!(state.asteroids.len(), config.count - 1);
+        field.update(&mut state, &config, &debris_config, 0.2);
+        assert_eq!(state.asteroids.len(), config.count);
+        assert!(state.asteroids.iter().all(|a| a.id != target.id && a.health == config.health));
+    }
+}
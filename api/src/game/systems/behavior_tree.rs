@@ -0,0 +1,319 @@
+//! Data-driven bot behavior trees
+//!
+//! Optional replacement for the hardcoded behavior switch used by Full-LOD bots
+//! in `ai_soa`. Trees are loaded from a JSON file (`AI_SOA_BEHAVIOR_TREE`);
+//! Reduced/Dormant bots keep the fast hardcoded path.
+//!
+//! # Format
+//!
+//! ```json
+//! { "type": "selector", "children": [
+//!     { "type": "sequence", "children": [
+//!         { "type": "condition", "input": "threat_level", "op": "gt", "value": 1.2 },
+//!         { "type": "condition", "input": "random", "op": "gt", "value": "aggression" },
+//!         { "type": "action", "behavior": "flee" } ] },
+//!     { "type": "action", "behavior": "orbit" } ] }
+//! ```
+//!
+//! Condition `value` is either a number or another input name.
+
+use std::sync::{Arc, OnceLock};
+
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::game::state::PlayerId;
+use crate::game::systems::ai_soa::{AiBehavior, AiSoaConfig};
+use crate::util::vec2::Vec2;
+
+/// Maximum nesting depth accepted when loading a tree
+const MAX_DEPTH: usize = 16;
+
+/// Maximum total node count accepted when loading a tree
+const MAX_NODES: usize = 256;
+
+/// Global tree loaded from `AiSoaConfig::behavior_tree_path` (None = hardcoded path)
+static GLOBAL_TREE: OnceLock<Option<Arc<BehaviorTree>>> = OnceLock::new();
+
+/// Values a condition can read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BtInput {
+    /// Bot mass / nearest human mass (0 when no human is alive)
+    MassRatio,
+    /// Distance to the nearest alive human (infinity when none)
+    HumanDistance,
+    /// Strongest nearby zone threat mass / bot mass (0 when no threat)
+    ThreatLevel,
+    /// 1.0 when debris exists in the arena, else 0.0
+    HasDebris,
+    /// Bot personality aggression (0-1)
+    Aggression,
+    /// Fresh uniform sample in 0..1 per read
+    Random,
+}
+
+/// Right-hand side of a condition
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum BtOperand {
+    Value(f32),
+    Input(BtInput),
+}
+
+/// Comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BtOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Behavior selected by an action leaf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BtBehavior {
+    Orbit,
+    Chase,
+    Flee,
+    Collect,
+    Idle,
+}
+
+impl From<BtBehavior> for AiBehavior {
+    fn from(b: BtBehavior) -> Self {
+        match b {
+            BtBehavior::Orbit => AiBehavior::Orbit,
+            BtBehavior::Chase => AiBehavior::Chase,
+            BtBehavior::Flee => AiBehavior::Flee,
+            BtBehavior::Collect => AiBehavior::Collect,
+            BtBehavior::Idle => AiBehavior::Idle,
+        }
+    }
+}
+
+/// Behavior tree node
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BtNode {
+    /// Succeeds with the first child that succeeds
+    Selector { children: Vec<BtNode> },
+    /// Succeeds only if every child succeeds (stops at first failure)
+    Sequence { children: Vec<BtNode> },
+    /// Succeeds when `input op value` holds
+    Condition { input: BtInput, op: BtOp, value: BtOperand },
+    /// Always succeeds, selecting a behavior
+    Action { behavior: BtBehavior },
+}
+
+/// Per-bot inputs gathered before evaluating a tree
+#[derive(Debug, Clone, Copy)]
+pub struct BtContext {
+    pub mass_ratio: f32,
+    pub human_distance: f32,
+    pub threat_level: f32,
+    pub has_debris: bool,
+    pub aggression: f32,
+    pub nearest_human: Option<PlayerId>,
+    /// Unit vector pointing away from the strongest threat
+    pub flee_direction: Vec2,
+}
+
+impl BtContext {
+    fn read<R: Rng>(&self, input: BtInput, rng: &mut R) -> f32 {
+        match input {
+            BtInput::MassRatio => self.mass_ratio,
+            BtInput::HumanDistance => self.human_distance,
+            BtInput::ThreatLevel => self.threat_level,
+            BtInput::HasDebris => {
+                if self.has_debris {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            BtInput::Aggression => self.aggression,
+            BtInput::Random => rng.gen::<f32>(),
+        }
+    }
+}
+
+/// A validated behavior tree
+#[derive(Debug, Clone)]
+pub struct BehaviorTree {
+    root: BtNode,
+}
+
+impl BehaviorTree {
+    /// Parse and validate a tree from JSON
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let root: BtNode =
+            serde_json::from_str(json).map_err(|e| format!("Invalid behavior tree: {}", e))?;
+        let mut nodes = 0;
+        validate(&root, 1, &mut nodes)?;
+        Ok(Self { root })
+    }
+
+    /// Load a tree from a JSON file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read behavior tree {}: {}", path, e))?;
+        Self::from_json(&contents)
+    }
+
+    /// Global tree configured via `AI_SOA_BEHAVIOR_TREE` (loaded once)
+    pub fn global() -> Option<Arc<Self>> {
+        GLOBAL_TREE
+            .get_or_init(|| {
+                let path = AiSoaConfig::global().behavior_tree_path.as_ref()?;
+                match Self::load(path) {
+                    Ok(tree) => {
+                        tracing::info!("Loaded bot behavior tree from {}", path);
+                        Some(Arc::new(tree))
+                    }
+                    Err(e) => {
+                        tracing::warn!("{}, using hardcoded bot behaviors", e);
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    /// Evaluate the tree; returns the selected behavior or None if no action fired
+    pub fn evaluate<R: Rng>(&self, ctx: &BtContext, rng: &mut R) -> Option<AiBehavior> {
+        let mut selected = None;
+        if eval_node(&self.root, ctx, rng, &mut selected) {
+            selected.map(AiBehavior::from)
+        } else {
+            None
+        }
+    }
+}
+
+fn validate(node: &BtNode, depth: usize, nodes: &mut usize) -> Result<(), String> {
+    *nodes += 1;
+    if depth > MAX_DEPTH {
+        return Err(format!("Behavior tree deeper than {} levels", MAX_DEPTH));
+    }
+    if *nodes > MAX_NODES {
+        return Err(format!("Behavior tree has more than {} nodes", MAX_NODES));
+    }
+    match node {
+        BtNode::Selector { children } | BtNode::Sequence { children } => {
+            if children.is_empty() {
+                return Err("Selector/sequence nodes need at least one child".to_string());
+            }
+            for child in children {
+                validate(child, depth + 1, nodes)?;
+            }
+            Ok(())
+        }
+        BtNode::Condition { value: BtOperand::Value(v), .. } if !v.is_finite() => {
+            Err("Condition values must be finite".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn eval_node<R: Rng>(
+    node: &BtNode,
+    ctx: &BtContext,
+    rng: &mut R,
+    selected: &mut Option<BtBehavior>,
+) -> bool {
+    match node {
+        BtNode::Selector { children } => children.iter().any(|c| eval_node(c, ctx, rng, selected)),
+        BtNode::Sequence { children } => children.iter().all(|c| eval_node(c, ctx, rng, selected)),
+        BtNode::Condition { input, op, value } => {
+            let lhs = ctx.read(*input, rng);
+            let rhs = match value {
+                BtOperand::Value(v) => *v,
+                BtOperand::Input(i) => ctx.read(*i, rng),
+            };
+            match op {
+                BtOp::Lt => lhs < rhs,
+                BtOp::Le => lhs <= rhs,
+                BtOp::Gt => lhs > rhs,
+                BtOp::Ge => lhs >= rhs,
+            }
+        }
+        BtNode::Action { behavior } => {
+            *selected = Some(*behavior);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLEE_OR_ORBIT: &str = r#"
+        { "type": "selector", "children": [
+            { "type": "sequence", "children": [
+                { "type": "condition", "input": "threat_level", "op": "gt", "value": 1.2 },
+                { "type": "action", "behavior": "flee" } ] },
+            { "type": "sequence", "children": [
+                { "type": "condition", "input": "mass_ratio", "op": "ge", "value": 0.8 },
+                { "type": "condition", "input": "human_distance", "op": "lt", "value": 400 },
+                { "type": "action", "behavior": "chase" } ] },
+            { "type": "action", "behavior": "orbit" } ] }
+    "#;
+
+    fn ctx() -> BtContext {
+        BtContext {
+            mass_ratio: 0.0,
+            human_distance: f32::INFINITY,
+            threat_level: 0.0,
+            has_debris: false,
+            aggression: 0.5,
+            nearest_human: None,
+            flee_direction: Vec2::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_selector_picks_first_matching_branch() {
+        let tree = BehaviorTree::from_json(FLEE_OR_ORBIT).unwrap();
+        let mut rng = rand::thread_rng();
+
+        assert_eq!(tree.evaluate(&ctx(), &mut rng), Some(AiBehavior::Orbit));
+
+        let threatened = BtContext { threat_level: 2.0, ..ctx() };
+        assert_eq!(tree.evaluate(&threatened, &mut rng), Some(AiBehavior::Flee));
+
+        let hunter = BtContext { mass_ratio: 1.5, human_distance: 100.0, ..ctx() };
+        assert_eq!(tree.evaluate(&hunter, &mut rng), Some(AiBehavior::Chase));
+    }
+
+    #[test]
+    fn test_condition_can_compare_inputs() {
+        let json = r#"
+            { "type": "sequence", "children": [
+                { "type": "condition", "input": "aggression", "op": "gt", "value": "mass_ratio" },
+                { "type": "action", "behavior": "idle" } ] }
+        "#;
+        let tree = BehaviorTree::from_json(json).unwrap();
+        let mut rng = rand::thread_rng();
+
+        assert_eq!(tree.evaluate(&BtContext { mass_ratio: 0.1, ..ctx() }, &mut rng), Some(AiBehavior::Idle));
+        assert_eq!(tree.evaluate(&BtContext { mass_ratio: 0.9, ..ctx() }, &mut rng), None);
+    }
+
+    #[test]
+    fn test_rejects_invalid_trees() {
+        assert!(BehaviorTree::from_json("{}").is_err());
+        assert!(BehaviorTree::from_json(r#"{ "type": "selector", "children": [] }"#).is_err());
+        assert!(BehaviorTree::from_json(r#"{ "type": "action", "behavior": "dance" }"#).is_err());
+
+        // Excessive nesting
+        let mut json = r#"{ "type": "action", "behavior": "orbit" }"#.to_string();
+        for _ in 0..MAX_DEPTH {
+            json = format!(r#"{{ "type": "sequence", "children": [{}] }}"#, json);
+        }
+        assert!(BehaviorTree::from_json(&json).is_err());
+    }
+}
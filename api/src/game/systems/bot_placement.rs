@@ -0,0 +1,177 @@
+//! Bot spawn placement
+//!
+//! Places a configurable share of bot spawns within view of human players,
+//! so the arena around them feels populated, and spreads the rest across all
+//! wells as before. Near-human spawns go to the human whose surroundings hold
+//! the fewest bots according to the AI zone grid, so bots fill thin areas
+//! instead of piling up around whoever joined first.
+
+use crate::config::BotPlacementConfig;
+use crate::game::state::{GameState, GravityWell, CENTRAL_WELL_ID};
+use crate::game::systems::ai_soa::ZoneGrid;
+use crate::game::systems::arena;
+use crate::util::vec2::Vec2;
+
+/// Tracks placements so the near-human share holds over time
+#[derive(Debug, Clone, Default)]
+pub struct BotPlacer {
+    near_human: u32,
+    total: u32,
+}
+
+impl BotPlacer {
+    /// Pick a spawn position for a bot, away from `existing_positions`
+    pub fn place(
+        &mut self,
+        state: &GameState,
+        zones: &ZoneGrid,
+        config: &BotPlacementConfig,
+        existing_positions: &[Vec2],
+    ) -> Vec2 {
+        let wants_near_human = (self.near_human as f32) < config.near_human_share * (self.total + 1) as f32;
+        self.total += 1;
+
+        if wants_near_human {
+            if let Some(wells) = wells_near_sparsest_human(state, zones, config) {
+                self.near_human += 1;
+                return arena::safe_spawn_near_well(&wells, existing_positions);
+            }
+        }
+
+        let wells: Vec<_> = state.arena.gravity_wells.values().cloned().collect();
+        arena::safe_spawn_near_well(&wells, existing_positions)
+    }
+}
+
+/// Bots in the zone-grid neighbourhood around a position
+fn nearby_bots(zones: &ZoneGrid, position: Vec2) -> u32 {
+    zones
+        .adjacent_cells(zones.position_to_cell(position))
+        .filter_map(|cell| zones.get_zone(cell))
+        .map(|zone| zone.bot_count)
+        .sum()
+}
+
+/// Orbital wells within view of the alive human with the fewest bots nearby.
+/// None when there are no humans, or none has an orbital well in range.
+fn wells_near_sparsest_human(
+    state: &GameState,
+    zones: &ZoneGrid,
+    config: &BotPlacementConfig,
+) -> Option<Vec<GravityWell>> {
+    let radius_sq = config.near_human_radius * config.near_human_radius;
+    let mut humans: Vec<(Vec2, u32)> = state
+        .players
+        .values()
+        .filter(|p| p.alive && !p.is_bot)
+        .map(|p| (p.position, nearby_bots(zones, p.position)))
+        .collect();
+    humans.sort_by_key(|&(_, bots)| bots);
+
+    humans.into_iter().find_map(|(position, _)| {
+        let wells: Vec<_> = state
+            .arena
+            .gravity_wells
+            .values()
+            .filter(|w| w.id != CENTRAL_WELL_ID && w.position.distance_sq_to(position) <= radius_sq)
+            .cloned()
+            .collect();
+        (!wells.is_empty()).then_some(wells)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::constants::spawn::ZONE_MAX;
+    use crate::game::state::{Player, PlayerId, WellId};
+    use crate::game::systems::ai_soa::AiManagerSoA;
+
+    fn add_player(state: &mut GameState, position: Vec2, is_bot: bool) -> PlayerId {
+        let player = Player {
+            id: uuid::Uuid::new_v4(),
+            name: "P".to_string(),
+            position,
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass: 100.0,
+            alive: true,
+            kills: 0,
+            deaths: 0,
+            spawn_protection: 0.0,
+            is_bot,
+            color_index: 0,
+            respawn_timer: 0.0,
+            spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
+        };
+        let id = player.id;
+        state.add_player(player);
+        id
+    }
+
+    /// Orbital wells spread along the x axis, 3000 units apart
+    fn arena_with_wells(count: u32) -> GameState {
+        let mut state = GameState::new();
+        for i in 1..=count {
+            let id = i as WellId;
+            state.arena.insert_well(GravityWell::new(id, Vec2::new(3000.0 * i as f32, 0.0), 5000.0, 50.0));
+        }
+        state
+    }
+
+    fn within_view(position: Vec2, human: Vec2, config: &BotPlacementConfig) -> bool {
+        position.distance_to(human) <= config.near_human_radius + ZONE_MAX
+    }
+
+    #[test]
+    fn test_share_of_bots_spawn_near_humans() {
+        let config = BotPlacementConfig { near_human_share: 0.5, ..Default::default() };
+        let mut state = arena_with_wells(16);
+        let human = Vec2::new(3000.0, 300.0);
+        add_player(&mut state, human, false);
+
+        let mut placer = BotPlacer::default();
+        let zones = ZoneGrid::default();
+        let near = (0..20)
+            .map(|_| placer.place(&state, &zones, &config, &[]))
+            .filter(|&p| within_view(p, human, &config))
+            .count();
+        // Half placed near the human on purpose, plus the odd random spread spawn
+        assert!((10..=14).contains(&near), "{near} of 20 near the human");
+    }
+
+    #[test]
+    fn test_prefers_human_with_fewest_bots_nearby() {
+        let config = BotPlacementConfig { near_human_share: 1.0, ..Default::default() };
+        let mut state = arena_with_wells(8);
+        let crowded = Vec2::new(3000.0, 300.0);
+        let lonely = Vec2::new(21_000.0, 300.0);
+        add_player(&mut state, crowded, false);
+        add_player(&mut state, lonely, false);
+        let mut ai = AiManagerSoA::default();
+        for i in 0..5 {
+            ai.register_bot(add_player(&mut state, Vec2::new(2900.0 + i as f32 * 20.0, 500.0), true));
+        }
+        ai.update_zones(&state);
+
+        let position = BotPlacer::default().place(&state, &ai.zone_grid, &config, &[]);
+        assert!(within_view(position, lonely, &config), "spawned at {position:?}");
+    }
+
+    #[test]
+    fn test_spreads_when_no_human_in_range() {
+        let config = BotPlacementConfig { near_human_share: 1.0, ..Default::default() };
+        let mut state = arena_with_wells(2);
+        // No orbital well within view of this human
+        add_player(&mut state, Vec2::new(-5000.0, 0.0), false);
+
+        let mut placer = BotPlacer::default();
+        let position = placer.place(&state, &ZoneGrid::default(), &config, &[]);
+        assert!(state.arena.gravity_wells.values().any(|w| w.position.distance_to(position) <= ZONE_MAX + 1.0));
+        assert_eq!(placer.near_human, 0);
+    }
+}
@@ -0,0 +1,346 @@
+//! Learned bot policy (tiny MLP inference)
+//!
+//! Maps a compact observation vector to bot actions for "elite" bots.
+//! Weights are loaded from a JSON file (`AI_SOA_POLICY_PATH`) and inference is
+//! batched across all elite bots each tick, layer by layer, so the cost is a
+//! handful of small dense matrix products rather than per-bot allocations.
+//!
+//! # Format
+//!
+//! ```json
+//! { "layers": [
+//!     { "weights": [[...OBS_DIM floats...], ...], "biases": [...], "activation": "tanh" },
+//!     { "weights": [[...]], "biases": [...ACT_DIM floats...], "activation": "linear" } ] }
+//! ```
+//! `weights` is row-major: one row per output neuron.
+
+use std::sync::{Arc, OnceLock};
+
+use rayon::prelude::*;
+use serde::Deserialize;
+
+use crate::game::state::{GameState, Player, PlayerId};
+use crate::game::systems::ai_soa::AiSoaConfig;
+use crate::util::vec2::Vec2;
+
+/// Observation vector length
+/// [vel_x, vel_y, human_dx, human_dy, mass_ratio, well_dx, well_dy, center_dist, mass, has_human]
+pub const OBS_DIM: usize = 10;
+
+/// Action vector length
+/// [thrust_x, thrust_y, aim_x, aim_y, boost, fire] (boost/fire fire when > 0)
+pub const ACT_DIM: usize = 6;
+
+/// Batch size at which inference switches to rayon
+const MIN_PARALLEL_BATCH: usize = 64;
+
+/// Observation normalization scales
+const VELOCITY_SCALE: f32 = 500.0;
+const DISTANCE_SCALE: f32 = 1000.0;
+const MASS_SCALE: f32 = 100.0;
+const MAX_MASS_RATIO: f32 = 4.0;
+
+/// Global policy loaded from `AiSoaConfig::policy_path` (None = no elite bots)
+static GLOBAL_POLICY: OnceLock<Option<Arc<MlpPolicy>>> = OnceLock::new();
+
+/// Layer activation function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activation {
+    Linear,
+    Relu,
+    Tanh,
+}
+
+impl Activation {
+    #[inline]
+    fn apply(self, x: f32) -> f32 {
+        match self {
+            Activation::Linear => x,
+            Activation::Relu => x.max(0.0),
+            Activation::Tanh => x.tanh(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LayerFile {
+    weights: Vec<Vec<f32>>,
+    biases: Vec<f32>,
+    activation: Activation,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolicyFile {
+    layers: Vec<LayerFile>,
+}
+
+/// Dense layer with flattened row-major weights
+#[derive(Debug, Clone)]
+struct DenseLayer {
+    in_dim: usize,
+    out_dim: usize,
+    weights: Vec<f32>,
+    biases: Vec<f32>,
+    activation: Activation,
+}
+
+impl DenseLayer {
+    /// Compute one sample's outputs
+    #[inline]
+    fn forward_one(&self, input: &[f32], output: &mut [f32]) {
+        for (o, out) in output.iter_mut().enumerate() {
+            let row = &self.weights[o * self.in_dim..(o + 1) * self.in_dim];
+            let sum: f32 = row.iter().zip(input).map(|(w, x)| w * x).sum();
+            *out = self.activation.apply(sum + self.biases[o]);
+        }
+    }
+}
+
+/// Multi-layer perceptron policy
+#[derive(Debug, Clone)]
+pub struct MlpPolicy {
+    layers: Vec<DenseLayer>,
+}
+
+impl MlpPolicy {
+    /// Parse and validate a policy from JSON
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: PolicyFile =
+            serde_json::from_str(json).map_err(|e| format!("Invalid policy file: {}", e))?;
+        if file.layers.is_empty() {
+            return Err("Policy needs at least one layer".to_string());
+        }
+
+        let mut layers = Vec::with_capacity(file.layers.len());
+        let mut expected_in = OBS_DIM;
+        for (i, layer) in file.layers.into_iter().enumerate() {
+            let out_dim = layer.weights.len();
+            if out_dim == 0 || layer.biases.len() != out_dim {
+                return Err(format!("Layer {}: weights/biases size mismatch", i));
+            }
+            if layer.weights.iter().any(|row| row.len() != expected_in) {
+                return Err(format!("Layer {}: expected {} inputs per row", i, expected_in));
+            }
+            let weights: Vec<f32> = layer.weights.into_iter().flatten().collect();
+            if weights.iter().chain(&layer.biases).any(|v| !v.is_finite()) {
+                return Err(format!("Layer {}: non-finite parameter", i));
+            }
+            layers.push(DenseLayer {
+                in_dim: expected_in,
+                out_dim,
+                weights,
+                biases: layer.biases,
+                activation: layer.activation,
+            });
+            expected_in = out_dim;
+        }
+        if expected_in != ACT_DIM {
+            return Err(format!("Final layer must output {} actions, got {}", ACT_DIM, expected_in));
+        }
+
+        Ok(Self { layers })
+    }
+
+    /// Load a policy from a JSON file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read policy {}: {}", path, e))?;
+        Self::from_json(&contents)
+    }
+
+    /// Global policy configured via `AI_SOA_POLICY_PATH` (loaded once)
+    pub fn global() -> Option<Arc<Self>> {
+        GLOBAL_POLICY
+            .get_or_init(|| {
+                let path = AiSoaConfig::global().policy_path.as_ref()?;
+                match Self::load(path) {
+                    Ok(policy) => {
+                        tracing::info!("Loaded elite bot policy from {} ({} layers)", path, policy.layers.len());
+                        Some(Arc::new(policy))
+                    }
+                    Err(e) => {
+                        tracing::warn!("{}, elite bots disabled", e);
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    /// Widest layer (scratch buffer sizing)
+    fn max_width(&self) -> usize {
+        self.layers.iter().map(|l| l.out_dim).max().unwrap_or(0).max(OBS_DIM)
+    }
+
+    /// Run inference for `obs.len() / OBS_DIM` samples, writing `ACT_DIM` actions per sample
+    /// OPTIMIZATION: Processes the whole batch per layer with two reused buffers;
+    /// large batches are split across rayon workers.
+    pub fn forward_batch(&self, obs: &[f32], actions: &mut Vec<f32>) {
+        debug_assert_eq!(obs.len() % OBS_DIM, 0);
+        let batch = obs.len() / OBS_DIM;
+        actions.clear();
+        actions.resize(batch * ACT_DIM, 0.0);
+        if batch == 0 {
+            return;
+        }
+
+        let width = self.max_width();
+        let run = |sample: &[f32], out: &mut [f32]| {
+            let mut a = vec![0.0f32; width];
+            let mut b = vec![0.0f32; width];
+            a[..OBS_DIM].copy_from_slice(sample);
+            let mut in_dim = OBS_DIM;
+            for layer in &self.layers {
+                layer.forward_one(&a[..in_dim], &mut b[..layer.out_dim]);
+                std::mem::swap(&mut a, &mut b);
+                in_dim = layer.out_dim;
+            }
+            out.copy_from_slice(&a[..ACT_DIM]);
+        };
+
+        if batch >= MIN_PARALLEL_BATCH {
+            actions
+                .par_chunks_mut(ACT_DIM)
+                .zip(obs.par_chunks(OBS_DIM))
+                .for_each(|(out, sample)| run(sample, out));
+        } else {
+            for (out, sample) in actions.chunks_mut(ACT_DIM).zip(obs.chunks(OBS_DIM)) {
+                run(sample, out);
+            }
+        }
+    }
+}
+
+/// Build the observation vector for a bot, appending `OBS_DIM` values to `out`
+pub fn observe(bot: &Player, state: &GameState, humans: &[(PlayerId, Vec2, f32)], out: &mut Vec<f32>) {
+    let nearest_human = humans
+        .iter()
+        .map(|&(_, pos, mass)| (pos, mass, bot.position.distance_sq_to(pos)))
+        .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let nearest_well = state
+        .arena
+        .gravity_wells
+        .values()
+        .map(|w| (w.position, bot.position.distance_sq_to(w.position)))
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let (human_rel, mass_ratio, has_human) = match nearest_human {
+        Some((pos, mass, _)) => {
+            let ratio = if mass > 0.0 { (bot.mass / mass).min(MAX_MASS_RATIO) } else { 0.0 };
+            ((pos - bot.position) * (1.0 / DISTANCE_SCALE), ratio, 1.0)
+        }
+        None => (Vec2::ZERO, 0.0, 0.0),
+    };
+    let well_rel = nearest_well
+        .map(|(pos, _)| (pos - bot.position) * (1.0 / DISTANCE_SCALE))
+        .unwrap_or(Vec2::ZERO);
+    let escape_radius = state.arena.escape_radius.max(1.0);
+
+    out.extend_from_slice(&[
+        bot.velocity.x / VELOCITY_SCALE,
+        bot.velocity.y / VELOCITY_SCALE,
+        human_rel.x,
+        human_rel.y,
+        mass_ratio,
+        well_rel.x,
+        well_rel.y,
+        bot.position.length() / escape_radius,
+        bot.mass / MASS_SCALE,
+        has_human,
+    ]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Single linear layer that copies selected observations to actions
+    fn identity_policy_json() -> String {
+        let mut rows = vec![vec![0.0f32; OBS_DIM]; ACT_DIM];
+        rows[0][2] = 1.0; // thrust_x <- human_dx
+        rows[1][3] = 1.0; // thrust_y <- human_dy
+        rows[2][2] = 1.0; // aim_x <- human_dx
+        rows[3][3] = 1.0; // aim_y <- human_dy
+        rows[5][9] = 1.0; // fire <- has_human
+        serde_json::json!({
+            "layers": [{ "weights": rows, "biases": [0.0, 0.0, 0.0, 0.0, -1.0, -0.5], "activation": "linear" }]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_forward_batch_linear() {
+        let policy = MlpPolicy::from_json(&identity_policy_json()).unwrap();
+        let mut obs = vec![0.0f32; OBS_DIM * 2];
+        obs[2] = 0.5;
+        obs[9] = 1.0;
+        obs[OBS_DIM + 3] = -0.25;
+
+        let mut actions = Vec::new();
+        policy.forward_batch(&obs, &mut actions);
+
+        assert_eq!(actions.len(), ACT_DIM * 2);
+        assert_eq!(actions[0], 0.5);
+        assert_eq!(actions[5], 0.5); // 1.0 - 0.5
+        assert_eq!(actions[ACT_DIM + 1], -0.25);
+        assert_eq!(actions[ACT_DIM + 5], -0.5);
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let json = serde_json::json!({
+            "layers": [
+                { "weights": vec![vec![0.1f32; OBS_DIM]; 8], "biases": vec![0.05f32; 8], "activation": "relu" },
+                { "weights": vec![vec![-0.2f32; 8]; ACT_DIM], "biases": vec![0.0f32; ACT_DIM], "activation": "tanh" }
+            ]
+        })
+        .to_string();
+        let policy = MlpPolicy::from_json(&json).unwrap();
+
+        let batch = MIN_PARALLEL_BATCH + 3;
+        let obs: Vec<f32> = (0..batch * OBS_DIM).map(|i| (i % 7) as f32 * 0.1).collect();
+        let mut batched = Vec::new();
+        policy.forward_batch(&obs, &mut batched);
+
+        for s in [0, batch - 1] {
+            let mut single = Vec::new();
+            policy.forward_batch(&obs[s * OBS_DIM..(s + 1) * OBS_DIM], &mut single);
+            assert_eq!(&batched[s * ACT_DIM..(s + 1) * ACT_DIM], single.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_shapes() {
+        let wrong_in = serde_json::json!({
+            "layers": [{ "weights": vec![vec![0.0f32; 3]; ACT_DIM], "biases": vec![0.0f32; ACT_DIM], "activation": "linear" }]
+        });
+        assert!(MlpPolicy::from_json(&wrong_in.to_string()).is_err());
+
+        let wrong_out = serde_json::json!({
+            "layers": [{ "weights": vec![vec![0.0f32; OBS_DIM]; 2], "biases": vec![0.0f32; 2], "activation": "linear" }]
+        });
+        assert!(MlpPolicy::from_json(&wrong_out.to_string()).is_err());
+        assert!(MlpPolicy::from_json(r#"{ "layers": [] }"#).is_err());
+    }
+
+    #[test]
+    fn test_observe_layout() {
+        let state = GameState::new();
+        let bot = Player {
+            position: Vec2::new(100.0, 0.0),
+            velocity: Vec2::new(250.0, 0.0),
+            ..Default::default()
+        };
+
+        let mut obs = Vec::new();
+        observe(&bot, &state, &[(uuid::Uuid::new_v4(), Vec2::new(600.0, 0.0), bot.mass)], &mut obs);
+
+        assert_eq!(obs.len(), OBS_DIM);
+        assert_eq!(obs[0], 0.5);
+        assert_eq!(obs[2], 0.5);
+        assert_eq!(obs[4], 1.0);
+        assert_eq!(obs[9], 1.0);
+    }
+}
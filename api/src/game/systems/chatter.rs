@@ -0,0 +1,365 @@
+//! Bot chatter
+//!
+//! Emits rare, contextual bot messages (a taunt after a kill, a distress call
+//! while fleeing a bigger human) as `GameLoopEvent::BotChatter`. Messages are
+//! picked from a phrase table (built-in or `CHATTER_PHRASES_PATH`), never
+//! generated, and are rate limited both per bot and across the arena.
+//! The session delivers them only to clients whose AOI contains the bot.
+
+use std::collections::VecDeque;
+
+use hashbrown::HashMap;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::config::ChatterConfig;
+use crate::game::game_loop::GameLoopEvent;
+use crate::game::state::{GameState, PlayerId};
+use crate::game::systems::custom::GameSystem;
+use crate::net::protocol::ChatterKind;
+
+/// Seconds between distress scans
+const DISTRESS_CHECK_INTERVAL: f32 = 1.0;
+
+/// A human within this distance counts as a threat
+const DISTRESS_RADIUS: f32 = 350.0;
+
+/// Threat must outweigh the bot by this factor
+const DISTRESS_MASS_RATIO: f32 = 1.5;
+
+/// Window used by the arena-wide rate limit (seconds)
+const RATE_WINDOW_SECS: f32 = 60.0;
+
+/// Longest phrase accepted from a phrase file (characters)
+const MAX_PHRASE_CHARS: usize = 80;
+
+const DEFAULT_TAUNTS: &[&str] = &[
+    "gg {victim}",
+    "Orbit denied.",
+    "Too slow, {victim}!",
+    "Another one for the well.",
+    "Nice try.",
+];
+
+const DEFAULT_DISTRESS: &[&str] = &[
+    "Help!",
+    "Not today!",
+    "Leave me alone!",
+    "Nope nope nope",
+    "Running!",
+];
+
+/// Phrase table, one list per chatter kind
+/// `{victim}` in taunts is replaced with the victim's name.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PhraseTable {
+    #[serde(default)]
+    pub taunt: Vec<String>,
+    #[serde(default)]
+    pub distress: Vec<String>,
+}
+
+impl Default for PhraseTable {
+    fn default() -> Self {
+        Self {
+            taunt: DEFAULT_TAUNTS.iter().map(|s| s.to_string()).collect(),
+            distress: DEFAULT_DISTRESS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl PhraseTable {
+    /// Parse a phrase table from JSON; empty/overlong phrases are dropped
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let mut table: PhraseTable =
+            serde_json::from_str(json).map_err(|e| format!("Invalid phrase table: {}", e))?;
+        let keep = |p: &String| !p.trim().is_empty() && p.chars().count() <= MAX_PHRASE_CHARS;
+        table.taunt.retain(keep);
+        table.distress.retain(keep);
+        Ok(table)
+    }
+
+    /// Load from `path`, falling back to the built-in table on error
+    pub fn load_or_default(path: Option<&str>) -> Self {
+        let Some(path) = path else {
+            return Self::default();
+        };
+        match std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read phrase table {}: {}", path, e))
+            .and_then(|json| Self::from_json(&json))
+        {
+            Ok(table) => table,
+            Err(e) => {
+                tracing::warn!("{}, using built-in bot phrases", e);
+                Self::default()
+            }
+        }
+    }
+
+    fn phrases(&self, kind: ChatterKind) -> &[String] {
+        match kind {
+            ChatterKind::Taunt => &self.taunt,
+            ChatterKind::Distress => &self.distress,
+        }
+    }
+}
+
+/// Custom system producing bot chatter events (run in the `Late` phase)
+pub struct BotChatterSystem {
+    config: ChatterConfig,
+    phrases: PhraseTable,
+    /// Seconds since the system started (advanced by `dt`)
+    clock: f32,
+    /// Clock value of the next distress scan
+    next_distress_check: f32,
+    /// Last message time per bot
+    last_spoke: HashMap<PlayerId, f32>,
+    /// Times of recent messages across the arena
+    recent: VecDeque<f32>,
+}
+
+impl BotChatterSystem {
+    pub fn new(config: ChatterConfig, phrases: PhraseTable) -> Self {
+        Self {
+            config,
+            phrases,
+            clock: 0.0,
+            next_distress_check: DISTRESS_CHECK_INTERVAL,
+            last_spoke: HashMap::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Build from `ChatterConfig::from_env()` and its phrase file
+    pub fn from_env() -> Self {
+        let config = ChatterConfig::from_env();
+        let phrases = PhraseTable::load_or_default(config.phrases_path.as_deref());
+        Self::new(config, phrases)
+    }
+
+    /// Check both rate limits; records the message if allowed
+    fn try_acquire(&mut self, bot_id: PlayerId) -> bool {
+        while self.recent.front().is_some_and(|&t| self.clock - t >= RATE_WINDOW_SECS) {
+            self.recent.pop_front();
+        }
+        if self.recent.len() >= self.config.max_per_minute as usize {
+            return false;
+        }
+        if let Some(&last) = self.last_spoke.get(&bot_id) {
+            if self.clock - last < self.config.bot_cooldown_secs {
+                return false;
+            }
+        }
+        self.recent.push_back(self.clock);
+        self.last_spoke.insert(bot_id, self.clock);
+        true
+    }
+
+    /// Pick a phrase and emit an event, subject to rate limits
+    fn speak<R: Rng>(
+        &mut self,
+        state: &GameState,
+        bot_id: PlayerId,
+        kind: ChatterKind,
+        victim: Option<&str>,
+        rng: &mut R,
+        events: &mut Vec<GameLoopEvent>,
+    ) {
+        let Some(bot) = state.get_player(bot_id) else {
+            return;
+        };
+        let phrases = self.phrases.phrases(kind);
+        if phrases.is_empty() {
+            return;
+        }
+        let phrase = &phrases[rng.gen_range(0..phrases.len())];
+        let text = phrase.replace("{victim}", victim.unwrap_or("you"));
+        let position = bot.position;
+
+        if self.try_acquire(bot_id) {
+            events.push(GameLoopEvent::BotChatter { bot_id, kind, text, position });
+        }
+    }
+
+    /// Bots moving away from a nearby, much heavier human
+    fn fleeing_bots(state: &GameState) -> Vec<PlayerId> {
+        let threats: Vec<_> = state
+            .players
+            .values()
+            .filter(|p| !p.is_bot && p.alive)
+            .map(|p| (p.position, p.mass))
+            .collect();
+        if threats.is_empty() {
+            return Vec::new();
+        }
+
+        let radius_sq = DISTRESS_RADIUS * DISTRESS_RADIUS;
+        state
+            .players
+            .values()
+            .filter(|bot| bot.is_bot && bot.alive)
+            .filter(|bot| {
+                threats.iter().any(|&(pos, mass)| {
+                    let away = bot.position - pos;
+                    mass > bot.mass * DISTRESS_MASS_RATIO
+                        && away.length_sq() < radius_sq
+                        && bot.velocity.dot(away) > 0.0
+                })
+            })
+            .map(|bot| bot.id)
+            .collect()
+    }
+}
+
+impl GameSystem for BotChatterSystem {
+    fn run(&mut self, state: &mut GameState, dt: f32, events: &mut Vec<GameLoopEvent>) {
+        self.clock += dt;
+        if !self.config.enabled || self.config.max_per_minute == 0 {
+            return;
+        }
+        let mut rng = rand::thread_rng();
+
+        // Taunts: bot killers from this tick's kill events
+        let kills: Vec<(PlayerId, PlayerId)> = events
+            .iter()
+            .filter_map(|e| match e {
+                GameLoopEvent::PlayerKilled { killer_id, victim_id } => Some((*killer_id, *victim_id)),
+                _ => None,
+            })
+            .collect();
+        for (killer_id, victim_id) in kills {
+            let is_live_bot = state.get_player(killer_id).is_some_and(|p| p.is_bot && p.alive);
+            if !is_live_bot || rng.gen::<f32>() >= self.config.taunt_chance {
+                continue;
+            }
+            let victim = state.get_player(victim_id).map(|p| p.name.clone());
+            self.speak(state, killer_id, ChatterKind::Taunt, victim.as_deref(), &mut rng, events);
+        }
+
+        // Distress: periodic scan for bots fleeing a heavier human
+        if self.clock >= self.next_distress_check {
+            self.next_distress_check = self.clock + DISTRESS_CHECK_INTERVAL;
+            for bot_id in Self::fleeing_bots(state) {
+                if rng.gen::<f32>() < self.config.distress_chance {
+                    self.speak(state, bot_id, ChatterKind::Distress, None, &mut rng, events);
+                }
+            }
+            // Forget bots that have left so the map stays bounded
+            self.last_spoke.retain(|id, _| state.get_player(*id).is_some());
+        }
+    }
+
+    fn name(&self) -> &str {
+        "bot_chatter"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Player;
+    use crate::util::vec2::Vec2;
+
+    fn always_config() -> ChatterConfig {
+        ChatterConfig {
+            enabled: true,
+            phrases_path: None,
+            taunt_chance: 1.0,
+            distress_chance: 1.0,
+            bot_cooldown_secs: 10.0,
+            max_per_minute: 2,
+        }
+    }
+
+    fn add_player(state: &mut GameState, is_bot: bool, position: Vec2, velocity: Vec2, mass: f32) -> PlayerId {
+        let player = Player {
+            id: uuid::Uuid::new_v4(),
+            name: if is_bot { "Bot".into() } else { "Human".into() },
+            is_bot,
+            position,
+            velocity,
+            mass,
+            alive: true,
+            ..Default::default()
+        };
+        let id = player.id;
+        state.add_player(player);
+        id
+    }
+
+    fn chatter(events: &[GameLoopEvent]) -> Vec<(PlayerId, ChatterKind, String)> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                GameLoopEvent::BotChatter { bot_id, kind, text, .. } => Some((*bot_id, *kind, text.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_taunt_after_bot_kill_substitutes_victim() {
+        let mut state = GameState::new();
+        let bot = add_player(&mut state, true, Vec2::ZERO, Vec2::ZERO, 100.0);
+        let human = add_player(&mut state, false, Vec2::new(50.0, 0.0), Vec2::ZERO, 100.0);
+        let phrases = PhraseTable { taunt: vec!["bye {victim}".into()], distress: vec![] };
+        let mut system = BotChatterSystem::new(always_config(), phrases);
+
+        let mut events = vec![GameLoopEvent::PlayerKilled { killer_id: bot, victim_id: human }];
+        system.run(&mut state, 0.05, &mut events);
+
+        assert_eq!(chatter(&events), vec![(bot, ChatterKind::Taunt, "bye Human".to_string())]);
+    }
+
+    #[test]
+    fn test_rate_limits() {
+        let mut state = GameState::new();
+        let bots: Vec<_> = (0..3).map(|_| add_player(&mut state, true, Vec2::ZERO, Vec2::ZERO, 100.0)).collect();
+        let human = add_player(&mut state, false, Vec2::new(900.0, 0.0), Vec2::ZERO, 100.0);
+        let mut system = BotChatterSystem::new(always_config(), PhraseTable::default());
+
+        // Same bot twice within its cooldown: one message
+        let mut events = vec![
+            GameLoopEvent::PlayerKilled { killer_id: bots[0], victim_id: human },
+            GameLoopEvent::PlayerKilled { killer_id: bots[0], victim_id: human },
+        ];
+        system.run(&mut state, 0.05, &mut events);
+        assert_eq!(chatter(&events).len(), 1);
+
+        // Arena-wide cap (2 per minute) stops the third bot
+        let mut events: Vec<_> = bots[1..]
+            .iter()
+            .map(|&killer_id| GameLoopEvent::PlayerKilled { killer_id, victim_id: human })
+            .collect();
+        system.run(&mut state, 0.05, &mut events);
+        assert_eq!(chatter(&events).len(), 1);
+    }
+
+    #[test]
+    fn test_distress_when_fleeing_heavier_human() {
+        let mut state = GameState::new();
+        let fleeing = add_player(&mut state, true, Vec2::new(100.0, 0.0), Vec2::new(50.0, 0.0), 50.0);
+        let approaching = add_player(&mut state, true, Vec2::new(-100.0, 0.0), Vec2::new(50.0, 0.0), 50.0);
+        add_player(&mut state, false, Vec2::ZERO, Vec2::ZERO, 200.0);
+        let mut system = BotChatterSystem::new(always_config(), PhraseTable::default());
+
+        let mut events = Vec::new();
+        system.run(&mut state, DISTRESS_CHECK_INTERVAL, &mut events);
+
+        let said = chatter(&events);
+        assert_eq!(said.len(), 1);
+        assert_eq!(said[0].0, fleeing);
+        assert_eq!(said[0].1, ChatterKind::Distress);
+        assert!(said.iter().all(|(id, _, _)| *id != approaching));
+    }
+
+    #[test]
+    fn test_phrase_table_filters_bad_entries() {
+        let long = "x".repeat(MAX_PHRASE_CHARS + 1);
+        let json = serde_json::json!({ "taunt": ["ok", "  ", long] }).to_string();
+        let table = PhraseTable::from_json(&json).unwrap();
+        assert_eq!(table.taunt, vec!["ok".to_string()]);
+        assert!(table.distress.is_empty());
+        assert!(PhraseTable::from_json("42").is_err());
+    }
+}
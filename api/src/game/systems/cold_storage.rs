@@ -0,0 +1,235 @@
+//! Cold storage for far-off dormant bots
+//!
+//! At 1M bots most of them sit dormant far from every human, yet each still
+//! owns a slot in every hot array of `AiManagerSoA`. Bots in zones further
+//! than the cold radius from every human are moved out of those arrays into
+//! per-zone buckets here: just the player id and a quantized personality, a
+//! fraction of a hot slot. They get no AI at all and coast on physics. Each
+//! bucket keeps the zone's bot count, mass and position sum so zone queries
+//! still see them, refreshed from the game state every few seconds as bots
+//! drift between zones.
+//!
+//! When a human comes within the cold radius of a zone (or the zone shows up
+//! in someone's viewport) the whole bucket is materialized back into the hot
+//! arrays as Dormant bots, so the usual wake-up rate limiting spreads out the
+//! cost of bringing them to Full.
+
+use hashbrown::HashMap;
+
+use crate::game::state::{GameState, PlayerId};
+use crate::game::systems::ai_soa::ZoneGrid;
+use crate::util::vec2::Vec2;
+
+/// A bot's personality and difficulty, as kept in the hot arrays
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BotPersonality {
+    pub aggression: f32,
+    pub preferred_radius: f32,
+    pub accuracy: f32,
+    pub reaction_variance: f32,
+    pub aggression_scale: f32,
+    pub accuracy_scale: f32,
+    pub is_elite: bool,
+}
+
+/// A frozen bot: 0-1 values are stored in 1/255 steps, the radius in whole units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColdBot {
+    pub player_id: PlayerId,
+    preferred_radius: u16,
+    aggression: u8,
+    accuracy: u8,
+    reaction_variance: u8,
+    aggression_scale: u8,
+    accuracy_scale: u8,
+    is_elite: bool,
+}
+
+fn quantize(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn dequantize(value: u8) -> f32 {
+    value as f32 / 255.0
+}
+
+impl ColdBot {
+    pub fn pack(player_id: PlayerId, personality: &BotPersonality) -> Self {
+        Self {
+            player_id,
+            preferred_radius: personality.preferred_radius.round().clamp(0.0, u16::MAX as f32) as u16,
+            aggression: quantize(personality.aggression),
+            accuracy: quantize(personality.accuracy),
+            reaction_variance: quantize(personality.reaction_variance),
+            aggression_scale: quantize(personality.aggression_scale),
+            accuracy_scale: quantize(personality.accuracy_scale),
+            is_elite: personality.is_elite,
+        }
+    }
+
+    pub fn unpack(&self) -> BotPersonality {
+        BotPersonality {
+            aggression: dequantize(self.aggression),
+            preferred_radius: self.preferred_radius as f32,
+            accuracy: dequantize(self.accuracy),
+            reaction_variance: dequantize(self.reaction_variance),
+            aggression_scale: dequantize(self.aggression_scale),
+            accuracy_scale: dequantize(self.accuracy_scale),
+            is_elite: self.is_elite,
+        }
+    }
+
+    /// Overwrite the personality, keeping the id
+    pub fn repack(&mut self, personality: &BotPersonality) {
+        *self = Self::pack(self.player_id, personality);
+    }
+}
+
+/// Frozen bots of one zone and their aggregates
+#[derive(Debug, Default)]
+pub struct ColdZone {
+    pub bots: Vec<ColdBot>,
+    pub total_mass: f32,
+    pub position_sum: Vec2,
+}
+
+/// Frozen bots bucketed by AI zone
+#[derive(Debug, Default)]
+pub struct ColdStorage {
+    zones: HashMap<(i32, i32), ColdZone>,
+    /// Zone each frozen bot is bucketed in
+    zone_of: HashMap<PlayerId, (i32, i32)>,
+}
+
+impl ColdStorage {
+    /// Frozen bots
+    pub fn len(&self) -> usize {
+        self.zone_of.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zone_of.is_empty()
+    }
+
+    /// Zones holding frozen bots
+    pub fn zones(&self) -> impl Iterator<Item = (&(i32, i32), &ColdZone)> {
+        self.zones.iter()
+    }
+
+    /// Freeze a bot into `cell`
+    pub fn insert(&mut self, cell: (i32, i32), bot: ColdBot, mass: f32, position: Vec2) {
+        if let Some(old_cell) = self.zone_of.insert(bot.player_id, cell) {
+            self.detach(old_cell, bot.player_id);
+        }
+        let zone = self.zones.entry(cell).or_default();
+        zone.bots.push(bot);
+        zone.total_mass += mass;
+        zone.position_sum += position;
+    }
+
+    /// Take a single bot out (it left the game or is being re-registered)
+    pub fn remove(&mut self, player_id: PlayerId) -> Option<ColdBot> {
+        let cell = self.zone_of.remove(&player_id)?;
+        self.detach(cell, player_id)
+    }
+
+    /// Take every bot out of a zone
+    pub fn take_zone(&mut self, cell: (i32, i32)) -> Vec<ColdBot> {
+        let Some(zone) = self.zones.remove(&cell) else {
+            return Vec::new();
+        };
+        for bot in &zone.bots {
+            self.zone_of.remove(&bot.player_id);
+        }
+        zone.bots
+    }
+
+    pub fn bots(&self) -> impl Iterator<Item = &ColdBot> {
+        self.zones.values().flat_map(|zone| zone.bots.iter())
+    }
+
+    pub fn bots_mut(&mut self) -> impl Iterator<Item = &mut ColdBot> {
+        self.zones.values_mut().flat_map(|zone| zone.bots.iter_mut())
+    }
+
+    /// Re-bucket every bot by its current position and refresh the zone
+    /// aggregates. Bots missing from the state stay where they were.
+    pub fn rebucket(&mut self, grid: &ZoneGrid, state: &GameState) {
+        let zones = std::mem::take(&mut self.zones);
+        for (cell, zone) in zones {
+            for bot in zone.bots {
+                match state.get_player(bot.player_id) {
+                    Some(player) => {
+                        let new_cell = grid.position_to_cell(player.position);
+                        let mass = if player.alive { player.mass } else { 0.0 };
+                        self.zone_of.insert(bot.player_id, new_cell);
+                        let target = self.zones.entry(new_cell).or_default();
+                        target.bots.push(bot);
+                        target.total_mass += mass;
+                        target.position_sum += player.position;
+                    }
+                    None => self.zones.entry(cell).or_default().bots.push(bot),
+                }
+            }
+        }
+    }
+
+    fn detach(&mut self, cell: (i32, i32), player_id: PlayerId) -> Option<ColdBot> {
+        let zone = self.zones.get_mut(&cell)?;
+        let index = zone.bots.iter().position(|b| b.player_id == player_id)?;
+        let bot = zone.bots.swap_remove(index);
+        if zone.bots.is_empty() {
+            self.zones.remove(&cell);
+        }
+        Some(bot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn personality() -> BotPersonality {
+        BotPersonality {
+            aggression: 0.6,
+            preferred_radius: 312.4,
+            accuracy: 0.75,
+            reaction_variance: 0.3,
+            aggression_scale: 1.0,
+            accuracy_scale: 0.4,
+            is_elite: true,
+        }
+    }
+
+    #[test]
+    fn test_pack_round_trip_is_close() {
+        let original = personality();
+        let unpacked = ColdBot::pack(Uuid::new_v4(), &original).unpack();
+        assert!((unpacked.aggression - original.aggression).abs() < 0.005);
+        assert!((unpacked.accuracy_scale - original.accuracy_scale).abs() < 0.005);
+        assert_eq!(unpacked.preferred_radius, 312.0);
+        assert!(unpacked.is_elite);
+    }
+
+    #[test]
+    fn test_insert_remove_and_take_zone() {
+        let mut storage = ColdStorage::default();
+        let ids: Vec<_> = (0..3).map(|_| Uuid::new_v4()).collect();
+        storage.insert((0, 0), ColdBot::pack(ids[0], &personality()), 100.0, Vec2::new(10.0, 0.0));
+        storage.insert((0, 0), ColdBot::pack(ids[1], &personality()), 50.0, Vec2::new(20.0, 0.0));
+        storage.insert((5, 5), ColdBot::pack(ids[2], &personality()), 80.0, Vec2::ZERO);
+        assert_eq!(storage.len(), 3);
+
+        let zone = storage.zones().find(|(cell, _)| **cell == (0, 0)).unwrap().1;
+        assert_eq!((zone.bots.len(), zone.total_mass, zone.position_sum), (2, 150.0, Vec2::new(30.0, 0.0)));
+
+        assert_eq!(storage.remove(ids[2]).unwrap().player_id, ids[2]);
+        assert!(storage.remove(ids[2]).is_none());
+        assert_eq!(storage.zones().count(), 1);
+
+        let taken = storage.take_zone((0, 0));
+        assert_eq!(taken.len(), 2);
+        assert!(storage.is_empty() && storage.remove(ids[0]).is_none());
+    }
+}
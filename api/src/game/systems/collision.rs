@@ -1,15 +1,22 @@
 //! Collision detection and resolution
 //!
-//! Handles player-player, player-projectile, and player-debris collisions.
+//! Handles player-player, player-projectile, and player-debris collisions,
+//! and asteroids stopping projectiles and deflecting players.
+//!
+//! Which entity kinds interact is decided by a [`CollisionMatrix`] over
+//! [`CollisionLayer`]s rather than per-system special cases: spawn-protected
+//! players sit on the `Ghost` layer, gravity well cores are `Hazard`s (checked
+//! by the arena system), and optional pairs such as projectile-debris are
+//! simulated only when enabled.
 //!
 //! With `advanced_physics` feature: uses momentum and kinetic energy helpers
 //! for more accurate collision calculations.
 
 #![allow(dead_code)] // Collision event fields and helpers
 
-use crate::game::constants::{collision::*, mass::*, spawn::RESPAWN_DELAY};
+use crate::game::constants::{collision::*, mass::*, physics, spawn::RESPAWN_DELAY};
 use crate::game::spatial::{SpatialEntity, SpatialEntityId, SpatialGrid, ENTITY_GRID_CELL_SIZE};
-use crate::game::state::{GameState, PlayerId};
+use crate::game::state::{GameState, Player, PlayerId};
 use crate::util::vec2::Vec2;
 
 // ============================================================================
@@ -38,6 +45,9 @@ const COLLISION_INTENSITY_MAX_VELOCITY: f32 = 200.0;
 /// Cell size for debris collision spatial grid
 const DEBRIS_COLLISION_GRID_CELL_SIZE: f32 = 32.0;
 
+/// Extra distance added when pushing overlapping players apart
+const SEPARATION_MARGIN: f32 = 2.0;
+
 /// Maximum debris radius for query padding
 const DEBRIS_QUERY_MAX_RADIUS: f32 = 15.0;
 
@@ -48,6 +58,144 @@ const PROJECTILE_OWNER_ABSORPTION_LIFETIME_THRESHOLD: f32 = 7.5;
 #[cfg(feature = "advanced_physics")]
 use crate::game::systems::physics::{kinetic_energy, momentum_magnitude};
 
+// ============================================================================
+// Collision Layers
+// ============================================================================
+
+/// Collision layer of an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CollisionLayer {
+    Player,
+    Projectile,
+    Debris,
+    /// Gravity well cores (instant death zones)
+    Hazard,
+    /// Spawn-protected players
+    Ghost,
+    /// Destructible asteroids (immovable until broken)
+    Asteroid,
+}
+
+impl CollisionLayer {
+    pub const ALL: [CollisionLayer; 6] = [
+        CollisionLayer::Player,
+        CollisionLayer::Projectile,
+        CollisionLayer::Debris,
+        CollisionLayer::Hazard,
+        CollisionLayer::Ghost,
+        CollisionLayer::Asteroid,
+    ];
+
+    #[inline]
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    /// Parse from a layer name (case-insensitive)
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "player" | "players" => Some(Self::Player),
+            "projectile" | "projectiles" => Some(Self::Projectile),
+            "debris" => Some(Self::Debris),
+            "hazard" | "hazards" => Some(Self::Hazard),
+            "ghost" | "ghosts" => Some(Self::Ghost),
+            "asteroid" | "asteroids" => Some(Self::Asteroid),
+            _ => None,
+        }
+    }
+}
+
+/// Layer a player currently collides on
+#[inline]
+pub fn player_layer(player: &Player) -> CollisionLayer {
+    if player.spawn_protection > 0.0 {
+        CollisionLayer::Ghost
+    } else {
+        CollisionLayer::Player
+    }
+}
+
+/// Symmetric table of which layers interact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionMatrix {
+    /// Per-layer bitmask of interacting layers (indexed by layer)
+    masks: [u8; CollisionLayer::ALL.len()],
+}
+
+impl CollisionMatrix {
+    /// Matrix where nothing interacts
+    pub const fn none() -> Self {
+        Self { masks: [0; CollisionLayer::ALL.len()] }
+    }
+
+    /// Enable or disable interaction between two layers (both directions)
+    pub fn set(&mut self, a: CollisionLayer, b: CollisionLayer, enabled: bool) {
+        if enabled {
+            self.masks[a as usize] |= b.bit();
+            self.masks[b as usize] |= a.bit();
+        } else {
+            self.masks[a as usize] &= !b.bit();
+            self.masks[b as usize] &= !a.bit();
+        }
+    }
+
+    /// Builder-style [`Self::set`]
+    pub fn with(mut self, a: CollisionLayer, b: CollisionLayer, enabled: bool) -> Self {
+        self.set(a, b, enabled);
+        self
+    }
+
+    #[inline]
+    pub fn interacts(&self, a: CollisionLayer, b: CollisionLayer) -> bool {
+        self.masks[a as usize] & b.bit() != 0
+    }
+}
+
+impl Default for CollisionMatrix {
+    /// Standard rules: players interact with everything; spawn-protected
+    /// players still collect mass and die in cores but can't be hit by (or
+    /// hit) other players; projectiles pass through debris but not asteroids
+    fn default() -> Self {
+        use CollisionLayer::*;
+        Self::none()
+            .with(Player, Player, true)
+            .with(Player, Projectile, true)
+            .with(Player, Debris, true)
+            .with(Player, Hazard, true)
+            .with(Ghost, Projectile, true)
+            .with(Ghost, Debris, true)
+            .with(Ghost, Hazard, true)
+            .with(Player, Asteroid, true)
+            .with(Ghost, Asteroid, true)
+            .with(Projectile, Asteroid, true)
+    }
+}
+
+// ============================================================================
+// Collision Response
+// ============================================================================
+
+/// Impulse response tuning for player-player deflections
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionResponse {
+    /// Coefficient of restitution (0 = players stick together, 1 = perfectly elastic)
+    pub restitution: f32,
+    /// Multiplier on the collision impulse (above 1 makes ramming knock harder)
+    pub knockback_scale: f32,
+    /// Cap on the speed change a single collision can give a player
+    pub max_knockback: f32,
+}
+
+impl Default for CollisionResponse {
+    fn default() -> Self {
+        Self {
+            restitution: RESTITUTION,
+            knockback_scale: 1.0,
+            max_knockback: physics::MAX_VELOCITY,
+        }
+    }
+}
+
 /// Events generated by collision resolution
 #[derive(Debug, Clone)]
 pub enum CollisionEvent {
@@ -60,6 +208,10 @@ pub enum CollisionEvent {
     ProjectileAbsorbed {
         player_id: PlayerId,
         projectile_id: u64,
+        /// Player who fired the projectile
+        owner_id: PlayerId,
+        /// Where the projectile struck
+        position: Vec2,
         mass_gained: f32,
     },
     /// Player collected debris
@@ -68,6 +220,22 @@ pub enum CollisionEvent {
         debris_id: u64,
         mass_gained: f32,
     },
+    /// Projectile swept up debris (only when projectile-debris is enabled)
+    DebrisSwept {
+        projectile_id: u64,
+        debris_id: u64,
+        mass_gained: f32,
+    },
+    /// Projectile struck an asteroid and was stopped
+    AsteroidHit {
+        asteroid_id: u64,
+        projectile_id: u64,
+        /// Player who fired the projectile
+        owner_id: PlayerId,
+        position: Vec2,
+        /// Health taken off the asteroid (the projectile's mass)
+        damage: f32,
+    },
     /// Elastic collision (both survived)
     Deflection {
         player_a: PlayerId,
@@ -79,33 +247,57 @@ pub enum CollisionEvent {
     },
 }
 
-/// Update collision detection and resolution
+/// Update collision detection and resolution with the default rules
 pub fn update(state: &mut GameState) -> Vec<CollisionEvent> {
+    update_with_rules(state, &CollisionMatrix::default(), &CollisionResponse::default())
+}
+
+/// Update collision detection and resolution with custom layer rules and response tuning
+pub fn update_with_rules(
+    state: &mut GameState,
+    matrix: &CollisionMatrix,
+    response: &CollisionResponse,
+) -> Vec<CollisionEvent> {
     let mut events = Vec::new();
 
     // Player-player collisions
-    events.extend(update_player_collisions(state));
+    events.extend(update_player_collisions(state, matrix, response));
+
+    // Asteroids stop projectiles before they reach players behind them
+    events.extend(update_asteroid_collisions(state, matrix, response));
 
     // Player-projectile collisions
-    events.extend(update_projectile_collisions(state));
+    events.extend(update_projectile_collisions(state, matrix));
 
     // Player-debris collisions
-    events.extend(update_debris_collisions(state));
+    events.extend(update_debris_collisions(state, matrix));
+
+    // Projectile-debris collisions (off by default)
+    if matrix.interacts(CollisionLayer::Projectile, CollisionLayer::Debris) {
+        events.extend(update_projectile_debris_collisions(state));
+    }
 
     events
 }
 
 /// Handle player-player collisions using spatial grid for O(n) performance
-fn update_player_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
+fn update_player_collisions(
+    state: &mut GameState,
+    matrix: &CollisionMatrix,
+    response: &CollisionResponse,
+) -> Vec<CollisionEvent> {
     let mut events = Vec::new();
 
     // Build spatial grid with players (O(n))
     // Cell size of 64 units covers typical player radii (10-30)
     let mut grid = SpatialGrid::new(ENTITY_GRID_CELL_SIZE);
 
-    // Insert players into grid (no need to collect, just iterate)
+    // Insert players into grid (no need to collect, just iterate),
+    // skipping layers that can't touch any player layer
     for player in state.players.values() {
-        if player.alive && player.spawn_protection <= 0.0 {
+        let layer = player_layer(player);
+        let collides = matrix.interacts(layer, CollisionLayer::Player) || matrix.interacts(layer, CollisionLayer::Ghost);
+        if player.alive && collides {
             let radius = mass_to_radius(player.mass);
             grid.insert(SpatialEntity {
                 id: SpatialEntityId::Player(player.id),
@@ -125,13 +317,17 @@ fn update_player_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
             (SpatialEntityId::Player(a), SpatialEntityId::Player(b)) => (a, b),
             _ => continue, // Skip non-player pairs
         };
+        let layers = state.get_player(id_a).map(player_layer).zip(state.get_player(id_b).map(player_layer));
+        if !layers.is_some_and(|(a, b)| matrix.interacts(a, b)) {
+            continue;
+        }
 
         // Squared distance check to avoid sqrt()
         let dist_sq = entity_a.position.distance_sq_to(entity_b.position);
         let combined_radius = entity_a.radius + entity_b.radius;
 
         if dist_sq < combined_radius * combined_radius {
-            if let Some(event) = resolve_player_collision(state, id_a, id_b) {
+            if let Some(event) = resolve_player_collision(state, id_a, id_b, response) {
                 events.push(event);
             }
         }
@@ -145,6 +341,7 @@ fn resolve_player_collision(
     state: &mut GameState,
     id_a: PlayerId,
     id_b: PlayerId,
+    response: &CollisionResponse,
 ) -> Option<CollisionEvent> {
     // Get player data (read phase)
     let (pos_a, pos_b, vel_a, vel_b, mass_a, mass_b) = {
@@ -191,7 +388,7 @@ fn resolve_player_collision(
 
     // If neither is moving toward the other, just deflect
     if momentum_a == 0.0 && momentum_b == 0.0 {
-        deflect(state, id_a, id_b, normal);
+        deflect(state, id_a, id_b, normal, response);
         return None;
     }
 
@@ -240,7 +437,7 @@ fn resolve_player_collision(
         })
     } else if ratio > 1.0 / DECISIVE_THRESHOLD {
         // Close fight - both survive but lose mass
-        deflect(state, id_a, id_b, normal);
+        deflect(state, id_a, id_b, normal, response);
 
         // Winner loses less mass
         if ratio > 1.0 {
@@ -314,8 +511,10 @@ fn resolve_player_collision(
     }
 }
 
-/// Apply elastic deflection between two players
-fn deflect(state: &mut GameState, id_a: PlayerId, id_b: PlayerId, normal: Vec2) {
+/// Apply a partially elastic, mass-weighted impulse between two players and
+/// push them apart. The lighter player takes the larger share of both the
+/// velocity change and the separation, so ramming with more mass pays off.
+fn deflect(state: &mut GameState, id_a: PlayerId, id_b: PlayerId, normal: Vec2, response: &CollisionResponse) {
     // Get player data (read phase)
     let (vel_a, vel_b, m_a, m_b, pos_a, pos_b) = {
         let player_a = match state.players.get(&id_a) {
@@ -335,37 +534,35 @@ fn deflect(state: &mut GameState, id_a: PlayerId, id_b: PlayerId, normal: Vec2)
             player_b.position,
         )
     };
+    let (inv_a, inv_b) = (1.0 / m_a, 1.0 / m_b);
 
-    let rel_vel = vel_a - vel_b;
-    let rel_n = rel_vel.dot(normal);
-
-    // Only deflect if moving toward each other (rel_n >= 0 means approaching)
-    if rel_n < 0.0 {
-        return;
-    }
-
-    // Calculate impulse magnitude using coefficient of restitution
-    let j = (-(1.0 + RESTITUTION) * rel_n) / (1.0 / m_a + 1.0 / m_b);
-    let impulse = normal * j;
+    // Impulse along the normal, only while approaching (rel_n > 0)
+    let rel_n = (vel_a - vel_b).dot(normal);
+    let (dv_a, dv_b) = if rel_n > 0.0 {
+        let j = (1.0 + response.restitution) * rel_n / (inv_a + inv_b) * response.knockback_scale;
+        ((j * inv_a).min(response.max_knockback), (j * inv_b).min(response.max_knockback))
+    } else {
+        (0.0, 0.0)
+    };
 
-    // Calculate separation
+    // Resolve overlap, split by inverse mass
     let (r_a, r_b) = (mass_to_radius(m_a), mass_to_radius(m_b));
-    let dist = (pos_a - pos_b).length();
-    let overlap = r_a + r_b - dist;
-    let sep = if overlap > 0.0 { overlap / 2.0 + 1.0 } else { 0.0 };
+    let overlap = r_a + r_b - (pos_a - pos_b).length();
+    let (sep_a, sep_b) = if overlap > 0.0 {
+        let per_inv_mass = (overlap + SEPARATION_MARGIN) / (inv_a + inv_b);
+        (per_inv_mass * inv_a, per_inv_mass * inv_b)
+    } else {
+        (0.0, 0.0)
+    };
 
     // Apply impulses and separation (write phase)
     if let Some(player_a) = state.players.get_mut(&id_a) {
-        player_a.velocity += impulse * (1.0 / m_a);
-        if sep > 0.0 {
-            player_a.position -= normal * sep;
-        }
+        player_a.velocity -= normal * dv_a;
+        player_a.position -= normal * sep_a;
     }
     if let Some(player_b) = state.players.get_mut(&id_b) {
-        player_b.velocity -= impulse * (1.0 / m_b);
-        if sep > 0.0 {
-            player_b.position += normal * sep;
-        }
+        player_b.velocity += normal * dv_b;
+        player_b.position += normal * sep_b;
     }
 }
 
@@ -381,7 +578,7 @@ fn check_min_mass(state: &mut GameState, id: PlayerId) {
 }
 
 /// Handle player-projectile collisions
-fn update_projectile_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
+fn update_projectile_collisions(state: &mut GameState, matrix: &CollisionMatrix) -> Vec<CollisionEvent> {
     let mut events = Vec::new();
     let mut projectiles_to_remove = Vec::new();
     let mut mass_gains: Vec<(PlayerId, f32)> = Vec::new();
@@ -395,7 +592,7 @@ fn update_projectile_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
         let lifetime = projectile.lifetime;
 
         for player in state.players.values() {
-            if !player.alive {
+            if !player.alive || !matrix.interacts(player_layer(player), CollisionLayer::Projectile) {
                 continue;
             }
 
@@ -417,6 +614,8 @@ fn update_projectile_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
                 events.push(CollisionEvent::ProjectileAbsorbed {
                     player_id: player.id,
                     projectile_id: proj_id,
+                    owner_id,
+                    position: proj_pos,
                     mass_gained: mass_gain,
                 });
 
@@ -441,9 +640,69 @@ fn update_projectile_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
     events
 }
 
+/// Handle asteroid collisions: a projectile touching an asteroid is removed
+/// and takes its mass off the asteroid's health; players bounce off
+/// (asteroids are few, so both are checked directly)
+fn update_asteroid_collisions(
+    state: &mut GameState,
+    matrix: &CollisionMatrix,
+    response: &CollisionResponse,
+) -> Vec<CollisionEvent> {
+    let mut events = Vec::new();
+    if state.asteroids.is_empty() {
+        return events;
+    }
+
+    if matrix.interacts(CollisionLayer::Projectile, CollisionLayer::Asteroid) {
+        let asteroids = &mut state.asteroids;
+        state.projectiles.retain(|projectile| {
+            let proj_radius = mass_to_radius(projectile.mass);
+            let hit = asteroids.iter_mut().find(|a| {
+                let combined_radius = a.radius + proj_radius;
+                !a.is_broken() && a.position.distance_sq_to(projectile.position) < combined_radius * combined_radius
+            });
+            let Some(asteroid) = hit else {
+                return true;
+            };
+            asteroid.health -= projectile.mass;
+            events.push(CollisionEvent::AsteroidHit {
+                asteroid_id: asteroid.id,
+                projectile_id: projectile.id,
+                owner_id: projectile.owner_id,
+                position: projectile.position,
+                damage: projectile.mass,
+            });
+            false
+        });
+    }
+
+    for player in state.players.values_mut() {
+        if !player.alive || !matrix.interacts(player_layer(player), CollisionLayer::Asteroid) {
+            continue;
+        }
+        let player_radius = mass_to_radius(player.mass);
+        for asteroid in &state.asteroids {
+            let combined_radius = asteroid.radius + player_radius;
+            let (normal, distance) = (player.position - asteroid.position).normalize_with_length();
+            if distance >= combined_radius {
+                continue;
+            }
+            let normal = if distance > COLLISION_DISTANCE_EPSILON { normal } else { Vec2::new(1.0, 0.0) };
+            player.position = asteroid.position + normal * (combined_radius + SEPARATION_MARGIN);
+            // Reflect the approaching part of the velocity
+            let approach = player.velocity.dot(normal);
+            if approach < 0.0 {
+                player.velocity -= normal * (approach * (1.0 + response.restitution));
+            }
+        }
+    }
+
+    events
+}
+
 /// Handle player-debris collisions using spatial grid for O(n) performance
 /// Previously O(D × P), now O(D + P) with spatial hashing
-fn update_debris_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
+fn update_debris_collisions(state: &mut GameState, matrix: &CollisionMatrix) -> Vec<CollisionEvent> {
     use hashbrown::HashSet;
 
     let mut events = Vec::new();
@@ -472,7 +731,7 @@ fn update_debris_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
 
     // For each player, query nearby debris from spatial grid (O(P × avg_nearby))
     for player in state.players.values() {
-        if !player.alive {
+        if !player.alive || !matrix.interacts(player_layer(player), CollisionLayer::Debris) {
             continue;
         }
 
@@ -532,6 +791,62 @@ fn update_debris_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
     events
 }
 
+/// Handle projectile-debris collisions: a projectile sweeps up debris it
+/// touches, gaining its mass (each debris is swept by at most one projectile)
+fn update_projectile_debris_collisions(state: &mut GameState) -> Vec<CollisionEvent> {
+    use hashbrown::{HashMap, HashSet};
+
+    let mut events = Vec::new();
+    if state.projectiles.is_empty() || state.debris.is_empty() {
+        return events;
+    }
+
+    let mut grid = SpatialGrid::new(DEBRIS_COLLISION_GRID_CELL_SIZE);
+    let debris_mass: HashMap<u64, f32> = state
+        .debris
+        .iter()
+        .map(|d| {
+            grid.insert(SpatialEntity {
+                id: SpatialEntityId::Debris(d.id),
+                position: d.position,
+                radius: d.radius(),
+            });
+            (d.id, d.mass())
+        })
+        .collect();
+
+    let mut swept = HashSet::new();
+    for projectile in state.projectiles.iter_mut() {
+        let proj_radius = mass_to_radius(projectile.mass);
+        for nearby in grid.query_radius(projectile.position, proj_radius + DEBRIS_QUERY_MAX_RADIUS) {
+            let SpatialEntityId::Debris(debris_id) = nearby.id else {
+                continue;
+            };
+            if swept.contains(&debris_id) {
+                continue;
+            }
+            let combined_radius = proj_radius + nearby.radius;
+            if projectile.position.distance_sq_to(nearby.position) >= combined_radius * combined_radius {
+                continue;
+            }
+            let mass_gained = debris_mass[&debris_id];
+            projectile.mass += mass_gained;
+            swept.insert(debris_id);
+            events.push(CollisionEvent::DebrisSwept {
+                projectile_id: projectile.id,
+                debris_id,
+                mass_gained,
+            });
+        }
+    }
+
+    if !swept.is_empty() {
+        state.debris.retain(|d| !swept.contains(&d.id));
+    }
+
+    events
+}
+
 /// Calculate radius from mass
 #[inline]
 pub fn mass_to_radius(mass: f32) -> f32 {
@@ -559,6 +874,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         }
     }
 
@@ -649,8 +968,9 @@ mod tests {
         state.add_player(player);
 
         // Add projectile from different owner at same position
+        let shooter_id = uuid::Uuid::new_v4();
         state.add_projectile(
-            uuid::Uuid::new_v4(), // Different owner
+            shooter_id, // Different owner
             Vec2::new(100.0, 100.0),
             Vec2::ZERO,
             20.0,
@@ -661,9 +981,11 @@ mod tests {
         let initial_mass = state.get_player(player_id).unwrap().mass;
         let events = update(&mut state);
 
-        assert!(events
-            .iter()
-            .any(|e| matches!(e, CollisionEvent::ProjectileAbsorbed { .. })));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            CollisionEvent::ProjectileAbsorbed { player_id: hit, owner_id, position, .. }
+                if *hit == player_id && *owner_id == shooter_id && *position == Vec2::new(100.0, 100.0)
+        )));
         assert!(state.projectiles.is_empty());
         assert!(state.get_player(player_id).unwrap().mass > initial_mass);
     }
@@ -784,7 +1106,7 @@ mod tests {
         let vel_b_before = state.get_player(id2).unwrap().velocity;
 
         let normal = Vec2::new(1.0, 0.0);
-        deflect(&mut state, id1, id2, normal);
+        deflect(&mut state, id1, id2, normal, &CollisionResponse::default());
 
         let vel_b_after = state.get_player(id2).unwrap().velocity;
 
@@ -906,4 +1228,176 @@ mod tests {
         assert!(collections >= 1);
         assert!(state.get_player(player_id).unwrap().mass > initial_mass);
     }
+
+    #[test]
+    fn test_default_matrix_matches_standard_rules() {
+        use CollisionLayer::*;
+        let matrix = CollisionMatrix::default();
+        assert!(matrix.interacts(Player, Player));
+        assert!(matrix.interacts(Ghost, Hazard));
+        assert!(matrix.interacts(Debris, Ghost));
+        assert!(!matrix.interacts(Ghost, Player));
+        assert!(!matrix.interacts(Ghost, Ghost));
+        assert!(!matrix.interacts(Projectile, Debris));
+        assert!(matrix.interacts(Asteroid, Projectile));
+        assert!(!matrix.interacts(Asteroid, Debris));
+        for a in CollisionLayer::ALL {
+            for b in CollisionLayer::ALL {
+                assert_eq!(matrix.interacts(a, b), matrix.interacts(b, a));
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_can_make_ghosts_non_interactive() {
+        let mut state = GameState::new();
+        let mut player = create_player("A", Vec2::new(100.0, 100.0), Vec2::ZERO, 100.0);
+        player.spawn_protection = 3.0;
+        let player_id = player.id;
+        state.add_player(player);
+        state.add_debris(Vec2::new(100.0, 100.0), Vec2::ZERO, DebrisSize::Medium);
+        state.add_projectile(
+            uuid::Uuid::new_v4(),
+            Vec2::new(100.0, 100.0),
+            Vec2::ZERO,
+            20.0,
+        );
+        state.projectiles[0].lifetime = 5.0;
+
+        let matrix = CollisionMatrix::default()
+            .with(CollisionLayer::Ghost, CollisionLayer::Projectile, false)
+            .with(CollisionLayer::Ghost, CollisionLayer::Debris, false);
+        let events = update_with_rules(&mut state, &matrix, &CollisionResponse::default());
+
+        assert!(events.is_empty());
+        assert_eq!(state.debris.len(), 1);
+        assert_eq!(state.projectiles.len(), 1);
+        assert_eq!(state.get_player(player_id).unwrap().mass, 100.0);
+    }
+
+    #[test]
+    fn test_projectile_debris_sweep_when_enabled() {
+        let mut state = GameState::new();
+        state.add_projectile(
+            uuid::Uuid::new_v4(),
+            Vec2::new(100.0, 100.0),
+            Vec2::ZERO,
+            20.0,
+        );
+        state.add_debris(Vec2::new(102.0, 100.0), Vec2::ZERO, DebrisSize::Small);
+
+        assert!(update(&mut state).is_empty());
+        assert_eq!(state.debris.len(), 1);
+
+        let matrix = CollisionMatrix::default().with(
+            CollisionLayer::Projectile,
+            CollisionLayer::Debris,
+            true,
+        );
+        let events = update_with_rules(&mut state, &matrix, &CollisionResponse::default());
+
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, CollisionEvent::DebrisSwept { .. })));
+        assert!(state.debris.is_empty());
+        assert!(state.projectiles[0].mass > 20.0);
+    }
+
+    #[test]
+    fn test_asteroid_stops_projectiles_and_deflects_players() {
+        let mut state = GameState::new();
+        let asteroid_id = state.add_asteroid(Vec2::new(500.0, 0.0), 50.0, 100.0);
+        let shooter = uuid::Uuid::new_v4();
+        state.add_projectile(shooter, Vec2::new(445.0, 0.0), Vec2::new(200.0, 0.0), 20.0);
+        let player = create_player("A", Vec2::new(500.0, 60.0), Vec2::new(0.0, -100.0), 100.0);
+        let player_id = player.id;
+        state.add_player(player);
+
+        let events = update(&mut state);
+
+        assert!(state.projectiles.is_empty());
+        assert!(events.iter().any(|e| matches!(
+            e,
+            CollisionEvent::AsteroidHit { asteroid_id: id, owner_id, damage, .. }
+                if *id == asteroid_id && *owner_id == shooter && *damage == 20.0
+        )));
+        assert_eq!(state.asteroids[0].health, 80.0);
+        // Pushed clear of the rock and bouncing away from it
+        let player = state.get_player(player_id).unwrap();
+        assert!(player.position.y > 50.0 + mass_to_radius(100.0));
+        assert!(player.velocity.y > 0.0);
+    }
+
+    #[test]
+    fn test_layer_from_name() {
+        assert_eq!(
+            CollisionLayer::from_name("Projectiles"),
+            Some(CollisionLayer::Projectile)
+        );
+        assert_eq!(
+            CollisionLayer::from_name(" ghost "),
+            Some(CollisionLayer::Ghost)
+        );
+        assert_eq!(CollisionLayer::from_name("wall"), None);
+    }
+
+    fn head_on(mass_a: f32, mass_b: f32) -> (GameState, PlayerId, PlayerId) {
+        let mut state = GameState::new();
+        let a = create_player("A", Vec2::new(0.0, 0.0), Vec2::new(100.0, 0.0), mass_a);
+        let b = create_player("B", Vec2::new(20.0, 0.0), Vec2::new(-100.0, 0.0), mass_b);
+        let (id_a, id_b) = (a.id, b.id);
+        state.add_player(a);
+        state.add_player(b);
+        (state, id_a, id_b)
+    }
+
+    #[test]
+    fn test_deflect_conserves_momentum() {
+        let (mut state, id_a, id_b) = head_on(100.0, 300.0);
+        let momentum = |s: &GameState| {
+            let (a, b) = (s.get_player(id_a).unwrap(), s.get_player(id_b).unwrap());
+            a.velocity * a.mass + b.velocity * b.mass
+        };
+        let before = momentum(&state);
+
+        deflect(&mut state, id_a, id_b, Vec2::new(1.0, 0.0), &CollisionResponse::default());
+
+        assert!((momentum(&state) - before).length() < 1e-2);
+        // Relative normal velocity reverses, scaled by restitution
+        let (a, b) = (state.get_player(id_a).unwrap(), state.get_player(id_b).unwrap());
+        assert!(((b.velocity.x - a.velocity.x) - RESTITUTION * 200.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_deflect_lighter_player_knocked_further() {
+        let (mut state, id_a, id_b) = head_on(300.0, 100.0);
+        deflect(&mut state, id_a, id_b, Vec2::new(1.0, 0.0), &CollisionResponse::default());
+
+        let (a, b) = (state.get_player(id_a).unwrap(), state.get_player(id_b).unwrap());
+        assert!((b.velocity.x + 100.0).abs() > (a.velocity.x - 100.0).abs());
+        assert!(b.position.x - 20.0 > -a.position.x);
+        assert!(b.position.x - a.position.x > mass_to_radius(300.0) + mass_to_radius(100.0));
+    }
+
+    #[test]
+    fn test_deflect_restitution_and_knockback_cap() {
+        let (mut state, id_a, id_b) = head_on(100.0, 100.0);
+        let inelastic = CollisionResponse {
+            restitution: 0.0,
+            ..Default::default()
+        };
+        deflect(&mut state, id_a, id_b, Vec2::new(1.0, 0.0), &inelastic);
+        let (a, b) = (state.get_player(id_a).unwrap(), state.get_player(id_b).unwrap());
+        assert!(a.velocity.x.abs() < 1e-3 && b.velocity.x.abs() < 1e-3);
+
+        let (mut state, id_a, id_b) = head_on(100.0, 100.0);
+        let capped = CollisionResponse {
+            knockback_scale: 3.0,
+            max_knockback: 50.0,
+            ..Default::default()
+        };
+        deflect(&mut state, id_a, id_b, Vec2::new(1.0, 0.0), &capped);
+        assert!((state.get_player(id_a).unwrap().velocity.x - 50.0).abs() < 1e-3);
+        assert!((state.get_player(id_b).unwrap().velocity.x + 50.0).abs() < 1e-3);
+    }
 }
@@ -0,0 +1,200 @@
+//! Bot-vs-bot combat resolution outside human view
+//!
+//! A bot chasing another bot fights it with charged projectiles, each one
+//! simulated, collided and absorbed. When no human is close enough to see any
+//! of it, only the outcome matters. Engagements where both bots are further
+//! than `human_clearance` from every alive human are resolved statistically:
+//! the attacker holds fire, and every `interval_ticks` the fight is rolled from
+//! the bots' stats. The attacker spends shot mass in proportion to its
+//! aggression, the target absorbs the share its accuracy would have landed,
+//! and with a chance that also grows with aggression the fight ends in a kill,
+//! won in proportion to mass × accuracy. Only the outcome reaches the world:
+//! mass changes and the usual kill bookkeeping.
+//!
+//! Once a human comes within clearance the engagement drops out at the next
+//! roll and the bots go back to shooting for real.
+
+use rand::Rng;
+
+use crate::config::CombatResolverConfig;
+use crate::game::constants::mass::{ABSORPTION_CAP, ABSORPTION_RATE, MINIMUM};
+use crate::game::constants::spawn::RESPAWN_DELAY;
+use crate::game::state::{GameState, PlayerId};
+use crate::util::vec2::Vec2;
+
+/// A bot chasing another bot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Engagement {
+    pub attacker: PlayerId,
+    pub target: PlayerId,
+    /// Attacker's aggression (0-1): how much it shoots
+    pub aggression: f32,
+    pub attacker_accuracy: f32,
+    pub target_accuracy: f32,
+}
+
+/// A fight resolved into a kill
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedKill {
+    pub killer_id: PlayerId,
+    pub victim_id: PlayerId,
+}
+
+/// The `candidates` within engagement range of their target and out of every
+/// human's view. Nothing when the resolver is disabled.
+pub fn unseen(
+    state: &GameState,
+    candidates: impl IntoIterator<Item = Engagement>,
+    config: &CombatResolverConfig,
+) -> Vec<Engagement> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let humans: Vec<Vec2> = state.players.values().filter(|p| p.alive && !p.is_bot).map(|p| p.position).collect();
+    let clearance_sq = config.human_clearance * config.human_clearance;
+    let range_sq = config.engagement_range * config.engagement_range;
+    let hidden = |position: Vec2| humans.iter().all(|h| h.distance_sq_to(position) > clearance_sq);
+
+    candidates
+        .into_iter()
+        .filter(|e| {
+            let (Some(attacker), Some(target)) = (state.get_player(e.attacker), state.get_player(e.target)) else {
+                return false;
+            };
+            attacker.alive
+                && target.alive
+                && attacker.position.distance_sq_to(target.position) <= range_sq
+                && hidden(attacker.position)
+                && hidden(target.position)
+        })
+        .collect()
+}
+
+/// Roll `engagements` over `window` simulated seconds, applying mass changes
+/// and kills to `state`
+pub fn resolve(
+    state: &mut GameState,
+    engagements: &[Engagement],
+    config: &CombatResolverConfig,
+    window: f32,
+    rng: &mut impl Rng,
+) -> Vec<ResolvedKill> {
+    let mut kills = Vec::new();
+
+    for e in engagements {
+        // An earlier fight this roll may have taken either bot out
+        let (Some(attacker_mass), Some(target_mass)) = (alive_mass(state, e.attacker), alive_mass(state, e.target))
+        else {
+            continue;
+        };
+        let aggression = e.aggression.clamp(0.0, 1.0);
+
+        // Shots fired this window, never below the attacker's minimum mass
+        let spent = (config.mass_exchange_per_sec * window * aggression).min((attacker_mass - MINIMUM).max(0.0));
+        let landed = spent * e.attacker_accuracy.clamp(0.0, 1.0);
+        if let Some(attacker) = state.get_player_mut(e.attacker) {
+            attacker.mass -= spent;
+        }
+        if let Some(target) = state.get_player_mut(e.target) {
+            target.mass += landed;
+        }
+
+        let kill_chance = 1.0 - (-config.kill_rate_per_sec * window * aggression).exp();
+        if !rng.gen_bool(kill_chance.clamp(0.0, 1.0) as f64) {
+            continue;
+        }
+        let attacker_strength = (attacker_mass - spent) * e.attacker_accuracy.max(0.0);
+        let target_strength = (target_mass + landed) * e.target_accuracy.max(0.0);
+        let total = attacker_strength + target_strength;
+        let attacker_wins = total <= 0.0 || rng.gen_bool((attacker_strength / total).clamp(0.0, 1.0) as f64);
+
+        let (killer_id, victim_id) = if attacker_wins { (e.attacker, e.target) } else { (e.target, e.attacker) };
+        apply_kill(state, killer_id, victim_id);
+        kills.push(ResolvedKill { killer_id, victim_id });
+    }
+
+    kills
+}
+
+fn alive_mass(state: &GameState, id: PlayerId) -> Option<f32> {
+    state.get_player(id).filter(|p| p.alive).map(|p| p.mass)
+}
+
+/// Same bookkeeping as a collision kill
+fn apply_kill(state: &mut GameState, killer_id: PlayerId, victim_id: PlayerId) {
+    let Some(victim) = state.get_player_mut(victim_id) else {
+        return;
+    };
+    let mass_gain = (victim.mass * ABSORPTION_RATE).min(ABSORPTION_CAP);
+    victim.alive = false;
+    victim.deaths += 1;
+    victim.respawn_timer = RESPAWN_DELAY;
+    if let Some(killer) = state.get_player_mut(killer_id) {
+        killer.kills += 1;
+        killer.mass += mass_gain;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Player;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn add(state: &mut GameState, x: f32, is_bot: bool) -> PlayerId {
+        let mut player = Player::new(uuid::Uuid::new_v4(), "P".to_string(), is_bot, 0);
+        player.position = Vec2::new(x, 0.0);
+        let id = player.id;
+        state.add_player(player);
+        id
+    }
+
+    fn engagement(attacker: PlayerId, target: PlayerId) -> Engagement {
+        Engagement { attacker, target, aggression: 1.0, attacker_accuracy: 0.9, target_accuracy: 0.5 }
+    }
+
+    #[test]
+    fn test_only_fights_out_of_human_view_are_unseen() {
+        let mut state = GameState::new();
+        let config = CombatResolverConfig::default();
+        add(&mut state, 0.0, false);
+        let (near_a, near_b) = (add(&mut state, 1000.0, true), add(&mut state, 1500.0, true));
+        let (far_a, far_b) = (add(&mut state, 9000.0, true), add(&mut state, 9500.0, true));
+        let distant = add(&mut state, 20000.0, true);
+
+        let candidates = [engagement(near_a, near_b), engagement(far_a, far_b), engagement(far_a, distant)];
+        assert_eq!(unseen(&state, candidates, &config), vec![engagement(far_a, far_b)]);
+
+        let disabled = CombatResolverConfig { enabled: false, ..Default::default() };
+        assert!(unseen(&state, candidates, &disabled).is_empty());
+    }
+
+    #[test]
+    fn test_resolution_moves_mass_and_kills() {
+        let mut state = GameState::new();
+        let (a, b) = (add(&mut state, 9000.0, true), add(&mut state, 9500.0, true));
+        let fight = [engagement(a, b)];
+
+        // No kill chance: the attacker only trades shot mass for hits
+        let config = CombatResolverConfig { kill_rate_per_sec: 0.0, mass_exchange_per_sec: 10.0, ..Default::default() };
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!(resolve(&mut state, &fight, &config, 1.0, &mut rng).is_empty());
+        assert_eq!(state.get_player(a).unwrap().mass, 90.0);
+        assert_eq!(state.get_player(b).unwrap().mass, 109.0);
+
+        // Certain kill: exactly one bot dies and the other absorbs it
+        let config = CombatResolverConfig { kill_rate_per_sec: 5.0, mass_exchange_per_sec: 0.0, ..Default::default() };
+        let kills = resolve(&mut state, &fight, &config, 100.0, &mut rng);
+        assert_eq!(kills.len(), 1);
+        let killer = state.get_player(kills[0].killer_id).unwrap();
+        let victim = state.get_player(kills[0].victim_id).unwrap();
+        assert!(killer.alive && !victim.alive);
+        assert_eq!((killer.kills, victim.deaths), (1, 1));
+        assert!(killer.mass > 150.0);
+
+        // Dead bots don't fight again
+        assert!(resolve(&mut state, &fight, &config, 100.0, &mut rng).is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! Pluggable game systems
+//!
+//! Lets embedders insert their own per-tick logic (pickups, scripted events)
+//! at fixed points of `GameLoop::tick()` without touching the built-in systems.
+
+#![allow(dead_code)] // Public API for embedders
+
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::game::game_loop::GameLoopEvent;
+use crate::game::state::GameState;
+
+/// A custom system run by the game loop every playing tick
+pub trait GameSystem: Send {
+    /// Run one step. Push any events to `events`; they are broadcast like built-in ones.
+    fn run(&mut self, state: &mut GameState, dt: f32, events: &mut Vec<GameLoopEvent>);
+
+    /// Human-readable name (for logs/debugging)
+    fn name(&self) -> &str {
+        "custom"
+    }
+}
+
+/// Point in the tick at which a custom system runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemPhase {
+    /// After player/AI inputs are applied, before gravity and physics
+    Input,
+    /// After gravity, physics and gravity waves, before collision
+    Physics,
+    /// After collision resolution, before arena/debris updates
+    Collision,
+    /// After all built-in systems, before the match end check
+    Late,
+}
+
+/// Shared handle to a registered system
+/// Wrapped in Arc<Mutex> so `GameLoopConfig` stays `Clone`
+pub type SharedGameSystem = Arc<Mutex<dyn GameSystem>>;
+
+/// Ordered list of custom systems, grouped by phase at run time
+#[derive(Clone, Default)]
+pub struct SystemRegistry {
+    entries: Vec<(SystemPhase, SharedGameSystem)>,
+}
+
+impl SystemRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a system; systems in the same phase run in registration order
+    ///
+    /// Returns a typed handle so the caller can inspect/adjust the system later.
+    pub fn register<S: GameSystem + 'static>(&mut self, phase: SystemPhase, system: S) -> Arc<Mutex<S>> {
+        let handle = Arc::new(Mutex::new(system));
+        self.entries.push((phase, handle.clone() as SharedGameSystem));
+        handle
+    }
+
+    /// Register every system of `other` after this registry's own
+    pub fn append(&mut self, other: &SystemRegistry) {
+        self.entries.extend(other.entries.iter().cloned());
+    }
+
+    /// Number of registered systems
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Run every system registered for `phase`
+    pub fn run_phase(
+        &self,
+        phase: SystemPhase,
+        state: &mut GameState,
+        dt: f32,
+        events: &mut Vec<GameLoopEvent>,
+    ) {
+        for (system_phase, system) in &self.entries {
+            if *system_phase == phase {
+                system.lock().run(state, dt, events);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SystemRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for (phase, system) in &self.entries {
+            list.entry(&(phase, system.lock().name().to_string()));
+        }
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter {
+        runs: u32,
+    }
+
+    impl GameSystem for Counter {
+        fn run(&mut self, state: &mut GameState, _dt: f32, events: &mut Vec<GameLoopEvent>) {
+            self.runs += 1;
+            events.push(GameLoopEvent::Tick { tick: state.tick });
+        }
+
+        fn name(&self) -> &str {
+            "counter"
+        }
+    }
+
+    #[test]
+    fn test_run_phase_only_runs_matching_systems() {
+        let mut registry = SystemRegistry::new();
+        let post_input = registry.register(SystemPhase::Input, Counter { runs: 0 });
+        let post_tick = registry.register(SystemPhase::Late, Counter { runs: 0 });
+        assert_eq!(registry.len(), 2);
+
+        let mut state = GameState::new();
+        let mut events = Vec::new();
+        registry.run_phase(SystemPhase::Input, &mut state, 0.1, &mut events);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(post_input.lock().runs, 1);
+
+        // Clones share the same system instance
+        let cloned = registry.clone();
+        cloned.run_phase(SystemPhase::Input, &mut state, 0.1, &mut events);
+        assert_eq!(post_input.lock().runs, 2);
+        assert_eq!(post_tick.lock().runs, 0);
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_debug_lists_names() {
+        let mut registry = SystemRegistry::new();
+        registry.register(SystemPhase::Collision, Counter { runs: 0 });
+        let debug = format!("{:?}", registry);
+        assert!(debug.contains("counter"));
+        assert!(debug.contains("Collision"));
+    }
+}
@@ -1,11 +1,13 @@
 //! Debris spawning system
 //! Spawns collectible debris particles across the arena zones for players to collect
-//! Also spawns debris in orbital rings around gravity wells for concentrated "feeding zones"
+//! Also spawns debris in orbital rings around gravity wells for concentrated "feeding zones",
+//! and the fragments of broken asteroids
 
 use rand::Rng;
 
 use crate::config::DebrisSpawnConfig;
 use crate::game::constants::arena::{CORE_RADIUS, INNER_RADIUS, MIDDLE_RADIUS, OUTER_RADIUS};
+use crate::game::modifiers::Parameter;
 use crate::game::state::{DebrisSize, GameState, GravityWell};
 use crate::util::vec2::Vec2;
 
@@ -105,6 +107,7 @@ pub fn spawn_initial(state: &mut GameState, config: &DebrisSpawnConfig) {
 }
 
 /// Update debris spawning - accumulate spawn rates and spawn when ready
+/// (rates scaled by any debris surge modifier)
 pub fn update(
     state: &mut GameState,
     config: &DebrisSpawnConfig,
@@ -121,6 +124,7 @@ pub fn update(
     }
 
     // Accumulate spawn times
+    let dt = dt * state.modifiers.multiplier(Parameter::DebrisSpawnRate);
     spawn_state.inner_small += config.spawn_rate_inner_small * dt;
     spawn_state.inner_medium += config.spawn_rate_inner_medium * dt;
     spawn_state.inner_large += config.spawn_rate_inner_large * dt;
@@ -274,7 +278,7 @@ pub fn spawn_around_wells(state: &mut GameState, config: &DebrisSpawnConfig) {
 }
 
 /// Spawn a single debris particle in an orbital ring around a gravity well
-fn spawn_near_well(state: &mut GameState, config: &DebrisSpawnConfig, well: &GravityWell) {
+pub(crate) fn spawn_near_well(state: &mut GameState, config: &DebrisSpawnConfig, well: &GravityWell) {
     let mut rng = rand::thread_rng();
 
     // Orbital ring: between 2.5x and 5x the well's core radius (death zone)
@@ -312,6 +316,31 @@ fn spawn_near_well(state: &mut GameState, config: &DebrisSpawnConfig, well: &Gra
     state.add_debris_with_lifetime(position, velocity, size, config.lifetime);
 }
 
+// === FRAGMENTS ===
+
+/// Scatter fragments of something that broke apart at `center` (within
+/// `radius` of it), flying outward. At most `config.max_fragments` of `sizes`
+/// spawn, and none past `config.max_count`; returns how many did.
+pub fn spawn_fragments(
+    state: &mut GameState,
+    config: &DebrisSpawnConfig,
+    center: Vec2,
+    radius: f32,
+    sizes: &[DebrisSize],
+) -> usize {
+    let mut rng = rand::thread_rng();
+    let room = config.max_count.saturating_sub(state.debris.len());
+    let count = sizes.len().min(config.max_fragments).min(room);
+
+    for &size in &sizes[..count] {
+        let direction = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+        let position = center + direction * rng.gen_range(0.0..radius.max(1.0));
+        let speed = rng.gen_range(config.orbital_velocity_min..config.orbital_velocity_max);
+        state.add_debris_with_lifetime(position, direction * speed, size, config.lifetime);
+    }
+    count
+}
+
 /// Update: spawn debris around wells over time (called each tick)
 pub fn update_well_spawning(
     state: &mut GameState,
@@ -330,7 +359,7 @@ pub fn update_well_spawning(
     }
 
     // Accumulate spawn time
-    *accumulator += config.well_spawn_rate * dt;
+    *accumulator += config.well_spawn_rate * state.modifiers.multiplier(Parameter::DebrisSpawnRate) * dt;
 
     // Spawn when accumulator exceeds 1
     while *accumulator >= 1.0 && state.debris.len() < config.max_count {
@@ -416,6 +445,27 @@ mod tests {
         assert!(state.debris.len() >= 9 && state.debris.len() <= 11);
     }
 
+    #[test]
+    fn test_debris_surge_doubles_spawning() {
+        use crate::game::modifiers::GlobalModifier;
+
+        let mut config = test_config();
+        config.spawn_rate_inner_small = 10.0;
+        let spawned = |surge: bool| {
+            let mut state = GameState::new();
+            if surge {
+                state.modifiers.activate(GlobalModifier::DebrisSurge, 10.0);
+            }
+            let mut spawn_state = DebrisSpawnState::new();
+            for _ in 0..30 {
+                update(&mut state, &config, &mut spawn_state, 1.0 / 30.0);
+            }
+            state.debris.len() as i32
+        };
+
+        assert!((spawned(true) - 2 * spawned(false)).abs() <= 2);
+    }
+
     #[test]
     fn test_debris_positions_in_zone() {
         let mut state = GameState::new();
@@ -446,6 +496,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fragments_capped_per_break_and_by_max_count() {
+        let mut state = GameState::new();
+        let mut config = test_config();
+        config.max_fragments = 5;
+        config.max_count = 8;
+        let sizes = [DebrisSize::Small; 20];
+
+        assert_eq!(spawn_fragments(&mut state, &config, Vec2::new(500.0, 0.0), 40.0, &sizes), 5);
+        assert!(state.debris.iter().all(|d| d.position.distance_to(Vec2::new(500.0, 0.0)) < 40.0));
+        // Only room for three more
+        assert_eq!(spawn_fragments(&mut state, &config, Vec2::ZERO, 40.0, &sizes), 3);
+        assert_eq!(state.debris.len(), config.max_count);
+    }
+
     // === EDGE CASE TESTS ===
 
     #[test]
@@ -0,0 +1,151 @@
+//! Dynamic difficulty for solo humans
+//!
+//! With one or two humans online, every hunting bot in the arena is after
+//! them, and a new player can be farmed before they learn to orbit. While at
+//! most `max_humans` humans are in the match, the controller tracks each one's
+//! deaths over the last `window_secs` and eases off the bots around them: at
+//! or below `target_deaths_per_min` bots play at full strength, and the
+//! aggression/accuracy multiplier falls linearly to `min_multiplier` at
+//! `max_deaths_per_min`. Bots within `radius` of a human take that human's
+//! multiplier (the lowest, if several are near). Deaths age out of the window,
+//! so bots sharpen back up as the player stops dying.
+//!
+//! The multipliers live in [`AiManagerSoA`]; personalities themselves are
+//! never changed.
+
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashMap;
+
+use crate::config::DifficultyConfig;
+use crate::game::state::{GameState, PlayerId};
+use crate::game::systems::ai_soa::AiManagerSoA;
+use crate::util::vec2::Vec2;
+
+/// Ticks between difficulty updates
+pub const UPDATE_INTERVAL_TICKS: u64 = 30;
+
+/// Recent deaths of one human
+#[derive(Debug, Default)]
+struct DeathRecord {
+    /// Death counter when last seen
+    deaths: u32,
+    /// Match time of each death in the window
+    recent: VecDeque<f32>,
+}
+
+/// Tracks humans' recent deaths and scales the bots around them
+#[derive(Debug, Default)]
+pub struct DifficultyController {
+    humans: FxHashMap<PlayerId, DeathRecord>,
+    /// Some bot has a multiplier other than 1.0
+    scaled: bool,
+}
+
+impl DifficultyController {
+    /// Record new deaths and set every bot's multipliers
+    pub fn update(&mut self, state: &GameState, ai: &mut AiManagerSoA, config: &DifficultyConfig) {
+        let now = state.match_state.match_time;
+        self.humans.retain(|id, _| state.players.get(id).is_some_and(|p| !p.is_bot));
+
+        let mut humans: Vec<(Vec2, f32)> = Vec::new();
+        for player in state.players.values().filter(|p| !p.is_bot) {
+            let record = self
+                .humans
+                .entry(player.id)
+                .or_insert_with(|| DeathRecord { deaths: player.deaths, recent: VecDeque::new() });
+            for _ in record.deaths..player.deaths {
+                record.recent.push_back(now);
+            }
+            record.deaths = player.deaths;
+            while record.recent.front().is_some_and(|&at| now - at > config.window_secs) {
+                record.recent.pop_front();
+            }
+            humans.push((player.position, multiplier(record.recent.len(), config)));
+        }
+
+        let active = config.enabled && (1..=config.max_humans).contains(&humans.len());
+        if !active && !self.scaled {
+            return;
+        }
+
+        let radius_sq = config.radius * config.radius;
+        self.scaled = false;
+        for i in 0..ai.count {
+            let scale = match state.get_player(ai.bot_ids[i]) {
+                Some(bot) if active => humans
+                    .iter()
+                    .filter(|(position, _)| position.distance_sq_to(bot.position) <= radius_sq)
+                    .map(|&(_, multiplier)| multiplier)
+                    .fold(1.0, f32::min),
+                _ => 1.0,
+            };
+            ai.aggression_scale[i] = scale;
+            ai.accuracy_scale[i] = scale;
+            self.scaled |= scale < 1.0;
+        }
+    }
+}
+
+/// Multiplier for `deaths` within the window
+fn multiplier(deaths: usize, config: &DifficultyConfig) -> f32 {
+    let per_min = deaths as f32 * 60.0 / config.window_secs;
+    let span = (config.max_deaths_per_min - config.target_deaths_per_min).max(0.01);
+    let struggle = ((per_min - config.target_deaths_per_min) / span).clamp(0.0, 1.0);
+    1.0 - struggle * (1.0 - config.min_multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Player;
+
+    fn add(state: &mut GameState, x: f32, is_bot: bool) -> PlayerId {
+        let mut player = Player::new(uuid::Uuid::new_v4(), "P".to_string(), is_bot, 0);
+        player.position = Vec2::new(x, 0.0);
+        let id = player.id;
+        state.add_player(player);
+        id
+    }
+
+    #[test]
+    fn test_multiplier_falls_with_deaths_per_minute() {
+        // 120s window: 2 deaths = 1/min, 8 deaths = 4/min
+        let config = DifficultyConfig::default();
+        assert_eq!(multiplier(0, &config), 1.0);
+        assert_eq!(multiplier(2, &config), 1.0);
+        assert!((multiplier(5, &config) - 0.7).abs() < 0.001);
+        assert!((multiplier(8, &config) - config.min_multiplier).abs() < 0.001);
+        assert_eq!(multiplier(50, &config), multiplier(8, &config));
+    }
+
+    #[test]
+    fn test_bots_near_struggling_solo_human_ease_off() {
+        let config = DifficultyConfig::default();
+        let mut state = GameState::new();
+        let mut ai = AiManagerSoA::new();
+        let human = add(&mut state, 0.0, false);
+        let (near, far) = (add(&mut state, 1000.0, true), add(&mut state, 9000.0, true));
+        ai.register_bot(near);
+        ai.register_bot(far);
+        let mut controller = DifficultyController::default();
+        controller.update(&state, &mut ai, &config);
+
+        state.get_player_mut(human).unwrap().deaths = 8;
+        state.match_state.match_time = 60.0;
+        controller.update(&state, &mut ai, &config);
+        let (near_idx, far_idx) = (ai.get_index(near).unwrap() as usize, ai.get_index(far).unwrap() as usize);
+        assert!((ai.aggression_scale[near_idx] - config.min_multiplier).abs() < 0.001);
+        assert_eq!(ai.accuracy_scale[far_idx], 1.0);
+        assert!(ai.effective_accuracy(near_idx) < ai.accuracy[near_idx]);
+
+        // More than `max_humans` humans turn it off; deaths also age out of the window
+        add(&mut state, 0.0, false);
+        add(&mut state, 0.0, false);
+        controller.update(&state, &mut ai, &config);
+        assert_eq!(ai.aggression_scale[near_idx], 1.0);
+        state.match_state.match_time = 200.0;
+        controller.update(&state, &mut ai, &config);
+        assert_eq!(multiplier(controller.humans[&human].recent.len(), &config), 1.0);
+    }
+}
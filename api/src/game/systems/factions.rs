@@ -0,0 +1,289 @@
+//! Bot factions
+//!
+//! With factions enabled every bot joins one of `count` factions (the
+//! smallest, when it first shows up) and each faction holds a home well. Its
+//! bots orbit that well rather than the nearest one and engage bots of other
+//! factions that come within `home_radius` of it. Every `raid_interval_secs`
+//! the next faction in turn raids whichever rival holds the most AI zones
+//! (ties go to the nearest): for `raid_duration_secs` its bots orbit the
+//! rival's home and fight there instead. Territory then shifts on its own as
+//! raids thin out defenders and bots drift between wells.
+//!
+//! The faction lives on `Player::faction` so it reaches snapshots (for
+//! colors) and the AI zone stats; orders reach the AI as one
+//! [`FactionOrder`] per faction. Humans never join a faction.
+
+use tracing::debug;
+
+use crate::config::FactionConfig;
+use crate::game::state::{GameState, WellId, CENTRAL_WELL_ID};
+use crate::game::systems::ai_soa::AiManagerSoA;
+use crate::util::vec2::Vec2;
+
+/// Ticks between faction updates
+pub const UPDATE_INTERVAL_TICKS: u64 = 15;
+
+/// Most factions a config can ask for (faction ids are 1..=MAX_FACTIONS)
+pub const MAX_FACTIONS: usize = 8;
+
+/// Where one faction's bots orbit and fight, handed to the AI every update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FactionOrder {
+    /// Well its bots orbit: home, or the raided faction's home
+    pub well: WellId,
+    /// Center of the region its bots fight for
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl FactionOrder {
+    #[inline]
+    pub fn contains(&self, position: Vec2) -> bool {
+        position.distance_sq_to(self.center) <= self.radius * self.radius
+    }
+}
+
+/// Order for a faction id (0 = none) out of the AI's per-faction orders
+#[inline]
+pub fn faction_order(orders: &[Option<FactionOrder>], faction: u8) -> Option<&FactionOrder> {
+    orders.get((faction as usize).wrapping_sub(1))?.as_ref()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Faction {
+    home_well: Option<WellId>,
+    /// Faction id being raided
+    raiding: Option<u8>,
+}
+
+/// Assigns bots to factions, keeps their homes and runs raids
+#[derive(Debug, Default)]
+pub struct FactionController {
+    /// Index = faction id - 1
+    factions: Vec<Faction>,
+    /// Seconds until the next raid starts
+    raid_timer: f32,
+    /// Seconds left in the current raid
+    raid_remaining: f32,
+    /// Faction index taking the next raid
+    next_raider: usize,
+}
+
+impl FactionController {
+    /// Assign new bots, re-home factions whose well is gone, advance raids and
+    /// publish orders. `dt` is the simulated time since the last update.
+    pub fn update(&mut self, state: &mut GameState, ai: &mut AiManagerSoA, config: &FactionConfig, dt: f32) {
+        if !config.enabled {
+            if !self.factions.is_empty() {
+                for player in state.players.values_mut() {
+                    player.faction = 0;
+                }
+                self.factions.clear();
+                ai.faction_orders.clear();
+            }
+            return;
+        }
+
+        let count = (config.count as usize).clamp(1, MAX_FACTIONS);
+        if self.factions.len() != count {
+            self.factions = vec![Faction::default(); count];
+            self.raid_timer = config.raid_interval_secs;
+            self.raid_remaining = 0.0;
+        }
+
+        self.assign_bots(state, count);
+        self.assign_homes(state);
+        self.advance_raids(state, ai, config, dt);
+
+        ai.faction_orders = self
+            .factions
+            .iter()
+            .map(|faction| {
+                let held = faction.raiding.map_or(Some(faction), |target| self.factions.get(target as usize - 1));
+                let well = held?.home_well?;
+                let center = state.arena.gravity_wells.get(&well)?.position;
+                Some(FactionOrder { well, center, radius: config.home_radius })
+            })
+            .collect();
+    }
+
+    /// Put bots without a (valid) faction into the smallest one
+    fn assign_bots(&self, state: &mut GameState, count: usize) {
+        let mut members = [0usize; MAX_FACTIONS];
+        for player in state.players.values() {
+            if player.is_bot && (1..=count).contains(&(player.faction as usize)) {
+                members[player.faction as usize - 1] += 1;
+            }
+        }
+        for player in state.players.values_mut() {
+            if !player.is_bot {
+                player.faction = 0;
+                continue;
+            }
+            if (1..=count).contains(&(player.faction as usize)) {
+                continue;
+            }
+            let smallest = (0..count).min_by_key(|&i| members[i]).unwrap_or(0);
+            members[smallest] += 1;
+            player.faction = smallest as u8 + 1;
+        }
+    }
+
+    /// Give every faction whose home well is missing a free orbital well
+    /// (shared once there are more factions than wells)
+    fn assign_homes(&mut self, state: &GameState) {
+        let mut wells: Vec<WellId> =
+            state.arena.gravity_wells.keys().copied().filter(|&id| id != CENTRAL_WELL_ID).collect();
+        wells.sort_unstable();
+        for faction in &mut self.factions {
+            if faction.home_well.is_some_and(|well| !wells.contains(&well)) {
+                faction.home_well = None;
+            }
+        }
+        if wells.is_empty() {
+            return;
+        }
+        for i in 0..self.factions.len() {
+            if self.factions[i].home_well.is_some() {
+                continue;
+            }
+            let taken: Vec<WellId> = self.factions.iter().filter_map(|f| f.home_well).collect();
+            let home = wells.iter().copied().find(|w| !taken.contains(w)).unwrap_or(wells[i % wells.len()]);
+            self.factions[i].home_well = Some(home);
+        }
+    }
+
+    fn advance_raids(&mut self, state: &GameState, ai: &AiManagerSoA, config: &FactionConfig, dt: f32) {
+        if self.raid_remaining > 0.0 {
+            self.raid_remaining -= dt;
+            if self.raid_remaining <= 0.0 {
+                for faction in &mut self.factions {
+                    faction.raiding = None;
+                }
+            }
+            return;
+        }
+
+        self.raid_timer -= dt;
+        if self.raid_timer > 0.0 || self.factions.len() < 2 {
+            return;
+        }
+        self.raid_timer = config.raid_interval_secs;
+
+        let raider = self.next_raider % self.factions.len();
+        self.next_raider = raider + 1;
+        let home = |index: usize| {
+            self.factions[index].home_well.and_then(|w| state.arena.gravity_wells.get(&w)).map(|w| w.position)
+        };
+        let Some(raider_home) = home(raider) else {
+            return;
+        };
+
+        // Raid the rival holding the most zones, the nearest one on a tie
+        let mut zones_held = [0usize; MAX_FACTIONS];
+        for zone in ai.zone_grid.zones() {
+            if let Some(faction) = zone.dominant_faction() {
+                zones_held[faction as usize - 1] += 1;
+            }
+        }
+        let target = (0..self.factions.len())
+            .filter(|&i| i != raider)
+            .filter_map(|i| Some((i, home(i)?.distance_sq_to(raider_home))))
+            .min_by(|a, b| zones_held[b.0].cmp(&zones_held[a.0]).then(a.1.total_cmp(&b.1)))
+            .map(|(i, _)| i);
+        let Some(target) = target else {
+            return;
+        };
+
+        debug!(
+            "Faction {} raids faction {} ({} zones held)",
+            raider + 1,
+            target + 1,
+            zones_held[target]
+        );
+        self.factions[raider].raiding = Some(target as u8 + 1);
+        self.raid_remaining = config.raid_duration_secs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::{GravityWell, Player};
+    use uuid::Uuid;
+
+    fn setup(bots: usize) -> (GameState, AiManagerSoA) {
+        let mut state = GameState::new();
+        state.arena.gravity_wells.clear();
+        for id in 1..=3 {
+            let position = Vec2::new(id as f32 * 3000.0, 0.0);
+            state.arena.gravity_wells.insert(id, GravityWell::new(id, position, 10000.0, 50.0));
+        }
+        let mut ai = AiManagerSoA::default();
+        for i in 0..bots {
+            let mut bot = Player::new(Uuid::new_v4(), format!("Bot{}", i), true, 0);
+            bot.position = Vec2::new(3000.0, 200.0);
+            ai.register_bot(bot.id);
+            state.add_player(bot);
+        }
+        let human = Player::new(Uuid::new_v4(), "Human".to_string(), false, 0);
+        state.add_player(human);
+        (state, ai)
+    }
+
+    fn config() -> FactionConfig {
+        FactionConfig { enabled: true, count: 3, ..Default::default() }
+    }
+
+    #[test]
+    fn test_bots_are_balanced_and_homed() {
+        let (mut state, mut ai) = setup(7);
+        let mut controller = FactionController::default();
+        controller.update(&mut state, &mut ai, &config(), 0.5);
+
+        let mut members = [0; 3];
+        for player in state.players.values() {
+            match player.is_bot {
+                true => members[player.faction as usize - 1] += 1,
+                false => assert_eq!(player.faction, 0),
+            }
+        }
+        assert_eq!(members, [3, 2, 2]);
+        let wells: Vec<_> = ai.faction_orders.iter().map(|o| o.unwrap().well).collect();
+        assert_eq!(wells, [1, 2, 3]);
+
+        // A collapsed home well is replaced, sharing one as no well is free
+        state.arena.gravity_wells.remove(&2);
+        controller.update(&mut state, &mut ai, &config(), 0.5);
+        assert_eq!(ai.faction_orders[1].unwrap().well, 3);
+
+        controller.update(&mut state, &mut ai, &FactionConfig::default(), 0.5);
+        assert!(ai.faction_orders.is_empty());
+        assert!(state.players.values().all(|p| p.faction == 0));
+    }
+
+    #[test]
+    fn test_raid_targets_strongest_rival_then_ends() {
+        let (mut state, mut ai) = setup(6);
+        let config = FactionConfig { raid_interval_secs: 10.0, raid_duration_secs: 5.0, ..config() };
+        let mut controller = FactionController::default();
+        controller.update(&mut state, &mut ai, &config, 0.5);
+        // Every bot sits in one zone; outnumbering the rest there, faction 1 holds it
+        for bot in state.players.values_mut().filter(|p| p.is_bot).take(4) {
+            bot.faction = 1;
+        }
+        ai.update_zones(&state);
+        assert_eq!(ai.zone_grid.zones().filter_map(|z| z.dominant_faction()).collect::<Vec<_>>(), [1]);
+
+        // Faction 2 raids faction 1, which stays home to defend
+        controller.next_raider = 1;
+        controller.update(&mut state, &mut ai, &config, 10.0);
+        let homes: Vec<_> = controller.factions.iter().map(|f| f.home_well.unwrap()).collect();
+        assert_eq!(ai.faction_orders[1].unwrap().well, homes[0]);
+        assert_eq!(ai.faction_orders[0].unwrap().well, homes[0]);
+
+        // Back home once the raid is over
+        controller.update(&mut state, &mut ai, &config, 5.0);
+        assert_eq!(ai.faction_orders[1].unwrap().well, homes[1]);
+    }
+}
@@ -0,0 +1,122 @@
+//! Shared flow fields for low-LOD bot steering
+//!
+//! Reduced and Dormant bots don't need individually computed directions:
+//! bots in the same AI zone want roughly the same thing. Once per tick the
+//! field stores, for every zone holding bots, the nearest debris cluster and
+//! the dominant threat in the surrounding 3x3 zones. Bots at those LODs steer
+//! toward or away from their zone's entry instead of scanning every piece of
+//! debris, turning Collect from O(bots × debris) into O(zones × clusters +
+//! bots). Full-LOD bots (near humans) keep exact per-bot steering, and Chase
+//! stays per-bot since it leads one specific target at constant cost.
+
+use hashbrown::HashMap;
+
+use crate::game::systems::ai_soa::ZoneGrid;
+use crate::util::vec2::Vec2;
+
+/// Steering targets shared by every bot in one zone
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FlowCell {
+    /// Centroid of the nearest debris cluster (None when there is no debris)
+    pub debris_target: Option<Vec2>,
+    /// Center of the neighbouring zone with the most threat mass (None when no threat nearby)
+    pub threat_source: Option<Vec2>,
+}
+
+impl FlowCell {
+    /// Unit direction toward the debris target
+    pub fn toward_debris(&self, position: Vec2) -> Option<Vec2> {
+        self.debris_target.map(|target| (target - position).normalize())
+    }
+
+    /// Unit direction away from the threat source
+    pub fn away_from_threat(&self, position: Vec2) -> Option<Vec2> {
+        self.threat_source.map(|source| (position - source).normalize())
+    }
+}
+
+/// Per-zone steering targets, rebuilt from the zone grid every AI tick
+#[derive(Debug, Default)]
+pub struct FlowField {
+    cells: HashMap<(i32, i32), FlowCell>,
+}
+
+impl FlowField {
+    /// Recompute the field from freshly updated zones and the current debris
+    pub fn rebuild(&mut self, zones: &ZoneGrid, debris: impl Iterator<Item = Vec2>) {
+        self.cells.clear();
+
+        // Debris clustered by zone: (position sum, count)
+        let mut sums: HashMap<(i32, i32), (Vec2, u32)> = HashMap::new();
+        for position in debris {
+            let entry = sums.entry(zones.position_to_cell(position)).or_insert((Vec2::ZERO, 0));
+            entry.0 += position;
+            entry.1 += 1;
+        }
+        let clusters: Vec<Vec2> = sums.values().map(|&(sum, count)| sum * (1.0 / count as f32)).collect();
+
+        for zone in zones.zones().filter(|z| z.bot_count > 0) {
+            let cell = zones.position_to_cell(zone.center);
+            let debris_target = clusters
+                .iter()
+                .copied()
+                .min_by(|a, b| a.distance_sq_to(zone.center).total_cmp(&b.distance_sq_to(zone.center)));
+            let threat_source = zones
+                .adjacent_cells(cell)
+                .filter_map(|c| zones.get_zone(c))
+                .filter(|z| z.threat_mass > 0.0)
+                .max_by(|a, b| a.threat_mass.total_cmp(&b.threat_mass))
+                .map(|z| z.center);
+            self.cells.insert(cell, FlowCell { debris_target, threat_source });
+        }
+    }
+
+    /// Steering targets for the zone containing `position`
+    pub fn sample(&self, zones: &ZoneGrid, position: Vec2) -> Option<&FlowCell> {
+        self.cells.get(&zones.position_to_cell(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zones_with_bots(cells: &[(i32, i32)]) -> ZoneGrid {
+        let mut zones = ZoneGrid::new(1000.0);
+        for &cell in cells {
+            zones.get_or_create_zone(cell).bot_count = 1;
+        }
+        zones
+    }
+
+    #[test]
+    fn test_debris_target_is_nearest_cluster_centroid() {
+        let zones = zones_with_bots(&[(0, 0), (5, 0)]);
+        let debris = [Vec2::new(1200.0, 400.0), Vec2::new(1400.0, 600.0), Vec2::new(-8000.0, 0.0)];
+        let mut field = FlowField::default();
+        field.rebuild(&zones, debris.into_iter());
+
+        let cell = field.sample(&zones, Vec2::new(100.0, 100.0)).unwrap();
+        assert_eq!(cell.debris_target, Some(Vec2::new(1300.0, 500.0)));
+        let dir = cell.toward_debris(Vec2::new(1300.0, 0.0)).unwrap();
+        assert!((dir - Vec2::new(0.0, 1.0)).length() < 1e-5);
+
+        // Zones without bots get no entry
+        assert!(field.sample(&zones, Vec2::new(-8000.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn test_threat_source_is_heaviest_neighbour() {
+        let mut zones = zones_with_bots(&[(0, 0)]);
+        zones.get_or_create_zone((1, 0)).threat_mass = 100.0;
+        zones.get_or_create_zone((0, -1)).threat_mass = 300.0;
+        zones.get_or_create_zone((3, 0)).threat_mass = 1000.0; // Not adjacent
+        let mut field = FlowField::default();
+        field.rebuild(&zones, std::iter::empty());
+
+        let cell = field.sample(&zones, Vec2::new(500.0, 500.0)).unwrap();
+        assert_eq!(cell.debris_target, None);
+        assert_eq!(cell.threat_source, Some(Vec2::new(500.0, -500.0)));
+        assert_eq!(cell.away_from_threat(Vec2::new(500.0, 500.0)), Some(Vec2::new(0.0, 1.0)));
+    }
+}
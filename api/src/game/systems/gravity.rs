@@ -104,6 +104,8 @@ const WAVE_DEBRIS_FORCE_RATIO: f32 = 0.7;
 /// Apply gravity from gravity wells to all entities
 /// Dispatches to limited or unlimited mode based on config
 pub fn update_central_with_config(state: &mut GameState, config: &GravityConfig, dt: f32) {
+    // Gravity only ever adds `acceleration * dt` to velocities, so scaling the step scales the pull
+    let dt = dt * config.strength;
     match config.range_mode {
         GravityRangeMode::Limited => update_central_limited(state, config, dt),
         GravityRangeMode::Unlimited => update_central_unlimited(state, dt),
@@ -596,6 +598,9 @@ pub fn update_explosions(
     let mut new_charging_this_tick: usize = 0;
     let max_charging = config.max_concurrent_charging;
 
+    // Hand-made layouts keep their wells: an explosion only restarts the timer
+    let fixed_layout = state.arena.fixed_layout;
+
     // Skip central well (ID 0 - supermassive black hole, too stable to explode)
     for well in state.arena.gravity_wells.values_mut() {
         if well.id == crate::game::state::CENTRAL_WELL_ID {
@@ -640,7 +645,13 @@ pub fn update_explosions(
             // Create the gravity wave
             new_waves.push(GravityWave::new(well.position, strength));
 
-            // ALWAYS destroy the well - scale_for_simulation will add new ones at DIFFERENT positions
+            if fixed_layout {
+                well.is_charging = false;
+                well.explosion_timer = config.random_explosion_delay();
+                continue;
+            }
+
+            // Destroy the well - scale_for_simulation will add new ones at DIFFERENT positions
             // This keeps the arena dynamic: wells cycle through new locations over time
             // The golden angle distribution ensures new wells appear at different angles
             wells_to_remove.push(well.id);
@@ -751,6 +762,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         };
         state.add_player(player);
         (state, player_id)
@@ -1591,6 +1606,7 @@ mod tests {
         let config = GravityConfig {
             range_mode: GravityRangeMode::Unlimited,
             influence_radius: 5000.0,
+            strength: 1.0,
         };
 
         let initial_velocity = state.get_player(player_id).unwrap().velocity;
@@ -1608,6 +1624,7 @@ mod tests {
         let config = GravityConfig {
             range_mode: GravityRangeMode::Limited,
             influence_radius: 5000.0,
+            strength: 1.0,
         };
 
         let initial_velocity = state.get_player(player_id).unwrap().velocity;
@@ -1617,6 +1634,22 @@ mod tests {
         assert!(state.get_player(player_id).unwrap().velocity != initial_velocity);
     }
 
+    #[test]
+    fn test_gravity_strength_scales_pull() {
+        use crate::config::GravityConfig;
+
+        let pull = |strength: f32| {
+            let (mut state, player_id) = create_test_state();
+            let initial_velocity = state.get_player(player_id).unwrap().velocity;
+            let config = GravityConfig { strength, ..GravityConfig::default() };
+            update_central_with_config(&mut state, &config, DT);
+            (state.get_player(player_id).unwrap().velocity - initial_velocity).length()
+        };
+
+        assert_eq!(pull(0.0), 0.0);
+        assert!((pull(2.0) / pull(1.0) - 2.0).abs() < 1e-3);
+    }
+
     // === INSIGNIFICANCE CULLING TESTS ===
 
     #[test]
@@ -1,8 +1,24 @@
 pub mod physics;
+#[cfg(test)]
+mod physics_validation;
 pub mod gravity;
 pub mod collision;
 pub mod arena;
 pub mod projectile;
 pub mod ai;
 pub mod ai_soa;
+pub mod flow_field;
+pub mod cold_storage;
+pub mod factions;
 pub mod debris;
+pub mod custom;
+pub mod behavior_tree;
+pub mod bot_policy;
+pub mod chatter;
+pub mod orbit_assist;
+pub mod well_capture;
+pub mod combat_resolver;
+pub mod difficulty;
+pub mod bot_placement;
+pub mod asteroids;
+pub mod status_effects;
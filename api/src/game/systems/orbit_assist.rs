@@ -0,0 +1,148 @@
+//! Orbit assist
+//!
+//! Accessibility helper for players who opt in via their join request. While
+//! an assisted player is not thrusting near a gravity well, the server nudges
+//! their velocity toward a circular orbit around the nearest well, keeping
+//! their current direction of travel. The correction is capped well below
+//! boost thrust, so it steadies drifting players without flying for them.
+//! Because it runs in the authoritative simulation, no client-side autopilot
+//! (which input validation would flag as scripted) is needed.
+
+use crate::config::OrbitAssistConfig;
+use crate::game::state::{GameState, GravityWell, PlayerId};
+use crate::game::systems::gravity::calculate_gravity_from_well;
+use crate::util::vec2::Vec2;
+
+/// Velocity of a circular orbit around `well` through `position`, in the
+/// rotation sense of `velocity` (counter-clockwise when at rest).
+/// None when the well exerts no pull there (inside its core cutoff).
+pub fn circular_orbit_velocity(position: Vec2, velocity: Vec2, well: &GravityWell) -> Option<Vec2> {
+    let offset = position - well.position;
+    let radius = offset.length();
+    let pull = calculate_gravity_from_well(position, well).length();
+    if radius <= 0.0 || pull <= 0.0 {
+        return None;
+    }
+
+    // Centripetal balance: v^2 / r = pull
+    let speed = (pull * radius).sqrt();
+    let tangent = offset.perpendicular() * (1.0 / radius);
+    let direction = if offset.cross(velocity) < 0.0 { -tangent } else { tangent };
+    Some(direction * speed)
+}
+
+/// Nudge idle assisted players toward a stable orbit around their nearest well
+pub fn update<'a>(
+    state: &mut GameState,
+    assisted: impl Iterator<Item = &'a PlayerId>,
+    config: &OrbitAssistConfig,
+    dt: f32,
+) {
+    if !config.enabled {
+        return;
+    }
+    let max_range_sq = config.max_range * config.max_range;
+    let max_delta = config.max_correction * dt;
+
+    for player_id in assisted {
+        let Some(player) = state.players.get(player_id) else {
+            continue;
+        };
+        if !player.alive {
+            continue;
+        }
+        let (position, velocity) = (player.position, player.velocity);
+
+        let nearest = state
+            .arena
+            .gravity_wells
+            .values()
+            .map(|w| (w, w.position.distance_sq_to(position)))
+            .filter(|&(_, d)| d <= max_range_sq)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some(target) = nearest.and_then(|(well, _)| circular_orbit_velocity(position, velocity, well)) else {
+            continue;
+        };
+
+        if let Some(player) = state.players.get_mut(player_id) {
+            player.velocity += (target - velocity).clamp_length(max_delta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::Player;
+
+    fn setup(position: Vec2, velocity: Vec2) -> (GameState, PlayerId) {
+        // Default arena: a single central well at the origin
+        let mut state = GameState::new();
+        let player = Player {
+            id: uuid::Uuid::new_v4(),
+            name: "A".to_string(),
+            position,
+            velocity,
+            rotation: 0.0,
+            mass: 100.0,
+            alive: true,
+            kills: 0,
+            deaths: 0,
+            spawn_protection: 0.0,
+            is_bot: false,
+            color_index: 0,
+            respawn_timer: 0.0,
+            spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
+        };
+        let id = player.id;
+        state.add_player(player);
+        (state, id)
+    }
+
+    #[test]
+    fn test_orbit_velocity_is_tangential_and_keeps_direction() {
+        let (state, _) = setup(Vec2::ZERO, Vec2::ZERO);
+        let well = state.arena.gravity_wells.values().next().unwrap();
+        let position = Vec2::new(300.0, 0.0);
+
+        let ccw = circular_orbit_velocity(position, Vec2::new(0.0, 10.0), well).unwrap();
+        let cw = circular_orbit_velocity(position, Vec2::new(0.0, -10.0), well).unwrap();
+        assert!(ccw.x.abs() < 1e-3 && ccw.y > 0.0);
+        assert!((cw + ccw).length() < 1e-3);
+
+        // Speed balances the well's pull at this radius
+        let pull = calculate_gravity_from_well(position, well).length();
+        assert!((ccw.length_sq() / 300.0 - pull).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_assist_corrects_drift_with_bounded_force() {
+        let config = OrbitAssistConfig::default();
+        // Falling straight in: the assist should add tangential speed and slow the fall
+        let (mut state, id) = setup(Vec2::new(300.0, 0.0), Vec2::new(-40.0, 1.0));
+        let dt = 1.0 / 30.0;
+        update(&mut state, [id].iter(), &config, dt);
+
+        let v = state.get_player(id).unwrap().velocity;
+        assert!(v.y > 1.0);
+        assert!(v.x > -40.0);
+        assert!((v - Vec2::new(-40.0, 1.0)).length() <= config.max_correction * dt + 1e-4);
+    }
+
+    #[test]
+    fn test_assist_skips_unassisted_and_out_of_range_players() {
+        let config = OrbitAssistConfig::default();
+        let (mut state, id) = setup(Vec2::new(300.0, 0.0), Vec2::new(-40.0, 0.0));
+        update(&mut state, [].iter(), &config, 0.1);
+        assert_eq!(state.get_player(id).unwrap().velocity, Vec2::new(-40.0, 0.0));
+
+        let far = Vec2::new(config.max_range + 100.0, 0.0);
+        let (mut state, id) = setup(far, Vec2::new(-40.0, 0.0));
+        update(&mut state, [id].iter(), &config, 0.1);
+        assert_eq!(state.get_player(id).unwrap().velocity, Vec2::new(-40.0, 0.0));
+    }
+}
@@ -1,7 +1,9 @@
 use rayon::prelude::*;
 
-use crate::game::constants::physics::{DRAG, MAX_VELOCITY};
+use crate::config::GravityConfig;
+use crate::game::constants::physics::{DRAG, DT, MAX_VELOCITY};
 use crate::game::state::GameState;
+use crate::game::systems::{gravity, projectile, status_effects};
 use crate::net::protocol::PlayerInput;
 use crate::util::vec2::Vec2;
 
@@ -22,14 +24,93 @@ const THRUST_INPUT_THRESHOLD_SQ: f32 = 0.01;
 /// Minimum input magnitude squared for aim to update rotation
 const AIM_INPUT_THRESHOLD_SQ: f32 = 0.01;
 
+/// Speed tolerance (fraction of orbital speed) for a player to cool down as orbiting
+const ORBIT_HEAT_TOLERANCE: f32 = 0.2;
+
 /// Kinetic energy formula coefficient (1/2 * m * v²)
 const KINETIC_ENERGY_COEFFICIENT: f32 = 0.5;
 
+/// Time integration scheme for a simulation step
+/// Legacy and semi-implicit Euler are identical at the standard 30Hz timestep;
+/// the schemes differ in how orbits hold up at other timesteps (large ticks,
+/// slowed rooms).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Integrator {
+    /// Original step: gravity kick, drag, drift. Drag is applied per tick, so
+    /// its strength per simulated second changes with the timestep.
+    #[default]
+    Legacy,
+    /// Same kick-drift order with drag scaled to the timestep, so orbits decay
+    /// at the same rate per simulated second whatever the tick length
+    SemiImplicitEuler,
+    /// Half kick, drift, half kick (velocity Verlet), with timestep-scaled drag.
+    /// Second-order accurate: long-lived orbits stay stable at large timesteps.
+    /// Evaluates gravity twice per tick.
+    VelocityVerlet,
+}
+
+impl Integrator {
+    /// Parse from string (case-insensitive)
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "legacy" => Some(Self::Legacy),
+            "semi_implicit_euler" | "euler" => Some(Self::SemiImplicitEuler),
+            "velocity_verlet" | "verlet" => Some(Self::VelocityVerlet),
+            _ => None,
+        }
+    }
+}
+
+/// Advance gravity and motion by one tick using the chosen integrator
+pub fn step(
+    state: &mut GameState,
+    integrator: Integrator,
+    gravity_config: &GravityConfig,
+    inter_entity_gravity: bool,
+    dt: f32,
+) {
+    let kick = |state: &mut GameState, dt: f32| {
+        gravity::update_central_with_config(state, gravity_config, dt);
+        if inter_entity_gravity {
+            gravity::update_inter_entity(state, dt);
+        }
+    };
+    // Velocity retained over `dt` at the per-tick drag rate
+    let drag_factor = (1.0 - DRAG).powf(dt / DT);
+
+    match integrator {
+        Integrator::Legacy => {
+            kick(state, dt);
+            update(state, dt);
+        }
+        Integrator::SemiImplicitEuler => {
+            kick(state, dt);
+            integrate(state, dt, drag_factor);
+        }
+        Integrator::VelocityVerlet => {
+            kick(state, dt * 0.5);
+            integrate(state, dt, drag_factor);
+            kick(state, dt * 0.5);
+        }
+    }
+}
+
 /// Update physics for all entities
 /// CRITICAL: Uses exponential drag (velocity *= 1 - DRAG), NOT linear drag
 /// Uses rayon for parallel iteration over players, projectiles, and debris
 pub fn update(state: &mut GameState, dt: f32) {
-    let drag_factor = 1.0 - DRAG;
+    integrate(state, dt, 1.0 - DRAG);
+}
+
+/// Apply drag, integrate positions and age out projectiles and debris
+fn integrate(state: &mut GameState, dt: f32, drag_factor: f32) {
+    let heat = *state.modifiers.heat();
+    let fuel = *state.modifiers.fuel();
+    let wells: Vec<(Vec2, f32)> = if fuel.enabled {
+        state.arena.gravity_wells.values().map(|w| (w.position, w.core_radius)).collect()
+    } else {
+        Vec::new()
+    };
 
     // Update players in parallel
     state.players.par_values_mut().for_each(|player| {
@@ -37,11 +118,31 @@ pub fn update(state: &mut GameState, dt: f32) {
             return;
         }
 
+        // Refuel inside a well's orbit band
+        if fuel.enabled && player.fuel < 1.0 {
+            let in_band = wells.iter().any(|&(well, core)| fuel.in_orbit_band(player.position, well, core));
+            if in_band {
+                player.fuel = (player.fuel + fuel.regen_per_sec * dt).min(1.0);
+            }
+        }
+
+        // Dissipate heat, faster in a stable orbit
+        if heat.enabled && player.heat > 0.0 {
+            let mut cooling = heat.dissipation_per_sec;
+            if gravity::is_in_orbit(player.position, player.velocity, ORBIT_HEAT_TOLERANCE) {
+                cooling += heat.orbit_dissipation_per_sec;
+            }
+            player.heat = (player.heat - cooling * dt).max(0.0);
+        }
+
+        // Burn, slow and shield
+        status_effects::update(player, dt);
+
         // Apply exponential drag
         player.velocity *= drag_factor;
 
-        // Clamp velocity to maximum
-        player.velocity = player.velocity.clamp_length(MAX_VELOCITY);
+        // Clamp velocity to maximum (lower while slowed)
+        player.velocity = player.velocity.clamp_length(MAX_VELOCITY * player.effects.speed_factor());
 
         // Integrate position
         player.position += player.velocity * dt;
@@ -64,7 +165,8 @@ pub fn update(state: &mut GameState, dt: f32) {
         projectile.lifetime -= dt;
     });
 
-    // Remove expired or out-of-bounds projectiles
+    // Refund misses, then remove expired or out-of-bounds projectiles
+    projectile::refund_expired(state);
     let escape_radius = state.arena.escape_radius;
     state.projectiles.retain(|p| p.lifetime > 0.0 && p.position.length() < escape_radius * PROJECTILE_BOUNDARY_MULTIPLIER);
 
@@ -89,19 +191,26 @@ pub fn apply_thrust(
 ) -> bool {
     use crate::game::constants::{boost, mass, mass_to_thrust_multiplier};
 
+    let heat = *state.modifiers.heat();
+    let fuel = *state.modifiers.fuel();
     let player = match state.get_player_mut(player_id) {
         Some(p) if p.alive => p,
         _ => return false,
     };
 
+    // An overheated player can't boost until some heat has dissipated
+    let overheated = heat.enabled && player.heat >= 1.0;
+    // Nor with an empty tank, until it refuels in an orbit band
+    let out_of_fuel = fuel.enabled && player.fuel <= 0.0;
+
     // Apply thrust if player is boosting
-    if input.boost && input.thrust.length_sq() > THRUST_INPUT_THRESHOLD_SQ {
+    if input.boost && !overheated && !out_of_fuel && input.thrust.length_sq() > THRUST_INPUT_THRESHOLD_SQ {
         let thrust_dir = input.thrust.normalize();
 
         // Scale thrust by mass - smaller players are more agile (agar.io style)
         // Uses sqrt curve: multiplier = sqrt(100/mass)
         let thrust_multiplier = mass_to_thrust_multiplier(player.mass);
-        let thrust_force = boost::BASE_THRUST * thrust_multiplier;
+        let thrust_force = boost::BASE_THRUST * thrust_multiplier * player.effects.speed_factor();
 
         // Apply thrust to velocity
         player.velocity += thrust_dir * thrust_force * dt;
@@ -112,6 +221,12 @@ pub fn apply_thrust(
             let mass_cost = boost::BASE_COST + player.mass * boost::MASS_COST_RATIO;
             player.mass = (player.mass - mass_cost * dt).max(mass::MINIMUM);
         }
+        if heat.enabled {
+            player.heat = (player.heat + heat.boost_per_sec * dt).min(1.0);
+        }
+        if fuel.enabled {
+            player.fuel = (player.fuel - fuel.burn_per_sec * dt).max(0.0);
+        }
 
         // Update rotation to face thrust direction
         player.rotation = thrust_dir.angle();
@@ -149,6 +264,7 @@ pub fn momentum_magnitude(mass: f32, velocity: Vec2) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{FuelConfig, HeatConfig};
     use crate::game::constants::physics::DT;
     use crate::game::state::Player;
 
@@ -170,6 +286,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         };
         state.add_player(player);
         (state, player_id)
@@ -268,6 +388,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         // Apply thrust with boost while spawn protected
@@ -307,6 +428,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         // Apply thrust with boost without spawn protection
@@ -368,6 +490,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         let applied = apply_thrust(&mut state, player_id, &input, DT);
@@ -376,6 +499,69 @@ mod tests {
         assert!(state.get_player(player_id).unwrap().velocity.x > initial_velocity.x);
     }
 
+    #[test]
+    fn test_heat_builds_on_boost_and_dissipates_faster_in_orbit() {
+        let (mut state, player_id) = create_test_state();
+        let input = PlayerInput { thrust: Vec2::new(1.0, 0.0), boost: true, ..Default::default() };
+
+        // Heat only counts when the ruleset enables it
+        apply_thrust(&mut state, player_id, &input, 1.0);
+        assert_eq!(state.get_player(player_id).unwrap().heat, 0.0);
+
+        let heat = HeatConfig { enabled: true, ..Default::default() };
+        state.modifiers.set_heat(heat);
+        apply_thrust(&mut state, player_id, &input, 1.0);
+        assert!((state.get_player(player_id).unwrap().heat - heat.boost_per_sec).abs() < 0.001);
+
+        // Overheated: no thrust until some heat is gone
+        state.get_player_mut(player_id).unwrap().heat = 1.0;
+        assert!(!apply_thrust(&mut state, player_id, &input, DT));
+
+        update(&mut state, 1.0);
+        let drifting = 1.0 - state.get_player(player_id).unwrap().heat;
+        assert!((drifting - heat.dissipation_per_sec).abs() < 0.001);
+
+        let position = Vec2::new(400.0, 0.0);
+        let player = state.get_player_mut(player_id).unwrap();
+        (player.position, player.velocity, player.heat) =
+            (position, Vec2::new(0.0, gravity::orbital_velocity(400.0)), 1.0);
+        integrate(&mut state, 0.1, 1.0);
+        let orbiting = 1.0 - state.get_player(player_id).unwrap().heat;
+        assert!((orbiting - (heat.dissipation_per_sec + heat.orbit_dissipation_per_sec) * 0.1).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_boost_burns_fuel_which_refills_only_in_orbit_bands() {
+        let (mut state, player_id) = create_test_state();
+        let input = PlayerInput { thrust: Vec2::new(1.0, 0.0), boost: true, ..Default::default() };
+
+        // Unlimited boost unless the ruleset enables fuel
+        apply_thrust(&mut state, player_id, &input, 1.0);
+        assert_eq!(state.get_player(player_id).unwrap().fuel, 1.0);
+
+        let fuel = FuelConfig { enabled: true, ..Default::default() };
+        state.modifiers.set_fuel(fuel);
+        apply_thrust(&mut state, player_id, &input, 1.0);
+        assert!((state.get_player(player_id).unwrap().fuel - (1.0 - fuel.burn_per_sec)).abs() < 0.001);
+
+        // Empty tank: no boost
+        state.get_player_mut(player_id).unwrap().fuel = 0.0;
+        assert!(!apply_thrust(&mut state, player_id, &input, DT));
+
+        // Far out of every band the tank stays empty
+        let player = state.get_player_mut(player_id).unwrap();
+        (player.position, player.velocity) = (Vec2::new(2000.0, 0.0), Vec2::ZERO);
+        integrate(&mut state, 1.0, 1.0);
+        assert_eq!(state.get_player(player_id).unwrap().fuel, 0.0);
+
+        // Inside the central well's band it refills
+        let player = state.get_player_mut(player_id).unwrap();
+        (player.position, player.velocity) = (Vec2::new(400.0, 0.0), Vec2::ZERO);
+        integrate(&mut state, 1.0, 1.0);
+        assert!((state.get_player(player_id).unwrap().fuel - fuel.regen_per_sec).abs() < 0.001);
+        assert!(apply_thrust(&mut state, player_id, &input, DT));
+    }
+
     #[test]
     fn test_thrust_consumes_mass() {
         let (mut state, player_id) = create_test_state();
@@ -390,6 +576,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         apply_thrust(&mut state, player_id, &input, DT);
@@ -411,6 +598,7 @@ mod tests {
             boost: false, // Not boosting
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         let applied = apply_thrust(&mut state, player_id, &input, DT);
@@ -655,6 +843,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         // Apply thrust many times
@@ -680,6 +869,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         apply_thrust(&mut state, player_id, &input, DT);
@@ -706,6 +896,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         let applied = apply_thrust(&mut state, player_id, &input, DT);
@@ -728,6 +919,7 @@ mod tests {
             boost: false,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         apply_thrust(&mut state, player_id, &input, DT);
@@ -752,6 +944,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         let applied = apply_thrust(&mut state, player_id, &input, DT);
@@ -774,6 +967,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: None,
         };
 
         let applied = apply_thrust(&mut state, fake_id, &input, DT);
@@ -806,4 +1000,71 @@ mod tests {
         // Should complete without panic
         assert!(true);
     }
+
+    /// A player on a circular orbit at r=400 around the central well
+    fn orbit_state() -> (GameState, uuid::Uuid) {
+        let (mut state, player_id) = create_test_state();
+        let position = Vec2::new(400.0, 0.0);
+        let well = state.arena.gravity_wells.values().next().unwrap().clone();
+        // Centripetal balance: v^2 / r = pull
+        let speed_sq = gravity::calculate_gravity_from_well(position, &well).length() * 400.0;
+        let player = state.get_player_mut(player_id).unwrap();
+        player.position = position;
+        player.velocity = Vec2::new(0.0, speed_sq.sqrt());
+        (state, player_id)
+    }
+
+    /// Position after `ticks` steps of `dt`
+    fn run_orbit(integrator: Integrator, dt: f32, ticks: u32) -> Vec2 {
+        let (mut state, player_id) = orbit_state();
+        let config = GravityConfig::default();
+        for _ in 0..ticks {
+            step(&mut state, integrator, &config, false, dt);
+        }
+        state.get_player(player_id).unwrap().position
+    }
+
+    #[test]
+    fn test_integrator_from_name() {
+        assert_eq!(Integrator::from_name("Legacy"), Some(Integrator::Legacy));
+        assert_eq!(Integrator::from_name("euler"), Some(Integrator::SemiImplicitEuler));
+        assert_eq!(Integrator::from_name("velocity_verlet"), Some(Integrator::VelocityVerlet));
+        assert_eq!(Integrator::from_name("rk4"), None);
+    }
+
+    #[test]
+    fn test_semi_implicit_matches_legacy_at_standard_dt() {
+        let legacy = run_orbit(Integrator::Legacy, DT, 300);
+        let euler = run_orbit(Integrator::SemiImplicitEuler, DT, 300);
+        assert!((legacy - euler).length() < 1e-3, "{legacy:?} vs {euler:?}");
+    }
+
+    #[test]
+    fn test_scaled_drag_is_timestep_independent() {
+        let speed_after = |integrator: Integrator, dt: f32| {
+            let (mut state, player_id) = create_test_state();
+            state.arena.remove_well(crate::game::state::CENTRAL_WELL_ID);
+            for _ in 0..(1.0 / dt).round() as u32 {
+                step(&mut state, integrator, &GravityConfig::default(), false, dt);
+            }
+            state.get_player(player_id).unwrap().velocity.length()
+        };
+
+        // Per-tick drag: three times fewer ticks lose far less speed
+        assert!(speed_after(Integrator::Legacy, DT * 3.0) - speed_after(Integrator::Legacy, DT) > 1.0);
+        for integrator in [Integrator::SemiImplicitEuler, Integrator::VelocityVerlet] {
+            let diff = speed_after(integrator, DT * 3.0) - speed_after(integrator, DT);
+            assert!(diff.abs() < 1e-2, "{integrator:?} drag differs by {diff}");
+        }
+    }
+
+    #[test]
+    fn test_verlet_orbit_stable_at_high_dt() {
+        // 8 simulated seconds at 8x the standard timestep, against a 4x finer reference
+        let reference = run_orbit(Integrator::VelocityVerlet, DT / 4.0, 960);
+        let euler_error = (run_orbit(Integrator::SemiImplicitEuler, DT * 8.0, 30) - reference).length();
+        let verlet_error = (run_orbit(Integrator::VelocityVerlet, DT * 8.0, 30) - reference).length();
+        assert!(verlet_error < 5.0, "verlet drifted {verlet_error}");
+        assert!(verlet_error * 3.0 < euler_error, "verlet {verlet_error} vs euler {euler_error}");
+    }
 }
@@ -0,0 +1,299 @@
+//! Physics validation suite
+//!
+//! Guards gameplay feel against accidental physics changes. Golden
+//! trajectories pin the exact paths of reference orbits, so any change to
+//! physics constants, gravity falloff or the order of the integration steps
+//! shows up as a failure here. Conservation checks bound energy and momentum
+//! drift over 10k ticks with drag undone, which catches integrator changes
+//! that would make orbits slowly gain or lose energy.
+//!
+//! When a change to the simulation is intentional, regenerate the fixtures
+//! with `PHYSICS_GOLDEN_PRINT=1 cargo test physics_validation -- --nocapture`
+//! and paste the printed tables over the old ones.
+
+use crate::config::GravityConfig;
+use crate::game::constants::physics::{DRAG, DT, MAX_VELOCITY};
+use crate::game::state::{GameState, GravityWell, Player, PlayerId, WellId, CENTRAL_WELL_ID};
+use crate::game::systems::gravity::calculate_gravity_from_well;
+use crate::game::systems::orbit_assist::circular_orbit_velocity;
+use crate::game::systems::physics::{self, Integrator};
+use crate::util::vec2::Vec2;
+
+/// Ticks between golden trajectory samples (one second)
+const SAMPLE_INTERVAL: u32 = 30;
+
+/// Allowed position error against a golden sample, in world units
+const GOLDEN_TOLERANCE: f32 = 0.01;
+
+/// Ticks simulated by the conservation checks
+const CONSERVATION_TICKS: u32 = 10_000;
+
+/// Largest allowed orbital energy swing, as a fraction of the initial kinetic energy
+const ENERGY_DRIFT_BOUND: f32 = 0.02;
+
+/// Largest allowed relative drift of conserved momenta
+const MOMENTUM_DRIFT_BOUND: f32 = 1e-3;
+
+/// Every selectable integrator; all must conserve as well as the default
+const INTEGRATORS: [Integrator; 3] = [Integrator::Legacy, Integrator::SemiImplicitEuler, Integrator::VelocityVerlet];
+
+/// A standard (non-central) well, as spawned in mid-size arenas
+const STANDARD_WELL_ID: WellId = 1;
+
+/// Golden trajectory samples: (tick, x, y)
+type Trajectory = &'static [(u32, f32, f32)];
+
+/// Circular start at r=400 around the central well, spiralling in under drag
+const CENTRAL_ORBIT: Trajectory = &[
+    (30, 393.691, 68.204),
+    (60, 375.820, 130.365),
+    (90, 347.495, 184.909),
+    (120, 309.966, 230.487),
+    (150, 264.569, 265.914),
+    (180, 212.725, 290.102),
+    (210, 155.962, 301.996),
+    (240, 96.008, 300.500),
+    (270, 34.944, 284.426),
+    (300, -24.502, 252.479),
+];
+
+/// Eccentric orbit around a standard well, perturbed by the central well
+const STANDARD_WELL_ORBIT: Trajectory = &[
+    (30, 2294.679, 28.948),
+    (60, 2279.396, 55.373),
+    (90, 2254.516, 78.533),
+    (120, 2220.226, 97.549),
+    (150, 2176.539, 111.218),
+    (180, 2123.354, 117.649),
+    (210, 2060.833, 113.482),
+    (240, 1991.537, 93.255),
+    (270, 1922.482, 68.062),
+    (300, 1865.375, 37.373),
+];
+
+fn add_player(state: &mut GameState, position: Vec2, velocity: Vec2, mass: f32) -> PlayerId {
+    let player = Player {
+        id: uuid::Uuid::new_v4(),
+        name: "Probe".to_string(),
+        position,
+        velocity,
+        rotation: 0.0,
+        mass,
+        alive: true,
+        kills: 0,
+        deaths: 0,
+        spawn_protection: 0.0,
+        is_bot: false,
+        color_index: 0,
+        respawn_timer: 0.0,
+        spawn_tick: 0,
+        heat: 0.0,
+        fuel: 1.0,
+        faction: 0,
+        effects: Default::default(),
+    };
+    let id = player.id;
+    state.add_player(player);
+    id
+}
+
+fn central_well(state: &GameState) -> GravityWell {
+    state.arena.gravity_wells[&CENTRAL_WELL_ID].clone()
+}
+
+/// One simulation tick as the game loop runs it
+fn step(state: &mut GameState, integrator: Integrator, inter_entity: bool) {
+    physics::step(state, integrator, &GravityConfig::default(), inter_entity, DT);
+}
+
+/// A tick with drag undone, leaving only the conservative part of the integrator
+fn step_without_drag(state: &mut GameState, integrator: Integrator, inter_entity: bool) {
+    for player in state.players.values_mut() {
+        player.velocity *= 1.0 / (1.0 - DRAG);
+    }
+    step(state, integrator, inter_entity);
+}
+
+fn central_orbit_state() -> (GameState, PlayerId) {
+    let mut state = GameState::new();
+    let position = Vec2::new(400.0, 0.0);
+    let velocity = circular_orbit_velocity(position, Vec2::ZERO, &central_well(&state)).unwrap();
+    let id = add_player(&mut state, position, velocity, 100.0);
+    (state, id)
+}
+
+fn standard_well_state() -> (GameState, PlayerId) {
+    let mut state = GameState::new();
+    state.arena.insert_well(GravityWell::new(STANDARD_WELL_ID, Vec2::new(2000.0, 0.0), 5000.0, 50.0));
+    let id = add_player(&mut state, Vec2::new(2300.0, 0.0), Vec2::new(0.0, 30.0), 100.0);
+    (state, id)
+}
+
+/// Sample a player's position every second for as many samples as the fixture holds
+fn record(mut state: GameState, id: PlayerId, samples: usize) -> Vec<(u32, f32, f32)> {
+    let mut trajectory = Vec::with_capacity(samples);
+    let mut tick = 0;
+    while trajectory.len() < samples {
+        for _ in 0..SAMPLE_INTERVAL {
+            step(&mut state, Integrator::Legacy, false);
+        }
+        tick += SAMPLE_INTERVAL;
+        let p = state.get_player(id).unwrap().position;
+        trajectory.push((tick, p.x, p.y));
+    }
+    trajectory
+}
+
+fn assert_golden(name: &str, golden: Trajectory, (state, id): (GameState, PlayerId)) {
+    let actual = record(state, id, golden.len());
+    if std::env::var("PHYSICS_GOLDEN_PRINT").is_ok() {
+        println!("const {name}: Trajectory = &[");
+        for (tick, x, y) in &actual {
+            println!("    ({tick}, {x:.3}, {y:.3}),");
+        }
+        println!("];");
+    }
+
+    for (&(tick, x, y), &(_, ax, ay)) in golden.iter().zip(&actual) {
+        let error = (Vec2::new(ax, ay) - Vec2::new(x, y)).length();
+        assert!(
+            error <= GOLDEN_TOLERANCE,
+            "{name} diverged at tick {tick}: expected ({x}, {y}), got ({ax}, {ay}). \
+             If the physics change is intentional, regenerate the fixtures (see module docs)."
+        );
+    }
+}
+
+/// Specific orbital energy around a single 1/r well: v²/2 + k·ln(r), with k = a·r
+fn orbital_energy(position: Vec2, velocity: Vec2, well: &GravityWell) -> f32 {
+    let radius = (position - well.position).length();
+    let k = calculate_gravity_from_well(position, well).length() * radius;
+    0.5 * velocity.length_sq() + k * radius.ln()
+}
+
+#[test]
+fn test_physics_constants_are_pinned() {
+    // Changing any of these changes how every orbit feels; update the golden
+    // fixtures alongside them.
+    assert_eq!(DRAG, 0.002);
+    assert_eq!(MAX_VELOCITY, 500.0);
+    assert_eq!(DT, 1.0 / 30.0);
+
+    let state = GameState::new();
+    let pull = calculate_gravity_from_well(Vec2::new(300.0, 0.0), &central_well(&state));
+    assert!((pull.x + 10_000.0 * 0.5 / 300.0).abs() < 1e-4, "central pull at r=300 is {pull:?}");
+}
+
+#[test]
+fn test_golden_central_orbit() {
+    assert_golden("CENTRAL_ORBIT", CENTRAL_ORBIT, central_orbit_state());
+}
+
+#[test]
+fn test_golden_standard_well_orbit() {
+    assert_golden("STANDARD_WELL_ORBIT", STANDARD_WELL_ORBIT, standard_well_state());
+}
+
+#[test]
+fn test_orbit_energy_drift_is_bounded() {
+    for integrator in INTEGRATORS {
+        let (mut state, id) = central_orbit_state();
+        let well = central_well(&state);
+        let start = state.get_player(id).unwrap().clone();
+        let initial = orbital_energy(start.position, start.velocity, &well);
+        let scale = 0.5 * start.velocity.length_sq();
+
+        let mut worst: f32 = 0.0;
+        for _ in 0..CONSERVATION_TICKS {
+            step_without_drag(&mut state, integrator, false);
+            let p = state.get_player(id).unwrap();
+            worst = worst.max((orbital_energy(p.position, p.velocity, &well) - initial).abs() / scale);
+        }
+        assert!(worst < ENERGY_DRIFT_BOUND, "{integrator:?} energy drifted by {worst} of initial kinetic energy");
+
+        // The orbit neither decayed into the core nor escaped
+        let radius = state.get_player(id).unwrap().position.length();
+        assert!((radius - 400.0).abs() < 400.0 * ENERGY_DRIFT_BOUND, "{integrator:?} radius drifted to {radius}");
+    }
+}
+
+#[test]
+fn test_energy_bound_rejects_explicit_euler() {
+    // Integrating position before velocity (explicit Euler) pumps energy into
+    // every orbit; the drift bound must be tight enough to notice.
+    let (state, id) = central_orbit_state();
+    let well = central_well(&state);
+    let start = state.get_player(id).unwrap();
+    let (mut position, mut velocity) = (start.position, start.velocity);
+    let initial = orbital_energy(position, velocity, &well);
+    let scale = 0.5 * velocity.length_sq();
+
+    for _ in 0..CONSERVATION_TICKS {
+        let accel = calculate_gravity_from_well(position, &well);
+        position += velocity * DT;
+        velocity += accel * DT;
+    }
+    let drift = (orbital_energy(position, velocity, &well) - initial).abs() / scale;
+    assert!(drift > ENERGY_DRIFT_BOUND, "explicit Euler drifted only {drift}");
+}
+
+#[test]
+fn test_angular_momentum_conserved_around_well() {
+    for integrator in INTEGRATORS {
+        let (mut state, id) = central_orbit_state();
+        let angular = |s: &GameState| {
+            let p = s.get_player(id).unwrap();
+            p.position.cross(p.velocity)
+        };
+        let initial = angular(&state);
+
+        for _ in 0..CONSERVATION_TICKS {
+            step_without_drag(&mut state, integrator, false);
+        }
+        let drift = (angular(&state) - initial).abs() / initial.abs();
+        assert!(drift < MOMENTUM_DRIFT_BOUND, "{integrator:?} angular momentum drifted by {drift}");
+    }
+}
+
+#[test]
+fn test_linear_momentum_conserved_under_inter_entity_gravity() {
+    let mut state = GameState::new();
+    state.arena.remove_well(CENTRAL_WELL_ID);
+    let a = add_player(&mut state, Vec2::new(-100.0, 0.0), Vec2::new(0.0, 2.0), 1000.0);
+    let b = add_player(&mut state, Vec2::new(100.0, 0.0), Vec2::new(0.0, -1.0), 1500.0);
+    let momentum = |s: &GameState| {
+        s.players.values().fold(Vec2::ZERO, |total, p| total + physics::momentum(p.mass, p.velocity))
+    };
+    let initial = momentum(&state);
+    let scale: f32 = state.players.values().map(|p| physics::momentum_magnitude(p.mass, p.velocity)).sum();
+
+    for _ in 0..CONSERVATION_TICKS {
+        step_without_drag(&mut state, Integrator::Legacy, true);
+    }
+    // The pair attracted each other, so the check is not vacuous
+    assert!((state.get_player(a).unwrap().velocity.x).abs() > 0.1);
+    assert!((state.get_player(b).unwrap().velocity.x).abs() > 0.1);
+
+    let drift = (momentum(&state) - initial).length() / scale;
+    assert!(drift < MOMENTUM_DRIFT_BOUND, "linear momentum drifted by {drift}");
+}
+
+#[test]
+fn test_drag_only_removes_energy() {
+    for integrator in INTEGRATORS {
+        let (mut state, id) = central_orbit_state();
+        let well = central_well(&state);
+        let energy = |s: &GameState| {
+            let p = s.get_player(id).unwrap();
+            orbital_energy(p.position, p.velocity, &well)
+        };
+
+        let mut previous = energy(&state);
+        for tick in 0..300 {
+            step(&mut state, integrator, false);
+            let current = energy(&state);
+            assert!(current < previous, "{integrator:?} energy rose at tick {tick}: {previous} -> {current}");
+            previous = current;
+        }
+    }
+}
@@ -2,11 +2,17 @@
 //!
 //! Provides charge-based firing mechanics with event tracking.
 //! The `fire_direct` function is available for AI/scripted firing.
+//!
+//! What a shot costs and what a miss gives back come from the projectile
+//! economy in the state's parameter registry, not from constants. Rulesets
+//! with heat enabled charge shots in heat instead of mass: a shot that would
+//! push the player's heat past full isn't fired.
 
 #![allow(dead_code)] // API functions kept for future use
 
 use crate::game::constants::eject::*;
 use crate::game::constants::mass::MINIMUM;
+use crate::game::modifiers::Parameter;
 use crate::game::state::{GameState, PlayerId};
 use crate::net::protocol::PlayerInput;
 use crate::util::vec2::Vec2;
@@ -153,19 +159,29 @@ fn fire_projectile(
     player_id: PlayerId,
     charge: &ChargeState,
 ) -> Option<ProjectileEvent> {
+    let speed_multiplier = state.modifiers.multiplier(Parameter::ProjectileSpeed);
+    let cost_multiplier = state.modifiers.projectile_economy().cost_multiplier(charge.charge_progress());
+    let heat = *state.modifiers.heat();
     let player = state.get_player_mut(player_id)?;
 
     // Calculate projectile properties
     let mass = charge.projected_mass(player.mass);
-    let speed = charge.projected_velocity();
+    let speed = charge.projected_velocity() * speed_multiplier;
+
+    let (cost, shot_heat) = if heat.enabled {
+        (0.0, heat.fire_heat(charge.charge_progress()))
+    } else {
+        (mass * cost_multiplier, 0.0)
+    };
 
-    // Ensure player has enough mass
-    if player.mass - mass < MINIMUM {
+    // Ensure player can afford the shot, without overheating
+    if player.mass - cost < MINIMUM || player.heat + shot_heat > 1.0 {
         return None;
     }
 
-    // Deduct mass from player
-    player.mass -= mass;
+    // Deduct the cost from player
+    player.mass -= cost;
+    player.heat += shot_heat;
 
     // Calculate spawn position (at edge of player)
     let direction = if charge.aim_direction.length_sq() > 0.01 {
@@ -174,7 +190,7 @@ fn fire_projectile(
         Vec2::from_angle(player.rotation)
     };
 
-    let player_radius = crate::game::constants::mass_to_radius(player.mass + mass);
+    let player_radius = crate::game::constants::mass_to_radius(player.mass + cost);
     let spawn_offset = player_radius + 5.0;
     let position = player.position + direction * spawn_offset;
 
@@ -197,6 +213,28 @@ fn fire_projectile(
     })
 }
 
+/// Give owners back the economy's miss refund for projectiles whose lifetime
+/// ran out. A projectile that hit something was already removed, so anything
+/// expiring here missed. Call before expired projectiles are dropped.
+pub fn refund_expired(state: &mut GameState) {
+    let refund = state.modifiers.projectile_economy().miss_refund;
+    if refund <= 0.0 {
+        return;
+    }
+
+    let refunds: Vec<(PlayerId, f32)> = state
+        .projectiles
+        .iter()
+        .filter(|p| p.lifetime <= 0.0)
+        .map(|p| (p.owner_id, p.mass * refund))
+        .collect();
+    for (owner_id, mass) in refunds {
+        if let Some(owner) = state.get_player_mut(owner_id).filter(|p| p.alive) {
+            owner.mass += mass;
+        }
+    }
+}
+
 /// Direct fire function (without charge, for AI)
 pub fn fire_direct(
     state: &mut GameState,
@@ -221,6 +259,7 @@ pub fn fire_direct(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{HeatConfig, ProjectileEconomyConfig};
     use crate::game::state::Player;
     use uuid::Uuid;
 
@@ -242,6 +281,10 @@ mod tests {
             color_index: 0,
             respawn_timer: 0.0,
             spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
         };
         state.add_player(player);
         (state, player_id)
@@ -494,4 +537,58 @@ mod tests {
         assert!(state.projectiles[0].velocity.x > 0.0);
         assert!(state.projectiles[0].velocity.y > 0.0);
     }
+
+    #[test]
+    fn test_economy_scales_cost_by_charge_level() {
+        let (mut state, player_id) = create_test_state();
+        let economy = ProjectileEconomyConfig { tap_cost: 0.5, full_charge_cost: 2.0, ..Default::default() };
+        state.modifiers.set_projectile_economy(economy);
+
+        fire_direct(&mut state, player_id, Vec2::new(1.0, 0.0), 0.0);
+        let tap_mass = state.projectiles[0].mass;
+        assert!((100.0 - state.get_player(player_id).unwrap().mass - tap_mass * 0.5).abs() < 0.01);
+
+        state.get_player_mut(player_id).unwrap().mass = 100.0;
+        fire_direct(&mut state, player_id, Vec2::new(1.0, 0.0), 0.5);
+        let half_mass = state.projectiles[1].mass;
+        assert!((100.0 - state.get_player(player_id).unwrap().mass - half_mass * 1.25).abs() < 0.01);
+
+        // A full charge would take more than the player can spare at 2x
+        assert!(fire_direct(&mut state, player_id, Vec2::new(1.0, 0.0), 1.0).is_none());
+    }
+
+    #[test]
+    fn test_heat_replaces_mass_cost() {
+        let (mut state, player_id) = create_test_state();
+        let heat = HeatConfig { enabled: true, ..Default::default() };
+        state.modifiers.set_heat(heat);
+
+        assert!(fire_direct(&mut state, player_id, Vec2::new(1.0, 0.0), 1.0).is_some());
+        let player = state.get_player(player_id).unwrap();
+        assert_eq!(player.mass, 100.0);
+        assert!((player.heat - heat.fire_full).abs() < 0.001);
+
+        // A shot that would overheat the player isn't fired
+        state.get_player_mut(player_id).unwrap().heat = 0.95;
+        assert!(fire_direct(&mut state, player_id, Vec2::new(1.0, 0.0), 0.0).is_none());
+        assert_eq!(state.projectiles.len(), 1);
+    }
+
+    #[test]
+    fn test_expired_miss_refunds_owner() {
+        let (mut state, player_id) = create_test_state();
+        state.add_projectile(player_id, Vec2::ZERO, Vec2::ZERO, 20.0);
+        state.add_projectile(player_id, Vec2::ZERO, Vec2::ZERO, 10.0);
+        state.projectiles[0].lifetime = 0.0;
+
+        // No refund by default
+        refund_expired(&mut state);
+        assert_eq!(state.get_player(player_id).unwrap().mass, 100.0);
+
+        let economy = ProjectileEconomyConfig { miss_refund: 0.5, ..Default::default() };
+        state.modifiers.set_projectile_economy(economy);
+        refund_expired(&mut state);
+        // Only the expired projectile is refunded
+        assert_eq!(state.get_player(player_id).unwrap().mass, 110.0);
+    }
 }
@@ -0,0 +1,225 @@
+//! Status effects
+//!
+//! Timed effects on a player, kept in `Player::effects` and advanced every
+//! physics tick. Systems start them through `StatusEffects::apply` instead of
+//! keeping timers of their own, and each kind has a fixed stacking rule:
+//! - Burn drains mass per second (pulsar regions). Applications stack their
+//!   drain up to `MAX_BURN_STACKS` and refresh the duration. Burn never takes
+//!   a player below minimum mass.
+//! - Slow cuts thrust and top speed by its magnitude, 0-1 (tether hits). The
+//!   strongest slow wins and the duration refreshes to the longer one.
+//! - Shield blocks mass loss from burn and the arena boundary (pickups).
+//!   Applying it again adds to the time left, up to `MAX_SHIELD_SECS`.
+//!
+//! Snapshots carry the active kinds as a bitmask (see `StatusEffects::flags`).
+
+#![allow(dead_code)] // Effect API for game systems; not every build starts effects
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::constants::mass;
+use crate::game::state::Player;
+
+/// Burn applications that add to the drain
+pub const MAX_BURN_STACKS: u8 = 3;
+/// Longest a shield can be extended to
+pub const MAX_SHIELD_SECS: f32 = 10.0;
+
+/// Effect kinds, each with its snapshot flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectKind {
+    Burn,
+    Slow,
+    Shield,
+}
+
+impl EffectKind {
+    /// Bit in the snapshot's `effects` mask
+    pub fn flag(self) -> u8 {
+        match self {
+            EffectKind::Burn => 0b0000_0001,
+            EffectKind::Slow => 0b0000_0010,
+            EffectKind::Shield => 0b0000_0100,
+        }
+    }
+
+    pub fn stacking(self) -> Stacking {
+        match self {
+            EffectKind::Burn => Stacking::Intensity { max_stacks: MAX_BURN_STACKS },
+            EffectKind::Slow => Stacking::Strongest,
+            EffectKind::Shield => Stacking::Duration { max_secs: MAX_SHIELD_SECS },
+        }
+    }
+}
+
+/// How an application combines with an active effect of the same kind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stacking {
+    /// Magnitudes add up, for up to `max_stacks` applications; the duration refreshes
+    Intensity { max_stacks: u8 },
+    /// The stronger magnitude and the longer duration win
+    Strongest,
+    /// Durations add up to `max_secs`; the stronger magnitude wins
+    Duration { max_secs: f32 },
+}
+
+/// One active effect
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: EffectKind,
+    /// Mass per second for burn, fraction of speed for slow (unused by shield)
+    pub magnitude: f32,
+    /// Seconds left
+    pub remaining: f32,
+    /// Applications counted toward the stacking limit
+    pub stacks: u8,
+}
+
+/// A player's active effects, at most one per kind
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffects {
+    active: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Start an effect, or combine it with the active one by the kind's stacking rule
+    pub fn apply(&mut self, kind: EffectKind, magnitude: f32, duration: f32) {
+        if duration <= 0.0 {
+            return;
+        }
+        let Some(effect) = self.active.iter_mut().find(|e| e.kind == kind) else {
+            self.active.push(StatusEffect { kind, magnitude, remaining: duration, stacks: 1 });
+            return;
+        };
+        match kind.stacking() {
+            Stacking::Intensity { max_stacks } => {
+                if effect.stacks < max_stacks {
+                    effect.stacks += 1;
+                    effect.magnitude += magnitude;
+                }
+                effect.remaining = effect.remaining.max(duration);
+            }
+            Stacking::Strongest => {
+                effect.magnitude = effect.magnitude.max(magnitude);
+                effect.remaining = effect.remaining.max(duration);
+            }
+            Stacking::Duration { max_secs } => {
+                effect.magnitude = effect.magnitude.max(magnitude);
+                effect.remaining = (effect.remaining + duration).min(max_secs.max(duration));
+            }
+        }
+    }
+
+    pub fn remove(&mut self, kind: EffectKind) {
+        self.active.retain(|e| e.kind != kind);
+    }
+
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+
+    pub fn get(&self, kind: EffectKind) -> Option<&StatusEffect> {
+        self.active.iter().find(|e| e.kind == kind)
+    }
+
+    pub fn has(&self, kind: EffectKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// Magnitude of an active effect, 0 when it isn't active
+    pub fn magnitude(&self, kind: EffectKind) -> f32 {
+        self.get(kind).map_or(0.0, |e| e.magnitude)
+    }
+
+    /// Multiplier on thrust and top speed from slow (1 when not slowed)
+    pub fn speed_factor(&self) -> f32 {
+        1.0 - self.magnitude(EffectKind::Slow).clamp(0.0, 1.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Active kinds as a bitmask of `EffectKind::flag`s
+    pub fn flags(&self) -> u8 {
+        self.active.iter().fold(0, |flags, e| flags | e.kind.flag())
+    }
+
+    /// Count down and drop effects that ran out
+    fn advance(&mut self, dt: f32) {
+        for effect in &mut self.active {
+            effect.remaining -= dt;
+        }
+        self.active.retain(|e| e.remaining > 0.0);
+    }
+}
+
+/// Apply a living player's effects for one tick, then count them down
+#[inline]
+pub fn update(player: &mut Player, dt: f32) {
+    if player.effects.is_empty() {
+        return;
+    }
+    let burn = player.effects.magnitude(EffectKind::Burn);
+    if burn > 0.0 && !player.effects.has(EffectKind::Shield) && player.mass > mass::MINIMUM {
+        player.mass = (player.mass - burn * dt).max(mass::MINIMUM);
+    }
+    player.effects.advance(dt);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_stacking_rules() {
+        let mut effects = StatusEffects::default();
+
+        // Burn adds intensity up to the stack limit and refreshes the duration
+        for _ in 0..5 {
+            effects.apply(EffectKind::Burn, 2.0, 3.0);
+        }
+        let burn = effects.get(EffectKind::Burn).unwrap();
+        assert_eq!((burn.magnitude, burn.stacks, burn.remaining), (6.0, MAX_BURN_STACKS, 3.0));
+
+        // The strongest slow wins, durations don't add
+        effects.apply(EffectKind::Slow, 0.5, 2.0);
+        effects.apply(EffectKind::Slow, 0.3, 4.0);
+        let slow = effects.get(EffectKind::Slow).unwrap();
+        assert_eq!((slow.magnitude, slow.remaining), (0.5, 4.0));
+        assert_eq!(effects.speed_factor(), 0.5);
+
+        // Shield time adds up to the cap
+        effects.apply(EffectKind::Shield, 0.0, 6.0);
+        effects.apply(EffectKind::Shield, 0.0, 6.0);
+        assert_eq!(effects.get(EffectKind::Shield).unwrap().remaining, MAX_SHIELD_SECS);
+
+        assert_eq!(effects.flags(), 0b111);
+        effects.remove(EffectKind::Slow);
+        assert_eq!(effects.flags(), EffectKind::Burn.flag() | EffectKind::Shield.flag());
+    }
+
+    #[test]
+    fn test_burn_drains_mass_unless_shielded_and_expires() {
+        let mut player = Player::new(Uuid::new_v4(), "Burning".to_string(), false, 0);
+        player.effects.apply(EffectKind::Burn, 10.0, 1.0);
+
+        update(&mut player, 0.5);
+        assert_eq!(player.mass, mass::STARTING - 5.0);
+
+        player.effects.apply(EffectKind::Shield, 0.0, 0.25);
+        update(&mut player, 0.25);
+        assert_eq!(player.mass, mass::STARTING - 5.0);
+
+        // The shield ran out; the burn has a quarter second left
+        update(&mut player, 0.25);
+        assert_eq!(player.mass, mass::STARTING - 7.5);
+        assert!(player.effects.is_empty());
+
+        // Burn never kills on its own
+        player.effects.apply(EffectKind::Burn, 1000.0, 5.0);
+        update(&mut player, 1.0);
+        assert_eq!(player.mass, mass::MINIMUM);
+    }
+}
@@ -0,0 +1,231 @@
+//! Gravity well capture
+//!
+//! A player claims a well by orbiting it alone: staying inside its capture
+//! ring (outside the death zone, within `ring_multiplier` core radii) for
+//! `capture_secs`. A second player in the ring contests the well and freezes
+//! progress; an empty ring lets progress decay at the capture rate. Owned
+//! wells generate passive debris, and ownership is dropped when the owner
+//! leaves the match. The central well cannot be claimed.
+//!
+//! Ownership is per player: there are no teams yet.
+
+use crate::config::{DebrisSpawnConfig, WellCaptureConfig};
+use crate::game::state::{GameState, PlayerId, WellId, CENTRAL_WELL_ID};
+use crate::game::systems::debris::spawn_near_well;
+use crate::util::vec2::Vec2;
+
+/// Inner edge of the capture ring, as a multiple of the core radius
+const RING_INNER_MULTIPLIER: f32 = 2.0;
+
+/// Capture state changes worth telling clients about
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaptureEvent {
+    /// A player finished claiming a well
+    Captured {
+        well_id: WellId,
+        owner_id: PlayerId,
+        previous_owner: Option<PlayerId>,
+    },
+    /// A second player entered the capture ring
+    Contested { well_id: WellId },
+}
+
+/// Advance capture progress for every claimable well and spawn owners' debris
+pub fn update(
+    state: &mut GameState,
+    config: &WellCaptureConfig,
+    debris_config: &DebrisSpawnConfig,
+    dt: f32,
+) -> Vec<CaptureEvent> {
+    let mut events = Vec::new();
+    if !config.enabled {
+        return events;
+    }
+
+    let orbiters: Vec<(PlayerId, Vec2)> = state
+        .players
+        .values()
+        .filter(|p| p.alive)
+        .map(|p| (p.id, p.position))
+        .collect();
+    let rate = dt / config.capture_secs;
+    let mut owed_debris: Vec<WellId> = Vec::new();
+
+    for well in state.arena.gravity_wells.values_mut() {
+        if well.id == CENTRAL_WELL_ID {
+            continue;
+        }
+        let ownership = &mut well.ownership;
+        if ownership.owner.is_some_and(|id| !state.players.contains_key(&id)) {
+            ownership.owner = None;
+        }
+
+        let inner_sq = (well.core_radius * RING_INNER_MULTIPLIER).powi(2);
+        let outer_sq = (well.core_radius * config.ring_multiplier).powi(2);
+        let mut in_ring = orbiters
+            .iter()
+            .filter(|(_, pos)| (inner_sq..=outer_sq).contains(&pos.distance_sq_to(well.position)))
+            .map(|(id, _)| *id);
+
+        match (in_ring.next(), in_ring.next()) {
+            (Some(_), Some(_)) => {
+                if !ownership.contested {
+                    ownership.contested = true;
+                    events.push(CaptureEvent::Contested { well_id: well.id });
+                }
+            }
+            (Some(id), None) => {
+                ownership.contested = false;
+                if ownership.owner == Some(id) {
+                    ownership.capturer = None;
+                    ownership.progress = 0.0;
+                } else {
+                    if ownership.capturer != Some(id) {
+                        ownership.capturer = Some(id);
+                        ownership.progress = 0.0;
+                    }
+                    ownership.progress += rate;
+                    if ownership.progress >= 1.0 {
+                        events.push(CaptureEvent::Captured {
+                            well_id: well.id,
+                            owner_id: id,
+                            previous_owner: ownership.owner,
+                        });
+                        ownership.owner = Some(id);
+                        ownership.capturer = None;
+                        ownership.progress = 0.0;
+                    }
+                }
+            }
+            (None, _) => {
+                ownership.contested = false;
+                ownership.progress = (ownership.progress - rate).max(0.0);
+                if ownership.progress == 0.0 {
+                    ownership.capturer = None;
+                }
+            }
+        }
+
+        if ownership.owner.is_some() {
+            ownership.debris_accumulator += config.debris_per_sec * dt;
+            while ownership.debris_accumulator >= 1.0 {
+                ownership.debris_accumulator -= 1.0;
+                owed_debris.push(well.id);
+            }
+        }
+    }
+
+    for well_id in owed_debris {
+        if state.debris.len() >= debris_config.max_count {
+            break;
+        }
+        if let Some(well) = state.arena.gravity_wells.get(&well_id).cloned() {
+            spawn_near_well(state, debris_config, &well);
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::{GravityWell, Player};
+
+    const WELL_ID: WellId = 7;
+
+    fn setup() -> GameState {
+        let mut state = GameState::new();
+        state
+            .arena
+            .gravity_wells
+            .insert(WELL_ID, GravityWell::new(WELL_ID, Vec2::new(2000.0, 0.0), 5000.0, 50.0));
+        state
+    }
+
+    fn add_player(state: &mut GameState, position: Vec2) -> PlayerId {
+        let player = Player {
+            id: uuid::Uuid::new_v4(),
+            name: "P".to_string(),
+            position,
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass: 100.0,
+            alive: true,
+            kills: 0,
+            deaths: 0,
+            spawn_protection: 0.0,
+            is_bot: false,
+            color_index: 0,
+            respawn_timer: 0.0,
+            spawn_tick: 0,
+            heat: 0.0,
+            fuel: 1.0,
+            faction: 0,
+            effects: Default::default(),
+        };
+        let id = player.id;
+        state.add_player(player);
+        id
+    }
+
+    fn ownership(state: &GameState) -> &crate::game::state::WellOwnership {
+        &state.arena.gravity_wells[&WELL_ID].ownership
+    }
+
+    #[test]
+    fn test_uncontested_orbit_claims_well() {
+        let config = WellCaptureConfig::default();
+        let debris_config = DebrisSpawnConfig::default();
+        let mut state = setup();
+        let id = add_player(&mut state, Vec2::new(2200.0, 0.0));
+
+        let steps = (config.capture_secs / 0.5).ceil() as usize;
+        let events: Vec<_> = (0..steps)
+            .flat_map(|_| update(&mut state, &config, &debris_config, 0.5))
+            .collect();
+        assert_eq!(
+            events,
+            vec![CaptureEvent::Captured { well_id: WELL_ID, owner_id: id, previous_owner: None }]
+        );
+        assert_eq!(ownership(&state).owner, Some(id));
+
+        // Owned wells feed their owner's area
+        let debris_before = state.debris.len();
+        update(&mut state, &config, &debris_config, 1.0 / config.debris_per_sec);
+        assert!(state.debris.len() > debris_before);
+    }
+
+    #[test]
+    fn test_second_player_contests_and_freezes_progress() {
+        let config = WellCaptureConfig::default();
+        let debris_config = DebrisSpawnConfig::default();
+        let mut state = setup();
+        add_player(&mut state, Vec2::new(2200.0, 0.0));
+        update(&mut state, &config, &debris_config, 1.0);
+        let progress = ownership(&state).progress;
+        assert!(progress > 0.0);
+
+        add_player(&mut state, Vec2::new(1800.0, 0.0));
+        let events = update(&mut state, &config, &debris_config, 1.0);
+        assert_eq!(events, vec![CaptureEvent::Contested { well_id: WELL_ID }]);
+        assert!(update(&mut state, &config, &debris_config, 1.0).is_empty());
+        assert!(ownership(&state).contested);
+        assert_eq!(ownership(&state).progress, progress);
+    }
+
+    #[test]
+    fn test_progress_decays_and_core_does_not_count() {
+        let config = WellCaptureConfig::default();
+        let debris_config = DebrisSpawnConfig::default();
+        let mut state = setup();
+        let id = add_player(&mut state, Vec2::new(2200.0, 0.0));
+        update(&mut state, &config, &debris_config, 1.0);
+
+        // Too deep in the well to count as orbiting
+        state.get_player_mut(id).unwrap().position = Vec2::new(2050.0, 0.0);
+        update(&mut state, &config, &debris_config, 2.0);
+        assert_eq!(ownership(&state).progress, 0.0);
+        assert_eq!(ownership(&state).capturer, None);
+    }
+}
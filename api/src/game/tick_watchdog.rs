@@ -0,0 +1,220 @@
+//! Tick-time anomaly watchdog
+//!
+//! Rare stutters are hard to diagnose after the fact: by the time anyone
+//! attaches a profiler the spike is gone. The watchdog compares every tick's
+//! duration against a threshold. A spike arms a sampling profiler (pprof, with
+//! the `profiling` feature), and the next spike within the capture window
+//! writes the samples around it as a flamegraph SVG to the diagnostics
+//! directory. If no second spike arrives in the window the profile is dropped.
+//! After a capture the watchdog cools down, and it stops capturing after
+//! `max_captures` so a persistently slow server can't fill the disk.
+//!
+//! Without the `profiling` feature spikes are still detected, counted and
+//! logged, but no profile is taken.
+
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::config::TickWatchdogConfig;
+
+/// What the watchdog did for one tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogEvent {
+    /// Nothing notable
+    Quiet,
+    /// A spike while no capture could start (cooling down or out of captures)
+    Spike,
+    /// A spike armed the profiler
+    Armed,
+    /// A spike during the capture window; the profile was written
+    Captured,
+    /// The capture window passed without another spike; the profile was dropped
+    Expired,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum WatchState {
+    Watching,
+    Armed { since: Instant },
+    CoolingDown { until: Instant },
+}
+
+/// Detects tick-time spikes and captures profiles around them
+pub struct TickWatchdog {
+    config: TickWatchdogConfig,
+    state: WatchState,
+    captures: u32,
+    #[cfg(feature = "profiling")]
+    profiler: Option<pprof::ProfilerGuard<'static>>,
+}
+
+impl TickWatchdog {
+    pub fn new(config: TickWatchdogConfig) -> Self {
+        Self {
+            config,
+            state: WatchState::Watching,
+            captures: 0,
+            #[cfg(feature = "profiling")]
+            profiler: None,
+        }
+    }
+
+    /// Check one tick's duration, starting, writing or dropping a profile as needed
+    pub fn observe(&mut self, tick: u64, tick_time: Duration, now: Instant) -> WatchdogEvent {
+        if !self.config.enabled {
+            return WatchdogEvent::Quiet;
+        }
+        let spike = tick_time >= Duration::from_millis(self.config.spike_ms);
+        let event = self.step(spike, now);
+        match event {
+            WatchdogEvent::Armed => {
+                warn!(
+                    "Tick {} took {:.1}ms (threshold {}ms), profiling until the next spike",
+                    tick,
+                    tick_time.as_secs_f64() * 1000.0,
+                    self.config.spike_ms
+                );
+                self.start_profile();
+            }
+            WatchdogEvent::Captured => {
+                warn!("Tick {} took {:.1}ms, capturing profile", tick, tick_time.as_secs_f64() * 1000.0);
+                self.write_profile(tick);
+            }
+            WatchdogEvent::Expired => {
+                info!("No tick spike within {}s, dropping profile", self.config.capture_window_secs);
+                self.drop_profile();
+            }
+            WatchdogEvent::Spike | WatchdogEvent::Quiet => {}
+        }
+        event
+    }
+
+    /// Advance the state machine
+    fn step(&mut self, spike: bool, now: Instant) -> WatchdogEvent {
+        if let WatchState::CoolingDown { until } = self.state {
+            if now >= until {
+                self.state = WatchState::Watching;
+            }
+        }
+
+        match self.state {
+            WatchState::Watching if spike && self.captures < self.config.max_captures => {
+                self.state = WatchState::Armed { since: now };
+                WatchdogEvent::Armed
+            }
+            WatchState::Armed { .. } if spike => {
+                self.captures += 1;
+                self.state = WatchState::CoolingDown { until: now + Duration::from_secs(self.config.cooldown_secs) };
+                WatchdogEvent::Captured
+            }
+            WatchState::Armed { since }
+                if now.duration_since(since) >= Duration::from_secs(self.config.capture_window_secs) =>
+            {
+                self.state = WatchState::Watching;
+                WatchdogEvent::Expired
+            }
+            _ if spike => WatchdogEvent::Spike,
+            _ => WatchdogEvent::Quiet,
+        }
+    }
+
+    #[cfg(feature = "profiling")]
+    fn start_profile(&mut self) {
+        match pprof::ProfilerGuardBuilder::default()
+            .frequency(self.config.sample_hz)
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()
+        {
+            Ok(guard) => self.profiler = Some(guard),
+            Err(e) => warn!("Failed to start the profiler: {}", e),
+        }
+    }
+
+    #[cfg(not(feature = "profiling"))]
+    fn start_profile(&mut self) {}
+
+    /// Stop profiling and write the flamegraph off the game loop thread
+    #[cfg(feature = "profiling")]
+    fn write_profile(&mut self, tick: u64) {
+        let Some(guard) = self.profiler.take() else {
+            return;
+        };
+        let report = match guard.report().build() {
+            Ok(report) => report,
+            Err(e) => {
+                warn!("Failed to build the profile report: {}", e);
+                return;
+            }
+        };
+        drop(guard);
+
+        let dir = std::path::PathBuf::from(&self.config.diagnostics_dir);
+        let unix_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let path = dir.join(format!("tick-spike-{}-{}.svg", unix_secs, tick));
+        std::thread::spawn(move || {
+            let result = std::fs::create_dir_all(&dir)
+                .map_err(|e| e.to_string())
+                .and_then(|_| std::fs::File::create(&path).map_err(|e| e.to_string()))
+                .and_then(|file| report.flamegraph(file).map_err(|e| e.to_string()));
+            match result {
+                Ok(()) => info!("Wrote tick spike flamegraph to {}", path.display()),
+                Err(e) => warn!("Failed to write flamegraph {}: {}", path.display(), e),
+            }
+        });
+    }
+
+    #[cfg(not(feature = "profiling"))]
+    fn write_profile(&mut self, _tick: u64) {}
+
+    fn drop_profile(&mut self) {
+        #[cfg(feature = "profiling")]
+        {
+            self.profiler = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> TickWatchdogConfig {
+        TickWatchdogConfig {
+            capture_window_secs: 60,
+            cooldown_secs: 600,
+            max_captures: 2,
+            ..TickWatchdogConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_second_spike_captures_then_cools_down() {
+        let mut watchdog = TickWatchdog::new(config());
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert_eq!(watchdog.step(false, at(0)), WatchdogEvent::Quiet);
+        assert_eq!(watchdog.step(true, at(1)), WatchdogEvent::Armed);
+        assert_eq!(watchdog.step(false, at(2)), WatchdogEvent::Quiet);
+        assert_eq!(watchdog.step(true, at(10)), WatchdogEvent::Captured);
+        // Cooling down: spikes are only reported
+        assert_eq!(watchdog.step(true, at(100)), WatchdogEvent::Spike);
+        assert_eq!(watchdog.step(true, at(700)), WatchdogEvent::Armed);
+        assert_eq!(watchdog.step(true, at(701)), WatchdogEvent::Captured);
+        // Out of captures
+        assert_eq!(watchdog.step(true, at(2000)), WatchdogEvent::Spike);
+    }
+
+    #[test]
+    fn test_profile_expires_without_second_spike() {
+        let mut watchdog = TickWatchdog::new(config());
+        let start = Instant::now();
+
+        assert_eq!(watchdog.step(true, start), WatchdogEvent::Armed);
+        assert_eq!(watchdog.step(false, start + Duration::from_secs(61)), WatchdogEvent::Expired);
+        assert_eq!(watchdog.step(true, start + Duration::from_secs(62)), WatchdogEvent::Armed);
+    }
+}
@@ -0,0 +1,318 @@
+//! Guided tutorial for new players
+//!
+//! `TutorialSystem` walks each enrolled player through three objectives in
+//! order: hold a steady orbit around a well, collect a ring of debris spawned
+//! around them, and land a shot on another player. A `TutorialObjective` event
+//! starts each step, `TutorialProgress` reports it in tenths, and
+//! `TutorialCompleted` ends the sequence. Who counts as new, gentle bots and
+//! the ranked-room gate are the lobby's business (see `lobby::profiles`).
+
+#![allow(dead_code)] // Driven by lobby rooms
+
+use hashbrown::HashMap;
+
+use crate::config::TutorialConfig;
+use crate::game::game_loop::GameLoopEvent;
+use crate::game::state::{DebrisSize, GameState, PlayerId, WellId};
+use crate::game::systems::custom::GameSystem;
+use crate::util::vec2::Vec2;
+
+/// Orbit must stay within this distance of the well (units)
+const ORBIT_MAX_DISTANCE: f32 = 900.0;
+
+/// ... and this many core radii outside its core
+const ORBIT_MIN_CORE_RADII: f32 = 3.0;
+
+/// Debris placed per required pickup, so a few misses don't stall the step
+const DEBRIS_PER_GOAL: u32 = 2;
+
+/// Radius of the debris ring around the player (units)
+const DEBRIS_RING_RADIUS: f32 = 150.0;
+
+/// Tutorial objective, in the order they are given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TutorialStep {
+    /// Hold a steady orbit around the nearest well
+    OrbitWell,
+    /// Collect the debris spawned around the player
+    CollectDebris,
+    /// Hit another player with a projectile
+    LandShot,
+}
+
+impl TutorialStep {
+    fn next(self) -> Option<Self> {
+        match self {
+            TutorialStep::OrbitWell => Some(TutorialStep::CollectDebris),
+            TutorialStep::CollectDebris => Some(TutorialStep::LandShot),
+            TutorialStep::LandShot => None,
+        }
+    }
+}
+
+/// One player's place in the tutorial
+#[derive(Debug, Clone)]
+struct Progress {
+    step: TutorialStep,
+    /// Whether the current step's objective event has been sent
+    started: bool,
+    /// Orbit seconds or pickups so far in the current step
+    amount: f32,
+    /// Tenths of the step already reported
+    reported: u32,
+    /// Well being orbited (first step)
+    well: Option<WellId>,
+}
+
+impl Progress {
+    fn new() -> Self {
+        Self { step: TutorialStep::OrbitWell, started: false, amount: 0.0, reported: 0, well: None }
+    }
+}
+
+/// Runs the tutorial for enrolled players
+#[derive(Debug)]
+pub struct TutorialSystem {
+    config: TutorialConfig,
+    players: HashMap<PlayerId, Progress>,
+}
+
+impl TutorialSystem {
+    pub fn new(config: TutorialConfig) -> Self {
+        Self { config, players: HashMap::new() }
+    }
+
+    /// Start the tutorial for a player (restarts it if already running)
+    pub fn enroll(&mut self, player_id: PlayerId) {
+        self.players.insert(player_id, Progress::new());
+    }
+
+    /// Stop the tutorial for a player (e.g. they left)
+    pub fn withdraw(&mut self, player_id: PlayerId) {
+        self.players.remove(&player_id);
+    }
+
+    /// Whether anyone is taking the tutorial
+    pub fn has_players(&self) -> bool {
+        !self.players.is_empty()
+    }
+
+    /// Current objective of a player, if they are taking the tutorial
+    pub fn step(&self, player_id: PlayerId) -> Option<TutorialStep> {
+        self.players.get(&player_id).map(|p| p.step)
+    }
+
+    /// Amount the current step needs
+    fn goal(&self, step: TutorialStep) -> f32 {
+        match step {
+            TutorialStep::OrbitWell => self.config.orbit_secs,
+            TutorialStep::CollectDebris => self.config.debris_goal as f32,
+            TutorialStep::LandShot => 1.0,
+        }
+    }
+
+    /// Set up a step: pick the well to orbit or lay out the debris ring
+    fn start_step(&self, state: &mut GameState, player_id: PlayerId, progress: &mut Progress) -> Option<Vec2> {
+        let position = state.get_player(player_id)?.position;
+        match progress.step {
+            TutorialStep::OrbitWell => {
+                let well = nearest_well(state, position)?;
+                progress.well = Some(well);
+                state.arena.gravity_wells.get(&well).map(|w| w.position)
+            }
+            TutorialStep::CollectDebris => {
+                let count = self.config.debris_goal * DEBRIS_PER_GOAL;
+                for i in 0..count {
+                    let angle = std::f32::consts::TAU * i as f32 / count as f32;
+                    let offset = Vec2::from_angle(angle) * DEBRIS_RING_RADIUS;
+                    state.add_debris(position + offset, Vec2::ZERO, DebrisSize::Small);
+                }
+                Some(position)
+            }
+            TutorialStep::LandShot => state
+                .players
+                .values()
+                .filter(|p| p.alive && p.id != player_id)
+                .min_by(|a, b| {
+                    let da = (a.position - position).length_sq();
+                    let db = (b.position - position).length_sq();
+                    da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .map(|p| p.position),
+        }
+    }
+}
+
+/// Nearest well to a position
+fn nearest_well(state: &GameState, position: Vec2) -> Option<WellId> {
+    state
+        .arena
+        .gravity_wells
+        .values()
+        .min_by(|a, b| {
+            let da = (a.position - position).length_sq();
+            let db = (b.position - position).length_sq();
+            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|w| w.id)
+}
+
+/// Whether a player is in a steady orbit of a well: inside the orbit band and
+/// moving mostly sideways rather than falling in or flying off
+fn is_orbiting(state: &GameState, player_id: PlayerId, well_id: WellId) -> bool {
+    let (Some(player), Some(well)) = (state.get_player(player_id), state.arena.gravity_wells.get(&well_id)) else {
+        return false;
+    };
+    let offset = player.position - well.position;
+    let distance = offset.length();
+    if distance > ORBIT_MAX_DISTANCE || distance < well.core_radius * ORBIT_MIN_CORE_RADII {
+        return false;
+    }
+    let radial = offset.normalize();
+    let radial_speed = player.velocity.dot(radial).abs();
+    let tangential_speed = (player.velocity - radial * player.velocity.dot(radial)).length();
+    tangential_speed > radial_speed
+}
+
+impl GameSystem for TutorialSystem {
+    fn run(&mut self, state: &mut GameState, dt: f32, events: &mut Vec<GameLoopEvent>) {
+        if self.players.is_empty() {
+            return;
+        }
+        self.players.retain(|id, _| state.players.contains_key(id));
+
+        let mut pickups: HashMap<PlayerId, u32> = HashMap::new();
+        let mut hits: HashMap<PlayerId, u32> = HashMap::new();
+        for event in events.iter() {
+            match *event {
+                GameLoopEvent::DebrisCollected { player_id, .. } => *pickups.entry(player_id).or_default() += 1,
+                GameLoopEvent::PlayerHit { shooter_id, .. } => *hits.entry(shooter_id).or_default() += 1,
+                _ => {}
+            }
+        }
+
+        let mut players = std::mem::take(&mut self.players);
+        players.retain(|&player_id, progress| {
+            if !state.get_player(player_id).is_some_and(|p| p.alive) {
+                return true;
+            }
+            if !progress.started {
+                let target = self.start_step(state, player_id, progress);
+                progress.started = true;
+                events.push(GameLoopEvent::TutorialObjective { player_id, step: progress.step, target });
+            }
+
+            progress.amount += match progress.step {
+                TutorialStep::OrbitWell => match progress.well {
+                    Some(well) if is_orbiting(state, player_id, well) => dt,
+                    Some(well) if state.arena.gravity_wells.contains_key(&well) => 0.0,
+                    // The well is gone: orbit whichever is nearest now
+                    _ => {
+                        progress.well = state.get_player(player_id).and_then(|p| nearest_well(state, p.position));
+                        0.0
+                    }
+                },
+                TutorialStep::CollectDebris => pickups.get(&player_id).copied().unwrap_or(0) as f32,
+                TutorialStep::LandShot => hits.get(&player_id).copied().unwrap_or(0) as f32,
+            };
+
+            let fraction = (progress.amount / self.goal(progress.step)).min(1.0);
+            let tenths = (fraction * 10.0) as u32;
+            if tenths > progress.reported {
+                progress.reported = tenths;
+                events.push(GameLoopEvent::TutorialProgress { player_id, step: progress.step, progress: fraction });
+            }
+            if fraction < 1.0 {
+                return true;
+            }
+
+            match progress.step.next() {
+                Some(step) => {
+                    *progress = Progress { step, ..Progress::new() };
+                    true
+                }
+                None => {
+                    events.push(GameLoopEvent::TutorialCompleted { player_id });
+                    false
+                }
+            }
+        });
+        self.players = players;
+    }
+
+    fn name(&self) -> &str {
+        "tutorial"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::{GravityWell, Player};
+
+    fn setup() -> (GameState, TutorialSystem, PlayerId) {
+        let mut state = GameState::new();
+        state.arena.gravity_wells.clear();
+        state.arena.gravity_wells.insert(1, GravityWell::new(1, Vec2::ZERO, 10000.0, 50.0));
+        let mut player = Player::new(uuid::Uuid::new_v4(), "New".to_string(), false, 0);
+        player.position = Vec2::new(400.0, 0.0);
+        player.velocity = Vec2::new(0.0, 200.0);
+        let id = player.id;
+        state.add_player(player);
+
+        let config = TutorialConfig { orbit_secs: 1.0, debris_goal: 2, ..TutorialConfig::default() };
+        let mut tutorial = TutorialSystem::new(config);
+        tutorial.enroll(id);
+        (state, tutorial, id)
+    }
+
+    #[test]
+    fn test_objectives_run_in_order() {
+        let (mut state, mut tutorial, id) = setup();
+
+        // Orbit: objective targets the well, progress accrues while orbiting
+        let mut events = Vec::new();
+        tutorial.run(&mut state, 0.5, &mut events);
+        assert!(matches!(
+            events[0],
+            GameLoopEvent::TutorialObjective { step: TutorialStep::OrbitWell, target: Some(Vec2::ZERO), .. }
+        ));
+        let halfway = |e: &GameLoopEvent| matches!(e, GameLoopEvent::TutorialProgress { progress, .. } if *progress == 0.5);
+        assert!(events.iter().any(halfway));
+        tutorial.run(&mut state, 0.5, &mut Vec::new());
+        assert_eq!(tutorial.step(id), Some(TutorialStep::CollectDebris));
+
+        // Debris: a ring is laid out, pickups count
+        tutorial.run(&mut state, 0.1, &mut Vec::new());
+        assert_eq!(state.debris.len(), 4);
+        let mut events: Vec<_> =
+            (0..2).map(|i| GameLoopEvent::DebrisCollected { player_id: id, debris_id: i }).collect();
+        tutorial.run(&mut state, 0.1, &mut events);
+        assert_eq!(tutorial.step(id), Some(TutorialStep::LandShot));
+
+        // Shot: one hit on another player completes the tutorial
+        let hit = GameLoopEvent::PlayerHit {
+            shooter_id: id,
+            victim_id: uuid::Uuid::new_v4(),
+            projectile_id: 1,
+            amount: 5.0,
+            position: Vec2::ZERO,
+        };
+        let mut events = vec![hit];
+        tutorial.run(&mut state, 0.1, &mut events);
+        assert!(events.iter().any(|e| matches!(e, GameLoopEvent::TutorialCompleted { player_id } if *player_id == id)));
+        assert!(!tutorial.has_players());
+    }
+
+    #[test]
+    fn test_falling_toward_the_well_is_not_an_orbit() {
+        let (mut state, mut tutorial, id) = setup();
+        state.get_player_mut(id).unwrap().velocity = Vec2::new(-300.0, 20.0);
+
+        for _ in 0..20 {
+            tutorial.run(&mut state, 0.1, &mut Vec::new());
+        }
+        assert_eq!(tutorial.step(id), Some(TutorialStep::OrbitWell));
+        assert!(!is_orbiting(&state, id, 1));
+    }
+}
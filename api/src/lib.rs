@@ -6,13 +6,24 @@
 //!
 //! - `anticheat` - Anti-cheat system with input validation, rate limiting, and behavior analysis (enabled by default)
 //! - `lobby` - Advanced lobby system with rooms, matchmaking, and session management (enabled by default)
+//! - `scripting` - WASM scripting hooks for community servers (requires wasmtime)
+//! - `profiling` - Flamegraph capture around tick-time spikes (requires pprof, Unix only)
+//! - `s3_export` - Background export of match data to S3-compatible storage (requires reqwest)
 //! - `minimal` - Build without optional features for testing/debugging
 
+pub mod cluster;
 pub mod config;
+pub mod export;
+pub mod features;
 pub mod util;
 pub mod game;
 pub mod net;
 pub mod metrics;
+pub mod public_api;
+pub mod roles;
+pub mod runtime;
+pub mod server;
+pub mod tenants;
 
 // Feature-gated modules (enabled by default)
 #[cfg(feature = "lobby")]
@@ -24,3 +35,7 @@ pub mod anticheat;
 // AI Simulation Manager (optional, requires API key)
 #[cfg(feature = "ai_manager")]
 pub mod ai_manager;
+
+// Server-side WASM scripting (optional, operator-provided scripts)
+#[cfg(feature = "scripting")]
+pub mod scripting;
@@ -0,0 +1,265 @@
+//! Galaxy: lobby arenas linked by wormholes
+//!
+//! A galaxy is a fixed set of arenas, lobby rooms whose match never ends,
+//! connected by one-way wormholes. The topology comes from a JSON file
+//! (`GALAXY_FILE`). A human who flies into a wormhole's mouth is handed to the
+//! linked arena with the mass and velocity they had, coming out at the
+//! wormhole's exit. Players get a `GalaxyMap` when they enter the galaxy and
+//! after every transit.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::game::state::PlayerId;
+use crate::lobby::room::{GameRoom, RoomKind};
+use crate::net::protocol::{GalaxyArena, GalaxyMap, GalaxyWormhole};
+use crate::util::vec2::Vec2;
+
+fn default_radius() -> f32 {
+    80.0
+}
+
+/// Arenas and wormholes of a galaxy, as written in the topology file
+#[derive(Debug, Clone, Deserialize)]
+pub struct GalaxyTopology {
+    pub arenas: Vec<ArenaSpec>,
+    #[serde(default)]
+    pub wormholes: Vec<WormholeSpec>,
+}
+
+/// An arena in the topology file
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArenaSpec {
+    /// Unique name, also the room name
+    pub name: String,
+    /// Room mode (`standard`, `slow_mode` or `practice`)
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+/// A one-way wormhole in the topology file
+#[derive(Debug, Clone, Deserialize)]
+pub struct WormholeSpec {
+    /// Arena the mouth is in
+    pub from: String,
+    /// Arena the wormhole leads to
+    pub to: String,
+    /// Mouth position in `from`
+    pub position: Vec2,
+    /// Where travelers come out in `to` (None = a usual spawn point)
+    #[serde(default)]
+    pub exit: Option<Vec2>,
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+}
+
+impl GalaxyTopology {
+    /// Load and validate a topology file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read galaxy file: {}", e))?;
+        let topology: Self =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse galaxy file: {}", e))?;
+        topology.validate()?;
+        Ok(topology)
+    }
+
+    /// Check arena names are unique and known to every wormhole
+    pub fn validate(&self) -> Result<(), String> {
+        if self.arenas.is_empty() {
+            return Err("Galaxy has no arenas".to_string());
+        }
+        let mut names = HashSet::new();
+        for arena in &self.arenas {
+            if !names.insert(arena.name.as_str()) {
+                return Err(format!("Galaxy arena {} is listed twice", arena.name));
+            }
+            if arena.mode.as_deref().is_some_and(|mode| RoomKind::from_name(mode).is_none()) {
+                return Err(format!("Galaxy arena {} has an unknown mode", arena.name));
+            }
+        }
+        for wormhole in &self.wormholes {
+            for end in [&wormhole.from, &wormhole.to] {
+                if !names.contains(end.as_str()) {
+                    return Err(format!("Wormhole links unknown arena {}", end));
+                }
+            }
+            if wormhole.from == wormhole.to {
+                return Err(format!("Wormhole in {} leads back to itself", wormhole.from));
+            }
+            if wormhole.radius <= 0.0 {
+                return Err(format!("Wormhole from {} to {} needs a positive radius", wormhole.from, wormhole.to));
+            }
+        }
+        Ok(())
+    }
+
+    /// Room kind of an arena
+    pub fn kind(arena: &ArenaSpec) -> RoomKind {
+        arena.mode.as_deref().and_then(RoomKind::from_name).unwrap_or_default()
+    }
+}
+
+/// A wormhole between two rooms
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wormhole {
+    pub from: Uuid,
+    pub to: Uuid,
+    pub position: Vec2,
+    pub exit: Option<Vec2>,
+    pub radius: f32,
+}
+
+/// A galaxy's rooms, wormholes and recent transits
+#[derive(Debug, Clone)]
+pub struct Galaxy {
+    /// Rooms in topology order (the first with space takes new players)
+    arenas: Vec<Uuid>,
+    wormholes: Vec<Wormhole>,
+    transit_cooldown: Duration,
+    /// When each player last came through a wormhole
+    last_transit: HashMap<PlayerId, Instant>,
+}
+
+impl Galaxy {
+    /// Link the rooms created for a topology's arenas (`rooms` in the same order)
+    pub fn new(topology: &GalaxyTopology, rooms: &[Uuid], transit_cooldown: Duration) -> Self {
+        let room_of = |name: &str| {
+            let index = topology.arenas.iter().position(|a| a.name == name);
+            index.and_then(|i| rooms.get(i)).copied()
+        };
+        let wormholes = topology
+            .wormholes
+            .iter()
+            .filter_map(|w| {
+                Some(Wormhole {
+                    from: room_of(&w.from)?,
+                    to: room_of(&w.to)?,
+                    position: w.position,
+                    exit: w.exit,
+                    radius: w.radius,
+                })
+            })
+            .collect();
+        Self {
+            arenas: rooms.to_vec(),
+            wormholes,
+            transit_cooldown,
+            last_transit: HashMap::new(),
+        }
+    }
+
+    /// Whether a room is one of the galaxy's arenas
+    pub fn contains(&self, room_id: Uuid) -> bool {
+        self.arenas.contains(&room_id)
+    }
+
+    /// Arena rooms in topology order
+    pub fn arenas(&self) -> &[Uuid] {
+        &self.arenas
+    }
+
+    /// Wormhole whose mouth a player at `position` in `room_id` is in, unless
+    /// they came through one too recently
+    pub fn wormhole_at(&self, room_id: Uuid, player_id: PlayerId, position: Vec2, now: Instant) -> Option<Wormhole> {
+        let cooling = self
+            .last_transit
+            .get(&player_id)
+            .is_some_and(|&at| now.saturating_duration_since(at) < self.transit_cooldown);
+        if cooling {
+            return None;
+        }
+        self.wormholes
+            .iter()
+            .find(|w| w.from == room_id && position.distance_sq_to(w.position) <= w.radius * w.radius)
+            .copied()
+    }
+
+    /// Note a transit, starting the player's cooldown
+    pub fn record_transit(&mut self, player_id: PlayerId, now: Instant) {
+        self.last_transit.retain(|_, at| now.saturating_duration_since(*at) < self.transit_cooldown);
+        self.last_transit.insert(player_id, now);
+    }
+
+    /// Forget a player who left the galaxy
+    pub fn forget(&mut self, player_id: PlayerId) {
+        self.last_transit.remove(&player_id);
+    }
+
+    /// The galaxy as seen from `current`
+    pub fn map(&self, rooms: &HashMap<Uuid, GameRoom>, current: Uuid) -> GalaxyMap {
+        let arenas = self
+            .arenas
+            .iter()
+            .filter_map(|id| rooms.get(id))
+            .map(|room| GalaxyArena {
+                room_id: room.id(),
+                name: room.name.clone(),
+                mode: room.kind.name().to_string(),
+                players: room.player_count() as u32,
+                max_players: room.max_humans as u32,
+            })
+            .collect();
+        let wormholes = self
+            .wormholes
+            .iter()
+            .map(|w| GalaxyWormhole { from: w.from, to: w.to, position: w.position, radius: w.radius })
+            .collect();
+        GalaxyMap { current, arenas, wormholes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topology(json: &str) -> Result<GalaxyTopology, String> {
+        let topology: GalaxyTopology = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        topology.validate().map(|_| topology)
+    }
+
+    #[test]
+    fn test_topology_validation() {
+        let valid = topology(
+            r#"{ "arenas": [{"name": "Sol"}, {"name": "Vega", "mode": "slow_mode"}],
+                 "wormholes": [{"from": "Sol", "to": "Vega", "position": {"x": 1200, "y": 0}}] }"#,
+        )
+        .unwrap();
+        assert_eq!(GalaxyTopology::kind(&valid.arenas[1]), RoomKind::SlowMode);
+        assert_eq!(valid.wormholes[0].radius, 80.0);
+
+        assert!(topology(r#"{ "arenas": [] }"#).is_err());
+        assert!(topology(r#"{ "arenas": [{"name": "Sol"}, {"name": "Sol"}] }"#).is_err());
+        assert!(topology(r#"{ "arenas": [{"name": "Sol", "mode": "ranked"}] }"#).is_err());
+        let unknown = r#"{ "arenas": [{"name": "Sol"}],
+                           "wormholes": [{"from": "Sol", "to": "Rigel", "position": {"x": 0, "y": 0}}] }"#;
+        assert!(topology(unknown).is_err());
+        let looped = r#"{ "arenas": [{"name": "Sol"}],
+                          "wormholes": [{"from": "Sol", "to": "Sol", "position": {"x": 0, "y": 0}}] }"#;
+        assert!(topology(looped).is_err());
+    }
+
+    #[test]
+    fn test_wormholes_wait_out_the_transit_cooldown() {
+        let topology = topology(
+            r#"{ "arenas": [{"name": "Sol"}, {"name": "Vega"}],
+                 "wormholes": [{"from": "Sol", "to": "Vega", "position": {"x": 1000, "y": 0}, "radius": 50}] }"#,
+        )
+        .unwrap();
+        let (sol, vega) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut galaxy = Galaxy::new(&topology, &[sol, vega], Duration::from_secs(3));
+        let (player, now) = (Uuid::new_v4(), Instant::now());
+
+        assert_eq!(galaxy.wormhole_at(sol, player, Vec2::new(900.0, 0.0), now), None);
+        let wormhole = galaxy.wormhole_at(sol, player, Vec2::new(1030.0, 20.0), now).unwrap();
+        assert_eq!((wormhole.from, wormhole.to), (sol, vega));
+        // The mouth is one-way
+        assert_eq!(galaxy.wormhole_at(vega, player, Vec2::new(1000.0, 0.0), now), None);
+
+        galaxy.record_transit(player, now);
+        assert_eq!(galaxy.wormhole_at(sol, player, Vec2::new(1000.0, 0.0), now + Duration::from_secs(2)), None);
+        assert!(galaxy.wormhole_at(sol, player, Vec2::new(1000.0, 0.0), now + Duration::from_secs(3)).is_some());
+    }
+}
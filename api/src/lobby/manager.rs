@@ -1,37 +1,141 @@
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::config::{
+    GalaxyConfig, LobbyQueueConfig, MatchHistoryConfig, ReportConfig, RoomBrowserConfig, SmurfConfig, TutorialConfig,
+};
+use crate::game::arena_seed::ArenaSeed;
+use crate::game::game_loop::GameLoopEvent;
 use crate::game::state::PlayerId;
+use crate::lobby::galaxy::{Galaxy, GalaxyTopology};
+use crate::lobby::match_history::MatchHistory;
 use crate::lobby::player::LobbyPlayer;
-use crate::lobby::room::{GameRoom, RoomError, RoomState};
+use crate::lobby::profiles::{unix_secs, PlayerProfiles};
+use crate::lobby::queue_stats::{ModeQueue, QueueReport, QueueStats};
+use crate::lobby::room::{GameRoom, RoomError, RoomKind, RoomState, MAX_ROOM_SIZE};
+use crate::lobby::smurf::{self, MatchSkill};
+use crate::net::game_session::MAX_SPECTATORS;
+use crate::net::account::AccountId;
+use crate::net::presence::Presence;
+use crate::net::room_link::RoomLinks;
+use crate::net::protocol::{PlayerInput, RoomFilter, RoomListing, ServerMessage};
+use crate::tenants::Tenant;
+
+/// Room modes reported on `/metrics`, including empty queues
+const QUEUE_MODES: [RoomKind; 3] = [RoomKind::Standard, RoomKind::SlowMode, RoomKind::Practice];
+
+/// A player waiting for their matchmade room to start
+#[derive(Debug, Clone, Copy)]
+struct QueuedPlayer {
+    room_id: Uuid,
+    kind: RoomKind,
+    mmr: u32,
+    since: Instant,
+}
 
 /// Lobby manager for managing game rooms
 pub struct LobbyManager {
     rooms: HashMap<Uuid, GameRoom>,
     player_rooms: HashMap<PlayerId, Uuid>,
+    /// Owning tenant of each hosted room
+    room_tenants: HashMap<Uuid, String>,
     max_rooms: usize,
     default_room_size: usize,
     default_max_humans: usize,
+    /// Players below this karma are matched with each other
+    low_karma_threshold: f32,
+    /// Whether new players get the tutorial
+    tutorial_enabled: bool,
+    /// Who has already completed the tutorial, and account records for smurf detection
+    profiles: PlayerProfiles,
+    smurf_config: SmurfConfig,
+    /// Summaries of finished matches
+    match_history: MatchHistory,
+    queue_config: LobbyQueueConfig,
+    /// Players in waiting matchmade rooms
+    queued: HashMap<PlayerId, QueuedPlayer>,
+    /// Finished waits per mode and MMR bucket
+    queue_stats: QueueStats,
+    /// When waiting players next get an estimate
+    next_estimate: Option<Instant>,
+    /// Estimates not yet sent to waiting players
+    queue_updates: Vec<(PlayerId, ServerMessage)>,
+    room_browser: RoomBrowserConfig,
+    /// Last room list served to each connection
+    room_list_times: HashMap<u64, Instant>,
+    /// Room each spectating connection was brokered to
+    room_spectators: HashMap<u64, Uuid>,
+    /// Arenas linked by wormholes, when a galaxy is configured
+    galaxy: Option<Galaxy>,
+    /// Galaxy maps not yet sent to players who entered or changed arenas
+    galaxy_updates: Vec<(PlayerId, ServerMessage)>,
+    /// Outbound channels of the connections playing in rooms
+    links: RoomLinks,
 }
 
 impl LobbyManager {
     pub fn new(max_rooms: usize) -> Self {
-        Self {
+        let tutorial = TutorialConfig::from_env();
+        let profiles = match tutorial.profiles_path.as_deref().map(PlayerProfiles::load) {
+            Some(Ok(profiles)) => profiles,
+            Some(Err(e)) => {
+                warn!("Failed to load player profiles, starting empty: {}", e);
+                PlayerProfiles::default()
+            }
+            None => PlayerProfiles::default(),
+        };
+        let queue_config = LobbyQueueConfig::from_env();
+        let mut manager = Self {
             rooms: HashMap::new(),
             player_rooms: HashMap::new(),
+            room_tenants: HashMap::new(),
             max_rooms,
             default_room_size: 10,
             default_max_humans: 10,
+            low_karma_threshold: ReportConfig::from_env().low_karma_threshold,
+            tutorial_enabled: tutorial.enabled,
+            profiles,
+            smurf_config: SmurfConfig::from_env(),
+            match_history: MatchHistory::from_config(&MatchHistoryConfig::from_env()),
+            queue_stats: QueueStats::new(&queue_config),
+            queue_config,
+            queued: HashMap::new(),
+            next_estimate: None,
+            queue_updates: Vec::new(),
+            room_browser: RoomBrowserConfig::from_env(),
+            room_list_times: HashMap::new(),
+            room_spectators: HashMap::new(),
+            galaxy: None,
+            galaxy_updates: Vec::new(),
+            links: RoomLinks::default(),
+        };
+
+        let galaxy = GalaxyConfig::from_env();
+        if let Some(path) = galaxy.topology_path.as_deref() {
+            let cooldown = Duration::from_secs_f32(galaxy.transit_cooldown_secs);
+            match GalaxyTopology::load(path).map(|topology| manager.create_galaxy(&topology, cooldown)) {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => warn!("Failed to create the galaxy: {}", e),
+                Err(e) => warn!("Invalid galaxy file {}: {}", path, e),
+            }
         }
+        manager
     }
 
     /// Create a new room
     pub fn create_room(&mut self, name: String) -> Result<Uuid, ManagerError> {
+        self.create_room_of_kind(name, RoomKind::Standard)
+    }
+
+    /// Create a new room running the given ruleset preset
+    pub fn create_room_of_kind(&mut self, name: String, kind: RoomKind) -> Result<Uuid, ManagerError> {
         if self.rooms.len() >= self.max_rooms {
             return Err(ManagerError::TooManyRooms);
         }
 
-        let room = GameRoom::new(name, self.default_room_size, self.default_max_humans);
+        let room = GameRoom::with_kind(name, self.default_room_size, self.default_max_humans, kind);
         let id = room.id();
         self.rooms.insert(id, room);
 
@@ -40,15 +144,145 @@ impl LobbyManager {
 
     /// Get or create a room for quick play
     pub fn find_or_create_room(&mut self) -> Result<Uuid, ManagerError> {
-        // Find a waiting room with space
+        self.find_or_create_room_of_kind(RoomKind::Standard)
+    }
+
+    /// Get or create a quick play room matching the player's slow-mode choice,
+    /// so slow-mode rooms only ever contain players who opted in
+    pub fn find_or_create_room_for(&mut self, player: &LobbyPlayer) -> Result<Uuid, ManagerError> {
+        let low_karma = player.karma < self.low_karma_threshold;
+        self.find_or_create_matching_room(RoomKind::preferred_by(player), low_karma)
+    }
+
+    /// Join the room a player asked for or, when it is a matchmade room of the
+    /// other karma bracket, a matchmade room of the same kind in the player's
+    /// own bracket. Returns the room joined.
+    pub fn join_room_in_bracket(&mut self, room_id: Uuid, player: LobbyPlayer) -> Result<Uuid, ManagerError> {
+        let low_karma = player.karma < self.low_karma_threshold;
+        let other_bracket = self.rooms.get(&room_id).filter(|room| {
+            room.low_karma != low_karma && !self.room_tenants.contains_key(&room_id) && !self.is_galaxy_room(room_id)
+        });
+        let room_id = match other_bracket.map(|room| room.kind) {
+            Some(kind) => self.find_or_create_matching_room(kind, low_karma)?,
+            None => room_id,
+        };
+        self.join_room(room_id, player)?;
+        Ok(room_id)
+    }
+
+    /// Get or create a waiting room of the given kind
+    pub fn find_or_create_room_of_kind(&mut self, kind: RoomKind) -> Result<Uuid, ManagerError> {
+        self.find_or_create_matching_room(kind, false)
+    }
+
+    /// Get or create a waiting room of the given kind and karma bracket
+    fn find_or_create_matching_room(&mut self, kind: RoomKind, low_karma: bool) -> Result<Uuid, ManagerError> {
+        // Find a waiting room with space (hosted rooms are never matchmade into)
         for (id, room) in &self.rooms {
-            if room.state == RoomState::Waiting && !room.is_full() {
+            if room.kind == kind
+                && room.low_karma == low_karma
+                && room.state == RoomState::Waiting
+                && !room.is_full()
+                && !self.room_tenants.contains_key(id)
+                && !self.is_galaxy_room(*id)
+            {
                 return Ok(*id);
             }
         }
 
         // Create a new room
-        self.create_room(format!("Game {}", self.rooms.len() + 1))
+        let prefix = match kind {
+            RoomKind::Standard => "Game",
+            RoomKind::SlowMode => "Slow Game",
+            RoomKind::Practice => "Practice",
+        };
+        let id = self.create_room_of_kind(format!("{} {}", prefix, self.rooms.len() + 1), kind)?;
+        if let Some(room) = self.rooms.get_mut(&id) {
+            room.low_karma = low_karma;
+        }
+        Ok(id)
+    }
+
+    /// Create a room hosted by a tenant, within the tenant's quota and
+    /// `MAX_ROOM_SIZE` (a seed pins every match in the room to that arena layout)
+    pub fn create_tenant_room(
+        &mut self,
+        tenant: &Tenant,
+        name: String,
+        max_players: usize,
+        arena_seed: Option<ArenaSeed>,
+    ) -> Result<Uuid, ManagerError> {
+        let usage = self.tenant_usage(&tenant.id);
+        if usage.rooms >= tenant.quota.max_rooms {
+            return Err(ManagerError::TenantRoomQuota);
+        }
+        if max_players == 0 || max_players > MAX_ROOM_SIZE {
+            return Err(ManagerError::InvalidRoomSize);
+        }
+        if usage.capacity.checked_add(max_players).map_or(true, |capacity| capacity > tenant.quota.max_players) {
+            return Err(ManagerError::TenantPlayerQuota);
+        }
+        if self.rooms.len() >= self.max_rooms {
+            return Err(ManagerError::TooManyRooms);
+        }
+
+        let mut room = GameRoom::new(name, max_players, max_players);
+        if let Some(seed) = arena_seed {
+            room = room.with_arena_seed(seed);
+        }
+        let id = room.id();
+        self.rooms.insert(id, room);
+        self.room_tenants.insert(id, tenant.id.clone());
+
+        Ok(id)
+    }
+
+    /// Start the next match in a room hosted by a tenant; players join hosted
+    /// rooms by id and wait until their tenant starts them
+    pub fn start_tenant_room(&mut self, tenant_id: &str, room_id: Uuid) -> Result<(), ManagerError> {
+        if self.room_tenants.get(&room_id).map(String::as_str) != Some(tenant_id) {
+            return Err(ManagerError::RoomNotFound);
+        }
+        let room = self.rooms.get_mut(&room_id).ok_or(ManagerError::RoomNotFound)?;
+        if room.state == RoomState::Ended {
+            room.reset();
+        }
+        room.start_game().map_err(ManagerError::RoomError)
+    }
+
+    /// Close a room hosted by a tenant (other tenants' rooms are not found)
+    pub fn close_tenant_room(&mut self, tenant_id: &str, room_id: Uuid) -> Result<GameRoom, ManagerError> {
+        if self.room_tenants.get(&room_id).map(String::as_str) != Some(tenant_id) {
+            return Err(ManagerError::RoomNotFound);
+        }
+        self.remove_room(room_id).ok_or(ManagerError::RoomNotFound)
+    }
+
+    /// Rooms hosted by one tenant
+    pub fn tenant_rooms(&self, tenant_id: &str) -> Vec<RoomInfo> {
+        self.list_rooms()
+            .into_iter()
+            .filter(|room| room.tenant_id.as_deref() == Some(tenant_id))
+            .collect()
+    }
+
+    /// Current usage of one tenant
+    pub fn tenant_usage(&self, tenant_id: &str) -> TenantUsage {
+        let mut usage = TenantUsage { tenant_id: tenant_id.to_string(), ..Default::default() };
+        for room in self.tenant_rooms(tenant_id) {
+            usage.rooms += 1;
+            usage.players += room.player_count;
+            usage.capacity += room.max_players;
+        }
+        usage
+    }
+
+    /// Usage of every tenant with open rooms
+    pub fn all_tenant_usage(&self) -> Vec<TenantUsage> {
+        let mut tenant_ids: Vec<&String> = self.room_tenants.values().collect();
+        tenant_ids.sort();
+        tenant_ids.dedup();
+        tenant_ids.into_iter().map(|id| self.tenant_usage(id)).collect()
     }
 
     /// Get a room by ID
@@ -61,12 +295,33 @@ impl LobbyManager {
         self.rooms.get_mut(&room_id)
     }
 
+    /// Every open room
+    pub fn rooms(&self) -> impl Iterator<Item = &GameRoom> {
+        self.rooms.values()
+    }
+
+    /// Channels of the connections playing in rooms
+    pub fn links(&self) -> &RoomLinks {
+        &self.links
+    }
+
+    /// Channels of the connections playing in rooms, to link or unlink one
+    pub fn links_mut(&mut self) -> &mut RoomLinks {
+        &mut self.links
+    }
+
     /// Remove a room
     pub fn remove_room(&mut self, room_id: Uuid) -> Option<GameRoom> {
         if let Some(room) = self.rooms.remove(&room_id) {
+            self.room_tenants.remove(&room_id);
+            for connection_id in self.room_spectators(room_id) {
+                self.stop_spectating(connection_id);
+            }
             // Remove player mappings
             for player_id in room.player_ids() {
                 self.player_rooms.remove(&player_id);
+                self.queued.remove(&player_id);
+                self.links.unlink_player(player_id);
             }
             Some(room)
         } else {
@@ -74,42 +329,79 @@ impl LobbyManager {
         }
     }
 
-    /// Join a player to a room
+    /// Join a player to a room (new players start the tutorial unless it is ranked)
     pub fn join_room(
         &mut self,
         room_id: Uuid,
         player: LobbyPlayer,
     ) -> Result<(), ManagerError> {
         let player_id = player.id;
+        let mmr = self.effective_mmr(&player);
+        let is_new = self.tutorial_enabled && self.profiles.is_new(player.profile_account());
 
         // Check if player is already in a room
         if self.player_rooms.contains_key(&player_id) {
             return Err(ManagerError::AlreadyInRoom);
         }
 
+        // Galaxy arenas never end, so players enter them mid-match
+        let in_galaxy = self.is_galaxy_room(room_id);
         let room = self
             .rooms
             .get_mut(&room_id)
             .ok_or(ManagerError::RoomNotFound)?;
 
-        room.add_player(player).map_err(ManagerError::RoomError)?;
+        if in_galaxy {
+            room.enter(player).map_err(ManagerError::RoomError)?;
+        } else {
+            room.add_player(player).map_err(ManagerError::RoomError)?;
+        }
         self.player_rooms.insert(player_id, room_id);
+        if !in_galaxy && !self.room_tenants.contains_key(&room_id) {
+            let queued = QueuedPlayer { room_id, kind: room.kind, mmr, since: Instant::now() };
+            self.queued.insert(player_id, queued);
+        }
+        if is_new {
+            // Ranked rooms decline; the player simply plays without it
+            let _ = room.start_tutorial(player_id);
+        }
+        if in_galaxy {
+            self.queue_galaxy_map(player_id, room_id);
+        }
 
         Ok(())
     }
 
+    /// Queue a room player's input in their room
+    pub fn process_input(&mut self, player_id: PlayerId, input: PlayerInput) {
+        if let Some(room) = self.player_rooms.get(&player_id).and_then(|id| self.rooms.get_mut(id)) {
+            room.process_input(player_id, input);
+        }
+    }
+
+    /// MMR matchmaking uses for a player: their rating, raised while they look like a smurf
+    pub fn effective_mmr(&self, player: &LobbyPlayer) -> u32 {
+        smurf::effective_mmr(player.mmr, self.profiles.account(player.profile_account()), unix_secs(), &self.smurf_config)
+    }
+
     /// Leave current room
     pub fn leave_room(&mut self, player_id: PlayerId) -> Result<(), ManagerError> {
         let room_id = self
             .player_rooms
             .remove(&player_id)
             .ok_or(ManagerError::NotInRoom)?;
+        self.queued.remove(&player_id);
+        self.links.unlink_player(player_id);
+        if let Some(galaxy) = &mut self.galaxy {
+            galaxy.forget(player_id);
+        }
+        let persistent = self.room_tenants.contains_key(&room_id) || self.is_galaxy_room(room_id);
 
         if let Some(room) = self.rooms.get_mut(&room_id) {
             room.remove_player(player_id);
 
-            // Clean up empty rooms
-            if room.is_empty() && room.state != RoomState::Playing {
+            // Clean up empty rooms (hosted rooms stay open until their tenant closes them)
+            if room.is_empty() && room.state != RoomState::Playing && !persistent {
                 self.rooms.remove(&room_id);
             }
         }
@@ -132,6 +424,25 @@ impl LobbyManager {
         self.player_rooms.len()
     }
 
+    /// Summaries of recently finished matches
+    pub fn match_history(&self) -> &MatchHistory {
+        &self.match_history
+    }
+
+    /// Drop match history and tutorial completions from before `cutoff`
+    /// (Unix seconds), returning how many records went
+    pub fn purge_before(&mut self, cutoff: u64) -> Result<usize, String> {
+        Ok(self.match_history.purge_before(cutoff)? + self.profiles.purge_before(cutoff)?)
+    }
+
+    /// Presence of a player in a lobby room, by account id (see `net::presence`)
+    pub fn presence(&self, account: AccountId) -> Option<Presence> {
+        self.rooms.values().find_map(|room| {
+            let player_id = room.player_with_account(account)?;
+            Presence::find(&room.get_snapshot(), account, player_id, room.kind.name(), &room.name)
+        })
+    }
+
     /// Get list of available rooms (for room browser)
     pub fn list_rooms(&self) -> Vec<RoomInfo> {
         self.rooms
@@ -139,25 +450,162 @@ impl LobbyManager {
             .map(|room| RoomInfo {
                 id: room.id(),
                 name: room.name.clone(),
+                kind: room.kind,
                 player_count: room.player_count(),
                 max_players: room.max_players,
                 state: room.state,
+                arena_code: room.game_state().arena.seed.code(),
+                tenant_id: self.room_tenants.get(&room.id()).cloned(),
             })
             .collect()
     }
 
+    /// One page of the room browser for a connection, as a `RoomList`.
+    /// Rooms are listed oldest first; hosted and closing rooms never are.
+    pub fn browse_rooms(
+        &mut self,
+        connection_id: u64,
+        filter: &RoomFilter,
+        page: u32,
+        now: Instant,
+    ) -> Result<ServerMessage, ManagerError> {
+        let min_interval = Duration::from_secs_f32(self.room_browser.min_interval_secs);
+        let last = self.room_list_times.get(&connection_id);
+        if last.is_some_and(|&at| now.saturating_duration_since(at) < min_interval) {
+            return Err(ManagerError::RateLimited);
+        }
+        self.room_list_times.retain(|_, at| now.saturating_duration_since(*at) < min_interval);
+        self.room_list_times.insert(connection_id, now);
+
+        let region = &self.room_browser.region;
+        if filter.region.as_ref().is_some_and(|r| r != region) {
+            return Ok(ServerMessage::RoomList { rooms: Vec::new(), page, total_pages: 0 });
+        }
+        let mut rooms: Vec<&GameRoom> = self
+            .rooms
+            .iter()
+            .filter(|(id, room)| !self.room_tenants.contains_key(*id) && room.state != RoomState::Closing)
+            .map(|(_, room)| room)
+            .filter(|room| filter.mode.as_deref().map_or(true, |mode| mode == room.kind.name()))
+            .filter(|room| !filter.not_full || !room.is_full())
+            .filter(|room| filter.friends.is_empty() || filter.friends.iter().any(|f| room.get_player(*f).is_some()))
+            .collect();
+        rooms.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+        let page_size = self.room_browser.page_size.max(1);
+        let total_pages = rooms.len().div_ceil(page_size) as u32;
+        let rooms = rooms
+            .into_iter()
+            .skip(page as usize * page_size)
+            .take(page_size)
+            .map(|room| RoomListing {
+                room_id: room.id(),
+                name: room.name.clone(),
+                mode: room.kind.name().to_string(),
+                region: region.clone(),
+                players: room.player_count() as u32,
+                max_players: room.max_humans as u32,
+                in_progress: room.state != RoomState::Waiting,
+                friends: filter.friends.iter().copied().filter(|f| room.get_player(*f).is_some()).collect(),
+            })
+            .collect();
+        Ok(ServerMessage::RoomList { rooms, page, total_pages })
+    }
+
+    /// Let a connection spectate a public room without taking a player slot.
+    /// At most `MAX_SPECTATORS` connections watch one room; spectating another
+    /// room gives up the previous one.
+    pub fn spectate_room(&mut self, connection_id: u64, room_id: Uuid) -> Result<(), ManagerError> {
+        let public = self.rooms.get(&room_id).is_some_and(|room| room.state != RoomState::Closing)
+            && !self.room_tenants.contains_key(&room_id);
+        if !public {
+            return Err(ManagerError::RoomNotFound);
+        }
+        if self.room_spectators.get(&connection_id) == Some(&room_id) {
+            return Ok(());
+        }
+        if self.spectator_count(room_id) >= MAX_SPECTATORS {
+            return Err(ManagerError::SpectatorsFull);
+        }
+        self.room_spectators.insert(connection_id, room_id);
+        debug!("Connection {} spectating room {}", connection_id, room_id);
+        Ok(())
+    }
+
+    /// Free a connection's spectator place, returning the room it watched
+    pub fn stop_spectating(&mut self, connection_id: u64) -> Option<Uuid> {
+        self.links.unlink_spectator(connection_id);
+        self.room_spectators.remove(&connection_id)
+    }
+
+    /// Connections spectating a room from the lobby
+    pub fn spectator_count(&self, room_id: Uuid) -> usize {
+        self.room_spectators.values().filter(|&&spectated| spectated == room_id).count()
+    }
+
+    /// Ids of the connections spectating a room
+    pub fn room_spectators(&self, room_id: Uuid) -> Vec<u64> {
+        self.room_spectators.iter().filter(|(_, &spectated)| spectated == room_id).map(|(&id, _)| id).collect()
+    }
+
     /// Update all rooms
     pub fn update_all(&mut self) {
         for room in self.rooms.values_mut() {
-            room.update();
+            for event in room.update() {
+                match event {
+                    GameLoopEvent::TutorialCompleted { player_id } => {
+                        if let Some(player) = room.get_player(player_id) {
+                            self.profiles.record_completed(player.profile_account());
+                        }
+                    }
+                    GameLoopEvent::MatchEnded { mut result } => {
+                        // Humans' accounts first, while players are still attached
+                        let skill = room.take_match_skill();
+                        let no_skill = MatchSkill::default();
+                        for ranking in result.rankings.iter().filter(|r| !r.is_bot) {
+                            let Some(player) = room.get_player(ranking.player_id) else {
+                                continue;
+                            };
+                            let skill = skill.get(&ranking.player_id).unwrap_or(&no_skill);
+                            let (won, early_matches) = (ranking.rank == 1, self.smurf_config.early_matches);
+                            let account = self.profiles.record_match(player.profile_account(), won, skill, early_matches);
+                            let assessment = smurf::assess(account, unix_secs(), &self.smurf_config);
+                            if assessment.flagged {
+                                info!(
+                                    "Player {} looks like a smurf ({}), matching at +{} MMR",
+                                    ranking.player_id,
+                                    assessment.signals.join(", "),
+                                    assessment.mmr_boost
+                                );
+                            }
+                        }
+
+                        // Players who opted out of analytics stay in the totals, but not by name
+                        let anonymous: Vec<PlayerId> = result
+                            .rankings
+                            .iter()
+                            .map(|r| r.player_id)
+                            .filter(|&id| room.get_player(id).is_some_and(|p| !p.telemetry.analytics))
+                            .collect();
+                        for player_id in anonymous {
+                            result.anonymize(player_id);
+                        }
+                        self.match_history.record(room.kind.name(), &room.name, &result);
+                    }
+                    _ => {}
+                }
+            }
         }
 
-        // Clean up ended rooms that are empty
+        // Clean up ended rooms that are empty (hosted rooms are closed by their tenant)
         let rooms_to_remove: Vec<Uuid> = self
             .rooms
             .iter()
-            .filter(|(_, room)| {
-                room.state == RoomState::Ended && room.is_empty()
+            .filter(|(id, room)| {
+                room.state == RoomState::Ended
+                    && room.is_empty()
+                    && !self.room_tenants.contains_key(*id)
+                    && !self.is_galaxy_room(**id)
                     || room.state == RoomState::Closing
             })
             .map(|(id, _)| *id)
@@ -166,6 +614,204 @@ impl LobbyManager {
         for room_id in rooms_to_remove {
             self.remove_room(room_id);
         }
+
+        self.update_galaxy(Instant::now());
+        self.update_queue(Instant::now());
+    }
+
+    /// Create the galaxy's arenas and link them. A server has at most one galaxy.
+    pub fn create_galaxy(&mut self, topology: &GalaxyTopology, transit_cooldown: Duration) -> Result<(), ManagerError> {
+        if self.galaxy.is_some() {
+            return Err(ManagerError::GalaxyExists);
+        }
+        if self.rooms.len() + topology.arenas.len() > self.max_rooms {
+            return Err(ManagerError::TooManyRooms);
+        }
+        let rooms: Vec<Uuid> = topology
+            .arenas
+            .iter()
+            .map(|arena| {
+                let kind = GalaxyTopology::kind(arena);
+                let room = GameRoom::with_kind(arena.name.clone(), self.default_room_size, self.default_max_humans, kind)
+                    .with_endless();
+                let id = room.id();
+                self.rooms.insert(id, room);
+                id
+            })
+            .collect();
+        info!("Galaxy: {} arena(s), {} wormhole(s)", rooms.len(), topology.wormholes.len());
+        self.galaxy = Some(Galaxy::new(topology, &rooms, transit_cooldown));
+        Ok(())
+    }
+
+    /// Whether a room is a galaxy arena
+    pub fn is_galaxy_room(&self, room_id: Uuid) -> bool {
+        self.galaxy.as_ref().is_some_and(|galaxy| galaxy.contains(room_id))
+    }
+
+    /// Join a player to the first galaxy arena with space, in topology order
+    pub fn join_galaxy(&mut self, player: LobbyPlayer) -> Result<Uuid, ManagerError> {
+        let galaxy = self.galaxy.as_ref().ok_or(ManagerError::RoomNotFound)?;
+        let room_id = galaxy
+            .arenas()
+            .iter()
+            .copied()
+            .find(|id| self.rooms.get(id).is_some_and(|room| room.admits_traveler(&player).is_ok()))
+            .ok_or(ManagerError::RoomError(RoomError::RoomFull))?;
+        self.join_room(room_id, player)?;
+        Ok(room_id)
+    }
+
+    /// Hand humans who flew into a wormhole mouth to the linked arena, with
+    /// their mass and velocity. A full (or ended) destination leaves them where they are.
+    fn update_galaxy(&mut self, now: Instant) {
+        let Some(galaxy) = &self.galaxy else {
+            return;
+        };
+        let mut transits = Vec::new();
+        for &room_id in galaxy.arenas() {
+            let Some(room) = self.rooms.get(&room_id) else {
+                continue;
+            };
+            for player in room.game_state().players.values() {
+                if !player.alive || player.is_bot || room.get_player(player.id).is_none() {
+                    continue;
+                }
+                if let Some(wormhole) = galaxy.wormhole_at(room_id, player.id, player.position, now) {
+                    transits.push((player.id, wormhole));
+                }
+            }
+        }
+
+        for (player_id, wormhole) in transits {
+            let admitted = match (self.rooms.get(&wormhole.to), self.rooms.get(&wormhole.from)) {
+                (Some(to), Some(from)) => from.get_player(player_id).is_some_and(|p| to.admits_traveler(p).is_ok()),
+                _ => false,
+            };
+            if !admitted {
+                continue;
+            }
+            let Some((lobby_player, player)) = self.rooms.get_mut(&wormhole.from).and_then(|r| r.depart(player_id))
+            else {
+                continue;
+            };
+            let Some(to) = self.rooms.get_mut(&wormhole.to) else {
+                continue;
+            };
+            if let Err(e) = to.arrive(lobby_player, player, wormhole.exit) {
+                warn!("Player {} lost in a wormhole: {}", player_id, e);
+                self.player_rooms.remove(&player_id);
+                self.links.unlink_player(player_id);
+                continue;
+            }
+            debug!("Player {} took a wormhole to {}", player_id, to.name);
+            self.player_rooms.insert(player_id, wormhole.to);
+            if let Some(galaxy) = &mut self.galaxy {
+                galaxy.record_transit(player_id, now);
+            }
+            self.queue_galaxy_map(player_id, wormhole.to);
+        }
+    }
+
+    fn queue_galaxy_map(&mut self, player_id: PlayerId, room_id: Uuid) {
+        if let Some(galaxy) = &self.galaxy {
+            let map = galaxy.map(&self.rooms, room_id);
+            self.galaxy_updates.push((player_id, ServerMessage::GalaxyMap(map)));
+        }
+    }
+
+    /// Galaxy maps for players who entered the galaxy or changed arenas, to be sent to each one
+    pub fn take_galaxy_updates(&mut self) -> Vec<(PlayerId, ServerMessage)> {
+        std::mem::take(&mut self.galaxy_updates)
+    }
+
+    /// Start waiting rooms with bots once someone waited past the backfill
+    /// threshold, record the waits of players whose room started and queue
+    /// estimates for the rest when due
+    pub fn update_queue(&mut self, now: Instant) {
+        let waited = |queued: &QueuedPlayer| now.saturating_duration_since(queued.since).as_secs_f32();
+
+        let backfill_after = self.queue_config.backfill_after_secs;
+        if backfill_after > 0.0 {
+            let mut overdue: Vec<Uuid> =
+                self.queued.values().filter(|q| waited(q) >= backfill_after).map(|q| q.room_id).collect();
+            overdue.sort_unstable();
+            overdue.dedup();
+            for room_id in overdue {
+                let Some(room) = self.rooms.get_mut(&room_id) else {
+                    continue;
+                };
+                if room.state == RoomState::Waiting && room.start_game().is_ok() {
+                    debug!("Backfilled {} with bots after {}s in queue", room.name, backfill_after);
+                    self.queue_stats.backfills += 1;
+                }
+            }
+        }
+
+        let rooms = &self.rooms;
+        let stats = &mut self.queue_stats;
+        self.queued.retain(|_, queued| match rooms.get(&queued.room_id).map(|room| room.state) {
+            Some(RoomState::Waiting) => true,
+            Some(RoomState::Playing) => {
+                stats.record(queued.kind, queued.mmr, waited(queued));
+                false
+            }
+            _ => false,
+        });
+
+        if self.next_estimate.is_some_and(|at| now < at) {
+            return;
+        }
+        self.next_estimate = Some(now + Duration::from_secs_f32(self.queue_config.estimate_interval_secs));
+        for (&player_id, queued) in &self.queued {
+            let waited = waited(queued);
+            let backfill_in = (backfill_after > 0.0).then_some(backfill_after - waited);
+            let eta = match (self.queue_stats.estimate(queued.kind, queued.mmr), backfill_in) {
+                (Some(estimate), Some(backfill_in)) => (estimate - waited).min(backfill_in),
+                (Some(estimate), None) => estimate - waited,
+                (None, Some(backfill_in)) => backfill_in,
+                (None, None) => continue,
+            };
+            // Earlier arrivals in the same room go first
+            let position = 1 + self
+                .queued
+                .values()
+                .filter(|other| other.room_id == queued.room_id && other.since < queued.since)
+                .count();
+            let eta_secs = eta.max(0.0).round() as u32;
+            self.queue_updates.push((player_id, ServerMessage::QueueUpdate { position: position as u32, eta_secs }));
+        }
+    }
+
+    /// Queue estimates for waiting players, to be sent to each one
+    pub fn take_queue_updates(&mut self) -> Vec<(PlayerId, ServerMessage)> {
+        std::mem::take(&mut self.queue_updates)
+    }
+
+    /// Waiting players per mode and average waits per MMR bucket
+    pub fn queue_report(&self, now: Instant) -> QueueReport {
+        let modes = QUEUE_MODES
+            .iter()
+            .map(|&kind| {
+                let waits: Vec<f32> = self
+                    .queued
+                    .values()
+                    .filter(|q| q.kind == kind)
+                    .map(|q| now.saturating_duration_since(q.since).as_secs_f32())
+                    .collect();
+                ModeQueue {
+                    mode: kind.name(),
+                    waiting: waits.len(),
+                    longest_wait_secs: waits.into_iter().fold(0.0, f32::max),
+                }
+            })
+            .collect();
+        QueueReport {
+            modes,
+            buckets: self.queue_stats.bucket_waits(),
+            matched: self.queue_stats.matched,
+            backfills: self.queue_stats.backfills,
+        }
     }
 
     /// Shutdown all rooms
@@ -176,6 +822,11 @@ impl LobbyManager {
         }
         self.rooms.clear();
         self.player_rooms.clear();
+        self.room_tenants.clear();
+        self.queued.clear();
+        self.room_spectators.clear();
+        self.links = RoomLinks::default();
+        self.galaxy = None;
     }
 }
 
@@ -190,9 +841,24 @@ impl Default for LobbyManager {
 pub struct RoomInfo {
     pub id: Uuid,
     pub name: String,
+    pub kind: RoomKind,
     pub player_count: usize,
     pub max_players: usize,
     pub state: RoomState,
+    /// Shareable code of the current match's arena seed
+    pub arena_code: String,
+    /// Hosting tenant (None for the server's own rooms)
+    pub tenant_id: Option<String>,
+}
+
+/// Rooms, players and capacity a tenant is currently using
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TenantUsage {
+    pub tenant_id: String,
+    pub rooms: usize,
+    pub players: usize,
+    /// Sum of the tenant's room sizes (counted against `TenantQuota::max_players`)
+    pub capacity: usize,
 }
 
 /// Manager errors
@@ -206,6 +872,18 @@ pub enum ManagerError {
     AlreadyInRoom,
     #[error("Not in a room")]
     NotInRoom,
+    #[error("Tenant room quota reached")]
+    TenantRoomQuota,
+    #[error("Tenant player quota reached")]
+    TenantPlayerQuota,
+    #[error("Room size must be between 1 and {}", MAX_ROOM_SIZE)]
+    InvalidRoomSize,
+    #[error("Room list requested too often")]
+    RateLimited,
+    #[error("Room has no spectator places left")]
+    SpectatorsFull,
+    #[error("A galaxy already exists")]
+    GalaxyExists,
     #[error("Room error: {0}")]
     RoomError(#[from] RoomError),
 }
@@ -213,6 +891,7 @@ pub enum ManagerError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::state::MatchPhase;
     use crate::net::session::SessionToken;
 
     fn create_player(name: &str) -> LobbyPlayer {
@@ -325,4 +1004,324 @@ mod tests {
 
         assert_eq!(manager.total_player_count(), 2);
     }
+
+    #[test]
+    fn test_tenant_rooms_respect_quota_and_isolation() {
+        use crate::tenants::TenantQuota;
+
+        let mut manager = LobbyManager::new(10);
+        let tenant = |id: &str| Tenant { id: id.to_string(), quota: TenantQuota { max_rooms: 2, max_players: 30 } };
+        let (a, b) = (tenant("a"), tenant("b"));
+
+        let room = manager.create_tenant_room(&a, "A1".to_string(), 20, None).unwrap();
+        assert!(matches!(
+            manager.create_tenant_room(&a, "A2".to_string(), 20, None),
+            Err(ManagerError::TenantPlayerQuota)
+        ));
+        manager.create_tenant_room(&a, "A2".to_string(), 10, None).unwrap();
+        assert!(matches!(
+            manager.create_tenant_room(&a, "A3".to_string(), 1, None),
+            Err(ManagerError::TenantRoomQuota)
+        ));
+        manager.create_tenant_room(&b, "B1".to_string(), 30, None).unwrap();
+        let huge = Tenant { id: "c".to_string(), quota: TenantQuota { max_rooms: 2, max_players: usize::MAX } };
+        assert!(matches!(
+            manager.create_tenant_room(&huge, "C1".to_string(), MAX_ROOM_SIZE + 1, None),
+            Err(ManagerError::InvalidRoomSize)
+        ));
+
+        let usage = manager.tenant_usage("a");
+        assert_eq!((usage.rooms, usage.players, usage.capacity), (2, 0, 30));
+        assert_eq!(manager.tenant_rooms("b").len(), 1);
+        assert_eq!(manager.all_tenant_usage().len(), 2);
+
+        // Hosted rooms are not matchmade into, and only their tenant can start or close them
+        assert_ne!(manager.find_or_create_room().unwrap(), room);
+        manager.join_room(room, create_player("Host")).unwrap();
+        assert!(matches!(manager.start_tenant_room("b", room), Err(ManagerError::RoomNotFound)));
+        manager.start_tenant_room("a", room).unwrap();
+        assert_eq!(manager.get_room(room).unwrap().state, RoomState::Playing);
+        assert!(matches!(manager.close_tenant_room("b", room), Err(ManagerError::RoomNotFound)));
+        manager.close_tenant_room("a", room).unwrap();
+        assert_eq!(manager.tenant_usage("a").rooms, 1);
+    }
+
+    #[test]
+    fn test_matchmaking_separates_slow_mode_players() {
+        let mut manager = LobbyManager::new(10);
+        let standard = create_player("P1");
+        let mut slow = create_player("P2");
+        slow.prefers_slow_mode = true;
+
+        let standard_room = manager.find_or_create_room_for(&standard).unwrap();
+        let slow_room = manager.find_or_create_room_for(&slow).unwrap();
+        assert_ne!(standard_room, slow_room);
+        assert_eq!(manager.get_room(slow_room).unwrap().kind, RoomKind::SlowMode);
+
+        manager.join_room(slow_room, slow).unwrap();
+        assert!(matches!(
+            manager.join_room(slow_room, standard),
+            Err(ManagerError::RoomError(RoomError::SlowModeNotChosen))
+        ));
+        assert_eq!(manager.find_or_create_room().unwrap(), standard_room);
+    }
+
+    #[test]
+    fn test_likely_smurfs_queue_at_a_higher_effective_mmr() {
+        let mut manager = LobbyManager::new(10);
+        let mut ace = create_player("Ace");
+        ace.account = Some(AccountId::generate());
+        assert_eq!(manager.effective_mmr(&ace), ace.mmr);
+
+        // A new account winning every match with most of its shots hitting
+        let mut accurate = MatchSkill::default();
+        (accurate.shots, accurate.hits) = (20, 15);
+        for _ in 0..manager.smurf_config.win_streak {
+            manager.profiles.record_match(ace.profile_account(), true, &accurate, manager.smurf_config.early_matches);
+        }
+        let boosted = manager.effective_mmr(&ace);
+        assert!(boosted > ace.mmr);
+
+        // Taking the name on another account doesn't take the record
+        let mut namesake = create_player("Ace");
+        namesake.account = Some(AccountId::generate());
+        assert_eq!(manager.effective_mmr(&namesake), namesake.mmr);
+
+        let room = manager.find_or_create_room_for(&ace).unwrap();
+        let ace_id = ace.id;
+        manager.join_room(room, ace).unwrap();
+        assert_eq!(manager.queued[&ace_id].mmr, boosted);
+    }
+
+    #[test]
+    fn test_matchmaking_separates_low_karma_players() {
+        let mut manager = LobbyManager::new(10);
+        let friendly = create_player("Friendly");
+        let mut toxic = create_player("Toxic");
+        toxic.karma = manager.low_karma_threshold - 1.0;
+
+        let friendly_room = manager.find_or_create_room_for(&friendly).unwrap();
+        let toxic_room = manager.find_or_create_room_for(&toxic).unwrap();
+        assert_ne!(friendly_room, toxic_room);
+        assert!(manager.get_room(toxic_room).unwrap().low_karma);
+
+        let mut also_toxic = create_player("Also Toxic");
+        also_toxic.karma = 0.0;
+        assert_eq!(manager.find_or_create_room_for(&also_toxic).unwrap(), toxic_room);
+        assert_eq!(manager.find_or_create_room().unwrap(), friendly_room);
+    }
+
+    #[test]
+    fn test_low_karma_players_picking_a_room_join_their_bracket() {
+        let mut manager = LobbyManager::new(10);
+        let listed = manager.create_room("Game 1".to_string()).unwrap();
+        let mut toxic = create_player("Toxic");
+        toxic.karma = manager.low_karma_threshold - 1.0;
+
+        let placed = manager.join_room_in_bracket(listed, toxic).unwrap();
+        assert_ne!(placed, listed);
+        assert!(manager.get_room(placed).unwrap().low_karma);
+
+        // Everyone else gets the room they asked for, but not a low-karma one
+        assert_eq!(manager.join_room_in_bracket(listed, create_player("Friendly")).unwrap(), listed);
+        assert_ne!(manager.join_room_in_bracket(placed, create_player("Kind")).unwrap(), placed);
+    }
+
+    #[test]
+    fn test_tutorial_only_for_new_players() {
+        let mut manager = LobbyManager::new(10);
+        let mut veteran = create_player("Veteran");
+        veteran.account = Some(AccountId::generate());
+        manager.profiles.record_completed(veteran.profile_account());
+        // Taking a veteran's name on a new account still gets the tutorial
+        let mut newcomer = create_player("Veteran");
+        newcomer.account = Some(AccountId::generate());
+        let (newcomer_id, veteran_id) = (newcomer.id, veteran.id);
+
+        let room_id = manager.create_room("Casual".to_string()).unwrap();
+        manager.join_room(room_id, newcomer).unwrap();
+        manager.join_room(room_id, veteran).unwrap();
+        let room = manager.get_room_mut(room_id).unwrap();
+        room.start_game().unwrap();
+        let mut events = Vec::new();
+        while room.game_state().match_state.phase != MatchPhase::Playing {
+            events.extend(room.tick());
+        }
+
+        let objectives: Vec<_> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                GameLoopEvent::TutorialObjective { player_id, .. } => Some(player_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(objectives, vec![newcomer_id]);
+        assert!(!objectives.contains(&veteran_id));
+    }
+
+    #[test]
+    fn test_room_browser_filters_pages_and_rate_limits() {
+        let mut manager = LobbyManager::new(10);
+        manager.room_browser = RoomBrowserConfig { region: "eu".to_string(), page_size: 2, min_interval_secs: 1.0 };
+        let friend = create_player("Friend");
+        let friend_id = friend.id;
+        let a = manager.create_room("A".to_string()).unwrap();
+        manager.join_room(a, friend).unwrap();
+        let b = manager.create_room_of_kind("B".to_string(), RoomKind::SlowMode).unwrap();
+        let c = manager.create_room("C".to_string()).unwrap();
+        manager.get_room_mut(c).unwrap().max_humans = 1;
+        manager.join_room(c, create_player("Solo")).unwrap();
+
+        let start = Instant::now();
+        let mut browse = |connection: u64, filter: RoomFilter, page: u32, secs: u64| {
+            match manager.browse_rooms(connection, &filter, page, start + Duration::from_secs(secs)) {
+                Ok(ServerMessage::RoomList { rooms, total_pages, .. }) => {
+                    Ok((rooms.into_iter().map(|r| (r.room_id, r.friends)).collect::<Vec<_>>(), total_pages))
+                }
+                Ok(other) => panic!("unexpected {:?}", other),
+                Err(e) => Err(e),
+            }
+        };
+
+        assert_eq!(browse(1, RoomFilter::default(), 0, 0).unwrap(), (vec![(a, vec![]), (b, vec![])], 2));
+        assert!(matches!(browse(1, RoomFilter::default(), 1, 0), Err(ManagerError::RateLimited)));
+        assert_eq!(browse(2, RoomFilter::default(), 1, 0).unwrap(), (vec![(c, vec![])], 2));
+
+        let open_standard = RoomFilter { mode: Some("standard".to_string()), not_full: true, ..Default::default() };
+        assert_eq!(browse(1, open_standard, 0, 2).unwrap().0, [(a, vec![])]);
+        let friends = RoomFilter { friends: vec![friend_id, Uuid::new_v4()], ..Default::default() };
+        assert_eq!(browse(1, friends, 0, 4).unwrap(), (vec![(a, vec![friend_id])], 1));
+        let elsewhere = RoomFilter { region: Some("us".to_string()), ..Default::default() };
+        assert_eq!(browse(1, elsewhere, 0, 6).unwrap(), (vec![], 0));
+    }
+
+    #[test]
+    fn test_wormholes_hand_players_between_galaxy_arenas() {
+        let mut manager = LobbyManager::new(10);
+        // Mouths spanning the whole arena, so any player is inside one
+        let topology: GalaxyTopology = serde_json::from_str(
+            r#"{ "arenas": [{"name": "Sol"}, {"name": "Vega"}],
+                 "wormholes": [
+                     {"from": "Sol", "to": "Vega", "position": {"x": 0, "y": 0}, "radius": 1e9},
+                     {"from": "Vega", "to": "Sol", "position": {"x": 0, "y": 0}, "radius": 1e9}
+                 ] }"#,
+        )
+        .unwrap();
+        manager.create_galaxy(&topology, Duration::from_secs(60)).unwrap();
+        assert!(matches!(manager.create_galaxy(&topology, Duration::ZERO), Err(ManagerError::GalaxyExists)));
+        let [sol, vega] = manager.galaxy.as_ref().unwrap().arenas() else {
+            panic!("expected two arenas");
+        };
+        let (sol, vega) = (*sol, *vega);
+
+        // Galaxy arenas are never matchmade into
+        let matchmade = manager.find_or_create_room().unwrap();
+        assert!(!manager.is_galaxy_room(matchmade));
+
+        let player = create_player("Nova");
+        let id = player.id;
+        assert_eq!(manager.join_galaxy(player).unwrap(), sol);
+        assert_eq!(manager.get_room(sol).unwrap().state, RoomState::Playing);
+        let mass = manager.get_room(sol).unwrap().game_state().get_player(id).unwrap().mass;
+        match &manager.take_galaxy_updates()[..] {
+            [(to, ServerMessage::GalaxyMap(map))] => {
+                assert_eq!((*to, map.current, map.arenas.len(), map.wormholes.len()), (id, sol, 2, 2))
+            }
+            other => panic!("unexpected updates: {:?}", other),
+        }
+
+        manager.update_all();
+        assert_eq!(manager.get_player_room(id), Some(vega));
+        assert!(manager.get_room(sol).unwrap().game_state().get_player(id).is_none());
+        let arrived = manager.get_room(vega).unwrap().game_state().get_player(id).unwrap();
+        assert!(arrived.alive && (arrived.mass - mass).abs() < 1.0);
+        let updates = manager.take_galaxy_updates();
+        assert!(matches!(&updates[..], [(_, ServerMessage::GalaxyMap(map))] if map.current == vega));
+
+        // The way back waits out the cooldown, and the emptied arena stays open
+        manager.update_all();
+        assert_eq!(manager.get_player_room(id), Some(vega));
+        manager.leave_room(id).unwrap();
+        assert!(manager.get_room(sol).is_some() && manager.get_room(vega).is_some());
+    }
+
+    #[test]
+    fn test_spectate_room_is_brokered_without_player_slots() {
+        let mut manager = LobbyManager::new(10);
+        let room_id = manager.create_room("Watched".to_string()).unwrap();
+        let other = manager.create_room("Other".to_string()).unwrap();
+
+        for connection in 0..MAX_SPECTATORS as u64 {
+            manager.spectate_room(connection, room_id).unwrap();
+        }
+        // Spectating again is not another place
+        manager.spectate_room(0, room_id).unwrap();
+        assert_eq!(manager.spectator_count(room_id), MAX_SPECTATORS);
+        assert_eq!(manager.get_room(room_id).unwrap().player_count(), 0);
+        assert!(matches!(manager.spectate_room(99, room_id), Err(ManagerError::SpectatorsFull)));
+
+        // Moving to another room frees the place
+        manager.spectate_room(1, other).unwrap();
+        manager.spectate_room(99, room_id).unwrap();
+        assert_eq!(manager.stop_spectating(99), Some(room_id));
+
+        assert!(matches!(manager.spectate_room(99, Uuid::new_v4()), Err(ManagerError::RoomNotFound)));
+        manager.get_room_mut(other).unwrap().state = RoomState::Closing;
+        assert!(matches!(manager.spectate_room(99, other), Err(ManagerError::RoomNotFound)));
+        manager.remove_room(other);
+        assert_eq!(manager.stop_spectating(1), None);
+    }
+
+    #[test]
+    fn test_queue_estimates_and_bot_backfill() {
+        let mut manager = LobbyManager::new(10);
+        manager.queue_config = LobbyQueueConfig { backfill_after_secs: 30.0, ..Default::default() };
+        manager.queue_stats = QueueStats::new(&manager.queue_config);
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        let players: Vec<_> = ["First", "Second"].map(create_player).into();
+        let room_id = manager.find_or_create_room_for(&players[0]).unwrap();
+        for player in players.iter().cloned() {
+            manager.join_room(room_id, player).unwrap();
+        }
+
+        // Nothing was matched yet, so the backfill bounds the estimate
+        manager.update_queue(at(10));
+        let mut updates = manager.take_queue_updates();
+        updates.sort_by_key(|(id, _)| players.iter().position(|p| p.id == *id));
+        let updates: Vec<_> = updates
+            .into_iter()
+            .map(|(_, message)| match message {
+                ServerMessage::QueueUpdate { position, eta_secs } => (position, eta_secs),
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        assert_eq!(updates, [(1, 20), (2, 20)]);
+        manager.update_queue(at(12));
+        assert!(manager.take_queue_updates().is_empty());
+        assert_eq!(manager.queue_report(at(12)).modes[0].waiting, 2);
+
+        manager.update_queue(at(31));
+        assert_eq!(manager.get_room(room_id).unwrap().state, RoomState::Playing);
+        let report = manager.queue_report(at(31));
+        assert_eq!((report.matched, report.backfills, report.modes[0].waiting), (2, 1, 0));
+        assert_eq!(report.buckets.len(), 1);
+        assert!((report.buckets[0].average_secs - 31.0).abs() < 0.1);
+
+        // A later player in the same bucket expects the average wait
+        let late = create_player("Late");
+        let late_id = late.id;
+        let room_id = manager.find_or_create_room_for(&late).unwrap();
+        manager.join_room(room_id, late).unwrap();
+        manager.next_estimate = None;
+        manager.update_queue(Instant::now() + Duration::from_secs(10));
+        let updates = manager.take_queue_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0, late_id);
+        assert!(matches!(updates[0].1, ServerMessage::QueueUpdate { position: 1, eta_secs: 20 }));
+
+        manager.leave_room(late_id).unwrap();
+        assert_eq!(manager.queue_report(Instant::now()).modes[0].waiting, 0);
+    }
 }
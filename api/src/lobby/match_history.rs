@@ -0,0 +1,352 @@
+//! Match history
+//!
+//! Every match a lobby room finishes is kept as a `MatchSummary`: the room's
+//! mode, how long it ran, the podium, the awards and a few totals. The newest `capacity`
+//! summaries stay in memory for the `/matches` endpoints, so site frontends
+//! can list past matches without reading a database. With a configured path
+//! each summary is also appended to a JSON lines file and the newest ones are
+//! read back at startup.
+//!
+//! Match ids count up from 1, so a page of older matches is requested with
+//! the lowest id already shown as the `before` cursor.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config::MatchHistoryConfig;
+use crate::game::match_result::{Award, AwardKind, MatchResult, PlayerRanking};
+
+/// Players listed on a summary's podium
+pub const PODIUM_SIZE: usize = 3;
+
+/// One player on the podium
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodiumEntry {
+    pub rank: u32,
+    pub name: String,
+    pub kills: u32,
+    pub deaths: u32,
+    pub final_mass: f32,
+    pub survived: bool,
+    pub is_bot: bool,
+}
+
+impl From<&PlayerRanking> for PodiumEntry {
+    fn from(ranking: &PlayerRanking) -> Self {
+        Self {
+            rank: ranking.rank,
+            name: ranking.name.clone(),
+            kills: ranking.kills,
+            deaths: ranking.deaths,
+            final_mass: ranking.final_mass,
+            survived: ranking.survived,
+            is_bot: ranking.is_bot,
+        }
+    }
+}
+
+/// An end-of-match award on a summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwardEntry {
+    pub kind: AwardKind,
+    pub title: String,
+    pub name: String,
+    pub value: f32,
+}
+
+impl From<&Award> for AwardEntry {
+    fn from(award: &Award) -> Self {
+        Self { kind: award.kind, title: award.kind.title().to_string(), name: award.name.clone(), value: award.value }
+    }
+}
+
+/// Totals over every player in the match
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchStats {
+    pub players: u32,
+    pub humans: u32,
+    pub bots: u32,
+    pub total_kills: u32,
+    pub total_deaths: u32,
+    /// Deaths during the match by cause name (see `DeathCause::name`)
+    #[serde(default)]
+    pub deaths_by_cause: BTreeMap<String, u32>,
+}
+
+/// A finished match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchSummary {
+    pub id: u64,
+    /// Ruleset of the room it was played in (see `RoomKind::name`)
+    pub mode: String,
+    pub room_name: String,
+    /// Unix seconds when it ended
+    pub ended_at: u64,
+    pub duration_secs: f32,
+    pub winner_name: Option<String>,
+    pub podium: Vec<PodiumEntry>,
+    /// Empty for matches recorded before awards existed
+    #[serde(default)]
+    pub awards: Vec<AwardEntry>,
+    pub stats: MatchStats,
+}
+
+/// Recent match summaries, optionally backed by a JSON lines file
+#[derive(Debug)]
+pub struct MatchHistory {
+    /// Oldest first
+    matches: VecDeque<MatchSummary>,
+    capacity: usize,
+    next_id: u64,
+    path: Option<String>,
+}
+
+impl MatchHistory {
+    /// In-memory history keeping the newest `capacity` matches
+    pub fn new(capacity: usize) -> Self {
+        Self { matches: VecDeque::new(), capacity: capacity.max(1), next_id: 1, path: None }
+    }
+
+    /// History from the config, loading the file if one is set (a missing
+    /// file is an empty history). Failing to read it falls back to memory only.
+    pub fn from_config(config: &MatchHistoryConfig) -> Self {
+        let Some(path) = config.path.as_deref() else {
+            return Self::new(config.capacity);
+        };
+        match Self::load(path, config.capacity) {
+            Ok(history) => history,
+            Err(e) => {
+                warn!("Failed to load match history, keeping it in memory: {}", e);
+                Self::new(config.capacity)
+            }
+        }
+    }
+
+    /// Load the newest `capacity` summaries from a JSON lines file; later
+    /// matches are appended to the same path. Unreadable lines are skipped.
+    pub fn load(path: &str, capacity: usize) -> Result<Self, String> {
+        let mut history = Self::new(capacity);
+        if Path::new(path).exists() {
+            let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read match history: {}", e))?;
+            for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+                match serde_json::from_str::<MatchSummary>(line) {
+                    Ok(summary) => history.push(summary),
+                    Err(e) => warn!("Skipping unreadable match history line: {}", e),
+                }
+            }
+        } else {
+            debug!("No existing match history file at {}", path);
+        }
+        history.path = Some(path.to_string());
+        Ok(history)
+    }
+
+    /// Summarize a finished match, keep it and append it to the file (if any)
+    pub fn record(&mut self, mode: &str, room_name: &str, result: &MatchResult) -> &MatchSummary {
+        let ended_at = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let humans = result.rankings.iter().filter(|r| !r.is_bot).count() as u32;
+        let summary = MatchSummary {
+            id: self.next_id,
+            mode: mode.to_string(),
+            room_name: room_name.to_string(),
+            ended_at,
+            duration_secs: result.match_duration,
+            winner_name: result.winner_name.clone(),
+            podium: result.rankings.iter().take(PODIUM_SIZE).map(PodiumEntry::from).collect(),
+            awards: result.awards.iter().map(AwardEntry::from).collect(),
+            stats: MatchStats {
+                players: result.rankings.len() as u32,
+                humans,
+                bots: result.rankings.len() as u32 - humans,
+                total_kills: result.total_kills,
+                total_deaths: result.rankings.iter().map(|r| r.deaths).sum(),
+                deaths_by_cause: result
+                    .deaths_by_cause
+                    .iter()
+                    .map(|&(cause, count)| (cause.name().to_string(), count))
+                    .collect(),
+            },
+        };
+        if let Err(e) = self.append(&summary) {
+            warn!("Failed to save match history: {}", e);
+        }
+        self.push(summary);
+        self.matches.back().expect("just pushed")
+    }
+
+    /// Up to `limit` matches older than the `before` id, newest first
+    pub fn page(&self, limit: usize, before: Option<u64>) -> Vec<&MatchSummary> {
+        self.matches
+            .iter()
+            .rev()
+            .filter(|m| before.map_or(true, |before| m.id < before))
+            .take(limit)
+            .collect()
+    }
+
+    /// A kept match by id
+    pub fn get(&self, id: u64) -> Option<&MatchSummary> {
+        self.matches.iter().find(|m| m.id == id)
+    }
+
+    /// Drop matches that ended before `cutoff` (Unix seconds) from memory and
+    /// the file, returning how many were dropped (from the file, if there is
+    /// one). Unreadable file lines are dropped too.
+    pub fn purge_before(&mut self, cutoff: u64) -> Result<usize, String> {
+        let in_memory = self.matches.len();
+        self.matches.retain(|m| m.ended_at >= cutoff);
+        let purged = in_memory - self.matches.len();
+        let Some(path) = self.path.as_deref().filter(|path| Path::new(path).exists()) else {
+            return Ok(purged);
+        };
+        let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read match history: {}", e))?;
+        let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+        let kept: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|line| serde_json::from_str::<MatchSummary>(line).is_ok_and(|m| m.ended_at >= cutoff))
+            .collect();
+        if kept.len() < lines.len() {
+            let rewritten: String = kept.iter().map(|line| format!("{}\n", line)).collect();
+            fs::write(path, rewritten).map_err(|e| format!("Failed to rewrite match history file: {}", e))?;
+        }
+        Ok(lines.len() - kept.len())
+    }
+
+    fn push(&mut self, summary: MatchSummary) {
+        self.next_id = self.next_id.max(summary.id + 1);
+        self.matches.push_back(summary);
+        while self.matches.len() > self.capacity {
+            self.matches.pop_front();
+        }
+    }
+
+    fn append(&self, summary: &MatchSummary) -> Result<(), String> {
+        let Some(path) = self.path.as_deref().map(Path::new) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+        }
+        let line = serde_json::to_string(summary).map_err(|e| format!("Failed to serialize match: {}", e))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open match history file: {}", e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to write match history file: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::protocol::DeathCause;
+    use uuid::Uuid;
+
+    fn ranking(name: &str, rank: u32, kills: u32, is_bot: bool) -> PlayerRanking {
+        PlayerRanking {
+            player_id: Uuid::new_v4(),
+            name: name.to_string(),
+            rank,
+            kills,
+            deaths: 1,
+            final_mass: 100.0,
+            survived: rank == 1,
+            is_bot,
+        }
+    }
+
+    fn result() -> MatchResult {
+        MatchResult {
+            winner_id: None,
+            winner_name: Some("Nova".to_string()),
+            rankings: vec![
+                ranking("Nova", 1, 4, false),
+                ranking("Bot A", 2, 2, true),
+                ranking("Comet", 3, 1, false),
+                ranking("Bot B", 4, 0, true),
+            ],
+            match_duration: 182.5,
+            total_kills: 7,
+            awards: vec![],
+            deaths_by_cause: vec![(DeathCause::Crush, 3), (DeathCause::Disconnect, 1)],
+        }
+    }
+
+    #[test]
+    fn test_summary_and_paging() {
+        let mut history = MatchHistory::new(3);
+        let summary = history.record("standard", "Game 1", &result()).clone();
+        assert_eq!(summary.id, 1);
+        assert_eq!(summary.podium.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), ["Nova", "Bot A", "Comet"]);
+        let deaths_by_cause = BTreeMap::from([("crush".to_string(), 3), ("disconnect".to_string(), 1)]);
+        assert_eq!(
+            summary.stats,
+            MatchStats { players: 4, humans: 2, bots: 2, total_kills: 7, total_deaths: 4, deaths_by_cause }
+        );
+
+        for _ in 0..3 {
+            history.record("practice", "Practice 2", &result());
+        }
+        // The oldest match fell out of the history
+        assert!(history.get(1).is_none());
+        let ids = |page: Vec<&MatchSummary>| page.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids(history.page(10, None)), [4, 3, 2]);
+        assert_eq!(ids(history.page(1, None)), [4]);
+        assert_eq!(ids(history.page(10, Some(4))), [3, 2]);
+        assert_eq!(history.get(3).unwrap().mode, "practice");
+    }
+
+    #[test]
+    fn test_history_is_reloaded_from_file() {
+        let path = std::env::temp_dir().join(format!("orbit_matches_{}.jsonl", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let _ = fs::remove_file(&path);
+
+        let mut history = MatchHistory::load(path_str, 2).unwrap();
+        for _ in 0..3 {
+            history.record("standard", "Game 1", &result());
+        }
+        let mut reloaded = MatchHistory::load(path_str, 2).unwrap();
+        assert_eq!(reloaded.page(10, None), history.page(10, None));
+
+        // Ids keep counting after a restart
+        let id = reloaded.record("standard", "Game 1", &result()).id;
+        let _ = fs::remove_file(&path);
+        assert_eq!(id, 4);
+    }
+
+    #[test]
+    fn test_purge_drops_old_matches_from_memory_and_file() {
+        let path = std::env::temp_dir().join(format!("orbit_matches_purge_{}.jsonl", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let mut scratch = MatchHistory::new(10);
+        let lines: Vec<String> = [100, 2000]
+            .iter()
+            .map(|&ended_at| {
+                let mut summary = scratch.record("standard", "Game 1", &result()).clone();
+                summary.ended_at = ended_at;
+                serde_json::to_string(&summary).unwrap() + "\n"
+            })
+            .collect();
+        fs::write(&path, lines.concat()).unwrap();
+
+        let mut history = MatchHistory::load(path_str, 10).unwrap();
+        assert_eq!(history.purge_before(1000), Ok(1));
+        assert_eq!(history.purge_before(1000), Ok(0));
+        let reloaded = MatchHistory::load(path_str, 10).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(history.page(10, None).len(), 1);
+        assert_eq!(reloaded.page(10, None), history.page(10, None));
+        assert_eq!(reloaded.get(2).unwrap().ended_at, 2000);
+    }
+}
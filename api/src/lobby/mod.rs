@@ -7,3 +7,8 @@
 pub mod room;
 pub mod manager;
 pub mod player;
+pub mod profiles;
+pub mod match_history;
+pub mod queue_stats;
+pub mod smurf;
+pub mod galaxy;
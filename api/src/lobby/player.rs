@@ -1,8 +1,14 @@
 use uuid::Uuid;
 
 use crate::game::state::PlayerId;
+use crate::net::account::AccountId;
+use crate::net::protocol::TelemetryConsent;
+use crate::net::reports::MAX_KARMA;
 use crate::net::session::SessionToken;
 
+/// Matchmaking rating of players without one
+pub const DEFAULT_MMR: u32 = 1000;
+
 /// Player connection state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlayerConnectionState {
@@ -25,6 +31,18 @@ pub struct LobbyPlayer {
     pub is_ready: bool,
     pub is_spectator: bool,
     pub ping_ms: u32,
+    /// Opted into slow-mode rooms (only such players are placed in them)
+    pub prefers_slow_mode: bool,
+    /// Karma from player reports (see `net::reports`); low-karma players are matched together
+    pub karma: f32,
+    /// Matchmaking rating; queue waits are averaged per MMR bucket (see `lobby::queue_stats`)
+    pub mmr: u32,
+    /// Per-player telemetry the player allows; without analytics, match
+    /// history shows them anonymously
+    pub telemetry: TelemetryConsent,
+    /// Account the player proved at join (see `net::account`); presence is
+    /// only published for players with one
+    pub account: Option<AccountId>,
 }
 
 impl LobbyPlayer {
@@ -38,9 +56,19 @@ impl LobbyPlayer {
             is_ready: false,
             is_spectator: false,
             ping_ms: 0,
+            prefers_slow_mode: false,
+            karma: MAX_KARMA,
+            mmr: DEFAULT_MMR,
+            telemetry: TelemetryConsent::default(),
+            account: None,
         }
     }
 
+    /// Account profiles are kept under; players who joined without one are their own account
+    pub fn profile_account(&self) -> AccountId {
+        self.account.unwrap_or_else(|| AccountId::from(self.id))
+    }
+
     pub fn is_connected(&self) -> bool {
         self.connection_state == PlayerConnectionState::Connected
     }
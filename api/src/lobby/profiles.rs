@@ -0,0 +1,182 @@
+//! Persistent player profiles
+//!
+//! Remembers which players have finished the tutorial so it only runs for
+//! newcomers, and each account's age, streak and early stats for smurf
+//! detection (see `lobby::smurf`). Profiles are keyed by the server-issued
+//! account (see `net::account`), never by name, so taking someone's name
+//! doesn't take their profile and the file holds no player names. Without a
+//! configured path profiles live in memory and everyone is new again after a
+//! restart. Completions and accounts older than the retention window are
+//! purged, after which the player sees the tutorial again and counts as a new
+//! account.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::lobby::smurf::{AccountRecord, MatchSkill};
+use crate::net::account::AccountId;
+
+/// Profiles of players seen by this server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerProfiles {
+    /// Accounts that completed the tutorial, with when (Unix seconds)
+    #[serde(default)]
+    tutorial_completed_at: BTreeMap<String, u64>,
+    /// Completions from files written before completion times were kept
+    #[serde(default, skip_serializing)]
+    tutorial_completed: BTreeSet<String>,
+    /// Smurf detection records by account id
+    #[serde(default)]
+    accounts: BTreeMap<String, AccountRecord>,
+    #[serde(skip)]
+    path: Option<String>,
+}
+
+impl PlayerProfiles {
+    /// Load profiles from a JSON file (a missing file is an empty profile set);
+    /// later changes are saved back to the same path
+    pub fn load(path: &str) -> Result<Self, String> {
+        let mut profiles = if Path::new(path).exists() {
+            let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read profiles file: {}", e))?;
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse profiles file: {}", e))?
+        } else {
+            debug!("No existing profiles file at {}", path);
+            Self::default()
+        };
+        // Undated completions count from now
+        let now = unix_secs();
+        for key in std::mem::take(&mut profiles.tutorial_completed) {
+            profiles.tutorial_completed_at.entry(key).or_insert(now);
+        }
+        profiles.path = Some(path.to_string());
+        Ok(profiles)
+    }
+
+    /// Whether an account has never completed the tutorial
+    pub fn is_new(&self, account: AccountId) -> bool {
+        !self.tutorial_completed_at.contains_key(&account.to_string())
+    }
+
+    /// Remember that an account completed the tutorial, saving if backed by a file
+    pub fn record_completed(&mut self, account: AccountId) {
+        if let Entry::Vacant(entry) = self.tutorial_completed_at.entry(account.to_string()) {
+            entry.insert(unix_secs());
+            if let Err(e) = self.save() {
+                warn!("Failed to save player profiles: {}", e);
+            }
+        }
+    }
+
+    /// An account's smurf detection record, if it has finished a match
+    pub fn account(&self, account: AccountId) -> Option<&AccountRecord> {
+        self.accounts.get(&account.to_string())
+    }
+
+    /// Add a finished match to an account's record, saving if backed by a file
+    pub fn record_match(&mut self, account: AccountId, won: bool, skill: &MatchSkill, early_matches: u32) -> &AccountRecord {
+        let key = account.to_string();
+        self.accounts.entry(key.clone()).or_default().record_match(won, skill, early_matches, unix_secs());
+        if let Err(e) = self.save() {
+            warn!("Failed to save player profiles: {}", e);
+        }
+        &self.accounts[&key]
+    }
+
+    /// Forget completions and accounts last seen before `cutoff` (Unix seconds), returning how many
+    pub fn purge_before(&mut self, cutoff: u64) -> Result<usize, String> {
+        let before = self.tutorial_completed_at.len() + self.accounts.len();
+        self.tutorial_completed_at.retain(|_, completed_at| *completed_at >= cutoff);
+        self.accounts.retain(|_, account| account.last_seen >= cutoff);
+        let purged = before - self.tutorial_completed_at.len() - self.accounts.len();
+        if purged > 0 {
+            self.save()?;
+        }
+        Ok(purged)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = self.path.as_deref().map(Path::new) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+        }
+        let contents =
+            serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write profiles file: {}", e))
+    }
+}
+
+pub(crate) fn unix_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_is_persisted_by_account() {
+        let path = std::env::temp_dir().join(format!("orbit_profiles_{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let _ = fs::remove_file(&path);
+
+        let (nova, comet) = (AccountId::generate(), AccountId::generate());
+        let mut profiles = PlayerProfiles::load(path_str).unwrap();
+        assert!(profiles.is_new(nova));
+        profiles.record_completed(nova);
+        assert!(!profiles.is_new(nova));
+
+        let reloaded = PlayerProfiles::load(path_str).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(!reloaded.is_new(nova));
+        assert!(reloaded.is_new(comet));
+    }
+
+    #[test]
+    fn test_accounts_are_persisted_and_purged() {
+        let path = std::env::temp_dir().join(format!("orbit_profiles_accounts_{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let _ = fs::remove_file(&path);
+
+        let (nova, impostor) = (AccountId::generate(), AccountId::generate());
+        let mut profiles = PlayerProfiles::load(path_str).unwrap();
+        assert_eq!(profiles.account(nova), None);
+        profiles.record_match(nova, true, &MatchSkill::default(), 10);
+        let record = profiles.record_match(nova, true, &MatchSkill::default(), 10).clone();
+        assert_eq!((record.matches, record.win_streak), (2, 2));
+        assert_eq!(profiles.account(impostor), None);
+
+        let mut reloaded = PlayerProfiles::load(path_str).unwrap();
+        assert_eq!(reloaded.account(nova), Some(&record));
+        assert_eq!(reloaded.purge_before(record.last_seen + 1), Ok(1));
+        let _ = fs::remove_file(&path);
+        assert_eq!(reloaded.account(nova), None);
+    }
+
+    #[test]
+    fn test_undated_completions_are_kept_until_purged() {
+        let path = std::env::temp_dir().join(format!("orbit_profiles_purge_{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let nova = AccountId::generate();
+        let old_format = serde_json::json!({ "tutorial_completed": [nova.to_string()] });
+        fs::write(&path, old_format.to_string()).unwrap();
+
+        let mut profiles = PlayerProfiles::load(path_str).unwrap();
+        assert!(!profiles.is_new(nova));
+        assert_eq!(profiles.purge_before(unix_secs() - 60), Ok(0));
+        assert_eq!(profiles.purge_before(unix_secs() + 60), Ok(1));
+
+        let reloaded = PlayerProfiles::load(path_str).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(reloaded.is_new(nova));
+    }
+}
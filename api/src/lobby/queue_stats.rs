@@ -0,0 +1,147 @@
+//! Lobby queue analytics
+//!
+//! A player is queued from joining a waiting matchmade room until the room
+//! starts. Every finished wait is kept per room mode and MMR bucket (the
+//! newest `window` of them), and their average is what a player of that mode
+//! and bucket can expect to wait; buckets without waits fall back to the
+//! mode's average over every bucket. The lobby sends the estimates to waiting
+//! players every few seconds, starts rooms with bots once someone waited past
+//! the backfill threshold and reports everything on `/metrics`.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::config::LobbyQueueConfig;
+use crate::lobby::room::RoomKind;
+
+/// Average wait of one mode and MMR bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct BucketWait {
+    pub mode: &'static str,
+    /// Lowest MMR in the bucket
+    pub mmr_floor: u32,
+    pub average_secs: f32,
+    pub samples: usize,
+}
+
+/// Players waiting in one mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeQueue {
+    pub mode: &'static str,
+    pub waiting: usize,
+    pub longest_wait_secs: f32,
+}
+
+/// Queue state for the Prometheus export
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueueReport {
+    pub modes: Vec<ModeQueue>,
+    pub buckets: Vec<BucketWait>,
+    /// Players whose room started since the server came up
+    pub matched: u64,
+    /// Rooms started with bots because someone waited too long
+    pub backfills: u64,
+}
+
+/// Rolling waits per mode and MMR bucket
+#[derive(Debug)]
+pub struct QueueStats {
+    bucket_size: u32,
+    window: usize,
+    /// Newest waits in seconds, oldest first
+    waits: HashMap<(RoomKind, u32), VecDeque<f32>>,
+    pub matched: u64,
+    pub backfills: u64,
+}
+
+impl QueueStats {
+    pub fn new(config: &LobbyQueueConfig) -> Self {
+        Self {
+            bucket_size: config.mmr_bucket_size.max(1),
+            window: config.window.max(1),
+            waits: HashMap::new(),
+            matched: 0,
+            backfills: 0,
+        }
+    }
+
+    /// Bucket an MMR falls in
+    pub fn bucket(&self, mmr: u32) -> u32 {
+        mmr / self.bucket_size
+    }
+
+    /// Keep a finished wait
+    pub fn record(&mut self, kind: RoomKind, mmr: u32, wait_secs: f32) {
+        let waits = self.waits.entry((kind, self.bucket(mmr))).or_default();
+        waits.push_back(wait_secs);
+        while waits.len() > self.window {
+            waits.pop_front();
+        }
+        self.matched += 1;
+    }
+
+    /// Expected wait for a player of this mode and MMR, None before any
+    /// player of the mode was matched
+    pub fn estimate(&self, kind: RoomKind, mmr: u32) -> Option<f32> {
+        if let Some(waits) = self.waits.get(&(kind, self.bucket(mmr))) {
+            return Some(average(waits.iter()));
+        }
+        let mode_waits: Vec<&f32> =
+            self.waits.iter().filter(|((k, _), _)| *k == kind).flat_map(|(_, waits)| waits.iter()).collect();
+        (!mode_waits.is_empty()).then(|| average(mode_waits.into_iter()))
+    }
+
+    /// Average wait of every bucket with waits, by mode then MMR
+    pub fn bucket_waits(&self) -> Vec<BucketWait> {
+        let mut buckets: Vec<BucketWait> = self
+            .waits
+            .iter()
+            .map(|(&(kind, bucket), waits)| BucketWait {
+                mode: kind.name(),
+                mmr_floor: bucket * self.bucket_size,
+                average_secs: average(waits.iter()),
+                samples: waits.len(),
+            })
+            .collect();
+        buckets.sort_by(|a, b| a.mode.cmp(b.mode).then(a.mmr_floor.cmp(&b.mmr_floor)));
+        buckets
+    }
+}
+
+fn average<'a>(waits: impl Iterator<Item = &'a f32>) -> f32 {
+    let (sum, count) = waits.fold((0.0, 0), |(sum, count), wait| (sum + wait, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_average_per_bucket_with_mode_fallback() {
+        let config = LobbyQueueConfig { mmr_bucket_size: 500, window: 2, ..Default::default() };
+        let mut stats = QueueStats::new(&config);
+        assert!(stats.estimate(RoomKind::Standard, 1000).is_none());
+
+        for wait in [30.0, 10.0, 20.0] {
+            stats.record(RoomKind::Standard, 1200, wait);
+        }
+        stats.record(RoomKind::Standard, 2100, 40.0);
+        stats.record(RoomKind::SlowMode, 1000, 90.0);
+
+        // Only the newest two waits of the bucket count
+        assert_eq!(stats.estimate(RoomKind::Standard, 1499), Some(15.0));
+        assert_eq!(stats.estimate(RoomKind::Standard, 2000), Some(40.0));
+        // No waits at this MMR yet: the mode's average
+        assert_eq!(stats.estimate(RoomKind::Standard, 0), Some(70.0 / 3.0));
+        assert!(stats.estimate(RoomKind::Practice, 1000).is_none());
+
+        let buckets = stats.bucket_waits();
+        let keys: Vec<_> = buckets.iter().map(|b| (b.mode, b.mmr_floor, b.samples)).collect();
+        assert_eq!(keys, [("slow_mode", 1000, 1), ("standard", 1000, 2), ("standard", 2000, 1)]);
+        assert_eq!(stats.matched, 5);
+    }
+}
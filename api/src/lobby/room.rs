@@ -1,11 +1,97 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
+use parking_lot::Mutex;
 use uuid::Uuid;
 
+use crate::config::{PracticeConfig, TutorialConfig};
+use crate::game::arena_layout::ArenaLayout;
+use crate::game::arena_seed::ArenaSeed;
 use crate::game::game_loop::{GameLoop, GameLoopConfig, GameLoopEvent};
+use crate::game::ghost::{GhostPlayback, GhostTrack};
+use crate::game::scenario::{PracticeCommand, PracticeScenario, MAX_GRAVITY_STRENGTH};
 use crate::game::state::{Player, PlayerId};
+use crate::game::systems::custom::SystemPhase;
+use crate::game::systems::physics::Integrator;
+use crate::game::tutorial::TutorialSystem;
+use crate::util::vec2::Vec2;
 use crate::lobby::player::LobbyPlayer;
-use crate::net::protocol::{GameSnapshot, PlayerInput};
+use crate::lobby::smurf::MatchSkill;
+use crate::net::account::AccountId;
+use crate::net::protocol::{GameSnapshot, PlayerInput, PlayerSnapshot};
+
+/// Custom system shared between a room and its game loop
+type Shared<S> = Arc<Mutex<S>>;
+
+/// Simulation speed of slow-mode rooms (0.75x velocities, forces and timers)
+pub const SLOW_MODE_SIM_SPEED: f32 = 0.75;
+
+/// Most players (bots included) any room is created for
+pub const MAX_ROOM_SIZE: usize = 50;
+
+/// Ruleset preset a room runs with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RoomKind {
+    #[default]
+    Standard,
+    /// Reduced simulation speed for accessibility; consenting players only
+    SlowMode,
+    /// Drills against stationary targets: no bots, endless match, respawns
+    /// and DPS/accuracy readouts (see `game::scenario`)
+    Practice,
+}
+
+impl RoomKind {
+    /// Game loop configuration for this preset
+    pub fn game_loop_config(self) -> GameLoopConfig {
+        match self {
+            RoomKind::Standard => GameLoopConfig::default(),
+            // Timestep-scaled drag, so the shorter ticks don't bleed extra speed
+            RoomKind::SlowMode => GameLoopConfig {
+                sim_speed: SLOW_MODE_SIM_SPEED,
+                integrator: Integrator::SemiImplicitEuler,
+                ..GameLoopConfig::default()
+            },
+            RoomKind::Practice => GameLoopConfig {
+                endless: true,
+                ..GameLoopConfig::default()
+            },
+        }
+    }
+
+    /// Name used for the mode in match history
+    pub fn name(self) -> &'static str {
+        match self {
+            RoomKind::Standard => "standard",
+            RoomKind::SlowMode => "slow_mode",
+            RoomKind::Practice => "practice",
+        }
+    }
+
+    /// Kind with this mode name (see `name`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        [RoomKind::Standard, RoomKind::SlowMode, RoomKind::Practice]
+            .into_iter()
+            .find(|kind| kind.name() == name)
+    }
+
+    /// Whether a player may be placed in a room of this kind
+    pub fn admits(self, player: &LobbyPlayer) -> bool {
+        match self {
+            RoomKind::Standard | RoomKind::Practice => true,
+            RoomKind::SlowMode => player.prefers_slow_mode,
+        }
+    }
+
+    /// Room kind matchmaking should place a player in
+    pub fn preferred_by(player: &LobbyPlayer) -> Self {
+        if player.prefers_slow_mode {
+            RoomKind::SlowMode
+        } else {
+            RoomKind::Standard
+        }
+    }
+}
 
 /// Room state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,30 +110,96 @@ pub enum RoomState {
 pub struct GameRoom {
     pub id: Uuid,
     pub name: String,
+    pub kind: RoomKind,
     pub state: RoomState,
     pub max_players: usize,
     pub max_humans: usize,
+    /// Ranked rooms never run the tutorial
+    pub ranked: bool,
+    /// Matchmaking places only low-karma players here
+    pub low_karma: bool,
     pub created_at: Instant,
     players: HashMap<PlayerId, LobbyPlayer>,
     game_loop: GameLoop,
     fill_with_bots: bool,
+    /// Scenario driver of practice rooms
+    practice: Option<Shared<PracticeScenario>>,
+    /// Recorded runs replayed in practice rooms
+    ghosts: GhostPlayback,
+    /// Guided objectives for new players
+    tutorial: Shared<TutorialSystem>,
+    /// Shots, hits and aim of each player this match, for smurf detection
+    skill: HashMap<PlayerId, MatchSkill>,
 }
 
 impl GameRoom {
     pub fn new(name: String, max_players: usize, max_humans: usize) -> Self {
+        Self::with_kind(name, max_players, max_humans, RoomKind::Standard)
+    }
+
+    /// Create a room running the given ruleset preset
+    pub fn with_kind(name: String, max_players: usize, max_humans: usize, kind: RoomKind) -> Self {
+        let (game_loop, practice, tutorial) = Self::build_game_loop(kind, None, max_players);
         Self {
             id: Uuid::new_v4(),
             name,
+            kind,
             state: RoomState::Waiting,
             max_players,
             max_humans,
+            ranked: false,
+            low_karma: false,
             created_at: Instant::now(),
             players: HashMap::new(),
-            game_loop: GameLoop::new(GameLoopConfig::default()),
-            fill_with_bots: true,
+            game_loop,
+            fill_with_bots: kind != RoomKind::Practice,
+            practice,
+            ghosts: GhostPlayback::default(),
+            tutorial,
+            skill: HashMap::new(),
         }
     }
 
+    /// Pin every match in this room to one arena layout (builder style)
+    pub fn with_arena_seed(mut self, seed: ArenaSeed) -> Self {
+        (self.game_loop, self.practice, self.tutorial) = Self::build_game_loop(self.kind, Some(seed), self.max_players);
+        self
+    }
+
+    /// Mark the room as ranked (builder style)
+    pub fn with_ranked(mut self, ranked: bool) -> Self {
+        self.ranked = ranked;
+        self
+    }
+
+    /// Never end the match, so players can come and go mid-match as in
+    /// galaxy arenas (builder style)
+    pub fn with_endless(mut self) -> Self {
+        self.game_loop.set_endless(true);
+        self
+    }
+
+    /// Game loop for a room of `kind`, with the tutorial attached and the
+    /// scenario driver in practice rooms. Rooms use the operator's arena
+    /// layout if it holds `max_players`, unless pinned to a seed.
+    fn build_game_loop(
+        kind: RoomKind,
+        arena_seed: Option<ArenaSeed>,
+        max_players: usize,
+    ) -> (GameLoop, Option<Shared<PracticeScenario>>, Shared<TutorialSystem>) {
+        let mut config = GameLoopConfig { arena_seed, ..kind.game_loop_config() };
+        if arena_seed.is_none() {
+            config.arena_layout = ArenaLayout::from_env(max_players, &config.arena_scaling_config);
+        }
+        let practice = (kind == RoomKind::Practice).then(|| {
+            let practice_config = PracticeConfig::from_env();
+            config.gravity_config.strength = practice_config.gravity_strength;
+            config.register_system(SystemPhase::Late, PracticeScenario::new(practice_config))
+        });
+        let tutorial = config.register_system(SystemPhase::Late, TutorialSystem::new(TutorialConfig::from_env()));
+        (GameLoop::new(config), practice, tutorial)
+    }
+
     /// Get room ID
     pub fn id(&self) -> Uuid {
         self.id
@@ -91,6 +243,10 @@ impl GameRoom {
             return Err(RoomError::GameInProgress);
         }
 
+        if !self.kind.admits(&lobby_player) {
+            return Err(RoomError::SlowModeNotChosen);
+        }
+
         let player_id = lobby_player.id;
 
         // Create game player
@@ -107,12 +263,66 @@ impl GameRoom {
         if let Some(mut player) = self.players.remove(&player_id) {
             player.leave();
             self.game_loop.remove_player(player_id);
+            self.tutorial.lock().withdraw(player_id);
             Some(player)
         } else {
             None
         }
     }
 
+    /// Whether a player could be brought in mid-match (see `enter` and `arrive`)
+    pub fn admits_traveler(&self, lobby_player: &LobbyPlayer) -> Result<(), RoomError> {
+        if self.is_full() {
+            return Err(RoomError::RoomFull);
+        }
+        if matches!(self.state, RoomState::Ended | RoomState::Closing) {
+            return Err(RoomError::MatchOver);
+        }
+        if !self.kind.admits(lobby_player) {
+            return Err(RoomError::SlowModeNotChosen);
+        }
+        Ok(())
+    }
+
+    /// Bring a new player into the room mid-match, spawning as usual.
+    /// A waiting room starts its match.
+    pub fn enter(&mut self, lobby_player: LobbyPlayer) -> Result<(), RoomError> {
+        self.admits_traveler(&lobby_player)?;
+        let game_player = Player::new(lobby_player.id, lobby_player.name.clone(), false, self.players.len() as u8);
+        self.game_loop.add_player(game_player);
+        self.seat_traveler(lobby_player)
+    }
+
+    /// Take a player out of the room to hand them to another one, with their
+    /// game state. They don't die or leave; see `arrive`.
+    pub fn depart(&mut self, player_id: PlayerId) -> Option<(LobbyPlayer, Player)> {
+        if !self.players.contains_key(&player_id) {
+            return None;
+        }
+        let player = self.game_loop.hand_off_player(player_id)?;
+        self.tutorial.lock().withdraw(player_id);
+        self.skill.remove(&player_id);
+        let lobby_player = self.players.remove(&player_id)?;
+        Some((lobby_player, player))
+    }
+
+    /// Bring in a player who departed another room, keeping their mass and
+    /// velocity. They appear at `position` (None = a usual spawn point).
+    /// A waiting room starts its match.
+    pub fn arrive(&mut self, lobby_player: LobbyPlayer, player: Player, position: Option<Vec2>) -> Result<(), RoomError> {
+        self.admits_traveler(&lobby_player)?;
+        self.game_loop.receive_player(player, position);
+        self.seat_traveler(lobby_player)
+    }
+
+    fn seat_traveler(&mut self, lobby_player: LobbyPlayer) -> Result<(), RoomError> {
+        self.players.insert(lobby_player.id, lobby_player);
+        if self.state == RoomState::Waiting {
+            self.start_game()?;
+        }
+        Ok(())
+    }
+
     /// Get a player by ID
     pub fn get_player(&self, player_id: PlayerId) -> Option<&LobbyPlayer> {
         self.players.get(&player_id)
@@ -125,6 +335,9 @@ impl GameRoom {
 
     /// Process player input
     pub fn process_input(&mut self, player_id: PlayerId, input: PlayerInput) {
+        if self.state == RoomState::Playing && self.players.contains_key(&player_id) {
+            self.skill.entry(player_id).or_default().record_input(&input);
+        }
         self.game_loop.queue_input(player_id, input);
     }
 
@@ -143,10 +356,70 @@ impl GameRoom {
             self.game_loop.fill_with_bots(self.max_players);
         }
 
+        // Newcomers face gentle bots
+        if self.tutorial.lock().has_players() {
+            self.game_loop.soften_bots();
+        }
+
+        // Practice starts with a ring of targets around the first player
+        if let Some(practice) = &self.practice {
+            let mut practice = practice.lock();
+            let center = self.game_loop.state().players.values().next().map_or(Vec2::ZERO, |p| p.position);
+            let count = practice.initial_targets();
+            practice.spawn_targets(self.game_loop.state_mut(), center, count);
+        }
+
+        self.skill.clear();
         self.state = RoomState::Playing;
         Ok(())
     }
 
+    /// Run a practice command for a player in this room
+    pub fn practice_command(&mut self, player_id: PlayerId, command: PracticeCommand) -> Result<(), RoomError> {
+        let practice = self.practice.as_ref().ok_or(RoomError::NotPracticeRoom)?;
+        if !self.players.contains_key(&player_id) {
+            return Err(RoomError::PlayerNotFound);
+        }
+
+        match command {
+            PracticeCommand::SpawnTargets { count } => {
+                let center = self.game_loop.state().get_player(player_id).map_or(Vec2::ZERO, |p| p.position);
+                practice.lock().spawn_targets(self.game_loop.state_mut(), center, count);
+            }
+            PracticeCommand::ClearTargets => practice.lock().clear_targets(self.game_loop.state_mut()),
+            PracticeCommand::SetGravity(strength) => {
+                if !(0.0..=MAX_GRAVITY_STRENGTH).contains(&strength) {
+                    return Err(RoomError::InvalidGravityStrength);
+                }
+                self.game_loop.set_gravity_strength(strength);
+            }
+            PracticeCommand::RestartGhosts => self.ghosts.restart(self.game_state().match_state.match_time),
+            PracticeCommand::ClearGhosts => self.ghosts.clear(),
+        }
+        Ok(())
+    }
+
+    /// Walk a player through the tutorial objectives (not in ranked rooms)
+    pub fn start_tutorial(&mut self, player_id: PlayerId) -> Result<(), RoomError> {
+        if self.ranked {
+            return Err(RoomError::RankedRoom);
+        }
+        if !self.players.contains_key(&player_id) {
+            return Err(RoomError::PlayerNotFound);
+        }
+        self.tutorial.lock().enroll(player_id);
+        Ok(())
+    }
+
+    /// Replay a recorded run as a ghost in this practice room, returning its snapshot id
+    pub fn add_ghost(&mut self, track: GhostTrack) -> Result<PlayerId, RoomError> {
+        if self.practice.is_none() {
+            return Err(RoomError::NotPracticeRoom);
+        }
+        let match_time = self.game_state().match_state.match_time;
+        self.ghosts.add(track, match_time).ok_or(RoomError::GhostUnavailable)
+    }
+
     /// Update game state (called each frame/tick)
     pub fn update(&mut self) -> Vec<GameLoopEvent> {
         if self.state != RoomState::Playing {
@@ -155,16 +428,27 @@ impl GameRoom {
 
         let events = self.game_loop.update();
 
-        // Check for game end
+        // Check for game end, and count hits
         for event in &events {
-            if matches!(event, GameLoopEvent::MatchEnded { .. }) {
-                self.state = RoomState::Ended;
+            match event {
+                GameLoopEvent::MatchEnded { .. } => self.state = RoomState::Ended,
+                GameLoopEvent::PlayerHit { shooter_id, .. } => {
+                    if let Some(skill) = self.skill.get_mut(shooter_id) {
+                        skill.record_hit();
+                    }
+                }
+                _ => {}
             }
         }
 
         events
     }
 
+    /// Shots, hits and aim of each player who sent input this match, leaving none behind
+    pub fn take_match_skill(&mut self) -> HashMap<PlayerId, MatchSkill> {
+        std::mem::take(&mut self.skill)
+    }
+
     /// Run a single tick (for testing or manual control)
     pub fn tick(&mut self) -> Vec<GameLoopEvent> {
         self.game_loop.tick()
@@ -172,13 +456,23 @@ impl GameRoom {
 
     /// Get current game snapshot
     pub fn get_snapshot(&self) -> GameSnapshot {
-        GameSnapshot::from_game_state(self.game_loop.state())
+        let mut snapshot = GameSnapshot::from_game_state(self.game_loop.state());
+        snapshot.apply_charges(self.game_loop.charges());
+        let match_time = self.game_state().match_state.match_time;
+        snapshot
+            .players
+            .extend(self.ghosts.frames(match_time).map(|(ghost, frame)| PlayerSnapshot::from_ghost(ghost, frame)));
+        snapshot
     }
 
     /// Reset the room for a new game
     pub fn reset(&mut self) {
         self.game_loop.reset();
         self.state = RoomState::Waiting;
+        if let Some(practice) = &self.practice {
+            practice.lock().reset();
+        }
+        self.ghosts.restart(0.0);
 
         // Re-add existing players to new game
         for player in self.players.values() {
@@ -195,6 +489,11 @@ impl GameRoom {
         self.players.keys().copied().collect()
     }
 
+    /// Player who joined with an account
+    pub fn player_with_account(&self, account: AccountId) -> Option<PlayerId> {
+        self.players.values().find(|p| p.account == Some(account)).map(|p| p.id)
+    }
+
     /// Get connected player IDs
     pub fn connected_player_ids(&self) -> Vec<PlayerId> {
         self.players
@@ -226,6 +525,18 @@ pub enum RoomError {
     NotEnoughPlayers,
     #[error("Player not found")]
     PlayerNotFound,
+    #[error("Room is slow-mode and player has not opted in")]
+    SlowModeNotChosen,
+    #[error("Not a practice room")]
+    NotPracticeRoom,
+    #[error("Gravity strength must be 0-{MAX_GRAVITY_STRENGTH}")]
+    InvalidGravityStrength,
+    #[error("Room already replays the most ghosts, or the run is empty")]
+    GhostUnavailable,
+    #[error("Ranked rooms don't run the tutorial")]
+    RankedRoom,
+    #[error("Match is over")]
+    MatchOver,
 }
 
 #[cfg(test)]
@@ -330,4 +641,161 @@ mod tests {
         // Should have filled with bots
         assert_eq!(room.game_state().players.len(), 5);
     }
+
+    #[test]
+    fn test_slow_mode_room_admits_only_opted_in_players() {
+        let mut room = GameRoom::with_kind("Slow".to_string(), 10, 10, RoomKind::SlowMode);
+
+        let result = room.add_player(create_lobby_player("P1"));
+        assert!(matches!(result, Err(RoomError::SlowModeNotChosen)));
+
+        let mut player = create_lobby_player("P2");
+        player.prefers_slow_mode = true;
+        room.add_player(player).unwrap();
+        assert_eq!(room.player_count(), 1);
+    }
+
+    #[test]
+    fn test_slow_mode_room_slows_simulation() {
+        let distance_after_tick = |kind: RoomKind| {
+            let mut room = GameRoom::with_kind("Room".to_string(), 10, 10, kind);
+            room.fill_with_bots = false;
+            let mut player = create_lobby_player("P1");
+            player.prefers_slow_mode = true;
+            let id = player.id;
+            room.add_player(player).unwrap();
+            room.game_loop.state_mut().match_state.phase = crate::game::state::MatchPhase::Playing;
+
+            let before = room.game_state().get_player(id).unwrap().position;
+            room.tick();
+            (room.game_state().get_player(id).unwrap().position - before).length()
+        };
+
+        let normal = distance_after_tick(RoomKind::Standard);
+        let slow = distance_after_tick(RoomKind::SlowMode);
+        assert!(normal > 0.0);
+        assert!((slow / normal - SLOW_MODE_SIM_SPEED).abs() < 0.05, "ratio {}", slow / normal);
+    }
+
+    #[test]
+    fn test_practice_room_targets_and_commands() {
+        let mut room = GameRoom::with_kind("Practice".to_string(), 10, 10, RoomKind::Practice);
+        let player = create_lobby_player("P1");
+        let id = player.id;
+        room.add_player(player).unwrap();
+        room.start_game().unwrap();
+
+        // No bots; the initial ring of targets is enough to start the match
+        let initial = PracticeConfig::default().initial_targets as usize;
+        assert!(room.game_state().players.values().all(|p| !p.is_bot));
+        assert_eq!(room.player_count(), 1);
+        assert_eq!(room.game_state().players.len(), 1 + initial);
+
+        room.practice_command(id, PracticeCommand::SpawnTargets { count: 2 }).unwrap();
+        assert_eq!(room.game_state().players.len(), 3 + initial);
+        room.practice_command(id, PracticeCommand::ClearTargets).unwrap();
+        assert_eq!(room.game_state().players.len(), 1);
+
+        room.practice_command(id, PracticeCommand::SetGravity(0.5)).unwrap();
+        assert!(matches!(
+            room.practice_command(id, PracticeCommand::SetGravity(10.0)),
+            Err(RoomError::InvalidGravityStrength)
+        ));
+
+        // Alone in the arena, yet the match keeps going
+        room.game_loop.state_mut().match_state.phase = crate::game::state::MatchPhase::Playing;
+        let events = room.tick();
+        assert!(!events.iter().any(|e| matches!(e, GameLoopEvent::MatchEnded { .. })));
+    }
+
+    #[test]
+    fn test_practice_room_replays_ghosts() {
+        use crate::game::ghost::GhostFrame;
+
+        let mut room = GameRoom::with_kind("Practice".to_string(), 10, 10, RoomKind::Practice);
+        let player = create_lobby_player("P1");
+        let id = player.id;
+        room.add_player(player).unwrap();
+        room.start_game().unwrap();
+
+        let frame = |at: f32, x: f32| GhostFrame {
+            at,
+            position: Vec2::new(x, 0.0),
+            velocity: Vec2::ZERO,
+            mass: 100.0,
+            alive: true,
+        };
+        let track = GhostTrack { name: "Best run".to_string(), frames: vec![frame(0.0, 0.0), frame(10.0, 1000.0)] };
+        let ghost_id = room.add_ghost(track.clone()).unwrap();
+
+        // Flagged in snapshots, but never simulated
+        let snapshot = room.get_snapshot();
+        let ghost = snapshot.players.iter().find(|p| p.id == ghost_id).unwrap();
+        assert!(ghost.is_ghost() && ghost.alive());
+        assert!(snapshot.players.iter().filter(|p| p.id != ghost_id).all(|p| !p.is_ghost()));
+        assert!(room.game_state().get_player(ghost_id).is_none());
+
+        room.practice_command(id, PracticeCommand::ClearGhosts).unwrap();
+        assert!(room.get_snapshot().players.iter().all(|p| !p.is_ghost()));
+        assert!(matches!(GameRoom::new("Room".to_string(), 10, 10).add_ghost(track), Err(RoomError::NotPracticeRoom)));
+    }
+
+    #[test]
+    fn test_practice_commands_rejected_elsewhere() {
+        let mut room = GameRoom::new("Test Room".to_string(), 10, 10);
+        let player = create_lobby_player("P1");
+        let id = player.id;
+        room.add_player(player).unwrap();
+        assert!(matches!(
+            room.practice_command(id, PracticeCommand::ClearTargets),
+            Err(RoomError::NotPracticeRoom)
+        ));
+    }
+
+    #[test]
+    fn test_tutorial_runs_only_in_unranked_rooms() {
+        let player = create_lobby_player("New");
+        let id = player.id;
+        let mut ranked = GameRoom::new("Ranked".to_string(), 10, 10).with_ranked(true);
+        ranked.add_player(player.clone()).unwrap();
+        assert!(matches!(ranked.start_tutorial(id), Err(RoomError::RankedRoom)));
+
+        let mut room = GameRoom::new("Casual".to_string(), 10, 10);
+        room.add_player(player).unwrap();
+        room.start_tutorial(id).unwrap();
+        room.start_game().unwrap();
+        room.game_loop.state_mut().match_state.phase = crate::game::state::MatchPhase::Playing;
+        let events = room.tick();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GameLoopEvent::TutorialObjective { player_id, .. } if *player_id == id)));
+    }
+
+    #[test]
+    fn test_travelers_keep_their_mass_between_rooms() {
+        let mut from = GameRoom::new("Sol".to_string(), 10, 10).with_endless();
+        let mut to = GameRoom::new("Vega".to_string(), 10, 10).with_endless();
+        let player = create_lobby_player("Nova");
+        let id = player.id;
+        from.enter(player).unwrap();
+        assert_eq!(from.state, RoomState::Playing);
+        let traveler = from.game_loop.state_mut().get_player_mut(id).unwrap();
+        traveler.mass = 240.0;
+        traveler.velocity = Vec2::new(30.0, 0.0);
+
+        let (lobby_player, player) = from.depart(id).unwrap();
+        assert!(from.get_player(id).is_none() && from.game_state().get_player(id).is_none());
+        assert!(lobby_player.is_connected());
+        to.arrive(lobby_player, player, Some(Vec2::new(-900.0, 0.0))).unwrap();
+
+        let arrived = to.game_state().get_player(id).unwrap();
+        assert_eq!((arrived.mass, arrived.velocity), (240.0, Vec2::new(30.0, 0.0)));
+        assert_eq!(arrived.position, Vec2::new(-900.0, 0.0));
+        assert_eq!(to.state, RoomState::Playing);
+
+        // Rooms whose match is over take no travelers
+        to.state = RoomState::Ended;
+        let (lobby_player, player) = (create_lobby_player("Late"), Player::new(Uuid::new_v4(), "Late".into(), false, 0));
+        assert!(matches!(to.arrive(lobby_player, player, None), Err(RoomError::MatchOver)));
+    }
 }
@@ -0,0 +1,194 @@
+//! Smurf detection
+//!
+//! Looks for experienced players on fresh accounts: a new account that is
+//! accurate from its first matches, snaps its aim like a practiced player and
+//! wins several in a row. Early accuracy and aim snaps come from the same
+//! input stats the game session publishes (see `game::input_stats`),
+//! gathered per match by each room; the account record is persisted with the
+//! player profiles. A flag only raises the player's effective MMR, so
+//! matchmaking pairs them with stronger players right away instead of after
+//! many lopsided wins. It is never a sanction.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::SmurfConfig;
+use crate::game::input_stats::InputStatsTracker;
+use crate::net::protocol::PlayerInput;
+
+/// Share of the score from early accuracy
+const ACCURACY_WEIGHT: f32 = 0.4;
+/// Share of the score from early aim snaps
+const AIM_SNAP_WEIGHT: f32 = 0.3;
+/// Share of the score from the win streak
+const WIN_STREAK_WEIGHT: f32 = 0.3;
+
+const SECS_PER_DAY: f32 = 86_400.0;
+
+/// One player's shooting and aiming over a match, gathered by the room
+#[derive(Debug, Clone, Default)]
+pub struct MatchSkill {
+    inputs: InputStatsTracker,
+    pub shots: u32,
+    pub hits: u32,
+}
+
+impl MatchSkill {
+    pub fn record_input(&mut self, input: &PlayerInput) {
+        self.inputs.record(input);
+        if input.fire_released {
+            self.shots += 1;
+        }
+    }
+
+    pub fn record_hit(&mut self) {
+        self.hits += 1;
+    }
+
+    pub fn aim_snap_ratio(&self) -> f32 {
+        self.inputs.stats().aim_snap_ratio
+    }
+}
+
+/// What the server remembers about an account for smurf detection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountRecord {
+    /// End of the first recorded match (Unix seconds)
+    pub first_seen: u64,
+    /// End of the latest recorded match (Unix seconds)
+    pub last_seen: u64,
+    pub matches: u32,
+    /// Wins in a row, up to the latest match
+    pub win_streak: u32,
+    /// Shots and hits over the early matches
+    pub early_shots: u32,
+    pub early_hits: u32,
+    /// Mean aim snap ratio over the early matches
+    pub early_aim_snap_ratio: f32,
+}
+
+impl AccountRecord {
+    /// Add a finished match; skill only counts during the first `early_matches`
+    pub fn record_match(&mut self, won: bool, skill: &MatchSkill, early_matches: u32, now: u64) {
+        if self.matches == 0 {
+            self.first_seen = now;
+        }
+        self.last_seen = now;
+        if self.matches < early_matches {
+            let played = self.matches as f32;
+            self.early_shots += skill.shots;
+            self.early_hits += skill.hits;
+            self.early_aim_snap_ratio = (self.early_aim_snap_ratio * played + skill.aim_snap_ratio()) / (played + 1.0);
+        }
+        self.matches += 1;
+        self.win_streak = if won { self.win_streak + 1 } else { 0 };
+    }
+
+    /// Hits per early shot, once there are enough shots to judge
+    pub fn early_accuracy(&self, min_shots: u32) -> Option<f32> {
+        (self.early_shots >= min_shots.max(1)).then(|| (self.early_hits as f32 / self.early_shots as f32).min(1.0))
+    }
+}
+
+/// How much an account looks like a smurf, and what matchmaking does about it
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SmurfAssessment {
+    /// 0-1, from the signals below
+    pub score: f32,
+    pub flagged: bool,
+    /// Signals that counted: "accuracy", "aim_snaps", "win_streak"
+    pub signals: Vec<&'static str>,
+    /// Added to the player's MMR for matchmaking
+    pub mmr_boost: u32,
+}
+
+/// Score an account; only accounts younger than `new_account_days` can be flagged
+pub fn assess(account: &AccountRecord, now: u64, config: &SmurfConfig) -> SmurfAssessment {
+    let age_days = now.saturating_sub(account.first_seen) as f32 / SECS_PER_DAY;
+    if !config.enabled || account.matches == 0 || age_days >= config.new_account_days {
+        return SmurfAssessment::default();
+    }
+
+    let mut assessment = SmurfAssessment::default();
+    if account.early_accuracy(config.min_shots).is_some_and(|accuracy| accuracy >= config.accuracy_threshold) {
+        assessment.score += ACCURACY_WEIGHT;
+        assessment.signals.push("accuracy");
+    }
+    if account.early_aim_snap_ratio >= config.aim_snap_threshold {
+        assessment.score += AIM_SNAP_WEIGHT;
+        assessment.signals.push("aim_snaps");
+    }
+    if account.win_streak >= config.win_streak {
+        assessment.score += WIN_STREAK_WEIGHT;
+        assessment.signals.push("win_streak");
+    }
+    assessment.flagged = assessment.score >= config.flag_score;
+    if assessment.flagged {
+        assessment.mmr_boost = (config.max_mmr_boost as f32 * assessment.score).round() as u32;
+    }
+    assessment
+}
+
+/// MMR matchmaking should use for a player with this account record
+pub fn effective_mmr(mmr: u32, account: Option<&AccountRecord>, now: u64, config: &SmurfConfig) -> u32 {
+    account.map_or(mmr, |account| mmr.saturating_add(assess(account, now, config).mmr_boost))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vec2::Vec2;
+
+    /// A match with `shots` shots, `hits` hits and every aim change a snap
+    fn sharp_match(shots: u32, hits: u32) -> MatchSkill {
+        let mut skill = MatchSkill { shots, hits, ..Default::default() };
+        for i in 0..10 {
+            let aim = if i % 2 == 0 { Vec2::new(1.0, 0.0) } else { Vec2::new(-1.0, 0.0) };
+            skill.record_input(&PlayerInput { aim, ..Default::default() });
+        }
+        skill
+    }
+
+    #[test]
+    fn test_new_accurate_winning_account_gets_an_mmr_boost() {
+        let config = SmurfConfig::default();
+        let day = SECS_PER_DAY as u64;
+        let mut account = AccountRecord::default();
+        for i in 0..config.win_streak as u64 {
+            account.record_match(true, &sharp_match(10, 6), config.early_matches, 1_000 + i);
+        }
+
+        let assessment = assess(&account, 1_000 + day, &config);
+        assert!(assessment.flagged);
+        assert_eq!(assessment.signals, ["accuracy", "aim_snaps", "win_streak"]);
+        assert_eq!(effective_mmr(1000, Some(&account), 1_000 + day, &config), 1000 + config.max_mmr_boost);
+
+        // A loss breaks the streak, but accuracy and snaps still flag the account
+        account.record_match(false, &MatchSkill::default(), config.early_matches, 1_000 + day);
+        assert!(assess(&account, 1_000 + day, &config).flagged);
+
+        // Old accounts are never flagged, nor are unknown ones boosted
+        let later = 1_000 + day * (config.new_account_days as u64 + 1);
+        assert_eq!(assess(&account, later, &config), SmurfAssessment::default());
+        assert_eq!(effective_mmr(1000, None, later, &config), 1000);
+    }
+
+    #[test]
+    fn test_accuracy_needs_enough_early_shots() {
+        let config = SmurfConfig::default();
+        let early = config.early_matches;
+        let plain = |shots: u32, hits: u32| MatchSkill { shots, hits, ..Default::default() };
+        let mut account = AccountRecord::default();
+        account.record_match(false, &plain(4, 4), early, 0);
+        assert_eq!(account.early_accuracy(config.min_shots), None);
+        assert!(!assess(&account, 0, &config).flagged);
+
+        // Matches past the early ones don't move the early stats
+        for _ in 0..early {
+            account.record_match(false, &plain(10, 1), early, 0);
+        }
+        let early_shots = account.early_shots;
+        account.record_match(false, &plain(100, 100), early, 0);
+        assert_eq!(account.early_shots, early_shots);
+        assert!(account.early_accuracy(config.min_shots).unwrap() < config.accuracy_threshold);
+    }
+}
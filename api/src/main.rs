@@ -1,7 +1,15 @@
+mod cluster;
 mod config;
+mod export;
+mod features;
 mod game;
 mod metrics;
 mod net;
+mod public_api;
+mod roles;
+mod runtime;
+mod server;
+mod tenants;
 mod util;
 
 #[cfg(feature = "anticheat")]
@@ -10,23 +18,17 @@ mod anticheat;
 mod lobby;
 #[cfg(feature = "ai_manager")]
 mod ai_manager;
+#[cfg(feature = "scripting")]
+mod scripting;
 
-use std::sync::Arc;
-use tokio::sync::RwLock;
 use tracing::{error, info};
 use tracing_subscriber::EnvFilter;
 
-use crate::config::ServerConfig;
-use crate::metrics::Metrics;
-use crate::net::transport::WebTransportServer;
+use crate::config::{RetentionConfig, SanctionsConfig, ServerConfig, ShutdownConfig};
+use crate::features::FeatureSettings;
+use crate::server::ServerBuilder;
 
-#[cfg(feature = "anticheat")]
-use crate::anticheat::sanctions::BanList;
-#[cfg(feature = "lobby")]
-use crate::lobby::manager::LobbyManager;
-
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<()> {
     // Load .env file if present
     dotenvy::dotenv().ok();
 
@@ -55,81 +57,36 @@ async fn main() -> anyhow::Result<()> {
         return Err(anyhow::anyhow!("Configuration validation failed: {}", e));
     }
 
-    info!(
-        "Configuration loaded: {}:{}, max_rooms={}",
-        config.bind_address, config.port, config.max_rooms
-    );
-
-    // Initialize metrics
-    let metrics = Arc::new(Metrics::new());
+    // Size the thread pools before anything starts them
+    runtime::configure_rayon(&config.runtime);
+    runtime::build_tokio(&config.runtime)?.block_on(serve(config))
+}
 
-    // Start metrics server on port 9090 (configurable via METRICS_PORT)
+async fn serve(config: ServerConfig) -> anyhow::Result<()> {
+    // Metrics server port (configurable via METRICS_PORT)
     let metrics_port: u16 = std::env::var("METRICS_PORT")
         .ok()
         .and_then(|s| s.parse().ok())
         .unwrap_or(9090);
 
-    let metrics_clone = metrics.clone();
-    tokio::spawn(async move {
-        if let Err(e) = metrics::start_metrics_server(metrics_clone, metrics_port).await {
-            error!("Metrics server error: {}", e);
-        }
-    });
-
-    // Initialize shared state (feature-gated)
-    #[cfg(feature = "lobby")]
-    let lobby_manager = Arc::new(RwLock::new(LobbyManager::new(config.max_rooms)));
-    #[cfg(not(feature = "lobby"))]
-    let lobby_manager = Arc::new(RwLock::new(()));
-
-    #[cfg(feature = "anticheat")]
-    let ban_list = Arc::new(RwLock::new(BanList::new()));
-    #[cfg(not(feature = "anticheat"))]
-    let ban_list = Arc::new(RwLock::new(()));
-
-    // Create WebTransport server
-    let server = WebTransportServer::new(
-        config.clone(),
-        lobby_manager.clone(),
-        ban_list.clone(),
-        metrics.clone(),
-    )
-    .await?;
-
-    info!(
-        "Server ready on https://{}:{}",
-        config.bind_address, config.port
-    );
-    info!("Certificate hash: {}", server.cert_hash());
-    info!(
-        "Chrome flag: --ignore-certificate-errors-spki-list={}",
-        server.cert_hash()
-    );
+    let server = ServerBuilder::new(config)
+        .metrics_port(metrics_port)
+        .feature_settings(FeatureSettings::from_env())
+        .shutdown_config(ShutdownConfig::from_env())
+        .sanctions_config(SanctionsConfig::from_env())
+        .retention_config(RetentionConfig::from_env())
+        .build()
+        .await?;
 
     // Shutdown signal handler
-    let shutdown = async {
+    let shutdown = server.shutdown_token();
+    tokio::spawn(async move {
         tokio::signal::ctrl_c()
             .await
             .expect("Failed to install Ctrl+C handler");
         info!("Shutdown signal received");
-    };
-
-    // Run server with graceful shutdown
-    tokio::select! {
-        result = server.run() => {
-            if let Err(e) = result {
-                error!("Server error: {}", e);
-            }
-        }
-        _ = shutdown => {
-            info!("Shutting down...");
-        }
-    }
-
-    // Cleanup
-    #[cfg(feature = "lobby")]
-    lobby_manager.write().await.shutdown_all_rooms().await;
-    info!("Server stopped");
+        shutdown.trigger();
+    });
 
-    Ok(())
+    server.run().await
 }
@@ -3,10 +3,52 @@
 //! Exposes game server metrics in Prometheus format for Grafana dashboards.
 //! - /metrics: Prometheus format for Grafana scraping
 //! - /json: Simple JSON format for direct API access
-//! - /health: Health check endpoint
+//! - /health: Health check endpoint (always OK while the process serves HTTP)
+//! - /healthz: Liveness probe, 503 when the game loop stops ticking (see `ProbeConfig`)
+//! - /readyz: Readiness probe, 503 until connections are accepted and the lobby is up,
+//!   and while draining
+//! - /debug/drain, POST /debug/drain?enabled=true|false: Show or toggle drain mode
+//! - /cluster/status, POST /cluster/place?party_size=N: Instances known in cluster mode, or the
+//!   least-loaded instance for a party, reserving its seats (admin route, see `cluster`)
+//! - /players/input-stats: Per-player input stats (opt-in via INPUT_STATS_ENDPOINT=true)
+//! - /debug/snapshot-diff?from=N&to=M[&epsilon=E]: Entity diff between two retained
+//!   ticks (requires SNAPSHOT_HISTORY_CAPACITY > 0)
+//! - /analytics/heatmap?layer=L[&format=json|png][&size=N]: Downsampled occupancy, kills or
+//!   well_deaths heatmap (see `game::heatmap`)
+//! - /analytics/balance: Deaths by cause over the last hour and the shares that look degenerate
+//!   (see `game::balance`)
+//! - /debug/netsim[?player=ID], POST /debug/netsim?player=ID[&delay_ms=D&jitter_ms=J&loss=L&reorder=true|clear=true]:
+//!   List, show, set or clear simulated network conditions per connection (requires NETSIM_ENABLED, see `net::netsim`)
+//! - /debug/capture[?player=ID], POST /debug/capture?player=ID&start=true|stop=true: List captured
+//!   connections, dump one player's recent inbound messages and snapshot digests, or start/stop an
+//!   opt-in capture (requires CAPTURE_MODE, see `net::capture`)
+//! - /debug/replay[?file=NAME[&tick=T]]: List match replays, show one's chapters and seek index, or
+//!   the full snapshot at a tick (requires REPLAY_DIR, see `net::replay`)
+//! - POST /debug/ghost?player=ID&room=ID[&name=N]: Replay a captured player's run as a ghost in a
+//!   practice room (see `game::ghost`)
+//! - /debug/state: Summary of the latest published game state view (see `net::state_view`)
+//! - /highlights[?limit=N]: Automatically cut highlight clips, newest first (requires HIGHLIGHT_DIR,
+//!   see `net::highlights`)
+//! - /matches[?limit=N&before=ID], /matches/ID: Recently finished lobby matches, newest first, or one
+//!   match by id (see `lobby::match_history`)
+//!
+//! - /presence/ACCOUNT: What a player is doing (mode, arena, mass rank) for rich presence relays, by account
+//!   id; requires a relay token as the bearer token (see `net::presence`)
+//!
+//! - /api/v1/server, /api/v1/leaderboard[?limit=N], /api/v1/matches[?limit=N&before=ID], /api/v1/matches/ID,
+//!   /api/v1/players/ACCOUNT: Public read-only stats for community sites, rate limited per API key and cached
+//!   (see `public_api`)
+//!
+//! - /tenant/rooms, POST /tenant/rooms/create?name=N&max_players=M&seed=CODE|/start?room=ID|/close?room=ID,
+//!   /tenant/metrics: Hosted rooms and metrics of the tenant whose API key is the bearer token (see `tenants`)
+//!
+//! Every other route (`/players/*`, `/debug/*`, `/analytics/*`, ...) is an admin route: it requires an
+//! `Authorization: Bearer <token>` with the admin role, and without any role tokens configured it is only
+//! served to loopback peers.
 
-use std::collections::VecDeque;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{BTreeMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use parking_lot::RwLock;
@@ -15,6 +57,511 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpListener;
 use tracing::{info, debug};
 
+use crate::cluster::{ClusterError, ClusterState};
+use crate::features::{FeatureSet, FeatureSettings};
+use crate::config::{
+    ArenaPresetConfig, CaptureConfig, ClusterConfig, HeatmapConfig, HighlightConfig, NetSimConfig, PresenceConfig,
+    ProbeConfig, PublicApiConfig, ReplayConfig, SnapshotHistoryConfig,
+};
+use crate::game::arena_preset::{ArenaPreset, ArenaPresets, PresetError};
+use crate::game::balance::BalanceAnalytics;
+use crate::game::heatmap::{HeatmapLayer, Heatmaps};
+use crate::game::input_stats::PlayerInputStats;
+use crate::game::systems::ai_soa::BatchTimings;
+use crate::game::state::PlayerId;
+use crate::net::capture::SessionCapture;
+use crate::net::egress::{EgressStats, MessageCategory};
+use crate::net::netsim::{NetConditions, NetSimulator};
+use crate::net::account::AccountId;
+use crate::net::presence::{self, Presence};
+use crate::net::protocol::DeathCause;
+use crate::net::replay::{ChapterKind, ReplayReader, REPLAY_EXTENSION};
+use crate::net::send_pacing::BurstMeter;
+use crate::net::snapshot_history::SnapshotHistory;
+use crate::net::state_view::PublishedState;
+use crate::public_api::{self, PublicApi, PublicApiError};
+use crate::roles::{bearer_token, AccessDenied, Permission, RoleRegistry};
+use crate::server::ShutdownToken;
+use crate::tenants::{Tenant, TenantRegistry};
+
+#[cfg(feature = "lobby")]
+type LobbyManagerType = crate::lobby::manager::LobbyManager;
+#[cfg(not(feature = "lobby"))]
+type LobbyManagerType = ();
+
+/// Room size for tenant-created rooms when the request doesn't specify one
+#[cfg(feature = "lobby")]
+const TENANT_DEFAULT_ROOM_SIZE: usize = 10;
+
+/// Largest party one `/cluster/place` request may reserve seats for
+const CLUSTER_MAX_PARTY_SIZE: usize = 8;
+
+/// Heatmap export resolution when the request doesn't specify one
+const HEATMAP_DEFAULT_SIZE: usize = 64;
+
+/// Largest heatmap export resolution
+const HEATMAP_MAX_SIZE: usize = 512;
+
+/// Matches listed by `/matches` when the request doesn't specify a limit
+#[cfg(feature = "lobby")]
+const MATCHES_DEFAULT_LIMIT: usize = 20;
+
+/// Most matches listed by one `/matches` request
+#[cfg(feature = "lobby")]
+const MATCHES_MAX_LIMIT: usize = 100;
+
+/// Players listed by `/api/v1/leaderboard` when the request doesn't specify a limit
+const LEADERBOARD_DEFAULT_LIMIT: usize = 10;
+
+/// Most players listed by one `/api/v1/leaderboard` request
+const LEADERBOARD_MAX_LIMIT: usize = 100;
+
+/// Highlights listed by `/highlights` when the request doesn't specify a limit
+const HIGHLIGHTS_DEFAULT_LIMIT: usize = 20;
+
+/// Most highlights listed by one `/highlights` request
+const HIGHLIGHTS_MAX_LIMIT: usize = 100;
+
+/// Upper bounds of the suspicion buckets: rejected or sanitized inputs per
+/// connected player this session (a last `+Inf` bucket counts everyone)
+pub const SUSPICION_BUCKETS: [u32; 4] = [0, 5, 25, 100];
+
+/// Counter family keyed by one label; label values appear once first counted
+#[derive(Debug, Default)]
+#[allow(dead_code)] // Fed by the anticheat feature
+pub struct LabeledCounters(RwLock<BTreeMap<&'static str, u64>>);
+
+#[allow(dead_code)]
+impl LabeledCounters {
+    pub fn increment(&self, label: &'static str) {
+        *self.0.write().entry(label).or_insert(0) += 1;
+    }
+
+    pub fn get(&self, label: &str) -> u64 {
+        self.0.read().get(label).copied().unwrap_or(0)
+    }
+
+    /// Prometheus text for the family, one sample per label value
+    fn to_prometheus(&self, name: &str, help: &str, label: &str) -> String {
+        let mut output = format!("# HELP {} {}\n# TYPE {} counter\n", name, help, name);
+        for (value, count) in self.0.read().iter() {
+            output.push_str(&format!("{}{{{}=\"{}\"}} {}\n", name, label, value, count));
+        }
+        output
+    }
+}
+
+/// GET routes served without the admin role, with their sub-paths: they are
+/// public or check their own key (tenants, public API, presence relays)
+const PUBLIC_ROUTES: [&str; 13] = [
+    "/metrics", "/json", "/health", "/healthz", "/readyz", "/info", "/highlights", "/matches", "/cluster/status",
+    "/api", "/presence", "/tenant", "/",
+];
+
+/// POST routes served without the admin role, with their sub-paths (tenants check their own key)
+const PUBLIC_POST_ROUTES: [&str; 1] = ["/tenant"];
+
+/// Permission required for a request's route (None = public). Anything not
+/// listed in `PUBLIC_ROUTES` or `PUBLIC_POST_ROUTES` is an admin route, so new
+/// routes start out closed.
+fn route_permission(request: &str) -> Option<Permission> {
+    let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("");
+    let path = request_line.next().unwrap_or("").split('?').next().unwrap_or("");
+    let routes: &[&str] = match method {
+        "GET" => &PUBLIC_ROUTES,
+        "POST" => &PUBLIC_POST_ROUTES,
+        _ => &[],
+    };
+    let public = routes.iter().any(|route| {
+        path == *route || (*route != "/" && path.strip_prefix(route).is_some_and(|rest| rest.starts_with('/')))
+    });
+    (!public).then_some(Permission::AdminApi)
+}
+
+/// Value of a query parameter in the request line
+fn query_param<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    let target = request.lines().next()?.split_whitespace().nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key == name).then_some(value)
+    })
+}
+
+/// Parsed query parameter (Ok(None) when absent, Err(name) when malformed)
+fn parsed_param<'a, T: std::str::FromStr>(request: &str, name: &'a str) -> Result<Option<T>, &'a str> {
+    query_param(request, name).map(|v| v.parse::<T>().map_err(|_| name)).transpose()
+}
+
+/// Handle `/tenant/*` for the tenant owning the request's API key: returns (status line, content type, body).
+/// Tenants only ever see and change their own rooms; creating, starting and closing them is a POST.
+#[cfg(feature = "lobby")]
+fn tenant_response(
+    request: &str,
+    tenant: &Tenant,
+    lobby: &mut LobbyManagerType,
+) -> (&'static str, &'static str, String) {
+    use crate::game::arena_seed::ArenaSeed;
+    use crate::lobby::manager::{ManagerError, RoomInfo};
+    use crate::lobby::room::MAX_ROOM_SIZE;
+
+    let json = |status, body: serde_json::Value| (status, "application/json", body.to_string());
+    let error = |status, message: &str| json(status, serde_json::json!({ "error": message }));
+    let room_json = |room: &RoomInfo| {
+        serde_json::json!({
+            "id": room.id,
+            "name": room.name,
+            "players": room.player_count,
+            "max_players": room.max_players,
+            "state": format!("{:?}", room.state).to_lowercase(),
+            "arena_code": room.arena_code,
+        })
+    };
+
+    let (method, target) = request.split_once(' ').unwrap_or_default();
+    let changes_rooms =
+        ["/tenant/rooms/create", "/tenant/rooms/start", "/tenant/rooms/close"].iter().any(|r| target.starts_with(r));
+    if changes_rooms != (method == "POST") {
+        let message = if changes_rooms { "this route changes rooms, use POST" } else { "read-only route, use GET" };
+        return error("405 Method Not Allowed", message);
+    }
+
+    if target.starts_with("/tenant/rooms/create") {
+        let name = query_param(request, "name").unwrap_or("Hosted room");
+        let max_players = match parsed_param::<usize>(request, "max_players") {
+            Ok(size) if (1..=MAX_ROOM_SIZE).contains(&size.unwrap_or(TENANT_DEFAULT_ROOM_SIZE)) => {
+                size.unwrap_or(TENANT_DEFAULT_ROOM_SIZE)
+            }
+            _ => return error("400 Bad Request", &format!("max_players must be between 1 and {}", MAX_ROOM_SIZE)),
+        };
+        let arena_seed = match query_param(request, "seed").map(str::parse::<ArenaSeed>).transpose() {
+            Ok(seed) => seed,
+            Err(e) => return error("400 Bad Request", &e.to_string()),
+        };
+        match lobby.create_tenant_room(tenant, format!("{}: {}", tenant.id, name), max_players, arena_seed) {
+            Ok(id) => json("200 OK", serde_json::json!({ "id": id })),
+            Err(e) => error("429 Too Many Requests", &e.to_string()),
+        }
+    } else if target.starts_with("/tenant/rooms/start") {
+        let Some(Ok(room_id)) = query_param(request, "room").map(|r| r.parse::<uuid::Uuid>()) else {
+            return error("400 Bad Request", "room must be a room id");
+        };
+        match lobby.start_tenant_room(&tenant.id, room_id) {
+            Ok(()) => json("200 OK", serde_json::json!({ "started": room_id })),
+            Err(ManagerError::RoomNotFound) => error("404 Not Found", "Room not found"),
+            Err(e) => error("409 Conflict", &e.to_string()),
+        }
+    } else if target.starts_with("/tenant/rooms/close") {
+        let Some(Ok(room_id)) = query_param(request, "room").map(|r| r.parse::<uuid::Uuid>()) else {
+            return error("400 Bad Request", "room must be a room id");
+        };
+        match lobby.close_tenant_room(&tenant.id, room_id) {
+            Ok(_) => json("200 OK", serde_json::json!({ "closed": room_id })),
+            Err(e) => error("404 Not Found", &e.to_string()),
+        }
+    } else if target.starts_with("/tenant/rooms") {
+        let rooms: Vec<_> = lobby.tenant_rooms(&tenant.id).iter().map(room_json).collect();
+        json("200 OK", serde_json::json!({ "tenant": tenant.id, "quota": tenant.quota, "rooms": rooms }))
+    } else if target.starts_with("/tenant/metrics") {
+        ("200 OK", "text/plain; version=0.0.4", tenant_prometheus(&[lobby.tenant_usage(&tenant.id)]))
+    } else {
+        error("404 Not Found", "unknown tenant route")
+    }
+}
+
+/// Handle `/debug/ghost`: replay a player's captured run in a practice room (a POST, since it adds a ghost to
+/// the room); returns (status line, JSON body)
+#[cfg(feature = "lobby")]
+fn ghost_response(request: &str, capture: &SessionCapture, lobby: &mut LobbyManagerType) -> (&'static str, String) {
+    let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+
+    if !request.starts_with("POST ") {
+        return error("405 Method Not Allowed", "replaying a ghost changes the room, use POST");
+    }
+
+    let Some(Ok(player_id)) = query_param(request, "player").map(str::parse::<PlayerId>) else {
+        return error("400 Bad Request", "player must be a player id");
+    };
+    let Some(Ok(room_id)) = query_param(request, "room").map(str::parse::<uuid::Uuid>) else {
+        return error("400 Bad Request", "room must be a room id");
+    };
+    let dump = match capture.dump(player_id) {
+        Ok(dump) => dump,
+        Err(e) => return error("404 Not Found", &e.to_string()),
+    };
+    let name = query_param(request, "name").unwrap_or("Ghost").to_string();
+    let Some(track) = dump.ghost_track(name) else {
+        return error("404 Not Found", "capture has no snapshots of the player");
+    };
+    let Some(room) = lobby.get_room_mut(room_id) else {
+        return error("404 Not Found", "room not found");
+    };
+    match room.add_ghost(track) {
+        Ok(ghost_id) => ("200 OK", serde_json::json!({ "room": room_id, "ghost": ghost_id }).to_string()),
+        Err(e) => error("400 Bad Request", &e.to_string()),
+    }
+}
+
+/// `/info`: server version, the features it was built with and what the
+/// feature self-check finds in the current environment
+fn info_json(features: &FeatureSet, settings: &FeatureSettings) -> String {
+    serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "features": features.enabled(),
+        "issues": features.self_check(settings),
+    })
+    .to_string()
+}
+
+/// Handle `/matches` and `/matches/ID`: returns (status line, JSON body)
+#[cfg(feature = "lobby")]
+fn matches_response(request: &str, history: &crate::lobby::match_history::MatchHistory) -> (&'static str, String) {
+    let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+
+    if let Some(rest) = request.strip_prefix("GET /matches/") {
+        let id = rest.split(|c: char| c == '?' || c.is_whitespace()).next().unwrap_or("");
+        let Ok(id) = id.parse::<u64>() else {
+            return error("400 Bad Request", "match id must be a number");
+        };
+        return match history.get(id) {
+            Some(summary) => ("200 OK", serde_json::json!(summary).to_string()),
+            None => error("404 Not Found", "match not found"),
+        };
+    }
+
+    let limit = match parsed_param::<usize>(request, "limit") {
+        Ok(limit) if limit != Some(0) => limit.unwrap_or(MATCHES_DEFAULT_LIMIT).min(MATCHES_MAX_LIMIT),
+        _ => return error("400 Bad Request", "limit must be a positive number"),
+    };
+    let before = match parsed_param::<u64>(request, "before") {
+        Ok(before) => before,
+        Err(_) => return error("400 Bad Request", "before must be a match id"),
+    };
+    let matches = history.page(limit, before);
+    // Cursor for the next page, when there may be one
+    let next_before = (matches.len() == limit).then(|| matches.last().map(|m| m.id)).flatten();
+    ("200 OK", serde_json::json!({ "matches": matches, "next_before": next_before }).to_string())
+}
+
+/// Handle `/presence/ACCOUNT`: the main arena first, then lobby rooms; returns (status line, JSON body)
+fn presence_response(request: &str, state_view: &PublishedState, lobby: &LobbyManagerType) -> (&'static str, String) {
+    let account = request
+        .strip_prefix("GET /presence/")
+        .and_then(|rest| rest.split(|c: char| c == '?' || c.is_whitespace()).next())
+        .and_then(AccountId::parse);
+    let Some(account) = account else {
+        let body = serde_json::json!({ "error": "account must be a 32-character hex account id" }).to_string();
+        return ("400 Bad Request", body);
+    };
+
+    let view = state_view.load();
+    #[allow(unused_mut)]
+    let mut found = view.accounts.get(&account).and_then(|&player_id| {
+        Presence::find(&view.snapshot, account, player_id, presence::MAIN_ARENA_MODE, presence::MAIN_ARENA_NAME)
+    });
+    #[cfg(feature = "lobby")]
+    {
+        found = found.or_else(|| lobby.presence(account));
+    }
+    #[cfg(not(feature = "lobby"))]
+    let _ = lobby;
+    let presence = found.unwrap_or_else(|| Presence::offline(account));
+    ("200 OK", serde_json::to_string(&presence).unwrap_or_else(|_| "{}".to_string()))
+}
+
+/// Handle a public API route (`/api/v1/...`) for an admitted request: returns (status line, JSON body).
+/// Matches and players share the handlers of `/matches` and `/presence`.
+fn public_api_route(request: &str, metrics: &Metrics, lobby: &LobbyManagerType) -> (&'static str, String) {
+    let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+    let Some(route) = request.strip_prefix("GET ").and_then(|r| r.strip_prefix(public_api::ROUTE_PREFIX)) else {
+        return error("404 Not Found", "unknown public API route");
+    };
+    let view = metrics.state_view.load();
+
+    if route.starts_with("server") {
+        let body = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "features": FeatureSet::compiled().enabled(),
+            "uptime_seconds": metrics.uptime_seconds(),
+            "players": metrics.total_players.load(Ordering::Relaxed),
+            "humans": metrics.human_players.load(Ordering::Relaxed),
+            "bots": metrics.bot_players.load(Ordering::Relaxed),
+            "match_phase": format!("{:?}", view.snapshot.match_phase).to_lowercase(),
+            "match_time": view.snapshot.match_time,
+        });
+        ("200 OK", body.to_string())
+    } else if route.starts_with("leaderboard") {
+        let limit = match parsed_param::<usize>(request, "limit") {
+            Ok(limit) if limit != Some(0) => limit.unwrap_or(LEADERBOARD_DEFAULT_LIMIT).min(LEADERBOARD_MAX_LIMIT),
+            _ => return error("400 Bad Request", "limit must be a positive number"),
+        };
+        let mut alive: Vec<_> = view.snapshot.players.iter().filter(|p| p.alive() && !p.is_ghost()).collect();
+        alive.sort_by(|a, b| b.mass.total_cmp(&a.mass));
+        let players: Vec<_> = alive
+            .iter()
+            .take(limit)
+            .enumerate()
+            .map(|(i, p)| {
+                serde_json::json!({
+                    "rank": i + 1,
+                    "name": p.name,
+                    "mass": p.mass,
+                    "kills": p.kills,
+                    "is_bot": p.is_bot(),
+                })
+            })
+            .collect();
+        ("200 OK", serde_json::json!({ "alive": alive.len(), "players": players }).to_string())
+    } else if route.starts_with("matches") {
+        #[cfg(feature = "lobby")]
+        return matches_response(&format!("GET /{}", route), lobby.match_history());
+        #[cfg(not(feature = "lobby"))]
+        return error("404 Not Found", "match history requires the lobby feature");
+    } else if let Some(account) = route.strip_prefix("players/") {
+        presence_response(&format!("GET /presence/{}", account), &metrics.state_view, lobby)
+    } else {
+        error("404 Not Found", "unknown public API route")
+    }
+}
+
+#[cfg(not(feature = "lobby"))]
+fn ghost_response(_: &str, _: &SessionCapture, _: &mut LobbyManagerType) -> (&'static str, String) {
+    let body = serde_json::json!({ "error": "ghost runs require the lobby feature" }).to_string();
+    ("404 Not Found", body)
+}
+
+#[cfg(not(feature = "lobby"))]
+fn tenant_response(_: &str, _: &Tenant, _: &mut LobbyManagerType) -> (&'static str, &'static str, String) {
+    let body = serde_json::json!({ "error": "hosted rooms require the lobby feature" }).to_string();
+    ("404 Not Found", "application/json", body)
+}
+
+/// Per-tenant room gauges, labelled with the tenant id
+#[cfg(feature = "lobby")]
+fn tenant_prometheus(usage: &[crate::lobby::manager::TenantUsage]) -> String {
+    use crate::lobby::manager::TenantUsage;
+
+    let mut output = String::new();
+    let mut gauge = |name: &str, help: &str, value: fn(&TenantUsage) -> usize| {
+        output.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n", name, help, name));
+        for tenant in usage {
+            output.push_str(&format!("{}{{tenant=\"{}\"}} {}\n", name, tenant.tenant_id, value(tenant)));
+        }
+    };
+    gauge("orbit_royale_tenant_rooms", "Rooms hosted by the tenant", |u| u.rooms);
+    gauge("orbit_royale_tenant_players", "Players in the tenant's rooms", |u| u.players);
+    gauge("orbit_royale_tenant_capacity", "Player capacity of the tenant's rooms", |u| u.capacity);
+    output
+}
+
+/// Lobby queue gauges per mode and MMR bucket, plus match and backfill counters
+#[cfg(feature = "lobby")]
+fn queue_prometheus(report: &crate::lobby::queue_stats::QueueReport) -> String {
+    use crate::lobby::queue_stats::ModeQueue;
+
+    let mut output = String::new();
+    let mut family = |name: &str, help: &str, kind: &str, samples: Vec<(String, String)>| {
+        output.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind));
+        for (labels, value) in samples {
+            output.push_str(&format!("{}{} {}\n", name, labels, value));
+        }
+    };
+    let by_mode = |value: fn(&ModeQueue) -> String| {
+        report.modes.iter().map(|m| (format!("{{mode=\"{}\"}}", m.mode), value(m))).collect::<Vec<_>>()
+    };
+
+    family(
+        "orbit_royale_lobby_queue_waiting",
+        "Players waiting for their room to start",
+        "gauge",
+        by_mode(|m| m.waiting.to_string()),
+    );
+    family(
+        "orbit_royale_lobby_queue_longest_wait_seconds",
+        "Longest current wait",
+        "gauge",
+        by_mode(|m| format!("{:.1}", m.longest_wait_secs)),
+    );
+    family(
+        "orbit_royale_lobby_queue_average_wait_seconds",
+        "Rolling average wait per mode and MMR bucket (labelled with its lowest MMR)",
+        "gauge",
+        report
+            .buckets
+            .iter()
+            .map(|b| (format!("{{mode=\"{}\",mmr=\"{}\"}}", b.mode, b.mmr_floor), format!("{:.1}", b.average_secs)))
+            .collect(),
+    );
+    family(
+        "orbit_royale_lobby_queue_matched_total",
+        "Queued players whose room started",
+        "counter",
+        vec![(String::new(), report.matched.to_string())],
+    );
+    family(
+        "orbit_royale_lobby_queue_backfills_total",
+        "Waiting rooms started with bots after a long wait",
+        "counter",
+        vec![(String::new(), report.backfills.to_string())],
+    );
+    output
+}
+
+/// Central access check for the HTTP endpoints.
+/// Without configured role tokens admin routes are only served to loopback
+/// peers, so a default deployment never exposes them on the network.
+fn authorize_request(request: &str, registry: &RoleRegistry, peer: IpAddr) -> Result<(), AccessDenied> {
+    let Some(permission) = route_permission(request) else {
+        return Ok(());
+    };
+    if !registry.is_configured() && peer.is_loopback() {
+        return Ok(());
+    }
+    registry.resolve(bearer_token(request)).require(permission)
+}
+
+/// Per-second increases of a counter over the last minute
+#[derive(Debug, Default)]
+pub struct RateWindow {
+    last_total: u64,
+    /// Oldest first
+    samples: VecDeque<u64>,
+}
+
+impl RateWindow {
+    /// Seconds of samples kept
+    const SECONDS: usize = 60;
+
+    /// Record how much the counter grew since the last roll (call once a second)
+    pub fn roll(&mut self, total: u64) {
+        self.samples.push_back(total.saturating_sub(self.last_total));
+        self.last_total = total;
+        while self.samples.len() > Self::SECONDS {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Mean increase per second
+    #[cfg_attr(not(feature = "ai_manager"), allow(dead_code))]
+    pub fn mean(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().sum::<u64>() as f32 / self.samples.len() as f32
+    }
+
+    /// Variance of the per-second increases
+    #[cfg_attr(not(feature = "ai_manager"), allow(dead_code))]
+    pub fn variance(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let mean = self.mean();
+        self.samples.iter().map(|&s| (s as f32 - mean).powi(2)).sum::<f32>() / self.samples.len() as f32
+    }
+}
+
 /// Metrics registry for the game server
 #[derive(Debug)]
 pub struct Metrics {
@@ -41,6 +588,16 @@ pub struct Metrics {
 
     // Tick counter
     pub tick_count: AtomicU64,
+    pub hibernating: AtomicBool, // Idle hibernation: few bots, reduced tick rate
+    pub safe_mode: AtomicBool,   // Parameters frozen after an incident
+    pub safe_mode_incidents_total: AtomicU64, // Counter: incidents that started a parameter freeze
+    pub tick_spikes_total: AtomicU64,   // Counter: ticks over the watchdog spike threshold
+    pub tick_profiles_total: AtomicU64, // Counter: flamegraphs captured around spikes
+    pub aoi_enters_total: AtomicU64,    // Counter: entities entering a client's AOI
+    pub aoi_exits_total: AtomicU64,     // Counter: entities leaving a client's AOI
+    pub aoi_shared_queries_total: AtomicU64, // Counter: AOI queries answered by another client's candidate list
+    pub broadcast_frames_dropped_total: AtomicU64, // Counter: frames dropped while the broadcast pool was behind
+    pub region_hints_total: AtomicU64, // Counter: region hints sent to players
 
     // Network stats
     pub connections_active: AtomicU64,
@@ -79,6 +636,9 @@ pub struct Metrics {
     pub anticheat_inputs_rejected: AtomicU64,    // Inputs rejected (invalid)
     pub anticheat_inputs_sanitized: AtomicU64,   // Inputs sanitized (fixed)
     pub anticheat_sequence_violations: AtomicU64, // Sequence validation failures
+    pub anticheat_violations: LabeledCounters,   // Counter: failed validations by violation type
+    pub anticheat_sanctions: LabeledCounters,    // Counter: sanctions issued by tier
+    anticheat_suspicion: RwLock<Vec<u64>>,       // Connected players per suspicion bucket (cumulative)
 
     // DoS protection metrics
     pub dos_connections_rejected: AtomicU64,   // Connections rejected by DoS
@@ -99,8 +659,11 @@ pub struct Metrics {
     pub bot_ai_full_mode: AtomicU64,           // Bots in full update mode
     pub bot_ai_reduced_mode: AtomicU64,        // Bots in reduced update mode
     pub bot_ai_dormant_mode: AtomicU64,        // Bots in dormant mode
+    pub bot_ai_watched_zones: AtomicU64,       // AI zones on some human's screen
+    pub bot_ai_cold: AtomicU64,                // Bots frozen in cold storage
     pub bot_ai_lod_scale: AtomicU64,           // LOD scale factor (x100, e.g., 100 = 1.0x)
     pub bot_ai_health_status: AtomicU64,       // Health status (0=Excellent, 4=Catastrophic)
+    bot_ai_batches: RwLock<BatchTimings>,      // Per-behavior batch cost of the last AI update
 
     // Spectator metrics
     pub spectators_total: AtomicU64,              // Active spectator count
@@ -122,10 +685,29 @@ pub struct Metrics {
     pub spawn_projectiles_total: AtomicU64,    // Projectiles created
     pub kills_total: AtomicU64,                // Total kills
     pub deaths_arena_total: AtomicU64,         // Deaths from arena boundary
+    pub deaths_total: [AtomicU64; DeathCause::ALL.len()], // Deaths by cause, in `DeathCause::ALL` order
+    pub kill_rate: RwLock<RateWindow>,         // Kills per second over the last minute
 
     // Network quality metrics
     pub network_write_failures_total: AtomicU64, // Failed network writes
-    pub broadcast_latency_us: AtomicU64,         // Broadcast time in microseconds
+    pub broadcast_latency_us: AtomicU64,         // Broadcast time in microseconds (frame capture to last send)
+    pub send_bytes_total: AtomicU64,             // Counter: bytes written by writer tasks
+    pub send_burst_peak_bytes: AtomicU64,        // Peak bytes in one 5ms bucket (last second)
+    pub send_burst: BurstMeter,                  // Live burst measurement (rolled each second)
+    pub send_rate: RwLock<RateWindow>,           // Bytes written per second over the last minute
+    pub snapshot_rate_low_clients: AtomicU64,    // Players currently on 5Hz snapshots
+    pub snapshot_rate_normal_clients: AtomicU64, // Players currently on 10Hz snapshots
+    pub snapshot_rate_high_clients: AtomicU64,   // Players currently on 20Hz snapshots
+    pub snapshot_rate_changes_total: AtomicU64,  // Counter: adaptive snapshot rate switches
+    pub snapshot_resyncs_total: AtomicU64,       // Counter: full resyncs sent relative to the acked base
+    pub snapshot_resync_bytes_saved: AtomicU64,  // Counter: bytes those resyncs saved over full snapshots
+
+    // Client prediction desync detection
+    pub desync_checks_total: AtomicU64,          // Counter: client state hashes compared
+    pub desync_mismatches_total: AtomicU64,      // Counter: hashes that didn't match
+    pub desync_players: AtomicU64,               // Players with a sustained mismatch streak
+    pub client_jitter_avg_ms: AtomicU64,         // Mean measured input jitter across players
+    pub interp_delay_avg_ms: AtomicU64,          // Mean recommended interpolation delay
 
     // Delta compression metrics
     pub delta_updates_sent: AtomicU64,           // Delta update messages sent
@@ -145,6 +727,51 @@ pub struct Metrics {
 
     // Rolling tick times for percentile calculation (VecDeque for O(1) pop_front)
     tick_history: RwLock<VecDeque<u64>>,
+
+    // Latest per-player input stats (published by the game session once per second)
+    input_stats: RwLock<Vec<PlayerInputStats>>,
+
+    // Retained full snapshots for /debug/snapshot-diff
+    pub snapshot_history: SnapshotHistory,
+
+    // Latest game state view (published by the game session every tick) for /debug/state
+    pub state_view: Arc<PublishedState>,
+
+    // Accumulated analytics heatmaps for /analytics/heatmap
+    pub heatmaps: Heatmaps,
+
+    // Deaths by cause over the last hour for /analytics/balance
+    pub balance: BalanceAnalytics,
+
+    // Simulated per-connection network conditions for /debug/netsim
+    pub netsim: NetSimulator,
+
+    // Per-connection session captures for /debug/capture
+    pub capture: SessionCapture,
+
+    // Named arena presets for /debug/arena-presets (switches are taken by the game session)
+    pub arena_presets: ArenaPresets,
+
+    // Bytes written to clients by message category, overall and per connection (/debug/egress)
+    pub egress: EgressStats,
+
+    // Directory match replays are written to, for /debug/replay
+    pub replay_dir: Option<String>,
+
+    // Directory highlight clips are written to, for /highlights
+    pub highlight_dir: Option<String>,
+
+    // Cluster mode (see `cluster`): view of all instances and cluster-wide gauges
+    pub cluster: ClusterState,
+    pub cluster_players: AtomicU64,            // Human players across all instances
+    pub cluster_instances: AtomicU64,          // Live instances (including this one)
+
+    // Health and readiness probe inputs (/healthz, /readyz)
+    last_tick_at: RwLock<Option<Instant>>,
+    pub accepting_connections: AtomicBool,     // Transport is accepting connections
+    pub draining: AtomicBool,                  // Drain mode: finish current games, take no new traffic
+    pub lobby_ready: AtomicBool,               // Lobby state initialized
+    probes: ProbeConfig,
 }
 
 impl Metrics {
@@ -164,6 +791,16 @@ impl Metrics {
             performance_status: AtomicU64::new(0),
             budget_usage_percent: AtomicU64::new(0),
             tick_count: AtomicU64::new(0),
+            hibernating: AtomicBool::new(false),
+            safe_mode: AtomicBool::new(false),
+            safe_mode_incidents_total: AtomicU64::new(0),
+            tick_spikes_total: AtomicU64::new(0),
+            tick_profiles_total: AtomicU64::new(0),
+            aoi_enters_total: AtomicU64::new(0),
+            aoi_exits_total: AtomicU64::new(0),
+            aoi_shared_queries_total: AtomicU64::new(0),
+            broadcast_frames_dropped_total: AtomicU64::new(0),
+            region_hints_total: AtomicU64::new(0),
             connections_active: AtomicU64::new(0),
             messages_sent: AtomicU64::new(0),
             messages_received: AtomicU64::new(0),
@@ -191,6 +828,9 @@ impl Metrics {
             anticheat_inputs_rejected: AtomicU64::new(0),
             anticheat_inputs_sanitized: AtomicU64::new(0),
             anticheat_sequence_violations: AtomicU64::new(0),
+            anticheat_violations: LabeledCounters::default(),
+            anticheat_sanctions: LabeledCounters::default(),
+            anticheat_suspicion: RwLock::new(vec![0; SUSPICION_BUCKETS.len() + 1]),
             // DoS metrics
             dos_connections_rejected: AtomicU64::new(0),
             dos_messages_rate_limited: AtomicU64::new(0),
@@ -207,8 +847,11 @@ impl Metrics {
             bot_ai_full_mode: AtomicU64::new(0),
             bot_ai_reduced_mode: AtomicU64::new(0),
             bot_ai_dormant_mode: AtomicU64::new(0),
+            bot_ai_watched_zones: AtomicU64::new(0),
+            bot_ai_cold: AtomicU64::new(0),
             bot_ai_lod_scale: AtomicU64::new(100), // 1.0x default
             bot_ai_health_status: AtomicU64::new(0),
+            bot_ai_batches: RwLock::new(BatchTimings::default()),
             // Spectator metrics
             spectators_total: AtomicU64::new(0),
             spectators_full_view: AtomicU64::new(0),
@@ -227,9 +870,27 @@ impl Metrics {
             spawn_projectiles_total: AtomicU64::new(0),
             kills_total: AtomicU64::new(0),
             deaths_arena_total: AtomicU64::new(0),
+            deaths_total: Default::default(),
+            kill_rate: RwLock::new(RateWindow::default()),
             // Network quality
             network_write_failures_total: AtomicU64::new(0),
             broadcast_latency_us: AtomicU64::new(0),
+            send_bytes_total: AtomicU64::new(0),
+            send_burst_peak_bytes: AtomicU64::new(0),
+            send_burst: BurstMeter::new(),
+            send_rate: RwLock::new(RateWindow::default()),
+            snapshot_rate_low_clients: AtomicU64::new(0),
+            snapshot_rate_normal_clients: AtomicU64::new(0),
+            snapshot_rate_high_clients: AtomicU64::new(0),
+            snapshot_rate_changes_total: AtomicU64::new(0),
+            snapshot_resyncs_total: AtomicU64::new(0),
+            snapshot_resync_bytes_saved: AtomicU64::new(0),
+            // Desync detection
+            desync_checks_total: AtomicU64::new(0),
+            desync_mismatches_total: AtomicU64::new(0),
+            desync_players: AtomicU64::new(0),
+            client_jitter_avg_ms: AtomicU64::new(0),
+            interp_delay_avg_ms: AtomicU64::new(0),
             // Delta compression
             delta_updates_sent: AtomicU64::new(0),
             full_updates_sent: AtomicU64::new(0),
@@ -244,7 +905,399 @@ impl Metrics {
             avg_snapshot_size_bytes: AtomicU64::new(0),
             compression_ratio: AtomicU64::new(0),
             tick_history: RwLock::new(VecDeque::with_capacity(1000)),
+            input_stats: RwLock::new(Vec::new()),
+            snapshot_history: SnapshotHistory::new(&SnapshotHistoryConfig::from_env()),
+            state_view: Arc::new(PublishedState::default()),
+            heatmaps: Heatmaps::new(&HeatmapConfig::from_env()),
+            balance: BalanceAnalytics::new(Instant::now()),
+            netsim: NetSimulator::new(&NetSimConfig::from_env()),
+            capture: SessionCapture::new(&CaptureConfig::from_env()),
+            arena_presets: ArenaPresets::new(&ArenaPresetConfig::from_env()),
+            egress: EgressStats::default(),
+            replay_dir: ReplayConfig::from_env().dir,
+            highlight_dir: HighlightConfig::from_env().dir,
+            cluster: ClusterState::new(&ClusterConfig::from_env()),
+            cluster_players: AtomicU64::new(0),
+            cluster_instances: AtomicU64::new(0),
+            last_tick_at: RwLock::new(None),
+            accepting_connections: AtomicBool::new(false),
+            draining: AtomicBool::new(false),
+            lobby_ready: AtomicBool::new(false),
+            probes: ProbeConfig::from_env(),
+        }
+    }
+
+    /// Handle `/debug/snapshot-diff`: returns (status line, JSON body)
+    fn snapshot_diff_response(&self, request: &str) -> (&'static str, String) {
+        let error = |status, message: String| (status, serde_json::json!({ "error": message }).to_string());
+
+        let (from, to) = match (query_param(request, "from"), query_param(request, "to")) {
+            (Some(from), Some(to)) => match (from.parse::<u64>(), to.parse::<u64>()) {
+                (Ok(from), Ok(to)) => (from, to),
+                _ => return error("400 Bad Request", "from and to must be tick numbers".to_string()),
+            },
+            _ => return error("400 Bad Request", "from and to are required".to_string()),
+        };
+        let epsilon = match query_param(request, "epsilon").map(|e| e.parse::<f32>()) {
+            None => self.snapshot_history.default_epsilon(),
+            Some(Ok(e)) if e >= 0.0 => e,
+            Some(_) => return error("400 Bad Request", "epsilon must be a non-negative number".to_string()),
+        };
+
+        match self.snapshot_history.diff(from, to, epsilon) {
+            Ok(diff) => ("200 OK", serde_json::to_string(&diff).unwrap_or_else(|_| "{}".to_string())),
+            Err(e) => error("404 Not Found", e.to_string()),
+        }
+    }
+
+    /// Count a death toward the per-cause totals and the balance window
+    pub fn record_death(&self, cause: DeathCause) {
+        let index = DeathCause::ALL.iter().position(|&c| c == cause).expect("every cause is listed");
+        self.deaths_total[index].fetch_add(1, Ordering::Relaxed);
+        if cause == DeathCause::Boundary {
+            self.deaths_arena_total.fetch_add(1, Ordering::Relaxed);
         }
+        self.balance.record(cause, Instant::now());
+    }
+
+    /// Handle `/analytics/balance`: returns (status line, JSON body)
+    fn balance_response(&self) -> (&'static str, String) {
+        let report = self.balance.report(Instant::now());
+        ("200 OK", serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()))
+    }
+
+    /// Handle `/analytics/heatmap`: returns (status line, content type, body)
+    fn heatmap_response(&self, request: &str) -> (&'static str, &'static str, Vec<u8>) {
+        let error = |status, message: &str| {
+            (status, "application/json", serde_json::json!({ "error": message }).to_string().into_bytes())
+        };
+
+        if !self.heatmaps.is_enabled() {
+            return error("404 Not Found", "heatmaps are disabled (set HEATMAP_ENABLED)");
+        }
+        let Some(layer) = query_param(request, "layer").and_then(HeatmapLayer::from_name) else {
+            return error("400 Bad Request", "layer must be occupancy, kills or well_deaths");
+        };
+        let size = match query_param(request, "size").map(|s| s.parse::<usize>()) {
+            None => HEATMAP_DEFAULT_SIZE,
+            Some(Ok(size)) if (1..=HEATMAP_MAX_SIZE).contains(&size) => size,
+            Some(_) => return error("400 Bad Request", "size must be 1-512"),
+        };
+
+        let image = self.heatmaps.export(layer, size);
+        match query_param(request, "format").unwrap_or("json") {
+            "json" => ("200 OK", "application/json", serde_json::to_vec(&image).unwrap_or_default()),
+            "png" => ("200 OK", "image/png", image.to_png()),
+            _ => error("400 Bad Request", "format must be json or png"),
+        }
+    }
+
+    /// Handle `/debug/netsim`: list or show simulated conditions, or set or clear them with a POST; returns
+    /// (status line, JSON body)
+    fn netsim_response(&self, request: &str) -> (&'static str, String) {
+        let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+
+        if !self.netsim.is_enabled() {
+            return error("404 Not Found", "network simulator is disabled (set NETSIM_ENABLED)");
+        }
+        let Some(player) = query_param(request, "player") else {
+            let connections: Vec<_> = self
+                .netsim
+                .all()
+                .into_iter()
+                .map(|(player_id, conditions)| serde_json::json!({ "player_id": player_id, "conditions": conditions }))
+                .collect();
+            return ("200 OK", serde_json::json!({ "connections": connections }).to_string());
+        };
+        let Ok(player_id) = player.parse::<PlayerId>() else {
+            return error("400 Bad Request", "player must be a player id");
+        };
+        if !request.starts_with("POST ") {
+            let changes = ["delay_ms", "jitter_ms", "loss", "reorder", "clear"];
+            if changes.iter().any(|name| query_param(request, name).is_some()) {
+                return error("405 Method Not Allowed", "setting or clearing conditions changes it, use POST");
+            }
+            let conditions = self.netsim.conditions(player_id);
+            return ("200 OK", serde_json::json!({ "player_id": player_id, "conditions": conditions }).to_string());
+        }
+
+        if matches!(query_param(request, "clear"), Some("true") | Some("1")) {
+            let cleared = self.netsim.clear(player_id);
+            return ("200 OK", serde_json::json!({ "player_id": player_id, "cleared": cleared }).to_string());
+        }
+
+        let conditions = match (
+            parsed_param(request, "delay_ms"),
+            parsed_param(request, "jitter_ms"),
+            parsed_param(request, "loss"),
+        ) {
+            (Ok(delay_ms), Ok(jitter_ms), Ok(loss)) => NetConditions {
+                delay_ms: delay_ms.unwrap_or(0),
+                jitter_ms: jitter_ms.unwrap_or(0),
+                loss: loss.unwrap_or(0.0),
+                reorder: matches!(query_param(request, "reorder"), Some("true") | Some("1")),
+            },
+            (Err(name), _, _) | (_, Err(name), _) | (_, _, Err(name)) => {
+                return error("400 Bad Request", &format!("{} is not a valid number", name));
+            }
+        };
+        match self.netsim.set(player_id, conditions) {
+            Ok(()) => ("200 OK", serde_json::json!({ "player_id": player_id, "conditions": conditions }).to_string()),
+            Err(e) => error("400 Bad Request", &e.to_string()),
+        }
+    }
+
+    /// Handle `/debug/arena-presets`: list or show presets, or with a POST save
+    /// the running arena (`save=name`) or switch to a preset (`apply=name`,
+    /// optional `secs`); returns (status line, JSON body)
+    fn arena_presets_response(&self, request: &str) -> (&'static str, String) {
+        /// Longest transition accepted from the admin API (seconds)
+        const MAX_TRANSITION_SECS: f32 = 30.0;
+
+        let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+        let preset_json = |preset: &ArenaPreset| serde_json::to_value(preset).unwrap_or_default();
+        let presets = &self.arena_presets;
+
+        let changes_arena = query_param(request, "save").is_some() || query_param(request, "apply").is_some();
+        if changes_arena && !request.starts_with("POST ") {
+            return error("405 Method Not Allowed", "saving or applying a preset changes the arena, use POST");
+        }
+        if let Some(name) = query_param(request, "save") {
+            let preset = ArenaPreset::capture(name, &self.state_view.load());
+            return match presets.save(preset.clone()) {
+                Ok(()) => {
+                    info!("Arena preset {} saved ({} wells)", name, preset.layout.wells.len());
+                    ("200 OK", serde_json::json!({ "saved": preset_json(&preset) }).to_string())
+                }
+                Err(e @ PresetError::Io(_)) => error("500 Internal Server Error", &e.to_string()),
+                Err(e) => error("400 Bad Request", &e.to_string()),
+            };
+        }
+        if let Some(name) = query_param(request, "apply") {
+            let secs = match parsed_param::<f32>(request, "secs") {
+                Ok(secs) => secs.unwrap_or(presets.transition_secs),
+                Err(name) => return error("400 Bad Request", &format!("{} is not a valid number", name)),
+            };
+            if !(0.0..=MAX_TRANSITION_SECS).contains(&secs) {
+                return error("400 Bad Request", &format!("secs must be 0-{}", MAX_TRANSITION_SECS));
+            }
+            return match presets.request_apply(name, secs) {
+                Ok(preset) => {
+                    ("200 OK", serde_json::json!({ "applying": preset_json(&preset), "secs": secs }).to_string())
+                }
+                Err(e) => error("404 Not Found", &e.to_string()),
+            };
+        }
+        if let Some(name) = query_param(request, "name") {
+            return match presets.get(name) {
+                Some(preset) => ("200 OK", preset_json(&preset).to_string()),
+                None => error("404 Not Found", &PresetError::NotFound(name.to_string()).to_string()),
+            };
+        }
+        let list = serde_json::json!({ "presets": presets.names(), "transition_secs": presets.transition_secs });
+        ("200 OK", list.to_string())
+    }
+
+    /// Handle `/debug/egress`: bytes sent per message category and the
+    /// connections sent the most (`top`, default 10); returns (status line, JSON body)
+    fn egress_response(&self, request: &str) -> (&'static str, String) {
+        /// Connections listed when the request doesn't say
+        const DEFAULT_TOP_TALKERS: usize = 10;
+        /// Most connections listed by one request
+        const MAX_TOP_TALKERS: usize = 100;
+
+        let top = match parsed_param::<usize>(request, "top") {
+            Ok(top) => top.unwrap_or(DEFAULT_TOP_TALKERS).min(MAX_TOP_TALKERS),
+            Err(name) => {
+                let body = serde_json::json!({ "error": format!("{} must be a number", name) });
+                return ("400 Bad Request", body.to_string());
+            }
+        };
+        let categories: serde_json::Map<String, serde_json::Value> = MessageCategory::ALL
+            .iter()
+            .map(|&c| (c.name().to_string(), self.egress.total(c).into()))
+            .collect();
+        let body = serde_json::json!({
+            "bytes": categories,
+            "top_talkers": self.egress.top_talkers(top),
+        });
+        ("200 OK", body.to_string())
+    }
+
+    /// Handle `/debug/capture`: list or dump session captures, or start or stop one with a POST; returns (status
+    /// line, JSON body)
+    fn capture_response(&self, request: &str) -> (&'static str, String) {
+        let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+        let flag = |name: &str| matches!(query_param(request, name), Some("true") | Some("1"));
+
+        if !self.capture.is_enabled() {
+            return error("404 Not Found", "session capture is disabled (set CAPTURE_MODE)");
+        }
+        let Some(player) = query_param(request, "player") else {
+            let captures: Vec<_> = self
+                .capture
+                .list()
+                .into_iter()
+                .map(|(player_id, entries)| serde_json::json!({ "player_id": player_id, "entries": entries }))
+                .collect();
+            return ("200 OK", serde_json::json!({ "captures": captures }).to_string());
+        };
+        let Ok(player_id) = player.parse::<PlayerId>() else {
+            return error("400 Bad Request", "player must be a player id");
+        };
+        let changes = query_param(request, "start").is_some() || query_param(request, "stop").is_some();
+        if changes && !request.starts_with("POST ") {
+            return error("405 Method Not Allowed", "starting or stopping a capture changes it, use POST");
+        }
+
+        if flag("start") {
+            return match self.capture.start(player_id) {
+                Ok(()) => ("200 OK", serde_json::json!({ "player_id": player_id, "capturing": true }).to_string()),
+                Err(e) => error("400 Bad Request", &e.to_string()),
+            };
+        }
+        if flag("stop") {
+            let stopped = self.capture.stop(player_id);
+            return ("200 OK", serde_json::json!({ "player_id": player_id, "stopped": stopped }).to_string());
+        }
+        match self.capture.dump(player_id) {
+            Ok(dump) => ("200 OK", serde_json::to_string(&dump).unwrap_or_else(|_| "{}".to_string())),
+            Err(e) => error("404 Not Found", &e.to_string()),
+        }
+    }
+
+    /// Handle `/debug/replay`: list replays, show one's index or seek to a tick; returns (status line, JSON body)
+    fn replay_response(&self, request: &str) -> (&'static str, String) {
+        let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+
+        let Some(dir) = &self.replay_dir else {
+            return error("404 Not Found", "replays are disabled (set REPLAY_DIR)");
+        };
+        let Some(file) = query_param(request, "file") else {
+            let mut replays: Vec<String> = std::fs::read_dir(dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter_map(|entry| entry.file_name().into_string().ok())
+                        .filter(|name| name.ends_with(&format!(".{}", REPLAY_EXTENSION)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            replays.sort();
+            return ("200 OK", serde_json::json!({ "replays": replays }).to_string());
+        };
+        // A plain file name, never a path out of the replay directory
+        if file.contains(['/', '\\']) || file.starts_with('.') || !file.ends_with(&format!(".{}", REPLAY_EXTENSION)) {
+            return error("400 Bad Request", "file must be a replay file name");
+        }
+        let tick = match parsed_param::<u64>(request, "tick") {
+            Ok(tick) => tick,
+            Err(_) => return error("400 Bad Request", "tick must be a number"),
+        };
+
+        let file = match std::fs::File::open(std::path::Path::new(dir).join(file)) {
+            Ok(file) => file,
+            Err(_) => return error("404 Not Found", "replay not found"),
+        };
+        let mut reader = match ReplayReader::open(std::io::BufReader::new(file)) {
+            Ok(reader) => reader,
+            Err(e) => return error("400 Bad Request", &e.to_string()),
+        };
+        let Some(tick) = tick else {
+            return ("200 OK", serde_json::to_string(reader.index()).unwrap_or_else(|_| "{}".to_string()));
+        };
+        match reader.seek(tick) {
+            Ok(playback) => ("200 OK", serde_json::to_string(playback.current()).unwrap_or_else(|_| "{}".to_string())),
+            Err(e) => error("404 Not Found", &e.to_string()),
+        }
+    }
+
+    /// Handle `/highlights`: the newest highlight clips with their moments; returns (status line, JSON body)
+    fn highlights_response(&self, request: &str) -> (&'static str, String) {
+        let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+
+        let Some(dir) = &self.highlight_dir else {
+            return error("404 Not Found", "highlights are disabled (set HIGHLIGHT_DIR)");
+        };
+        let limit = match parsed_param::<usize>(request, "limit") {
+            Ok(limit) if limit != Some(0) => limit.unwrap_or(HIGHLIGHTS_DEFAULT_LIMIT).min(HIGHLIGHTS_MAX_LIMIT),
+            _ => return error("400 Bad Request", "limit must be a positive number"),
+        };
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter(|name| name.ends_with(&format!(".{}", REPLAY_EXTENSION)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Names start with the Unix time they were cut
+        files.sort_by(|a, b| b.cmp(a));
+
+        let highlights: Vec<_> = files
+            .into_iter()
+            .filter_map(|file| {
+                let input = std::fs::File::open(std::path::Path::new(dir).join(&file)).ok()?;
+                let reader = ReplayReader::open(std::io::BufReader::new(input)).ok()?;
+                let index = reader.index();
+                let (chapter, player, highlight) = index.chapters.iter().find_map(|c| match &c.kind {
+                    ChapterKind::Highlight { player, highlight } => Some((c, player, highlight)),
+                    _ => None,
+                })?;
+                let duration_ticks = index.last_tick - index.first_tick;
+                Some(serde_json::json!({
+                    "file": file,
+                    "tick": chapter.tick,
+                    "player_id": player,
+                    "kind": highlight.name(),
+                    "details": highlight,
+                    "title": chapter.title,
+                    "duration_secs": duration_ticks as f32 / crate::game::constants::physics::TICK_RATE as f32,
+                }))
+            })
+            .take(limit)
+            .collect();
+        ("200 OK", serde_json::json!({ "highlights": highlights }).to_string())
+    }
+
+    /// Publish the last second's peak send burst and start a new window
+    pub fn roll_send_burst_window(&self) {
+        self.send_burst_peak_bytes.store(self.send_burst.take_peak(), Ordering::Relaxed);
+    }
+
+    /// Sample the last second's kills and bytes sent
+    pub fn roll_rate_windows(&self) {
+        self.kill_rate.write().roll(self.kills_total.load(Ordering::Relaxed));
+        self.send_rate.write().roll(self.send_bytes_total.load(Ordering::Relaxed));
+    }
+
+    /// Replace the published per-player input stats
+    pub fn set_input_stats(&self, stats: Vec<PlayerInputStats>) {
+        *self.input_stats.write() = stats;
+    }
+
+    /// Publish the per-behavior batch costs of the last AI update
+    pub fn set_ai_batch_timings(&self, timings: BatchTimings) {
+        *self.bot_ai_batches.write() = timings;
+    }
+
+    /// Replace the suspicion distribution with the scores of the connected players
+    #[allow(dead_code)] // Fed by the anticheat feature
+    pub fn set_suspicion_scores(&self, scores: impl IntoIterator<Item = u32>) {
+        let mut buckets = vec![0; SUSPICION_BUCKETS.len() + 1];
+        for score in scores {
+            let first = SUSPICION_BUCKETS.iter().position(|&le| score <= le).unwrap_or(SUSPICION_BUCKETS.len());
+            for bucket in &mut buckets[first..] {
+                *bucket += 1;
+            }
+        }
+        *self.anticheat_suspicion.write() = buckets;
+    }
+
+    /// Per-player input stats as JSON
+    pub fn input_stats_json(&self) -> String {
+        serde_json::to_string(&*self.input_stats.read()).unwrap_or_else(|_| "[]".to_string())
     }
 
     /// Record a tick time and update percentiles
@@ -252,6 +1305,7 @@ impl Metrics {
         let us = duration.as_micros() as u64;
         self.tick_time_us.store(us, Ordering::Relaxed);
         self.tick_count.fetch_add(1, Ordering::Relaxed);
+        *self.last_tick_at.write() = Some(Instant::now());
 
         // Update rolling history for percentiles
         let mut history = self.tick_history.write();
@@ -276,6 +1330,80 @@ impl Metrics {
         }
     }
 
+    /// Liveness: the game loop has ticked recently (and, when strict, isn't catastrophically over budget)
+    pub fn health(&self) -> ProbeReport {
+        let tick_age_ms = self.last_tick_at.read().map(|at| at.elapsed().as_millis() as u64);
+        // Before the first tick, the grace period runs from startup
+        let age_ms = tick_age_ms.unwrap_or_else(|| self.start_time.elapsed().as_millis() as u64);
+        let mut report = ProbeReport::default();
+        report.check("game_loop_ticking", age_ms <= self.probes.max_tick_age_ms);
+        if self.probes.strict {
+            report.check("tick_budget", self.performance_status.load(Ordering::Relaxed) < 4);
+        }
+        report
+    }
+
+    /// Readiness: accepting connections, not draining, lobby initialized and the game loop running
+    pub fn readiness(&self) -> ProbeReport {
+        let mut report = ProbeReport::default();
+        report.check("accepting_connections", self.accepting_connections.load(Ordering::Relaxed));
+        report.check("not_draining", !self.draining.load(Ordering::Relaxed));
+        report.check("lobby_initialized", self.lobby_ready.load(Ordering::Relaxed));
+        report.check("game_loop_started", self.last_tick_at.read().is_some());
+        if self.probes.strict {
+            report.check("tick_budget", self.performance_status.load(Ordering::Relaxed) < 3);
+        }
+        report
+    }
+
+    /// Handle `/cluster/*`: status of every instance, or a placement for a party (a POST, since it reserves
+    /// seats); returns (status line, JSON body)
+    fn cluster_response(&self, request: &str) -> (&'static str, String) {
+        let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+
+        if request.starts_with("GET /cluster/place") {
+            return error("405 Method Not Allowed", "placing a party reserves seats, use POST");
+        }
+        if request.starts_with("POST /cluster/place") {
+            let party_size = match parsed_param::<usize>(request, "party_size") {
+                Ok(size) if size != Some(0) && size <= Some(CLUSTER_MAX_PARTY_SIZE) => size.unwrap_or(1),
+                _ => {
+                    let message = format!("party_size must be between 1 and {}", CLUSTER_MAX_PARTY_SIZE);
+                    return error("400 Bad Request", &message);
+                }
+            };
+            return match self.cluster.place_party(party_size) {
+                Ok(placement) => ("200 OK", serde_json::to_string(&placement).unwrap_or_else(|_| "{}".to_string())),
+                Err(e @ ClusterError::Inactive) => error("404 Not Found", &e.to_string()),
+                Err(e) => error("503 Service Unavailable", &e.to_string()),
+            };
+        }
+        let body = serde_json::json!({
+            "active": self.cluster.is_active(),
+            "players": self.cluster.total_players(),
+            "instances": self.cluster.instances(),
+        });
+        ("200 OK", body.to_string())
+    }
+
+    /// Handle `/debug/drain`: show drain mode, or toggle it with `POST ?enabled=true|false`; returns (status
+    /// line, JSON body)
+    fn drain_response(&self, request: &str) -> (&'static str, String) {
+        let error = |status, message: &str| (status, serde_json::json!({ "error": message }).to_string());
+
+        let enabled = query_param(request, "enabled");
+        if enabled.is_some() && !request.starts_with("POST ") {
+            return error("405 Method Not Allowed", "toggling drain mode changes server state, use POST");
+        }
+        match enabled {
+            Some("true") | Some("1") => self.draining.store(true, Ordering::Relaxed),
+            Some("false") | Some("0") => self.draining.store(false, Ordering::Relaxed),
+            Some(_) => return error("400 Bad Request", "enabled must be true or false"),
+            None => {}
+        }
+        ("200 OK", serde_json::json!({ "draining": self.draining.load(Ordering::Relaxed) }).to_string())
+    }
+
     /// Get uptime in seconds
     pub fn uptime_seconds(&self) -> u64 {
         self.start_time.elapsed().as_secs()
@@ -324,6 +1452,30 @@ impl Metrics {
             self.tick_time_max_us.load(Ordering::Relaxed));
         metric!("orbit_royale_tick_count", "Total ticks processed", "counter",
             self.tick_count.load(Ordering::Relaxed));
+        metric!("orbit_royale_hibernating", "Whether the idle server is hibernating (1=yes)", "gauge",
+            self.hibernating.load(Ordering::Relaxed) as u8);
+        metric!("orbit_royale_safe_mode", "Whether parameters are frozen after an incident (1=yes)", "gauge",
+            self.safe_mode.load(Ordering::Relaxed) as u8);
+        metric!("orbit_royale_safe_mode_incidents_total", "Incidents that froze parameters", "counter",
+            self.safe_mode_incidents_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_tick_spikes_total", "Ticks over the watchdog spike threshold", "counter",
+            self.tick_spikes_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_tick_profiles_total", "Profiles captured around tick spikes", "counter",
+            self.tick_profiles_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_ai_arena_high_water_bytes", "Most bytes the AI tick arena used in one tick", "gauge",
+            crate::util::arena::ai_high_water_bytes());
+        metric!("orbit_royale_net_arena_high_water_bytes", "Most bytes a network thread's tick arena used in one tick",
+            "gauge", crate::util::arena::net_high_water_bytes());
+        metric!("orbit_royale_aoi_enters_total", "Entities that entered a client's AOI", "counter",
+            self.aoi_enters_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_aoi_exits_total", "Entities that left a client's AOI", "counter",
+            self.aoi_exits_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_aoi_shared_queries_total", "AOI queries served from a candidate list shared by a cell",
+            "counter", self.aoi_shared_queries_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_broadcast_frames_dropped_total", "Broadcast frames dropped while the pool was behind",
+            "counter", self.broadcast_frames_dropped_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_region_hints_total", "Region hints sent to players",
+            "counter", self.region_hints_total.load(Ordering::Relaxed));
 
         // Budget metrics
         metric!("orbit_royale_performance_status", "Performance status (0=Excellent, 4=Catastrophic)", "gauge",
@@ -374,6 +1526,12 @@ impl Metrics {
         metric!("orbit_royale_uptime_seconds", "Server uptime in seconds", "counter",
             self.uptime_seconds());
 
+        // Cluster mode (zero unless the cluster bus is connected)
+        metric!("orbit_royale_cluster_players", "Human players across all cluster instances", "gauge",
+            self.cluster_players.load(Ordering::Relaxed));
+        metric!("orbit_royale_cluster_instances", "Live cluster instances", "gauge",
+            self.cluster_instances.load(Ordering::Relaxed));
+
         // Simulation mode metrics
         metric!("orbit_royale_simulation_enabled", "Simulation mode enabled (0/1)", "gauge",
             self.simulation_enabled.load(Ordering::Relaxed));
@@ -406,6 +1564,25 @@ impl Metrics {
                 self.anticheat_inputs_sanitized.load(Ordering::Relaxed));
             metric!("orbit_royale_anticheat_sequence_violations", "Sequence validation failures", "counter",
                 self.anticheat_sequence_violations.load(Ordering::Relaxed));
+            output.push_str(&self.anticheat_violations.to_prometheus(
+                "orbit_royale_anticheat_violations_total",
+                "Failed input validations by violation type",
+                "type",
+            ));
+            output.push_str(&self.anticheat_sanctions.to_prometheus(
+                "orbit_royale_anticheat_sanctions_total",
+                "Sanctions issued by tier",
+                "tier",
+            ));
+            output.push_str(
+                "# HELP orbit_royale_anticheat_suspicion_players Connected players by rejected or sanitized inputs \
+                 this session (cumulative buckets)\n# TYPE orbit_royale_anticheat_suspicion_players gauge\n",
+            );
+            let suspicion = self.anticheat_suspicion.read();
+            let bounds = SUSPICION_BUCKETS.iter().map(|le| le.to_string()).chain(["+Inf".to_string()]);
+            for (le, players) in bounds.zip(suspicion.iter()) {
+                output.push_str(&format!("orbit_royale_anticheat_suspicion_players{{le=\"{}\"}} {}\n", le, players));
+            }
 
             // DoS protection metrics
             metric!("orbit_royale_dos_connections_rejected", "Connections rejected by DoS protection", "counter",
@@ -448,6 +1625,10 @@ impl Metrics {
             self.bot_ai_reduced_mode.load(Ordering::Relaxed));
         metric!("orbit_royale_bot_ai_dormant_mode", "Bots in dormant mode (far from humans)", "gauge",
             self.bot_ai_dormant_mode.load(Ordering::Relaxed));
+        metric!("orbit_royale_bot_ai_watched_zones", "AI zones on some human's screen (bots there run full AI)", "gauge",
+            self.bot_ai_watched_zones.load(Ordering::Relaxed));
+        metric!("orbit_royale_bot_ai_cold", "Bots frozen in cold storage (far from every human, no AI)", "gauge",
+            self.bot_ai_cold.load(Ordering::Relaxed));
 
         // LOD scale (stored as x100, display as float)
         let lod_scale = self.bot_ai_lod_scale.load(Ordering::Relaxed);
@@ -472,6 +1653,30 @@ impl Metrics {
             health_name
         ));
 
+        // Per-behavior AI batch costs (last update)
+        let batches = self.bot_ai_batches.read().by_behavior();
+        let mut batch_family = |name: &str, help: &str, value: fn(&crate::game::systems::ai_soa::BatchTiming) -> u64| {
+            output.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n", name, help, name));
+            for (behavior, timing) in &batches {
+                output.push_str(&format!("{}{{behavior=\"{}\"}} {}\n", name, behavior, value(timing)));
+            }
+        };
+        batch_family("orbit_royale_bot_ai_batch_us", "Time spent on each behavior batch (microseconds)", |t| t.micros);
+        batch_family("orbit_royale_bot_ai_batch_size", "Bots in each behavior batch", |t| t.size as u64);
+        batch_family(
+            "orbit_royale_bot_ai_batch_parallel",
+            "Whether each behavior batch took the parallel path (0/1)",
+            |t| t.parallel as u64,
+        );
+        if let Some(behavior) = self.bot_ai_batches.read().dominant() {
+            output.push_str(&format!(
+                "# HELP orbit_royale_bot_ai_dominant_behavior Behavior batch that cost the most last update\n\
+                 # TYPE orbit_royale_bot_ai_dominant_behavior gauge\n\
+                 orbit_royale_bot_ai_dominant_behavior{{behavior=\"{}\"}} 1\n",
+                behavior
+            ));
+        }
+
         // Spectator metrics
         metric!("orbit_royale_spectators_total", "Active spectator count", "gauge",
             self.spectators_total.load(Ordering::Relaxed));
@@ -507,12 +1712,61 @@ impl Metrics {
             self.kills_total.load(Ordering::Relaxed));
         metric!("orbit_royale_deaths_arena_total", "Deaths from arena boundary", "counter",
             self.deaths_arena_total.load(Ordering::Relaxed));
+        output.push_str("# HELP orbit_royale_deaths_total Deaths by cause\n# TYPE orbit_royale_deaths_total counter\n");
+        for (cause, deaths) in DeathCause::ALL.iter().zip(&self.deaths_total) {
+            output.push_str(&format!(
+                "orbit_royale_deaths_total{{cause=\"{}\"}} {}\n",
+                cause.name(),
+                deaths.load(Ordering::Relaxed)
+            ));
+        }
 
         // Network quality metrics
         metric!("orbit_royale_network_write_failures_total", "Failed network writes", "counter",
             self.network_write_failures_total.load(Ordering::Relaxed));
         metric!("orbit_royale_broadcast_latency_microseconds", "Broadcast time", "gauge",
             self.broadcast_latency_us.load(Ordering::Relaxed));
+        metric!("orbit_royale_send_bytes_total", "Bytes written to client streams", "counter",
+            self.send_bytes_total.load(Ordering::Relaxed));
+        output.push_str(
+            "# HELP orbit_royale_egress_bytes_total Bytes written to clients by message category\n\
+             # TYPE orbit_royale_egress_bytes_total counter\n",
+        );
+        for category in MessageCategory::ALL {
+            output.push_str(&format!(
+                "orbit_royale_egress_bytes_total{{category=\"{}\"}} {}\n",
+                category.name(),
+                self.egress.total(category)
+            ));
+        }
+        metric!("orbit_royale_send_burst_peak_bytes", "Peak bytes written across clients in one 5ms window", "gauge",
+            self.send_burst_peak_bytes.load(Ordering::Relaxed));
+        metric!("orbit_royale_snapshot_rate_low_clients", "Players receiving 5Hz snapshots", "gauge",
+            self.snapshot_rate_low_clients.load(Ordering::Relaxed));
+        metric!("orbit_royale_snapshot_rate_normal_clients", "Players receiving 10Hz snapshots", "gauge",
+            self.snapshot_rate_normal_clients.load(Ordering::Relaxed));
+        metric!("orbit_royale_snapshot_rate_high_clients", "Players receiving 20Hz snapshots", "gauge",
+            self.snapshot_rate_high_clients.load(Ordering::Relaxed));
+        metric!("orbit_royale_snapshot_rate_changes_total", "Adaptive snapshot rate switches", "counter",
+            self.snapshot_rate_changes_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_snapshot_resyncs_total", "Full resyncs sent as changes since the acked base", "counter",
+            self.snapshot_resyncs_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_snapshot_resync_bytes_saved_total", "Bytes saved by resyncing from the acked base",
+            "counter", self.snapshot_resync_bytes_saved.load(Ordering::Relaxed));
+
+        // Desync detection metrics
+        metric!("orbit_royale_desync_checks_total", "Client state hashes compared", "counter",
+            self.desync_checks_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_desync_mismatches_total", "Client state hashes that mismatched", "counter",
+            self.desync_mismatches_total.load(Ordering::Relaxed));
+        metric!("orbit_royale_desync_players", "Players with a sustained prediction desync", "gauge",
+            self.desync_players.load(Ordering::Relaxed));
+
+        // Interpolation delay negotiation
+        metric!("orbit_royale_client_jitter_avg_milliseconds", "Mean measured client input jitter", "gauge",
+            self.client_jitter_avg_ms.load(Ordering::Relaxed));
+        metric!("orbit_royale_interp_delay_avg_milliseconds", "Mean recommended interpolation delay", "gauge",
+            self.interp_delay_avg_ms.load(Ordering::Relaxed));
 
         // Delta compression metrics
         metric!("orbit_royale_delta_updates_sent", "Delta update messages sent", "counter",
@@ -638,6 +1892,44 @@ impl Metrics {
 
 }
 
+/// Outcome of a health or readiness probe
+#[derive(Debug, Clone, Default)]
+pub struct ProbeReport {
+    pub ok: bool,
+    /// Individual checks by name (true = passing)
+    pub checks: Vec<(&'static str, bool)>,
+}
+
+impl ProbeReport {
+    fn check(&mut self, name: &'static str, passed: bool) {
+        self.checks.push((name, passed));
+        self.ok = self.checks.iter().all(|&(_, passed)| passed);
+    }
+
+    /// HTTP response: 200 when every check passes, 503 otherwise
+    fn to_http(&self) -> Vec<u8> {
+        let status = if self.ok { "200 OK" } else { "503 Service Unavailable" };
+        let checks: serde_json::Map<String, serde_json::Value> =
+            self.checks.iter().map(|&(name, passed)| (name.to_string(), passed.into())).collect();
+        let body = serde_json::json!({ "ok": self.ok, "checks": checks }).to_string();
+        http_response(status, "application/json", body)
+    }
+}
+
+/// HTTP response with a body, closing the connection
+fn http_response(status: &str, content_type: &str, body: impl AsRef<[u8]>) -> Vec<u8> {
+    let body = body.as_ref();
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
 /// AI Manager metrics for JSON endpoint
 #[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
@@ -683,6 +1975,8 @@ pub struct AIOutcomeSummary {
     pub success: bool,
     pub performance_delta_us: i64,
     pub player_delta: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
 }
 
 impl Default for Metrics {
@@ -691,16 +1985,39 @@ impl Default for Metrics {
     }
 }
 
-/// Start the metrics HTTP server
-pub async fn start_metrics_server(metrics: Arc<Metrics>, port: u16) -> anyhow::Result<()> {
+/// Start the metrics HTTP server, serving until `shutdown` is triggered
+pub async fn start_metrics_server(
+    metrics: Arc<Metrics>,
+    port: u16,
+    lobby: Arc<tokio::sync::RwLock<LobbyManagerType>>,
+    shutdown: ShutdownToken,
+) -> anyhow::Result<()> {
     let addr = format!("0.0.0.0:{}", port);
     let listener = TcpListener::bind(&addr).await?;
 
     info!("Metrics server listening on http://{}/metrics", addr);
 
+    // Opt-in: per-player stats are only served when explicitly enabled
+    let input_stats_enabled = std::env::var("INPUT_STATS_ENDPOINT")
+        .map(|v| v.to_lowercase() == "true" || v == "1")
+        .unwrap_or(false);
+    let presence_config = Arc::new(PresenceConfig::from_env());
+    let public_api = Arc::new(PublicApi::from_config(&PublicApiConfig::from_env()));
+
+    let stopped = shutdown.triggered();
+    tokio::pin!(stopped);
     loop {
-        let (mut socket, peer) = listener.accept().await?;
+        let (mut socket, peer) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = &mut stopped => {
+                info!("Metrics server stopped");
+                return Ok(());
+            }
+        };
         let metrics = metrics.clone();
+        let lobby = lobby.clone();
+        let presence_config = presence_config.clone();
+        let public_api = public_api.clone();
 
         tokio::spawn(async move {
             let mut buffer = [0u8; 1024];
@@ -710,32 +2027,132 @@ pub async fn start_metrics_server(metrics: Arc<Metrics>, port: u16) -> anyhow::R
                     let request = String::from_utf8_lossy(&buffer[..n]);
 
                     // Parse the request line
-                    let response = if request.starts_with("GET /metrics") {
-                        let body = metrics.to_prometheus();
-                        format!(
-                            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-                            body.len(),
-                            body
-                        )
+                    let response = if let Err(denied) = authorize_request(&request, RoleRegistry::global(), peer.ip()) {
+                        debug!("Metrics request from {} denied: {}", peer, denied);
+                        b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+                    } else if request.starts_with("GET /tenant/") || request.starts_with("POST /tenant/") {
+                        match TenantRegistry::global().resolve(bearer_token(&request)) {
+                            Some(tenant) => {
+                                let (status, content_type, body) =
+                                    tenant_response(&request, tenant, &mut *lobby.write().await);
+                                http_response(status, content_type, body)
+                            }
+                            None => b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+                        }
+                    } else if request.starts_with("GET /tenants/metrics") {
+                        // Every tenant's usage, for the operator (an admin route, unlike `/tenant/metrics`)
+                        #[cfg(feature = "lobby")]
+                        let body = tenant_prometheus(&lobby.read().await.all_tenant_usage());
+                        #[cfg(not(feature = "lobby"))]
+                        let body = String::new();
+                        http_response("200 OK", "text/plain; version=0.0.4", body)
+                    } else if request.starts_with("GET /metrics") {
+                        #[allow(unused_mut)]
+                        let mut body = metrics.to_prometheus();
+                        #[cfg(feature = "lobby")]
+                        body.push_str(&queue_prometheus(&lobby.read().await.queue_report(Instant::now())));
+                        http_response("200 OK", "text/plain; version=0.0.4", body)
                     } else if request.starts_with("GET /metrics/json") || request.starts_with("GET /json") {
-                        let body = metrics.to_json();
-                        format!(
-                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-                            body.len(),
-                            body
-                        )
+                        http_response("200 OK", "application/json", metrics.to_json())
+                    } else if input_stats_enabled && request.starts_with("GET /players/input-stats") {
+                        http_response("200 OK", "application/json", metrics.input_stats_json())
+                    } else if request.starts_with("GET /debug/snapshot-diff") {
+                        let (status, body) = metrics.snapshot_diff_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /debug/state") {
+                        http_response("200 OK", "application/json", metrics.state_view.summary_json())
+                    } else if request.starts_with("GET /debug/netsim") || request.starts_with("POST /debug/netsim") {
+                        let (status, body) = metrics.netsim_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /debug/ghost") || request.starts_with("POST /debug/ghost") {
+                        let (status, body) = ghost_response(&request, &metrics.capture, &mut *lobby.write().await);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /debug/replay") {
+                        let (status, body) = metrics.replay_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /debug/arena-presets")
+                        || request.starts_with("POST /debug/arena-presets")
+                    {
+                        let (status, body) = metrics.arena_presets_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /debug/egress") {
+                        let (status, body) = metrics.egress_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /debug/capture") || request.starts_with("POST /debug/capture") {
+                        let (status, body) = metrics.capture_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /analytics/balance") {
+                        let (status, body) = metrics.balance_response();
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /analytics/heatmap") {
+                        let (status, content_type, body) = metrics.heatmap_response(&request);
+                        http_response(status, content_type, body)
+                    } else if request.starts_with("GET /cluster/") || request.starts_with("POST /cluster/place") {
+                        let (status, body) = metrics.cluster_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /highlights") {
+                        let (status, body) = metrics.highlights_response(&request);
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /matches") {
+                        #[cfg(feature = "lobby")]
+                        let (status, body) = matches_response(&request, lobby.read().await.match_history());
+                        #[cfg(not(feature = "lobby"))]
+                        let (status, body) =
+                            ("404 Not Found", r#"{"error":"match history requires the lobby feature"}"#.to_string());
+                        http_response(status, "application/json", body)
+                    } else if request.starts_with("GET /api/") {
+                        match public_api.admit(bearer_token(&request), Instant::now()) {
+                            Ok((_, rate)) => {
+                                let target = request.lines().next().and_then(|l| l.split_whitespace().nth(1));
+                                let lobby = lobby.read().await;
+                                let (status, body) = public_api.cached(target.unwrap_or(""), Instant::now(), || {
+                                    public_api_route(&request, &metrics, &lobby)
+                                });
+                                format!(
+                                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nCache-Control: public, max-age={}\r\nX-RateLimit-Limit: {}\r\nX-RateLimit-Remaining: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                                    status,
+                                    public_api.cache_secs(),
+                                    rate.limit,
+                                    rate.remaining,
+                                    body.len(),
+                                    body
+                                )
+                                .into_bytes()
+                            }
+                            Err(PublicApiError::UnknownKey) => {
+                                b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+                            }
+                            Err(PublicApiError::RateLimited { retry_after_secs }) => format!(
+                                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                                retry_after_secs
+                            )
+                            .into_bytes(),
+                        }
+                    } else if request.starts_with("GET /presence/") {
+                        if presence_config.allows(bearer_token(&request)) {
+                            let (status, body) =
+                                presence_response(&request, &metrics.state_view, &*lobby.read().await);
+                            http_response(status, "application/json", body)
+                        } else {
+                            b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+                        }
+                    } else if request.starts_with("GET /info") {
+                        let body = info_json(&FeatureSet::compiled(), &FeatureSettings::from_env());
+                        http_response("200 OK", "application/json", body)
+                    } else if request.starts_with("GET /healthz") {
+                        metrics.health().to_http()
+                    } else if request.starts_with("GET /readyz") {
+                        metrics.readiness().to_http()
+                    } else if request.starts_with("GET /debug/drain") || request.starts_with("POST /debug/drain") {
+                        let (status, body) = metrics.drain_response(&request);
+                        http_response(status, "application/json", body)
                     } else if request.starts_with("GET /health") || request.starts_with("GET /") {
-                        let body = "OK";
-                        format!(
-                            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-                            body.len(),
-                            body
-                        )
+                        http_response("200 OK", "text/plain", "OK")
                     } else {
-                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                        b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
                     };
 
-                    if let Err(e) = socket.write_all(response.as_bytes()).await {
+                    if let Err(e) = socket.write_all(&response).await {
                         debug!("Failed to write metrics response to {}: {}", peer, e);
                     }
                 }
@@ -751,6 +2168,485 @@ pub async fn start_metrics_server(metrics: Arc<Metrics>, port: u16) -> anyhow::R
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::net::Ipv4Addr;
+    use crate::config::RolesConfig;
+
+    #[test]
+    fn test_query_param() {
+        let request = "GET /debug/snapshot-diff?from=10&to=20&epsilon=0.5 HTTP/1.1\r\nHost: x\r\n\r\n";
+        assert_eq!(query_param(request, "from"), Some("10"));
+        assert_eq!(query_param(request, "epsilon"), Some("0.5"));
+        assert_eq!(query_param(request, "tick"), None);
+        assert_eq!(query_param("GET /debug/snapshot-diff HTTP/1.1\r\n", "from"), None);
+    }
+
+    #[test]
+    fn test_http_response_counts_body_bytes() {
+        let response = http_response("200 OK", "image/png", [0x89, b'P', b'N', b'G']);
+        let expected = b"HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: 4\r\nConnection: close\r\n\r\n\x89PNG";
+        assert_eq!(response, expected.to_vec());
+    }
+
+    #[test]
+    fn test_authorize_request_admin_routes() {
+        let registry = RoleRegistry::from_config(&RolesConfig {
+            admin_tokens: vec!["root".to_string()],
+            vip_tokens: vec!["gold".to_string()],
+            ..Default::default()
+        });
+        let stats = |auth: &str| format!("GET /players/input-stats HTTP/1.1\r\n{}\r\n", auth);
+
+        let remote = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+        let local = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        assert!(authorize_request("GET /metrics HTTP/1.1\r\n\r\n", &registry, remote).is_ok());
+        assert!(authorize_request(&stats(""), &registry, remote).is_err());
+        assert!(authorize_request(&stats("Authorization: Bearer gold"), &registry, remote).is_err());
+        assert!(authorize_request(&stats("Authorization: Bearer root"), &registry, remote).is_ok());
+        assert!(authorize_request("GET /debug/snapshot-diff?from=1&to=2 HTTP/1.1\r\n\r\n", &registry, remote).is_err());
+        assert!(authorize_request("GET /analytics/heatmap?layer=kills HTTP/1.1\r\n\r\n", &registry, remote).is_err());
+        // Configured tokens apply to loopback peers too
+        assert!(authorize_request(&stats(""), &registry, local).is_err());
+        // No roles configured: admin routes are closed to the network, open to loopback
+        assert!(authorize_request(&stats(""), &RoleRegistry::default(), remote).is_err());
+        assert!(authorize_request(&stats(""), &RoleRegistry::default(), local).is_ok());
+        assert!(authorize_request("GET /healthz HTTP/1.1\r\n\r\n", &RoleRegistry::default(), remote).is_ok());
+    }
+
+    #[test]
+    fn test_route_permission_defaults_to_admin() {
+        let route = |line: &str| route_permission(&format!("{} HTTP/1.1\r\n\r\n", line));
+        assert_eq!(route("GET /metrics"), None);
+        assert_eq!(route("GET /metrics/json"), None);
+        assert_eq!(route("GET /matches/42"), None);
+        assert_eq!(route("GET /api/v1/server"), None);
+        assert_eq!(route("GET /"), None);
+        assert_eq!(route("GET /debug/drain?enabled=true"), Some(Permission::AdminApi));
+        assert_eq!(route("POST /cluster/place?party_size=2"), Some(Permission::AdminApi));
+        // Tenants check their own key when changing rooms
+        assert_eq!(route("POST /tenant/rooms/create"), None);
+        assert_eq!(route("POST /tenants/metrics"), Some(Permission::AdminApi));
+        // Unlisted routes and methods are admin routes
+        assert_eq!(route("GET /healthzz"), Some(Permission::AdminApi));
+        assert_eq!(route("GET /new-route"), Some(Permission::AdminApi));
+        assert_eq!(route("GET /tenants/metrics"), Some(Permission::AdminApi));
+        assert_eq!(route("POST /metrics"), Some(Permission::AdminApi));
+    }
+
+    #[test]
+    fn test_cluster_place_is_a_bounded_post() {
+        let metrics = Metrics::new();
+        let place = |line: &str| metrics.cluster_response(&format!("{} HTTP/1.1\r\n\r\n", line)).0;
+
+        assert_eq!(place("GET /cluster/place?party_size=2"), "405 Method Not Allowed");
+        assert_eq!(place("POST /cluster/place?party_size=0"), "400 Bad Request");
+        assert_eq!(place("POST /cluster/place?party_size=1000000"), "400 Bad Request");
+        // Within bounds, placement reaches the (inactive) cluster
+        assert_eq!(place("POST /cluster/place?party_size=4"), "404 Not Found");
+    }
+
+    #[test]
+    fn test_arena_presets_route_saves_and_applies() {
+        let request = |query: &str| format!("GET /debug/arena-presets{} HTTP/1.1\r\n\r\n", query);
+        let post = |query: &str| format!("POST /debug/arena-presets{} HTTP/1.1\r\n\r\n", query);
+        let mut metrics = Metrics::new();
+        metrics.arena_presets = ArenaPresets::new(&ArenaPresetConfig::default());
+
+        // Nothing has been published yet
+        assert_eq!(metrics.arena_presets_response(&post("?save=finals")).0, "400 Bad Request");
+        let preset: ArenaPreset = serde_json::from_str(
+            r#"{ "name": "finals", "escape_radius": 2000, "wells": [{"position": {"x": 900, "y": 0}}] }"#,
+        )
+        .unwrap();
+        metrics.arena_presets.save(preset).unwrap();
+
+        let (status, body) = metrics.arena_presets_response(&request(""));
+        assert_eq!(status, "200 OK");
+        assert!(body.contains("\"presets\":[\"finals\"]"));
+        assert!(metrics.arena_presets_response(&request("?name=finals")).1.contains("\"escape_radius\":2000"));
+
+        // GET stays read-only
+        assert_eq!(metrics.arena_presets_response(&request("?apply=finals")).0, "405 Method Not Allowed");
+        assert_eq!(metrics.arena_presets_response(&request("?save=warmup")).0, "405 Method Not Allowed");
+        assert!(metrics.arena_presets.take_request().is_none());
+
+        assert_eq!(metrics.arena_presets_response(&post("?apply=warmup")).0, "404 Not Found");
+        assert_eq!(metrics.arena_presets_response(&post("?apply=finals&secs=90")).0, "400 Bad Request");
+        assert_eq!(metrics.arena_presets_response(&post("?apply=finals&secs=5")).0, "200 OK");
+        let (preset, secs) = metrics.arena_presets.take_request().unwrap();
+        assert_eq!((preset.name(), secs), ("finals", 5.0));
+    }
+
+    #[test]
+    fn test_egress_route_lists_categories_and_top_talkers() {
+        let request = |query: &str| format!("GET /debug/egress{} HTTP/1.1\r\n\r\n", query);
+        let metrics = Metrics::new();
+        let (quiet, loud) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let quiet_egress = metrics.egress.register(quiet);
+        let loud_egress = metrics.egress.register(loud);
+        metrics.egress.record(Some(&quiet_egress), MessageCategory::Chat, 36);
+        metrics.egress.record(Some(&loud_egress), MessageCategory::Delta, 996);
+
+        let (status, body) = metrics.egress_response(&request("?top=1"));
+        assert_eq!(status, "200 OK");
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["bytes"]["delta"], 1000);
+        assert_eq!(json["bytes"]["snapshot"], 0);
+        assert_eq!(json["top_talkers"].as_array().unwrap().len(), 1);
+        assert_eq!(json["top_talkers"][0]["player_id"], loud.to_string());
+        assert_eq!(metrics.egress_response(&request("?top=lots")).0, "400 Bad Request");
+        assert!(metrics.to_prometheus().contains("orbit_royale_egress_bytes_total{category=\"chat\"} 40"));
+    }
+
+    #[test]
+    fn test_netsim_route_sets_and_clears_conditions() {
+        let request = |query: &str| format!("POST /debug/netsim{} HTTP/1.1\r\n\r\n", query);
+        let read = |query: &str| format!("GET /debug/netsim{} HTTP/1.1\r\n\r\n", query);
+        let mut metrics = Metrics::new();
+        metrics.netsim = NetSimulator::new(&NetSimConfig { enabled: true });
+        let player = uuid::Uuid::new_v4();
+
+        // A GET never degrades a connection
+        let set = format!("?player={}&delay_ms=120&loss=0.1", player);
+        assert_eq!(metrics.netsim_response(&read(&set)).0, "405 Method Not Allowed");
+        assert_eq!(metrics.netsim.conditions(player), None);
+
+        let (status, _) = metrics.netsim_response(&request(&set));
+        assert_eq!(status, "200 OK");
+        let conditions = metrics.netsim.conditions(player).unwrap();
+        assert_eq!((conditions.delay_ms, conditions.jitter_ms, conditions.reorder), (120, 0, false));
+
+        let (status, body) = metrics.netsim_response(&read(""));
+        assert_eq!(status, "200 OK");
+        assert!(body.contains(&player.to_string()));
+        let (status, body) = metrics.netsim_response(&read(&format!("?player={}", player)));
+        assert_eq!(status, "200 OK");
+        assert!(body.contains("\"delay_ms\":120"));
+
+        assert_eq!(metrics.netsim_response(&request(&format!("?player={}&loss=2", player))).0, "400 Bad Request");
+        assert_eq!(metrics.netsim_response(&request(&format!("?player={}&delay_ms=x", player))).0, "400 Bad Request");
+        assert_eq!(metrics.netsim_response(&request("?player=nobody")).0, "400 Bad Request");
+
+        let clear = format!("?player={}&clear=true", player);
+        assert_eq!(metrics.netsim_response(&read(&clear)).0, "405 Method Not Allowed");
+        metrics.netsim_response(&request(&clear));
+        assert_eq!(metrics.netsim.conditions(player), None);
+    }
+
+    #[test]
+    fn test_capture_route_dumps_opt_in_capture() {
+        use crate::config::CaptureMode;
+        use crate::net::protocol::{ClientMessage, PlayerInput};
+
+        let request = |query: &str| format!("GET /debug/capture{} HTTP/1.1\r\n\r\n", query);
+        let post = |query: &str| format!("POST /debug/capture{} HTTP/1.1\r\n\r\n", query);
+        let mut metrics = Metrics::new();
+        metrics.capture = SessionCapture::new(&CaptureConfig { mode: CaptureMode::OptIn, window_secs: 60 });
+        let player = uuid::Uuid::new_v4();
+        let start = format!("?player={}&start=true", player);
+
+        assert_eq!(metrics.capture_response(&request(&format!("?player={}", player))).0, "404 Not Found");
+        assert_eq!(metrics.capture_response(&request(&start)).0, "405 Method Not Allowed");
+        assert_eq!(metrics.capture_response(&post(&start)).0, "200 OK");
+        metrics.capture.record_inbound(player, &ClientMessage::Input(PlayerInput { sequence: 42, ..Default::default() }));
+
+        let (status, body) = metrics.capture_response(&request(&format!("?player={}", player)));
+        assert_eq!(status, "200 OK");
+        let dump: crate::net::capture::CaptureDump = serde_json::from_str(&body).unwrap();
+        assert_eq!(dump.inbound().count(), 1);
+    }
+
+    #[test]
+    fn test_replay_route_seeks_into_saved_replays() {
+        use crate::net::replay::ReplayWriter;
+
+        let request = |query: &str| format!("GET /debug/replay{} HTTP/1.1\r\n\r\n", query);
+        let dir = std::env::temp_dir().join(format!("orbit_replay_route_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut writer = ReplayWriter::new(std::fs::File::create(dir.join("match-1-0.orbr")).unwrap(), 10).unwrap();
+        let mut snapshot = (*PublishedState::default().load().snapshot).clone();
+        for tick in 0..15 {
+            snapshot.tick = tick;
+            writer.record(&snapshot).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let mut metrics = Metrics::new();
+        assert_eq!(metrics.replay_response(&request("")).0, "404 Not Found");
+        metrics.replay_dir = Some(dir.to_str().unwrap().to_string());
+        let (status, body) = metrics.replay_response(&request(""));
+        assert_eq!((status, body.as_str()), ("200 OK", r#"{"replays":["match-1-0.orbr"]}"#));
+        assert_eq!(metrics.replay_response(&request("?file=../secrets.orbr")).0, "400 Bad Request");
+
+        let (status, body) = metrics.replay_response(&request("?file=match-1-0.orbr&tick=12"));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(status, "200 OK");
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap()["tick"], 12);
+    }
+
+    #[test]
+    fn test_highlights_route_lists_clips_newest_first() {
+        use crate::net::highlights::HighlightKind;
+        use crate::net::replay::{Chapter, ReplayWriter};
+
+        let dir = std::env::temp_dir().join(format!("orbit_highlights_route_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut snapshot = (*PublishedState::default().load().snapshot).clone();
+        for (file, kills) in [("highlight-100-5-multi_kill.orbr", 3), ("highlight-200-9-multi_kill.orbr", 4)] {
+            let mut writer = ReplayWriter::new(std::fs::File::create(dir.join(file)).unwrap(), 30).unwrap();
+            for tick in 0..=60 {
+                snapshot.tick = tick;
+                writer.record(&snapshot).unwrap();
+            }
+            let highlight = HighlightKind::MultiKill { kills };
+            let kind = ChapterKind::Highlight { player: uuid::Uuid::nil(), highlight };
+            writer.chapter(Chapter { tick: 30, kind, title: format!("{} kills", kills) });
+            writer.finish().unwrap();
+        }
+
+        let mut metrics = Metrics::new();
+        metrics.highlight_dir = Some(dir.to_str().unwrap().to_string());
+        let (status, body) = metrics.highlights_response("GET /highlights?limit=1 HTTP/1.1\r\n\r\n");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(status, "200 OK");
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let highlights = body["highlights"].as_array().unwrap();
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0]["title"], "4 kills");
+        assert_eq!(highlights[0]["kind"], "multi_kill");
+        assert_eq!(highlights[0]["duration_secs"], 2.0);
+    }
+
+    #[cfg(feature = "lobby")]
+    #[test]
+    fn test_ghost_route_replays_captured_run() {
+        use crate::config::CaptureMode;
+        use crate::lobby::room::RoomKind;
+        use crate::net::capture::{LocalStateDigest, SnapshotDigest, SnapshotKind};
+
+        let capture = SessionCapture::new(&CaptureConfig { mode: CaptureMode::Ring, window_secs: 60 });
+        let mut lobby = LobbyManagerType::new(10);
+        let practice = lobby.create_room_of_kind("Practice".to_string(), RoomKind::Practice).unwrap();
+        let standard = lobby.create_room("Game".to_string()).unwrap();
+        let player = uuid::Uuid::new_v4();
+        let request =
+            |room: uuid::Uuid| format!("POST /debug/ghost?player={}&room={}&name=Ace HTTP/1.1\r\n\r\n", player, room);
+
+        let get = request(practice).replacen("POST", "GET", 1);
+        assert_eq!(ghost_response(&get, &capture, &mut lobby).0, "405 Method Not Allowed");
+        assert_eq!(ghost_response(&request(practice), &capture, &mut lobby).0, "404 Not Found");
+        let local = LocalStateDigest {
+            position: crate::util::vec2::Vec2::new(10.0, 20.0),
+            velocity: crate::util::vec2::Vec2::ZERO,
+            mass: 100.0,
+            alive: true,
+            state_hash: 0,
+        };
+        let digest =
+            SnapshotDigest { tick: 1, kind: SnapshotKind::Full, bytes: 100, players: 1, projectiles: 0, local: Some(local) };
+        capture.record_outbound(player, digest);
+
+        assert_eq!(ghost_response(&request(standard), &capture, &mut lobby).0, "400 Bad Request");
+        assert_eq!(ghost_response(&request(practice), &capture, &mut lobby).0, "200 OK");
+        let snapshot = lobby.get_room(practice).unwrap().get_snapshot();
+        assert!(snapshot.players.iter().any(|p| p.is_ghost() && p.name == "Ace"));
+    }
+
+    #[cfg(feature = "lobby")]
+    #[test]
+    fn test_matches_routes_page_through_history() {
+        use crate::game::match_result::MatchResult;
+        use crate::lobby::match_history::MatchHistory;
+
+        let mut history = MatchHistory::new(10);
+        let result = MatchResult {
+            winner_id: None,
+            winner_name: Some("Nova".to_string()),
+            rankings: vec![],
+            match_duration: 90.0,
+            total_kills: 3,
+            awards: vec![],
+            deaths_by_cause: vec![],
+        };
+        for _ in 0..3 {
+            history.record("standard", "Game 1", &result);
+        }
+        let get = |path: &str| {
+            let (status, body) = matches_response(&format!("GET /matches{} HTTP/1.1\r\n\r\n", path), &history);
+            (status, serde_json::from_str::<serde_json::Value>(&body).unwrap())
+        };
+
+        let (status, page) = get("?limit=2");
+        assert_eq!(status, "200 OK");
+        assert_eq!(page["matches"][0]["id"], 3);
+        assert_eq!(page["next_before"], 2);
+        let (_, page) = get("?limit=2&before=2");
+        assert_eq!(page["matches"].as_array().unwrap().len(), 1);
+        assert!(page["next_before"].is_null());
+        assert_eq!(get("?limit=0").0, "400 Bad Request");
+
+        let (status, summary) = get("/2");
+        assert_eq!((status, summary["winner_name"].as_str()), ("200 OK", Some("Nova")));
+        assert_eq!(get("/9").0, "404 Not Found");
+        assert_eq!(get("/abc").0, "400 Bad Request");
+    }
+
+    #[cfg(feature = "lobby")]
+    #[test]
+    fn test_presence_route_finds_players_in_lobby_rooms() {
+        use crate::lobby::manager::LobbyManager;
+        use crate::lobby::player::LobbyPlayer;
+        use crate::net::account::AccountId;
+        use crate::net::session::SessionToken;
+
+        let mut lobby = LobbyManager::new(10);
+        let room = lobby.create_room("Game 1".to_string()).unwrap();
+        let account = AccountId::generate();
+        let mut nova = LobbyPlayer::new(uuid::Uuid::new_v4(), "Nova".to_string(), SessionToken::generate());
+        nova.account = Some(account);
+        lobby.join_room(room, nova).unwrap();
+        // Players are only found by the account they joined with
+        let comet = LobbyPlayer::new(uuid::Uuid::new_v4(), "Comet".to_string(), SessionToken::generate());
+        let comet_account = AccountId::from(comet.id);
+        lobby.join_room(room, comet).unwrap();
+        let state_view = PublishedState::default();
+        let get = |account: &str| {
+            let request = format!("GET /presence/{} HTTP/1.1\r\n\r\n", account);
+            let (status, body) = presence_response(&request, &state_view, &lobby);
+            (status, serde_json::from_str::<serde_json::Value>(&body).unwrap())
+        };
+
+        let (status, body) = get(&account.to_string());
+        assert_eq!(status, "200 OK");
+        assert_eq!((body["status"].as_str(), body["arena"].as_str()), (Some("waiting"), Some("Game 1")));
+        assert_eq!(get(&comet_account.to_string()).1["status"], "offline");
+        assert_eq!(get("Nova").0, "400 Bad Request");
+
+        let config = PresenceConfig { relay_tokens: vec!["relay".to_string()] };
+        assert!(config.allows(Some("relay")));
+        assert!(!config.allows(Some("admin")));
+        assert!(!PresenceConfig::default().allows(None));
+    }
+
+    #[test]
+    fn test_public_api_routes() {
+        let metrics = Metrics::new();
+        let lobby = LobbyManagerType::default();
+        let get = |path: &str| {
+            let (status, body) = public_api_route(&format!("GET /api/v1/{} HTTP/1.1\r\n\r\n", path), &metrics, &lobby);
+            (status, serde_json::from_str::<serde_json::Value>(&body).unwrap())
+        };
+
+        let (status, server) = get("server");
+        assert_eq!((status, server["version"].as_str()), ("200 OK", Some(env!("CARGO_PKG_VERSION"))));
+        let (status, leaderboard) = get("leaderboard?limit=5");
+        assert_eq!((status, leaderboard["alive"].as_u64()), ("200 OK", Some(0)));
+        assert_eq!(get("leaderboard?limit=0").0, "400 Bad Request");
+        let account = crate::net::account::AccountId::generate();
+        assert_eq!(get(&format!("players/{}", account)).1["status"], "offline");
+        assert_eq!(get("debug/state").0, "404 Not Found");
+        #[cfg(feature = "lobby")]
+        assert_eq!(get("matches?limit=2").1["matches"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_info_lists_features_and_issues() {
+        let features = FeatureSet::compiled();
+        let settings = FeatureSettings { cluster_redis_url: !features.cluster, ..Default::default() };
+        let info: serde_json::Value = serde_json::from_str(&info_json(&features, &settings)).unwrap();
+        assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(info["features"].as_array().unwrap().len(), features.enabled().len());
+        // A cluster URL is only an error without the cluster feature
+        let has_error = info["issues"].as_array().unwrap().iter().any(|issue| issue["severity"] == "error");
+        assert_eq!(has_error, !features.cluster);
+    }
+
+    #[cfg(feature = "lobby")]
+    #[test]
+    fn test_tenant_routes_are_scoped_to_the_tenant() {
+        use crate::tenants::TenantQuota;
+
+        let request = |path: &str| format!("POST /tenant/{} HTTP/1.1\r\n\r\n", path);
+        let read = |path: &str| format!("GET /tenant/{} HTTP/1.1\r\n\r\n", path);
+        let mut lobby = LobbyManagerType::new(10);
+        let a = Tenant { id: "a".to_string(), quota: TenantQuota { max_rooms: 1, max_players: 20 } };
+        let b = Tenant { id: "b".to_string(), quota: TenantQuota::default() };
+
+        // Changing rooms takes a POST, reading them a GET
+        assert_eq!(tenant_response(&read("rooms/create?name=Cup"), &a, &mut lobby).0, "405 Method Not Allowed");
+        assert_eq!(tenant_response(&request("rooms"), &a, &mut lobby).0, "405 Method Not Allowed");
+        assert!(lobby.tenant_rooms(&a.id).is_empty());
+
+        let bad_seed = request("rooms/create?name=Cup&seed=AB%23");
+        assert_eq!(tenant_response(&bad_seed, &a, &mut lobby).0, "400 Bad Request");
+        let oversized = request("rooms/create?name=Cup&max_players=18446744073709551615");
+        assert_eq!(tenant_response(&oversized, &a, &mut lobby).0, "400 Bad Request");
+        let create = request("rooms/create?name=Cup&max_players=12&seed=1bcd-2ef");
+        let (status, _, body) = tenant_response(&create, &a, &mut lobby);
+        assert_eq!(status, "200 OK");
+        let room_id = serde_json::from_str::<serde_json::Value>(&body).unwrap()["id"].as_str().unwrap().to_string();
+        assert_eq!(tenant_response(&request("rooms/create"), &a, &mut lobby).0, "429 Too Many Requests");
+        let (_, _, body) = tenant_response(&read("rooms"), &a, &mut lobby);
+        assert!(body.contains("\"arena_code\":\"1BCD2EF\""));
+
+        let (_, _, body) = tenant_response(&read("rooms"), &b, &mut lobby);
+        assert!(!body.contains(&room_id));
+        let (_, _, body) = tenant_response(&read("metrics"), &a, &mut lobby);
+        assert!(body.contains("orbit_royale_tenant_capacity{tenant=\"a\"} 12"));
+        assert!(!body.contains("tenant=\"b\""));
+
+        // Players join by room id; the match runs once the tenant starts it
+        let start = request(&format!("rooms/start?room={}", room_id));
+        assert_eq!(tenant_response(&start, &a, &mut lobby).0, "409 Conflict");
+        let host = crate::lobby::player::LobbyPlayer::new(
+            uuid::Uuid::new_v4(),
+            "Host".to_string(),
+            crate::net::session::SessionToken::generate(),
+        );
+        lobby.join_room(room_id.parse().unwrap(), host).unwrap();
+        assert_eq!(tenant_response(&start, &b, &mut lobby).0, "404 Not Found");
+        assert_eq!(tenant_response(&start, &a, &mut lobby).0, "200 OK");
+
+        let close = request(&format!("rooms/close?room={}", room_id));
+        assert_eq!(tenant_response(&close, &b, &mut lobby).0, "404 Not Found");
+        assert_eq!(tenant_response(&close, &a, &mut lobby).0, "200 OK");
+    }
+
+    #[test]
+    fn test_probes() {
+        let mut metrics = Metrics::new();
+        metrics.probes = ProbeConfig { max_tick_age_ms: 1000, strict: false };
+
+        // Fresh process: alive (within the startup grace period) but not ready
+        assert!(metrics.health().ok);
+        assert!(!metrics.readiness().ok);
+
+        metrics.record_tick_time(Duration::from_micros(500));
+        metrics.accepting_connections.store(true, Ordering::Relaxed);
+        metrics.lobby_ready.store(true, Ordering::Relaxed);
+        assert!(metrics.readiness().ok);
+
+        // Toggling changes server state, so a plain GET (say, from a page on the host) can't
+        let drain = |line: &str| metrics.drain_response(&format!("{} HTTP/1.1\r\n\r\n", line)).0;
+        assert_eq!(drain("GET /debug/drain?enabled=true"), "405 Method Not Allowed");
+        assert!(metrics.readiness().ok);
+        assert_eq!(drain("POST /debug/drain?enabled=true"), "200 OK");
+        let readiness = metrics.readiness();
+        assert!(!readiness.ok);
+        assert!(readiness.checks.contains(&("not_draining", false)));
+        assert!(metrics.health().ok);
+
+        // Stalled game loop
+        *metrics.last_tick_at.write() = Some(Instant::now() - Duration::from_secs(2));
+        assert!(!metrics.health().ok);
+
+        // Strict mode also fails on a catastrophic tick budget
+        *metrics.last_tick_at.write() = Some(Instant::now());
+        metrics.performance_status.store(4, Ordering::Relaxed);
+        assert!(metrics.health().ok);
+        metrics.probes.strict = true;
+        assert!(!metrics.health().ok);
+    }
 
     #[test]
     fn test_metrics_new() {
@@ -857,4 +2753,43 @@ mod tests {
             assert!(output.contains("orbit_royale_ai_success_rate_percent 80")); // 8/10 = 80%
         }
     }
+
+    #[test]
+    fn test_ai_batch_timings_in_prometheus() {
+        use crate::game::systems::ai_soa::BatchTiming;
+
+        let metrics = Metrics::new();
+        metrics.set_ai_batch_timings(BatchTimings {
+            chase: BatchTiming { micros: 420, size: 300, parallel: true },
+            ..Default::default()
+        });
+        let output = metrics.to_prometheus();
+        assert!(output.contains("orbit_royale_bot_ai_batch_us{behavior=\"chase\"} 420"));
+        assert!(output.contains("orbit_royale_bot_ai_batch_size{behavior=\"chase\"} 300"));
+        assert!(output.contains("orbit_royale_bot_ai_batch_parallel{behavior=\"chase\"} 1"));
+        assert!(output.contains("orbit_royale_bot_ai_batch_us{behavior=\"orbit\"} 0"));
+        assert!(output.contains("orbit_royale_bot_ai_dominant_behavior{behavior=\"chase\"} 1"));
+    }
+
+    #[test]
+    fn test_anticheat_metrics_in_prometheus() {
+        let metrics = Metrics::new();
+        metrics.anticheat_violations.increment("invalid_aim");
+        metrics.anticheat_violations.increment("invalid_aim");
+        metrics.anticheat_sanctions.increment("kick");
+        metrics.set_suspicion_scores([0, 0, 3, 40, 500]);
+
+        #[allow(unused_variables)]
+        let output = metrics.to_prometheus();
+
+        #[cfg(feature = "metrics_extended")]
+        {
+            assert!(output.contains("orbit_royale_anticheat_violations_total{type=\"invalid_aim\"} 2"));
+            assert!(output.contains("orbit_royale_anticheat_sanctions_total{tier=\"kick\"} 1"));
+            assert!(output.contains("orbit_royale_anticheat_suspicion_players{le=\"0\"} 2"));
+            assert!(output.contains("orbit_royale_anticheat_suspicion_players{le=\"5\"} 3"));
+            assert!(output.contains("orbit_royale_anticheat_suspicion_players{le=\"100\"} 4"));
+            assert!(output.contains("orbit_royale_anticheat_suspicion_players{le=\"+Inf\"} 5"));
+        }
+    }
 }
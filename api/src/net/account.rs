@@ -0,0 +1,140 @@
+//! Player accounts
+//!
+//! A player's account is a random id the server issues on their first join,
+//! handed out as an account token: the id followed by its HMAC-SHA256 under the
+//! server's account secret. The client keeps the token and sends it with later
+//! joins (`JoinRequest.account_token`). A token whose signature checks out
+//! proves the client owns that account; anything else gets a fresh one. Chat
+//! mutes, reports and karma are keyed by account, so they follow a player
+//! across reconnects, and rich presence is published by account id, so only
+//! the token holder's play shows up under it.
+//!
+//! Tokens only stay valid across restarts with `ACCOUNT_SECRET` set; without
+//! it each process signs with a random key.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use rand::RngCore;
+use ring::hmac;
+use tracing::warn;
+
+use crate::config::AccountConfig;
+use crate::game::state::PlayerId;
+
+/// Global account token keys
+static ACCOUNT_KEYS: OnceLock<AccountKeys> = OnceLock::new();
+
+/// Length of an account id in bytes
+const ACCOUNT_ID_LEN: usize = 16;
+
+/// Server-issued player account id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AccountId([u8; ACCOUNT_ID_LEN]);
+
+impl AccountId {
+    /// A new random account id
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; ACCOUNT_ID_LEN];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Parse the hex form shown by `Display`
+    pub fn parse(hex: &str) -> Option<Self> {
+        if hex.len() != ACCOUNT_ID_LEN * 2 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let mut bytes = [0u8; ACCOUNT_ID_LEN];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+        }
+        Some(Self(bytes))
+    }
+}
+
+/// Connections that joined without an account (bots, tests) are their own account
+impl From<PlayerId> for AccountId {
+    fn from(player_id: PlayerId) -> Self {
+        Self(*player_id.as_bytes())
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+/// Issues and verifies account tokens
+pub struct AccountKeys {
+    key: hmac::Key,
+}
+
+impl AccountKeys {
+    pub fn new(secret: &[u8]) -> Self {
+        Self { key: hmac::Key::new(hmac::HMAC_SHA256, secret) }
+    }
+
+    /// Get the global keys (loads the secret from env on first call)
+    pub fn global() -> &'static Self {
+        ACCOUNT_KEYS.get_or_init(|| match AccountConfig::from_env().secret {
+            Some(secret) => Self::new(secret.as_bytes()),
+            None => {
+                warn!("ACCOUNT_SECRET not set: account tokens won't survive a restart");
+                let mut secret = [0u8; 32];
+                rand::thread_rng().fill_bytes(&mut secret);
+                Self::new(&secret)
+            }
+        })
+    }
+
+    /// Token proving ownership of an account
+    pub fn token(&self, account: AccountId) -> Vec<u8> {
+        let mut token = account.0.to_vec();
+        token.extend_from_slice(hmac::sign(&self.key, &account.0).as_ref());
+        token
+    }
+
+    /// Account of a token, if it was issued with these keys
+    pub fn verify(&self, token: &[u8]) -> Option<AccountId> {
+        if token.len() <= ACCOUNT_ID_LEN {
+            return None;
+        }
+        let (id, tag) = token.split_at(ACCOUNT_ID_LEN);
+        hmac::verify(&self.key, id, tag).ok()?;
+        let mut bytes = [0u8; ACCOUNT_ID_LEN];
+        bytes.copy_from_slice(id);
+        Some(AccountId(bytes))
+    }
+
+    /// Account of a join: the token's if it verifies, otherwise a new one
+    pub fn resolve(&self, token: Option<&[u8]>) -> AccountId {
+        token.and_then(|token| self.verify(token)).unwrap_or_else(AccountId::generate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_prove_account_ownership() {
+        let keys = AccountKeys::new(b"secret");
+        let account = AccountId::generate();
+        let token = keys.token(account);
+        assert_eq!(keys.verify(&token), Some(account));
+        assert_eq!(keys.resolve(Some(&token)), account);
+
+        // Forged, truncated or foreign tokens get no account
+        let mut forged = token.clone();
+        forged[0] ^= 1;
+        assert_eq!(keys.verify(&forged), None);
+        assert_eq!(keys.verify(&token[..ACCOUNT_ID_LEN]), None);
+        assert_eq!(AccountKeys::new(b"other").verify(&token), None);
+        assert_ne!(keys.resolve(Some(&forged)), account);
+
+        assert_eq!(AccountId::parse(&account.to_string().to_uppercase()), Some(account));
+        assert_eq!(AccountId::parse("not an account"), None);
+    }
+}
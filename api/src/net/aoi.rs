@@ -12,6 +12,14 @@
 //! **No hardcoded entity caps.** If an entity is within the dynamic radius,
 //! it's included. The viewport zoom IS the filter.
 //!
+//! ## Apparent-Size Culling
+//!
+//! Zoom also decides what is too small to see: an entity's apparent radius is
+//! its mass-derived world radius times the viewport zoom, and entities below
+//! `min_apparent_radius_px` are left out. Size, not distance, decides, so a
+//! large mass at the edge of the AOI is always sent. The local player and the
+//! top N players are never culled.
+//!
 //! ## Performance Characteristics
 //!
 //! Worst case (150 players clustered, max zoom out):
@@ -23,21 +31,40 @@
 //! - 20-50 players within view
 //! - ~2KB per tick
 //! - ~60KB/s bandwidth
+//!
+//! ## Hysteresis
+//!
+//! Entities hovering at the boundary would otherwise pop in and out between
+//! snapshots. Each viewer keeps an [`AoiMembership`]: an entity enters at the
+//! AOI radius but only leaves once it is beyond a larger exit radius AND has
+//! been visible for a minimum number of ticks.
+//!
+//! ## Incremental Filtering
+//!
+//! Broadcasts build an [`AoiIndex`] of the full snapshot once and filter each
+//! client through [`AOIManager::filter_indexed`], which only visits grid cells
+//! near the viewer; clients in the same cell share one candidate list and
+//! each trims it to its own radius. Hysteresis then only reconsiders the entities that left
+//! the entry radius, reporting enters and exits as [`AoiChanges`].
 
 use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 use smallvec::SmallVec;
 
+use crate::game::constants::mass_to_radius;
 use crate::game::state::PlayerId;
+use crate::net::aoi_index::AoiIndex;
 use crate::net::protocol::GameSnapshot;
+use crate::util::arena::{with_tick_arena, ArenaVec};
 use crate::util::vec2::Vec2;
 
 // Thread-local reusable buffers to avoid per-filter allocations
 thread_local! {
     /// Buffer for sorting players by score (top N calculation)
     static PLAYERS_BY_SCORE_BUFFER: RefCell<Vec<(PlayerId, u32)>> = RefCell::new(Vec::with_capacity(256));
-    /// Buffer for storing nearby players with distances (for sorting)
-    static NEARBY_WITH_DISTANCE_BUFFER: RefCell<Vec<(usize, f32)>> = RefCell::new(Vec::with_capacity(256));
 }
 
 // ============================================================================
@@ -76,6 +103,15 @@ const VELOCITY_EXPANSION_MAX_RATIO: f32 = 0.4;
 /// These players are included regardless of distance for gameplay purposes
 const DEFAULT_ALWAYS_INCLUDE_TOP_N: usize = 5;
 
+/// Default exit radius as a multiple of the entry radius
+const DEFAULT_EXIT_RADIUS_RATIO: f32 = 1.15;
+
+/// Default minimum ticks an entity stays visible once it entered (0.5s at 30 TPS)
+const DEFAULT_MIN_PERSISTENCE_TICKS: u64 = 15;
+
+/// Default minimum on-screen radius in pixels for an entity to be sent
+const DEFAULT_MIN_APPARENT_RADIUS_PX: f32 = 2.0;
+
 // ============================================================================
 // Pre-computed Constants (Avoid Runtime Calculation)
 // ============================================================================
@@ -127,6 +163,12 @@ fn calculate_velocity_expansion(speed: f32, base_radius: f32) -> f32 {
     if velocity_expansion < max_expansion { velocity_expansion } else { max_expansion }
 }
 
+/// On-screen radius in pixels of an entity with `world_radius` at `viewport_zoom`
+#[inline(always)]
+pub fn apparent_radius(world_radius: f32, viewport_zoom: f32) -> f32 {
+    world_radius * viewport_zoom
+}
+
 // ============================================================================
 // AOI Configuration
 // ============================================================================
@@ -137,28 +179,143 @@ pub struct AOIConfig {
     /// Always include top N players by score (for leaderboard visibility)
     /// Set to 0 to disable. These are included regardless of distance.
     pub always_include_top_n: usize,
+    /// Exit radius as a multiple of the entry radius (1.0 = no radius hysteresis)
+    pub exit_radius_ratio: f32,
+    /// Minimum ticks an entity stays visible once it entered (0 = none)
+    pub min_persistence_ticks: u64,
+    /// Entities whose apparent radius is below this many pixels are culled (0 = off)
+    pub min_apparent_radius_px: f32,
 }
 
 impl Default for AOIConfig {
     fn default() -> Self {
         Self {
             always_include_top_n: DEFAULT_ALWAYS_INCLUDE_TOP_N,
+            exit_radius_ratio: DEFAULT_EXIT_RADIUS_RATIO,
+            min_persistence_ticks: DEFAULT_MIN_PERSISTENCE_TICKS,
+            min_apparent_radius_px: DEFAULT_MIN_APPARENT_RADIUS_PX,
         }
     }
 }
 
+// ============================================================================
+// Per-Viewer Hysteresis State
+// ============================================================================
+
+/// An entity tracked for AOI hysteresis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum AoiEntity {
+    Player(PlayerId),
+    Projectile(u64),
+    Debris(u64),
+}
+
+/// Entities currently visible to one viewer, with the tick each entered
+#[derive(Debug, Default)]
+pub struct AoiMembership {
+    entered: HashMap<AoiEntity, u64>,
+}
+
+/// Membership changes from one [`AOIManager::apply_hysteresis`] call
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AoiChanges {
+    /// Entities that entered the viewer's AOI
+    pub entered: usize,
+    /// Entities that left it (or the game)
+    pub exited: usize,
+}
+
 // ============================================================================
 // AOI Manager
 // ============================================================================
 
+/// One viewer's filter parameters
+struct Viewer {
+    id: PlayerId,
+    position: Vec2,
+    radius: f32,
+    zoom: f32,
+}
+
+/// Snapshot indices to visit: the given ascending subset, or all `len`
+fn subset(indices: Option<&[u32]>, len: usize) -> impl Iterator<Item = usize> + '_ {
+    let all = indices.is_none().then_some(0..len).into_iter().flatten();
+    all.chain(indices.into_iter().flatten().map(|&i| i as usize))
+}
+
 /// Manages Area of Interest filtering for network optimization
+#[derive(Clone)]
 pub struct AOIManager {
     config: AOIConfig,
+    /// Multiplier on every viewer's radius (global modifiers, e.g. a dense nebula)
+    vision_scale: f32,
 }
 
 impl AOIManager {
     pub fn new(config: AOIConfig) -> Self {
-        Self { config }
+        Self { config, vision_scale: 1.0 }
+    }
+
+    /// Scale every viewer's radius (1.0 = normal vision)
+    pub fn set_vision_scale(&mut self, scale: f32) {
+        self.vision_scale = scale;
+    }
+
+    /// Entry radius for a viewer: zoom-based radius plus velocity lookahead
+    #[inline]
+    pub fn effective_radius(&self, player_velocity: Vec2, viewport_zoom: f32, arena_scale: f32) -> f32 {
+        let base_radius = calculate_base_radius(viewport_zoom, arena_scale) * self.vision_scale;
+        base_radius + calculate_velocity_expansion(player_velocity.length(), base_radius)
+    }
+
+    /// Minimum on-screen radius in pixels for an entity to be sent
+    pub fn min_apparent_radius_px(&self) -> f32 {
+        self.config.min_apparent_radius_px
+    }
+
+    /// Whether an entity of `world_radius` is big enough on screen to be sent
+    #[inline]
+    pub fn is_visible_size(&self, world_radius: f32, viewport_zoom: f32) -> bool {
+        apparent_radius(world_radius, viewport_zoom) >= self.config.min_apparent_radius_px
+    }
+
+    /// IDs of the top N players by kills (always sent, for leaderboard visibility)
+    fn top_player_ids(&self, full_snapshot: &GameSnapshot) -> SmallVec<[PlayerId; 16]> {
+        if self.config.always_include_top_n == 0 {
+            return SmallVec::new();
+        }
+        PLAYERS_BY_SCORE_BUFFER.with(|buffer_cell| {
+            let mut buffer = buffer_cell.borrow_mut();
+            buffer.clear();
+
+            // Collect (id, kills) pairs for sorting
+            buffer.extend(full_snapshot.players.iter().map(|p| (p.id, p.kills)));
+
+            // Sort by kills descending
+            buffer.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+
+            // Take top N player IDs
+            buffer
+                .iter()
+                .take(self.config.always_include_top_n)
+                .map(|(id, _)| *id)
+                .collect()
+        })
+    }
+
+    /// Remove players too small to see at `viewport_zoom` from an unfiltered
+    /// (spectator) snapshot. The top N players are kept regardless of size.
+    pub fn cull_small_players(&self, snapshot: &mut GameSnapshot, viewport_zoom: f32) {
+        let top_player_ids = self.top_player_ids(snapshot);
+        snapshot.players.retain(|p| {
+            top_player_ids.contains(&p.id) || self.is_visible_size(mass_to_radius(p.mass), viewport_zoom)
+        });
+    }
+
+    /// Check if `target` is inside a viewer's AOI radius (no velocity expansion)
+    pub fn is_in_range(&self, viewer_position: Vec2, viewport_zoom: f32, arena_scale: f32, target: Vec2) -> bool {
+        let radius = calculate_base_radius(viewport_zoom, arena_scale) * self.vision_scale;
+        viewer_position.distance_sq_to(target) <= radius * radius
     }
 
     /// Filter a game snapshot for a specific player based on their viewport and velocity
@@ -166,7 +323,8 @@ impl AOIManager {
     /// # Fully Dynamic Filtering
     ///
     /// **No hardcoded entity caps.** The dynamic radius (calculated from viewport_zoom)
-    /// is the ONLY filter. If an entity is within the radius, it's included.
+    /// is the only distance filter. If an entity is within the radius and not
+    /// below the minimum apparent size, it's included.
     ///
     /// This ensures:
     /// - No "invisible enemies" within view distance
@@ -185,16 +343,19 @@ impl AOIManager {
     /// A personalized snapshot containing:
     /// - The player themselves (always, first priority)
     /// - Top N players by score (for leaderboard, regardless of distance)
-    /// - ALL players within dynamic AOI radius (sorted by distance)
-    /// - ALL projectiles within dynamic AOI radius
-    /// - ALL debris within dynamic AOI radius
+    /// - ALL visible-size players within dynamic AOI radius (sorted by distance)
+    /// - ALL visible-size projectiles within dynamic AOI radius
+    /// - ALL visible-size debris within dynamic AOI radius
+    /// - Asteroids reaching into the AOI radius
     /// - All gravity wells (sparse and always important)
     ///
     /// # Performance
     /// - O(n log n) for player sorting by distance
-    /// - Uses thread-local buffers to avoid allocations
+    /// - Uses a thread-local buffer and the per-tick arena to avoid allocations
     /// - Pre-computes squared radius to avoid sqrt in distance checks
     /// - Inlined radius calculations
+    ///
+    /// Scans the whole snapshot; [`Self::filter_indexed`] is the per-broadcast path.
     #[inline]
     pub fn filter_for_player(
         &self,
@@ -205,124 +366,131 @@ impl AOIManager {
         arena_scale: f32,
         full_snapshot: &GameSnapshot,
     ) -> GameSnapshot {
-        // Calculate dynamic AOI radius from viewport zoom and arena scale
-        let base_radius = calculate_base_radius(viewport_zoom, arena_scale);
+        let viewer = self.viewer(player_id, player_position, player_velocity, viewport_zoom, arena_scale);
+        self.filter(&viewer, full_snapshot, None)
+    }
+
+    /// Same result as [`Self::filter_for_player`] on `index.snapshot()`, but
+    /// only visits the entities in index cells near the viewer
+    #[inline]
+    pub fn filter_indexed(
+        &self,
+        index: &AoiIndex,
+        player_id: PlayerId,
+        player_position: Vec2,
+        player_velocity: Vec2,
+        viewport_zoom: f32,
+        arena_scale: f32,
+    ) -> GameSnapshot {
+        let viewer = self.viewer(player_id, player_position, player_velocity, viewport_zoom, arena_scale);
+        self.filter(&viewer, index.snapshot(), Some(index))
+    }
 
-        // Expand AOI based on speed to prevent pop-in when moving fast
-        let speed = player_velocity.length();
-        let velocity_expansion = calculate_velocity_expansion(speed, base_radius);
-        let effective_radius = base_radius + velocity_expansion;
+    /// Index a full snapshot for [`Self::filter_indexed`] (once per broadcast)
+    pub fn build_index(&self, full_snapshot: Arc<GameSnapshot>) -> AoiIndex {
+        let top_players = self.top_player_ids(&full_snapshot);
+        AoiIndex::build(full_snapshot, &top_players)
+    }
 
+    fn viewer(&self, id: PlayerId, position: Vec2, velocity: Vec2, zoom: f32, arena_scale: f32) -> Viewer {
+        // Dynamic AOI radius from viewport zoom and arena scale, expanded
+        // based on speed to prevent pop-in when moving fast
+        Viewer { id, position, radius: self.effective_radius(velocity, zoom, arena_scale), zoom }
+    }
+
+    fn filter(&self, viewer: &Viewer, full_snapshot: &GameSnapshot, index: Option<&AoiIndex>) -> GameSnapshot {
         // OPTIMIZATION: Pre-compute squared radius to avoid sqrt in distance checks
-        let effective_radius_sq = effective_radius * effective_radius;
+        let radius_sq = viewer.radius * viewer.radius;
+        let in_view = |position: Vec2, world_radius: f32| {
+            (position - viewer.position).length_sq() <= radius_sq && self.is_visible_size(world_radius, viewer.zoom)
+        };
 
         // Pre-allocate with reasonable capacity (will grow if needed)
         let mut filtered_players = Vec::with_capacity(64);
         let mut filtered_projectiles = Vec::with_capacity(32);
         let mut filtered_debris = Vec::with_capacity(64);
 
-        // CRITICAL: First, find and add the local player BEFORE processing others
-        // This ensures they're always included
-        let mut player_found = false;
-        for player in &full_snapshot.players {
-            if player.id == player_id {
-                filtered_players.push(player.clone());
-                player_found = true;
-                break;
-            }
-        }
-
-        // Get top N players by score (for leaderboard visibility)
-        // These are included regardless of distance - important for gameplay
-        let top_player_ids: SmallVec<[PlayerId; 16]> = if self.config.always_include_top_n > 0 {
-            PLAYERS_BY_SCORE_BUFFER.with(|buffer_cell| {
-                let mut buffer = buffer_cell.borrow_mut();
-                buffer.clear();
-
-                // Collect (id, kills) pairs for sorting
-                buffer.extend(full_snapshot.players.iter().map(|p| (p.id, p.kills)));
-
-                // Sort by kills descending
-                buffer.sort_unstable_by(|a, b| b.1.cmp(&a.1));
-
-                // Take top N player IDs
-                buffer
-                    .iter()
-                    .take(self.config.always_include_top_n)
-                    .map(|(id, _)| *id)
-                    .collect()
-            })
-        } else {
-            SmallVec::new()
-        };
+        // With an index only entities near the viewer's cell are candidates (shared with its neighbors)
+        let shared = index.map(|index| index.candidates_near(viewer.position, viewer.radius));
+        with_tick_arena(|bump| {
+            // With an index the viewer and top players are lookups
+            let (own, top, candidates) = match index {
+                Some(index) => (index.player(viewer.id), index.top_players(), shared.as_deref()),
+                None => {
+                    let top_player_ids = self.top_player_ids(full_snapshot);
+                    let top = full_snapshot
+                        .players
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, p)| top_player_ids.contains(&p.id))
+                        .map(|(i, _)| i as u32);
+                    let own = full_snapshot.players.iter().position(|p| p.id == viewer.id);
+                    (own, ArenaVec::from_iter_in(top, bump).into_bump_slice(), None)
+                }
+            };
+            let player_candidates = candidates.map(|c| c.players.as_slice());
 
-        // Add top players (skip self, already added)
-        for player in &full_snapshot.players {
-            if player.id == player_id {
-                continue; // Already added
+            // CRITICAL: First, add the local player BEFORE processing others
+            // This ensures they're always included
+            if let Some(own) = own {
+                filtered_players.push(full_snapshot.players[own].clone());
             }
-            if top_player_ids.contains(&player.id) {
-                filtered_players.push(player.clone());
-            }
-        }
 
-        // Collect ALL nearby players within radius (no cap!)
-        // Sort by distance to ensure consistent ordering
-        let nearby_indices: Vec<usize> = NEARBY_WITH_DISTANCE_BUFFER.with(|buffer_cell| {
-            let mut buffer = buffer_cell.borrow_mut();
-            buffer.clear();
+            // Add top N players by score (for leaderboard visibility), skipping self
+            // These are included regardless of distance - important for gameplay
+            for &idx in top {
+                if Some(idx as usize) != own {
+                    filtered_players.push(full_snapshot.players[idx as usize].clone());
+                }
+            }
 
-            // Collect (index, distance_sq) pairs for players not already included
-            for (idx, p) in full_snapshot.players.iter().enumerate() {
-                if p.id == player_id || top_player_ids.contains(&p.id) {
+            // Collect ALL nearby players within radius (no cap!)
+            // Sort by distance to ensure consistent ordering
+            let mut nearby = ArenaVec::with_capacity_in(64, bump);
+            for idx in subset(player_candidates, full_snapshot.players.len()) {
+                if Some(idx) == own || top.binary_search(&(idx as u32)).is_ok() {
                     continue;
                 }
-                let distance_sq = (p.position - player_position).length_sq();
-                if distance_sq <= effective_radius_sq {
-                    buffer.push((idx, distance_sq));
+                let p = &full_snapshot.players[idx];
+                if in_view(p.position, mass_to_radius(p.mass)) {
+                    nearby.push((idx, (p.position - viewer.position).length_sq()));
                 }
             }
 
             // Sort by squared distance (closest first) - sqrt is monotonic so order is preserved
-            buffer.sort_unstable_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-
-            // Return ALL sorted indices (no cap!)
-            buffer.iter().map(|(idx, _)| *idx).collect()
-        });
+            nearby.sort_unstable_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
 
-        // Add ALL nearby players (no cap - dynamic radius is the only filter)
-        for idx in nearby_indices {
-            filtered_players.push(full_snapshot.players[idx].clone());
-        }
+            // Add ALL nearby players (no cap - dynamic radius is the only filter)
+            for &(idx, _) in &nearby {
+                filtered_players.push(full_snapshot.players[idx].clone());
+            }
 
-        // Fallback should never happen now, but keep for safety
-        if !player_found {
-            // Player not in snapshot - include all nearby players
-            filtered_players = full_snapshot.players
-                .iter()
-                .filter(|p| {
-                    let distance_sq = (p.position - player_position).length_sq();
-                    distance_sq <= effective_radius_sq
-                })
-                .cloned()
-                .collect();
-        }
+            // Fallback should never happen now, but keep for safety
+            if own.is_none() {
+                // Player not in snapshot - include all nearby players
+                filtered_players = subset(player_candidates, full_snapshot.players.len())
+                    .map(|idx| &full_snapshot.players[idx])
+                    .filter(|p| in_view(p.position, mass_to_radius(p.mass)))
+                    .cloned()
+                    .collect();
+            }
 
-        // Filter ALL projectiles within radius (no cap!)
-        for proj in &full_snapshot.projectiles {
-            let distance_sq = (proj.position - player_position).length_sq();
-            if distance_sq <= effective_radius_sq {
-                filtered_projectiles.push(proj.clone());
+            // Filter ALL projectiles within radius (no cap!), skipping sub-pixel ones
+            for idx in subset(candidates.map(|c| c.projectiles.as_slice()), full_snapshot.projectiles.len()) {
+                let proj = &full_snapshot.projectiles[idx];
+                if in_view(proj.position, mass_to_radius(proj.mass)) {
+                    filtered_projectiles.push(proj.clone());
+                }
             }
-        }
 
-        // Filter ALL debris within radius (no cap!)
-        for debris in &full_snapshot.debris {
-            let distance_sq = (debris.position - player_position).length_sq();
-            if distance_sq <= effective_radius_sq {
-                filtered_debris.push(debris.clone());
+            // Filter ALL debris within radius (no cap!), skipping sub-pixel ones
+            for idx in subset(candidates.map(|c| c.debris.as_slice()), full_snapshot.debris.len()) {
+                let debris = &full_snapshot.debris[idx];
+                if in_view(debris.position, debris.radius()) {
+                    filtered_debris.push(debris.clone());
+                }
             }
-        }
+        });
 
         GameSnapshot {
             tick: full_snapshot.tick,
@@ -337,6 +505,13 @@ impl AOIManager {
             arena_scale: full_snapshot.arena_scale,
             // Always include all gravity wells - they're sparse and important
             gravity_wells: full_snapshot.gravity_wells.clone(),
+            // Asteroids are few: include any that reaches into view
+            asteroids: full_snapshot
+                .asteroids
+                .iter()
+                .filter(|a| (a.position - viewer.position).length() <= viewer.radius + a.radius)
+                .cloned()
+                .collect(),
             // Preserve totals from full snapshot so UI shows correct counts
             total_players: full_snapshot.total_players,
             total_alive: full_snapshot.total_alive,
@@ -344,11 +519,115 @@ impl AOIManager {
             density_grid: full_snapshot.density_grid.clone(),
             // Set per-player in broadcast
             echo_client_time: 0,
+            interp_delay_ms: 0,
             // Preserve AI status from full snapshot
             ai_status: full_snapshot.ai_status.clone(),
         }
     }
 
+    /// Apply per-viewer hysteresis to a snapshot filtered from `index.snapshot()`.
+    ///
+    /// Entities in `filtered` are recorded as entered. Entities that entered
+    /// earlier but fell outside the entry radius are added back (looked up in
+    /// the index) while they are within the exit radius or younger than the
+    /// minimum persistence; otherwise (or once gone from the game) they are
+    /// dropped from the membership. Entities below the minimum apparent size at
+    /// `viewport_zoom` are never added back. Only the viewer's own entities are
+    /// visited, never the whole snapshot.
+    pub fn apply_hysteresis(
+        &self,
+        membership: &mut AoiMembership,
+        viewer_position: Vec2,
+        entry_radius: f32,
+        viewport_zoom: f32,
+        index: &AoiIndex,
+        filtered: &mut GameSnapshot,
+    ) -> AoiChanges {
+        let full_snapshot = index.snapshot();
+        let tick = full_snapshot.tick;
+        let mut changes = AoiChanges::default();
+        with_tick_arena(|bump| {
+            // Sorted entity list in the per-tick arena instead of a per-call hash set
+            let current = ArenaVec::from_iter_in(
+                filtered
+                    .players
+                    .iter()
+                    .map(|p| AoiEntity::Player(p.id))
+                    .chain(filtered.projectiles.iter().map(|p| AoiEntity::Projectile(p.id)))
+                    .chain(filtered.debris.iter().map(|d| AoiEntity::Debris(d.id))),
+                bump,
+            )
+            .into_bump_slice_mut();
+            current.sort_unstable();
+            for &entity in current.iter() {
+                if let Entry::Vacant(slot) = membership.entered.entry(entity) {
+                    slot.insert(tick);
+                    changes.entered += 1;
+                }
+            }
+            if membership.entered.len() == current.len() {
+                return; // Nothing outside the entry radius to reconsider
+            }
+
+            let exit_radius = entry_radius * self.config.exit_radius_ratio.max(1.0);
+            let exit_radius_sq = exit_radius * exit_radius;
+            let min_ticks = self.config.min_persistence_ticks;
+            let keep = |position: Vec2, radius: f32, since: u64| {
+                self.is_visible_size(radius, viewport_zoom)
+                    && ((position - viewer_position).length_sq() <= exit_radius_sq
+                        || tick.saturating_sub(since) < min_ticks)
+            };
+
+            // Reconsider only the entities that left the entry radius (or the game)
+            let mut players = ArenaVec::new_in(bump);
+            let mut projectiles = ArenaVec::new_in(bump);
+            let mut debris = ArenaVec::new_in(bump);
+            membership.entered.retain(|&entity, &mut since| {
+                if current.binary_search(&entity).is_ok() {
+                    return true;
+                }
+                let kept = match entity {
+                    AoiEntity::Player(id) => index
+                        .player(id)
+                        .filter(|&i| {
+                            let p = &full_snapshot.players[i];
+                            keep(p.position, mass_to_radius(p.mass), since)
+                        })
+                        .map(|i| players.push(i)),
+                    AoiEntity::Projectile(id) => index
+                        .projectile(id)
+                        .filter(|&i| {
+                            let p = &full_snapshot.projectiles[i];
+                            keep(p.position, mass_to_radius(p.mass), since)
+                        })
+                        .map(|i| projectiles.push(i)),
+                    AoiEntity::Debris(id) => index
+                        .debris(id)
+                        .filter(|&i| {
+                            let d = &full_snapshot.debris[i];
+                            keep(d.position, d.radius(), since)
+                        })
+                        .map(|i| debris.push(i)),
+                }
+                .is_some();
+                // Forget entities that left (or no longer exist)
+                if !kept {
+                    changes.exited += 1;
+                }
+                kept
+            });
+
+            // Snapshot order, as a full filter would produce
+            players.sort_unstable();
+            projectiles.sort_unstable();
+            debris.sort_unstable();
+            filtered.players.extend(players.iter().map(|&i| full_snapshot.players[i].clone()));
+            filtered.projectiles.extend(projectiles.iter().map(|&i| full_snapshot.projectiles[i].clone()));
+            filtered.debris.extend(debris.iter().map(|&i| full_snapshot.debris[i].clone()));
+        });
+        changes
+    }
+
     /// Get statistics about a filtered snapshot
     pub fn snapshot_stats(original: &GameSnapshot, filtered: &GameSnapshot) -> AOIStats {
         AOIStats {
@@ -390,7 +669,7 @@ pub struct AOIStats {
 mod tests {
     use super::*;
     use crate::game::state::MatchPhase;
-    use crate::net::protocol::{GravityWellSnapshot, PlayerSnapshot, ProjectileSnapshot};
+    use crate::net::protocol::{DebrisSnapshot, GravityWellSnapshot, PlayerSnapshot, ProjectileSnapshot};
     use uuid::Uuid;
 
     fn create_player_snapshot(id: Uuid, position: Vec2, kills: u32) -> PlayerSnapshot {
@@ -407,6 +686,10 @@ mod tests {
             deaths: 0,
             color_index: 0,
             spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
         }
     }
 
@@ -442,10 +725,12 @@ mod tests {
             arena_safe_radius: 800.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: player_len,
             total_alive: player_len,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         }
     }
@@ -475,6 +760,22 @@ mod tests {
         assert_eq!(radius_at_min, radius_below_min, "Zoom below min should be clamped");
     }
 
+    #[test]
+    fn test_is_in_range_uses_base_radius() {
+        let manager = AOIManager::new(AOIConfig::default());
+        assert!(manager.is_in_range(Vec2::ZERO, 1.0, 1.0, Vec2::new(1500.0, 0.0)));
+        assert!(!manager.is_in_range(Vec2::ZERO, 1.0, 1.0, Vec2::new(1600.0, 0.0)));
+    }
+
+    #[test]
+    fn test_vision_scale_shrinks_radius() {
+        let mut manager = AOIManager::new(AOIConfig::default());
+        let normal = manager.effective_radius(Vec2::ZERO, 1.0, 1.0);
+        manager.set_vision_scale(0.5);
+        assert_eq!(manager.effective_radius(Vec2::ZERO, 1.0, 1.0), normal * 0.5);
+        assert!(!manager.is_in_range(Vec2::ZERO, 1.0, 1.0, Vec2::new(1000.0, 0.0)));
+    }
+
     #[test]
     fn test_min_zoom_for_arena_scale_1() {
         let min_zoom = min_zoom_for_arena(1.0);
@@ -511,6 +812,7 @@ mod tests {
     fn test_dynamic_aoi_zoomed_in_filters_more() {
         let aoi = AOIManager::new(AOIConfig {
             always_include_top_n: 0,
+            ..Default::default()
         });
 
         let player_id = Uuid::new_v4();
@@ -533,10 +835,12 @@ mod tests {
             arena_safe_radius: 5000.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 4,
             total_alive: 4,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
@@ -562,6 +866,7 @@ mod tests {
     fn test_no_player_cap_all_nearby_included() {
         let aoi = AOIManager::new(AOIConfig {
             always_include_top_n: 0,
+            ..Default::default()
         });
 
         let player_id = Uuid::new_v4();
@@ -636,6 +941,7 @@ mod tests {
     fn test_aoi_filter_distant_players_excluded() {
         let aoi = AOIManager::new(AOIConfig {
             always_include_top_n: 0,
+            ..Default::default()
         });
 
         let player_id = Uuid::new_v4();
@@ -657,10 +963,12 @@ mod tests {
             arena_safe_radius: 800.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 3,
             total_alive: 3,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
@@ -673,6 +981,7 @@ mod tests {
     fn test_aoi_filter_top_players_included() {
         let aoi = AOIManager::new(AOIConfig {
             always_include_top_n: 3,
+            ..Default::default()
         });
 
         let player_id = Uuid::new_v4();
@@ -694,10 +1003,12 @@ mod tests {
             arena_safe_radius: 800.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 3,
             total_alive: 3,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
@@ -720,12 +1031,18 @@ mod tests {
                 position: Vec2::new(50000.0, 50000.0),
                 mass: 10000.0,
                 core_radius: 50.0,
+                owner_color: None,
+                capture_progress: 0.0,
+                contested: false,
             },
             GravityWellSnapshot {
                 id: 1,
                 position: Vec2::new(-50000.0, -50000.0),
                 mass: 10000.0,
                 core_radius: 50.0,
+                owner_color: None,
+                capture_progress: 0.0,
+                contested: false,
             },
         ];
 
@@ -738,6 +1055,7 @@ mod tests {
     fn test_aoi_velocity_expansion() {
         let aoi = AOIManager::new(AOIConfig {
             always_include_top_n: 0,
+            ..Default::default()
         });
 
         let player_id = Uuid::new_v4();
@@ -759,10 +1077,12 @@ mod tests {
             arena_safe_radius: 5000.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 2,
             total_alive: 2,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
@@ -784,6 +1104,7 @@ mod tests {
     fn test_aoi_no_duplicate_players() {
         let aoi = AOIManager::new(AOIConfig {
             always_include_top_n: 5,
+            ..Default::default()
         });
 
         let player_id = Uuid::new_v4();
@@ -844,8 +1165,11 @@ mod tests {
     #[test]
     fn test_aoi_filter_large_arena_spectator_view() {
         // Test that at large arena scale, spectator with min zoom can see everything
+        // (radius only: apparent-size culling would drop these players at zoom 0.01)
         let aoi = AOIManager::new(AOIConfig {
             always_include_top_n: 0,
+            min_apparent_radius_px: 0.0,
+            ..Default::default()
         });
 
         let player_id = Uuid::new_v4();
@@ -886,4 +1210,199 @@ mod tests {
             base_radius_scale_1, base_radius_scale_10
         );
     }
+
+    // ========================================================================
+    // Apparent-Size Culling Tests
+    // ========================================================================
+
+    #[test]
+    fn test_apparent_radius_scales_with_zoom() {
+        assert_eq!(apparent_radius(20.0, 1.0), 20.0);
+        assert_eq!(apparent_radius(20.0, 0.1), 2.0);
+
+        let aoi = AOIManager::default();
+        assert!(aoi.is_visible_size(20.0, 0.1));
+        assert!(!aoi.is_visible_size(5.0, 0.1));
+
+        let disabled = AOIManager::new(AOIConfig { min_apparent_radius_px: 0.0, ..Default::default() });
+        assert!(disabled.is_visible_size(0.01, 0.01));
+    }
+
+    #[test]
+    fn test_large_mass_far_entities_never_culled() {
+        let aoi = AOIManager::new(AOIConfig { always_include_top_n: 0, ..Default::default() });
+        let viewer = Uuid::new_v4();
+        let (giant, speck) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // At 10x arena scale and zoom 0.1 the AOI radius is ~15600 units
+        let mut snapshot = create_test_snapshot(0);
+        snapshot.arena_scale = 10.0;
+        let mut giant_player = create_player_snapshot(giant, Vec2::new(15_000.0, 0.0), 0);
+        giant_player.mass = 10_000.0; // radius 200 -> 20px
+        let mut speck_player = create_player_snapshot(speck, Vec2::new(50.0, 0.0), 0);
+        speck_player.mass = 10.0; // radius ~6.3 -> ~0.6px
+        snapshot.players = vec![create_player_snapshot(viewer, Vec2::ZERO, 0), giant_player, speck_player];
+        let mut giant_projectile = create_projectile_snapshot(1, Vec2::new(0.0, 15_000.0));
+        giant_projectile.mass = 2_500.0;
+        snapshot.projectiles = vec![giant_projectile, create_projectile_snapshot(2, Vec2::new(10.0, 0.0))];
+        snapshot.debris = vec![
+            DebrisSnapshot { id: 1, position: Vec2::new(20.0, 0.0), size: 0 },
+            DebrisSnapshot { id: 2, position: Vec2::new(20.0, 0.0), size: 2 },
+        ];
+
+        let filtered = aoi.filter_for_player(viewer, Vec2::ZERO, Vec2::ZERO, 0.1, 10.0, &snapshot);
+        let player_ids: Vec<PlayerId> = filtered.players.iter().map(|p| p.id).collect();
+        assert_eq!(player_ids, vec![viewer, giant]);
+        assert_eq!(filtered.projectiles.iter().map(|p| p.id).collect::<Vec<_>>(), vec![1]);
+        assert!(filtered.debris.is_empty(), "all debris is sub-pixel at zoom 0.1");
+
+        // Zoomed in, everything in range is big enough again
+        let filtered = aoi.filter_for_player(viewer, Vec2::ZERO, Vec2::ZERO, 1.0, 10.0, &snapshot);
+        assert!(filtered.players.iter().any(|p| p.id == speck));
+        assert_eq!(filtered.debris.len(), 2);
+    }
+
+    #[test]
+    fn test_culling_spares_self_and_top_players() {
+        let aoi = AOIManager::new(AOIConfig { always_include_top_n: 1, ..Default::default() });
+        let (viewer, leader, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let mut snapshot = create_test_snapshot(0);
+        snapshot.players = vec![
+            create_player_snapshot(viewer, Vec2::ZERO, 0),
+            create_player_snapshot(leader, Vec2::new(100.0, 0.0), 9),
+            create_player_snapshot(other, Vec2::new(100.0, 0.0), 1),
+        ];
+        for player in &mut snapshot.players {
+            player.mass = 10.0;
+        }
+
+        let filtered = aoi.filter_for_player(viewer, Vec2::ZERO, Vec2::ZERO, 0.1, 1.0, &snapshot);
+        let player_ids: Vec<PlayerId> = filtered.players.iter().map(|p| p.id).collect();
+        assert_eq!(player_ids, vec![viewer, leader]);
+
+        // Spectator path: same rule on the unfiltered snapshot
+        aoi.cull_small_players(&mut snapshot, 0.1);
+        assert_eq!(snapshot.players.iter().map(|p| p.id).collect::<Vec<_>>(), vec![leader]);
+    }
+
+    #[test]
+    fn test_indexed_filter_matches_full_scan() {
+        let mut snapshot = create_test_snapshot(120);
+        snapshot.projectiles = (0..60)
+            .map(|i| create_projectile_snapshot(i, Vec2::new(i as f32 * 97.0 - 3000.0, i as f32 * -53.0 + 1500.0)))
+            .collect();
+        snapshot.debris = (0..60)
+            .map(|i| DebrisSnapshot { id: i, position: Vec2::new(i as f32 * -71.0 + 2000.0, i as f32 * 41.0), size: 2 })
+            .collect();
+        let aoi = AOIManager::default();
+        let index = aoi.build_index(Arc::new(snapshot.clone()));
+
+        let ids = |s: &GameSnapshot| {
+            let players: Vec<PlayerId> = s.players.iter().map(|p| p.id).collect();
+            let projectiles: Vec<u64> = s.projectiles.iter().map(|p| p.id).collect();
+            let debris: Vec<u64> = s.debris.iter().map(|d| d.id).collect();
+            (players, projectiles, debris)
+        };
+        let unknown = Uuid::new_v4();
+        for (viewer, zoom) in [(10, 1.0), (60, 0.5), (119, 0.1), (0, 1.0)] {
+            let id = if viewer == 0 { unknown } else { snapshot.players[viewer].id };
+            let position = snapshot.players[viewer].position;
+            let full = aoi.filter_for_player(id, position, Vec2::new(300.0, 0.0), zoom, 1.0, &snapshot);
+            let indexed = aoi.filter_indexed(&index, id, position, Vec2::new(300.0, 0.0), zoom, 1.0);
+            assert_eq!(ids(&full), ids(&indexed), "viewer {} at zoom {}", viewer, zoom);
+        }
+    }
+
+    // ========================================================================
+    // Hysteresis Tests
+    // ========================================================================
+
+    /// Snapshot at `tick` with one player at `x` (plus a projectile and debris alongside)
+    fn boundary_snapshot(id: Uuid, x: f32, tick: u64) -> GameSnapshot {
+        let mut snapshot = create_test_snapshot(0);
+        snapshot.tick = tick;
+        snapshot.players = vec![create_player_snapshot(id, Vec2::new(x, 0.0), 0)];
+        snapshot.projectiles = vec![create_projectile_snapshot(7, Vec2::new(x, 0.0))];
+        snapshot.debris = vec![DebrisSnapshot { id: 9, position: Vec2::new(x, 0.0), size: 0 }];
+        snapshot
+    }
+
+    /// Entry-radius filter around the origin, then hysteresis
+    fn visible_with_hysteresis(aoi: &AOIManager, membership: &mut AoiMembership, full: &GameSnapshot) -> usize {
+        let mut filtered = full.clone();
+        filtered.players.retain(|p| p.position.length() <= 100.0);
+        filtered.projectiles.retain(|p| p.position.length() <= 100.0);
+        filtered.debris.retain(|d| d.position.length() <= 100.0);
+        let index = aoi.build_index(Arc::new(full.clone()));
+        aoi.apply_hysteresis(membership, Vec2::ZERO, 100.0, 1.0, &index, &mut filtered);
+        assert_eq!(filtered.players.len(), filtered.projectiles.len());
+        assert_eq!(filtered.players.len(), filtered.debris.len());
+        filtered.players.len()
+    }
+
+    #[test]
+    fn test_hysteresis_exit_radius_larger_than_entry() {
+        let aoi = AOIManager::new(AOIConfig { min_persistence_ticks: 0, exit_radius_ratio: 1.2, ..Default::default() });
+        let mut membership = AoiMembership::default();
+        let id = Uuid::new_v4();
+
+        // Outside the entry radius and never entered: not visible
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 110.0, 1)), 0);
+        // Enters at the entry radius
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 95.0, 2)), 1);
+        // Drifts just outside: kept until beyond the exit radius
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 110.0, 3)), 1);
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 130.0, 4)), 0);
+        // Once removed, re-entry needs the entry radius again
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 110.0, 5)), 0);
+        assert!(membership.entered.is_empty());
+    }
+
+    #[test]
+    fn test_hysteresis_min_persistence() {
+        let aoi = AOIManager::new(AOIConfig { min_persistence_ticks: 10, exit_radius_ratio: 1.0, ..Default::default() });
+        let mut membership = AoiMembership::default();
+        let id = Uuid::new_v4();
+
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 50.0, 100)), 1);
+        // Far outside, but entered only 5 ticks ago
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 500.0, 105)), 1);
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 500.0, 110)), 0);
+    }
+
+    #[test]
+    fn test_hysteresis_reports_enters_and_exits() {
+        let aoi = AOIManager::new(AOIConfig { min_persistence_ticks: 0, exit_radius_ratio: 1.0, ..Default::default() });
+        let mut membership = AoiMembership::default();
+        let id = Uuid::new_v4();
+        let mut changes_at = |x: f32, tick: u64| {
+            let full = boundary_snapshot(id, x, tick);
+            let mut filtered = full.clone();
+            filtered.players.retain(|p| p.position.length() <= 100.0);
+            filtered.projectiles.retain(|p| p.position.length() <= 100.0);
+            filtered.debris.retain(|d| d.position.length() <= 100.0);
+            let index = aoi.build_index(Arc::new(full));
+            aoi.apply_hysteresis(&mut membership, Vec2::ZERO, 100.0, 1.0, &index, &mut filtered)
+        };
+
+        assert_eq!(changes_at(50.0, 1), AoiChanges { entered: 3, exited: 0 });
+        assert_eq!(changes_at(60.0, 2), AoiChanges::default());
+        assert_eq!(changes_at(500.0, 3), AoiChanges { entered: 0, exited: 3 });
+    }
+
+    #[test]
+    fn test_hysteresis_forgets_despawned_entities() {
+        let aoi = AOIManager::default();
+        let mut membership = AoiMembership::default();
+        let id = Uuid::new_v4();
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &boundary_snapshot(id, 50.0, 1)), 1);
+
+        let mut gone = boundary_snapshot(id, 50.0, 2);
+        gone.players.clear();
+        gone.projectiles.clear();
+        gone.debris.clear();
+        assert_eq!(visible_with_hysteresis(&aoi, &mut membership, &gone), 0);
+        assert!(membership.entered.is_empty());
+    }
 }
@@ -0,0 +1,287 @@
+//! Spatial index over one broadcast's full snapshot
+//!
+//! Filtering every client's AOI against the whole snapshot costs
+//! O(entities × clients) per broadcast. The index is built once per broadcast:
+//! snapshot entities are bucketed into a coarse grid, ids are mapped to their
+//! snapshot position and the top players are looked up once. A client's filter
+//! then only visits the cells near it, finds its own player and the top
+//! players by lookup, and hysteresis only looks up the entities that fell
+//! outside the entry radius. Per-client cost follows what is near the viewer
+//! and what changed, not the size of the arena.
+//!
+//! Clustered clients ask nearly the same question, so candidate lists are
+//! shared: every client in the same cell with a similar AOI radius gets the
+//! same list (everything in range of the cell), and trims it by its own
+//! distance and size checks.
+//!
+//! Indices are positions in the snapshot's `players`, `projectiles` and
+//! `debris` lists, so filtered output keeps snapshot order. The index shares
+//! ownership of the snapshot, so broadcast workers can each hold it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::game::state::PlayerId;
+use crate::net::protocol::GameSnapshot;
+use crate::util::vec2::Vec2;
+
+/// Grid cell size in world units (a zoomed-in AOI spans a few cells)
+pub const AOI_INDEX_CELL_SIZE: f32 = 512.0;
+
+pub type CellKey = (i32, i32);
+
+/// Snapshot indices of the entities in one grid cell
+#[derive(Debug, Default)]
+struct IndexCell {
+    players: Vec<u32>,
+    projectiles: Vec<u32>,
+    debris: Vec<u32>,
+}
+
+/// Snapshot indices near one or more viewers, each list ascending
+#[derive(Debug, Default)]
+pub struct SharedCandidates {
+    pub players: Vec<u32>,
+    pub projectiles: Vec<u32>,
+    pub debris: Vec<u32>,
+}
+
+/// Candidate lists already built this broadcast, by viewer cell and reach in cells
+#[derive(Debug, Default)]
+struct QueryCache {
+    entries: HashMap<(CellKey, i32), Arc<SharedCandidates>>,
+    hits: usize,
+}
+
+/// Grid and id lookups over a full snapshot
+#[derive(Debug)]
+pub struct AoiIndex {
+    snapshot: Arc<GameSnapshot>,
+    inv_cell_size: f32,
+    cells: HashMap<CellKey, IndexCell>,
+    players: HashMap<PlayerId, u32>,
+    projectiles: HashMap<u64, u32>,
+    debris: HashMap<u64, u32>,
+    /// Snapshot indices of the always-sent top players, ascending
+    top_players: Vec<u32>,
+    /// Locked only while a viewer's candidates are looked up or built
+    shared: Mutex<QueryCache>,
+}
+
+impl AoiIndex {
+    /// Index `snapshot`; `top_player_ids` are always sent (see `AOIManager::top_player_ids`)
+    pub fn build(snapshot: Arc<GameSnapshot>, top_player_ids: &[PlayerId]) -> Self {
+        let mut index = Self {
+            inv_cell_size: 1.0 / AOI_INDEX_CELL_SIZE,
+            cells: HashMap::new(),
+            players: HashMap::with_capacity(snapshot.players.len()),
+            projectiles: HashMap::with_capacity(snapshot.projectiles.len()),
+            debris: HashMap::with_capacity(snapshot.debris.len()),
+            top_players: Vec::new(),
+            shared: Mutex::new(QueryCache::default()),
+            snapshot: snapshot.clone(),
+        };
+        for (i, player) in snapshot.players.iter().enumerate() {
+            index.players.insert(player.id, i as u32);
+            index.cell_mut(player.position).players.push(i as u32);
+        }
+        for (i, projectile) in snapshot.projectiles.iter().enumerate() {
+            index.projectiles.insert(projectile.id, i as u32);
+            index.cell_mut(projectile.position).projectiles.push(i as u32);
+        }
+        for (i, debris) in snapshot.debris.iter().enumerate() {
+            index.debris.insert(debris.id, i as u32);
+            index.cell_mut(debris.position).debris.push(i as u32);
+        }
+
+        index.top_players = top_player_ids.iter().filter_map(|id| index.players.get(id).copied()).collect();
+        index.top_players.sort_unstable();
+        index.top_players.dedup();
+        index
+    }
+
+    /// Grid cell containing `position`
+    pub fn cell_key(&self, position: Vec2) -> CellKey {
+        ((position.x * self.inv_cell_size).floor() as i32, (position.y * self.inv_cell_size).floor() as i32)
+    }
+
+    fn cell_mut(&mut self, position: Vec2) -> &mut IndexCell {
+        let key = self.cell_key(position);
+        self.cells.entry(key).or_default()
+    }
+
+    /// The indexed snapshot
+    pub fn snapshot(&self) -> &GameSnapshot {
+        &self.snapshot
+    }
+
+    pub fn player(&self, id: PlayerId) -> Option<usize> {
+        self.players.get(&id).map(|&i| i as usize)
+    }
+
+    pub fn projectile(&self, id: u64) -> Option<usize> {
+        self.projectiles.get(&id).map(|&i| i as usize)
+    }
+
+    pub fn debris(&self, id: u64) -> Option<usize> {
+        self.debris.get(&id).map(|&i| i as usize)
+    }
+
+    /// Debris in one grid cell
+    pub fn debris_in(&self, cell: CellKey) -> usize {
+        self.cells.get(&cell).map_or(0, |c| c.debris.len())
+    }
+
+    /// Snapshot indices of the always-sent top players, ascending
+    pub fn top_players(&self) -> &[u32] {
+        &self.top_players
+    }
+
+    /// Candidates for a viewer at `position` with AOI `radius`: every entity in
+    /// range of the viewer's cell. Viewers in the same cell whose radius rounds
+    /// up to the same number of cells share one list, which each trims by distance.
+    pub fn candidates_near(&self, position: Vec2, radius: f32) -> Arc<SharedCandidates> {
+        let cell = self.cell_key(position);
+        let reach = (radius.max(0.0) * self.inv_cell_size).ceil() as i32;
+        let mut guard = self.shared.lock();
+        let cache = &mut *guard;
+        if let Some(candidates) = cache.entries.get(&(cell, reach)) {
+            cache.hits += 1;
+            return candidates.clone();
+        }
+        let candidates = Arc::new(self.collect_around(cell, reach));
+        cache.entries.insert((cell, reach), candidates.clone());
+        candidates
+    }
+
+    /// Queries answered from a list another viewer already built
+    pub fn shared_query_hits(&self) -> usize {
+        self.shared.lock().hits
+    }
+
+    /// Entities in cells within `reach` cells of `center` (skipping corners no
+    /// point of the center cell can reach)
+    fn collect_around(&self, (cx, cy): CellKey, reach: i32) -> SharedCandidates {
+        let mut candidates = SharedCandidates::default();
+        let in_reach = |&(x, y): &CellKey| {
+            // Gap between the center cell and this one, in whole cells
+            let gap_x = ((x as i64 - cx as i64).abs() - 1).max(0);
+            let gap_y = ((y as i64 - cy as i64).abs() - 1).max(0);
+            gap_x * gap_x + gap_y * gap_y <= reach as i64 * reach as i64
+        };
+        let mut take = |cell: &IndexCell| {
+            candidates.players.extend_from_slice(&cell.players);
+            candidates.projectiles.extend_from_slice(&cell.projectiles);
+            candidates.debris.extend_from_slice(&cell.debris);
+        };
+
+        let side = 2 * reach as i64 + 1;
+        if side * side > self.cells.len() as i64 {
+            // Zoomed far out: fewer occupied cells than cells in reach
+            for (key, cell) in &self.cells {
+                if in_reach(key) {
+                    take(cell);
+                }
+            }
+        } else {
+            for y in cy.saturating_sub(reach)..=cy.saturating_add(reach) {
+                for x in cx.saturating_sub(reach)..=cx.saturating_add(reach) {
+                    if let Some(cell) = self.cells.get(&(x, y)).filter(|_| in_reach(&(x, y))) {
+                        take(cell);
+                    }
+                }
+            }
+        }
+
+        candidates.players.sort_unstable();
+        candidates.projectiles.sort_unstable();
+        candidates.debris.sort_unstable();
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::MatchPhase;
+    use crate::net::protocol::{player_flags, PlayerSnapshot};
+    use uuid::Uuid;
+
+    fn player_at(x: f32, kills: u32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            id: Uuid::new_v4(),
+            name: String::new(),
+            position: Vec2::new(x, 0.0),
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass: 100.0,
+            flags: player_flags::ALIVE,
+            kills,
+            deaths: 0,
+            color_index: 0,
+            spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
+        }
+    }
+
+    fn snapshot(players: Vec<PlayerSnapshot>) -> GameSnapshot {
+        GameSnapshot {
+            tick: 1,
+            match_phase: MatchPhase::Playing,
+            match_time: 0.0,
+            countdown: 0.0,
+            players,
+            projectiles: vec![],
+            debris: vec![],
+            arena_collapse_phase: 0,
+            arena_safe_radius: 1000.0,
+            arena_scale: 1.0,
+            gravity_wells: vec![],
+            asteroids: vec![],
+            total_players: 0,
+            total_alive: 0,
+            density_grid: vec![],
+            echo_client_time: 0,
+            interp_delay_ms: 0,
+            ai_status: None,
+        }
+    }
+
+    #[test]
+    fn test_candidates_cover_reach_in_snapshot_order() {
+        let snapshot = Arc::new(snapshot(vec![player_at(5000.0, 0), player_at(100.0, 0), player_at(-300.0, 0)]));
+        let index = AoiIndex::build(snapshot.clone(), &[]);
+
+        assert_eq!(index.candidates_near(Vec2::ZERO, 400.0).players, vec![1, 2]);
+        assert_eq!(index.candidates_near(Vec2::ZERO, 10_000.0).players, vec![0, 1, 2]);
+        assert_eq!(index.player(snapshot.players[2].id), Some(2));
+    }
+
+    #[test]
+    fn test_viewers_in_same_cell_share_candidates() {
+        let index = AoiIndex::build(Arc::new(snapshot(vec![player_at(100.0, 0), player_at(900.0, 0)])), &[]);
+
+        let first = index.candidates_near(Vec2::new(10.0, 10.0), 400.0);
+        let second = index.candidates_near(Vec2::new(500.0, 500.0), 300.0);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(index.shared_query_hits(), 1);
+
+        // A radius reaching further is a separate list
+        let wider = index.candidates_near(Vec2::new(10.0, 10.0), 600.0);
+        assert!(!Arc::ptr_eq(&first, &wider));
+        assert_eq!(index.shared_query_hits(), 1);
+    }
+
+    #[test]
+    fn test_top_players_in_snapshot_order() {
+        let snapshot = Arc::new(snapshot(vec![player_at(0.0, 1), player_at(0.0, 9), player_at(0.0, 5)]));
+        let top = [snapshot.players[1].id, snapshot.players[2].id, Uuid::new_v4()];
+        // Unknown ids are skipped
+        assert_eq!(AoiIndex::build(snapshot, &top).top_players(), &[1, 2]);
+    }
+}
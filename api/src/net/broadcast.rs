@@ -0,0 +1,236 @@
+//! Snapshot broadcast worker pool
+//!
+//! Filtering, delta generation, encoding and sealing every client's snapshot
+//! used to run in a task holding the session read lock, so a slow broadcast
+//! held up the game loop's next write lock. Now the game loop captures a
+//! [`BroadcastFrame`] while it already holds the session: the tick's snapshot
+//! (shared, immutable) plus what the broadcast needs to know about each
+//! connection. Nothing in a frame borrows the session.
+//!
+//! Frames go to a [`BroadcastPool`] over a short bounded queue. Its dispatcher
+//! broadcasts frames one at a time, in order (each client's delta state must
+//! see ticks in order), splitting a frame's players across `workers` tasks.
+//! Submitting never waits: if the pool falls behind, new frames are dropped
+//! and counted, and clients catch up on the next frame like after a lost packet.
+//!
+//! The time from capturing a frame to its last send is reported as
+//! `broadcast_latency_us`, separately from tick time.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+use crate::config::{BroadcastConfig, RegionHintConfig};
+use crate::game::state::PlayerId;
+use crate::metrics::Metrics;
+use crate::net::aoi::AOIManager;
+use crate::net::egress::Outbound;
+use crate::net::game_session::{broadcast_filtered_snapshots, ClientNetState};
+use crate::net::protocol::{GameSnapshot, SnapshotRate};
+use crate::net::snapshot_crypto::SnapshotCipher;
+use crate::net::spectator_delay::SpectatorDelay;
+use crate::util::vec2::Vec2;
+
+/// What a broadcast needs to know about one connection
+pub struct ClientView {
+    pub player_id: PlayerId,
+    pub sender: mpsc::UnboundedSender<Outbound>,
+    pub net_state: Arc<tokio::sync::Mutex<ClientNetState>>,
+    pub snapshot_cipher: Option<Arc<SnapshotCipher>>,
+    pub is_spectator: bool,
+    pub spectate_target: Option<PlayerId>,
+    pub viewport_zoom: f32,
+    /// The player's position and velocity (zero without a living entity)
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub rate: SnapshotRate,
+    /// Client time to echo for RTT measurement
+    pub echo_client_time: u64,
+    pub interp_delay_ms: u16,
+    /// Latest full snapshot tick the client acknowledged
+    pub acked_tick: Option<u64>,
+    /// Watches the delayed spectator stream
+    pub delayed: bool,
+}
+
+/// One tick's broadcast, captured by the game loop
+pub struct BroadcastFrame {
+    pub tick: u64,
+    pub snapshot: Arc<GameSnapshot>,
+    pub arena_scale: f32,
+    pub aoi: AOIManager,
+    pub clients: Vec<ClientView>,
+    /// Bots followed by spectators: (id, position, velocity)
+    pub bot_targets: Vec<(PlayerId, Vec2, Vec2)>,
+    pub spectator_delay: Arc<Mutex<SpectatorDelay>>,
+    /// Interpolation delay for full-view spectators
+    pub spectator_interp_delay: u16,
+    /// Region hint settings (None when hints are off)
+    pub region_hint: Option<RegionHintConfig>,
+    pub metrics: Option<Arc<Metrics>>,
+    pub captured_at: Instant,
+}
+
+/// Broadcasts frames off the game loop
+pub struct BroadcastPool {
+    frames: mpsc::Sender<BroadcastFrame>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl BroadcastPool {
+    /// Start the dispatcher; it stops once the pool is dropped
+    pub fn start(config: &BroadcastConfig, metrics: Option<Arc<Metrics>>) -> Self {
+        let (frames, mut queue) = mpsc::channel::<BroadcastFrame>(config.queue_depth.max(1));
+        let workers = config.workers.max(1);
+        tokio::spawn(async move {
+            while let Some(frame) = queue.recv().await {
+                let captured_at = frame.captured_at;
+                let metrics = frame.metrics.clone();
+                broadcast_filtered_snapshots(Arc::new(frame), workers).await;
+                if let Some(metrics) = metrics {
+                    let latency = captured_at.elapsed().as_micros() as u64;
+                    metrics.broadcast_latency_us.store(latency, Ordering::Relaxed);
+                }
+            }
+            debug!("Broadcast pool stopped");
+        });
+        Self { frames, metrics }
+    }
+
+    /// Queue a frame without waiting. Returns false if it was dropped because
+    /// the pool is still busy with earlier frames.
+    pub fn submit(&self, frame: BroadcastFrame) -> bool {
+        match self.frames.try_send(frame) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(frame)) => {
+                debug!("Broadcast pool behind, dropping frame for tick {}", frame.tick);
+                if let Some(metrics) = &self.metrics {
+                    metrics.broadcast_frames_dropped_total.fetch_add(1, Ordering::Relaxed);
+                }
+                false
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::MatchPhase;
+    use crate::net::aoi::AOIConfig;
+    use crate::net::protocol::{player_flags, PlayerSnapshot};
+    use uuid::Uuid;
+
+    fn snapshot(ids: &[PlayerId]) -> GameSnapshot {
+        let players = ids
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| PlayerSnapshot {
+                id,
+                name: String::new(),
+                position: Vec2::new(i as f32 * 50.0, 0.0),
+                velocity: Vec2::ZERO,
+                rotation: 0.0,
+                mass: 100.0,
+                flags: player_flags::ALIVE,
+                kills: 0,
+                deaths: 0,
+                color_index: 0,
+                spawn_tick: 0,
+                charge: 0,
+                heat: 0,
+                faction: 0,
+                effects: 0,
+            })
+            .collect();
+        GameSnapshot {
+            tick: 1,
+            match_phase: MatchPhase::Playing,
+            match_time: 0.0,
+            countdown: 0.0,
+            players,
+            projectiles: vec![],
+            debris: vec![],
+            arena_collapse_phase: 0,
+            arena_safe_radius: 1000.0,
+            arena_scale: 1.0,
+            gravity_wells: vec![],
+            asteroids: vec![],
+            total_players: 0,
+            total_alive: 0,
+            density_grid: vec![],
+            echo_client_time: 0,
+            interp_delay_ms: 0,
+            ai_status: None,
+        }
+    }
+
+    fn client(player_id: PlayerId) -> (ClientView, mpsc::UnboundedReceiver<Outbound>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let view = ClientView {
+            player_id,
+            sender,
+            net_state: Arc::new(tokio::sync::Mutex::new(ClientNetState::default())),
+            snapshot_cipher: None,
+            is_spectator: false,
+            spectate_target: None,
+            viewport_zoom: 1.0,
+            position: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            rate: SnapshotRate::default(),
+            echo_client_time: 0,
+            interp_delay_ms: 0,
+            acked_tick: None,
+            delayed: false,
+        };
+        (view, receiver)
+    }
+
+    fn frame(tick: u64, clients: Vec<ClientView>, metrics: Option<Arc<Metrics>>) -> BroadcastFrame {
+        let ids: Vec<PlayerId> = clients.iter().map(|c| c.player_id).collect();
+        BroadcastFrame {
+            tick,
+            snapshot: Arc::new(snapshot(&ids)),
+            arena_scale: 1.0,
+            aoi: AOIManager::new(AOIConfig::default()),
+            clients,
+            bot_targets: vec![],
+            spectator_delay: Arc::new(Mutex::new(SpectatorDelay::new(0, 1))),
+            spectator_interp_delay: 0,
+            region_hint: None,
+            metrics,
+            captured_at: Instant::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_players_split_across_workers_all_receive() {
+        let (clients, mut receivers): (Vec<_>, Vec<_>) = (0..5).map(|_| client(Uuid::new_v4())).unzip();
+        broadcast_filtered_snapshots(Arc::new(frame(1, clients, None)), 2).await;
+
+        for receiver in &mut receivers {
+            assert!(receiver.try_recv().is_ok(), "every player gets a snapshot");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_drops_frames_while_pool_is_behind() {
+        let metrics = Arc::new(Metrics::new());
+        let pool = BroadcastPool::start(&BroadcastConfig { workers: 1, queue_depth: 1 }, Some(metrics.clone()));
+        let (view, mut receiver) = client(Uuid::new_v4());
+        let (dropped, _) = client(Uuid::new_v4());
+
+        // The single-threaded test runtime hasn't run the dispatcher yet: the queue stays full
+        assert!(pool.submit(frame(1, vec![view], Some(metrics.clone()))));
+        assert!(!pool.submit(frame(2, vec![dropped], Some(metrics.clone()))));
+        assert_eq!(metrics.broadcast_frames_dropped_total.load(Ordering::Relaxed), 1);
+
+        assert!(receiver.recv().await.is_some());
+        drop(pool);
+    }
+}
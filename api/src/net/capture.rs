@@ -0,0 +1,476 @@
+//! Per-connection session capture for bug reports
+//!
+//! Records what one client sent (decoded inbound messages) and a digest of
+//! each snapshot or delta sent back to it, so a "my shot didn't register"
+//! report can be checked against what the server actually received and
+//! showed the player. Captures are ring buffers over the last N seconds;
+//! in `ring` mode every connection is captured, in `opt_in` mode only players
+//! an admin starts a capture for (`POST /debug/capture`). Dumps come from
+//! `/debug/capture` and keep the inbound messages with their timing, so they
+//! can be fed back into a session to replay the client. Players who opt out
+//! of replays in their join request are never captured.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{CaptureConfig, CaptureMode};
+use crate::game::desync::state_hash;
+use crate::game::ghost::{GhostFrame, GhostTrack};
+use crate::game::state::PlayerId;
+use crate::net::protocol::{ClientMessage, GameSnapshot};
+use crate::util::vec2::Vec2;
+
+/// Hard cap on entries per connection, whatever the window
+const MAX_ENTRIES_PER_CONNECTION: usize = 20_000;
+
+/// How a snapshot reached the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotKind {
+    Full,
+    Delta,
+    /// Full resync sent as the changes since the acknowledged base
+    Resync,
+}
+
+/// The receiving player's own state in a sent snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LocalStateDigest {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub mass: f32,
+    pub alive: bool,
+    /// Same hash clients attach for desync detection (see `game::desync`)
+    pub state_hash: u32,
+}
+
+/// Summary of one snapshot or delta sent to the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDigest {
+    pub tick: u64,
+    pub kind: SnapshotKind,
+    /// Encoded message size
+    pub bytes: usize,
+    pub players: usize,
+    pub projectiles: usize,
+    pub local: Option<LocalStateDigest>,
+}
+
+impl SnapshotDigest {
+    /// Digest of the (filtered) snapshot a client's message was built from
+    pub fn new(player_id: PlayerId, snapshot: &GameSnapshot, kind: SnapshotKind, bytes: usize) -> Self {
+        let local = snapshot.players.iter().find(|p| p.id == player_id).map(|p| LocalStateDigest {
+            position: p.position,
+            velocity: p.velocity,
+            mass: p.mass,
+            alive: p.alive(),
+            state_hash: state_hash(p.position, p.velocity),
+        });
+        Self {
+            tick: snapshot.tick,
+            kind,
+            bytes,
+            players: snapshot.players.len(),
+            projectiles: snapshot.projectiles.len(),
+            local,
+        }
+    }
+}
+
+/// One captured event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureEvent {
+    Inbound(ClientMessage),
+    Outbound(SnapshotDigest),
+}
+
+/// A captured event with its time since the capture started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureEntry {
+    pub at_ms: u64,
+    pub event: CaptureEvent,
+}
+
+/// Captured session of one client, as served by the admin API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureDump {
+    pub player_id: PlayerId,
+    pub window_secs: u64,
+    /// Whether the connection had ended when the dump was taken
+    pub ended: bool,
+    pub entries: Vec<CaptureEntry>,
+}
+
+impl CaptureDump {
+    /// Inbound messages in arrival order with their offset from the capture start
+    #[allow(dead_code)] // Replay entry point for offline tooling
+    pub fn inbound(&self) -> impl Iterator<Item = (Duration, &ClientMessage)> {
+        self.entries.iter().filter_map(|entry| match &entry.event {
+            CaptureEvent::Inbound(message) => Some((Duration::from_millis(entry.at_ms), message)),
+            CaptureEvent::Outbound(_) => None,
+        })
+    }
+
+    /// The player's run as seen in the snapshots sent to them, for replay as
+    /// a practice-room ghost (None when no digest carried the player's state)
+    #[cfg_attr(not(feature = "lobby"), allow(dead_code))]
+    pub fn ghost_track(&self, name: String) -> Option<GhostTrack> {
+        let mut frames: Vec<GhostFrame> = Vec::new();
+        let mut start_ms = None;
+        let mut last_tick = None;
+        for entry in &self.entries {
+            let CaptureEvent::Outbound(SnapshotDigest { tick, local: Some(local), .. }) = &entry.event else {
+                continue;
+            };
+            // A tick can be digested more than once (e.g. a resync right after a delta)
+            if last_tick.is_some_and(|last| *tick <= last) {
+                continue;
+            }
+            last_tick = Some(*tick);
+            let start_ms = *start_ms.get_or_insert(entry.at_ms);
+            frames.push(GhostFrame {
+                at: entry.at_ms.saturating_sub(start_ms) as f32 / 1000.0,
+                position: local.position,
+                velocity: local.velocity,
+                mass: local.mass,
+                alive: local.alive,
+            });
+        }
+        (!frames.is_empty()).then_some(GhostTrack { name, frames })
+    }
+}
+
+#[derive(Debug)]
+struct ConnectionCapture {
+    started: Instant,
+    ended: Option<Instant>,
+    entries: VecDeque<(Instant, CaptureEvent)>,
+}
+
+impl ConnectionCapture {
+    fn new(now: Instant) -> Self {
+        Self { started: now, ended: None, entries: VecDeque::new() }
+    }
+
+    fn push(&mut self, now: Instant, event: CaptureEvent, window: Duration) {
+        while let Some((at, _)) = self.entries.front() {
+            if now.duration_since(*at) <= window && self.entries.len() < MAX_ENTRIES_PER_CONNECTION {
+                break;
+            }
+            self.entries.pop_front();
+        }
+        self.entries.push_back((now, event));
+    }
+}
+
+/// Capture buffers for every captured connection
+#[derive(Debug)]
+pub struct SessionCapture {
+    mode: CaptureMode,
+    window: Duration,
+    connections: Mutex<HashMap<PlayerId, ConnectionCapture>>,
+    /// Connected players who opted out of replays
+    opted_out: Mutex<HashSet<PlayerId>>,
+}
+
+/// Why a capture request could not be served
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CaptureError {
+    #[error("session capture is disabled (set CAPTURE_MODE)")]
+    Disabled,
+    #[error("captures are started automatically in ring mode")]
+    NotOptIn,
+    #[error("no capture for player {0}")]
+    NotCaptured(PlayerId),
+    #[error("player {0} opted out of replays")]
+    OptedOut(PlayerId),
+}
+
+impl SessionCapture {
+    pub fn new(config: &CaptureConfig) -> Self {
+        Self {
+            mode: config.mode,
+            window: Duration::from_secs(config.window_secs),
+            connections: Mutex::new(HashMap::new()),
+            opted_out: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.mode != CaptureMode::Off
+    }
+
+    /// Start capturing a player (opt-in mode; a running capture is kept)
+    pub fn start(&self, player_id: PlayerId) -> Result<(), CaptureError> {
+        match self.mode {
+            CaptureMode::Off => Err(CaptureError::Disabled),
+            CaptureMode::Ring => Err(CaptureError::NotOptIn),
+            CaptureMode::OptIn if self.opted_out.lock().contains(&player_id) => {
+                Err(CaptureError::OptedOut(player_id))
+            }
+            CaptureMode::OptIn => {
+                let now = Instant::now();
+                let mut connections = self.connections.lock();
+                let capture = connections.entry(player_id).or_insert_with(|| ConnectionCapture::new(now));
+                capture.ended = None;
+                Ok(())
+            }
+        }
+    }
+
+    /// Never capture a player, discarding what was captured so far. Lasts
+    /// until their connection ends.
+    pub fn opt_out(&self, player_id: PlayerId) {
+        self.opted_out.lock().insert(player_id);
+        self.connections.lock().remove(&player_id);
+    }
+
+    /// Stop and discard a player's capture (returns whether there was one)
+    pub fn stop(&self, player_id: PlayerId) -> bool {
+        self.connections.lock().remove(&player_id).is_some()
+    }
+
+    /// Record a message received from a client. Auth, resume and account
+    /// tokens are never kept.
+    pub fn record_inbound(&self, player_id: PlayerId, message: &ClientMessage) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut message = message.clone();
+        if let ClientMessage::JoinRequest { auth_token, resume_token, account_token, .. } = &mut message {
+            *auth_token = None;
+            *resume_token = None;
+            *account_token = None;
+        }
+        self.record(player_id, CaptureEvent::Inbound(message));
+    }
+
+    /// Record a digest of a snapshot or delta sent to a client
+    pub fn record_outbound(&self, player_id: PlayerId, digest: SnapshotDigest) {
+        self.record(player_id, CaptureEvent::Outbound(digest));
+    }
+
+    fn record(&self, player_id: PlayerId, event: CaptureEvent) {
+        if !self.is_enabled() || self.opted_out.lock().contains(&player_id) {
+            return;
+        }
+        let now = Instant::now();
+        let mut connections = self.connections.lock();
+        let capture = match self.mode {
+            CaptureMode::Ring => connections.entry(player_id).or_insert_with(|| ConnectionCapture::new(now)),
+            _ => match connections.get_mut(&player_id) {
+                Some(capture) => capture,
+                None => return,
+            },
+        };
+        if capture.ended.is_none() {
+            capture.push(now, event, self.window);
+        }
+    }
+
+    /// Mark a player's connection as ended. The capture stays dumpable for one
+    /// window so reports filed right after leaving still have data.
+    pub fn end(&self, player_id: PlayerId) {
+        self.opted_out.lock().remove(&player_id);
+        let now = Instant::now();
+        let mut connections = self.connections.lock();
+        if let Some(capture) = connections.get_mut(&player_id) {
+            capture.ended = Some(now);
+        }
+        connections.retain(|_, c| c.ended.map_or(true, |ended| now.duration_since(ended) <= self.window));
+    }
+
+    /// Captured players with their entry counts
+    pub fn list(&self) -> Vec<(PlayerId, usize)> {
+        self.connections.lock().iter().map(|(id, c)| (*id, c.entries.len())).collect()
+    }
+
+    /// Copy out a player's capture
+    pub fn dump(&self, player_id: PlayerId) -> Result<CaptureDump, CaptureError> {
+        if !self.is_enabled() {
+            return Err(CaptureError::Disabled);
+        }
+        let connections = self.connections.lock();
+        let capture = connections.get(&player_id).ok_or(CaptureError::NotCaptured(player_id))?;
+        Ok(CaptureDump {
+            player_id,
+            window_secs: self.window.as_secs(),
+            ended: capture.ended.is_some(),
+            entries: capture
+                .entries
+                .iter()
+                .map(|(at, event)| CaptureEntry {
+                    at_ms: at.saturating_duration_since(capture.started).as_millis() as u64,
+                    event: event.clone(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::protocol::PlayerInput;
+    use uuid::Uuid;
+
+    fn capture(mode: CaptureMode) -> SessionCapture {
+        SessionCapture::new(&CaptureConfig { mode, window_secs: 60 })
+    }
+
+    fn input(sequence: u64) -> ClientMessage {
+        ClientMessage::Input(PlayerInput { sequence, ..Default::default() })
+    }
+
+    #[test]
+    fn test_ring_mode_captures_every_connection() {
+        let capture = capture(CaptureMode::Ring);
+        let player = Uuid::new_v4();
+        capture.record_inbound(player, &input(1));
+        capture.record_outbound(
+            player,
+            SnapshotDigest { tick: 5, kind: SnapshotKind::Full, bytes: 100, players: 1, projectiles: 0, local: None },
+        );
+        capture.record_inbound(player, &input(2));
+
+        let dump = capture.dump(player).unwrap();
+        assert_eq!(dump.entries.len(), 3);
+        let sequences: Vec<u64> = dump
+            .inbound()
+            .filter_map(|(_, m)| match m {
+                ClientMessage::Input(i) => Some(i.sequence),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sequences, vec![1, 2]);
+        assert_eq!(capture.start(player), Err(CaptureError::NotOptIn));
+    }
+
+    #[test]
+    fn test_opt_in_mode_only_captures_started_players() {
+        let capture = capture(CaptureMode::OptIn);
+        let (armed, other) = (Uuid::new_v4(), Uuid::new_v4());
+        capture.start(armed).unwrap();
+        capture.record_inbound(armed, &input(1));
+        capture.record_inbound(other, &input(1));
+
+        assert_eq!(capture.dump(armed).unwrap().entries.len(), 1);
+        assert_eq!(capture.dump(other).unwrap_err(), CaptureError::NotCaptured(other));
+        assert!(capture.stop(armed));
+        assert!(capture.list().is_empty());
+    }
+
+    #[test]
+    fn test_opted_out_players_are_never_captured() {
+        let ring = capture(CaptureMode::Ring);
+        let player = Uuid::new_v4();
+        ring.record_inbound(player, &input(1));
+        ring.opt_out(player);
+        ring.record_inbound(player, &input(2));
+        assert_eq!(ring.dump(player).unwrap_err(), CaptureError::NotCaptured(player));
+
+        let opt_in = capture(CaptureMode::OptIn);
+        opt_in.opt_out(player);
+        assert_eq!(opt_in.start(player), Err(CaptureError::OptedOut(player)));
+        // A later connection with the same id starts out consenting
+        opt_in.end(player);
+        assert_eq!(opt_in.start(player), Ok(()));
+    }
+
+    #[test]
+    fn test_tokens_are_not_captured() {
+        let capture = capture(CaptureMode::Ring);
+        let player = Uuid::new_v4();
+        capture.record_inbound(
+            player,
+            &ClientMessage::JoinRequest {
+                player_name: "Ace".to_string(),
+                color_index: 0,
+                is_spectator: false,
+                party_invite: None,
+                resume_token: Some(vec![1, 2, 3]),
+                auth_token: Some("secret".to_string()),
+                capabilities: Default::default(),
+                accessibility: Default::default(),
+                telemetry: Default::default(),
+                encryption_key: None,
+                room: None,
+                account_token: Some(vec![7; 48]),
+            },
+        );
+
+        let json = serde_json::to_string(&capture.dump(player).unwrap()).unwrap();
+        assert!(!json.contains("secret"));
+        assert!(json.contains(r#""account_token":null"#));
+        assert!(json.contains("Ace"));
+    }
+
+    #[test]
+    fn test_ended_capture_stays_dumpable_and_stops_recording() {
+        let capture = capture(CaptureMode::Ring);
+        let player = Uuid::new_v4();
+        capture.record_inbound(player, &input(1));
+        capture.end(player);
+        capture.record_inbound(player, &input(2));
+
+        let dump = capture.dump(player).unwrap();
+        assert!(dump.ended);
+        assert_eq!(dump.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_old_entries_fall_out_of_the_window() {
+        let start = Instant::now();
+        let mut connection = ConnectionCapture::new(start);
+        let window = Duration::from_secs(60);
+        connection.push(start, CaptureEvent::Inbound(input(1)), window);
+        connection.push(start + Duration::from_secs(30), CaptureEvent::Inbound(input(2)), window);
+        connection.push(start + Duration::from_secs(75), CaptureEvent::Inbound(input(3)), window);
+        assert_eq!(connection.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_ghost_track_from_snapshot_digests() {
+        let digest = |tick: u64, x: f32| {
+            let local = LocalStateDigest {
+                position: Vec2::new(x, 0.0),
+                velocity: Vec2::ZERO,
+                mass: 100.0,
+                alive: true,
+                state_hash: 0,
+            };
+            CaptureEvent::Outbound(SnapshotDigest {
+                tick,
+                kind: SnapshotKind::Delta,
+                bytes: 40,
+                players: 1,
+                projectiles: 0,
+                local: Some(local),
+            })
+        };
+        let entry = |at_ms: u64, event: CaptureEvent| CaptureEntry { at_ms, event };
+        let dump = CaptureDump {
+            player_id: Uuid::new_v4(),
+            window_secs: 60,
+            ended: true,
+            entries: vec![
+                entry(500, CaptureEvent::Inbound(input(1))),
+                entry(1000, digest(10, 0.0)),
+                entry(1000, digest(10, 0.0)),
+                entry(1500, digest(13, 30.0)),
+            ],
+        };
+
+        let track = dump.ghost_track("Ace".to_string()).unwrap();
+        assert_eq!(track.frames.len(), 2);
+        assert_eq!(track.duration(), 0.5);
+        assert_eq!(track.frames[1].position, Vec2::new(30.0, 0.0));
+
+        let inputs_only = CaptureDump { entries: vec![entry(0, CaptureEvent::Inbound(input(1)))], ..dump };
+        assert_eq!(inputs_only.ghost_track("Ace".to_string()), None);
+    }
+}
@@ -9,9 +9,17 @@
 //! - Epsilon-based change detection (avoids sending tiny changes)
 //! - Distance-based rate limiting (close entities get more updates)
 //! - Reuses existing DeltaUpdate protocol structures
-//! - Maintains full f32 precision for pixel-perfect quality
+//! - Packs player deltas tightly on the wire: a varint bitmask of changed
+//!   fields (most frequently changed first) followed by quantized varints
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+use bumpalo::Bump;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::game::state::PlayerId;
 use crate::game::systems::ai_soa::{
@@ -19,8 +27,9 @@ use crate::game::systems::ai_soa::{
     DEFAULT_REDUCED_UPDATE_INTERVAL,
 };
 use crate::net::protocol::{
-    DeltaUpdate, GameSnapshot, PlayerDelta, PlayerSnapshot, ProjectileDelta, ProjectileSnapshot,
+    DeltaUpdate, GameSnapshot, PlayerDelta, PlayerSnapshot, ProjectileDelta, ResyncUpdate,
 };
+use crate::util::arena::{with_tick_arena, ArenaVec};
 use crate::util::vec2::Vec2;
 
 // ============================================================================
@@ -133,6 +142,8 @@ pub struct DeltaStats {
 /// - `current`: The current snapshot
 /// - `viewer_position`: Position of the viewing player (for distance calculation)
 /// - `current_tick`: Current game tick (used for delta metadata)
+/// - `epsilon_scale`: Multiplier on player change thresholds (coarser deltas
+///   for clients on a reduced snapshot rate; 1.0 = default)
 ///
 /// # Returns
 /// - `Some((DeltaUpdate, DeltaStats))` if there are changes to send
@@ -142,13 +153,24 @@ pub fn generate_delta(
     current: &GameSnapshot,
     viewer_position: Vec2,
     _current_tick: u64,
+    epsilon_scale: f32,
+) -> Option<(DeltaUpdate, DeltaStats)> {
+    // Lookup tables live in the per-tick arena (this runs once per client per broadcast)
+    with_tick_arena(|bump| delta_in(bump, base, current, viewer_position, epsilon_scale))
+}
+
+fn delta_in(
+    bump: &Bump,
+    base: &GameSnapshot,
+    current: &GameSnapshot,
+    viewer_position: Vec2,
+    epsilon_scale: f32,
 ) -> Option<(DeltaUpdate, DeltaStats)> {
     let mut player_updates = Vec::with_capacity(current.players.len());
     let mut stats = DeltaStats::default();
 
-    // Build lookup map for base players
-    let base_players: HashMap<PlayerId, &PlayerSnapshot> =
-        base.players.iter().map(|p| (p.id, p)).collect();
+    // Build lookup table for base players
+    let base_players = sorted_by_id(bump, &base.players, |p| p.id);
 
     for player in &current.players {
         // Track distance tier for metrics (no rate limiting - causes visual jumping)
@@ -161,8 +183,8 @@ pub fn generate_delta(
 
         // Generate delta - compare against base snapshot
         // Delta compression only sends changed fields, preserving smooth interpolation
-        let delta = if let Some(base_player) = base_players.get(&player.id) {
-            generate_player_delta(base_player, player)
+        let delta = if let Some(base_player) = find_by_id(base_players, player.id) {
+            generate_player_delta(base_player, player, epsilon_scale)
         } else {
             // New player - send full state as delta
             Some(PlayerDelta {
@@ -173,6 +195,10 @@ pub fn generate_delta(
                 mass: Some(player.mass),
                 alive: Some(player.alive()),
                 kills: Some(player.kills),
+                charge: Some(player.charge),
+                heat: Some(player.heat),
+                faction: Some(player.faction),
+                effects: Some(player.effects),
             })
         };
 
@@ -183,20 +209,32 @@ pub fn generate_delta(
     }
 
     // Generate projectile deltas (projectiles change every tick, include all)
-    let projectile_updates = generate_projectile_deltas(base, current);
+    let projectile_updates = generate_projectile_deltas(bump, base, current);
 
     // Find removed projectiles
-    let base_projectile_ids: HashSet<u64> = base.projectiles.iter().map(|p| p.id).collect();
-    let current_projectile_ids: HashSet<u64> = current.projectiles.iter().map(|p| p.id).collect();
-    let removed_projectiles: Vec<u64> = base_projectile_ids
-        .difference(&current_projectile_ids)
-        .copied()
+    let current_projectiles = sorted_by_id(bump, &current.projectiles, |p| p.id);
+    let removed_projectiles: Vec<u64> = base
+        .projectiles
+        .iter()
+        .map(|p| p.id)
+        .filter(|&id| find_by_id(current_projectiles, id).is_none())
+        .collect();
+
+    // Find removed players (left the viewer's AOI or despawned)
+    let current_players = sorted_by_id(bump, &current.players, |p| p.id);
+    let removed_players: Vec<PlayerId> = base
+        .players
+        .iter()
+        .map(|p| p.id)
+        .filter(|&id| find_by_id(current_players, id).is_none())
         .collect();
 
     // Only return delta if there's something to send
     if player_updates.is_empty()
         && projectile_updates.is_empty()
         && removed_projectiles.is_empty()
+        && removed_players.is_empty()
+        && current.asteroids == base.asteroids
     {
         return None;
     }
@@ -210,6 +248,9 @@ pub fn generate_delta(
             removed_projectiles,
             // Include full debris list (debris moves slowly, full list is efficient)
             debris: current.debris.clone(),
+            removed_players,
+            // Asteroids are few and only change when shot: sent in full, like debris
+            asteroids: current.asteroids.clone(),
         },
         stats,
     ))
@@ -217,7 +258,9 @@ pub fn generate_delta(
 
 /// Generate a delta for a single player by comparing base and current state.
 /// Returns `None` if no changes detected (within epsilon thresholds).
-fn generate_player_delta(base: &PlayerSnapshot, current: &PlayerSnapshot) -> Option<PlayerDelta> {
+fn generate_player_delta(base: &PlayerSnapshot, current: &PlayerSnapshot, epsilon_scale: f32) -> Option<PlayerDelta> {
+    let position_epsilon = POSITION_EPSILON * epsilon_scale;
+    let velocity_epsilon = VELOCITY_EPSILON * epsilon_scale;
     let mut delta = PlayerDelta {
         id: current.id,
         position: None,
@@ -226,31 +269,35 @@ fn generate_player_delta(base: &PlayerSnapshot, current: &PlayerSnapshot) -> Opt
         mass: None,
         alive: None,
         kills: None,
+        charge: None,
+        heat: None,
+        faction: None,
+        effects: None,
     };
     let mut has_changes = false;
 
     // Position change detection (with epsilon)
     let pos_diff = current.position - base.position;
-    if pos_diff.length_sq() > POSITION_EPSILON * POSITION_EPSILON {
+    if pos_diff.length_sq() > position_epsilon * position_epsilon {
         delta.position = Some(current.position);
         has_changes = true;
     }
 
     // Velocity change detection
     let vel_diff = current.velocity - base.velocity;
-    if vel_diff.length_sq() > VELOCITY_EPSILON * VELOCITY_EPSILON {
+    if vel_diff.length_sq() > velocity_epsilon * velocity_epsilon {
         delta.velocity = Some(current.velocity);
         has_changes = true;
     }
 
     // Rotation change detection
-    if (current.rotation - base.rotation).abs() > ROTATION_EPSILON {
+    if (current.rotation - base.rotation).abs() > ROTATION_EPSILON * epsilon_scale {
         delta.rotation = Some(current.rotation);
         has_changes = true;
     }
 
     // Mass change detection
-    if (current.mass - base.mass).abs() > MASS_EPSILON {
+    if (current.mass - base.mass).abs() > MASS_EPSILON * epsilon_scale {
         delta.mass = Some(current.mass);
         has_changes = true;
     }
@@ -266,6 +313,27 @@ fn generate_player_delta(base: &PlayerSnapshot, current: &PlayerSnapshot) -> Opt
         has_changes = true;
     }
 
+    // Charge windup is sent exactly: opponents react to it
+    if current.charge != base.charge {
+        delta.charge = Some(current.charge);
+        has_changes = true;
+    }
+
+    if current.heat != base.heat {
+        delta.heat = Some(current.heat);
+        has_changes = true;
+    }
+
+    if current.faction != base.faction {
+        delta.faction = Some(current.faction);
+        has_changes = true;
+    }
+
+    if current.effects != base.effects {
+        delta.effects = Some(current.effects);
+        has_changes = true;
+    }
+
     if has_changes {
         Some(delta)
     } else {
@@ -275,16 +343,15 @@ fn generate_player_delta(base: &PlayerSnapshot, current: &PlayerSnapshot) -> Opt
 
 /// Generate deltas for projectiles. Since projectiles move every tick,
 /// we include all projectiles that exist in the current snapshot.
-fn generate_projectile_deltas(base: &GameSnapshot, current: &GameSnapshot) -> Vec<ProjectileDelta> {
-    let base_projectiles: HashMap<u64, &ProjectileSnapshot> =
-        base.projectiles.iter().map(|p| (p.id, p)).collect();
+fn generate_projectile_deltas(bump: &Bump, base: &GameSnapshot, current: &GameSnapshot) -> Vec<ProjectileDelta> {
+    let base_projectiles = sorted_by_id(bump, &base.projectiles, |p| p.id);
 
     current
         .projectiles
         .iter()
         .filter_map(|proj| {
             // Only include if position changed from base (or new projectile)
-            let should_include = base_projectiles.get(&proj.id).map_or(true, |base_proj| {
+            let should_include = find_by_id(base_projectiles, proj.id).map_or(true, |base_proj| {
                 let pos_diff = proj.position - base_proj.position;
                 pos_diff.length_sq() > POSITION_EPSILON * POSITION_EPSILON
             });
@@ -302,6 +369,288 @@ fn generate_projectile_deltas(base: &GameSnapshot, current: &GameSnapshot) -> Ve
         .collect()
 }
 
+/// Entities sorted by id in the arena, for binary-search lookups
+fn sorted_by_id<'a, K: Ord + Copy, T>(bump: &'a Bump, items: &'a [T], id: impl Fn(&T) -> K) -> &'a [(K, &'a T)] {
+    let sorted = ArenaVec::from_iter_in(items.iter().map(|item| (id(item), item)), bump).into_bump_slice_mut();
+    sorted.sort_unstable_by_key(|&(key, _)| key);
+    sorted
+}
+
+fn find_by_id<'a, K: Ord + Copy, T>(sorted: &[(K, &'a T)], id: K) -> Option<&'a T> {
+    sorted.binary_search_by_key(&id, |&(key, _)| key).ok().map(|i| sorted[i].1)
+}
+
+// ============================================================================
+// Player Delta Wire Format
+// ============================================================================
+//
+// A player delta is the raw 16-byte id, a LEB128 varint mask of the fields
+// present, then each present field in bit order. Bits are ordered by how
+// often the field changes, so a moving player's mask fits one byte. Floats
+// are quantized to steps finer than their change thresholds and written as
+// zigzag varints; the small enums are written as single bytes.
+
+/// Field bits of the changed-field mask, most frequently changed first
+pub mod field {
+    pub const POSITION: u16 = 1 << 0;
+    pub const VELOCITY: u16 = 1 << 1;
+    pub const ROTATION: u16 = 1 << 2;
+    pub const HEAT: u16 = 1 << 3;
+    pub const CHARGE: u16 = 1 << 4;
+    pub const MASS: u16 = 1 << 5;
+    pub const EFFECTS: u16 = 1 << 6;
+    pub const KILLS: u16 = 1 << 7;
+    pub const ALIVE: u16 = 1 << 8;
+    pub const FACTION: u16 = 1 << 9;
+}
+
+/// Position quantization steps per world unit (below `POSITION_EPSILON`)
+pub const POSITION_STEPS: f32 = 8.0;
+
+/// Velocity quantization steps per unit/second (below `VELOCITY_EPSILON`)
+pub const VELOCITY_STEPS: f32 = 4.0;
+
+/// Rotation quantization steps per full turn (below `ROTATION_EPSILON`)
+pub const ROTATION_STEPS: f32 = 4096.0;
+
+/// Mass quantization steps per mass unit (below `MASS_EPSILON`)
+pub const MASS_STEPS: f32 = 8.0;
+
+/// Largest packed player delta: id, mask and every field at its widest varint
+const MAX_PACKED_PLAYER_DELTA: usize = 16 + 3 + 4 * 5 + 5 + 5 + 1 + 5 + 1 + 4;
+
+/// Bytes of a packed player delta
+struct PackBuf {
+    bytes: [u8; MAX_PACKED_PLAYER_DELTA],
+    len: usize,
+}
+
+impl PackBuf {
+    fn push(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn varint(&mut self, mut value: u32) {
+        while value >= 0x80 {
+            self.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.push(value as u8);
+    }
+
+    fn zigzag(&mut self, value: i32) {
+        self.varint(((value << 1) ^ (value >> 31)) as u32);
+    }
+
+    /// Quantize `value` to `steps` per unit (saturating at the i32 range)
+    fn quantized(&mut self, value: f32, steps: f32) {
+        self.zigzag((value * steps).round() as i32);
+    }
+}
+
+fn unzigzag(value: u32) -> i32 {
+    (value >> 1) as i32 ^ -((value & 1) as i32)
+}
+
+/// Changed-field mask of a delta
+fn field_mask(delta: &PlayerDelta) -> u16 {
+    [
+        (delta.position.is_some(), field::POSITION),
+        (delta.velocity.is_some(), field::VELOCITY),
+        (delta.rotation.is_some(), field::ROTATION),
+        (delta.heat.is_some(), field::HEAT),
+        (delta.charge.is_some(), field::CHARGE),
+        (delta.mass.is_some(), field::MASS),
+        (delta.effects.is_some(), field::EFFECTS),
+        (delta.kills.is_some(), field::KILLS),
+        (delta.alive.is_some(), field::ALIVE),
+        (delta.faction.is_some(), field::FACTION),
+    ]
+    .into_iter()
+    .filter(|&(present, _)| present)
+    .fold(0, |mask, (_, bit)| mask | bit)
+}
+
+impl Serialize for PlayerDelta {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut buf = PackBuf { bytes: [0; MAX_PACKED_PLAYER_DELTA], len: 0 };
+        for &byte in self.id.as_bytes() {
+            buf.push(byte);
+        }
+        buf.varint(field_mask(self) as u32);
+        if let Some(position) = self.position {
+            buf.quantized(position.x, POSITION_STEPS);
+            buf.quantized(position.y, POSITION_STEPS);
+        }
+        if let Some(velocity) = self.velocity {
+            buf.quantized(velocity.x, VELOCITY_STEPS);
+            buf.quantized(velocity.y, VELOCITY_STEPS);
+        }
+        if let Some(rotation) = self.rotation {
+            buf.quantized(rotation, ROTATION_STEPS / std::f32::consts::TAU);
+        }
+        if let Some(heat) = self.heat {
+            buf.push(heat);
+        }
+        if let Some(charge) = self.charge {
+            buf.push(charge);
+        }
+        if let Some(mass) = self.mass {
+            buf.quantized(mass, MASS_STEPS);
+        }
+        if let Some(effects) = self.effects {
+            buf.push(effects);
+        }
+        if let Some(kills) = self.kills {
+            buf.varint(kills);
+        }
+        if let Some(alive) = self.alive {
+            buf.push(alive as u8);
+        }
+        if let Some(faction) = self.faction {
+            buf.push(faction);
+        }
+
+        // A tuple of bytes is written without a length prefix
+        let mut tuple = serializer.serialize_tuple(buf.len)?;
+        for byte in &buf.bytes[..buf.len] {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+}
+
+/// Reads a packed player delta from a serde sequence of bytes
+struct PackedReader<A> {
+    seq: A,
+}
+
+impl<'de, A: SeqAccess<'de>> PackedReader<A> {
+    fn byte(&mut self) -> Result<u8, A::Error> {
+        self.seq.next_element::<u8>()?.ok_or_else(|| de::Error::custom("truncated player delta"))
+    }
+
+    fn varint(&mut self) -> Result<u32, A::Error> {
+        let mut value = 0u32;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u32) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(de::Error::custom("overlong varint in player delta"))
+    }
+
+    fn quantized(&mut self, steps: f32) -> Result<f32, A::Error> {
+        Ok(unzigzag(self.varint()?) as f32 / steps)
+    }
+
+    fn vec2(&mut self, steps: f32) -> Result<Vec2, A::Error> {
+        Ok(Vec2::new(self.quantized(steps)?, self.quantized(steps)?))
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayerDelta {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PackedVisitor;
+
+        impl<'de> Visitor<'de> for PackedVisitor {
+            type Value = PlayerDelta;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a packed player delta")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<PlayerDelta, A::Error> {
+                let mut reader = PackedReader { seq };
+                let mut id = [0u8; 16];
+                for byte in &mut id {
+                    *byte = reader.byte()?;
+                }
+                let mask = reader.varint()?;
+                let has = |bit: u16| mask & bit as u32 != 0;
+                let rotation_steps = ROTATION_STEPS / std::f32::consts::TAU;
+
+                Ok(PlayerDelta {
+                    id: PlayerId::from_bytes(id),
+                    position: if has(field::POSITION) { Some(reader.vec2(POSITION_STEPS)?) } else { None },
+                    velocity: if has(field::VELOCITY) { Some(reader.vec2(VELOCITY_STEPS)?) } else { None },
+                    rotation: if has(field::ROTATION) { Some(reader.quantized(rotation_steps)?) } else { None },
+                    heat: if has(field::HEAT) { Some(reader.byte()?) } else { None },
+                    charge: if has(field::CHARGE) { Some(reader.byte()?) } else { None },
+                    mass: if has(field::MASS) { Some(reader.quantized(MASS_STEPS)?) } else { None },
+                    effects: if has(field::EFFECTS) { Some(reader.byte()?) } else { None },
+                    kills: if has(field::KILLS) { Some(reader.varint()?) } else { None },
+                    alive: if has(field::ALIVE) { Some(reader.byte()? != 0) } else { None },
+                    faction: if has(field::FACTION) { Some(reader.byte()?) } else { None },
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple(MAX_PACKED_PLAYER_DELTA, PackedVisitor)
+    }
+}
+
+// ============================================================================
+// Resync From Acknowledged Base
+// ============================================================================
+
+/// Build a resync that turns `base` (acknowledged by the client) into `current`.
+///
+/// Unlike deltas, entities are compared exactly and sent whole, so the client
+/// reconstructs `current` bit for bit and can use it as the next pinned base.
+pub fn generate_resync(base: &GameSnapshot, current: &GameSnapshot) -> ResyncUpdate {
+    let (players, removed_players) = changed_entities(&base.players, &current.players, |p| p.id);
+    let (projectiles, removed_projectiles) = changed_entities(&base.projectiles, &current.projectiles, |p| p.id);
+    let (debris, removed_debris) = changed_entities(&base.debris, &current.debris, |d| d.id);
+
+    let mut snapshot = current.clone();
+    snapshot.players = players;
+    snapshot.projectiles = projectiles;
+    snapshot.debris = debris;
+
+    ResyncUpdate { base_tick: base.tick, snapshot, removed_players, removed_projectiles, removed_debris }
+}
+
+/// Apply a resync to `base` the way the client does, rebuilding the snapshot it was made from
+pub fn apply_resync(base: &GameSnapshot, resync: &ResyncUpdate) -> GameSnapshot {
+    fn merge<T: Clone, K: Eq + Hash>(base: &[T], changed: &[T], removed: &[K], id: impl Fn(&T) -> K) -> Vec<T> {
+        let changed_ids: HashSet<K> = changed.iter().map(&id).collect();
+        base.iter()
+            .filter(|e| !changed_ids.contains(&id(e)) && !removed.contains(&id(e)))
+            .chain(changed)
+            .cloned()
+            .collect()
+    }
+
+    let mut snapshot = resync.snapshot.clone();
+    snapshot.players = merge(&base.players, &resync.snapshot.players, &resync.removed_players, |p| p.id);
+    snapshot.projectiles =
+        merge(&base.projectiles, &resync.snapshot.projectiles, &resync.removed_projectiles, |p| p.id);
+    snapshot.debris = merge(&base.debris, &resync.snapshot.debris, &resync.removed_debris, |d| d.id);
+    snapshot
+}
+
+/// Entities in `current` that are new or differ from `base`, and ids of those gone from `current`
+fn changed_entities<T, K>(base: &[T], current: &[T], id: impl Fn(&T) -> K) -> (Vec<T>, Vec<K>)
+where
+    T: Clone + PartialEq,
+    K: Copy + Eq + Hash,
+{
+    let base_by_id: HashMap<K, &T> = base.iter().map(|e| (id(e), e)).collect();
+    let current_ids: HashSet<K> = current.iter().map(&id).collect();
+
+    let changed = current
+        .iter()
+        .filter(|e| base_by_id.get(&id(e)).map_or(true, |b| *b != *e))
+        .cloned()
+        .collect();
+    let removed = base.iter().map(&id).filter(|k| !current_ids.contains(k)).collect();
+    (changed, removed)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -310,7 +659,8 @@ fn generate_projectile_deltas(base: &GameSnapshot, current: &GameSnapshot) -> Ve
 mod tests {
     use super::*;
     use crate::game::state::MatchPhase;
-    use crate::net::protocol::player_flags;
+    use crate::game::systems::status_effects::EffectKind;
+    use crate::net::protocol::{player_flags, AsteroidSnapshot, ProjectileSnapshot};
     use uuid::Uuid;
 
     fn create_player(id: Uuid, position: Vec2, kills: u32) -> PlayerSnapshot {
@@ -326,6 +676,10 @@ mod tests {
             deaths: 0,
             color_index: 0,
             spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
         }
     }
 
@@ -353,10 +707,12 @@ mod tests {
             arena_safe_radius: 800.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: player_count,
             total_alive: player_count,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         }
     }
@@ -437,7 +793,7 @@ mod tests {
     #[test]
     fn test_no_changes_produces_no_delta() {
         let player = create_player(Uuid::new_v4(), Vec2::new(100.0, 100.0), 5);
-        let delta = generate_player_delta(&player, &player);
+        let delta = generate_player_delta(&player, &player, 1.0);
         assert!(delta.is_none());
     }
 
@@ -448,7 +804,7 @@ mod tests {
         let mut current = base.clone();
         current.position = Vec2::new(110.0, 100.0); // Moved 10 units
 
-        let delta = generate_player_delta(&base, &current).unwrap();
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
         assert!(delta.position.is_some());
         assert!(delta.velocity.is_none());
         assert!(delta.rotation.is_none());
@@ -462,7 +818,7 @@ mod tests {
         let mut current = base.clone();
         current.position = Vec2::new(100.05, 100.05); // Tiny change
 
-        let delta = generate_player_delta(&base, &current);
+        let delta = generate_player_delta(&base, &current, 1.0);
         assert!(delta.is_none());
     }
 
@@ -473,7 +829,7 @@ mod tests {
         let mut current = base.clone();
         current.velocity = Vec2::new(50.0, 0.0);
 
-        let delta = generate_player_delta(&base, &current).unwrap();
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
         assert!(delta.position.is_none());
         assert!(delta.velocity.is_some());
     }
@@ -485,7 +841,7 @@ mod tests {
         let mut current = base.clone();
         current.rotation = 1.5; // Significant rotation change
 
-        let delta = generate_player_delta(&base, &current).unwrap();
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
         assert!(delta.rotation.is_some());
     }
 
@@ -496,7 +852,7 @@ mod tests {
         let mut current = base.clone();
         current.mass = 150.0;
 
-        let delta = generate_player_delta(&base, &current).unwrap();
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
         assert!(delta.mass.is_some());
     }
 
@@ -507,7 +863,7 @@ mod tests {
         let mut current = base.clone();
         current.flags = 0; // Dead
 
-        let delta = generate_player_delta(&base, &current).unwrap();
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
         assert!(delta.alive.is_some());
         assert_eq!(delta.alive, Some(false));
     }
@@ -519,11 +875,60 @@ mod tests {
         let mut current = base.clone();
         current.kills = 6;
 
-        let delta = generate_player_delta(&base, &current).unwrap();
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
         assert!(delta.kills.is_some());
         assert_eq!(delta.kills, Some(6));
     }
 
+    #[test]
+    fn test_charge_change_detected() {
+        let id = Uuid::new_v4();
+        let base = create_player(id, Vec2::new(100.0, 100.0), 5);
+        let mut current = base.clone();
+        current.charge = 1;
+
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
+        assert_eq!(delta.charge, Some(1));
+
+        // Releasing the shot is sent too, even at coarse epsilon
+        let delta = generate_player_delta(&current, &base, 4.0).unwrap();
+        assert_eq!(delta.charge, Some(0));
+    }
+
+    #[test]
+    fn test_heat_change_detected() {
+        let id = Uuid::new_v4();
+        let base = create_player(id, Vec2::new(100.0, 100.0), 5);
+        let mut current = base.clone();
+        current.heat = 128;
+
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
+        assert_eq!(delta.heat, Some(128));
+        assert!(generate_player_delta(&current, &current, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_faction_change_detected() {
+        let id = Uuid::new_v4();
+        let base = create_player(id, Vec2::new(100.0, 100.0), 5);
+        let mut current = base.clone();
+        current.faction = 3;
+
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
+        assert_eq!(delta.faction, Some(3));
+    }
+
+    #[test]
+    fn test_effects_change_detected() {
+        let id = Uuid::new_v4();
+        let base = create_player(id, Vec2::new(100.0, 100.0), 5);
+        let mut current = base.clone();
+        current.effects = EffectKind::Slow.flag();
+
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
+        assert_eq!(delta.effects, Some(0b010));
+    }
+
     #[test]
     fn test_multiple_changes_combined() {
         let id = Uuid::new_v4();
@@ -533,7 +938,7 @@ mod tests {
         current.velocity = Vec2::new(50.0, 50.0);
         current.kills = 10;
 
-        let delta = generate_player_delta(&base, &current).unwrap();
+        let delta = generate_player_delta(&base, &current, 1.0).unwrap();
         assert!(delta.position.is_some());
         assert!(delta.velocity.is_some());
         assert!(delta.kills.is_some());
@@ -541,6 +946,17 @@ mod tests {
         assert!(delta.mass.is_none());     // Unchanged
     }
 
+    #[test]
+    fn test_epsilon_scale_coarsens_position_threshold() {
+        let id = Uuid::new_v4();
+        let base = create_player(id, Vec2::new(100.0, 100.0), 5);
+        let mut current = base.clone();
+        current.position = Vec2::new(100.3, 100.0);
+
+        assert!(generate_player_delta(&base, &current, 1.0).is_some());
+        assert!(generate_player_delta(&base, &current, 4.0).is_none());
+    }
+
     // ========================================================================
     // Full Delta Generation Tests
     // ========================================================================
@@ -551,7 +967,7 @@ mod tests {
             create_player(Uuid::new_v4(), Vec2::new(100.0, 100.0), 5),
         ]);
 
-        let result = generate_delta(&snapshot, &snapshot, Vec2::ZERO, 100);
+        let result = generate_delta(&snapshot, &snapshot, Vec2::ZERO, 100, 1.0);
         assert!(result.is_none());
     }
 
@@ -565,7 +981,7 @@ mod tests {
         current.tick = 101;
         current.players[0].position = Vec2::new(200.0, 200.0);
 
-        let (delta, stats) = generate_delta(&base, &current, Vec2::ZERO, 101).unwrap();
+        let (delta, stats) = generate_delta(&base, &current, Vec2::ZERO, 101, 1.0).unwrap();
 
         assert_eq!(delta.tick, 101);
         assert_eq!(delta.base_tick, 100);
@@ -574,13 +990,26 @@ mod tests {
         assert_eq!(stats.players_included, 1);
     }
 
+    #[test]
+    fn test_generate_delta_sends_shot_asteroid() {
+        let mut base = create_snapshot(vec![]);
+        base.asteroids = vec![AsteroidSnapshot { id: 3, position: Vec2::new(500.0, 0.0), radius: 60.0, health: 255 }];
+        let mut current = base.clone();
+        assert!(generate_delta(&base, &current, Vec2::ZERO, 100, 1.0).is_none());
+
+        current.asteroids[0].health = 200;
+        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 100, 1.0).unwrap();
+        assert!(delta.player_updates.is_empty());
+        assert_eq!(delta.asteroids, current.asteroids);
+    }
+
     #[test]
     fn test_generate_delta_new_player() {
         let base = create_snapshot(vec![]);
         let new_player = create_player(Uuid::new_v4(), Vec2::new(100.0, 100.0), 0);
         let current = create_snapshot(vec![new_player.clone()]);
 
-        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 100).unwrap();
+        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 100, 1.0).unwrap();
 
         assert_eq!(delta.player_updates.len(), 1);
         // New player should have all fields set
@@ -606,7 +1035,7 @@ mod tests {
         current.players[0].position = Vec2::new(5010.0, 0.0); // Moved
 
         // Even distant players are included (no rate limiting)
-        let (delta, stats) = generate_delta(&base, &current, Vec2::ZERO, 101).unwrap();
+        let (delta, stats) = generate_delta(&base, &current, Vec2::ZERO, 101, 1.0).unwrap();
         assert_eq!(stats.players_included, 1);
         assert_eq!(stats.dormant_rate_count, 1); // Still tracks distance tier for metrics
         assert_eq!(delta.player_updates.len(), 1);
@@ -627,7 +1056,7 @@ mod tests {
             p.position.x += 10.0;
         }
 
-        let (_, stats) = generate_delta(&base, &current, Vec2::ZERO, 101).unwrap();
+        let (_, stats) = generate_delta(&base, &current, Vec2::ZERO, 101, 1.0).unwrap();
 
         assert_eq!(stats.full_rate_count, 1);
         assert_eq!(stats.reduced_rate_count, 1);
@@ -651,7 +1080,7 @@ mod tests {
         // Move projectile 1, leave projectile 2 unchanged
         current.projectiles[0].position = Vec2::new(110.0, 0.0);
 
-        let deltas = generate_projectile_deltas(&base, &current);
+        let deltas = generate_projectile_deltas(&Bump::new(), &base, &current);
         assert_eq!(deltas.len(), 1); // Only changed projectile
         assert_eq!(deltas[0].id, 1);
     }
@@ -674,11 +1103,27 @@ mod tests {
         base.players = vec![create_player(id, Vec2::new(0.0, 0.0), 0)];
         current.players = vec![create_player(id, Vec2::new(10.0, 0.0), 0)];
 
-        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 101).unwrap();
+        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 101, 1.0).unwrap();
         assert!(delta.removed_projectiles.contains(&1));
         assert!(!delta.removed_projectiles.contains(&2));
     }
 
+    #[test]
+    fn test_removed_players_detected() {
+        let (stays, leaves) = (Uuid::new_v4(), Uuid::new_v4());
+        let base = create_snapshot(vec![
+            create_player(stays, Vec2::new(0.0, 0.0), 0),
+            create_player(leaves, Vec2::new(500.0, 0.0), 0),
+        ]);
+        let mut current = create_snapshot(vec![create_player(stays, Vec2::new(0.0, 0.0), 0)]);
+        current.tick = 101;
+
+        // Removal alone is enough to produce a delta
+        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 101, 1.0).unwrap();
+        assert!(delta.player_updates.is_empty());
+        assert_eq!(delta.removed_players, vec![leaves]);
+    }
+
     #[test]
     fn test_new_projectile_included() {
         let base = create_snapshot(vec![]);
@@ -690,9 +1135,200 @@ mod tests {
         let id = Uuid::new_v4();
         current.players = vec![create_player(id, Vec2::new(0.0, 0.0), 0)];
 
-        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 101).unwrap();
+        let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, 101, 1.0).unwrap();
 
         assert_eq!(delta.projectile_updates.len(), 1);
         assert_eq!(delta.projectile_updates[0].id, 1);
     }
+
+    // ========================================================================
+    // Wire Format Tests
+    // ========================================================================
+
+    /// Player delta layout before packing: an `Option` tag per field and full-width values
+    #[derive(Serialize)]
+    struct UnpackedPlayerDelta {
+        id: PlayerId,
+        position: Option<Vec2>,
+        velocity: Option<Vec2>,
+        rotation: Option<f32>,
+        mass: Option<f32>,
+        alive: Option<bool>,
+        kills: Option<u32>,
+        charge: Option<u8>,
+        heat: Option<u8>,
+        faction: Option<u8>,
+        effects: Option<u8>,
+    }
+
+    impl From<&PlayerDelta> for UnpackedPlayerDelta {
+        fn from(d: &PlayerDelta) -> Self {
+            Self {
+                id: d.id,
+                position: d.position,
+                velocity: d.velocity,
+                rotation: d.rotation,
+                mass: d.mass,
+                alive: d.alive,
+                kills: d.kills,
+                charge: d.charge,
+                heat: d.heat,
+                faction: d.faction,
+                effects: d.effects,
+            }
+        }
+    }
+
+    #[test]
+    fn test_packed_player_delta_roundtrip() {
+        use crate::net::protocol::{decode, encode};
+
+        let full = PlayerDelta {
+            id: Uuid::new_v4(),
+            position: Some(Vec2::new(-1234.56, 98765.4)),
+            velocity: Some(Vec2::new(310.3, -0.2)),
+            rotation: Some(-2.5),
+            mass: Some(412.33),
+            alive: Some(false),
+            kills: Some(300),
+            charge: Some(128),
+            heat: Some(255),
+            faction: Some(3),
+            effects: Some(0b101),
+        };
+        let decoded: PlayerDelta = decode(&encode(&full).unwrap()).unwrap();
+        assert_eq!(decoded.id, full.id);
+        let (p, v) = (decoded.position.unwrap(), decoded.velocity.unwrap());
+        assert!((p - full.position.unwrap()).length() <= 0.5 / POSITION_STEPS * 2f32.sqrt());
+        assert!((v - full.velocity.unwrap()).length() <= 0.5 / VELOCITY_STEPS * 2f32.sqrt());
+        assert!((decoded.rotation.unwrap() + 2.5).abs() < ROTATION_EPSILON / 2.0);
+        assert!((decoded.mass.unwrap() - 412.33).abs() < MASS_EPSILON / 2.0);
+        assert_eq!(
+            (decoded.alive, decoded.kills, decoded.charge, decoded.heat, decoded.faction, decoded.effects),
+            (Some(false), Some(300), Some(128), Some(255), Some(3), Some(0b101))
+        );
+
+        // Id, a one-byte mask and two small varints
+        let moved = PlayerDelta { position: Some(Vec2::new(10.0, -10.0)), ..unchanged_player(full.id) };
+        let encoded = encode(&moved).unwrap();
+        assert_eq!(encoded.len(), 16 + 1 + 2 + 2);
+        let decoded: PlayerDelta = decode(&encoded).unwrap();
+        assert_eq!(decoded.position, Some(Vec2::new(10.0, -10.0)));
+        assert!(decoded.velocity.is_none() && decoded.kills.is_none());
+    }
+
+    fn unchanged_player(id: PlayerId) -> PlayerDelta {
+        PlayerDelta {
+            id,
+            position: None,
+            velocity: None,
+            rotation: None,
+            mass: None,
+            alive: None,
+            kills: None,
+            charge: None,
+            heat: None,
+            faction: None,
+            effects: None,
+        }
+    }
+
+    #[test]
+    fn test_packed_deltas_are_30_percent_smaller_at_150_entity_aoi() {
+        use crate::net::protocol::{encode, DebrisSnapshot};
+
+        // 150 players in view, all moving, some turning, firing or growing
+        let players = (0..150)
+            .map(|i| {
+                let angle = i as f32 * 0.7;
+                let mut p = create_player(Uuid::new_v4(), Vec2::from_angle(angle) * (400.0 + i as f32 * 15.0), i % 7);
+                p.velocity = Vec2::from_angle(angle + 1.5) * 180.0;
+                p.rotation = angle % std::f32::consts::PI;
+                p
+            })
+            .collect();
+        let mut base = create_snapshot(players);
+        base.projectiles = (0..20).map(|i| create_projectile(i, Vec2::new(i as f32 * 40.0, 0.0))).collect();
+        base.debris = (0..30)
+            .map(|i| DebrisSnapshot { id: i, position: Vec2::new(0.0, i as f32 * 50.0), size: 1 })
+            .collect();
+
+        let (mut packed, mut unpacked) = (0, 0);
+        for ticks in 1..=10u64 {
+            let dt = ticks as f32 / 30.0;
+            let mut current = base.clone();
+            current.tick = base.tick + ticks;
+            for (i, p) in current.players.iter_mut().enumerate() {
+                p.position += p.velocity * dt;
+                if i % 3 == 0 {
+                    p.rotation += 0.2 * ticks as f32;
+                }
+                if i % 5 == 0 {
+                    p.heat = (ticks * 20) as u8;
+                }
+                if i % 11 == 0 {
+                    p.mass += 2.0;
+                }
+            }
+            for proj in &mut current.projectiles {
+                proj.position += proj.velocity * dt;
+            }
+
+            let (delta, _) = generate_delta(&base, &current, Vec2::ZERO, current.tick, 1.0).unwrap();
+            assert_eq!(delta.player_updates.len(), 150);
+            let size = encode(&delta).unwrap().len();
+            let players_packed = encode(&delta.player_updates).unwrap().len();
+            let players_unpacked: Vec<UnpackedPlayerDelta> = delta.player_updates.iter().map(Into::into).collect();
+            packed += size;
+            unpacked += size - players_packed + encode(&players_unpacked).unwrap().len();
+        }
+
+        let (packed, unpacked) = (packed / 10, unpacked / 10);
+        assert!(
+            (packed as f32) <= unpacked as f32 * 0.7,
+            "average delta {} bytes packed vs {} unpacked",
+            packed,
+            unpacked
+        );
+    }
+
+    // ========================================================================
+    // Resync Tests
+    // ========================================================================
+
+    #[test]
+    fn test_resync_sends_only_changed_entities_and_reconstructs_exactly() {
+        let (idle, moving, leaves, joins) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut base = create_snapshot(vec![
+            create_player(idle, Vec2::new(0.0, 0.0), 1),
+            create_player(moving, Vec2::new(100.0, 0.0), 2),
+            create_player(leaves, Vec2::new(200.0, 0.0), 3),
+        ]);
+        base.projectiles = vec![create_projectile(1, Vec2::new(0.0, 0.0)), create_projectile(2, Vec2::ZERO)];
+
+        let mut current = base.clone();
+        current.tick = 130;
+        current.match_time = 61.0;
+        // Below the delta epsilon, but a resync must still carry it
+        current.players[1].position = Vec2::new(100.01, 0.0);
+        current.players.remove(2);
+        current.players.push(create_player(joins, Vec2::new(300.0, 0.0), 0));
+        current.projectiles.remove(0);
+
+        let resync = generate_resync(&base, &current);
+        assert_eq!(resync.base_tick, 100);
+        assert_eq!(resync.snapshot.tick, 130);
+        let sent: Vec<_> = resync.snapshot.players.iter().map(|p| p.id).collect();
+        assert_eq!(sent, vec![moving, joins]);
+        assert_eq!(resync.removed_players, vec![leaves]);
+        assert!(resync.snapshot.projectiles.is_empty());
+        assert_eq!(resync.removed_projectiles, vec![1]);
+
+        let mut rebuilt = apply_resync(&base, &resync);
+        rebuilt.players.sort_by_key(|p| p.id);
+        current.players.sort_by_key(|p| p.id);
+        assert_eq!(rebuilt.players, current.players);
+        assert_eq!(rebuilt.projectiles, current.projectiles);
+        assert_eq!(rebuilt.match_time, 61.0);
+    }
 }
@@ -0,0 +1,227 @@
+//! Outbound bandwidth accounting
+//!
+//! Every message queued for a connection is tagged with a category (snapshot,
+//! delta, event, chat, system). Writer tasks count the bytes they actually
+//! write per category, both overall (exported in metrics) and per connection,
+//! so the admin API (`/debug/egress`) can list the clients costing the most
+//! egress and what they are being sent.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+
+use crate::game::state::PlayerId;
+use crate::net::protocol::ServerMessage;
+
+/// Length prefix written in front of every message on the reliable stream
+pub const FRAME_PREFIX_BYTES: usize = 4;
+
+/// What an outbound message carries, for bandwidth accounting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageCategory {
+    /// Full snapshots, resyncs and world streaming
+    Snapshot,
+    /// Delta updates against an acknowledged snapshot
+    Delta,
+    /// Game events, phase changes and announcements
+    Event,
+    /// Player chat
+    Chat,
+    /// Join handshake, pings, lobby and command replies
+    System,
+}
+
+impl MessageCategory {
+    pub const ALL: [MessageCategory; 5] = [
+        MessageCategory::Snapshot,
+        MessageCategory::Delta,
+        MessageCategory::Event,
+        MessageCategory::Chat,
+        MessageCategory::System,
+    ];
+
+    /// Name used in metrics labels and the admin API
+    pub fn name(self) -> &'static str {
+        match self {
+            MessageCategory::Snapshot => "snapshot",
+            MessageCategory::Delta => "delta",
+            MessageCategory::Event => "event",
+            MessageCategory::Chat => "chat",
+            MessageCategory::System => "system",
+        }
+    }
+
+    /// Category of a message (sealed messages are always snapshot traffic)
+    pub fn of(message: &ServerMessage) -> Self {
+        match message {
+            ServerMessage::Snapshot(_)
+            | ServerMessage::Resync(_)
+            | ServerMessage::Sealed { .. }
+            | ServerMessage::WorldChunk(_)
+            | ServerMessage::RegionHint(_) => MessageCategory::Snapshot,
+            ServerMessage::Delta(_) => MessageCategory::Delta,
+            ServerMessage::Event(_)
+            | ServerMessage::Events(_)
+            | ServerMessage::PhaseChange { .. }
+            | ServerMessage::Commentary { .. }
+            | ServerMessage::Announcement { .. }
+            | ServerMessage::MatchPaused { .. }
+            | ServerMessage::MatchResumed => MessageCategory::Event,
+            ServerMessage::Chat { .. } => MessageCategory::Chat,
+            ServerMessage::JoinAccepted { .. }
+            | ServerMessage::JoinRejected { .. }
+            | ServerMessage::Pong { .. }
+            | ServerMessage::Kicked { .. }
+            | ServerMessage::SpectatorModeChanged { .. }
+            | ServerMessage::QueueUpdate { .. }
+            | ServerMessage::CommandResult { .. }
+            | ServerMessage::SpectateTargetChanged { .. }
+            | ServerMessage::SnapshotRate { .. }
+            | ServerMessage::RoomList { .. }
+            | ServerMessage::GalaxyMap(_) => MessageCategory::System,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// An encoded message queued for a connection, tagged with its category
+pub type Outbound = (MessageCategory, Arc<Vec<u8>>);
+
+/// Bytes written to one connection, by category
+#[derive(Debug, Default)]
+pub struct ClientEgress {
+    bytes: [AtomicU64; 5],
+}
+
+impl ClientEgress {
+    pub fn bytes(&self, category: MessageCategory) -> u64 {
+        self.bytes[category.index()].load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        MessageCategory::ALL.iter().map(|&c| self.bytes(c)).sum()
+    }
+}
+
+/// One connection's egress, as listed by the admin API
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopTalker {
+    pub player_id: PlayerId,
+    pub total_bytes: u64,
+    /// Bytes per category name
+    pub bytes: HashMap<&'static str, u64>,
+}
+
+/// Bytes written to clients by category, overall and per live connection
+#[derive(Debug, Default)]
+pub struct EgressStats {
+    totals: [AtomicU64; 5],
+    clients: RwLock<HashMap<PlayerId, Arc<ClientEgress>>>,
+}
+
+impl EgressStats {
+    /// Start counting a connection (its writer task keeps the handle)
+    pub fn register(&self, player_id: PlayerId) -> Arc<ClientEgress> {
+        let client = Arc::new(ClientEgress::default());
+        self.clients.write().insert(player_id, client.clone());
+        client
+    }
+
+    /// Stop listing a connection whose writer task ended (unless the player
+    /// has reconnected and a newer writer registered since)
+    pub fn forget(&self, player_id: PlayerId, client: &Arc<ClientEgress>) {
+        let mut clients = self.clients.write();
+        if clients.get(&player_id).is_some_and(|c| Arc::ptr_eq(c, client)) {
+            clients.remove(&player_id);
+        }
+    }
+
+    /// Count a written message of `len` encoded bytes (plus its length prefix)
+    pub fn record(&self, client: Option<&ClientEgress>, category: MessageCategory, len: usize) {
+        let bytes = (len + FRAME_PREFIX_BYTES) as u64;
+        self.totals[category.index()].fetch_add(bytes, Ordering::Relaxed);
+        if let Some(client) = client {
+            client.bytes[category.index()].fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Bytes written in a category since startup
+    pub fn total(&self, category: MessageCategory) -> u64 {
+        self.totals[category.index()].load(Ordering::Relaxed)
+    }
+
+    /// Connections that have been sent the most bytes, largest first
+    pub fn top_talkers(&self, limit: usize) -> Vec<TopTalker> {
+        let mut talkers: Vec<TopTalker> = self
+            .clients
+            .read()
+            .iter()
+            .map(|(&player_id, client)| TopTalker {
+                player_id,
+                total_bytes: client.total(),
+                bytes: MessageCategory::ALL.iter().map(|&c| (c.name(), client.bytes(c))).collect(),
+            })
+            .collect();
+        talkers.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes).then(a.player_id.cmp(&b.player_id)));
+        talkers.truncate(limit);
+        talkers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::protocol::GameEvent;
+
+    #[test]
+    fn test_messages_are_categorized() {
+        assert_eq!(MessageCategory::of(&ServerMessage::Events(Vec::<GameEvent>::new())), MessageCategory::Event);
+        assert_eq!(
+            MessageCategory::of(&ServerMessage::Chat {
+                player_id: PlayerId::new_v4(),
+                name: "Ann".to_string(),
+                text: "gg".to_string()
+            }),
+            MessageCategory::Chat
+        );
+        assert_eq!(MessageCategory::of(&ServerMessage::Pong { client_timestamp: 1, server_timestamp: 2 }), MessageCategory::System);
+        assert_eq!(MessageCategory::of(&ServerMessage::Sealed { nonce: 1, payload: vec![] }), MessageCategory::Snapshot);
+    }
+
+    #[test]
+    fn test_top_talkers_rank_live_connections_by_bytes() {
+        let stats = EgressStats::default();
+        let (quiet, loud) = (PlayerId::new_v4(), PlayerId::new_v4());
+        let quiet_egress = stats.register(quiet);
+        let loud_egress = stats.register(loud);
+
+        stats.record(Some(&quiet_egress), MessageCategory::Chat, 96);
+        stats.record(Some(&loud_egress), MessageCategory::Snapshot, 2996);
+        stats.record(Some(&loud_egress), MessageCategory::Delta, 496);
+        stats.record(None, MessageCategory::System, 60);
+
+        assert_eq!(stats.total(MessageCategory::Snapshot), 3000);
+        assert_eq!(stats.total(MessageCategory::System), 64);
+        let top = stats.top_talkers(10);
+        assert_eq!(top.iter().map(|t| t.player_id).collect::<Vec<_>>(), vec![loud, quiet]);
+        assert_eq!(top[0].total_bytes, 3500);
+        assert_eq!(top[0].bytes["delta"], 500);
+        assert_eq!(stats.top_talkers(1).len(), 1);
+
+        // A reconnect registers a new writer; the old one ending leaves it listed
+        let reconnected = stats.register(quiet);
+        stats.forget(quiet, &quiet_egress);
+        stats.forget(loud, &loud_egress);
+        assert_eq!(stats.top_talkers(10).len(), 1);
+        stats.forget(quiet, &reconnected);
+        assert!(stats.top_talkers(10).is_empty());
+        assert_eq!(stats.total(MessageCategory::Snapshot), 3000);
+    }
+}
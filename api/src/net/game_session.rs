@@ -6,7 +6,7 @@
 //! - Batched writes with coalescing (reduces syscalls)
 //! - Pre-allocated encode buffers (reduces allocations)
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
@@ -86,6 +86,11 @@ impl BufferPool {
             let _ = self.sender.send(buf);
         }
     }
+
+    /// Free pooled buffers beyond `keep` (`get` allocates again on demand)
+    pub fn shrink_to(&self, keep: usize) {
+        while self.receiver.len() > keep && self.receiver.try_recv().is_ok() {}
+    }
 }
 
 /// Global buffer pool for encoding (lazy initialized)
@@ -111,31 +116,107 @@ pub fn encode_pooled<T: serde::Serialize>(message: &T) -> Result<Vec<u8>, String
     }
 }
 
+/// Seal an encoded snapshot for a connection that negotiated snapshot encryption
+/// (None if sealing fails; the snapshot is then dropped like a lost packet)
+fn seal_for(conn: &ClientView, encoded: Arc<Vec<u8>>) -> Option<Arc<Vec<u8>>> {
+    let Some(cipher) = &conn.snapshot_cipher else {
+        return Some(encoded);
+    };
+    match cipher.seal(&encoded).map_err(|e| e.to_string()).and_then(|sealed| encode_pooled(&sealed)) {
+        Ok(sealed) => Some(Arc::new(sealed)),
+        Err(e) => {
+            warn!("Failed to seal snapshot for {}: {}", conn.player_id, e);
+            None
+        }
+    }
+}
+
 /// Return a buffer to the pool after use
 pub fn return_buffer(buf: Vec<u8>) {
     get_encode_pool().put(buf);
 }
 
-use crate::config::{ArenaScalingConfig, DebrisSpawnConfig, GravityWaveConfig};
+/// Free every pooled encode buffer (while hibernating)
+fn release_encode_buffers() {
+    if let Some(pool) = ENCODE_POOL.get() {
+        pool.shrink_to(0);
+    }
+}
+
+use crate::config::{
+    AoiCullingConfig, ArenaScalingConfig, AsteroidConfig, BotPlacementConfig, BroadcastConfig, BoundaryConfig,
+    CollisionConfig, CombatResolverConfig, DebrisSpawnConfig, DesyncConfig, DifficultyConfig, FactionConfig,
+    FuelConfig, GravityWaveConfig, HeatConfig, HibernationConfig, InterpDelayConfig, JoinQueueConfig, JoinStreamConfig,
+    HighlightConfig, ModerationConfig, OrbitAssistConfig, PhysicsConfig, ProjectileEconomyConfig, RegionHintConfig,
+    ReplayConfig, ReportConfig, SafeModeConfig, ScheduleConfig, SendPacingConfig,
+    SnapshotEncryptionConfig, SnapshotEncryptionMode, SnapshotRateConfig, SpectatorDelayConfig, TickWatchdogConfig,
+    WeatherConfig, WellCaptureConfig,
+};
+use crate::game::arena_layout::ArenaLayout;
 use crate::game::constants::{ai, physics};
-use crate::game::game_loop::{GameLoop, GameLoopConfig, GameLoopEvent};
+use crate::game::game_loop::{GameLoop, GameLoopConfig, GameLoopEvent, PauseState};
+use crate::game::desync::{DesyncCheck, DesyncTracker};
+use crate::net::interp_delay::{recommend_delay_ms, InterpDelayTracker};
+use crate::net::send_pacing::{PacingSlot, SendPacer};
+use crate::net::snapshot_rate::{SendSchedule, SnapshotRateController};
+use crate::game::heatmap::HeatmapLayer;
+use crate::game::input_stats::{InputStatsTracker, PlayerInputStats};
+use crate::game::modifiers::{GlobalModifier, ModifierRequests, Parameter};
 use crate::game::performance::{PerformanceMonitor, PerformanceStatus};
+use crate::game::tick_watchdog::{TickWatchdog, WatchdogEvent};
+use crate::game::safe_mode::{ParameterSet, SafeMode, SafeModeEvent};
 use crate::game::state::{MatchPhase, Player, PlayerId};
+use crate::game::systems::chatter::BotChatterSystem;
+use crate::game::systems::custom::{SystemPhase, SystemRegistry};
 use crate::metrics::Metrics;
-use crate::net::aoi::{AOIConfig, AOIManager};
-use crate::net::delta::{generate_delta, DeltaStats};
-use crate::net::protocol::{GameEvent, GameSnapshot, PlayerInput, RejectionReason, ServerMessage};
+use crate::server::ShutdownToken;
+use crate::net::aoi::{AOIConfig, AOIManager, AoiChanges, AoiMembership};
+use crate::net::aoi_index::AoiIndex;
+use crate::net::region_hint::region_hint;
+use crate::net::kill_feed::KillFeed;
+use crate::net::highlights::HighlightRecorder;
+use crate::net::replay::ReplayRecorder;
+use crate::net::broadcast::{BroadcastFrame, BroadcastPool, ClientView};
+use crate::net::delta::{generate_delta, generate_resync, DeltaStats};
+use crate::net::egress::{MessageCategory, Outbound};
+use crate::net::join_queue::{JoinPriority, JoinQueue, PartyInvite, QueueStatus, TicketId, PARTY_INVITE_TTL};
+use crate::net::capture::{SnapshotDigest, SnapshotKind};
+use crate::net::netsim::{DelayQueue, Verdict};
+use crate::net::snapshot_crypto::SnapshotCipher;
+use crate::net::spectator_delay::SpectatorDelay;
+use crate::net::state_view::{GameStateView, PublishedState};
+use crate::net::hibernation::Hibernation;
+use crate::net::account::AccountId;
+use crate::net::moderation::{
+    format_duration, sanitize_chat, unix_millis, AuditEntry, AuditLog, ChatCommand, MuteList,
+};
+use crate::net::protocol::{
+    GameEvent, GameSnapshot, PlayerInput, RejectionReason, ServerMessage, SnapshotRate, StateHash, TelemetryConsent,
+};
+use crate::net::i18n::{keys, LocalizedText};
+use crate::net::reports::{ReportBook, ReportError, ReportReason};
+use crate::roles::{AccessDenied, Permission, Role};
+use crate::util::privacy;
 
 // ============================================================================
 // SPECTATOR MODE CONSTANTS
 // ============================================================================
 
-/// Maximum number of spectators allowed per game session
-const MAX_SPECTATORS: usize = 20;
+/// Maximum number of spectators allowed per game session (and per lobby room)
+pub const MAX_SPECTATORS: usize = 20;
 
 /// Spectator rate limiting: send updates every N ticks (2 = 5Hz at 10Hz tick rate)
 const SPECTATOR_TICK_DIVISOR: u64 = 2;
 
+/// Game ticks between snapshot broadcasts (10 Hz)
+pub(crate) const TICKS_PER_SNAPSHOT: u32 = physics::TICK_RATE / 10;
+
+/// Interval between snapshots sent to players (ms)
+const SNAPSHOT_INTERVAL_MS: f32 = TICKS_PER_SNAPSHOT as f32 * 1000.0 / physics::TICK_RATE as f32;
+
+/// Snapshot send times kept for matching acks (about 3 seconds at 20Hz)
+const SNAPSHOT_SEND_TIMES_RETAINED: usize = 64;
+
 /// Spectator inactivity timeout: kick spectators after this many seconds of no messages
 const SPECTATOR_IDLE_TIMEOUT_SECS: u64 = 300; // 5 minutes
 
@@ -164,6 +245,13 @@ const SPECTATOR_MIN_DEBRIS_SIZE: u8 = 1;
 /// Must fit within client's 32-snapshot buffer
 const FULL_RESYNC_INTERVAL: u64 = 30;
 
+/// Least time between client resync requests that are honored
+pub const MIN_RESYNC_REQUEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Earlier baselines kept per client while a newer one is unacknowledged
+/// (must not exceed the client's retained baselines)
+const MAX_RETAINED_BASELINES: usize = 4;
+
 // ============================================================================
 
 // Feature-gated anticheat integration
@@ -240,26 +328,99 @@ impl SimulationConfig {
 // ============================================================================
 
 /// Per-client network state for delta compression
-/// Tracks the last sent FULL snapshot to generate deltas (pinned base strategy)
+/// Tracks the sent FULL snapshots (baselines); deltas reference the newest one
+/// the client acknowledged, so a lost full snapshot doesn't break its deltas
 pub struct ClientNetState {
-    /// Last full snapshot sent (used as delta base - the "pinned base")
+    /// Last full snapshot sent (the newest baseline)
     pub last_snapshot: Option<GameSnapshot>,
+    /// Baselines sent before `last_snapshot`, oldest first, dropped once a newer one is acknowledged
+    pub earlier_baselines: VecDeque<GameSnapshot>,
     /// Tick of the last full snapshot sent
     pub last_full_tick: u64,
     /// Whether this client needs a full resync (first message or error recovery)
     pub needs_full_resync: bool,
+    /// When the next snapshot is due at this client's snapshot rate
+    pub schedule: SendSchedule,
+    /// Entities currently in this client's AOI (for enter/exit hysteresis)
+    pub aoi: AoiMembership,
+    /// Tick the next region hint is due at
+    pub next_region_hint_tick: u64,
+    /// When the client last had a resync request honored
+    pub last_resync_request: Option<std::time::Instant>,
 }
 
 impl Default for ClientNetState {
     fn default() -> Self {
         Self {
             last_snapshot: None,
+            earlier_baselines: VecDeque::new(),
             last_full_tick: 0,
             needs_full_resync: true, // First message is always full
+            schedule: SendSchedule::default(),
+            aoi: AoiMembership::default(),
+            next_region_hint_tick: 0,
+            last_resync_request: None,
         }
     }
 }
 
+impl ClientNetState {
+    /// The client asked for a full snapshot because its state is corrupt. The
+    /// baselines are dropped too, so the next snapshot is complete rather than
+    /// a resync against state the client no longer trusts. Returns false (and
+    /// changes nothing) within `MIN_RESYNC_REQUEST_INTERVAL` of the last one.
+    pub fn request_resync(&mut self, now: std::time::Instant) -> bool {
+        if self.last_resync_request.is_some_and(|at| now.saturating_duration_since(at) < MIN_RESYNC_REQUEST_INTERVAL) {
+            return false;
+        }
+        self.last_resync_request = Some(now);
+        self.needs_full_resync = true;
+        self.last_snapshot = None;
+        self.earlier_baselines.clear();
+        true
+    }
+
+    /// Make `snapshot` the newest baseline, keeping earlier ones the client may still be acking
+    pub fn push_baseline(&mut self, snapshot: GameSnapshot, acked_tick: Option<u64>) {
+        if let Some(previous) = self.last_snapshot.replace(snapshot) {
+            self.earlier_baselines.push_back(previous);
+        }
+        // Baselines older than the acknowledged one are never referenced again
+        if let Some(acked) = acked_tick {
+            self.earlier_baselines.retain(|base| base.tick >= acked);
+        }
+        while self.earlier_baselines.len() > MAX_RETAINED_BASELINES {
+            self.earlier_baselines.pop_front();
+        }
+    }
+
+    /// Retained baseline the client acknowledged at `acked_tick`
+    pub fn acked_baseline(&self, acked_tick: Option<u64>) -> Option<&GameSnapshot> {
+        let acked = acked_tick?;
+        self.last_snapshot
+            .iter()
+            .chain(self.earlier_baselines.iter().rev())
+            .find(|base| base.tick == acked)
+    }
+
+    /// Base for the next delta: the acknowledged baseline, else the newest sent one
+    /// (before the first ack arrives, or for clients that don't ack)
+    pub fn delta_base(&self, acked_tick: Option<u64>) -> Option<&GameSnapshot> {
+        self.acked_baseline(acked_tick).or(self.last_snapshot.as_ref())
+    }
+}
+
+
+/// Outcome of asking for a player slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinSlot {
+    /// Slot available, add the player now
+    Open,
+    /// Waiting in the join queue
+    Queued(TicketId),
+    /// No slot and the queue is disabled or full
+    Full,
+}
 
 /// A connected player's message channel for lock-free sending
 /// Uses unbounded channel to avoid backpressure blocking the game loop
@@ -270,7 +431,7 @@ pub struct PlayerConnection {
     /// Channel sender for outgoing messages (lock-free)
     /// OPTIMIZATION: Uses Arc<Vec<u8>> to avoid cloning data when broadcasting
     /// to multiple players - only the Arc pointer is cloned (16 bytes)
-    pub sender: mpsc::UnboundedSender<Arc<Vec<u8>>>,
+    pub sender: mpsc::UnboundedSender<Outbound>,
     /// Legacy writer for backwards compatibility during transition
     pub writer: Arc<RwLock<Option<wtransport::SendStream>>>,
     /// Whether this connection is a spectator (no game entity)
@@ -284,6 +445,18 @@ pub struct PlayerConnection {
     pub viewport_zoom: f32,
     /// Delta compression state for this client (interior mutability for lock-free broadcast)
     pub net_state: Arc<tokio::sync::Mutex<ClientNetState>>,
+    /// Access role resolved from the join auth token
+    pub role: Role,
+    /// Account the connection proved it owns at join (see `net::account`)
+    pub account: AccountId,
+    /// Key for sealing this player's snapshots, if negotiated at join
+    pub snapshot_cipher: Option<Arc<SnapshotCipher>>,
+    /// Join snapshot is still being streamed (no regular snapshots until it's done)
+    pub world_streaming: bool,
+    /// Per-player telemetry the player allows (from the join request)
+    pub telemetry: TelemetryConsent,
+    /// Send pacing slot of this connection's writer task
+    pub pacing: PacingSlot,
 }
 
 /// Shared game session that manages the game loop and player connections
@@ -294,11 +467,17 @@ pub struct GameSession {
     pub aoi_manager: AOIManager,
     pub metrics: Option<Arc<Metrics>>,
     last_snapshot_tick: u64,
+    /// Last broadcast on the base 10Hz cadence (recorders stay on it)
+    last_base_snapshot_tick: u64,
     bot_count: usize,
     /// Simulation mode configuration
     simulation_config: SimulationConfig,
     /// Arena scaling configuration (shared with AI manager)
     arena_config: Arc<parking_lot::RwLock<ArenaScalingConfig>>,
+    /// Global modifiers requested by the AI manager
+    modifier_requests: ModifierRequests,
+    /// Projectile economy (shared with AI manager), copied into the game loop each tick
+    projectile_economy: Arc<parking_lot::RwLock<ProjectileEconomyConfig>>,
     /// When the session started (for simulation timing)
     session_start: std::time::Instant,
     /// Last tick when simulation target was updated (rate limiting)
@@ -313,6 +492,57 @@ pub struct GameSession {
     last_client_times: HashMap<PlayerId, u64>,
     /// Last processed input sequence per player (for deduplication)
     last_input_sequences: HashMap<PlayerId, u64>,
+    /// Derived input stats per human player (APM, thrust, boost usage)
+    input_stats: HashMap<PlayerId, InputStatsTracker>,
+    /// Authoritative state hashes and client prediction mismatches
+    desync: DesyncTracker,
+    /// Interpolation delay recommendation settings
+    interp_delay_config: InterpDelayConfig,
+    /// Streaming join snapshots in chunks
+    join_stream_config: JoinStreamConfig,
+    /// Hints of the cells players are about to see
+    region_hint_config: RegionHintConfig,
+    /// Per-client jitter and recommended interpolation delay
+    interp_delays: HashMap<PlayerId, InterpDelayTracker>,
+    /// Assigns per-connection send offsets within the snapshot interval
+    send_pacer: SendPacer,
+    /// Writer tasks of the connections, awaited on shutdown so queued messages go out
+    writer_tasks: Vec<tokio::task::JoinHandle<()>>,
+    /// Snapshot rate negotiation and adaptation settings
+    snapshot_rate_config: SnapshotRateConfig,
+    /// Whether joining players' snapshots are encrypted
+    snapshot_encryption: SnapshotEncryptionMode,
+    /// Past full-view spectator snapshots for the delayed spectator stream
+    /// (shared with broadcast frames, which record into it)
+    spectator_delay: Arc<parking_lot::Mutex<SpectatorDelay>>,
+    /// Casters (moderators and above) watch live despite the delay
+    spectator_delay_exempt_casters: bool,
+    /// Per-player snapshot rate and link quality
+    snapshot_rates: HashMap<PlayerId, SnapshotRateController>,
+    /// Recent (snapshot tick, send time) pairs for ack-based RTT
+    snapshot_send_times: VecDeque<(u64, std::time::Instant)>,
+    /// Latest full snapshot (or resync) tick each client acknowledged applying
+    snapshot_acks: HashMap<PlayerId, u64>,
+    /// Players waiting for a slot, plus session tokens for reconnect priority
+    join_queue: JoinQueue,
+    /// Chat and moderator command settings
+    moderation_config: ModerationConfig,
+    /// Accounts muted from chat
+    mutes: MuteList,
+    /// Connections by the account they joined with, published for presence
+    accounts: Arc<HashMap<AccountId, PlayerId>>,
+    /// Audit trail of moderator commands
+    audit_log: Arc<AuditLog>,
+    /// Player reports, karma and the moderation review queue
+    reports: ReportBook,
+    /// Idle hibernation (reduced bots and tick rate with no one connected)
+    hibernation: Hibernation,
+    /// Tick-time spike detection and profile capture
+    watchdog: TickWatchdog,
+    /// Last-known-good parameters and the freeze after incidents
+    safe_mode: SafeMode,
+    /// Read-only view of the latest tick (shared with metrics when enabled)
+    state_view: Arc<PublishedState>,
     /// Last tick when we checked for idle spectators
     last_idle_check_tick: u64,
     /// Input validator for anti-cheat (feature-gated)
@@ -326,17 +556,29 @@ pub struct GameSession {
 impl GameSession {
     /// Create a new game session without metrics
     pub fn new() -> Self {
-        Self::new_with_metrics_opt(None)
+        Self::new_with_metrics_opt(None, &SystemRegistry::new())
     }
 
     /// Create a new game session with metrics collection
+    #[allow(dead_code)]
     pub fn new_with_metrics(metrics: Arc<Metrics>) -> Self {
-        Self::new_with_metrics_opt(Some(metrics))
+        Self::new_with_metrics_opt(Some(metrics), &SystemRegistry::new())
+    }
+
+    /// Create a new game session with metrics collection that also runs an
+    /// embedder's custom systems (after the server's own, such as bot chatter)
+    pub fn with_systems(metrics: Arc<Metrics>, systems: &SystemRegistry) -> Self {
+        Self::new_with_metrics_opt(Some(metrics), systems)
     }
 
-    fn new_with_metrics_opt(metrics: Option<Arc<Metrics>>) -> Self {
+    fn new_with_metrics_opt(metrics: Option<Arc<Metrics>>, systems: &SystemRegistry) -> Self {
         // Load simulation config from environment
         let simulation_config = SimulationConfig::from_env();
+        let moderation_config = ModerationConfig::from_env();
+        let audit_log = Arc::new(AuditLog::new(moderation_config.audit_log_path.as_deref()));
+        let snapshot_encryption = SnapshotEncryptionConfig::from_env().mode;
+        let spectator_delay_config = SpectatorDelayConfig::from_env().for_encryption(snapshot_encryption);
+        let state_view = metrics.as_ref().map(|m| m.state_view.clone()).unwrap_or_default();
 
         // Determine initial bot count
         let bot_count = if simulation_config.enabled {
@@ -374,12 +616,45 @@ impl GameSession {
         let gravity_wave_config = GravityWaveConfig::from_env();
         let debris_spawn_config = DebrisSpawnConfig::from_env();
         let arena_config = Arc::new(parking_lot::RwLock::new(ArenaScalingConfig::from_env()));
+        let projectile_economy = ProjectileEconomyConfig::from_env();
+        let safe_mode = SafeMode::new(
+            SafeModeConfig::from_env(),
+            ParameterSet { arena: arena_config.read().clone(), projectile_economy },
+        );
 
-        let mut game_loop = GameLoop::new(GameLoopConfig {
+        let collision_config = CollisionConfig::from_env();
+        let mut loop_config = GameLoopConfig {
             gravity_wave_config,
             debris_spawn_config: debris_spawn_config.clone(),
+            collision_matrix: collision_config.matrix,
+            collision_response: collision_config.response,
+            orbit_assist_config: OrbitAssistConfig::from_env(),
+            weather_config: WeatherConfig::from_env(),
+            well_capture_config: WellCaptureConfig::from_env(),
+            combat_resolver_config: CombatResolverConfig::from_env(),
+            difficulty_config: DifficultyConfig::from_env(),
+            faction_config: FactionConfig::from_env(),
+            projectile_economy,
+            heat_config: HeatConfig::from_env(),
+            fuel_config: FuelConfig::from_env(),
+            asteroid_config: AsteroidConfig::from_env(),
+            schedule_config: ScheduleConfig::from_env(),
+            boundary_config: BoundaryConfig::from_env(),
+            bot_placement_config: BotPlacementConfig::from_env(),
+            integrator: PhysicsConfig::from_env().integrator,
+            arena_layout: ArenaLayout::from_env(bot_count, &arena_config.read()),
             ..GameLoopConfig::default()
-        });
+        };
+
+        // Rare bot taunts/distress calls (rate limited, phrase table driven)
+        loop_config.register_system(SystemPhase::Late, BotChatterSystem::from_env());
+
+        // Operator-provided WASM scripts run as a custom system (feature-gated)
+        #[cfg(feature = "scripting")]
+        crate::scripting::register_from_env(&mut loop_config);
+        loop_config.custom_systems.append(systems);
+
+        let mut game_loop = GameLoop::new(loop_config);
 
         // Start in Playing phase immediately (no waiting/countdown)
         game_loop.state_mut().match_state.phase = MatchPhase::Playing;
@@ -413,18 +688,26 @@ impl GameSession {
                 game_loop.state().debris.len()
             );
         }
+        // Asteroids go in once the arena is sized (no-op unless enabled)
+        game_loop.spawn_asteroids();
 
         // Create AOI manager with fully dynamic viewport-based filtering
         // Radius is calculated at runtime from player's viewport_zoom:
         //   radius = (1200 * 1.3) / viewport_zoom
         // At zoom=1.0: ~1560 units, at zoom=0.45: ~3467 units
-        // NO HARDCODED CAPS - the dynamic radius is the ONLY filter
+        // NO HARDCODED CAPS - the dynamic radius is the only distance filter;
+        // entities too small to see at the viewer's zoom are culled
         let aoi_config = AOIConfig {
             always_include_top_n: 10,     // Always show top 10 players for leaderboard
+            min_apparent_radius_px: AoiCullingConfig::from_env().min_apparent_radius_px,
+            ..AOIConfig::default()
         };
         info!(
-            "AOI configured: fully dynamic radius from viewport_zoom, always_top={}",
-            aoi_config.always_include_top_n
+            "AOI configured: fully dynamic radius from viewport_zoom, always_top={}, exit_ratio={}, min_persistence={}, min_apparent_px={}",
+            aoi_config.always_include_top_n,
+            aoi_config.exit_radius_ratio,
+            aoi_config.min_persistence_ticks,
+            aoi_config.min_apparent_radius_px
         );
 
         // Initialize metrics with current state (starts at 0 bots, will ramp up)
@@ -445,9 +728,12 @@ impl GameSession {
             aoi_manager: AOIManager::new(aoi_config),
             metrics,
             last_snapshot_tick: 0,
+            last_base_snapshot_tick: 0,
             bot_count,
             simulation_config,
             arena_config,
+            modifier_requests: ModifierRequests::default(),
+            projectile_economy: Arc::new(parking_lot::RwLock::new(projectile_economy)),
             session_start: std::time::Instant::now(),
             last_simulation_update_tick: 0,
             last_bot_spawn_tick: 0,
@@ -455,6 +741,34 @@ impl GameSession {
             initial_ramp_complete: false,
             last_client_times: HashMap::new(),
             last_input_sequences: HashMap::new(),
+            input_stats: HashMap::new(),
+            desync: DesyncTracker::new(DesyncConfig::from_env()),
+            interp_delay_config: InterpDelayConfig::from_env(),
+            join_stream_config: JoinStreamConfig::from_env(),
+            region_hint_config: RegionHintConfig::from_env(),
+            interp_delays: HashMap::new(),
+            send_pacer: SendPacer::new(SendPacingConfig::from_env()),
+            writer_tasks: Vec::new(),
+            snapshot_rate_config: SnapshotRateConfig::from_env(),
+            snapshot_encryption,
+            spectator_delay: Arc::new(parking_lot::Mutex::new(SpectatorDelay::new(
+                spectator_delay_config.delay_secs * physics::TICK_RATE as u64,
+                TICKS_PER_SNAPSHOT as u64 * SPECTATOR_TICK_DIVISOR,
+            ))),
+            spectator_delay_exempt_casters: spectator_delay_config.exempt_casters,
+            snapshot_rates: HashMap::new(),
+            snapshot_send_times: VecDeque::with_capacity(SNAPSHOT_SEND_TIMES_RETAINED),
+            snapshot_acks: HashMap::new(),
+            join_queue: JoinQueue::new(JoinQueueConfig::from_env()),
+            moderation_config,
+            mutes: MuteList::default(),
+            accounts: Arc::default(),
+            audit_log,
+            reports: ReportBook::new(ReportConfig::from_env()),
+            hibernation: Hibernation::new(HibernationConfig::from_env()),
+            watchdog: TickWatchdog::new(TickWatchdogConfig::from_env()),
+            safe_mode,
+            state_view,
             last_idle_check_tick: 0,
             #[cfg(feature = "anticheat")]
             input_validator: InputValidator::default(),
@@ -468,6 +782,362 @@ impl GameSession {
         self.performance.can_accept_players()
     }
 
+    /// Number of connected human players (excludes spectators)
+    pub fn human_count(&self) -> usize {
+        self.players.values().filter(|c| !c.is_spectator).count()
+    }
+
+    /// Check if a player slot is open for a join of the given priority.
+    /// Normal joins leave the reserved slots free when a human cap is configured.
+    pub fn has_open_slot(&self, priority: JoinPriority) -> bool {
+        if !self.can_accept_player() {
+            return false;
+        }
+        let config = self.join_queue.config();
+        if config.max_humans == 0 {
+            return true;
+        }
+        let reserved = if priority.is_priority() { 0 } else { config.reserved_slots };
+        self.human_count() + reserved < config.max_humans
+    }
+
+    /// Determine queue priority for a join request.
+    /// Consumes the resume token if it belongs to a recently departed player,
+    /// and the party invite if one was presented.
+    pub fn classify_join(
+        &mut self,
+        role: Role,
+        party_invite: Option<PartyInvite>,
+        resume_token: Option<&[u8]>,
+    ) -> JoinPriority {
+        let now = std::time::Instant::now();
+        if resume_token.is_some_and(|token| self.join_queue.claim_reconnect(token, now)) {
+            return JoinPriority::Reconnect;
+        }
+        // Claimed before the role check so a code can't be replayed later
+        let inviter = party_invite.and_then(|code| self.join_queue.claim_party_invite(code, now));
+        if role.allows(Permission::ReservedSlot) {
+            return JoinPriority::Vip;
+        }
+        let party_in_game = inviter
+            .and_then(|id| self.players.get(&id))
+            .is_some_and(|c| !c.is_spectator);
+        if party_in_game {
+            JoinPriority::Party
+        } else {
+            JoinPriority::Normal
+        }
+    }
+
+    /// Ask for a player slot: admit now, queue, or reject.
+    /// Joins never skip ahead of queued players of the same or higher priority.
+    pub fn request_player_slot(&mut self, priority: JoinPriority) -> JoinSlot {
+        if !self.join_queue.has_waiting_ahead(priority) && self.has_open_slot(priority) {
+            self.join_queue.record_admission(std::time::Instant::now());
+            return JoinSlot::Open;
+        }
+        match self.join_queue.enqueue(priority) {
+            Some(ticket) => {
+                debug!("Queued join ticket {} ({:?}), queue length {}", ticket, priority, self.join_queue.len());
+                JoinSlot::Queued(ticket)
+            }
+            None => JoinSlot::Full,
+        }
+    }
+
+    /// Poll a queued join. On `Admit` the caller must add the player before
+    /// releasing the session lock so the slot can't be taken by someone else.
+    pub fn poll_join_ticket(&mut self, ticket: TicketId) -> QueueStatus {
+        let normal_open = self.has_open_slot(JoinPriority::Normal);
+        let priority_open = self.has_open_slot(JoinPriority::Reconnect);
+        self.join_queue.poll(ticket, std::time::Instant::now(), |p| {
+            if p.is_priority() { priority_open } else { normal_open }
+        })
+    }
+
+    /// Drop a queued join (connection closed while waiting)
+    pub fn cancel_join_ticket(&mut self, ticket: TicketId) {
+        self.join_queue.remove(ticket);
+    }
+
+    /// Record the access role of a connection
+    pub fn set_role(&mut self, player_id: PlayerId, role: Role) {
+        if let Some(conn) = self.players.get_mut(&player_id) {
+            conn.role = role;
+        }
+    }
+
+    /// Record the account a connection joined with
+    pub fn set_account(&mut self, player_id: PlayerId, account: AccountId) {
+        if let Some(conn) = self.players.get_mut(&player_id) {
+            conn.account = account;
+            Arc::make_mut(&mut self.accounts).insert(account, player_id);
+        }
+    }
+
+    /// Account of a connection
+    pub fn account_of(&self, player_id: PlayerId) -> Option<AccountId> {
+        self.players.get(&player_id).map(|c| c.account)
+    }
+
+    /// Access role of a connection (unknown connections are plain players)
+    pub fn role_of(&self, player_id: PlayerId) -> Role {
+        self.players.get(&player_id).map(|c| c.role).unwrap_or_default()
+    }
+
+    /// Central permission check for connection-initiated actions
+    pub fn authorize(&self, player_id: PlayerId, permission: Permission) -> Result<(), AccessDenied> {
+        self.role_of(player_id).require(permission)
+    }
+
+    /// Queue a message on a single connection's channel
+    fn send_direct(&self, player_id: PlayerId, message: &ServerMessage) {
+        let Some(conn) = self.players.get(&player_id) else { return };
+        match encode_pooled(message) {
+            Ok(data) => {
+                if let Err(e) = conn.sender.send((MessageCategory::of(message), Arc::new(data))) {
+                    debug!("Send to {}: channel closed ({})", player_id, e);
+                }
+            }
+            Err(e) => warn!("Failed to encode message for {}: {}", player_id, e),
+        }
+    }
+
+    /// Find a connection by player ID or (case-insensitive, unique) name
+    fn find_connection(&self, query: &str) -> Result<PlayerId, LocalizedText> {
+        if let Ok(id) = query.parse::<PlayerId>() {
+            if self.players.contains_key(&id) {
+                return Ok(id);
+            }
+        }
+        let mut matches = self
+            .players
+            .values()
+            .filter(|c| c.player_name.eq_ignore_ascii_case(query))
+            .map(|c| c.player_id);
+        match (matches.next(), matches.next()) {
+            (Some(id), None) => Ok(id),
+            (Some(_), Some(_)) => Err(LocalizedText::new(keys::PLAYER_AMBIGUOUS).with("query", query)),
+            (None, _) => Err(LocalizedText::new(keys::PLAYER_NOT_FOUND).with("query", query)),
+        }
+    }
+
+    /// Handle a chat line from a connection.
+    /// Commands are permission-checked, executed and audit-logged; the caller
+    /// broadcasts the returned message (plain chat or an announcement).
+    pub fn handle_chat(&mut self, sender: PlayerId, text: &str) -> Option<ServerMessage> {
+        let (name, account) = self.players.get(&sender).map(|c| (c.player_name.clone(), c.account))?;
+        self.update_activity(sender);
+        let text = sanitize_chat(text, self.moderation_config.chat_max_len)?;
+
+        let command = match ChatCommand::parse(&text) {
+            None => {
+                if !self.moderation_config.chat_enabled {
+                    return None;
+                }
+                if let Some(left) = self.mutes.remaining(account, std::time::Instant::now()) {
+                    self.send_direct(sender, &ServerMessage::CommandResult {
+                        success: false,
+                        message: LocalizedText::new(keys::CHAT_MUTED).with("duration", format_duration(left)),
+                    });
+                    return None;
+                }
+                return Some(ServerMessage::Chat { player_id: sender, name, text });
+            }
+            Some(Err(e)) => {
+                self.send_direct(sender, &ServerMessage::CommandResult { success: false, message: e.message() });
+                return None;
+            }
+            Some(Ok(command)) => command,
+        };
+
+        let role = self.role_of(sender);
+        let (result, broadcast) = match self.authorize(sender, command.permission()) {
+            Ok(()) => match self.run_command(sender, &command) {
+                Ok((message, broadcast)) => (Ok(message), broadcast),
+                Err(message) => (Err(message), None),
+            },
+            Err(denied) => {
+                debug!("Chat command denied for {}: {}", sender, denied);
+                (Err(LocalizedText::new(keys::COMMAND_DENIED).with("command", command.name())), None)
+            }
+        };
+
+        let (success, message) = match result {
+            Ok(message) => (true, message),
+            Err(message) => (false, message),
+        };
+        self.audit_log.record(AuditEntry {
+            timestamp_ms: unix_millis(),
+            actor_id: sender,
+            actor_name: &privacy::name(&name),
+            role,
+            command: &text,
+            allowed: role.allows(command.permission()),
+            outcome: &message.english(),
+        });
+        self.send_direct(sender, &ServerMessage::CommandResult { success, message });
+        broadcast
+    }
+
+    /// Handle a report from a connection; the reporter is told whether it was accepted
+    pub fn handle_report(&mut self, reporter: PlayerId, target: PlayerId, reason: ReportReason) {
+        let Some(reporter_account) = self.account_of(reporter) else {
+            return;
+        };
+        self.update_activity(reporter);
+        let now = std::time::Instant::now();
+        let result = match self.players.get(&target) {
+            Some(conn) => {
+                let (name, target_account) = (conn.player_name.clone(), conn.account);
+                self.reports.report(reporter_account, target_account, &name, reason, now)
+            }
+            None => Err(ReportError::UnknownTarget),
+        };
+        let (success, message) = match result {
+            Ok(weight) => {
+                info!("Report against {} ({:?}) by {}, weight {:.2}", target, reason, reporter, weight);
+                (true, LocalizedText::new(keys::REPORT_RECEIVED))
+            }
+            Err(e) => (false, e.message()),
+        };
+        self.send_direct(reporter, &ServerMessage::CommandResult { success, message });
+    }
+
+    /// Current karma of an account (0-100, see `net::reports`), carried into lobby matchmaking
+    pub fn karma(&mut self, account: AccountId) -> f32 {
+        self.reports.karma(account, std::time::Instant::now())
+    }
+
+    /// Execute an authorized command. Returns the reply text and an optional broadcast.
+    fn run_command(
+        &mut self,
+        actor: PlayerId,
+        command: &ChatCommand,
+    ) -> Result<(LocalizedText, Option<ServerMessage>), LocalizedText> {
+        match command {
+            ChatCommand::Kick { target, reason } => {
+                let target_id = self.moderation_target(actor, target)?;
+                let name = self.players[&target_id].player_name.clone();
+                self.send_direct(target_id, &ServerMessage::Kicked {
+                    reason: reason
+                        .as_deref()
+                        .map_or_else(|| LocalizedText::new(keys::KICK_BY_MODERATOR), LocalizedText::verbatim),
+                });
+                self.remove_player(target_id);
+                Ok((LocalizedText::new(keys::COMMAND_KICKED).with("name", name), None))
+            }
+            ChatCommand::Mute { target, duration } => {
+                let target_id = self.moderation_target(actor, target)?;
+                let target_conn = &self.players[&target_id];
+                let (name, account) = (target_conn.player_name.clone(), target_conn.account);
+                let duration = duration
+                    .unwrap_or(Duration::from_secs(self.moderation_config.default_mute_secs));
+                self.mutes.mute(account, duration, std::time::Instant::now());
+                if duration.is_zero() {
+                    Ok((LocalizedText::new(keys::COMMAND_UNMUTED).with("name", name), None))
+                } else {
+                    let reply = LocalizedText::new(keys::COMMAND_MUTED).with("name", name);
+                    Ok((reply.with("duration", format_duration(duration)), None))
+                }
+            }
+            ChatCommand::TpSpectate { target } => {
+                if !self.players.get(&actor).is_some_and(|c| c.is_spectator) {
+                    return Err(LocalizedText::new(keys::COMMAND_SPECTATE_FIRST));
+                }
+                let target_id = self.find_connection(target)?;
+                let target_conn = &self.players[&target_id];
+                if target_conn.is_spectator {
+                    let name = &target_conn.player_name;
+                    return Err(LocalizedText::new(keys::COMMAND_TARGET_SPECTATING).with("name", name));
+                }
+                let name = target_conn.player_name.clone();
+                self.set_spectate_target(actor, Some(target_id));
+                self.send_direct(actor, &ServerMessage::SpectateTargetChanged { target_id: Some(target_id) });
+                Ok((LocalizedText::new(keys::COMMAND_FOLLOWING).with("name", name), None))
+            }
+            ChatCommand::Announce { text } => {
+                let announcement = ServerMessage::Announcement { text: LocalizedText::verbatim(text.clone()) };
+                Ok((LocalizedText::new(keys::COMMAND_ANNOUNCED), Some(announcement)))
+            }
+            ChatCommand::Pause { reason } => {
+                // An empty reason stands for the default notice (see `pause_notice`)
+                if !self.game_loop.pause(reason.clone().unwrap_or_default()) {
+                    return Err(LocalizedText::new(keys::COMMAND_ALREADY_PAUSED));
+                }
+                info!("Match paused by {}: {}", actor, reason.as_deref().unwrap_or("no reason"));
+                Ok((LocalizedText::new(keys::COMMAND_PAUSED), self.pause_notice()))
+            }
+            ChatCommand::Resume => {
+                let Some(countdown) = self.game_loop.resume() else {
+                    return Err(LocalizedText::new(keys::COMMAND_NOT_PAUSED));
+                };
+                info!("Match resuming in {:.0}s ({})", countdown, actor);
+                let reply = LocalizedText::new(keys::COMMAND_RESUMING).with("seconds", format!("{:.0}", countdown));
+                Ok((reply, self.pause_notice()))
+            }
+            ChatCommand::Reviews => {
+                let queue = self.reports.review_queue();
+                if queue.is_empty() {
+                    return Ok((LocalizedText::new(keys::COMMAND_NO_REVIEWS), None));
+                }
+                let cases: Vec<String> = queue
+                    .iter()
+                    .map(|case| {
+                        let reasons: Vec<String> =
+                            case.reasons.iter().map(|(reason, count)| format!("{:?} x{}", reason, count)).collect();
+                        let reasons = reasons.join(", ");
+                        format!("{} ({}): score {:.1}, {}", case.target_name, case.target, case.score, reasons)
+                    })
+                    .collect();
+                // Moderator tooling output, not translated
+                Ok((LocalizedText::verbatim(cases.join("; ")), None))
+            }
+            ChatCommand::Resolve { target, upheld } => {
+                // Cases outlive connections, so match the queue rather than connected players
+                let target_id = self
+                    .reports
+                    .review_queue()
+                    .iter()
+                    .find(|case| case.target.to_string() == *target || case.target_name.eq_ignore_ascii_case(target))
+                    .map(|case| case.target)
+                    .ok_or_else(|| LocalizedText::new(keys::COMMAND_NO_CASE).with("player", target))?;
+                let case = self
+                    .reports
+                    .resolve(target_id, *upheld, std::time::Instant::now())
+                    .ok_or_else(|| LocalizedText::new(keys::COMMAND_NO_CASE).with("player", target))?;
+                let key = if *upheld { keys::COMMAND_UPHELD } else { keys::COMMAND_DISMISSED };
+                Ok((LocalizedText::new(key).with("name", case.target_name), None))
+            }
+            ChatCommand::Invite => {
+                if !self.players.get(&actor).is_some_and(|c| !c.is_spectator) {
+                    return Err(LocalizedText::new(keys::COMMAND_INVITE_IN_ARENA));
+                }
+                let code = self.join_queue.issue_party_invite(actor, std::time::Instant::now());
+                let reply = LocalizedText::new(keys::COMMAND_PARTY_INVITE).with("code", code);
+                Ok((reply.with("duration", format_duration(PARTY_INVITE_TTL)), None))
+            }
+        }
+    }
+
+    /// Resolve the target of kick/mute: not yourself, and only lower roles
+    fn moderation_target(&self, actor: PlayerId, query: &str) -> Result<PlayerId, LocalizedText> {
+        let target_id = self.find_connection(query)?;
+        if target_id == actor {
+            return Err(LocalizedText::new(keys::COMMAND_SELF_TARGET));
+        }
+        if self.role_of(target_id) >= self.role_of(actor) {
+            let role = format!("{:?}", self.role_of(target_id)).to_lowercase();
+            return Err(LocalizedText::new(keys::COMMAND_OUTRANKED).with("role", role));
+        }
+        Ok(target_id)
+    }
+
+    /// Remember the session token issued to a player for reconnect priority
+    pub fn remember_session_token(&mut self, player_id: PlayerId, token: Vec<u8>) {
+        self.join_queue.remember_token(player_id, token);
+    }
+
     /// Get rejection reason for when server is at capacity
     pub fn rejection_reason(&self) -> RejectionReason {
         let player_count = self.game_loop.state().players.len() as u32;
@@ -488,6 +1158,82 @@ impl GameSession {
         Arc::clone(&self.arena_config)
     }
 
+    /// Get the modifier request queue (for AI manager)
+    #[allow(dead_code)]
+    pub fn modifier_requests(&self) -> ModifierRequests {
+        Arc::clone(&self.modifier_requests)
+    }
+
+    /// Get shared projectile economy for AI manager
+    #[allow(dead_code)]
+    pub fn projectile_economy(&self) -> Arc<parking_lot::RwLock<ProjectileEconomyConfig>> {
+        Arc::clone(&self.projectile_economy)
+    }
+
+    /// Get the moderator audit log (for the retention job)
+    pub fn audit_log(&self) -> Arc<AuditLog> {
+        Arc::clone(&self.audit_log)
+    }
+
+    /// Announcements of the modifiers already running, for a joining client
+    pub fn active_modifier_events(&self) -> Vec<ServerMessage> {
+        self.game_loop
+            .state()
+            .modifiers
+            .active()
+            .iter()
+            .map(|a| ServerMessage::Event(modifier_started_event(a.modifier, a.remaining)))
+            .collect()
+    }
+
+    /// Spawn the writer task for a connection, returning its pacing slot
+    fn spawn_writer_task(
+        &mut self,
+        player_id: PlayerId,
+        receiver: mpsc::UnboundedReceiver<Outbound>,
+        writer: Arc<RwLock<Option<wtransport::SendStream>>>,
+    ) -> PacingSlot {
+        let pacing = self.send_pacer.assign_slot();
+        let task_pacing = pacing.clone();
+        let metrics = self.metrics.clone();
+
+        // Dev mode: route outbound messages through the network simulator first
+        let receiver = match &self.metrics {
+            Some(metrics) if metrics.netsim.is_enabled() => {
+                let (sender, relayed) = mpsc::unbounded_channel::<Outbound>();
+                let metrics = metrics.clone();
+                tokio::spawn(async move {
+                    run_netsim_relay(player_id, receiver, sender, metrics).await;
+                });
+                relayed
+            }
+            _ => receiver,
+        };
+
+        self.writer_tasks.retain(|task| !task.is_finished());
+        self.writer_tasks.push(tokio::spawn(async move {
+            run_writer_task(player_id, receiver, writer, task_pacing, metrics).await;
+        }));
+        pacing
+    }
+
+    /// Tell every connection the server is going away and drop them all.
+    /// Returns their writer tasks, which end once the goodbye is written.
+    pub fn shutdown(&mut self) -> Vec<tokio::task::JoinHandle<()>> {
+        let connections: Vec<PlayerId> = self.players.keys().copied().collect();
+        info!("Closing {} connections", connections.len());
+        let goodbye = ServerMessage::Kicked { reason: LocalizedText::new(keys::KICK_SERVER_SHUTDOWN) };
+        for player_id in connections {
+            self.send_direct(player_id, &goodbye);
+            self.remove_player(player_id);
+        }
+        // A clean shutdown, so the next start isn't treated as a crash restart
+        if let Err(e) = self.safe_mode.save(false) {
+            warn!("{}", e);
+        }
+        std::mem::take(&mut self.writer_tasks)
+    }
+
     /// Add a player to the game session
     /// Creates a channel-based message sender for lock-free broadcasting
     pub fn add_player(
@@ -497,7 +1243,7 @@ impl GameSession {
         color_index: u8,
         writer: Arc<RwLock<Option<wtransport::SendStream>>>,
     ) -> PlayerId {
-        info!("Player joined: {} ({})", player_name, player_id);
+        info!("Player joined: {} ({})", privacy::name(&player_name), player_id);
 
         // Create player entity with their selected color
         let player = Player::new(player_id, player_name.clone(), false, color_index);
@@ -507,15 +1253,11 @@ impl GameSession {
 
         // Create unbounded channel for lock-free message sending
         // OPTIMIZATION: Uses Arc<Vec<u8>> to avoid cloning broadcast data
-        let (sender, receiver) = mpsc::unbounded_channel::<Arc<Vec<u8>>>();
+        let (sender, receiver) = mpsc::unbounded_channel::<Outbound>();
 
         // Spawn dedicated writer task for this connection
         // This eliminates lock contention - messages are sent via channel
-        let writer_clone = writer.clone();
-        let pid = player_id;
-        tokio::spawn(async move {
-            run_writer_task(pid, receiver, writer_clone).await;
-        });
+        let pacing = self.spawn_writer_task(player_id, receiver, writer.clone());
 
         // Store connection with channel sender
         self.players.insert(
@@ -530,6 +1272,12 @@ impl GameSession {
                 last_activity: Instant::now(),
                 viewport_zoom: 1.0, // Default to normal zoom
                 net_state: Arc::new(tokio::sync::Mutex::new(ClientNetState::default())),
+                role: Role::Player,
+                account: AccountId::from(player_id),
+                snapshot_cipher: None,
+                world_streaming: false,
+                telemetry: TelemetryConsent::default(),
+                pacing,
             },
         );
 
@@ -546,7 +1294,7 @@ impl GameSession {
         player_name: String,
         writer: Arc<RwLock<Option<wtransport::SendStream>>>,
     ) -> PlayerId {
-        info!("Spectator joined: {} ({})", player_name, player_id);
+        info!("Spectator joined: {} ({})", privacy::name(&player_name), player_id);
 
         // Track spectator join
         if let Some(ref metrics) = self.metrics {
@@ -555,14 +1303,10 @@ impl GameSession {
 
         // Create unbounded channel for lock-free message sending
         // OPTIMIZATION: Uses Arc<Vec<u8>> to avoid cloning broadcast data
-        let (sender, receiver) = mpsc::unbounded_channel::<Arc<Vec<u8>>>();
+        let (sender, receiver) = mpsc::unbounded_channel::<Outbound>();
 
         // Spawn dedicated writer task for this connection
-        let writer_clone = writer.clone();
-        let pid = player_id;
-        tokio::spawn(async move {
-            run_writer_task(pid, receiver, writer_clone).await;
-        });
+        let pacing = self.spawn_writer_task(player_id, receiver, writer.clone());
 
         // Store connection as spectator (no game entity created)
         self.players.insert(
@@ -577,6 +1321,12 @@ impl GameSession {
                 last_activity: Instant::now(),
                 viewport_zoom: 0.05, // Spectators start fully zoomed out (supports 10x+ arena)
                 net_state: Arc::new(tokio::sync::Mutex::new(ClientNetState::default())),
+                role: Role::Player,
+                account: AccountId::from(player_id),
+                snapshot_cipher: None,
+                world_streaming: false,
+                telemetry: TelemetryConsent::default(),
+                pacing,
             },
         );
 
@@ -637,6 +1387,8 @@ impl GameSession {
                     metrics.spectator_conversions_total.fetch_add(1, Ordering::Relaxed);
                 }
 
+                // Joined as a spectator, so no rate was negotiated: start at the default
+                self.negotiate_snapshot_rate(spectator_id, SnapshotRate::default());
                 self.update_arena_scale();
                 return true;
             }
@@ -728,6 +1480,44 @@ impl GameSession {
         false
     }
 
+    /// Send each shooter and victim one reliable message with this tick's hits
+    fn send_hit_confirmations(&self, events: &[GameLoopEvent]) {
+        for (player_id, hits) in hit_batches(events) {
+            self.send_direct(player_id, &ServerMessage::Events(hits));
+        }
+    }
+
+    /// Feed kill and well-death locations, and periodic occupancy samples, into the analytics heatmaps
+    fn record_heatmaps(&self, events: &[GameLoopEvent]) {
+        let Some(metrics) = &self.metrics else { return };
+        let heatmaps = &metrics.heatmaps;
+        if !heatmaps.is_enabled() {
+            return;
+        }
+        let state = self.game_loop.state();
+        for event in events {
+            match event {
+                GameLoopEvent::PlayerKilled { victim_id, .. } => {
+                    if let Some(victim) = state.get_player(*victim_id) {
+                        heatmaps.record(HeatmapLayer::Kills, victim.position, 1.0);
+                    }
+                }
+                GameLoopEvent::PlayerFellIntoWell { position, .. } => {
+                    heatmaps.record(HeatmapLayer::WellDeaths, *position, 1.0);
+                }
+                _ => {}
+            }
+        }
+        if heatmaps.sample_due(state.tick) {
+            heatmaps.sample_occupancy(self.game_loop.zone_grid(), state.arena.escape_radius);
+        }
+        if heatmaps.save_due(state.tick) {
+            if let Err(e) = heatmaps.save() {
+                warn!("Failed to save heatmaps: {}", e);
+            }
+        }
+    }
+
     /// Check if we should run idle spectator cleanup this tick
     pub fn should_check_idle_spectators(&mut self) -> bool {
         let current_tick = self.game_loop.state().tick;
@@ -755,11 +1545,28 @@ impl GameSession {
         } else {
             info!("Player left: {}", player_id);
             self.game_loop.remove_player(player_id);
+            self.join_queue.mark_departed(player_id, std::time::Instant::now());
         }
 
-        self.players.remove(&player_id); // Dropping sender closes the channel, ending writer task
+        // Dropping sender closes the channel, ending writer task
+        if let Some(conn) = self.players.remove(&player_id) {
+            if self.accounts.get(&conn.account) == Some(&player_id) {
+                Arc::make_mut(&mut self.accounts).remove(&conn.account);
+            }
+        }
         self.last_client_times.remove(&player_id);
         self.last_input_sequences.remove(&player_id);
+        self.input_stats.remove(&player_id);
+        #[cfg(feature = "anticheat")]
+        self.rejected_inputs.remove(&player_id);
+        self.desync.remove(player_id);
+        self.interp_delays.remove(&player_id);
+        self.snapshot_rates.remove(&player_id);
+        self.snapshot_acks.remove(&player_id);
+        if let Some(ref metrics) = self.metrics {
+            metrics.netsim.clear(player_id);
+            metrics.capture.end(player_id);
+        }
 
         if !was_spectator {
             // Ensure we have enough bots
@@ -811,6 +1618,14 @@ impl GameSession {
         }
     }
 
+    /// Count a failed input validation by violation type
+    #[cfg(feature = "anticheat")]
+    fn record_violation(&self, violation: &crate::anticheat::validator::CheatViolation) {
+        if let Some(metrics) = &self.metrics {
+            metrics.anticheat_violations.increment(violation.kind());
+        }
+    }
+
     /// Queue input for a player with deduplication and validation
     /// Inputs with sequence <= last processed are dropped (duplicate from stream+datagram)
     /// With anticheat feature: validates and sanitizes inputs before processing
@@ -823,6 +1638,11 @@ impl GameSession {
         {
             // Validate sequence progression (catches replay attacks and manipulation)
             if let Err(violation) = self.input_validator.validate_sequence(last_seq, input.sequence) {
+                self.record_violation(&violation);
+                if let Some(metrics) = &self.metrics {
+                    metrics.anticheat_sequence_violations.fetch_add(1, Ordering::Relaxed);
+                }
+
                 // Track rejected inputs
                 *self.rejected_inputs.entry(player_id).or_insert(0) += 1;
                 let count = self.rejected_inputs.get(&player_id).copied().unwrap_or(0);
@@ -838,12 +1658,23 @@ impl GameSession {
                 // For regression, reject the input completely (potential replay attack)
                 // For jumps, log but allow (could be legitimate packet loss recovery)
                 if matches!(violation, crate::anticheat::validator::CheatViolation::SequenceRegression(_, _)) {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.anticheat_inputs_rejected.fetch_add(1, Ordering::Relaxed);
+                    }
                     return;
                 }
             }
 
             // Validate input values
+            if let Some(metrics) = &self.metrics {
+                metrics.anticheat_inputs_validated.fetch_add(1, Ordering::Relaxed);
+            }
             if let Err(violation) = self.input_validator.validate_input(&input) {
+                self.record_violation(&violation);
+                if let Some(metrics) = &self.metrics {
+                    metrics.anticheat_inputs_sanitized.fetch_add(1, Ordering::Relaxed);
+                }
+
                 // Track rejected inputs
                 *self.rejected_inputs.entry(player_id).or_insert(0) += 1;
                 let count = self.rejected_inputs.get(&player_id).copied().unwrap_or(0);
@@ -863,6 +1694,7 @@ impl GameSession {
             // Validate timing (with RTT compensation)
             let server_tick = self.game_loop.state().tick;
             if let Err(violation) = self.input_validator.validate_timing(input.tick, server_tick, 10) {
+                self.record_violation(&violation);
                 // Log but don't reject - timing issues are common with network jitter
                 debug!("Player {} timing issue: {}", player_id, violation);
             }
@@ -876,74 +1708,534 @@ impl GameSession {
         }
 
         self.last_input_sequences.insert(player_id, input.sequence);
+        if self.players.get(&player_id).map_or(true, |c| c.telemetry.behavior) {
+            self.input_stats.entry(player_id).or_default().record(&input);
+        }
+        if let Some(controller) = self.snapshot_rates.get_mut(&player_id) {
+            controller.link_mut().record_sequence(input.sequence);
+        }
+        if let Some(state_hash) = input.state_hash {
+            self.check_state_hash(player_id, state_hash);
+        }
 
-        // Track client timestamp for RTT echo
+        // Track client timestamp for RTT echo and interpolation delay
         if input.client_time > 0 {
             self.last_client_times.insert(player_id, input.client_time);
+            if self.interp_delay_config.enabled {
+                let arrival_ms = self.session_start.elapsed().as_secs_f64() * 1000.0;
+                let interval_ms = 1000.0 / self.snapshot_rate_for(player_id).hz() as f32;
+                self.interp_delays.entry(player_id).or_default().record_input(
+                    input.client_time,
+                    arrival_ms,
+                    interval_ms,
+                    &self.interp_delay_config,
+                );
+            }
         }
         self.game_loop.queue_input(player_id, input);
     }
 
-    /// Run a game tick and return events
-    pub fn tick(&mut self) -> Vec<GameLoopEvent> {
-        // Start performance timing
-        let tick_start = std::time::Instant::now();
-        self.performance.tick_start();
+    /// Recommended interpolation delay for a player (0 = none yet)
+    fn interp_delay_for(&self, player_id: PlayerId) -> u16 {
+        self.interp_delays
+            .get(&player_id)
+            .map(|t| t.recommended_ms())
+            .unwrap_or(0)
+    }
 
-        let events = self.game_loop.tick();
+    /// Apply a player's orbit assist preference (ignored for spectators)
+    pub fn set_orbit_assist(&mut self, player_id: PlayerId, enabled: bool) {
+        if self.players.get(&player_id).is_some_and(|c| !c.is_spectator) {
+            self.game_loop.set_orbit_assist(player_id, enabled);
+        }
+    }
 
-        // Continuously update arena scale for smooth lerping
-        // (scale_for_simulation uses lerp factors that need per-tick updates)
-        self.update_arena_scale();
+    /// Honor a player's telemetry consent: no captures without replays, no
+    /// input stats without behavior analysis, and no per-player listing
+    /// without analytics. They still count toward aggregates.
+    pub fn set_telemetry(&mut self, player_id: PlayerId, telemetry: TelemetryConsent) {
+        let Some(connection) = self.players.get_mut(&player_id) else {
+            return;
+        };
+        connection.telemetry = telemetry;
+        if !telemetry.replays {
+            if let Some(metrics) = &self.metrics {
+                metrics.capture.opt_out(player_id);
+            }
+        }
+        if !telemetry.behavior {
+            self.input_stats.remove(&player_id);
+        }
+        if !telemetry.is_full() {
+            debug!("Player {} limited telemetry: {:?}", player_id, telemetry);
+        }
+    }
 
-        // Respawn dead players (humans always, bots only if performance allows)
-        self.respawn_dead_players();
+    /// Players who opted out of replays, shown anonymously in match replays
+    pub fn replay_opt_outs(&self) -> Vec<PlayerId> {
+        self.players.iter().filter(|(_, conn)| !conn.telemetry.replays).map(|(id, _)| *id).collect()
+    }
 
-        // Update simulation bot target if in simulation mode
-        self.update_simulation_bot_count();
+    /// Snapshot encryption mode for joining players
+    pub fn snapshot_encryption(&self) -> SnapshotEncryptionMode {
+        self.snapshot_encryption
+    }
 
-        // Periodically clean up idle spectators
-        if self.should_check_idle_spectators() {
-            let kicked = self.cleanup_idle_spectators();
-            if !kicked.is_empty() {
-                debug!("Cleaned up {} idle spectators", kicked.len());
-            }
+    /// Seal a player's snapshots with the key negotiated at join
+    pub fn set_snapshot_cipher(&mut self, player_id: PlayerId, cipher: Option<Arc<SnapshotCipher>>) {
+        if let Some(conn) = self.players.get_mut(&player_id) {
+            conn.snapshot_cipher = cipher;
         }
+    }
 
-        // Performance-based bot management
-        // Only forcibly remove bots in catastrophic situations (>150% budget)
-        // Otherwise, let natural attrition handle it by not respawning dead bots
-        if self.performance.should_force_reduce() {
-            // Catastrophic: remove one bot per tick to reduce load
-            self.remove_one_bot();
-        } else if self.performance.can_add_bots() {
-            // Excellent/Good: maintain target bot count (spawns bots up to target)
-            self.maintain_player_count();
-            // In simulation mode, also clean up dead bots if we're over target
-            if self.simulation_config.enabled {
-                self.scale_down_bots_if_needed();
+    /// Key negotiated for a player's snapshots
+    pub fn snapshot_cipher(&self, player_id: PlayerId) -> Option<Arc<SnapshotCipher>> {
+        self.players.get(&player_id).and_then(|conn| conn.snapshot_cipher.clone())
+    }
+
+    /// Hold back regular snapshots while a connection's join snapshot is streamed
+    /// (see `world_stream`). Returns false if streaming is disabled.
+    pub fn begin_world_stream(&mut self, player_id: PlayerId) -> bool {
+        if !self.join_stream_config.enabled {
+            return false;
+        }
+        match self.players.get_mut(&player_id) {
+            Some(conn) => {
+                conn.world_streaming = true;
+                true
             }
-        } else if self.simulation_config.enabled {
-            // In simulation mode during Warning/Critical: still scale down if target is lower
-            self.scale_down_bots_if_needed();
+            None => false,
         }
-        // Warning/Critical (non-simulation): do nothing - bots that die won't respawn, natural reduction
+    }
 
-        // Keep game running forever - reset phase to Playing if it ended
-        // This is an eternal game mode with no match end
-        if self.game_loop.state().match_state.phase == MatchPhase::Ended {
-            self.game_loop.state_mut().match_state.phase = MatchPhase::Playing;
-        }
+    pub fn is_world_streaming(&self, player_id: PlayerId) -> bool {
+        self.players.get(&player_id).is_some_and(|conn| conn.world_streaming)
+    }
+
+    /// Resume regular snapshots once the join snapshot is sent (or wasn't streamed after all)
+    pub fn end_world_stream(&mut self, player_id: PlayerId) {
+        if let Some(conn) = self.players.get_mut(&player_id) {
+            conn.world_streaming = false;
+        }
+    }
+
+    pub fn join_stream_config(&self) -> &JoinStreamConfig {
+        &self.join_stream_config
+    }
+
+    /// Delta compression state of a connection
+    pub fn client_net_state(&self, player_id: PlayerId) -> Option<Arc<tokio::sync::Mutex<ClientNetState>>> {
+        self.players.get(&player_id).map(|conn| conn.net_state.clone())
+    }
+
+    /// Set a player's snapshot rate from the rate its client asked for.
+    /// Returns the rate actually applied.
+    pub fn negotiate_snapshot_rate(&mut self, player_id: PlayerId, requested: SnapshotRate) -> SnapshotRate {
+        if self.players.get(&player_id).map_or(true, |c| c.is_spectator) {
+            return SnapshotRate::default();
+        }
+        let controller = SnapshotRateController::new(requested, &self.snapshot_rate_config, std::time::Instant::now());
+        let rate = controller.rate();
+        self.snapshot_rates.insert(player_id, controller);
+        if let Some(conn) = self.players.get(&player_id) {
+            conn.pacing.set_rate(rate);
+        }
+        if requested != rate {
+            debug!("Player {} asked for {}Hz snapshots, starting at {}Hz", player_id, requested.hz(), rate.hz());
+        }
+        rate
+    }
+
+    /// Current snapshot rate for a player (the default for unknown connections)
+    pub fn snapshot_rate_for(&self, player_id: PlayerId) -> SnapshotRate {
+        self.snapshot_rates
+            .get(&player_id)
+            .map(|c| c.rate())
+            .unwrap_or_default()
+    }
+
+    /// Pause notice for a client joining mid-pause (None while running)
+    pub fn pause_notice(&self) -> Option<ServerMessage> {
+        // Referees may pause without a reason; those get the translatable default
+        let notice = |reason: &String| {
+            if reason.is_empty() {
+                LocalizedText::new(keys::PAUSE_BY_REFEREE)
+            } else {
+                LocalizedText::verbatim(reason.clone())
+            }
+        };
+        match self.game_loop.pause_state() {
+            PauseState::Running => None,
+            PauseState::Paused { reason } => {
+                Some(ServerMessage::MatchPaused { reason: notice(reason), resume_countdown: 0.0 })
+            }
+            PauseState::Resuming { reason, remaining } => {
+                Some(ServerMessage::MatchPaused { reason: notice(reason), resume_countdown: *remaining })
+            }
+        }
+    }
+
+    /// Record a client's snapshot acknowledgement: the base it can resync from, and an RTT sample
+    pub fn record_snapshot_ack(&mut self, player_id: PlayerId, tick: u64) {
+        let acked = self.snapshot_acks.entry(player_id).or_insert(tick);
+        *acked = (*acked).max(tick);
+        let Some(&(_, sent_at)) = self.snapshot_send_times.iter().find(|(t, _)| *t == tick) else {
+            return;
+        };
+        if let Some(controller) = self.snapshot_rates.get_mut(&player_id) {
+            // Includes any send pacing offset, which is at most one interval
+            controller.link_mut().record_rtt(sent_at.elapsed().as_secs_f32() * 1000.0);
+        }
+    }
+
+    /// Check for incidents once per second: enter or leave safe mode, record
+    /// the last-known-good parameters, and undo parameter changes while frozen
+    pub fn update_safe_mode(&mut self) {
+        let now = std::time::Instant::now();
+        let current = ParameterSet {
+            arena: self.arena_config.read().clone(),
+            projectile_economy: *self.projectile_economy.read(),
+        };
+        match self.safe_mode.observe(self.performance.status(), &current, now) {
+            SafeModeEvent::Entered(incident) => {
+                warn!(
+                    "Safe mode after {}: parameters reverted to the last-known-good set and frozen",
+                    incident.description()
+                );
+                if let Some(metrics) = &self.metrics {
+                    metrics.safe_mode_incidents_total.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            SafeModeEvent::Released => info!("Safe mode ended, parameters can change again"),
+            SafeModeEvent::Snapshot => {
+                debug!("Parameters held up, saved as the last-known-good set");
+                if let Err(e) = self.safe_mode.save(true) {
+                    warn!("{}", e);
+                }
+            }
+            SafeModeEvent::Quiet => {}
+        }
+
+        let frozen = self.safe_mode.is_frozen(now);
+        if frozen && current != *self.safe_mode.last_good() {
+            let last_good = self.safe_mode.last_good().clone();
+            *self.arena_config.write() = last_good.arena;
+            *self.projectile_economy.write() = last_good.projectile_economy;
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.safe_mode.store(frozen, Ordering::Relaxed);
+        }
+    }
+
+    /// Re-evaluate every player's snapshot rate, notifying clients whose rate changed
+    pub fn update_snapshot_rates(&mut self) {
+        let now = std::time::Instant::now();
+        let mut changed = Vec::new();
+        for (&player_id, controller) in self.snapshot_rates.iter_mut() {
+            if let Some(rate) = controller.evaluate(now, &self.snapshot_rate_config) {
+                changed.push((player_id, rate));
+            }
+        }
+        for (player_id, rate) in changed {
+            debug!("Player {} snapshot rate now {}Hz", player_id, rate.hz());
+            if let Some(conn) = self.players.get(&player_id) {
+                conn.pacing.set_rate(rate);
+            }
+            self.send_direct(player_id, &ServerMessage::SnapshotRate { hz: rate.hz() });
+            if let Some(ref metrics) = self.metrics {
+                metrics.snapshot_rate_changes_total.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        if let Some(ref metrics) = self.metrics {
+            let count = |rate: SnapshotRate| self.snapshot_rates.values().filter(|c| c.rate() == rate).count() as u64;
+            metrics.snapshot_rate_low_clients.store(count(SnapshotRate::Low), Ordering::Relaxed);
+            metrics.snapshot_rate_normal_clients.store(count(SnapshotRate::Normal), Ordering::Relaxed);
+            metrics.snapshot_rate_high_clients.store(count(SnapshotRate::High), Ordering::Relaxed);
+        }
+    }
+
+    /// Shareable code of the current match's arena seed
+    pub fn arena_code(&self) -> String {
+        self.game_loop.state().arena.seed.code()
+    }
+
+    /// Whether a connection watches the delayed spectator stream
+    /// (spectators only; casters are exempt unless configured otherwise)
+    pub fn is_delayed_spectator(&self, player_id: PlayerId) -> bool {
+        self.players.get(&player_id).is_some_and(|conn| self.watches_delayed(conn))
+    }
+
+    fn watches_delayed(&self, conn: &PlayerConnection) -> bool {
+        conn.is_spectator
+            && self.spectator_delay.lock().is_enabled()
+            && !(self.spectator_delay_exempt_casters && conn.role.allows(Permission::CasterTools))
+    }
+
+    /// Published game state; readers load the latest view without the session lock
+    pub fn state_view(&self) -> Arc<PublishedState> {
+        self.state_view.clone()
+    }
+
+    /// Publish this tick's view (call after the tick, with the session still locked)
+    pub fn publish_state_view(&self) -> Arc<GameStateView> {
+        let bots = self.game_loop.state().players.values().filter(|p| p.is_bot).count();
+        self.state_view.publish(GameStateView {
+            snapshot: Arc::new(self.get_snapshot()),
+            connections: self.players.len(),
+            spectators: self.players.values().filter(|c| c.is_spectator).count(),
+            bots,
+            bot_target: self.bot_count,
+            accounts: self.accounts.clone(),
+            escape_radius: self.game_loop.state().arena.escape_radius,
+            modifiers: self.game_loop.state().modifiers.active().to_vec(),
+            performance: self.performance.status(),
+            budget_usage_percent: self.performance.budget_usage_percent(),
+            published_at: std::time::Instant::now(),
+        })
+    }
+
+    /// Capture what broadcasting `snapshot` needs, so the broadcast runs without the session
+    pub fn broadcast_frame(&self, tick: u64, snapshot: Arc<GameSnapshot>) -> BroadcastFrame {
+        let state = self.game_loop.state();
+        // Streaming joins get their entities from the stream instead
+        let clients = self
+            .players
+            .iter()
+            .filter(|(_, conn)| !conn.world_streaming)
+            .map(|(&player_id, conn)| {
+                let (position, velocity) = state
+                    .get_player(player_id)
+                    .map(|p| (p.position, p.velocity))
+                    .unwrap_or((crate::util::vec2::Vec2::ZERO, crate::util::vec2::Vec2::ZERO));
+                ClientView {
+                    player_id,
+                    sender: conn.sender.clone(),
+                    net_state: conn.net_state.clone(),
+                    snapshot_cipher: conn.snapshot_cipher.clone(),
+                    is_spectator: conn.is_spectator,
+                    spectate_target: conn.spectate_target,
+                    viewport_zoom: conn.viewport_zoom,
+                    position,
+                    velocity,
+                    rate: self.snapshot_rate_for(player_id),
+                    echo_client_time: self.last_client_times.get(&player_id).copied().unwrap_or(0),
+                    interp_delay_ms: self.interp_delay_for(player_id),
+                    acked_tick: self.snapshot_acks.get(&player_id).copied(),
+                    delayed: self.watches_delayed(conn),
+                }
+            })
+            .collect();
+
+        // Bots followed by spectators (bots have no connection)
+        let followed_bots: std::collections::HashSet<PlayerId> = self.players.values()
+            .filter(|c| c.is_spectator)
+            .filter_map(|c| c.spectate_target)
+            .filter(|target_id| !self.players.contains_key(target_id))
+            .collect();
+        let bot_targets = followed_bots
+            .into_iter()
+            .filter_map(|id| state.get_player(id).map(|bot| (id, bot.position, bot.velocity)))
+            .collect();
+
+        BroadcastFrame {
+            tick,
+            snapshot,
+            arena_scale: state.arena.scale,
+            aoi: self.aoi_manager.clone(),
+            clients,
+            bot_targets,
+            spectator_delay: self.spectator_delay.clone(),
+            spectator_interp_delay: self.spectator_interp_delay(),
+            region_hint: Some(self.region_hint_config.clone()).filter(|config| config.enabled),
+            metrics: self.metrics.clone(),
+            captured_at: std::time::Instant::now(),
+        }
+    }
+
+    /// Recommended interpolation delay for full-view spectators (rate-limited, no jitter data)
+    fn spectator_interp_delay(&self) -> u16 {
+        if !self.interp_delay_config.enabled {
+            return 0;
+        }
+        let interval = SNAPSHOT_INTERVAL_MS * SPECTATOR_TICK_DIVISOR as f32;
+        recommend_delay_ms(interval, 0.0, &self.interp_delay_config)
+    }
+
+    /// Compare a client's predicted state hash with the authoritative one
+    fn check_state_hash(&mut self, player_id: PlayerId, state_hash: StateHash) {
+        if !self.desync.is_enabled() {
+            return;
+        }
+        let result = self.desync.check(player_id, state_hash.tick, state_hash.hash);
+        if result == DesyncCheck::Unknown {
+            return;
+        }
+        if let Some(ref metrics) = self.metrics {
+            metrics.desync_checks_total.fetch_add(1, Ordering::Relaxed);
+            if result == DesyncCheck::Mismatch {
+                metrics.desync_mismatches_total.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        // Warn once per streak when it reaches the threshold
+        if let Some(stats) = self.desync.stats(player_id) {
+            if stats.streak == self.desync.warn_streak() {
+                let name = self
+                    .players
+                    .get(&player_id)
+                    .map(|c| privacy::name(&c.player_name))
+                    .unwrap_or_default();
+                warn!(
+                    "Player {} ({}) prediction desync: {} consecutive hash mismatches ({}/{} total)",
+                    player_id, name, stats.streak, stats.mismatches, stats.checks
+                );
+            }
+        }
+    }
+
+    /// Record authoritative state hashes for human players after a tick
+    fn record_state_hashes(&mut self) {
+        if !self.desync.is_enabled() {
+            return;
+        }
+        let state = self.game_loop.state();
+        for player in state.players.values().filter(|p| !p.is_bot && p.alive) {
+            self.desync.record(player.id, state.tick, player.position, player.velocity);
+        }
+    }
+
+    /// Current input stats for every tracked player
+    pub fn input_stats_report(&self) -> Vec<PlayerInputStats> {
+        self.input_stats
+            .iter()
+            .filter(|(player_id, _)| self.players.get(player_id).map_or(true, |c| c.telemetry.analytics))
+            .map(|(&player_id, tracker)| PlayerInputStats {
+                player_id,
+                name: self
+                    .players
+                    .get(&player_id)
+                    .map(|c| privacy::name(&c.player_name))
+                    .unwrap_or_default(),
+                stats: tracker.stats(),
+            })
+            .collect()
+    }
+
+    /// Publish input stats to the metrics registry (served by /players/input-stats)
+    pub fn publish_input_stats(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.set_input_stats(self.input_stats_report());
+            // Suspicion: rejected or sanitized inputs of each connected player
+            #[cfg(feature = "anticheat")]
+            metrics.set_suspicion_scores(
+                self.players
+                    .iter()
+                    .filter(|(_, conn)| !conn.is_spectator)
+                    .map(|(id, _)| self.rejected_inputs.get(id).copied().unwrap_or(0)),
+            );
+        }
+    }
+
+    /// Subscribe to game loop events (kills, match end, well explosions, ...)
+    ///
+    /// Intended for embedders (Discord bots, analytics) that want typed events
+    /// without hooking into the broadcast path.
+    #[allow(dead_code)]
+    pub fn subscribe_events(&self) -> tokio::sync::broadcast::Receiver<GameLoopEvent> {
+        self.game_loop.subscribe()
+    }
+
+    /// Run a game tick and return events
+    pub fn tick(&mut self) -> Vec<GameLoopEvent> {
+        // Start performance timing
+        let tick_start = std::time::Instant::now();
+        self.performance.tick_start();
+        crate::util::arena::begin_tick();
+
+        for modifier in self.modifier_requests.lock().drain(..) {
+            self.game_loop.queue_modifier(modifier);
+        }
+        if let Some((preset, secs)) = self.metrics.as_ref().and_then(|m| m.arena_presets.take_request()) {
+            self.game_loop.queue_preset(preset, secs);
+        }
+        let economy = *self.projectile_economy.read();
+        if *self.game_loop.state().modifiers.projectile_economy() != economy {
+            self.game_loop.set_projectile_economy(economy);
+        }
+        self.update_human_views();
+        let events = self.game_loop.tick();
+        self.record_state_hashes();
+        self.record_heatmaps(&events);
+        self.send_hit_confirmations(&events);
+        self.aoi_manager
+            .set_vision_scale(self.game_loop.state().modifiers.multiplier(Parameter::VisionRadius));
+
+        // Continuously update arena scale for smooth lerping
+        // (scale_for_simulation uses lerp factors that need per-tick updates)
+        self.update_arena_scale();
+
+        // Periodically clean up idle spectators
+        if self.should_check_idle_spectators() {
+            let kicked = self.cleanup_idle_spectators();
+            if !kicked.is_empty() {
+                debug!("Cleaned up {} idle spectators", kicked.len());
+            }
+        }
+
+        // A referee pause freezes the world: no respawns or bot top-ups
+        if !self.game_loop.pause_state().is_frozen() {
+            // Respawn dead players (humans always, bots only if performance allows)
+            self.respawn_dead_players();
+
+            // Update simulation bot target if in simulation mode
+            self.update_simulation_bot_count();
+
+            // Performance-based bot management
+            // Only forcibly remove bots in catastrophic situations (>150% budget)
+            // Otherwise, let natural attrition handle it by not respawning dead bots
+            if self.performance.should_force_reduce() {
+                // Catastrophic: remove one bot per tick to reduce load
+                self.remove_one_bot();
+            } else if self.performance.can_add_bots() {
+                // Excellent/Good: maintain target bot count (spawns bots up to target)
+                self.maintain_player_count();
+                // In simulation mode, also clean up dead bots if we're over target
+                if self.simulation_config.enabled {
+                    self.scale_down_bots_if_needed();
+                }
+            } else if self.simulation_config.enabled {
+                // In simulation mode during Warning/Critical: still scale down if target is lower
+                self.scale_down_bots_if_needed();
+            }
+            // Warning/Critical (non-simulation): do nothing - bots that die won't respawn, natural reduction
+        }
+
+        self.update_hibernation();
+
+        // Keep game running forever - reset phase to Playing if it ended
+        // This is an eternal game mode with no match end
+        if self.game_loop.state().match_state.phase == MatchPhase::Ended {
+            self.game_loop.state_mut().match_state.phase = MatchPhase::Playing;
+        }
 
         // End performance timing
         let entity_count = self.game_loop.state().players.len()
             + self.game_loop.state().projectiles.len();
         self.performance.tick_end(entity_count);
+        let tick_duration = tick_start.elapsed();
+        let watchdog_event =
+            self.watchdog.observe(self.game_loop.state().tick, tick_duration, std::time::Instant::now());
 
         // Update metrics
         if let Some(ref metrics) = self.metrics {
-            let tick_duration = tick_start.elapsed();
             metrics.record_tick_time(tick_duration);
+            if matches!(watchdog_event, WatchdogEvent::Spike | WatchdogEvent::Armed | WatchdogEvent::Captured) {
+                metrics.tick_spikes_total.fetch_add(1, Ordering::Relaxed);
+            }
+            if watchdog_event == WatchdogEvent::Captured {
+                metrics.tick_profiles_total.fetch_add(1, Ordering::Relaxed);
+            }
 
             let state = self.game_loop.state();
 
@@ -957,6 +2249,13 @@ impl GameSession {
             metrics.human_players.store(humans, Ordering::Relaxed);
             metrics.bot_players.store(bots, Ordering::Relaxed);
             metrics.alive_players.store(alive, Ordering::Relaxed);
+            let kills = events.iter().filter(|e| matches!(e, GameLoopEvent::PlayerKilled { .. })).count();
+            metrics.kills_total.fetch_add(kills as u64, Ordering::Relaxed);
+            for event in &events {
+                if let GameLoopEvent::PlayerDied { cause, .. } = event {
+                    metrics.record_death(*cause);
+                }
+            }
 
             // Entity counts
             metrics.projectile_count.store(state.projectiles.len() as u64, Ordering::Relaxed);
@@ -993,6 +2292,14 @@ impl GameSession {
                 state.arena.escape_radius as u64,
                 Ordering::Relaxed,
             );
+            metrics.desync_players.store(self.desync.desynced_count() as u64, Ordering::Relaxed);
+            if !self.interp_delays.is_empty() {
+                let n = self.interp_delays.len() as f32;
+                let jitter: f32 = self.interp_delays.values().map(|t| t.jitter_ms()).sum();
+                let delay: u32 = self.interp_delays.values().map(|t| t.recommended_ms() as u32).sum();
+                metrics.client_jitter_avg_ms.store((jitter / n).round() as u64, Ordering::Relaxed);
+                metrics.interp_delay_avg_ms.store((delay as f32 / n).round() as u64, Ordering::Relaxed);
+            }
 
             // Calculate target radius and area per player for metrics
             {
@@ -1048,6 +2355,9 @@ impl GameSession {
             metrics.bot_ai_full_mode.store(ai_stats.full_mode as u64, Ordering::Relaxed);
             metrics.bot_ai_reduced_mode.store(ai_stats.reduced_mode as u64, Ordering::Relaxed);
             metrics.bot_ai_dormant_mode.store(ai_stats.dormant_mode as u64, Ordering::Relaxed);
+            metrics.bot_ai_watched_zones.store(ai_stats.watched_zones as u64, Ordering::Relaxed);
+            metrics.bot_ai_cold.store(ai_stats.cold_bots as u64, Ordering::Relaxed);
+            metrics.set_ai_batch_timings(ai_stats.batch_timings);
             if let Some(adaptive) = &ai_stats.adaptive {
                 metrics.bot_ai_lod_scale.store((adaptive.lod_scale * 100.0) as u64, Ordering::Relaxed);
                 metrics.bot_ai_health_status.store(adaptive.health_status as u64, Ordering::Relaxed);
@@ -1079,6 +2389,76 @@ impl GameSession {
         events
     }
 
+    /// Hibernate once no client has been connected for the configured idle time
+    /// (never in simulation mode, which exists to load the server without humans)
+    fn update_hibernation(&mut self) {
+        if self.simulation_config.enabled
+            || !self.hibernation.should_sleep(self.players.len(), std::time::Instant::now())
+        {
+            return;
+        }
+
+        let full_target = self.bot_count;
+        self.bot_count = self.hibernation.sleep(full_target);
+        let excess_bots: Vec<PlayerId> = self
+            .game_loop
+            .state()
+            .players
+            .values()
+            .filter(|p| p.is_bot)
+            .skip(self.bot_count)
+            .map(|p| p.id)
+            .collect();
+        for bot_id in &excess_bots {
+            self.game_loop.remove_player(*bot_id);
+        }
+
+        // Per-client state of long-gone connections, and the pooled encode buffers
+        self.last_client_times.shrink_to_fit();
+        self.last_input_sequences.shrink_to_fit();
+        self.input_stats.shrink_to_fit();
+        self.interp_delays.shrink_to_fit();
+        self.snapshot_rates.shrink_to_fit();
+        self.snapshot_acks.shrink_to_fit();
+        self.snapshot_send_times.clear();
+        self.snapshot_send_times.shrink_to_fit();
+        release_encode_buffers();
+
+        if let Some(metrics) = &self.metrics {
+            metrics.hibernating.store(true, Ordering::Relaxed);
+        }
+        info!(
+            "No clients connected, hibernating: {} bots despawned (target {} → {}), ticking at {:?} intervals",
+            excess_bots.len(),
+            full_target,
+            self.bot_count,
+            self.hibernation.tick_interval()
+        );
+    }
+
+    /// Wake from hibernation on a connection attempt: restore the bot target
+    /// (bots ramp back in at the startup spawn rate) and the full tick rate
+    pub fn wake_from_hibernation(&mut self) {
+        let Some(target) = self.hibernation.wake() else {
+            return;
+        };
+        self.bot_count = target;
+        self.initial_ramp_complete = false;
+        if let Some(metrics) = &self.metrics {
+            metrics.hibernating.store(false, Ordering::Relaxed);
+        }
+        info!("Connection attempt, waking from hibernation (bot target {})", target);
+    }
+
+    pub fn is_hibernating(&self) -> bool {
+        self.hibernation.is_asleep()
+    }
+
+    /// Time between ticks, when hibernation slows the game loop
+    fn hibernation_tick_interval(&self) -> Option<Duration> {
+        self.is_hibernating().then(|| self.hibernation.tick_interval())
+    }
+
     /// Remove one bot to reduce server load
     fn remove_one_bot(&mut self) {
         // Find a bot to remove (prefer dead bots, then any bot)
@@ -1178,21 +2558,36 @@ impl GameSession {
     /// Check if we should send a snapshot this tick
     pub fn should_send_snapshot(&self) -> bool {
         let current_tick = self.game_loop.state().tick;
-        // Send snapshot every 3 ticks (30 Hz tick rate / 3 = 10 Hz snapshots)
-        // Or use SNAPSHOT_RATE from constants for 20 Hz
-        let ticks_per_snapshot = physics::TICK_RATE / 10; // 10 Hz for now
-        current_tick > self.last_snapshot_tick &&
-            (current_tick - self.last_snapshot_tick) >= ticks_per_snapshot as u64
+        // Send snapshot every 3 ticks (30 Hz tick rate / 3 = 10 Hz snapshots).
+        // With any 20Hz client, broadcast every tick and let per-client schedules pick.
+        let interval = if self.snapshot_rates.values().any(|c| c.rate() == SnapshotRate::High) {
+            1
+        } else {
+            TICKS_PER_SNAPSHOT as u64
+        };
+        current_tick > self.last_snapshot_tick && (current_tick - self.last_snapshot_tick) >= interval
     }
 
-    /// Mark that a snapshot was sent
-    pub fn mark_snapshot_sent(&mut self) {
-        self.last_snapshot_tick = self.game_loop.state().tick;
+    /// Mark that a snapshot was sent. Returns whether it is on the base 10Hz
+    /// cadence, which 20Hz clients don't change.
+    pub fn mark_snapshot_sent(&mut self) -> bool {
+        let tick = self.game_loop.state().tick;
+        self.last_snapshot_tick = tick;
+        let on_base_cadence = tick.saturating_sub(self.last_base_snapshot_tick) >= TICKS_PER_SNAPSHOT as u64;
+        if on_base_cadence {
+            self.last_base_snapshot_tick = tick;
+        }
+        if self.snapshot_send_times.len() >= SNAPSHOT_SEND_TIMES_RETAINED {
+            self.snapshot_send_times.pop_front();
+        }
+        self.snapshot_send_times.push_back((tick, std::time::Instant::now()));
+        on_base_cadence
     }
 
     /// Get current game snapshot (full, unfiltered)
     pub fn get_snapshot(&self) -> GameSnapshot {
         let mut snapshot = GameSnapshot::from_game_state(self.game_loop.state());
+        snapshot.apply_charges(self.game_loop.charges());
 
         // Add AI manager status if available
         if let Some(metrics) = &self.metrics {
@@ -1216,6 +2611,25 @@ impl GameSession {
         snapshot
     }
 
+    /// Tell the bot AI what every human is looking at: players see around
+    /// themselves, spectators around whoever they follow. Full-map spectators
+    /// would cover everything, so they don't count.
+    fn update_human_views(&mut self) {
+        let state = self.game_loop.state();
+        let arena_scale = state.arena.scale;
+        let views = self
+            .players
+            .values()
+            .filter_map(|conn| {
+                let watched_id = if conn.is_spectator { conn.spectate_target? } else { conn.player_id };
+                let watched = state.get_player(watched_id).filter(|p| p.alive)?;
+                let radius = self.aoi_manager.effective_radius(watched.velocity, conn.viewport_zoom, arena_scale);
+                Some((watched.position, radius))
+            })
+            .collect();
+        self.game_loop.set_human_views(views);
+    }
+
     /// Get a filtered snapshot for a specific player using AOI
     /// Used for initial snapshots on player join to ensure consistency with broadcast filtering
     pub fn get_filtered_snapshot(&self, player_id: PlayerId) -> GameSnapshot {
@@ -1288,21 +2702,13 @@ impl GameSession {
             };
 
             if should_respawn {
-                // Collect alive player positions and wells for safe spawn
-                let alive_positions: Vec<_> = self
-                    .game_loop
-                    .state()
-                    .players
-                    .values()
-                    .filter(|p| p.alive && p.id != player_id)
-                    .map(|p| p.position)
-                    .collect();
+                // Find safe spawn position near a gravity well, away from other players
+                let position = self.game_loop.spawn_position(player_id, is_bot);
                 let wells: Vec<_> = self.game_loop.state().arena.gravity_wells.values().cloned().collect();
                 let current_tick = self.game_loop.state().tick;
 
                 if let Some(player) = self.game_loop.state_mut().get_player_mut(player_id) {
-                    // Find safe spawn position near a gravity well, away from other players
-                    player.position = arena::safe_spawn_near_well(&wells, &alive_positions);
+                    player.position = position;
 
                     // Use orbital velocity relative to nearest well
                     player.velocity = arena::spawn_velocity_for_well(player.position, &wells);
@@ -1312,6 +2718,9 @@ impl GameSession {
                     player.spawn_protection = crate::game::constants::spawn::PROTECTION_DURATION;
                     player.respawn_timer = 0.0;
                     player.spawn_tick = current_tick;
+                    player.heat = 0.0;
+                    player.fuel = 1.0;
+                    player.effects.clear();
 
                     respawned += 1;
                     debug!("Respawned player {}", player_id);
@@ -1399,6 +2808,11 @@ const WRITE_BATCH_SIZE: usize = 16;
 /// Maximum bytes to batch before writing (64KB)
 const WRITE_BATCH_BYTES: usize = 65536;
 
+/// Snapshot traffic, which the writer task holds until its pacing slot
+fn is_paced(category: MessageCategory) -> bool {
+    matches!(category, MessageCategory::Snapshot | MessageCategory::Delta)
+}
+
 /// Dedicated writer task for a player connection
 /// Batches multiple messages before writing to reduce syscall overhead
 /// Reads from channel and writes to stream - eliminates lock contention
@@ -1408,17 +2822,43 @@ const WRITE_BATCH_BYTES: usize = 65536;
 /// (30 players = 29 × 2.5KB = 72.5KB saved per tick)
 async fn run_writer_task(
     player_id: PlayerId,
-    mut receiver: mpsc::UnboundedReceiver<Arc<Vec<u8>>>,
+    mut receiver: mpsc::UnboundedReceiver<Outbound>,
     writer: Arc<RwLock<Option<wtransport::SendStream>>>,
+    pacing: PacingSlot,
+    metrics: Option<Arc<Metrics>>,
 ) {
-    debug!("Writer task started for player {}", player_id);
+    debug!("Writer task started for player {} (pacing offset {:?})", player_id, pacing.offset());
+    let egress = metrics.as_ref().map(|m| m.egress.register(player_id));
+    // Categories and sizes of the messages in the batch, counted once it is written
+    let mut batch_messages: Vec<(MessageCategory, usize)> = Vec::with_capacity(WRITE_BATCH_SIZE);
 
     // Pre-allocated write buffer for batching
     let mut batch_buffer = Vec::with_capacity(WRITE_BATCH_BYTES);
 
-    while let Some(first_data) = receiver.recv().await {
+    // A snapshot pulled into an unpaced batch, held back for the next one
+    let mut deferred: Option<Outbound> = None;
+
+    loop {
+        let next = match deferred.take() {
+            Some(message) => Some(message),
+            None => receiver.recv().await,
+        };
+        let Some((first_category, first_data)) = next else { break };
+
+        // Send pacing: hold snapshot batches until this connection's slot in its
+        // current snapshot interval (messages arriving meanwhile join the batch)
+        let paced = is_paced(first_category);
+        if paced {
+            let offset = pacing.offset();
+            if !offset.is_zero() {
+                tokio::time::sleep(offset).await;
+            }
+        }
+
         // Start building the batch with the first message
         batch_buffer.clear();
+        batch_messages.clear();
+        batch_messages.push((first_category, first_data.len()));
 
         // Add first message with length prefix
         // OPTIMIZATION: Access Arc contents directly, no clone needed
@@ -1430,7 +2870,12 @@ async fn run_writer_task(
         let mut msg_count = 1;
         while msg_count < WRITE_BATCH_SIZE && batch_buffer.len() < WRITE_BATCH_BYTES {
             match receiver.try_recv() {
-                Ok(data) => {
+                Ok((category, data)) if !paced && is_paced(category) && !pacing.offset().is_zero() => {
+                    deferred = Some((category, data));
+                    break;
+                }
+                Ok((category, data)) => {
+                    batch_messages.push((category, data.len()));
                     batch_buffer.extend_from_slice(&(data.len() as u32).to_le_bytes());
                     batch_buffer.extend_from_slice(&*data);
                     msg_count += 1;
@@ -1451,15 +2896,66 @@ async fn run_writer_task(
                 warn!("Writer task {}: flush failed: {}", player_id, e);
                 break;
             }
+            if let Some(metrics) = &metrics {
+                metrics.send_bytes_total.fetch_add(batch_buffer.len() as u64, Ordering::Relaxed);
+                metrics.send_burst.record(batch_buffer.len());
+                for &(category, len) in &batch_messages {
+                    metrics.egress.record(egress.as_deref(), category, len);
+                }
+            }
         } else {
             warn!("Writer task {}: stream closed", player_id);
             break;
         }
     }
 
+    if let (Some(metrics), Some(egress)) = (&metrics, &egress) {
+        metrics.egress.forget(player_id, egress);
+    }
     debug!("Writer task ended for player {}", player_id);
 }
 
+/// Network simulator relay between a connection's channel and its writer task.
+/// Holds, drops or reorders outbound messages according to the player's
+/// current simulated conditions (messages pass straight through when none are set).
+async fn run_netsim_relay(
+    player_id: PlayerId,
+    mut receiver: mpsc::UnboundedReceiver<Outbound>,
+    sender: mpsc::UnboundedSender<Outbound>,
+    metrics: Arc<Metrics>,
+) {
+    let mut held = DelayQueue::default();
+
+    loop {
+        let next_release = held.next_release();
+        tokio::select! {
+            data = receiver.recv() => {
+                let Some(data) = data else { break };
+                let now = std::time::Instant::now();
+                match metrics.netsim.conditions(player_id) {
+                    // No conditions: keep ordering behind anything still held
+                    None => held.push(data, now, Duration::ZERO, false),
+                    Some(conditions) => match conditions.roll(&mut rand::thread_rng()) {
+                        Verdict::Drop => continue,
+                        Verdict::Deliver(delay) => held.push(data, now, delay, conditions.reorder),
+                    },
+                }
+            }
+            _ = tokio::time::sleep_until(next_release.map_or_else(Instant::now, Instant::from_std)),
+                if next_release.is_some() => {}
+        }
+
+        let now = std::time::Instant::now();
+        while let Some(data) = held.pop_due(now) {
+            if sender.send(data).is_err() {
+                return; // Writer task ended
+            }
+        }
+    }
+
+    debug!("Network simulator relay ended for player {}", player_id);
+}
+
 /// Broadcast a message to all connected players using channels (lock-free)
 ///
 /// OPTIMIZATION: Wraps encoded data in Arc so each player receives a cheap
@@ -1480,55 +2976,136 @@ pub async fn broadcast_message(
 
     // Wrap in Arc for zero-copy sharing across all players
     let shared = Arc::new(encoded);
+    let category = MessageCategory::of(message);
 
     // Send via channels - no locks, no spawning
     // Each channel sender clones the Arc pointer, not the data
     for (player_id, conn) in session.players.iter() {
-        if let Err(e) = conn.sender.send(shared.clone()) {
+        if let Err(e) = conn.sender.send((category, shared.clone())) {
             debug!("Broadcast to {}: channel closed ({})", player_id, e);
         }
     }
     // Arc is dropped here; Vec freed when all receivers process their messages
 }
 
+/// Broadcast a message to spectator connections only
+#[cfg(feature = "ai_manager")]
+pub async fn broadcast_to_spectators(session: &GameSession, message: &ServerMessage) {
+    let encoded = match encode_pooled(message) {
+        Ok(data) => data,
+        Err(e) => {
+            warn!("Failed to encode message for spectators: {}", e);
+            return;
+        }
+    };
+    let shared = Arc::new(encoded);
+    let category = MessageCategory::of(message);
+    for (player_id, conn) in session.players.iter().filter(|(_, c)| c.is_spectator) {
+        if let Err(e) = conn.sender.send((category, shared.clone())) {
+            debug!("Spectator broadcast to {}: channel closed ({})", player_id, e);
+        }
+    }
+}
+
+/// Broadcast a message only to connections whose AOI contains `position`
+///
+/// Players use their own entity, follow-mode spectators their target;
+/// full-view spectators see the whole map and always receive it.
+pub async fn broadcast_message_near(
+    session: &GameSession,
+    message: &ServerMessage,
+    position: crate::util::vec2::Vec2,
+) {
+    broadcast_message_witnessed(session, message, position, &[], false).await;
+}
+
+/// Broadcast a message to connections whose AOI contains `position`, plus the
+/// `involved` players and, with `all_spectators`, every spectator
+pub async fn broadcast_message_witnessed(
+    session: &GameSession,
+    message: &ServerMessage,
+    position: crate::util::vec2::Vec2,
+    involved: &[PlayerId],
+    all_spectators: bool,
+) {
+    let state = session.game_loop.state();
+    let arena_scale = state.arena.scale;
+    let recipients: Vec<_> = session
+        .players
+        .iter()
+        .filter(|(player_id, conn)| {
+            if involved.contains(player_id) || (conn.is_spectator && all_spectators) {
+                return true;
+            }
+            let viewer_id = if conn.is_spectator {
+                match conn.spectate_target {
+                    Some(target) => target,
+                    None => return true,
+                }
+            } else {
+                **player_id
+            };
+            state.get_player(viewer_id).is_some_and(|viewer| {
+                session
+                    .aoi_manager
+                    .is_in_range(viewer.position, conn.viewport_zoom, arena_scale, position)
+            })
+        })
+        .collect();
+    if recipients.is_empty() {
+        return;
+    }
+
+    let encoded = match encode_pooled(message) {
+        Ok(data) => data,
+        Err(e) => {
+            warn!("Failed to encode message for AOI broadcast: {}", e);
+            return;
+        }
+    };
+    let shared = Arc::new(encoded);
+    let category = MessageCategory::of(message);
+    for (player_id, conn) in recipients {
+        if let Err(e) = conn.sender.send((category, shared.clone())) {
+            debug!("AOI broadcast to {}: channel closed ({})", player_id, e);
+        }
+    }
+}
+
 /// Broadcast AOI-filtered snapshots to each player using channels (lock-free)
 /// Each player receives only entities relevant to their position
 /// Uses pooled buffers to minimize allocations
 ///
+/// Runs on the broadcast pool (see `net::broadcast`) from a frame the game loop
+/// captured, never touching the session. Players are split across `workers` tasks.
+///
 /// SPECTATOR OPTIMIZATION:
 /// - Full-view spectators share a single pre-encoded snapshot (Arc)
 /// - Follow-mode spectators reuse the target player's cached snapshot
 /// - Spectators receive updates at 5Hz (every 2nd tick) vs 10Hz for players
+/// - With a spectator delay, non-caster spectators get buffered full views instead
 ///
 /// DELTA COMPRESSION:
 /// - Sends full snapshot periodically (every FULL_RESYNC_INTERVAL ticks)
 /// - Between full snapshots, sends deltas with only changed fields
 /// - Distance-based rate limiting: close entities 30Hz, medium 7.5Hz, far 3.75Hz
-/// - Uses "pinned base" strategy: deltas always reference last FULL snapshot
-pub async fn broadcast_filtered_snapshots(session: &GameSession, tick: u64) {
-    use std::sync::Arc;
-
-    // Get full snapshot once
-    let full_snapshot = session.get_snapshot();
-
-    // Track AOI stats for metrics (feature-gated)
-    #[cfg(feature = "metrics_extended")]
-    let mut total_original_players = 0usize;
-    #[cfg(feature = "metrics_extended")]
-    let mut total_filtered_players = 0usize;
-    #[cfg(feature = "metrics_extended")]
-    let mut total_original_projectiles = 0usize;
-    #[cfg(feature = "metrics_extended")]
-    let mut total_filtered_projectiles = 0usize;
+/// - Deltas reference the newest FULL snapshot the client acknowledged (retained per client)
+/// - Full resyncs go out as the changes since the client's acknowledged base when smaller
+pub async fn broadcast_filtered_snapshots(frame: Arc<BroadcastFrame>, workers: usize) {
+    let tick = frame.tick;
+    let full_snapshot = &frame.snapshot;
 
     // OPTIMIZATION: Check if we have any spectators that need full snapshot
     // This includes full-view spectators AND follow-mode spectators following bots
     // (bots don't have connections, so won't be in player_snapshot_cache)
-    let has_spectators = session.players.values().any(|c| c.is_spectator);
+    let has_spectators = frame.clients.iter().any(|c| c.is_spectator);
+    // The delayed stream keeps recording with nobody watching, so a spectator
+    // who joins later is served as soon as the delay has elapsed
+    let delay_enabled = frame.spectator_delay.lock().is_enabled();
 
     // Find minimum zoom among full-view spectators for conservative filtering
     // Lower zoom = more zoomed out = filter more aggressively
-    let min_spectator_zoom = session.players.values()
+    let min_spectator_zoom = frame.clients.iter()
         .filter(|c| c.is_spectator && c.spectate_target.is_none())
         .map(|c| c.viewport_zoom)
         .fold(1.0f32, f32::min);
@@ -1536,10 +3113,16 @@ pub async fn broadcast_filtered_snapshots(session: &GameSession, tick: u64) {
     // OPTIMIZATION: Pre-encode full snapshot ONCE for spectators
     // This saves ~2ms per spectator by encoding only once and sharing via Arc
     // Always create if there are ANY spectators (needed as fallback for bot targets)
-    let full_snapshot_bytes: Option<Arc<Vec<u8>>> = if has_spectators {
+    let full_snapshot_bytes: Option<Arc<Vec<u8>>> = if has_spectators || delay_enabled {
         // Create a spectator-optimized snapshot using minimum zoom for filtering
         // This conservatively filters based on the most zoomed-out spectator
-        let spectator_snapshot = create_spectator_snapshot(&full_snapshot, min_spectator_zoom);
+        let mut spectator_snapshot = create_spectator_snapshot(
+            full_snapshot,
+            min_spectator_zoom,
+            frame.aoi.min_apparent_radius_px(),
+        );
+        frame.aoi.cull_small_players(&mut spectator_snapshot, min_spectator_zoom);
+        spectator_snapshot.interp_delay_ms = frame.spectator_interp_delay;
         let message = ServerMessage::Snapshot(spectator_snapshot);
         match encode_pooled(&message) {
             Ok(encoded) => Some(Arc::new(encoded)),
@@ -1551,132 +3134,363 @@ pub async fn broadcast_filtered_snapshots(session: &GameSession, tick: u64) {
     } else {
         None
     };
+    if let Some(ref full) = full_snapshot_bytes {
+        frame.spectator_delay.lock().record(tick, full.clone());
+    }
 
     // OPTIMIZATION: Cache player snapshots for follow-mode spectators
     // Spectators following a player get the exact same bytes (zero extra encoding)
     let mut player_snapshot_cache: HashMap<PlayerId, Arc<Vec<u8>>> = HashMap::new();
 
-    // OPTIMIZATION: Pre-compute bot snapshots for spectators following bots
-    // Collect unique bot targets first, then compute snapshots once per bot
-    let bot_targets: std::collections::HashSet<PlayerId> = session.players.values()
-        .filter(|c| c.is_spectator)
-        .filter_map(|c| c.spectate_target)
-        .filter(|target_id| {
-            // It's a bot if there's no connection for this player ID
-            !session.players.contains_key(target_id)
-        })
-        .collect();
+    // Index the full snapshot once so each client's AOI filter only visits nearby entities
+    let aoi_index = Arc::new(frame.aoi.build_index(full_snapshot.clone()));
 
     // Pre-compute AOI snapshots for bots with spectator followers
     // Bots use default zoom=1.0 (they don't have viewport settings)
-    let arena_scale = session.game_loop.state().arena.scale;
-    let mut bot_snapshot_cache: HashMap<PlayerId, Arc<Vec<u8>>> = HashMap::with_capacity(bot_targets.len());
-    for &bot_id in &bot_targets {
-        if let Some(bot) = session.game_loop.state().get_player(bot_id) {
-            let filtered = session.aoi_manager.filter_for_player(
-                bot_id,
-                bot.position,
-                bot.velocity,
-                1.0, // Bots use default zoom
-                arena_scale,
-                &full_snapshot,
-            );
-            let message = ServerMessage::Snapshot(filtered);
-            match encode_pooled(&message) {
-                Ok(encoded) => {
-                    bot_snapshot_cache.insert(bot_id, Arc::new(encoded));
-                }
-                Err(e) => {
-                    warn!("Failed to encode bot snapshot for {}: {}", bot_id, e);
-                }
+    let mut bot_snapshot_cache: HashMap<PlayerId, Arc<Vec<u8>>> = HashMap::with_capacity(frame.bot_targets.len());
+    for &(bot_id, position, velocity) in &frame.bot_targets {
+        let filtered = frame.aoi.filter_indexed(
+            &aoi_index,
+            bot_id,
+            position,
+            velocity,
+            1.0, // Bots use default zoom
+            frame.arena_scale,
+        );
+        let message = ServerMessage::Snapshot(filtered);
+        match encode_pooled(&message) {
+            Ok(encoded) => {
+                bot_snapshot_cache.insert(bot_id, Arc::new(encoded));
+            }
+            Err(e) => {
+                warn!("Failed to encode bot snapshot for {}: {}", bot_id, e);
             }
         }
     }
 
-    // Rate limit: spectators get updates at reduced rate (every Nth tick)
-    let spectator_tick = tick % SPECTATOR_TICK_DIVISOR == 0;
+    // Rate limit: full-view spectators get updates at a reduced rate (every Nth snapshot interval)
+    let spectator_interval = TICKS_PER_SNAPSHOT as f64 * SPECTATOR_TICK_DIVISOR as f64;
 
     // Pre-compute set of players with spectator followers (for Bug #5: avoid double encoding)
-    let followed_players: std::collections::HashSet<PlayerId> = session.players.values()
+    let followed_players: Arc<std::collections::HashSet<PlayerId>> = Arc::new(frame.clients.iter()
         .filter_map(|c| if c.is_spectator { c.spectate_target } else { None })
+        .collect());
+
+    // First pass: encode and send to players, split across workers, caching for potential followers
+    let players: Vec<usize> = (0..frame.clients.len()).filter(|&i| !frame.clients[i].is_spectator).collect();
+    let chunk_size = players.len().div_ceil(workers.max(1)).max(1);
+    let passes: Vec<_> = players
+        .chunks(chunk_size)
+        .map(|chunk| {
+            tokio::spawn(broadcast_to_players(
+                frame.clone(),
+                aoi_index.clone(),
+                followed_players.clone(),
+                chunk.to_vec(),
+            ))
+        })
         .collect();
+    let mut totals = PlayerPass::default();
+    for pass in passes {
+        match pass.await {
+            Ok(pass) => totals.merge(pass, &mut player_snapshot_cache),
+            Err(e) => warn!("Broadcast worker failed: {}", e),
+        }
+    }
 
-    // Metrics tracking for delta compression
-    #[cfg(feature = "metrics_extended")]
-    let mut delta_updates_sent = 0u64;
+    // Second pass: spectators
+    // - Follow-mode spectators get updates at the followed player's rate
+    // - Full-view spectators get updates at reduced rate (large snapshots, bandwidth savings)
+    for conn in frame.clients.iter() {
+        if !conn.is_spectator {
+            continue;
+        }
+        let player_id = conn.player_id;
+
+        // Delayed spectators get the full view from the delay buffer at the reduced
+        // rate (follow mode is applied client-side; a live AOI view would defeat the delay)
+        let delayed = conn.delayed;
+
+        // Bot targets are followed at the normal rate; full view (including the
+        // follow-mode fallback) at the reduced spectator rate
+        let interval = match conn.spectate_target {
+            Some(target_id) if !delayed && bot_snapshot_cache.contains_key(&target_id) => TICKS_PER_SNAPSHOT as f64,
+            _ => spectator_interval,
+        };
+        let spectator_due = conn.net_state.lock().await.schedule.take(tick, interval);
+
+        if delayed {
+            if !spectator_due {
+                continue;
+            }
+            let Some(delayed) = frame.spectator_delay.lock().delayed(tick) else {
+                continue;
+            };
+            if let Err(e) = conn.sender.send((MessageCategory::Snapshot, delayed)) {
+                debug!("Delayed spectator broadcast to {}: channel closed ({})", player_id, e);
+            }
+            continue;
+        }
+
+        let bytes: Arc<Vec<u8>> = match conn.spectate_target {
+            // FULL VIEW: Rate-limited (large snapshots)
+            None => {
+                // Only send when this spectator's reduced-rate slot is due
+                if !spectator_due {
+                    continue;
+                }
+                if let Some(ref full) = full_snapshot_bytes {
+                    full.clone() // Arc::clone - O(1)
+                } else {
+                    continue;
+                }
+            }
+            // FOLLOW MODE: Same rate and AOI-filtered data as the target player
+            // (the cache only holds players sent a snapshot this pass)
+            Some(target_id) => {
+                if let Some(cached) = player_snapshot_cache.get(&target_id) {
+                    // Human player - use their cached AOI-filtered snapshot (O(1))
+                    cached.clone() // Arc::clone - O(1)
+                } else if frame.clients.iter().any(|c| c.player_id == target_id && !c.is_spectator) {
+                    // Connected player not due this pass
+                    continue;
+                } else if let Some(cached) = bot_snapshot_cache.get(&target_id) {
+                    if !spectator_due {
+                        continue;
+                    }
+                    // Bot with cached snapshot - reuse pre-computed AOI snapshot (O(1))
+                    // This optimization ensures N spectators following same bot = O(1) not O(N)
+                    cached.clone() // Arc::clone - O(1)
+                } else if let Some(ref full) = full_snapshot_bytes {
+                    // Target doesn't exist (disconnected/dead) - fall back to full view (rate-limited)
+                    if !spectator_due {
+                        continue;
+                    }
+                    full.clone() // Arc::clone - O(1)
+                } else {
+                    continue;
+                }
+            }
+        };
+
+        if let Err(e) = conn.sender.send((MessageCategory::Snapshot, bytes)) {
+            debug!("Spectator broadcast to {}: channel closed ({})", player_id, e);
+        }
+    }
+
+    if let Some(metrics) = &frame.metrics {
+        metrics.aoi_enters_total.fetch_add(totals.aoi_changes.entered as u64, Ordering::Relaxed);
+        metrics.aoi_exits_total.fetch_add(totals.aoi_changes.exited as u64, Ordering::Relaxed);
+        metrics.aoi_shared_queries_total.fetch_add(aoi_index.shared_query_hits() as u64, Ordering::Relaxed);
+    }
+
+    // Update metrics with AOI stats (feature-gated)
     #[cfg(feature = "metrics_extended")]
-    let mut full_updates_sent = 0u64;
+    if let Some(metrics) = &frame.metrics {
+        let stats = &totals.stats;
+        metrics.aoi_original_players.store(stats.original_players as u64, Ordering::Relaxed);
+        metrics.aoi_filtered_players.store(stats.filtered_players as u64, Ordering::Relaxed);
+        metrics.aoi_original_projectiles.store(stats.original_projectiles as u64, Ordering::Relaxed);
+        metrics.aoi_filtered_projectiles.store(stats.filtered_projectiles as u64, Ordering::Relaxed);
+        if stats.original_players > 0 {
+            let reduction = (1.0 - (stats.filtered_players as f32 / stats.original_players as f32)) * 100.0;
+            metrics.aoi_reduction_percent.store(reduction as u64, Ordering::Relaxed);
+        }
+
+        // Delta compression metrics
+        metrics.delta_updates_sent.fetch_add(stats.delta_updates_sent, Ordering::Relaxed);
+        metrics.full_updates_sent.fetch_add(stats.full_updates_sent, Ordering::Relaxed);
+        metrics.updates_full_rate.fetch_add(stats.delta.full_rate_count as u64, Ordering::Relaxed);
+        metrics.updates_reduced_rate.fetch_add(stats.delta.reduced_rate_count as u64, Ordering::Relaxed);
+        metrics.updates_dormant_rate.fetch_add(stats.delta.dormant_rate_count as u64, Ordering::Relaxed);
+        metrics.updates_skipped_total.fetch_add(stats.delta.players_skipped as u64, Ordering::Relaxed);
+    }
+}
+
+/// One broadcast worker's share of the player pass
+#[derive(Default)]
+struct PlayerPass {
+    /// Full snapshots sent this pass, for follow-mode spectators
+    snapshot_cache: Vec<(PlayerId, Arc<Vec<u8>>)>,
+    aoi_changes: AoiChanges,
     #[cfg(feature = "metrics_extended")]
-    let mut total_delta_stats = DeltaStats::default();
+    stats: BroadcastStats,
+}
 
-    // First pass: encode and send to players, cache for potential followers
-    for (&player_id, conn) in session.players.iter() {
-        if conn.is_spectator {
-            continue; // Handle spectators in second pass
+/// AOI and delta compression totals (metrics_extended)
+#[cfg(feature = "metrics_extended")]
+#[derive(Default)]
+struct BroadcastStats {
+    original_players: usize,
+    filtered_players: usize,
+    original_projectiles: usize,
+    filtered_projectiles: usize,
+    delta_updates_sent: u64,
+    full_updates_sent: u64,
+    delta: DeltaStats,
+}
+
+impl PlayerPass {
+    /// Fold another worker's pass into this one, moving its cached snapshots to `cache`
+    fn merge(&mut self, other: PlayerPass, cache: &mut HashMap<PlayerId, Arc<Vec<u8>>>) {
+        cache.extend(other.snapshot_cache);
+        self.aoi_changes.entered += other.aoi_changes.entered;
+        self.aoi_changes.exited += other.aoi_changes.exited;
+        #[cfg(feature = "metrics_extended")]
+        {
+            let (stats, other) = (&mut self.stats, other.stats);
+            stats.original_players += other.original_players;
+            stats.filtered_players += other.filtered_players;
+            stats.original_projectiles += other.original_projectiles;
+            stats.filtered_projectiles += other.filtered_projectiles;
+            stats.delta_updates_sent += other.delta_updates_sent;
+            stats.full_updates_sent += other.full_updates_sent;
+            stats.delta.players_included += other.delta.players_included;
+            stats.delta.players_skipped += other.delta.players_skipped;
+            stats.delta.full_rate_count += other.delta.full_rate_count;
+            stats.delta.reduced_rate_count += other.delta.reduced_rate_count;
+            stats.delta.dormant_rate_count += other.delta.dormant_rate_count;
         }
+    }
+}
 
-        // Get player position and velocity for filtering
-        let (player_position, player_velocity) = session.game_loop.state()
-            .get_player(player_id)
-            .map(|p| (p.position, p.velocity))
-            .unwrap_or((crate::util::vec2::Vec2::ZERO, crate::util::vec2::Vec2::ZERO));
+/// Filter, encode and send snapshots to the players at `clients` (indices into the frame)
+/// Send a player its region hint, if new cells are coming into view
+fn send_region_hint(frame: &BroadcastFrame, conn: &ClientView, aoi_index: &AoiIndex, config: &RegionHintConfig) {
+    let radius = frame.aoi.effective_radius(conn.velocity, conn.viewport_zoom, frame.arena_scale);
+    let Some(hint) = region_hint(aoi_index, frame.tick, conn.position, conn.velocity, radius, config) else {
+        return;
+    };
+    match encode_pooled(&ServerMessage::RegionHint(hint)) {
+        Ok(encoded) => {
+            if conn.sender.send((MessageCategory::Snapshot, Arc::new(encoded))).is_ok() {
+                if let Some(metrics) = &frame.metrics {
+                    metrics.region_hints_total.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        Err(e) => warn!("Failed to encode region hint for {}: {}", conn.player_id, e),
+    }
+}
+
+async fn broadcast_to_players(
+    frame: Arc<BroadcastFrame>,
+    aoi_index: Arc<AoiIndex>,
+    followed_players: Arc<std::collections::HashSet<PlayerId>>,
+    clients: Vec<usize>,
+) -> PlayerPass {
+    let tick = frame.tick;
+    let mut pass = PlayerPass::default();
+
+    for conn in clients.into_iter().map(|i| &frame.clients[i]) {
+        let player_id = conn.player_id;
+
+        // Lock individual client net_state (interior mutability for lock-free broadcast)
+        let mut state = conn.net_state.lock().await;
+
+        // Now and then, the cells this player is about to see (independent of its snapshot rate)
+        if let Some(config) = frame.region_hint.as_ref().filter(|_| tick >= state.next_region_hint_tick) {
+            state.next_region_hint_tick = tick + config.interval_ticks;
+            send_region_hint(&frame, conn, &aoi_index, config);
+        }
+
+        // Skip clients whose snapshot rate isn't due this pass
+        let rate = conn.rate;
+        if !state.schedule.take(tick, rate.interval_ticks()) {
+            continue;
+        }
+
+        // Player position and velocity for filtering
+        let (player_position, player_velocity) = (conn.position, conn.velocity);
 
         // Filter snapshot for this player (AOI radius based on viewport zoom + velocity)
-        let mut filtered = session.aoi_manager.filter_for_player(
+        let mut filtered = frame.aoi.filter_indexed(
+            &aoi_index,
             player_id,
             player_position,
             player_velocity,
             conn.viewport_zoom,
-            arena_scale,
-            &full_snapshot,
+            frame.arena_scale,
         );
+        // Keep recently visible entities until they clear the exit radius
+        let changes = frame.aoi.apply_hysteresis(
+            &mut state.aoi,
+            player_position,
+            frame.aoi.effective_radius(player_velocity, conn.viewport_zoom, frame.arena_scale),
+            conn.viewport_zoom,
+            &aoi_index,
+            &mut filtered,
+        );
+        pass.aoi_changes.entered += changes.entered;
+        pass.aoi_changes.exited += changes.exited;
 
         // Update AOI stats (feature-gated)
         #[cfg(feature = "metrics_extended")]
         {
-            use crate::net::aoi::AOIManager;
-            let stats = AOIManager::snapshot_stats(&full_snapshot, &filtered);
-            total_original_players += stats.original_players;
-            total_filtered_players += stats.filtered_players;
-            total_original_projectiles += stats.original_projectiles;
-            total_filtered_projectiles += stats.filtered_projectiles;
+            let stats = AOIManager::snapshot_stats(&frame.snapshot, &filtered);
+            pass.stats.original_players += stats.original_players;
+            pass.stats.filtered_players += stats.filtered_players;
+            pass.stats.original_projectiles += stats.original_projectiles;
+            pass.stats.filtered_projectiles += stats.filtered_projectiles;
         }
 
         // Set echo_client_time for RTT measurement
-        filtered.echo_client_time = session.last_client_times
-            .get(&player_id)
-            .copied()
-            .unwrap_or(0);
-
-        // Lock individual client net_state (interior mutability for lock-free broadcast)
-        let mut state = conn.net_state.lock().await;
+        filtered.echo_client_time = conn.echo_client_time;
+        filtered.interp_delay_ms = conn.interp_delay_ms;
 
         // Determine if we need a full resync for this client
+        let acked_tick = conn.acked_tick;
         let needs_full = state.needs_full_resync
             || state.last_snapshot.is_none()
-            || tick - state.last_full_tick >= FULL_RESYNC_INTERVAL;
+            || tick - state.last_full_tick >= FULL_RESYNC_INTERVAL * rate.full_resync_multiplier();
 
         if needs_full {
             // === FULL SNAPSHOT PATH ===
-            // This becomes the "pinned base" for future deltas
+            // This becomes the newest baseline for future deltas. If the client has
+            // acknowledged a retained baseline, only what changed since it is sent
+            // (whenever that encodes smaller than the full snapshot).
+            let resync = state
+                .acked_baseline(acked_tick)
+                .map(|base| ServerMessage::Resync(generate_resync(base, &filtered)));
             let message = ServerMessage::Snapshot(filtered.clone());
             match encode_pooled(&message) {
                 Ok(encoded) => {
-                    let shared = Arc::new(encoded);
-                    player_snapshot_cache.insert(player_id, shared.clone());
+                    let full = Arc::new(encoded);
+                    pass.snapshot_cache.push((player_id, full.clone()));
+
+                    let (shared, kind) = match resync.as_ref().and_then(|m| encode_pooled(m).ok()) {
+                        Some(compact) if compact.len() < full.len() => {
+                            if let Some(metrics) = &frame.metrics {
+                                metrics.snapshot_resyncs_total.fetch_add(1, Ordering::Relaxed);
+                                metrics
+                                    .snapshot_resync_bytes_saved
+                                    .fetch_add((full.len() - compact.len()) as u64, Ordering::Relaxed);
+                            }
+                            (Arc::new(compact), SnapshotKind::Resync)
+                        }
+                        _ => (full, SnapshotKind::Full),
+                    };
 
-                    if let Err(e) = conn.sender.send(shared) {
-                        debug!("AOI broadcast to {}: channel closed ({})", player_id, e);
+                    if let Some(metrics) = &frame.metrics {
+                        if metrics.capture.is_enabled() {
+                            let digest = SnapshotDigest::new(player_id, &filtered, kind, shared.len());
+                            metrics.capture.record_outbound(player_id, digest);
+                        }
+                    }
+                    if let Some(shared) = seal_for(conn, shared) {
+                        if let Err(e) = conn.sender.send((MessageCategory::Snapshot, shared)) {
+                            debug!("AOI broadcast to {}: channel closed ({})", player_id, e);
+                        }
                     }
 
-                    // Store as pinned base for future deltas
-                    state.last_snapshot = Some(filtered);
+                    // Store as the newest baseline; deltas switch to it once acknowledged
+                    state.push_baseline(filtered, acked_tick);
                     state.last_full_tick = tick;
                     state.needs_full_resync = false;
 
                     #[cfg(feature = "metrics_extended")]
                     {
-                        full_updates_sent += 1;
+                        pass.stats.full_updates_sent += 1;
                     }
                 }
                 Err(e) => {
@@ -1686,156 +3500,96 @@ pub async fn broadcast_filtered_snapshots(session: &GameSession, tick: u64) {
             }
         } else {
             // === DELTA PATH ===
-            // Always use pinned base (last FULL snapshot), never update it here
-            let base_snapshot = state.last_snapshot.as_ref().unwrap();
+            // Base is the acknowledged baseline (a FULL snapshot), never updated here
+            let base_snapshot = state.delta_base(acked_tick).unwrap();
 
             match generate_delta(
                 base_snapshot,
                 &filtered,
                 player_position,
                 tick,
+                rate.delta_epsilon_scale(),
             ) {
                 Some((delta, stats)) => {
-                    let message = ServerMessage::Delta(delta);
-                    match encode_pooled(&message) {
-                        Ok(encoded) => {
-                            let shared = Arc::new(encoded);
-
-                            if let Err(e) = conn.sender.send(shared) {
-                                debug!("Delta broadcast to {}: channel closed ({})", player_id, e);
-                            }
-
-                            // Cache for spectators ONLY if this player has followers (Bug #5 fix)
-                            if followed_players.contains(&player_id) {
-                                if let Ok(full_encoded) = encode_pooled(&ServerMessage::Snapshot(filtered.clone())) {
-                                    player_snapshot_cache.insert(player_id, Arc::new(full_encoded));
-                                }
-                            }
-
-                            #[cfg(feature = "metrics_extended")]
-                            {
-                                delta_updates_sent += 1;
-                                total_delta_stats.players_included += stats.players_included;
-                                total_delta_stats.players_skipped += stats.players_skipped;
-                                total_delta_stats.full_rate_count += stats.full_rate_count;
-                                total_delta_stats.reduced_rate_count += stats.reduced_rate_count;
-                                total_delta_stats.dormant_rate_count += stats.dormant_rate_count;
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to encode delta for {}: {}", player_id, e);
-                            state.needs_full_resync = true; // Fallback to full
-                        }
-                    }
-                }
-                None => {
-                    // No changes - DO NOTHING (Bug #1 fix)
-                    // Don't update last_snapshot, don't send anything
-                    // Client still has valid base from last full snapshot
-                }
-            }
-        }
-
-        // Release lock before next iteration (explicit for clarity)
-        drop(state);
-    }
-
-    // Second pass: spectators
-    // - Follow-mode spectators get updates at FULL rate (same as the player they follow)
-    // - Full-view spectators get updates at reduced rate (large snapshots, bandwidth savings)
-    for (&player_id, conn) in session.players.iter() {
-        if !conn.is_spectator {
-            continue;
-        }
-
-        let bytes: Arc<Vec<u8>> = match conn.spectate_target {
-            // FULL VIEW: Rate-limited (large snapshots)
-            None => {
-                // Only send on spectator ticks to save bandwidth
-                if !spectator_tick {
-                    continue;
-                }
-                if let Some(ref full) = full_snapshot_bytes {
-                    full.clone() // Arc::clone - O(1)
-                } else {
-                    continue;
-                }
-            }
-            // FOLLOW MODE: Full rate (same AOI-filtered data as the target player)
-            // No rate limiting - spectators following a player should see smooth movement
-            Some(target_id) => {
-                if let Some(cached) = player_snapshot_cache.get(&target_id) {
-                    // Human player - use their cached AOI-filtered snapshot (O(1))
-                    cached.clone() // Arc::clone - O(1)
-                } else if let Some(cached) = bot_snapshot_cache.get(&target_id) {
-                    // Bot with cached snapshot - reuse pre-computed AOI snapshot (O(1))
-                    // This optimization ensures N spectators following same bot = O(1) not O(N)
-                    cached.clone() // Arc::clone - O(1)
-                } else if let Some(ref full) = full_snapshot_bytes {
-                    // Target doesn't exist (disconnected/dead) - fall back to full view (rate-limited)
-                    if !spectator_tick {
-                        continue;
+                    let message = ServerMessage::Delta(delta);
+                    match encode_pooled(&message) {
+                        Ok(encoded) => {
+                            let shared = Arc::new(encoded);
+
+                            if let Some(metrics) = &frame.metrics {
+                                if metrics.capture.is_enabled() {
+                                    let digest =
+                                        SnapshotDigest::new(player_id, &filtered, SnapshotKind::Delta, shared.len());
+                                    metrics.capture.record_outbound(player_id, digest);
+                                }
+                            }
+                            if let Some(shared) = seal_for(conn, shared) {
+                                if let Err(e) = conn.sender.send((MessageCategory::Delta, shared)) {
+                                    debug!("Delta broadcast to {}: channel closed ({})", player_id, e);
+                                }
+                            }
+
+                            // Cache for spectators ONLY if this player has followers (Bug #5 fix)
+                            if followed_players.contains(&player_id) {
+                                if let Ok(full_encoded) = encode_pooled(&ServerMessage::Snapshot(filtered.clone())) {
+                                    pass.snapshot_cache.push((player_id, Arc::new(full_encoded)));
+                                }
+                            }
+
+                            #[cfg(feature = "metrics_extended")]
+                            {
+                                pass.stats.delta_updates_sent += 1;
+                                pass.stats.delta.players_included += stats.players_included;
+                                pass.stats.delta.players_skipped += stats.players_skipped;
+                                pass.stats.delta.full_rate_count += stats.full_rate_count;
+                                pass.stats.delta.reduced_rate_count += stats.reduced_rate_count;
+                                pass.stats.delta.dormant_rate_count += stats.dormant_rate_count;
+                            }
+                        }
+                        Err(e) => {
+                            warn!("Failed to encode delta for {}: {}", player_id, e);
+                            state.needs_full_resync = true; // Fallback to full
+                        }
                     }
-                    full.clone() // Arc::clone - O(1)
-                } else {
-                    continue;
+                }
+                None => {
+                    // No changes - DO NOTHING (Bug #1 fix)
+                    // Don't update last_snapshot, don't send anything
+                    // Client still has valid base from last full snapshot
                 }
             }
-        };
-
-        if let Err(e) = conn.sender.send(bytes) {
-            debug!("Spectator broadcast to {}: channel closed ({})", player_id, e);
-        }
-    }
-
-    // Update metrics with AOI stats (feature-gated)
-    #[cfg(feature = "metrics_extended")]
-    if let Some(metrics) = &session.metrics {
-        use std::sync::atomic::Ordering;
-        metrics.aoi_original_players.store(total_original_players as u64, Ordering::Relaxed);
-        metrics.aoi_filtered_players.store(total_filtered_players as u64, Ordering::Relaxed);
-        metrics.aoi_original_projectiles.store(total_original_projectiles as u64, Ordering::Relaxed);
-        metrics.aoi_filtered_projectiles.store(total_filtered_projectiles as u64, Ordering::Relaxed);
-        if total_original_players > 0 {
-            let reduction = (1.0 - (total_filtered_players as f32 / total_original_players as f32)) * 100.0;
-            metrics.aoi_reduction_percent.store(reduction as u64, Ordering::Relaxed);
         }
 
-        // Delta compression metrics
-        metrics.delta_updates_sent.fetch_add(delta_updates_sent, Ordering::Relaxed);
-        metrics.full_updates_sent.fetch_add(full_updates_sent, Ordering::Relaxed);
-        metrics.updates_full_rate.fetch_add(total_delta_stats.full_rate_count as u64, Ordering::Relaxed);
-        metrics.updates_reduced_rate.fetch_add(total_delta_stats.reduced_rate_count as u64, Ordering::Relaxed);
-        metrics.updates_dormant_rate.fetch_add(total_delta_stats.dormant_rate_count as u64, Ordering::Relaxed);
-        metrics.updates_skipped_total.fetch_add(total_delta_stats.players_skipped as u64, Ordering::Relaxed);
+        // Release lock before next iteration (explicit for clarity)
+        drop(state);
     }
-}
 
-/// Minimum screen pixels for entity to be visible (below this, skip sending)
-const MIN_VISIBLE_SCREEN_PIXELS: f32 = 2.0;
+    pass
+}
 
 /// Radius scale factor (must match client MASS.RADIUS_SCALE)
 const RADIUS_SCALE: f32 = 2.0;
 
 /// Calculate minimum mass visible at a given zoom level
 /// Formula: screen_radius = sqrt(mass) * RADIUS_SCALE * zoom
-/// We want screen_radius >= MIN_VISIBLE_SCREEN_PIXELS
-/// So: sqrt(mass) >= MIN_VISIBLE_SCREEN_PIXELS / (RADIUS_SCALE * zoom)
-/// mass >= (MIN_VISIBLE_SCREEN_PIXELS / (RADIUS_SCALE * zoom))^2
-fn min_visible_mass(zoom: f32) -> f32 {
-    let min_world_radius = MIN_VISIBLE_SCREEN_PIXELS / (RADIUS_SCALE * zoom.max(0.05));
+/// We want screen_radius >= min_screen_pixels
+/// So: sqrt(mass) >= min_screen_pixels / (RADIUS_SCALE * zoom)
+/// mass >= (min_screen_pixels / (RADIUS_SCALE * zoom))^2
+fn min_visible_mass(zoom: f32, min_screen_pixels: f32) -> f32 {
+    let min_world_radius = min_screen_pixels / (RADIUS_SCALE * zoom.max(0.05));
     min_world_radius * min_world_radius
 }
 
 /// Create a filtered snapshot for spectators (reduce bandwidth)
-/// Filters out entities that would be too small to see at the given zoom level
+/// Filters out projectiles and debris that would be too small to see at the given
+/// zoom level (players are culled separately by the AOI manager)
 /// Uses viewport_zoom to dynamically determine what's visible
-fn create_spectator_snapshot(full: &GameSnapshot, zoom: f32) -> GameSnapshot {
-    // Calculate minimum visible mass based on zoom
+fn create_spectator_snapshot(full: &GameSnapshot, zoom: f32, min_screen_pixels: f32) -> GameSnapshot {
+    // Calculate minimum visible mass based on zoom (with the default 2px threshold)
     // At zoom 0.1: min_mass = 100 (only large entities visible)
     // At zoom 0.5: min_mass = 4 (most entities visible)
     // At zoom 1.0: min_mass = 1 (everything visible)
-    let min_mass = min_visible_mass(zoom);
+    let min_mass = min_visible_mass(zoom, min_screen_pixels);
 
     // Use whichever is more restrictive: zoom-based or fixed constant
     let effective_projectile_min = min_mass.max(SPECTATOR_MIN_PROJECTILE_MASS);
@@ -1855,7 +3609,7 @@ fn create_spectator_snapshot(full: &GameSnapshot, zoom: f32) -> GameSnapshot {
         match_phase: full.match_phase.clone(),
         match_time: full.match_time,
         countdown: full.countdown,
-        players: full.players.clone(), // ALL players always visible
+        players: full.players.clone(), // Culled by apparent size in the broadcast
         projectiles: full.projectiles.iter()
             .filter(|p| p.mass > effective_projectile_min)
             .take(SPECTATOR_MAX_PROJECTILES)
@@ -1870,18 +3624,22 @@ fn create_spectator_snapshot(full: &GameSnapshot, zoom: f32) -> GameSnapshot {
         arena_safe_radius: full.arena_safe_radius,
         arena_scale: full.arena_scale,
         gravity_wells: full.gravity_wells.clone(),
+        asteroids: full.asteroids.clone(),
         total_players: full.total_players,
         total_alive: full.total_alive,
         density_grid: full.density_grid.clone(),
         echo_client_time: 0, // Spectators don't need RTT measurement
+        interp_delay_ms: 0,
         ai_status: full.ai_status.clone(),
     }
 }
 
-/// Send a message to a specific player using pooled buffers
+/// Send a message to a specific player using pooled buffers, bypassing the
+/// connection's writer task (counted toward the egress totals only)
 pub async fn send_to_player(
     writer: &Arc<RwLock<Option<wtransport::SendStream>>>,
     message: &ServerMessage,
+    metrics: &Metrics,
 ) -> Result<(), String> {
     let encoded = encode_pooled(message)?;
     let len_bytes = (encoded.len() as u32).to_le_bytes();
@@ -1895,6 +3653,7 @@ pub async fn send_to_player(
             .write_all(&encoded)
             .await
             .map_err(|e| e.to_string())?;
+        metrics.egress.record(None, MessageCategory::of(message), encoded.len());
         Ok(())
     } else {
         Err("Writer not available".to_string())
@@ -2001,11 +3760,49 @@ mod simulation_tests {
     }
 }
 
+#[cfg(test)]
+mod hibernation_tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_pool_shrink() {
+        let pool = BufferPool::new(8, 64);
+        pool.shrink_to(2);
+        assert_eq!(pool.receiver.len(), 2);
+        pool.shrink_to(0);
+        assert_eq!(pool.receiver.len(), 0);
+        assert!(pool.get().capacity() >= BUFFER_POOL_CAPACITY);
+    }
+
+    #[test]
+    fn test_idle_session_hibernates_and_wakes() {
+        let mut session = GameSession::new();
+        let config = HibernationConfig { enabled: true, idle_minutes: 0, tick_hz: 2, bots: 2 };
+        session.hibernation = Hibernation::new(config);
+        let full_target = session.bot_count;
+        session.game_loop.fill_with_bots(10);
+
+        session.tick();
+        assert!(session.is_hibernating());
+        assert_eq!(session.hibernation_tick_interval(), Some(Duration::from_millis(500)));
+        let bots = session.game_loop.state().players.values().filter(|p| p.is_bot).count();
+        assert!(bots <= 2, "expected at most 2 bots while hibernating, got {}", bots);
+
+        session.wake_from_hibernation();
+        assert!(!session.is_hibernating());
+        assert_eq!(session.bot_count, full_target);
+        assert_eq!(session.hibernation_tick_interval(), None);
+    }
+}
+
 #[cfg(test)]
 mod spectator_tests {
     use super::*;
     use crate::net::protocol::GameSnapshot;
 
+    /// Default apparent-size threshold (AoiCullingConfig)
+    const MIN_PX: f32 = 2.0;
+
     #[test]
     fn test_create_spectator_snapshot_filters_small_entities() {
         // Create a full snapshot with various entity sizes
@@ -2040,15 +3837,17 @@ mod spectator_tests {
             arena_safe_radius: 1000.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 0,
             total_alive: 0,
             density_grid: vec![],
             echo_client_time: 12345,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
         // Use moderate zoom (0.5) for basic filtering behavior
-        let spectator_snap = create_spectator_snapshot(&full, 0.5);
+        let spectator_snap = create_spectator_snapshot(&full, 0.5, MIN_PX);
 
         // Projectiles: only mass > 10 kept
         assert_eq!(spectator_snap.projectiles.len(), 1);
@@ -2100,15 +3899,17 @@ mod spectator_tests {
             arena_safe_radius: 1000.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 0,
             total_alive: 0,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
         // Use high zoom (1.0) so all entities pass mass filter
-        let spectator_snap = create_spectator_snapshot(&full, 1.0);
+        let spectator_snap = create_spectator_snapshot(&full, 1.0, MIN_PX);
 
         // Should respect limits (100 projectiles, 50 debris)
         assert_eq!(spectator_snap.projectiles.len(), 100);
@@ -2149,29 +3950,31 @@ mod spectator_tests {
             arena_safe_radius: 1000.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 0,
             total_alive: 0,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
         // At zoom 0.1 (very zoomed out), min_visible_mass = 100
         // Only projectile with mass 150 should pass
-        let snap_zoomed_out = create_spectator_snapshot(&full, 0.1);
+        let snap_zoomed_out = create_spectator_snapshot(&full, 0.1, MIN_PX);
         assert_eq!(snap_zoomed_out.projectiles.len(), 1, "At zoom 0.1, only mass > 100 should pass");
         assert_eq!(snap_zoomed_out.projectiles[0].id, 2);
         assert_eq!(snap_zoomed_out.debris.len(), 1, "At zoom 0.1, only large debris should pass");
 
         // At zoom 0.5 (moderate), min_visible_mass = 4, but fixed threshold is 10
         // Both projectiles should pass (50 and 150 > 10)
-        let snap_moderate = create_spectator_snapshot(&full, 0.5);
+        let snap_moderate = create_spectator_snapshot(&full, 0.5, MIN_PX);
         assert_eq!(snap_moderate.projectiles.len(), 2, "At zoom 0.5, both projectiles should pass");
         assert_eq!(snap_moderate.debris.len(), 2, "At zoom 0.5, medium and large debris should pass");
 
         // At zoom 1.0 (normal), min_visible_mass = 1
         // All entities should pass their respective thresholds
-        let snap_normal = create_spectator_snapshot(&full, 1.0);
+        let snap_normal = create_spectator_snapshot(&full, 1.0, MIN_PX);
         assert_eq!(snap_normal.projectiles.len(), 2, "At zoom 1.0, both projectiles should pass");
         assert_eq!(snap_normal.debris.len(), 2, "At zoom 1.0, medium and large debris should pass");
     }
@@ -2180,15 +3983,15 @@ mod spectator_tests {
     fn test_min_visible_mass_calculation() {
         // Verify the min_visible_mass formula
         // At zoom 0.1: min_mass = (2 / (2 * 0.1))^2 = 100
-        let mass_01 = min_visible_mass(0.1);
+        let mass_01 = min_visible_mass(0.1, MIN_PX);
         assert!((mass_01 - 100.0).abs() < 0.1, "At zoom 0.1, min_mass should be ~100");
 
         // At zoom 0.5: min_mass = (2 / (2 * 0.5))^2 = 4
-        let mass_05 = min_visible_mass(0.5);
+        let mass_05 = min_visible_mass(0.5, MIN_PX);
         assert!((mass_05 - 4.0).abs() < 0.1, "At zoom 0.5, min_mass should be ~4");
 
         // At zoom 1.0: min_mass = (2 / (2 * 1.0))^2 = 1
-        let mass_10 = min_visible_mass(1.0);
+        let mass_10 = min_visible_mass(1.0, MIN_PX);
         assert!((mass_10 - 1.0).abs() < 0.1, "At zoom 1.0, min_mass should be ~1");
     }
 
@@ -2519,163 +4322,377 @@ fn sanitize_game_state(session: &mut GameSession) {
     }
 }
 
-/// Start the game loop background task
-pub fn start_game_loop(session: Arc<RwLock<GameSession>>) {
-    tokio::spawn(async move {
-        let tick_duration = Duration::from_millis(physics::TICK_DURATION_MS);
-        let mut ticker = interval(tick_duration);
-        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+/// Group a tick's projectile hits by the players who should hear about them:
+/// the shooter and the victim each get every hit they took part in
+fn hit_batches(events: &[GameLoopEvent]) -> HashMap<PlayerId, Vec<GameEvent>> {
+    let mut batches: HashMap<PlayerId, Vec<GameEvent>> = HashMap::new();
+    for event in events {
+        if let GameLoopEvent::PlayerHit { shooter_id, victim_id, projectile_id, amount, position } = *event {
+            let hit = GameEvent::Hit { shooter_id, victim_id, projectile_id, amount, position };
+            batches.entry(shooter_id).or_default().push(hit.clone());
+            batches.entry(victim_id).or_default().push(hit);
+        }
+    }
+    batches
+}
 
-        info!("Game loop started at {} Hz", physics::TICK_RATE);
-        let start = Instant::now();
-        let mut tick_count: u64 = 0;
+/// Client event announcing a global modifier with `remaining` seconds left
+fn modifier_started_event(modifier: GlobalModifier, remaining: f32) -> GameEvent {
+    GameEvent::ModifierStarted {
+        name: modifier.name().to_string(),
+        description: modifier.description(),
+        duration: remaining,
+    }
+}
 
-        loop {
-            ticker.tick().await;
-            tick_count += 1;
+/// Announcement text for a scheduled event coming up or carried out
+fn schedule_announcement(event: &GameLoopEvent, view: &GameStateView) -> Option<LocalizedText> {
+    match event {
+        GameLoopEvent::ScheduledEventUpcoming { action, starts_in } => {
+            let when = match *starts_in {
+                secs if secs >= 120 => {
+                    LocalizedText::new(keys::ANNOUNCE_SCHEDULED_MINUTES).with("minutes", (secs + 30) / 60)
+                }
+                secs if secs >= 60 => LocalizedText::new(keys::ANNOUNCE_SCHEDULED_MINUTE),
+                secs => LocalizedText::new(keys::ANNOUNCE_SCHEDULED_SECONDS).with("seconds", secs),
+            };
+            Some(when.with("event_key", action.description().key))
+        }
+        GameLoopEvent::BossSpawned { player_id } => view
+            .player(*player_id)
+            .map(|boss| LocalizedText::new(keys::ANNOUNCE_BOSS_SPAWN).with("name", &boss.name)),
+        GameLoopEvent::ArenaReset => Some(LocalizedText::new(keys::ANNOUNCE_ARENA_RESET)),
+        GameLoopEvent::ArenaTransition { name, .. } => {
+            Some(LocalizedText::new(keys::ANNOUNCE_ARENA_CHANGING).with("name", name))
+        }
+        _ => None,
+    }
+}
+
+/// Start the game loop, as a tokio task or on a dedicated thread pinned to
+/// `core`. It stops ticking once `shutdown` is triggered.
+pub fn start_game_loop(
+    session: Arc<RwLock<GameSession>>,
+    core: Option<usize>,
+    shutdown: ShutdownToken,
+) -> std::io::Result<()> {
+    match core {
+        Some(core) => crate::runtime::spawn_pinned("game-loop", core, run_game_loop(session, shutdown)),
+        None => {
+            tokio::spawn(run_game_loop(session, shutdown));
+            Ok(())
+        }
+    }
+}
+
+/// A tick's events, its published view, the broadcast frame (on snapshot ticks)
+/// and the players to leave unnamed in replays and highlights (when recording them)
+type TickOutput = (Vec<GameLoopEvent>, Arc<GameStateView>, Option<BroadcastFrame>, bool, Vec<PlayerId>);
+
+/// The game loop: ticks, broadcasts and periodic status logging
+async fn run_game_loop(session: Arc<RwLock<GameSession>>, shutdown: ShutdownToken) {
+    let tick_duration = Duration::from_millis(physics::TICK_DURATION_MS);
+    let mut ticker = interval(tick_duration);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
-            // Run game tick with error recovery
-            let tick_result: Result<(Vec<GameLoopEvent>, Option<GameSnapshot>), String> = {
-                let mut session_guard = session.write().await;
+    info!("Game loop started at {} Hz", physics::TICK_RATE);
+    let start = Instant::now();
+    let mut tick_count: u64 = 0;
+    let (wake, metrics, simulation_config) = {
+        let session_guard = session.read().await;
+        (session_guard.hibernation.wake_signal(), session_guard.metrics.clone(), session_guard.simulation_config.clone())
+    };
+    let broadcast_pool = BroadcastPool::start(&BroadcastConfig::from_env(), metrics.clone());
+    let mut kill_feed = KillFeed::default();
+    let mut replay = ReplayRecorder::new(ReplayConfig::from_env());
+    let mut highlights = HighlightRecorder::new(HighlightConfig::from_env());
+    let mut hibernation_interval: Option<Duration> = None;
+    let stopped = shutdown.triggered();
+    tokio::pin!(stopped);
+
+    loop {
+        match hibernation_interval {
+            // Hibernating: tick slowly until the interval passes or a connection attempt wakes the session
+            Some(period) => {
+                tokio::select! {
+                    _ = tokio::time::sleep(period) => {}
+                    _ = wake.notified() => {}
+                    _ = &mut stopped => break,
+                }
+                ticker.reset();
+            }
+            None => {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = &mut stopped => break,
+                }
+            }
+        }
+        tick_count += 1;
 
-                // Sanitize state before tick to prevent NaN propagation
-                sanitize_game_state(&mut session_guard);
+        // Run game tick with error recovery
+        let tick_result: Result<TickOutput, String> = {
+            let mut session_guard = session.write().await;
 
-                let events = session_guard.tick();
+            // Sanitize state before tick to prevent NaN propagation
+            sanitize_game_state(&mut session_guard);
 
-                // Sanitize again after tick
-                sanitize_game_state(&mut session_guard);
+            let events = session_guard.tick();
 
-                let snapshot = if session_guard.should_send_snapshot() {
-                    session_guard.mark_snapshot_sent();
-                    Some(session_guard.get_snapshot())
-                } else {
-                    None
-                };
-                Ok((events, snapshot))
-            };
+            // Sanitize again after tick
+            sanitize_game_state(&mut session_guard);
+            hibernation_interval = session_guard.hibernation_tick_interval();
 
-            let (events, snapshot) = match tick_result {
-                Ok(result) => result,
-                Err(e) => {
-                    warn!("Game tick error: {}", e);
-                    continue;
+            // Refresh published input stats once per second
+            if tick_count % physics::TICK_RATE as u64 == 0 {
+                session_guard.publish_input_stats();
+                session_guard.update_snapshot_rates();
+                session_guard.update_safe_mode();
+                if let Some(metrics) = &session_guard.metrics {
+                    metrics.roll_send_burst_window();
+                    metrics.roll_rate_windows();
                 }
+            }
+
+            // Publish the tick's view and capture the broadcast frame while the session is locked anyway
+            let view = session_guard.publish_state_view();
+            let mut record = false;
+            let frame = if session_guard.should_send_snapshot() {
+                record = session_guard.mark_snapshot_sent();
+                Some(session_guard.broadcast_frame(tick_count, view.snapshot.clone()))
+            } else {
+                None
+            };
+            let replay_opt_outs = if (record && replay.is_enabled()) || highlights.is_enabled() {
+                session_guard.replay_opt_outs()
+            } else {
+                Vec::new()
             };
+            Ok((events, view, frame, record, replay_opt_outs))
+        };
 
-            // Log kill events only
-            for event in &events {
-                if let GameLoopEvent::PlayerKilled { killer_id, victim_id } = event {
-                    debug!("Player {:?} killed {:?}", killer_id, victim_id);
-                }
+        let (events, view, frame, record, replay_opt_outs) = match tick_result {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Game tick error: {}", e);
+                continue;
             }
+        };
+        replay.observe(&events, &view.snapshot);
+        highlights.observe(&events, &view.snapshot, &replay_opt_outs);
+        if record {
+            if let Some(metrics) = &metrics {
+                metrics.snapshot_history.record(&view.snapshot);
+            }
+            replay.record(&view.snapshot, &replay_opt_outs);
+            highlights.record(&view.snapshot);
+        }
 
-            // Broadcast game events to all players
-            for event in &events {
-                let game_event = match event {
-                    GameLoopEvent::PlayerDeflection { player_a, player_b, position, intensity } => {
-                        Some(GameEvent::PlayerDeflection {
-                            player_a: *player_a,
-                            player_b: *player_b,
-                            position: *position,
-                            intensity: *intensity,
-                        })
-                    }
-                    GameLoopEvent::GravityWellCharging { well_id, position } => {
-                        Some(GameEvent::GravityWellCharging {
-                            well_id: *well_id,
-                            position: *position,
-                        })
-                    }
-                    GameLoopEvent::GravityWaveExplosion { well_id, position, strength } => {
-                        Some(GameEvent::GravityWaveExplosion {
-                            well_id: *well_id,
-                            position: *position,
-                            strength: *strength,
-                        })
-                    }
-                    GameLoopEvent::GravityWellDestroyed { well_id, position } => {
-                        Some(GameEvent::GravityWellDestroyed {
+        // Log kill events only
+        for event in &events {
+            if let GameLoopEvent::PlayerKilled { killer_id, victim_id } = event {
+                debug!("Player {:?} killed {:?}", killer_id, victim_id);
+            }
+        }
+
+        // Broadcast game events to all players
+        for event in &events {
+            let game_event = match event {
+                GameLoopEvent::PlayerDeflection { player_a, player_b, position, intensity } => {
+                    Some(GameEvent::PlayerDeflection {
+                        player_a: *player_a,
+                        player_b: *player_b,
+                        position: *position,
+                        intensity: *intensity,
+                    })
+                }
+                GameLoopEvent::GravityWellCharging { well_id, position } => {
+                    Some(GameEvent::GravityWellCharging {
+                        well_id: *well_id,
+                        position: *position,
+                    })
+                }
+                GameLoopEvent::GravityWaveExplosion { well_id, position, strength } => {
+                    Some(GameEvent::GravityWaveExplosion {
+                        well_id: *well_id,
+                        position: *position,
+                        strength: *strength,
+                    })
+                }
+                GameLoopEvent::GravityWellDestroyed { well_id, position } => {
+                    Some(GameEvent::GravityWellDestroyed {
+                        well_id: *well_id,
+                        position: *position,
+                    })
+                }
+                GameLoopEvent::BotChatter { bot_id, kind, text, position } => {
+                    // AOI-scoped: only clients that can see the bot
+                    let message = ServerMessage::Event(GameEvent::BotChatter {
+                        player_id: *bot_id,
+                        kind: *kind,
+                        text: text.clone(),
+                        position: *position,
+                    });
+                    let position = *position;
+                    let session_clone = session.clone();
+                    tokio::spawn(async move {
+                        let session_guard = session_clone.read().await;
+                        broadcast_message_near(&session_guard, &message, position).await;
+                    });
+                    None
+                }
+                GameLoopEvent::AsteroidDestroyed { asteroid_id, position, fragments } => {
+                    // AOI-scoped like chatter: only clients that can see the asteroid
+                    let message = ServerMessage::Event(GameEvent::AsteroidDestroyed {
+                        asteroid_id: *asteroid_id,
+                        position: *position,
+                        fragments: (*fragments).min(u16::MAX as usize) as u16,
+                    });
+                    let position = *position;
+                    let session_clone = session.clone();
+                    tokio::spawn(async move {
+                        let session_guard = session_clone.read().await;
+                        broadcast_message_near(&session_guard, &message, position).await;
+                    });
+                    None
+                }
+                GameLoopEvent::WellCaptured { well_id, owner_id, .. } => {
+                    view.player(*owner_id).map(|owner| {
+                        GameEvent::WellCaptured {
                             well_id: *well_id,
-                            position: *position,
-                        })
+                            owner_id: *owner_id,
+                            owner_name: owner.name.clone(),
+                            color_index: owner.color_index,
+                        }
+                    })
+                }
+                GameLoopEvent::WellContested { well_id } => Some(GameEvent::WellContested { well_id: *well_id }),
+                GameLoopEvent::ModifierStarted { modifier, duration } => {
+                    Some(modifier_started_event(*modifier, *duration))
+                }
+                GameLoopEvent::ModifierEnded { modifier } => {
+                    Some(GameEvent::ModifierEnded { name: modifier.name().to_string() })
+                }
+                GameLoopEvent::PlayerDied { victim_id, killer_id, cause } => {
+                    // AOI-scoped, but the players involved and spectators always hear about it
+                    if let Some((entry, position)) =
+                        kill_feed.record(view.tick(), *victim_id, *killer_id, *cause, &view.snapshot)
+                    {
+                        let involved: Vec<PlayerId> = entry.killer_id.into_iter().chain([entry.victim_id]).collect();
+                        let message = ServerMessage::Event(GameEvent::KillFeed(entry));
+                        let session_clone = session.clone();
+                        tokio::spawn(async move {
+                            let session_guard = session_clone.read().await;
+                            broadcast_message_witnessed(&session_guard, &message, position, &involved, true).await;
+                        });
                     }
-                    // Other events are already reflected in state snapshots
-                    _ => None,
-                };
-
-                if let Some(game_event) = game_event {
+                    None
+                }
+                GameLoopEvent::PhaseChange { phase: MatchPhase::Playing, .. } => {
+                    kill_feed.reset();
+                    None
+                }
+                GameLoopEvent::MatchEnded { result } => Some(GameEvent::MatchEnded {
+                    winner_id: result.winner_id,
+                    winner_name: result.winner_name.clone(),
+                    awards: result.awards.clone(),
+                }),
+                GameLoopEvent::MatchResumed => {
                     let session_clone = session.clone();
                     tokio::spawn(async move {
                         let session_guard = session_clone.read().await;
-                        broadcast_message(&session_guard, &ServerMessage::Event(game_event)).await;
+                        broadcast_message(&session_guard, &ServerMessage::MatchResumed).await;
                     });
+                    None
                 }
-            }
+                GameLoopEvent::ScheduledEventUpcoming { .. }
+                | GameLoopEvent::BossSpawned { .. }
+                | GameLoopEvent::ArenaReset
+                | GameLoopEvent::ArenaTransition { .. } => {
+                    if let Some(text) = schedule_announcement(event, &view) {
+                        let session_clone = session.clone();
+                        tokio::spawn(async move {
+                            let session_guard = session_clone.read().await;
+                            broadcast_message(&session_guard, &ServerMessage::Announcement { text }).await;
+                        });
+                    }
+                    None
+                }
+                // Other events are already reflected in state snapshots
+                _ => None,
+            };
 
-            // Broadcast AOI-filtered snapshots if needed (each player gets their own filtered view)
-            // Uses read lock - delta compression state is per-client with interior mutability
-            if snapshot.is_some() {
+            if let Some(game_event) = game_event {
                 let session_clone = session.clone();
-                let current_tick = tick_count;
                 tokio::spawn(async move {
                     let session_guard = session_clone.read().await;
-                    // Use AOI filtering + delta compression for per-client snapshots
-                    broadcast_filtered_snapshots(&session_guard, current_tick).await;
+                    broadcast_message(&session_guard, &ServerMessage::Event(game_event)).await;
                 });
             }
+        }
 
-            // Log stats periodically (every 60 seconds by default, configurable via LOG_STATUS_INTERVAL_SECS)
-            let log_interval = std::env::var("LOG_STATUS_INTERVAL_SECS")
-                .ok()
-                .and_then(|s| s.parse::<u64>().ok())
-                .unwrap_or(60);
-            if log_interval > 0 && tick_count % (physics::TICK_RATE as u64 * log_interval) == 0 {
-                let session_guard = session.read().await;
-                let elapsed = start.elapsed().as_secs();
-                let human_count = session_guard.players.len();
-                let bot_count = session_guard.game_loop.state().players.values().filter(|p| p.is_bot).count();
-                let well_count = session_guard.game_loop.state().arena.gravity_wells.len();
-                let perf_status = session_guard.performance.status();
-                let perf_budget = session_guard.performance.budget_usage_percent();
-
-                if session_guard.simulation_config.enabled {
-                    let target = session_guard.bot_count;
-                    let cycle_progress = (elapsed as f32 % session_guard.simulation_config.cycle_duration_secs)
-                        / session_guard.simulation_config.cycle_duration_secs * 100.0;
-                    info!(
-                        "Game: {}s, tick {}, {} humans + {}/{} bots, {} wells | Perf: {:?} ({:.1}%) | Sim: {:.1}% cycle",
-                        elapsed,
-                        session_guard.game_loop.state().tick,
-                        human_count,
-                        bot_count,
-                        target,
-                        well_count,
-                        perf_status,
-                        perf_budget,
-                        cycle_progress
-                    );
-                } else {
-                    info!(
-                        "Game: {}s, tick {}, {} humans + {} bots, {} wells | Perf: {:?} ({:.1}%)",
-                        elapsed,
-                        session_guard.game_loop.state().tick,
-                        human_count,
-                        bot_count,
-                        well_count,
-                        perf_status,
-                        perf_budget
-                    );
-                }
+        // Broadcast AOI-filtered snapshots if needed (each player gets their own filtered view)
+        // on the broadcast pool; it never takes the session lock, and a full queue drops the frame
+        if let Some(frame) = frame {
+            broadcast_pool.submit(frame);
+        }
+
+        // Log stats periodically (every 60 seconds by default, configurable via LOG_STATUS_INTERVAL_SECS)
+        let log_interval = std::env::var("LOG_STATUS_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(60);
+        if log_interval > 0 && tick_count % (physics::TICK_RATE as u64 * log_interval) == 0 {
+            let elapsed = start.elapsed().as_secs();
+            let human_count = view.connections;
+            let bot_count = view.bots;
+            let well_count = view.snapshot.gravity_wells.len();
+            let perf_status = view.performance;
+            let perf_budget = view.budget_usage_percent;
+
+            if simulation_config.enabled {
+                let target = view.bot_target;
+                let cycle_progress = (elapsed as f32 % simulation_config.cycle_duration_secs)
+                    / simulation_config.cycle_duration_secs * 100.0;
+                info!(
+                    "Game: {}s, tick {}, {} humans + {}/{} bots, {} wells | Perf: {:?} ({:.1}%) | Sim: {:.1}% cycle",
+                    elapsed,
+                    view.tick(),
+                    human_count,
+                    bot_count,
+                    target,
+                    well_count,
+                    perf_status,
+                    perf_budget,
+                    cycle_progress
+                );
+            } else {
+                info!(
+                    "Game: {}s, tick {}, {} humans + {} bots, {} wells | Perf: {:?} ({:.1}%)",
+                    elapsed,
+                    view.tick(),
+                    human_count,
+                    bot_count,
+                    well_count,
+                    perf_status,
+                    perf_budget
+                );
             }
         }
-    });
+    }
+
+    replay.finish();
+    info!("Game loop stopped after {} ticks", tick_count);
 }
 
 /// Start the AI manager for autonomous parameter tuning (if enabled)
-/// This runs alongside the game loop and periodically analyzes metrics
+/// This runs alongside the game loop and periodically analyzes metrics.
+/// The returned task ends, with its history saved, once `shutdown` is triggered.
 #[cfg(feature = "ai_manager")]
-pub async fn start_ai_manager(session: Arc<RwLock<GameSession>>) {
+pub async fn start_ai_manager(
+    session: Arc<RwLock<GameSession>>,
+    shutdown: ShutdownToken,
+) -> Option<tokio::task::JoinHandle<()>> {
     use crate::ai_manager::AIManager;
 
     // Load AI manager config
@@ -2684,7 +4701,7 @@ pub async fn start_ai_manager(session: Arc<RwLock<GameSession>>) {
     // Skip if not enabled or no API key
     if !config.is_active() {
         info!("AI Manager disabled (AI_ENABLED=false or ORBIT_API_KEY not set)");
-        return;
+        return None;
     }
 
     // Get references needed by AI manager (using async read)
@@ -2694,22 +4711,81 @@ pub async fn start_ai_manager(session: Arc<RwLock<GameSession>>) {
             Some(m) => Arc::clone(m),
             None => {
                 warn!("AI Manager requires metrics to be enabled");
-                return;
+                return None;
             }
         }
     };
 
-    let arena_config = {
+    let (arena_config, modifier_requests, projectile_economy) = {
         let session_guard = session.read().await;
-        session_guard.arena_config()
+        (session_guard.arena_config(), session_guard.modifier_requests(), session_guard.projectile_economy())
     };
 
     // Create and spawn the AI manager
     let manager = AIManager::new(config);
 
-    tokio::spawn(async move {
+    Some(tokio::spawn(async move {
         info!("Starting AI Simulation Manager");
-        manager.run(metrics, arena_config).await;
+        manager.run(metrics, arena_config, modifier_requests, projectile_economy, shutdown).await;
+    }))
+}
+
+/// Start the spectator narrator (if enabled)
+/// Aggregates game loop events and broadcasts a `Commentary` line to spectators
+/// every `AI_NARRATOR_INTERVAL_SECS`, skipping the API when nobody is watching.
+/// Stops once `shutdown` is triggered.
+#[cfg(feature = "ai_manager")]
+pub async fn start_narrator(session: Arc<RwLock<GameSession>>, shutdown: ShutdownToken) {
+    use crate::ai_manager::{ClaudeClient, EventAggregator, Narrator};
+    use tokio::sync::broadcast::error::RecvError;
+
+    let config = AIManagerConfig::from_env();
+    if !config.narrator_active() {
+        info!("Narrator disabled (AI_NARRATOR_ENABLED=false or ORBIT_API_KEY not set)");
+        return;
+    }
+
+    let client = ClaudeClient::new(config.api_key.clone().unwrap_or_default(), config.model.clone());
+    let mut narrator = Narrator::new(client, config.narrator_max_calls_per_hour);
+    let window_secs = config.narrator_interval_secs;
+    let mut events = session.read().await.subscribe_events();
+
+    tokio::spawn(async move {
+        info!("Starting spectator narrator (every {}s)", window_secs);
+        let mut aggregator = EventAggregator::new();
+        let mut ticker = interval(Duration::from_secs(window_secs as u64));
+        ticker.tick().await; // First tick fires immediately
+        let stopped = shutdown.triggered();
+        tokio::pin!(stopped);
+
+        loop {
+            tokio::select! {
+                _ = &mut stopped => break,
+                event = events.recv() => match event {
+                    Ok(event) => aggregator.record(&event),
+                    Err(RecvError::Lagged(skipped)) => debug!("Narrator lagged, skipped {} events", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => {
+                    let (digest, tick, has_spectators) = {
+                        let session_guard = session.read().await;
+                        let state = session_guard.game_loop.state();
+                        (
+                            aggregator.take_digest(state, window_secs),
+                            state.tick,
+                            session_guard.players.values().any(|c| c.is_spectator),
+                        )
+                    };
+                    if !has_spectators {
+                        continue;
+                    }
+                    if let Some(text) = narrator.commentary(&digest).await {
+                        let session_guard = session.read().await;
+                        broadcast_to_spectators(&session_guard, &ServerMessage::Commentary { text, tick }).await;
+                    }
+                }
+            }
+        }
     });
 }
 
@@ -2731,14 +4807,38 @@ mod client_net_state_tests {
             arena_safe_radius: 3000.0,
             arena_scale: 1.0,
             gravity_wells: Vec::new(),
+            asteroids: vec![],
             total_players: 0,
             total_alive: 0,
             density_grid: Vec::new(),
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         }
     }
 
+    #[tokio::test]
+    async fn test_high_rate_client_keeps_recording_at_base_cadence() {
+        let mut session = GameSession::new();
+        session.snapshot_rate_config = SnapshotRateConfig { adaptive: false, allow_high: true, ..Default::default() };
+        let player_id = uuid::Uuid::new_v4();
+        session.add_player(player_id, "Fast".to_string(), 0, Arc::new(RwLock::new(None)));
+        assert_eq!(session.negotiate_snapshot_rate(player_id, SnapshotRate::High), SnapshotRate::High);
+
+        let (mut sent, mut recorded) = (0, 0);
+        for tick in 1..=6 {
+            session.game_loop.state_mut().tick = tick;
+            if session.should_send_snapshot() {
+                sent += 1;
+                if session.mark_snapshot_sent() {
+                    recorded += 1;
+                }
+            }
+        }
+        assert_eq!(sent, 6);
+        assert_eq!(recorded, 2);
+    }
+
     #[test]
     fn test_client_net_state_default() {
         let state = ClientNetState::default();
@@ -2766,8 +4866,13 @@ mod client_net_state_tests {
     fn test_needs_full_after_interval() {
         let state = ClientNetState {
             last_snapshot: Some(test_snapshot()),
+            earlier_baselines: VecDeque::new(),
             last_full_tick: 0,
             needs_full_resync: false,
+            schedule: SendSchedule::default(),
+            aoi: AoiMembership::default(),
+            next_region_hint_tick: 0,
+            last_resync_request: None,
         };
 
         let current_tick = FULL_RESYNC_INTERVAL + 1;
@@ -2782,8 +4887,13 @@ mod client_net_state_tests {
     fn test_delta_within_interval() {
         let state = ClientNetState {
             last_snapshot: Some(test_snapshot()),
+            earlier_baselines: VecDeque::new(),
             last_full_tick: 100,
             needs_full_resync: false,
+            schedule: SendSchedule::default(),
+            aoi: AoiMembership::default(),
+            next_region_hint_tick: 0,
+            last_resync_request: None,
         };
 
         let current_tick = 115; // Only 15 ticks since last full, interval is 30
@@ -2794,6 +4904,49 @@ mod client_net_state_tests {
         assert!(!needs_full, "Should send delta within interval");
     }
 
+    #[test]
+    fn test_requested_resync_is_rate_limited() {
+        let mut state = ClientNetState::default();
+        state.push_baseline(test_snapshot(), None);
+        state.push_baseline(GameSnapshot { tick: 30, ..test_snapshot() }, None);
+        let now = std::time::Instant::now();
+
+        assert!(state.request_resync(now));
+        assert!(state.needs_full_resync);
+        assert!(state.last_snapshot.is_none() && state.earlier_baselines.is_empty());
+
+        state.needs_full_resync = false;
+        assert!(!state.request_resync(now + Duration::from_millis(500)));
+        assert!(!state.needs_full_resync);
+        assert!(state.request_resync(now + MIN_RESYNC_REQUEST_INTERVAL));
+    }
+
+    #[test]
+    fn test_deltas_use_acknowledged_baseline() {
+        let baseline = |tick| GameSnapshot { tick, ..test_snapshot() };
+        let mut state = ClientNetState::default();
+        assert!(state.delta_base(None).is_none());
+
+        // Before any ack, deltas use the newest sent baseline
+        state.push_baseline(baseline(100), None);
+        assert_eq!(state.delta_base(None).unwrap().tick, 100);
+
+        // A newer full snapshot (maybe lost) doesn't move the base until acked
+        state.push_baseline(baseline(130), Some(100));
+        assert_eq!(state.delta_base(Some(100)).unwrap().tick, 100);
+        assert_eq!(state.delta_base(Some(130)).unwrap().tick, 130);
+        assert!(state.acked_baseline(Some(115)).is_none());
+
+        // Baselines older than the ack are dropped, and retention is bounded
+        state.push_baseline(baseline(160), Some(130));
+        assert!(state.acked_baseline(Some(100)).is_none());
+        for tick in (190..400).step_by(30) {
+            state.push_baseline(baseline(tick), Some(130));
+        }
+        assert_eq!(state.earlier_baselines.len(), MAX_RETAINED_BASELINES);
+        assert_eq!(state.delta_base(Some(130)).unwrap().tick, 370);
+    }
+
     #[test]
     fn test_pinned_base_strategy() {
         // Bug #1 & #2 fix: last_snapshot should ONLY be updated on FULL resync
@@ -2898,3 +5051,193 @@ mod client_net_state_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod hit_confirmation_tests {
+    use super::*;
+    use crate::util::vec2::Vec2;
+
+    fn hit(shooter_id: PlayerId, victim_id: PlayerId, projectile_id: u64) -> GameLoopEvent {
+        GameLoopEvent::PlayerHit { shooter_id, victim_id, projectile_id, amount: 12.0, position: Vec2::new(5.0, 5.0) }
+    }
+
+    #[test]
+    fn test_hits_batched_per_shooter_and_victim() {
+        let (a, b, c) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let events = vec![hit(a, b, 1), GameLoopEvent::Tick { tick: 7 }, hit(a, c, 2), hit(c, b, 3)];
+
+        let batches = hit_batches(&events);
+        let projectiles = |id: PlayerId| -> Vec<u64> {
+            batches[&id]
+                .iter()
+                .map(|e| match e {
+                    GameEvent::Hit { projectile_id, .. } => *projectile_id,
+                    other => panic!("unexpected event {other:?}"),
+                })
+                .collect()
+        };
+        // The shooter confirms both hits, each victim only hears about its own
+        assert_eq!(projectiles(a), vec![1, 2]);
+        assert_eq!(projectiles(b), vec![1, 3]);
+        assert_eq!(projectiles(c), vec![2, 3]);
+        assert!(hit_batches(&[GameLoopEvent::MatchResumed]).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod join_priority_tests {
+    use super::*;
+
+    fn no_writer() -> Arc<RwLock<Option<wtransport::SendStream>>> {
+        Arc::new(RwLock::new(None))
+    }
+
+    #[tokio::test]
+    async fn test_party_priority_needs_an_issued_invite() {
+        let mut session = GameSession::new();
+        let leader = uuid::Uuid::new_v4();
+        session.add_player(leader, "Leader".to_string(), 0, no_writer());
+
+        // Naming a player in the arena is not enough
+        assert_eq!(session.classify_join(Role::Player, Some(leader), None), JoinPriority::Normal);
+
+        // Join with the code the player is shown in the /invite reply
+        let (reply, _) = session.run_command(leader, &ChatCommand::Invite).unwrap();
+        assert_eq!(reply.key, keys::COMMAND_PARTY_INVITE);
+        let code = reply.params.iter().find(|(name, _)| name == "code").unwrap().1.parse().unwrap();
+        assert_eq!(session.classify_join(Role::Player, Some(code), None), JoinPriority::Party);
+        assert_eq!(session.classify_join(Role::Player, Some(code), None), JoinPriority::Normal);
+
+        // Spectators can't invite
+        let spectator = uuid::Uuid::new_v4();
+        session.add_spectator(spectator, "Watcher".to_string(), no_writer());
+        assert!(session.run_command(spectator, &ChatCommand::Invite).is_err());
+    }
+}
+
+#[cfg(test)]
+mod moderation_tests {
+    use super::*;
+    use crate::net::account::AccountId;
+    use crate::net::reports::MAX_KARMA;
+
+    fn join(session: &mut GameSession, name: &str, account: AccountId) -> PlayerId {
+        let player_id = uuid::Uuid::new_v4();
+        session.add_player(player_id, name.to_string(), 0, Arc::new(RwLock::new(None)));
+        session.set_account(player_id, account);
+        player_id
+    }
+
+    #[tokio::test]
+    async fn test_mute_survives_reconnect() {
+        let mut session = GameSession::new();
+        let moderator = join(&mut session, "Mod", AccountId::generate());
+        session.set_role(moderator, Role::Moderator);
+        let account = AccountId::generate();
+        let player = join(&mut session, "Rude", account);
+
+        let mute = ChatCommand::Mute { target: "Rude".to_string(), duration: Some(Duration::from_secs(600)) };
+        assert!(session.run_command(moderator, &mute).is_ok());
+        assert!(session.handle_chat(player, "hello").is_none());
+
+        // Reconnecting with the same account keeps the mute; other accounts may chat
+        session.remove_player(player);
+        let rejoined = join(&mut session, "Rude", account);
+        assert!(session.handle_chat(rejoined, "hello again").is_none());
+        let other = join(&mut session, "Polite", AccountId::generate());
+        assert!(matches!(session.handle_chat(other, "hi"), Some(ServerMessage::Chat { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_reports_follow_the_account() {
+        let mut session = GameSession::new();
+        let (target_account, reporter_account) = (AccountId::generate(), AccountId::generate());
+        let target = join(&mut session, "Griefer", target_account);
+        let reporter = join(&mut session, "Victim", reporter_account);
+        session.handle_report(reporter, target, ReportReason::Griefing);
+        let karma = session.karma(target_account);
+        assert!(karma < MAX_KARMA);
+
+        // Reconnecting restores neither the target's karma nor the reporter's right to report again
+        session.remove_player(target);
+        session.remove_player(reporter);
+        let target = join(&mut session, "Griefer", target_account);
+        let reporter = join(&mut session, "Victim", reporter_account);
+        session.handle_report(reporter, target, ReportReason::Griefing);
+        assert!((session.karma(target_account) - karma).abs() < 0.01);
+    }
+}
+
+#[cfg(test)]
+mod presence_tests {
+    use super::*;
+    use crate::net::account::AccountId;
+
+    #[tokio::test]
+    async fn test_published_accounts_follow_connections() {
+        let mut session = GameSession::new();
+        let (player_id, account) = (uuid::Uuid::new_v4(), AccountId::generate());
+        session.add_player(player_id, "Nova".to_string(), 0, Arc::new(RwLock::new(None)));
+        // Only accounts proven at join are published
+        assert!(session.publish_state_view().accounts.is_empty());
+
+        session.set_account(player_id, account);
+        assert_eq!(session.publish_state_view().accounts.get(&account), Some(&player_id));
+        session.remove_player(player_id);
+        assert!(session.publish_state_view().accounts.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod input_stats_tests {
+    use super::*;
+    use crate::util::vec2::Vec2;
+
+    #[tokio::test]
+    async fn test_queued_inputs_feed_the_published_stats() {
+        let mut session = GameSession::new();
+        let player_id = uuid::Uuid::new_v4();
+        session.add_player(player_id, "Pilot".to_string(), 0, Arc::new(RwLock::new(None)));
+        let input = |sequence, boost| PlayerInput {
+            sequence,
+            thrust: Vec2::new(1.0, 0.0),
+            aim: Vec2::new(0.0, 1.0),
+            boost,
+            ..Default::default()
+        };
+
+        session.queue_input(player_id, input(1, true));
+        session.queue_input(player_id, input(2, false));
+        // Duplicates (stream and datagram copies) count once
+        session.queue_input(player_id, input(2, false));
+
+        let report = session.input_stats_report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].player_id, player_id);
+        assert_eq!(report[0].stats.samples, 2);
+        assert_eq!(report[0].stats.boost_ratio, 0.5);
+    }
+}
+
+#[cfg(all(test, feature = "anticheat"))]
+mod anticheat_metrics_tests {
+    use super::*;
+    use crate::util::vec2::Vec2;
+
+    #[test]
+    fn test_input_violations_are_counted_by_type() {
+        let metrics = Arc::new(Metrics::new());
+        let mut session = GameSession::new_with_metrics(metrics.clone());
+        let player_id = uuid::Uuid::new_v4();
+        let input = |sequence, thrust| PlayerInput { sequence, tick: 0, thrust, ..Default::default() };
+
+        session.queue_input(player_id, input(5, Vec2::new(3.0, 0.0)));
+        session.queue_input(player_id, input(3, Vec2::ZERO));
+        assert_eq!(metrics.anticheat_violations.get("invalid_thrust"), 1);
+        assert_eq!(metrics.anticheat_violations.get("sequence_regression"), 1);
+        assert_eq!(metrics.anticheat_inputs_validated.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.anticheat_inputs_sanitized.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.anticheat_inputs_rejected.load(Ordering::Relaxed), 1);
+        assert_eq!(session.rejected_inputs[&player_id], 2);
+    }
+}
@@ -0,0 +1,114 @@
+//! Idle-server hibernation
+//!
+//! A server nobody is connected to still simulates a full arena of bots at
+//! the full tick rate, which is pure cloud cost. After `idle_minutes` without
+//! any connected client the session hibernates: most bots are despawned, the
+//! game loop drops to a few ticks per second and pooled encode buffers are
+//! released. The next connection attempt wakes it immediately, restoring the
+//! bot target (bots ramp back in at the startup spawn rate) and the full tick
+//! rate; the wake signal interrupts the game loop's long hibernation sleep.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+use crate::config::HibernationConfig;
+
+/// Hibernation state of a game session
+#[derive(Debug)]
+pub struct Hibernation {
+    config: HibernationConfig,
+    /// Since when no client has been connected (None while someone is)
+    idle_since: Option<Instant>,
+    /// Bot target to restore on wake (Some while hibernating)
+    saved_bot_target: Option<usize>,
+    /// Wakes the game loop out of its hibernation sleep
+    wake: Arc<Notify>,
+}
+
+impl Hibernation {
+    pub fn new(config: HibernationConfig) -> Self {
+        Self { config, idle_since: None, saved_bot_target: None, wake: Arc::new(Notify::new()) }
+    }
+
+    pub fn is_asleep(&self) -> bool {
+        self.saved_bot_target.is_some()
+    }
+
+    /// Track the connected client count; true once the server has been idle long enough to hibernate
+    pub fn should_sleep(&mut self, connected: usize, now: Instant) -> bool {
+        if !self.config.enabled || self.is_asleep() {
+            return false;
+        }
+        if connected > 0 {
+            self.idle_since = None;
+            return false;
+        }
+        let since = *self.idle_since.get_or_insert(now);
+        now.duration_since(since) >= Duration::from_secs(self.config.idle_minutes * 60)
+    }
+
+    /// Enter hibernation, remembering `bot_target`; returns the bot target while asleep
+    pub fn sleep(&mut self, bot_target: usize) -> usize {
+        self.saved_bot_target = Some(bot_target);
+        self.config.bots.min(bot_target)
+    }
+
+    /// Leave hibernation and signal the game loop; returns the bot target to restore (None if awake)
+    pub fn wake(&mut self) -> Option<usize> {
+        self.idle_since = None;
+        let target = self.saved_bot_target.take()?;
+        self.wake.notify_one();
+        Some(target)
+    }
+
+    /// Time between ticks while hibernating
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_millis(1000 / self.config.tick_hz.max(1) as u64)
+    }
+
+    /// Signal raised by `wake`, for the game loop to wait on
+    pub fn wake_signal(&self) -> Arc<Notify> {
+        self.wake.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> HibernationConfig {
+        HibernationConfig { enabled: true, idle_minutes: 10, tick_hz: 2, bots: 4 }
+    }
+
+    #[test]
+    fn test_sleeps_after_idle_period_and_wakes() {
+        let mut hibernation = Hibernation::new(config());
+        let start = Instant::now();
+
+        assert!(!hibernation.should_sleep(0, start));
+        assert!(!hibernation.should_sleep(0, start + Duration::from_secs(300)));
+        // A connection resets the idle timer
+        assert!(!hibernation.should_sleep(1, start + Duration::from_secs(400)));
+        assert!(!hibernation.should_sleep(0, start + Duration::from_secs(600)));
+        assert!(hibernation.should_sleep(0, start + Duration::from_secs(1200)));
+
+        assert_eq!(hibernation.sleep(500), 4);
+        assert!(hibernation.is_asleep());
+        assert!(!hibernation.should_sleep(0, start + Duration::from_secs(2000)));
+        assert_eq!(hibernation.tick_interval(), Duration::from_millis(500));
+
+        assert_eq!(hibernation.wake(), Some(500));
+        assert!(!hibernation.is_asleep());
+        assert_eq!(hibernation.wake(), None);
+    }
+
+    #[test]
+    fn test_disabled_never_sleeps() {
+        let mut hibernation = Hibernation::new(HibernationConfig { enabled: false, ..config() });
+        let start = Instant::now();
+        assert!(!hibernation.should_sleep(0, start));
+        assert!(!hibernation.should_sleep(0, start + Duration::from_secs(86_400)));
+    }
+}
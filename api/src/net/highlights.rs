@@ -0,0 +1,384 @@
+//! Automatic highlight clips
+//!
+//! Watches the game loop's events and snapshots for moments worth sharing
+//! and cuts a short replay around each one, so social clips need no manual
+//! review:
+//!
+//! - multi-kills: `multi_kill` kills by one player within `multi_kill_secs`
+//! - mass swings: a player's mass grew or shrank by `mass_ratio` within 5 seconds
+//! - narrow well escapes: a player came within `well_margin` of a well's core
+//!   and got away
+//!
+//! Clips cover `pre_roll_secs` before the moment to `post_roll_secs` after it
+//! and use the replay format (see `net::replay`); a `Highlight` chapter marks
+//! the moment. They are written to `HIGHLIGHT_DIR` and listed by `/highlights`.
+//! Bots only get highlights with `HIGHLIGHT_INCLUDE_BOTS`, and players who opt
+//! out of replays appear as "Anonymous".
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::HighlightConfig;
+use crate::game::constants::physics::TICK_RATE;
+use crate::game::game_loop::GameLoopEvent;
+use crate::game::match_result::ANONYMOUS_NAME;
+use crate::game::state::{PlayerId, WellId};
+use crate::net::protocol::GameSnapshot;
+use crate::net::replay::{anonymized, Chapter, ChapterKind, ReplayError, ReplayWriter, REPLAY_EXTENSION};
+
+/// Window a mass swing has to happen in
+const MASS_SWING_SECS: f32 = 5.0;
+/// How far past the margin a player has to get before an approach counts as an escape
+const ESCAPE_FACTOR: f32 = 3.0;
+
+/// What made a moment a highlight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightKind {
+    MultiKill { kills: usize },
+    MassSwing { from: f32, to: f32 },
+    /// Closest the player got to the well's core
+    WellEscape { well_id: WellId, clearance: f32 },
+}
+
+impl HighlightKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HighlightKind::MultiKill { .. } => "multi_kill",
+            HighlightKind::MassSwing { .. } => "mass_swing",
+            HighlightKind::WellEscape { .. } => "well_escape",
+        }
+    }
+}
+
+/// A detected moment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Highlight {
+    pub tick: u64,
+    pub player_id: PlayerId,
+    pub kind: HighlightKind,
+    pub title: String,
+}
+
+/// Finds highlights in the event stream and snapshots
+struct HighlightDetector {
+    config: HighlightConfig,
+    /// Recent kill ticks per killer
+    kills: HashMap<PlayerId, VecDeque<u64>>,
+    /// Mass per tick over the swing window, per player
+    masses: HashMap<PlayerId, VecDeque<(u64, f32)>>,
+    /// Closest approach to a well core so far: (tick, well, clearance)
+    near_wells: HashMap<PlayerId, (u64, WellId, f32)>,
+}
+
+impl HighlightDetector {
+    fn new(config: HighlightConfig) -> Self {
+        Self { config, kills: HashMap::new(), masses: HashMap::new(), near_wells: HashMap::new() }
+    }
+
+    fn observe(&mut self, events: &[GameLoopEvent], snapshot: &GameSnapshot) -> Vec<(u64, PlayerId, HighlightKind)> {
+        let tick = snapshot.tick;
+        let include_bots = self.config.include_bots;
+        let tracked =
+            |id: &PlayerId| snapshot.players.iter().any(|p| p.id == *id && (include_bots || !p.is_bot()));
+        let mut found = Vec::new();
+
+        let multi_kill_ticks = (self.config.multi_kill_secs * TICK_RATE as f32) as u64;
+        for event in events {
+            match event {
+                GameLoopEvent::PlayerKilled { killer_id, .. } if tracked(killer_id) => {
+                    let kills = self.kills.entry(*killer_id).or_default();
+                    kills.push_back(tick);
+                    while kills.front().is_some_and(|&at| tick - at > multi_kill_ticks) {
+                        kills.pop_front();
+                    }
+                    if kills.len() >= self.config.multi_kill {
+                        found.push((tick, *killer_id, HighlightKind::MultiKill { kills: kills.len() }));
+                        kills.clear();
+                    }
+                }
+                GameLoopEvent::PlayerDied { victim_id, .. } => {
+                    self.masses.remove(victim_id);
+                    self.near_wells.remove(victim_id);
+                }
+                _ => {}
+            }
+        }
+
+        let swing_ticks = (MASS_SWING_SECS * TICK_RATE as f32) as u64;
+        let ratio = self.config.mass_ratio;
+        let margin = self.config.well_margin;
+        for player in &snapshot.players {
+            if !player.alive() || (player.is_bot() && !self.config.include_bots) {
+                continue;
+            }
+
+            let masses = self.masses.entry(player.id).or_default();
+            masses.push_back((tick, player.mass));
+            while masses.front().is_some_and(|&(at, _)| tick - at > swing_ticks) {
+                masses.pop_front();
+            }
+            let (_, from) = masses[0];
+            if from > 0.0 && (player.mass >= from * ratio || player.mass * ratio <= from) {
+                found.push((tick, player.id, HighlightKind::MassSwing { from, to: player.mass }));
+                masses.clear();
+            }
+
+            let closest = snapshot
+                .gravity_wells
+                .iter()
+                .map(|w| (w.id, w.position.distance_to(player.position) - w.core_radius))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            let Some((well_id, clearance)) = closest else {
+                continue;
+            };
+            match self.near_wells.get(&player.id).copied() {
+                Some((_, _, closest)) if clearance < closest => {
+                    self.near_wells.insert(player.id, (tick, well_id, clearance));
+                }
+                Some((at, near_well, closest)) if clearance > margin * ESCAPE_FACTOR => {
+                    self.near_wells.remove(&player.id);
+                    found.push((at, player.id, HighlightKind::WellEscape { well_id: near_well, clearance: closest }));
+                }
+                None if clearance < margin => {
+                    self.near_wells.insert(player.id, (tick, well_id, clearance));
+                }
+                _ => {}
+            }
+        }
+
+        self.masses.retain(|id, _| snapshot.players.iter().any(|p| p.id == *id));
+        self.near_wells.retain(|id, _| snapshot.players.iter().any(|p| p.id == *id));
+        self.kills.retain(|_, kills| kills.back().is_some_and(|&at| tick - at <= multi_kill_ticks));
+        found
+    }
+}
+
+/// Detects highlights and writes a clip around each
+pub struct HighlightRecorder {
+    detector: HighlightDetector,
+    /// Recent broadcast snapshots, anonymized, covering pre- and post-roll
+    buffer: VecDeque<Arc<GameSnapshot>>,
+    /// Highlights waiting for their post-roll
+    pending: Vec<Highlight>,
+    /// Connected players who opted out of replays
+    anonymous: HashSet<PlayerId>,
+}
+
+impl HighlightRecorder {
+    pub fn new(config: HighlightConfig) -> Self {
+        Self {
+            detector: HighlightDetector::new(config),
+            buffer: VecDeque::new(),
+            pending: Vec::new(),
+            anonymous: HashSet::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.detector.config.dir.is_some()
+    }
+
+    /// Look for highlights in a tick's events and snapshot. `anonymous` are the
+    /// connected players who opted out of replays.
+    pub fn observe(&mut self, events: &[GameLoopEvent], snapshot: &GameSnapshot, anonymous: &[PlayerId]) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.anonymous = anonymous.iter().copied().collect();
+        for (tick, player_id, kind) in self.detector.observe(events, snapshot) {
+            let name = match snapshot.players.iter().find(|p| p.id == player_id) {
+                _ if self.anonymous.contains(&player_id) => ANONYMOUS_NAME.to_string(),
+                Some(player) => player.name.clone(),
+                None => continue,
+            };
+            let title = match &kind {
+                HighlightKind::MultiKill { kills } => format!("{} takes down {} players in a row", name, kills),
+                HighlightKind::MassSwing { from, to } if to > from => {
+                    format!("{} grows from {:.0} to {:.0} mass", name, from, to)
+                }
+                HighlightKind::MassSwing { from, to } => format!("{} drops from {:.0} to {:.0} mass", name, from, to),
+                HighlightKind::WellEscape { clearance, .. } => {
+                    format!("{} escapes a gravity well with {:.0} units to spare", name, clearance.max(0.0))
+                }
+            };
+            self.pending.push(Highlight { tick, player_id, kind, title });
+        }
+    }
+
+    /// Keep a broadcast snapshot for clips, writing those whose post-roll is complete
+    pub fn record(&mut self, snapshot: &Arc<GameSnapshot>) {
+        if !self.is_enabled() {
+            return;
+        }
+        let config = &self.detector.config;
+        let pre_roll = (config.pre_roll_secs * TICK_RATE as f32) as u64;
+        let post_roll = (config.post_roll_secs * TICK_RATE as f32) as u64;
+        let snapshot = match anonymized(snapshot, &self.anonymous) {
+            std::borrow::Cow::Borrowed(_) => Arc::clone(snapshot),
+            std::borrow::Cow::Owned(snapshot) => Arc::new(snapshot),
+        };
+        let tick = snapshot.tick;
+        self.buffer.push_back(snapshot);
+        while self.buffer.front().is_some_and(|s| s.tick + pre_roll + post_roll < tick) {
+            self.buffer.pop_front();
+        }
+
+        let (due, pending): (Vec<Highlight>, Vec<Highlight>) =
+            std::mem::take(&mut self.pending).into_iter().partition(|h| tick >= h.tick + post_roll);
+        self.pending = pending;
+        for highlight in due {
+            let frames = self.buffer.iter().filter(|s| s.tick + pre_roll >= highlight.tick);
+            match self.write_clip(&highlight, frames) {
+                Ok(file) => info!("Saved highlight {}: {}", file, highlight.title),
+                Err(e) => warn!("Failed to save highlight: {}", e),
+            }
+        }
+    }
+
+    fn write_clip<'a>(
+        &self,
+        highlight: &Highlight,
+        frames: impl Iterator<Item = &'a Arc<GameSnapshot>>,
+    ) -> Result<String, ReplayError> {
+        let dir = self.detector.config.dir.as_deref().unwrap_or(".");
+        fs::create_dir_all(dir)?;
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let file = format!("highlight-{}-{}-{}.{}", secs, highlight.tick, highlight.kind.name(), REPLAY_EXTENSION);
+        let out = BufWriter::new(File::create(Path::new(dir).join(&file))?);
+        let mut writer = ReplayWriter::new(out, TICK_RATE as u64)?;
+        for snapshot in frames {
+            writer.record(snapshot)?;
+        }
+        writer.chapter(Chapter {
+            tick: highlight.tick,
+            kind: ChapterKind::Highlight { player: highlight.player_id, highlight: highlight.kind.clone() },
+            title: highlight.title.clone(),
+        });
+        writer.finish()?;
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::MatchPhase;
+    use crate::net::protocol::{player_flags, GravityWellSnapshot, PlayerSnapshot};
+    use crate::net::replay::ReplayReader;
+    use crate::util::vec2::Vec2;
+    use uuid::Uuid;
+
+    fn player(id: PlayerId, x: f32, mass: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            id,
+            name: "Nova".to_string(),
+            position: Vec2::new(x, 0.0),
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass,
+            flags: player_flags::ALIVE,
+            kills: 0,
+            deaths: 0,
+            color_index: 0,
+            spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
+        }
+    }
+
+    fn snapshot(tick: u64, players: Vec<PlayerSnapshot>) -> GameSnapshot {
+        let mut snapshot = (*crate::net::state_view::PublishedState::default().load().snapshot).clone();
+        snapshot.tick = tick;
+        snapshot.match_phase = MatchPhase::Playing;
+        snapshot.players = players;
+        snapshot.gravity_wells = vec![GravityWellSnapshot {
+            id: 7,
+            position: Vec2::new(1000.0, 0.0),
+            mass: 10000.0,
+            core_radius: 50.0,
+            owner_color: None,
+            capture_progress: 0.0,
+            contested: false,
+        }];
+        snapshot
+    }
+
+    fn kill(killer_id: PlayerId) -> GameLoopEvent {
+        GameLoopEvent::PlayerKilled { killer_id, victim_id: Uuid::new_v4() }
+    }
+
+    #[test]
+    fn test_detects_multi_kills_mass_swings_and_well_escapes() {
+        let mut detector = HighlightDetector::new(HighlightConfig::default());
+        let ace = Uuid::new_v4();
+
+        // Two quick kills, then a third too late to count
+        assert!(detector.observe(&[kill(ace), kill(ace)], &snapshot(1, vec![player(ace, 0.0, 100.0)])).is_empty());
+        let late = 1 + 11 * TICK_RATE as u64;
+        assert!(detector.observe(&[kill(ace)], &snapshot(late, vec![player(ace, 0.0, 100.0)])).is_empty());
+        let found = detector.observe(&[kill(ace), kill(ace)], &snapshot(late + 1, vec![player(ace, 0.0, 100.0)]));
+        assert_eq!(found, vec![(late + 1, ace, HighlightKind::MultiKill { kills: 3 })]);
+
+        // Mass doubling within the window
+        let found = detector.observe(&[], &snapshot(late + 30, vec![player(ace, 0.0, 210.0)]));
+        assert_eq!(found, vec![(late + 30, ace, HighlightKind::MassSwing { from: 100.0, to: 210.0 })]);
+
+        // Skimming a core (clearance 20, then 10) and getting away
+        detector.observe(&[], &snapshot(late + 31, vec![player(ace, 930.0, 210.0)]));
+        detector.observe(&[], &snapshot(late + 32, vec![player(ace, 940.0, 210.0)]));
+        assert!(detector.observe(&[], &snapshot(late + 33, vec![player(ace, 900.0, 210.0)])).is_empty());
+        let found = detector.observe(&[], &snapshot(late + 34, vec![player(ace, 800.0, 210.0)]));
+        assert_eq!(found, vec![(late + 32, ace, HighlightKind::WellEscape { well_id: 7, clearance: 10.0 })]);
+
+        // Falling in is no escape
+        detector.observe(&[], &snapshot(late + 35, vec![player(ace, 940.0, 210.0)]));
+        let died = GameLoopEvent::PlayerDied {
+            victim_id: ace,
+            killer_id: None,
+            cause: crate::net::protocol::DeathCause::Well,
+        };
+        detector.observe(&[died], &snapshot(late + 36, vec![]));
+        assert!(detector.observe(&[], &snapshot(late + 37, vec![player(ace, 0.0, 100.0)])).is_empty());
+    }
+
+    #[test]
+    fn test_clip_covers_the_moment_and_hides_opted_out_names() {
+        let dir = std::env::temp_dir().join(format!("orbit_highlights_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let config = HighlightConfig {
+            dir: Some(dir.to_str().unwrap().to_string()),
+            pre_roll_secs: 1.0,
+            post_roll_secs: 1.0,
+            ..HighlightConfig::default()
+        };
+        let mut recorder = HighlightRecorder::new(config);
+        let ace = Uuid::new_v4();
+        let moment = 3 * TICK_RATE as u64;
+
+        for tick in 1..=5 * TICK_RATE as u64 {
+            let current = snapshot(tick, vec![player(ace, 0.0, 100.0)]);
+            let events = if tick >= moment && tick < moment + 3 { vec![kill(ace)] } else { vec![] };
+            recorder.observe(&events, &current, &[ace]);
+            recorder.record(&Arc::new(current));
+        }
+
+        let path = fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+        let mut reader = ReplayReader::open(File::open(&path).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        let index = reader.index().clone();
+        let highlight = moment + 2;
+        assert_eq!((index.first_tick, index.last_tick), (highlight - TICK_RATE as u64, highlight + TICK_RATE as u64));
+        assert_eq!(index.chapters[0].title, "Anonymous takes down 3 players in a row");
+        assert_eq!(reader.seek(highlight).unwrap().current().players[0].name, ANONYMOUS_NAME);
+    }
+}
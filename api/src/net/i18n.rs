@@ -0,0 +1,233 @@
+//! Localizable server strings
+//!
+//! User-facing text generated by the server (kick reasons, join rejections,
+//! command replies, pause notices, modifier descriptions, announcements)
+//! travels as a `LocalizedText`: a stable message key plus named parameters.
+//! Clients look the key up in their own translation table and substitute
+//! `{param}` placeholders. The English table here is the fallback for clients
+//! without a translation, and what the server writes to its own logs.
+//!
+//! A parameter whose name ends in `_key` holds another message key rather than
+//! a value: `event_key` fills `{event}` with that message, rendered in the same
+//! language. This is how an announcement embeds an event description.
+//!
+//! Text typed by people, such as a moderator's kick or pause reason, is not
+//! translatable and is sent verbatim under `keys::VERBATIM`.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Message keys. Keys are part of the protocol: rename one only together with
+/// the client tables.
+pub mod keys {
+    /// Untranslated text in the `text` parameter
+    pub const VERBATIM: &str = "verbatim";
+
+    pub const KICK_BY_MODERATOR: &str = "kick.by_moderator";
+    pub const KICK_SERVER_SHUTDOWN: &str = "kick.server_shutdown";
+    pub const PAUSE_BY_REFEREE: &str = "pause.by_referee";
+
+    pub const REJECT_ENCRYPTION_REQUIRED: &str = "reject.encryption_required";
+    pub const REJECT_ENCRYPTION_FAILED: &str = "reject.encryption_failed";
+    pub const REJECT_ROOM_UNAVAILABLE: &str = "reject.room_unavailable";
+
+    pub const CHAT_MUTED: &str = "chat.muted";
+
+    pub const COMMAND_UNKNOWN: &str = "command.unknown";
+    pub const COMMAND_USAGE: &str = "command.usage";
+    pub const COMMAND_INVALID_DURATION: &str = "command.invalid_duration";
+    pub const COMMAND_DENIED: &str = "command.denied";
+    pub const COMMAND_KICKED: &str = "command.kicked";
+    pub const COMMAND_MUTED: &str = "command.muted";
+    pub const COMMAND_UNMUTED: &str = "command.unmuted";
+    pub const COMMAND_SPECTATE_FIRST: &str = "command.spectate_first";
+    pub const COMMAND_TARGET_SPECTATING: &str = "command.target_spectating";
+    pub const COMMAND_FOLLOWING: &str = "command.following";
+    pub const COMMAND_ANNOUNCED: &str = "command.announced";
+    pub const COMMAND_PAUSED: &str = "command.paused";
+    pub const COMMAND_ALREADY_PAUSED: &str = "command.already_paused";
+    pub const COMMAND_RESUMING: &str = "command.resuming";
+    pub const COMMAND_NOT_PAUSED: &str = "command.not_paused";
+    pub const COMMAND_NO_REVIEWS: &str = "command.no_reviews";
+    pub const COMMAND_NO_CASE: &str = "command.no_case";
+    pub const COMMAND_UPHELD: &str = "command.upheld";
+    pub const COMMAND_DISMISSED: &str = "command.dismissed";
+    pub const COMMAND_SELF_TARGET: &str = "command.self_target";
+    pub const COMMAND_OUTRANKED: &str = "command.outranked";
+    pub const COMMAND_PARTY_INVITE: &str = "command.party_invite";
+    pub const COMMAND_INVITE_IN_ARENA: &str = "command.invite_in_arena";
+
+    pub const PLAYER_NOT_FOUND: &str = "player.not_found";
+    pub const PLAYER_AMBIGUOUS: &str = "player.ambiguous";
+
+    pub const REPORT_RECEIVED: &str = "report.received";
+    pub const REPORT_SELF: &str = "report.self";
+    pub const REPORT_UNKNOWN_TARGET: &str = "report.unknown_target";
+    pub const REPORT_RATE_LIMITED: &str = "report.rate_limited";
+    pub const REPORT_DUPLICATE: &str = "report.duplicate";
+
+    pub const KILL_CRUSH: &str = "kill.crush";
+    pub const KILL_PROJECTILE: &str = "kill.projectile";
+    pub const KILL_WELL: &str = "kill.well";
+    pub const KILL_BOUNDARY: &str = "kill.boundary";
+    pub const KILL_DIED: &str = "kill.died";
+    pub const KILL_DOUBLE: &str = "kill.double";
+    pub const KILL_TRIPLE: &str = "kill.triple";
+    pub const KILL_MULTI: &str = "kill.multi";
+    pub const KILL_STREAK: &str = "kill.streak";
+    pub const KILL_SHUTDOWN: &str = "kill.shutdown";
+
+    pub const MODIFIER_SOLAR_FLARE: &str = "modifier.solar_flare";
+    pub const MODIFIER_DENSE_NEBULA: &str = "modifier.dense_nebula";
+    pub const MODIFIER_DEBRIS_SURGE: &str = "modifier.debris_surge";
+
+    pub const SCHEDULE_BOSS_SPAWN: &str = "schedule.boss_spawn";
+    pub const SCHEDULE_ARENA_RESET: &str = "schedule.arena_reset";
+
+    pub const ANNOUNCE_SCHEDULED_MINUTES: &str = "announce.scheduled_minutes";
+    pub const ANNOUNCE_SCHEDULED_MINUTE: &str = "announce.scheduled_minute";
+    pub const ANNOUNCE_SCHEDULED_SECONDS: &str = "announce.scheduled_seconds";
+    pub const ANNOUNCE_BOSS_SPAWN: &str = "announce.boss_spawn";
+    pub const ANNOUNCE_ARENA_RESET: &str = "announce.arena_reset";
+    pub const ANNOUNCE_ARENA_CHANGING: &str = "announce.arena_changing";
+}
+
+/// Fallback English table
+const ENGLISH: &[(&str, &str)] = &[
+    (keys::VERBATIM, "{text}"),
+    (keys::KICK_BY_MODERATOR, "Kicked by a moderator"),
+    (keys::KICK_SERVER_SHUTDOWN, "The server is shutting down"),
+    (keys::PAUSE_BY_REFEREE, "Paused by the referee"),
+    (keys::REJECT_ENCRYPTION_REQUIRED, "This server requires snapshot encryption; update your client"),
+    (keys::REJECT_ENCRYPTION_FAILED, "Snapshot encryption failed: {error}"),
+    (keys::REJECT_ROOM_UNAVAILABLE, "That room can't be spectated"),
+    (keys::CHAT_MUTED, "You are muted for another {duration}"),
+    (keys::COMMAND_UNKNOWN, "Unknown command /{command}"),
+    (keys::COMMAND_USAGE, "Usage: {usage}"),
+    (keys::COMMAND_INVALID_DURATION, "Invalid duration '{duration}' (use e.g. 30s, 10m, 2h)"),
+    (keys::COMMAND_DENIED, "You don't have permission to use /{command}"),
+    (keys::COMMAND_KICKED, "Kicked {name}"),
+    (keys::COMMAND_MUTED, "Muted {name} for {duration}"),
+    (keys::COMMAND_UNMUTED, "Unmuted {name}"),
+    (keys::COMMAND_SPECTATE_FIRST, "Switch to spectator mode first"),
+    (keys::COMMAND_TARGET_SPECTATING, "{name} is spectating"),
+    (keys::COMMAND_FOLLOWING, "Following {name}"),
+    (keys::COMMAND_ANNOUNCED, "Announcement sent"),
+    (keys::COMMAND_PAUSED, "Match paused"),
+    (keys::COMMAND_ALREADY_PAUSED, "The match is already paused"),
+    (keys::COMMAND_RESUMING, "Resuming in {seconds}s"),
+    (keys::COMMAND_NOT_PAUSED, "The match is not paused"),
+    (keys::COMMAND_NO_REVIEWS, "No players awaiting review"),
+    (keys::COMMAND_NO_CASE, "No open case for '{player}'"),
+    (keys::COMMAND_UPHELD, "Reports against {name} upheld"),
+    (keys::COMMAND_DISMISSED, "Reports against {name} dismissed"),
+    (keys::COMMAND_SELF_TARGET, "You can't target yourself"),
+    (keys::COMMAND_OUTRANKED, "Can't moderate a {role}"),
+    (keys::COMMAND_PARTY_INVITE, "Party invite code {code} (one use, valid for {duration})"),
+    (keys::COMMAND_INVITE_IN_ARENA, "Join the arena as a player to invite friends"),
+    (keys::PLAYER_NOT_FOUND, "No player named '{query}'"),
+    (keys::PLAYER_AMBIGUOUS, "'{query}' matches several players, use their ID"),
+    (keys::REPORT_RECEIVED, "Thanks, your report was received"),
+    (keys::REPORT_SELF, "You can't report yourself"),
+    (keys::REPORT_UNKNOWN_TARGET, "No such player"),
+    (keys::REPORT_RATE_LIMITED, "Too many reports, try again later"),
+    (keys::REPORT_DUPLICATE, "You already reported this player recently"),
+    (keys::KILL_CRUSH, "{killer} crushed {victim}"),
+    (keys::KILL_PROJECTILE, "{killer} shot down {victim}"),
+    (keys::KILL_WELL, "{victim} fell into a gravity well"),
+    (keys::KILL_BOUNDARY, "{victim} was lost beyond the arena edge"),
+    (keys::KILL_DIED, "{victim} died"),
+    (keys::KILL_DOUBLE, "Double kill"),
+    (keys::KILL_TRIPLE, "Triple kill"),
+    (keys::KILL_MULTI, "{count} kills in a row"),
+    (keys::KILL_STREAK, "{name} is on a {count} kill streak"),
+    (keys::KILL_SHUTDOWN, "Shutdown! {name}'s {count} kill streak ended"),
+    (keys::MODIFIER_SOLAR_FLARE, "Solar flare: projectiles travel 30% faster"),
+    (keys::MODIFIER_DENSE_NEBULA, "Dense nebula: vision radius halved"),
+    (keys::MODIFIER_DEBRIS_SURGE, "Debris surge: debris spawns twice as fast"),
+    (keys::SCHEDULE_BOSS_SPAWN, "A boss bot enters the arena"),
+    (keys::SCHEDULE_ARENA_RESET, "The arena resets and everyone respawns"),
+    (keys::ANNOUNCE_SCHEDULED_MINUTES, "In {minutes} minutes: {event}"),
+    (keys::ANNOUNCE_SCHEDULED_MINUTE, "In 1 minute: {event}"),
+    (keys::ANNOUNCE_SCHEDULED_SECONDS, "In {seconds} seconds: {event}"),
+    (keys::ANNOUNCE_BOSS_SPAWN, "{name} has entered the arena. Bring it down!"),
+    (keys::ANNOUNCE_ARENA_RESET, "The arena has been reset"),
+    (keys::ANNOUNCE_ARENA_CHANGING, "The arena is changing to {name}"),
+];
+
+/// A server string as a message key and its parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalizedText {
+    pub key: String,
+    /// Named values for the `{name}` placeholders of the message
+    pub params: Vec<(String, String)>,
+}
+
+impl LocalizedText {
+    pub fn new(key: &str) -> Self {
+        Self { key: key.to_string(), params: Vec::new() }
+    }
+
+    /// Add a parameter (builder style)
+    pub fn with(mut self, name: &str, value: impl fmt::Display) -> Self {
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Text shown as-is in every language
+    pub fn verbatim(text: impl Into<String>) -> Self {
+        Self { key: keys::VERBATIM.to_string(), params: vec![("text".to_string(), text.into())] }
+    }
+
+    /// Render with the fallback English table (unknown keys render as the key)
+    pub fn english(&self) -> String {
+        self.params.iter().fold(english_template(&self.key).to_string(), |text, (name, value)| {
+            match name.strip_suffix("_key") {
+                Some(name) => text.replace(&format!("{{{}}}", name), english_template(value)),
+                None => text.replace(&format!("{{{}}}", name), value),
+            }
+        })
+    }
+}
+
+/// English text of a key, or the key itself if it has none
+fn english_template(key: &str) -> &str {
+    ENGLISH.iter().find(|(k, _)| *k == key).map_or(key, |(_, text)| text)
+}
+
+impl fmt::Display for LocalizedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.english())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_fallback() {
+        let muted = LocalizedText::new(keys::COMMAND_MUTED).with("name", "Bob").with("duration", "10m");
+        assert_eq!(muted.english(), "Muted Bob for 10m");
+        assert_eq!(LocalizedText::verbatim("Griefing {name}").to_string(), "Griefing {name}");
+        assert_eq!(LocalizedText::new("no.such.key").english(), "no.such.key");
+    }
+
+    #[test]
+    fn test_key_params_render_nested_messages() {
+        let upcoming = LocalizedText::new(keys::ANNOUNCE_SCHEDULED_SECONDS)
+            .with("seconds", 30)
+            .with("event_key", keys::SCHEDULE_BOSS_SPAWN);
+        assert_eq!(upcoming.english(), "In 30 seconds: A boss bot enters the arena");
+    }
+
+    #[test]
+    fn test_english_table_is_well_formed() {
+        for (i, (key, text)) in ENGLISH.iter().enumerate() {
+            assert!(!text.is_empty(), "{} has no text", key);
+            assert!(ENGLISH[i + 1..].iter().all(|(other, _)| other != key), "{} is listed twice", key);
+            assert_eq!(text.matches('{').count(), text.matches('}').count(), "{} has unbalanced braces", key);
+        }
+    }
+}
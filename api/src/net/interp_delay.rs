@@ -0,0 +1,129 @@
+//! Per-client interpolation delay recommendation
+//!
+//! Clients render remote entities slightly in the past so there are always two
+//! snapshots to interpolate between. A fixed delay stutters on jittery links,
+//! so the server estimates each client's network jitter from input arrival
+//! times (RFC 3550 interarrival jitter over the client timestamp) and
+//! recommends a delay of a few snapshot intervals plus a jitter margin. The
+//! value is sent in every full snapshot.
+
+use crate::config::InterpDelayConfig;
+
+/// Jitter smoothing gain (1/16, as in RFC 3550)
+const JITTER_GAIN: f32 = 1.0 / 16.0;
+
+/// Interarrival jitter estimate for one client
+#[derive(Debug, Clone, Default)]
+pub struct JitterEstimator {
+    /// Previous (arrival - client send time) in ms; offset by the unknown clock skew
+    last_transit_ms: Option<f64>,
+    jitter_ms: f32,
+}
+
+impl JitterEstimator {
+    /// Record a packet sent at `client_time_ms` (client clock) that arrived at
+    /// `arrival_ms` (server clock)
+    pub fn record(&mut self, client_time_ms: u64, arrival_ms: f64) {
+        let transit = arrival_ms - client_time_ms as f64;
+        if let Some(last) = self.last_transit_ms {
+            let d = (transit - last).abs() as f32;
+            self.jitter_ms += (d - self.jitter_ms) * JITTER_GAIN;
+        }
+        self.last_transit_ms = Some(transit);
+    }
+
+    pub fn jitter_ms(&self) -> f32 {
+        self.jitter_ms
+    }
+}
+
+/// Jitter tracking plus the current (hysteresis-filtered) recommendation
+#[derive(Debug, Clone, Default)]
+pub struct InterpDelayTracker {
+    jitter: JitterEstimator,
+    recommended_ms: u16,
+}
+
+impl InterpDelayTracker {
+    /// Record an input's client timestamp and server arrival time (ms), then
+    /// refresh the recommendation for the given snapshot interval
+    pub fn record_input(
+        &mut self,
+        client_time_ms: u64,
+        arrival_ms: f64,
+        snapshot_interval_ms: f32,
+        config: &InterpDelayConfig,
+    ) {
+        self.jitter.record(client_time_ms, arrival_ms);
+
+        let target = recommend_delay_ms(snapshot_interval_ms, self.jitter.jitter_ms(), config);
+        if self.recommended_ms == 0 || target.abs_diff(self.recommended_ms) >= config.hysteresis_ms {
+            self.recommended_ms = target;
+        }
+    }
+
+    /// Current recommendation (0 until the first input arrives)
+    pub fn recommended_ms(&self) -> u16 {
+        self.recommended_ms
+    }
+
+    pub fn jitter_ms(&self) -> f32 {
+        self.jitter.jitter_ms()
+    }
+}
+
+/// Delay covering `buffer_snapshots` snapshot intervals plus a jitter margin
+pub fn recommend_delay_ms(snapshot_interval_ms: f32, jitter_ms: f32, config: &InterpDelayConfig) -> u16 {
+    let delay = snapshot_interval_ms * config.buffer_snapshots + jitter_ms * config.jitter_multiplier;
+    (delay.round() as u16).clamp(config.min_ms, config.max_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steady_arrivals_have_no_jitter() {
+        let mut estimator = JitterEstimator::default();
+        // Constant 40ms transit (plus arbitrary clock offset)
+        for i in 0..50u64 {
+            estimator.record(1_000 + i * 16, 5_040.0 + (i * 16) as f64);
+        }
+        assert!(estimator.jitter_ms() < 1e-3);
+    }
+
+    #[test]
+    fn test_variable_arrivals_raise_jitter() {
+        let mut estimator = JitterEstimator::default();
+        for i in 0..200u64 {
+            let spike = if i % 2 == 0 { 0.0 } else { 60.0 };
+            estimator.record(i * 16, (i * 16) as f64 + spike);
+        }
+        // Alternating 60ms spikes converge towards 60ms jitter
+        assert!(estimator.jitter_ms() > 50.0, "jitter {}", estimator.jitter_ms());
+    }
+
+    #[test]
+    fn test_recommendation_is_clamped() {
+        let config = InterpDelayConfig::default();
+        assert_eq!(recommend_delay_ms(100.0, 0.0, &config), 200);
+        assert_eq!(recommend_delay_ms(100.0, 20.0, &config), 240);
+        assert_eq!(recommend_delay_ms(1.0, 0.0, &config), config.min_ms);
+        assert_eq!(recommend_delay_ms(100.0, 1_000.0, &config), config.max_ms);
+    }
+
+    #[test]
+    fn test_tracker_applies_hysteresis() {
+        let config = InterpDelayConfig::default();
+        let mut tracker = InterpDelayTracker::default();
+        assert_eq!(tracker.recommended_ms(), 0);
+
+        tracker.record_input(0, 0.0, 100.0, &config);
+        assert_eq!(tracker.recommended_ms(), 200);
+
+        // One late packet: jitter rises a little, below the hysteresis band
+        tracker.record_input(16, 56.0, 100.0, &config);
+        assert!(tracker.jitter_ms() > 0.0);
+        assert_eq!(tracker.recommended_ms(), 200);
+    }
+}
@@ -0,0 +1,376 @@
+//! Join queue for players who arrive while the server is at capacity
+//!
+//! Instead of rejecting joins outright, connections wait in a FIFO queue and
+//! receive periodic position/ETA updates. Party members of players already in
+//! the arena, returning reconnects and roles with reserved-slot access are
+//! priority entries: they are placed ahead of normal entries and may use the
+//! reserved slots.
+//!
+//! Party priority is never taken on the client's word: a player in the arena
+//! issues a single-use invite code (`/invite`) and hands it to a friend, who
+//! presents it in their join request.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::config::JoinQueueConfig;
+use crate::game::state::PlayerId;
+
+/// Window of recent admissions used for ETA estimates
+const ADMISSION_WINDOW: Duration = Duration::from_secs(300);
+
+/// How long a party invite code stays valid
+pub const PARTY_INVITE_TTL: Duration = Duration::from_secs(600);
+
+/// Outstanding invite codes per inviting player (roughly a full party)
+const MAX_PARTY_INVITES: usize = 4;
+
+/// Queue ticket handed to a waiting connection
+pub type TicketId = u64;
+
+/// Single-use party invite code
+pub type PartyInvite = uuid::Uuid;
+
+/// Why a join gets (or doesn't get) priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinPriority {
+    /// Returning player presenting a recent session token
+    Reconnect,
+    /// Joining a party member who is already in the arena
+    Party,
+    /// Role with reserved-slot access (vip and above)
+    Vip,
+    /// Everyone else
+    Normal,
+}
+
+impl JoinPriority {
+    /// Priority joins may use reserved slots and skip ahead of normal entries
+    pub fn is_priority(&self) -> bool {
+        !matches!(self, JoinPriority::Normal)
+    }
+}
+
+/// Result of polling a ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueStatus {
+    /// Ticket reached the front and a slot is free; it has been removed
+    Admit,
+    /// Still waiting (1-based position, estimated seconds until admission)
+    Waiting { position: u32, eta_secs: u32 },
+    /// Ticket is not in the queue
+    Gone,
+}
+
+#[derive(Debug)]
+struct QueueEntry {
+    ticket: TicketId,
+    priority: JoinPriority,
+}
+
+/// Ordered join queue plus the session-token bookkeeping for reconnects
+#[derive(Debug)]
+pub struct JoinQueue {
+    config: JoinQueueConfig,
+    /// Priority entries first, FIFO within each class
+    entries: VecDeque<QueueEntry>,
+    next_ticket: TicketId,
+    /// Recent admissions (direct or queued) for ETA estimates
+    admissions: VecDeque<Instant>,
+    /// Session tokens of players currently in the arena
+    active_tokens: HashMap<PlayerId, Vec<u8>>,
+    /// Session tokens of players who left, with departure time
+    departed_tokens: HashMap<Vec<u8>, Instant>,
+    /// Outstanding party invite codes: inviting player and issue time
+    party_invites: HashMap<PartyInvite, (PlayerId, Instant)>,
+}
+
+impl JoinQueue {
+    pub fn new(config: JoinQueueConfig) -> Self {
+        Self {
+            config,
+            entries: VecDeque::new(),
+            next_ticket: 1,
+            admissions: VecDeque::new(),
+            active_tokens: HashMap::new(),
+            departed_tokens: HashMap::new(),
+            party_invites: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &JoinQueueConfig {
+        &self.config
+    }
+
+    /// Number of queued connections
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether a new join of this priority must queue behind existing entries
+    /// even if a slot is free (no jumping ahead of people already waiting)
+    pub fn has_waiting_ahead(&self, priority: JoinPriority) -> bool {
+        if priority.is_priority() {
+            self.entries.iter().any(|e| e.priority.is_priority())
+        } else {
+            !self.is_empty()
+        }
+    }
+
+    /// Add a connection to the queue. Returns None when disabled or full.
+    pub fn enqueue(&mut self, priority: JoinPriority) -> Option<TicketId> {
+        if !self.config.enabled || self.entries.len() >= self.config.max_len {
+            return None;
+        }
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+
+        let entry = QueueEntry { ticket, priority };
+        if priority.is_priority() {
+            let idx = self.entries.iter().take_while(|e| e.priority.is_priority()).count();
+            self.entries.insert(idx, entry);
+        } else {
+            self.entries.push_back(entry);
+        }
+        Some(ticket)
+    }
+
+    /// Drop a ticket (connection closed while waiting)
+    pub fn remove(&mut self, ticket: TicketId) {
+        self.entries.retain(|e| e.ticket != ticket);
+    }
+
+    /// 1-based queue position of a ticket
+    pub fn position(&self, ticket: TicketId) -> Option<u32> {
+        self.entries
+            .iter()
+            .position(|e| e.ticket == ticket)
+            .map(|idx| idx as u32 + 1)
+    }
+
+    /// Poll a ticket. `has_slot` reports whether a slot is open for a given priority.
+    /// Only the front of the queue is ever admitted.
+    pub fn poll(
+        &mut self,
+        ticket: TicketId,
+        now: Instant,
+        has_slot: impl Fn(JoinPriority) -> bool,
+    ) -> QueueStatus {
+        let Some(position) = self.position(ticket) else {
+            return QueueStatus::Gone;
+        };
+        if position == 1 && has_slot(self.entries[0].priority) {
+            self.entries.pop_front();
+            self.record_admission(now);
+            return QueueStatus::Admit;
+        }
+        QueueStatus::Waiting {
+            position,
+            eta_secs: self.eta_secs(position, now),
+        }
+    }
+
+    /// Note a player admission (feeds the ETA estimate)
+    pub fn record_admission(&mut self, now: Instant) {
+        self.admissions.push_back(now);
+        while self
+            .admissions
+            .front()
+            .is_some_and(|&t| now.duration_since(t) > ADMISSION_WINDOW)
+        {
+            self.admissions.pop_front();
+        }
+    }
+
+    /// Estimated seconds until a given position is admitted, based on the
+    /// recent admission rate (or the configured default without history)
+    pub fn eta_secs(&self, position: u32, now: Instant) -> u32 {
+        let recent = self
+            .admissions
+            .iter()
+            .filter(|&&t| now.duration_since(t) <= ADMISSION_WINDOW)
+            .count();
+        let secs_per_slot = if recent >= 2 {
+            ADMISSION_WINDOW.as_secs_f32() / recent as f32
+        } else {
+            self.config.default_secs_per_slot as f32
+        };
+        (position as f32 * secs_per_slot).ceil() as u32
+    }
+
+    /// Remember the session token issued to an admitted player
+    pub fn remember_token(&mut self, player_id: PlayerId, token: Vec<u8>) {
+        self.active_tokens.insert(player_id, token);
+    }
+
+    /// Player left: their token grants reconnect priority for the grace period
+    /// and their party invites lapse
+    pub fn mark_departed(&mut self, player_id: PlayerId, now: Instant) {
+        self.party_invites.retain(|_, (inviter, _)| *inviter != player_id);
+        let grace = Duration::from_secs(self.config.reconnect_grace_secs);
+        self.departed_tokens.retain(|_, left| now.duration_since(*left) <= grace);
+        if let Some(token) = self.active_tokens.remove(&player_id) {
+            if grace > Duration::ZERO {
+                self.departed_tokens.insert(token, now);
+            }
+        }
+    }
+
+    /// Consume a returning player's token. True if it was issued to a player
+    /// who left within the grace period.
+    pub fn claim_reconnect(&mut self, token: &[u8], now: Instant) -> bool {
+        let grace = Duration::from_secs(self.config.reconnect_grace_secs);
+        self.departed_tokens
+            .remove(token)
+            .is_some_and(|left| now.duration_since(left) <= grace)
+    }
+
+    /// Issue a party invite code for a player in the arena. Issuing beyond
+    /// the per-player limit replaces their oldest outstanding code.
+    pub fn issue_party_invite(&mut self, inviter: PlayerId, now: Instant) -> PartyInvite {
+        self.party_invites.retain(|_, (_, issued)| now.duration_since(*issued) <= PARTY_INVITE_TTL);
+        let mut outstanding: Vec<(PartyInvite, Instant)> = self
+            .party_invites
+            .iter()
+            .filter(|(_, (id, _))| *id == inviter)
+            .map(|(&code, &(_, issued))| (code, issued))
+            .collect();
+        if outstanding.len() >= MAX_PARTY_INVITES {
+            outstanding.sort_by_key(|&(_, issued)| issued);
+            self.party_invites.remove(&outstanding[0].0);
+        }
+        let code = uuid::Uuid::new_v4();
+        self.party_invites.insert(code, (inviter, now));
+        code
+    }
+
+    /// Consume a party invite code. Returns the inviting player if the code
+    /// was issued within its lifetime.
+    pub fn claim_party_invite(&mut self, code: PartyInvite, now: Instant) -> Option<PlayerId> {
+        self.party_invites
+            .remove(&code)
+            .filter(|&(_, issued)| now.duration_since(issued) <= PARTY_INVITE_TTL)
+            .map(|(inviter, _)| inviter)
+    }
+}
+
+impl Default for JoinQueue {
+    fn default() -> Self {
+        Self::new(JoinQueueConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_entries_skip_normal_but_stay_fifo() {
+        let mut queue = JoinQueue::default();
+        let a = queue.enqueue(JoinPriority::Normal).unwrap();
+        let b = queue.enqueue(JoinPriority::Party).unwrap();
+        let c = queue.enqueue(JoinPriority::Reconnect).unwrap();
+
+        assert_eq!(queue.position(b), Some(1));
+        assert_eq!(queue.position(c), Some(2));
+        assert_eq!(queue.position(a), Some(3));
+        assert!(queue.has_waiting_ahead(JoinPriority::Party));
+
+        queue.remove(b);
+        queue.remove(c);
+        assert!(!queue.has_waiting_ahead(JoinPriority::Reconnect));
+        assert!(queue.has_waiting_ahead(JoinPriority::Normal));
+    }
+
+    #[test]
+    fn test_poll_admits_only_front_with_slot() {
+        let mut queue = JoinQueue::default();
+        let now = Instant::now();
+        let a = queue.enqueue(JoinPriority::Normal).unwrap();
+        let b = queue.enqueue(JoinPriority::Normal).unwrap();
+
+        // Only reserved slots open: normal entries wait
+        let reserved_only = |p: JoinPriority| p.is_priority();
+        assert!(matches!(queue.poll(a, now, reserved_only), QueueStatus::Waiting { position: 1, .. }));
+
+        assert_eq!(queue.poll(b, now, |_| true), QueueStatus::Waiting { position: 2, eta_secs: 30 });
+        assert_eq!(queue.poll(a, now, |_| true), QueueStatus::Admit);
+        assert_eq!(queue.poll(a, now, |_| true), QueueStatus::Gone);
+        assert_eq!(queue.position(b), Some(1));
+    }
+
+    #[test]
+    fn test_eta_uses_recent_admission_rate() {
+        let mut queue = JoinQueue::default();
+        let now = Instant::now();
+        for _ in 0..10 {
+            queue.record_admission(now);
+        }
+        // 10 admissions per 300s window = 30s per slot
+        assert_eq!(queue.eta_secs(2, now), 60);
+    }
+
+    #[test]
+    fn test_reconnect_token_within_grace() {
+        let mut queue = JoinQueue::default();
+        let now = Instant::now();
+        let player = uuid::Uuid::new_v4();
+        queue.remember_token(player, vec![1, 2, 3]);
+
+        // Still in game: token is not claimable
+        assert!(!queue.claim_reconnect(&[1, 2, 3], now));
+
+        queue.mark_departed(player, now);
+        assert!(!queue.claim_reconnect(&[9], now));
+        assert!(queue.claim_reconnect(&[1, 2, 3], now + Duration::from_secs(60)));
+        // Single use
+        assert!(!queue.claim_reconnect(&[1, 2, 3], now));
+
+        let late = uuid::Uuid::new_v4();
+        queue.remember_token(late, vec![4]);
+        queue.mark_departed(late, now);
+        assert!(!queue.claim_reconnect(&[4], now + Duration::from_secs(121)));
+    }
+
+    #[test]
+    fn test_party_invites_are_single_use_and_lapse() {
+        let mut queue = JoinQueue::default();
+        let now = Instant::now();
+        let leader = uuid::Uuid::new_v4();
+
+        // Codes are only ever issued by the server
+        assert_eq!(queue.claim_party_invite(leader, now), None);
+
+        let code = queue.issue_party_invite(leader, now);
+        assert_eq!(queue.claim_party_invite(code, now), Some(leader));
+        assert_eq!(queue.claim_party_invite(code, now), None);
+
+        let stale = queue.issue_party_invite(leader, now);
+        assert_eq!(queue.claim_party_invite(stale, now + PARTY_INVITE_TTL + Duration::from_secs(1)), None);
+
+        // Oldest code is replaced past the per-player limit
+        let codes: Vec<_> = (0..=MAX_PARTY_INVITES as u64)
+            .map(|i| queue.issue_party_invite(leader, now + Duration::from_secs(i)))
+            .collect();
+        assert_eq!(queue.claim_party_invite(codes[0], now), None);
+        assert_eq!(queue.claim_party_invite(codes[1], now), Some(leader));
+
+        // Leaving revokes the rest
+        queue.mark_departed(leader, now);
+        assert_eq!(queue.claim_party_invite(codes[2], now), None);
+    }
+
+    #[test]
+    fn test_disabled_or_full_queue_refuses() {
+        let mut disabled = JoinQueue::new(JoinQueueConfig { enabled: false, ..Default::default() });
+        assert!(disabled.enqueue(JoinPriority::Normal).is_none());
+
+        let mut full = JoinQueue::new(JoinQueueConfig { max_len: 1, ..Default::default() });
+        assert!(full.enqueue(JoinPriority::Normal).is_some());
+        assert!(full.enqueue(JoinPriority::Reconnect).is_none());
+    }
+}
@@ -0,0 +1,210 @@
+//! Kill feed
+//!
+//! Every death becomes one `KillFeed` event: who died, who (if anyone) gets
+//! the credit, the cause, and the line as localizable text, so clients no
+//! longer piece deaths together from snapshots. Kills are annotated by rule:
+//! a second or third kill within `MULTI_KILL_WINDOW_TICKS` of the previous is
+//! a double or triple kill (more are counted), every `STREAK_MILESTONE`th kill
+//! without dying is called out, and killing someone on a streak of at least
+//! `SHUTDOWN_STREAK` is a shutdown.
+//!
+//! Streaks live here rather than in the game state: they only matter for the
+//! feed, and are forgotten when a match starts.
+
+use std::collections::HashMap;
+
+use crate::game::constants::physics::TICK_RATE;
+use crate::game::state::PlayerId;
+use crate::net::i18n::{keys, LocalizedText};
+use crate::net::protocol::{GameSnapshot, DeathCause, KillFeedEntry};
+use crate::util::vec2::Vec2;
+
+/// Ticks between kills that still count toward a multi-kill
+const MULTI_KILL_WINDOW_TICKS: u64 = TICK_RATE as u64 * 4;
+
+/// Streak lengths called out in the feed (every multiple)
+const STREAK_MILESTONE: u32 = 5;
+
+/// Shortest streak whose end is a shutdown
+const SHUTDOWN_STREAK: u32 = 3;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Streak {
+    /// Kills since the player last died
+    kills: u32,
+    /// Kills in the current multi-kill run
+    run: u32,
+    last_kill_tick: u64,
+}
+
+/// Builds kill feed lines and keeps the streaks they're annotated with
+#[derive(Debug, Default)]
+pub struct KillFeed {
+    streaks: HashMap<PlayerId, Streak>,
+}
+
+impl KillFeed {
+    /// Forget every streak (a new match started)
+    pub fn reset(&mut self) {
+        self.streaks.clear();
+    }
+
+    /// The feed line for a death at `tick` and where the victim died. None
+    /// when the victim isn't in `snapshot` (already left).
+    pub fn record(
+        &mut self,
+        tick: u64,
+        victim_id: PlayerId,
+        killer_id: Option<PlayerId>,
+        cause: DeathCause,
+        snapshot: &GameSnapshot,
+    ) -> Option<(KillFeedEntry, Vec2)> {
+        let victim = snapshot.players.iter().find(|p| p.id == victim_id)?;
+        let killer = killer_id.filter(|&id| id != victim_id).and_then(|id| snapshot.players.iter().find(|p| p.id == id));
+        let ended = self.streaks.remove(&victim_id).map_or(0, |streak| streak.kills);
+
+        let mut annotations = Vec::new();
+        if let Some(killer) = killer {
+            let streak = self.streaks.entry(killer.id).or_default();
+            let chained = streak.run > 0 && tick.saturating_sub(streak.last_kill_tick) <= MULTI_KILL_WINDOW_TICKS;
+            streak.run = if chained { streak.run + 1 } else { 1 };
+            streak.kills += 1;
+            streak.last_kill_tick = tick;
+
+            match streak.run {
+                2 => annotations.push(LocalizedText::new(keys::KILL_DOUBLE)),
+                3 => annotations.push(LocalizedText::new(keys::KILL_TRIPLE)),
+                run if run > 3 => annotations.push(LocalizedText::new(keys::KILL_MULTI).with("count", run)),
+                _ => {}
+            }
+            if streak.kills % STREAK_MILESTONE == 0 {
+                let text = LocalizedText::new(keys::KILL_STREAK).with("name", &killer.name).with("count", streak.kills);
+                annotations.push(text);
+            }
+            if ended >= SHUTDOWN_STREAK {
+                annotations.push(LocalizedText::new(keys::KILL_SHUTDOWN).with("name", &victim.name).with("count", ended));
+            }
+        }
+
+        let text = match (cause, killer) {
+            (DeathCause::Well, _) => LocalizedText::new(keys::KILL_WELL),
+            (DeathCause::Boundary, _) => LocalizedText::new(keys::KILL_BOUNDARY),
+            (DeathCause::Crush, Some(killer)) => LocalizedText::new(keys::KILL_CRUSH).with("killer", &killer.name),
+            (DeathCause::Projectile, Some(killer)) => {
+                LocalizedText::new(keys::KILL_PROJECTILE).with("killer", &killer.name)
+            }
+            // The killer left the match in the same tick
+            (DeathCause::Disconnect, _) | (_, None) => LocalizedText::new(keys::KILL_DIED),
+        };
+
+        let entry = KillFeedEntry {
+            killer_id: killer.map(|k| k.id),
+            victim_id,
+            killer_name: killer.map(|k| k.name.clone()),
+            victim_name: victim.name.clone(),
+            cause,
+            text: text.with("victim", &victim.name),
+            annotations,
+        };
+        Some((entry, victim.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::MatchPhase;
+    use crate::net::protocol::{player_flags, PlayerSnapshot};
+    use uuid::Uuid;
+
+    fn player(name: &str) -> PlayerSnapshot {
+        PlayerSnapshot {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            position: Vec2::new(10.0, 20.0),
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass: 100.0,
+            flags: player_flags::ALIVE,
+            kills: 0,
+            deaths: 0,
+            color_index: 0,
+            spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
+        }
+    }
+
+    fn snapshot(players: Vec<PlayerSnapshot>) -> GameSnapshot {
+        GameSnapshot {
+            tick: 1,
+            match_phase: MatchPhase::Playing,
+            match_time: 0.0,
+            countdown: 0.0,
+            players,
+            projectiles: vec![],
+            debris: vec![],
+            arena_collapse_phase: 0,
+            arena_safe_radius: 1000.0,
+            arena_scale: 1.0,
+            gravity_wells: vec![],
+            asteroids: vec![],
+            total_players: 0,
+            total_alive: 0,
+            density_grid: vec![],
+            echo_client_time: 0,
+            interp_delay_ms: 0,
+            ai_status: None,
+        }
+    }
+
+    fn keys_of(entry: &KillFeedEntry) -> Vec<&str> {
+        entry.annotations.iter().map(|a| a.key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_death_without_killer() {
+        let victim = player("Bob");
+        let snapshot = snapshot(vec![victim.clone()]);
+        let mut feed = KillFeed::default();
+
+        let (entry, position) = feed.record(1, victim.id, None, DeathCause::Well, &snapshot).unwrap();
+        assert_eq!((entry.killer_id, entry.cause), (None, DeathCause::Well));
+        assert_eq!(entry.text.english(), "Bob fell into a gravity well");
+        assert!(entry.annotations.is_empty());
+        assert_eq!(position, Vec2::new(10.0, 20.0));
+        assert!(feed.record(1, Uuid::new_v4(), None, DeathCause::Boundary, &snapshot).is_none());
+    }
+
+    #[test]
+    fn test_multi_kills_streaks_and_shutdown() {
+        let alice = player("Alice");
+        let victims: Vec<_> = (0..5).map(|i| player(&format!("Bot{}", i))).collect();
+        let bob = player("Bob");
+        let mut players = vec![alice.clone(), bob.clone()];
+        players.extend(victims.iter().cloned());
+        let snapshot = snapshot(players);
+        let mut feed = KillFeed::default();
+
+        let kill = |feed: &mut KillFeed, tick, victim: &PlayerSnapshot, killer: &PlayerSnapshot| {
+            feed.record(tick, victim.id, Some(killer.id), DeathCause::Crush, &snapshot).unwrap().0
+        };
+        assert!(kill(&mut feed, 100, &victims[0], &alice).annotations.is_empty());
+        let double = kill(&mut feed, 110, &victims[1], &alice);
+        assert_eq!(double.text.english(), "Alice crushed Bot1");
+        assert_eq!(keys_of(&double), vec![keys::KILL_DOUBLE]);
+        assert_eq!(keys_of(&kill(&mut feed, 120, &victims[2], &alice)), vec![keys::KILL_TRIPLE]);
+        // Too late to chain, but still on a streak
+        assert!(kill(&mut feed, 1000, &victims[3], &alice).annotations.is_empty());
+        let fifth = kill(&mut feed, 2000, &victims[4], &alice);
+        assert_eq!(fifth.annotations[0].english(), "Alice is on a 5 kill streak");
+
+        let shutdown = kill(&mut feed, 3000, &alice, &bob);
+        assert_eq!(keys_of(&shutdown), vec![keys::KILL_SHUTDOWN]);
+        assert_eq!(shutdown.annotations[0].english(), "Shutdown! Alice's 5 kill streak ended");
+        // Alice's streak starts over
+        assert!(kill(&mut feed, 3001, &bob, &alice).annotations.is_empty());
+    }
+}
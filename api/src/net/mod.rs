@@ -6,5 +6,31 @@ pub mod dos_protection;
 pub mod transport;
 pub mod connection;
 pub mod game_session;
+pub mod join_queue;
+pub mod moderation;
+pub mod account;
+pub mod i18n;
+pub mod reports;
+pub mod snapshot_history;
+pub mod interp_delay;
+pub mod send_pacing;
+pub mod snapshot_rate;
 pub mod aoi;
+pub mod aoi_index;
+pub mod broadcast;
+pub mod state_view;
+pub mod world_stream;
+pub mod region_hint;
+pub mod kill_feed;
 pub mod delta;
+pub mod egress;
+pub mod netsim;
+pub mod capture;
+pub mod snapshot_crypto;
+pub mod spectator_delay;
+pub mod hibernation;
+pub mod replay;
+pub mod highlights;
+pub mod presence;
+#[cfg(feature = "lobby")]
+pub mod room_link;
@@ -0,0 +1,417 @@
+//! Player chat and moderator chat commands
+//!
+//! Chat lines starting with `/` are parsed as commands server-side:
+//! - `/kick <player> [reason]`
+//! - `/mute <player> [duration]` (e.g. `30s`, `10m`, `2h`; 0 unmutes)
+//! - `/tp-spectate <player>` (spectators only: follow a player)
+//! - `/announce <text>`
+//! - `/pause [reason]` and `/resume` (referee match control)
+//! - `/reviews` and `/resolve <player> upheld|dismissed` (report review queue)
+//!
+//! Each command maps to a roles [`Permission`]; every attempt, allowed or
+//! not, is written to the audit log.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::game::state::PlayerId;
+use crate::net::account::AccountId;
+use crate::net::i18n::{keys, LocalizedText};
+use crate::roles::{Permission, Role};
+
+/// Longest mute a command may set
+const MAX_MUTE: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// A parsed moderator command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatCommand {
+    Kick { target: String, reason: Option<String> },
+    /// `duration` of None uses the configured default
+    Mute { target: String, duration: Option<Duration> },
+    TpSpectate { target: String },
+    Announce { text: String },
+    Pause { reason: Option<String> },
+    Resume,
+    /// List players awaiting review
+    Reviews,
+    /// Close a review case
+    Resolve { target: String, upheld: bool },
+    /// Issue a party invite code (see `join_queue`)
+    Invite,
+}
+
+/// Why a command line could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CommandError {
+    #[error("Unknown command /{0}")]
+    Unknown(String),
+    #[error("Usage: {0}")]
+    Usage(&'static str),
+    #[error("Invalid duration '{0}' (use e.g. 30s, 10m, 2h)")]
+    InvalidDuration(String),
+}
+
+impl CommandError {
+    /// The error as a localizable message for the sender
+    pub fn message(&self) -> LocalizedText {
+        match self {
+            CommandError::Unknown(command) => LocalizedText::new(keys::COMMAND_UNKNOWN).with("command", command),
+            CommandError::Usage(usage) => LocalizedText::new(keys::COMMAND_USAGE).with("usage", usage),
+            CommandError::InvalidDuration(duration) => {
+                LocalizedText::new(keys::COMMAND_INVALID_DURATION).with("duration", duration)
+            }
+        }
+    }
+}
+
+impl ChatCommand {
+    /// Parse a chat line. Returns None if the line is not a command.
+    pub fn parse(line: &str) -> Option<Result<Self, CommandError>> {
+        let body = line.trim().strip_prefix('/')?;
+        let (name, rest) = match body.split_once(char::is_whitespace) {
+            Some((name, rest)) => (name, rest.trim()),
+            None => (body, ""),
+        };
+        let mut args = rest.split_whitespace();
+
+        let command = match name.to_lowercase().as_str() {
+            "kick" => match args.next() {
+                Some(target) => {
+                    let reason = args.collect::<Vec<_>>().join(" ");
+                    Ok(ChatCommand::Kick {
+                        target: target.to_string(),
+                        reason: (!reason.is_empty()).then_some(reason),
+                    })
+                }
+                None => Err(CommandError::Usage("/kick <player> [reason]")),
+            },
+            "mute" => match (args.next(), args.next()) {
+                (Some(target), duration) => match duration.map(parse_duration) {
+                    Some(None) => Err(CommandError::InvalidDuration(duration.unwrap_or_default().to_string())),
+                    parsed => Ok(ChatCommand::Mute {
+                        target: target.to_string(),
+                        duration: parsed.flatten(),
+                    }),
+                },
+                (None, _) => Err(CommandError::Usage("/mute <player> [duration]")),
+            },
+            "tp-spectate" => match args.next() {
+                Some(target) => Ok(ChatCommand::TpSpectate { target: target.to_string() }),
+                None => Err(CommandError::Usage("/tp-spectate <player>")),
+            },
+            "announce" if !rest.is_empty() => Ok(ChatCommand::Announce { text: rest.to_string() }),
+            "announce" => Err(CommandError::Usage("/announce <text>")),
+            "pause" => Ok(ChatCommand::Pause { reason: (!rest.is_empty()).then(|| rest.to_string()) }),
+            "resume" => Ok(ChatCommand::Resume),
+            "reviews" => Ok(ChatCommand::Reviews),
+            "invite" => Ok(ChatCommand::Invite),
+            "resolve" => match (args.next(), args.next().map(str::to_lowercase).as_deref()) {
+                (Some(target), Some("upheld")) => Ok(ChatCommand::Resolve { target: target.to_string(), upheld: true }),
+                (Some(target), Some("dismissed")) => {
+                    Ok(ChatCommand::Resolve { target: target.to_string(), upheld: false })
+                }
+                _ => Err(CommandError::Usage("/resolve <player> upheld|dismissed")),
+            },
+            other => Err(CommandError::Unknown(other.to_string())),
+        };
+        Some(command)
+    }
+
+    /// Permission required to run this command
+    pub fn permission(&self) -> Permission {
+        match self {
+            ChatCommand::Kick { .. }
+            | ChatCommand::Mute { .. }
+            | ChatCommand::Reviews
+            | ChatCommand::Resolve { .. } => Permission::ModerationCommands,
+            ChatCommand::TpSpectate { .. } | ChatCommand::Announce { .. } => Permission::CasterTools,
+            ChatCommand::Pause { .. } | ChatCommand::Resume => Permission::MatchControl,
+            ChatCommand::Invite => Permission::InviteParty,
+        }
+    }
+
+    /// Command name for logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChatCommand::Kick { .. } => "kick",
+            ChatCommand::Mute { .. } => "mute",
+            ChatCommand::TpSpectate { .. } => "tp-spectate",
+            ChatCommand::Announce { .. } => "announce",
+            ChatCommand::Pause { .. } => "pause",
+            ChatCommand::Resume => "resume",
+            ChatCommand::Reviews => "reviews",
+            ChatCommand::Resolve { .. } => "resolve",
+            ChatCommand::Invite => "invite",
+        }
+    }
+}
+
+/// Parse a duration like `45s`, `10m`, `2h`, `1d` (bare numbers are minutes)
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: u64 = number.parse().ok()?;
+    let secs = match unit {
+        "s" => value,
+        "" | "m" => value.checked_mul(60)?,
+        "h" => value.checked_mul(3600)?,
+        "d" => value.checked_mul(86_400)?,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs).min(MAX_MUTE))
+}
+
+/// Short human-readable duration (`45s`, `10m`, `2h`)
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 && secs % 3600 == 0 {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 && secs % 60 == 0 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Trim, strip control characters and cap length of a chat line
+pub fn sanitize_chat(text: &str, max_len: usize) -> Option<String> {
+    let cleaned: String = text
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if cleaned.is_empty() {
+        return None;
+    }
+    Some(cleaned.chars().take(max_len).collect())
+}
+
+/// Active chat mutes, by account so they outlast the connection
+#[derive(Debug, Default)]
+pub struct MuteList {
+    until: HashMap<AccountId, Instant>,
+}
+
+impl MuteList {
+    /// Mute an account (a zero duration unmutes), dropping expired mutes
+    pub fn mute(&mut self, account: AccountId, duration: Duration, now: Instant) {
+        self.until.retain(|_, until| *until > now);
+        if duration.is_zero() {
+            self.until.remove(&account);
+        } else {
+            self.until.insert(account, now + duration);
+        }
+    }
+
+    /// Time left on an account's mute, if muted
+    pub fn remaining(&mut self, account: AccountId, now: Instant) -> Option<Duration> {
+        let until = *self.until.get(&account)?;
+        if until <= now {
+            self.until.remove(&account);
+            return None;
+        }
+        Some(until - now)
+    }
+}
+
+/// One audit log entry
+#[derive(Debug, Serialize)]
+pub struct AuditEntry<'a> {
+    /// Unix time in milliseconds
+    pub timestamp_ms: u64,
+    pub actor_id: PlayerId,
+    /// Display name after the configured privacy redaction
+    pub actor_name: &'a str,
+    pub role: Role,
+    pub command: &'a str,
+    pub allowed: bool,
+    pub outcome: &'a str,
+}
+
+/// Moderation audit log: always traced, optionally appended to a JSON-lines file
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    file: Option<Mutex<File>>,
+    path: Option<String>,
+}
+
+impl AuditLog {
+    pub fn new(path: Option<&str>) -> Self {
+        let file = path.and_then(|path| {
+            match OpenOptions::new().create(true).append(true).open(path) {
+                Ok(file) => Some(Mutex::new(file)),
+                Err(e) => {
+                    warn!("Failed to open moderation audit log {}: {}", path, e);
+                    None
+                }
+            }
+        });
+        Self { path: file.as_ref().and(path.map(str::to_string)), file }
+    }
+
+    /// Drop file entries older than `cutoff_ms` (Unix milliseconds), returning
+    /// how many were dropped. Lines without a readable timestamp go too.
+    pub fn purge_before(&self, cutoff_ms: u64) -> Result<usize, String> {
+        let (Some(file), Some(path)) = (&self.file, &self.path) else {
+            return Ok(0);
+        };
+        // Held while rewriting so no entry is appended in between
+        let _file = file.lock();
+        let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read moderation audit log: {}", e))?;
+        let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+        let kept: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|line| {
+                serde_json::from_str::<serde_json::Value>(line)
+                    .ok()
+                    .and_then(|entry| entry["timestamp_ms"].as_u64())
+                    .is_some_and(|timestamp| timestamp >= cutoff_ms)
+            })
+            .collect();
+        let purged = lines.len() - kept.len();
+        if purged > 0 {
+            // The log is opened for appending, so later entries land after what is kept
+            let rewritten: String = kept.iter().map(|line| format!("{}\n", line)).collect();
+            fs::write(path, rewritten).map_err(|e| format!("Failed to rewrite moderation audit log: {}", e))?;
+        }
+        Ok(purged)
+    }
+
+    pub fn record(&self, entry: AuditEntry<'_>) {
+        info!(
+            target: "audit",
+            "{} ({:?}) /{} allowed={} -> {}",
+            entry.actor_name, entry.role, entry.command, entry.allowed, entry.outcome
+        );
+        let Some(file) = &self.file else { return };
+        match serde_json::to_string(&entry) {
+            Ok(line) => {
+                if let Err(e) = writeln!(file.lock(), "{}", line) {
+                    warn!("Failed to write moderation audit log: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to encode audit entry: {}", e),
+        }
+    }
+}
+
+/// Current Unix time in milliseconds (audit timestamps)
+pub fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roles::Role;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(ChatCommand::parse("hello there"), None);
+        assert_eq!(
+            ChatCommand::parse("/kick Bob being rude"),
+            Some(Ok(ChatCommand::Kick { target: "Bob".to_string(), reason: Some("being rude".to_string()) }))
+        );
+        assert_eq!(
+            ChatCommand::parse("/MUTE Bob 10m"),
+            Some(Ok(ChatCommand::Mute { target: "Bob".to_string(), duration: Some(Duration::from_secs(600)) }))
+        );
+        assert_eq!(
+            ChatCommand::parse("/mute Bob"),
+            Some(Ok(ChatCommand::Mute { target: "Bob".to_string(), duration: None }))
+        );
+        assert_eq!(
+            ChatCommand::parse("/announce  Finals start in 5 minutes "),
+            Some(Ok(ChatCommand::Announce { text: "Finals start in 5 minutes".to_string() }))
+        );
+        assert_eq!(
+            ChatCommand::parse("/tp-spectate Alice"),
+            Some(Ok(ChatCommand::TpSpectate { target: "Alice".to_string() }))
+        );
+        assert_eq!(
+            ChatCommand::parse("/pause Player disconnected"),
+            Some(Ok(ChatCommand::Pause { reason: Some("Player disconnected".to_string()) }))
+        );
+        assert_eq!(ChatCommand::parse("/pause"), Some(Ok(ChatCommand::Pause { reason: None })));
+        assert_eq!(ChatCommand::parse("/resume"), Some(Ok(ChatCommand::Resume)));
+        assert_eq!(ChatCommand::parse("/reviews"), Some(Ok(ChatCommand::Reviews)));
+        assert_eq!(ChatCommand::parse("/invite"), Some(Ok(ChatCommand::Invite)));
+        assert_eq!(
+            ChatCommand::parse("/resolve Bob Dismissed"),
+            Some(Ok(ChatCommand::Resolve { target: "Bob".to_string(), upheld: false }))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(ChatCommand::parse("/kick"), Some(Err(CommandError::Usage("/kick <player> [reason]"))));
+        assert_eq!(ChatCommand::parse("/announce"), Some(Err(CommandError::Usage("/announce <text>"))));
+        assert_eq!(ChatCommand::parse("/ban Bob"), Some(Err(CommandError::Unknown("ban".to_string()))));
+        assert_eq!(
+            ChatCommand::parse("/resolve Bob maybe"),
+            Some(Err(CommandError::Usage("/resolve <player> upheld|dismissed")))
+        );
+        assert_eq!(
+            ChatCommand::parse("/mute Bob soon"),
+            Some(Err(CommandError::InvalidDuration("soon".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_command_permissions() {
+        let kick = ChatCommand::Kick { target: "x".to_string(), reason: None };
+        let announce = ChatCommand::Announce { text: "x".to_string() };
+        assert!(!Role::Vip.allows(kick.permission()));
+        assert!(Role::Moderator.allows(kick.permission()));
+        assert!(Role::Moderator.allows(announce.permission()));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("2H"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("0"), Some(Duration::ZERO));
+        assert_eq!(parse_duration("99999d"), Some(MAX_MUTE));
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("10w"), None);
+        assert_eq!(format_duration(Duration::from_secs(600)), "10m");
+        assert_eq!(format_duration(Duration::from_secs(7200)), "2h");
+        assert_eq!(format_duration(Duration::from_secs(90)), "90s");
+    }
+
+    #[test]
+    fn test_mute_list_expires() {
+        let mut mutes = MuteList::default();
+        let now = Instant::now();
+        let player = AccountId::generate();
+
+        mutes.mute(player, Duration::from_secs(60), now);
+        assert_eq!(mutes.remaining(player, now + Duration::from_secs(20)), Some(Duration::from_secs(40)));
+        assert_eq!(mutes.remaining(player, now + Duration::from_secs(60)), None);
+
+        mutes.mute(player, Duration::from_secs(60), now);
+        mutes.mute(player, Duration::ZERO, now);
+        assert_eq!(mutes.remaining(player, now), None);
+    }
+
+    #[test]
+    fn test_sanitize_chat() {
+        assert_eq!(sanitize_chat("  gg\u{0007}  wp ", 200).as_deref(), Some("gg wp"));
+        assert_eq!(sanitize_chat(" \n ", 200), None);
+        assert_eq!(sanitize_chat("abcdef", 3).as_deref(), Some("abc"));
+    }
+}
@@ -0,0 +1,327 @@
+//! Dev-mode network condition simulator
+//!
+//! Injects latency, jitter, loss and reordering per connection so delta
+//! compression, input dedup and reconciliation can be exercised against a
+//! bad network without external tooling. Outbound messages pass through a
+//! relay in front of the connection's writer task; inbound inputs are
+//! delayed or dropped before they reach the session. Conditions are set per
+//! player from the admin API (`POST /debug/netsim`) and only apply when the
+//! simulator is enabled with NETSIM_ENABLED.
+
+use std::collections::{BinaryHeap, HashMap};
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::NetSimConfig;
+use crate::game::state::PlayerId;
+
+/// Longest base delay or jitter accepted from the admin API (ms)
+pub const MAX_SIM_DELAY_MS: u32 = 5000;
+
+/// Simulated conditions for one connection (applied to both directions)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetConditions {
+    /// Base one-way delay (ms)
+    pub delay_ms: u32,
+    /// Uniform jitter added to or subtracted from the delay (ms)
+    pub jitter_ms: u32,
+    /// Probability that a message is dropped (0-1)
+    pub loss: f32,
+    /// Whether jittered messages may overtake earlier ones
+    pub reorder: bool,
+}
+
+/// What happens to one simulated message
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Drop,
+    Deliver(Duration),
+}
+
+/// Why conditions could not be applied
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum NetSimError {
+    #[error("network simulator is disabled (set NETSIM_ENABLED)")]
+    Disabled,
+    #[error("delay_ms and jitter_ms must be at most {MAX_SIM_DELAY_MS}")]
+    DelayOutOfRange,
+    #[error("loss must be between 0 and 1")]
+    LossOutOfRange,
+}
+
+impl NetConditions {
+    /// Check the conditions are within the accepted ranges
+    pub fn validate(&self) -> Result<(), NetSimError> {
+        if self.delay_ms > MAX_SIM_DELAY_MS || self.jitter_ms > MAX_SIM_DELAY_MS {
+            return Err(NetSimError::DelayOutOfRange);
+        }
+        if !(0.0..=1.0).contains(&self.loss) {
+            return Err(NetSimError::LossOutOfRange);
+        }
+        Ok(())
+    }
+
+    /// Decide whether one message is dropped and, if not, how long it is held
+    pub fn roll(&self, rng: &mut impl Rng) -> Verdict {
+        if self.loss > 0.0 && rng.gen::<f32>() < self.loss {
+            return Verdict::Drop;
+        }
+        let jitter = if self.jitter_ms > 0 {
+            rng.gen_range(-(self.jitter_ms as i64)..=self.jitter_ms as i64)
+        } else {
+            0
+        };
+        let delay_ms = (self.delay_ms as i64 + jitter).max(0) as u64;
+        Verdict::Deliver(Duration::from_millis(delay_ms))
+    }
+}
+
+/// Keeps release times monotonic unless reordering is allowed
+#[derive(Debug, Default)]
+struct ReleaseClock {
+    last: Option<Instant>,
+}
+
+impl ReleaseClock {
+    fn schedule(&mut self, now: Instant, delay: Duration, reorder: bool) -> Instant {
+        let mut release = now + delay;
+        if !reorder {
+            if let Some(last) = self.last {
+                release = release.max(last);
+            }
+        }
+        self.last = Some(self.last.map_or(release, |last| last.max(release)));
+        release
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConnectionSim {
+    conditions: NetConditions,
+    inbound: ReleaseClock,
+}
+
+/// Per-connection conditions shared by the transport, the writer relays and the admin API
+#[derive(Debug)]
+pub struct NetSimulator {
+    enabled: bool,
+    connections: Mutex<HashMap<PlayerId, ConnectionSim>>,
+}
+
+impl NetSimulator {
+    pub fn new(config: &NetSimConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            connections: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Apply conditions to a connection, replacing any previous ones
+    pub fn set(&self, player_id: PlayerId, conditions: NetConditions) -> Result<(), NetSimError> {
+        if !self.enabled {
+            return Err(NetSimError::Disabled);
+        }
+        conditions.validate()?;
+        self.connections.lock().entry(player_id).or_default().conditions = conditions;
+        Ok(())
+    }
+
+    /// Remove a connection's conditions (returns whether it had any)
+    pub fn clear(&self, player_id: PlayerId) -> bool {
+        self.connections.lock().remove(&player_id).is_some()
+    }
+
+    /// Current conditions for a connection (None = unaffected)
+    pub fn conditions(&self, player_id: PlayerId) -> Option<NetConditions> {
+        if !self.enabled {
+            return None;
+        }
+        self.connections.lock().get(&player_id).map(|c| c.conditions)
+    }
+
+    /// All connections with conditions applied
+    pub fn all(&self) -> Vec<(PlayerId, NetConditions)> {
+        self.connections.lock().iter().map(|(id, c)| (*id, c.conditions)).collect()
+    }
+
+    /// Decide the fate of one inbound message (None = no conditions, pass it straight through)
+    pub fn inbound(&self, player_id: PlayerId, now: Instant) -> Option<Verdict> {
+        if !self.enabled {
+            return None;
+        }
+        let mut connections = self.connections.lock();
+        let connection = connections.get_mut(&player_id)?;
+        let conditions = connection.conditions;
+        Some(match conditions.roll(&mut rand::thread_rng()) {
+            Verdict::Drop => Verdict::Drop,
+            Verdict::Deliver(delay) => {
+                let release = connection.inbound.schedule(now, delay, conditions.reorder);
+                Verdict::Deliver(release.saturating_duration_since(now))
+            }
+        })
+    }
+}
+
+struct Held<T> {
+    release: Instant,
+    seq: u64,
+    item: T,
+}
+
+impl<T> PartialEq for Held<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.release == other.release && self.seq == other.seq
+    }
+}
+
+impl<T> Eq for Held<T> {}
+
+impl<T> PartialOrd for Held<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Held<T> {
+    // Reversed so the max-heap pops the earliest release (ties in arrival order)
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.release.cmp(&self.release).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// Messages held back until their simulated release time
+pub struct DelayQueue<T> {
+    heap: BinaryHeap<Held<T>>,
+    clock: ReleaseClock,
+    next_seq: u64,
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            clock: ReleaseClock::default(),
+            next_seq: 0,
+        }
+    }
+}
+
+impl<T> DelayQueue<T> {
+    pub fn push(&mut self, item: T, now: Instant, delay: Duration, reorder: bool) {
+        let release = self.clock.schedule(now, delay, reorder);
+        self.heap.push(Held { release, seq: self.next_seq, item });
+        self.next_seq += 1;
+    }
+
+    /// Earliest pending release time
+    pub fn next_release(&self) -> Option<Instant> {
+        self.heap.peek().map(|held| held.release)
+    }
+
+    /// Pop the next message whose release time has passed
+    pub fn pop_due(&mut self, now: Instant) -> Option<T> {
+        if self.heap.peek()?.release > now {
+            return None;
+        }
+        self.heap.pop().map(|held| held.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn enabled() -> NetSimulator {
+        NetSimulator::new(&NetSimConfig { enabled: true })
+    }
+
+    #[test]
+    fn test_roll_loss_extremes() {
+        let mut rng = rand::thread_rng();
+        let lossless = NetConditions { delay_ms: 100, ..Default::default() };
+        let blackhole = NetConditions { loss: 1.0, ..Default::default() };
+        for _ in 0..100 {
+            assert_eq!(lossless.roll(&mut rng), Verdict::Deliver(Duration::from_millis(100)));
+            assert_eq!(blackhole.roll(&mut rng), Verdict::Drop);
+        }
+
+        let jittery = NetConditions { delay_ms: 50, jitter_ms: 80, ..Default::default() };
+        for _ in 0..100 {
+            match jittery.roll(&mut rng) {
+                Verdict::Deliver(delay) => assert!(delay <= Duration::from_millis(130)),
+                Verdict::Drop => panic!("lossless conditions dropped a message"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_delay_queue_keeps_order_without_reorder() {
+        let now = Instant::now();
+        let mut queue = DelayQueue::default();
+        queue.push(1, now, Duration::from_millis(200), false);
+        queue.push(2, now, Duration::from_millis(50), false);
+        queue.push(3, now, Duration::from_millis(250), false);
+
+        // The second message can't overtake the first
+        assert_eq!(queue.next_release(), Some(now + Duration::from_millis(200)));
+        assert_eq!(queue.pop_due(now + Duration::from_millis(100)), None);
+        let later = now + Duration::from_millis(300);
+        assert_eq!(
+            std::iter::from_fn(|| queue.pop_due(later)).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(queue.next_release(), None);
+    }
+
+    #[test]
+    fn test_delay_queue_reorders_when_allowed() {
+        let now = Instant::now();
+        let mut queue = DelayQueue::default();
+        queue.push(1, now, Duration::from_millis(200), true);
+        queue.push(2, now, Duration::from_millis(50), true);
+
+        assert_eq!(queue.pop_due(now + Duration::from_millis(60)), Some(2));
+        assert_eq!(queue.pop_due(now + Duration::from_millis(60)), None);
+        assert_eq!(queue.next_release(), Some(now + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_conditions_are_per_connection() {
+        let sim = enabled();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let lossy = NetConditions { loss: 1.0, ..Default::default() };
+        sim.set(a, lossy).unwrap();
+
+        assert_eq!(sim.conditions(a), Some(lossy));
+        assert_eq!(sim.conditions(b), None);
+        assert_eq!(sim.inbound(a, Instant::now()), Some(Verdict::Drop));
+        assert_eq!(sim.inbound(b, Instant::now()), None);
+
+        assert!(sim.clear(a));
+        assert!(!sim.clear(a));
+        assert_eq!(sim.inbound(a, Instant::now()), None);
+    }
+
+    #[test]
+    fn test_set_rejected_when_disabled_or_out_of_range() {
+        let disabled = NetSimulator::new(&NetSimConfig::default());
+        let id = Uuid::new_v4();
+        assert_eq!(disabled.set(id, NetConditions::default()), Err(NetSimError::Disabled));
+
+        let sim = enabled();
+        let too_slow = NetConditions { delay_ms: MAX_SIM_DELAY_MS + 1, ..Default::default() };
+        assert_eq!(sim.set(id, too_slow), Err(NetSimError::DelayOutOfRange));
+        let bad_loss = NetConditions { loss: 1.5, ..Default::default() };
+        assert_eq!(sim.set(id, bad_loss), Err(NetSimError::LossOutOfRange));
+        assert!(sim.all().is_empty());
+    }
+}
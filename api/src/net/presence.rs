@@ -0,0 +1,157 @@
+//! Player presence for rich presence relays
+//!
+//! A companion bot (e.g. a Discord bot) polls `/presence/<account id>` with a
+//! relay token to show what a player is doing ("in match, rank 3/120")
+//! without decoding snapshots. The account id is the player's server-issued
+//! account (see `net::account`), which the player links to the bot. Only
+//! connections that joined with the account, by holding its signed token,
+//! are found under it, so a name can't be used to claim someone's presence.
+//! Presence is read from published snapshots: the main arena's latest view
+//! and, with the lobby, each room's.
+
+use serde::Serialize;
+
+use crate::game::state::{MatchPhase, PlayerId};
+use crate::net::account::AccountId;
+use crate::net::protocol::GameSnapshot;
+
+/// Mode reported for the main arena (lobby rooms report their room kind)
+pub const MAIN_ARENA_MODE: &str = "standard";
+
+/// Arena name reported for the main arena
+pub const MAIN_ARENA_NAME: &str = "main";
+
+/// What a player is doing right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceStatus {
+    /// Not in any arena on this server
+    Offline,
+    /// In an arena waiting for the match to start
+    Waiting,
+    /// Alive in a running match
+    InMatch,
+    /// In a running match, waiting to respawn or spectating
+    Dead,
+    /// The match just ended
+    MatchOver,
+}
+
+/// `/presence` body
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Presence {
+    pub account: String,
+    pub status: PresenceStatus,
+    /// Ruleset of the player's arena (`standard`, `slow_mode`, `practice`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// Name of the player's arena
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arena: Option<String>,
+    /// Rank by mass among alive players (1 = heaviest), while alive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<u32>,
+    /// Alive players the rank is out of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub of: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kills: Option<u32>,
+    /// Seconds into the match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_time: Option<f32>,
+}
+
+impl Presence {
+    /// Presence of an account found in no arena
+    pub fn offline(account: AccountId) -> Self {
+        Self {
+            account: account.to_string(),
+            status: PresenceStatus::Offline,
+            mode: None,
+            arena: None,
+            rank: None,
+            of: None,
+            kills: None,
+            match_time: None,
+        }
+    }
+
+    /// Find the human player an account joined as in an arena's snapshot
+    pub fn find(snapshot: &GameSnapshot, account: AccountId, player_id: PlayerId, mode: &str, arena: &str) -> Option<Self> {
+        let player = snapshot.players.iter().find(|p| p.id == player_id && !p.is_bot() && !p.is_ghost())?;
+
+        let status = match snapshot.match_phase {
+            MatchPhase::Waiting | MatchPhase::Countdown => PresenceStatus::Waiting,
+            MatchPhase::Playing if player.alive() => PresenceStatus::InMatch,
+            MatchPhase::Playing => PresenceStatus::Dead,
+            MatchPhase::Ended => PresenceStatus::MatchOver,
+        };
+        let (rank, of) = if status == PresenceStatus::InMatch {
+            let alive = snapshot.players.iter().filter(|p| p.alive() && !p.is_ghost());
+            let (heavier, of) = alive.fold((0, 0), |(heavier, of), p| {
+                (heavier + u32::from(p.mass > player.mass), of + 1)
+            });
+            (Some(heavier + 1), Some(of))
+        } else {
+            (None, None)
+        };
+
+        Some(Self {
+            account: account.to_string(),
+            status,
+            mode: Some(mode.to_string()),
+            arena: Some(arena.to_string()),
+            rank,
+            of,
+            kills: Some(player.kills),
+            match_time: Some(snapshot.match_time),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::{GameState, Player};
+    use uuid::Uuid;
+
+    fn player(name: &str, mass: f32, is_bot: bool) -> Player {
+        let mut player = Player::new(Uuid::new_v4(), name.to_string(), is_bot, 0);
+        player.mass = mass;
+        player
+    }
+
+    #[test]
+    fn test_presence_ranks_by_mass_among_alive_players() {
+        let mut state = GameState::new();
+        state.match_state.phase = MatchPhase::Playing;
+        let (bot, nova, comet, mut ash) = (
+            player("Bot", 300.0, true),
+            player("Nova", 200.0, false),
+            player("Comet", 100.0, false),
+            player("Ash", 900.0, false),
+        );
+        ash.alive = false;
+        let ids = [bot.id, nova.id, ash.id];
+        for player in [bot, nova, comet, ash] {
+            state.add_player(player);
+        }
+        let snapshot = GameSnapshot::from_game_state(&state);
+        let account = AccountId::generate();
+        let find = |player_id| Presence::find(&snapshot, account, player_id, "standard", "main");
+
+        let nova = find(ids[1]).unwrap();
+        assert_eq!(nova.account, account.to_string());
+        assert_eq!(nova.status, PresenceStatus::InMatch);
+        assert_eq!((nova.rank, nova.of), (Some(2), Some(3)));
+
+        let ash = find(ids[2]).unwrap();
+        assert_eq!((ash.status, ash.rank), (PresenceStatus::Dead, None));
+
+        // Bots have no presence, and players not in the arena are not found
+        assert_eq!(find(ids[0]), None);
+        assert_eq!(find(Uuid::new_v4()), None);
+        let body = serde_json::to_string(&Presence::offline(account)).unwrap();
+        assert_eq!(body, format!(r#"{{"account":"{}","status":"offline"}}"#, account));
+    }
+}
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 
+use crate::game::match_result::Award;
 use crate::game::state::{GameState, MatchPhase, PlayerId, WellId};
+use crate::net::i18n::LocalizedText;
+use crate::net::join_queue::PartyInvite;
+use crate::net::reports::ReportReason;
 use crate::util::vec2::Vec2;
 
 // Thread-local reusable buffers to avoid per-snapshot allocations
@@ -21,6 +25,35 @@ pub enum ClientMessage {
         color_index: u8,
         #[serde(default)]
         is_spectator: bool,
+        /// Invite code from a party member already in the arena (`/invite`),
+        /// grants join queue priority
+        #[serde(default)]
+        party_invite: Option<PartyInvite>,
+        /// Session token from a previous JoinAccepted (grants reconnect priority)
+        #[serde(default)]
+        resume_token: Option<Vec<u8>>,
+        /// Operator-issued auth token (resolves to a role: vip, moderator, admin)
+        #[serde(default)]
+        auth_token: Option<String>,
+        /// Optional client features (e.g. highest snapshot rate it wants)
+        #[serde(default)]
+        capabilities: ClientCapabilities,
+        /// Accessibility options chosen by the player
+        #[serde(default)]
+        accessibility: AccessibilitySettings,
+        /// Per-player telemetry the player allows
+        #[serde(default)]
+        telemetry: TelemetryConsent,
+        /// Ephemeral ECDH P-256 public key, offered to encrypt snapshots (see `snapshot_crypto`)
+        #[serde(default)]
+        encryption_key: Option<Vec<u8>>,
+        /// Lobby room to play in instead of the main arena (a listed room,
+        /// galaxy arena or tenant room; players only)
+        #[serde(default)]
+        room: Option<uuid::Uuid>,
+        /// Account token from a previous JoinAccepted (see `account`)
+        #[serde(default)]
+        account_token: Option<Vec<u8>>,
     },
     /// Player input for current tick
     Input(PlayerInput),
@@ -40,6 +73,23 @@ pub enum ClientMessage {
         /// Current zoom level (0.1 = zoomed out, 1.0 = normal)
         zoom: f32,
     },
+    /// Chat line; lines starting with `/` are moderator commands
+    Chat { text: String },
+    /// Report another player's behavior (see `net::reports`)
+    Report { target: PlayerId, reason: ReportReason },
+    /// Room browser: one page of the lobby's rooms matching the filter (0 = first page)
+    ListRooms {
+        #[serde(default)]
+        filter: RoomFilter,
+        #[serde(default)]
+        page: u32,
+    },
+    /// Spectate a lobby room (from `RoomList`) without joining it; answered
+    /// like a spectator `JoinRequest`
+    SpectateRoom { room_id: uuid::Uuid, player_name: String },
+    /// Ask for a full snapshot now (the client found its state corrupt);
+    /// honored at most once per `MIN_RESYNC_REQUEST_INTERVAL`
+    RequestResync,
 }
 
 /// Reason for rejecting a join request
@@ -57,8 +107,8 @@ pub enum RejectionReason {
     Banned,
     /// Server is in maintenance
     Maintenance,
-    /// Other reason with custom message
-    Other { message: String },
+    /// Other reason with a localizable message
+    Other { message: LocalizedText },
 }
 
 /// Messages from server to client
@@ -70,6 +120,19 @@ pub enum ServerMessage {
         session_token: Vec<u8>,
         #[serde(default)]
         is_spectator: bool,
+        /// Server's ECDH public key when snapshots for this connection will be sealed
+        #[serde(default)]
+        encryption_key: Option<Vec<u8>>,
+        /// Shareable code of the current match's arena seed (see `game::arena_seed`)
+        #[serde(default)]
+        arena_code: String,
+        /// Arena and match state when the entities follow in `WorldChunk`s
+        /// instead of an initial snapshot (see `world_stream`)
+        #[serde(default)]
+        world: Option<JoinWorld>,
+        /// Token of the player's account, to send with later joins (see `account`)
+        #[serde(default)]
+        account_token: Vec<u8>,
     },
     /// Join was rejected
     JoinRejected { reason: RejectionReason },
@@ -85,11 +148,167 @@ pub enum ServerMessage {
         server_timestamp: u64,
     },
     /// Server is kicking the player
-    Kicked { reason: String },
+    Kicked { reason: LocalizedText },
     /// Match phase changed
     PhaseChange { phase: MatchPhase, countdown: f32 },
     /// Spectator mode changed (after switch)
     SpectatorModeChanged { is_spectator: bool },
+    /// Short natural-language match commentary (spectators only)
+    Commentary { text: String, tick: u64 },
+    /// Waiting in the join queue (1-based position, estimated seconds to admission)
+    QueueUpdate { position: u32, eta_secs: u32 },
+    /// Chat line from a connection
+    Chat { player_id: PlayerId, name: String, text: String },
+    /// Server-wide announcement, from a moderator or about a scheduled event
+    Announcement { text: LocalizedText },
+    /// Reply to a chat command (or a refused chat line)
+    CommandResult { success: bool, message: LocalizedText },
+    /// Server changed this spectator's follow target
+    SpectateTargetChanged { target_id: Option<PlayerId> },
+    /// Snapshot rate negotiated (or later adapted) for this client
+    SnapshotRate { hz: u8 },
+    /// A referee paused the match. `resume_countdown` is 0 while paused
+    /// indefinitely, otherwise the seconds until play resumes.
+    MatchPaused { reason: LocalizedText, resume_countdown: f32 },
+    /// A paused match is running again
+    MatchResumed,
+    /// Several events for this client from one tick, sent together
+    Events(Vec<GameEvent>),
+    /// Full resync sent as the changes since a snapshot the client acknowledged
+    Resync(ResyncUpdate),
+    /// An encoded message (a snapshot) encrypted with the connection's key;
+    /// `nonce` is the sender's counter (see `snapshot_crypto`)
+    Sealed { nonce: u64, payload: Vec<u8> },
+    /// Part of the entity set streamed to a joining client
+    WorldChunk(WorldChunk),
+    /// Arena cells the player is about to see, for preloading (see `region_hint`)
+    RegionHint(RegionHint),
+    /// Page of the room browser answering `ListRooms`
+    RoomList { rooms: Vec<RoomListing>, page: u32, total_pages: u32 },
+    /// Galaxy arenas and wormholes, sent on entering the galaxy and after each transit
+    GalaxyMap(GalaxyMap),
+}
+
+/// Snapshot send rate for one client
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SnapshotRate {
+    /// 5Hz with coarser deltas (lossy or constrained links)
+    Low,
+    /// 10Hz (the server default)
+    #[default]
+    Normal,
+    /// 20Hz (opt-in for low-latency clients)
+    High,
+}
+
+impl SnapshotRate {
+    pub fn hz(self) -> u8 {
+        match self {
+            SnapshotRate::Low => 5,
+            SnapshotRate::Normal => 10,
+            SnapshotRate::High => 20,
+        }
+    }
+}
+
+/// Features a client announces in its join request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientCapabilities {
+    /// Highest snapshot rate the client wants; the server may lower it
+    pub max_snapshot_rate: SnapshotRate,
+    /// Client can assemble its first snapshot from streamed chunks
+    #[serde(default)]
+    pub progressive_join: bool,
+}
+
+/// Accessibility options a player opts into in their join request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessibilitySettings {
+    /// Server nudges the player toward a stable orbit while thrust is idle
+    pub orbit_assist: bool,
+}
+
+/// Per-player telemetry a player allows in their join request. Opted-out
+/// players still count toward aggregates (player counts, heatmaps, match totals).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelemetryConsent {
+    /// Listed by name or id in per-player analytics (input stats, match history)
+    pub analytics: bool,
+    /// Session captures, and the replays and ghosts built from them
+    pub replays: bool,
+    /// Per-player behavior analysis of the input stream
+    pub behavior: bool,
+}
+
+impl Default for TelemetryConsent {
+    fn default() -> Self {
+        Self { analytics: true, replays: true, behavior: true }
+    }
+}
+
+impl TelemetryConsent {
+    /// Allows everything (nothing to track)
+    pub fn is_full(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Room browser filter; a room is listed when it matches every set field
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomFilter {
+    /// Room mode (`standard`, `slow_mode` or `practice`)
+    pub mode: Option<String>,
+    /// Region the server hosting the room runs in
+    pub region: Option<String>,
+    /// Only rooms with a free slot
+    pub not_full: bool,
+    /// Only rooms with at least one of these players (empty = any room)
+    pub friends: Vec<PlayerId>,
+}
+
+/// A room as listed in the room browser
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomListing {
+    pub room_id: uuid::Uuid,
+    pub name: String,
+    pub mode: String,
+    pub region: String,
+    /// Players in the room and the most it takes (bots fill the rest of a match)
+    pub players: u32,
+    pub max_players: u32,
+    /// A match is running; only waiting rooms take new players
+    pub in_progress: bool,
+    /// Players from the filter's friends who are in the room
+    pub friends: Vec<PlayerId>,
+}
+
+/// The galaxy as seen from the player's current arena (see `lobby::galaxy`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GalaxyMap {
+    /// Arena the player is in
+    pub current: uuid::Uuid,
+    pub arenas: Vec<GalaxyArena>,
+    pub wormholes: Vec<GalaxyWormhole>,
+}
+
+/// An arena of the galaxy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GalaxyArena {
+    pub room_id: uuid::Uuid,
+    pub name: String,
+    pub mode: String,
+    pub players: u32,
+    pub max_players: u32,
+}
+
+/// A one-way wormhole from one arena to another
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GalaxyWormhole {
+    pub from: uuid::Uuid,
+    pub to: uuid::Uuid,
+    /// Mouth in the `from` arena; flying within `radius` of it makes the transit
+    pub position: Vec2,
+    pub radius: f32,
 }
 
 /// Player input state for one tick
@@ -112,6 +331,16 @@ pub struct PlayerInput {
     pub fire: bool,
     /// Fire button just released (for charge release)
     pub fire_released: bool,
+    /// Hash of the client's predicted local state (desync detection)
+    #[serde(default)]
+    pub state_hash: Option<StateHash>,
+}
+
+/// Client-predicted state hash for a simulated tick (see `game::desync`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateHash {
+    pub tick: u64,
+    pub hash: u32,
 }
 
 impl PlayerInput {
@@ -133,15 +362,28 @@ pub struct GravityWellSnapshot {
     pub position: Vec2,
     pub mass: f32,
     pub core_radius: f32,
+    /// Color index of the player who owns the well
+    #[serde(default)]
+    pub owner_color: Option<u8>,
+    /// Progress of the capture in progress (0-1)
+    #[serde(default)]
+    pub capture_progress: f32,
+    /// Several players are fighting over the well
+    #[serde(default)]
+    pub contested: bool,
 }
 
 impl GravityWellSnapshot {
-    pub fn from_gravity_well(well: &crate::game::state::GravityWell) -> Self {
+    pub fn from_gravity_well(well: &crate::game::state::GravityWell, state: &GameState) -> Self {
+        let ownership = &well.ownership;
         Self {
             id: well.id,
             position: well.position,
             mass: well.mass,
             core_radius: well.core_radius,
+            owner_color: ownership.owner.and_then(|id| state.get_player(id)).map(|p| p.color_index),
+            capture_progress: ownership.progress,
+            contested: ownership.contested,
         }
     }
 }
@@ -169,6 +411,9 @@ pub struct GameSnapshot {
     /// Gravity wells in the arena
     #[serde(default)]
     pub gravity_wells: Vec<GravityWellSnapshot>,
+    /// Destructible asteroids in view
+    #[serde(default)]
+    pub asteroids: Vec<AsteroidSnapshot>,
     /// Total players in match (before AOI filtering)
     #[serde(default)]
     pub total_players: u32,
@@ -181,6 +426,9 @@ pub struct GameSnapshot {
     /// Echo of client's last input timestamp for RTT measurement
     #[serde(default)]
     pub echo_client_time: u64,
+    /// Recommended client interpolation delay in ms (0 = no recommendation)
+    #[serde(default)]
+    pub interp_delay_ms: u16,
     /// AI Manager status (if enabled)
     #[serde(default)]
     pub ai_status: Option<AIStatusSnapshot>,
@@ -240,16 +488,26 @@ impl GameSnapshot {
                 .arena
                 .gravity_wells
                 .values()
-                .map(GravityWellSnapshot::from_gravity_well)
+                .map(|well| GravityWellSnapshot::from_gravity_well(well, state))
                 .collect(),
+            asteroids: state.asteroids.iter().map(AsteroidSnapshot::from_asteroid).collect(),
             total_players,
             total_alive,
             density_grid,
             echo_client_time: 0, // Set per-player in broadcast
+            interp_delay_ms: 0,
             ai_status: None, // Set by game_session when AI manager is active
         }
     }
 
+    /// Fill in charge windups from the authoritative server charge state.
+    /// Dead players never report a charge.
+    pub fn apply_charges(&mut self, charges: &crate::game::systems::projectile::ChargeManager) {
+        for player in &mut self.players {
+            player.charge = if player.alive() { encode_charge(charges.get(player.id)) } else { 0 };
+        }
+    }
+
     /// Calculate mass density grid (16x16) for minimap heatmap
     /// Includes: player mass + gravity well influence (1/r falloff)
     ///
@@ -325,15 +583,18 @@ impl GameSnapshot {
 }
 
 /// Player flags - bit-packed booleans for bandwidth efficiency
-/// OPTIMIZATION: Packs 3 bools into 1 byte (saves 2 bytes per player per snapshot)
+/// OPTIMIZATION: Packs 4 bools into 1 byte (saves 3 bytes per player per snapshot)
 pub mod player_flags {
     pub const ALIVE: u8 = 0b0000_0001;
     pub const SPAWN_PROTECTION: u8 = 0b0000_0010;
     pub const IS_BOT: u8 = 0b0000_0100;
+    /// Replayed run in a practice room, not a live player (see `game::ghost`)
+    #[cfg_attr(not(feature = "lobby"), allow(dead_code))]
+    pub const GHOST: u8 = 0b0000_1000;
 }
 
 /// Compressed player state
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlayerSnapshot {
     pub id: PlayerId,
     pub name: String,
@@ -341,7 +602,7 @@ pub struct PlayerSnapshot {
     pub velocity: Vec2,
     pub rotation: f32,
     pub mass: f32,
-    /// Bit-packed flags: bit 0 = alive, bit 1 = spawn_protection, bit 2 = is_bot
+    /// Bit-packed flags: bit 0 = alive, bit 1 = spawn_protection, bit 2 = is_bot, bit 3 = ghost
     pub flags: u8,
     pub kills: u32,
     pub deaths: u32,
@@ -349,6 +610,33 @@ pub struct PlayerSnapshot {
     /// Tick when player spawned/respawned (for birth animation detection)
     #[serde(default)]
     pub spawn_tick: u64,
+    /// Charge windup: 0 = not charging, 1-255 = charging (see `encode_charge`)
+    #[serde(default)]
+    pub charge: u8,
+    /// Heat gauge scaled to 0-255 (always 0 unless the ruleset uses heat)
+    #[serde(default)]
+    pub heat: u8,
+    /// Bot faction for territory colors (0 = none)
+    #[serde(default)]
+    pub faction: u8,
+    /// Active status effects: bit 0 = burn, bit 1 = slow, bit 2 = shield (see `EffectKind::flag`)
+    #[serde(default)]
+    pub effects: u8,
+}
+
+/// Quantize a charge for the wire: 0 when not charging, otherwise 1-255 scaled
+/// from charge time over the maximum. A charge that has started never encodes
+/// as 0, so even the first tick of a windup is visible to opponents.
+pub fn encode_charge(charge: Option<&crate::game::systems::projectile::ChargeState>) -> u8 {
+    use crate::game::constants::eject::MAX_CHARGE_TIME;
+
+    match charge {
+        Some(c) if c.is_charging => {
+            let ratio = (c.charge_time / MAX_CHARGE_TIME).clamp(0.0, 1.0);
+            1 + (ratio * 254.0).round() as u8
+        }
+        _ => 0,
+    }
 }
 
 impl PlayerSnapshot {
@@ -376,6 +664,37 @@ impl PlayerSnapshot {
             deaths: player.deaths,
             color_index: player.color_index,
             spawn_tick: player.spawn_tick,
+            charge: 0,
+            heat: (player.heat.clamp(0.0, 1.0) * 255.0).round() as u8,
+            faction: player.faction,
+            effects: player.effects.flags(),
+        }
+    }
+
+    /// Snapshot of a replayed ghost run
+    #[cfg_attr(not(feature = "lobby"), allow(dead_code))]
+    pub fn from_ghost(ghost: &crate::game::ghost::Ghost, frame: crate::game::ghost::GhostFrame) -> Self {
+        let mut flags = player_flags::GHOST;
+        if frame.alive {
+            flags |= player_flags::ALIVE;
+        }
+
+        Self {
+            id: ghost.id,
+            name: ghost.track.name.clone(),
+            position: frame.position,
+            velocity: frame.velocity,
+            rotation: frame.velocity.y.atan2(frame.velocity.x),
+            mass: frame.mass,
+            flags,
+            kills: 0,
+            deaths: 0,
+            color_index: 0,
+            spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
         }
     }
 
@@ -396,10 +715,29 @@ impl PlayerSnapshot {
     pub fn is_bot(&self) -> bool {
         self.flags & player_flags::IS_BOT != 0
     }
+
+    /// Check if this is a replayed ghost rather than a live player
+    #[inline]
+    pub fn is_ghost(&self) -> bool {
+        self.flags & player_flags::GHOST != 0
+    }
+
+    /// Check if player is charging a shot
+    #[cfg(test)]
+    #[inline]
+    pub fn is_charging(&self) -> bool {
+        self.charge != 0
+    }
+
+    /// Normalized charge (0.0 to 1.0) of the windup in progress
+    #[cfg(test)]
+    pub fn charge_ratio(&self) -> f32 {
+        self.charge.saturating_sub(1) as f32 / 254.0
+    }
 }
 
 /// Compressed projectile state
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProjectileSnapshot {
     pub id: u64,
     pub owner_id: PlayerId,
@@ -421,7 +759,7 @@ impl ProjectileSnapshot {
 }
 
 /// Debris (collectible particle) snapshot
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DebrisSnapshot {
     pub id: u64,
     pub position: Vec2,
@@ -441,6 +779,39 @@ impl DebrisSnapshot {
             },
         }
     }
+
+    /// World radius of this debris' size category
+    pub fn radius(&self) -> f32 {
+        use crate::game::state::DebrisSize;
+        match self.size {
+            0 => DebrisSize::Small,
+            1 => DebrisSize::Medium,
+            _ => DebrisSize::Large,
+        }
+        .radius()
+    }
+}
+
+/// Destructible asteroid snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsteroidSnapshot {
+    pub id: u64,
+    pub position: Vec2,
+    pub radius: f32,
+    /// Remaining health scaled to 0-255
+    pub health: u8,
+}
+
+impl AsteroidSnapshot {
+    pub fn from_asteroid(asteroid: &crate::game::state::Asteroid) -> Self {
+        let share = asteroid.health / asteroid.max_health.max(f32::EPSILON);
+        Self {
+            id: asteroid.id,
+            position: asteroid.position,
+            radius: asteroid.radius,
+            health: (share.clamp(0.0, 1.0) * 255.0).round() as u8,
+        }
+    }
 }
 
 /// Delta update containing only changes
@@ -453,10 +824,75 @@ pub struct DeltaUpdate {
     pub removed_projectiles: Vec<u64>,
     /// Full debris list (debris moves slowly, sending full list is efficient)
     pub debris: Vec<DebrisSnapshot>,
+    /// Players in the base snapshot that are no longer visible (left AOI, despawned),
+    /// so clients can fade them out instead of waiting for the next full snapshot
+    #[serde(default)]
+    pub removed_players: Vec<PlayerId>,
+    /// Full asteroid list in view (few, and their health changes when shot)
+    #[serde(default)]
+    pub asteroids: Vec<AsteroidSnapshot>,
+}
+
+/// Full resync relative to an acknowledged base snapshot
+///
+/// `snapshot` carries every top-level field of the new snapshot, but only the
+/// players, projectiles and debris that differ from the base (new or changed,
+/// compared exactly). The client keeps the other entities from the base and
+/// drops the removed ids; the result equals the full snapshot, so it becomes
+/// the new pinned base like a full snapshot would.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResyncUpdate {
+    pub base_tick: u64,
+    pub snapshot: GameSnapshot,
+    pub removed_players: Vec<PlayerId>,
+    pub removed_projectiles: Vec<u64>,
+    pub removed_debris: Vec<u64>,
+}
+
+/// What a streaming join starts from
+///
+/// `base` is the join snapshot without its players, projectiles and debris:
+/// arena geometry, gravity wells and match state. The entities arrive in
+/// `chunks` `WorldChunk`s for the same tick; the client appends them to the
+/// base and uses the result as its first full snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinWorld {
+    pub base: GameSnapshot,
+    pub chunks: u16,
 }
 
-/// Delta for a single player
+/// Entities of a streamed join snapshot, nearest the viewer first
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldChunk {
+    /// Tick of the join snapshot (`JoinWorld::base.tick`)
+    pub tick: u64,
+    /// Position in the stream, from 0
+    pub index: u16,
+    pub players: Vec<PlayerSnapshot>,
+    pub projectiles: Vec<ProjectileSnapshot>,
+    pub debris: Vec<DebrisSnapshot>,
+}
+
+/// Cells of the AOI index grid a player's view is heading into, soonest first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionHint {
+    pub tick: u64,
+    /// Cell size in world units; cell (x, y) spans [x, x + 1) × [y, y + 1) cells
+    pub cell_size: f32,
+    pub cells: Vec<RegionCell>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionCell {
+    pub x: i32,
+    pub y: i32,
+    /// Debris in the cell (other entities aren't revealed)
+    pub debris: u16,
+}
+
+/// Delta for a single player (packed on the wire as a changed-field mask and
+/// quantized varints, see `net::delta`)
+#[derive(Debug, Clone)]
 pub struct PlayerDelta {
     pub id: PlayerId,
     pub position: Option<Vec2>,
@@ -465,6 +901,14 @@ pub struct PlayerDelta {
     pub mass: Option<f32>,
     pub alive: Option<bool>,
     pub kills: Option<u32>,
+    /// Charge windup, encoded as in [`PlayerSnapshot::charge`]
+    pub charge: Option<u8>,
+    /// Heat gauge, encoded as in [`PlayerSnapshot::heat`]
+    pub heat: Option<u8>,
+    /// Faction, as in [`PlayerSnapshot::faction`]
+    pub faction: Option<u8>,
+    /// Status effect bits, as in [`PlayerSnapshot::effects`]
+    pub effects: Option<u8>,
 }
 
 /// Delta for a projectile
@@ -478,21 +922,17 @@ pub struct ProjectileDelta {
 /// Game events that clients should be notified about
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GameEvent {
-    /// A player was killed
-    PlayerKilled {
-        killer_id: PlayerId,
-        victim_id: PlayerId,
-        killer_name: String,
-        victim_name: String,
-    },
+    /// A player died. Sent to clients that can see the victim, the players
+    /// involved and every spectator.
+    KillFeed(KillFeedEntry),
     /// A player joined
     PlayerJoined { player_id: PlayerId, name: String },
     /// A player left
     PlayerLeft { player_id: PlayerId, name: String },
     /// Match started
     MatchStarted,
-    /// Match ended with a winner
-    MatchEnded { winner_id: Option<PlayerId>, winner_name: Option<String> },
+    /// Match ended with a winner, and the end-of-match awards (MVP first)
+    MatchEnded { winner_id: Option<PlayerId>, winner_name: Option<String>, awards: Vec<Award> },
     /// Zone is collapsing
     ZoneCollapse { phase: u8, new_safe_radius: f32 },
     /// Two players collided and deflected (both survived)
@@ -527,6 +967,101 @@ pub enum GameEvent {
         /// Well position before removal
         position: Vec2,
     },
+    /// A bot said something (sent only to clients whose AOI contains the bot)
+    BotChatter {
+        player_id: PlayerId,
+        kind: ChatterKind,
+        text: String,
+        /// Bot position when speaking (for speech bubble placement)
+        position: Vec2,
+    },
+    /// A global modifier started (e.g. solar flare), lasting `duration` more seconds
+    ModifierStarted { name: String, description: LocalizedText, duration: f32 },
+    /// A global modifier expired
+    ModifierEnded { name: String },
+    /// A player claimed a gravity well
+    WellCaptured {
+        well_id: WellId,
+        owner_id: PlayerId,
+        owner_name: String,
+        /// Owner's color index (wells are drawn in it)
+        color_index: u8,
+    },
+    /// A second player entered a well's capture ring, freezing progress
+    WellContested { well_id: WellId },
+    /// A projectile struck a player. Sent only to the shooter and the victim,
+    /// batched per tick in `ServerMessage::Events`.
+    Hit {
+        shooter_id: PlayerId,
+        victim_id: PlayerId,
+        /// Server-assigned ID of the projectile (for reconciling predicted shots)
+        projectile_id: u64,
+        /// Projectile mass transferred to the victim
+        amount: f32,
+        position: Vec2,
+    },
+    /// An asteroid broke apart (sent only to clients whose AOI contains it)
+    AsteroidDestroyed {
+        asteroid_id: u64,
+        position: Vec2,
+        /// Debris fragments it left behind
+        fragments: u16,
+    },
+}
+
+/// What killed a player, recorded on every death (clients pick the kill feed icon by it)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DeathCause {
+    /// Rammed and absorbed by a heavier player
+    Crush,
+    /// Lost a fight at range (shots, or a bot fight resolved out of view)
+    Projectile,
+    /// Fell into a gravity well's core
+    Well,
+    /// Drained below minimum mass outside the arena
+    Boundary,
+    /// Left the match while alive
+    Disconnect,
+}
+
+impl DeathCause {
+    pub const ALL: [DeathCause; 5] =
+        [DeathCause::Crush, DeathCause::Projectile, DeathCause::Well, DeathCause::Boundary, DeathCause::Disconnect];
+
+    /// Name used in metrics labels and stats
+    pub fn name(self) -> &'static str {
+        match self {
+            DeathCause::Crush => "crush",
+            DeathCause::Projectile => "projectile",
+            DeathCause::Well => "well",
+            DeathCause::Boundary => "boundary",
+            DeathCause::Disconnect => "disconnect",
+        }
+    }
+}
+
+/// One kill feed line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KillFeedEntry {
+    /// None for deaths nobody caused (wells, the boundary)
+    pub killer_id: Option<PlayerId>,
+    pub victim_id: PlayerId,
+    pub killer_name: Option<String>,
+    pub victim_name: String,
+    pub cause: DeathCause,
+    /// The line itself, e.g. "{killer} crushed {victim}"
+    pub text: LocalizedText,
+    /// Streak notes shown alongside: multi-kills, streaks, shutdowns
+    pub annotations: Vec<LocalizedText>,
+}
+
+/// Context of a bot chatter message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChatterKind {
+    /// After the bot scored a kill
+    Taunt,
+    /// While the bot flees a heavier player
+    Distress,
 }
 
 /// Encode a message using bincode (used in tests, production uses encode_pooled)
@@ -565,11 +1100,20 @@ mod tests {
             player_name: "TestPlayer".to_string(),
             color_index: 3,
             is_spectator: false,
+            party_invite: None,
+            resume_token: None,
+            auth_token: None,
+            capabilities: ClientCapabilities::default(),
+            accessibility: AccessibilitySettings::default(),
+            telemetry: TelemetryConsent::default(),
+            encryption_key: None,
+            room: None,
+            account_token: None,
         };
         let encoded = encode(&msg).unwrap();
         let decoded: ClientMessage = decode(&encoded).unwrap();
         match decoded {
-            ClientMessage::JoinRequest { player_name, color_index, is_spectator } => {
+            ClientMessage::JoinRequest { player_name, color_index, is_spectator, .. } => {
                 assert_eq!(player_name, "TestPlayer");
                 assert_eq!(color_index, 3);
                 assert!(!is_spectator);
@@ -584,6 +1128,15 @@ mod tests {
             player_name: "Spectator".to_string(),
             color_index: 0,
             is_spectator: true,
+            party_invite: None,
+            resume_token: None,
+            auth_token: None,
+            capabilities: ClientCapabilities::default(),
+            accessibility: AccessibilitySettings::default(),
+            telemetry: TelemetryConsent::default(),
+            encryption_key: None,
+            room: None,
+            account_token: None,
         };
         let encoded = encode(&msg).unwrap();
         let decoded: ClientMessage = decode(&encoded).unwrap();
@@ -596,6 +1149,148 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_client_message_join_with_priority_hints() {
+        let (invite, room_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let msg = ClientMessage::JoinRequest {
+            player_name: "Friend".to_string(),
+            color_index: 1,
+            is_spectator: false,
+            party_invite: Some(invite),
+            resume_token: Some(vec![7; 32]),
+            auth_token: Some("vip-token".to_string()),
+            capabilities: ClientCapabilities { max_snapshot_rate: SnapshotRate::High, progressive_join: true },
+            accessibility: AccessibilitySettings { orbit_assist: true },
+            telemetry: TelemetryConsent { analytics: false, replays: true, behavior: false },
+            encryption_key: Some(vec![4; 65]),
+            room: Some(room_id),
+            account_token: Some(vec![9; 48]),
+        };
+        let encoded = encode(&msg).unwrap();
+        let decoded: ClientMessage = decode(&encoded).unwrap();
+        match decoded {
+            ClientMessage::JoinRequest {
+                party_invite, resume_token, auth_token, capabilities, telemetry, encryption_key, room, account_token, ..
+            } => {
+                assert_eq!(party_invite, Some(invite));
+                assert_eq!(room, Some(room_id));
+                assert_eq!(account_token, Some(vec![9; 48]));
+                assert!(!telemetry.analytics && telemetry.replays && !telemetry.behavior);
+                assert_eq!(encryption_key, Some(vec![4; 65]));
+                assert_eq!(resume_token, Some(vec![7; 32]));
+                assert_eq!(auth_token.as_deref(), Some("vip-token"));
+                assert_eq!(capabilities.max_snapshot_rate, SnapshotRate::High);
+                assert!(capabilities.progressive_join);
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_client_message_request_resync() {
+        let encoded = encode(&ClientMessage::RequestResync).unwrap();
+        assert_eq!(encoded, 12u32.to_le_bytes());
+        assert!(matches!(decode::<ClientMessage>(&encoded).unwrap(), ClientMessage::RequestResync));
+    }
+
+    #[test]
+    fn test_server_message_queue_update() {
+        let msg = ServerMessage::QueueUpdate { position: 3, eta_secs: 45 };
+        let encoded = encode(&msg).unwrap();
+        let decoded: ServerMessage = decode(&encoded).unwrap();
+        match decoded {
+            ServerMessage::QueueUpdate { position, eta_secs } => {
+                assert_eq!(position, 3);
+                assert_eq!(eta_secs, 45);
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_room_browser_messages_roundtrip() {
+        let friend = Uuid::new_v4();
+        let mode = Some("standard".to_string());
+        let filter = RoomFilter { mode, not_full: true, friends: vec![friend], ..Default::default() };
+        let encoded = encode(&ClientMessage::ListRooms { filter: filter.clone(), page: 2 }).unwrap();
+        match decode::<ClientMessage>(&encoded).unwrap() {
+            ClientMessage::ListRooms { filter: decoded, page } => assert_eq!((decoded, page), (filter, 2)),
+            _ => panic!("Wrong message type"),
+        }
+
+        let listing = RoomListing {
+            room_id: Uuid::new_v4(),
+            name: "Game 1".to_string(),
+            mode: "standard".to_string(),
+            region: "eu-west".to_string(),
+            players: 3,
+            max_players: 10,
+            in_progress: false,
+            friends: vec![friend],
+        };
+        let msg = ServerMessage::RoomList { rooms: vec![listing.clone()], page: 0, total_pages: 1 };
+        match decode::<ServerMessage>(&encode(&msg).unwrap()).unwrap() {
+            ServerMessage::RoomList { rooms, page, total_pages } => {
+                assert_eq!((rooms, page, total_pages), (vec![listing], 0, 1))
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_galaxy_map_roundtrip() {
+        let (sol, vega) = (Uuid::new_v4(), Uuid::new_v4());
+        let arena = |room_id, name: &str| GalaxyArena {
+            room_id,
+            name: name.to_string(),
+            mode: "standard".to_string(),
+            players: 2,
+            max_players: 10,
+        };
+        let map = GalaxyMap {
+            current: vega,
+            arenas: vec![arena(sol, "Sol"), arena(vega, "Vega")],
+            wormholes: vec![GalaxyWormhole { from: sol, to: vega, position: Vec2::new(1200.0, -40.0), radius: 80.0 }],
+        };
+        match decode::<ServerMessage>(&encode(&ServerMessage::GalaxyMap(map.clone())).unwrap()).unwrap() {
+            ServerMessage::GalaxyMap(decoded) => assert_eq!(decoded, map),
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_chat_messages_roundtrip() {
+        let encoded = encode(&ClientMessage::Chat { text: "/mute Bob 10m".to_string() }).unwrap();
+        match decode::<ClientMessage>(&encoded).unwrap() {
+            ClientMessage::Chat { text } => assert_eq!(text, "/mute Bob 10m"),
+            _ => panic!("Wrong message type"),
+        }
+
+        let player_id = Uuid::new_v4();
+        let msg = ServerMessage::Chat { player_id, name: "Alice".to_string(), text: "gg".to_string() };
+        match decode::<ServerMessage>(&encode(&msg).unwrap()).unwrap() {
+            ServerMessage::Chat { player_id: id, name, text } => {
+                assert_eq!(id, player_id);
+                assert_eq!(name, "Alice");
+                assert_eq!(text, "gg");
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_report_roundtrip() {
+        let target = Uuid::new_v4();
+        let encoded = encode(&ClientMessage::Report { target, reason: ReportReason::Griefing }).unwrap();
+        match decode::<ClientMessage>(&encoded).unwrap() {
+            ClientMessage::Report { target: decoded, reason } => {
+                assert_eq!(decoded, target);
+                assert_eq!(reason, ReportReason::Griefing);
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
     #[test]
     fn test_client_message_viewport_info() {
         let msg = ClientMessage::ViewportInfo { zoom: 0.15 };
@@ -620,6 +1315,7 @@ mod tests {
             boost: true,
             fire: false,
             fire_released: false,
+            state_hash: Some(StateHash { tick: 98, hash: 0xdead_beef }),
         };
         let msg = ClientMessage::Input(input);
         let encoded = encode(&msg).unwrap();
@@ -629,6 +1325,7 @@ mod tests {
                 assert_eq!(i.sequence, 42);
                 assert_eq!(i.tick, 100);
                 assert!(i.boost);
+                assert_eq!(i.state_hash, Some(StateHash { tick: 98, hash: 0xdead_beef }));
             }
             _ => panic!("Wrong message type"),
         }
@@ -641,6 +1338,10 @@ mod tests {
             player_id,
             session_token: vec![1, 2, 3, 4],
             is_spectator: false,
+            encryption_key: Some(vec![4; 65]),
+            arena_code: "1BCD2EF".to_string(),
+            world: None,
+            account_token: vec![9; 48],
         };
         let encoded = encode(&msg).unwrap();
         let decoded: ServerMessage = decode(&encoded).unwrap();
@@ -649,10 +1350,18 @@ mod tests {
                 player_id: pid,
                 session_token,
                 is_spectator,
+                encryption_key,
+                arena_code,
+                world,
+                account_token,
             } => {
                 assert_eq!(pid, player_id);
                 assert_eq!(session_token, vec![1, 2, 3, 4]);
                 assert!(!is_spectator);
+                assert_eq!(encryption_key, Some(vec![4; 65]));
+                assert_eq!(arena_code, "1BCD2EF");
+                assert!(world.is_none());
+                assert_eq!(account_token, vec![9; 48]);
             }
             _ => panic!("Wrong message type"),
         }
@@ -690,6 +1399,10 @@ mod tests {
                 deaths: 1,
                 color_index: 2,
                 spawn_tick: 0,
+                charge: 0,
+                heat: 0,
+                faction: 0,
+                effects: 0,
             }],
             projectiles: vec![],
             debris: vec![DebrisSnapshot {
@@ -705,11 +1418,16 @@ mod tests {
                 position: Vec2::ZERO,
                 mass: 10000.0,
                 core_radius: 50.0,
+                owner_color: None,
+                capture_progress: 0.0,
+                contested: false,
             }],
+            asteroids: vec![AsteroidSnapshot { id: 7, position: Vec2::new(500.0, 0.0), radius: 60.0, health: 128 }],
             total_players: 1,
             total_alive: 1,
             density_grid: vec![0; 64],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
@@ -727,33 +1445,57 @@ mod tests {
         assert_eq!(decoded.gravity_wells.len(), 1);
         assert_eq!(decoded.density_grid.len(), 64);
         assert_eq!(decoded.debris.len(), 1);
+        assert_eq!(decoded.asteroids[0].health, 128);
     }
 
     #[test]
     fn test_game_event_serialization() {
-        let event = GameEvent::PlayerKilled {
-            killer_id: Uuid::new_v4(),
+        let event = GameEvent::KillFeed(KillFeedEntry {
+            killer_id: Some(Uuid::new_v4()),
             victim_id: Uuid::new_v4(),
-            killer_name: "Alice".to_string(),
+            killer_name: Some("Alice".to_string()),
             victim_name: "Bob".to_string(),
-        };
+            cause: DeathCause::Crush,
+            text: LocalizedText::new("kill.crush"),
+            annotations: vec![LocalizedText::new("kill.double")],
+        });
 
         let encoded = encode(&event).unwrap();
         let decoded: GameEvent = decode(&encoded).unwrap();
 
         match decoded {
-            GameEvent::PlayerKilled {
-                killer_name,
-                victim_name,
-                ..
-            } => {
-                assert_eq!(killer_name, "Alice");
-                assert_eq!(victim_name, "Bob");
+            GameEvent::KillFeed(entry) => {
+                assert_eq!(entry.killer_name.as_deref(), Some("Alice"));
+                assert_eq!(entry.victim_name, "Bob");
+                assert_eq!(entry.cause, DeathCause::Crush);
+                assert_eq!(entry.annotations.len(), 1);
             }
             _ => panic!("Wrong event type"),
         }
     }
 
+    #[test]
+    fn test_encode_charge() {
+        use crate::game::systems::projectile::ChargeState;
+
+        assert_eq!(encode_charge(None), 0);
+        assert_eq!(encode_charge(Some(&ChargeState::default())), 0);
+
+        let mut charge = ChargeState { is_charging: true, ..Default::default() };
+        // Just started: still visible
+        assert_eq!(encode_charge(Some(&charge)), 1);
+        charge.charge_time = 10.0;
+        assert_eq!(encode_charge(Some(&charge)), 255);
+        charge.charge_time = f32::NAN;
+        assert_eq!(encode_charge(Some(&charge)), 1);
+
+        let mut snapshot = PlayerSnapshot::from_player(&crate::game::state::Player::default());
+        let quarter = ChargeState { is_charging: true, charge_time: 0.25, ..Default::default() };
+        snapshot.charge = encode_charge(Some(&quarter));
+        assert!(snapshot.is_charging());
+        assert!((snapshot.charge_ratio() - 0.25).abs() < 0.005);
+    }
+
     #[test]
     fn test_player_input_default() {
         let input = PlayerInput::default();
@@ -777,6 +1519,10 @@ mod tests {
                 mass: None,
                 alive: None,
                 kills: Some(1),
+                charge: None,
+                heat: None,
+                faction: None,
+                effects: None,
             }],
             projectile_updates: vec![],
             removed_projectiles: vec![1, 2, 3],
@@ -785,6 +1531,8 @@ mod tests {
                 position: Vec2::new(100.0, 200.0),
                 size: 1,
             }],
+            removed_players: vec![Uuid::new_v4()],
+            asteroids: vec![],
         };
 
         let encoded = encode(&delta).unwrap();
@@ -793,6 +1541,7 @@ mod tests {
         assert_eq!(decoded.tick, 500);
         assert_eq!(decoded.removed_projectiles.len(), 3);
         assert_eq!(decoded.debris.len(), 1);
+        assert_eq!(decoded.removed_players.len(), 1);
     }
 
     #[test]
@@ -858,6 +1607,10 @@ mod encoding_tests {
             player_id: uuid::Uuid::nil(),
             session_token: vec![1, 2, 3, 4],
             is_spectator: false,
+            encryption_key: None,
+            arena_code: String::new(),
+            world: None,
+            account_token: vec![],
         };
         let encoded = encode(&msg).unwrap();
         println!("\n=== JoinAccepted ===");
@@ -886,7 +1639,7 @@ mod encoding_tests {
         
         // Test Kicked
         let msg3 = ServerMessage::Kicked {
-            reason: "Test kick".to_string(),
+            reason: LocalizedText::verbatim("Test kick"),
         };
         let encoded3 = encode(&msg3).unwrap();
         println!("\n=== Kicked ===");
@@ -959,15 +1712,18 @@ mod encoding_tests {
     fn inspect_game_event_encoding() {
         use uuid::Uuid;
         
-        let event = GameEvent::PlayerKilled {
-            killer_id: Uuid::from_bytes([0x01; 16]),
+        let event = GameEvent::KillFeed(KillFeedEntry {
+            killer_id: Some(Uuid::from_bytes([0x01; 16])),
             victim_id: Uuid::from_bytes([0x02; 16]),
-            killer_name: "Alice".to_string(),
+            killer_name: Some("Alice".to_string()),
             victim_name: "Bob".to_string(),
-        };
+            cause: DeathCause::Crush,
+            text: LocalizedText::new("kill.crush"),
+            annotations: vec![],
+        });
         
         let encoded = encode(&event).unwrap();
-        println!("\n=== PlayerKilled Event ===");
+        println!("\n=== KillFeed Event ===");
         println!("Encoded bytes: {:?}", &encoded[..]);
         println!("Hex: {}", encoded.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" "));
         println!("Length: {}", encoded.len());
@@ -979,7 +1735,8 @@ mod encoding_tests {
             offset = 4;
         }
         
-        // First UUID
+        // First UUID (after its Option tag)
+        offset += 1;
         if offset + 8 <= encoded.len() {
             let uuid_len = u64::from_le_bytes([
                 encoded[offset], encoded[offset+1], encoded[offset+2], encoded[offset+3],
@@ -999,7 +1756,8 @@ mod encoding_tests {
             offset += 8 + uuid_len as usize;
         }
         
-        // Killer name
+        // Killer name (after its Option tag)
+        offset += 1;
         if offset + 8 <= encoded.len() {
             let name_len = u64::from_le_bytes([
                 encoded[offset], encoded[offset+1], encoded[offset+2], encoded[offset+3],
@@ -1065,6 +1823,10 @@ mod encoding_tests {
                 deaths: 0,
                 color_index: 2,
                 spawn_tick: 0,
+                charge: 0,
+                heat: 0,
+                faction: 0,
+                effects: 0,
             }],
             projectiles: vec![],
             debris: vec![],
@@ -1072,10 +1834,12 @@ mod encoding_tests {
             arena_safe_radius: 500.0,
             arena_scale: 1.0,
             gravity_wells: vec![],
+            asteroids: vec![],
             total_players: 1,
             total_alive: 1,
             density_grid: vec![],
             echo_client_time: 0,
+            interp_delay_ms: 0,
             ai_status: None,
         };
 
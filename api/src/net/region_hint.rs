@@ -0,0 +1,119 @@
+//! Region hints: the arena cells a player is about to see
+//!
+//! Every `interval_ticks` each moving player gets a `RegionHint`: the AOI
+//! index cells in view range of where its velocity puts it `lookahead_ms`
+//! from now, but out of range of where it is, soonest first. Clients use it
+//! to prepare rendering before the entities show up in snapshots.
+//!
+//! The cells come from the broadcast's [`AoiIndex`], which is built anyway,
+//! so a hint costs a bounded number of cell checks. Only debris is counted:
+//! players and projectiles out of view stay hidden.
+
+use crate::config::RegionHintConfig;
+use crate::net::aoi_index::{AoiIndex, CellKey, AOI_INDEX_CELL_SIZE};
+use crate::net::protocol::{RegionCell, RegionHint};
+use crate::util::vec2::Vec2;
+
+/// Cells checked around the projected position in each direction (a zoomed-out
+/// AOI is larger; its far cells aren't worth hinting)
+const MAX_HINT_REACH: i32 = 16;
+
+/// Hint for a player at `position` moving at `velocity` with AOI `radius`.
+/// None when no new cells come into range (e.g. the player isn't moving).
+pub fn region_hint(
+    index: &AoiIndex,
+    tick: u64,
+    position: Vec2,
+    velocity: Vec2,
+    radius: f32,
+    config: &RegionHintConfig,
+) -> Option<RegionHint> {
+    let ahead = position + velocity * (config.lookahead_ms as f32 / 1000.0);
+    let (cx, cy) = index.cell_key(ahead);
+    let reach = ((radius.max(0.0) / AOI_INDEX_CELL_SIZE).ceil() as i32).min(MAX_HINT_REACH);
+
+    let mut cells = Vec::new();
+    for y in cy.saturating_sub(reach)..=cy.saturating_add(reach) {
+        for x in cx.saturating_sub(reach)..=cx.saturating_add(reach) {
+            if distance_to_cell((x, y), ahead) > radius {
+                continue;
+            }
+            // Already in view
+            let now = distance_to_cell((x, y), position);
+            if now <= radius {
+                continue;
+            }
+            let debris = index.debris_in((x, y)).min(u16::MAX as usize) as u16;
+            cells.push((now, RegionCell { x, y, debris }));
+        }
+    }
+    if cells.is_empty() {
+        return None;
+    }
+
+    cells.sort_by(|a, b| a.0.total_cmp(&b.0));
+    cells.truncate(config.max_cells);
+    Some(RegionHint { tick, cell_size: AOI_INDEX_CELL_SIZE, cells: cells.into_iter().map(|(_, c)| c).collect() })
+}
+
+/// Distance from `point` to the nearest point of a cell (0 inside it)
+fn distance_to_cell((x, y): CellKey, point: Vec2) -> f32 {
+    let min_x = x as f32 * AOI_INDEX_CELL_SIZE;
+    let min_y = y as f32 * AOI_INDEX_CELL_SIZE;
+    let dx = (min_x - point.x).max(point.x - (min_x + AOI_INDEX_CELL_SIZE)).max(0.0);
+    let dy = (min_y - point.y).max(point.y - (min_y + AOI_INDEX_CELL_SIZE)).max(0.0);
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::MatchPhase;
+    use crate::net::protocol::{DebrisSnapshot, GameSnapshot};
+    use std::sync::Arc;
+
+    fn index(debris: Vec<DebrisSnapshot>) -> AoiIndex {
+        let snapshot = GameSnapshot {
+            tick: 1,
+            match_phase: MatchPhase::Playing,
+            match_time: 0.0,
+            countdown: 0.0,
+            players: vec![],
+            projectiles: vec![],
+            debris,
+            arena_collapse_phase: 0,
+            arena_safe_radius: 5000.0,
+            arena_scale: 1.0,
+            gravity_wells: vec![],
+            asteroids: vec![],
+            total_players: 0,
+            total_alive: 0,
+            density_grid: vec![],
+            echo_client_time: 0,
+            interp_delay_ms: 0,
+            ai_status: None,
+        };
+        AoiIndex::build(Arc::new(snapshot), &[])
+    }
+
+    #[test]
+    fn test_stationary_player_gets_no_hint() {
+        let config = RegionHintConfig::default();
+        assert!(region_hint(&index(vec![]), 1, Vec2::new(100.0, 100.0), Vec2::ZERO, 800.0, &config).is_none());
+    }
+
+    #[test]
+    fn test_hint_covers_cells_ahead_soonest_first() {
+        let debris = (0..3).map(|i| DebrisSnapshot { id: i, position: Vec2::new(1800.0, 100.0), size: 0 }).collect();
+        let config = RegionHintConfig { lookahead_ms: 1000, max_cells: 256, ..Default::default() };
+        let hint = region_hint(&index(debris), 9, Vec2::new(256.0, 256.0), Vec2::new(1000.0, 0.0), 1000.0, &config)
+            .unwrap();
+
+        assert_eq!(hint.tick, 9);
+        // Every cell is ahead (east) and out of view now
+        assert!(hint.cells.iter().all(|c| c.x >= 2));
+        // Cells straight ahead in the order they come into view; only debris is counted
+        let position = |cell: RegionCell| hint.cells.iter().position(|&c| c == cell).unwrap();
+        assert!(position(RegionCell { x: 3, y: 0, debris: 3 }) < position(RegionCell { x: 4, y: 0, debris: 0 }));
+    }
+}
@@ -0,0 +1,599 @@
+//! Match replay files with keyframes, a seek index and chapters
+//!
+//! A replay holds every broadcast snapshot of one match, full and unfiltered,
+//! so it can be watched from any player's point of view. Playback can start
+//! anywhere: every `keyframe_secs` a frame holds the whole snapshot, and the
+//! frames in between only the entities that changed since the frame before
+//! (exactly, see `delta::generate_resync`). Seeking jumps to the last
+//! keyframe at or before the wanted tick and applies at most one keyframe
+//! interval of changes. Chapters mark the moments casters jump to: phase
+//! changes, zone collapses and kills of the match leader.
+//!
+//! Layout (integers little-endian, records bincode like the wire protocol):
+//!
+//! ```text
+//! "ORBR" version:u16
+//! (length:u32 ReplayFrame)*        keyframes and changes, in tick order
+//! ReplayIndex                      keyframe offsets by tick, chapters
+//! index_offset:u64 "ORBI"
+//! ```
+//!
+//! With `REPLAY_DIR` set, the game loop writes one file per match (and starts
+//! a new one after `REPLAY_MAX_MINUTES`). Players who opt out of replays are
+//! recorded as "Anonymous".
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::ReplayConfig;
+use crate::game::constants::physics::TICK_RATE;
+use crate::game::game_loop::GameLoopEvent;
+use crate::game::match_result::ANONYMOUS_NAME;
+use crate::game::state::{MatchPhase, PlayerId};
+use crate::net::delta::{apply_resync, generate_resync};
+use crate::net::highlights::HighlightKind;
+use crate::net::protocol::{GameSnapshot, ResyncUpdate};
+
+/// File extension of replays
+pub const REPLAY_EXTENSION: &str = "orbr";
+
+const MAGIC: &[u8; 4] = b"ORBR";
+const INDEX_MAGIC: &[u8; 4] = b"ORBI";
+const VERSION: u16 = 1;
+/// Magic and version
+const HEADER_LEN: u64 = 6;
+/// Index offset and magic
+const TRAILER_LEN: u64 = 12;
+
+/// One recorded snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ReplayFrame {
+    /// The whole snapshot; playback can start here
+    Keyframe(GameSnapshot),
+    /// What changed since the previous frame
+    Changes(ResyncUpdate),
+}
+
+impl ReplayFrame {
+    fn tick(&self) -> u64 {
+        match self {
+            ReplayFrame::Keyframe(snapshot) => snapshot.tick,
+            ReplayFrame::Changes(changes) => changes.snapshot.tick,
+        }
+    }
+}
+
+/// Where a keyframe starts in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeekEntry {
+    pub tick: u64,
+    pub offset: u64,
+}
+
+/// What a chapter marks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChapterKind {
+    Phase(MatchPhase),
+    ZoneCollapse { phase: u8 },
+    /// The player with the most kills went down
+    LeaderKilled { leader: PlayerId, killer: Option<PlayerId> },
+    /// The moment a highlight clip is about (see `net::highlights`)
+    Highlight { player: PlayerId, highlight: HighlightKind },
+}
+
+/// A notable moment of the match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub tick: u64,
+    pub kind: ChapterKind,
+    pub title: String,
+}
+
+/// Everything needed to navigate a replay without reading its frames
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayIndex {
+    pub first_tick: u64,
+    pub last_tick: u64,
+    pub frames: u64,
+    pub keyframes: Vec<SeekEntry>,
+    pub chapters: Vec<Chapter>,
+}
+
+/// Why a replay could not be written or read
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("replay I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a replay file")]
+    NotAReplay,
+    #[error("unsupported replay version {0}")]
+    UnsupportedVersion(u16),
+    #[error("corrupt replay: {0}")]
+    Corrupt(String),
+    #[error("tick {tick} is outside the replay ({first}-{last})")]
+    OutOfRange { tick: u64, first: u64, last: u64 },
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, ReplayError> {
+    bincode::serde::encode_to_vec(value, bincode::config::legacy()).map_err(|e| ReplayError::Corrupt(e.to_string()))
+}
+
+fn decode<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Result<T, ReplayError> {
+    bincode::serde::decode_from_slice(data, bincode::config::legacy())
+        .map(|(value, _)| value)
+        .map_err(|e| ReplayError::Corrupt(e.to_string()))
+}
+
+/// Writes snapshots and chapters to a replay, finished by [`ReplayWriter::finish`]
+pub struct ReplayWriter<W: Write> {
+    out: W,
+    offset: u64,
+    keyframe_interval: u64,
+    previous: Option<GameSnapshot>,
+    index: ReplayIndex,
+}
+
+impl<W: Write> ReplayWriter<W> {
+    /// Start a replay with a keyframe at least every `keyframe_interval` ticks
+    pub fn new(mut out: W, keyframe_interval: u64) -> Result<Self, ReplayError> {
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self {
+            out,
+            offset: HEADER_LEN,
+            keyframe_interval: keyframe_interval.max(1),
+            previous: None,
+            index: ReplayIndex::default(),
+        })
+    }
+
+    /// Ticks since the first recorded snapshot
+    pub fn duration_ticks(&self) -> u64 {
+        self.index.last_tick - self.index.first_tick
+    }
+
+    /// Append a snapshot (ticks must increase)
+    pub fn record(&mut self, snapshot: &GameSnapshot) -> Result<(), ReplayError> {
+        let keyframe_due = match (self.index.keyframes.last(), &self.previous) {
+            (Some(last), Some(_)) => snapshot.tick >= last.tick + self.keyframe_interval,
+            _ => true,
+        };
+        let frame = match &self.previous {
+            Some(previous) if !keyframe_due => ReplayFrame::Changes(generate_resync(previous, snapshot)),
+            _ => ReplayFrame::Keyframe(snapshot.clone()),
+        };
+        if keyframe_due {
+            self.index.keyframes.push(SeekEntry { tick: snapshot.tick, offset: self.offset });
+        }
+        if self.index.frames == 0 {
+            self.index.first_tick = snapshot.tick;
+        }
+        self.index.last_tick = snapshot.tick;
+        self.index.frames += 1;
+        self.write_record(&frame)?;
+        self.previous = Some(snapshot.clone());
+        Ok(())
+    }
+
+    pub fn chapter(&mut self, chapter: Chapter) {
+        self.index.chapters.push(chapter);
+    }
+
+    /// Write the index and trailer, returning the output
+    pub fn finish(mut self) -> Result<W, ReplayError> {
+        let index_offset = self.offset;
+        let index = encode(&self.index)?;
+        self.out.write_all(&index)?;
+        self.out.write_all(&index_offset.to_le_bytes())?;
+        self.out.write_all(INDEX_MAGIC)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn write_record(&mut self, frame: &ReplayFrame) -> Result<(), ReplayError> {
+        let data = encode(frame)?;
+        self.out.write_all(&(data.len() as u32).to_le_bytes())?;
+        self.out.write_all(&data)?;
+        self.offset += 4 + data.len() as u64;
+        Ok(())
+    }
+}
+
+/// Reads a finished replay
+pub struct ReplayReader<R: Read + Seek> {
+    input: R,
+    index: ReplayIndex,
+    /// Where the frames end (the index starts)
+    frames_end: u64,
+}
+
+impl<R: Read + Seek> ReplayReader<R> {
+    /// Check the header and load the index
+    pub fn open(mut input: R) -> Result<Self, ReplayError> {
+        let mut header = [0u8; HEADER_LEN as usize];
+        input.read_exact(&mut header).map_err(|_| ReplayError::NotAReplay)?;
+        if &header[..4] != MAGIC {
+            return Err(ReplayError::NotAReplay);
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let len = input.seek(SeekFrom::End(0))?;
+        if len < HEADER_LEN + TRAILER_LEN {
+            return Err(ReplayError::Corrupt("unfinished replay (no index)".to_string()));
+        }
+        input.seek(SeekFrom::Start(len - TRAILER_LEN))?;
+        let mut trailer = [0u8; TRAILER_LEN as usize];
+        input.read_exact(&mut trailer)?;
+        if &trailer[8..] != INDEX_MAGIC {
+            return Err(ReplayError::Corrupt("unfinished replay (no index)".to_string()));
+        }
+        let frames_end = u64::from_le_bytes(trailer[..8].try_into().expect("8 bytes"));
+        if !(HEADER_LEN..=len - TRAILER_LEN).contains(&frames_end) {
+            return Err(ReplayError::Corrupt("index offset out of range".to_string()));
+        }
+        input.seek(SeekFrom::Start(frames_end))?;
+        let mut index = vec![0u8; (len - TRAILER_LEN - frames_end) as usize];
+        input.read_exact(&mut index)?;
+        Ok(Self { input, index: decode(&index)?, frames_end })
+    }
+
+    pub fn index(&self) -> &ReplayIndex {
+        &self.index
+    }
+
+    /// Playback positioned on the last frame at or before `tick`
+    pub fn seek(&mut self, tick: u64) -> Result<Playback<'_, R>, ReplayError> {
+        let out_of_range = ReplayError::OutOfRange { tick, first: self.index.first_tick, last: self.index.last_tick };
+        let Some(keyframe) = self.index.keyframes.iter().rev().find(|k| k.tick <= tick).copied() else {
+            return Err(out_of_range);
+        };
+        if self.index.frames == 0 || tick > self.index.last_tick {
+            return Err(out_of_range);
+        }
+        self.input.seek(SeekFrom::Start(keyframe.offset))?;
+        let mut playback = Playback { reader: self, current: None };
+        playback.advance()?;
+        loop {
+            let position = playback.reader.input.stream_position()?;
+            if position >= playback.reader.frames_end {
+                break;
+            }
+            let frame = playback.reader.read_frame()?;
+            if frame.tick() > tick {
+                playback.reader.input.seek(SeekFrom::Start(position))?;
+                break;
+            }
+            playback.apply(frame)?;
+        }
+        Ok(playback)
+    }
+
+    fn read_frame(&mut self) -> Result<ReplayFrame, ReplayError> {
+        let mut len = [0u8; 4];
+        self.input.read_exact(&mut len)?;
+        let mut data = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.input.read_exact(&mut data)?;
+        decode(&data)
+    }
+}
+
+/// Snapshots of a replay from a seek position on
+pub struct Playback<'a, R: Read + Seek> {
+    reader: &'a mut ReplayReader<R>,
+    current: Option<GameSnapshot>,
+}
+
+impl<R: Read + Seek> Playback<'_, R> {
+    /// The snapshot at the current position
+    pub fn current(&self) -> &GameSnapshot {
+        self.current.as_ref().expect("seek reads a keyframe first")
+    }
+
+    /// Move to the next frame, returning its snapshot (None at the end)
+    pub fn advance(&mut self) -> Result<Option<&GameSnapshot>, ReplayError> {
+        if self.reader.input.stream_position()? >= self.reader.frames_end {
+            return Ok(None);
+        }
+        let frame = self.reader.read_frame()?;
+        self.apply(frame)?;
+        Ok(self.current.as_ref())
+    }
+
+    fn apply(&mut self, frame: ReplayFrame) -> Result<(), ReplayError> {
+        self.current = Some(match (frame, &self.current) {
+            (ReplayFrame::Keyframe(snapshot), _) => snapshot,
+            (ReplayFrame::Changes(changes), Some(base)) if changes.base_tick == base.tick => {
+                apply_resync(base, &changes)
+            }
+            (ReplayFrame::Changes(changes), _) => {
+                return Err(ReplayError::Corrupt(format!("changes for tick {} have no base", changes.snapshot.tick)))
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Records the game loop's matches into replay files, one per match
+pub struct ReplayRecorder {
+    config: ReplayConfig,
+    writer: Option<(PathBuf, ReplayWriter<BufWriter<File>>)>,
+    /// Player with the most kills as of the last recorded tick
+    leader: Option<PlayerId>,
+    /// Players shown as "Anonymous"
+    anonymous: HashSet<PlayerId>,
+}
+
+impl ReplayRecorder {
+    pub fn new(config: ReplayConfig) -> Self {
+        Self { config, writer: None, leader: None, anonymous: HashSet::new() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.dir.is_some()
+    }
+
+    /// Turn a tick's events into chapters, starting a replay when a countdown
+    /// begins and finishing it when the match ends
+    pub fn observe(&mut self, events: &[GameLoopEvent], snapshot: &GameSnapshot) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut ended = false;
+        for event in events {
+            let (kind, title) = match event {
+                GameLoopEvent::PhaseChange { phase, .. } => {
+                    if *phase == MatchPhase::Countdown {
+                        self.finish();
+                        self.start(snapshot.tick);
+                    }
+                    let title = match phase {
+                        MatchPhase::Waiting => "Waiting for players",
+                        MatchPhase::Countdown => "Countdown",
+                        MatchPhase::Playing => "Match started",
+                        MatchPhase::Ended => "Match ended",
+                    };
+                    (ChapterKind::Phase(*phase), title.to_string())
+                }
+                GameLoopEvent::ZoneCollapse { phase, .. } => {
+                    (ChapterKind::ZoneCollapse { phase: *phase }, format!("Zone collapse {}", phase))
+                }
+                GameLoopEvent::PlayerKilled { killer_id, victim_id } if Some(*victim_id) == self.leader => {
+                    let (killer, leader) = (self.name(snapshot, *killer_id), self.name(snapshot, *victim_id));
+                    let title = format!("{} took down leader {}", killer, leader);
+                    (ChapterKind::LeaderKilled { leader: *victim_id, killer: Some(*killer_id) }, title)
+                }
+                GameLoopEvent::MatchEnded { .. } => {
+                    ended = true;
+                    (ChapterKind::Phase(MatchPhase::Ended), "Match ended".to_string())
+                }
+                _ => continue,
+            };
+            if let Some((_, writer)) = &mut self.writer {
+                writer.chapter(Chapter { tick: snapshot.tick, kind, title });
+            }
+        }
+        self.leader = snapshot.players.iter().filter(|p| p.kills > 0).max_by_key(|p| p.kills).map(|p| p.id);
+        if ended {
+            self.record(snapshot, &[]);
+            self.finish();
+        }
+    }
+
+    /// Record a broadcast snapshot, with `anonymous` players' names hidden.
+    /// A match already under way when recording starts gets a replay too.
+    pub fn record(&mut self, snapshot: &GameSnapshot, anonymous: &[PlayerId]) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.anonymous.extend(anonymous);
+        let max_ticks = self.config.max_minutes * 60 * TICK_RATE as u64;
+        match &self.writer {
+            Some((_, writer)) if writer.duration_ticks() >= max_ticks => {
+                self.finish();
+                self.start(snapshot.tick);
+            }
+            None if matches!(snapshot.match_phase, MatchPhase::Countdown | MatchPhase::Playing) => {
+                self.start(snapshot.tick)
+            }
+            _ => {}
+        }
+        let Some((path, writer)) = &mut self.writer else {
+            return;
+        };
+
+        if let Err(e) = writer.record(&anonymized(snapshot, &self.anonymous)) {
+            warn!("Stopped recording replay {}: {}", path.display(), e);
+            self.writer = None;
+        }
+    }
+
+    /// Finish the replay being recorded, if any
+    pub fn finish(&mut self) {
+        let Some((path, writer)) = self.writer.take() else {
+            return;
+        };
+        self.anonymous.clear();
+        match writer.finish() {
+            Ok(_) => info!("Saved replay {}", path.display()),
+            Err(e) => warn!("Failed to finish replay {}: {}", path.display(), e),
+        }
+    }
+
+    fn start(&mut self, tick: u64) {
+        let Some(dir) = &self.config.dir else {
+            return;
+        };
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let path = Path::new(dir).join(format!("match-{}-{}.{}", secs, tick, REPLAY_EXTENSION));
+        let keyframe_interval = self.config.keyframe_secs * TICK_RATE as u64;
+        let opened = fs::create_dir_all(dir)
+            .and_then(|_| File::create(&path))
+            .map_err(ReplayError::from)
+            .and_then(|file| ReplayWriter::new(BufWriter::new(file), keyframe_interval));
+        match opened {
+            Ok(writer) => self.writer = Some((path, writer)),
+            Err(e) => warn!("Failed to start replay {}: {}", path.display(), e),
+        }
+    }
+
+    fn name(&self, snapshot: &GameSnapshot, player_id: PlayerId) -> String {
+        if self.anonymous.contains(&player_id) {
+            return ANONYMOUS_NAME.to_string();
+        }
+        let player = snapshot.players.iter().find(|p| p.id == player_id);
+        player.map_or_else(|| "Someone".to_string(), |p| p.name.clone())
+    }
+}
+
+/// `snapshot` with the names of `anonymous` players hidden (copied only if any are in it)
+pub fn anonymized<'a>(snapshot: &'a GameSnapshot, anonymous: &HashSet<PlayerId>) -> Cow<'a, GameSnapshot> {
+    if !snapshot.players.iter().any(|p| anonymous.contains(&p.id)) {
+        return Cow::Borrowed(snapshot);
+    }
+    let mut snapshot = snapshot.clone();
+    for player in snapshot.players.iter_mut().filter(|p| anonymous.contains(&p.id)) {
+        player.name = ANONYMOUS_NAME.to_string();
+    }
+    Cow::Owned(snapshot)
+}
+
+/// Delete replays in `dir` last written before `cutoff` (Unix seconds), returning how many
+pub fn purge_before(dir: &str, cutoff: u64) -> Result<usize, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Failed to list replays: {}", e)),
+    };
+    let mut purged = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(REPLAY_EXTENSION) {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|m| m.modified()).ok();
+        let secs = modified.and_then(|m| m.duration_since(UNIX_EPOCH).ok()).map(|d| d.as_secs());
+        if secs.is_some_and(|secs| secs < cutoff) {
+            fs::remove_file(&path).map_err(|e| format!("Failed to delete replay {}: {}", path.display(), e))?;
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::protocol::{player_flags, PlayerSnapshot};
+    use crate::util::vec2::Vec2;
+    use std::io::Cursor;
+    use uuid::Uuid;
+
+    fn player(id: PlayerId, x: f32, kills: u32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            id,
+            name: format!("P{}", kills),
+            position: Vec2::new(x, 0.0),
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass: 100.0,
+            flags: player_flags::ALIVE,
+            kills,
+            deaths: 0,
+            color_index: 0,
+            spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
+        }
+    }
+
+    fn snapshot(tick: u64, players: Vec<PlayerSnapshot>) -> GameSnapshot {
+        let mut snapshot = (*crate::net::state_view::PublishedState::default().load().snapshot).clone();
+        snapshot.tick = tick;
+        snapshot.match_phase = MatchPhase::Playing;
+        snapshot.players = players;
+        snapshot
+    }
+
+    #[test]
+    fn test_seek_starts_playback_mid_match() {
+        let (mover, idle) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut writer = ReplayWriter::new(Cursor::new(Vec::new()), 10).unwrap();
+        for tick in 0..35 {
+            let mut players = vec![player(mover, tick as f32, 0)];
+            // One player leaves halfway through
+            if tick < 17 {
+                players.push(player(idle, -50.0, 1));
+            }
+            writer.record(&snapshot(tick, players)).unwrap();
+        }
+        writer.chapter(Chapter { tick: 20, kind: ChapterKind::ZoneCollapse { phase: 1 }, title: "Zone".into() });
+        let data = writer.finish().unwrap().into_inner();
+
+        let mut reader = ReplayReader::open(Cursor::new(data)).unwrap();
+        let keyframe_ticks: Vec<u64> = reader.index().keyframes.iter().map(|k| k.tick).collect();
+        assert_eq!(keyframe_ticks, vec![0, 10, 20, 30]);
+        assert_eq!(reader.index().chapters[0].tick, 20);
+
+        let mut playback = reader.seek(17).unwrap();
+        assert_eq!(playback.current().tick, 17);
+        assert_eq!(playback.current().players.len(), 1);
+        assert_eq!(playback.current().players[0].position, Vec2::new(17.0, 0.0));
+        let next = playback.advance().unwrap().unwrap();
+        assert_eq!((next.tick, next.players[0].position), (18, Vec2::new(18.0, 0.0)));
+
+        let mut playback = reader.seek(34).unwrap();
+        assert_eq!(playback.current().tick, 34);
+        assert!(playback.advance().unwrap().is_none());
+        assert!(matches!(reader.seek(35), Err(ReplayError::OutOfRange { tick: 35, first: 0, last: 34 })));
+    }
+
+    #[test]
+    fn test_unfinished_or_foreign_files_are_rejected() {
+        let mut writer = ReplayWriter::new(Vec::new(), 10).unwrap();
+        writer.record(&snapshot(1, vec![])).unwrap();
+        assert!(matches!(ReplayReader::open(Cursor::new(writer.out.clone())), Err(ReplayError::Corrupt(_))));
+        assert!(matches!(ReplayReader::open(Cursor::new(b"{\"json\": true}".to_vec())), Err(ReplayError::NotAReplay)));
+    }
+
+    #[test]
+    fn test_recorder_marks_leader_kills_and_hides_opted_out_names() {
+        let dir = std::env::temp_dir().join(format!("orbit_replays_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let config = ReplayConfig { dir: Some(dir.to_str().unwrap().to_string()), ..ReplayConfig::default() };
+        let mut recorder = ReplayRecorder::new(config);
+        let (leader, challenger, private) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let countdown = [GameLoopEvent::PhaseChange { phase: MatchPhase::Countdown, countdown: 3.0 }];
+        let first = snapshot(1, vec![player(leader, 0.0, 5), player(challenger, 10.0, 2), player(private, 20.0, 0)]);
+        recorder.observe(&countdown, &first);
+        recorder.record(&first, &[private]);
+        let kill = [GameLoopEvent::PlayerKilled { killer_id: challenger, victim_id: leader }];
+        recorder.observe(&kill, &snapshot(2, vec![player(leader, 0.0, 5), player(challenger, 10.0, 3)]));
+        recorder.finish();
+
+        let path = fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+        let mut reader = ReplayReader::open(File::open(&path).unwrap()).unwrap();
+        let titles: Vec<&str> = reader.index().chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Countdown", "P3 took down leader P5"]);
+        let playback = reader.seek(1).unwrap();
+        let names: Vec<&str> = playback.current().players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["P5", "P2", ANONYMOUS_NAME]);
+
+        assert_eq!(purge_before(dir.to_str().unwrap(), 0), Ok(0));
+        assert_eq!(purge_before(dir.to_str().unwrap(), u64::MAX), Ok(1));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
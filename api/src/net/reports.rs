@@ -0,0 +1,350 @@
+//! Player reports and karma
+//!
+//! Players report each other with `ClientMessage::Report`. Each reporter may
+//! file `max_per_window` reports per window and report the same player once
+//! per window. A report is weighted by the reporter's credibility: their own
+//! karma, scaled by how often moderators upheld their past reports.
+//!
+//! A report costs the target karma in proportion to its weight. Karma slowly
+//! recovers while no reports come in, and matchmaking places low-karma players
+//! together. Once a player's open reports add up to `review_threshold`, a case
+//! opens in the moderation review queue. Moderators resolve cases with
+//! `/resolve`: upheld reports raise their reporters' credibility, and
+//! dismissed ones lower it and refund the karma they cost.
+//!
+//! Everything is kept by account (see `net::account`), so reconnecting resets
+//! neither a player's karma nor their report limits.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ReportConfig;
+use crate::net::account::AccountId;
+use crate::net::i18n::{keys, LocalizedText};
+use crate::net::moderation::unix_millis;
+
+/// Karma of a player nobody has reported
+pub const MAX_KARMA: f32 = 100.0;
+
+/// Credibility bounds (1.0 for players without a track record)
+const MIN_CREDIBILITY: f32 = 0.1;
+const MAX_CREDIBILITY: f32 = 2.0;
+
+/// Credibility gained per upheld report
+const UPHELD_CREDIBILITY_BONUS: f32 = 0.1;
+
+/// Credibility multiplier per dismissed report
+const DISMISSED_CREDIBILITY_FACTOR: f32 = 0.5;
+
+/// Open reports older than this no longer count toward a review
+const REPORT_EXPIRY: Duration = Duration::from_secs(24 * 3600);
+
+/// What a player was reported for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReportReason {
+    Cheating,
+    Harassment,
+    Griefing,
+    Spam,
+    Other,
+}
+
+/// Why a report was not accepted
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReportError {
+    #[error("You can't report yourself")]
+    SelfReport,
+    #[error("No such player")]
+    UnknownTarget,
+    #[error("Too many reports, try again later")]
+    RateLimited,
+    #[error("You already reported this player recently")]
+    AlreadyReported,
+}
+
+impl ReportError {
+    /// The error as a localizable message for the reporter
+    pub fn message(&self) -> LocalizedText {
+        LocalizedText::new(match self {
+            ReportError::SelfReport => keys::REPORT_SELF,
+            ReportError::UnknownTarget => keys::REPORT_UNKNOWN_TARGET,
+            ReportError::RateLimited => keys::REPORT_RATE_LIMITED,
+            ReportError::AlreadyReported => keys::REPORT_DUPLICATE,
+        })
+    }
+}
+
+/// A player's reputation
+#[derive(Debug, Clone, Copy)]
+struct Standing {
+    karma: f32,
+    credibility: f32,
+    /// When recovery was last applied to `karma`
+    updated: Instant,
+}
+
+impl Standing {
+    fn new(now: Instant) -> Self {
+        Self { karma: MAX_KARMA, credibility: 1.0, updated: now }
+    }
+}
+
+/// A report awaiting review
+#[derive(Debug, Clone, Copy)]
+struct OpenReport {
+    reporter: AccountId,
+    reason: ReportReason,
+    weight: f32,
+    at: Instant,
+}
+
+/// A player in the moderation review queue
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewCase {
+    pub target: AccountId,
+    pub target_name: String,
+    /// Sum of the weights of the open reports
+    pub score: f32,
+    /// Report count per reason, most reported first
+    pub reasons: Vec<(ReportReason, u32)>,
+    /// Unix time in milliseconds
+    pub opened_at_ms: u64,
+}
+
+/// Reports, karma and the review queue of one game session
+#[derive(Debug)]
+pub struct ReportBook {
+    config: ReportConfig,
+    standings: HashMap<AccountId, Standing>,
+    /// When each reporter filed their recent reports
+    filed: HashMap<AccountId, Vec<Instant>>,
+    /// Open reports against each player
+    open: HashMap<AccountId, Vec<OpenReport>>,
+    /// Cases awaiting a moderator, oldest first
+    queue: Vec<ReviewCase>,
+}
+
+impl ReportBook {
+    pub fn new(config: ReportConfig) -> Self {
+        Self {
+            config,
+            standings: HashMap::new(),
+            filed: HashMap::new(),
+            open: HashMap::new(),
+            queue: Vec::new(),
+        }
+    }
+
+    /// A player's standing with karma recovery applied up to `now`
+    fn standing(&mut self, account: AccountId, now: Instant) -> &mut Standing {
+        let recovery_per_sec = self.config.karma_recovery_per_hour / 3600.0;
+        let standing = self.standings.entry(account).or_insert_with(|| Standing::new(now));
+        let elapsed = now.saturating_duration_since(standing.updated).as_secs_f32();
+        standing.karma = (standing.karma + recovery_per_sec * elapsed).min(MAX_KARMA);
+        standing.updated = now;
+        standing
+    }
+
+    /// Current karma of an account (0-100)
+    pub fn karma(&mut self, account: AccountId, now: Instant) -> f32 {
+        self.standing(account, now).karma
+    }
+
+    /// File a report, returning its weight
+    pub fn report(
+        &mut self,
+        reporter: AccountId,
+        target: AccountId,
+        target_name: &str,
+        reason: ReportReason,
+        now: Instant,
+    ) -> Result<f32, ReportError> {
+        if reporter == target {
+            return Err(ReportError::SelfReport);
+        }
+        self.prune(now);
+
+        let window = Duration::from_secs(self.config.window_secs);
+        let filed = self.filed.entry(reporter).or_default();
+        filed.retain(|&at| now.saturating_duration_since(at) < window);
+        if filed.len() >= self.config.max_per_window as usize {
+            return Err(ReportError::RateLimited);
+        }
+        let open = self.open.entry(target).or_default();
+        open.retain(|r| now.saturating_duration_since(r.at) < REPORT_EXPIRY);
+        if open.iter().any(|r| r.reporter == reporter && now.saturating_duration_since(r.at) < window) {
+            return Err(ReportError::AlreadyReported);
+        }
+        filed.push(now);
+
+        let reporter_standing = *self.standing(reporter, now);
+        let weight = reporter_standing.credibility * reporter_standing.karma / MAX_KARMA;
+        let penalty = self.config.karma_penalty * weight;
+        let target_standing = self.standing(target, now);
+        target_standing.karma = (target_standing.karma - penalty).max(0.0);
+
+        let open = self.open.entry(target).or_default();
+        open.push(OpenReport { reporter, reason, weight, at: now });
+        let score: f32 = open.iter().map(|r| r.weight).sum();
+        let reasons = reason_counts(open);
+
+        match self.queue.iter_mut().find(|case| case.target == target) {
+            Some(case) => {
+                case.score = score;
+                case.reasons = reasons;
+            }
+            None if score >= self.config.review_threshold => self.queue.push(ReviewCase {
+                target,
+                target_name: target_name.to_string(),
+                score,
+                reasons,
+                opened_at_ms: unix_millis(),
+            }),
+            None => {}
+        }
+        Ok(weight)
+    }
+
+    /// Cases awaiting a moderator, oldest first
+    pub fn review_queue(&self) -> &[ReviewCase] {
+        &self.queue
+    }
+
+    /// Close a player's case. Upheld reports raise their reporters' credibility;
+    /// dismissed ones lower it and give the target back the karma they cost.
+    pub fn resolve(&mut self, target: AccountId, upheld: bool, now: Instant) -> Option<ReviewCase> {
+        let index = self.queue.iter().position(|case| case.target == target)?;
+        let case = self.queue.remove(index);
+
+        let penalty = self.config.karma_penalty;
+        for report in self.open.remove(&target).unwrap_or_default() {
+            let reporter = self.standing(report.reporter, now);
+            reporter.credibility = if upheld {
+                (reporter.credibility + UPHELD_CREDIBILITY_BONUS).min(MAX_CREDIBILITY)
+            } else {
+                (reporter.credibility * DISMISSED_CREDIBILITY_FACTOR).max(MIN_CREDIBILITY)
+            };
+            if !upheld {
+                let reported = self.standing(target, now);
+                reported.karma = (reported.karma + penalty * report.weight).min(MAX_KARMA);
+            }
+        }
+        Some(case)
+    }
+
+    /// Forget players with a clean record (full karma, default credibility,
+    /// nothing open and no recent reports filed)
+    fn prune(&mut self, now: Instant) {
+        let window = Duration::from_secs(self.config.window_secs);
+        self.filed.retain(|_, times| times.iter().any(|&at| now.saturating_duration_since(at) < window));
+        self.open.retain(|_, reports| {
+            reports.retain(|r| now.saturating_duration_since(r.at) < REPORT_EXPIRY);
+            !reports.is_empty()
+        });
+        let queue = &self.queue;
+        let open = &self.open;
+        let filed = &self.filed;
+        let recovery_per_sec = self.config.karma_recovery_per_hour / 3600.0;
+        self.standings.retain(|id, standing| {
+            let elapsed = now.saturating_duration_since(standing.updated).as_secs_f32();
+            standing.karma + recovery_per_sec * elapsed < MAX_KARMA
+                || standing.credibility != 1.0
+                || open.contains_key(id)
+                || filed.contains_key(id)
+                || queue.iter().any(|case| case.target == *id)
+        });
+    }
+}
+
+/// Report count per reason, most reported first
+fn reason_counts(reports: &[OpenReport]) -> Vec<(ReportReason, u32)> {
+    let mut counts: Vec<(ReportReason, u32)> = Vec::new();
+    for report in reports {
+        match counts.iter_mut().find(|(reason, _)| *reason == report.reason) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((report.reason, 1)),
+        }
+    }
+    counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> ReportBook {
+        ReportBook::new(ReportConfig { review_threshold: 2.0, ..ReportConfig::default() })
+    }
+
+    #[test]
+    fn test_rate_limit_and_duplicates() {
+        let mut book = book();
+        let now = Instant::now();
+        let reporter = AccountId::generate();
+        let target = AccountId::generate();
+
+        assert_eq!(book.report(reporter, reporter, "Me", ReportReason::Spam, now), Err(ReportError::SelfReport));
+        assert_eq!(book.report(reporter, target, "T", ReportReason::Spam, now), Ok(1.0));
+        assert_eq!(book.report(reporter, target, "T", ReportReason::Spam, now), Err(ReportError::AlreadyReported));
+        for _ in 1..book.config.max_per_window {
+            book.report(reporter, AccountId::generate(), "Other", ReportReason::Other, now).unwrap();
+        }
+        assert_eq!(
+            book.report(reporter, AccountId::generate(), "Other", ReportReason::Other, now),
+            Err(ReportError::RateLimited)
+        );
+
+        // A new window lifts both limits
+        let later = now + Duration::from_secs(book.config.window_secs);
+        assert!(book.report(reporter, target, "T", ReportReason::Cheating, later).is_ok());
+    }
+
+    #[test]
+    fn test_karma_drops_and_recovers() {
+        let mut book = book();
+        let now = Instant::now();
+        let target = AccountId::generate();
+        for _ in 0..7 {
+            book.report(AccountId::generate(), target, "T", ReportReason::Harassment, now).unwrap();
+        }
+        assert_eq!(book.karma(target, now), MAX_KARMA - 7.0 * book.config.karma_penalty);
+        assert!(book.karma(target, now) < book.config.low_karma_threshold);
+
+        let hour_later = now + Duration::from_secs(3600);
+        let expected = MAX_KARMA - 7.0 * book.config.karma_penalty + book.config.karma_recovery_per_hour;
+        assert!((book.karma(target, hour_later) - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_review_queue_and_credibility() {
+        let mut book = book();
+        let now = Instant::now();
+        let (honest, liar) = (AccountId::generate(), AccountId::generate());
+        let (cheater, victim) = (AccountId::generate(), AccountId::generate());
+
+        // Weighted score reaches the threshold on the second report
+        book.report(honest, cheater, "Cheater", ReportReason::Cheating, now).unwrap();
+        assert!(book.review_queue().is_empty());
+        book.report(AccountId::generate(), cheater, "Cheater", ReportReason::Cheating, now).unwrap();
+        assert_eq!(book.review_queue().len(), 1);
+        assert_eq!(book.review_queue()[0].reasons, vec![(ReportReason::Cheating, 2)]);
+
+        let case = book.resolve(cheater, true, now).unwrap();
+        assert_eq!(case.target_name, "Cheater");
+        assert!(book.review_queue().is_empty());
+
+        // Dismissed reports refund karma and discount the reporter
+        book.report(liar, victim, "Victim", ReportReason::Griefing, now).unwrap();
+        book.report(AccountId::generate(), victim, "Victim", ReportReason::Spam, now).unwrap();
+        book.resolve(victim, false, now).unwrap();
+        assert_eq!(book.karma(victim, now), MAX_KARMA);
+
+        let upheld_weight = book.report(honest, AccountId::generate(), "X", ReportReason::Other, now).unwrap();
+        let dismissed_weight = book.report(liar, AccountId::generate(), "Y", ReportReason::Other, now).unwrap();
+        assert!(upheld_weight > 1.0);
+        assert!(dismissed_weight < 1.0);
+    }
+}
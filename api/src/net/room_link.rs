@@ -0,0 +1,241 @@
+//! Connections in lobby rooms
+//!
+//! Lobby rooms run their own game loops beside the main arena. A connection
+//! that joined a room (`JoinRequest.room`) gets the room's snapshots and its
+//! lobby updates (galaxy maps, queue estimates) through a [`RoomLinks`]
+//! channel instead of the game session, as does one spectating a room
+//! (`SpectateRoom`). The lobby loop ticks every room and flushes those messages.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, RwLock};
+use tracing::{debug, warn};
+
+use crate::game::constants::physics;
+use crate::game::state::PlayerId;
+use crate::lobby::manager::LobbyManager;
+use crate::lobby::room::RoomState;
+use crate::metrics::Metrics;
+use crate::net::egress::{MessageCategory, Outbound};
+use crate::net::game_session::{encode_pooled, TICKS_PER_SNAPSHOT};
+use crate::net::protocol::ServerMessage;
+use crate::server::ShutdownToken;
+
+/// Outbound channels of the connections in lobby rooms
+#[derive(Debug, Default)]
+pub struct RoomLinks {
+    players: HashMap<PlayerId, mpsc::UnboundedSender<Outbound>>,
+    /// Spectating connections, by connection id
+    spectators: HashMap<u64, mpsc::UnboundedSender<Outbound>>,
+}
+
+impl RoomLinks {
+    /// Send a room player's messages down `sender` (replacing any earlier link)
+    pub fn link_player(&mut self, player_id: PlayerId, sender: mpsc::UnboundedSender<Outbound>) {
+        self.players.insert(player_id, sender);
+    }
+
+    /// Drop a player's link, ending its writer task
+    pub fn unlink_player(&mut self, player_id: PlayerId) {
+        self.players.remove(&player_id);
+    }
+
+    /// Send a spectating connection's room messages down `sender`
+    pub fn link_spectator(&mut self, connection_id: u64, sender: mpsc::UnboundedSender<Outbound>) {
+        self.spectators.insert(connection_id, sender);
+    }
+
+    /// Drop a spectator's link, ending its writer task
+    pub fn unlink_spectator(&mut self, connection_id: u64) {
+        self.spectators.remove(&connection_id);
+    }
+
+    /// Send one message to one linked player
+    pub fn send(&self, player_id: PlayerId, message: &ServerMessage) {
+        if let Some(sender) = self.players.get(&player_id) {
+            if let Some(data) = encode_shared(message) {
+                let _ = sender.send((MessageCategory::of(message), data));
+            }
+        }
+    }
+
+    /// Send one message to every linked player in `players` and spectator
+    /// in `spectators`, encoded once
+    pub fn broadcast(&self, players: &[PlayerId], spectators: &[u64], message: &ServerMessage) {
+        let senders: Vec<_> = players
+            .iter()
+            .filter_map(|id| self.players.get(id))
+            .chain(spectators.iter().filter_map(|id| self.spectators.get(id)))
+            .collect();
+        if senders.is_empty() {
+            return;
+        }
+        let Some(data) = encode_shared(message) else {
+            return;
+        };
+        let category = MessageCategory::of(message);
+        for sender in senders {
+            let _ = sender.send((category, data.clone()));
+        }
+    }
+}
+
+fn encode_shared(message: &ServerMessage) -> Option<Arc<Vec<u8>>> {
+    match encode_pooled(message) {
+        Ok(data) => Some(Arc::new(data)),
+        Err(e) => {
+            warn!("Failed to encode room message: {}", e);
+            None
+        }
+    }
+}
+
+/// Spawn a writer task for a connection's room messages, returning its channel.
+/// The task ends when the link is dropped or the stream fails.
+pub fn spawn_link_writer(
+    writer: Arc<RwLock<Option<wtransport::SendStream>>>,
+    metrics: Arc<Metrics>,
+) -> mpsc::UnboundedSender<Outbound> {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Outbound>();
+    tokio::spawn(async move {
+        while let Some((category, data)) = receiver.recv().await {
+            let mut guard = writer.write().await;
+            let Some(stream) = guard.as_mut() else { break };
+            let mut frame = Vec::with_capacity(4 + data.len());
+            frame.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            frame.extend_from_slice(&data);
+            if let Err(e) = stream.write_all(&frame).await {
+                debug!("Room link write failed: {}", e);
+                break;
+            }
+            if let Err(e) = stream.flush().await {
+                debug!("Room link flush failed: {}", e);
+                break;
+            }
+            metrics.egress.record(None, category, data.len());
+        }
+    });
+    sender
+}
+
+/// Run one lobby tick: advance every room, then send galaxy maps, queue
+/// estimates and, when `send_snapshots`, each running room's snapshot to its
+/// linked players and spectators
+pub fn lobby_tick(lobby: &mut LobbyManager, send_snapshots: bool) {
+    lobby.update_all();
+
+    let mut updates = lobby.take_galaxy_updates();
+    updates.extend(lobby.take_queue_updates());
+    for (player_id, message) in updates {
+        lobby.links().send(player_id, &message);
+    }
+
+    if send_snapshots {
+        for room in lobby.rooms().filter(|room| room.state != RoomState::Waiting) {
+            let snapshot = ServerMessage::Snapshot(room.get_snapshot());
+            let spectators = lobby.room_spectators(room.id);
+            lobby.links().broadcast(&room.connected_player_ids(), &spectators, &snapshot);
+        }
+    }
+}
+
+/// Tick the lobby's rooms at the game's tick rate until `shutdown` fires
+pub fn start_lobby_loop(lobby: Arc<RwLock<LobbyManager>>, shutdown: ShutdownToken) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_millis(physics::TICK_DURATION_MS));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let stopped = shutdown.triggered();
+        tokio::pin!(stopped);
+        let mut tick: u32 = 0;
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = &mut stopped => break,
+            }
+            tick = tick.wrapping_add(1);
+            lobby_tick(&mut *lobby.write().await, tick % TICKS_PER_SNAPSHOT == 0);
+        }
+        debug!("Lobby loop stopped");
+    });
+}
+
+#[cfg(test)]
+mod room_link_tests {
+    use super::*;
+    use crate::lobby::galaxy::GalaxyTopology;
+    use crate::lobby::player::LobbyPlayer;
+    use crate::net::protocol::decode;
+    use crate::net::session::SessionToken;
+    use uuid::Uuid;
+
+    fn received(receiver: &mut mpsc::UnboundedReceiver<Outbound>) -> Vec<ServerMessage> {
+        std::iter::from_fn(|| receiver.try_recv().ok()).map(|(_, data)| decode(&data).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_lobby_tick_sends_galaxy_maps_and_snapshots_to_linked_players() {
+        let mut lobby = LobbyManager::new(10);
+        let topology: GalaxyTopology = serde_json::from_str(r#"{ "arenas": [{"name": "Sol"}], "wormholes": [] }"#).unwrap();
+        lobby.create_galaxy(&topology, Duration::ZERO).unwrap();
+
+        let (linked, unlinked) = (Uuid::new_v4(), Uuid::new_v4());
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        lobby.links_mut().link_player(linked, sender);
+        for id in [linked, unlinked] {
+            lobby.join_galaxy(LobbyPlayer::new(id, "Ace".to_string(), SessionToken::generate())).unwrap();
+        }
+
+        lobby_tick(&mut lobby, false);
+        let messages = received(&mut receiver);
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(&messages[0], ServerMessage::GalaxyMap(map) if map.arenas.len() == 1));
+
+        lobby_tick(&mut lobby, true);
+        let messages = received(&mut receiver);
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(&messages[0], ServerMessage::Snapshot(snapshot) if snapshot.players.iter().any(|p| p.id == linked)));
+
+        // Leaving the room drops the link, which ends the writer task
+        lobby.leave_room(linked).unwrap();
+        lobby_tick(&mut lobby, true);
+        assert!(matches!(receiver.try_recv(), Err(mpsc::error::TryRecvError::Disconnected)));
+    }
+
+    #[test]
+    fn test_lobby_tick_sends_queue_estimates_to_waiting_players() {
+        let mut lobby = LobbyManager::new(10);
+        let room_id = lobby.create_room("Game 1".to_string()).unwrap();
+        let player_id = Uuid::new_v4();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        lobby.links_mut().link_player(player_id, sender);
+        lobby.join_room(room_id, LobbyPlayer::new(player_id, "Ace".to_string(), SessionToken::generate())).unwrap();
+
+        // No match history yet: the estimate is the time left until bots backfill the room
+        lobby_tick(&mut lobby, true);
+        let messages = received(&mut receiver);
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], ServerMessage::QueueUpdate { position: 1, eta_secs } if eta_secs > 0));
+    }
+
+    #[test]
+    fn test_room_spectators_get_snapshots_until_they_stop() {
+        let mut lobby = LobbyManager::new(10);
+        let room_id = lobby.create_room("Game 1".to_string()).unwrap();
+        lobby.join_room(room_id, LobbyPlayer::new(Uuid::new_v4(), "Ace".to_string(), SessionToken::generate())).unwrap();
+        lobby.get_room_mut(room_id).unwrap().start_game().unwrap();
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        lobby.spectate_room(7, room_id).unwrap();
+        lobby.links_mut().link_spectator(7, sender);
+        lobby_tick(&mut lobby, true);
+        let messages = received(&mut receiver);
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(&messages[0], ServerMessage::Snapshot(snapshot) if !snapshot.players.is_empty()));
+
+        assert_eq!(lobby.stop_spectating(7), Some(room_id));
+        assert!(matches!(receiver.try_recv(), Err(mpsc::error::TryRecvError::Disconnected)));
+    }
+}
@@ -0,0 +1,171 @@
+//! Snapshot send pacing
+//!
+//! Every client's snapshot is produced in the same broadcast pass, so without
+//! pacing all writer tasks flush at once and the server emits one large burst
+//! per snapshot interval. With pacing enabled, each connection is assigned a
+//! round-robin slot and its writer task holds snapshot and delta batches until
+//! that slot's offset into the client's current snapshot interval, smoothing
+//! bandwidth at the cost of up to one interval of extra latency for the last
+//! slot. Other messages (events, chat, pings) are written immediately.
+//!
+//! [`BurstMeter`] measures burstiness either way: the peak bytes written by all
+//! writers within one short bucket, reported once per second.
+
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::config::SendPacingConfig;
+use crate::net::protocol::SnapshotRate;
+
+/// Width of a burst measurement bucket
+const BURST_BUCKET_MS: u64 = 5;
+
+/// Assigns pacing slots to new connections
+#[derive(Debug)]
+pub struct SendPacer {
+    config: SendPacingConfig,
+    next_slot: usize,
+}
+
+impl SendPacer {
+    pub fn new(config: SendPacingConfig) -> Self {
+        Self { config, next_slot: 0 }
+    }
+
+    /// Slot for the next connection (offset zero when pacing is disabled)
+    pub fn assign_slot(&mut self) -> PacingSlot {
+        if !self.config.enabled || self.config.slots <= 1 {
+            return PacingSlot::new(0.0);
+        }
+        let slot = self.next_slot % self.config.slots;
+        self.next_slot = self.next_slot.wrapping_add(1);
+        PacingSlot::new(slot as f32 / self.config.slots as f32)
+    }
+}
+
+/// A connection's place in its snapshot interval, shared between the session
+/// (which tracks the client's rate) and the connection's writer task
+#[derive(Debug, Clone)]
+pub struct PacingSlot {
+    /// Fraction of the interval to wait (0 = send at once)
+    phase: f32,
+    hz: Arc<AtomicU8>,
+}
+
+impl PacingSlot {
+    fn new(phase: f32) -> Self {
+        Self {
+            phase,
+            hz: Arc::new(AtomicU8::new(SnapshotRate::default().hz())),
+        }
+    }
+
+    /// Follow a change of the client's snapshot rate
+    pub fn set_rate(&self, rate: SnapshotRate) {
+        self.hz.store(rate.hz(), Ordering::Relaxed);
+    }
+
+    /// How long to hold a snapshot batch at the client's current rate
+    pub fn offset(&self) -> Duration {
+        if self.phase <= 0.0 {
+            return Duration::ZERO;
+        }
+        let hz = self.hz.load(Ordering::Relaxed).max(1);
+        Duration::from_secs_f32(self.phase / hz as f32)
+    }
+}
+
+/// Peak bytes written across all writers within a short bucket
+#[derive(Debug)]
+pub struct BurstMeter {
+    epoch: Instant,
+    bucket: AtomicU64,
+    bucket_bytes: AtomicU64,
+    peak_bytes: AtomicU64,
+}
+
+impl BurstMeter {
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            bucket: AtomicU64::new(0),
+            bucket_bytes: AtomicU64::new(0),
+            peak_bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a write of `bytes` happening now
+    pub fn record(&self, bytes: usize) {
+        let bucket = self.epoch.elapsed().as_millis() as u64 / BURST_BUCKET_MS;
+        self.record_in_bucket(bucket, bytes as u64);
+    }
+
+    fn record_in_bucket(&self, bucket: u64, bytes: u64) {
+        // Approximate under races: a write at a bucket boundary may land in
+        // either bucket, which is fine for a burstiness gauge
+        let total = if self.bucket.swap(bucket, Ordering::Relaxed) == bucket {
+            self.bucket_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes
+        } else {
+            self.bucket_bytes.store(bytes, Ordering::Relaxed);
+            bytes
+        };
+        self.peak_bytes.fetch_max(total, Ordering::Relaxed);
+    }
+
+    /// Peak since the last call, resetting the window
+    pub fn take_peak(&self) -> u64 {
+        self.peak_bytes.swap(0, Ordering::Relaxed)
+    }
+}
+
+impl Default for BurstMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets_round_robin_across_interval() {
+        let config = SendPacingConfig { enabled: true, slots: 4 };
+        let mut pacer = SendPacer::new(config);
+        let offsets: Vec<u128> = (0..5).map(|_| pacer.assign_slot().offset().as_millis()).collect();
+        assert_eq!(offsets, vec![0, 25, 50, 75, 0]);
+    }
+
+    #[test]
+    fn test_offset_follows_client_rate() {
+        let config = SendPacingConfig { enabled: true, slots: 2 };
+        let mut pacer = SendPacer::new(config);
+        pacer.assign_slot();
+        let slot = pacer.assign_slot();
+        assert_eq!(slot.offset().as_millis(), 50);
+        slot.set_rate(SnapshotRate::High);
+        assert_eq!(slot.offset().as_millis(), 25);
+        slot.set_rate(SnapshotRate::Low);
+        assert_eq!(slot.offset().as_millis(), 100);
+    }
+
+    #[test]
+    fn test_disabled_pacing_has_no_offset() {
+        let mut pacer = SendPacer::new(SendPacingConfig::default());
+        assert_eq!(pacer.assign_slot().offset(), Duration::ZERO);
+        assert_eq!(pacer.assign_slot().offset(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_burst_meter_tracks_peak_bucket() {
+        let meter = BurstMeter::new();
+        meter.record_in_bucket(1, 100);
+        meter.record_in_bucket(1, 300);
+        meter.record_in_bucket(2, 50);
+        assert_eq!(meter.take_peak(), 400);
+        assert_eq!(meter.take_peak(), 0);
+        meter.record_in_bucket(2, 10);
+        assert_eq!(meter.take_peak(), 60);
+    }
+}
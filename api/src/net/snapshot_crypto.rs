@@ -0,0 +1,138 @@
+//! Optional snapshot encryption above the transport
+//!
+//! A client that supports it sends an ephemeral ECDH P-256 public key in its
+//! join request; the server answers with its own in `JoinAccepted`, and both
+//! sides derive an AES-256-GCM key from the shared secret with HKDF-SHA256.
+//! The player's snapshots (full, delta and resync) are then sent as
+//! `ServerMessage::Sealed`, so a tool sniffing the stream on a shared machine
+//! (LAN events) only sees ciphertext. Other messages stay in the clear.
+//!
+//! The nonce is a per-connection counter sent with each payload: a lost or
+//! reordered message never desynchronizes the two sides, and a nonce is never
+//! reused under a key since every connection negotiates a fresh one.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+use ring::agreement::{self, EphemeralPrivateKey, UnparsedPublicKey, ECDH_P256};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::SystemRandom;
+
+use crate::net::protocol::ServerMessage;
+
+/// HKDF salt and info (must match the client's SnapshotCrypto)
+const HKDF_SALT: &[u8] = b"orbit-royale/snapshot";
+const HKDF_INFO: &[u8] = b"snapshot-key-v1";
+
+/// Why snapshot encryption could not be set up or applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum CryptoError {
+    #[error("invalid client public key")]
+    InvalidPublicKey,
+    #[error("key generation failed")]
+    KeyGeneration,
+    #[error("encryption failed")]
+    Seal,
+}
+
+/// Per-connection snapshot cipher
+pub struct SnapshotCipher {
+    key: LessSafeKey,
+    /// Server's public key, sent to the client in `JoinAccepted`
+    public_key: Vec<u8>,
+    next_nonce: AtomicU64,
+}
+
+impl std::fmt::Debug for SnapshotCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotCipher").field("next_nonce", &self.next_nonce).finish_non_exhaustive()
+    }
+}
+
+impl SnapshotCipher {
+    /// Agree on a key with a client's public key (uncompressed P-256 point)
+    pub fn negotiate(client_public_key: &[u8]) -> Result<Self, CryptoError> {
+        let rng = SystemRandom::new();
+        let private_key =
+            EphemeralPrivateKey::generate(&ECDH_P256, &rng).map_err(|_| CryptoError::KeyGeneration)?;
+        let public_key = private_key.compute_public_key().map_err(|_| CryptoError::KeyGeneration)?.as_ref().to_vec();
+
+        let peer = UnparsedPublicKey::new(&ECDH_P256, client_public_key);
+        let key = agreement::agree_ephemeral(private_key, &peer, derive_key)
+            .map_err(|_| CryptoError::InvalidPublicKey)?;
+        Ok(Self { key, public_key, next_nonce: AtomicU64::new(0) })
+    }
+
+    /// Public key to send back to the client
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// Seal an encoded server message
+    pub fn seal(&self, encoded: &[u8]) -> Result<ServerMessage, CryptoError> {
+        let nonce = self.next_nonce.fetch_add(1, Ordering::Relaxed);
+        let mut payload = encoded.to_vec();
+        self.key
+            .seal_in_place_append_tag(nonce_bytes(nonce), Aad::empty(), &mut payload)
+            .map_err(|_| CryptoError::Seal)?;
+        Ok(ServerMessage::Sealed { nonce, payload })
+    }
+}
+
+/// AES-256-GCM key from the ECDH shared secret
+fn derive_key(shared_secret: &[u8]) -> LessSafeKey {
+    let prk = Salt::new(HKDF_SHA256, HKDF_SALT).extract(shared_secret);
+    let okm = prk
+        .expand(&[HKDF_INFO], &AES_256_GCM)
+        .expect("AES-256-GCM key length is a valid HKDF output length");
+    LessSafeKey::new(UnboundKey::from(okm))
+}
+
+/// 96-bit nonce: four zero bytes, then the counter big-endian
+fn nonce_bytes(counter: u64) -> Nonce {
+    let mut bytes = [0u8; 12];
+    bytes[4..].copy_from_slice(&counter.to_be_bytes());
+    Nonce::assume_unique_for_key(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run both sides of the exchange: the server's cipher and the client's key
+    fn negotiate_pair() -> (SnapshotCipher, LessSafeKey) {
+        let private_key = EphemeralPrivateKey::generate(&ECDH_P256, &SystemRandom::new()).unwrap();
+        let cipher = SnapshotCipher::negotiate(private_key.compute_public_key().unwrap().as_ref()).unwrap();
+        let server_public = UnparsedPublicKey::new(&ECDH_P256, cipher.public_key().to_vec());
+        let key = agreement::agree_ephemeral(private_key, &server_public, derive_key).unwrap();
+        (cipher, key)
+    }
+
+    #[test]
+    fn test_client_opens_sealed_snapshots() {
+        let (cipher, key) = negotiate_pair();
+
+        for expected_nonce in 0..2 {
+            let ServerMessage::Sealed { nonce, mut payload } = cipher.seal(b"snapshot bytes").unwrap() else {
+                panic!("expected a sealed message");
+            };
+            assert_eq!(nonce, expected_nonce);
+            assert_ne!(&payload[..14], b"snapshot bytes");
+
+            let opened = key.open_in_place(nonce_bytes(nonce), Aad::empty(), &mut payload).unwrap();
+            assert_eq!(opened, b"snapshot bytes");
+        }
+    }
+
+    #[test]
+    fn test_tampering_and_bad_keys_are_rejected() {
+        assert_eq!(SnapshotCipher::negotiate(&[4; 10]).unwrap_err(), CryptoError::InvalidPublicKey);
+
+        let (cipher, key) = negotiate_pair();
+        let ServerMessage::Sealed { nonce, mut payload } = cipher.seal(b"snapshot").unwrap() else {
+            panic!("expected a sealed message");
+        };
+        payload[0] ^= 1;
+        assert!(key.open_in_place(nonce_bytes(nonce), Aad::empty(), &mut payload).is_err());
+    }
+}
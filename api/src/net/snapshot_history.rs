@@ -0,0 +1,263 @@
+//! Retained snapshot history and snapshot diffs
+//!
+//! Keeps the last N full (unfiltered) snapshots produced by the game loop so
+//! two ticks can be compared from the debug endpoint: which entities were
+//! added, removed, or moved further than an epsilon. Useful for diagnosing
+//! desync and delta-compression bugs without attaching a debugger.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::config::SnapshotHistoryConfig;
+use crate::net::protocol::GameSnapshot;
+use crate::util::vec2::Vec2;
+
+/// Ring buffer of recent full snapshots (disabled when capacity is 0)
+#[derive(Debug)]
+pub struct SnapshotHistory {
+    capacity: usize,
+    default_epsilon: f32,
+    snapshots: Mutex<VecDeque<Arc<GameSnapshot>>>,
+}
+
+/// Why a diff could not be produced
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum HistoryError {
+    #[error("snapshot history is disabled (set SNAPSHOT_HISTORY_CAPACITY)")]
+    Disabled,
+    #[error("tick {tick} is not retained (available: {oldest}-{newest})")]
+    NotRetained { tick: u64, oldest: u64, newest: u64 },
+    #[error("no snapshots retained yet")]
+    Empty,
+}
+
+impl SnapshotHistory {
+    pub fn new(config: &SnapshotHistoryConfig) -> Self {
+        Self {
+            capacity: config.capacity,
+            default_epsilon: config.default_epsilon,
+            snapshots: Mutex::new(VecDeque::with_capacity(config.capacity)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Epsilon used when the request doesn't specify one
+    pub fn default_epsilon(&self) -> f32 {
+        self.default_epsilon
+    }
+
+    /// Retain a snapshot (cloned only when history is enabled)
+    pub fn record(&self, snapshot: &GameSnapshot) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut snapshots = self.snapshots.lock();
+        if snapshots.len() >= self.capacity {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(Arc::new(snapshot.clone()));
+    }
+
+    /// Oldest and newest retained ticks
+    pub fn window(&self) -> Option<(u64, u64)> {
+        let snapshots = self.snapshots.lock();
+        Some((snapshots.front()?.tick, snapshots.back()?.tick))
+    }
+
+    fn get(&self, tick: u64) -> Result<Arc<GameSnapshot>, HistoryError> {
+        if !self.is_enabled() {
+            return Err(HistoryError::Disabled);
+        }
+        let (oldest, newest) = self.window().ok_or(HistoryError::Empty)?;
+        self.snapshots
+            .lock()
+            .iter()
+            .find(|s| s.tick == tick)
+            .cloned()
+            .ok_or(HistoryError::NotRetained { tick, oldest, newest })
+    }
+
+    /// Diff two retained ticks
+    pub fn diff(&self, from: u64, to: u64, epsilon: f32) -> Result<SnapshotDiff, HistoryError> {
+        let a = self.get(from)?;
+        let b = self.get(to)?;
+        Ok(diff_snapshots(&a, &b, epsilon))
+    }
+}
+
+/// Entity present in only one of the two snapshots
+#[derive(Debug, Clone, Serialize)]
+pub struct EntityPresence {
+    pub id: String,
+    pub position: Vec2,
+}
+
+/// Entity that moved further than epsilon (or changed mass)
+#[derive(Debug, Clone, Serialize)]
+pub struct EntityMove {
+    pub id: String,
+    pub from: Vec2,
+    pub to: Vec2,
+    pub distance: f32,
+    pub mass_delta: f32,
+}
+
+/// Changes for one entity kind
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct KindDiff {
+    pub added: Vec<EntityPresence>,
+    pub removed: Vec<EntityPresence>,
+    pub moved: Vec<EntityMove>,
+    /// Entities in both snapshots within epsilon
+    pub unchanged: usize,
+}
+
+/// Diff between two snapshots
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotDiff {
+    pub from_tick: u64,
+    pub to_tick: u64,
+    pub epsilon: f32,
+    pub players: KindDiff,
+    pub projectiles: KindDiff,
+    pub debris: KindDiff,
+    pub gravity_wells: KindDiff,
+}
+
+/// Position and mass of an entity, keyed by ID
+type EntityMap = HashMap<String, (Vec2, f32)>;
+
+fn diff_kind(from: EntityMap, mut to: EntityMap, epsilon: f32) -> KindDiff {
+    let mut diff = KindDiff::default();
+    for (id, (from_pos, from_mass)) in from {
+        match to.remove(&id) {
+            Some((to_pos, to_mass)) => {
+                let distance = from_pos.distance_to(to_pos);
+                let mass_delta = to_mass - from_mass;
+                if distance > epsilon || mass_delta.abs() > epsilon {
+                    diff.moved.push(EntityMove { id, from: from_pos, to: to_pos, distance, mass_delta });
+                } else {
+                    diff.unchanged += 1;
+                }
+            }
+            None => diff.removed.push(EntityPresence { id, position: from_pos }),
+        }
+    }
+    diff.added = to
+        .into_iter()
+        .map(|(id, (position, _))| EntityPresence { id, position })
+        .collect();
+
+    // Stable output: largest moves first, then by ID
+    diff.moved.sort_by(|a, b| b.distance.total_cmp(&a.distance).then_with(|| a.id.cmp(&b.id)));
+    diff.added.sort_by(|a, b| a.id.cmp(&b.id));
+    diff.removed.sort_by(|a, b| a.id.cmp(&b.id));
+    diff
+}
+
+/// Compare two snapshots entity by entity
+pub fn diff_snapshots(a: &GameSnapshot, b: &GameSnapshot, epsilon: f32) -> SnapshotDiff {
+    let players = |s: &GameSnapshot| -> EntityMap {
+        s.players.iter().map(|p| (p.id.to_string(), (p.position, p.mass))).collect()
+    };
+    let projectiles = |s: &GameSnapshot| -> EntityMap {
+        s.projectiles.iter().map(|p| (p.id.to_string(), (p.position, p.mass))).collect()
+    };
+    let debris = |s: &GameSnapshot| -> EntityMap {
+        s.debris.iter().map(|d| (d.id.to_string(), (d.position, d.size as f32))).collect()
+    };
+    let wells = |s: &GameSnapshot| -> EntityMap {
+        s.gravity_wells.iter().map(|w| (w.id.to_string(), (w.position, w.mass))).collect()
+    };
+
+    SnapshotDiff {
+        from_tick: a.tick,
+        to_tick: b.tick,
+        epsilon,
+        players: diff_kind(players(a), players(b), epsilon),
+        projectiles: diff_kind(projectiles(a), projectiles(b), epsilon),
+        debris: diff_kind(debris(a), debris(b), epsilon),
+        gravity_wells: diff_kind(wells(a), wells(b), epsilon),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::protocol::{DebrisSnapshot, PlayerSnapshot};
+
+    fn snapshot(tick: u64) -> GameSnapshot {
+        let mut state = crate::game::state::GameState::new();
+        state.tick = tick;
+        let mut snap = GameSnapshot::from_game_state(&state);
+        snap.players.clear();
+        snap.gravity_wells.clear();
+        snap
+    }
+
+    fn player(id: uuid::Uuid, x: f32, mass: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            id,
+            name: "p".to_string(),
+            position: Vec2::new(x, 0.0),
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass,
+            flags: 1,
+            kills: 0,
+            deaths: 0,
+            color_index: 0,
+            spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
+        }
+    }
+
+    #[test]
+    fn test_diff_added_removed_moved() {
+        let (stay, mover, gone, new) =
+            (uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let mut a = snapshot(10);
+        a.players = vec![player(stay, 0.0, 100.0), player(mover, 0.0, 100.0), player(gone, 5.0, 100.0)];
+        a.debris = vec![DebrisSnapshot { id: 7, position: Vec2::ZERO, size: 1 }];
+        let mut b = snapshot(13);
+        b.players = vec![player(stay, 0.1, 100.0), player(mover, 50.0, 100.0), player(new, 1.0, 100.0)];
+
+        let diff = diff_snapshots(&a, &b, 0.5);
+        assert_eq!((diff.from_tick, diff.to_tick), (10, 13));
+        assert_eq!(diff.players.unchanged, 1);
+        assert_eq!(diff.players.moved.len(), 1);
+        assert_eq!(diff.players.moved[0].id, mover.to_string());
+        assert!((diff.players.moved[0].distance - 50.0).abs() < 1e-3);
+        assert_eq!(diff.players.added[0].id, new.to_string());
+        assert_eq!(diff.players.removed[0].id, gone.to_string());
+        assert_eq!(diff.debris.removed[0].id, "7");
+    }
+
+    #[test]
+    fn test_history_window_and_errors() {
+        let disabled = SnapshotHistory::new(&SnapshotHistoryConfig::default());
+        disabled.record(&snapshot(1));
+        assert_eq!(disabled.diff(1, 1, 0.5).unwrap_err(), HistoryError::Disabled);
+
+        let history = SnapshotHistory::new(&SnapshotHistoryConfig { capacity: 2, ..Default::default() });
+        assert_eq!(history.diff(1, 2, 0.5).unwrap_err(), HistoryError::Empty);
+        for tick in [3, 6, 9] {
+            history.record(&snapshot(tick));
+        }
+        assert_eq!(history.window(), Some((6, 9)));
+        assert_eq!(
+            history.diff(3, 9, 0.5).unwrap_err(),
+            HistoryError::NotRetained { tick: 3, oldest: 6, newest: 9 }
+        );
+        assert!(history.diff(6, 9, 0.5).is_ok());
+    }
+}
@@ -0,0 +1,297 @@
+//! Adaptive per-client snapshot rate
+//!
+//! Clients announce the highest snapshot rate they want in the join request
+//! (`ClientCapabilities`); the server caps it by configuration and then adapts
+//! it to the measured link: clients with high input loss or RTT drop to 5Hz
+//! with coarser delta thresholds, and recover once the link is clean again.
+//! Opted-in low-latency clients get 20Hz while their RTT stays low.
+//!
+//! Loss is estimated from gaps in the input sequence (inputs travel as
+//! unreliable datagrams), RTT from snapshot acknowledgements.
+
+use std::time::{Duration, Instant};
+
+use crate::config::SnapshotRateConfig;
+use crate::game::constants::physics;
+use crate::net::protocol::SnapshotRate;
+
+/// Loss smoothing gain per input packet
+const LOSS_GAIN: f32 = 1.0 / 32.0;
+
+/// RTT smoothing gain per sample (as TCP's SRTT)
+const RTT_GAIN: f32 = 1.0 / 8.0;
+
+/// Largest sequence gap counted as loss (bigger jumps are reconnects or stalls)
+const MAX_COUNTED_GAP: u64 = 64;
+
+impl SnapshotRate {
+    /// Game-loop ticks between snapshots (1.5 for 20Hz at 30 TPS)
+    pub fn interval_ticks(self) -> f64 {
+        physics::TICK_RATE as f64 / self.hz() as f64
+    }
+
+    /// Multiplier on delta change thresholds (coarser deltas at low rates)
+    pub fn delta_epsilon_scale(self) -> f32 {
+        match self {
+            SnapshotRate::Low => 4.0,
+            SnapshotRate::Normal | SnapshotRate::High => 1.0,
+        }
+    }
+
+    /// Multiplier on the full resync interval (fewer full snapshots at low rates)
+    pub fn full_resync_multiplier(self) -> u64 {
+        match self {
+            SnapshotRate::Low => 2,
+            SnapshotRate::Normal | SnapshotRate::High => 1,
+        }
+    }
+}
+
+/// Smoothed loss and RTT estimates for one client
+#[derive(Debug, Clone, Default)]
+pub struct LinkQuality {
+    loss: f32,
+    rtt_ms: Option<f32>,
+    last_sequence: Option<u64>,
+}
+
+impl LinkQuality {
+    /// Record a newly received input sequence number
+    pub fn record_sequence(&mut self, sequence: u64) {
+        if let Some(last) = self.last_sequence {
+            if sequence <= last {
+                return;
+            }
+            let lost = (sequence - last - 1).min(MAX_COUNTED_GAP);
+            for _ in 0..lost {
+                self.loss += (1.0 - self.loss) * LOSS_GAIN;
+            }
+            self.loss -= self.loss * LOSS_GAIN;
+        }
+        self.last_sequence = Some(sequence);
+    }
+
+    /// Record a round-trip time sample
+    pub fn record_rtt(&mut self, rtt_ms: f32) {
+        self.rtt_ms = Some(match self.rtt_ms {
+            Some(rtt) => rtt + (rtt_ms - rtt) * RTT_GAIN,
+            None => rtt_ms,
+        });
+    }
+
+    /// Estimated fraction of inputs lost (0-1)
+    pub fn loss(&self) -> f32 {
+        self.loss
+    }
+
+    /// Smoothed RTT, once a sample has arrived
+    pub fn rtt_ms(&self) -> Option<f32> {
+        self.rtt_ms
+    }
+}
+
+/// Negotiated ceiling plus the currently applied rate for one client
+#[derive(Debug, Clone)]
+pub struct SnapshotRateController {
+    max_rate: SnapshotRate,
+    rate: SnapshotRate,
+    link: LinkQuality,
+    last_change: Instant,
+}
+
+impl SnapshotRateController {
+    /// Start a client at the rate it asked for, capped by configuration.
+    /// Opted-in clients start at the normal rate and earn 20Hz once RTT is known.
+    pub fn new(requested: SnapshotRate, config: &SnapshotRateConfig, now: Instant) -> Self {
+        let max_rate = if requested == SnapshotRate::High && !config.allow_high {
+            SnapshotRate::Normal
+        } else {
+            requested
+        };
+        let rate = if config.adaptive { max_rate.min(SnapshotRate::Normal) } else { max_rate };
+        Self {
+            max_rate,
+            rate,
+            link: LinkQuality::default(),
+            last_change: now,
+        }
+    }
+
+    pub fn rate(&self) -> SnapshotRate {
+        self.rate
+    }
+
+    pub fn link_mut(&mut self) -> &mut LinkQuality {
+        &mut self.link
+    }
+
+    /// Rate the link currently supports, ignoring switch hold-off
+    fn target(&self, config: &SnapshotRateConfig) -> SnapshotRate {
+        if !config.adaptive {
+            return self.max_rate;
+        }
+        let loss = self.link.loss();
+        let rtt = self.link.rtt_ms();
+        let degraded = loss > config.loss_degrade || rtt.is_some_and(|r| r > config.rtt_degrade_ms);
+        // Hysteresis: a degraded client stays low until loss is well below the trigger
+        let recovering = self.rate == SnapshotRate::Low && loss > config.loss_recover;
+
+        let target = if degraded || recovering {
+            SnapshotRate::Low
+        } else if loss <= config.loss_recover && rtt.is_some_and(|r| r <= config.high_max_rtt_ms) {
+            SnapshotRate::High
+        } else {
+            SnapshotRate::Normal
+        };
+        target.min(self.max_rate)
+    }
+
+    /// Re-evaluate the rate; returns the new rate when it changed.
+    /// Degrading is immediate, upgrades wait out the minimum switch interval.
+    pub fn evaluate(&mut self, now: Instant, config: &SnapshotRateConfig) -> Option<SnapshotRate> {
+        let target = self.target(config);
+        if target == self.rate {
+            return None;
+        }
+        let held = now.duration_since(self.last_change) >= Duration::from_secs(config.min_switch_secs);
+        if target > self.rate && !held {
+            return None;
+        }
+        self.rate = target;
+        self.last_change = now;
+        Some(target)
+    }
+}
+
+/// Fractional send schedule in game-loop ticks, so rates that don't divide the
+/// tick rate (20Hz at 30 TPS) alternate between 1- and 2-tick gaps
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SendSchedule {
+    next_due: Option<f64>,
+}
+
+impl SendSchedule {
+    /// Whether a snapshot is due at `tick`; advances the schedule when it is.
+    /// Missed slots are skipped rather than sent in a burst.
+    pub fn take(&mut self, tick: u64, interval_ticks: f64) -> bool {
+        let tick = tick as f64;
+        match self.next_due {
+            Some(due) if tick + 1e-6 < due => false,
+            Some(due) if tick - due < interval_ticks => {
+                self.next_due = Some(due + interval_ticks);
+                true
+            }
+            _ => {
+                self.next_due = Some(tick + interval_ticks);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SnapshotRateConfig {
+        SnapshotRateConfig { allow_high: true, ..SnapshotRateConfig::default() }
+    }
+
+    #[test]
+    fn test_interval_ticks() {
+        assert_eq!(SnapshotRate::Low.interval_ticks(), 6.0);
+        assert_eq!(SnapshotRate::Normal.interval_ticks(), 3.0);
+        assert_eq!(SnapshotRate::High.interval_ticks(), 1.5);
+    }
+
+    #[test]
+    fn test_schedule_paces_fractional_intervals() {
+        let mut schedule = SendSchedule::default();
+        let sent = (1..=30u64).filter(|&t| schedule.take(t, 1.5)).count();
+        assert_eq!(sent, 20);
+
+        // Broadcasts every 3 ticks with a 6-tick interval send every other pass
+        let mut schedule = SendSchedule::default();
+        let sent: Vec<u64> = (1..=10u64).map(|i| i * 3).filter(|&t| schedule.take(t, 6.0)).collect();
+        assert_eq!(sent, vec![3, 9, 15, 21, 27]);
+    }
+
+    #[test]
+    fn test_schedule_skips_missed_slots() {
+        let mut schedule = SendSchedule::default();
+        assert!(schedule.take(3, 3.0));
+        // Long stall: one send, then back on a 3-tick cadence
+        assert!(schedule.take(30, 3.0));
+        assert!(!schedule.take(31, 3.0));
+        assert!(schedule.take(33, 3.0));
+    }
+
+    #[test]
+    fn test_loss_estimate_from_sequence_gaps() {
+        let mut link = LinkQuality::default();
+        for seq in 1..=200 {
+            link.record_sequence(seq);
+        }
+        assert!(link.loss() < 1e-3);
+
+        // Every fifth input lost (20%)
+        for seq in (201..=2000u64).filter(|s| s % 5 != 0) {
+            link.record_sequence(seq);
+        }
+        assert!((link.loss() - 0.2).abs() < 0.05, "loss {}", link.loss());
+    }
+
+    #[test]
+    fn test_degrades_immediately_and_recovers_after_hold() {
+        let config = config();
+        let start = Instant::now();
+        let mut controller = SnapshotRateController::new(SnapshotRate::Normal, &config, start);
+        controller.link_mut().record_rtt(40.0);
+        assert_eq!(controller.evaluate(start, &config), None);
+
+        controller.link_mut().record_rtt(2_000.0);
+        assert_eq!(controller.evaluate(start + Duration::from_secs(1), &config), Some(SnapshotRate::Low));
+
+        for _ in 0..40 {
+            controller.link_mut().record_rtt(40.0);
+        }
+        assert_eq!(controller.evaluate(start + Duration::from_secs(2), &config), None);
+        assert_eq!(
+            controller.evaluate(start + Duration::from_secs(1 + config.min_switch_secs), &config),
+            Some(SnapshotRate::Normal)
+        );
+    }
+
+    #[test]
+    fn test_high_rate_requires_opt_in_and_low_rtt() {
+        let config = config();
+        let start = Instant::now();
+        let later = start + Duration::from_secs(config.min_switch_secs);
+
+        let mut normal = SnapshotRateController::new(SnapshotRate::Normal, &config, start);
+        normal.link_mut().record_rtt(20.0);
+        assert_eq!(normal.evaluate(later, &config), None);
+
+        let mut opted_in = SnapshotRateController::new(SnapshotRate::High, &config, start);
+        assert_eq!(opted_in.rate(), SnapshotRate::Normal);
+        assert_eq!(opted_in.evaluate(later, &config), None);
+        opted_in.link_mut().record_rtt(20.0);
+        assert_eq!(opted_in.evaluate(later, &config), Some(SnapshotRate::High));
+
+        let capped = SnapshotRateConfig { allow_high: false, ..config };
+        let mut refused = SnapshotRateController::new(SnapshotRate::High, &capped, start);
+        refused.link_mut().record_rtt(20.0);
+        assert_eq!(refused.evaluate(later, &capped), None);
+        assert_eq!(refused.rate(), SnapshotRate::Normal);
+    }
+
+    #[test]
+    fn test_non_adaptive_uses_negotiated_rate() {
+        let config = SnapshotRateConfig { adaptive: false, ..config() };
+        let start = Instant::now();
+        let mut controller = SnapshotRateController::new(SnapshotRate::High, &config, start);
+        assert_eq!(controller.rate(), SnapshotRate::High);
+        controller.link_mut().record_rtt(2_000.0);
+        assert_eq!(controller.evaluate(start + Duration::from_secs(60), &config), None);
+    }
+}
@@ -0,0 +1,92 @@
+//! Delayed spectator stream
+//!
+//! In competitive matches a live spectator view lets a friend (or a stream)
+//! call out positions to players ("ghosting"). With a delay configured,
+//! full-view spectator snapshots are recorded into a ring buffer as they are
+//! encoded, and spectators are served the newest one at least `delay` old.
+//! Until the buffer reaches back that far, delayed spectators receive nothing.
+//!
+//! Frames are recorded at the full-view spectator rate, so the buffer holds
+//! about `delay * spectator rate` encoded snapshots.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Ring buffer of past encoded spectator snapshots
+#[derive(Debug)]
+pub struct SpectatorDelay {
+    /// Delay in ticks (0 = disabled)
+    delay_ticks: u64,
+    /// Minimum ticks between recorded frames
+    interval_ticks: u64,
+    /// (tick, encoded snapshot), oldest first
+    frames: VecDeque<(u64, Arc<Vec<u8>>)>,
+}
+
+impl SpectatorDelay {
+    pub fn new(delay_ticks: u64, interval_ticks: u64) -> Self {
+        Self { delay_ticks, interval_ticks: interval_ticks.max(1), frames: VecDeque::new() }
+    }
+
+    /// Whether spectators are delayed at all
+    pub fn is_enabled(&self) -> bool {
+        self.delay_ticks > 0
+    }
+
+    /// Record the encoded spectator snapshot for `tick` (skipped if the last
+    /// frame is more recent than the recording interval)
+    pub fn record(&mut self, tick: u64, encoded: Arc<Vec<u8>>) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.frames.back().is_some_and(|&(last, _)| tick < last + self.interval_ticks) {
+            return;
+        }
+        self.frames.push_back((tick, encoded));
+
+        // Keep the newest frame at or before the cutoff; everything older is never served again
+        let cutoff = tick.saturating_sub(self.delay_ticks);
+        while self.frames.len() > 1 && self.frames[1].0 <= cutoff {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Newest snapshot at least the delay older than `tick`
+    pub fn delayed(&self, tick: u64) -> Option<Arc<Vec<u8>>> {
+        let cutoff = tick.checked_sub(self.delay_ticks)?;
+        self.frames.iter().rev().find(|&&(frame_tick, _)| frame_tick <= cutoff).map(|(_, encoded)| encoded.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(tick: u64) -> Arc<Vec<u8>> {
+        Arc::new(tick.to_le_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_serves_newest_frame_past_the_delay() {
+        let mut delay = SpectatorDelay::new(100, 10);
+        for tick in (0..=150).step_by(5) {
+            delay.record(tick, frame(tick));
+        }
+
+        // Frames are recorded every 10 ticks; nothing is served before the delay has elapsed
+        assert_eq!(delay.delayed(99), None);
+        assert_eq!(delay.delayed(150), Some(frame(50)));
+        assert_eq!(delay.delayed(155), Some(frame(50)));
+
+        // Frames older than the one currently served are dropped
+        assert_eq!(delay.frames.len(), 11);
+    }
+
+    #[test]
+    fn test_disabled_records_nothing() {
+        let mut delay = SpectatorDelay::new(0, 10);
+        delay.record(10, frame(10));
+        assert!(!delay.is_enabled());
+        assert!(delay.frames.is_empty());
+    }
+}
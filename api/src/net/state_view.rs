@@ -0,0 +1,190 @@
+//! Published per-tick game state
+//!
+//! Readers that only need to look at the game (status logging, metrics,
+//! joining spectators, admin endpoints) used to take the session lock and
+//! build what they needed from the live state, queueing behind the game loop's
+//! write lock and holding it up in turn. Now the game loop publishes an
+//! immutable [`GameStateView`] at the end of every tick, while it still holds
+//! the session: the full snapshot plus a few counters. The view is swapped in
+//! atomically, so a reader loads the latest one without any lock and keeps a
+//! consistent tick for as long as it holds the `Arc`. Broadcast frames share
+//! the view's snapshot instead of building another.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use arc_swap::ArcSwap;
+use serde::Serialize;
+
+use crate::game::modifiers::ActiveModifier;
+use crate::game::performance::PerformanceStatus;
+use crate::game::state::{MatchPhase, PlayerId};
+use crate::net::account::AccountId;
+use crate::net::protocol::{GameSnapshot, PlayerSnapshot};
+
+/// Read-only game state after one tick
+#[derive(Debug)]
+pub struct GameStateView {
+    /// Full, unfiltered snapshot of the tick
+    pub snapshot: Arc<GameSnapshot>,
+    /// Connections, spectators included
+    pub connections: usize,
+    pub spectators: usize,
+    pub bots: usize,
+    /// Bots the session is aiming for
+    pub bot_target: usize,
+    /// Connections by the account they joined with (presence lookups)
+    pub accounts: Arc<HashMap<AccountId, PlayerId>>,
+    /// Arena size and running modifiers, for saving arena presets
+    pub escape_radius: f32,
+    pub modifiers: Vec<ActiveModifier>,
+    pub performance: PerformanceStatus,
+    pub budget_usage_percent: f32,
+    pub published_at: Instant,
+}
+
+impl GameStateView {
+    /// View before the first tick
+    fn empty() -> Self {
+        Self {
+            snapshot: Arc::new(GameSnapshot {
+                tick: 0,
+                match_phase: MatchPhase::Waiting,
+                match_time: 0.0,
+                countdown: 0.0,
+                players: vec![],
+                projectiles: vec![],
+                debris: vec![],
+                arena_collapse_phase: 0,
+                arena_safe_radius: 0.0,
+                arena_scale: 1.0,
+                gravity_wells: vec![],
+                asteroids: vec![],
+                total_players: 0,
+                total_alive: 0,
+                density_grid: vec![],
+                echo_client_time: 0,
+                interp_delay_ms: 0,
+                ai_status: None,
+            }),
+            connections: 0,
+            spectators: 0,
+            bots: 0,
+            bot_target: 0,
+            accounts: Arc::default(),
+            escape_radius: 0.0,
+            modifiers: Vec::new(),
+            performance: PerformanceStatus::Excellent,
+            budget_usage_percent: 0.0,
+            published_at: Instant::now(),
+        }
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.snapshot.tick
+    }
+
+    pub fn player(&self, id: PlayerId) -> Option<&PlayerSnapshot> {
+        self.snapshot.players.iter().find(|p| p.id == id)
+    }
+
+    /// Summary for the admin endpoint
+    pub fn summary(&self) -> StateSummary {
+        StateSummary {
+            tick: self.snapshot.tick,
+            age_ms: self.published_at.elapsed().as_millis() as u64,
+            match_phase: format!("{:?}", self.snapshot.match_phase),
+            connections: self.connections,
+            spectators: self.spectators,
+            bots: self.bots,
+            bot_target: self.bot_target,
+            players: self.snapshot.players.len(),
+            alive: self.snapshot.total_alive,
+            projectiles: self.snapshot.projectiles.len(),
+            debris: self.snapshot.debris.len(),
+            gravity_wells: self.snapshot.gravity_wells.len(),
+            arena_scale: self.snapshot.arena_scale,
+            performance: format!("{:?}", self.performance),
+            budget_usage_percent: self.budget_usage_percent,
+        }
+    }
+}
+
+/// `/debug/state` body
+#[derive(Debug, Serialize)]
+pub struct StateSummary {
+    pub tick: u64,
+    /// Time since the view was published
+    pub age_ms: u64,
+    pub match_phase: String,
+    pub connections: usize,
+    pub spectators: usize,
+    pub bots: usize,
+    pub bot_target: usize,
+    pub players: usize,
+    pub alive: u32,
+    pub projectiles: usize,
+    pub debris: usize,
+    pub gravity_wells: usize,
+    pub arena_scale: f32,
+    pub performance: String,
+    pub budget_usage_percent: f32,
+}
+
+/// The latest published view, swapped in once per tick
+#[derive(Debug)]
+pub struct PublishedState {
+    current: ArcSwap<GameStateView>,
+}
+
+impl Default for PublishedState {
+    fn default() -> Self {
+        Self { current: ArcSwap::from_pointee(GameStateView::empty()) }
+    }
+}
+
+impl PublishedState {
+    /// Replace the current view (readers holding the old one keep it)
+    pub fn publish(&self, view: GameStateView) -> Arc<GameStateView> {
+        let view = Arc::new(view);
+        self.current.store(view.clone());
+        view
+    }
+
+    /// The latest view (never blocks)
+    pub fn load(&self) -> Arc<GameStateView> {
+        self.current.load_full()
+    }
+
+    /// Handle `/debug/state`: the latest view's summary as JSON
+    pub fn summary_json(&self) -> String {
+        serde_json::to_string(&self.load().summary()).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(tick: u64, connections: usize) -> GameStateView {
+        let mut view = GameStateView::empty();
+        Arc::make_mut(&mut view.snapshot).tick = tick;
+        view.connections = connections;
+        view
+    }
+
+    #[test]
+    fn test_readers_keep_their_view_across_publishes() {
+        let published = PublishedState::default();
+        assert_eq!(published.load().tick(), 0);
+
+        published.publish(view(7, 3));
+        let held = published.load();
+        published.publish(view(8, 4));
+
+        assert_eq!((held.tick(), held.connections), (7, 3));
+        assert_eq!(published.load().tick(), 8);
+        assert!(published.summary_json().contains("\"tick\":8"));
+    }
+}
@@ -46,7 +46,7 @@ impl TlsConfig {
     }
 
     /// Load certificate from PEM file paths
-    async fn load_from_paths(cert_path: &str, key_path: &str) -> Result<Self> {
+    pub async fn load_from_paths(cert_path: &str, key_path: &str) -> Result<Self> {
         let identity = Identity::load_pemfiles(cert_path, key_path)
             .await
             .context("Failed to load certificate from PEM files")?;
@@ -4,24 +4,40 @@
 //! Integrates with GameSession for real-time multiplayer gameplay.
 
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use crate::config::ServerConfig;
+use crate::config::{ServerConfig, SnapshotEncryptionMode};
+use crate::game::constants::physics;
 use crate::game::state::PlayerId;
 use crate::metrics::Metrics;
+use crate::net::account::{AccountId, AccountKeys};
 use crate::net::dos_protection::DoSProtection;
-use crate::net::game_session::{start_game_loop, send_to_player, GameSession};
+use crate::net::game_session::{broadcast_message, start_game_loop, send_to_player, GameSession, JoinSlot};
+use crate::net::join_queue::{QueueStatus, TicketId};
+use crate::net::netsim::Verdict;
+use crate::net::i18n::{keys, LocalizedText};
 #[cfg(feature = "ai_manager")]
-use crate::net::game_session::start_ai_manager;
-use crate::net::protocol::{decode, ClientMessage, RejectionReason, ServerMessage};
+use crate::net::game_session::{start_ai_manager, start_narrator};
+use crate::net::protocol::{
+    decode, encode, AccessibilitySettings, ClientCapabilities, ClientMessage, PlayerInput, RejectionReason, RoomFilter,
+    ServerMessage, TelemetryConsent, WorldChunk,
+};
+use crate::net::snapshot_crypto::SnapshotCipher;
 use crate::net::tls::TlsConfig;
+use crate::net::world_stream::{self, WorldStream};
+use crate::roles::{Role, RoleRegistry};
+use crate::server::ShutdownToken;
+use crate::util::privacy;
 
 // Feature-gated imports
 #[cfg(feature = "anticheat")]
 use crate::anticheat::sanctions::BanList;
 #[cfg(feature = "lobby")]
-use crate::lobby::manager::LobbyManager;
+use crate::lobby::manager::{LobbyManager, ManagerError as LobbyError};
+#[cfg(feature = "lobby")]
+use crate::net::room_link::{spawn_link_writer, start_lobby_loop};
 
 // Type aliases for feature-gated types
 #[cfg(feature = "lobby")]
@@ -34,6 +50,16 @@ type BanListType = BanList;
 #[cfg(not(feature = "anticheat"))]
 type BanListType = ();
 
+/// How the WebTransport endpoint is set up, beyond `ServerConfig`
+#[derive(Debug, Clone, Default)]
+pub struct TransportOptions {
+    /// Port to listen on instead of `ServerConfig::port`
+    pub port: Option<u16>,
+    /// PEM certificate and key files, instead of TLS_CERT_PATH/TLS_KEY_PATH
+    /// or the dev certificates in certs/
+    pub tls_pem_files: Option<(String, String)>,
+}
+
 /// WebTransport server
 pub struct WebTransportServer {
     config: ServerConfig,
@@ -50,15 +76,34 @@ pub struct WebTransportServer {
 
 impl WebTransportServer {
     /// Create a new WebTransport server
+    #[allow(dead_code)]
     pub async fn new(
         config: ServerConfig,
         lobby_manager: Arc<RwLock<LobbyManagerType>>,
         ban_list: Arc<RwLock<BanListType>>,
         metrics: Arc<Metrics>,
     ) -> anyhow::Result<Self> {
-        let tls_config = TlsConfig::generate_self_signed().await?;
-        let dos_protection = Arc::new(RwLock::new(DoSProtection::default()));
         let game_session = Arc::new(RwLock::new(GameSession::new_with_metrics(metrics.clone())));
+        Self::with_options(config, &TransportOptions::default(), lobby_manager, ban_list, game_session, metrics).await
+    }
+
+    /// Create a WebTransport server for an existing game session
+    pub async fn with_options(
+        mut config: ServerConfig,
+        options: &TransportOptions,
+        lobby_manager: Arc<RwLock<LobbyManagerType>>,
+        ban_list: Arc<RwLock<BanListType>>,
+        game_session: Arc<RwLock<GameSession>>,
+        metrics: Arc<Metrics>,
+    ) -> anyhow::Result<Self> {
+        let tls_config = match &options.tls_pem_files {
+            Some((cert_path, key_path)) => TlsConfig::load_from_paths(cert_path, key_path).await?,
+            None => TlsConfig::generate_self_signed().await?,
+        };
+        if let Some(port) = options.port {
+            config.port = port;
+        }
+        let dos_protection = Arc::new(RwLock::new(DoSProtection::default()));
 
         Ok(Self {
             config,
@@ -76,14 +121,21 @@ impl WebTransportServer {
         self.tls_config.get_cert_hash()
     }
 
+    /// Session the server's connections play in
+    #[allow(dead_code)]
+    pub fn game_session(&self) -> &Arc<RwLock<GameSession>> {
+        &self.game_session
+    }
+
     /// Get the bind address
     #[allow(dead_code)]
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new(self.config.bind_address, self.config.port)
     }
 
-    /// Run the server
-    pub async fn run(self) -> anyhow::Result<()> {
+    /// Run the server until `shutdown` is triggered, then close every
+    /// connection (after telling it why) and wait for the AI manager to save
+    pub async fn run(self, shutdown: ShutdownToken) -> anyhow::Result<()> {
         use wtransport::Endpoint;
         use wtransport::ServerConfig;
 
@@ -103,37 +155,65 @@ impl WebTransportServer {
         tracing::info!("Certificate hash: {}", self.tls_config.cert_hash);
 
         // Start the game loop background task
-        start_game_loop(self.game_session.clone());
+        start_game_loop(self.game_session.clone(), self.config.runtime.game_loop_core, shutdown.clone())?;
+
+        // Lobby rooms tick beside the main arena
+        #[cfg(feature = "lobby")]
+        start_lobby_loop(self.lobby_manager.clone(), shutdown.clone());
 
         // Start AI manager for autonomous parameter tuning (if enabled)
         #[cfg(feature = "ai_manager")]
-        start_ai_manager(self.game_session.clone()).await;
+        let ai_manager = start_ai_manager(self.game_session.clone(), shutdown.clone()).await;
+
+        // Start spectator narrator (if enabled)
+        #[cfg(feature = "ai_manager")]
+        start_narrator(self.game_session.clone(), shutdown.clone()).await;
 
         // Accept connections
+        self.metrics.accepting_connections.store(true, std::sync::atomic::Ordering::Relaxed);
+        let stopped = shutdown.triggered();
+        tokio::pin!(stopped);
         loop {
-            let incoming = server.accept().await;
+            let incoming = tokio::select! {
+                incoming = server.accept() => incoming,
+                _ = &mut stopped => break,
+            };
 
             let lobby = self.lobby_manager.clone();
             let bans = self.ban_list.clone();
             let dos = self.dos_protection.clone();
             let game_session = self.game_session.clone();
+            let metrics = self.metrics.clone();
 
             tokio::spawn(async move {
-                if let Err(e) = handle_connection(incoming, lobby, bans, dos, game_session).await {
+                if let Err(e) = handle_connection(incoming, lobby, bans, dos, game_session, metrics).await {
                     tracing::warn!("Connection error: {}", e);
                 }
             });
         }
+
+        self.metrics.accepting_connections.store(false, std::sync::atomic::Ordering::Relaxed);
+        let writers = self.game_session.write().await.shutdown();
+        for writer in writers {
+            let _ = writer.await;
+        }
+        #[cfg(feature = "ai_manager")]
+        if let Some(ai_manager) = ai_manager {
+            let _ = ai_manager.await;
+        }
+        tracing::info!("WebTransport server stopped");
+        Ok(())
     }
 }
 
 /// Handle a single WebTransport connection
 async fn handle_connection(
     incoming: wtransport::endpoint::IncomingSession,
-    _lobby_manager: Arc<RwLock<LobbyManagerType>>,
+    lobby_manager: Arc<RwLock<LobbyManagerType>>,
     ban_list: Arc<RwLock<BanListType>>,
     dos_protection: Arc<RwLock<DoSProtection>>,
     game_session: Arc<RwLock<GameSession>>,
+    metrics: Arc<Metrics>,
 ) -> anyhow::Result<()> {
     use std::net::{IpAddr, Ipv4Addr};
     #[cfg(feature = "dos_ratelimit")]
@@ -154,8 +234,8 @@ async fn handle_connection(
                 .map(|d| format!("{:.0}s", d.as_secs_f32()))
                 .unwrap_or_else(|| "permanent".to_string());
             tracing::warn!(
-                "Connection rejected - IP banned: {:?}, remaining: {}, reason: {}",
-                client_ip, remaining, ban.reason
+                "Connection rejected - IP banned: {}, remaining: {}, reason: {}",
+                privacy::ip(client_ip), remaining, ban.reason
             );
             return Err(anyhow::anyhow!("Connection banned: {}", ban.reason));
         }
@@ -175,6 +255,11 @@ async fn handle_connection(
         }
     };
 
+    // A connection attempt wakes an idle, hibernating server before the client's JoinRequest arrives
+    if game_session.read().await.is_hibernating() {
+        game_session.write().await.wake_from_hibernation();
+    }
+
     tracing::debug!(
         "New connection from: {:?}, path: {}, conn_id: {}",
         session_request.authority(),
@@ -191,11 +276,21 @@ async fn handle_connection(
 
     // Track this connection's player ID (set after JoinRequest)
     let player_id: Arc<RwLock<Option<PlayerId>>> = Arc::new(RwLock::new(None));
+    // Set once a join is admitted or queued; a connection joins at most once
+    let join_claimed = Arc::new(AtomicBool::new(false));
+    // Cleared on disconnect so a queued join is never admitted after the client left
+    let connected = Arc::new(AtomicBool::new(true));
+    // Set when the player joined a lobby room rather than the main arena
+    let in_room = Arc::new(AtomicBool::new(false));
 
     // Main connection loop
     loop {
         let player_id_clone = player_id.clone();
+        let join_claimed_clone = join_claimed.clone();
+        let connected_clone = connected.clone();
+        let in_room_clone = in_room.clone();
         let game_session_clone = game_session.clone();
+        let lobby_clone = lobby_manager.clone();
         #[cfg(feature = "dos_ratelimit")]
         let dos_clone = dos_protection.clone();
         #[cfg(feature = "dos_ratelimit")]
@@ -212,7 +307,12 @@ async fn handle_connection(
                         let writer = Arc::new(RwLock::new(Some(send)));
 
                         let player_id = player_id_clone.clone();
+                        let join_claimed = join_claimed_clone.clone();
+                        let connected = connected_clone.clone();
+                        let in_room = in_room_clone.clone();
                         let game_session = game_session_clone.clone();
+                        let lobby = lobby_clone.clone();
+                        let metrics = metrics.clone();
                         #[cfg(feature = "dos_ratelimit")]
                         let dos_for_stream = dos_clone.clone();
 
@@ -286,73 +386,169 @@ async fn handle_connection(
                                     }
                                 };
 
+                                // Session capture for bug reports (joined connections only)
+                                if metrics.capture.is_enabled() {
+                                    if let Some(pid) = *player_id.read().await {
+                                        metrics.capture.record_inbound(pid, &client_msg);
+                                    }
+                                }
+
                                 match client_msg {
-                                    ClientMessage::JoinRequest { player_name, color_index, is_spectator } => {
+                                    ClientMessage::JoinRequest { player_name, color_index, is_spectator, party_invite, resume_token, auth_token, capabilities, accessibility, telemetry, encryption_key, room, account_token } => {
+                                        // A connection already in game or in the join queue can't join again.
+                                        // Rejections below release the claim so the client may retry.
+                                        if join_claimed.swap(true, Ordering::AcqRel) {
+                                            tracing::warn!("Ignoring JoinRequest from a joined or queued connection");
+                                            continue;
+                                        }
+
+                                        // A draining server finishes its games but takes no one new
+                                        if metrics.draining.load(std::sync::atomic::Ordering::Relaxed) {
+                                            join_claimed.store(false, Ordering::Release);
+                                            let response_msg = ServerMessage::JoinRejected {
+                                                reason: RejectionReason::Maintenance,
+                                            };
+                                            if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
+                                                tracing::warn!("Failed to send JoinRejected: {}", e);
+                                            }
+                                            continue;
+                                        }
+
                                         // === INPUT VALIDATION ===
-                                        // Sanitize player name: trim, remove control chars, limit length
-                                        let sanitized_name: String = player_name
-                                            .trim()
-                                            .chars()
-                                            // Remove control characters (0x00-0x1F and 0x7F)
-                                            .filter(|c| !c.is_control())
-                                            // Remove potentially dangerous characters
-                                            .filter(|c| *c != '<' && *c != '>' && *c != '&')
-                                            .take(16) // Max 16 characters
-                                            .collect();
-
-                                        // Collapse multiple spaces
-                                        let sanitized_name: String = sanitized_name
-                                            .split_whitespace()
-                                            .collect::<Vec<_>>()
-                                            .join(" ");
+                                        let sanitized_name = sanitize_player_name(&player_name);
 
                                         // Validate name is not empty after sanitization
                                         if sanitized_name.is_empty() {
                                             tracing::warn!("Rejecting player with empty/invalid name");
+                                            join_claimed.store(false, Ordering::Release);
                                             let response_msg = ServerMessage::JoinRejected {
                                                 reason: RejectionReason::InvalidName,
                                             };
-                                            if let Err(e) = send_to_player(&writer, &response_msg).await {
+                                            if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
                                                 tracing::warn!("Failed to send JoinRejected: {}", e);
                                             }
                                             continue;
                                         }
 
+                                        // A verified account token keeps the player's account; otherwise a new one is issued
+                                        let account = AccountKeys::global().resolve(account_token.as_deref());
+
+                                        // Lobby rooms run beside the main arena and send through their own link.
+                                        // Spectators watch rooms with SpectateRoom; room snapshots are never sealed.
+                                        if let Some(room_id) = room {
+                                            let (sealed, karma) = {
+                                                let mut session = game_session.write().await;
+                                                (session.snapshot_encryption() == SnapshotEncryptionMode::Required, session.karma(account))
+                                            };
+                                            let new_player_id = uuid::Uuid::new_v4();
+                                            let joined = if is_spectator || sealed {
+                                                Err(RejectionReason::Other { message: LocalizedText::new(keys::REJECT_ROOM_UNAVAILABLE) })
+                                            } else {
+                                                let player = RoomPlayer { id: new_player_id, name: &sanitized_name, account, karma };
+                                                join_lobby_room(&lobby, &writer, &metrics, room_id, player).await
+                                            };
+                                            match joined {
+                                                Ok(room_id) => {
+                                                    in_room.store(true, Ordering::Release);
+                                                    *player_id.write().await = Some(new_player_id);
+                                                    tracing::debug!("Player {} joined lobby room {}", new_player_id, room_id);
+                                                }
+                                                Err(reason) => {
+                                                    join_claimed.store(false, Ordering::Release);
+                                                    tracing::debug!("Rejecting player for room {}: {:?}", room_id, reason);
+                                                    let response_msg = ServerMessage::JoinRejected { reason };
+                                                    if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
+                                                        tracing::warn!("Failed to send JoinRejected: {}", e);
+                                                    }
+                                                }
+                                            }
+                                            continue;
+                                        }
+
                                         // Clamp color index to valid range (0-19)
                                         let safe_color_index = color_index.min(19);
 
+                                        let encryption_mode = game_session.read().await.snapshot_encryption();
+                                        let snapshot_cipher = if is_spectator {
+                                            None
+                                        } else {
+                                            match snapshot_cipher_for(encryption_mode, encryption_key.as_deref()) {
+                                                Ok(cipher) => cipher,
+                                                Err(message) => {
+                                                    tracing::warn!("Rejecting player '{}': {}", privacy::name(&sanitized_name), message);
+                                                    join_claimed.store(false, Ordering::Release);
+                                                    let response_msg = ServerMessage::JoinRejected {
+                                                        reason: RejectionReason::Other { message },
+                                                    };
+                                                    if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
+                                                        tracing::warn!("Failed to send JoinRejected: {}", e);
+                                                    }
+                                                    continue;
+                                                }
+                                            }
+                                        };
+
+                                        let role = RoleRegistry::global().resolve(auth_token.as_deref());
+                                        let profile = JoinProfile { role, account, capabilities, accessibility, telemetry, snapshot_cipher };
+
                                         let join_type = if is_spectator { "spectator" } else { "player" };
-                                        tracing::debug!("Received JoinRequest from '{}' as {} with color {}", sanitized_name, join_type, safe_color_index);
+                                        tracing::debug!("Received JoinRequest from '{}' as {} with color {}", privacy::name(&sanitized_name), join_type, safe_color_index);
 
-                                        // Check if server can accept new connections (performance-based)
+                                        // Spectators are admitted or rejected immediately.
+                                        // Players get a slot, a place in the join queue, or a rejection.
                                         // Note: can_accept_spectator needs write access for potential eviction
-                                        let can_accept = if is_spectator {
+                                        let slot = {
                                             let mut session = game_session.write().await;
-                                            session.can_accept_spectator()
-                                        } else {
-                                            let session = game_session.read().await;
-                                            session.can_accept_player()
+                                            if is_spectator {
+                                                if session.can_accept_spectator() { JoinSlot::Open } else { JoinSlot::Full }
+                                            } else {
+                                                let priority = session.classify_join(role, party_invite, resume_token.as_deref());
+                                                session.request_player_slot(priority)
+                                            }
                                         };
 
-                                        if !can_accept {
-                                            // Reject due to performance/capacity
-                                            let rejection_reason = {
-                                                let session = game_session.read().await;
-                                                if is_spectator {
-                                                    RejectionReason::SpectatorsFull
-                                                } else {
-                                                    session.rejection_reason()
+                                        match slot {
+                                            JoinSlot::Open => {}
+                                            JoinSlot::Queued(ticket) => {
+                                                tracing::info!("Queued player '{}' (ticket {})", privacy::name(&sanitized_name), ticket);
+                                                let queued = QueuedJoin {
+                                                    ticket,
+                                                    player_name: sanitized_name,
+                                                    color_index: safe_color_index,
+                                                    profile,
+                                                };
+                                                tokio::spawn(wait_in_join_queue(
+                                                    game_session.clone(),
+                                                    connected.clone(),
+                                                    writer.clone(),
+                                                    player_id.clone(),
+                                                    join_claimed.clone(),
+                                                    queued,
+                                                    metrics.clone(),
+                                                ));
+                                                continue;
+                                            }
+                                            JoinSlot::Full => {
+                                                // Reject due to performance/capacity; the connection may try again
+                                                join_claimed.store(false, Ordering::Release);
+                                                let rejection_reason = {
+                                                    let session = game_session.read().await;
+                                                    if is_spectator {
+                                                        RejectionReason::SpectatorsFull
+                                                    } else {
+                                                        session.rejection_reason()
+                                                    }
+                                                };
+                                                tracing::warn!("Rejecting {} '{}': {:?}", join_type, privacy::name(&sanitized_name), rejection_reason);
+
+                                                let response_msg = ServerMessage::JoinRejected {
+                                                    reason: rejection_reason,
+                                                };
+                                                if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
+                                                    tracing::warn!("Failed to send JoinRejected: {}", e);
                                                 }
-                                            };
-                                            tracing::warn!("Rejecting {} '{}': {:?}", join_type, sanitized_name, rejection_reason);
-
-                                            let response_msg = ServerMessage::JoinRejected {
-                                                reason: rejection_reason,
-                                            };
-                                            if let Err(e) = send_to_player(&writer, &response_msg).await {
-                                                tracing::warn!("Failed to send JoinRejected: {}", e);
+                                                continue;
                                             }
-                                            continue;
                                         }
 
                                         // Generate player ID
@@ -375,65 +571,31 @@ async fn handle_connection(
                                                     writer.clone(),
                                                 );
                                             }
+                                            profile.apply(&mut session, new_player_id);
                                         }
 
-                                        // Store player ID for this connection
-                                        *player_id.write().await = Some(new_player_id);
-
-                                        // Send JoinAccepted with secure random token
-                                        let session_token: Vec<u8> = (0..32).map(|_| rand::random::<u8>()).collect();
-                                        let response_msg = ServerMessage::JoinAccepted {
-                                            player_id: new_player_id,
-                                            session_token,
-                                            is_spectator,
-                                        };
-
-                                        if let Err(e) = send_to_player(&writer, &response_msg).await {
-                                            tracing::warn!("Failed to send JoinAccepted: {}", e);
+                                        if !finish_join(&game_session, &writer, &player_id, new_player_id, is_spectator, &metrics).await {
                                             break;
                                         }
-                                        tracing::debug!("Sent JoinAccepted (player_id: {})", new_player_id);
-
-                                        // Send initial snapshot (AOI-filtered for players, full for spectators)
-                                        let snapshot = {
-                                            let session = game_session.read().await;
-                                            if is_spectator {
-                                                session.get_snapshot()
-                                            } else {
-                                                session.get_filtered_snapshot(new_player_id)
-                                            }
-                                        };
-                                        let snapshot_msg = ServerMessage::Snapshot(snapshot);
-                                        if let Err(e) = send_to_player(&writer, &snapshot_msg).await {
-                                            tracing::warn!("Failed to send initial snapshot: {}", e);
-                                        } else {
-                                            tracing::debug!("Sent initial snapshot to player {}", new_player_id);
-                                        }
-
-                                        // Send PhaseChange to let client know game is playing
-                                        let phase_msg = ServerMessage::PhaseChange {
-                                            phase: crate::game::state::MatchPhase::Playing,
-                                            countdown: 0.0,
-                                        };
-                                        if let Err(e) = send_to_player(&writer, &phase_msg).await {
-                                            tracing::warn!("Failed to send PhaseChange: {}", e);
-                                        }
                                     }
 
                                     ClientMessage::Input(input) => {
                                         // Queue input for this player
                                         if let Some(pid) = *player_id.read().await {
-                                            let mut session = game_session.write().await;
-                                            session.queue_input(pid, input);
+                                            if in_room.load(Ordering::Acquire) {
+                                                queue_room_input(&lobby, pid, input).await;
+                                            } else {
+                                                queue_client_input(&game_session, &metrics, pid, input).await;
+                                            }
                                         }
                                     }
 
                                     ClientMessage::Leave => {
                                         tracing::debug!("Player requested to leave");
                                         if let Some(pid) = *player_id.read().await {
-                                            let mut session = game_session.write().await;
-                                            session.remove_player(pid);
+                                            remove_player(&game_session, &lobby, pid, in_room.load(Ordering::Acquire)).await;
                                         }
+                                        release_spectator(&lobby, connection_id).await;
                                         break;
                                     }
 
@@ -452,13 +614,17 @@ async fn handle_connection(
                                                 .as_millis() as u64,
                                         };
 
-                                        if let Err(e) = send_to_player(&writer, &response_msg).await {
+                                        if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
                                             tracing::debug!("Failed to send Pong: {}", e);
                                         }
                                     }
 
-                                    ClientMessage::SnapshotAck { tick: _ } => {
-                                        // Acknowledge received, could be used for delta compression
+                                    ClientMessage::SnapshotAck { tick } => {
+                                        // Full snapshot acks give a server-side RTT sample for rate adaptation
+                                        if let Some(pid) = *player_id.read().await {
+                                            let mut session = game_session.write().await;
+                                            session.record_snapshot_ack(pid, tick);
+                                        }
                                     }
 
                                     ClientMessage::SpectateTarget { target_id } => {
@@ -482,7 +648,7 @@ async fn handle_connection(
                                             let response_msg = ServerMessage::SpectatorModeChanged {
                                                 is_spectator: !success,
                                             };
-                                            if let Err(e) = send_to_player(&writer, &response_msg).await {
+                                            if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
                                                 tracing::warn!("Failed to send SpectatorModeChanged: {}", e);
                                             }
                                             if success {
@@ -491,6 +657,24 @@ async fn handle_connection(
                                         }
                                     }
 
+                                    ClientMessage::Chat { text } => {
+                                        // Chat line or moderator command (permission-checked in the session)
+                                        if let Some(pid) = *player_id.read().await {
+                                            let mut session = game_session.write().await;
+                                            if let Some(message) = session.handle_chat(pid, &text) {
+                                                broadcast_message(&session, &message).await;
+                                            }
+                                        }
+                                    }
+
+                                    ClientMessage::Report { target, reason } => {
+                                        // Weighted, rate limited and queued for review in the session
+                                        if let Some(pid) = *player_id.read().await {
+                                            let mut session = game_session.write().await;
+                                            session.handle_report(pid, target, reason);
+                                        }
+                                    }
+
                                     ClientMessage::ViewportInfo { zoom } => {
                                         // Client reporting current zoom level for entity filtering
                                         if let Some(pid) = *player_id.read().await {
@@ -498,14 +682,63 @@ async fn handle_connection(
                                             session.set_viewport_zoom(pid, zoom);
                                         }
                                     }
+
+                                    ClientMessage::RequestResync => {
+                                        // The client's state is corrupt; its next snapshot is sent in full
+                                        if let Some(pid) = *player_id.read().await {
+                                            let net_state = game_session.read().await.client_net_state(pid);
+                                            if let Some(net_state) = net_state {
+                                                if !net_state.lock().await.request_resync(std::time::Instant::now()) {
+                                                    tracing::debug!("Rate limiting resync request from {}", pid);
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    ClientMessage::ListRooms { filter, page } => {
+                                        // Room browser, served before or after joining
+                                        if let Some(response) = room_list(&lobby, connection_id, &filter, page).await {
+                                            if let Err(e) = send_to_player(&writer, &response, &metrics).await {
+                                                tracing::debug!("Failed to send RoomList: {}", e);
+                                            }
+                                        }
+                                    }
+
+                                    ClientMessage::SpectateRoom { room_id, player_name } => {
+                                        // The room's snapshots come through a lobby link, not the game session.
+                                        // Live room views would bypass the delay required encryption imposes.
+                                        if join_claimed.swap(true, Ordering::AcqRel) {
+                                            tracing::debug!("Ignoring SpectateRoom from a joined or queued connection");
+                                            continue;
+                                        }
+                                        let sealed = game_session.read().await.snapshot_encryption() == SnapshotEncryptionMode::Required;
+                                        let admitted = if metrics.draining.load(std::sync::atomic::Ordering::Relaxed) {
+                                            Err(RejectionReason::Maintenance)
+                                        } else if sanitize_player_name(&player_name).is_empty() {
+                                            Err(RejectionReason::InvalidName)
+                                        } else if sealed {
+                                            Err(RejectionReason::Other { message: LocalizedText::new(keys::REJECT_ROOM_UNAVAILABLE) })
+                                        } else {
+                                            spectate_lobby_room(&lobby, &writer, &metrics, connection_id, room_id).await
+                                        };
+                                        if let Err(reason) = admitted {
+                                            join_claimed.store(false, Ordering::Release);
+                                            tracing::debug!("Rejecting spectator for room {}: {:?}", room_id, reason);
+                                            let response_msg = ServerMessage::JoinRejected { reason };
+                                            if let Err(e) = send_to_player(&writer, &response_msg, &metrics).await {
+                                                tracing::warn!("Failed to send JoinRejected: {}", e);
+                                            }
+                                            continue;
+                                        }
+                                        tracing::debug!("Connection {} spectating lobby room {}", connection_id, room_id);
+                                    }
                                 }
                             }
 
                             // Clean up: remove player from session when stream closes
                             if let Some(pid) = *player_id.read().await {
                                 tracing::debug!("Player {} stream closed, removing from game", pid);
-                                let mut session = game_session.write().await;
-                                session.remove_player(pid);
+                                remove_player(&game_session, &lobby, pid, in_room.load(Ordering::Acquire)).await;
                             }
                         });
                     }
@@ -542,8 +775,14 @@ async fn handle_connection(
                         match decode::<ClientMessage>(&data) {
                             Ok(ClientMessage::Input(input)) => {
                                 if let Some(pid) = *player_id_clone.read().await {
-                                    let mut session = game_session_clone.write().await;
-                                    session.queue_input(pid, input);
+                                    if metrics.capture.is_enabled() {
+                                        metrics.capture.record_inbound(pid, &ClientMessage::Input(input.clone()));
+                                    }
+                                    if in_room_clone.load(Ordering::Acquire) {
+                                        queue_room_input(&lobby_clone, pid, input).await;
+                                    } else {
+                                        queue_client_input(&game_session_clone, &metrics, pid, input).await;
+                                    }
                                 }
                             }
                             Ok(_) => {}
@@ -562,11 +801,12 @@ async fn handle_connection(
     }
 
     // Clean up on disconnect
+    connected.store(false, Ordering::Release);
     if let Some(pid) = *player_id.read().await {
         tracing::debug!("Connection closed, removing player {}", pid);
-        let mut session = game_session.write().await;
-        session.remove_player(pid);
+        remove_player(&game_session, &lobby_manager, pid, in_room.load(Ordering::Acquire)).await;
     }
+    release_spectator(&lobby_manager, connection_id).await;
 
     // Unregister from DoS protection
     {
@@ -578,6 +818,521 @@ async fn handle_connection(
     Ok(())
 }
 
+/// How often a queued connection checks for a free slot
+const JOIN_QUEUE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Resend QueueUpdate at least this often even if the position is unchanged
+const JOIN_QUEUE_UPDATE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Room browser page for a connection (None while it is rate limited)
+#[cfg(feature = "lobby")]
+async fn room_list(
+    lobby: &RwLock<LobbyManagerType>,
+    connection_id: u64,
+    filter: &RoomFilter,
+    page: u32,
+) -> Option<ServerMessage> {
+    match lobby.write().await.browse_rooms(connection_id, filter, page, std::time::Instant::now()) {
+        Ok(response) => Some(response),
+        Err(e) => {
+            tracing::debug!("Room list refused for conn_id {}: {}", connection_id, e);
+            None
+        }
+    }
+}
+
+/// Without the lobby there are no rooms to browse
+#[cfg(not(feature = "lobby"))]
+async fn room_list(_: &RwLock<LobbyManagerType>, _: u64, _: &RoomFilter, page: u32) -> Option<ServerMessage> {
+    Some(ServerMessage::RoomList { rooms: Vec::new(), page, total_pages: 0 })
+}
+
+/// Reserve a lobby room's spectator place for a connection and link it to
+/// the room's snapshots. JoinAccepted goes down the link, ahead of them.
+#[cfg(feature = "lobby")]
+async fn spectate_lobby_room(
+    lobby: &RwLock<LobbyManagerType>,
+    writer: &Arc<RwLock<Option<wtransport::SendStream>>>,
+    metrics: &Arc<Metrics>,
+    connection_id: u64,
+    room_id: uuid::Uuid,
+) -> Result<(), RejectionReason> {
+    let mut lobby = lobby.write().await;
+    match lobby.spectate_room(connection_id, room_id) {
+        Ok(()) => {}
+        Err(LobbyError::SpectatorsFull) => return Err(RejectionReason::SpectatorsFull),
+        Err(_) => return Err(RejectionReason::Other { message: LocalizedText::new(keys::REJECT_ROOM_UNAVAILABLE) }),
+    }
+    let arena_code = lobby.get_room(room_id).map(|room| room.game_state().arena.seed.code()).unwrap_or_default();
+    lobby.links_mut().link_spectator(connection_id, spawn_link_writer(writer.clone(), metrics.clone()));
+    let accepted = ServerMessage::JoinAccepted {
+        player_id: uuid::Uuid::new_v4(),
+        session_token: Vec::new(),
+        is_spectator: true,
+        encryption_key: None,
+        arena_code,
+        world: None,
+        account_token: Vec::new(),
+    };
+    lobby.links().broadcast(&[], &[connection_id], &accepted);
+    Ok(())
+}
+
+/// Without the lobby there are no rooms to spectate
+#[cfg(not(feature = "lobby"))]
+async fn spectate_lobby_room(
+    _: &RwLock<LobbyManagerType>,
+    _: &Arc<RwLock<Option<wtransport::SendStream>>>,
+    _: &Arc<Metrics>,
+    _: u64,
+    _: uuid::Uuid,
+) -> Result<(), RejectionReason> {
+    Err(RejectionReason::Other { message: LocalizedText::new(keys::REJECT_ROOM_UNAVAILABLE) })
+}
+
+/// A connection joining a lobby room as a player
+#[cfg_attr(not(feature = "lobby"), allow(dead_code))] // Only the lobby has rooms to join
+struct RoomPlayer<'a> {
+    id: PlayerId,
+    name: &'a str,
+    account: AccountId,
+    /// Places the player in a room of their karma bracket (see `net::reports`)
+    karma: f32,
+}
+
+/// Join a lobby room as a player and link the connection to it. A matchmade
+/// room of the other karma bracket is swapped for one of the player's own.
+/// JoinAccepted goes down the link, so it always precedes the room's
+/// snapshots. Returns the room joined.
+#[cfg(feature = "lobby")]
+async fn join_lobby_room(
+    lobby: &RwLock<LobbyManagerType>,
+    writer: &Arc<RwLock<Option<wtransport::SendStream>>>,
+    metrics: &Arc<Metrics>,
+    room_id: uuid::Uuid,
+    player: RoomPlayer<'_>,
+) -> Result<uuid::Uuid, RejectionReason> {
+    use crate::lobby::player::LobbyPlayer;
+    use crate::net::session::SessionToken;
+
+    let RoomPlayer { id: player_id, name, account, karma } = player;
+    let mut lobby = lobby.write().await;
+    let session_token = SessionToken::generate();
+    let mut lobby_player = LobbyPlayer::new(player_id, name.to_string(), session_token.clone());
+    lobby_player.karma = karma;
+    lobby_player.account = Some(account);
+    let room_id = match lobby.join_room_in_bracket(room_id, lobby_player) {
+        Ok(room_id) => room_id,
+        Err(e) => {
+            tracing::debug!("Room {} refused player {}: {}", room_id, player_id, e);
+            return Err(RejectionReason::Other { message: LocalizedText::new(keys::REJECT_ROOM_UNAVAILABLE) });
+        }
+    };
+    let arena_code = lobby.get_room(room_id).map(|room| room.game_state().arena.seed.code()).unwrap_or_default();
+    lobby.links_mut().link_player(player_id, spawn_link_writer(writer.clone(), metrics.clone()));
+    let accepted = ServerMessage::JoinAccepted {
+        player_id,
+        session_token: session_token.to_vec(),
+        is_spectator: false,
+        encryption_key: None,
+        arena_code,
+        world: None,
+        account_token: AccountKeys::global().token(account),
+    };
+    lobby.links().send(player_id, &accepted);
+    Ok(room_id)
+}
+
+/// Without the lobby there are no rooms to join
+#[cfg(not(feature = "lobby"))]
+async fn join_lobby_room(
+    _: &RwLock<LobbyManagerType>,
+    _: &Arc<RwLock<Option<wtransport::SendStream>>>,
+    _: &Arc<Metrics>,
+    _: uuid::Uuid,
+    _: RoomPlayer<'_>,
+) -> Result<uuid::Uuid, RejectionReason> {
+    Err(RejectionReason::Other { message: LocalizedText::new(keys::REJECT_ROOM_UNAVAILABLE) })
+}
+
+/// Queue a room player's input in their lobby room
+async fn queue_room_input(lobby: &RwLock<LobbyManagerType>, player_id: PlayerId, input: PlayerInput) {
+    #[cfg(feature = "lobby")]
+    lobby.write().await.process_input(player_id, input);
+    #[cfg(not(feature = "lobby"))]
+    let _ = (lobby, player_id, input);
+}
+
+/// Remove a connection's player from its lobby room or the game session
+async fn remove_player(
+    game_session: &RwLock<GameSession>,
+    lobby: &RwLock<LobbyManagerType>,
+    player_id: PlayerId,
+    in_room: bool,
+) {
+    if !in_room {
+        game_session.write().await.remove_player(player_id);
+        return;
+    }
+    #[cfg(feature = "lobby")]
+    if lobby.write().await.leave_room(player_id).is_ok() {
+        tracing::debug!("Player {} left their lobby room", player_id);
+    }
+    #[cfg(not(feature = "lobby"))]
+    let _ = lobby;
+}
+
+/// Give up a connection's lobby spectator place, if it holds one
+async fn release_spectator(lobby: &RwLock<LobbyManagerType>, connection_id: u64) {
+    #[cfg(feature = "lobby")]
+    if let Some(room_id) = lobby.write().await.stop_spectating(connection_id) {
+        tracing::debug!("Connection {} stopped spectating room {}", connection_id, room_id);
+    }
+    #[cfg(not(feature = "lobby"))]
+    let _ = (lobby, connection_id);
+}
+
+/// Trim a requested player name, drop control and markup characters, cap it
+/// at 16 characters and collapse runs of whitespace
+fn sanitize_player_name(name: &str) -> String {
+    let sanitized: String = name
+        .trim()
+        .chars()
+        // Remove control characters (0x00-0x1F and 0x7F)
+        .filter(|c| !c.is_control())
+        // Remove potentially dangerous characters
+        .filter(|c| *c != '<' && *c != '>' && *c != '&')
+        .take(16) // Max 16 characters
+        .collect();
+    sanitized.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Queue a client input, first passing it through the network simulator
+/// when conditions are set for this player (dev mode only)
+async fn queue_client_input(
+    game_session: &Arc<RwLock<GameSession>>,
+    metrics: &Metrics,
+    player_id: PlayerId,
+    input: PlayerInput,
+) {
+    match metrics.netsim.inbound(player_id, std::time::Instant::now()) {
+        None => game_session.write().await.queue_input(player_id, input),
+        Some(Verdict::Drop) => {}
+        Some(Verdict::Deliver(delay)) => {
+            let game_session = game_session.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                game_session.write().await.queue_input(player_id, input);
+            });
+        }
+    }
+}
+
+/// Connection settings resolved from a JoinRequest, applied once the
+/// connection is added to the session
+#[derive(Debug, Clone)]
+struct JoinProfile {
+    role: Role,
+    account: AccountId,
+    capabilities: ClientCapabilities,
+    accessibility: AccessibilitySettings,
+    telemetry: TelemetryConsent,
+    snapshot_cipher: Option<Arc<SnapshotCipher>>,
+}
+
+impl JoinProfile {
+    fn apply(&self, session: &mut GameSession, player_id: PlayerId) {
+        if self.capabilities.progressive_join {
+            session.begin_world_stream(player_id);
+        }
+        session.set_role(player_id, self.role);
+        session.set_account(player_id, self.account);
+        session.negotiate_snapshot_rate(player_id, self.capabilities.max_snapshot_rate);
+        session.set_orbit_assist(player_id, self.accessibility.orbit_assist);
+        session.set_telemetry(player_id, self.telemetry);
+        session.set_snapshot_cipher(player_id, self.snapshot_cipher.clone());
+    }
+}
+
+/// Set up snapshot encryption for a joining player under the server's mode.
+/// Err is the rejection message when the server requires encryption and the
+/// client can't provide it.
+fn snapshot_cipher_for(
+    mode: SnapshotEncryptionMode,
+    client_key: Option<&[u8]>,
+) -> Result<Option<Arc<SnapshotCipher>>, LocalizedText> {
+    match (mode, client_key) {
+        (SnapshotEncryptionMode::Off, _) => Ok(None),
+        (_, Some(key)) => match SnapshotCipher::negotiate(key) {
+            Ok(cipher) => Ok(Some(Arc::new(cipher))),
+            Err(e) if mode == SnapshotEncryptionMode::Required => {
+                Err(LocalizedText::new(keys::REJECT_ENCRYPTION_FAILED).with("error", e))
+            }
+            Err(e) => {
+                tracing::debug!("Snapshot encryption not negotiated: {}", e);
+                Ok(None)
+            }
+        },
+        (SnapshotEncryptionMode::Required, None) => {
+            Err(LocalizedText::new(keys::REJECT_ENCRYPTION_REQUIRED))
+        }
+        (SnapshotEncryptionMode::Optional, None) => Ok(None),
+    }
+}
+
+/// Encode and seal a snapshot-like message with the connection's key.
+/// None if sealing fails: the message is never sent in the clear.
+fn seal_message(cipher: Option<&SnapshotCipher>, message: ServerMessage) -> Option<ServerMessage> {
+    let Some(cipher) = cipher else {
+        return Some(message);
+    };
+    let sealed = encode(&message)
+        .map_err(|e| e.to_string())
+        .and_then(|encoded| cipher.seal(&encoded).map_err(|e| e.to_string()));
+    match sealed {
+        Ok(sealed) => Some(sealed),
+        Err(e) => {
+            tracing::warn!("Failed to seal message: {}", e);
+            None
+        }
+    }
+}
+
+/// Send a streamed join snapshot's chunks, `per_tick` each tick.
+/// Returns false if the connection is gone or a chunk could not be sealed.
+async fn send_world_chunks(
+    writer: &Arc<RwLock<Option<wtransport::SendStream>>>,
+    cipher: Option<&SnapshotCipher>,
+    chunks: Vec<WorldChunk>,
+    per_tick: usize,
+    metrics: &Metrics,
+) -> bool {
+    let mut ticks = tokio::time::interval(std::time::Duration::from_millis(physics::TICK_DURATION_MS));
+    let mut chunks = chunks.into_iter().peekable();
+    while chunks.peek().is_some() {
+        ticks.tick().await;
+        for chunk in chunks.by_ref().take(per_tick.max(1)) {
+            // A missing chunk would leave the client's world incomplete
+            let Some(message) = seal_message(cipher, ServerMessage::WorldChunk(chunk)) else {
+                return false;
+            };
+            if let Err(e) = send_to_player(writer, &message, metrics).await {
+                tracing::warn!("Failed to send world chunk: {}", e);
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Complete a join after the connection has been added to the session:
+/// issue a session token, record the player ID for this connection and send
+/// JoinAccepted, the initial snapshot (whole or streamed in chunks, see
+/// `world_stream`) and the current phase.
+/// Returns false if the connection is gone.
+async fn finish_join(
+    game_session: &Arc<RwLock<GameSession>>,
+    writer: &Arc<RwLock<Option<wtransport::SendStream>>>,
+    player_id: &Arc<RwLock<Option<PlayerId>>>,
+    new_player_id: PlayerId,
+    is_spectator: bool,
+    metrics: &Metrics,
+) -> bool {
+    // Secure random token; players can present it later for reconnect priority
+    let session_token: Vec<u8> = (0..32).map(|_| rand::random::<u8>()).collect();
+    if !is_spectator {
+        let mut session = game_session.write().await;
+        session.remember_session_token(new_player_id, session_token.clone());
+    }
+
+    // Store player ID for this connection
+    *player_id.write().await = Some(new_player_id);
+
+    // Initial snapshot (AOI-filtered for players, full for spectators;
+    // delayed spectators wait for the delayed stream instead of seeing live state)
+    // (live spectators get the latest published view instead of a freshly built snapshot)
+    let (cipher, arena_code, snapshot, stream, chunks_per_tick, account_token) = {
+        let session = game_session.read().await;
+        let snapshot = if !is_spectator {
+            Some(session.get_filtered_snapshot(new_player_id))
+        } else if session.is_delayed_spectator(new_player_id) {
+            None
+        } else {
+            Some(session.state_view().load().snapshot.as_ref().clone())
+        };
+        let config = session.join_stream_config();
+        let stream = snapshot
+            .as_ref()
+            .filter(|_| session.is_world_streaming(new_player_id))
+            .and_then(|snapshot| world_stream::plan(snapshot, Some(new_player_id), config.chunk_entities));
+        let chunks_per_tick = config.chunks_per_tick;
+        let account_token = session.account_of(new_player_id).map(|account| AccountKeys::global().token(account));
+        (session.snapshot_cipher(new_player_id), session.arena_code(), snapshot, stream, chunks_per_tick, account_token)
+    };
+    if stream.is_none() {
+        game_session.write().await.end_world_stream(new_player_id);
+    }
+    let (world, chunks) = match stream {
+        Some(WorldStream { world, chunks }) => (Some(world), chunks),
+        None => (None, Vec::new()),
+    };
+
+    let response_msg = ServerMessage::JoinAccepted {
+        player_id: new_player_id,
+        session_token,
+        is_spectator,
+        encryption_key: cipher.as_ref().map(|c| c.public_key().to_vec()),
+        arena_code,
+        world,
+        account_token: account_token.unwrap_or_default(),
+    };
+
+    if let Err(e) = send_to_player(writer, &response_msg, metrics).await {
+        tracing::warn!("Failed to send JoinAccepted: {}", e);
+        return false;
+    }
+    tracing::debug!("Sent JoinAccepted (player_id: {})", new_player_id);
+
+    if !is_spectator {
+        let hz = game_session.read().await.snapshot_rate_for(new_player_id).hz();
+        if let Err(e) = send_to_player(writer, &ServerMessage::SnapshotRate { hz }, metrics).await {
+            tracing::warn!("Failed to send SnapshotRate: {}", e);
+        }
+    }
+
+    match snapshot {
+        Some(snapshot) if !chunks.is_empty() => {
+            let count = chunks.len();
+            if !send_world_chunks(writer, cipher.as_deref(), chunks, chunks_per_tick, metrics).await {
+                return false;
+            }
+            // The assembled snapshot is the player's first baseline, so regular
+            // snapshots resume with a delta
+            let net_state = game_session.read().await.client_net_state(new_player_id);
+            if let Some(net_state) = net_state.filter(|_| !is_spectator) {
+                let mut state = net_state.lock().await;
+                state.last_full_tick = snapshot.tick;
+                state.push_baseline(snapshot, None);
+                state.needs_full_resync = false;
+            }
+            game_session.write().await.end_world_stream(new_player_id);
+            tracing::debug!("Streamed initial snapshot to player {} in {} chunks", new_player_id, count);
+        }
+        Some(snapshot) => {
+            // Unsealable: skipped, the first regular broadcast is a full resync
+            if let Some(snapshot_msg) = seal_message(cipher.as_deref(), ServerMessage::Snapshot(snapshot)) {
+                if let Err(e) = send_to_player(writer, &snapshot_msg, metrics).await {
+                    tracing::warn!("Failed to send initial snapshot: {}", e);
+                } else {
+                    tracing::debug!("Sent initial snapshot to player {}", new_player_id);
+                }
+            }
+        }
+        None => {}
+    }
+
+    // Send PhaseChange to let client know game is playing
+    let phase_msg = ServerMessage::PhaseChange {
+        phase: crate::game::state::MatchPhase::Playing,
+        countdown: 0.0,
+    };
+    if let Err(e) = send_to_player(writer, &phase_msg, metrics).await {
+        tracing::warn!("Failed to send PhaseChange: {}", e);
+    }
+
+    let (pause_notice, modifier_events) = {
+        let session = game_session.read().await;
+        (session.pause_notice(), session.active_modifier_events())
+    };
+    if let Some(notice) = pause_notice {
+        if let Err(e) = send_to_player(writer, &notice, metrics).await {
+            tracing::warn!("Failed to send MatchPaused: {}", e);
+        }
+    }
+    for event in modifier_events {
+        if let Err(e) = send_to_player(writer, &event, metrics).await {
+            tracing::warn!("Failed to send active modifier: {}", e);
+        }
+    }
+    true
+}
+
+/// A player join waiting in the join queue
+struct QueuedJoin {
+    ticket: TicketId,
+    player_name: String,
+    color_index: u8,
+    profile: JoinProfile,
+}
+
+/// Keep a queued connection informed of its position until a slot opens,
+/// then admit it. Leaves the queue if the connection goes away.
+async fn wait_in_join_queue(
+    game_session: Arc<RwLock<GameSession>>,
+    connected: Arc<AtomicBool>,
+    writer: Arc<RwLock<Option<wtransport::SendStream>>>,
+    player_id: Arc<RwLock<Option<PlayerId>>>,
+    join_claimed: Arc<AtomicBool>,
+    queued: QueuedJoin,
+    metrics: Arc<Metrics>,
+) {
+    let QueuedJoin { ticket, player_name, color_index, profile } = queued;
+    let mut last_sent: Option<(u32, std::time::Instant)> = None;
+    loop {
+        let new_player_id = uuid::Uuid::new_v4();
+        let status = {
+            let mut session = game_session.write().await;
+            // Checked under the lock so a client that left is never admitted
+            if !connected.load(Ordering::Acquire) {
+                tracing::debug!("Queued connection gone (ticket {})", ticket);
+                session.cancel_join_ticket(ticket);
+                join_claimed.store(false, Ordering::Release);
+                return;
+            }
+            let status = session.poll_join_ticket(ticket);
+            if status == QueueStatus::Admit {
+                // Add while still holding the lock so the slot can't be taken
+                session.add_player(new_player_id, player_name.clone(), color_index, writer.clone());
+                profile.apply(&mut session, new_player_id);
+            }
+            status
+        };
+
+        match status {
+            QueueStatus::Admit => {
+                tracing::info!("Admitted queued player '{}' (ticket {})", privacy::name(&player_name), ticket);
+                let joined = finish_join(&game_session, &writer, &player_id, new_player_id, false, &metrics).await;
+                // The connection's own cleanup may have run before player_id was set
+                if !joined || !connected.load(Ordering::Acquire) {
+                    game_session.write().await.remove_player(new_player_id);
+                }
+                return;
+            }
+            QueueStatus::Waiting { position, eta_secs } => {
+                let due = last_sent.map_or(true, |(last_position, at)| {
+                    last_position != position || at.elapsed() >= JOIN_QUEUE_UPDATE_INTERVAL
+                });
+                if due {
+                    let update = ServerMessage::QueueUpdate { position, eta_secs };
+                    if let Err(e) = send_to_player(&writer, &update, &metrics).await {
+                        tracing::debug!("Queued connection gone (ticket {}): {}", ticket, e);
+                        game_session.write().await.cancel_join_ticket(ticket);
+                        join_claimed.store(false, Ordering::Release);
+                        return;
+                    }
+                    last_sent = Some((position, std::time::Instant::now()));
+                }
+            }
+            QueueStatus::Gone => {
+                join_claimed.store(false, Ordering::Release);
+                return;
+            }
+        }
+
+        tokio::time::sleep(JOIN_QUEUE_POLL_INTERVAL).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // WebTransport tests require a more complex setup with actual
@@ -609,4 +1364,21 @@ mod tests {
 
         assert!(!hash.is_empty());
     }
+
+    #[test]
+    fn test_snapshot_cipher_for_mode() {
+        use ring::agreement::{EphemeralPrivateKey, ECDH_P256};
+        let private_key = EphemeralPrivateKey::generate(&ECDH_P256, &ring::rand::SystemRandom::new()).unwrap();
+        let client_key = private_key.compute_public_key().unwrap().as_ref().to_vec();
+
+        assert!(snapshot_cipher_for(SnapshotEncryptionMode::Off, Some(&client_key)).unwrap().is_none());
+        assert!(snapshot_cipher_for(SnapshotEncryptionMode::Optional, None).unwrap().is_none());
+        assert!(snapshot_cipher_for(SnapshotEncryptionMode::Optional, Some(&[1, 2])).unwrap().is_none());
+        assert!(snapshot_cipher_for(SnapshotEncryptionMode::Optional, Some(&client_key)).unwrap().is_some());
+
+        // Required rejects clients without a usable key
+        assert!(snapshot_cipher_for(SnapshotEncryptionMode::Required, None).is_err());
+        assert!(snapshot_cipher_for(SnapshotEncryptionMode::Required, Some(&[1, 2])).is_err());
+        assert!(snapshot_cipher_for(SnapshotEncryptionMode::Required, Some(&client_key)).unwrap().is_some());
+    }
 }
@@ -0,0 +1,189 @@
+//! Streaming the join snapshot in chunks
+//!
+//! A joining client used to get its whole AOI in one initial snapshot, which
+//! in a crowded arena is one large message arriving just as the connection
+//! opens. A client that announces `progressive_join` instead gets the arena
+//! in `JoinAccepted` (geometry, gravity wells, match state: the snapshot with
+//! its entity lists emptied) and the entities over the next few ticks in
+//! `WorldChunk`s, most important first: its own player, then everything by
+//! distance from it, with debris counted as twice as far as players and
+//! projectiles.
+//!
+//! Regular snapshots skip the client while it streams. Once the last chunk
+//! is sent, the join snapshot becomes the client's delta baseline, so its
+//! first broadcast after the stream is a delta against what it assembled.
+
+use crate::game::state::PlayerId;
+use crate::net::protocol::{GameSnapshot, JoinWorld, WorldChunk};
+use crate::util::vec2::Vec2;
+
+/// Debris sorts as if this many times further away
+const DEBRIS_DISTANCE_FACTOR: f32 = 2.0;
+
+/// A join snapshot split for streaming
+#[derive(Debug, Clone)]
+pub struct WorldStream {
+    pub world: JoinWorld,
+    pub chunks: Vec<WorldChunk>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Entity {
+    Player(usize),
+    Projectile(usize),
+    Debris(usize),
+}
+
+/// Split `snapshot` into chunks of at most `chunk_entities` entities, ordered
+/// around `focus_id` (the arena center without one). None when everything fits
+/// in one chunk: the snapshot is then sent whole, as before.
+pub fn plan(snapshot: &GameSnapshot, focus_id: Option<PlayerId>, chunk_entities: usize) -> Option<WorldStream> {
+    let chunk_entities = chunk_entities.max(1);
+    let total = snapshot.players.len() + snapshot.projectiles.len() + snapshot.debris.len();
+    if total <= chunk_entities {
+        return None;
+    }
+
+    let focus = focus_id
+        .and_then(|id| snapshot.players.iter().find(|p| p.id == id))
+        .map_or(Vec2::ZERO, |p| p.position);
+    let mut order: Vec<(f32, Entity)> = Vec::with_capacity(total);
+    for (i, player) in snapshot.players.iter().enumerate() {
+        let distance = if Some(player.id) == focus_id { -1.0 } else { player.position.distance_to(focus) };
+        order.push((distance, Entity::Player(i)));
+    }
+    for (i, projectile) in snapshot.projectiles.iter().enumerate() {
+        order.push((projectile.position.distance_to(focus), Entity::Projectile(i)));
+    }
+    for (i, debris) in snapshot.debris.iter().enumerate() {
+        order.push((debris.position.distance_to(focus) * DEBRIS_DISTANCE_FACTOR, Entity::Debris(i)));
+    }
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let chunks: Vec<WorldChunk> = order
+        .chunks(chunk_entities)
+        .enumerate()
+        .map(|(index, entities)| {
+            let mut chunk = WorldChunk {
+                tick: snapshot.tick,
+                index: index as u16,
+                players: Vec::new(),
+                projectiles: Vec::new(),
+                debris: Vec::new(),
+            };
+            for &(_, entity) in entities {
+                match entity {
+                    Entity::Player(i) => chunk.players.push(snapshot.players[i].clone()),
+                    Entity::Projectile(i) => chunk.projectiles.push(snapshot.projectiles[i].clone()),
+                    Entity::Debris(i) => chunk.debris.push(snapshot.debris[i].clone()),
+                }
+            }
+            chunk
+        })
+        .collect();
+
+    let base = GameSnapshot { players: vec![], projectiles: vec![], debris: vec![], ..snapshot.clone() };
+    Some(WorldStream { world: JoinWorld { base, chunks: chunks.len() as u16 }, chunks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::MatchPhase;
+    use crate::net::protocol::{decode, encode, player_flags, DebrisSnapshot, PlayerSnapshot, ServerMessage};
+    use uuid::Uuid;
+
+    fn player_at(x: f32) -> PlayerSnapshot {
+        PlayerSnapshot {
+            id: Uuid::new_v4(),
+            name: String::new(),
+            position: Vec2::new(x, 0.0),
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            mass: 100.0,
+            flags: player_flags::ALIVE,
+            kills: 0,
+            deaths: 0,
+            color_index: 0,
+            spawn_tick: 0,
+            charge: 0,
+            heat: 0,
+            faction: 0,
+            effects: 0,
+        }
+    }
+
+    fn snapshot(players: Vec<PlayerSnapshot>, debris: Vec<DebrisSnapshot>) -> GameSnapshot {
+        GameSnapshot {
+            tick: 42,
+            match_phase: MatchPhase::Playing,
+            match_time: 0.0,
+            countdown: 0.0,
+            players,
+            projectiles: vec![],
+            debris,
+            arena_collapse_phase: 0,
+            arena_safe_radius: 1000.0,
+            arena_scale: 1.0,
+            gravity_wells: vec![],
+            asteroids: vec![],
+            total_players: 0,
+            total_alive: 0,
+            density_grid: vec![],
+            echo_client_time: 0,
+            interp_delay_ms: 0,
+            ai_status: None,
+        }
+    }
+
+    #[test]
+    fn test_small_world_is_not_streamed() {
+        let snapshot = snapshot(vec![player_at(0.0), player_at(10.0)], vec![]);
+        assert!(plan(&snapshot, None, 16).is_none());
+    }
+
+    #[test]
+    fn test_chunks_start_with_focus_then_nearest() {
+        let players: Vec<_> = [900.0, 500.0, 100.0, 300.0, 700.0].into_iter().map(player_at).collect();
+        let focus = players[1].id;
+        let debris = vec![DebrisSnapshot { id: 1, position: Vec2::new(550.0, 0.0), size: 0 }];
+        let snapshot = snapshot(players, debris);
+
+        let stream = plan(&snapshot, Some(focus), 2).unwrap();
+        assert_eq!(stream.world.chunks, 3);
+        assert!(stream.world.base.players.is_empty() && stream.world.base.debris.is_empty());
+        assert_eq!(stream.world.base.arena_safe_radius, 1000.0);
+
+        // Focus first; the debris 50 away sorts as 100, ahead of players 200 away.
+        // Ties keep snapshot order.
+        assert_eq!(stream.chunks[0].players[0].id, focus);
+        assert_eq!(stream.chunks[0].debris.len(), 1);
+        let xs: Vec<f32> = stream.chunks.iter().flat_map(|c| c.players.iter().map(|p| p.position.x)).collect();
+        assert_eq!(xs, vec![500.0, 300.0, 700.0, 900.0, 100.0]);
+        assert!(stream.chunks.iter().enumerate().all(|(i, c)| c.index as usize == i && c.tick == 42));
+    }
+
+    #[test]
+    fn test_join_world_and_chunks_round_trip() {
+        let snapshot = snapshot((0..4).map(|i| player_at(i as f32)).collect(), vec![]);
+        let stream = plan(&snapshot, None, 3).unwrap();
+
+        let msg = ServerMessage::JoinAccepted {
+            player_id: Uuid::new_v4(),
+            session_token: vec![],
+            is_spectator: true,
+            encryption_key: None,
+            arena_code: String::new(),
+            world: Some(stream.world.clone()),
+            account_token: vec![],
+        };
+        match decode::<ServerMessage>(&encode(&msg).unwrap()).unwrap() {
+            ServerMessage::JoinAccepted { world: Some(world), .. } => assert_eq!(world.chunks, 2),
+            _ => panic!("Wrong message type"),
+        }
+        match decode::<ServerMessage>(&encode(&ServerMessage::WorldChunk(stream.chunks[1].clone())).unwrap()).unwrap() {
+            ServerMessage::WorldChunk(chunk) => assert_eq!((chunk.index, chunk.players.len()), (1, 1)),
+            _ => panic!("Wrong message type"),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Public stats API
+//!
+//! Read-only routes under `/api/v1/` for community sites: server info, the
+//! live leaderboard, finished matches and player presence. Each caller needs an
+//! API key from the keys file, passed as the bearer token. Keys are separate
+//! from role tokens and tenant keys: they never reach admin or tenant routes.
+//! Every key has its own requests-per-minute limit, and responses are cached
+//! for a few seconds across keys, so a popular site polling the API costs the
+//! server one build of each response per cache window.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::PublicApiConfig;
+
+/// Prefix of every public API route
+pub const ROUTE_PREFIX: &str = "/api/v1/";
+
+/// Length of a rate limit window
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// A community site's API key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Stable id, used in logs instead of the key
+    pub id: String,
+    /// Requests allowed per minute (None = the configured default)
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+}
+
+/// Why a public API request was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PublicApiError {
+    #[error("missing or unknown API key")]
+    UnknownKey,
+    #[error("rate limit exceeded, retry in {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+}
+
+/// Requests one key made in the current window
+#[derive(Debug, Clone, Copy)]
+struct RateWindow {
+    started: Instant,
+    requests: u32,
+}
+
+/// Requests allowed and left for a key in its current window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateStatus {
+    pub limit: u32,
+    pub remaining: u32,
+}
+
+/// A cached response body
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    cached_at: Instant,
+    body: String,
+}
+
+/// API keys, their rate windows and the shared response cache
+#[derive(Debug)]
+pub struct PublicApi {
+    keys: HashMap<String, ApiKey>,
+    default_requests_per_minute: u32,
+    cache_ttl: Duration,
+    windows: Mutex<HashMap<String, RateWindow>>,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl PublicApi {
+    /// Build from config, loading the keys file (`{"<key>": {"id": "...", "requests_per_minute": N}}`)
+    pub fn from_config(config: &PublicApiConfig) -> Self {
+        let mut keys = HashMap::new();
+        if let Some(path) = &config.keys_file {
+            match std::fs::read_to_string(path) {
+                Ok(json) => match serde_json::from_str::<HashMap<String, ApiKey>>(&json) {
+                    Ok(loaded) => keys.extend(loaded),
+                    Err(e) => warn!("Invalid public API keys file {}: {}", path, e),
+                },
+                Err(e) => warn!("Failed to read public API keys file {}: {}", path, e),
+            }
+        }
+        if !keys.is_empty() {
+            info!("Public API: {} key(s) configured", keys.len());
+        }
+        Self::with_keys(keys, config)
+    }
+
+    fn with_keys(keys: HashMap<String, ApiKey>, config: &PublicApiConfig) -> Self {
+        Self {
+            keys,
+            default_requests_per_minute: config.requests_per_minute,
+            cache_ttl: Duration::from_secs(config.cache_secs),
+            windows: Mutex::new(HashMap::new()),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Seconds responses are cached for
+    pub fn cache_secs(&self) -> u64 {
+        self.cache_ttl.as_secs()
+    }
+
+    /// Resolve an API key and count the request against its limit
+    pub fn admit(&self, api_key: Option<&str>, now: Instant) -> Result<(&ApiKey, RateStatus), PublicApiError> {
+        let key = api_key.and_then(|k| self.keys.get(k.trim())).ok_or(PublicApiError::UnknownKey)?;
+        let limit = key.requests_per_minute.unwrap_or(self.default_requests_per_minute);
+
+        let mut windows = self.windows.lock();
+        let window = windows.entry(key.id.clone()).or_insert(RateWindow { started: now, requests: 0 });
+        if now.duration_since(window.started) >= RATE_WINDOW {
+            *window = RateWindow { started: now, requests: 0 };
+        }
+        if window.requests >= limit {
+            let retry_after = RATE_WINDOW.saturating_sub(now.duration_since(window.started));
+            return Err(PublicApiError::RateLimited { retry_after_secs: retry_after.as_secs().max(1) });
+        }
+        window.requests += 1;
+        Ok((key, RateStatus { limit, remaining: limit - window.requests }))
+    }
+
+    /// Cached body for a route, building and caching it when missing or stale.
+    /// Only successful responses are cached.
+    pub fn cached(
+        &self,
+        route: &str,
+        now: Instant,
+        build: impl FnOnce() -> (&'static str, String),
+    ) -> (&'static str, String) {
+        if let Some(hit) = self.cache.lock().get(route) {
+            if now.duration_since(hit.cached_at) < self.cache_ttl {
+                return ("200 OK", hit.body.clone());
+            }
+        }
+        let (status, body) = build();
+        if status == "200 OK" && !self.cache_ttl.is_zero() {
+            let mut cache = self.cache.lock();
+            cache.retain(|_, cached| now.duration_since(cached.cached_at) < self.cache_ttl);
+            cache.insert(route.to_string(), CachedResponse { cached_at: now, body: body.clone() });
+        }
+        (status, body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api(cache_secs: u64) -> PublicApi {
+        let keys = serde_json::from_str(
+            r#"{ "key-a": {"id": "site-a", "requests_per_minute": 2}, "key-b": {"id": "site-b"} }"#,
+        )
+        .unwrap();
+        PublicApi::with_keys(keys, &PublicApiConfig { cache_secs, ..Default::default() })
+    }
+
+    #[test]
+    fn test_each_key_has_its_own_rate_window() {
+        let api = api(5);
+        let now = Instant::now();
+
+        assert_eq!(api.admit(None, now).unwrap_err(), PublicApiError::UnknownKey);
+        assert_eq!(api.admit(Some("nope"), now).unwrap_err(), PublicApiError::UnknownKey);
+
+        let (key, status) = api.admit(Some("key-a"), now).unwrap();
+        assert_eq!((key.id.as_str(), status), ("site-a", RateStatus { limit: 2, remaining: 1 }));
+        api.admit(Some("key-a"), now).unwrap();
+        assert_eq!(
+            api.admit(Some("key-a"), now + Duration::from_secs(45)).unwrap_err(),
+            PublicApiError::RateLimited { retry_after_secs: 15 }
+        );
+
+        // Other keys are unaffected, and the window resets after a minute
+        let default_limit = PublicApiConfig::default().requests_per_minute;
+        assert_eq!(api.admit(Some("key-b"), now).unwrap().1.limit, default_limit);
+        assert!(api.admit(Some("key-a"), now + RATE_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_successful_responses_are_cached_until_stale() {
+        let api = api(5);
+        let now = Instant::now();
+        let mut builds = 0;
+        let mut get = |route: &str, at: Instant, status: &'static str| {
+            api.cached(route, at, || {
+                builds += 1;
+                (status, format!("body {}", builds))
+            })
+        };
+
+        assert_eq!(get("/api/v1/server", now, "200 OK").1, "body 1");
+        assert_eq!(get("/api/v1/server", now + Duration::from_secs(4), "200 OK").1, "body 1");
+        assert_eq!(get("/api/v1/server", now + Duration::from_secs(5), "200 OK").1, "body 2");
+        // Errors are rebuilt every time
+        get("/api/v1/matches/9", now, "404 Not Found");
+        assert_eq!(get("/api/v1/matches/9", now, "404 Not Found").1, "body 4");
+    }
+}
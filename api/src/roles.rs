@@ -0,0 +1,204 @@
+//! Roles and access control
+//!
+//! Every connection and admin HTTP request resolves to a [`Role`] from its
+//! auth token. Features never compare roles directly; they ask whether the
+//! role grants a [`Permission`], so the whole policy lives in
+//! [`Role::allows`].
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::RolesConfig;
+
+/// Global role registry
+static ROLE_REGISTRY: OnceLock<RoleRegistry> = OnceLock::new();
+
+/// Role of a connection, lowest to highest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    #[default]
+    Player,
+    Vip,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    /// Whether this role grants a permission (the access policy)
+    pub fn allows(self, permission: Permission) -> bool {
+        let required = match permission {
+            Permission::InviteParty => Role::Player,
+            Permission::ReservedSlot => Role::Vip,
+            Permission::ModerationCommands => Role::Moderator,
+            Permission::CasterTools => Role::Moderator,
+            Permission::MatchControl => Role::Moderator,
+            Permission::AdminApi => Role::Admin,
+        };
+        self >= required
+    }
+
+    /// Check a permission, returning an error suitable for logging/replies
+    pub fn require(self, permission: Permission) -> Result<(), AccessDenied> {
+        if self.allows(permission) {
+            Ok(())
+        } else {
+            Err(AccessDenied { role: self, permission })
+        }
+    }
+}
+
+/// Capabilities gated by role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Issue party invite codes for the join queue
+    InviteParty,
+    /// Use join queue reserved slots
+    ReservedSlot,
+    /// Run moderation chat commands
+    ModerationCommands,
+    /// Spectator caster tools (forced follow, announcements)
+    CasterTools,
+    /// Referee controls (pause/resume the match)
+    MatchControl,
+    /// Access admin HTTP endpoints
+    AdminApi,
+}
+
+/// A role lacked the permission for an action
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{role:?} lacks permission {permission:?}")]
+pub struct AccessDenied {
+    pub role: Role,
+    pub permission: Permission,
+}
+
+/// Token to role lookup
+#[derive(Debug, Default)]
+pub struct RoleRegistry {
+    tokens: HashMap<String, Role>,
+}
+
+impl RoleRegistry {
+    /// Build from config: the roles file first, then env token lists (env wins)
+    pub fn from_config(config: &RolesConfig) -> Self {
+        let mut registry = Self::default();
+
+        if let Some(path) = &config.roles_file {
+            match std::fs::read_to_string(path) {
+                Ok(json) => match serde_json::from_str::<HashMap<String, Role>>(&json) {
+                    Ok(tokens) => registry.tokens.extend(tokens),
+                    Err(e) => warn!("Invalid roles file {}: {}", path, e),
+                },
+                Err(e) => warn!("Failed to read roles file {}: {}", path, e),
+            }
+        }
+
+        for (tokens, role) in [
+            (&config.vip_tokens, Role::Vip),
+            (&config.moderator_tokens, Role::Moderator),
+            (&config.admin_tokens, Role::Admin),
+        ] {
+            for token in tokens {
+                registry.tokens.insert(token.clone(), role);
+            }
+        }
+        registry
+    }
+
+    /// Get the global registry (loads from env on first call)
+    pub fn global() -> &'static Self {
+        ROLE_REGISTRY.get_or_init(|| {
+            let registry = Self::from_config(&RolesConfig::from_env());
+            if registry.is_configured() {
+                info!("Roles: {} privileged token(s) configured", registry.tokens.len());
+            }
+            registry
+        })
+    }
+
+    /// Whether any privileged tokens exist
+    pub fn is_configured(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    /// Role for an auth token (unknown or missing tokens are plain players)
+    pub fn resolve(&self, token: Option<&str>) -> Role {
+        token
+            .and_then(|t| self.tokens.get(t.trim()))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Extract the bearer token from a raw HTTP request
+pub fn bearer_token(request: &str) -> Option<&str> {
+    request.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if !name.trim().eq_ignore_ascii_case("authorization") {
+            return None;
+        }
+        let value = value.trim();
+        let scheme = value.get(..7)?;
+        scheme.eq_ignore_ascii_case("bearer ").then(|| value[7..].trim())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_policy() {
+        assert!(Role::Player.allows(Permission::InviteParty));
+        assert!(!Role::Player.allows(Permission::ReservedSlot));
+        assert!(Role::Vip.allows(Permission::ReservedSlot));
+        assert!(!Role::Vip.allows(Permission::ModerationCommands));
+        assert!(Role::Moderator.allows(Permission::CasterTools));
+        assert!(!Role::Vip.allows(Permission::MatchControl));
+        assert!(!Role::Moderator.allows(Permission::AdminApi));
+        assert!(Role::Admin.allows(Permission::AdminApi));
+        assert_eq!(
+            Role::Player.require(Permission::AdminApi),
+            Err(AccessDenied { role: Role::Player, permission: Permission::AdminApi })
+        );
+    }
+
+    #[test]
+    fn test_registry_resolves_tokens() {
+        let config = RolesConfig {
+            admin_tokens: vec!["root".to_string()],
+            vip_tokens: vec!["gold".to_string(), "root".to_string()],
+            ..Default::default()
+        };
+        let registry = RoleRegistry::from_config(&config);
+
+        assert!(registry.is_configured());
+        assert_eq!(registry.resolve(Some("root")), Role::Admin);
+        assert_eq!(registry.resolve(Some("gold")), Role::Vip);
+        assert_eq!(registry.resolve(Some("nope")), Role::Player);
+        assert_eq!(registry.resolve(None), Role::Player);
+        assert!(!RoleRegistry::default().is_configured());
+    }
+
+    #[test]
+    fn test_roles_file_json() {
+        let tokens: HashMap<String, Role> =
+            serde_json::from_str(r#"{"a": "moderator", "b": "vip"}"#).unwrap();
+        assert_eq!(tokens["a"], Role::Moderator);
+        assert_eq!(tokens["b"], Role::Vip);
+        assert!(serde_json::from_str::<HashMap<String, Role>>(r#"{"c": "owner"}"#).is_err());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let request = "GET /players/input-stats HTTP/1.1\r\nHost: x\r\nAuthorization: Bearer abc123\r\n\r\n";
+        assert_eq!(bearer_token(request), Some("abc123"));
+        assert_eq!(bearer_token("GET / HTTP/1.1\r\nauthorization: bearer  t \r\n"), Some("t"));
+        assert_eq!(bearer_token("GET / HTTP/1.1\r\nAuthorization: Basic Zm9v\r\n"), None);
+        assert_eq!(bearer_token("GET / HTTP/1.1\r\n\r\n"), None);
+    }
+}
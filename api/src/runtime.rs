@@ -0,0 +1,92 @@
+//! Thread pools and core pinning
+//!
+//! By default tokio and Rayon each start one thread per core, and the game
+//! loop is just another tokio task. With very large bot counts, the Rayon
+//! workers used inside a tick compete with tokio's workers (and with the tick
+//! itself) for the same cores, which shows up as tick jitter. `RuntimeConfig`
+//! sizes both pools and can move the game loop onto a dedicated thread pinned
+//! to one core, e.g. core 0 for the loop, a few cores for Rayon and the rest
+//! for tokio.
+//!
+//! The pinned thread drives the game loop future through the tokio runtime's
+//! handle, so timers keep working and tasks it spawns (snapshot and event
+//! broadcasts) still run on the tokio workers.
+
+use std::future::Future;
+use std::io;
+
+use tracing::{info, warn};
+
+use crate::config::RuntimeConfig;
+
+/// Size the global Rayon pool (must run before anything uses Rayon)
+pub fn configure_rayon(config: &RuntimeConfig) {
+    let Some(threads) = config.rayon_threads else {
+        return;
+    };
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("rayon-{}", i))
+        .build_global()
+    {
+        Ok(()) => info!("Rayon pool: {} threads", threads),
+        Err(e) => warn!("Failed to configure the Rayon pool, using the default: {}", e),
+    }
+}
+
+/// Build the multi-threaded tokio runtime with the configured worker count
+pub fn build_tokio(config: &RuntimeConfig) -> io::Result<tokio::runtime::Runtime> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all();
+    if let Some(workers) = config.tokio_workers {
+        builder.worker_threads(workers);
+        info!("Tokio runtime: {} worker threads", workers);
+    }
+    builder.build()
+}
+
+/// Run `future` to completion on a new thread pinned to `core`, within the
+/// current tokio runtime (a missing core only logs a warning)
+pub fn spawn_pinned<F>(name: &str, core: usize, future: F) -> io::Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let handle = tokio::runtime::Handle::current();
+    let thread_name = name.to_string();
+    std::thread::Builder::new().name(thread_name.clone()).spawn(move || {
+        if pin_current_thread(core) {
+            info!("{} thread pinned to core {}", thread_name, core);
+        } else {
+            warn!("Core {} is not available, {} thread runs unpinned", core, thread_name);
+        }
+        handle.block_on(future);
+    })?;
+    Ok(())
+}
+
+fn pin_current_thread(core: usize) -> bool {
+    core_affinity::get_core_ids()
+        .and_then(|ids| ids.into_iter().find(|id| id.id == core))
+        .is_some_and(core_affinity::set_for_current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_future_runs_on_named_thread_within_runtime() {
+        let runtime = build_tokio(&RuntimeConfig { tokio_workers: Some(2), ..RuntimeConfig::default() }).unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        runtime.block_on(async {
+            spawn_pinned("test-pinned", 0, async move {
+                // Timers need the runtime's drivers
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                tx.send(std::thread::current().name().map(str::to_string)).unwrap();
+            })
+            .unwrap();
+        });
+        let name = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(name.as_deref(), Some("test-pinned"));
+    }
+}
@@ -0,0 +1,512 @@
+//! Server-side WASM scripting hooks
+//!
+//! Loads operator-provided `.wasm` modules and runs their exported hooks as a
+//! custom game system, so community servers can add rules without recompiling.
+//!
+//! # Hooks (guest exports, all optional, signature `() -> ()`)
+//! - `on_match_start` - first playing tick of a match
+//! - `on_kill` - once per kill in the current tick
+//! - `on_interval` - every `SCRIPTING_INTERVAL_TICKS` ticks
+//!
+//! # Host API (guest imports from module `orbit`)
+//! - `tick() -> i64`, `player_count() -> i32`, `alive_count() -> i32`, `arena_radius() -> f32`
+//! - `spawn_debris(x: f32, y: f32, size: i32)` - size 0/1/2 = small/medium/large
+//! - `emit_event(ptr: i32, len: i32)` - UTF-8 message, surfaced as `GameLoopEvent::ScriptEvent`
+//! - `log(ptr: i32, len: i32)` - UTF-8 message written to the server log
+//!
+//! Scripts never touch `GameState` directly: they read a copied view and queue
+//! commands that are applied after the hook returns. Each call is fuel-limited.
+
+#![allow(dead_code)] // Host API surface is larger than what the server itself calls
+
+use std::path::Path;
+
+use tracing::{debug, info, warn};
+use wasmtime::{Caller, Config, Engine, Linker, Module, Store, TypedFunc};
+
+use crate::config::ScriptingConfig;
+use crate::game::game_loop::{GameLoopConfig, GameLoopEvent};
+use crate::game::state::{DebrisSize, GameState};
+use crate::game::systems::custom::{GameSystem, SystemPhase};
+use crate::util::vec2::Vec2;
+
+/// Maximum bytes read from guest memory for a single message
+const MAX_MESSAGE_LEN: usize = 256;
+
+/// Consecutive traps before a script is disabled
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Scripting errors
+#[derive(Debug, thiserror::Error)]
+pub enum ScriptError {
+    #[error("Failed to read scripts: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to create engine: {0}")]
+    Engine(String),
+    #[error("Failed to load script '{name}': {message}")]
+    Load { name: String, message: String },
+}
+
+/// Hook exported by a script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    MatchStart,
+    Kill,
+    Interval,
+}
+
+impl Hook {
+    fn export_name(self) -> &'static str {
+        match self {
+            Hook::MatchStart => "on_match_start",
+            Hook::Kill => "on_kill",
+            Hook::Interval => "on_interval",
+        }
+    }
+}
+
+/// Read-only copy of the game state exposed to scripts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StateView {
+    pub tick: u64,
+    pub player_count: u32,
+    pub alive_count: u32,
+    pub arena_radius: f32,
+}
+
+impl StateView {
+    pub fn from_state(state: &GameState) -> Self {
+        Self {
+            tick: state.tick,
+            player_count: state.players.len() as u32,
+            alive_count: state.alive_count() as u32,
+            arena_radius: state.arena.escape_radius,
+        }
+    }
+}
+
+/// Command queued by a script, applied after the hook returns
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptCommand {
+    SpawnDebris { position: Vec2, size: DebrisSize },
+    Event { source: String, message: String },
+}
+
+/// Per-store host state
+struct HostState {
+    script_name: String,
+    view: StateView,
+    commands: Vec<ScriptCommand>,
+    spawns_this_call: usize,
+    max_spawns_per_call: usize,
+}
+
+/// A loaded script instance
+struct Script {
+    name: String,
+    store: Store<HostState>,
+    on_match_start: Option<TypedFunc<(), ()>>,
+    on_kill: Option<TypedFunc<(), ()>>,
+    on_interval: Option<TypedFunc<(), ()>>,
+    consecutive_failures: u32,
+}
+
+impl Script {
+    fn hook(&self, hook: Hook) -> Option<TypedFunc<(), ()>> {
+        match hook {
+            Hook::MatchStart => self.on_match_start.clone(),
+            Hook::Kill => self.on_kill.clone(),
+            Hook::Interval => self.on_interval.clone(),
+        }
+    }
+
+    fn disabled(&self) -> bool {
+        self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES
+    }
+}
+
+/// Read a UTF-8 string from guest memory (bounded, lossy)
+fn read_guest_str(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> Option<String> {
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let start = usize::try_from(ptr).ok()?;
+    let len = usize::try_from(len).ok()?.min(MAX_MESSAGE_LEN);
+    let bytes = memory.data(&*caller).get(start..start.checked_add(len)?)?;
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Register the `orbit` host API on a linker
+fn define_host_api(linker: &mut Linker<HostState>) -> wasmtime::Result<()> {
+    linker.func_wrap("orbit", "tick", |caller: Caller<'_, HostState>| -> i64 {
+        caller.data().view.tick as i64
+    })?;
+    linker.func_wrap("orbit", "player_count", |caller: Caller<'_, HostState>| -> i32 {
+        caller.data().view.player_count as i32
+    })?;
+    linker.func_wrap("orbit", "alive_count", |caller: Caller<'_, HostState>| -> i32 {
+        caller.data().view.alive_count as i32
+    })?;
+    linker.func_wrap("orbit", "arena_radius", |caller: Caller<'_, HostState>| -> f32 {
+        caller.data().view.arena_radius
+    })?;
+    linker.func_wrap(
+        "orbit",
+        "spawn_debris",
+        |mut caller: Caller<'_, HostState>, x: f32, y: f32, size: i32| {
+            let host = caller.data_mut();
+            if !x.is_finite() || !y.is_finite() || host.spawns_this_call >= host.max_spawns_per_call {
+                return;
+            }
+            let size = match size {
+                2 => DebrisSize::Large,
+                1 => DebrisSize::Medium,
+                _ => DebrisSize::Small,
+            };
+            host.spawns_this_call += 1;
+            host.commands.push(ScriptCommand::SpawnDebris {
+                position: Vec2::new(x, y),
+                size,
+            });
+        },
+    )?;
+    linker.func_wrap(
+        "orbit",
+        "emit_event",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+            if let Some(message) = read_guest_str(&mut caller, ptr, len) {
+                let host = caller.data_mut();
+                let source = host.script_name.clone();
+                host.commands.push(ScriptCommand::Event { source, message });
+            }
+        },
+    )?;
+    linker.func_wrap(
+        "orbit",
+        "log",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+            if let Some(message) = read_guest_str(&mut caller, ptr, len) {
+                info!("[script:{}] {}", caller.data().script_name, message);
+            }
+        },
+    )?;
+    Ok(())
+}
+
+/// Owns the WASM engine and all loaded scripts
+pub struct ScriptHost {
+    engine: Engine,
+    linker: Linker<HostState>,
+    config: ScriptingConfig,
+    scripts: Vec<Script>,
+}
+
+impl ScriptHost {
+    pub fn new(config: ScriptingConfig) -> Result<Self, ScriptError> {
+        let mut engine_config = Config::new();
+        engine_config.consume_fuel(true);
+        let engine = Engine::new(&engine_config).map_err(|e| ScriptError::Engine(e.to_string()))?;
+
+        let mut linker = Linker::new(&engine);
+        define_host_api(&mut linker).map_err(|e| ScriptError::Engine(e.to_string()))?;
+
+        Ok(Self {
+            engine,
+            linker,
+            config,
+            scripts: Vec::new(),
+        })
+    }
+
+    /// Load every `*.wasm` file in the configured directory
+    /// Returns the number of scripts loaded; a bad script is logged and skipped
+    pub fn load_dir(&mut self) -> Result<usize, ScriptError> {
+        let dir = self.config.script_dir.clone();
+        let dir = Path::new(&dir);
+        if !dir.exists() {
+            warn!("Script directory {} does not exist", dir.display());
+            return Ok(0);
+        }
+
+        let mut paths: Vec<_> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "wasm"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        for path in paths {
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "script".to_string());
+            let bytes = std::fs::read(&path)?;
+            match self.load_bytes(&name, &bytes) {
+                Ok(()) => loaded += 1,
+                Err(e) => warn!("{}", e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Compile and instantiate a script from WASM bytes
+    pub fn load_bytes(&mut self, name: &str, bytes: &[u8]) -> Result<(), ScriptError> {
+        let load_err = |e: wasmtime::Error| ScriptError::Load {
+            name: name.to_string(),
+            message: e.to_string(),
+        };
+
+        let module = Module::new(&self.engine, bytes).map_err(load_err)?;
+        let mut store = Store::new(
+            &self.engine,
+            HostState {
+                script_name: name.to_string(),
+                view: StateView::default(),
+                commands: Vec::new(),
+                spawns_this_call: 0,
+                max_spawns_per_call: self.config.max_spawns_per_call,
+            },
+        );
+        // Instantiation runs the start function, so it needs fuel too
+        store.set_fuel(self.config.fuel_per_call).map_err(load_err)?;
+        let instance = self.linker.instantiate(&mut store, &module).map_err(load_err)?;
+
+        let on_match_start = instance.get_typed_func::<(), ()>(&mut store, Hook::MatchStart.export_name()).ok();
+        let on_kill = instance.get_typed_func::<(), ()>(&mut store, Hook::Kill.export_name()).ok();
+        let on_interval = instance.get_typed_func::<(), ()>(&mut store, Hook::Interval.export_name()).ok();
+
+        info!(
+            "Loaded script '{}' (match_start={}, kill={}, interval={})",
+            name,
+            on_match_start.is_some(),
+            on_kill.is_some(),
+            on_interval.is_some()
+        );
+
+        self.scripts.push(Script {
+            name: name.to_string(),
+            store,
+            on_match_start,
+            on_kill,
+            on_interval,
+            consecutive_failures: 0,
+        });
+        Ok(())
+    }
+
+    /// Number of loaded scripts (including disabled ones)
+    pub fn script_count(&self) -> usize {
+        self.scripts.len()
+    }
+
+    /// Invoke a hook on every script that exports it, returning queued commands
+    pub fn call_hook(&mut self, hook: Hook, view: StateView) -> Vec<ScriptCommand> {
+        let mut commands = Vec::new();
+        let fuel = self.config.fuel_per_call;
+
+        for script in &mut self.scripts {
+            if script.disabled() {
+                continue;
+            }
+            let Some(func) = script.hook(hook) else {
+                continue;
+            };
+
+            {
+                let host = script.store.data_mut();
+                host.view = view;
+                host.spawns_this_call = 0;
+            }
+            if let Err(e) = script.store.set_fuel(fuel) {
+                warn!("Script '{}': failed to set fuel: {}", script.name, e);
+                continue;
+            }
+
+            match func.call(&mut script.store, ()) {
+                Ok(()) => {
+                    script.consecutive_failures = 0;
+                    commands.append(&mut script.store.data_mut().commands);
+                }
+                Err(e) => {
+                    // Commands from a trapped call are discarded
+                    script.store.data_mut().commands.clear();
+                    script.consecutive_failures += 1;
+                    warn!("Script '{}' trapped in {}: {}", script.name, hook.export_name(), e);
+                    if script.disabled() {
+                        warn!("Script '{}' disabled after {} consecutive failures", script.name, MAX_CONSECUTIVE_FAILURES);
+                    }
+                }
+            }
+        }
+
+        commands
+    }
+}
+
+/// Runs script hooks as part of the game loop (registered at `SystemPhase::Late`)
+pub struct ScriptSystem {
+    host: ScriptHost,
+    interval_ticks: u32,
+}
+
+impl ScriptSystem {
+    pub fn new(host: ScriptHost) -> Self {
+        let interval_ticks = host.config.interval_ticks;
+        Self { host, interval_ticks }
+    }
+}
+
+/// Apply queued script commands to the game state
+fn apply_commands(commands: Vec<ScriptCommand>, state: &mut GameState, events: &mut Vec<GameLoopEvent>) {
+    for command in commands {
+        match command {
+            ScriptCommand::SpawnDebris { position, size } => {
+                state.add_debris(position, Vec2::ZERO, size);
+            }
+            ScriptCommand::Event { source, message } => {
+                debug!("Script event from {}: {}", source, message);
+                events.push(GameLoopEvent::ScriptEvent { source, message });
+            }
+        }
+    }
+}
+
+impl GameSystem for ScriptSystem {
+    fn run(&mut self, state: &mut GameState, _dt: f32, events: &mut Vec<GameLoopEvent>) {
+        let view = StateView::from_state(state);
+        let mut commands = Vec::new();
+
+        // match_time is advanced after the Late phase, so 0 means first playing tick
+        if state.match_state.match_time == 0.0 {
+            commands.extend(self.host.call_hook(Hook::MatchStart, view));
+        }
+
+        let kills = events
+            .iter()
+            .filter(|e| matches!(e, GameLoopEvent::PlayerKilled { .. }))
+            .count();
+        for _ in 0..kills {
+            commands.extend(self.host.call_hook(Hook::Kill, view));
+        }
+
+        if self.interval_ticks > 0 && state.tick % self.interval_ticks as u64 == 0 {
+            commands.extend(self.host.call_hook(Hook::Interval, view));
+        }
+
+        apply_commands(commands, state, events);
+    }
+
+    fn name(&self) -> &str {
+        "scripting"
+    }
+}
+
+/// Load scripts per `SCRIPTING_*` env config and register them on the game loop
+pub fn register_from_env(loop_config: &mut GameLoopConfig) {
+    let config = ScriptingConfig::from_env();
+    if !config.enabled {
+        return;
+    }
+
+    let mut host = match ScriptHost::new(config) {
+        Ok(host) => host,
+        Err(e) => {
+            warn!("Scripting disabled: {}", e);
+            return;
+        }
+    };
+
+    match host.load_dir() {
+        Ok(0) => info!("Scripting enabled but no scripts found"),
+        Ok(count) => {
+            info!("Registered {} script(s)", count);
+            loop_config.register_system(SystemPhase::Late, ScriptSystem::new(host));
+        }
+        Err(e) => warn!("Scripting disabled: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host() -> ScriptHost {
+        ScriptHost::new(ScriptingConfig {
+            enabled: true,
+            fuel_per_call: 10_000,
+            max_spawns_per_call: 2,
+            ..ScriptingConfig::default()
+        })
+        .unwrap()
+    }
+
+    const SPAWNER: &str = r#"
+        (module
+          (import "orbit" "spawn_debris" (func $spawn (param f32 f32 i32)))
+          (import "orbit" "emit_event" (func $emit (param i32 i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "hello")
+          (func (export "on_match_start")
+            (call $spawn (f32.const 10) (f32.const 20) (i32.const 1))
+            (call $spawn (f32.const 10) (f32.const 20) (i32.const 1))
+            (call $spawn (f32.const 10) (f32.const 20) (i32.const 1))
+            (call $emit (i32.const 0) (i32.const 5))))
+    "#;
+
+    const LOOPER: &str = r#"
+        (module
+          (func (export "on_interval") (loop $l (br $l))))
+    "#;
+
+    #[test]
+    fn test_hook_queues_commands_with_spawn_cap() {
+        let mut host = host();
+        host.load_bytes("spawner", SPAWNER.as_bytes()).unwrap();
+
+        let commands = host.call_hook(Hook::MatchStart, StateView::default());
+        let spawns = commands
+            .iter()
+            .filter(|c| matches!(c, ScriptCommand::SpawnDebris { .. }))
+            .count();
+        assert_eq!(spawns, 2, "spawns are capped per call");
+        assert!(commands.contains(&ScriptCommand::Event {
+            source: "spawner".to_string(),
+            message: "hello".to_string(),
+        }));
+
+        // Missing hooks are a no-op
+        assert!(host.call_hook(Hook::Kill, StateView::default()).is_empty());
+    }
+
+    #[test]
+    fn test_runaway_script_is_disabled() {
+        let mut host = host();
+        host.load_bytes("looper", LOOPER.as_bytes()).unwrap();
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            assert!(host.call_hook(Hook::Interval, StateView::default()).is_empty());
+        }
+        assert!(host.scripts[0].disabled());
+    }
+
+    #[test]
+    fn test_invalid_script_is_rejected() {
+        let mut host = host();
+        assert!(host.load_bytes("broken", b"not wasm").is_err());
+        assert_eq!(host.script_count(), 0);
+    }
+
+    #[test]
+    fn test_apply_commands() {
+        let mut state = GameState::new();
+        let mut events = Vec::new();
+        apply_commands(
+            vec![
+                ScriptCommand::SpawnDebris { position: Vec2::new(1.0, 2.0), size: DebrisSize::Large },
+                ScriptCommand::Event { source: "s".to_string(), message: "m".to_string() },
+            ],
+            &mut state,
+            &mut events,
+        );
+        assert_eq!(state.debris.len(), 1);
+        assert!(matches!(&events[0], GameLoopEvent::ScriptEvent { source, .. } if source == "s"));
+    }
+}
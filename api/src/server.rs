@@ -0,0 +1,498 @@
+//! Server assembly and the embedding API
+//!
+//! Everything `main` used to wire up by hand, behind a builder: the feature
+//! self-check, metrics, the cluster bus, the lobby and ban list (or their
+//! `()` placeholders when not built), the game session with any custom
+//! systems, the metrics server and the WebTransport server. The
+//! feature-gated wiring lives here and nowhere else.
+//!
+//! Embedders build a [`Server`] with [`ServerBuilder`], keep whatever handles
+//! they need from its accessors (metrics, the game session, the lobby) and
+//! then `run()` it until its [`ShutdownToken`] is triggered. `drain()` stops
+//! new players and spectators from joining while running matches finish.
+//!
+//! Every long-running task gets a clone of the token: the game loop, the
+//! metrics server, the cluster bus, the AI manager and narrator, and the
+//! WebTransport accept loop. Once it fires they stop, clients are told the
+//! server is going away and their writer tasks flush, the AI manager saves
+//! its history, and the ban list is saved, all within the shutdown timeout.
+//!
+//! With a retention window set, a background job purges per-player records
+//! older than it: the moderator audit log, match history, tutorial
+//! completions, match replays and highlight clips. Bans expire on their own terms and aren't
+//! touched.
+//!
+//! Built with `s3_export`, a background job also uploads those replays and
+//! clips, the heatmap totals and the match history log to object storage.
+
+#![allow(dead_code)] // Public API for embedders
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::sync::{watch, RwLock};
+use tracing::{error, info, warn};
+
+use crate::config::{RetentionConfig, SanctionsConfig, ServerConfig, ShutdownConfig};
+use crate::features::{FeatureSet, FeatureSettings, Severity};
+use crate::game::systems::custom::{GameSystem, SystemPhase, SystemRegistry};
+use crate::metrics::{self, Metrics};
+use crate::net::game_session::GameSession;
+use crate::net::transport::{TransportOptions, WebTransportServer};
+
+#[cfg(feature = "anticheat")]
+use crate::anticheat::sanctions::BanList;
+#[cfg(feature = "lobby")]
+use crate::lobby::manager::LobbyManager;
+
+#[cfg(feature = "lobby")]
+type LobbyManagerType = LobbyManager;
+#[cfg(not(feature = "lobby"))]
+type LobbyManagerType = ();
+
+#[cfg(feature = "anticheat")]
+type BanListType = BanList;
+#[cfg(not(feature = "anticheat"))]
+type BanListType = ();
+
+/// Metrics server port unless set
+const DEFAULT_METRICS_PORT: u16 = 9090;
+
+/// Tells a running [`Server`] to stop. Clones share one signal.
+#[derive(Debug, Clone)]
+pub struct ShutdownToken {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Default for ShutdownToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownToken {
+    pub fn new() -> Self {
+        Self { sender: Arc::new(watch::channel(false).0) }
+    }
+
+    /// Stop the server (idempotent)
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// Resolves once the token is triggered
+    pub async fn triggered(&self) {
+        let mut receiver = self.sender.subscribe();
+        // The sender lives in self, so waiting can't fail
+        let _ = receiver.wait_for(|&triggered| triggered).await;
+    }
+}
+
+/// Assembles a [`Server`] from its configuration
+pub struct ServerBuilder {
+    config: ServerConfig,
+    metrics: Option<Arc<Metrics>>,
+    metrics_port: Option<u16>,
+    systems: SystemRegistry,
+    transport: TransportOptions,
+    shutdown: ShutdownToken,
+    shutdown_config: ShutdownConfig,
+    sanctions: SanctionsConfig,
+    retention: RetentionConfig,
+    features: FeatureSet,
+    settings: FeatureSettings,
+}
+
+impl ServerBuilder {
+    /// Builder for this binary's features, checked against no settings
+    pub fn new(config: ServerConfig) -> Self {
+        Self {
+            config,
+            metrics: None,
+            metrics_port: Some(DEFAULT_METRICS_PORT),
+            systems: SystemRegistry::new(),
+            transport: TransportOptions::default(),
+            shutdown: ShutdownToken::new(),
+            shutdown_config: ShutdownConfig::default(),
+            sanctions: SanctionsConfig::default(),
+            retention: RetentionConfig::default(),
+            features: FeatureSet::compiled(),
+            settings: FeatureSettings::default(),
+        }
+    }
+
+    /// Share an existing metrics registry instead of creating one
+    pub fn metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn metrics_port(mut self, port: u16) -> Self {
+        self.metrics_port = Some(port);
+        self
+    }
+
+    /// Don't serve `/metrics` and the other HTTP endpoints
+    pub fn without_metrics_server(mut self) -> Self {
+        self.metrics_port = None;
+        self
+    }
+
+    /// Run a custom system in the game session at `phase` (builder style)
+    pub fn with_system<S: GameSystem + 'static>(mut self, phase: SystemPhase, system: S) -> Self {
+        self.systems.register(phase, system);
+        self
+    }
+
+    /// Run a custom system in the game session at `phase`, returning a handle to it
+    pub fn register_system<S: GameSystem + 'static>(
+        &mut self,
+        phase: SystemPhase,
+        system: S,
+    ) -> Arc<parking_lot::Mutex<S>> {
+        self.systems.register(phase, system)
+    }
+
+    pub fn transport_options(mut self, options: TransportOptions) -> Self {
+        self.transport = options;
+        self
+    }
+
+    /// Token that stops the built server (a fresh one by default)
+    pub fn shutdown_token(mut self, token: ShutdownToken) -> Self {
+        self.shutdown = token;
+        self
+    }
+
+    /// How long the flush after a shutdown may take
+    pub fn shutdown_config(mut self, config: ShutdownConfig) -> Self {
+        self.shutdown_config = config;
+        self
+    }
+
+    /// Where bans are loaded from and saved to (in memory only by default)
+    pub fn sanctions_config(mut self, config: SanctionsConfig) -> Self {
+        self.sanctions = config;
+        self
+    }
+
+    /// How long per-player data is kept (forever by default)
+    pub fn retention_config(mut self, config: RetentionConfig) -> Self {
+        self.retention = config;
+        self
+    }
+
+    /// Settings the self-check holds the features against
+    pub fn feature_settings(mut self, settings: FeatureSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Run the feature self-check, then start the metrics server and bind
+    /// the WebTransport server. Fails on any self-check error.
+    pub async fn build(self) -> anyhow::Result<Server> {
+        let ServerBuilder {
+            config,
+            metrics,
+            metrics_port,
+            systems,
+            transport,
+            shutdown,
+            shutdown_config,
+            sanctions,
+            retention,
+            features,
+            settings,
+        } = self;
+        info!("Features: {}", features.enabled().join(", "));
+        let issues = features.self_check(&settings);
+        for issue in &issues {
+            match issue.severity {
+                Severity::Warning => warn!("Feature check: {}", issue.message),
+                Severity::Error => error!("Feature check: {}", issue.message),
+            }
+        }
+        if issues.iter().any(|issue| issue.severity == Severity::Error) {
+            return Err(anyhow::anyhow!("Feature self-check failed"));
+        }
+
+        info!("Configuration loaded: {}:{}, max_rooms={}", config.bind_address, config.port, config.max_rooms);
+        let metrics = metrics.unwrap_or_else(|| Arc::new(Metrics::new()));
+
+        // Join the cluster bus (no-op unless CLUSTER_REDIS_URL is set)
+        #[cfg(feature = "cluster")]
+        crate::cluster::redis::start_cluster(
+            crate::config::ClusterConfig::from_env(),
+            metrics.clone(),
+            shutdown.clone(),
+        );
+
+        #[cfg(feature = "lobby")]
+        let lobby_manager = Arc::new(RwLock::new(LobbyManager::new(config.max_rooms)));
+        #[cfg(not(feature = "lobby"))]
+        let lobby_manager = Arc::new(RwLock::new(()));
+        // Without the lobby there is nothing to initialize
+        metrics.lobby_ready.store(true, Ordering::Relaxed);
+
+        if let Some(port) = metrics_port {
+            let metrics = metrics.clone();
+            let lobby = lobby_manager.clone();
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                if let Err(e) = metrics::start_metrics_server(metrics, port, lobby, shutdown).await {
+                    error!("Metrics server error: {}", e);
+                }
+            });
+        }
+
+        #[cfg(feature = "anticheat")]
+        let ban_list = {
+            let mut bans = BanList::new().with_metrics(metrics.clone());
+            if let Some(path) = &sanctions.path {
+                match bans.load(path) {
+                    Ok(count) => info!("Loaded {} bans from {}", count, path),
+                    Err(e) => warn!("{}, starting without saved bans", e),
+                }
+            }
+            Arc::new(RwLock::new(bans))
+        };
+        #[cfg(not(feature = "anticheat"))]
+        let ban_list = Arc::new(RwLock::new(()));
+
+        let game_session = Arc::new(RwLock::new(GameSession::with_systems(metrics.clone(), &systems)));
+        if retention.days > 0 {
+            info!("Purging per-player data older than {} days", retention.days);
+            // Match replays and highlight clips
+            let replay_dirs = metrics.replay_dir.iter().chain(&metrics.highlight_dir).cloned().collect();
+            start_retention(retention, replay_dirs, game_session.clone(), lobby_manager.clone(), shutdown.clone());
+        }
+        // Ship match data to object storage (no-op unless EXPORT_S3_BUCKET is set)
+        #[cfg(feature = "s3_export")]
+        crate::export::s3::start_exporter(crate::config::ExportConfig::from_env(), export_sources(&metrics), shutdown.clone());
+
+        let transport = WebTransportServer::with_options(
+            config.clone(),
+            &transport,
+            lobby_manager.clone(),
+            ban_list.clone(),
+            game_session,
+            metrics.clone(),
+        )
+        .await?;
+        Ok(Server {
+            config,
+            features,
+            metrics,
+            lobby_manager,
+            ban_list,
+            sanctions,
+            transport,
+            shutdown,
+            shutdown_timeout: Duration::from_secs(shutdown_config.timeout_secs),
+        })
+    }
+}
+
+/// What the exporter uploads: whichever of replays, highlights, heatmaps and
+/// the match history log are written to disk
+#[cfg(feature = "s3_export")]
+fn export_sources(metrics: &Metrics) -> Vec<crate::export::ExportSource> {
+    use crate::config::{HeatmapConfig, MatchHistoryConfig};
+    use crate::export::{ExportKind, ExportSource, SourceLayout};
+
+    let mut sources = Vec::new();
+    for (kind, dir) in [(ExportKind::Replays, &metrics.replay_dir), (ExportKind::Highlights, &metrics.highlight_dir)] {
+        if let Some(dir) = dir {
+            sources.push(ExportSource { kind, layout: SourceLayout::Replays { dir: dir.clone() } });
+        }
+    }
+    if let Some(path) = HeatmapConfig::from_env().path {
+        sources.push(ExportSource { kind: ExportKind::Heatmaps, layout: SourceLayout::Snapshot { path } });
+    }
+    if let Some(path) = MatchHistoryConfig::from_env().path {
+        sources.push(ExportSource { kind: ExportKind::Matches, layout: SourceLayout::AppendLog { path } });
+    }
+    sources
+}
+
+/// Purge per-player data past the retention window every `interval_minutes`
+/// until the shutdown token fires
+fn start_retention(
+    config: RetentionConfig,
+    replay_dirs: Vec<String>,
+    game_session: Arc<RwLock<GameSession>>,
+    lobby_manager: Arc<RwLock<LobbyManagerType>>,
+    shutdown: ShutdownToken,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_minutes * 60));
+        let stopped = shutdown.triggered();
+        tokio::pin!(stopped);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = &mut stopped => break,
+            }
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+            let Some(cutoff) = config.cutoff(now) else {
+                continue;
+            };
+
+            let audit_log = game_session.read().await.audit_log();
+            match audit_log.purge_before(cutoff.saturating_mul(1000)) {
+                Ok(0) => {}
+                Ok(purged) => info!("Retention: purged {} audit log entries", purged),
+                Err(e) => warn!("Retention: {}", e),
+            }
+
+            for dir in &replay_dirs {
+                match crate::net::replay::purge_before(dir, cutoff) {
+                    Ok(0) => {}
+                    Ok(purged) => info!("Retention: deleted {} replays", purged),
+                    Err(e) => warn!("Retention: {}", e),
+                }
+            }
+
+            #[cfg(feature = "lobby")]
+            match lobby_manager.write().await.purge_before(cutoff) {
+                Ok(0) => {}
+                Ok(purged) => info!("Retention: purged {} match and profile records", purged),
+                Err(e) => warn!("Retention: {}", e),
+            }
+            #[cfg(not(feature = "lobby"))]
+            let _ = &lobby_manager;
+        }
+    });
+}
+
+/// A bound server, ready to run
+pub struct Server {
+    config: ServerConfig,
+    features: FeatureSet,
+    metrics: Arc<Metrics>,
+    lobby_manager: Arc<RwLock<LobbyManagerType>>,
+    ban_list: Arc<RwLock<BanListType>>,
+    sanctions: SanctionsConfig,
+    transport: WebTransportServer,
+    shutdown: ShutdownToken,
+    shutdown_timeout: Duration,
+}
+
+impl Server {
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    pub fn features(&self) -> FeatureSet {
+        self.features
+    }
+
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    /// Session every connection plays or spectates in
+    pub fn game_session(&self) -> &Arc<RwLock<GameSession>> {
+        self.transport.game_session()
+    }
+
+    #[cfg(feature = "lobby")]
+    pub fn lobby(&self) -> &Arc<RwLock<LobbyManager>> {
+        &self.lobby_manager
+    }
+
+    /// Certificate hash for client configuration
+    pub fn cert_hash(&self) -> &str {
+        self.transport.cert_hash()
+    }
+
+    /// A clone of the token that stops `run()`
+    pub fn shutdown_token(&self) -> ShutdownToken {
+        self.shutdown.clone()
+    }
+
+    /// Take no one new: joins are rejected with `Maintenance`, `/readyz`
+    /// fails and the cluster stops placing parties here. Running matches
+    /// and connected players carry on.
+    pub fn drain(&self) {
+        info!("Draining");
+        self.metrics.draining.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.metrics.draining.load(Ordering::Relaxed)
+    }
+
+    /// Serve until the shutdown token is triggered, then drain, flush every
+    /// subsystem (giving up after the shutdown timeout), close the lobby and
+    /// save the bans
+    pub async fn run(self) -> anyhow::Result<()> {
+        info!("Server ready on https://{}:{}", self.config.bind_address, self.config.port);
+        info!("Certificate hash: {}", self.transport.cert_hash());
+        info!("Chrome flag: --ignore-certificate-errors-spki-list={}", self.transport.cert_hash());
+
+        let shutdown = self.shutdown.clone();
+        let serving = self.transport.run(shutdown.clone());
+        tokio::pin!(serving);
+        let result = tokio::select! {
+            result = &mut serving => result,
+            _ = shutdown.triggered() => {
+                self.metrics.draining.store(true, Ordering::Relaxed);
+                info!("Shutting down (up to {:?})...", self.shutdown_timeout);
+                match tokio::time::timeout(self.shutdown_timeout, &mut serving).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        warn!("Shutdown timed out after {:?}, stopping anyway", self.shutdown_timeout);
+                        Ok(())
+                    }
+                }
+            }
+        };
+        if let Err(e) = result {
+            error!("Server error: {}", e);
+        }
+        // Stop whatever is still running if the transport failed on its own
+        shutdown.trigger();
+
+        #[cfg(feature = "lobby")]
+        self.lobby_manager.write().await.shutdown_all_rooms().await;
+        #[cfg(not(feature = "lobby"))]
+        let _ = &self.lobby_manager;
+        #[cfg(feature = "anticheat")]
+        if let Some(path) = &self.sanctions.path {
+            match self.ban_list.read().await.save(path) {
+                Ok(count) => info!("Saved {} bans to {}", count, path),
+                Err(e) => error!("{}", e),
+            }
+        }
+        #[cfg(not(feature = "anticheat"))]
+        let _ = (&self.ban_list, &self.sanctions);
+        info!("Server stopped");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_shutdown_token_is_shared_by_clones() {
+        let token = ShutdownToken::new();
+        let waiter = token.clone();
+        let waiting = tokio::spawn(async move { waiter.triggered().await });
+        assert!(!token.is_triggered());
+
+        token.clone().trigger();
+        tokio::time::timeout(Duration::from_secs(1), waiting).await.unwrap().unwrap();
+        assert!(token.is_triggered());
+        // Already triggered: resolves right away
+        token.triggered().await;
+    }
+}
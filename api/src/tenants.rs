@@ -0,0 +1,130 @@
+//! Tenants for hosted rooms
+//!
+//! External communities get an API key that resolves to a [`Tenant`]. With it
+//! they can create, start and close their own rooms through the tenant HTTP
+//! routes (`/tenant/*`), within their [`TenantQuota`], and read metrics
+//! labelled with their tenant id only. Tenant keys are separate from role tokens: a tenant
+//! key never grants admin access, and an admin token is not a tenant.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::TenantsConfig;
+
+/// Global tenant registry
+static TENANT_REGISTRY: OnceLock<TenantRegistry> = OnceLock::new();
+
+/// Limits on what one tenant may host at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenantQuota {
+    /// Rooms the tenant may have open
+    pub max_rooms: usize,
+    /// Total player capacity across the tenant's open rooms
+    pub max_players: usize,
+}
+
+impl Default for TenantQuota {
+    fn default() -> Self {
+        Self { max_rooms: 2, max_players: 40 }
+    }
+}
+
+/// A community hosting rooms under its own API key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tenant {
+    /// Stable id, used as the `tenant` metrics label
+    pub id: String,
+    #[serde(flatten)]
+    pub quota: TenantQuota,
+}
+
+impl Tenant {
+    /// Whether the id is safe to use as a Prometheus label value
+    pub fn has_valid_id(&self) -> bool {
+        !self.id.is_empty()
+            && self.id.len() <= 64
+            && self.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+}
+
+/// API key to tenant lookup
+#[derive(Debug, Default)]
+pub struct TenantRegistry {
+    keys: HashMap<String, Tenant>,
+}
+
+impl TenantRegistry {
+    /// Build from the tenants file (`{"<api key>": {"id": "...", "max_rooms": N, "max_players": N}}`).
+    /// Tenants with invalid ids are skipped.
+    pub fn from_config(config: &TenantsConfig) -> Self {
+        let mut registry = Self::default();
+
+        if let Some(path) = &config.tenants_file {
+            match std::fs::read_to_string(path) {
+                Ok(json) => match serde_json::from_str::<HashMap<String, Tenant>>(&json) {
+                    Ok(keys) => registry.extend(keys),
+                    Err(e) => warn!("Invalid tenants file {}: {}", path, e),
+                },
+                Err(e) => warn!("Failed to read tenants file {}: {}", path, e),
+            }
+        }
+        registry
+    }
+
+    /// Get the global registry (loads from env on first call)
+    pub fn global() -> &'static Self {
+        TENANT_REGISTRY.get_or_init(|| {
+            let registry = Self::from_config(&TenantsConfig::from_env());
+            if !registry.keys.is_empty() {
+                info!("Tenants: {} API key(s) configured", registry.keys.len());
+            }
+            registry
+        })
+    }
+
+    fn extend(&mut self, keys: HashMap<String, Tenant>) {
+        for (key, tenant) in keys {
+            if tenant.has_valid_id() {
+                self.keys.insert(key, tenant);
+            } else {
+                warn!("Skipping tenant with invalid id {:?} (use letters, digits, '-' or '_')", tenant.id);
+            }
+        }
+    }
+
+    /// Tenant for an API key
+    pub fn resolve(&self, api_key: Option<&str>) -> Option<&Tenant> {
+        api_key.and_then(|key| self.keys.get(key.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenants_file_json() {
+        let mut registry = TenantRegistry::default();
+        registry.extend(
+            serde_json::from_str(
+                r#"{
+                    "key-a": {"id": "community-a", "max_rooms": 5, "max_players": 100},
+                    "key-b": {"id": "community_b"},
+                    "key-c": {"id": "bad id\n"}
+                }"#,
+            )
+            .unwrap(),
+        );
+
+        let a = registry.resolve(Some("key-a")).unwrap();
+        assert_eq!(a.quota, TenantQuota { max_rooms: 5, max_players: 100 });
+        assert_eq!(registry.resolve(Some(" key-b ")).unwrap().quota, TenantQuota::default());
+        assert!(registry.resolve(Some("key-c")).is_none());
+        assert!(registry.resolve(Some("nope")).is_none());
+        assert!(registry.resolve(None).is_none());
+    }
+}
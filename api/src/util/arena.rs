@@ -0,0 +1,137 @@
+//! Per-tick scratch arenas
+//!
+//! Every tick builds short-lived collections: AI batch results, AOI candidate
+//! lists, lookup tables for delta generation. Collected into fresh `Vec`s they
+//! cost an allocation and a free each, per batch or per client, every tick.
+//! A `TickArena` bump-allocates them instead and frees everything at once
+//! when it is reset for the next tick, keeping its largest chunk so a warmed-up
+//! arena no longer touches the global allocator.
+//!
+//! The AI system owns an arena and resets it at the start of its update.
+//! Network code (AOI filtering, delta generation) runs on whichever tokio
+//! worker broadcasts, so each thread has its own arena in `with_tick_arena`,
+//! reset the first time it is used after `begin_tick`.
+//!
+//! Before every reset the bytes used are folded into a high-water mark, so
+//! operators can see how big the arenas get (`ai_high_water_bytes`,
+//! `net_high_water_bytes`).
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bumpalo::Bump;
+use parking_lot::Mutex;
+
+pub use bumpalo::collections::Vec as ArenaVec;
+
+/// Most bytes the AI arena used in one tick
+static AI_HIGH_WATER_BYTES: AtomicU64 = AtomicU64::new(0);
+/// Most bytes one thread's network arena used in one tick
+static NET_HIGH_WATER_BYTES: AtomicU64 = AtomicU64::new(0);
+/// Current tick for thread-local arenas (bumped by the game loop)
+static TICK_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static NET_ARENA: RefCell<(u64, TickArena)> = RefCell::new((0, TickArena::new(&NET_HIGH_WATER_BYTES)));
+}
+
+/// Bump arena for one tick's temporary collections
+#[derive(Debug)]
+pub struct TickArena {
+    /// Only locked through `&mut self` (`get_mut`); the mutex just lets owners stay `Sync`
+    bump: Mutex<Bump>,
+    high_water: &'static AtomicU64,
+}
+
+impl TickArena {
+    fn new(high_water: &'static AtomicU64) -> Self {
+        Self { bump: Mutex::new(Bump::new()), high_water }
+    }
+
+    /// Arena for the AI system
+    pub fn for_ai() -> Self {
+        Self::new(&AI_HIGH_WATER_BYTES)
+    }
+
+    /// Allocator for this tick's collections (freed by the next `reset`)
+    pub fn bump(&mut self) -> &Bump {
+        self.bump.get_mut()
+    }
+
+    /// Free everything allocated since the last reset, recording how much was used
+    pub fn reset(&mut self) {
+        let bump = self.bump.get_mut();
+        let used: usize = bump.iter_allocated_chunks().map(|chunk| chunk.len()).sum();
+        self.high_water.fetch_max(used as u64, Ordering::Relaxed);
+        bump.reset();
+    }
+}
+
+/// Start a new tick: thread-local arenas reset on their next use
+pub fn begin_tick() {
+    TICK_EPOCH.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Run `f` with this thread's network arena (not re-entrant: `f` must not call it again)
+pub fn with_tick_arena<R>(f: impl FnOnce(&Bump) -> R) -> R {
+    NET_ARENA.with(|cell| {
+        let mut guard = cell.borrow_mut();
+        let (epoch, arena) = &mut *guard;
+        let current = TICK_EPOCH.load(Ordering::Relaxed);
+        if *epoch != current {
+            *epoch = current;
+            arena.reset();
+        }
+        f(arena.bump())
+    })
+}
+
+/// Most bytes the AI arena used in one tick
+pub fn ai_high_water_bytes() -> u64 {
+    AI_HIGH_WATER_BYTES.load(Ordering::Relaxed)
+}
+
+/// Most bytes any thread's network arena used in one tick
+pub fn net_high_water_bytes() -> u64 {
+    NET_HIGH_WATER_BYTES.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_records_high_water_and_reuses_memory() {
+        static HIGH_WATER: AtomicU64 = AtomicU64::new(0);
+        let mut arena = TickArena::new(&HIGH_WATER);
+
+        let values = ArenaVec::from_iter_in(0..1000u32, arena.bump()).into_bump_slice();
+        assert_eq!(values.iter().sum::<u32>(), 499_500);
+        arena.reset();
+        let after_first = HIGH_WATER.load(Ordering::Relaxed);
+        assert!(after_first >= 4000, "high water {} below the 4000 bytes used", after_first);
+
+        // A smaller tick leaves the high-water mark alone and fits in the retained chunk
+        let capacity = arena.bump().allocated_bytes();
+        arena.bump().alloc_slice_fill_copy(10, 0u64);
+        arena.reset();
+        assert_eq!(HIGH_WATER.load(Ordering::Relaxed), after_first);
+        assert_eq!(arena.bump().allocated_bytes(), capacity);
+    }
+
+    #[test]
+    fn test_thread_arena_resets_each_tick() {
+        begin_tick();
+        let first = with_tick_arena(|bump| {
+            bump.alloc_slice_fill_copy(64, 0u8);
+            bump.allocated_bytes()
+        });
+        begin_tick();
+        let second = with_tick_arena(|bump| {
+            let slice = bump.alloc_slice_fill_copy(64, 1u8);
+            assert!(slice.iter().all(|&b| b == 1));
+            bump.allocated_bytes()
+        });
+        assert_eq!(first, second);
+    }
+}
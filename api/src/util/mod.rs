@@ -1 +1,3 @@
 pub mod vec2;
+pub mod privacy;
+pub mod arena;
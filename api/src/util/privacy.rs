@@ -0,0 +1,197 @@
+//! Privacy helpers for logs, metrics and persisted files
+//!
+//! Applies the configured `RedactionPolicy` to IP addresses and player names,
+//! and scrubs free-form text (AI prompts, decision history) of PII before it
+//! leaves the process.
+
+use std::net::IpAddr;
+
+use crate::config::{PrivacyConfig, RedactionPolicy};
+
+/// Placeholder used for omitted/scrubbed values
+pub const REDACTED: &str = "[redacted]";
+
+/// Number of name characters kept by the truncate policy
+const NAME_TRUNCATE_CHARS: usize = 2;
+
+/// Salted SHA-256, shortened to 12 hex chars (enough to correlate log lines).
+/// Only the secret salt (see `PrivacyConfig::hash_salt`) keeps a short hash of
+/// an IPv4 address from being reversed by hashing all of them.
+fn short_hash(value: &[u8], salt: &str) -> String {
+    let mut ctx = ring::digest::Context::new(&ring::digest::SHA256);
+    ctx.update(salt.as_bytes());
+    ctx.update(value);
+    let digest = ctx.finish();
+    digest.as_ref()[..6].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Render an IP address according to a policy
+///
+/// Truncate keeps the /24 network for IPv4 and the /48 prefix for IPv6.
+pub fn redact_ip_with(ip: IpAddr, policy: RedactionPolicy, salt: &str) -> String {
+    match policy {
+        RedactionPolicy::Full => ip.to_string(),
+        RedactionPolicy::Truncate => match ip {
+            IpAddr::V4(v4) => {
+                let o = v4.octets();
+                format!("{}.{}.{}.0", o[0], o[1], o[2])
+            }
+            IpAddr::V6(v6) => {
+                let s = v6.segments();
+                format!("{:x}:{:x}:{:x}::", s[0], s[1], s[2])
+            }
+        },
+        RedactionPolicy::Hash => format!("ip#{}", short_hash(ip.to_string().as_bytes(), salt)),
+        RedactionPolicy::Omit => REDACTED.to_string(),
+    }
+}
+
+/// Render a player name according to a policy
+pub fn redact_name_with(name: &str, policy: RedactionPolicy, salt: &str) -> String {
+    match policy {
+        RedactionPolicy::Full => name.to_string(),
+        RedactionPolicy::Truncate => {
+            let prefix: String = name.chars().take(NAME_TRUNCATE_CHARS).collect();
+            format!("{}***", prefix)
+        }
+        RedactionPolicy::Hash => format!("name#{}", short_hash(name.as_bytes(), salt)),
+        RedactionPolicy::Omit => REDACTED.to_string(),
+    }
+}
+
+/// Render an IP address using the global privacy config
+#[cfg_attr(not(feature = "anticheat"), allow(dead_code))] // Ban checks are the only IP logging
+pub fn ip(ip: IpAddr) -> String {
+    let config = PrivacyConfig::global();
+    redact_ip_with(ip, config.ip_policy, &config.hash_salt)
+}
+
+/// Render a player name using the global privacy config
+pub fn name(name: &str) -> String {
+    let config = PrivacyConfig::global();
+    redact_name_with(name, config.name_policy, &config.hash_salt)
+}
+
+/// Check if a whitespace-delimited token looks like an IPv4/IPv6 address or email
+#[cfg_attr(not(feature = "ai_manager"), allow(dead_code))]
+fn is_pii_token(token: &str) -> bool {
+    let trimmed = token.trim_matches(|c: char| {
+        !(c.is_ascii_alphanumeric() || c == '.' || c == ':' || c == '@')
+    });
+    if trimmed.is_empty() {
+        return false;
+    }
+    if trimmed.parse::<IpAddr>().is_ok() {
+        return true;
+    }
+    // Strip an optional port suffix (e.g. "1.2.3.4:443")
+    if let Some((host, port)) = trimmed.rsplit_once(':') {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if port.chars().all(|c| c.is_ascii_digit()) && host.parse::<IpAddr>().is_ok() {
+            return true;
+        }
+    }
+    // Minimal email shape: local@domain.tld
+    if let Some((local, domain)) = trimmed.split_once('@') {
+        return !local.is_empty() && domain.contains('.') && !domain.ends_with('.');
+    }
+    false
+}
+
+/// Replace IP addresses and email addresses in free-form text
+///
+/// Whitespace is preserved so scrubbed prompts/history stay readable.
+#[cfg_attr(not(feature = "ai_manager"), allow(dead_code))] // AI prompts and narration are the only free-form text
+pub fn scrub_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut token_start: Option<usize> = None;
+
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() {
+            if let Some(start) = token_start.take() {
+                push_token(&mut out, &text[start..i]);
+            }
+            out.push(c);
+        } else if token_start.is_none() {
+            token_start = Some(i);
+        }
+    }
+    if let Some(start) = token_start {
+        push_token(&mut out, &text[start..]);
+    }
+    out
+}
+
+#[cfg_attr(not(feature = "ai_manager"), allow(dead_code))]
+fn push_token(out: &mut String, token: &str) {
+    if is_pii_token(token) {
+        out.push_str(REDACTED);
+    } else {
+        out.push_str(token);
+    }
+}
+
+/// Scrub text only when AI text scrubbing is enabled in the global config
+#[cfg_attr(not(feature = "ai_manager"), allow(dead_code))]
+pub fn scrub_ai_text(text: &str) -> String {
+    if PrivacyConfig::global().scrub_ai_text {
+        scrub_text(text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_redact_ipv4_policies() {
+        let ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 42));
+        assert_eq!(redact_ip_with(ip, RedactionPolicy::Full, ""), "203.0.113.42");
+        assert_eq!(redact_ip_with(ip, RedactionPolicy::Truncate, ""), "203.0.113.0");
+        assert_eq!(redact_ip_with(ip, RedactionPolicy::Omit, ""), REDACTED);
+
+        let hashed = redact_ip_with(ip, RedactionPolicy::Hash, "salt");
+        assert!(hashed.starts_with("ip#"));
+        assert!(!hashed.contains("203"));
+    }
+
+    #[test]
+    fn test_redact_ipv6_truncate() {
+        let ip = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0x85a3, 1, 2, 3, 4, 5));
+        assert_eq!(redact_ip_with(ip, RedactionPolicy::Truncate, ""), "2001:db8:85a3::");
+    }
+
+    #[test]
+    fn test_hash_is_stable_and_salted() {
+        let a = redact_name_with("Alice", RedactionPolicy::Hash, "s1");
+        let b = redact_name_with("Alice", RedactionPolicy::Hash, "s1");
+        let c = redact_name_with("Alice", RedactionPolicy::Hash, "s2");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_redact_name_truncate_handles_unicode() {
+        assert_eq!(redact_name_with("Ünïcode", RedactionPolicy::Truncate, ""), "Ün***");
+        assert_eq!(redact_name_with("A", RedactionPolicy::Truncate, ""), "A***");
+    }
+
+    #[test]
+    fn test_scrub_text_replaces_ips_and_emails() {
+        let text = "client 10.0.0.5 (admin@example.com) via [::1]:443, ratio 0.5";
+        let scrubbed = scrub_text(text);
+        assert!(!scrubbed.contains("10.0.0.5"));
+        assert!(!scrubbed.contains("admin@example.com"));
+        assert!(scrubbed.contains("ratio 0.5"));
+        assert_eq!(scrubbed.matches(REDACTED).count(), 3);
+    }
+
+    #[test]
+    fn test_scrub_text_preserves_whitespace_and_numbers() {
+        let text = "tick_time_p95_us: 1500\n  gravity 1.25";
+        assert_eq!(scrub_text(text), text);
+    }
+}
@@ -3,17 +3,67 @@
 import { World } from './World';
 import { GameTransport, type ConnectionState } from '@/net/Transport';
 import { StateSync } from '@/net/StateSync';
+import { SnapshotCrypto } from '@/net/SnapshotCrypto';
+import { WorldStream } from '@/net/WorldStream';
+import { decodeServerMessage } from '@/net/Codec';
+import { localize } from '@/net/Localization';
 import { InputSystem } from '@/systems/InputSystem';
 import { RenderSystem } from '@/systems/RenderSystem';
-import type { ServerMessage, GameEvent, MatchPhase, PlayerId, RejectionReason } from '@/net/Protocol';
+import type {
+  ServerMessage,
+  GameEvent,
+  GalaxyMap,
+  KillFeedEntry,
+  MatchPhase,
+  PlayerId,
+  RegionHint,
+  RejectionReason,
+  SnapshotRate,
+} from '@/net/Protocol';
+
+// Account token from the server, kept so mutes and reports follow the player, not the connection
+const STORAGE_KEY_ACCOUNT = 'orbit-royale-account-token';
+
+function loadAccountToken(): Uint8Array | null {
+  try {
+    const hex = localStorage.getItem(STORAGE_KEY_ACCOUNT);
+    if (!hex || !/^([0-9a-f]{2})+$/.test(hex)) return null;
+    return new Uint8Array(hex.match(/../g)!.map((byte) => parseInt(byte, 16)));
+  } catch {
+    return null; // localStorage not available
+  }
+}
+
+function saveAccountToken(token: Uint8Array): void {
+  try {
+    localStorage.setItem(STORAGE_KEY_ACCOUNT, Array.from(token, (b) => b.toString(16).padStart(2, '0')).join(''));
+  } catch {
+    // localStorage not available
+  }
+}
 
 export type GamePhase = 'menu' | 'connecting' | 'countdown' | 'playing' | 'ended' | 'disconnected';
 
 export interface GameEvents {
   onPhaseChange: (phase: GamePhase) => void;
-  onKillFeed: (killerName: string, victimName: string) => void;
+  onKillFeed: (entry: KillFeedEntry) => void;
   onConnectionError: (error: string) => void;
   onSpectatorModeChange?: (isSpectator: boolean) => void;
+  onBotChatter?: (playerId: PlayerId, text: string) => void;
+  onCommentary?: (text: string) => void;
+  onQueueUpdate?: (position: number, etaSecs: number) => void;
+  onChat?: (playerId: PlayerId, name: string, text: string) => void;
+  onAnnouncement?: (text: string) => void;
+  onCommandResult?: (success: boolean, message: string) => void;
+  onSnapshotRate?: (hz: number) => void;
+  onMatchPaused?: (reason: string, resumeCountdown: number) => void;
+  onMatchResumed?: () => void;
+  onModifierStarted?: (name: string, description: string, duration: number) => void;
+  onModifierEnded?: (name: string) => void;
+  onWellCaptured?: (wellId: number, ownerId: PlayerId, ownerName: string) => void;
+  onArenaCode?: (code: string) => void;
+  onRegionHint?: (hint: RegionHint) => void;
+  onGalaxyMap?: (map: GalaxyMap) => void;
 }
 
 export class Game {
@@ -43,6 +93,10 @@ export class Game {
   private lastReportedZoom: number = 1.0;
   private readonly VIEWPORT_REPORT_THRESHOLD = 0.05; // Report when zoom changes by 5%
 
+  // Last time a full resync was asked for (the server honors one per second)
+  private lastResyncRequest: number = 0;
+  private readonly RESYNC_REQUEST_INTERVAL_MS = 1000;
+
   // Pending phase change (when waiting for snapshot data to be ready)
   private pendingPhaseChange: { phase: MatchPhase; countdown: number } | null = null;
 
@@ -50,6 +104,33 @@ export class Game {
   private serverUrl: string = 'https://localhost:4433';
   private certHash?: string;
 
+  // Session token from the last JoinAccepted (sent on rejoin for reconnect priority)
+  private sessionToken: Uint8Array | null = null;
+
+  // Account token from the last JoinAccepted (persisted; proves the player's account on later joins)
+  private accountToken: Uint8Array | null = loadAccountToken();
+
+  // Operator-issued auth token (grants vip/moderator/admin role on the server)
+  private authToken: string | null = null;
+
+  // Highest snapshot rate to ask for ('high' opts into 20Hz on good links)
+  private preferredSnapshotRate: SnapshotRate = 'normal';
+
+  // Server-side orbit assist (accessibility option)
+  private orbitAssist = false;
+
+  // Opted out of per-player telemetry (still counted in aggregates)
+  private telemetryOptOut = false;
+
+  // Referee pause: the server freezes the simulation, so stop sending input
+  private matchPaused = false;
+
+  // Snapshot encryption offered at join (players only; null if unsupported)
+  private snapshotCrypto: SnapshotCrypto | null = null;
+
+  // Join snapshot being streamed in chunks (null once assembled or when sent whole)
+  private worldStream: WorldStream | null = null;
+
   constructor(canvas: HTMLCanvasElement, events: GameEvents) {
     this.canvas = canvas;
     const ctx = canvas.getContext('2d');
@@ -70,6 +151,7 @@ export class Game {
       onStateChange: this.handleConnectionStateChange.bind(this),
       onMessage: this.handleServerMessage.bind(this),
       onError: this.handleConnectionError.bind(this),
+      onDecodeError: () => this.requestResync(),
     });
 
     // Set up spectator click-to-follow
@@ -169,13 +251,36 @@ export class Game {
     this.certHash = certHash;
   }
 
+  setAuthToken(token: string | null): void {
+    this.authToken = token;
+  }
+
+  setPreferredSnapshotRate(rate: SnapshotRate): void {
+    this.preferredSnapshotRate = rate;
+  }
+
+  setOrbitAssist(enabled: boolean): void {
+    this.orbitAssist = enabled;
+  }
+
+  setTelemetryOptOut(optOut: boolean): void {
+    this.telemetryOptOut = optOut;
+  }
+
   // Start connecting and playing
-  async start(playerName: string, colorIndex: number, isSpectator: boolean = false): Promise<void> {
+  async start(
+    playerName: string,
+    colorIndex: number,
+    isSpectator: boolean = false,
+    partyInvite: string | null = null,
+    room: string | null = null
+  ): Promise<void> {
     this.setPhase('connecting');
     this.inputSequence = 0;
 
     try {
       await this.transport.connect(this.serverUrl, this.certHash);
+      this.snapshotCrypto = isSpectator ? null : await SnapshotCrypto.create();
 
       // Send join request
       await this.transport.sendReliable({
@@ -183,6 +288,19 @@ export class Game {
         playerName,
         colorIndex,
         isSpectator,
+        partyInvite,
+        resumeToken: isSpectator ? null : this.sessionToken,
+        authToken: this.authToken,
+        capabilities: { maxSnapshotRate: this.preferredSnapshotRate, progressiveJoin: true },
+        accessibility: { orbitAssist: this.orbitAssist },
+        telemetry: {
+          analytics: !this.telemetryOptOut,
+          replays: !this.telemetryOptOut,
+          behavior: !this.telemetryOptOut,
+        },
+        encryptionKey: this.snapshotCrypto?.publicKey ?? null,
+        room,
+        accountToken: this.accountToken,
       });
     } catch (err) {
       this.setPhase('disconnected');
@@ -199,6 +317,11 @@ export class Game {
     });
   }
 
+  // Send a chat line (lines starting with '/' are moderator commands)
+  sendChat(text: string): void {
+    this.transport.sendReliable({ type: 'Chat', text });
+  }
+
   // Switch from spectator to player mode
   switchToPlayer(colorIndex: number): void {
     this.transport.sendReliable({
@@ -216,6 +339,7 @@ export class Game {
     this.inputSystem.reset();    // Reset state but keep listeners for next game
     this.renderSystem.reset();   // Clear camera and trails for fresh start
     this.pendingPhaseChange = null; // Clear any pending phase changes
+    this.matchPaused = false;
     this.setPhase('menu');
     this.stopGameLoop();
   }
@@ -280,7 +404,7 @@ export class Game {
       this.world.isInWorldPreview = this.stateSync.isInWorldPreview();
 
       // Update input and send to server
-      if (this.phase === 'playing' || this.phase === 'countdown') {
+      if ((this.phase === 'playing' || this.phase === 'countdown') && !this.matchPaused) {
         this.processInput(dt);
       }
 
@@ -319,6 +443,9 @@ export class Game {
       this.stateSync.getCurrentTick() + 1
     );
 
+    // Attach the latest prediction hash so the server can detect desyncs
+    input.stateHash = this.stateSync.takePredictedStateHash() ?? undefined;
+
     // Record for prediction
     this.stateSync.recordInput(input);
 
@@ -360,6 +487,18 @@ export class Game {
 
     switch (message.type) {
       case 'JoinAccepted':
+        if (!message.isSpectator) {
+          this.sessionToken = message.sessionToken;
+        }
+        if (message.accountToken.length > 0) {
+          this.accountToken = message.accountToken;
+          saveAccountToken(message.accountToken);
+        }
+        if (message.encryptionKey) {
+          this.snapshotCrypto?.deriveKey(message.encryptionKey);
+        }
+        this.events.onArenaCode?.(message.arenaCode);
+        this.worldStream = message.world ? new WorldStream(message.world) : null;
         this.handleJoinAccepted(message.playerId, message.isSpectator);
         break;
 
@@ -369,25 +508,74 @@ export class Game {
         break;
 
       case 'Snapshot':
+        // A full snapshot supersedes a stream that hasn't finished
+        this.worldStream = null;
         this.stateSync.applySnapshot(message.snapshot);
+        // Acks make this the delta baseline and give the server an RTT sample for rate adaptation
+        this.transport.sendReliable({ type: 'SnapshotAck', tick: message.snapshot.tick }).catch(() => {});
         // Update AI status from snapshot
         this.world.aiStatus = message.snapshot.aiStatus ?? null;
         break;
 
       case 'Delta':
-        this.stateSync.applyDelta(message.delta);
+        if (!this.stateSync.applyDelta(message.delta)) {
+          this.requestResync();
+        }
+        break;
+
+      case 'Resync':
+        // Acked like a full snapshot: it becomes the base for the following deltas
+        if (this.stateSync.applyResync(message.resync)) {
+          this.transport.sendReliable({ type: 'SnapshotAck', tick: message.resync.snapshot.tick }).catch(() => {});
+          this.world.aiStatus = message.resync.snapshot.aiStatus ?? null;
+        } else {
+          this.requestResync();
+        }
+        break;
+
+      case 'WorldChunk': {
+        // The assembled snapshot is the server's baseline, applied and acked like a full one
+        const snapshot = this.worldStream?.add(message.chunk);
+        if (snapshot) {
+          this.handleServerMessage({ type: 'Snapshot', snapshot });
+        }
+        break;
+      }
+
+      case 'RegionHint':
+        // Cells coming into view; the UI can prepare rendering for them
+        this.events.onRegionHint?.(message.hint);
+        break;
+
+      case 'GalaxyMap':
+        // We entered the galaxy or came through a wormhole into another arena
+        this.events.onGalaxyMap?.(message.map);
+        break;
+
+      case 'Sealed':
+        // A payload that fails to open is dropped like a lost packet
+        this.snapshotCrypto
+          ?.open(message.nonce, message.payload)
+          .then((plaintext) => this.handleServerMessage(decodeServerMessage(plaintext)))
+          .catch(() => {});
         break;
 
       case 'Event':
         this.handleGameEvent(message.event);
         break;
 
+      case 'Events':
+        for (const event of message.events) {
+          this.handleGameEvent(event);
+        }
+        break;
+
       case 'PhaseChange':
         this.handlePhaseChange(message.phase, message.countdown);
         break;
 
       case 'Kicked':
-        this.events.onConnectionError(`Kicked: ${message.reason}`);
+        this.events.onConnectionError(`Kicked: ${localize(message.reason)}`);
         this.disconnect();
         break;
 
@@ -406,6 +594,47 @@ export class Game {
         // Notify UI of spectator mode change
         this.events.onSpectatorModeChange?.(message.isSpectator);
         break;
+
+      case 'Commentary':
+        this.events.onCommentary?.(message.text);
+        break;
+
+      case 'QueueUpdate':
+        this.events.onQueueUpdate?.(message.position, message.etaSecs);
+        break;
+
+      case 'Chat':
+        this.events.onChat?.(message.playerId, message.name, message.text);
+        break;
+
+      case 'Announcement':
+        this.events.onAnnouncement?.(localize(message.text));
+        break;
+
+      case 'CommandResult':
+        this.events.onCommandResult?.(message.success, localize(message.message));
+        break;
+
+      case 'SpectateTargetChanged':
+        // Server-side follow (e.g. /tp-spectate); no need to echo it back
+        this.world.setSpectateWell(null);
+        this.world.spectateTargetId = message.targetId;
+        break;
+
+      case 'SnapshotRate':
+        // Interpolation adapts to the new interval on its own
+        this.events.onSnapshotRate?.(message.hz);
+        break;
+
+      case 'MatchPaused':
+        this.matchPaused = true;
+        this.events.onMatchPaused?.(localize(message.reason), message.resumeCountdown);
+        break;
+
+      case 'MatchResumed':
+        this.matchPaused = false;
+        this.events.onMatchResumed?.();
+        break;
     }
   }
 
@@ -459,8 +688,8 @@ export class Game {
 
   private handleGameEvent(event: GameEvent): void {
     switch (event.type) {
-      case 'PlayerKilled':
-        this.events.onKillFeed(event.killerName, event.victimName);
+      case 'KillFeed':
+        this.events.onKillFeed(event);
         break;
 
       case 'PlayerJoined':
@@ -587,6 +816,35 @@ export class Game {
         this.world.removeGravityWell(event.wellId);
         break;
       }
+
+      case 'BotChatter':
+        this.events.onBotChatter?.(event.playerId, event.text);
+        break;
+
+      case 'ModifierStarted':
+        this.events.onModifierStarted?.(event.name, localize(event.description), event.duration);
+        break;
+
+      case 'ModifierEnded':
+        this.events.onModifierEnded?.(event.name);
+        break;
+
+      case 'WellCaptured':
+        this.events.onWellCaptured?.(event.wellId, event.ownerId, event.ownerName);
+        break;
+
+      case 'WellContested':
+        // Contested state is drawn from snapshots
+        break;
+
+      case 'Hit':
+        this.world.addHitNumber(event.position, event.amount, event.shooterId === this.world.localPlayerId);
+        break;
+
+      case 'AsteroidDestroyed':
+        // The rock itself disappears with the next snapshot; fragments arrive as debris
+        this.world.addCollisionEffect(event.position, 1, RenderSystem.ASTEROID_COLOR);
+        break;
     }
   }
 
@@ -602,6 +860,15 @@ export class Game {
     }
   }
 
+  // Local state can't be trusted (undecodable message or missing delta base):
+  // ask for a full snapshot rather than waiting for the periodic one
+  private requestResync(): void {
+    const now = performance.now();
+    if (now - this.lastResyncRequest < this.RESYNC_REQUEST_INTERVAL_MS) return;
+    this.lastResyncRequest = now;
+    this.transport.sendReliable({ type: 'RequestResync' }).catch(() => {});
+  }
+
   private formatRejectionMessage(reason: RejectionReason): string {
     // Convert rejection codes to user-friendly messages
     switch (reason.type) {
@@ -618,7 +885,7 @@ export class Game {
       case 'Maintenance':
         return 'Server is undergoing maintenance.\nPlease try again shortly.';
       case 'Other':
-        return reason.message;
+        return localize(reason.message);
     }
   }
 
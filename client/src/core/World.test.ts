@@ -1,6 +1,6 @@
 import { describe, it, expect, beforeEach, vi, afterEach } from 'vitest';
 import { World } from './World';
-import { ARENA, MASS, PLAYER_COLORS } from '@/utils/Constants';
+import { ARENA, FACTION_COLORS, MASS, PLAYER_COLORS } from '@/utils/Constants';
 import type { InterpolatedState, InterpolatedPlayer, InterpolatedGravityWell } from '@/net/StateSync';
 import { Vec2 } from '@/utils/Vec2';
 
@@ -18,8 +18,13 @@ function createMockPlayer(overrides: Partial<InterpolatedPlayer> = {}): Interpol
     deaths: overrides.deaths ?? 0,
     spawnProtection: overrides.spawnProtection ?? false,
     isBot: overrides.isBot ?? false,
+    isGhost: overrides.isGhost ?? false,
     colorIndex: overrides.colorIndex ?? 0,
     bornTime: overrides.bornTime ?? 0,
+    charge: overrides.charge ?? null,
+    heat: overrides.heat ?? 0,
+    faction: overrides.faction ?? 0,
+    effects: overrides.effects ?? 0,
   };
 }
 
@@ -40,6 +45,7 @@ function createMockState(overrides: Partial<InterpolatedState> = {}): Interpolat
     totalPlayers: overrides.totalPlayers ?? 10,
     totalAlive: overrides.totalAlive ?? 8,
     densityGrid: overrides.densityGrid ?? [],
+    fadingPlayers: overrides.fadingPlayers ?? [],
   };
 }
 
@@ -450,6 +456,13 @@ describe('World', () => {
     });
   });
 
+  describe('getBodyColor', () => {
+    it('should use the faction color for faction bots', () => {
+      expect(world.getBodyColor(createMockPlayer({ colorIndex: 5 }))).toBe(PLAYER_COLORS[5]);
+      expect(world.getBodyColor(createMockPlayer({ colorIndex: 5, faction: 2 }))).toBe(FACTION_COLORS[1]);
+    });
+  });
+
   describe('getPlayerName', () => {
     it('should return name from player snapshot', () => {
       const players = new Map<string, InterpolatedPlayer>();
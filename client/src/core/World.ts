@@ -1,9 +1,9 @@
 // World state for multiplayer client
 // Stores interpolated server state and local player prediction
 
-import { ARENA, MASS, PLAYER_COLORS } from '@/utils/Constants';
-import type { PlayerId, MatchPhase, AIStatusSnapshot } from '@/net/Protocol';
-import type { InterpolatedState, InterpolatedPlayer, InterpolatedProjectile, InterpolatedDebris, InterpolatedGravityWell } from '@/net/StateSync';
+import { ARENA, FACTION_COLORS, MASS, PLAYER_COLORS } from '@/utils/Constants';
+import type { PlayerId, MatchPhase, AIStatusSnapshot, AsteroidSnapshot } from '@/net/Protocol';
+import type { InterpolatedState, InterpolatedPlayer, InterpolatedProjectile, InterpolatedDebris, InterpolatedGravityWell, FadingPlayer } from '@/net/StateSync';
 
 // Arena state
 export interface ArenaState {
@@ -34,6 +34,10 @@ const DEATH_EFFECT_DURATION = 800;
 const COLLISION_EFFECT_DURATION = 300;
 // Max collision effects at once
 const MAX_COLLISION_EFFECTS = 10;
+// Floating hit number duration in ms
+const HIT_NUMBER_DURATION = 900;
+// Max hit numbers at once
+const MAX_HIT_NUMBERS = 20;
 // Gravity wave effect duration in ms
 const WAVE_EFFECT_DURATION = 6000; // Waves expand for 6 seconds
 // Max wave effects at once
@@ -57,6 +61,14 @@ interface CollisionEffect {
   color: string;
 }
 
+// Floating hit number (confirmed projectile hit)
+interface HitNumber {
+  position: { x: number; y: number };
+  timestamp: number;
+  amount: number;
+  byLocalPlayer: boolean; // Our shot landed (vs. we were hit)
+}
+
 // Gravity wave effect data (expanding ring from well explosion)
 interface GravityWaveEffect {
   position: { x: number; y: number };
@@ -110,6 +122,9 @@ export class World {
   // Collision effects (flash + ring at collision point)
   private collisionEffects: CollisionEffect[] = [];
 
+  // Hit numbers (server-confirmed projectile hits involving us)
+  private hitNumbers: HitNumber[] = [];
+
   // Gravity wave effects (expanding rings from well explosions)
   private gravityWaveEffects: GravityWaveEffect[] = [];
 
@@ -257,6 +272,11 @@ export class World {
       (effect) => now - effect.timestamp < COLLISION_EFFECT_DURATION
     );
 
+    // Clean up old hit numbers
+    this.hitNumbers = this.hitNumbers.filter(
+      (hit) => now - hit.timestamp < HIT_NUMBER_DURATION
+    );
+
     // Clean up tracking for players no longer in state (prevents stale data accumulation)
     const currentPlayerIds = new Set(state.players.keys());
     for (const playerId of this.lastAliveStates.keys()) {
@@ -310,6 +330,11 @@ export class World {
     return this.state?.players ?? new Map();
   }
 
+  // Get players fading out after leaving our AOI (render only)
+  getFadingPlayers(): FadingPlayer[] {
+    return this.state?.fadingPlayers ?? [];
+  }
+
   // Get a specific player
   getPlayer(id: PlayerId): InterpolatedPlayer | undefined {
     return this.state?.players.get(id);
@@ -331,6 +356,11 @@ export class World {
     return this.state?.debris ?? new Map();
   }
 
+  // Get all asteroids
+  getAsteroids(): AsteroidSnapshot[] {
+    return this.state?.asteroids ?? [];
+  }
+
   // Get match phase
   getMatchPhase(): MatchPhase {
     return this.state?.matchPhase ?? 'waiting';
@@ -361,6 +391,14 @@ export class World {
     return PLAYER_COLORS[colorIndex % PLAYER_COLORS.length];
   }
 
+  // Faction bots wear their faction's color so territories read at a glance
+  getBodyColor(player: { colorIndex: number; faction: number }): string {
+    if (player.faction > 0) {
+      return FACTION_COLORS[(player.faction - 1) % FACTION_COLORS.length];
+    }
+    return this.getPlayerColor(player.colorIndex);
+  }
+
   // Get player name
   getPlayerName(playerId: PlayerId): string {
     // Get name from player snapshot
@@ -414,7 +452,7 @@ export class World {
   // Get leaderboard
   getLeaderboard(): LeaderboardEntry[] {
     return Array.from(this.getPlayers().values())
-      .filter((p) => p.alive)
+      .filter((p) => p.alive && !p.isGhost)
       .map((p) => ({
         id: p.id,
         name: this.getPlayerName(p.id),
@@ -485,6 +523,35 @@ export class World {
     }));
   }
 
+  // Add a floating hit number (called when a Hit event is received)
+  addHitNumber(position: { x: number; y: number }, amount: number, byLocalPlayer: boolean): void {
+    if (this.hitNumbers.length >= MAX_HIT_NUMBERS) {
+      this.hitNumbers.shift(); // Remove oldest
+    }
+    this.hitNumbers.push({
+      position: { x: position.x, y: position.y },
+      timestamp: Date.now(),
+      amount,
+      byLocalPlayer,
+    });
+  }
+
+  // Get active hit numbers for rendering
+  getHitNumbers(): Array<{
+    position: { x: number; y: number };
+    progress: number;
+    amount: number;
+    byLocalPlayer: boolean;
+  }> {
+    const now = Date.now();
+    return this.hitNumbers.map((hit) => ({
+      position: hit.position,
+      progress: 1 - (now - hit.timestamp) / HIT_NUMBER_DURATION,
+      amount: hit.amount,
+      byLocalPlayer: hit.byLocalPlayer,
+    }));
+  }
+
   // Add a charging well effect (called when GravityWellCharging event received)
   addChargingWell(position: { x: number; y: number }, wellId: number): void {
     // Remove any existing charging for this well
@@ -579,6 +646,7 @@ export class World {
     this.lastKillCounts.clear();
     this.deathEffects = [];
     this.collisionEffects = [];
+    this.hitNumbers = [];
     this.gravityWaveEffects = [];
     this.chargingWells = [];
     this.destroyedWellIds.clear();
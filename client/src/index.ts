@@ -2,6 +2,8 @@
 
 import { Game, type GamePhase } from './core/Game';
 import { Screens } from './ui/Screens';
+import { localize } from './net/Localization';
+import type { KillFeedEntry } from './net/Protocol';
 
 // Browser compatibility check
 function checkBrowserCompatibility(): string | null {
@@ -88,7 +90,7 @@ const screens = new Screens();
 screens.mount();
 
 // Kill feed for displaying eliminations
-const killFeed: { killer: string; victim: string; time: number }[] = [];
+const killFeed: { entry: KillFeedEntry; text: string; annotations: string[]; time: number }[] = [];
 
 // Track spectator state for UI updates
 let isCurrentlySpectator = false;
@@ -119,8 +121,13 @@ const game = new Game(canvas, {
         break;
     }
   },
-  onKillFeed: (killerName: string, victimName: string) => {
-    killFeed.push({ killer: killerName, victim: victimName, time: Date.now() });
+  onKillFeed: (entry: KillFeedEntry) => {
+    killFeed.push({
+      entry,
+      text: localize(entry.text),
+      annotations: entry.annotations.map(localize),
+      time: Date.now(),
+    });
     // Keep only last 5 entries
     while (killFeed.length > 5) {
       killFeed.shift();
@@ -153,6 +160,12 @@ game.setServer(serverUrl, certHash);
 // Parse URL parameters for spectator mode
 const urlParams = new URLSearchParams(window.location.search);
 const isSpectatorFromUrl = urlParams.get('spectate') === '1';
+game.setAuthToken(urlParams.get('token'));
+if (urlParams.get('snapshotRate') === 'high') {
+  game.setPreferredSnapshotRate('high');
+}
+game.setOrbitAssist(urlParams.get('orbitAssist') === '1');
+game.setTelemetryOptOut(urlParams.get('telemetry') === 'off');
 
 // Handle window resize
 window.addEventListener('resize', () => {
@@ -29,6 +29,143 @@ describe('Codec', () => {
         expect(bytes).toBeInstanceOf(Uint8Array);
       });
 
+      it('should encode JoinRequest priority hints and auth token as options', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const withHints: ClientMessage = {
+          ...base,
+          partyInvite: '12345678-1234-1234-1234-123456789abc',
+          resumeToken: new Uint8Array([1, 2, 3]),
+          authToken: 'vip',
+        };
+        const plain = encodeClientMessage(base);
+        const hinted = encodeClientMessage(withHints);
+        // Three None tags vs. Some(uuid: 8+16), Some(bytes: 8+3) and Some(string: 8+3)
+        expect(hinted.length - plain.length).toBe(24 + 11 + 11);
+        // Three None tags, then the capabilities (u32 snapshot rate, progressive join flag),
+        // the accessibility flag, three telemetry consent flags and the encryption key,
+        // room and account token tags
+        expect(plain[plain.length - 13]).toBe(0);
+        expect(plain[plain.length - 14]).toBe(0);
+        expect(plain[plain.length - 15]).toBe(0);
+      });
+
+      it('should encode JoinRequest snapshot rate capability', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const plain = encodeClientMessage(base);
+        const high = encodeClientMessage({ ...base, capabilities: { maxSnapshotRate: 'high' } });
+        const view = (bytes: Uint8Array) => new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+        // Defaults to Normal (variant 1); High is variant 2
+        expect(view(plain).getUint32(plain.length - 12, true)).toBe(1);
+        expect(view(high).getUint32(high.length - 12, true)).toBe(2);
+      });
+
+      it('should encode JoinRequest progressive join capability after the snapshot rate', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const plain = encodeClientMessage(base);
+        const progressive = encodeClientMessage({
+          ...base,
+          capabilities: { maxSnapshotRate: 'normal', progressiveJoin: true },
+        });
+        expect(progressive.length).toBe(plain.length);
+        expect(plain[plain.length - 8]).toBe(0);
+        expect(progressive[progressive.length - 8]).toBe(1);
+      });
+
+      it('should encode JoinRequest orbit assist as a trailing bool', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const plain = encodeClientMessage(base);
+        const assisted = encodeClientMessage({ ...base, accessibility: { orbitAssist: true } });
+        expect(assisted.length).toBe(plain.length);
+        expect(plain[plain.length - 7]).toBe(0);
+        expect(assisted[assisted.length - 7]).toBe(1);
+      });
+
+      it('should encode JoinRequest telemetry consent before the encryption key', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const plain = encodeClientMessage(base);
+        const optedOut = encodeClientMessage({
+          ...base,
+          telemetry: { analytics: false, replays: true, behavior: false },
+        });
+        expect(optedOut.length).toBe(plain.length);
+        // Everything is allowed unless the player opts out
+        expect(Array.from(plain.slice(plain.length - 6, plain.length - 3))).toEqual([1, 1, 1]);
+        expect(Array.from(optedOut.slice(optedOut.length - 6, optedOut.length - 3))).toEqual([0, 1, 0]);
+      });
+
+      it('should encode JoinRequest encryption key as an option before the room', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const plain = encodeClientMessage(base);
+        const keyed = encodeClientMessage({ ...base, encryptionKey: new Uint8Array(65).fill(4) });
+        expect(plain[plain.length - 3]).toBe(0);
+        // Some tag + u64 length + 65 key bytes
+        expect(keyed.length).toBe(plain.length + 8 + 65);
+        expect(keyed[plain.length - 3]).toBe(1);
+        expect(keyed[keyed.length - 3]).toBe(4);
+      });
+
+      it('should encode JoinRequest room as an option before the account token', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const plain = encodeClientMessage(base);
+        const inRoom = encodeClientMessage({ ...base, room: 'aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee' });
+        expect(plain[plain.length - 2]).toBe(0);
+        // Some tag + u64 length + 16 uuid bytes
+        expect(inRoom.length).toBe(plain.length + 8 + 16);
+        expect(inRoom[plain.length - 2]).toBe(1);
+        expect(inRoom[inRoom.length - 2]).toBe(0xee);
+      });
+
+      it('should encode JoinRequest account token as a trailing option', () => {
+        const base: ClientMessage = {
+          type: 'JoinRequest',
+          playerName: 'P',
+          colorIndex: 0,
+          isSpectator: false,
+        };
+        const plain = encodeClientMessage(base);
+        const returning = encodeClientMessage({ ...base, accountToken: new Uint8Array(48).fill(9) });
+        expect(plain[plain.length - 1]).toBe(0);
+        // Some tag + u64 length + 48 token bytes
+        expect(returning.length).toBe(plain.length + 8 + 48);
+        expect(returning[plain.length - 1]).toBe(1);
+        expect(returning[returning.length - 1]).toBe(9);
+      });
+
       it('should encode JoinRequest with empty name', () => {
         const msg: ClientMessage = {
           type: 'JoinRequest',
@@ -140,6 +277,25 @@ describe('Codec', () => {
         const bytes = encodeClientMessage(msg);
         expect(bytes).toBeInstanceOf(Uint8Array);
       });
+      it('should append an optional state hash', () => {
+        const input: PlayerInput = {
+          sequence: 1,
+          tick: 1,
+          clientTime: 1,
+          thrust: new Vec2(0, 0),
+          aim: new Vec2(1, 0),
+          boost: false,
+          fire: false,
+          fireReleased: false,
+        };
+        const without = encodeClientMessage({ type: 'Input', input });
+        const withHash = encodeClientMessage({
+          type: 'Input',
+          input: { ...input, stateHash: { tick: 1, hash: 0xdeadbeef } },
+        });
+        // Option tag + U64 tick + U32 hash
+        expect(withHash.length - without.length).toBe(12);
+      });
     });
 
     describe('Leave encoding', () => {
@@ -208,18 +364,82 @@ describe('Codec', () => {
         expect(bytes.length).toBe(5);
       });
     });
+
+    describe('Report encoding', () => {
+      it('should encode Report target and reason variant', () => {
+        const msg: ClientMessage = {
+          type: 'Report',
+          target: '12345678-1234-1234-1234-123456789abc',
+          reason: 'Griefing',
+        };
+        const bytes = encodeClientMessage(msg);
+        // Variant (4) + UUID (8 length prefix + 16 bytes) + reason variant (4) = 32 bytes
+        expect(bytes.length).toBe(32);
+        const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+        expect(view.getUint32(0, true)).toBe(9);
+        expect(view.getUint32(28, true)).toBe(2);
+      });
+    });
+
+    describe('ListRooms encoding', () => {
+      it('should encode the filter options, friends and page', () => {
+        const msg: ClientMessage = {
+          type: 'ListRooms',
+          filter: { mode: 'standard', notFull: true, friends: ['12345678-1234-1234-1234-123456789abc'] },
+          page: 3,
+        };
+        const bytes = encodeClientMessage(msg);
+        // Variant (4) + Some mode (1 + 8 + 8) + None region (1) + notFull (1)
+        // + friends (8 + 24) + page (4) = 59 bytes
+        expect(bytes.length).toBe(59);
+        const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+        expect(view.getUint32(0, true)).toBe(10);
+        expect(view.getUint8(21)).toBe(0); // region None
+        expect(view.getUint8(22)).toBe(1); // notFull
+        expect(view.getUint32(55, true)).toBe(3);
+      });
+    });
+
+    describe('SpectateRoom encoding', () => {
+      it('should encode the room id and name', () => {
+        const msg: ClientMessage = {
+          type: 'SpectateRoom',
+          roomId: 'aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee',
+          playerName: 'Ann',
+        };
+        const bytes = encodeClientMessage(msg);
+        // Variant (4) + room id (8 + 16) + name (8 + 3) = 39 bytes
+        expect(bytes.length).toBe(39);
+        const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+        expect(view.getUint32(0, true)).toBe(11);
+        expect(bytes[12]).toBe(0xaa);
+        expect(view.getUint32(28, true)).toBe(3);
+      });
+    });
+
+    describe('RequestResync encoding', () => {
+      it('should encode just the variant', () => {
+        const bytes = encodeClientMessage({ type: 'RequestResync' });
+        expect(Array.from(bytes)).toEqual([12, 0, 0, 0]);
+      });
+    });
   });
 
   describe('decodeServerMessage', () => {
     describe('JoinAccepted decoding', () => {
       it('should decode JoinAccepted message', () => {
         // Build a valid JoinAccepted binary:
-        // Variant=0 (U32), UUID (length + 16 bytes), SessionToken (length + bytes), isSpectator (bool)
+        // Variant=0 (U32), UUID (length + 16 bytes), SessionToken (length + bytes), isSpectator (bool),
+        // encryption key (Option<Vec<u8>>), arena code (String), world (Option), account token (Vec<u8>)
         const writer = new TestBinaryWriter();
         writer.writeU32(0); // JoinAccepted variant
         writer.writeUuid('12345678-1234-5678-1234-567812345678');
         writer.writeByteArray(new Uint8Array([1, 2, 3, 4])); // session token
         writer.writeBool(false);
+        writer.writeU8(0); // no encryption key
+        writer.writeString('1BCD2EF'); // arena code
+        writer.writeU8(0); // world: None (entities come in the initial snapshot)
+        writer.writeByteArray(new Uint8Array([9, 9, 9])); // account token
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('JoinAccepted');
@@ -227,6 +447,9 @@ describe('Codec', () => {
           expect(result.playerId).toBe('12345678-1234-5678-1234-567812345678');
           expect(result.sessionToken).toEqual(new Uint8Array([1, 2, 3, 4]));
           expect(result.isSpectator).toBe(false);
+          expect(result.encryptionKey).toBeNull();
+          expect(result.arenaCode).toBe('1BCD2EF');
+          expect(result.accountToken).toEqual(new Uint8Array([9, 9, 9]));
         }
       });
 
@@ -236,6 +459,10 @@ describe('Codec', () => {
         writer.writeUuid('aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee');
         writer.writeByteArray(new Uint8Array([0xff]));
         writer.writeBool(true);
+        writer.writeU8(0);
+        writer.writeString('1BCD2EF');
+        writer.writeU8(0);
+        writer.writeByteArray(new Uint8Array([]));
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('JoinAccepted');
@@ -243,6 +470,25 @@ describe('Codec', () => {
           expect(result.isSpectator).toBe(true);
         }
       });
+
+      it('should decode JoinAccepted with the server encryption key', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(0);
+        writer.writeUuid('aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee');
+        writer.writeByteArray(new Uint8Array([0xff]));
+        writer.writeBool(false);
+        writer.writeU8(1);
+        writer.writeByteArray(new Uint8Array([4, 5, 6]));
+        writer.writeString('1BCD2EF');
+        writer.writeU8(0);
+        writer.writeByteArray(new Uint8Array([]));
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('JoinAccepted');
+        if (result.type === 'JoinAccepted') {
+          expect(result.encryptionKey).toEqual(new Uint8Array([4, 5, 6]));
+        }
+      });
     });
 
     describe('JoinRejected decoding', () => {
@@ -290,14 +536,17 @@ describe('Codec', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(1); // JoinRejected variant
         writer.writeU32(6); // Other reason variant
-        writer.writeString('Custom error message');
+        writer.writeLocalizedText('reject.encryption_failed', { error: 'bad key' });
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('JoinRejected');
         if (result.type === 'JoinRejected') {
           expect(result.reason.type).toBe('Other');
           if (result.reason.type === 'Other') {
-            expect(result.reason.message).toBe('Custom error message');
+            expect(result.reason.message).toEqual({
+              key: 'reject.encryption_failed',
+              params: { error: 'bad key' },
+            });
           }
         }
       });
@@ -323,12 +572,12 @@ describe('Codec', () => {
       it('should decode Kicked message', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(6); // Kicked variant
-        writer.writeString('AFK timeout');
+        writer.writeLocalizedText('verbatim', { text: 'AFK timeout' });
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('Kicked');
         if (result.type === 'Kicked') {
-          expect(result.reason).toBe('AFK timeout');
+          expect(result.reason).toEqual({ key: 'verbatim', params: { text: 'AFK timeout' } });
         }
       });
     });
@@ -388,6 +637,151 @@ describe('Codec', () => {
       });
     });
 
+    describe('Commentary decoding', () => {
+      it('should decode Commentary', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(9);
+        writer.writeString('Alice dominates the north quadrant');
+        writer.writeU64(1200);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Commentary');
+        if (result.type === 'Commentary') {
+          expect(result.text).toBe('Alice dominates the north quadrant');
+          expect(result.tick).toBe(1200);
+        }
+      });
+    });
+
+    describe('QueueUpdate decoding', () => {
+      it('should decode QueueUpdate', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(10);
+        writer.writeU32(4);
+        writer.writeU32(60);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('QueueUpdate');
+        if (result.type === 'QueueUpdate') {
+          expect(result.position).toBe(4);
+          expect(result.etaSecs).toBe(60);
+        }
+      });
+    });
+
+    describe('Chat and moderation decoding', () => {
+      it('should decode Chat', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(11);
+        writer.writeUuid('12345678-1234-1234-1234-123456789abc');
+        writer.writeString('Alice');
+        writer.writeString('gg');
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Chat');
+        if (result.type === 'Chat') {
+          expect(result.playerId).toBe('12345678-1234-1234-1234-123456789abc');
+          expect(result.name).toBe('Alice');
+          expect(result.text).toBe('gg');
+        }
+      });
+
+      it('should decode CommandResult', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(13);
+        writer.writeBool(false);
+        writer.writeLocalizedText('command.usage', { usage: '/kick <player> [reason]' });
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('CommandResult');
+        if (result.type === 'CommandResult') {
+          expect(result.success).toBe(false);
+          expect(result.message).toEqual({ key: 'command.usage', params: { usage: '/kick <player> [reason]' } });
+        }
+      });
+
+      it('should decode SpectateTargetChanged None', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(14);
+        writer.writeU8(0);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('SpectateTargetChanged');
+        if (result.type === 'SpectateTargetChanged') {
+          expect(result.targetId).toBeNull();
+        }
+      });
+    });
+
+    describe('SnapshotRate decoding', () => {
+      it('should decode SnapshotRate', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(15);
+        writer.writeU8(20);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('SnapshotRate');
+        if (result.type === 'SnapshotRate') {
+          expect(result.hz).toBe(20);
+        }
+      });
+    });
+
+    describe('MatchPaused decoding', () => {
+      it('should decode MatchPaused with a resume countdown', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(16);
+        writer.writeLocalizedText('pause.by_referee');
+        writer.writeF32(3.0);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('MatchPaused');
+        if (result.type === 'MatchPaused') {
+          expect(result.reason).toEqual({ key: 'pause.by_referee', params: {} });
+          expect(result.resumeCountdown).toBe(3.0);
+        }
+      });
+
+      it('should decode MatchResumed', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(17);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('MatchResumed');
+      });
+    });
+
+    describe('Events decoding', () => {
+      it('should decode a batch of hit confirmations', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(18); // Events
+        writer.writeU64(2);
+        for (const projectileId of [7, 8]) {
+          writer.writeU32(15); // Hit
+          writer.writeUuid('11111111-1111-1111-1111-111111111111');
+          writer.writeUuid('22222222-2222-2222-2222-222222222222');
+          writer.writeU64(projectileId);
+          writer.writeF32(12.5);
+          writer.writeVec2(new Vec2(30, -40));
+        }
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Events');
+        if (result.type === 'Events') {
+          expect(result.events).toHaveLength(2);
+          const hit = result.events[1];
+          expect(hit.type).toBe('Hit');
+          if (hit.type === 'Hit') {
+            expect(hit.shooterId).toBe('11111111-1111-1111-1111-111111111111');
+            expect(hit.victimId).toBe('22222222-2222-2222-2222-222222222222');
+            expect(hit.projectileId).toBe(8);
+            expect(hit.amount).toBe(12.5);
+            expect(hit.position).toEqual({ x: 30, y: -40 });
+          }
+        }
+      });
+    });
+
     describe('SpectatorModeChanged decoding', () => {
       it('should decode SpectatorModeChanged true', () => {
         const writer = new TestBinaryWriter();
@@ -415,26 +809,56 @@ describe('Codec', () => {
     });
 
     describe('Event decoding', () => {
-      it('should decode PlayerKilled event', () => {
+      it('should decode KillFeed event', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(4); // Event variant
-        writer.writeU32(0); // PlayerKilled event
+        writer.writeU32(0); // KillFeed event
+        writer.writeU8(1); // Some(killer_id)
         writer.writeUuid('11111111-1111-1111-1111-111111111111');
         writer.writeUuid('22222222-2222-2222-2222-222222222222');
+        writer.writeU8(1); // Some(killer_name)
         writer.writeString('Killer');
         writer.writeString('Victim');
+        writer.writeU32(1); // Projectile
+        writer.writeLocalizedText('kill.projectile', { killer: 'Killer', victim: 'Victim' });
+        writer.writeU64(1); // annotations
+        writer.writeLocalizedText('kill.double', {});
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('Event');
         if (result.type === 'Event') {
-          expect(result.event.type).toBe('PlayerKilled');
-          if (result.event.type === 'PlayerKilled') {
+          expect(result.event.type).toBe('KillFeed');
+          if (result.event.type === 'KillFeed') {
             expect(result.event.killerName).toBe('Killer');
             expect(result.event.victimName).toBe('Victim');
+            expect(result.event.cause).toBe('Projectile');
+            expect(result.event.text.params.victim).toBe('Victim');
+            expect(result.event.annotations).toEqual([{ key: 'kill.double', params: {} }]);
           }
         }
       });
 
+      it('should decode KillFeed event without a killer', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(4);
+        writer.writeU32(0);
+        writer.writeU8(0); // None
+        writer.writeUuid('22222222-2222-2222-2222-222222222222');
+        writer.writeU8(0); // None
+        writer.writeString('Victim');
+        writer.writeU32(2); // Well
+        writer.writeLocalizedText('kill.well', { victim: 'Victim' });
+        writer.writeU64(0); // annotations
+
+        const result = decodeServerMessage(writer.getBuffer());
+        if (result.type === 'Event' && result.event.type === 'KillFeed') {
+          expect(result.event.killerId).toBeNull();
+          expect(result.event.cause).toBe('Well');
+        } else {
+          throw new Error('Expected KillFeed event');
+        }
+      });
+
       it('should decode PlayerJoined event', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(4);
@@ -479,14 +903,23 @@ describe('Codec', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(4);
         writer.writeU32(4); // MatchEnded
-        writer.writeBool(true); // has winner
+        writer.writeBool(true); // has winner id
+        writer.writeUuid('55555555-5555-5555-5555-555555555555');
+        writer.writeBool(true); // has winner name
+        writer.writeString('Winner');
+        writer.writeU64(1); // awards
+        writer.writeU32(1); // MostKills
         writer.writeUuid('55555555-5555-5555-5555-555555555555');
         writer.writeString('Winner');
+        writer.writeF32(4);
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('Event');
         if (result.type === 'Event' && result.event.type === 'MatchEnded') {
           expect(result.event.winnerName).toBe('Winner');
+          expect(result.event.awards).toEqual([
+            { kind: 'MostKills', playerId: '55555555-5555-5555-5555-555555555555', name: 'Winner', value: 4 },
+          ]);
         }
       });
 
@@ -494,7 +927,9 @@ describe('Codec', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(4);
         writer.writeU32(4); // MatchEnded
-        writer.writeBool(false); // no winner
+        writer.writeBool(false); // no winner id
+        writer.writeBool(false); // no winner name
+        writer.writeU64(0); // awards
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('Event');
@@ -586,6 +1021,69 @@ describe('Codec', () => {
           expect(result.event.wellId).toBe(123);
         }
       });
+
+      it('should decode BotChatter event', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(4);
+        writer.writeU32(10); // BotChatter
+        writer.writeUuid('aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee');
+        writer.writeU32(1); // Distress
+        writer.writeString('Help!');
+        writer.writeF32(10.0);
+        writer.writeF32(-20.0);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Event');
+        if (result.type === 'Event' && result.event.type === 'BotChatter') {
+          expect(result.event.kind).toBe('Distress');
+          expect(result.event.text).toBe('Help!');
+          expect(result.event.position.y).toBeCloseTo(-20.0);
+        }
+      });
+
+      it('should decode ModifierStarted and ModifierEnded events', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(4);
+        writer.writeU32(11); // ModifierStarted
+        writer.writeString('solar_flare');
+        writer.writeLocalizedText('modifier.solar_flare');
+        writer.writeF32(60.0);
+
+        const started = decodeServerMessage(writer.getBuffer());
+        expect(started.type).toBe('Event');
+        if (started.type === 'Event' && started.event.type === 'ModifierStarted') {
+          expect(started.event.name).toBe('solar_flare');
+          expect(started.event.description).toEqual({ key: 'modifier.solar_flare', params: {} });
+          expect(started.event.duration).toBeCloseTo(60.0);
+        }
+
+        const endWriter = new TestBinaryWriter();
+        endWriter.writeU32(4);
+        endWriter.writeU32(12); // ModifierEnded
+        endWriter.writeString('solar_flare');
+
+        const ended = decodeServerMessage(endWriter.getBuffer());
+        expect(ended.type === 'Event' && ended.event.type).toBe('ModifierEnded');
+      });
+
+      it('should decode AsteroidDestroyed event', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(4);
+        writer.writeU32(16); // AsteroidDestroyed
+        writer.writeU64(42);
+        writer.writeVec2(new Vec2(1200, -300));
+        writer.writeU16(5);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Event');
+        if (result.type === 'Event' && result.event.type === 'AsteroidDestroyed') {
+          expect(result.event.asteroidId).toBe(42);
+          expect(result.event.position).toEqual({ x: 1200, y: -300 });
+          expect(result.event.fragments).toBe(5);
+        } else {
+          throw new Error('Expected AsteroidDestroyed event');
+        }
+      });
     });
 
     describe('Snapshot decoding', () => {
@@ -608,6 +1106,7 @@ describe('Codec', () => {
         writer.writeF32(1.0); // arenaScale
 
         writer.writeU64(0); // 0 gravity wells
+        writer.writeU64(0); // asteroids
 
         writer.writeU32(0); // totalPlayers
         writer.writeU32(0); // totalAlive
@@ -631,6 +1130,33 @@ describe('Codec', () => {
         }
       });
 
+      it('should decode the recommended interpolation delay', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(2); // Snapshot variant
+        writer.writeU64(1); // tick
+        writer.writeU32(2); // playing
+        writer.writeF32(0);
+        writer.writeF32(0);
+        writer.writeU64(0); // players
+        writer.writeU64(0); // projectiles
+        writer.writeU64(0); // debris
+        writer.writeU8(0);
+        writer.writeF32(600.0);
+        writer.writeF32(1.0);
+        writer.writeU64(0); // wells
+        writer.writeU64(0); // asteroids
+        writer.writeU32(0);
+        writer.writeU32(0);
+        writer.writeU64(0); // density grid
+        writer.writeU64(0); // echoClientTime
+        writer.writeU8(240); // interpDelayMs (u16 LE)
+        writer.writeU8(0);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        if (result.type !== 'Snapshot') throw new Error('expected Snapshot');
+        expect(result.snapshot.interpDelayMs).toBe(240);
+      });
+
       it('should decode Snapshot with players', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(2); // Snapshot variant
@@ -655,6 +1181,10 @@ describe('Codec', () => {
           spawnProtection: false,
           isBot: false,
           colorIndex: 5,
+          charge: 255,
+          heat: 51,
+          faction: 2,
+          effects: 0b101,
         });
 
         writer.writeU64(0); // projectiles
@@ -663,6 +1193,7 @@ describe('Codec', () => {
         writer.writeF32(600.0);
         writer.writeF32(1.0);
         writer.writeU64(0); // wells
+        writer.writeU64(0); // asteroids
         writer.writeU32(1);
         writer.writeU32(1);
         writer.writeU64(0); // density grid
@@ -676,9 +1207,59 @@ describe('Codec', () => {
           expect(result.snapshot.players[0].name).toBe('Player1');
           expect(result.snapshot.players[0].mass).toBe(150);
           expect(result.snapshot.players[0].kills).toBe(3);
+          expect(result.snapshot.players[0].charge).toBe(1);
+          expect(result.snapshot.players[0].heat).toBeCloseTo(0.2);
+          expect(result.snapshot.players[0].faction).toBe(2);
+          expect(result.snapshot.players[0].effects).toBe(0b101);
+          expect(result.snapshot.players[0].isGhost).toBe(false);
         }
       });
 
+      it('should decode ghost players in Snapshot', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(2); // Snapshot variant
+
+        writer.writeU64(200); // tick
+        writer.writeU32(2); // playing
+        writer.writeF32(30.0);
+        writer.writeF32(0);
+
+        writer.writeU64(1);
+        writePlayerSnapshot(writer, {
+          id: 'bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb',
+          name: 'Best run',
+          position: new Vec2(100, 200),
+          velocity: new Vec2(5, 10),
+          rotation: 0,
+          mass: 100,
+          alive: true,
+          kills: 0,
+          deaths: 0,
+          spawnProtection: false,
+          isBot: false,
+          isGhost: true,
+          colorIndex: 0,
+        });
+
+        writer.writeU64(0); // projectiles
+        writer.writeU64(0); // debris
+        writer.writeU8(0);
+        writer.writeF32(600.0);
+        writer.writeF32(1.0);
+        writer.writeU64(0); // wells
+        writer.writeU64(0); // asteroids
+        writer.writeU32(1);
+        writer.writeU32(1);
+        writer.writeU64(0); // density grid
+        writer.writeU64(0); // notable
+        writer.writeU64(0);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        if (result.type !== 'Snapshot') throw new Error('expected Snapshot');
+        expect(result.snapshot.players[0].isGhost).toBe(true);
+        expect(result.snapshot.players[0].alive).toBe(true);
+      });
+
       it('should decode Snapshot with gravity wells', () => {
         const writer = new TestBinaryWriter();
         writer.writeU32(2);
@@ -703,11 +1284,19 @@ describe('Codec', () => {
         writer.writeVec2(new Vec2(100, 100));
         writer.writeF32(5000);
         writer.writeF32(25);
+        writer.writeU8(1); // Some(owner color)
+        writer.writeU8(7);
+        writer.writeF32(0);
+        writer.writeU8(0); // not contested
         // Well 2
         writer.writeU32(2);
         writer.writeVec2(new Vec2(-200, 300));
         writer.writeF32(3000);
         writer.writeF32(20);
+        writer.writeU8(0); // unowned
+        writer.writeF32(0.5);
+        writer.writeU8(1); // contested
+        writer.writeU64(0); // asteroids
 
         writer.writeU32(5);
         writer.writeU32(3);
@@ -721,6 +1310,10 @@ describe('Codec', () => {
           expect(result.snapshot.gravityWells).toHaveLength(2);
           expect(result.snapshot.gravityWells[0].id).toBe(1);
           expect(result.snapshot.gravityWells[1].mass).toBe(3000);
+          expect(result.snapshot.gravityWells[0].ownerColor).toBe(7);
+          expect(result.snapshot.gravityWells[1].ownerColor).toBeNull();
+          expect(result.snapshot.gravityWells[1].captureProgress).toBeCloseTo(0.5);
+          expect(result.snapshot.gravityWells[1].contested).toBe(true);
         }
       });
     });
@@ -737,6 +1330,8 @@ describe('Codec', () => {
         writer.writeU64(0); // 0 projectile updates
         writer.writeU64(0); // 0 removed projectiles
         writer.writeU64(0); // 0 debris
+        writer.writeU64(0); // 0 removed players
+        writer.writeU64(0); // 0 asteroids
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('Delta');
@@ -757,33 +1352,326 @@ describe('Codec', () => {
 
         // 1 player update
         writer.writeU64(1);
-        writer.writeUuid('bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb');
-        // Optional fields
-        writer.writeBool(true); // has position
-        writer.writeVec2(new Vec2(250, 350));
-        writer.writeBool(true); // has velocity
-        writer.writeVec2(new Vec2(10, 20));
-        writer.writeBool(false); // no rotation
-        writer.writeBool(true); // has mass
-        writer.writeF32(175);
-        writer.writeBool(false); // no alive change
-        writer.writeBool(true); // has kills
-        writer.writeU32(5);
+        // Packed: raw id, changed-field mask, then fields in mask bit order
+        const hex = 'bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb';
+        for (let i = 0; i < 16; i++) {
+          writer.writeU8(parseInt(hex.substring(i * 2, i * 2 + 2), 16));
+        }
+        // position | velocity | charge | mass | kills (two-byte mask)
+        writer.writeVarint((1 << 0) | (1 << 1) | (1 << 4) | (1 << 5) | (1 << 7));
+        writer.writeQuantized(250, 8);
+        writer.writeQuantized(-350.5, 8);
+        writer.writeQuantized(10, 4);
+        writer.writeQuantized(20.25, 4);
+        writer.writeU8(128); // charge
+        writer.writeQuantized(175, 8); // mass
+        writer.writeVarint(5); // kills
 
         writer.writeU64(0); // 0 projectile updates
         writer.writeU64(0); // 0 removed projectiles
         writer.writeU64(0); // 0 debris
+        writer.writeU64(0); // 0 removed players
+        writer.writeU64(0); // 0 asteroids
 
         const result = decodeServerMessage(writer.getBuffer());
         expect(result.type).toBe('Delta');
         if (result.type === 'Delta') {
           expect(result.delta.playerUpdates).toHaveLength(1);
+          expect(result.delta.playerUpdates[0].id).toBe('bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb');
           expect(result.delta.playerUpdates[0].position?.x).toBe(250);
+          expect(result.delta.playerUpdates[0].position?.y).toBe(-350.5);
+          expect(result.delta.playerUpdates[0].velocity?.y).toBe(20.25);
           expect(result.delta.playerUpdates[0].mass).toBe(175);
           expect(result.delta.playerUpdates[0].kills).toBe(5);
+          expect(result.delta.playerUpdates[0].charge).toBeCloseTo(0.5);
           expect(result.delta.playerUpdates[0].rotation).toBeUndefined();
         }
       });
+
+      it('should decode Delta with removed players', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(3);
+
+        writer.writeU64(200);
+        writer.writeU64(150);
+
+        writer.writeU64(0); // 0 player updates
+        writer.writeU64(0); // 0 projectile updates
+        writer.writeU64(0); // 0 removed projectiles
+        writer.writeU64(0); // 0 debris
+        writer.writeU64(1); // 1 removed player
+        writer.writeUuid('cccccccc-cccc-cccc-cccc-cccccccccccc');
+        writer.writeU64(0); // 0 asteroids
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Delta');
+        if (result.type === 'Delta') {
+          expect(result.delta.removedPlayers).toEqual(['cccccccc-cccc-cccc-cccc-cccccccccccc']);
+        }
+      });
+    });
+
+    describe('Resync decoding', () => {
+      it('should decode a Resync with removed entities', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(19); // Resync variant
+        writer.writeU64(100); // baseTick
+
+        // GameSnapshot (changed entities only)
+        writer.writeU64(130); // tick
+        writer.writeU32(2); // playing
+        writer.writeF32(61.0);
+        writer.writeF32(0);
+        writer.writeU64(0); // players
+        writer.writeU64(0); // projectiles
+        writer.writeU64(0); // debris
+        writer.writeU8(0);
+        writer.writeF32(600.0);
+        writer.writeF32(1.0);
+        writer.writeU64(0); // gravity wells
+        writer.writeU64(0); // asteroids
+        writer.writeU32(0);
+        writer.writeU32(0);
+        writer.writeU64(0); // density grid
+        writer.writeU64(0); // echoClientTime
+        writer.writeU8(0); // interpDelayMs (u16 LE)
+        writer.writeU8(0);
+        writer.writeU8(1); // aiStatus: Some
+        writer.writeBool(true); // enabled
+        writer.writeU8(0); // lastDecision: None
+        writer.writeU8(80); // confidence
+        writer.writeU8(75); // successRate
+        writer.writeU32(4); // decisionsTotal
+        writer.writeU32(3); // decisionsSuccessful
+
+        writer.writeU64(1); // 1 removed player
+        writer.writeUuid('cccccccc-cccc-cccc-cccc-cccccccccccc');
+        writer.writeU64(1); // 1 removed projectile
+        writer.writeU64(7);
+        writer.writeU64(0); // 0 removed debris
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Resync');
+        if (result.type === 'Resync') {
+          expect(result.resync.baseTick).toBe(100);
+          expect(result.resync.snapshot.tick).toBe(130);
+          expect(result.resync.snapshot.aiStatus?.decisionsTotal).toBe(4);
+          expect(result.resync.removedPlayers).toEqual(['cccccccc-cccc-cccc-cccc-cccccccccccc']);
+          expect(result.resync.removedProjectiles).toEqual([7]);
+          expect(result.resync.removedDebris).toEqual([]);
+        }
+      });
+    });
+
+    describe('streamed join decoding', () => {
+      it('should decode JoinAccepted with the join world', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(0); // JoinAccepted variant
+        writer.writeUuid('aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee');
+        writer.writeByteArray(new Uint8Array([0xff]));
+        writer.writeBool(false);
+        writer.writeU8(0); // no encryption key
+        writer.writeString('1BCD2EF');
+        writer.writeU8(1); // world: Some
+
+        // Base snapshot (no entities)
+        writer.writeU64(42); // tick
+        writer.writeU32(2); // playing
+        writer.writeF32(30.0);
+        writer.writeF32(0);
+        writer.writeU64(0); // players
+        writer.writeU64(0); // projectiles
+        writer.writeU64(0); // debris
+        writer.writeU8(0);
+        writer.writeF32(800.0);
+        writer.writeF32(1.0);
+        writer.writeU64(0); // gravity wells
+        writer.writeU64(0); // asteroids
+        writer.writeU32(150);
+        writer.writeU32(140);
+        writer.writeU64(0); // density grid
+        writer.writeU64(0); // echoClientTime
+        writer.writeU8(0); // interpDelayMs (u16 LE)
+        writer.writeU8(0);
+        writer.writeU8(0); // aiStatus: None
+        writer.writeU8(3); // chunks (u16 LE)
+        writer.writeU8(0);
+        writer.writeByteArray(new Uint8Array([])); // account token
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('JoinAccepted');
+        if (result.type === 'JoinAccepted') {
+          expect(result.arenaCode).toBe('1BCD2EF');
+          expect(result.world?.chunks).toBe(3);
+          expect(result.world?.base.tick).toBe(42);
+          expect(result.world?.base.arenaSafeRadius).toBe(800);
+          expect(result.world?.base.totalPlayers).toBe(150);
+        }
+      });
+
+      it('should decode a WorldChunk', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(21); // WorldChunk variant
+        writer.writeU64(42); // tick
+        writer.writeU8(1); // index (u16 LE)
+        writer.writeU8(0);
+        writer.writeU64(1); // players
+        writePlayerSnapshot(writer, {
+          id: 'cccccccc-cccc-cccc-cccc-cccccccccccc',
+          name: 'Near',
+          position: new Vec2(10, 20),
+          velocity: new Vec2(0, 0),
+          rotation: 0,
+          mass: 100,
+          alive: true,
+          kills: 0,
+          deaths: 0,
+          spawnProtection: false,
+          isBot: false,
+          colorIndex: 2,
+        });
+        writer.writeU64(0); // projectiles
+        writer.writeU64(1); // debris
+        writer.writeU64(5);
+        writer.writeVec2(new Vec2(30, 40));
+        writer.writeU8(1); // medium
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('WorldChunk');
+        if (result.type === 'WorldChunk') {
+          expect(result.chunk.tick).toBe(42);
+          expect(result.chunk.index).toBe(1);
+          expect(result.chunk.players[0].name).toBe('Near');
+          expect(result.chunk.projectiles).toEqual([]);
+          expect(result.chunk.debris[0].id).toBe(5);
+        }
+      });
+    });
+
+    describe('RegionHint decoding', () => {
+      it('should decode signed cell coordinates and debris counts', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(22); // RegionHint variant
+        writer.writeU64(300); // tick
+        writer.writeF32(512); // cellSize
+        writer.writeU64(2); // cells
+        writer.writeU32(3); // x (i32)
+        writer.writeU32(-2 >>> 0); // y (i32)
+        writer.writeU8(7); // debris (u16 LE)
+        writer.writeU8(0);
+        writer.writeU32(4);
+        writer.writeU32(0);
+        writer.writeU8(0);
+        writer.writeU8(0);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('RegionHint');
+        if (result.type === 'RegionHint') {
+          expect(result.hint.tick).toBe(300);
+          expect(result.hint.cellSize).toBe(512);
+          expect(result.hint.cells).toEqual([
+            { x: 3, y: -2, debris: 7 },
+            { x: 4, y: 0, debris: 0 },
+          ]);
+        }
+      });
+    });
+
+    describe('RoomList decoding', () => {
+      it('should decode listings with their friends and the page', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(23); // RoomList variant
+        writer.writeU64(1); // rooms
+        writer.writeUuid('aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee');
+        writer.writeString('Game 1');
+        writer.writeString('slow_mode');
+        writer.writeString('eu-west');
+        writer.writeU32(3); // players
+        writer.writeU32(10); // maxPlayers
+        writer.writeBool(true); // inProgress
+        writer.writeU64(1); // friends
+        writer.writeUuid('12345678-1234-5678-1234-567812345678');
+        writer.writeU32(1); // page
+        writer.writeU32(4); // totalPages
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('RoomList');
+        if (result.type === 'RoomList') {
+          expect(result.rooms).toEqual([
+            {
+              roomId: 'aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee',
+              name: 'Game 1',
+              mode: 'slow_mode',
+              region: 'eu-west',
+              players: 3,
+              maxPlayers: 10,
+              inProgress: true,
+              friends: ['12345678-1234-5678-1234-567812345678'],
+            },
+          ]);
+          expect(result.page).toBe(1);
+          expect(result.totalPages).toBe(4);
+        }
+      });
+    });
+
+    describe('GalaxyMap decoding', () => {
+      it('should decode arenas and wormholes', () => {
+        const sol = 'aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee';
+        const vega = '12345678-1234-5678-1234-567812345678';
+        const writer = new TestBinaryWriter();
+        writer.writeU32(24); // GalaxyMap variant
+        writer.writeUuid(vega); // current
+        writer.writeU64(2); // arenas
+        for (const [id, name] of [[sol, 'Sol'], [vega, 'Vega']]) {
+          writer.writeUuid(id);
+          writer.writeString(name);
+          writer.writeString('standard');
+          writer.writeU32(2); // players
+          writer.writeU32(10); // maxPlayers
+        }
+        writer.writeU64(1); // wormholes
+        writer.writeUuid(sol);
+        writer.writeUuid(vega);
+        writer.writeVec2(new Vec2(1200, -40));
+        writer.writeF32(80);
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('GalaxyMap');
+        if (result.type === 'GalaxyMap') {
+          expect(result.map.current).toBe(vega);
+          expect(result.map.arenas.map((a) => a.name)).toEqual(['Sol', 'Vega']);
+          expect(result.map.arenas[1]).toEqual({
+            roomId: vega,
+            name: 'Vega',
+            mode: 'standard',
+            players: 2,
+            maxPlayers: 10,
+          });
+          expect(result.map.wormholes).toHaveLength(1);
+          expect(result.map.wormholes[0].from).toBe(sol);
+          expect(result.map.wormholes[0].to).toBe(vega);
+          expect(result.map.wormholes[0].position.x).toBeCloseTo(1200);
+          expect(result.map.wormholes[0].position.y).toBeCloseTo(-40);
+          expect(result.map.wormholes[0].radius).toBeCloseTo(80);
+        }
+      });
+    });
+
+    describe('Sealed decoding', () => {
+      it('should decode a Sealed nonce and payload', () => {
+        const writer = new TestBinaryWriter();
+        writer.writeU32(20); // Sealed variant
+        writer.writeU64(9);
+        writer.writeByteArray(new Uint8Array([1, 2, 3]));
+
+        const result = decodeServerMessage(writer.getBuffer());
+        expect(result.type).toBe('Sealed');
+        if (result.type === 'Sealed') {
+          expect(result.nonce).toBe(9);
+          expect(result.payload).toEqual(new Uint8Array([1, 2, 3]));
+        }
+      });
     });
 
     describe('error handling', () => {
@@ -867,6 +1755,12 @@ class TestBinaryWriter {
     this.view.setUint8(this.offset++, value);
   }
 
+  writeU16(value: number): void {
+    this.ensureCapacity(2);
+    this.view.setUint16(this.offset, value, true);
+    this.offset += 2;
+  }
+
   writeU32(value: number): void {
     this.ensureCapacity(4);
     this.view.setUint32(this.offset, value, true);
@@ -897,6 +1791,16 @@ class TestBinaryWriter {
     this.offset += bytes.length;
   }
 
+  writeLocalizedText(key: string, params: Record<string, string> = {}): void {
+    this.writeString(key);
+    const entries = Object.entries(params);
+    this.writeU64(entries.length);
+    for (const [name, value] of entries) {
+      this.writeString(name);
+      this.writeString(value);
+    }
+  }
+
   writeVec2(v: Vec2): void {
     this.writeF32(v.x);
     this.writeF32(v.y);
@@ -910,6 +1814,20 @@ class TestBinaryWriter {
     }
   }
 
+  writeVarint(value: number): void {
+    while (value >= 0x80) {
+      this.writeU8((value % 0x80) | 0x80);
+      value = Math.floor(value / 0x80);
+    }
+    this.writeU8(value);
+  }
+
+  // Zigzag varint of `value` quantized to `steps` per unit
+  writeQuantized(value: number, steps: number): void {
+    const q = Math.round(value * steps);
+    this.writeVarint(q >= 0 ? q * 2 : -q * 2 - 1);
+  }
+
   writeByteArray(data: Uint8Array): void {
     this.writeU64(data.length);
     this.ensureCapacity(data.length);
@@ -958,6 +1876,7 @@ class TestBinaryReader {
 const PLAYER_FLAG_ALIVE = 0b0000_0001;
 const PLAYER_FLAG_SPAWN_PROTECTION = 0b0000_0010;
 const PLAYER_FLAG_IS_BOT = 0b0000_0100;
+const PLAYER_FLAG_GHOST = 0b0000_1000;
 
 function writePlayerSnapshot(writer: TestBinaryWriter, player: {
   id: string;
@@ -971,8 +1890,13 @@ function writePlayerSnapshot(writer: TestBinaryWriter, player: {
   deaths: number;
   spawnProtection: boolean;
   isBot: boolean;
+  isGhost?: boolean;
   colorIndex: number;
   spawnTick?: number;
+  charge?: number; // Raw charge byte
+  heat?: number; // Raw heat byte
+  faction?: number;
+  effects?: number;
 }): void {
   writer.writeUuid(player.id);
   writer.writeString(player.name);
@@ -985,9 +1909,14 @@ function writePlayerSnapshot(writer: TestBinaryWriter, player: {
   if (player.alive) flags |= PLAYER_FLAG_ALIVE;
   if (player.spawnProtection) flags |= PLAYER_FLAG_SPAWN_PROTECTION;
   if (player.isBot) flags |= PLAYER_FLAG_IS_BOT;
+  if (player.isGhost) flags |= PLAYER_FLAG_GHOST;
   writer.writeU8(flags);
   writer.writeU32(player.kills);
   writer.writeU32(player.deaths);
   writer.writeU8(player.colorIndex);
   writer.writeU64(player.spawnTick ?? 0);
+  writer.writeU8(player.charge ?? 0);
+  writer.writeU8(player.heat ?? 0);
+  writer.writeU8(player.faction ?? 0);
+  writer.writeU8(player.effects ?? 0);
 }
@@ -8,15 +8,34 @@ import type {
   PlayerInput,
   GameSnapshot,
   DeltaUpdate,
+  ResyncUpdate,
+  JoinWorld,
+  WorldChunk,
+  RegionHint,
+  RegionCell,
+  RoomListing,
+  GalaxyMap,
+  GalaxyArena,
+  GalaxyWormhole,
+  AIStatusSnapshot,
   GameEvent,
+  Award,
+  AwardKind,
+  KillCause,
+  KillFeedEntry,
   PlayerSnapshot,
   ProjectileSnapshot,
   DebrisSnapshot,
+  AsteroidSnapshot,
   PlayerDelta,
   ProjectileDelta,
   MatchPhase,
   GravityWellSnapshot,
   RejectionReason,
+  LocalizedText,
+  SnapshotRate,
+  ReportReason,
+  PlayerId,
 } from './Protocol';
 
 // Binary writer for encoding messages
@@ -81,6 +100,13 @@ class BinaryWriter {
     this.writeF32(v.y);
   }
 
+  writeByteArray(bytes: Uint8Array): void {
+    this.writeU64(bytes.length);
+    this.ensureCapacity(bytes.length);
+    new Uint8Array(this.buffer, this.offset).set(bytes);
+    this.offset += bytes.length;
+  }
+
   writeUuid(uuid: string): void {
     // bincode with legacy config serializes UUID with a length prefix (as if it were Vec<u8>)
     this.writeU64(16); // Length prefix
@@ -109,12 +135,24 @@ class BinaryReader {
     return this.view.getUint8(this.offset++);
   }
 
+  readU16(): number {
+    const value = this.view.getUint16(this.offset, true);
+    this.offset += 2;
+    return value;
+  }
+
   readU32(): number {
     const value = this.view.getUint32(this.offset, true);
     this.offset += 4;
     return value;
   }
 
+  readI32(): number {
+    const value = this.view.getInt32(this.offset, true);
+    this.offset += 4;
+    return value;
+  }
+
   readU64(): number {
     const value = this.view.getBigUint64(this.offset, true);
     this.offset += 8;
@@ -148,7 +186,11 @@ class BinaryReader {
     if (length !== 16) {
       throw new Error(`Invalid UUID length: expected 16, got ${length}`);
     }
+    return this.readRawUuid();
+  }
 
+  // UUID as 16 bytes without a length prefix (packed player deltas)
+  readRawUuid(): string {
     const bytes: string[] = [];
     for (let i = 0; i < 16; i++) {
       bytes.push(this.readU8().toString(16).padStart(2, '0'));
@@ -157,6 +199,24 @@ class BinaryReader {
     return `${bytes.slice(0, 4).join('')}-${bytes.slice(4, 6).join('')}-${bytes.slice(6, 8).join('')}-${bytes.slice(8, 10).join('')}-${bytes.slice(10, 16).join('')}`;
   }
 
+  // LEB128 unsigned varint (up to 32 bits)
+  readVarint(): number {
+    let value = 0;
+    for (let shift = 0; shift < 35; shift += 7) {
+      const byte = this.readU8();
+      value += (byte & 0x7f) * 2 ** shift;
+      if ((byte & 0x80) === 0) return value;
+    }
+    throw new Error('Overlong varint');
+  }
+
+  // Zigzag varint scaled down by `steps` per unit
+  readQuantized(steps: number): number {
+    const raw = this.readVarint();
+    const value = raw % 2 === 0 ? raw / 2 : -(raw + 1) / 2;
+    return value / steps;
+  }
+
   readBytes(length: number): Uint8Array {
     const bytes = new Uint8Array(this.view.buffer, this.offset, length);
     this.offset += length;
@@ -183,6 +243,57 @@ export function encodeClientMessage(msg: ClientMessage): Uint8Array {
       writer.writeString(msg.playerName);
       writer.writeU8(msg.colorIndex);
       writer.writeBool(msg.isSpectator);
+      // Option<PartyInvite> party_invite
+      if (msg.partyInvite) {
+        writer.writeU8(1);
+        writer.writeUuid(msg.partyInvite);
+      } else {
+        writer.writeU8(0);
+      }
+      // Option<Vec<u8>> resume_token
+      if (msg.resumeToken) {
+        writer.writeU8(1);
+        writer.writeByteArray(msg.resumeToken);
+      } else {
+        writer.writeU8(0);
+      }
+      // Option<String> auth_token
+      if (msg.authToken) {
+        writer.writeU8(1);
+        writer.writeString(msg.authToken);
+      } else {
+        writer.writeU8(0);
+      }
+      // ClientCapabilities { max_snapshot_rate, progressive_join }
+      writer.writeU32(snapshotRateVariant(msg.capabilities?.maxSnapshotRate ?? 'normal'));
+      writer.writeU8(msg.capabilities?.progressiveJoin ? 1 : 0);
+      // AccessibilitySettings { orbit_assist }
+      writer.writeU8(msg.accessibility?.orbitAssist ? 1 : 0);
+      // TelemetryConsent { analytics, replays, behavior }
+      writer.writeU8((msg.telemetry?.analytics ?? true) ? 1 : 0);
+      writer.writeU8((msg.telemetry?.replays ?? true) ? 1 : 0);
+      writer.writeU8((msg.telemetry?.behavior ?? true) ? 1 : 0);
+      // Option<Vec<u8>> encryption_key
+      if (msg.encryptionKey) {
+        writer.writeU8(1);
+        writer.writeByteArray(msg.encryptionKey);
+      } else {
+        writer.writeU8(0);
+      }
+      // Option<Uuid> room
+      if (msg.room) {
+        writer.writeU8(1);
+        writer.writeUuid(msg.room);
+      } else {
+        writer.writeU8(0);
+      }
+      // Option<Vec<u8>> account_token
+      if (msg.accountToken) {
+        writer.writeU8(1);
+        writer.writeByteArray(msg.accountToken);
+      } else {
+        writer.writeU8(0);
+      }
       break;
     case 'Input':
       writer.writeU32(1);
@@ -217,6 +328,43 @@ export function encodeClientMessage(msg: ClientMessage): Uint8Array {
       writer.writeU32(7);
       writer.writeF32(msg.zoom);
       break;
+    case 'Chat':
+      writer.writeU32(8);
+      writer.writeString(msg.text);
+      break;
+    case 'Report':
+      writer.writeU32(9);
+      writer.writeUuid(msg.target);
+      writer.writeU32(reportReasonVariant(msg.reason));
+      break;
+    case 'ListRooms': {
+      writer.writeU32(10);
+      // RoomFilter { mode: Option<String>, region: Option<String>, not_full, friends: Vec<PlayerId> }
+      for (const value of [msg.filter.mode, msg.filter.region]) {
+        if (value) {
+          writer.writeU8(1);
+          writer.writeString(value);
+        } else {
+          writer.writeU8(0);
+        }
+      }
+      writer.writeBool(msg.filter.notFull ?? false);
+      const friends = msg.filter.friends ?? [];
+      writer.writeU64(friends.length);
+      for (const friend of friends) {
+        writer.writeUuid(friend);
+      }
+      writer.writeU32(msg.page);
+      break;
+    }
+    case 'SpectateRoom':
+      writer.writeU32(11);
+      writer.writeUuid(msg.roomId);
+      writer.writeString(msg.playerName);
+      break;
+    case 'RequestResync':
+      writer.writeU32(12);
+      break;
   }
 
   return writer.getBytes();
@@ -231,6 +379,13 @@ function writePlayerInput(writer: BinaryWriter, input: PlayerInput): void {
   writer.writeBool(input.boost);
   writer.writeBool(input.fire);
   writer.writeBool(input.fireReleased);
+  if (input.stateHash) {
+    writer.writeU8(1);
+    writer.writeU64(input.stateHash.tick);
+    writer.writeU32(input.stateHash.hash);
+  } else {
+    writer.writeU8(0);
+  }
 }
 
 // Decode server message from binary
@@ -245,6 +400,10 @@ export function decodeServerMessage(data: ArrayBuffer): ServerMessage {
         playerId: reader.readUuid(),
         sessionToken: reader.readByteArray(),
         isSpectator: reader.readBool(),
+        encryptionKey: reader.readU8() === 1 ? reader.readByteArray() : null,
+        arenaCode: reader.readString(),
+        world: reader.readU8() === 1 ? readJoinWorld(reader) : null,
+        accountToken: reader.readByteArray(),
       };
     case 1: // JoinRejected
       return {
@@ -275,7 +434,7 @@ export function decodeServerMessage(data: ArrayBuffer): ServerMessage {
     case 6: // Kicked
       return {
         type: 'Kicked',
-        reason: reader.readString(),
+        reason: readLocalizedText(reader),
       };
     case 7: // PhaseChange
       return {
@@ -288,11 +447,124 @@ export function decodeServerMessage(data: ArrayBuffer): ServerMessage {
         type: 'SpectatorModeChanged',
         isSpectator: reader.readBool(),
       };
+    case 9: // Commentary
+      return {
+        type: 'Commentary',
+        text: reader.readString(),
+        tick: reader.readU64(),
+      };
+    case 10: // QueueUpdate
+      return {
+        type: 'QueueUpdate',
+        position: reader.readU32(),
+        etaSecs: reader.readU32(),
+      };
+    case 11: // Chat
+      return {
+        type: 'Chat',
+        playerId: reader.readUuid(),
+        name: reader.readString(),
+        text: reader.readString(),
+      };
+    case 12: // Announcement
+      return {
+        type: 'Announcement',
+        text: readLocalizedText(reader),
+      };
+    case 13: // CommandResult
+      return {
+        type: 'CommandResult',
+        success: reader.readBool(),
+        message: readLocalizedText(reader),
+      };
+    case 14: // SpectateTargetChanged
+      return {
+        type: 'SpectateTargetChanged',
+        targetId: reader.readU8() === 1 ? reader.readUuid() : null,
+      };
+    case 15: // SnapshotRate
+      return {
+        type: 'SnapshotRate',
+        hz: reader.readU8(),
+      };
+    case 16: // MatchPaused
+      return {
+        type: 'MatchPaused',
+        reason: readLocalizedText(reader),
+        resumeCountdown: reader.readF32(),
+      };
+    case 17: // MatchResumed
+      return { type: 'MatchResumed' };
+    case 18: { // Events
+      const count = reader.readU64();
+      const events: GameEvent[] = [];
+      for (let i = 0; i < count; i++) {
+        events.push(readGameEvent(reader));
+      }
+      return { type: 'Events', events };
+    }
+    case 19: // Resync
+      return {
+        type: 'Resync',
+        resync: readResyncUpdate(reader),
+      };
+    case 20: // Sealed
+      return {
+        type: 'Sealed',
+        nonce: reader.readU64(),
+        payload: reader.readByteArray(),
+      };
+    case 21: // WorldChunk
+      return {
+        type: 'WorldChunk',
+        chunk: readWorldChunk(reader),
+      };
+    case 22: // RegionHint
+      return {
+        type: 'RegionHint',
+        hint: readRegionHint(reader),
+      };
+    case 23: { // RoomList
+      const count = reader.readU64();
+      const rooms: RoomListing[] = [];
+      for (let i = 0; i < count; i++) {
+        rooms.push(readRoomListing(reader));
+      }
+      return {
+        type: 'RoomList',
+        rooms,
+        page: reader.readU32(),
+        totalPages: reader.readU32(),
+      };
+    }
+    case 24: // GalaxyMap
+      return {
+        type: 'GalaxyMap',
+        map: readGalaxyMap(reader),
+      };
     default:
       throw new Error(`Unknown server message variant: ${variant}`);
   }
 }
 
+function snapshotRateVariant(rate: SnapshotRate): number {
+  switch (rate) {
+    case 'low': return 0;
+    case 'normal': return 1;
+    case 'high': return 2;
+  }
+}
+
+function reportReasonVariant(reason: ReportReason): number {
+  switch (reason) {
+    case 'Cheating': return 0;
+    case 'Harassment': return 1;
+    case 'Griefing': return 2;
+    case 'Spam': return 3;
+    case 'Other': return 4;
+  }
+}
+
 function readMatchPhase(reader: BinaryReader): MatchPhase {
   const variant = reader.readU32();
   switch (variant) {
@@ -340,9 +612,18 @@ function readGameSnapshot(reader: BinaryReader): GameSnapshot {
       position: reader.readVec2(),
       mass: reader.readF32(),
       coreRadius: reader.readF32(),
+      ownerColor: reader.readU8() === 1 ? reader.readU8() : null,
+      captureProgress: reader.readF32(),
+      contested: reader.readBool(),
     });
   }
 
+  const asteroidCount = reader.readU64();
+  const asteroids: AsteroidSnapshot[] = [];
+  for (let i = 0; i < asteroidCount; i++) {
+    asteroids.push(readAsteroidSnapshot(reader));
+  }
+
   // Read total player counts (for UI display with AOI filtering)
   const totalPlayers = reader.readU32();
   const totalAlive = reader.readU32();
@@ -357,6 +638,9 @@ function readGameSnapshot(reader: BinaryReader): GameSnapshot {
   // Read echo_client_time for RTT measurement
   const echoClientTime = reader.readU64();
 
+  // Server-recommended interpolation delay (0 = none)
+  const interpDelayMs = reader.readU16();
+
   return {
     tick,
     matchPhase,
@@ -369,10 +653,12 @@ function readGameSnapshot(reader: BinaryReader): GameSnapshot {
     arenaSafeRadius,
     arenaScale,
     gravityWells,
+    asteroids,
     totalPlayers,
     totalAlive,
     densityGrid,
     echoClientTime,
+    interpDelayMs,
   };
 }
 
@@ -380,6 +666,12 @@ function readGameSnapshot(reader: BinaryReader): GameSnapshot {
 const PLAYER_FLAG_ALIVE = 0b0000_0001;
 const PLAYER_FLAG_SPAWN_PROTECTION = 0b0000_0010;
 const PLAYER_FLAG_IS_BOT = 0b0000_0100;
+const PLAYER_FLAG_GHOST = 0b0000_1000;
+
+// Charge byte: 0 = not charging, 1-255 = windup scaled to 0-1
+function decodeCharge(value: number): number | null {
+  return value === 0 ? null : (value - 1) / 254;
+}
 
 function readPlayerSnapshot(reader: BinaryReader): PlayerSnapshot {
   const id = reader.readUuid();
@@ -393,10 +685,15 @@ function readPlayerSnapshot(reader: BinaryReader): PlayerSnapshot {
   const alive = (flags & PLAYER_FLAG_ALIVE) !== 0;
   const spawnProtection = (flags & PLAYER_FLAG_SPAWN_PROTECTION) !== 0;
   const isBot = (flags & PLAYER_FLAG_IS_BOT) !== 0;
+  const isGhost = (flags & PLAYER_FLAG_GHOST) !== 0;
   const kills = reader.readU32();
   const deaths = reader.readU32();
   const colorIndex = reader.readU8();
   const spawnTick = reader.readU64();
+  const charge = decodeCharge(reader.readU8());
+  const heat = reader.readU8() / 255;
+  const faction = reader.readU8();
+  const effects = reader.readU8();
 
   return {
     id,
@@ -410,8 +707,13 @@ function readPlayerSnapshot(reader: BinaryReader): PlayerSnapshot {
     deaths,
     spawnProtection,
     isBot,
+    isGhost,
     colorIndex,
     spawnTick,
+    charge,
+    heat,
+    faction,
+    effects,
   };
 }
 
@@ -433,6 +735,15 @@ function readDebrisSnapshot(reader: BinaryReader): DebrisSnapshot {
   };
 }
 
+function readAsteroidSnapshot(reader: BinaryReader): AsteroidSnapshot {
+  return {
+    id: reader.readU64(),
+    position: reader.readVec2(),
+    radius: reader.readF32(),
+    health: reader.readU8() / 255,
+  };
+}
+
 function readDeltaUpdate(reader: BinaryReader): DeltaUpdate {
   const tick = reader.readU64();
   const baseTick = reader.readU64();
@@ -462,6 +773,18 @@ function readDeltaUpdate(reader: BinaryReader): DeltaUpdate {
     debris.push(readDebrisSnapshot(reader));
   }
 
+  const removedPlayerCount = reader.readU64();
+  const removedPlayers: PlayerId[] = [];
+  for (let i = 0; i < removedPlayerCount; i++) {
+    removedPlayers.push(reader.readUuid());
+  }
+
+  const asteroidCount = reader.readU64();
+  const asteroids: AsteroidSnapshot[] = [];
+  for (let i = 0; i < asteroidCount; i++) {
+    asteroids.push(readAsteroidSnapshot(reader));
+  }
+
   return {
     tick,
     baseTick,
@@ -469,31 +792,190 @@ function readDeltaUpdate(reader: BinaryReader): DeltaUpdate {
     projectileUpdates,
     removedProjectiles,
     debris,
+    removedPlayers,
+    asteroids,
+  };
+}
+
+function readJoinWorld(reader: BinaryReader): JoinWorld {
+  const base = readGameSnapshot(reader);
+  // Full snapshots end at aiStatus and stop decoding before it; here more fields follow
+  const aiStatus = readOptionalAIStatus(reader);
+  if (aiStatus) base.aiStatus = aiStatus;
+  return { base, chunks: reader.readU16() };
+}
+
+function readWorldChunk(reader: BinaryReader): WorldChunk {
+  const tick = reader.readU64();
+  const index = reader.readU16();
+
+  const playerCount = reader.readU64();
+  const players: PlayerSnapshot[] = [];
+  for (let i = 0; i < playerCount; i++) {
+    players.push(readPlayerSnapshot(reader));
+  }
+
+  const projectileCount = reader.readU64();
+  const projectiles: ProjectileSnapshot[] = [];
+  for (let i = 0; i < projectileCount; i++) {
+    projectiles.push(readProjectileSnapshot(reader));
+  }
+
+  const debrisCount = reader.readU64();
+  const debris: DebrisSnapshot[] = [];
+  for (let i = 0; i < debrisCount; i++) {
+    debris.push(readDebrisSnapshot(reader));
+  }
+
+  return { tick, index, players, projectiles, debris };
+}
+
+function readRegionHint(reader: BinaryReader): RegionHint {
+  const tick = reader.readU64();
+  const cellSize = reader.readF32();
+  const count = reader.readU64();
+  const cells: RegionCell[] = [];
+  for (let i = 0; i < count; i++) {
+    cells.push({ x: reader.readI32(), y: reader.readI32(), debris: reader.readU16() });
+  }
+  return { tick, cellSize, cells };
+}
+
+function readRoomListing(reader: BinaryReader): RoomListing {
+  const roomId = reader.readUuid();
+  const name = reader.readString();
+  const mode = reader.readString();
+  const region = reader.readString();
+  const players = reader.readU32();
+  const maxPlayers = reader.readU32();
+  const inProgress = reader.readBool();
+  const friendCount = reader.readU64();
+  const friends: PlayerId[] = [];
+  for (let i = 0; i < friendCount; i++) {
+    friends.push(reader.readUuid());
+  }
+  return { roomId, name, mode, region, players, maxPlayers, inProgress, friends };
+}
+
+function readGalaxyMap(reader: BinaryReader): GalaxyMap {
+  const current = reader.readUuid();
+  const arenaCount = reader.readU64();
+  const arenas: GalaxyArena[] = [];
+  for (let i = 0; i < arenaCount; i++) {
+    arenas.push({
+      roomId: reader.readUuid(),
+      name: reader.readString(),
+      mode: reader.readString(),
+      players: reader.readU32(),
+      maxPlayers: reader.readU32(),
+    });
+  }
+  const wormholeCount = reader.readU64();
+  const wormholes: GalaxyWormhole[] = [];
+  for (let i = 0; i < wormholeCount; i++) {
+    wormholes.push({
+      from: reader.readUuid(),
+      to: reader.readUuid(),
+      position: reader.readVec2(),
+      radius: reader.readF32(),
+    });
+  }
+  return { current, arenas, wormholes };
+}
+
+function readResyncUpdate(reader: BinaryReader): ResyncUpdate {
+  const baseTick = reader.readU64();
+  const snapshot = readGameSnapshot(reader);
+  // Full snapshots end at aiStatus and stop decoding before it; here more fields follow
+  const aiStatus = readOptionalAIStatus(reader);
+  if (aiStatus) snapshot.aiStatus = aiStatus;
+
+  const removedPlayerCount = reader.readU64();
+  const removedPlayers: PlayerId[] = [];
+  for (let i = 0; i < removedPlayerCount; i++) {
+    removedPlayers.push(reader.readUuid());
+  }
+
+  const removedProjectileCount = reader.readU64();
+  const removedProjectiles: number[] = [];
+  for (let i = 0; i < removedProjectileCount; i++) {
+    removedProjectiles.push(reader.readU64());
+  }
+
+  const removedDebrisCount = reader.readU64();
+  const removedDebris: number[] = [];
+  for (let i = 0; i < removedDebrisCount; i++) {
+    removedDebris.push(reader.readU64());
+  }
+
+  return { baseTick, snapshot, removedPlayers, removedProjectiles, removedDebris };
+}
+
+function readOptionalAIStatus(reader: BinaryReader): AIStatusSnapshot | undefined {
+  if (reader.readU8() !== 1) return undefined;
+  return {
+    enabled: reader.readBool(),
+    lastDecision: reader.readU8() === 1 ? reader.readString() : undefined,
+    confidence: reader.readU8(),
+    successRate: reader.readU8(),
+    decisionsTotal: reader.readU32(),
+    decisionsSuccessful: reader.readU32(),
   };
 }
 
+// Packed player delta fields, in mask bit order (matches net::delta::field on the server)
+const DELTA_POSITION = 1 << 0;
+const DELTA_VELOCITY = 1 << 1;
+const DELTA_ROTATION = 1 << 2;
+const DELTA_HEAT = 1 << 3;
+const DELTA_CHARGE = 1 << 4;
+const DELTA_MASS = 1 << 5;
+const DELTA_EFFECTS = 1 << 6;
+const DELTA_KILLS = 1 << 7;
+const DELTA_ALIVE = 1 << 8;
+const DELTA_FACTION = 1 << 9;
+
+// Quantization steps per unit of packed delta values
+const POSITION_STEPS = 8;
+const VELOCITY_STEPS = 4;
+const ROTATION_STEPS = 4096 / (2 * Math.PI);
+const MASS_STEPS = 8;
+
+// Raw id, a varint mask of the fields present, then each field in mask bit order
 function readPlayerDelta(reader: BinaryReader): PlayerDelta {
-  const id = reader.readUuid();
+  const id = reader.readRawUuid();
   const delta: PlayerDelta = { id };
+  const mask = reader.readVarint();
 
-  // Read Option<Vec2> for position
-  if (reader.readBool()) {
-    delta.position = reader.readVec2();
+  if (mask & DELTA_POSITION) {
+    delta.position = new Vec2(reader.readQuantized(POSITION_STEPS), reader.readQuantized(POSITION_STEPS));
   }
-  if (reader.readBool()) {
-    delta.velocity = reader.readVec2();
+  if (mask & DELTA_VELOCITY) {
+    delta.velocity = new Vec2(reader.readQuantized(VELOCITY_STEPS), reader.readQuantized(VELOCITY_STEPS));
   }
-  if (reader.readBool()) {
-    delta.rotation = reader.readF32();
+  if (mask & DELTA_ROTATION) {
+    delta.rotation = reader.readQuantized(ROTATION_STEPS);
   }
-  if (reader.readBool()) {
-    delta.mass = reader.readF32();
+  if (mask & DELTA_HEAT) {
+    delta.heat = reader.readU8() / 255;
   }
-  if (reader.readBool()) {
+  if (mask & DELTA_CHARGE) {
+    delta.charge = decodeCharge(reader.readU8());
+  }
+  if (mask & DELTA_MASS) {
+    delta.mass = reader.readQuantized(MASS_STEPS);
+  }
+  if (mask & DELTA_EFFECTS) {
+    delta.effects = reader.readU8();
+  }
+  if (mask & DELTA_KILLS) {
+    delta.kills = reader.readVarint();
+  }
+  if (mask & DELTA_ALIVE) {
     delta.alive = reader.readBool();
   }
-  if (reader.readBool()) {
-    delta.kills = reader.readU32();
+  if (mask & DELTA_FACTION) {
+    delta.faction = reader.readU8();
   }
 
   return delta;
@@ -511,14 +993,8 @@ function readGameEvent(reader: BinaryReader): GameEvent {
   const variant = reader.readU32();
 
   switch (variant) {
-    case 0: // PlayerKilled
-      return {
-        type: 'PlayerKilled',
-        killerId: reader.readUuid(),
-        victimId: reader.readUuid(),
-        killerName: reader.readString(),
-        victimName: reader.readString(),
-      };
+    case 0: // KillFeed
+      return { type: 'KillFeed', ...readKillFeedEntry(reader) };
     case 1: // PlayerJoined
       return {
         type: 'PlayerJoined',
@@ -533,13 +1009,21 @@ function readGameEvent(reader: BinaryReader): GameEvent {
       };
     case 3: // MatchStarted
       return { type: 'MatchStarted' };
-    case 4: // MatchEnded
-      const hasWinner = reader.readBool();
-      return {
-        type: 'MatchEnded',
-        winnerId: hasWinner ? reader.readUuid() : null,
-        winnerName: hasWinner ? reader.readString() : null,
-      };
+    case 4: { // MatchEnded
+      const winnerId = reader.readBool() ? reader.readUuid() : null;
+      const winnerName = reader.readBool() ? reader.readString() : null;
+      const awardCount = reader.readU64();
+      const awards: Award[] = [];
+      for (let i = 0; i < awardCount; i++) {
+        awards.push({
+          kind: AWARD_KINDS[reader.readU32()] ?? 'Mvp',
+          playerId: reader.readUuid(),
+          name: reader.readString(),
+          value: reader.readF32(),
+        });
+      }
+      return { type: 'MatchEnded', winnerId, winnerName, awards };
+    }
     case 5: // ZoneCollapse
       return {
         type: 'ZoneCollapse',
@@ -573,11 +1057,80 @@ function readGameEvent(reader: BinaryReader): GameEvent {
         wellId: reader.readU32(),
         position: { x: reader.readF32(), y: reader.readF32() },
       };
+    case 10: // BotChatter
+      return {
+        type: 'BotChatter',
+        playerId: reader.readUuid(),
+        kind: reader.readU32() === 0 ? 'Taunt' : 'Distress',
+        text: reader.readString(),
+        position: { x: reader.readF32(), y: reader.readF32() },
+      };
+    case 11: // ModifierStarted
+      return {
+        type: 'ModifierStarted',
+        name: reader.readString(),
+        description: readLocalizedText(reader),
+        duration: reader.readF32(),
+      };
+    case 12: // ModifierEnded
+      return {
+        type: 'ModifierEnded',
+        name: reader.readString(),
+      };
+    case 13: // WellCaptured
+      return {
+        type: 'WellCaptured',
+        wellId: reader.readU32(),
+        ownerId: reader.readUuid(),
+        ownerName: reader.readString(),
+        colorIndex: reader.readU8(),
+      };
+    case 14: // WellContested
+      return {
+        type: 'WellContested',
+        wellId: reader.readU32(),
+      };
+    case 15: // Hit
+      return {
+        type: 'Hit',
+        shooterId: reader.readUuid(),
+        victimId: reader.readUuid(),
+        projectileId: reader.readU64(),
+        amount: reader.readF32(),
+        position: { x: reader.readF32(), y: reader.readF32() },
+      };
+    case 16: // AsteroidDestroyed
+      return {
+        type: 'AsteroidDestroyed',
+        asteroidId: reader.readU64(),
+        position: { x: reader.readF32(), y: reader.readF32() },
+        fragments: reader.readU16(),
+      };
     default:
       throw new Error(`Unknown game event variant: ${variant}`);
   }
 }
 
+// KillCause variants in server order
+const KILL_CAUSES: KillCause[] = ['Crush', 'Projectile', 'Well', 'Boundary', 'Disconnect'];
+
+const AWARD_KINDS: AwardKind[] = ['Mvp', 'MostKills', 'Survivor', 'DebrisHoarder', 'Pacifist', 'Comeback'];
+
+function readKillFeedEntry(reader: BinaryReader): KillFeedEntry {
+  const killerId = reader.readBool() ? reader.readUuid() : null;
+  const victimId = reader.readUuid();
+  const killerName = reader.readBool() ? reader.readString() : null;
+  const victimName = reader.readString();
+  const cause = KILL_CAUSES[reader.readU32()] ?? 'Crush';
+  const text = readLocalizedText(reader);
+  const count = reader.readU64();
+  const annotations: LocalizedText[] = [];
+  for (let i = 0; i < count; i++) {
+    annotations.push(readLocalizedText(reader));
+  }
+  return { killerId, victimId, killerName, victimName, cause, text, annotations };
+}
+
 function readRejectionReason(reader: BinaryReader): RejectionReason {
   const variant = reader.readU32();
 
@@ -600,9 +1153,21 @@ function readRejectionReason(reader: BinaryReader): RejectionReason {
     case 6: // Other
       return {
         type: 'Other',
-        message: reader.readString(),
+        message: readLocalizedText(reader),
       };
     default:
       throw new Error(`Unknown rejection reason variant: ${variant}`);
   }
 }
+
+function readLocalizedText(reader: BinaryReader): LocalizedText {
+  const key = reader.readString();
+  // Vec<(String, String)>
+  const count = reader.readU64();
+  const params: Record<string, string> = {};
+  for (let i = 0; i < count; i++) {
+    const name = reader.readString();
+    params[name] = reader.readString();
+  }
+  return { key, params };
+}
@@ -0,0 +1,26 @@
+import { describe, it, expect, afterEach } from 'vitest';
+import { addTranslations, localize, setLanguage } from './Localization';
+
+describe('Localization', () => {
+  afterEach(() => setLanguage('en'));
+
+  it('should render English with parameters', () => {
+    expect(localize({ key: 'command.muted', params: { name: 'Bob', duration: '10m' } })).toBe('Muted Bob for 10m');
+    expect(localize({ key: 'verbatim', params: { text: 'Griefing {name}' } })).toBe('Griefing {name}');
+  });
+
+  it('should use the current language and fall back to English, then the key', () => {
+    addTranslations('de', { 'command.kicked': '{name} wurde gekickt' });
+    setLanguage('de');
+    expect(localize({ key: 'command.kicked', params: { name: 'Bob' } })).toBe('Bob wurde gekickt');
+    expect(localize({ key: 'command.paused', params: {} })).toBe('Match paused');
+    expect(localize({ key: 'future.key', params: {} })).toBe('future.key');
+  });
+
+  it('should render key params as nested messages in the same language', () => {
+    addTranslations('de', { 'schedule.arena_reset': 'Die Arena wird zurückgesetzt' });
+    setLanguage('de');
+    const upcoming = { key: 'announce.scheduled_minute', params: { event_key: 'schedule.arena_reset' } };
+    expect(localize(upcoming)).toBe('In 1 minute: Die Arena wird zurückgesetzt');
+  });
+});
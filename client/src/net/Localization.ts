@@ -0,0 +1,99 @@
+// Localization of server-generated text
+// The server sends message keys with named parameters (LocalizedText); this
+// module renders them with the player's language, falling back to English and
+// then to the raw key. Keys mirror the table in api/src/net/i18n.rs. A param
+// named `<name>_key` holds another message key, rendered in place of `{name}`.
+
+import type { LocalizedText } from './Protocol';
+
+type MessageTable = Record<string, string>;
+
+const ENGLISH: MessageTable = {
+  'verbatim': '{text}',
+  'kick.by_moderator': 'Kicked by a moderator',
+  'kick.server_shutdown': 'The server is shutting down',
+  'pause.by_referee': 'Paused by the referee',
+  'reject.encryption_required': 'This server requires snapshot encryption; update your client',
+  'reject.encryption_failed': 'Snapshot encryption failed: {error}',
+  'reject.room_unavailable': "That room can't be spectated",
+  'chat.muted': 'You are muted for another {duration}',
+  'command.unknown': 'Unknown command /{command}',
+  'command.usage': 'Usage: {usage}',
+  'command.invalid_duration': "Invalid duration '{duration}' (use e.g. 30s, 10m, 2h)",
+  'command.denied': "You don't have permission to use /{command}",
+  'command.kicked': 'Kicked {name}',
+  'command.muted': 'Muted {name} for {duration}',
+  'command.unmuted': 'Unmuted {name}',
+  'command.spectate_first': 'Switch to spectator mode first',
+  'command.target_spectating': '{name} is spectating',
+  'command.following': 'Following {name}',
+  'command.announced': 'Announcement sent',
+  'command.paused': 'Match paused',
+  'command.already_paused': 'The match is already paused',
+  'command.resuming': 'Resuming in {seconds}s',
+  'command.not_paused': 'The match is not paused',
+  'command.no_reviews': 'No players awaiting review',
+  'command.no_case': "No open case for '{player}'",
+  'command.upheld': 'Reports against {name} upheld',
+  'command.dismissed': 'Reports against {name} dismissed',
+  'command.self_target': "You can't target yourself",
+  'command.outranked': "Can't moderate a {role}",
+  'command.party_invite': 'Party invite code {code} (one use, valid for {duration})',
+  'command.invite_in_arena': 'Join the arena as a player to invite friends',
+  'player.not_found': "No player named '{query}'",
+  'player.ambiguous': "'{query}' matches several players, use their ID",
+  'report.received': 'Thanks, your report was received',
+  'report.self': "You can't report yourself",
+  'report.unknown_target': 'No such player',
+  'report.rate_limited': 'Too many reports, try again later',
+  'report.duplicate': 'You already reported this player recently',
+  'kill.crush': '{killer} crushed {victim}',
+  'kill.projectile': '{killer} shot down {victim}',
+  'kill.well': '{victim} fell into a gravity well',
+  'kill.boundary': '{victim} was lost beyond the arena edge',
+  'kill.died': '{victim} died',
+  'kill.double': 'Double kill',
+  'kill.triple': 'Triple kill',
+  'kill.multi': '{count} kills in a row',
+  'kill.streak': '{name} is on a {count} kill streak',
+  'kill.shutdown': "Shutdown! {name}'s {count} kill streak ended",
+  'modifier.solar_flare': 'Solar flare: projectiles travel 30% faster',
+  'modifier.dense_nebula': 'Dense nebula: vision radius halved',
+  'modifier.debris_surge': 'Debris surge: debris spawns twice as fast',
+  'schedule.boss_spawn': 'A boss bot enters the arena',
+  'schedule.arena_reset': 'The arena resets and everyone respawns',
+  'announce.scheduled_minutes': 'In {minutes} minutes: {event}',
+  'announce.scheduled_minute': 'In 1 minute: {event}',
+  'announce.scheduled_seconds': 'In {seconds} seconds: {event}',
+  'announce.boss_spawn': '{name} has entered the arena. Bring it down!',
+  'announce.arena_reset': 'The arena has been reset',
+  'announce.arena_changing': 'The arena is changing to {name}',
+};
+
+// Translations by language code (e.g. 'de'); missing keys fall back to English
+const tables: Record<string, MessageTable> = { en: ENGLISH };
+
+let currentLanguage = 'en';
+
+// Register (or extend) a translation table
+export function addTranslations(language: string, table: MessageTable): void {
+  tables[language] = { ...tables[language], ...table };
+}
+
+// Language used by localize()
+export function setLanguage(language: string): void {
+  currentLanguage = language;
+}
+
+// Template of a key in the current language
+function template(key: string): string {
+  return tables[currentLanguage]?.[key] ?? ENGLISH[key] ?? key;
+}
+
+// Render server text in the current language
+export function localize(text: LocalizedText): string {
+  return template(text.key).replace(/\{(\w+)\}/g, (placeholder, name: string) => {
+    const nestedKey = text.params[`${name}_key`];
+    return nestedKey !== undefined ? template(nestedKey) : (text.params[name] ?? placeholder);
+  });
+}
@@ -17,30 +17,147 @@ export type RejectionReason =
   | { type: 'RateLimited' }
   | { type: 'Banned' }
   | { type: 'Maintenance' }
-  | { type: 'Other'; message: string };
+  | { type: 'Other'; message: LocalizedText };
+
+// Server-generated text as a message key and named parameters (see Localization.ts)
+export interface LocalizedText {
+  key: string;
+  params: Record<string, string>;
+}
 
 // Client -> Server messages
 export type ClientMessage =
-  | { type: 'JoinRequest'; playerName: string; colorIndex: number; isSpectator: boolean }
+  | {
+      type: 'JoinRequest';
+      playerName: string;
+      colorIndex: number;
+      isSpectator: boolean;
+      partyInvite?: string | null; // Invite code from a party member in game (/invite, queue priority)
+      resumeToken?: Uint8Array | null; // Session token from a previous join (reconnect priority)
+      authToken?: string | null; // Operator-issued token (vip/moderator/admin role)
+      capabilities?: ClientCapabilities; // Optional features (defaults to 10Hz snapshots)
+      accessibility?: AccessibilitySettings; // Accessibility options (default: all off)
+      telemetry?: TelemetryConsent; // Per-player telemetry consent (default: all allowed)
+      encryptionKey?: Uint8Array | null; // ECDH public key to opt into snapshot encryption
+      room?: string | null; // Lobby room to play in instead of the main arena (players only)
+      accountToken?: Uint8Array | null; // Account token from a previous join (keeps the same account)
+    }
   | { type: 'Input'; input: PlayerInput }
   | { type: 'Leave' }
   | { type: 'Ping'; timestamp: number }
   | { type: 'SnapshotAck'; tick: number }
   | { type: 'SpectateTarget'; targetId: PlayerId | null }
   | { type: 'SwitchToPlayer'; colorIndex: number }
-  | { type: 'ViewportInfo'; zoom: number };
+  | { type: 'ViewportInfo'; zoom: number }
+  | { type: 'Chat'; text: string } // Lines starting with '/' are moderator commands
+  | { type: 'Report'; target: PlayerId; reason: ReportReason } // Report another player's behavior
+  | { type: 'ListRooms'; filter: RoomFilter; page: number } // Room browser page (0 = first)
+  | { type: 'SpectateRoom'; roomId: string; playerName: string } // Watch a listed room; answered like a spectator join
+  | { type: 'RequestResync' }; // Local state is corrupt; ask for a full snapshot (rate limited by the server)
 
 // Server -> Client messages
 export type ServerMessage =
-  | { type: 'JoinAccepted'; playerId: PlayerId; sessionToken: Uint8Array; isSpectator: boolean }
+  | {
+      type: 'JoinAccepted';
+      playerId: PlayerId;
+      sessionToken: Uint8Array;
+      isSpectator: boolean;
+      encryptionKey: Uint8Array | null; // Server's ECDH public key when snapshots will be sealed
+      arenaCode: string; // Shareable code of the match's arena seed (same code = same well layout)
+      world: JoinWorld | null; // Set when the entities follow in WorldChunk messages (see WorldStream)
+      accountToken: Uint8Array; // Token of the player's account, sent with later joins (empty if none)
+    }
   | { type: 'JoinRejected'; reason: RejectionReason }
   | { type: 'Snapshot'; snapshot: GameSnapshot }
   | { type: 'Delta'; delta: DeltaUpdate }
   | { type: 'Event'; event: GameEvent }
   | { type: 'Pong'; clientTimestamp: number; serverTimestamp: number }
-  | { type: 'Kicked'; reason: string }
+  | { type: 'Kicked'; reason: LocalizedText }
   | { type: 'PhaseChange'; phase: MatchPhase; countdown: number }
-  | { type: 'SpectatorModeChanged'; isSpectator: boolean };
+  | { type: 'SpectatorModeChanged'; isSpectator: boolean }
+  | { type: 'Commentary'; text: string; tick: number }
+  | { type: 'QueueUpdate'; position: number; etaSecs: number }
+  | { type: 'Chat'; playerId: PlayerId; name: string; text: string }
+  | { type: 'Announcement'; text: LocalizedText }
+  | { type: 'CommandResult'; success: boolean; message: LocalizedText }
+  | { type: 'SpectateTargetChanged'; targetId: PlayerId | null }
+  | { type: 'SnapshotRate'; hz: number } // Negotiated or adapted snapshot rate
+  | { type: 'MatchPaused'; reason: LocalizedText; resumeCountdown: number } // Referee pause (countdown 0 = indefinite)
+  | { type: 'MatchResumed' }
+  | { type: 'Events'; events: GameEvent[] } // Several events from one tick (e.g. hit confirmations)
+  | { type: 'Resync'; resync: ResyncUpdate } // Full resync as the changes since our acked base
+  | { type: 'Sealed'; nonce: number; payload: Uint8Array } // Encrypted snapshot (see SnapshotCrypto)
+  | { type: 'WorldChunk'; chunk: WorldChunk } // Part of a streamed join snapshot
+  | { type: 'RegionHint'; hint: RegionHint } // Arena cells we're about to see (for preloading)
+  | { type: 'RoomList'; rooms: RoomListing[]; page: number; totalPages: number } // Answer to ListRooms
+  | { type: 'GalaxyMap'; map: GalaxyMap }; // On entering the galaxy and after each wormhole transit
+
+// Room browser filter; rooms must match every set field
+export interface RoomFilter {
+  mode?: string | null; // 'standard', 'slow_mode' or 'practice'
+  region?: string | null;
+  notFull?: boolean; // Only rooms with a free slot
+  friends?: PlayerId[]; // Only rooms with at least one of these players
+}
+
+// A room in the room browser
+export interface RoomListing {
+  roomId: string;
+  name: string;
+  mode: string;
+  region: string;
+  players: number;
+  maxPlayers: number; // Human slots; bots fill the rest of a match
+  inProgress: boolean; // Only waiting rooms take new players
+  friends: PlayerId[]; // Players from the filter's friends in the room
+}
+
+// Galaxy arenas and wormholes, as seen from the arena we're in
+export interface GalaxyMap {
+  current: string; // Room id of our arena
+  arenas: GalaxyArena[];
+  wormholes: GalaxyWormhole[];
+}
+
+export interface GalaxyArena {
+  roomId: string;
+  name: string;
+  mode: string;
+  players: number;
+  maxPlayers: number;
+}
+
+// One-way wormhole; flying within radius of its mouth in `from` takes us to `to`
+export interface GalaxyWormhole {
+  from: string;
+  to: string;
+  position: Vec2;
+  radius: number;
+}
+
+// What a player can be reported for (answered with a CommandResult)
+export type ReportReason = 'Cheating' | 'Harassment' | 'Griefing' | 'Spam' | 'Other';
+
+// Snapshot rate a client can ask for (the server may lower it on poor links)
+export type SnapshotRate = 'low' | 'normal' | 'high'; // 5Hz, 10Hz, 20Hz
+
+// Features announced in JoinRequest
+export interface ClientCapabilities {
+  maxSnapshotRate: SnapshotRate;
+  progressiveJoin?: boolean; // Can assemble the first snapshot from streamed chunks
+}
+
+// Accessibility options announced in JoinRequest
+export interface AccessibilitySettings {
+  orbitAssist: boolean; // Server steadies the player's orbit while thrust is idle
+}
+
+// Per-player telemetry a player allows; opted-out players only count toward aggregates
+export interface TelemetryConsent {
+  analytics: boolean; // Listed in per-player analytics (input stats endpoint, match history names)
+  replays: boolean; // Session captures, and the replays and ghosts built from them
+  behavior: boolean; // Per-player behavior analysis of the input stream
+}
 
 // Player input for one tick
 export interface PlayerInput {
@@ -52,6 +169,13 @@ export interface PlayerInput {
   boost: boolean;
   fire: boolean;
   fireReleased: boolean;
+  stateHash?: StateHash; // Predicted local state hash for desync detection
+}
+
+// Hash of the predicted local state after simulating a tick
+export interface StateHash {
+  tick: number;
+  hash: number; // u32
 }
 
 // Gravity well in the arena
@@ -60,6 +184,9 @@ export interface GravityWellSnapshot {
   position: Vec2;
   mass: number;
   coreRadius: number;
+  ownerColor: number | null; // Color index of the player who claimed it
+  captureProgress: number; // 0-1 progress of a capture underway
+  contested: boolean; // Several players fighting over the well
 }
 
 // Density grid size (must match server DENSITY_GRID_SIZE)
@@ -78,10 +205,12 @@ export interface GameSnapshot {
   arenaSafeRadius: number;
   arenaScale: number;
   gravityWells: GravityWellSnapshot[];
+  asteroids: AsteroidSnapshot[]; // Destructible asteroids in view
   totalPlayers: number;  // Total players in match (before AOI filtering)
   totalAlive: number;    // Total alive players (before AOI filtering)
   densityGrid: number[]; // 16x16 grid of player counts for minimap heatmap
   echoClientTime: number; // Echo of client's last input timestamp for RTT
+  interpDelayMs?: number; // Server-recommended interpolation delay (0 = none)
   aiStatus?: AIStatusSnapshot; // AI Manager status (if enabled)
 }
 
@@ -108,9 +237,19 @@ export interface PlayerSnapshot {
   deaths: number;
   spawnProtection: boolean;
   isBot: boolean;
+  /** Replayed run in a practice room (render translucently, never collides) */
+  isGhost: boolean;
   colorIndex: number;
   /** Tick when player spawned/respawned (for birth animation detection) */
   spawnTick: number;
+  /** Shot windup 0-1, null when not charging */
+  charge: number | null;
+  /** Heat gauge 0-1 (always 0 unless the server's ruleset fires on heat) */
+  heat: number;
+  /** Bot faction, colored by territory (0 = none) */
+  faction: number;
+  /** Active status effects: bit 0 = burn, bit 1 = slow, bit 2 = shield */
+  effects: number;
 }
 
 // Projectile state in snapshot
@@ -129,6 +268,14 @@ export interface DebrisSnapshot {
   size: number; // 0=Small, 1=Medium, 2=Large
 }
 
+// Destructible asteroid state in snapshot
+export interface AsteroidSnapshot {
+  id: number;
+  position: Vec2;
+  radius: number;
+  health: number; // Remaining health 0-1
+}
+
 // Delta update (incremental changes)
 export interface DeltaUpdate {
   tick: number;
@@ -137,6 +284,48 @@ export interface DeltaUpdate {
   projectileUpdates: ProjectileDelta[];
   removedProjectiles: number[];
   debris: DebrisSnapshot[]; // Full debris list (debris moves slowly)
+  removedPlayers: PlayerId[]; // Left our AOI or despawned since the base snapshot
+  asteroids: AsteroidSnapshot[]; // Full asteroid list in view
+}
+
+// Full resync relative to a snapshot we acknowledged. `snapshot` has every
+// top-level field but only the entities that changed; the rest come from the base.
+export interface ResyncUpdate {
+  baseTick: number;
+  snapshot: GameSnapshot;
+  removedPlayers: PlayerId[];
+  removedProjectiles: number[];
+  removedDebris: number[];
+}
+
+// Streamed join: the join snapshot without its entities (arena, wells, match
+// state), followed by `chunks` WorldChunk messages for the same tick
+export interface JoinWorld {
+  base: GameSnapshot;
+  chunks: number;
+}
+
+// Entities of a streamed join snapshot, nearest first
+export interface WorldChunk {
+  tick: number;
+  index: number;
+  players: PlayerSnapshot[];
+  projectiles: ProjectileSnapshot[];
+  debris: DebrisSnapshot[];
+}
+
+// Cells of the server's AOI grid our view is heading into, soonest first.
+// Cell (x, y) spans [x * cellSize, (x + 1) * cellSize) on each axis.
+export interface RegionHint {
+  tick: number;
+  cellSize: number;
+  cells: RegionCell[];
+}
+
+export interface RegionCell {
+  x: number;
+  y: number;
+  debris: number; // Debris in the cell (other entities aren't revealed)
 }
 
 // Delta for a single player
@@ -148,6 +337,10 @@ export interface PlayerDelta {
   mass?: number;
   alive?: boolean;
   kills?: number;
+  charge?: number | null;
+  heat?: number;
+  faction?: number;
+  effects?: number;
 }
 
 // Delta for a projectile
@@ -159,17 +352,11 @@ export interface ProjectileDelta {
 
 // Game events
 export type GameEvent =
-  | {
-      type: 'PlayerKilled';
-      killerId: PlayerId;
-      victimId: PlayerId;
-      killerName: string;
-      victimName: string;
-    }
+  | ({ type: 'KillFeed' } & KillFeedEntry)
   | { type: 'PlayerJoined'; playerId: PlayerId; name: string }
   | { type: 'PlayerLeft'; playerId: PlayerId; name: string }
   | { type: 'MatchStarted' }
-  | { type: 'MatchEnded'; winnerId: PlayerId | null; winnerName: string | null }
+  | { type: 'MatchEnded'; winnerId: PlayerId | null; winnerName: string | null; awards: Award[] }
   | { type: 'ZoneCollapse'; phase: number; newSafeRadius: number }
   | {
       type: 'PlayerDeflection';
@@ -193,8 +380,64 @@ export type GameEvent =
       type: 'GravityWellDestroyed';
       wellId: number;
       position: { x: number; y: number };
+    }
+  | {
+      type: 'BotChatter';
+      playerId: PlayerId;
+      kind: ChatterKind;
+      text: string;
+      position: { x: number; y: number };
+    }
+  | { type: 'ModifierStarted'; name: string; description: LocalizedText; duration: number } // Global modifier (e.g. solar flare)
+  | { type: 'ModifierEnded'; name: string }
+  | { type: 'WellCaptured'; wellId: number; ownerId: PlayerId; ownerName: string; colorIndex: number }
+  | { type: 'WellContested'; wellId: number }
+  | {
+      // Projectile hit; only the shooter and the victim receive it
+      type: 'Hit';
+      shooterId: PlayerId;
+      victimId: PlayerId;
+      projectileId: number;
+      amount: number; // Projectile mass transferred to the victim
+      position: { x: number; y: number };
+    }
+  | {
+      // An asteroid in view broke into debris
+      type: 'AsteroidDestroyed';
+      asteroidId: number;
+      position: { x: number; y: number };
+      fragments: number;
     };
 
+// End-of-match award kinds (MVP first in the list)
+export type AwardKind = 'Mvp' | 'MostKills' | 'Survivor' | 'DebrisHoarder' | 'Pacifist' | 'Comeback';
+
+// One end-of-match award; value is kills, seconds alive, debris, mass or a ratio by kind
+export interface Award {
+  kind: AwardKind;
+  playerId: PlayerId;
+  name: string;
+  value: number;
+}
+
+// What killed a player (pick the kill feed icon by it)
+export type KillCause = 'Crush' | 'Projectile' | 'Well' | 'Boundary' | 'Disconnect';
+
+// One kill feed line; killer is null for wells, the boundary and disconnects
+export interface KillFeedEntry {
+  killerId: PlayerId | null;
+  victimId: PlayerId;
+  killerName: string | null;
+  victimName: string;
+  cause: KillCause;
+  text: LocalizedText;
+  // Streak notes: multi-kills, streaks, shutdowns
+  annotations: LocalizedText[];
+}
+
+// Context of a bot chatter message
+export type ChatterKind = 'Taunt' | 'Distress';
+
 // Create a default player input
 export function createPlayerInput(sequence: number, tick: number): PlayerInput {
   return {
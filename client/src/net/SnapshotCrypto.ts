@@ -0,0 +1,67 @@
+// Snapshot encryption negotiated at join (see snapshot_crypto.rs on the server)
+//
+// We send an ephemeral ECDH P-256 public key in JoinRequest; the server answers
+// with its own in JoinAccepted. Both sides derive an AES-256-GCM key from the
+// shared secret with HKDF-SHA256, and the server then sends our snapshots as
+// Sealed messages carrying a counter nonce.
+
+// Must match the server's HKDF salt and info
+const HKDF_SALT = new TextEncoder().encode('orbit-royale/snapshot');
+const HKDF_INFO = new TextEncoder().encode('snapshot-key-v1');
+
+export class SnapshotCrypto {
+  private key: Promise<CryptoKey> | null = null;
+
+  private constructor(
+    private readonly keyPair: CryptoKeyPair,
+    // Uncompressed P-256 point (65 bytes), sent in JoinRequest
+    readonly publicKey: Uint8Array
+  ) {}
+
+  // Generate a key pair (null if WebCrypto is unavailable, e.g. on insecure origins)
+  static async create(): Promise<SnapshotCrypto | null> {
+    if (typeof crypto === 'undefined' || !crypto.subtle) {
+      return null;
+    }
+    try {
+      const keyPair = await crypto.subtle.generateKey({ name: 'ECDH', namedCurve: 'P-256' }, false, ['deriveBits']);
+      const publicKey = new Uint8Array(await crypto.subtle.exportKey('raw', keyPair.publicKey));
+      return new SnapshotCrypto(keyPair, publicKey);
+    } catch {
+      return null;
+    }
+  }
+
+  // Derive the snapshot key from the server's public key in JoinAccepted
+  deriveKey(serverPublicKey: Uint8Array): void {
+    this.key = (async () => {
+      const serverKey = await crypto.subtle.importKey(
+        'raw',
+        serverPublicKey,
+        { name: 'ECDH', namedCurve: 'P-256' },
+        false,
+        []
+      );
+      const secret = await crypto.subtle.deriveBits({ name: 'ECDH', public: serverKey }, this.keyPair.privateKey, 256);
+      const hkdfKey = await crypto.subtle.importKey('raw', secret, 'HKDF', false, ['deriveKey']);
+      return crypto.subtle.deriveKey(
+        { name: 'HKDF', hash: 'SHA-256', salt: HKDF_SALT, info: HKDF_INFO },
+        hkdfKey,
+        { name: 'AES-GCM', length: 256 },
+        false,
+        ['decrypt']
+      );
+    })();
+  }
+
+  // Decrypt a Sealed payload into an encoded server message (rejects if tampered or no key)
+  async open(nonce: number, payload: Uint8Array): Promise<ArrayBuffer> {
+    if (!this.key) {
+      throw new Error('Snapshot key not negotiated');
+    }
+    // 96-bit nonce: four zero bytes, then the counter big-endian
+    const iv = new Uint8Array(12);
+    new DataView(iv.buffer).setBigUint64(4, BigInt(nonce), false);
+    return crypto.subtle.decrypt({ name: 'AES-GCM', iv }, await this.key, payload);
+  }
+}
@@ -1,8 +1,8 @@
 import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest';
 import { Vec2 } from '@/utils/Vec2';
-import { StateSync } from './StateSync';
+import { StateSync, hashPredictedState } from './StateSync';
 import { NETWORK, PHYSICS } from '@/utils/Constants';
-import type { GameSnapshot, PlayerSnapshot, DeltaUpdate, PlayerInput, MatchPhase } from './Protocol';
+import type { GameSnapshot, PlayerSnapshot, DeltaUpdate, ResyncUpdate, PlayerInput, MatchPhase } from './Protocol';
 
 const { ADAPTIVE_INTERPOLATION } = NETWORK;
 
@@ -20,9 +20,14 @@ function createMockPlayerSnapshot(overrides: Partial<PlayerSnapshot> = {}): Play
     deaths: overrides.deaths ?? 0,
     spawnProtection: overrides.spawnProtection ?? false,
     isBot: overrides.isBot ?? false,
+    isGhost: overrides.isGhost ?? false,
     colorIndex: overrides.colorIndex ?? 0,
     // Default spawnTick to old tick (spawned long ago) unless overridden
     spawnTick: overrides.spawnTick ?? 0,
+    charge: overrides.charge ?? null,
+    heat: overrides.heat ?? 0,
+    faction: overrides.faction ?? 0,
+    effects: overrides.effects ?? 0,
   };
 }
 
@@ -61,6 +66,7 @@ function createSnapshot(tick: number, overrides: Partial<GameSnapshot> = {}): Ga
     arenaSafeRadius: 600,
     arenaScale: 1.0,
     gravityWells: [],
+    asteroids: [],
     totalPlayers: 0,
     totalAlive: 0,
     densityGrid: [],
@@ -177,6 +183,7 @@ describe('StateSync', () => {
         projectileUpdates: [],
         removedProjectiles: [],
         debris: [],
+        removedPlayers: [],
       };
 
       mockPerformanceNow = 1500;
@@ -199,6 +206,7 @@ describe('StateSync', () => {
         ],
         removedProjectiles: [],
         debris: [],
+        removedPlayers: [],
       };
 
       mockPerformanceNow = 1500;
@@ -207,6 +215,21 @@ describe('StateSync', () => {
       expect(stateSync.getCurrentTick()).toBe(15);
     });
 
+    it('should report a delta whose base is not buffered', () => {
+      const delta: DeltaUpdate = {
+        tick: 15,
+        baseTick: 3,
+        playerUpdates: [],
+        projectileUpdates: [],
+        removedProjectiles: [],
+        debris: [],
+        removedPlayers: [],
+      };
+
+      expect(stateSync.applyDelta(delta)).toBe(false);
+      expect(stateSync.getCurrentTick()).toBe(10);
+    });
+
     it('should remove projectiles', () => {
       const delta: DeltaUpdate = {
         tick: 15,
@@ -215,6 +238,7 @@ describe('StateSync', () => {
         projectileUpdates: [],
         removedProjectiles: [1],
         debris: [],
+        removedPlayers: [],
       };
 
       mockPerformanceNow = 1500;
@@ -223,6 +247,79 @@ describe('StateSync', () => {
       expect(stateSync.getCurrentTick()).toBe(15);
     });
 
+    it('should apply charge windup from deltas', () => {
+      const chargeDelta = (tick: number, charge: number | null): DeltaUpdate => ({
+        tick,
+        baseTick: 10,
+        playerUpdates: [{ id: 'player-1', charge }],
+        projectileUpdates: [],
+        removedProjectiles: [],
+        debris: [],
+        removedPlayers: [],
+      });
+
+      mockPerformanceNow = 1100;
+      stateSync.applyDelta(chargeDelta(15, 0.5));
+      mockPerformanceNow = 1100 + stateSync.interpolationDelay + 100;
+      expect(stateSync.getInterpolatedState()?.players.get('player-1')?.charge).toBe(0.5);
+
+      // Released shot
+      stateSync.applyDelta(chargeDelta(18, null));
+      mockPerformanceNow += stateSync.interpolationDelay + 100;
+      expect(stateSync.getInterpolatedState()?.players.get('player-1')?.charge).toBeNull();
+    });
+
+    it('should fade out removed players instead of dropping them', () => {
+      const delta: DeltaUpdate = {
+        tick: 15,
+        baseTick: 10,
+        playerUpdates: [],
+        projectileUpdates: [],
+        removedProjectiles: [],
+        debris: [],
+        removedPlayers: ['player-1'],
+      };
+
+      mockPerformanceNow = 1100;
+      stateSync.applyDelta(delta);
+
+      // Render time reaches the removal after the interpolation delay
+      mockPerformanceNow = 1100 + stateSync.interpolationDelay + 100;
+      const state = stateSync.getInterpolatedState();
+      expect(state?.players.has('player-1')).toBe(false);
+      expect(state?.fadingPlayers).toHaveLength(1);
+      expect(state?.fadingPlayers[0].player.name).toBe('TestPlayer');
+      expect(state?.fadingPlayers[0].alpha).toBeGreaterThan(0);
+      expect(state?.fadingPlayers[0].alpha).toBeLessThan(1);
+
+      // Repeated notice (same pinned base) must not restart the fade
+      stateSync.applyDelta({ ...delta, tick: 18 });
+      mockPerformanceNow += stateSync.interpolationDelay + 200;
+      expect(stateSync.getInterpolatedState()?.fadingPlayers).toHaveLength(0);
+    });
+
+    it('should apply deltas against an acked base that left the interpolation buffer', () => {
+      const delta = (tick: number): DeltaUpdate => ({
+        tick,
+        baseTick: 10,
+        playerUpdates: [{ id: 'player-1', position: new Vec2(100 + tick, 100) }],
+        projectileUpdates: [],
+        removedProjectiles: [],
+        debris: [],
+        removedPlayers: [],
+      });
+
+      // Rebuilt snapshots push the base out of the buffer...
+      for (let tick = 11; tick < 11 + NETWORK.SNAPSHOT_BUFFER_SIZE; tick++) {
+        mockPerformanceNow = 1000 + tick * 100;
+        stateSync.applyDelta(delta(tick));
+      }
+      // ...but it is still retained as a baseline
+      mockPerformanceNow = 9000;
+      stateSync.applyDelta(delta(60));
+      expect(stateSync.getCurrentTick()).toBe(60);
+    });
+
     it('should ignore delta with missing base snapshot', () => {
       const delta: DeltaUpdate = {
         tick: 100,
@@ -231,6 +328,7 @@ describe('StateSync', () => {
         projectileUpdates: [],
         removedProjectiles: [],
         debris: [],
+        removedPlayers: [],
       };
 
       const tickBefore = stateSync.getCurrentTick();
@@ -240,6 +338,61 @@ describe('StateSync', () => {
     });
   });
 
+  describe('applyResync', () => {
+    beforeEach(() => {
+      stateSync.applySnapshot(
+        createMockSnapshot(10, {
+          players: [
+            createMockPlayerSnapshot({ id: 'player-1', position: new Vec2(100, 100) }),
+            createMockPlayerSnapshot({ id: 'player-2', position: new Vec2(300, 100) }),
+          ],
+        })
+      );
+    });
+
+    it('should rebuild the snapshot from the acked base and use it as the next base', () => {
+      const resync: ResyncUpdate = {
+        baseTick: 10,
+        snapshot: createMockSnapshot(40, {
+          players: [createMockPlayerSnapshot({ id: 'player-1', position: new Vec2(150, 100) })],
+        }),
+        removedPlayers: [],
+        removedProjectiles: [],
+        removedDebris: [],
+      };
+
+      mockPerformanceNow = 1500;
+      expect(stateSync.applyResync(resync)).toBe(true);
+      expect(stateSync.getCurrentTick()).toBe(40);
+
+      // Deltas against the resync tick apply
+      mockPerformanceNow = 1600;
+      stateSync.applyDelta({
+        tick: 45,
+        baseTick: 40,
+        playerUpdates: [{ id: 'player-2', position: new Vec2(310, 100) }],
+        projectileUpdates: [],
+        removedProjectiles: [],
+        debris: [],
+        removedPlayers: [],
+      });
+      expect(stateSync.getCurrentTick()).toBe(45);
+    });
+
+    it('should report a missing base', () => {
+      const resync: ResyncUpdate = {
+        baseTick: 99,
+        snapshot: createMockSnapshot(40),
+        removedPlayers: [],
+        removedProjectiles: [],
+        removedDebris: [],
+      };
+
+      expect(stateSync.applyResync(resync)).toBe(false);
+      expect(stateSync.getCurrentTick()).toBe(10);
+    });
+  });
+
   describe('recordInput', () => {
     it('should record player input', () => {
       const input: PlayerInput = {
@@ -452,14 +605,14 @@ describe('StateSync', () => {
       mockPerformanceNow = 1000;
       stateSync.applySnapshot(createMockSnapshot(1, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
       mockPerformanceNow = 1100;
       stateSync.applySnapshot(createMockSnapshot(2, {
         gravityWells: [
-          { id: 1, position: new Vec2(100, 100), mass: 6000, coreRadius: 25 },
+          { id: 1, position: new Vec2(100, 100), mass: 6000, coreRadius: 25, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -473,8 +626,8 @@ describe('StateSync', () => {
       mockPerformanceNow = 1000;
       stateSync.applySnapshot(createMockSnapshot(1, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
-          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
+          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -483,8 +636,8 @@ describe('StateSync', () => {
       mockPerformanceNow = 1100;
       stateSync.applySnapshot(createMockSnapshot(2, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
-          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
+          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -603,7 +756,7 @@ describe('StateSync', () => {
     it('should mark well as destroyed', () => {
       stateSync.applySnapshot(createMockSnapshot(1, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -612,7 +765,7 @@ describe('StateSync', () => {
       mockPerformanceNow = 1100;
       stateSync.applySnapshot(createMockSnapshot(2, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -623,8 +776,8 @@ describe('StateSync', () => {
     it('should clean up destroyed tracking when server confirms removal', () => {
       stateSync.applySnapshot(createMockSnapshot(1, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
-          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
+          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -634,7 +787,7 @@ describe('StateSync', () => {
       mockPerformanceNow = 1100;
       stateSync.applySnapshot(createMockSnapshot(2, {
         gravityWells: [
-          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15 },
+          { id: 2, position: new Vec2(100, 100), mass: 3000, coreRadius: 15, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -670,7 +823,7 @@ describe('StateSync', () => {
       stateSync.setLocalPlayerId('player-1');
       stateSync.applySnapshot(createMockSnapshot(10, {
         players: [createMockPlayerSnapshot({ id: 'player-1' })],
-        gravityWells: [{ id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 }],
+        gravityWells: [{ id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false }],
       }));
       stateSync.markWellDestroyed(1);
       stateSync.recordInput({
@@ -1312,7 +1465,7 @@ describe('StateSync', () => {
       mockPerformanceNow = 1000;
       stateSync.applySnapshot(createMockSnapshot(1, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -1327,13 +1480,14 @@ describe('StateSync', () => {
       mockPerformanceNow = 1000;
       stateSync.applySnapshot(createMockSnapshot(1, {
         gravityWells: [],
+        asteroids: [],
       }));
 
       // Second snapshot with well
       mockPerformanceNow = 2000;
       stateSync.applySnapshot(createMockSnapshot(2, {
         gravityWells: [
-          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20 },
+          { id: 1, position: new Vec2(0, 0), mass: 5000, coreRadius: 20, ownerColor: null, captureProgress: 0, contested: false },
         ],
       }));
 
@@ -1485,6 +1639,18 @@ describe('StateSync adaptive interpolation', () => {
       expect(stateSync.interpolationDelay).toBe(ADAPTIVE_INTERPOLATION.MIN_DELAY_MS);
     });
 
+    it('never renders closer than the server recommendation', () => {
+      const interval = 33;
+      stateSync.applySnapshot(createSnapshot(1, { interpDelayMs: 260 }));
+      for (let i = 2; i <= 10; i++) {
+        mockNow += interval;
+        stateSync.applySnapshot(createSnapshot(i, { interpDelayMs: 260 }));
+      }
+
+      // Local target would clamp to MIN_DELAY_MS; the jitter-aware hint wins
+      expect(stateSync.interpolationDelay).toBe(260);
+    });
+
     it('adapts delay for 15Hz updates (spectator rate)', () => {
       // Simulate 15Hz snapshots (~66ms interval)
       const interval = 66;
@@ -1734,3 +1900,18 @@ describe('StateSync adaptive interpolation', () => {
     });
   });
 });
+
+describe('hashPredictedState', () => {
+  it('matches the server reference value', () => {
+    // Same value as the server's state_hash(Vec2::ZERO, Vec2::ZERO)
+    expect(hashPredictedState(new Vec2(0, 0), new Vec2(0, 0))).toBe(0x69691905);
+  });
+
+  it('floors to whole units', () => {
+    const a = hashPredictedState(new Vec2(10.2, -3.7), new Vec2(1.9, 0));
+    const b = hashPredictedState(new Vec2(10.9, -3.1), new Vec2(1.1, 0.5));
+    const c = hashPredictedState(new Vec2(11.0, -3.1), new Vec2(1.1, 0.5));
+    expect(a).toBe(b);
+    expect(a).not.toBe(c);
+  });
+});
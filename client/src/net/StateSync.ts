@@ -7,12 +7,29 @@ const { ADAPTIVE_INTERPOLATION } = NETWORK;
 import type {
   GameSnapshot,
   DeltaUpdate,
+  ResyncUpdate,
   PlayerSnapshot,
   PlayerInput,
   PlayerId,
   MatchPhase,
+  StateHash,
+  AsteroidSnapshot,
 } from './Protocol';
 
+// FNV-1a over position/velocity floored to whole units (little-endian i32s).
+// Must match the server's game::desync::state_hash.
+export function hashPredictedState(position: Vec2, velocity: Vec2): number {
+  let hash = 0x811c9dc5;
+  for (const value of [position.x, position.y, velocity.x, velocity.y]) {
+    const int = Math.floor(value) | 0;
+    for (let shift = 0; shift < 32; shift += 8) {
+      hash ^= (int >>> shift) & 0xff;
+      hash = Math.imul(hash, 0x01000193);
+    }
+  }
+  return hash >>> 0;
+}
+
 // Gravity well for rendering
 export interface InterpolatedGravityWell {
   id: number;
@@ -20,6 +37,9 @@ export interface InterpolatedGravityWell {
   mass: number;
   coreRadius: number;
   bornTime: number; // Timestamp when well first appeared (for birth animation)
+  ownerColor: number | null; // Claiming player's color index
+  captureProgress: number;
+  contested: boolean;
 }
 
 // Interpolated state for rendering
@@ -35,9 +55,11 @@ export interface InterpolatedState {
   arenaSafeRadius: number;
   arenaScale: number;
   gravityWells: InterpolatedGravityWell[];
+  asteroids: AsteroidSnapshot[]; // Static, taken from the latest snapshot
   totalPlayers: number;  // Total players before AOI filtering
   totalAlive: number;    // Total alive before AOI filtering
   densityGrid: number[]; // 16x16 grid of player counts for minimap heatmap
+  fadingPlayers: FadingPlayer[]; // Recently removed from our AOI (render only, not counted)
 }
 
 export interface InterpolatedPlayer {
@@ -52,8 +74,19 @@ export interface InterpolatedPlayer {
   deaths: number;
   spawnProtection: boolean;
   isBot: boolean;
+  isGhost: boolean;
   colorIndex: number;
   bornTime: number; // Timestamp when player spawned (0 = skip animation, >0 = show birth effect)
+  charge: number | null; // Shot windup 0-1, null when not charging
+  heat: number; // Heat gauge 0-1
+  faction: number; // Bot faction (0 = none)
+  effects: number; // Status effect bits (burn, slow, shield)
+}
+
+// Player the server told us left our AOI, kept briefly so it fades out instead of popping
+export interface FadingPlayer {
+  player: InterpolatedPlayer;
+  alpha: number; // 1 = just removed, 0 = gone
 }
 
 export interface InterpolatedProjectile {
@@ -83,6 +116,13 @@ export class StateSync {
   private snapshots: SnapshotEntry[] = [];
   private readonly maxSnapshots = NETWORK.SNAPSHOT_BUFFER_SIZE;
 
+  // Recent baselines (full snapshots and resyncs), kept after they leave the
+  // interpolation buffer: deltas reference the last one we acked, which can lag
+  // behind when a newer full snapshot is lost or its ack is in flight
+  private baselines: GameSnapshot[] = [];
+  // Must cover the server's MAX_RETAINED_BASELINES
+  private static readonly MAX_BASELINES = 4;
+
   // Current authoritative state
   private currentTick: number = 0;
 
@@ -91,12 +131,16 @@ export class StateSync {
   private pendingInputs: PlayerInput[] = [];
   private predictedPosition: Vec2 = new Vec2();
   private predictedVelocity: Vec2 = new Vec2();
+  // Tick of the latest re-simulated prediction, reported once for desync detection
+  private unreportedPredictionTick: number | null = null;
 
   // Adaptive interpolation delay based on snapshot arrival rate
   // Spectators at reduced rate (15Hz) need more buffer than players (30Hz)
   private adaptiveDelay: number = NETWORK.INTERPOLATION_DELAY_MS;
   private lastSnapshotTime: number = 0;
   private snapshotIntervalAvg: number = PHYSICS.DT * 1000;  // Start assuming tick rate
+  // Server-recommended delay from the latest full snapshot (0 = none)
+  private serverDelayHint: number = 0;
 
   // Destroyed gravity wells (filter from interpolated state until server confirms removal)
   private destroyedWellIds: Set<number> = new Set();
@@ -130,6 +174,11 @@ export class StateSync {
   // At 30 TPS, 15 ticks = 0.5 seconds
   private static readonly BIRTH_ANIMATION_TICKS = 15;
 
+  // Players removed via delta notices, faded out once render time reaches the removal
+  // removedAt is the performance.now() arrival time of the first removal notice
+  private fadingPlayers: Map<PlayerId, { player: PlayerSnapshot; name: string; removedAt: number }> = new Map();
+  private static readonly PLAYER_FADE_MS = 300;
+
   // Track local player's last known state for respawn detection
   private localPlayerLastAlive: boolean = false;
   private localPlayerLastSpawnTick: number = 0;
@@ -161,10 +210,18 @@ export class StateSync {
     this.wellBornTimes.delete(wellId);
   }
 
-  // Apply a full snapshot from server
-  applySnapshot(snapshot: GameSnapshot): void {
+  // Apply a snapshot. Full snapshots (and resyncs) are baselines the server may
+  // send deltas against once we ack them; snapshots rebuilt from deltas are not.
+  applySnapshot(snapshot: GameSnapshot, isBaseline: boolean = true): void {
     const now = performance.now();
 
+    if (isBaseline) {
+      this.baselines.push(snapshot);
+      while (this.baselines.length > StateSync.MAX_BASELINES) {
+        this.baselines.shift();
+      }
+    }
+
     // Detect local player respawn - if respawned, reset interpolation state
     // This prevents other players from appearing delayed after respawn
     if (this.localPlayerId) {
@@ -195,11 +252,19 @@ export class StateSync {
     // Cache player names immediately when snapshot is received
     // This ensures names are available even if the snapshot is evicted before interpolation
     for (const player of snapshot.players) {
+      // Back in our AOI before the fade finished
+      this.fadingPlayers.delete(player.id);
+
       if (player.name !== undefined && player.name !== null) {
         this.playerNameCache.set(player.id, player.name);
       }
     }
 
+    // Server recommendation (based on our measured jitter) rides on full snapshots
+    if (snapshot.interpDelayMs !== undefined) {
+      this.serverDelayHint = snapshot.interpDelayMs;
+    }
+
     // Track snapshot arrival rate for adaptive interpolation
     if (this.lastSnapshotTime > 0) {
       const interval = now - this.lastSnapshotTime;
@@ -213,10 +278,12 @@ export class StateSync {
         // Calculate adaptive delay: buffer enough snapshots for smooth playback
         // delay = interval * bufferSnapshots, clamped to min/max
         const targetDelay = this.snapshotIntervalAvg * ADAPTIVE_INTERPOLATION.BUFFER_SNAPSHOTS;
-        this.adaptiveDelay = Math.max(
+        const localDelay = Math.max(
           ADAPTIVE_INTERPOLATION.MIN_DELAY_MS,
           Math.min(ADAPTIVE_INTERPOLATION.MAX_DELAY_MS, targetDelay)
         );
+        // The server accounts for our measured jitter; never render closer than it recommends
+        this.adaptiveDelay = Math.max(localDelay, this.serverDelayHint);
       }
     }
     this.lastSnapshotTime = now;
@@ -275,33 +342,34 @@ export class StateSync {
     }
   }
 
-  // Apply a delta update
-  applyDelta(delta: DeltaUpdate): void {
+  // Apply a delta update. Returns false if the base is no longer buffered.
+  applyDelta(delta: DeltaUpdate): boolean {
     // Find base snapshot
-    const baseEntry = this.snapshots.find((s) => s.tick === delta.baseTick);
-    if (!baseEntry) {
-      // Missing base snapshot, request full snapshot
-      return;
+    const base = this.findBase(delta.baseTick);
+    if (!base) {
+      return false;
     }
 
     // Create new snapshot from delta
     // Note: debris comes from delta (full list), not from base
     const newSnapshot: GameSnapshot = {
       tick: delta.tick,
-      matchPhase: baseEntry.snapshot.matchPhase,
-      matchTime: baseEntry.snapshot.matchTime,
-      countdown: baseEntry.snapshot.countdown,
-      players: [...baseEntry.snapshot.players],
-      projectiles: [...baseEntry.snapshot.projectiles],
+      matchPhase: base.matchPhase,
+      matchTime: base.matchTime,
+      countdown: base.countdown,
+      players: [...base.players],
+      projectiles: [...base.projectiles],
       debris: delta.debris, // Use debris from delta (full list)
-      arenaCollapsePhase: baseEntry.snapshot.arenaCollapsePhase,
-      arenaSafeRadius: baseEntry.snapshot.arenaSafeRadius,
-      arenaScale: baseEntry.snapshot.arenaScale,
-      gravityWells: baseEntry.snapshot.gravityWells,
-      totalPlayers: baseEntry.snapshot.totalPlayers,
-      totalAlive: baseEntry.snapshot.totalAlive,
-      densityGrid: baseEntry.snapshot.densityGrid,
-      echoClientTime: baseEntry.snapshot.echoClientTime,
+      arenaCollapsePhase: base.arenaCollapsePhase,
+      arenaSafeRadius: base.arenaSafeRadius,
+      arenaScale: base.arenaScale,
+      gravityWells: base.gravityWells,
+      asteroids: delta.asteroids, // Full list, like debris
+      totalPlayers: base.totalPlayers,
+      totalAlive: base.totalAlive,
+      densityGrid: base.densityGrid,
+      echoClientTime: base.echoClientTime,
+      interpDelayMs: base.interpDelayMs,
     };
 
     // Apply player deltas
@@ -315,6 +383,10 @@ export class StateSync {
         if (playerDelta.mass !== undefined) player.mass = playerDelta.mass;
         if (playerDelta.alive !== undefined) player.alive = playerDelta.alive;
         if (playerDelta.kills !== undefined) player.kills = playerDelta.kills;
+        if (playerDelta.charge !== undefined) player.charge = playerDelta.charge;
+        if (playerDelta.heat !== undefined) player.heat = playerDelta.heat;
+        if (playerDelta.faction !== undefined) player.faction = playerDelta.faction;
+        if (playerDelta.effects !== undefined) player.effects = playerDelta.effects;
         newSnapshot.players[playerIndex] = player;
       }
     }
@@ -346,7 +418,55 @@ export class StateSync {
       (p) => !delta.removedProjectiles.includes(p.id)
     );
 
-    this.applySnapshot(newSnapshot);
+    // Remove players that left our AOI, keeping their last state to fade out.
+    // Notices repeat until the base changes (deltas share the acked base),
+    // so only players still present in our latest snapshot start a fade.
+    if (delta.removedPlayers.length > 0) {
+      const removedAt = performance.now();
+      const removed = new Set(delta.removedPlayers);
+      const latest = this.snapshots[this.snapshots.length - 1].snapshot;
+      for (const p of latest.players) {
+        if (removed.has(p.id) && p.alive && p.id !== this.localPlayerId) {
+          const name = p.name ?? this.playerNameCache.get(p.id) ?? '';
+          this.fadingPlayers.set(p.id, { player: p, name, removedAt });
+        }
+      }
+      newSnapshot.players = newSnapshot.players.filter((p) => !removed.has(p.id));
+    }
+
+    this.applySnapshot(newSnapshot, false);
+    return true;
+  }
+
+  // Apply a resync (changes since an acked base). Returns false if the base is
+  // no longer buffered; the server then sends a full snapshot at the next resync.
+  applyResync(resync: ResyncUpdate): boolean {
+    const base = this.findBase(resync.baseTick);
+    if (!base) {
+      return false;
+    }
+
+    // Entities not resent are unchanged since the base, unless removed
+    const merge = <T extends { id: K }, K>(kept: T[], changed: T[], removed: K[]): T[] => {
+      const skip = new Set<K>([...removed, ...changed.map((e) => e.id)]);
+      return [...kept.filter((e) => !skip.has(e.id)), ...changed];
+    };
+
+    this.applySnapshot({
+      ...resync.snapshot,
+      players: merge(base.players, resync.snapshot.players, resync.removedPlayers),
+      projectiles: merge(base.projectiles, resync.snapshot.projectiles, resync.removedProjectiles),
+      debris: merge(base.debris, resync.snapshot.debris, resync.removedDebris),
+    });
+    return true;
+  }
+
+  // Snapshot at a delta/resync base tick, from the buffer or the retained baselines
+  private findBase(tick: number): GameSnapshot | undefined {
+    return (
+      this.snapshots.find((s) => s.tick === tick)?.snapshot ??
+      this.baselines.find((s) => s.tick === tick)
+    );
   }
 
   // Record an input for client prediction
@@ -361,6 +481,49 @@ export class StateSync {
 
   // Get interpolated state for rendering
   getInterpolatedState(): InterpolatedState | null {
+    const state = this.buildInterpolatedState();
+    if (state) {
+      state.fadingPlayers = this.collectFadingPlayers(state);
+    }
+    return state;
+  }
+
+  // Players fading out at the current render time (expired entries are dropped)
+  private collectFadingPlayers(state: InterpolatedState): FadingPlayer[] {
+    if (this.fadingPlayers.size === 0) return [];
+
+    const renderTime = performance.now() - this.interpolationDelay;
+    const fading: FadingPlayer[] = [];
+    for (const [id, entry] of this.fadingPlayers) {
+      const elapsed = renderTime - entry.removedAt;
+      if (elapsed >= StateSync.PLAYER_FADE_MS) {
+        this.fadingPlayers.delete(id);
+        continue;
+      }
+      // Not reached the removal yet (still rendered from older snapshots)
+      if (elapsed < 0 || state.players.has(id)) continue;
+
+      // Keep drifting along the last known velocity while fading
+      const { player } = entry;
+      const seconds = elapsed / 1000;
+      fading.push({
+        player: {
+          ...player,
+          name: entry.name,
+          position: new Vec2(
+            player.position.x + player.velocity.x * seconds,
+            player.position.y + player.velocity.y * seconds
+          ),
+          velocity: player.velocity.clone(),
+          bornTime: 0,
+        },
+        alpha: 1 - elapsed / StateSync.PLAYER_FADE_MS,
+      });
+    }
+    return fading;
+  }
+
+  private buildInterpolatedState(): InterpolatedState | null {
     if (this.snapshots.length < 2) {
       // Not enough data for interpolation, return latest
       if (this.snapshots.length === 1) {
@@ -431,6 +594,16 @@ export class StateSync {
     for (const input of this.pendingInputs) {
       this.simulateInput(input, serverPlayer.mass);
     }
+    this.unreportedPredictionTick =
+      this.pendingInputs.length > 0 ? this.pendingInputs[this.pendingInputs.length - 1].tick : null;
+  }
+
+  // Hash of the latest prediction, returned once per reconciliation
+  takePredictedStateHash(): StateHash | null {
+    if (this.unreportedPredictionTick === null) return null;
+    const tick = this.unreportedPredictionTick;
+    this.unreportedPredictionTick = null;
+    return { tick, hash: hashPredictedState(this.predictedPosition, this.predictedVelocity) };
   }
 
   private simulateInput(input: PlayerInput, mass: number): void {
@@ -556,6 +729,9 @@ export class StateSync {
           mass: w.mass,
           coreRadius: w.coreRadius,
           bornTime: this.wellBornTimes.get(w.id) ?? 0,
+          ownerColor: w.ownerColor,
+          captureProgress: w.captureProgress,
+          contested: w.contested,
         });
       }
     }
@@ -572,9 +748,11 @@ export class StateSync {
       arenaSafeRadius: snapshot.arenaSafeRadius,
       arenaScale: snapshot.arenaScale,
       gravityWells,
+      asteroids: snapshot.asteroids,
       totalPlayers: snapshot.totalPlayers,
       totalAlive: snapshot.totalAlive,
       densityGrid: snapshot.densityGrid,
+      fadingPlayers: [],
     };
   }
 
@@ -672,8 +850,13 @@ export class StateSync {
             deaths: afterPlayer.deaths,
             spawnProtection: afterPlayer.spawnProtection,
             isBot: afterPlayer.isBot,
+            isGhost: afterPlayer.isGhost,
             colorIndex: afterPlayer.colorIndex,
             bornTime,
+            charge: afterPlayer.charge,
+            heat: afterPlayer.heat,
+            faction: afterPlayer.faction,
+            effects: afterPlayer.effects,
           });
         }
       } else {
@@ -754,6 +937,9 @@ export class StateSync {
           mass: beforeWell.mass + (afterWell.mass - beforeWell.mass) * t,
           coreRadius: beforeWell.coreRadius + (afterWell.coreRadius - beforeWell.coreRadius) * t,
           bornTime,
+          ownerColor: afterWell.ownerColor,
+          captureProgress: afterWell.captureProgress,
+          contested: afterWell.contested,
         });
       } else {
         gravityWells.push({
@@ -762,6 +948,9 @@ export class StateSync {
           mass: afterWell.mass,
           coreRadius: afterWell.coreRadius,
           bornTime,
+          ownerColor: afterWell.ownerColor,
+          captureProgress: afterWell.captureProgress,
+          contested: afterWell.contested,
         });
       }
     }
@@ -779,9 +968,11 @@ export class StateSync {
         before.arenaSafeRadius + (after.arenaSafeRadius - before.arenaSafeRadius) * t,
       arenaScale: before.arenaScale + (after.arenaScale - before.arenaScale) * t,
       gravityWells,
+      asteroids: after.asteroids,
       totalPlayers: after.totalPlayers,
       totalAlive: after.totalAlive,
       densityGrid: after.densityGrid,
+      fadingPlayers: [],
     };
   }
 
@@ -795,14 +986,17 @@ export class StateSync {
 
   reset(): void {
     this.snapshots = [];
+    this.baselines = [];
     this.currentTick = 0;
     this.pendingInputs = [];
     this.predictedPosition = new Vec2();
     this.predictedVelocity = new Vec2();
+    this.unreportedPredictionTick = null;
     this.destroyedWellIds.clear();
     this.wellBornTimes.clear();
     // Reset adaptive interpolation state
     this.adaptiveDelay = NETWORK.INTERPOLATION_DELAY_MS;
+    this.serverDelayHint = 0;
     this.lastSnapshotTime = 0;
     this.snapshotIntervalAvg = PHYSICS.DT * 1000;
     this.playerBornTimes.clear();
@@ -9,6 +9,7 @@ export interface TransportEvents {
   onStateChange: (state: ConnectionState) => void;
   onMessage: (message: ServerMessage) => void;
   onError: (error: Error) => void;
+  onDecodeError?: (error: unknown) => void; // A message arrived that could not be decoded
 }
 
 export class GameTransport {
@@ -199,6 +200,7 @@ export class GameTransport {
             this.handleMessage(message);
           } catch (err) {
             console.error('Failed to decode message:', err);
+            this.events.onDecodeError?.(err);
           }
         }
       }
@@ -226,6 +228,7 @@ export class GameTransport {
           this.handleMessage(message);
         } catch (err) {
           // Datagram decode errors are expected occasionally
+          this.events.onDecodeError?.(err);
         }
       }
     } catch (err) {
@@ -0,0 +1,75 @@
+import { describe, it, expect } from 'vitest';
+import { Vec2 } from '@/utils/Vec2';
+import { WorldStream } from './WorldStream';
+import type { GameSnapshot, PlayerSnapshot, WorldChunk } from './Protocol';
+
+function player(id: string): PlayerSnapshot {
+  return {
+    id,
+    name: id,
+    position: new Vec2(0, 0),
+    velocity: new Vec2(0, 0),
+    rotation: 0,
+    mass: 100,
+    alive: true,
+    kills: 0,
+    deaths: 0,
+    spawnProtection: false,
+    isBot: false,
+    isGhost: false,
+    colorIndex: 0,
+    spawnTick: 0,
+    charge: null,
+    heat: 0,
+    faction: 0,
+    effects: 0,
+  };
+}
+
+function base(tick: number): GameSnapshot {
+  return {
+    tick,
+    matchPhase: 'playing',
+    matchTime: 30,
+    countdown: 0,
+    players: [],
+    projectiles: [],
+    debris: [],
+    arenaCollapsePhase: 0,
+    arenaSafeRadius: 800,
+    arenaScale: 1,
+    gravityWells: [],
+    asteroids: [],
+    totalPlayers: 3,
+    totalAlive: 3,
+    densityGrid: [],
+    echoClientTime: 0,
+  };
+}
+
+function chunk(tick: number, index: number, ids: string[]): WorldChunk {
+  return { tick, index, players: ids.map(player), projectiles: [], debris: [] };
+}
+
+describe('WorldStream', () => {
+  it('assembles the snapshot once every chunk arrived', () => {
+    const stream = new WorldStream({ base: base(42), chunks: 2 });
+
+    expect(stream.add(chunk(42, 1, ['c']))).toBeNull();
+    const snapshot = stream.add(chunk(42, 0, ['a', 'b']));
+
+    expect(snapshot?.tick).toBe(42);
+    expect(snapshot?.arenaSafeRadius).toBe(800);
+    expect(snapshot?.players.map((p) => p.id).sort()).toEqual(['a', 'b', 'c']);
+  });
+
+  it('ignores chunks for another tick and repeats', () => {
+    const stream = new WorldStream({ base: base(42), chunks: 2 });
+
+    expect(stream.add(chunk(41, 1, ['x']))).toBeNull();
+    expect(stream.add(chunk(42, 0, ['a']))).toBeNull();
+    expect(stream.add(chunk(42, 0, ['a']))).toBeNull();
+    expect(stream.add(chunk(42, 5, ['y']))).toBeNull();
+    expect(stream.add(chunk(42, 1, ['b']))?.players.map((p) => p.id)).toEqual(['a', 'b']);
+  });
+});
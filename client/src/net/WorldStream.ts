@@ -0,0 +1,35 @@
+// Streamed join snapshot (see world_stream.rs on the server)
+//
+// A client that announces progressiveJoin gets the arena and match state in
+// JoinAccepted and the entities over the next few ticks in WorldChunk
+// messages, nearest first. The server holds back regular snapshots until the
+// last chunk is sent and then sends deltas against the assembled snapshot,
+// so it's applied and acknowledged like a full snapshot.
+
+import type { DebrisSnapshot, GameSnapshot, JoinWorld, PlayerSnapshot, ProjectileSnapshot, WorldChunk } from './Protocol';
+
+export class WorldStream {
+  private readonly received = new Set<number>();
+  private readonly players: PlayerSnapshot[] = [];
+  private readonly projectiles: ProjectileSnapshot[] = [];
+  private readonly debris: DebrisSnapshot[] = [];
+
+  constructor(private readonly world: JoinWorld) {}
+
+  // Add a chunk; returns the full snapshot once the last one arrives.
+  // Chunks for another tick or seen before are ignored.
+  add(chunk: WorldChunk): GameSnapshot | null {
+    if (chunk.tick !== this.world.base.tick || chunk.index >= this.world.chunks || this.received.has(chunk.index)) {
+      return null;
+    }
+    this.received.add(chunk.index);
+    this.players.push(...chunk.players);
+    this.projectiles.push(...chunk.projectiles);
+    this.debris.push(...chunk.debris);
+
+    if (this.received.size < this.world.chunks) {
+      return null;
+    }
+    return { ...this.world.base, players: this.players, projectiles: this.projectiles, debris: this.debris };
+  }
+}
@@ -5,6 +5,7 @@ import { World } from '@/core/World';
 import { Vec2 } from '@/utils/Vec2';
 import type { GamePhase } from '@/core/Game';
 import type { ConnectionState } from '@/net/Transport';
+import type { InterpolatedGravityWell } from '@/net/StateSync';
 
 interface InputState {
   aimDirection: Vec2;
@@ -127,6 +128,7 @@ export class RenderSystem {
 
   // Player birth effect duration (bornTime comes from StateSync)
   private readonly PLAYER_BIRTH_DURATION = 800; // ms
+  private readonly GHOST_ALPHA = 0.35;
 
   // Unified motion trails for all players (consolidates trail + thrust visuals)
   private playerTrails: Map<string, TrailPoint[]> = new Map();
@@ -611,9 +613,12 @@ export class RenderSystem {
     this.renderCollisionEffects(world);
     this.renderPlayerTrails(world);                                    // Trails first (back)
     this.renderBoostFlames(world, state.input?.isBoosting ?? false);   // Flames on top of trails
+    this.renderAsteroids(world);
     this.renderDebris(world);
     this.renderProjectiles(world);
+    this.renderFadingPlayers(world);                                   // Players leaving our AOI
     this.renderPlayerBodies(world);                                    // Bodies on top
+    this.renderHitNumbers(world);                                      // Hit feedback above bodies
 
     // Render aim indicator
     if (state.input && state.phase === 'playing') {
@@ -630,6 +635,12 @@ export class RenderSystem {
       this.renderChargeIndicator(state.input.chargeRatio);
     }
 
+    // Render heat gauge (only rulesets that fire on heat ever report any)
+    const heat = world.getLocalPlayer()?.heat ?? 0;
+    if (heat > 0 && state.phase === 'playing') {
+      this.renderHeatGauge(heat);
+    }
+
     // Render connection status
     this.renderConnectionStatus(state);
   }
@@ -672,6 +683,36 @@ export class RenderSystem {
     this.ctx.fill();
   }
 
+  private renderChargeWindup(position: Vec2, radius: number, charge: number): void {
+    const start = -Math.PI / 2;
+    this.ctx.strokeStyle = `rgba(255, ${Math.round(255 - charge * 155)}, 100, ${0.5 + charge * 0.4})`;
+    this.ctx.lineWidth = 3;
+    this.ctx.beginPath();
+    this.ctx.arc(position.x, position.y, radius + 10, start, start + Math.max(charge, 0.05) * Math.PI * 2);
+    this.ctx.stroke();
+  }
+
+  private renderHeatGauge(heat: number): void {
+    const canvas = this.ctx.canvas;
+    const barWidth = 150;
+    const barHeight = 6;
+    const x = (canvas.width - barWidth) / 2;
+    const y = canvas.height - 40;
+    const overheated = heat >= 1;
+
+    this.ctx.fillStyle = 'rgba(0, 0, 0, 0.5)';
+    this.ctx.fillRect(x - 2, y - 2, barWidth + 4, barHeight + 4);
+    this.ctx.fillStyle = 'rgba(100, 100, 100, 0.5)';
+    this.ctx.fillRect(x, y, barWidth, barHeight);
+    this.ctx.fillStyle = overheated ? '#ef4444' : `rgb(255, ${Math.round(200 - heat * 120)}, 60)`;
+    this.ctx.fillRect(x, y, barWidth * Math.min(heat, 1), barHeight);
+
+    this.ctx.fillStyle = '#ffffff';
+    this.ctx.font = '10px Inter, system-ui, sans-serif';
+    this.ctx.textAlign = 'center';
+    this.ctx.fillText(overheated ? 'OVERHEATED' : 'HEAT', Math.round(canvas.width / 2), Math.round(y + barHeight + 12));
+  }
+
   private renderChargeIndicator(chargeRatio: number): void {
     const canvas = this.ctx.canvas;
     const barWidth = 150;
@@ -729,6 +770,8 @@ export class RenderSystem {
           this.renderWellZones(well.position.x, well.position.y, well.coreRadius);
         }
 
+        this.renderWellOwnership(well, world);
+
         // Spectator follow indicator - subtle ring around followed well
         if (world.isSpectator && world.spectateWellId === well.id) {
           const indicatorRadius = well.coreRadius * 2.5;
@@ -761,6 +804,41 @@ export class RenderSystem {
     }
   }
 
+  // Capture ring edge (matches server WELL_CAPTURE_RING_MULTIPLIER default)
+  private readonly WELL_CAPTURE_RING = 8;
+
+  // Owner color ring, capture progress arc, and contested warning
+  private renderWellOwnership(well: InterpolatedGravityWell, world: World): void {
+    const { x, y } = well.position;
+    const ringRadius = well.coreRadius * this.WELL_CAPTURE_RING;
+
+    if (well.ownerColor !== null) {
+      this.ctx.strokeStyle = world.getPlayerColor(well.ownerColor);
+      this.ctx.globalAlpha = 0.5;
+      this.ctx.lineWidth = 3;
+      this.ctx.beginPath();
+      this.ctx.arc(x, y, ringRadius, 0, Math.PI * 2);
+      this.ctx.stroke();
+      this.ctx.globalAlpha = 1;
+    }
+
+    if (well.contested) {
+      this.ctx.strokeStyle = 'rgba(255, 120, 80, 0.6)';
+      this.ctx.lineWidth = 2;
+      this.ctx.setLineDash([10, 10]);
+      this.ctx.beginPath();
+      this.ctx.arc(x, y, ringRadius, 0, Math.PI * 2);
+      this.ctx.stroke();
+      this.ctx.setLineDash([]);
+    } else if (well.captureProgress > 0) {
+      this.ctx.strokeStyle = 'rgba(255, 255, 255, 0.5)';
+      this.ctx.lineWidth = 3;
+      this.ctx.beginPath();
+      this.ctx.arc(x, y, ringRadius, -Math.PI / 2, -Math.PI / 2 + well.captureProgress * Math.PI * 2);
+      this.ctx.stroke();
+    }
+  }
+
   private renderWellZones(x: number, y: number, coreRadius: number): void {
     // Inner safe zone (spawn area)
     this.ctx.strokeStyle = 'rgba(120, 120, 160, 0.2)';
@@ -1250,6 +1328,36 @@ export class RenderSystem {
     'rgba(255, 180, 80, 1.0)',   // Large - bright orange
   ];
 
+  static readonly ASTEROID_COLOR = '#8a7a6a';
+
+  // Asteroids: rocky grey-brown discs that darken as they take damage
+  private renderAsteroids(world: World): void {
+    for (const asteroid of world.getAsteroids()) {
+      const { position, radius } = asteroid;
+      if (!this.isInViewport(position.x, position.y, radius)) continue;
+
+      const shade = 0.45 + 0.55 * asteroid.health;
+      const { r, g, b } = this.getRGB(RenderSystem.ASTEROID_COLOR);
+      this.ctx.fillStyle = `rgb(${Math.round(r * shade)}, ${Math.round(g * shade)}, ${Math.round(b * shade)})`;
+      this.ctx.beginPath();
+      this.ctx.arc(position.x, position.y, radius, 0, Math.PI * 2);
+      this.ctx.fill();
+
+      // Craters at fixed spots (seeded by id) so the rock doesn't look like a player
+      this.ctx.fillStyle = 'rgba(0, 0, 0, 0.25)';
+      this.ctx.beginPath();
+      for (let i = 0; i < 3; i++) {
+        const angle = (asteroid.id * 2.39996 + i * 2.1) % (Math.PI * 2);
+        const cx = position.x + Math.cos(angle) * radius * 0.5;
+        const cy = position.y + Math.sin(angle) * radius * 0.5;
+        const craterRadius = radius * (0.12 + 0.05 * i);
+        this.ctx.moveTo(cx + craterRadius, cy);
+        this.ctx.arc(cx, cy, craterRadius, 0, Math.PI * 2);
+      }
+      this.ctx.fill();
+    }
+  }
+
   private renderDebris(world: World): void {
     const debris = world.getDebris();
     if (debris.size === 0) return;
@@ -1366,7 +1474,7 @@ export class RenderSystem {
       // Use larger margin for birth/kill effects that extend beyond player radius
       if (!this.isInViewport(player.position.x, player.position.y, radius * 2)) continue;
 
-      const color = world.getPlayerColor(player.colorIndex);
+      const color = world.getBodyColor(player);
       const isLocal = player.id === world.localPlayerId;
 
       // World preview mode: hide local player to show world first (prevents "pop-in")
@@ -1414,6 +1522,15 @@ export class RenderSystem {
         this.ctx.setLineDash([]);
       }
 
+      // Enemy charge windup - arc filling clockwise, shifting yellow to red as it charges
+      // (the local player sees their own charge on the HUD bar instead)
+      if (!isLocal && player.charge !== null) {
+        this.renderChargeWindup(player.position, radius, player.charge);
+      }
+
+      // Ghost runs are drawn faded so they read as replays, not opponents
+      this.ctx.globalAlpha = player.isGhost ? this.GHOST_ALPHA : 1.0;
+
       // Player body - semi-transparent fill with solid outline (same style for all)
       // Semi-transparent fill
       this.ctx.fillStyle = this.colorWithAlpha(color, 0.15);
@@ -1474,9 +1591,32 @@ export class RenderSystem {
           this.ctx.fillText(playerName, player.position.x, nameY);
         }
       }
+      this.ctx.globalAlpha = 1.0;
     }
   }
 
+  // Render players that just left our AOI as fading outlines (no name, no effects)
+  private renderFadingPlayers(world: World): void {
+    const fading = world.getFadingPlayers();
+    if (fading.length === 0) return;
+
+    for (const { player, alpha } of fading) {
+      const radius = world.massToRadius(player.mass);
+      if (!this.isInViewport(player.position.x, player.position.y, radius)) continue;
+
+      const color = world.getPlayerColor(player.colorIndex);
+      this.ctx.globalAlpha = alpha;
+      this.ctx.fillStyle = this.colorWithAlpha(color, 0.15);
+      this.ctx.beginPath();
+      this.ctx.arc(player.position.x, player.position.y, radius, 0, Math.PI * 2);
+      this.ctx.fill();
+      this.ctx.strokeStyle = color;
+      this.ctx.lineWidth = 3;
+      this.ctx.stroke();
+    }
+    this.ctx.globalAlpha = 1.0;
+  }
+
   private renderKillEffect(position: Vec2, radius: number, progress: number): void {
     // Expanding ring effect
     const ringRadius = radius + 10 + (1 - progress) * 30;
@@ -1819,6 +1959,26 @@ export class RenderSystem {
     }
   }
 
+  // Floating numbers for confirmed hits: gold when our shot landed, red when we were hit
+  private renderHitNumbers(world: World): void {
+    const ctx = this.ctx;
+    ctx.font = `bold ${Math.round(14 / Math.max(this.currentZoom, 0.1))}px Inter, system-ui, sans-serif`;
+    ctx.textAlign = 'center';
+
+    for (const hit of world.getHitNumbers()) {
+      const { position, progress, amount, byLocalPlayer } = hit;
+      if (progress <= 0) continue;
+      if (!this.isInViewport(position.x, position.y, 60)) continue;
+
+      // Drift upward while fading out
+      const y = position.y - (1 - progress) * 40;
+      ctx.fillStyle = byLocalPlayer
+        ? `rgba(251, 191, 36, ${progress})`
+        : `rgba(239, 68, 68, ${progress})`;
+      ctx.fillText(`${Math.round(amount)}`, position.x, y);
+    }
+  }
+
   private renderCollisionEffects(world: World): void {
     // Skip collision effects entirely when very zoomed out
     const quality = this.getEffectQuality(world);
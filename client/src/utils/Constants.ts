@@ -120,3 +120,15 @@ export const PLAYER_COLORS = [
   '#64748b', // slate
   '#ffffff', // white
 ] as const;
+
+// Bot faction colors, by faction id from 1 (server allows up to 8 factions)
+export const FACTION_COLORS = [
+  '#dc2626', // crimson
+  '#2563eb', // royal blue
+  '#16a34a', // forest
+  '#ca8a04', // gold
+  '#9333ea', // purple
+  '#0891b2', // ocean
+  '#ea580c', // rust
+  '#db2777', // magenta
+] as const;